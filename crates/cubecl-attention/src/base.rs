@@ -4,9 +4,9 @@ use cubecl_std::tensor::TensorHandle;
 
 use crate::{
     components::{
-        AttentionPrecision, AttentionProblem, AttentionSelection, AttentionSetupError,
-        AvailableLineSizes, FlashIdent, args::TensorInputsLaunch, batch::HypercubeSelection,
-        tile::dummy::AttentionTileSize,
+        AttentionOptions, AttentionPrecision, AttentionProblem, AttentionSelection,
+        AttentionSetupError, AvailableLineSizes, FlashIdent, FormattedConfigError,
+        args::TensorInputsLaunch, batch::HypercubeSelection, tile::dummy::AttentionTileSize,
     },
     kernels::{Algorithm, dummy::DummyAlgorithm},
 };
@@ -15,6 +15,10 @@ use crate::components::batch::BatchAttentionConfig;
 use crate::components::batch::BatchAttentionFamily;
 use cubecl_core::frontend::CubePrimitive;
 
+/// The largest head dimension supported, a head of the query and the output being
+/// held by the tiles of a single cube
+pub const MAX_HEAD_DIM: usize = 128;
+
 pub enum Strategy {
     /// Temporary implementation
     Tmp,
@@ -28,6 +32,7 @@ pub fn launch<R: Runtime, AP: AttentionPrecision>(
     key: TensorHandle<R, AP::EI>,
     value: TensorHandle<R, AP::EI>,
     out: TensorHandle<R, AP::EO>,
+    options: AttentionOptions,
 ) -> Result<(), AttentionSetupError> {
     launch_ref::<R, AP>(
         strategy,
@@ -36,6 +41,7 @@ pub fn launch<R: Runtime, AP: AttentionPrecision>(
         &key.as_ref(),
         &value.as_ref(),
         &out.as_ref(),
+        options,
     )
 }
 
@@ -47,9 +53,10 @@ pub fn launch_ref<R: Runtime, AP: AttentionPrecision>(
     key: &TensorHandleRef<R>,
     value: &TensorHandleRef<R>,
     out: &TensorHandleRef<R>,
+    options: AttentionOptions,
 ) -> Result<(), AttentionSetupError> {
     match strategy {
-        Strategy::Tmp => launch_tmp::<R, AP>(client, query, key, value, out),
+        Strategy::Tmp => launch_tmp::<R, AP>(client, query, key, value, out, options),
     }
 }
 
@@ -59,7 +66,20 @@ pub fn launch_tmp<R: Runtime, AP: AttentionPrecision>(
     key: &TensorHandleRef<R>,
     value: &TensorHandleRef<R>,
     out: &TensorHandleRef<R>,
+    options: AttentionOptions,
 ) -> Result<(), AttentionSetupError> {
+    let head_dim = query.shape[3];
+    let val_dim = value.shape[3];
+    if head_dim > MAX_HEAD_DIM || val_dim > MAX_HEAD_DIM {
+        return Err(AttentionSetupError::InvalidConfig(
+            FormattedConfigError::new(move || {
+                format!(
+                    "Head dimensions of at most {MAX_HEAD_DIM} are supported, got {head_dim} for the query and {val_dim} for the value"
+                )
+            }),
+        ));
+    }
+
     let line_sizes = AvailableLineSizes::from_elem_types::<R>(
         &AP::EI::as_type_native_unchecked(),
         &AP::EM::as_type_native_unchecked(),
@@ -78,27 +98,24 @@ pub fn launch_tmp<R: Runtime, AP: AttentionPrecision>(
         seq_q: query.shape[1],
         seq_kv: key.shape[1],
         num_heads: query.shape[2],
-        head_dim: query.shape[3],
+        head_dim,
         masked: false,
+        options,
     };
 
+    // A tile spans the whole head, so the sequences are the only dimensions iterated over
     let attention_tile_size = AttentionTileSize {
         seq_q: 8,
-        head_dim: 8,
+        head_dim: head_dim as u32,
         seq_kv: 8,
-        val_dim: 8,
+        val_dim: val_dim as u32,
     };
 
     let selection = AttentionSelection {
         hypercube_selection: HypercubeSelection {
             tile_seq_q: attention_tile_size.seq_q,
         },
-        attention_tile_size: AttentionTileSize {
-            seq_q: 8,
-            head_dim: 8,
-            seq_kv: 8,
-            val_dim: 8,
-        },
+        attention_tile_size,
         plane_dim: 32,
     };
 
@@ -51,6 +51,7 @@ impl<GA: GlobalAttention<AP>, AP: AttentionPrecision> BatchAttention<AP>
             GA::init_key_loader(key, global_config),
             GA::init_value_loader(value, global_config),
             GA::init_writer(q_offset, out, global_config),
+            q_offset,
             seq_kv,
             config.global_config(),
         )
@@ -10,8 +10,8 @@ use crate::components::FlashIdent;
 use crate::components::global::base::GlobalAttentionConfig;
 use crate::components::global::dummy::load::{DummyKeyLoader, DummyQueryLoader, DummyValueLoader};
 use crate::components::stage::{StageAttention, StageAttentionConfig};
-use crate::components::tile::AttentionTilingLayout;
 use crate::components::tile::dummy::FlashMatmulConfig;
+use crate::components::tile::{AttentionTilingLayout, ScoreMask};
 use crate::components::{
     AttentionPrecision,
     global::{GlobalAttention, dummy::config::DummyGlobalConfig},
@@ -43,6 +43,7 @@ impl<
         mut key_loader: Self::KeyLoader,
         mut value_loader: Self::ValueLoader,
         mut writer: Self::Writer,
+        q_offset: u32,
         seq_kv: u32,
         #[comptime] config: Self::Config,
     ) {
@@ -69,11 +70,22 @@ impl<
             .attention_tile_size()
             .seq_q;
 
-        let num_stage_iterations = div_ceil(seq_kv, seq_kv_tile);
+        let causal = config.stage_config().tile_config().causal_mask();
+        let check_bounds = config.stage_config().tile_config().check_bounds();
+
+        // With a causal mask, the key tiles after the last query of the tile are skipped
+        let num_stage_iterations = if comptime!(causal) {
+            Min::min(
+                div_ceil(seq_kv, seq_kv_tile),
+                div_ceil(q_offset + seq_q_tile, seq_kv_tile),
+            )
+        } else {
+            div_ceil(seq_kv, seq_kv_tile)
+        };
 
         for i in 0..num_stage_iterations {
-            let out_of_bounds_mask = if config.stage_config().tile_config().check_bounds() {
-                CubeOption::new_Some((seq_q_tile, seq_kv - i * seq_kv_tile))
+            let mask = if comptime!(check_bounds || causal) {
+                CubeOption::new_Some(ScoreMask::new(q_offset, i * seq_kv_tile, seq_kv, causal))
             } else {
                 CubeOption::new_None()
             };
@@ -90,7 +102,7 @@ impl<
                 &mut score_prob,
                 &mut accumulator,
                 &mut stage_state,
-                out_of_bounds_mask,
+                mask,
                 config.stage_config(),
             );
 
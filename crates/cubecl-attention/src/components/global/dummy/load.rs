@@ -55,9 +55,8 @@ impl<AP: AttentionPrecision, G: GlobalAttentionConfig> DummyQueryLoader<AP, G> {
         comment!("Loading Query");
 
         let attention_tile_size = config.stage_config().tile_config().attention_tile_size();
-        let tile = Tile::<AP::EI> {
-            slice: self
-                .tensor_reader
+        let tile = Tile::<AP::EI>::new_strided(
+            self.tensor_reader
                 .view
                 .slice(
                     (
@@ -67,9 +66,9 @@ impl<AP: AttentionPrecision, G: GlobalAttentionConfig> DummyQueryLoader<AP, G> {
                     (1u32, attention_tile_size.query_size()).runtime(),
                 )
                 .to_linear_slice(),
-            stride: attention_tile_size.num_cols(FlashIdent::Query),
-            layout: MatrixLayout::RowMajor,
-        };
+            attention_tile_size.num_cols(FlashIdent::Query),
+            MatrixLayout::RowMajor,
+        );
 
         QueryRegisterReader::<AP::EI> { tile }
     }
@@ -14,4 +14,25 @@ pub struct AttentionProblem {
 
     /// Whether a mask is applied (shape is always [batch, seq_q, heads, seq_k])
     pub masked: bool,
+    /// How the scores are scaled and masked
+    pub options: AttentionOptions,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// How the scores of an attention are scaled and masked
+pub struct AttentionOptions {
+    /// Whether each query only attends to the keys at its position or before,
+    /// with the first query and the first key at the same position
+    pub causal: bool,
+    /// Factor of the scores, `1 / sqrt(head_dim)` by default
+    pub scale: Option<f32>,
+}
+
+impl AttentionProblem {
+    /// Factor of the scores before the softmax
+    pub fn score_scale(&self) -> f32 {
+        self.options
+            .scale
+            .unwrap_or_else(|| (self.head_dim as f32).sqrt().recip())
+    }
 }
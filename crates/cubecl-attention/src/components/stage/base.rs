@@ -9,7 +9,7 @@ use crate::components::{
     AttentionLineSizes, AttentionPrecision, AttentionProblem, AttentionSelection,
     AttentionSetupError, AvailableLineSizes,
     global::{GlobalAttentionConfig, dummy::QueryRegisterReader},
-    tile::{AttentionTilingLayout, dummy::FlashMatmulConfig},
+    tile::{AttentionTilingLayout, ScoreMask, dummy::FlashMatmulConfig},
 };
 
 /// A family of [TileAttention] implementations that operate with any [precision](AttentionPrecision).
@@ -79,7 +79,7 @@ pub trait StageAttention<AP: AttentionPrecision>: 'static + Send + Sync {
         score: &mut Self::Score,
         accumulator: &mut Self::Accumulator,
         prev_state: &mut Self::State,
-        out_of_bound_mask: CubeOption<(u32, u32)>,
+        mask: CubeOption<ScoreMask>,
         #[comptime] config: Self::Config,
     );
 
@@ -9,7 +9,7 @@ use std::marker::PhantomData;
 use crate::components::global::dummy::QueryRegisterReader;
 use crate::components::stage::dummy::DummyStageConfig;
 use crate::components::stage::{StageAttention, StageAttentionConfig};
-use crate::components::tile::TileAttention;
+use crate::components::tile::{ScoreMask, TileAttention};
 use crate::components::{AttentionPrecision, global::GlobalAttentionConfig};
 
 pub struct DummyStageAttention<AP: AttentionPrecision, R, TA: TileAttention<AP>> {
@@ -40,7 +40,7 @@ impl<AP: AttentionPrecision, R: StageReader<AP::ES, TileKind = Strided>, TA: Til
         score_prob: &mut Self::Score,
         accumulator: &mut Self::Accumulator,
         state: &mut Self::State,
-        out_of_bound_mask: CubeOption<(u32, u32)>,
+        mask: CubeOption<ScoreMask>,
         #[comptime] config: Self::Config,
     ) {
         let key_tile = <R as StageReader<AP::ES>>::read_tile::<
@@ -58,7 +58,7 @@ impl<AP: AttentionPrecision, R: StageReader<AP::ES, TileKind = Strided>, TA: Til
             score_prob,
             accumulator,
             state,
-            out_of_bound_mask,
+            mask,
             config.tile_config(),
         );
     }
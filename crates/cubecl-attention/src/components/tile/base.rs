@@ -10,8 +10,9 @@ use cubecl_std::tensor::{View, layout::Coords2d};
 use crate::components::global::dummy::QueryRegisterReader;
 use crate::components::{
     AttentionLineSizes, AttentionPrecision, AttentionProblem, AttentionSelection,
-    AttentionSetupError, AvailableLineSizes, global::GlobalAttentionConfig,
-    tile::dummy::FlashMatmulConfig,
+    AttentionSetupError, AvailableLineSizes,
+    global::GlobalAttentionConfig,
+    tile::{ScoreMask, dummy::FlashMatmulConfig},
 };
 
 pub type AttentionTilingLayout = ContiguousTilingLayout<RowMajorTilingOrder>;
@@ -67,7 +68,7 @@ pub trait TileAttention<AP: AttentionPrecision>: 'static + Send + Sync {
         score: &mut Self::Score,
         accumulator: &mut Self::Accumulator,
         state: &mut Self::State,
-        out_of_bound_mask: CubeOption<(u32, u32)>,
+        mask: CubeOption<ScoreMask>,
         #[comptime] config: Self::Config,
     );
 
@@ -7,10 +7,10 @@ use cubecl_std::{CubeOption, CubeOptionExpand};
 use std::marker::PhantomData;
 
 use crate::components::global::dummy::QueryRegisterReader;
-use crate::components::tile::TileAttention;
 use crate::components::tile::dummy::{
     FlashMatmul, FlashMatmulConfig, FlashPrecision, ScoreFragment,
 };
+use crate::components::tile::{ScoreMask, TileAttention};
 use crate::components::{
     AttentionPrecision,
     global::GlobalAttentionConfig,
@@ -36,7 +36,7 @@ impl<AP: AttentionPrecision, FM: FlashMatmul<AP::FlashPrecision>> TileAttention<
     type Score = ScoreFragment<AP::FlashPrecision, FM>;
     type Accumulator = AccumulatorFragment<AP::FlashPrecision, FM>;
 
-    type OutOfBoundMask = (u32, u32);
+    type OutOfBoundMask = ScoreMask;
 
     fn execute(
         key_tile: &Tile<AP::ES>,
@@ -46,13 +46,11 @@ impl<AP: AttentionPrecision, FM: FlashMatmul<AP::FlashPrecision>> TileAttention<
         score_prob: &mut Self::Score,
         accumulator: &mut Self::Accumulator,
         state: &mut Self::State,
-        out_of_bound_mask: CubeOption<(u32, u32)>,
+        mask: CubeOption<ScoreMask>,
         #[comptime] config: Self::Config,
     ) {
         comment!("Tile: Execute");
-        let inv_sqrt_dk = AP::EA::new(comptime!(
-            1.0 / (config.attention_tile_size().head_dim as f32).sqrt()
-        ));
+        let score_scale = AP::EA::new(comptime!(config.score_scale()));
 
         let prev_m = state.m;
         let prev_l = state.l;
@@ -66,10 +64,10 @@ impl<AP: AttentionPrecision, FM: FlashMatmul<AP::FlashPrecision>> TileAttention<
             &mut score_prob.fragment,
             config,
         );
-        score_prob.multiply_score(inv_sqrt_dk);
+        score_prob.multiply_score(score_scale);
 
-        match out_of_bound_mask {
-            CubeOption::Some(out_of_bound_mask) => score_prob.apply_mask(out_of_bound_mask),
+        match mask {
+            CubeOption::Some(mask) => score_prob.apply_mask(mask),
             CubeOption::None => {}
         }
 
@@ -19,6 +19,9 @@ pub struct AcceleratedFlashMatmulConfig {
     key_value_stage_line_size: u32,
     cast_query: bool,
     check_bounds: bool,
+    causal_mask: bool,
+    // The bits of the f32 factor, so the config stays hashable
+    score_scale: u32,
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -144,6 +147,14 @@ impl FlashMatmulConfig for AcceleratedFlashMatmulConfig {
     fn check_bounds(&self) -> bool {
         self.check_bounds
     }
+
+    fn causal_mask(&self) -> bool {
+        self.causal_mask
+    }
+
+    fn score_scale(&self) -> f32 {
+        f32::from_bits(self.score_scale)
+    }
 }
 
 impl AcceleratedFlashMatmulConfig {
@@ -154,6 +165,8 @@ impl AcceleratedFlashMatmulConfig {
         query_stage_line_size: u32,
         key_value_stage_line_size: u32,
         check_bounds: bool,
+        causal_mask: bool,
+        score_scale: f32,
     ) -> Result<Self, AttentionSetupError> {
         let score_config = ScoreConfig {
             plane_dim,
@@ -178,6 +191,8 @@ impl AcceleratedFlashMatmulConfig {
             cast_query: AP::EI::as_type_native_unchecked()
                 == <AP::FlashPrecision as FlashPrecision>::Q::as_type_native_unchecked(),
             check_bounds,
+            causal_mask,
+            score_scale: score_scale.to_bits(),
         }
         .validate()
     }
@@ -34,6 +34,8 @@ impl FlashMatmulFamily for AcceleratedFlashMatmul {
             line_sizes.query as u32,
             line_sizes.key as u32,
             !(problem.seq_kv as u32).is_multiple_of(selection.attention_tile_size.seq_kv),
+            problem.options.causal,
+            problem.score_scale(),
         )
     }
 }
@@ -106,6 +106,10 @@ pub trait FlashMatmulConfig:
     fn num_cols_per_unit(&self, ident: FlashIdent) -> u32;
 
     fn check_bounds(&self) -> bool;
+    /// Whether the scores of the keys after each query are masked
+    fn causal_mask(&self) -> bool;
+    /// Factor of the scores before the softmax
+    fn score_scale(&self) -> f32;
 }
 
 pub trait FlashMatmulFamily: Send + Sync + 'static {
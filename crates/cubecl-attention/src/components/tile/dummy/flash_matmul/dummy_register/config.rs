@@ -20,6 +20,9 @@ pub struct DummyRegisterFlashMatmulConfig {
     key_value_stage_line_size: u32,
     cast_query: bool,
     check_bounds: bool,
+    causal_mask: bool,
+    // The bits of the f32 factor, so the config stays hashable
+    score_scale: u32,
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -145,6 +148,14 @@ impl FlashMatmulConfig for DummyRegisterFlashMatmulConfig {
     fn check_bounds(&self) -> bool {
         self.check_bounds
     }
+
+    fn causal_mask(&self) -> bool {
+        self.causal_mask
+    }
+
+    fn score_scale(&self) -> f32 {
+        f32::from_bits(self.score_scale)
+    }
 }
 
 impl DummyRegisterFlashMatmulConfig {
@@ -155,6 +166,8 @@ impl DummyRegisterFlashMatmulConfig {
         query_stage_line_size: u32,
         key_value_stage_line_size: u32,
         check_bounds: bool,
+        causal_mask: bool,
+        score_scale: f32,
     ) -> Result<Self, AttentionSetupError> {
         let score_config = ScoreConfig {
             plane_dim,
@@ -179,6 +192,8 @@ impl DummyRegisterFlashMatmulConfig {
             cast_query: AP::EI::as_type_native_unchecked()
                 == <AP::FlashPrecision as FlashPrecision>::Q::as_type_native_unchecked(),
             check_bounds,
+            causal_mask,
+            score_scale: score_scale.to_bits(),
         }
         .validate()
     }
@@ -71,11 +71,11 @@ impl<FP: FlashPrecision, FM: FlashMatmul<FP>> AccumulatorFragment<FP, FM> {
             }
         }
 
-        let tile = Tile::<FP::A> {
-            slice: self.tmp_smem.to_slice().try_cast_unchecked(),
-            stride: self.num_cols.runtime(),
-            layout: MatrixLayout::RowMajor,
-        };
+        let tile = Tile::<FP::A>::new_strided(
+            self.tmp_smem.to_slice().try_cast_unchecked(),
+            self.num_cols.runtime(),
+            MatrixLayout::RowMajor,
+        );
 
         FM::tmp_fill_accumulator(&tile, &mut self.fragment, self.config);
     }
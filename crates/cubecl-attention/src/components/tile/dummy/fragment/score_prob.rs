@@ -5,7 +5,10 @@ use cubecl_matmul::components::tile::Tile;
 
 use crate::components::{
     FlashIdent,
-    tile::dummy::{FlashMatmul, FlashMatmulConfig, FlashPrecision},
+    tile::{
+        ScoreMask,
+        dummy::{FlashMatmul, FlashMatmulConfig, FlashPrecision},
+    },
 };
 
 #[derive(CubeType)]
@@ -103,11 +106,11 @@ impl<FP: FlashPrecision, FM: FlashMatmul<FP>> ScoreFragment<FP, FM> {
 
         sync_cube();
 
-        let tile = Tile::<FP::SP> {
-            slice: self.tmp_smem.to_slice().try_cast_unchecked(),
-            stride: self.num_cols.runtime(),
-            layout: MatrixLayout::RowMajor,
-        };
+        let tile = Tile::<FP::SP>::new_strided(
+            self.tmp_smem.to_slice().try_cast_unchecked(),
+            self.num_cols.runtime(),
+            MatrixLayout::RowMajor,
+        );
         FM::tmp_fill_prob(&tile, &mut self.fragment, self.config);
     }
 
@@ -122,15 +125,14 @@ impl<FP: FlashPrecision, FM: FlashMatmul<FP>> ScoreFragment<FP, FM> {
         rowsum
     }
 
-    pub fn apply_mask(&mut self, row_col_remove: (u32, u32)) {
+    pub fn apply_mask(&mut self, mask: ScoreMask) {
         sync_cube();
         if self.row < self.num_rows {
             #[unroll]
             for i in 0..self.num_cols_per_unit {
                 let col = self.col_start + i;
 
-                if col < self.num_cols && (self.row >= row_col_remove.0 || col >= row_col_remove.1)
-                {
+                if col < self.num_cols && mask.is_masked(self.row, col) {
                     let index = self.row * self.num_cols + col;
                     self.tmp_smem[index] = FP::SP::from_int(-9999999999);
                 }
@@ -139,11 +141,11 @@ impl<FP: FlashPrecision, FM: FlashMatmul<FP>> ScoreFragment<FP, FM> {
 
         sync_cube();
 
-        let tile = Tile::<FP::SP> {
-            slice: self.tmp_smem.to_slice().try_cast_unchecked(),
-            stride: self.num_cols.runtime(),
-            layout: MatrixLayout::RowMajor,
-        };
+        let tile = Tile::<FP::SP>::new_strided(
+            self.tmp_smem.to_slice().try_cast_unchecked(),
+            self.num_cols.runtime(),
+            MatrixLayout::RowMajor,
+        );
         FM::tmp_fill_prob(&tile, &mut self.fragment, self.config);
         sync_cube();
     }
@@ -0,0 +1,44 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+#[derive(CubeType, Clone, Copy)]
+/// Which scores of a tile are removed before the softmax
+pub struct ScoreMask {
+    /// Position of the first query of the tile
+    q_start: u32,
+    /// Position of the first key of the tile
+    kv_start: u32,
+    /// Number of keys of the tile that are in bounds
+    num_valid_kv: u32,
+    #[cube(comptime)]
+    causal: bool,
+}
+
+#[cube]
+impl ScoreMask {
+    pub fn new(q_start: u32, kv_start: u32, seq_kv: u32, #[comptime] causal: bool) -> ScoreMask {
+        let num_valid_kv = if kv_start < seq_kv {
+            seq_kv - kv_start
+        } else {
+            0u32
+        };
+
+        ScoreMask {
+            q_start,
+            kv_start,
+            num_valid_kv,
+            causal,
+        }
+    }
+
+    /// Whether the score of the query at `row` and the key at `col` of the tile is removed
+    pub fn is_masked(&self, row: u32, col: u32) -> bool {
+        let out_of_bounds = col >= self.num_valid_kv;
+
+        if comptime!(self.causal) {
+            out_of_bounds || self.kv_start + col > self.q_start + row
+        } else {
+            out_of_bounds
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub mod dummy;
 
 mod base;
+mod mask;
 
 pub use base::*;
+pub use mask::*;
@@ -40,6 +40,15 @@ pub fn test_attention_algorithm<A, P, R>(
         },
         Err(_) => false,
     };
+
+    if !client
+        .properties()
+        .supports_type(P::EG::as_type_native_unchecked())
+    {
+        println!("Can't launch the test: the element type isn't supported");
+        return;
+    }
+
     let query = tensor_raw_parts_input::<P, R, P::EG>(&client, &problem, FlashIdent::Query, 12);
     let key = tensor_raw_parts_input::<P, R, P::EG>(&client, &problem, FlashIdent::Key, 34);
     let value = tensor_raw_parts_input::<P, R, P::EG>(&client, &problem, FlashIdent::Value, 56);
@@ -2,11 +2,11 @@ use cubecl_core::{Runtime, client::ComputeClient};
 
 use crate::{
     components::{
-        AttentionProblem, AttentionSelection, batch::HypercubeSelection,
+        AttentionOptions, AttentionProblem, AttentionSelection, batch::HypercubeSelection,
         tile::dummy::AttentionTileSize,
     },
     kernels::dummy::DummyAlgorithm,
-    tests::attention_test_launcher::test_attention_algorithm,
+    tests::{attention_test_launcher::test_attention_algorithm, test_utils::TestPrecision},
 };
 
 pub fn attention_tile_wide<R: Runtime>(
@@ -23,6 +23,7 @@ pub fn attention_tile_wide<R: Runtime>(
         seq_kv: attention_tile_size.seq_kv as usize,
         head_dim: attention_tile_size.head_dim as usize,
         masked: false,
+        options: AttentionOptions::default(),
     };
 
     let selection = AttentionSelection {
@@ -49,6 +50,7 @@ pub fn attention_different_seq_kv<R: Runtime>(
         seq_kv,
         head_dim: attention_tile_size.head_dim as usize,
         masked: false,
+        options: AttentionOptions::default(),
     };
 
     let selection = AttentionSelection {
@@ -75,6 +77,7 @@ pub fn attention_different_seq_q<R: Runtime>(
         seq_kv: attention_tile_size.seq_kv as usize,
         head_dim: attention_tile_size.head_dim as usize,
         masked: false,
+        options: AttentionOptions::default(),
     };
 
     let selection = AttentionSelection {
@@ -86,13 +89,44 @@ pub fn attention_different_seq_q<R: Runtime>(
     test_attention_algorithm::<DummyAlgorithm, (f32, f32), R>(client, problem, selection);
 }
 
+pub fn attention_with_options<R: Runtime, P: TestPrecision>(
+    client: ComputeClient<R::Server, R::Channel>,
+    attention_tile_size: AttentionTileSize,
+    seq_q: usize,
+    seq_kv: usize,
+    options: AttentionOptions,
+) {
+    assert!(attention_tile_size.head_dim == attention_tile_size.val_dim);
+    let tile_seq_q = attention_tile_size.seq_q;
+
+    let problem = AttentionProblem {
+        batch: 1,
+        num_heads: 1,
+        seq_q,
+        seq_kv,
+        head_dim: attention_tile_size.head_dim as usize,
+        masked: false,
+        options,
+    };
+
+    let selection = AttentionSelection {
+        hypercube_selection: HypercubeSelection { tile_seq_q },
+        attention_tile_size,
+        plane_dim: 32,
+    };
+
+    test_attention_algorithm::<DummyAlgorithm, P, R>(client, problem, selection);
+}
+
 #[macro_export]
 macro_rules! testgen_attention {
     () => {
         #[cfg(feature = "attention_tests")]
         mod attention {
             use super::*;
-            use cubecl_attention::components::tile::dummy::AttentionTileSize;
+            use cubecl_attention::components::{
+                AttentionOptions, tile::dummy::AttentionTileSize,
+            };
 
             #[test]
             fn attention_8_8_8_8() {
@@ -218,6 +252,111 @@ macro_rules! testgen_attention {
                     4,
                 )
             }
+
+            #[test]
+            fn attention_8_causal_q30_kv30() {
+                let client = TestRuntime::client(&Default::default());
+                let attention_tile_size = AttentionTileSize {
+                    seq_q: 8,
+                    seq_kv: 8,
+                    head_dim: 8,
+                    val_dim: 8,
+                };
+                $crate::tests::macros::attention_with_options::<TestRuntime, (f32, f32)>(
+                    client,
+                    attention_tile_size,
+                    30,
+                    30,
+                    AttentionOptions {
+                        causal: true,
+                        scale: None,
+                    },
+                )
+            }
+
+            #[test]
+            fn attention_8_causal_q16_kv37() {
+                let client = TestRuntime::client(&Default::default());
+                let attention_tile_size = AttentionTileSize {
+                    seq_q: 8,
+                    seq_kv: 8,
+                    head_dim: 8,
+                    val_dim: 8,
+                };
+                $crate::tests::macros::attention_with_options::<TestRuntime, (f32, f32)>(
+                    client,
+                    attention_tile_size,
+                    16,
+                    37,
+                    AttentionOptions {
+                        causal: true,
+                        scale: None,
+                    },
+                )
+            }
+
+            #[test]
+            fn attention_8_scaled() {
+                let client = TestRuntime::client(&Default::default());
+                let attention_tile_size = AttentionTileSize {
+                    seq_q: 8,
+                    seq_kv: 8,
+                    head_dim: 8,
+                    val_dim: 8,
+                };
+                $crate::tests::macros::attention_with_options::<TestRuntime, (f32, f32)>(
+                    client,
+                    attention_tile_size,
+                    8,
+                    21,
+                    AttentionOptions {
+                        causal: false,
+                        scale: Some(0.3),
+                    },
+                )
+            }
+
+            #[test]
+            fn attention_8_8_128_128() {
+                let client = TestRuntime::client(&Default::default());
+                let attention_tile_size = AttentionTileSize {
+                    seq_q: 8,
+                    seq_kv: 8,
+                    head_dim: 128,
+                    val_dim: 128,
+                };
+                $crate::tests::macros::attention_with_options::<TestRuntime, (f32, f32)>(
+                    client,
+                    attention_tile_size,
+                    19,
+                    27,
+                    AttentionOptions::default(),
+                )
+            }
+
+            #[test]
+            fn attention_f16_causal_q30_kv30() {
+                let client = TestRuntime::client(&Default::default());
+                let attention_tile_size = AttentionTileSize {
+                    seq_q: 8,
+                    seq_kv: 8,
+                    head_dim: 64,
+                    val_dim: 64,
+                };
+                $crate::tests::macros::attention_with_options::<
+                    TestRuntime,
+                    (half::f16, half::f16),
+                >(
+                    client,
+                    attention_tile_size,
+                    30,
+                    30,
+                    AttentionOptions {
+                        causal: true,
+                        scale: None,
+                    },
+                )
+            }
         }
     };
 }
@@ -345,8 +345,9 @@ where
     let out_size = tensor_size(problem, FlashIdent::Out);
     let mut out = vec![P::EG::from_int(0); out_size];
 
-    // scaling factor 1/sqrt(dk)
-    let scale = P::EA::new((head_dim as f32).sqrt().recip());
+    // scaling factor, 1/sqrt(dk) by default
+    let scale = P::EA::new(problem.score_scale());
+    let causal = problem.options.causal;
 
     for b in 0..batch {
         for h in 0..num_heads {
@@ -381,11 +382,13 @@ where
                             let k_val: P::ES = key[k_idx].cast_into();
                             dot += (q_val * k_val).cast_into();
                         }
-                        // apply scale (1/sqrt(dk))
+                        // apply scale
                         dot *= scale;
 
                         // apply mask (for masked positions set -inf)
-                        let s_val = if masked {
+                        let s_val = if causal && j > i {
+                            P::EA::new(f32::NEG_INFINITY)
+                        } else if masked {
                             let m_idx = b * mask_strides[0]
                                 + i * mask_strides[1]
                                 + h * mask_strides[2]
@@ -10,6 +10,86 @@ pub use crate::profile::{Instant, TimingMethod};
 #[cfg(feature = "std")]
 pub use crate::profile::ProfileDuration;
 
+/// How outliers are handled before computing the statistics of a benchmark.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutlierPolicy {
+    /// Keep every sample.
+    #[default]
+    Keep,
+    /// Discard the samples outside of the Tukey fences, which are the given factor of
+    /// interquartile ranges away from the first and third quartiles. `1.5` is the usual factor.
+    Tukey(f64),
+}
+
+/// How a benchmark is sampled and how its statistics are computed.
+///
+/// Samples are taken until their total duration reaches the `target_duration`, while staying
+/// between `min_samples` and `max_samples`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    /// Executions before taking samples, letting the device clocks ramp up.
+    pub warmup_iters: usize,
+    /// The minimum number of samples, at least one sample is always taken.
+    pub min_samples: usize,
+    /// The maximum number of samples.
+    pub max_samples: usize,
+    /// The total duration of the samples to reach.
+    pub target_duration: Duration,
+    /// How outliers are handled when computing the statistics.
+    pub outlier_policy: OutlierPolicy,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self::fixed(10)
+    }
+}
+
+impl BenchmarkConfig {
+    /// A config taking exactly `num_samples` samples after 3 warmup executions.
+    pub fn fixed(num_samples: usize) -> Self {
+        Self {
+            warmup_iters: 3,
+            min_samples: num_samples,
+            max_samples: num_samples,
+            target_duration: Duration::ZERO,
+            outlier_policy: OutlierPolicy::Keep,
+        }
+    }
+
+    /// If another sample is needed after taking `num_samples` samples during `elapsed`.
+    pub fn needs_sample(&self, num_samples: usize, elapsed: Duration) -> bool {
+        if num_samples < usize::max(self.min_samples, 1) {
+            return true;
+        }
+
+        num_samples < self.max_samples && elapsed < self.target_duration
+    }
+
+    /// Run the warmup executions, then take samples with `measure` until enough are taken.
+    pub fn sample<E>(
+        &self,
+        mut measure: impl FnMut() -> Result<Duration, E>,
+    ) -> Result<Vec<Duration>, E> {
+        for _ in 0..self.warmup_iters {
+            measure()?;
+        }
+
+        let mut durations = Vec::with_capacity(self.min_samples);
+        let mut elapsed = Duration::ZERO;
+
+        while self.needs_sample(durations.len(), elapsed) {
+            let duration = measure()?;
+            elapsed += duration;
+            durations.push(duration);
+        }
+
+        Ok(durations)
+    }
+}
+
 /// Results of a benchmark run.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(new, Debug, Clone)]
@@ -31,8 +111,7 @@ impl BenchmarkDurations {
 
     /// Returns a tuple of durations: (min, max, median)
     fn min_max_median_durations(&self) -> (Duration, Duration, Duration) {
-        let mut sorted = self.durations.clone();
-        sorted.sort();
+        let sorted = self.sorted();
         let min = *sorted.first().unwrap();
         let max = *sorted.last().unwrap();
         let median = *sorted.get(sorted.len() / 2).unwrap();
@@ -55,35 +134,57 @@ impl BenchmarkDurations {
             .sum::<Duration>()
             / self.durations.len() as u32
     }
+
+    /// Returns the durations without the outliers of the given policy.
+    pub fn without_outliers(&self, policy: OutlierPolicy) -> Self {
+        let durations = match policy {
+            OutlierPolicy::Keep => self.durations.clone(),
+            OutlierPolicy::Tukey(factor) => {
+                let sorted = self.sorted();
+                let q1 = percentile(&sorted, 25);
+                let q3 = percentile(&sorted, 75);
+                let range = (q3 - q1).mul_f64(factor);
+                let (lower, upper) = (q1.saturating_sub(range), q3 + range);
+
+                self.durations
+                    .iter()
+                    .filter(|duration| (lower..=upper).contains(*duration))
+                    .copied()
+                    .collect()
+            }
+        };
+
+        Self {
+            timing_method: self.timing_method,
+            durations,
+        }
+    }
+
+    fn sorted(&self) -> Vec<Duration> {
+        let mut sorted = self.durations.clone();
+        sorted.sort();
+        sorted
+    }
+}
+
+/// The nearest-rank percentile of sorted durations.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (percent * (sorted.len() - 1) + 50) / 100;
+    sorted[rank]
 }
 
 impl Display for BenchmarkDurations {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let computed = BenchmarkComputations::new(self);
-        let BenchmarkComputations {
-            mean,
-            median,
-            variance,
-            min,
-            max,
-        } = computed;
-        let num_sample = self.durations.len();
         let timing_method = self.timing_method;
 
-        f.write_str(
-            format!(
-                "
+        write!(
+            f,
+            "
 ―――――――― Result ―――――――――
   Timing      {timing_method}
-  Samples     {num_sample}
-  Mean        {mean:.3?}
-  Variance    {variance:.3?}
-  Median      {median:.3?}
-  Min         {min:.3?}
-  Max         {max:.3?}
+{computed}
 ―――――――――――――――――――――――――"
-            )
-            .as_str(),
         )
     }
 }
@@ -105,6 +206,21 @@ pub struct BenchmarkComputations {
     pub min: Duration,
     /// Maximum duration amongst all durations.
     pub max: Duration,
+    /// 5th percentile of all the durations.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub p5: Duration,
+    /// 95th percentile of all the durations.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub p95: Duration,
+    /// Standard deviation of all the durations.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stddev: Duration,
+    /// The number of durations the values are computed from.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_samples: usize,
+    /// The number of durations discarded as outliers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub num_outliers: usize,
 }
 
 impl BenchmarkComputations {
@@ -112,16 +228,65 @@ impl BenchmarkComputations {
     pub fn new(durations: &BenchmarkDurations) -> Self {
         let mean = durations.mean_duration();
         let (min, max, median) = durations.min_max_median_durations();
+        let variance = durations.variance_duration(mean);
+        let sorted = durations.sorted();
+
         Self {
             mean,
             median,
             min,
             max,
-            variance: durations.variance_duration(mean),
+            variance,
+            p5: percentile(&sorted, 5),
+            p95: percentile(&sorted, 95),
+            stddev: Duration::from_secs_f64(num_traits::Float::sqrt(variance.as_secs_f64())),
+            num_samples: sorted.len(),
+            num_outliers: 0,
+        }
+    }
+
+    /// Compute duration values once the outliers of the given policy are discarded.
+    pub fn with_outlier_policy(durations: &BenchmarkDurations, policy: OutlierPolicy) -> Self {
+        let filtered = durations.without_outliers(policy);
+
+        Self {
+            num_outliers: durations.durations.len() - filtered.durations.len(),
+            ..Self::new(&filtered)
         }
     }
 }
 
+impl Display for BenchmarkComputations {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self {
+            mean,
+            median,
+            variance,
+            min,
+            max,
+            p5,
+            p95,
+            stddev,
+            num_samples,
+            num_outliers,
+        } = self;
+
+        write!(
+            f,
+            "  Samples     {num_samples}
+  Outliers    {num_outliers}
+  Mean        {mean:.3?}
+  Variance    {variance:.3?}
+  Stddev      {stddev:.3?}
+  Median      {median:.3?}
+  P5          {p5:.3?}
+  P95         {p95:.3?}
+  Min         {min:.3?}
+  Max         {max:.3?}"
+        )
+    }
+}
+
 /// Benchmark trait.
 pub trait Benchmark {
     /// Benchmark input arguments.
@@ -160,6 +325,11 @@ pub trait Benchmark {
         }
     }
 
+    /// How the benchmark is sampled, taking [num_samples](Self::num_samples) samples by default.
+    fn config(&self) -> BenchmarkConfig {
+        BenchmarkConfig::fixed(self.num_samples())
+    }
+
     /// Name of the benchmark, should be short and it should match the name
     /// defined in the crate Cargo.toml
     fn name(&self) -> String;
@@ -195,9 +365,23 @@ pub trait Benchmark {
         Ok(ProfileDuration::new_system_time(start_time, Instant::now()))
     }
 
-    /// Run the benchmark a number of times.
+    /// Run the benchmark a number of times, as defined by its [config](Self::config).
+    ///
+    /// With [device timing](TimingMethod::Device), the samples are measured with the
+    /// [profile](Self::profile) of the benchmark, which uses the timestamps of the device when
+    /// available.
     #[allow(unused_variables)]
     fn run(&self, timing_method: TimingMethod) -> Result<BenchmarkDurations, String> {
+        self.run_with_config(timing_method, &self.config())
+    }
+
+    /// Run the benchmark as defined by the given config.
+    #[allow(unused_variables)]
+    fn run_with_config(
+        &self,
+        timing_method: TimingMethod,
+        config: &BenchmarkConfig,
+    ) -> Result<BenchmarkDurations, String> {
         #[cfg(not(feature = "std"))]
         panic!("Attempting to run benchmark in a no-std environment");
 
@@ -215,23 +399,7 @@ pub trait Benchmark {
                 Ok(crate::future::block_on(profile.resolve()))
             };
             let args = self.prepare();
-
-            // Warmup
-            for _ in 0..3 {
-                let _duration: Result<crate::profile::ProfileTicks, _> = execute(&args);
-            }
-            std::thread::sleep(Duration::from_secs(1));
-
-            // Real execution.
-            let mut durations = Vec::with_capacity(self.num_samples());
-            for _ in 0..self.num_samples() {
-                match execute(&args) {
-                    Ok(val) => durations.push(val.duration()),
-                    Err(err) => {
-                        return Err(err);
-                    }
-                }
-            }
+            let durations = config.sample(|| execute(&args).map(|ticks| ticks.duration()))?;
 
             Ok(BenchmarkDurations {
                 timing_method,
@@ -282,20 +450,18 @@ pub fn run_benchmark<BM>(benchmark: BM) -> Result<BenchmarkResult, String>
 where
     BM: Benchmark,
 {
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
+    // The system time isn't available from std on wasm.
+    let timestamp = web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
         .unwrap()
         .as_millis();
-    let output = std::process::Command::new("git")
-        .args(["rev-parse", "HEAD"])
-        .output()
-        .unwrap();
-    let git_hash = String::from_utf8(output.stdout).unwrap().trim().to_string();
-    let durations = benchmark.run(TimingMethod::System)?;
+    let git_hash = git_hash();
+    let config = benchmark.config();
+    let durations = benchmark.run_with_config(TimingMethod::System, &config)?;
 
     Ok(BenchmarkResult {
         raw: durations.clone(),
-        computed: BenchmarkComputations::new(&durations),
+        computed: BenchmarkComputations::with_outlier_policy(&durations, config.outlier_policy),
         git_hash,
         name: benchmark.name(),
         options: benchmark.options(),
@@ -304,10 +470,30 @@ where
     })
 }
 
+/// The commit being benchmarked, unknown when processes can't be spawned.
+#[cfg(feature = "std")]
+fn git_hash() -> String {
+    #[cfg(target_family = "wasm")]
+    {
+        String::from("unknown")
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let output = std::process::Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::rc::Rc;
     use alloc::vec;
+    use core::cell::Cell;
 
     #[test]
     fn test_min_max_median_durations_even_number_of_samples() {
@@ -375,4 +561,101 @@ mod tests {
         let variance = durations.variance_duration(mean);
         assert_eq!(variance, Duration::from_secs(200));
     }
+
+    /// A timer returning the given durations in order, counting the measurements.
+    fn fake_timer(
+        durations: Vec<Duration>,
+    ) -> (impl FnMut() -> Result<Duration, ()>, Rc<Cell<usize>>) {
+        let count = Rc::new(Cell::new(0));
+        let counter = count.clone();
+        let timer = move || {
+            let index = counter.get();
+            counter.set(index + 1);
+            Ok(durations[index % durations.len()])
+        };
+        (timer, count)
+    }
+
+    #[test]
+    fn test_sample_fixed_number_of_samples() {
+        let (timer, count) = fake_timer(vec![Duration::from_millis(1)]);
+        let durations = BenchmarkConfig::fixed(10).sample(timer).unwrap();
+
+        assert_eq!(durations.len(), 10);
+        assert_eq!(count.get(), 13, "3 warmup executions are expected");
+    }
+
+    #[test]
+    fn test_sample_until_target_duration() {
+        let config = BenchmarkConfig {
+            warmup_iters: 0,
+            min_samples: 2,
+            max_samples: 100,
+            target_duration: Duration::from_millis(10),
+            outlier_policy: OutlierPolicy::Keep,
+        };
+        let (timer, _) = fake_timer(vec![Duration::from_millis(3)]);
+        let durations = config.sample(timer).unwrap();
+
+        // 3 samples only total 9ms.
+        assert_eq!(durations.len(), 4);
+    }
+
+    #[test]
+    fn test_sample_bounded_by_min_and_max_samples() {
+        let mut config = BenchmarkConfig {
+            warmup_iters: 0,
+            min_samples: 5,
+            max_samples: 8,
+            target_duration: Duration::from_millis(10),
+            outlier_policy: OutlierPolicy::Keep,
+        };
+
+        let (timer, _) = fake_timer(vec![Duration::from_secs(1)]);
+        assert_eq!(config.sample(timer).unwrap().len(), 5);
+
+        let (timer, _) = fake_timer(vec![Duration::from_micros(1)]);
+        assert_eq!(config.sample(timer).unwrap().len(), 8);
+
+        config.min_samples = 0;
+        config.max_samples = 0;
+        let (timer, _) = fake_timer(vec![Duration::from_micros(1)]);
+        assert_eq!(config.sample(timer).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_percentiles_and_stddev() {
+        let durations = BenchmarkDurations {
+            timing_method: TimingMethod::System,
+            durations: (1..=21).rev().map(Duration::from_secs).collect(),
+        };
+        let computed = BenchmarkComputations::new(&durations);
+
+        assert_eq!(computed.num_samples, 21);
+        assert_eq!(computed.median, Duration::from_secs(11));
+        assert_eq!(computed.p5, Duration::from_secs(2));
+        assert_eq!(computed.p95, Duration::from_secs(20));
+        // The variance of 1..=21 is 36.67.
+        assert_eq!(computed.stddev.as_millis(), 6055);
+    }
+
+    #[test]
+    fn test_tukey_discards_outliers() {
+        let mut samples: Vec<_> = (10..20).map(Duration::from_millis).collect();
+        samples.push(Duration::from_millis(500));
+        let durations = BenchmarkDurations {
+            timing_method: TimingMethod::Device,
+            durations: samples,
+        };
+
+        let kept = BenchmarkComputations::with_outlier_policy(&durations, OutlierPolicy::Keep);
+        let filtered =
+            BenchmarkComputations::with_outlier_policy(&durations, OutlierPolicy::Tukey(1.5));
+
+        assert_eq!(kept.num_outliers, 0);
+        assert_eq!(kept.max, Duration::from_millis(500));
+        assert_eq!(filtered.num_outliers, 1);
+        assert_eq!(filtered.num_samples, 10);
+        assert_eq!(filtered.max, Duration::from_millis(19));
+    }
 }
@@ -5,6 +5,7 @@ use core::{
 
 use bytemuck::{Pod, Zeroable};
 use float8::F8E5M2;
+use half::f16;
 use num_traits::{NumCast, ToPrimitive};
 
 /// A 8-bit floating point type with 5 exponent bits and 2 mantissa bits.
@@ -52,6 +53,38 @@ impl e5m2 {
         e5m2(F8E5M2::from_f64(value).to_bits())
     }
 
+    /// Constructs a [`e5m2`] value from a 16-bit floating point value, saturating as specified
+    /// by the OCP 8-bit floating point specification.
+    ///
+    /// See [`from_f64_saturating`](Self::from_f64_saturating).
+    #[inline]
+    #[must_use]
+    pub const fn from_f16_saturating(value: f16) -> e5m2 {
+        Self::from_f64_saturating(value.to_f64_const())
+    }
+
+    /// Constructs a [`e5m2`] value from a 32-bit floating point value, saturating as specified
+    /// by the OCP 8-bit floating point specification.
+    ///
+    /// See [`from_f64_saturating`](Self::from_f64_saturating).
+    #[inline]
+    #[must_use]
+    pub const fn from_f32_saturating(value: f32) -> e5m2 {
+        Self::from_f64_saturating(value as f64)
+    }
+
+    /// Constructs a [`e5m2`] value from a 64-bit floating point value, saturating as specified
+    /// by the OCP 8-bit floating point specification, like the conversions of the kernels.
+    ///
+    /// Values are rounded to the nearest representable value, with ties to even. Values too large
+    /// to fit, infinities included, result in ±[`MAX`](Self::MAX), and NaN values result in the
+    /// NaN encoding with the same sign.
+    #[inline]
+    #[must_use]
+    pub const fn from_f64_saturating(value: f64) -> e5m2 {
+        e5m2(super::encode_saturating(value, super::E5M2_FORMAT))
+    }
+
     /// Converts a [`e5m2`] into the underlying bit representation.
     #[inline]
     #[must_use]
@@ -76,6 +109,15 @@ impl e5m2 {
     pub const fn to_f64(self) -> f64 {
         F8E5M2::from_bits(self.0).to_f64()
     }
+
+    /// Converts a [`e5m2`] value into an [`f16`] value.
+    ///
+    /// This conversion is lossless as all values can be represented exactly in [`f16`].
+    #[inline]
+    #[must_use]
+    pub const fn to_f16(self) -> f16 {
+        f16::from_f64_const(self.to_f64())
+    }
 }
 
 impl Neg for e5m2 {
@@ -5,3 +5,290 @@ mod fp8_e8m0;
 pub use fp8_e4m3::*;
 pub use fp8_e5m2::*;
 pub use fp8_e8m0::*;
+
+/// The encoding of an 8-bit float format of the OCP specification, with a sign bit.
+#[derive(Clone, Copy)]
+struct Fp8Format {
+    mantissa_bits: u32,
+    bias: i32,
+    /// The encoding of the largest finite magnitude.
+    max_bits: u8,
+    /// The encoding of the positive NaN produced by conversions.
+    nan_bits: u8,
+}
+
+const E4M3_FORMAT: Fp8Format = Fp8Format {
+    mantissa_bits: 3,
+    bias: 7,
+    max_bits: 0x7E,
+    nan_bits: 0x7F,
+};
+
+const E5M2_FORMAT: Fp8Format = Fp8Format {
+    mantissa_bits: 2,
+    bias: 15,
+    max_bits: 0x7B,
+    nan_bits: 0x7E,
+};
+
+/// `2^exponent`, exactly, for exponents of normal `f64` values.
+const fn exp2(exponent: i32) -> f64 {
+    f64::from_bits(((exponent + 1023) as u64) << 52)
+}
+
+/// Converts `value` to the `format` in the saturation mode of the OCP specification, rounding to
+/// the nearest value, with ties to even.
+///
+/// Magnitudes above the largest finite value, infinities included, saturate to it, and NaN values
+/// give the NaN encoding with their sign.
+const fn encode_saturating(value: f64, format: Fp8Format) -> u8 {
+    let sign = if value.is_sign_negative() { 0x80 } else { 0 };
+    if value.is_nan() {
+        return sign | format.nan_bits;
+    }
+
+    let max = decode_finite(format.max_bits, format);
+    let magnitude = value.abs();
+    if magnitude >= max {
+        return sign | format.max_bits;
+    }
+
+    // The values of the binade of `magnitude` are multiples of `quantum`, down to the subnormals
+    // which share the quantum of the smallest normal binade.
+    let min_exponent = 1 - format.bias;
+    let exponent = ((magnitude.to_bits() >> 52) as i32 - 1023).max(min_exponent);
+    let quantum = exp2(exponent - format.mantissa_bits as i32);
+
+    // Exact, as both are powers of two apart.
+    let scaled = magnitude / quantum;
+    let mut mantissa = scaled as u32;
+    let remainder = scaled - mantissa as f64;
+    if remainder > 0.5 || (remainder == 0.5 && mantissa % 2 == 1) {
+        mantissa += 1;
+    }
+
+    // The implicit bit of the normal values, and a mantissa rounded up to the next binade, carry
+    // into the exponent field.
+    let exponent_offset = (exponent - min_exponent) as u32;
+    sign | ((exponent_offset << format.mantissa_bits) + mantissa) as u8
+}
+
+/// The value of the finite positive encoding `bits` of the `format`.
+const fn decode_finite(bits: u8, format: Fp8Format) -> f64 {
+    let exponent_field = (bits >> format.mantissa_bits) as i32;
+    let mantissa = (bits & ((1 << format.mantissa_bits) - 1)) as u32;
+    let quantum = exp2(1 - format.bias - format.mantissa_bits as i32);
+    match exponent_field {
+        0 => mantissa as f64 * quantum,
+        _ => ((1 << format.mantissa_bits) + mantissa) as f64 * quantum * exp2(exponent_field - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use half::f16;
+
+    /// The value of `bits` from the definition of the format: NaN for the encodings with all
+    /// exponent and mantissa bits set in e4m3, which has no infinities, and for those with all
+    /// exponent bits set and a non-zero mantissa in e5m2.
+    fn reference_decode(bits: u8, format: Fp8Format) -> f64 {
+        let sign = if bits & 0x80 != 0 { -1.0 } else { 1.0 };
+        let magnitude = bits & 0x7F;
+        let (exponent_bits, mantissa_bits) = (7 - format.mantissa_bits, format.mantissa_bits);
+        let exponent = (magnitude >> mantissa_bits) as i32;
+        let mantissa = (magnitude & ((1 << mantissa_bits) - 1)) as f64;
+        let all_ones = (1 << exponent_bits) - 1;
+
+        if format.mantissa_bits == 3 && magnitude == 0x7F {
+            return f64::NAN;
+        }
+        if format.mantissa_bits == 2 && exponent == all_ones {
+            return match mantissa == 0.0 {
+                true => sign * f64::INFINITY,
+                false => f64::NAN,
+            };
+        }
+
+        let scale = (1u32 << mantissa_bits) as f64;
+        let value = match exponent {
+            0 => mantissa / scale * 2f64.powi(1 - format.bias),
+            _ => (1.0 + mantissa / scale) * 2f64.powi(exponent - format.bias),
+        };
+        sign * value
+    }
+
+    /// The saturating conversion of `value` by searching the nearest of all the finite
+    /// encodings, with ties to the even encoding.
+    fn reference_encode(value: f64, format: Fp8Format) -> u8 {
+        let sign = if value.is_sign_negative() { 0x80 } else { 0 };
+        if value.is_nan() {
+            return sign | format.nan_bits;
+        }
+
+        let magnitude = value.abs().min(reference_decode(format.max_bits, format));
+        let mut nearest = 0u8;
+        for bits in 1..=format.max_bits {
+            let distance = (reference_decode(bits, format) - magnitude).abs();
+            let nearest_distance = (reference_decode(nearest, format) - magnitude).abs();
+            if distance < nearest_distance || (distance == nearest_distance && bits % 2 == 0) {
+                nearest = bits;
+            }
+        }
+        sign | nearest
+    }
+
+    fn formats() -> [(Fp8Format, fn(u8) -> f64, fn(f64) -> u8); 2] {
+        [
+            (
+                E4M3_FORMAT,
+                |bits| e4m3::from_bits(bits).to_f64(),
+                |value| e4m3::from_f64_saturating(value).to_bits(),
+            ),
+            (
+                E5M2_FORMAT,
+                |bits| e5m2::from_bits(bits).to_f64(),
+                |value| e5m2::from_f64_saturating(value).to_bits(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn decoding_matches_the_reference_for_all_encodings() {
+        for (format, decode, _) in formats() {
+            for bits in 0..=u8::MAX {
+                let (actual, expected) = (decode(bits), reference_decode(bits, format));
+                match expected.is_nan() {
+                    true => assert!(actual.is_nan(), "{bits:#04x} should be NaN, got {actual}"),
+                    false => assert_eq!(actual.to_bits(), expected.to_bits(), "{bits:#04x}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finite_encodings_round_trip() {
+        for (format, decode, encode) in formats() {
+            for bits in 0..=u8::MAX {
+                let value = reference_decode(bits, format);
+                if value.is_finite() {
+                    assert_eq!(encode(decode(bits)), bits, "{bits:#04x} from {value}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn midpoints_round_to_even() {
+        for (format, _, encode) in formats() {
+            for bits in 0..format.max_bits {
+                let (low, high) = (
+                    reference_decode(bits, format),
+                    reference_decode(bits + 1, format),
+                );
+                let midpoint = (low + high) / 2.0;
+                let even = if bits % 2 == 0 { bits } else { bits + 1 };
+                assert_eq!(encode(midpoint), even, "midpoint of {bits:#04x}");
+                assert_eq!(encode(-midpoint), 0x80 | even, "midpoint of -{bits:#04x}");
+
+                let epsilon = (high - low) / 1024.0;
+                assert_eq!(encode(midpoint - epsilon), bits);
+                assert_eq!(encode(midpoint + epsilon), bits + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_range_values_saturate_to_max_normal() {
+        for (format, _, encode) in formats() {
+            let max = reference_decode(format.max_bits, format);
+            for value in [max, max * 1.0625, max * 2.0, f64::MAX, f64::INFINITY] {
+                assert_eq!(encode(value), format.max_bits, "{value}");
+                assert_eq!(encode(-value), 0x80 | format.max_bits, "-{value}");
+            }
+        }
+        assert_eq!(e4m3::from_f32_saturating(1e6).to_f64(), 448.0);
+        assert_eq!(e5m2::from_f32_saturating(-1e6).to_f64(), -57344.0);
+    }
+
+    #[test]
+    fn nan_converts_to_the_nan_encoding() {
+        for (format, decode, encode) in formats() {
+            assert_eq!(encode(f64::NAN), format.nan_bits);
+            assert_eq!(encode(-f64::NAN), 0x80 | format.nan_bits);
+            assert!(decode(format.nan_bits).is_nan());
+        }
+        assert!(e4m3::from_f32_saturating(f32::NAN).to_f32().is_nan());
+        assert!(e5m2::from_f16_saturating(f16::NAN).to_f16().is_nan());
+    }
+
+    #[test]
+    fn tiny_values_flush_to_signed_zero() {
+        for (format, _, encode) in formats() {
+            let min_subnormal = reference_decode(1, format);
+            assert_eq!(encode(min_subnormal / 2.0), 0);
+            assert_eq!(encode(-min_subnormal / 2.0), 0x80);
+            assert_eq!(encode(min_subnormal * 0.5001), 1);
+            assert_eq!(encode(f64::MIN_POSITIVE), 0);
+            assert_eq!(encode(-0.0), 0x80);
+        }
+    }
+
+    #[test]
+    fn all_f16_values_convert_as_the_reference() {
+        for bits in 0..=u16::MAX {
+            let value = f16::from_bits(bits);
+            let e4m3 = e4m3::from_f16_saturating(value).to_bits();
+            let e5m2 = e5m2::from_f16_saturating(value).to_bits();
+            assert_eq!(
+                e4m3,
+                reference_encode(value.to_f64(), E4M3_FORMAT),
+                "e4m3 from {value}"
+            );
+            assert_eq!(
+                e5m2,
+                reference_encode(value.to_f64(), E5M2_FORMAT),
+                "e5m2 from {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn all_f32_binades_convert_as_the_reference() {
+        // Every exponent with a few mantissas each, as the 2^32 values are too many to check.
+        for exponent in 0..=255u32 {
+            for mantissa in [0, 1, 0x1FFFFF, 0x200000, 0x3FFFFF, 0x400000, 0x7FFFFF] {
+                let value = f32::from_bits((exponent << 23) | mantissa);
+                for value in [value, -value] {
+                    assert_eq!(
+                        e4m3::from_f32_saturating(value).to_bits(),
+                        reference_encode(value as f64, E4M3_FORMAT),
+                        "e4m3 from {value}"
+                    );
+                    assert_eq!(
+                        e5m2::from_f32_saturating(value).to_bits(),
+                        reference_encode(value as f64, E5M2_FORMAT),
+                        "e5m2 from {value}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn widening_to_f16_is_exact() {
+        for (format, _, _) in formats() {
+            for bits in 0..=u8::MAX {
+                let expected = reference_decode(bits, format);
+                let actual = match format.mantissa_bits {
+                    3 => e4m3::from_bits(bits).to_f16(),
+                    _ => e5m2::from_bits(bits).to_f16(),
+                };
+                match expected.is_nan() {
+                    true => assert!(actual.is_nan()),
+                    false => assert_eq!(actual.to_f64().to_bits(), expected.to_bits()),
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use std::ops::Deref;
 
-use cubecl_core::CubeDim;
+use cubecl_core::{CubeDim, ir::CacheHint};
 use cubecl_matmul::components::{
-    MatmulIdent, MatmulLineSizes, MatmulSetupError, MatrixLayout, TilingScheme,
+    MatmulIdent, MatmulLineSizes, MatmulSetupError, MatrixLayout, MemoryFormat, TilingScheme,
     global::{
         GlobalConfig, PlaneRoleConfig, SpecializedLoadingSides, load::LoaderMode,
         multi_stage::EventLoadingMode,
@@ -68,6 +68,14 @@ impl<M: GlobalConfig> GlobalConfig for ConvolutionConfig<M> {
         self.matmul.matrix_layout(ident)
     }
 
+    fn memory_format(&self, ident: MatmulIdent) -> MemoryFormat {
+        self.matmul.memory_format(ident)
+    }
+
+    fn cache_hint(&self, ident: MatmulIdent) -> CacheHint {
+        self.matmul.cache_hint(ident)
+    }
+
     fn num_loading_planes(&self, ident: MatmulIdent) -> u32 {
         self.matmul.num_loading_planes(ident)
     }
@@ -9,7 +9,8 @@ use cubecl_matmul::components::{
     },
 };
 use cubecl_std::{
-    CubeOption, CubeOptionExpand, FastDivmod, FastDivmodArgs, tensor::r#virtual::VirtualTensor,
+    CubeOption, CubeOptionExpand, FastDivmod, FastDivmodArgs, div_ceil,
+    tensor::r#virtual::VirtualTensor,
 };
 
 use crate::{
@@ -119,6 +120,85 @@ pub(crate) fn implicit_conv<
     );
 }
 
+/// The weight gradient as the matmul of the transposed im2col input by the output gradient.
+///
+/// Each cube along `z` computes a range of whole stages of `k`, written to its own batch of the
+/// output.
+#[cube(launch_unchecked)]
+pub(crate) fn implicit_wgrad<
+    Args: MatmulArgs,
+    LhsG: Numeric,
+    RhsG: Numeric,
+    AccG: Numeric,
+    LhsS: Numeric,
+    RhsS: Numeric,
+    AccS: Numeric,
+    GMM: GlobalConvolutionFamily,
+>(
+    inputs: &Input<Args, LhsG, RhsG, AccG>,
+    output: &mut Output<Args, AccG>,
+    runtime_args: RuntimeArgs,
+    #[comptime] config: GMM::Config,
+) {
+    let mut state = Args::init_state(inputs, output);
+
+    let lhs = TensorLhs::<LhsG, RhsG, AccG, Args>::new(&state);
+    let rhs = TensorRhs::<LhsG, RhsG, AccG, Args>::new(&state);
+    let mut out = TensorOutput::<LhsG, RhsG, AccG, Args>::new(&mut state);
+
+    let lhs = VirtualTensor::<LhsG>::new::<TensorLhs<LhsG, RhsG, AccG, Args>>(&lhs);
+    let rhs = VirtualTensor::<RhsG>::new::<TensorRhs<LhsG, RhsG, AccG, Args>>(&rhs);
+    let out =
+        VirtualTensor::<AccG, ReadWrite>::new::<TensorOutput<LhsG, RhsG, AccG, Args>>(&mut out);
+
+    let stage_m = config.tiling_scheme().elements_in_stage_m().runtime();
+    let stage_n = config.tiling_scheme().elements_in_stage_n().runtime();
+    let stage_k = config.tiling_scheme().elements_in_stage_k().runtime();
+
+    let m_offset = CUBE_POS_X * stage_m;
+    let n_offset = CUBE_POS_Y * stage_n;
+
+    // The stages of a split never overlap the next split
+    let k_size = runtime_args.shape_k;
+    let k_per_split = div_ceil(div_ceil(k_size, CUBE_COUNT_Z), stage_k) * stage_k;
+    let k_start = Min::min(CUBE_POS_Z * k_per_split, k_size);
+    let k_end = Min::min(k_start + k_per_split, k_size);
+    let k_range = (k_start, k_end);
+
+    GMM::Convolution::<(LhsG, RhsG, AccG, LhsS, RhsS, AccS)>::execute(
+        GMM::Convolution::<(LhsG, RhsG, AccG, LhsS, RhsS, AccS)>::init_lhs_loader(
+            lhs,
+            (m_offset, k_range.0),
+            (stage_m, k_end - k_start),
+            &runtime_args,
+            config,
+        ),
+        GMM::Convolution::<(LhsG, RhsG, AccG, LhsS, RhsS, AccS)>::init_rhs_loader(
+            rhs,
+            (k_range.0, n_offset),
+            (k_end - k_start, stage_n),
+            &runtime_args,
+            config,
+        ),
+        GMM::Convolution::<(LhsG, RhsG, AccG, LhsS, RhsS, AccS)>::init_bias_loader(
+            CubeOption::new_None(),
+            n_offset,
+            stage_n,
+            config,
+        ),
+        GMM::Convolution::<(LhsG, RhsG, AccG, LhsS, RhsS, AccS)>::init_global_writer(
+            out,
+            (m_offset, n_offset),
+            (stage_m, stage_n),
+            &runtime_args,
+            config,
+        ),
+        &mut GMM::Convolution::<(LhsG, RhsG, AccG, LhsS, RhsS, AccS)>::init_accumulator(config),
+        k_range,
+        config,
+    );
+}
+
 pub(crate) fn shape_divmod<'a, R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     shape: &[usize],
@@ -1,9 +1,11 @@
 mod im2col;
 mod spatial;
+mod transposed_im2col;
 mod weight;
 mod write;
 
 pub use im2col::*;
 pub use spatial::*;
+pub use transposed_im2col::*;
 pub use weight::*;
 pub use write::*;
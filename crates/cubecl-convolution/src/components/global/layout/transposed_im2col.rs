@@ -0,0 +1,145 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl};
+use cubecl_matmul::components::{
+    MatmulIdent,
+    global::{GlobalConfig, memory::GlobalMemoryConfig},
+};
+use cubecl_std::{
+    FastDivmod,
+    tensor::layout::{Coords2d, Layout, LayoutExpand},
+};
+
+use crate::{
+    components::{
+        ConvolutionConfig,
+        global::{
+            layout::{NhwcCoords, unwrap},
+            load::im2col_tma::div_mod_seq,
+        },
+    },
+    kernels::layered::selector::RuntimeArgs,
+};
+
+/// Maps a 4D NHWC tensor to the transposed im2col matrix used by the weight gradient
+/// It first decomposes the `(m, k)` matrix into `((k_h, k_w, c), (n, out_h, out_w))`, then applies
+/// the convolution parameters to calculate the position in the input tensor for that kernel element.
+/// Taps outside of the image are out of bounds of the spatial layout, so they're read as zeros.
+#[derive(CubeType, Clone)]
+pub struct TransposedIm2colLayout {
+    /// Shape of output DHW, for decomposing k
+    pub shape_out: Sequence<FastDivmod>,
+    /// Shape of channel, for decomposing m
+    pub shape_channel: FastDivmod,
+
+    /// Shape of the combined `m` dimension, the kernel positions and channels
+    pub shape_m: u32,
+    /// Shape of the combined `k` dimension, the batches and output positions
+    pub shape_k: u32,
+
+    /// Size of the convolution kernel in DHW
+    #[cube(comptime)]
+    pub kernel_size: [u32; 3],
+    /// Stride of the convolution in DHW
+    #[cube(comptime)]
+    pub stride: [u32; 3],
+    /// Dilation applied to the kernel positions in DHW
+    #[cube(comptime)]
+    pub dilation: [u32; 3],
+    /// Padding applied to the convolution in DHW
+    /// The input position will be offset from the output by `-padding`
+    #[cube(comptime)]
+    pub padding: [i32; 3],
+    /// Global memory config for the backing tensor
+    #[cube(comptime)]
+    pub config: GlobalMemoryConfig,
+}
+
+#[cube]
+impl TransposedIm2colLayout {
+    pub fn new<G: GlobalConfig>(
+        args: &RuntimeArgs,
+        #[comptime] config: ConvolutionConfig<G>,
+    ) -> TransposedIm2colLayout {
+        let shape_out = args.shape_out.clone();
+
+        TransposedIm2colLayout {
+            shape_out,
+            shape_channel: args.shape_channel,
+            shape_m: args.shape_m,
+            shape_k: args.shape_k,
+            kernel_size: config.kernel_size,
+            stride: config.stride,
+            dilation: config.dilation,
+            padding: config.padding,
+            config: config.global_memory_config(MatmulIdent::Lhs),
+        }
+    }
+}
+
+#[cube]
+impl Layout for TransposedIm2colLayout {
+    type Coordinates = Coords2d;
+    type SourceCoordinates = NhwcCoords;
+
+    fn to_source_pos(&self, pos: Self::Coordinates) -> NhwcCoords {
+        let (view_m, view_k) = pos;
+
+        let (batch, out_offs) = div_mod_seq(view_k, &self.shape_out);
+
+        let (mut rem, channel) = self.shape_channel.div_mod(view_m);
+
+        let spatial_dims = comptime![self.shape_out.len()];
+        let mut in_pos = Sequence::<i32>::new();
+
+        #[unroll]
+        for i in 0..spatial_dims {
+            let i = unwrap(i);
+            let dim = comptime![spatial_dims - i - 1];
+            let ksize = comptime![self.kernel_size[dim as usize]];
+            let k_pos = rem % ksize;
+            rem /= ksize;
+
+            let out_pos = *out_offs.index(dim);
+            let stride = comptime![self.stride[dim as usize]];
+            let dilate = comptime![self.dilation[dim as usize]];
+            let pad = comptime![self.padding[dim as usize]];
+
+            let pos = (out_pos * stride + k_pos * dilate) as i32 - pad;
+            in_pos.push(pos);
+        }
+
+        let in_pos = in_pos.rev();
+
+        NhwcCoords {
+            batch,
+            spatial: in_pos,
+            channel,
+        }
+    }
+
+    fn shape(&self) -> Self::Coordinates {
+        (self.shape_m, self.shape_k)
+    }
+
+    #[allow(unreachable_code)]
+    fn to_source_shape(&self, _shape: Self::Coordinates) -> Self::SourceCoordinates {
+        panic!("transposed im2col cannot transform an (m, k) shape to NHWC");
+        NhwcCoords {
+            batch: 0,
+            spatial: Sequence::new(),
+            channel: 0,
+        }
+    }
+
+    fn to_source_pos_checked(&self, pos: Self::Coordinates) -> (NhwcCoords, bool) {
+        (self.to_source_pos(pos), self.is_in_bounds(pos))
+    }
+
+    fn is_in_bounds(&self, pos: Self::Coordinates) -> bool {
+        let (view_m, view_k) = pos;
+        // Doesn't check spatial, which is done by the NHWC layout
+        let m_in_bounds = comptime!(!self.config.check_row_bounds) || view_m < self.shape_m;
+        let k_in_bounds = comptime!(!self.config.check_col_bounds) || view_k < self.shape_k;
+        m_in_bounds && k_in_bounds
+    }
+}
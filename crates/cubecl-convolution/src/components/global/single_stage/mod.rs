@@ -1,2 +1,3 @@
 pub mod simple;
 pub mod tma;
+pub mod wgrad;
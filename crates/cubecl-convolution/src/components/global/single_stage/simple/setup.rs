@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 use cubecl_core::{Runtime, client::ComputeClient};
 use cubecl_matmul::components::{
     AvailableLineSizes, MatmulLineSizes, MatmulPrecision, MatmulSelection, MatmulSetupError,
+    MatrixLayout, MemoryFormat,
     global::{load::NoLoadingValidation, single_stage::simple::SimpleConfig},
     stage::{
         ContiguousTilingLayout, FullStageReaderFamily, RowMajorTilingOrder, StageConfig as _,
@@ -67,6 +68,9 @@ where
                 true,
                 true,
                 true,
+                MatrixLayout::RowMajor,
+                MemoryFormat::Strided,
+                MemoryFormat::Strided,
                 stage_k,
                 selection.loading_precompute_strategy,
                 selection.loader_mode,
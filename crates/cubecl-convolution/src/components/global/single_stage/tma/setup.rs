@@ -3,6 +3,7 @@ use std::marker::PhantomData;
 use cubecl_core::{Runtime, client::ComputeClient};
 use cubecl_matmul::components::{
     AvailableLineSizes, MatmulLineSizes, MatmulPrecision, MatmulSelection, MatmulSetupError,
+    MatrixLayout,
     global::{load::NoLoadingValidation, single_stage::tma::SimpleTmaConfig},
     stage::{FullStageReaderFamily, StageConfig as _, StageMatmulFamily},
 };
@@ -79,6 +80,7 @@ where
                 true,
                 true,
                 true,
+                MatrixLayout::RowMajor,
                 stage_k,
                 selection.loading_precompute_strategy,
                 selection.loader_mode,
@@ -0,0 +1,144 @@
+use std::marker::PhantomData;
+
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use cubecl_matmul::components::{
+    AccG, AccS, LhsG, LhsS, MatmulIdent, MatmulPrecision, RhsG, RhsS,
+    global::{GlobalConfig as _, memory::SimpleGlobalLayout},
+    stage::{FullStageReader, StageMatmul},
+};
+use cubecl_std::{
+    CubeOption,
+    tensor::{layout::Coords2d, r#virtual::VirtualTensor},
+};
+
+use crate::{
+    components::{
+        ConvGemmConfig,
+        global::{
+            ConvTilingLayout, GlobalConvolution,
+            layout::{NhwcLayout, OutLayout, TransposedIm2colLayout},
+            load::bias::BiasStageReader,
+            single_stage::simple::SimpleConvolution,
+        },
+    },
+    kernels::layered::selector::RuntimeArgs,
+};
+
+/// Computes the weight gradient of a convolution at the global level, as the matmul of the
+/// transposed im2col input by the output gradient.
+///
+/// The matmul is the one of [`SimpleConvolution`] with different views of the inputs: `m` is the
+/// kernel positions and input channels, `k` the batches and output positions, and `n` the output
+/// channels. Each cube along `z` writes the product of its range of `k` to its own batch of the
+/// `[splits, m, n]` output, so the batches must be summed to get the gradient.
+pub struct SimpleWgrad<MP: MatmulPrecision, SMM: StageMatmul<MP>> {
+    _cs: PhantomData<MP>,
+    _stage_matmul: PhantomData<SMM>,
+}
+
+#[cube]
+impl<MP: MatmulPrecision, SMM> GlobalConvolution<MP> for SimpleWgrad<MP, SMM>
+where
+    SMM: StageMatmul<
+            MP,
+            LhsStageReader = FullStageReader<LhsS<MP>, ConvTilingLayout>,
+            RhsStageReader = FullStageReader<RhsS<MP>, ConvTilingLayout>,
+            AccStageReader = BiasStageReader<AccS<MP>>,
+            WriteCoords = Coords2d,
+        >,
+{
+    type LhsStageLoader = <SimpleConvolution<MP, SMM> as GlobalConvolution<MP>>::LhsStageLoader;
+    type Config = <SimpleConvolution<MP, SMM> as GlobalConvolution<MP>>::Config;
+    type RhsStageLoader = <SimpleConvolution<MP, SMM> as GlobalConvolution<MP>>::RhsStageLoader;
+    type AccStageLoader = <SimpleConvolution<MP, SMM> as GlobalConvolution<MP>>::AccStageLoader;
+
+    type StageUnloader = SMM::StageUnloader;
+    type Accumulators = SMM::Accumulators;
+
+    fn execute(
+        lhs_loader: Self::LhsStageLoader,
+        rhs_loader: Self::RhsStageLoader,
+        acc_loader: Self::AccStageLoader,
+        out_writer: Self::StageUnloader,
+        acc: &mut Self::Accumulators,
+        k_range: (u32, u32),
+        #[comptime] config: Self::Config,
+    ) {
+        SimpleConvolution::<MP, SMM>::execute(
+            lhs_loader, rhs_loader, acc_loader, out_writer, acc, k_range, config,
+        );
+    }
+
+    fn init_lhs_loader(
+        lhs: VirtualTensor<LhsG<MP>>,
+        offset: Coords2d,
+        slice_size: Coords2d,
+        runtime_args: &RuntimeArgs,
+        #[comptime] config: Self::Config,
+    ) -> Self::LhsStageLoader {
+        let check_spatial = comptime![config.check_spatial_bounds()];
+        let layout_global = NhwcLayout::new(lhs, comptime![config.dimensionality()], check_spatial);
+        let layout_im2col = TransposedIm2colLayout::new(runtime_args, config);
+        let lhs = lhs.view(layout_global).view(layout_im2col);
+        Self::LhsStageLoader::new(
+            lhs.slice_unchecked(offset, slice_size),
+            MatmulIdent::Lhs,
+            config,
+        )
+    }
+
+    fn init_rhs_loader(
+        rhs: VirtualTensor<RhsG<MP>>,
+        offset: Coords2d,
+        slice_size: Coords2d,
+        runtime_args: &RuntimeArgs,
+        #[comptime] config: Self::Config,
+    ) -> Self::RhsStageLoader {
+        // The output gradient is read like the output of the forward pass, with the output
+        // positions along `k`
+        let layout_global = NhwcLayout::new(rhs, comptime![config.dimensionality()], false);
+        let layout_out = OutLayout {
+            shape_out: runtime_args.shape_out.clone(),
+            shape_m: runtime_args.shape_k,
+            shape_n: runtime_args.shape_n,
+            config: config.global_memory_config(MatmulIdent::Rhs),
+        };
+        let rhs = rhs.view(layout_global).view(layout_out);
+        Self::RhsStageLoader::new(
+            rhs.slice_unchecked(offset, slice_size),
+            MatmulIdent::Rhs,
+            config,
+        )
+    }
+
+    fn init_bias_loader(
+        bias: CubeOption<VirtualTensor<AccG<MP>>>,
+        n_offset: u32,
+        slice_size: u32,
+        #[comptime] config: Self::Config,
+    ) -> Self::AccStageLoader {
+        SimpleConvolution::<MP, SMM>::init_bias_loader(bias, n_offset, slice_size, config)
+    }
+
+    fn init_global_writer(
+        out: VirtualTensor<AccG<MP>, ReadWrite>,
+        offset: Coords2d,
+        slice_size: Coords2d,
+        _runtime_args: &RuntimeArgs,
+        #[comptime] config: Self::Config,
+    ) -> Self::StageUnloader {
+        // Each split of `k` writes to its own batch
+        let layout = SimpleGlobalLayout::new(
+            &out,
+            CUBE_POS_Z * out.stride(0),
+            config.global_memory_config(MatmulIdent::Out),
+        );
+        let out = out.view_mut(layout);
+        SMM::init_writer(out.slice_mut_unchecked(offset, slice_size))
+    }
+
+    fn init_accumulator(#[comptime] config: Self::Config) -> Self::Accumulators {
+        SMM::init_accumulators(config.stage_config())
+    }
+}
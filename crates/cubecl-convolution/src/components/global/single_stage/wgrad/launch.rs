@@ -0,0 +1,71 @@
+use cubecl_core::{CubeCount, CubeDim, Runtime, client::ComputeClient, prelude::ScalarArg};
+use cubecl_matmul::components::{
+    AccG, AccS, InputRuntimeArg, LhsG, LhsS, MatmulSpec, OutputRuntimeArg, RhsG, RhsS,
+    stage::{FullStageReaderFamily, StageMatmulFamily},
+};
+use cubecl_std::{FastDivmodArgs, tensor::layout::Coords2d};
+
+use crate::{
+    components::{
+        ConvolutionProblem,
+        global::{
+            GlobalConfig,
+            entry_point::{ConvolutionLaunch, implicit_wgrad, shape_divmod},
+            single_stage::wgrad::SimpleWgradFamily,
+        },
+    },
+    kernels::layered::selector::RuntimeArgsLaunch,
+};
+
+impl<
+    SMM: StageMatmulFamily<
+            LhsStageReader = FullStageReaderFamily,
+            RhsStageReader = FullStageReaderFamily,
+            AccStageReader = Option<FullStageReaderFamily>,
+            WriteCoords = Coords2d,
+        >,
+> ConvolutionLaunch<GlobalConfig<Self>> for SimpleWgradFamily<SMM>
+{
+    unsafe fn launch_unchecked<'a, MS: MatmulSpec, R: Runtime>(
+        client: &ComputeClient<<R as Runtime>::Server, <R as Runtime>::Channel>,
+        cube_dim: CubeDim,
+        cube_count: CubeCount,
+        input: InputRuntimeArg<'a, MS, R>,
+        output: OutputRuntimeArg<'a, MS, R>,
+        problem: &ConvolutionProblem,
+        config: GlobalConfig<Self>,
+    ) {
+        let shape_channels = FastDivmodArgs::new(client, problem.channels as u32);
+
+        let runtime_args = RuntimeArgsLaunch::new(
+            ScalarArg::new(problem.m as u32),
+            ScalarArg::new(problem.n as u32),
+            ScalarArg::new(problem.k as u32),
+            shape_channels,
+            shape_divmod(client, &problem.out_shape),
+            shape_channels,
+        );
+
+        unsafe {
+            implicit_wgrad::launch_unchecked::<
+                MS::Args,
+                LhsG<MS>,
+                RhsG<MS>,
+                AccG<MS>,
+                LhsS<MS>,
+                RhsS<MS>,
+                AccS<MS>,
+                Self,
+                R,
+            >(
+                client,
+                cube_count,
+                cube_dim,
+                input,
+                output,
+                runtime_args,
+                config,
+            );
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod convolution;
+mod launch;
+mod setup;
+
+pub use convolution::*;
+pub use setup::*;
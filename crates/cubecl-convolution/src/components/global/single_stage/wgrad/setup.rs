@@ -0,0 +1,51 @@
+use std::marker::PhantomData;
+
+use cubecl_core::{Runtime, client::ComputeClient};
+use cubecl_matmul::components::{
+    AvailableLineSizes, MatmulLineSizes, MatmulPrecision, MatmulSelection, MatmulSetupError,
+    stage::{FullStageReaderFamily, StageMatmulFamily},
+};
+use cubecl_std::tensor::layout::Coords2d;
+
+use crate::components::{
+    ConvolutionProblem,
+    global::{
+        GlobalConvolutionFamily,
+        single_stage::{
+            simple::{ConvTilingLayout, SimpleConvolutionFamily},
+            wgrad::SimpleWgrad,
+        },
+    },
+    stage::reader::BiasTilingLayout,
+};
+
+pub struct SimpleWgradFamily<SMM: StageMatmulFamily> {
+    _smm: PhantomData<SMM>,
+}
+
+impl<SMM> GlobalConvolutionFamily for SimpleWgradFamily<SMM>
+where
+    SMM: StageMatmulFamily<
+            LhsStageReader = FullStageReaderFamily,
+            RhsStageReader = FullStageReaderFamily,
+            AccStageReader = Option<FullStageReaderFamily>,
+            WriteCoords = Coords2d,
+        >,
+{
+    type Convolution<MP: MatmulPrecision> =
+        SimpleWgrad<MP, SMM::Matmul<MP, ConvTilingLayout, ConvTilingLayout, BiasTilingLayout>>;
+    type Config = <SimpleConvolutionFamily<SMM> as GlobalConvolutionFamily>::Config;
+
+    fn filter_line_sizes(available_line_sizes: AvailableLineSizes) -> AvailableLineSizes {
+        SimpleConvolutionFamily::<SMM>::filter_line_sizes(available_line_sizes)
+    }
+
+    fn setup<R: Runtime, MP: MatmulPrecision>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        problem: &ConvolutionProblem,
+        selection: &MatmulSelection,
+        line_sizes: &MatmulLineSizes,
+    ) -> Result<Self::Config, MatmulSetupError> {
+        SimpleConvolutionFamily::<SMM>::setup::<R, MP>(client, problem, selection, line_sizes)
+    }
+}
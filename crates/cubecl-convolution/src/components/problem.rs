@@ -1,4 +1,4 @@
-use cubecl_matmul::components::{MatmulProblem, MatrixLayout};
+use cubecl_matmul::components::{MatmulProblem, MatrixLayout, MemoryFormat};
 
 #[derive(Clone, Debug)]
 /// Description of a matmul problem to solve, regardless of actual data
@@ -32,6 +32,9 @@ impl ConvolutionProblem {
             rhs_batches: vec![],
             lhs_layout: self.lhs_layout,
             rhs_layout: self.rhs_layout,
+            out_layout: MatrixLayout::RowMajor,
+            lhs_format: MemoryFormat::Strided,
+            rhs_format: MemoryFormat::Strided,
         }
     }
 }
@@ -18,6 +18,7 @@ use crate::components::{
 pub mod multi_stage_tma;
 pub mod simple;
 pub mod simple_tma;
+pub mod simple_wgrad;
 
 /// Specifications for a convolution algorithm
 pub trait Algorithm {
@@ -0,0 +1,61 @@
+use std::marker::PhantomData;
+
+use cubecl_core::{
+    Runtime,
+    client::ComputeClient,
+    prelude::{Numeric, TensorHandleRef},
+};
+use cubecl_matmul::components::{
+    MatmulElems, MatmulIdent, MatmulSelection, MatmulSetupError,
+    global::args::TensorArgs,
+    stage::{FullStageReaderFamily, NumStages, PlaneMatmulFamily},
+    tile::{TileMatmulFamily, loader::Strided},
+};
+
+use cubecl_std::{CubeOption, tensor::TensorHandle};
+
+use crate::components::{ConvolutionProblem, global::single_stage::wgrad::SimpleWgradFamily};
+
+use super::{Algorithm, simple::SimpleConvAlgorithm};
+
+/// Cmma weight gradient, with the input as lhs and the output gradient as rhs
+pub struct SimpleWgradAlgorithm<TMM: TileMatmulFamily> {
+    _tmm: PhantomData<TMM>,
+}
+
+impl<TMM: TileMatmulFamily<LhsTile = Strided, RhsTile = Strided, AccTile = CubeOption<Strided>>>
+    Algorithm for SimpleWgradAlgorithm<TMM>
+{
+    type TileMatmul = TMM;
+    type StageMatmul = PlaneMatmulFamily<
+        Self::TileMatmul,
+        FullStageReaderFamily,
+        FullStageReaderFamily,
+        Option<FullStageReaderFamily>,
+    >;
+    type GlobalConvolution = SimpleWgradFamily<Self::StageMatmul>;
+
+    type Args = TensorArgs;
+
+    /// Both inputs are NHWC tensors that need contiguous channels, like the input of the forward pass
+    fn into_tensor_handle<R: Runtime, E: Numeric>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        handle: &TensorHandleRef<'_, R>,
+        ident: MatmulIdent,
+    ) -> TensorHandle<R, E> {
+        SimpleConvAlgorithm::<TMM>::into_tensor_handle::<R, E>(client, handle, ident)
+    }
+
+    fn num_stages() -> NumStages {
+        (1, 1).into()
+    }
+
+    fn selection<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        problem: &ConvolutionProblem,
+        plane_dim: u32,
+        matmul_elems: MatmulElems,
+    ) -> Result<MatmulSelection, MatmulSetupError> {
+        SimpleConvAlgorithm::<TMM>::selection::<R>(client, problem, plane_dim, matmul_elems)
+    }
+}
@@ -189,7 +189,7 @@ where
         weight.data().shape,
         problem.rhs_layout,
     )
-    .filter_out_with_tensor(out.strides, out.shape);
+    .filter_out_with_tensor(out.strides, out.shape, components::MatrixLayout::RowMajor);
 
     let line_sizes = Alg::filter_line_sizes(line_sizes).pick_max()?;
 
@@ -6,5 +6,7 @@ pub mod kernels;
 pub mod launch;
 #[cfg(feature = "export_tests")]
 pub mod tests;
+pub mod wgrad;
 
 pub use launch::*;
+pub use wgrad::*;
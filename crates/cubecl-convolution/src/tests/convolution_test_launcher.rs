@@ -2,6 +2,7 @@ use cubecl_core::prelude::*;
 use cubecl_core::{CubeElement, server::Allocation};
 use cubecl_matmul::components::MatmulIdent;
 use cubecl_matmul::components::MatmulSelection;
+use cubecl_matmul::components::MatrixLayout;
 use cubecl_matmul::components::global::GlobalConfig;
 use cubecl_matmul::{MatmulInputHandleRef, components::AvailableLineSizes};
 
@@ -57,7 +58,7 @@ pub fn test_convolution_algorithm<A, Args, P, R>(
     }
     .filter_lhs_with_tensor(&lhs.strides, &lhs.shape, problem.lhs_layout)
     .filter_rhs_with_tensor(&rhs.strides, &rhs.shape, problem.rhs_layout)
-    .filter_out_with_tensor(&out.strides, &out.shape)
+    .filter_out_with_tensor(&out.strides, &out.shape, MatrixLayout::RowMajor)
     .pick_max()
     .unwrap();
 
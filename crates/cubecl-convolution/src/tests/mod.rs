@@ -1,5 +1,6 @@
 mod convolution_test_launcher;
 pub mod test_macros;
 mod test_utils;
+pub mod wgrad;
 
 pub use test_macros::suite::*;
@@ -25,5 +25,8 @@ macro_rules! testgen_conv2d_accelerated {
 
         #[cfg(feature="conv_tests")]
         $crate::conv2d_standard_tests!();
+
+        #[cfg(feature="conv_tests")]
+        $crate::conv2d_wgrad_tests!($eg);
     };
 }
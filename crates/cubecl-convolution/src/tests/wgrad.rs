@@ -0,0 +1,238 @@
+use std::fmt::Display;
+
+use cubecl_core::{CubeElement, Runtime, prelude::Float};
+use cubecl_matmul::{
+    MatmulInputHandleRef,
+    components::tile::{TileMatmulFamily, loader::Strided},
+    tests::test_utils::Sample,
+};
+use cubecl_std::{CubeOption, tensor::TensorHandle};
+
+use crate::{ConvolutionArgs, launch_wgrad, tests::calculate_conv_output_size};
+
+/// The shapes and arguments of a 2D convolution whose weight gradient is tested.
+#[derive(Clone, Debug)]
+pub struct WgradGeometry {
+    pub batches: usize,
+    pub h: usize,
+    pub w: usize,
+    pub c: usize,
+    pub out_c: usize,
+    pub kernel_size: [usize; 2],
+    pub stride: [usize; 2],
+    pub padding: [usize; 2],
+    pub dilation: [usize; 2],
+    pub split_k: Option<usize>,
+}
+
+impl WgradGeometry {
+    pub fn new(kernel_size: [usize; 2]) -> Self {
+        Self {
+            batches: 2,
+            h: 9,
+            w: 11,
+            c: 4,
+            out_c: 8,
+            kernel_size,
+            stride: [1, 1],
+            padding: [0, 0],
+            dilation: [1, 1],
+            split_k: Some(1),
+        }
+    }
+
+    fn out_shape(&self) -> [usize; 2] {
+        let out_size = |dim: usize, size: usize| {
+            calculate_conv_output_size(
+                self.kernel_size[dim] as u32,
+                self.stride[dim] as u32,
+                self.padding[dim] as i32,
+                self.dilation[dim] as u32,
+                size,
+            )
+        };
+        [out_size(0, self.h), out_size(1, self.w)]
+    }
+}
+
+/// Compute the weight gradient of random input and output gradient tensors, and compare it with a
+/// CPU reference.
+pub fn test_wgrad<R: Runtime, TMM, EG>(geometry: WgradGeometry)
+where
+    TMM: TileMatmulFamily<LhsTile = Strided, RhsTile = Strided, AccTile = CubeOption<Strided>>,
+    EG: Float + CubeElement + Display + Sample,
+{
+    let client = R::client(&Default::default());
+    let [out_h, out_w] = geometry.out_shape();
+
+    let input_shape = [geometry.batches, geometry.h, geometry.w, geometry.c];
+    let grad_output_shape = [geometry.batches, out_h, out_w, geometry.out_c];
+    let weight_shape = [
+        geometry.out_c,
+        geometry.kernel_size[0],
+        geometry.kernel_size[1],
+        geometry.c,
+    ];
+
+    let input = EG::sample::<R>(&client, &input_shape, 1234);
+    let grad_output = EG::sample::<R>(&client, &grad_output_shape, 5678);
+    let weight_grad = TensorHandle::<R, EG>::empty(&client, weight_shape.to_vec());
+
+    let input_data = client.read_one_tensor(input.as_copy_descriptor());
+    let input_data = EG::from_bytes(&input_data).to_owned();
+    let grad_output_data = client.read_one_tensor(grad_output.as_copy_descriptor());
+    let grad_output_data = EG::from_bytes(&grad_output_data).to_owned();
+
+    let result = launch_wgrad::<R, (EG, EG, EG, EG, EG, f32), TMM, 2>(
+        &client,
+        &MatmulInputHandleRef::new(input.as_ref()),
+        &MatmulInputHandleRef::new(grad_output.as_ref()),
+        &weight_grad.as_ref(),
+        ConvolutionArgs {
+            stride: geometry.stride,
+            padding: geometry.padding,
+            dilation: geometry.dilation,
+        },
+        geometry.split_k,
+    );
+    if let Err(err) = result {
+        println!("Can't launch the test: {err:?}");
+        return;
+    }
+
+    let actual = client.read_one_tensor(weight_grad.as_copy_descriptor());
+    let actual = EG::from_bytes(&actual);
+    let (expected, scale) = wgrad_cpu_reference(&geometry, &input_data, &grad_output_data);
+
+    // The stage may be in f16 or tf32, whose epsilons are at most the one of f16
+    let epsilon = f32::max(EG::EPSILON.to_f32().unwrap(), half::f16::EPSILON.to_f32()) as f64;
+    let tolerance = 4.0 * epsilon;
+
+    for (i, (actual, (expected, scale))) in
+        actual.iter().zip(expected.iter().zip(scale)).enumerate()
+    {
+        let actual = actual.to_f64().unwrap();
+        let difference = f64::abs(actual - expected);
+        assert!(
+            difference <= tolerance * scale.max(1.0),
+            "Values differ at index {i}: actual={actual}, expected={expected}, difference={difference}"
+        );
+    }
+}
+
+/// The weight gradient in `[out_c, k_h, k_w, c]`, along with the sum of the magnitudes of the terms
+/// of each element.
+fn wgrad_cpu_reference<EG: Float>(
+    geometry: &WgradGeometry,
+    input: &[EG],
+    grad_output: &[EG],
+) -> (Vec<f64>, Vec<f64>) {
+    let [out_h, out_w] = geometry.out_shape();
+    let [k_h, k_w] = geometry.kernel_size;
+    let (h, w, c, out_c) = (geometry.h, geometry.w, geometry.c, geometry.out_c);
+
+    let len = out_c * k_h * k_w * c;
+    let mut expected = vec![0.0; len];
+    let mut scale = vec![0.0; len];
+
+    for oc in 0..out_c {
+        for kh in 0..k_h {
+            for kw in 0..k_w {
+                for ic in 0..c {
+                    let index = ((oc * k_h + kh) * k_w + kw) * c + ic;
+                    for b in 0..geometry.batches {
+                        for oh in 0..out_h {
+                            for ow in 0..out_w {
+                                let ih = (oh * geometry.stride[0] + kh * geometry.dilation[0])
+                                    as isize
+                                    - geometry.padding[0] as isize;
+                                let iw = (ow * geometry.stride[1] + kw * geometry.dilation[1])
+                                    as isize
+                                    - geometry.padding[1] as isize;
+                                // Taps in the padding are zeros
+                                if ih < 0 || iw < 0 || ih >= h as isize || iw >= w as isize {
+                                    continue;
+                                }
+                                let (ih, iw) = (ih as usize, iw as usize);
+
+                                let x = input[((b * h + ih) * w + iw) * c + ic].to_f64().unwrap();
+                                let dy = grad_output[((b * out_h + oh) * out_w + ow) * out_c + oc]
+                                    .to_f64()
+                                    .unwrap();
+                                expected[index] += x * dy;
+                                scale[index] += f64::abs(x * dy);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    (expected, scale)
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! conv2d_wgrad_tests {
+    ($eg:ty) => {
+        mod wgrad {
+            use super::*;
+            use $crate::tests::wgrad::{WgradGeometry, test_wgrad};
+
+            #[test]
+            pub fn stride_2() {
+                let mut geometry = WgradGeometry::new([3, 3]);
+                geometry.stride = [2, 2];
+                test_wgrad::<TestRuntime, TMM, $eg>(geometry);
+            }
+
+            #[test]
+            pub fn padding_1() {
+                let mut geometry = WgradGeometry::new([3, 3]);
+                geometry.padding = [1, 1];
+                test_wgrad::<TestRuntime, TMM, $eg>(geometry);
+            }
+
+            #[test]
+            pub fn dilation_2() {
+                let mut geometry = WgradGeometry::new([3, 2]);
+                geometry.dilation = [2, 2];
+                test_wgrad::<TestRuntime, TMM, $eg>(geometry);
+            }
+
+            #[test]
+            pub fn stride_2_padding_1_dilation_2() {
+                let mut geometry = WgradGeometry::new([3, 3]);
+                geometry.c = 5;
+                geometry.out_c = 7;
+                geometry.stride = [2, 2];
+                geometry.padding = [1, 1];
+                geometry.dilation = [2, 2];
+                test_wgrad::<TestRuntime, TMM, $eg>(geometry);
+            }
+
+            #[test]
+            pub fn split_k_8() {
+                let mut geometry = WgradGeometry::new([3, 3]);
+                geometry.batches = 8;
+                geometry.h = 32;
+                geometry.w = 32;
+                geometry.padding = [1, 1];
+                geometry.split_k = Some(8);
+                test_wgrad::<TestRuntime, TMM, $eg>(geometry);
+            }
+
+            #[test]
+            pub fn split_k_auto() {
+                let mut geometry = WgradGeometry::new([3, 3]);
+                geometry.batches = 8;
+                geometry.h = 32;
+                geometry.w = 32;
+                geometry.stride = [2, 2];
+                geometry.split_k = None;
+                test_wgrad::<TestRuntime, TMM, $eg>(geometry);
+            }
+        }
+    };
+}
@@ -0,0 +1,332 @@
+//! Weight gradient of a convolution, computed with an implicit GEMM.
+//!
+//! The gradient is the matmul of the transposed im2col view of the input, with the kernel
+//! positions and input channels as rows and the batches and output positions as the reduced
+//! dimension, by the output gradient. The reduced dimension can be split between the cubes along
+//! `z`, each writing its partial product to its own batch of an `f32` buffer, which is then summed
+//! and cast into the weight gradient.
+use std::any::TypeId;
+
+use cubecl_core::{
+    self as cubecl, Runtime, calculate_cube_count_elemwise, client::ComputeClient, prelude::*,
+};
+use cubecl_matmul::{
+    MatmulInputHandleRef,
+    components::{
+        self, AccG, AvailableLineSizes, InputArg, LhsG, MatmulElems, MatmulIdent, MatmulPrecision,
+        MatmulSelection, OutputArg, RhsG,
+        global::{
+            GlobalConfig as _,
+            args::{ConcreteOutputFactory, TensorArgs},
+        },
+        tile::{TileMatmulFamily, loader::Strided},
+    },
+    kernels::layered::NUM_SM_APPROX,
+};
+use cubecl_runtime::TypeUsage;
+use cubecl_std::{CubeOption, tensor::TensorHandle};
+use half::f16;
+
+use crate::{
+    ConvolutionArgs,
+    components::{
+        ConvGemmConfig as _, ConvSetupError, ConvolutionProblem, Dimensionality,
+        global::{args::ConcreteInputsFactory, entry_point::ConvolutionLaunch},
+    },
+    kernels::layered::algorithm::{Algorithm, simple_wgrad::SimpleWgradAlgorithm},
+};
+
+/// The fewest stages of `k` computed by a split when the number of splits is picked automatically.
+const MIN_STAGES_PER_SPLIT: usize = 4;
+
+/// Compute the gradient of the weights of an n-dimensional convolution, using the implicit GEMM
+/// algorithm with cubecl tiling matmul components.
+///
+/// * `input` - The input feature map, layout should be [batches, depth, height, width, in_channels]
+/// * `grad_output` - The gradient of the output feature map, layout should be [batches, out_depth, out_height, out_width, out_channels]
+/// * `weight_grad` - The gradient of the weights, layout should be [out_channels, kernel_d, kernel_h, kernel_w, in_channels]
+/// * `args` - The arguments of the forward convolution
+/// * `split_k` - The number of splits of the batches and output positions, picked from the size of
+///   the problem when `None`
+///
+/// The products are always accumulated in `f32`, whatever the accumulator precision of `MP`.
+#[allow(clippy::result_large_err)]
+pub fn launch_wgrad<R: Runtime, MP: MatmulPrecision, TMM, const N_SPATIAL: usize>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &MatmulInputHandleRef<'_, R>,
+    grad_output: &MatmulInputHandleRef<'_, R>,
+    weight_grad: &TensorHandleRef<'_, R>,
+    args: ConvolutionArgs<N_SPATIAL>,
+    split_k: Option<usize>,
+) -> Result<(), ConvSetupError>
+where
+    TMM: TileMatmulFamily<LhsTile = Strided, RhsTile = Strided, AccTile = CubeOption<Strided>>,
+{
+    let ConvolutionArgs {
+        stride,
+        padding,
+        dilation,
+    } = args;
+
+    let dimensionality = match N_SPATIAL {
+        1 => Dimensionality::Dim1,
+        2 => Dimensionality::Dim2,
+        3 => Dimensionality::Dim3,
+        other => unimplemented!("Unsupported dimensionality {other}"),
+    };
+
+    let rank = input.data().shape.len();
+    let dim_c = rank - 1;
+
+    let n = input.data().shape[0];
+    let c = input.data().shape[dim_c];
+
+    let out_c = grad_output.data().shape[dim_c];
+
+    let in_shape = &input.data().shape[1..dim_c];
+    let kernel_shape = &weight_grad.shape[1..dim_c];
+    let out_shape = &grad_output.data().shape[1..dim_c];
+
+    let problem = ConvolutionProblem {
+        m: c * kernel_shape.iter().product::<usize>(),
+        n: out_c,
+        k: n * out_shape.iter().product::<usize>(),
+        lhs_layout: components::MatrixLayout::ColMajor,
+        rhs_layout: components::MatrixLayout::RowMajor,
+        kernel_size: kernel_shape.iter().map(|it| *it as u32).collect(),
+        stride: stride.iter().map(|it| *it as u32).collect(),
+        padding: padding.iter().map(|it| *it as i32).collect(),
+        dilation: dilation.iter().map(|it| *it as u32).collect(),
+
+        batches: n,
+        shape: in_shape.to_vec(),
+        out_shape: out_shape.to_vec(),
+        channels: c,
+
+        dimensionality,
+    };
+
+    let lhs_is_f32 = TypeId::of::<LhsG<MP>>() == TypeId::of::<f32>();
+    let rhs_is_f32 = TypeId::of::<RhsG<MP>>() == TypeId::of::<f32>();
+
+    let launch = if lhs_is_f32 || rhs_is_f32 {
+        if tf32::supported_uses(client).contains(TypeUsage::Conversion) {
+            if lhs_is_f32 && rhs_is_f32 {
+                launch_wgrad_kernel::<R, (LhsG<MP>, RhsG<MP>, f32, tf32, tf32, f32), AccG<MP>, TMM>
+            } else if lhs_is_f32 {
+                launch_wgrad_kernel::<R, (LhsG<MP>, RhsG<MP>, f32, tf32, f32, f32), AccG<MP>, TMM>
+            } else {
+                launch_wgrad_kernel::<R, (LhsG<MP>, RhsG<MP>, f32, f32, tf32, f32), AccG<MP>, TMM>
+            }
+        } else if lhs_is_f32 && rhs_is_f32 {
+            launch_wgrad_kernel::<R, (LhsG<MP>, RhsG<MP>, f32, f16, f16, f32), AccG<MP>, TMM>
+        } else if lhs_is_f32 {
+            launch_wgrad_kernel::<R, (LhsG<MP>, RhsG<MP>, f32, f16, f32, f32), AccG<MP>, TMM>
+        } else {
+            launch_wgrad_kernel::<R, (LhsG<MP>, RhsG<MP>, f32, f32, f16, f32), AccG<MP>, TMM>
+        }
+    } else {
+        launch_wgrad_kernel::<
+            R,
+            (
+                LhsG<MP>,
+                RhsG<MP>,
+                f32,
+                components::LhsS<MP>,
+                components::RhsS<MP>,
+                f32,
+            ),
+            AccG<MP>,
+            TMM,
+        >
+    };
+
+    launch(client, input, grad_output, weight_grad, problem, split_k)
+}
+
+/// Launch the weight gradient with the precision `MP` of the matmul, and cast the sum of the
+/// splits to the element `EO` of the weight gradient.
+#[allow(clippy::result_large_err)]
+fn launch_wgrad_kernel<R: Runtime, MP: MatmulPrecision, EO: Numeric, TMM>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &MatmulInputHandleRef<'_, R>,
+    grad_output: &MatmulInputHandleRef<'_, R>,
+    weight_grad: &TensorHandleRef<'_, R>,
+    problem: ConvolutionProblem,
+    split_k: Option<usize>,
+) -> Result<(), ConvSetupError>
+where
+    TMM: TileMatmulFamily<LhsTile = Strided, RhsTile = Strided, AccTile = CubeOption<Strided>>,
+{
+    let input_data = SimpleWgradAlgorithm::<TMM>::into_tensor_handle::<R, LhsG<MP>>(
+        client,
+        input.data(),
+        MatmulIdent::Lhs,
+    );
+    let grad_output_data = SimpleWgradAlgorithm::<TMM>::into_tensor_handle::<R, RhsG<MP>>(
+        client,
+        grad_output.data(),
+        MatmulIdent::Rhs,
+    );
+
+    let mut input = *input;
+    let mut grad_output = *grad_output;
+
+    *input.data_mut() = input_data.as_ref();
+    *grad_output.data_mut() = grad_output_data.as_ref();
+
+    let plane_dim = client.properties().hardware.plane_size_max;
+    let selection = SimpleWgradAlgorithm::<TMM>::selection::<R>(
+        client,
+        &problem,
+        plane_dim,
+        MatmulElems::new::<MP>(),
+    )?;
+
+    let splits = num_splits::<R>(client, &problem, &selection, split_k);
+    let partial = TensorHandle::<R, f32>::empty(client, vec![splits, problem.m, problem.n]);
+
+    // The channels of the input are contiguous along `m`, so the input is vectorized along its
+    // last dimension like a row-major tensor.
+    let line_sizes = AvailableLineSizes::from_types::<R>(
+        &LhsG::<MP>::as_type_native_unchecked(),
+        &RhsG::<MP>::as_type_native_unchecked(),
+        &f32::as_type_native_unchecked(),
+    )
+    .filter_lhs_with_tensor(
+        input.data().strides,
+        input.data().shape,
+        components::MatrixLayout::RowMajor,
+    )
+    .filter_rhs_with_tensor(
+        grad_output.data().strides,
+        grad_output.data().shape,
+        problem.rhs_layout,
+    )
+    .filter_out_with_tensor(
+        &partial.strides,
+        &partial.shape,
+        components::MatrixLayout::RowMajor,
+    );
+
+    let line_sizes = SimpleWgradAlgorithm::<TMM>::filter_line_sizes(line_sizes).pick_max()?;
+
+    let config =
+        SimpleWgradAlgorithm::<TMM>::setup::<R, MP>(client, &problem, &selection, &line_sizes)?;
+    let line_sizes = config.line_sizes();
+
+    let inputs = <InputArg<(MP, TensorArgs)> as ConcreteInputsFactory>::create(
+        &input,
+        &grad_output,
+        None,
+        &selection,
+        &problem,
+        &line_sizes,
+    );
+    let output = <OutputArg<(MP, TensorArgs)> as ConcreteOutputFactory>::create(
+        &partial.as_ref(),
+        &selection,
+        &problem.as_matmul_problem(),
+        &line_sizes,
+    );
+
+    let cube_count = CubeCount::Static(
+        (problem.m as u32).div_ceil(selection.tiling_scheme.elements_in_stage_m()),
+        (problem.n as u32).div_ceil(selection.tiling_scheme.elements_in_stage_n()),
+        splits as u32,
+    );
+
+    unsafe {
+        <SimpleWgradAlgorithm<TMM> as Algorithm>::GlobalConvolution::launch_unchecked::<
+            (MP, TensorArgs),
+            R,
+        >(
+            client,
+            config.cube_dim(),
+            cube_count,
+            inputs,
+            output,
+            &problem,
+            config,
+        );
+    }
+
+    let num_elems = problem.m * problem.n;
+    let cube_dim = CubeDim::default();
+    unsafe {
+        wgrad_reduce_kernel::launch_unchecked::<f32, EO, R>(
+            client,
+            calculate_cube_count_elemwise(num_elems, cube_dim),
+            cube_dim,
+            partial.as_arg(1),
+            weight_grad.as_tensor_arg(1),
+        );
+    }
+
+    Ok(())
+}
+
+/// The number of splits of `k`, enough to fill the device when `split_k` isn't given, and never
+/// more than the stages of `k`.
+fn num_splits<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    problem: &ConvolutionProblem,
+    selection: &MatmulSelection,
+    split_k: Option<usize>,
+) -> usize {
+    let stage_k = selection.tiling_scheme.elements_in_stage_k() as usize;
+    let k_stages = problem.k.div_ceil(stage_k).max(1);
+
+    match split_k {
+        Some(splits) => splits.clamp(1, k_stages),
+        None => {
+            let num_sm = client
+                .properties()
+                .hardware
+                .num_streaming_multiprocessors
+                .unwrap_or(NUM_SM_APPROX) as usize;
+            let cubes = problem
+                .m
+                .div_ceil(selection.tiling_scheme.elements_in_stage_m() as usize)
+                * problem
+                    .n
+                    .div_ceil(selection.tiling_scheme.elements_in_stage_n() as usize);
+            let max_splits = (k_stages / MIN_STAGES_PER_SPLIT).max(1);
+            num_sm.div_ceil(cubes).clamp(1, max_splits)
+        }
+    }
+}
+
+/// Each unit sums an element of the `[splits, m, n]` partial products and writes it to the weight
+/// gradient, decomposing `m` into the kernel positions and the input channel.
+#[cube(launch_unchecked)]
+fn wgrad_reduce_kernel<EA: Numeric, EO: Numeric>(
+    partial: &Tensor<EA>,
+    weight_grad: &mut Tensor<EO>,
+) {
+    let rows = partial.shape(1);
+    let cols = partial.shape(2);
+    if ABSOLUTE_POS >= rows * cols {
+        terminate!();
+    }
+
+    let row = ABSOLUTE_POS / cols;
+    let col = ABSOLUTE_POS % cols;
+
+    let rank = weight_grad.rank();
+    let mut rem = row;
+    let mut index = col * weight_grad.stride(0);
+    for i in 0..rank - 1 {
+        let dim = rank - 1 - i;
+        let shape = weight_grad.shape(dim);
+        index += (rem % shape) * weight_grad.stride(dim);
+        rem /= shape;
+    }
+
+    let mut sum = EA::from_int(0);
+    for split in 0..partial.shape(0) {
+        sum += partial[split * partial.stride(0) + ABSOLUTE_POS];
+    }
+
+    weight_grad[index] = EO::cast_from(sum);
+}
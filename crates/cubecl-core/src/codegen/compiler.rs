@@ -28,4 +28,15 @@ pub trait Compiler: Sync + Send + 'static + Clone + core::fmt::Debug {
 pub struct WgpuCompilationOptions {
     pub supports_fp_fast_math: bool,
     pub supports_u64: bool,
+    /// Whether atomics on 64-bit integers are supported.
+    pub supports_int64_atomics: bool,
+    pub supports_f16: bool,
+    /// Compute f16 in f32 when the device doesn't support it, packing f16 buffers in u32 words.
+    pub emulate_f16: bool,
+    /// Maximum size of the push constants holding the scalars, zero when they aren't supported.
+    pub max_push_constant_size: u32,
+    /// Lower the `u32` and `bool` constants of kernels to pipeline-overridable constants in
+    /// WGSL and specialization constants in SPIR-V, so kernels only differing by these values
+    /// share the same shader template.
+    pub override_constants: bool,
 }
@@ -38,6 +38,9 @@ pub struct KernelOptions {
     pub debug_symbols: bool,
     pub fp_math_mode: EnumSet<FastMath>,
     pub cluster_dim: Option<CubeDim>,
+    pub index_checks: bool,
+    pub shared_memory_carveout: Option<u8>,
+    pub dynamic_shared_memory: Option<u32>,
 }
 
 impl KernelSettings {
@@ -72,6 +75,27 @@ impl KernelSettings {
         self.options.cluster_dim = Some(cluster_dim);
         self
     }
+
+    /// Guard every index of the kernel, reporting the ones out of bounds to the active
+    /// [IndexReport](crate::compute::IndexReport)
+    pub fn index_checks(mut self, enabled: bool) -> Self {
+        self.options.index_checks = enabled;
+        self
+    }
+
+    /// Set the preferred percentage of the unified L1 cache and shared memory carved out as
+    /// shared memory, a hint ignored by the runtimes that can't configure it
+    pub fn shared_memory_carveout(mut self, percent: impl Into<Option<u8>>) -> Self {
+        self.options.shared_memory_carveout = percent.into();
+        self
+    }
+
+    /// Set the bytes of shared memory the kernel is launched with, when more than its shared
+    /// memories is required. Ignored by the runtimes without dynamic shared memory
+    pub fn dynamic_shared_memory(mut self, bytes: impl Into<Option<u32>>) -> Self {
+        self.options.dynamic_shared_memory = bytes.into();
+        self
+    }
 }
 
 /// Information related to a buffer binding.
@@ -11,11 +11,11 @@ use cubecl_ir::{
 use cubecl_runtime::config::{GlobalConfig, compilation::CompilationLogLevel};
 
 use crate::ir::{Id, Type};
-use crate::prelude::KernelDefinition;
+use crate::prelude::{Atomic, CubePrimitive, KernelDefinition};
 use crate::{BufferInfo, KernelSettings, ScalarInfo};
 use crate::{KernelExpansion, KernelIntegrator};
 
-use super::Visibility;
+use super::{Visibility, expand_index_checks};
 
 /// Prepare a kernel to create a [kernel definition](crate::KernelDefinition).
 pub struct KernelBuilder {
@@ -114,8 +114,22 @@ impl KernelBuilder {
         self.scope.runtime_properties = Rc::new(properties);
     }
 
+    /// Record the sources and variable names of the kernel, even when the compilation logger
+    /// doesn't need them, so they can be emitted as debug symbols.
+    pub fn debug_symbols(&mut self) {
+        self.scope.debug.enabled = true;
+    }
+
     /// Build the [kernel definition](KernelDefinition).
-    pub fn build(self, settings: KernelSettings) -> KernelDefinition {
+    ///
+    /// With [index checks](KernelSettings::index_checks), the report of the
+    /// [IndexReport](super::IndexReport) is registered as the last buffer.
+    pub fn build(mut self, settings: KernelSettings) -> KernelDefinition {
+        if settings.options.index_checks {
+            let report = self.output_array(Type::new(Atomic::<u32>::as_type(&self.scope)));
+            expand_index_checks(&mut self.scope, &report);
+        }
+
         let scalars = self
             .scalars
             .into_iter()
@@ -0,0 +1,180 @@
+use core::cell::RefCell;
+
+use cubecl_ir::{
+    Branch, ExpandElement, Instruction, Metadata, Operation, Operator, Scope, Type, Variable,
+    VariableKind,
+};
+use cubecl_runtime::server::Handle;
+
+use crate::{self as cubecl, prelude::*};
+
+std::thread_local! {
+    static ACTIVE: RefCell<Option<Handle>> = const { RefCell::new(None) };
+}
+
+/// The first out of bounds index of the kernels launched while the report is
+/// [checked](IndexReport::checked).
+///
+/// Kernels launched inside [checked](IndexReport::checked) are compiled with every index of a
+/// global array, tensor or shared memory guarded against its length. An index out of bounds is
+/// clamped to the last element, so the kernel keeps running, and the first one is written to the
+/// report to be [read](IndexReport::read) after the launch.
+///
+/// ```ignore
+/// let report = IndexReport::new::<R>(&client);
+/// report.checked(|| kernel::launch::<R>(&client, cube_count, cube_dim, input, output));
+/// assert_eq!(report.read::<R>(&client), None);
+/// ```
+///
+/// The checks are part of the kernel cache key, and kernels launched outside of a report have no
+/// code added.
+pub struct IndexReport {
+    handle: Handle,
+}
+
+/// An index out of bounds found by an [IndexReport].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexViolation {
+    /// The first index out of bounds, in lines of the buffer indexed.
+    pub index: u32,
+    /// The number of accesses out of bounds.
+    pub count: u32,
+}
+
+impl IndexReport {
+    /// A report without any violation.
+    pub fn new<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> Self {
+        Self {
+            handle: client.create(u32::as_bytes(&[0, 0])),
+        }
+    }
+
+    /// Run `func` with the kernels it launches on this thread checking their indices into this
+    /// report.
+    pub fn checked<T>(&self, func: impl FnOnce() -> T) -> T {
+        /// Restores the outer report, even when `func` panics.
+        struct Restore(Option<Handle>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                ACTIVE.with(|active| *active.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(ACTIVE.with(|active| active.replace(Some(self.handle.clone()))));
+        func()
+    }
+
+    /// Whether the kernels launched now are [checked](IndexReport::checked).
+    pub fn is_active() -> bool {
+        ACTIVE.with(|active| active.borrow().is_some())
+    }
+
+    pub(crate) fn active() -> Option<Handle> {
+        ACTIVE.with(|active| active.borrow().clone())
+    }
+
+    /// Read the first index out of bounds of the kernels launched with this report, if any.
+    pub fn read<R: Runtime>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Option<IndexViolation> {
+        let report = client.read_one(self.handle.clone());
+        let report = u32::from_bytes(&report);
+
+        (report[0] != 0).then(|| IndexViolation {
+            index: report[1],
+            count: report[0],
+        })
+    }
+}
+
+/// Returns `index` clamped to the buffer, reporting it when it's out of bounds.
+#[cube]
+fn checked_index(index: u32, length: u32, report: &Array<Atomic<u32>>) -> u32 {
+    if index >= length {
+        let count = Atomic::add(&report[0], 1);
+        if count == 0 {
+            Atomic::store(&report[1], index);
+        }
+    }
+
+    Min::min(index, Max::max(length, 1) - 1)
+}
+
+/// Guard the indexing of global buffers and shared memories in `scope` and its nested scopes,
+/// reporting the violations to `report`.
+pub(crate) fn expand_index_checks(scope: &mut Scope, report: &ExpandElement) {
+    let instructions = core::mem::take(&mut scope.instructions);
+
+    for mut instruction in instructions {
+        let out = instruction.out;
+        // The checks are attributed to the access they guard.
+        scope.debug.source_loc = instruction.source_loc.clone();
+
+        match &mut instruction.operation {
+            Operation::Branch(branch) => match branch {
+                Branch::If(op) => expand_index_checks(&mut op.scope, report),
+                Branch::IfElse(op) => {
+                    expand_index_checks(&mut op.scope_if, report);
+                    expand_index_checks(&mut op.scope_else, report);
+                }
+                Branch::Switch(op) => {
+                    expand_index_checks(&mut op.scope_default, report);
+                    for (_, case) in op.cases.iter_mut() {
+                        expand_index_checks(case, report);
+                    }
+                }
+                Branch::RangeLoop(op) => expand_index_checks(&mut op.scope, report),
+                Branch::Loop(op) => expand_index_checks(&mut op.scope, report),
+                Branch::Return | Branch::Break => {}
+            },
+            Operation::Operator(Operator::Index(op) | Operator::UncheckedIndex(op)) => {
+                if let Some(length) = list_length(scope, op.list) {
+                    op.index = check_index(scope, op.index, length, report);
+                }
+            }
+            Operation::Operator(Operator::IndexAssign(op) | Operator::UncheckedIndexAssign(op)) => {
+                if let Some(length) = out.and_then(|list| list_length(scope, list)) {
+                    op.index = check_index(scope, op.index, length, report);
+                }
+            }
+            _ => {}
+        }
+
+        scope.instructions.push(instruction);
+    }
+}
+
+/// The length of the lines of `list` when it's a buffer the checks guard.
+fn list_length(scope: &mut Scope, list: Variable) -> Option<ExpandElement> {
+    match list.kind {
+        VariableKind::GlobalInputArray(_) | VariableKind::GlobalOutputArray(_) => {
+            let length = scope.create_local(Type::new(u32::as_type(scope)));
+            scope.register(Instruction::new(
+                Metadata::BufferLength { var: list },
+                *length,
+            ));
+            Some(length)
+        }
+        VariableKind::SharedMemory { length, .. } => {
+            Some(ExpandElementTyped::<u32>::from_lit(scope, length).expand)
+        }
+        _ => None,
+    }
+}
+
+fn check_index(
+    scope: &mut Scope,
+    index: Variable,
+    length: ExpandElement,
+    report: &ExpandElement,
+) -> Variable {
+    *checked_index::expand(
+        scope,
+        ExpandElement::Plain(index).into(),
+        length.into(),
+        report.clone().into(),
+    )
+    .expand
+}
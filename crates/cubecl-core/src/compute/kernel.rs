@@ -188,7 +188,7 @@ pub enum Location {
     Cube,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub enum Visibility {
     Read,
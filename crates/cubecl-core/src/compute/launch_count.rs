@@ -0,0 +1,43 @@
+use core::cell::Cell;
+
+std::thread_local! {
+    static COUNT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Run `func`, returning its result with the number of kernels it launched on this thread.
+///
+/// Only the kernels launched with a [KernelLauncher](crate::prelude::KernelLauncher), like the
+/// ones generated by `#[cube(launch)]`, are counted. Counts can be nested, the kernels counted
+/// by an inner count being counted by the outer ones as well.
+///
+/// ```ignore
+/// let (result, launches) = count_launches(|| matmul::launch_ref::<R, MP>(&strategy, ...));
+/// assert_eq!(launches, 0);
+/// ```
+pub fn count_launches<T>(func: impl FnOnce() -> T) -> (T, usize) {
+    /// Restores the outer count, even when `func` panics.
+    struct Restore(Option<usize>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let inner = COUNT.get().unwrap_or_default();
+            COUNT.set(self.0.map(|outer| outer + inner));
+        }
+    }
+
+    let restore = Restore(COUNT.replace(Some(0)));
+    let result = func();
+    let count = COUNT.get().unwrap_or_default();
+    drop(restore);
+
+    (result, count)
+}
+
+/// Count a kernel launch, when [counting](count_launches).
+pub(crate) fn register_launch() {
+    COUNT.with(|count| {
+        if let Some(value) = count.get() {
+            count.set(Some(value + 1));
+        }
+    });
+}
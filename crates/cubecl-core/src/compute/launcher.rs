@@ -6,10 +6,10 @@ use crate::compute::KernelTask;
 use crate::prelude::{ArrayArg, TensorArg, TensorMapArg};
 use crate::{KernelSettings, prelude::CubePrimitive};
 use bytemuck::{AnyBitPattern, NoUninit};
-use cubecl_runtime::server::{Binding, CubeCount, ScalarBinding, TensorMapBinding};
+use cubecl_runtime::server::{Binding, CubeCount, IoError, ScalarBinding, TensorMapBinding};
 use cubecl_runtime::{client::ComputeClient, server::Bindings};
 
-use super::CubeKernel;
+use super::{CubeKernel, IndexReport, LaunchArgs, LaunchError, launch_count::register_launch};
 
 /// Prepare a kernel for [launch](KernelLauncher::launch).
 pub struct KernelLauncher<R: Runtime> {
@@ -27,12 +27,42 @@ pub struct KernelLauncher<R: Runtime> {
     scalar_i16: ScalarState<i16>,
     scalar_i8: ScalarState<i8>,
     pub settings: KernelSettings,
+    /// The metadata of the buffers registered, when they are recorded to be checked.
+    args: Option<LaunchArgs>,
     runtime: PhantomData<R>,
 }
 
 impl<R: Runtime> KernelLauncher<R> {
+    /// Create a launcher recording the metadata of the buffers registered, to check them against
+    /// the kernel with [launch_checked](Self::launch_checked).
+    pub fn checked() -> Self {
+        Self {
+            args: Some(LaunchArgs::default()),
+            ..Default::default()
+        }
+    }
+
+    /// Name the argument the next buffers registered come from, for the errors of
+    /// [launch_checked](Self::launch_checked).
+    pub fn arg_name(&mut self, name: &'static str) {
+        if let Some(args) = &mut self.args {
+            args.name(name);
+        }
+    }
+
+    /// Expect all the buffers registered to have the same line size with
+    /// [launch_checked](Self::launch_checked), for kernels indexing them at the same positions.
+    pub fn uniform_line_size(&mut self) {
+        if let Some(args) = &mut self.args {
+            args.uniform_line_size();
+        }
+    }
+
     /// Register a tensor to be launched.
     pub fn register_tensor(&mut self, tensor: &TensorArg<'_, R>) {
+        if let Some(args) = &mut self.args {
+            args.push_tensor(tensor);
+        }
         self.tensors.push_tensor(tensor);
     }
 
@@ -43,9 +73,22 @@ impl<R: Runtime> KernelLauncher<R> {
 
     /// Register an input array to be launched.
     pub fn register_array(&mut self, array: &ArrayArg<'_, R>) {
+        if let Some(args) = &mut self.args {
+            args.push_array(array);
+        }
         self.tensors.push_array(array);
     }
 
+    /// Register the buffer of the active [IndexReport] after the other buffers, for kernels
+    /// compiled with [index checks](KernelSettings::index_checks).
+    pub fn register_index_report(&mut self) {
+        if let Some(report) = IndexReport::active() {
+            // Not recorded for the checks, which only cover the arguments of the kernel.
+            let report = unsafe { ArrayArg::from_raw_parts::<u32>(&report, 2, 1) };
+            self.tensors.push_array(&report);
+        }
+    }
+
     /// Register a u8 scalar to be launched.
     pub fn register_u8(&mut self, scalar: u8) {
         self.scalar_u8.push(scalar);
@@ -116,10 +159,48 @@ impl<R: Runtime> KernelLauncher<R> {
     ) {
         let bindings = self.into_bindings();
         let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
+        register_launch();
 
         client.execute(kernel, cube_count, bindings);
     }
 
+    /// Launch the kernel, returning an error if the memory needed to launch it can't be
+    /// allocated.
+    #[track_caller]
+    pub fn try_launch<K: CubeKernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), IoError> {
+        let bindings = self.into_bindings();
+        let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
+        register_launch();
+
+        client.try_execute(kernel, cube_count, bindings)
+    }
+
+    /// Launch the kernel after checking the element size, line size and rank of the buffers
+    /// registered against the ones the kernel is compiled for.
+    ///
+    /// The kernel is expanded to know what it expects, so this is slower than
+    /// [launch](Self::launch), and meant for when the arguments aren't known to be valid. Only the
+    /// size of the elements is known, so element types of the same size aren't told apart. The
+    /// launcher must be created with [checked](Self::checked), otherwise nothing is checked.
+    #[track_caller]
+    pub fn launch_checked<K: CubeKernel>(
+        mut self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), LaunchError> {
+        if let Some(args) = self.args.take() {
+            args.check(&kernel.define())?;
+        }
+
+        Ok(self.try_launch(cube_count, kernel, client)?)
+    }
+
     /// Launch the kernel without check bounds.
     ///
     /// # Safety
@@ -138,11 +219,34 @@ impl<R: Runtime> KernelLauncher<R> {
         unsafe {
             let bindings = self.into_bindings();
             let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
+            register_launch();
 
             client.execute_unchecked(kernel, cube_count, bindings);
         }
     }
 
+    /// Launch the kernel without check bounds, returning an error if the memory needed to launch
+    /// it can't be allocated.
+    ///
+    /// # Safety
+    ///
+    /// See [launch_unchecked](Self::launch_unchecked).
+    #[track_caller]
+    pub unsafe fn try_launch_unchecked<K: CubeKernel>(
+        self,
+        cube_count: CubeCount,
+        kernel: K,
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Result<(), IoError> {
+        unsafe {
+            let bindings = self.into_bindings();
+            let kernel = Box::new(KernelTask::<R::Compiler, K>::new(kernel));
+            register_launch();
+
+            client.try_execute_unchecked(kernel, cube_count, bindings)
+        }
+    }
+
     /// We need to create the bindings in the same order they are defined in the compilation step.
     ///
     /// The function [crate::KernelIntegrator::integrate] stars by registering the input tensors followed
@@ -357,6 +461,7 @@ impl<R: Runtime> Default for KernelLauncher<R> {
             scalar_i16: ScalarState::Empty,
             scalar_i8: ScalarState::Empty,
             settings: Default::default(),
+            args: None,
             runtime: PhantomData,
         }
     }
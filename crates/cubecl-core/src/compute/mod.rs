@@ -1,7 +1,13 @@
 mod builder;
+mod index_checks;
 mod kernel;
+mod launch_count;
 mod launcher;
+mod validation;
 
 pub use builder::*;
+pub use index_checks::*;
 pub use kernel::*;
+pub use launch_count::count_launches;
 pub use launcher::*;
+pub use validation::*;
@@ -0,0 +1,341 @@
+use cubecl_ir::{Branch, Id, Metadata, Operation, Scope, VariableKind};
+use cubecl_runtime::server::IoError;
+use hashbrown::HashMap;
+use thiserror::Error;
+
+use crate::prelude::{ArrayArg, TensorArg};
+use crate::{Runtime, try_tensor_line_size_parallel, try_tensor_line_size_perpendicular};
+
+use super::KernelDefinition;
+
+/// Error returned when a kernel is launched with [checks](super::KernelLauncher::launch_checked).
+#[derive(Debug, Error)]
+pub enum LaunchError {
+    /// The kernel doesn't have as many buffers as the arguments provided
+    #[error("the kernel has {expected} buffer arguments, but {actual} were provided")]
+    BufferCount {
+        /// The number of buffers of the kernel.
+        expected: usize,
+        /// The number of buffers provided.
+        actual: usize,
+    },
+    /// The elements of the argument aren't the size of the ones of the kernel
+    #[error(
+        "`{arg}` has elements of {actual} bytes, but the kernel is compiled for elements of {expected} bytes"
+    )]
+    ElemSize {
+        /// The name of the argument.
+        arg: &'static str,
+        /// The size in bytes of the elements of the kernel.
+        expected: usize,
+        /// The size in bytes of the elements of the argument.
+        actual: usize,
+    },
+    /// The line sizes of the arguments aren't the ones of the kernel
+    #[error(
+        "the line sizes don't match the kernel: {}",
+        join_mismatches(mismatches)
+    )]
+    LineSizes {
+        /// Each argument with a line size the kernel isn't compiled for.
+        mismatches: Vec<LineSizeMismatch>,
+    },
+    /// The layout of the argument can't be read in lines of its line size
+    #[error(
+        "`{arg}` with shape {shape:?} and strides {strides:?} can't be read in lines of {line_size}"
+    )]
+    UnalignedLines {
+        /// The name of the argument.
+        arg: &'static str,
+        /// The line size of the argument.
+        line_size: u32,
+        /// The shape of the argument.
+        shape: Vec<usize>,
+        /// The strides of the argument.
+        strides: Vec<usize>,
+    },
+    /// The shape and the strides of the argument don't have the same rank
+    #[error("`{arg}` has a shape of rank {shape} but strides of rank {strides}")]
+    MismatchedRanks {
+        /// The name of the argument.
+        arg: &'static str,
+        /// The rank of the shape.
+        shape: usize,
+        /// The rank of the strides.
+        strides: usize,
+    },
+    /// The kernel reads dimensions the argument doesn't have
+    #[error("`{arg}` has a rank of {rank}, but the kernel needs a rank of at least {min_rank}")]
+    Rank {
+        /// The name of the argument.
+        arg: &'static str,
+        /// The lowest rank the kernel supports.
+        min_rank: usize,
+        /// The rank of the argument.
+        rank: usize,
+    },
+    /// The kernel can't be launched
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// An argument with a line size the kernel isn't compiled for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineSizeMismatch {
+    /// The name of the argument.
+    pub arg: &'static str,
+    /// The line size of the kernel.
+    pub expected: u32,
+    /// The line size of the argument.
+    pub actual: u32,
+}
+
+impl core::fmt::Display for LineSizeMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "`{}` has a line size of {} but {} is expected",
+            self.arg, self.actual, self.expected
+        )
+    }
+}
+
+fn join_mismatches(mismatches: &[LineSizeMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// What a kernel expects of one of its buffers, as known from its expansion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferExpectation {
+    /// The size in bytes of an element.
+    pub elem_size: usize,
+    /// The line size the kernel is compiled for.
+    pub line_size: u32,
+    /// The lowest rank with all the dimensions whose shape or stride the kernel reads at a constant
+    /// index.
+    pub min_rank: usize,
+}
+
+impl BufferExpectation {
+    /// The expectations of the buffers of a kernel, in the order of its bindings.
+    pub fn from_definition(definition: &KernelDefinition) -> Vec<Self> {
+        let mut min_ranks = HashMap::new();
+        read_dimensions(&definition.body, &mut min_ranks);
+
+        definition
+            .buffers
+            .iter()
+            .map(|binding| Self {
+                elem_size: binding.ty.storage_type().size(),
+                line_size: binding.ty.line_size(),
+                min_rank: min_ranks.get(&binding.id).copied().unwrap_or(0),
+            })
+            .collect()
+    }
+}
+
+/// Record the highest constant dimension + 1 whose shape or stride is read for each buffer.
+fn read_dimensions(scope: &Scope, min_ranks: &mut HashMap<Id, usize>) {
+    for instruction in scope.instructions.iter() {
+        match &instruction.operation {
+            Operation::Metadata(Metadata::Shape { dim, var } | Metadata::Stride { dim, var }) => {
+                let id = match var.kind {
+                    VariableKind::GlobalInputArray(id) | VariableKind::GlobalOutputArray(id) => id,
+                    _ => continue,
+                };
+                if let Some(dim) = dim.as_const() {
+                    let rank = min_ranks.entry(id).or_default();
+                    *rank = usize::max(*rank, dim.as_u32() as usize + 1);
+                }
+            }
+            Operation::Branch(branch) => match branch {
+                Branch::If(branch) => read_dimensions(&branch.scope, min_ranks),
+                Branch::IfElse(branch) => {
+                    read_dimensions(&branch.scope_if, min_ranks);
+                    read_dimensions(&branch.scope_else, min_ranks);
+                }
+                Branch::Switch(branch) => {
+                    read_dimensions(&branch.scope_default, min_ranks);
+                    for (_, scope) in branch.cases.iter() {
+                        read_dimensions(scope, min_ranks);
+                    }
+                }
+                Branch::RangeLoop(branch) => read_dimensions(&branch.scope, min_ranks),
+                Branch::Loop(branch) => read_dimensions(&branch.scope, min_ranks),
+                Branch::Return | Branch::Break => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// The metadata of the buffer arguments of a launch, recorded to be checked against the kernel.
+#[derive(Debug, Default)]
+pub(crate) struct LaunchArgs {
+    name: &'static str,
+    buffers: Vec<BufferArg>,
+    /// Whether the kernel reads all its buffers with the same line size.
+    uniform_line_size: bool,
+}
+
+#[derive(Debug)]
+struct BufferArg {
+    name: &'static str,
+    elem_size: usize,
+    line_size: u32,
+    /// The shape and strides of a tensor, or `None` for an array.
+    layout: Option<(Vec<usize>, Vec<usize>)>,
+    len: usize,
+}
+
+impl LaunchArgs {
+    /// Name the buffers registered next after the argument they come from.
+    pub(crate) fn name(&mut self, name: &'static str) {
+        self.name = name;
+    }
+
+    /// Expect all the buffers to have the same line size, for kernels indexing them at the same
+    /// positions.
+    pub(crate) fn uniform_line_size(&mut self) {
+        self.uniform_line_size = true;
+    }
+
+    pub(crate) fn push_tensor<R: Runtime>(&mut self, tensor: &TensorArg<'_, R>) {
+        if let TensorArg::Handle { handle, line_size } = tensor {
+            self.buffers.push(BufferArg {
+                name: self.name,
+                elem_size: handle.elem_size,
+                line_size: *line_size as u32,
+                layout: Some((handle.shape.to_vec(), handle.strides.to_vec())),
+                len: handle.shape.iter().product(),
+            });
+        }
+    }
+
+    pub(crate) fn push_array<R: Runtime>(&mut self, array: &ArrayArg<'_, R>) {
+        if let ArrayArg::Handle { handle, line_size } = array {
+            self.buffers.push(BufferArg {
+                name: self.name,
+                elem_size: handle.elem_size,
+                line_size: *line_size as u32,
+                layout: None,
+                len: handle.length[0],
+            });
+        }
+    }
+
+    /// Check the arguments against the buffers of the kernel.
+    pub(crate) fn check(&self, definition: &KernelDefinition) -> Result<(), LaunchError> {
+        let mut expectations = BufferExpectation::from_definition(definition);
+        if definition.options.index_checks {
+            // The report of the index checks isn't an argument of the kernel.
+            expectations.pop();
+        }
+        if expectations.len() != self.buffers.len() {
+            return Err(LaunchError::BufferCount {
+                expected: expectations.len(),
+                actual: self.buffers.len(),
+            });
+        }
+
+        let mismatches = self.line_size_mismatches(&expectations);
+        if !mismatches.is_empty() {
+            return Err(LaunchError::LineSizes { mismatches });
+        }
+
+        self.buffers
+            .iter()
+            .zip(expectations.iter())
+            .try_for_each(|(buffer, expected)| buffer.check(expected))
+    }
+
+    fn line_size_mismatches(&self, expectations: &[BufferExpectation]) -> Vec<LineSizeMismatch> {
+        // A kernel with a uniform line size is specialized for the smallest one, which all its
+        // buffers can be read with.
+        let uniform = expectations
+            .iter()
+            .map(|expected| expected.line_size)
+            .min()
+            .filter(|_| self.uniform_line_size);
+
+        self.buffers
+            .iter()
+            .zip(expectations.iter())
+            .filter_map(|(buffer, expected)| {
+                let expected = uniform.unwrap_or(expected.line_size);
+                (buffer.line_size != expected).then_some(LineSizeMismatch {
+                    arg: buffer.name,
+                    expected,
+                    actual: buffer.line_size,
+                })
+            })
+            .collect()
+    }
+}
+
+impl BufferArg {
+    fn check(&self, expected: &BufferExpectation) -> Result<(), LaunchError> {
+        let arg = self.name;
+        if self.elem_size != expected.elem_size {
+            return Err(LaunchError::ElemSize {
+                arg,
+                expected: expected.elem_size,
+                actual: self.elem_size,
+            });
+        }
+
+        let Some((shape, strides)) = &self.layout else {
+            return match self.len % self.line_size as usize {
+                0 => Ok(()),
+                _ => Err(LaunchError::UnalignedLines {
+                    arg,
+                    line_size: self.line_size,
+                    shape: vec![self.len],
+                    strides: vec![1],
+                }),
+            };
+        };
+        if shape.len() != strides.len() {
+            return Err(LaunchError::MismatchedRanks {
+                arg,
+                shape: shape.len(),
+                strides: strides.len(),
+            });
+        }
+        if shape.len() < expected.min_rank {
+            return Err(LaunchError::Rank {
+                arg,
+                min_rank: expected.min_rank,
+                rank: shape.len(),
+            });
+        }
+
+        let line_size = self.line_size as u8;
+        // Lines are either along a contiguous axis, or across the axes inside of a stride.
+        let aligned = line_size == 1
+            || (0..shape.len()).any(|axis| {
+                let parallel =
+                    try_tensor_line_size_parallel([line_size].into_iter(), shape, strides, axis);
+                let perpendicular = try_tensor_line_size_perpendicular(
+                    [line_size].into_iter(),
+                    shape,
+                    strides,
+                    axis,
+                );
+                parallel.is_ok() || perpendicular.is_ok()
+            });
+        match aligned {
+            true => Ok(()),
+            false => Err(LaunchError::UnalignedLines {
+                arg,
+                line_size: self.line_size,
+                shape: shape.clone(),
+                strides: strides.clone(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use alloc::string::String;
+use core::cell::RefCell;
+
+use cubecl_ir::Scope;
+
+std::thread_local! {
+    static KERNEL_NAME: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Expand the kernel `kernel_name` with `func`, naming it in the failed comptime assertions.
+pub fn in_kernel<R>(kernel_name: &str, func: impl FnOnce() -> R) -> R {
+    /// Restores the name of the outer kernel, even when `func` panics.
+    struct Restore(Option<String>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            KERNEL_NAME.with(|name| *name.borrow_mut() = self.0.take());
+        }
+    }
+
+    let outer = KERNEL_NAME.with(|name| name.replace(Some(kernel_name.into())));
+    let _restore = Restore(outer);
+    func()
+}
+
+/// Stop the compilation of the kernel being expanded with the message if the condition is false.
+pub fn expand(_scope: &mut Scope, condition: bool, message: impl FnOnce() -> String) {
+    if condition {
+        return;
+    }
+
+    let message = message();
+    match KERNEL_NAME.with(|name| name.borrow().clone()) {
+        Some(kernel_name) => {
+            panic!("Comptime assertion failed in kernel `{kernel_name}`: {message}")
+        }
+        None => panic!("Comptime assertion failed: {message}"),
+    }
+}
@@ -43,7 +43,7 @@ mod new {
             intrinsic!(|_| {
                 let elem: ExpandElementTyped<P> = val;
                 elem.expand.into()
-            })
+            }, host: Line { val })
         }
     }
 
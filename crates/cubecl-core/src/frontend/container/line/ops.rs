@@ -5,8 +5,8 @@ use num_traits::{NumCast, ToPrimitive};
 use crate::{self as cubecl, prelude::Powi};
 use crate::{
     frontend::{
-        Abs, Ceil, Clamp, Cos, CubePrimitive, Erf, Exp, ExpandElementTyped, Floor, Log, Log1p, Max,
-        Min, Powf, Recip, Remainder, Round, Sin, Sqrt, Tanh,
+        Abs, Ceil, Clamp, Cos, CubePrimitive, Erf, Erfc, Exp, Exp2, ExpandElementTyped, Floor, Fma,
+        Log, Log1p, Log2, Max, Min, Powf, Recip, Remainder, Round, Rsqrt, Sin, Sqrt, Tanh,
     },
     prelude::{BitwiseNot, CountOnes, FindFirstSet, LeadingZeros, ReverseBits},
     unexpanded,
@@ -237,13 +237,18 @@ impl<P: CubePrimitive + Abs> Abs for Line<P> {}
 impl<P: CubePrimitive + Max> Max for Line<P> {}
 impl<P: CubePrimitive + Min> Min for Line<P> {}
 impl<P: CubePrimitive + Clamp> Clamp for Line<P> {}
+impl<P: CubePrimitive + Fma> Fma for Line<P> {}
 impl<P: CubePrimitive + Log> Log for Line<P> {}
 impl<P: CubePrimitive + Log1p> Log1p for Line<P> {}
+impl<P: CubePrimitive + Log2> Log2 for Line<P> {}
 impl<P: CubePrimitive + Erf> Erf for Line<P> {}
+impl<P: CubePrimitive + Erfc> Erfc for Line<P> {}
 impl<P: CubePrimitive + Exp> Exp for Line<P> {}
+impl<P: CubePrimitive + Exp2> Exp2 for Line<P> {}
 impl<P: CubePrimitive + Powf> Powf for Line<P> {}
 impl<P: CubePrimitive + Powi<I>, I: CubePrimitive> Powi<Line<I>> for Line<P> {}
 impl<P: CubePrimitive + Sqrt> Sqrt for Line<P> {}
+impl<P: CubePrimitive + Rsqrt> Rsqrt for Line<P> {}
 impl<P: CubePrimitive + Cos> Cos for Line<P> {}
 impl<P: CubePrimitive + Sin> Sin for Line<P> {}
 impl<P: CubePrimitive + Tanh> Tanh for Line<P> {}
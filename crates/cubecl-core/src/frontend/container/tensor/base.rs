@@ -15,13 +15,99 @@ use crate as cubecl;
 
 /// The tensor type is similar to the [array type](crate::prelude::Array), however it comes with more
 /// metadata such as [stride](Tensor::stride) and [shape](Tensor::shape).
+///
+/// To evaluate a cube function on the host, a tensor can be backed by a vector with
+/// [host](Tensor::host).
 #[derive(new)]
 pub struct Tensor<T: CubeType> {
     _val: PhantomData<T>,
+    #[new(default)]
+    host: Option<HostTensor<T>>,
 }
 
 type TensorExpand<T> = ExpandElementTyped<Tensor<T>>;
 
+/// The data and metadata of a tensor on the host.
+struct HostTensor<T> {
+    data: Vec<T>,
+    shape: Vec<u32>,
+    strides: Vec<u32>,
+}
+
+impl<T: CubeType> Tensor<T> {
+    /// A tensor backed by `data` with the given shape and strides, to evaluate cube functions on
+    /// the host with a [HostContext](crate::HostContext).
+    ///
+    /// Lines hold a single element on the host, so tensors of lines have a line size of 1.
+    ///
+    /// # Panics
+    ///
+    /// If the rank of the shape and the strides differ, or the data is too short for them.
+    pub fn host(data: Vec<T>, shape: Vec<u32>, strides: Vec<u32>) -> Self {
+        assert_eq!(
+            shape.len(),
+            strides.len(),
+            "The shape {shape:?} and the strides {strides:?} should have the same rank"
+        );
+        let required = match shape.contains(&0) {
+            true => 0,
+            false => {
+                1 + shape
+                    .iter()
+                    .zip(&strides)
+                    .map(|(s, t)| (s - 1) * t)
+                    .sum::<u32>()
+            }
+        };
+        let len = data.len();
+        assert!(
+            len >= required as usize,
+            "The shape {shape:?} and strides {strides:?} need {required} elements, got {len}"
+        );
+
+        Self {
+            _val: PhantomData,
+            host: Some(HostTensor {
+                data,
+                shape,
+                strides,
+            }),
+        }
+    }
+
+    /// A contiguous row-major tensor backed by `data`, see [host](Tensor::host).
+    pub fn host_contiguous(data: Vec<T>, shape: Vec<u32>) -> Self {
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1];
+        }
+        Self::host(data, shape, strides)
+    }
+
+    /// The data of a tensor created with [host](Tensor::host).
+    pub fn host_data(&self) -> &[T] {
+        &self.host_tensor().data
+    }
+
+    fn host_tensor(&self) -> &HostTensor<T> {
+        match &self.host {
+            Some(host) => host,
+            None => unexpanded!("Only the tensors created with `Tensor::host` exist on the host"),
+        }
+    }
+
+    pub(crate) fn host_index(&self, i: u32) -> &T {
+        &self.host_tensor().data[i as usize]
+    }
+
+    pub(crate) fn host_index_mut(&mut self, i: u32) -> &mut T {
+        match &mut self.host {
+            Some(host) => &mut host.data[i as usize],
+            None => unexpanded!("Only the tensors created with `Tensor::host` exist on the host"),
+        }
+    }
+}
+
 /// Module that contains the implementation details of the metadata functions.
 mod metadata {
     use cubecl_ir::ExpandElement;
@@ -48,7 +134,7 @@ mod metadata {
                     out.clone().into(),
                 ));
                 out.into()
-            })
+            }, host: self.host_tensor().strides[dim as usize])
         }
 
         /// Obtain the shape of input at dimension dim
@@ -65,7 +151,7 @@ mod metadata {
                     out.clone().into(),
                 ));
                 out.into()
-            })
+            }, host: self.host_tensor().shape[dim as usize])
         }
 
         /// Obtain the coordinate corresponding to the given `index` of the tensor at dimension `dim`.
@@ -100,7 +186,7 @@ mod metadata {
                 ));
 
                 coordinate.into()
-            })
+            }, host: (index / self.stride(dim)) % self.shape(dim))
         }
 
         /// The number of vectorized elements in the tensor.
@@ -114,7 +200,7 @@ mod metadata {
             intrinsic!(|scope| {
                 let elem: ExpandElementTyped<Array<u32>> = self.expand.into();
                 elem.__expand_len_method(scope)
-            })
+            }, host: self.host_tensor().data.len() as u32)
         }
 
         /// The length of the buffer representing the tensor in terms of vectorized elements.
@@ -128,7 +214,7 @@ mod metadata {
             intrinsic!(|scope| {
                 let elem: ExpandElementTyped<Array<u32>> = self.expand.into();
                 elem.__expand_buffer_len_method(scope)
-            })
+            }, host: self.host_tensor().data.len() as u32)
         }
 
         /// Returns the rank of the tensor.
@@ -137,6 +223,29 @@ mod metadata {
                 let out = scope.create_local(Type::new(u32::as_type(scope)));
                 scope.register(Instruction::new(Metadata::Rank { var: *self.expand }, *out));
                 out.into()
+            }, host: self.host_tensor().shape.len() as u32)
+        }
+    }
+}
+
+/// Module that contains the implementation details of the cache hints.
+mod cache {
+    use super::*;
+    use crate::ir::{CacheHint, NonSemantic};
+
+    #[cube]
+    impl<T: CubeType> Tensor<T> {
+        /// Hint how the reads of the tensor following this call should be cached, e.g. to stream
+        /// an operand read once through the caches while keeping the ones reused.
+        ///
+        /// Only the CUDA backend applies the hints, they are ignored elsewhere.
+        #[allow(unused_variables)]
+        pub fn cache_hint(&self, #[comptime] hint: CacheHint) {
+            intrinsic!(|scope| {
+                scope.register(NonSemantic::CacheHint {
+                    buffer: *self.expand,
+                    hint,
+                });
             })
         }
     }
@@ -177,7 +286,7 @@ mod indexation {
                     *out,
                 ));
                 out.into()
-            })
+            }, host: self.host_index(i))
         }
 
         /// Perform an unchecked index assignment into the array
@@ -200,7 +309,7 @@ mod indexation {
                     }),
                     *self.expand,
                 ));
-            })
+            }, host: *self.host_index_mut(i) = value)
         }
     }
 }
@@ -218,7 +327,8 @@ mod line {
         /// let size = tensor[0].size();
         /// ```
         pub fn line_size(&self) -> u32 {
-            unexpanded!()
+            self.host_tensor();
+            1
         }
 
         // Expand function of [size](Tensor::line_size).
@@ -0,0 +1,169 @@
+use cubecl_runtime::server::Handle;
+
+use crate::{self as cubecl, prelude::*};
+
+/// The number of `u32` words of a record in the sink.
+const RECORD_WORDS: u32 = 5;
+/// The kind of a record whose value is the bits of an `f32`.
+const KIND_F32: u32 = 0;
+/// The kind of a record whose value is a `u32`.
+const KIND_U32: u32 = 1;
+
+/// A buffer kernels append tagged values to with [debug_record](crate::debug_record), to inspect
+/// intermediate values like the contents of shared memory after a launch.
+///
+/// Each record holds the tag, the `UNIT_POS` and the `CUBE_POS` of the unit that wrote it along
+/// with the value. Records past the capacity of the sink are dropped and the sink is marked as
+/// overflowed. When the sink is disabled, recording compiles to nothing, so the instrumentation can
+/// stay in the kernel.
+///
+/// The buffers of a sink are created and read back with a [DebugSinkHandle].
+#[derive(CubeLaunch, CubeType)]
+pub struct DebugSink {
+    /// The cursor of the next record, followed by the overflow flag.
+    header: Array<Atomic<u32>>,
+    records: Array<u32>,
+    #[cube(comptime)]
+    enabled: bool,
+}
+
+#[cube]
+impl DebugSink {
+    /// Append a record of `value` tagged with `tag` for the current unit.
+    ///
+    /// Floats are recorded as `f32` and integers as `u32`.
+    pub fn record<V: Numeric>(&mut self, tag: u32, value: V) {
+        if comptime!(self.enabled) {
+            let index = Atomic::add(&self.header[0], 1);
+
+            if index < self.records.len() / RECORD_WORDS {
+                let offset = index * RECORD_WORDS;
+                self.records[offset] = tag;
+                self.records[offset + 1] = UNIT_POS;
+                self.records[offset + 2] = CUBE_POS;
+
+                if comptime!(V::as_type_native_unchecked().is_float()) {
+                    self.records[offset + 3] = KIND_F32;
+                    self.records[offset + 4] = u32::reinterpret(f32::cast_from(value));
+                } else {
+                    self.records[offset + 3] = KIND_U32;
+                    self.records[offset + 4] = u32::cast_from(value);
+                }
+            } else {
+                Atomic::store(&self.header[1], 1);
+            }
+        }
+    }
+}
+
+/// Append a tagged value to a [DebugSink], which compiles to nothing when the sink is disabled.
+///
+/// ```ignore
+/// debug_record!(sink, 0, stage[UNIT_POS]);
+/// ```
+#[macro_export]
+macro_rules! debug_record {
+    ($sink:expr, $tag:expr, $value:expr) => {{
+        let _ = (&$sink, $tag, $value);
+    }};
+}
+
+/// The device buffers of a [DebugSink], to launch kernels with it and decode its records.
+pub struct DebugSinkHandle {
+    header: Handle,
+    records: Handle,
+    capacity: usize,
+    enabled: bool,
+}
+
+/// The records of a [DebugSink] after a launch, in the order they were appended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugRecords {
+    pub records: Vec<DebugRecord>,
+    /// Whether records were dropped because the sink was full.
+    pub overflowed: bool,
+}
+
+/// A value appended to a [DebugSink] by a unit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugRecord {
+    pub tag: u32,
+    pub unit_pos: u32,
+    pub cube_pos: u32,
+    pub value: DebugValue,
+}
+
+/// The value of a [DebugRecord].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugValue {
+    F32(f32),
+    U32(u32),
+}
+
+impl DebugSinkHandle {
+    /// A sink holding up to `capacity` records.
+    pub fn new<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>, capacity: usize) -> Self {
+        Self {
+            header: client.create(u32::as_bytes(&[0, 0])),
+            records: client.empty(capacity.max(1) * RECORD_WORDS as usize * size_of::<u32>()),
+            capacity,
+            enabled: true,
+        }
+    }
+
+    /// A sink for which recording compiles to nothing.
+    pub fn disabled<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> Self {
+        Self {
+            enabled: false,
+            ..Self::new::<R>(client, 0)
+        }
+    }
+
+    /// The launch argument of the sink.
+    pub fn as_arg<R: Runtime>(&self) -> DebugSinkLaunch<'_, R> {
+        let records_len = self.capacity * RECORD_WORDS as usize;
+
+        unsafe {
+            DebugSinkLaunch::new(
+                ArrayArg::from_raw_parts::<Atomic<u32>>(&self.header, 2, 1),
+                ArrayArg::from_raw_parts::<u32>(&self.records, records_len, 1),
+                &self.enabled,
+            )
+        }
+    }
+
+    /// Read the records appended by the kernels launched with this sink.
+    pub fn read<R: Runtime>(&self, client: &ComputeClient<R::Server, R::Channel>) -> DebugRecords {
+        let header = client.read_one(self.header.clone());
+        let header = u32::from_bytes(&header);
+        let count = (header[0] as usize).min(self.capacity);
+
+        let words = client.read_one(self.records.clone());
+        let words = u32::from_bytes(&words);
+        let records = words
+            .chunks_exact(RECORD_WORDS as usize)
+            .take(count)
+            .map(|record| DebugRecord {
+                tag: record[0],
+                unit_pos: record[1],
+                cube_pos: record[2],
+                value: match record[3] {
+                    KIND_F32 => DebugValue::F32(f32::from_bits(record[4])),
+                    _ => DebugValue::U32(record[4]),
+                },
+            })
+            .collect();
+
+        DebugRecords {
+            records,
+            overflowed: header[1] != 0,
+        }
+    }
+}
+
+impl DebugRecords {
+    /// The records with the given tag.
+    pub fn tagged(&self, tag: u32) -> impl Iterator<Item = &DebugRecord> {
+        self.records.iter().filter(move |record| record.tag == tag)
+    }
+}
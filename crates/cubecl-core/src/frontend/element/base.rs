@@ -110,6 +110,10 @@ pub trait CompilationArg:
 
 impl CompilationArg for () {}
 
+/// The presence of an optional argument is part of the compilation argument, so the kernels with
+/// and without it are compiled separately.
+impl<C: CompilationArg> CompilationArg for Option<C> {}
+
 /// Defines how a [launch argument](LaunchArg) can be expanded.
 ///
 /// TODO Verify the accuracy of the next comment.
@@ -383,6 +387,20 @@ impl<T: IntoMut> IntoMut for Option<T> {
     }
 }
 
+/// An optional value whose presence is known at compile time, so `if let Some(..)` only expands
+/// the taken branch.
+impl<T: CubeType> CubeType for Option<T> {
+    type ExpandType = Option<T::ExpandType>;
+}
+
+impl<T: CubeDebug> CubeDebug for Option<T> {
+    fn set_debug_name(&self, scope: &mut Scope, name: &'static str) {
+        if let Some(value) = self {
+            value.set_debug_name(scope, name);
+        }
+    }
+}
+
 impl<T: CubeType> CubeType for Vec<T> {
     type ExpandType = Vec<T::ExpandType>;
 }
@@ -433,3 +451,38 @@ impl LaunchArgExpand for () {
     ) -> <Self as CubeType>::ExpandType {
     }
 }
+
+impl<T: LaunchArg> LaunchArg for Option<T> {
+    type RuntimeArg<'a, R: Runtime> = Option<T::RuntimeArg<'a, R>>;
+
+    fn compilation_arg<R: Runtime>(runtime_arg: &Self::RuntimeArg<'_, R>) -> Self::CompilationArg {
+        runtime_arg.as_ref().map(T::compilation_arg::<R>)
+    }
+}
+
+/// Nothing is registered for a missing argument, so it doesn't get a binding.
+impl<R: Runtime, A: ArgSettings<R>> ArgSettings<R> for Option<A> {
+    fn register(&self, launcher: &mut KernelLauncher<R>) {
+        if let Some(arg) = self {
+            arg.register(launcher);
+        }
+    }
+}
+
+impl<T: LaunchArgExpand> LaunchArgExpand for Option<T> {
+    type CompilationArg = Option<T::CompilationArg>;
+
+    fn expand(
+        arg: &Self::CompilationArg,
+        builder: &mut KernelBuilder,
+    ) -> <Self as CubeType>::ExpandType {
+        arg.as_ref().map(|arg| T::expand(arg, builder))
+    }
+
+    fn expand_output(
+        arg: &Self::CompilationArg,
+        builder: &mut KernelBuilder,
+    ) -> <Self as CubeType>::ExpandType {
+        arg.as_ref().map(|arg| T::expand_output(arg, builder))
+    }
+}
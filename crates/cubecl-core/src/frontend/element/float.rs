@@ -18,22 +18,48 @@ mod typemap;
 pub use typemap::*;
 
 /// Floating point numbers. Used as input in float kernels
+///
+/// # Precision
+///
+/// The math functions are lowered to the intrinsics of each backend, with the bounds they document
+/// for `f32`, in ULPs unless stated otherwise:
+///
+/// | Function | CUDA / HIP | Metal | WGSL / SPIR-V | CPU |
+/// |----------|------------|-------|---------------|-----|
+/// | `erf`    | 2          | polyfill | polyfill   | polyfill |
+/// | `erfc`   | 4          | polyfill | polyfill   | polyfill |
+/// | `tanh`   | 2          | 5     | as `sinh / cosh` | 1 |
+/// | `exp2`   | 2          | 4     | `3 + 2 * abs(x)` | 1 |
+/// | `log2`   | 1          | 4     | 3, `2^-21` absolute in `[0.5, 2]` | 1 |
+/// | `rsqrt`  | 2          | 2     | 2             | 1 |
+/// | `recip`  | 0          | 2.5   | 2.5           | 0 |
+/// | `fma`    | 0          | 0     | as `a * b + c` | 0 |
+///
+/// The polyfills of `erf` and `erfc` are accurate to an absolute error of `2e-7`, see
+/// [erf()](crate::prelude::erf()). Half precision floats are computed in `f32` when the backend
+/// has no half precision intrinsic, and rounded once. The results outside of the domain of a
+/// function, such as `log2` or `rsqrt` of negative numbers, are the ones of the backend.
 pub trait Float:
     Numeric
     + Exp
+    + Exp2
     + Log
     + Log1p
+    + Log2
     + Cos
     + Sin
     + Tanh
     + Powf
     + Powi<i32>
     + Sqrt
+    + Rsqrt
     + Round
     + Floor
     + Ceil
     + Erf
+    + Erfc
     + Recip
+    + Fma
     + Magnitude
     + Normalize
     + Dot
@@ -233,20 +233,25 @@ impl<const POS: u8> Dot for ElemExpand<POS> {}
 impl<const POS: u8> Magnitude for ElemExpand<POS> {}
 impl<const POS: u8> Recip for ElemExpand<POS> {}
 impl<const POS: u8> Erf for ElemExpand<POS> {}
+impl<const POS: u8> Erfc for ElemExpand<POS> {}
 impl<const POS: u8> Exp for ElemExpand<POS> {}
+impl<const POS: u8> Exp2 for ElemExpand<POS> {}
 impl<const POS: u8> Remainder for ElemExpand<POS> {}
 impl<const POS: u8> Abs for ElemExpand<POS> {}
 impl<const POS: u8> Max for ElemExpand<POS> {}
 impl<const POS: u8> Min for ElemExpand<POS> {}
 impl<const POS: u8> Clamp for ElemExpand<POS> {}
+impl<const POS: u8> Fma for ElemExpand<POS> {}
 impl<const POS: u8> Log for ElemExpand<POS> {}
 impl<const POS: u8> Log1p for ElemExpand<POS> {}
+impl<const POS: u8> Log2 for ElemExpand<POS> {}
 impl<const POS: u8> Cos for ElemExpand<POS> {}
 impl<const POS: u8> Sin for ElemExpand<POS> {}
 impl<const POS: u8> Tanh for ElemExpand<POS> {}
 impl<const POS: u8> Powf for ElemExpand<POS> {}
 impl<const POS: u8, I: CubePrimitive> Powi<I> for ElemExpand<POS> {}
 impl<const POS: u8> Sqrt for ElemExpand<POS> {}
+impl<const POS: u8> Rsqrt for ElemExpand<POS> {}
 impl<const POS: u8> Round for ElemExpand<POS> {}
 impl<const POS: u8> Floor for ElemExpand<POS> {}
 impl<const POS: u8> Ceil for ElemExpand<POS> {}
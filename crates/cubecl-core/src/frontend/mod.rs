@@ -5,10 +5,12 @@ pub mod synchronization;
 
 mod base;
 mod comment;
+pub mod comptime_assert;
 pub mod comptime_error;
 mod const_expand;
 mod container;
 mod debug;
+mod debug_sink;
 mod element;
 mod indexation;
 mod list;
@@ -23,6 +25,7 @@ pub use comment::*;
 pub use const_expand::*;
 pub use container::*;
 pub use debug::*;
+pub use debug_sink::*;
 pub use element::*;
 pub use indexation::*;
 pub use list::*;
@@ -32,4 +35,4 @@ pub use plane::*;
 pub use polyfills::*;
 pub use topology::*;
 
-pub use crate::{debug_print, debug_print_expand};
+pub use crate::{debug_print, debug_print_expand, debug_record};
@@ -85,8 +85,10 @@ pub mod index_assign {
     }
 
     macro_rules! impl_index {
-        ($type:ident) => {
-            impl<E: CubePrimitive> CubeIndexMut for $type<E> {}
+        ($type:ident $(, $($host:tt)*)?) => {
+            impl<E: CubePrimitive> CubeIndexMut for $type<E> {
+                $($($host)*)?
+            }
 
             impl<E: CubePrimitive> CubeIndexMutExpand for ExpandElementTyped<$type<E>> {
                 fn expand_index_mut(
@@ -102,7 +104,12 @@ pub mod index_assign {
     }
 
     impl_index!(Array);
-    impl_index!(Tensor);
+    impl_index!(
+        Tensor,
+        fn cube_idx_mut(&mut self, i: u32) -> &mut E {
+            self.host_index_mut(i)
+        }
+    );
     impl_index!(SharedMemory);
     impl_index!(Line);
 }
@@ -128,10 +135,12 @@ pub mod index {
     }
 
     macro_rules! impl_index {
-        ($type:ident) => {
+        ($type:ident $(, $($host:tt)*)?) => {
             impl<E: CubePrimitive> CubeIndex for $type<E> {
                 type Output = E;
                 type Idx = u32;
+
+                $($($host)*)?
             }
 
             impl<E: CubePrimitive> CubeIndexExpand for ExpandElementTyped<$type<E>> {
@@ -157,7 +166,12 @@ pub mod index {
     }
 
     impl_index!(Array);
-    impl_index!(Tensor);
+    impl_index!(
+        Tensor,
+        fn cube_idx(&self, i: u32) -> &E {
+            self.host_index(i)
+        }
+    );
     impl_index!(SharedMemory);
     impl_index!(Line);
 }
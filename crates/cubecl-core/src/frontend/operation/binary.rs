@@ -156,8 +156,10 @@ pub mod shr {
 }
 
 /// For binary functions without special syntax
+///
+/// The types following `host:` also evaluate the function on the host with the given closure.
 macro_rules! impl_binary_func {
-    ($trait_name:ident, $method_name:ident, $func_name_expand:ident, $method_name_expand:ident, $operator:expr, $($type:ty),*) => {
+    ($trait_name:ident, $method_name:ident, $func_name_expand:ident, $method_name_expand:ident, $operator:expr, $($type:ty),* $(; host: $host:expr, $($host_type:ty),*)*) => {
         pub trait $trait_name: CubeType + Sized {
             fn $method_name(self, _rhs: Self) -> Self {
                 unexpanded!()
@@ -178,6 +180,16 @@ macro_rules! impl_binary_func {
                 binary_expand(scope, self.into(), rhs.into(), $operator).into()
             }
         })*
+        $($(impl $trait_name for $host_type {
+            fn $method_name(self, rhs: Self) -> Self {
+                ($host)(self, rhs)
+            }
+        })*)*
+        $($(impl ExpandElementTyped<$host_type> {
+            pub fn $method_name_expand(self, scope: &mut Scope, rhs: ExpandElementTyped<$host_type>) -> ExpandElementTyped<$host_type> {
+                binary_expand(scope, self.into(), rhs.into(), $operator).into()
+            }
+        })*)*
     }
 }
 
@@ -261,17 +273,9 @@ impl_binary_func!(
     f16,
     bf16,
     flex32,
-    tf32,
-    f32,
-    f64,
-    i8,
-    i16,
-    i32,
-    i64,
-    u8,
-    u16,
-    u32,
-    u64
+    tf32;
+    host: |lhs: Self, rhs: Self| lhs.max(rhs), f32, f64;
+    host: Ord::max, i8, i16, i32, i64, u8, u16, u32, u64
 );
 impl_binary_func!(
     Min,
@@ -286,17 +290,9 @@ impl_binary_func!(
     f16,
     bf16,
     flex32,
-    tf32,
-    f32,
-    f64,
-    i8,
-    i16,
-    i32,
-    i64,
-    u8,
-    u16,
-    u32,
-    u64
+    tf32;
+    host: |lhs: Self, rhs: Self| lhs.min(rhs), f32, f64;
+    host: Ord::min, i8, i16, i32, i64, u8, u16, u32, u64
 );
 impl_binary_func!(
     Remainder,
@@ -14,6 +14,13 @@ use crate::{
 ///
 /// Since both branches are *evaluated* regardless of the condition, both branches must be *valid*
 /// regardless of the condition. Illegal memory accesses should not be done in either branch.
+///
+/// # Host evaluation
+///
+/// Evaluated on the host with a [HostContext](crate::HostContext), both branches are evaluated
+/// as well, since they are arguments. Integer arithmetic panics on overflow in debug builds
+/// however, where the kernel wraps, so a branch like `a - b` guarded by `a > b` is better written
+/// `Max::max(a, b) - b` to behave the same on both sides.
 pub fn select<C: CubePrimitive>(condition: bool, then: C, or_else: C) -> C {
     if condition { then } else { or_else }
 }
@@ -26,7 +33,10 @@ pub fn select_many<C: CubePrimitive>(
     then: Line<C>,
     or_else: Line<C>,
 ) -> Line<C> {
-    intrinsic!(|scope| select::expand(scope, condition.expand.into(), then, or_else))
+    intrinsic!(
+        |scope| select::expand(scope, condition.expand.into(), then, or_else),
+        host: Line::new(select(condition.val, then.val, or_else.val))
+    )
 }
 
 pub mod select {
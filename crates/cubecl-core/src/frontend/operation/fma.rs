@@ -1,7 +1,10 @@
+use half::{bf16, f16};
+
 use crate::{
+    flex32,
     ir::{Arithmetic, ExpandElement, FmaOperator, Instruction, Scope},
     prelude::CubePrimitive,
-    unexpanded,
+    tf32, unexpanded,
 };
 
 /// Fused multiply-add `A*B+C`.
@@ -32,3 +35,28 @@ pub fn fma_expand<C: CubePrimitive>(
 
     output
 }
+
+/// Fused multiply-add of floats and lines of floats.
+pub trait Fma: CubePrimitive + Sized {
+    /// Compute `a * b + c`, rounded once on the backends with a fused instruction.
+    #[allow(unused_variables)]
+    fn fma(a: Self, b: Self, c: Self) -> Self {
+        unexpanded!()
+    }
+
+    fn __expand_fma(
+        scope: &mut Scope,
+        a: Self::ExpandType,
+        b: Self::ExpandType,
+        c: Self::ExpandType,
+    ) -> Self::ExpandType {
+        fma_expand::<Self>(scope, a.into(), b.into(), c.into()).into()
+    }
+}
+
+impl Fma for f16 {}
+impl Fma for bf16 {}
+impl Fma for flex32 {}
+impl Fma for tf32 {}
+impl Fma for f32 {}
+impl Fma for f64 {}
@@ -127,6 +127,18 @@ impl_unary_func!(
     // f32,
     f64
 );
+impl_unary_func!(
+    Exp2,
+    exp2,
+    __expand_exp2,
+    Arithmetic::Exp2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
 impl_unary_func!(
     Log,
     log,
@@ -151,6 +163,18 @@ impl_unary_func!(
     f32,
     f64
 );
+impl_unary_func!(
+    Log2,
+    log2,
+    __expand_log2,
+    Arithmetic::Log2,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
 impl_unary_func!(
     Cos,
     cos,
@@ -199,6 +223,18 @@ impl_unary_func!(
     f32,
     f64
 );
+impl_unary_func!(
+    Rsqrt,
+    rsqrt,
+    __expand_rsqrt,
+    Arithmetic::Rsqrt,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
 impl_unary_func!(
     Round,
     round,
@@ -247,6 +283,18 @@ impl_unary_func!(
     f32,
     f64
 );
+impl_unary_func!(
+    Erfc,
+    erfc,
+    __expand_erfc,
+    Arithmetic::Erfc,
+    f16,
+    bf16,
+    flex32,
+    tf32,
+    f32,
+    f64
+);
 impl_unary_func!(
     Recip,
     recip,
@@ -60,10 +60,48 @@ pub fn expand_checked_index_assign(
     );
 }
 
+/// The error function, accurate to an absolute error of `2e-7`.
+///
+/// Small inputs use the Taylor series instead, so that `±0` and tiny magnitudes keep their sign and
+/// their relative precision.
 #[cube]
 pub fn erf<F: Float>(x: Line<F>) -> Line<F> {
     let erf = erf_positive(Abs::abs(x));
-    select_many(x.less_than(Line::new(F::new(0.0))), -erf, erf)
+    let erf = select_many(x.less_than(Line::new(F::new(0.0))), -erf, erf);
+    select_many(
+        Abs::abs(x).less_than(Line::new(F::new(0.25))),
+        erf_small(x),
+        erf,
+    )
+}
+
+/// The complementary error function `1 - erf(x)`, accurate to an absolute error of `2e-7`.
+///
+/// It is computed directly instead of from [erf()], which would cancel the small values of large
+/// inputs, but its relative error still grows with the input, up to `3e-3` at `x = 4`.
+#[cube]
+pub fn erfc<F: Float>(x: Line<F>) -> Line<F> {
+    let erfc = erfc_positive(Abs::abs(x));
+    select_many(
+        x.less_than(Line::new(F::new(0.0))),
+        Line::new(F::new(2.0)) - erfc,
+        erfc,
+    )
+}
+
+/// The Taylor series of the error function up to `x^7`, with a relative error below `2e-7` for
+/// `|x| < 0.25`.
+#[cube]
+fn erf_small<F: Float>(x: Line<F>) -> Line<F> {
+    // 2 / sqrt(pi)
+    let scale = Line::new(F::new(1.1283791));
+    let c1 = Line::new(F::new(-0.33333334));
+    let c2 = Line::new(F::new(0.1));
+    let c3 = Line::new(F::new(-0.023809524));
+    let one = Line::new(F::new(1.0));
+
+    let x2 = x * x;
+    scale * x * (((c3 * x2 + c2) * x2 + c1) * x2 + one)
 }
 
 /// An approximation of the error function: https://en.wikipedia.org/wiki/Error_function#Numerical_approximations
@@ -72,6 +110,12 @@ pub fn erf<F: Float>(x: Line<F>) -> Line<F> {
 /// > All of these approximations are valid for x ≥ 0. To use these approximations for negative x, use the fact that erf x is an odd function, so erf x = −erf(−x).
 #[cube]
 fn erf_positive<F: Float>(x: Line<F>) -> Line<F> {
+    Line::new(F::new(1.0)) - erfc_positive(x)
+}
+
+/// The same approximation as [erf_positive], for `1 - erf(x)`.
+#[cube]
+fn erfc_positive<F: Float>(x: Line<F>) -> Line<F> {
     let p = Line::new(F::new(0.3275911));
     let a1 = Line::new(F::new(0.2548296));
     let a2 = Line::new(F::new(-0.28449674));
@@ -83,7 +127,7 @@ fn erf_positive<F: Float>(x: Line<F>) -> Line<F> {
     let t = one / (one + p * x);
     let tmp = ((((a5 * t + a4) * t) + a3) * t + a2) * t + a1;
 
-    one - (tmp * t * Exp::exp(-x * x))
+    tmp * t * Exp::exp(-x * x)
 }
 
 #[allow(missing_docs)]
@@ -93,6 +137,13 @@ pub fn expand_erf(scope: &mut Scope, input: Variable, out: Variable) {
     assign::expand_no_check(scope, res, ExpandElement::Plain(out).into());
 }
 
+#[allow(missing_docs)]
+pub fn expand_erfc(scope: &mut Scope, input: Variable, out: Variable) {
+    scope.register_type::<FloatExpand<0>>(input.ty.storage_type());
+    let res = erfc::expand::<FloatExpand<0>>(scope, ExpandElement::Plain(input).into());
+    assign::expand_no_check(scope, res, ExpandElement::Plain(out).into());
+}
+
 #[cube]
 fn himul_i64(lhs: Line<i32>, rhs: Line<i32>) -> Line<i32> {
     let shift = Line::empty(lhs.size()).fill(32);
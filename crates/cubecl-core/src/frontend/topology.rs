@@ -19,6 +19,12 @@ macro_rules! constant {
             pub fn expand(_scope: &mut Scope) -> ExpandElementTyped<u32> {
                 ExpandElementTyped::new(ExpandElement::Plain(crate::ir::Variable::builtin($var)))
             }
+
+            /// Value of the constant variable for the unit evaluated on the host, see
+            /// [HostContext](crate::HostContext).
+            pub fn host() -> u32 {
+                crate::HostContext::current($var)
+            }
         }
     };
 }
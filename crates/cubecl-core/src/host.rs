@@ -0,0 +1,283 @@
+use core::cell::Cell;
+
+use crate::ir::Builtin;
+use crate::prelude::CubeDim;
+
+std::thread_local! {
+    static CURRENT: Cell<Option<HostContext>> = const { Cell::new(None) };
+}
+
+/// The position of a unit in the grid of a launch, to evaluate cube functions on the host.
+///
+/// The plain Rust version of a `#[cube]` function that doesn't launch a kernel can be called like
+/// any other function. Inside [eval](HostContext::eval), the builtins like `UNIT_POS` or
+/// `CUBE_DIM_X` return the position of the unit of the context, so tests can call the exact
+/// function the kernel runs with chosen unit ids, and tensors are backed by a vector with
+/// [Tensor::host](crate::prelude::Tensor::host).
+///
+/// ```ignore
+/// let cube = HostContext::new(CubeDim::new_2d(32, 4));
+/// for unit in cube.units() {
+///     let offset = unit.eval(|| load_offset(&tensor, config));
+/// }
+/// ```
+///
+/// Operations without a meaning for a single unit on the host, like plane operations or
+/// synchronization, still panic when called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostContext {
+    cube_dim: CubeDim,
+    cube_count: (u32, u32, u32),
+    cube_pos: (u32, u32, u32),
+    unit_pos: (u32, u32, u32),
+    cluster_dim: (u32, u32, u32),
+    plane_dim: u32,
+}
+
+impl HostContext {
+    /// The first unit of the first cube of a launch of a single cube with the given dimensions.
+    pub fn new(cube_dim: CubeDim) -> Self {
+        Self {
+            cube_dim,
+            cube_count: (1, 1, 1),
+            cube_pos: (0, 0, 0),
+            unit_pos: (0, 0, 0),
+            cluster_dim: (1, 1, 1),
+            plane_dim: 32,
+        }
+    }
+
+    /// Set the number of cubes of the launch along each axis.
+    pub fn cube_count(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.cube_count = (x, y, z);
+        self
+    }
+
+    /// Set the position of the cube of the unit along each axis.
+    pub fn cube_pos(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.cube_pos = (x, y, z);
+        self
+    }
+
+    /// Set the position of the unit in its cube along each axis.
+    pub fn unit_pos(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.unit_pos = (x, y, z);
+        self
+    }
+
+    /// Set the number of cubes of a cluster along each axis.
+    pub fn cluster_dim(mut self, x: u32, y: u32, z: u32) -> Self {
+        self.cluster_dim = (x, y, z);
+        self
+    }
+
+    /// Set the number of units of a plane, 32 by default.
+    pub fn plane_dim(mut self, plane_dim: u32) -> Self {
+        self.plane_dim = plane_dim;
+        self
+    }
+
+    /// All the units of the cube of this context, in the order of `UNIT_POS`.
+    pub fn units(&self) -> impl Iterator<Item = HostContext> {
+        let this = *self;
+        let CubeDim { x, y, z } = self.cube_dim;
+
+        (0..z).flat_map(move |unit_z| {
+            (0..y).flat_map(move |unit_y| {
+                (0..x).map(move |unit_x| this.unit_pos(unit_x, unit_y, unit_z))
+            })
+        })
+    }
+
+    /// All the cubes of the launch of this context, each at its first unit, in the order of
+    /// `CUBE_POS`.
+    pub fn cubes(&self) -> impl Iterator<Item = HostContext> {
+        let this = self.unit_pos(0, 0, 0);
+        let (x, y, z) = self.cube_count;
+
+        (0..z).flat_map(move |cube_z| {
+            (0..y).flat_map(move |cube_y| {
+                (0..x).map(move |cube_x| this.cube_pos(cube_x, cube_y, cube_z))
+            })
+        })
+    }
+
+    /// Run `func` with the builtins evaluating to the position of this unit.
+    ///
+    /// # Panics
+    ///
+    /// If the unit is outside of its cube or the cube is outside of the launch.
+    pub fn eval<R>(&self, func: impl FnOnce() -> R) -> R {
+        let CubeDim { x, y, z } = self.cube_dim;
+        assert!(
+            self.unit_pos.0 < x && self.unit_pos.1 < y && self.unit_pos.2 < z,
+            "The unit {:?} is outside of a cube of {:?}",
+            self.unit_pos,
+            self.cube_dim
+        );
+        let (x, y, z) = self.cube_count;
+        assert!(
+            self.cube_pos.0 < x && self.cube_pos.1 < y && self.cube_pos.2 < z,
+            "The cube {:?} is outside of a launch of {:?} cubes",
+            self.cube_pos,
+            self.cube_count,
+        );
+
+        /// Restores the outer context, even when `func` panics.
+        struct Restore(Option<HostContext>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(*self))));
+        func()
+    }
+
+    /// The value of a builtin for the unit of the context being [evaluated](HostContext::eval).
+    ///
+    /// # Panics
+    ///
+    /// Outside of [eval](HostContext::eval).
+    pub fn current(builtin: Builtin) -> u32 {
+        match CURRENT.with(Cell::get) {
+            Some(context) => context.builtin(builtin),
+            None => panic!(
+                "The builtin {builtin:?} was read on the host outside of `HostContext::eval`"
+            ),
+        }
+    }
+
+    /// The value of a builtin for the unit of this context.
+    pub fn builtin(&self, builtin: Builtin) -> u32 {
+        let CubeDim { x, y, z } = self.cube_dim;
+        let (count_x, count_y, count_z) = self.cube_count;
+        let (cube_x, cube_y, cube_z) = self.cube_pos;
+        let (unit_x, unit_y, unit_z) = self.unit_pos;
+        let (cluster_x, cluster_y, cluster_z) = self.cluster_dim;
+        let absolute = (
+            cube_x * x + unit_x,
+            cube_y * y + unit_y,
+            cube_z * z + unit_z,
+        );
+        let unit_pos = unit_z * x * y + unit_y * x + unit_x;
+        let cube_pos_cluster = (cube_x % cluster_x, cube_y % cluster_y, cube_z % cluster_z);
+
+        match builtin {
+            Builtin::UnitPos => unit_pos,
+            Builtin::UnitPosX => unit_x,
+            Builtin::UnitPosY => unit_y,
+            Builtin::UnitPosZ => unit_z,
+            Builtin::CubePosCluster => {
+                cube_pos_cluster.2 * cluster_x * cluster_y
+                    + cube_pos_cluster.1 * cluster_x
+                    + cube_pos_cluster.0
+            }
+            Builtin::CubePosClusterX => cube_pos_cluster.0,
+            Builtin::CubePosClusterY => cube_pos_cluster.1,
+            Builtin::CubePosClusterZ => cube_pos_cluster.2,
+            Builtin::CubePos => cube_z * count_x * count_y + cube_y * count_x + cube_x,
+            Builtin::CubePosX => cube_x,
+            Builtin::CubePosY => cube_y,
+            Builtin::CubePosZ => cube_z,
+            Builtin::CubeDim => x * y * z,
+            Builtin::CubeDimX => x,
+            Builtin::CubeDimY => y,
+            Builtin::CubeDimZ => z,
+            Builtin::CubeClusterDim => cluster_x * cluster_y * cluster_z,
+            Builtin::CubeClusterDimX => cluster_x,
+            Builtin::CubeClusterDimY => cluster_y,
+            Builtin::CubeClusterDimZ => cluster_z,
+            Builtin::CubeCount => count_x * count_y * count_z,
+            Builtin::CubeCountX => count_x,
+            Builtin::CubeCountY => count_y,
+            Builtin::CubeCountZ => count_z,
+            Builtin::PlaneDim => self.plane_dim,
+            Builtin::UnitPosPlane => unit_pos % self.plane_dim,
+            Builtin::AbsolutePos => {
+                absolute.2 * count_x * x * count_y * y + absolute.1 * count_x * x + absolute.0
+            }
+            Builtin::AbsolutePosX => absolute.0,
+            Builtin::AbsolutePosY => absolute.1,
+            Builtin::AbsolutePosZ => absolute.2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as cubecl;
+    use crate::prelude::*;
+
+    #[cube]
+    fn transpose_unit(input: &Tensor<f32>, output: &mut Tensor<f32>) {
+        let row = UNIT_POS_Y;
+        let col = UNIT_POS_X;
+        output[row * output.stride(0) + col * output.stride(1)] =
+            input[col * input.stride(0) + row * input.stride(1)];
+    }
+
+    #[test]
+    fn builtins_follow_the_launch_grid() {
+        let unit = HostContext::new(CubeDim::new_2d(8, 4))
+            .cube_count(3, 2, 1)
+            .cube_pos(2, 1, 0)
+            .unit_pos(5, 3, 0)
+            .plane_dim(16);
+
+        assert_eq!(unit.builtin(Builtin::UnitPos), 29);
+        assert_eq!(unit.builtin(Builtin::UnitPosPlane), 13);
+        assert_eq!(unit.builtin(Builtin::CubePos), 5);
+        assert_eq!(unit.builtin(Builtin::CubeDim), 32);
+        assert_eq!(unit.builtin(Builtin::CubeCount), 6);
+        assert_eq!(unit.builtin(Builtin::AbsolutePosX), 21);
+        assert_eq!(unit.builtin(Builtin::AbsolutePosY), 7);
+        assert_eq!(unit.builtin(Builtin::AbsolutePos), 7 * 24 + 21);
+    }
+
+    #[test]
+    fn units_are_in_unit_pos_order() {
+        let cube = HostContext::new(CubeDim::new_3d(4, 3, 2));
+
+        let unit_pos = cube
+            .units()
+            .map(|unit| unit.builtin(Builtin::UnitPos))
+            .collect::<Vec<_>>();
+
+        assert_eq!(unit_pos, (0..24).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn eval_restores_the_outer_context() {
+        let outer = HostContext::new(CubeDim::new_1d(4)).unit_pos(1, 0, 0);
+        let inner = outer.unit_pos(3, 0, 0);
+
+        let (inner_pos, outer_pos) = outer.eval(|| {
+            let inner_pos = inner.eval(|| HostContext::current(Builtin::UnitPos));
+            (inner_pos, HostContext::current(Builtin::UnitPos))
+        });
+
+        assert_eq!((inner_pos, outer_pos), (3, 1));
+    }
+
+    #[test]
+    fn cube_functions_evaluate_on_the_host() {
+        let input = Tensor::host_contiguous((0..6).map(|i| i as f32).collect(), vec![2, 3]);
+        let mut output = Tensor::host(vec![0.0; 6], vec![3, 2], vec![1, 3]);
+
+        for unit in HostContext::new(CubeDim::new_2d(2, 3)).units() {
+            unit.eval(|| transpose_unit(&input, &mut output));
+        }
+
+        assert_eq!(output.host_data(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn builtins_panic_outside_of_eval() {
+        HostContext::current(Builtin::UnitPos);
+    }
+}
@@ -0,0 +1,516 @@
+use crate as cubecl;
+use crate::prelude::*;
+use crate::tensor_line_size_uniform;
+use thiserror::Error;
+
+/// The work of a kernel, turned into a [launch configuration](LaunchConfig) within the limits of
+/// the grid of the device.
+///
+/// ```ignore
+/// let config = LaunchShape::elementwise(len)
+///     .line_size(line_size)
+///     .units_per_cube(256)
+///     .build::<R>()?;
+/// ```
+///
+/// The cubes of an elementwise launch past the limit of the x axis are folded into the y and z
+/// axes, and with [round_robin](Self::round_robin), the ones past the capacity of the whole grid
+/// are computed in turn by the cubes launched. Kernels find their logical cubes with
+/// [logical_cube_pos] and [next_logical_cube_pos] however the grid is folded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchShape {
+    work: Work,
+    line_size: u8,
+    units_per_cube: u32,
+    per_unit: usize,
+    round_robin: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Work {
+    Elementwise {
+        len: usize,
+    },
+    Tiles {
+        rows: usize,
+        cols: usize,
+        tile_m: usize,
+        tile_n: usize,
+        batches: usize,
+    },
+}
+
+/// The cube count and cube dimensions of a launch, see [LaunchShape].
+#[derive(Debug, Clone)]
+pub struct LaunchConfig {
+    /// The number of cubes along each axis, with any overflow of an axis folded into the next
+    /// ones.
+    pub cube_count: CubeCount,
+    /// The units of each cube.
+    pub cube_dim: CubeDim,
+    /// The number of tiles along the columns, for kernels launched on [tiles](LaunchShape::tiles_2d)
+    /// to recover their tile with [unfold_cube_pos_yz]. It's 1 for elementwise kernels, which only
+    /// need `ABSOLUTE_POS` however the grid is folded.
+    pub fold: u32,
+    /// The number of cubes of the work, which may be more than the cubes launched when they are
+    /// [distributed round-robin](LaunchShape::round_robin), or fewer when the grid is folded.
+    pub num_logical_cubes: u32,
+    /// The line size the elements are read with.
+    pub line_size: u8,
+}
+
+/// Error returned when a launch doesn't fit in the grid of the device.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LaunchShapeError {
+    /// There are more cubes than the grid can hold, even folded along all of its axes
+    #[error("{num_cubes} cubes can't fit in a grid of at most {max_cube_count:?} cubes")]
+    TooManyCubes {
+        /// The number of cubes needed.
+        num_cubes: u64,
+        /// The maximum cube count along each axis.
+        max_cube_count: (u32, u32, u32),
+    },
+    /// There are more units than can be indexed by a `u32` position
+    #[error("{num_units} units can't be indexed with 32-bit positions")]
+    TooManyUnits {
+        /// The number of units launched.
+        num_units: u64,
+    },
+}
+
+impl LaunchShape {
+    /// One unit for every [per_unit](Self::per_unit) lines of a buffer of `len` elements, with the
+    /// cubes of [units_per_cube](Self::units_per_cube) units in a 1D cube dim.
+    pub fn elementwise(len: usize) -> Self {
+        Self::new(Work::Elementwise { len })
+    }
+
+    /// One cube for every tile of `tile_m` rows and `tile_n` columns of a matrix, and of each
+    /// of its [batches](Self::batches).
+    ///
+    /// The tiles along the rows are on the x axis of the grid, and the ones along the columns
+    /// and the batches on the y and z axes. The cube dim has a unit for each row of a tile along
+    /// x, and one for every [per_unit](Self::per_unit) lines of the columns of the tile along y.
+    pub fn tiles_2d(rows: usize, cols: usize, tile_m: usize, tile_n: usize) -> Self {
+        Self::new(Work::Tiles {
+            rows,
+            cols,
+            tile_m,
+            tile_n,
+            batches: 1,
+        })
+    }
+
+    fn new(work: Work) -> Self {
+        Self {
+            work,
+            line_size: 1,
+            units_per_cube: 256,
+            per_unit: 1,
+            round_robin: false,
+        }
+    }
+
+    /// The line size the elements are read with, 1 by default.
+    pub fn line_size(mut self, line_size: u8) -> Self {
+        self.line_size = line_size;
+        self
+    }
+
+    /// The largest of the supported line sizes all the tensors, given as their shape and strides,
+    /// can be read with along their last axis, as found by [tensor_line_size_uniform].
+    ///
+    /// For kernels indexing all their tensors at the same positions, the tensors must then be
+    /// launched with the [line size](LaunchConfig::line_size) of the configuration.
+    pub fn uniform_line_size<'a>(
+        self,
+        supported_line_sizes: impl Iterator<Item = u8>,
+        tensors: impl IntoIterator<Item = (&'a [usize], &'a [usize])>,
+    ) -> Self {
+        self.line_size(tensor_line_size_uniform(supported_line_sizes, tensors))
+    }
+
+    /// The number of units of a cube of an [elementwise](Self::elementwise) launch, 256 by default.
+    pub fn units_per_cube(mut self, units_per_cube: u32) -> Self {
+        self.units_per_cube = units_per_cube;
+        self
+    }
+
+    /// The number of lines computed by each unit, 1 by default.
+    pub fn per_unit(mut self, per_unit: usize) -> Self {
+        self.per_unit = per_unit;
+        self
+    }
+
+    /// The number of matrices of a [tiled](Self::tiles_2d) launch, 1 by default.
+    pub fn batches(mut self, batches: usize) -> Self {
+        if let Work::Tiles { batches: old, .. } = &mut self.work {
+            *old = batches;
+        }
+        self
+    }
+
+    /// Launch at most the capacity of the grid for an [elementwise](Self::elementwise) launch, each
+    /// cube computing its logical cubes in turn, instead of failing when there are more cubes than
+    /// the grid can hold.
+    ///
+    /// The kernel must then iterate over its logical cubes with [logical_cube_pos] and
+    /// [next_logical_cube_pos], given the [number of logical
+    /// cubes](LaunchConfig::num_logical_cubes), since `CUBE_POS` and `ABSOLUTE_POS` only cover
+    /// the cubes launched.
+    pub fn round_robin(mut self) -> Self {
+        self.round_robin = true;
+        self
+    }
+
+    /// The launch configuration within the maximum cube count of the runtime.
+    pub fn build<R: Runtime>(&self) -> Result<LaunchConfig, LaunchShapeError> {
+        self.build_with_limits(R::max_cube_count())
+    }
+
+    /// The launch configuration within the given maximum cube count along each axis.
+    pub fn build_with_limits(
+        &self,
+        max_cube_count: (u32, u32, u32),
+    ) -> Result<LaunchConfig, LaunchShapeError> {
+        let lines_per_unit = self.line_size as u64 * self.per_unit as u64;
+        let (max_x, max_y, max_z) = max_cube_count;
+        let too_many_cubes = |num_cubes| LaunchShapeError::TooManyCubes {
+            num_cubes,
+            max_cube_count,
+        };
+
+        let (count, cube_dim, fold, num_logical_cubes) = match self.work {
+            Work::Elementwise { len } => {
+                let num_units = (len as u64).div_ceil(lines_per_unit);
+                let num_cubes = num_units.div_ceil(self.units_per_cube as u64).max(1);
+                // Past the capacity of the grid, each cube launched computes multiple logical
+                // cubes.
+                let capacity = max_x as u64 * max_y as u64 * max_z as u64;
+                let launched = match self.round_robin {
+                    true => num_cubes.min(capacity),
+                    false => num_cubes,
+                };
+
+                let x = launched.min(max_x as u64);
+                let (y, z) = fold_yz(launched.div_ceil(x), max_y, max_z)
+                    .ok_or_else(|| too_many_cubes(num_cubes))?;
+
+                let cube_dim = CubeDim::new_1d(self.units_per_cube);
+                let num_units = num_cubes * cube_dim.num_elems() as u64;
+                if num_units > u32::MAX as u64 + 1 {
+                    return Err(LaunchShapeError::TooManyUnits { num_units });
+                }
+
+                ((x, y, z), cube_dim, 1, num_cubes)
+            }
+            Work::Tiles {
+                rows,
+                cols,
+                tile_m,
+                tile_n,
+                batches,
+            } => {
+                let tiles_m = (rows as u64).div_ceil(tile_m as u64).max(1);
+                let tiles_n = (cols as u64).div_ceil(tile_n as u64).max(1);
+                let batches = (batches as u64).max(1);
+                let num_cubes = tiles_m * tiles_n * batches;
+                if tiles_m > max_x as u64 {
+                    return Err(too_many_cubes(num_cubes));
+                }
+
+                // The batches stay on z when they fit, so that kernels ignoring the fold work as
+                // long as the grid isn't folded.
+                let (y, z) = match tiles_n <= max_y as u64 && batches <= max_z as u64 {
+                    true => (tiles_n, batches),
+                    false => fold_yz(tiles_n * batches, max_y, max_z)
+                        .ok_or_else(|| too_many_cubes(num_cubes))?,
+                };
+                let cube_dim = CubeDim::new_2d(
+                    tile_m as u32,
+                    (tile_n as u64).div_ceil(lines_per_unit) as u32,
+                );
+
+                ((tiles_m, y, z), cube_dim, tiles_n as u32, num_cubes)
+            }
+        };
+
+        let (x, y, z) = count;
+        let num_units = x * y * z * cube_dim.num_elems() as u64;
+        if num_units > u32::MAX as u64 + 1 {
+            return Err(LaunchShapeError::TooManyUnits { num_units });
+        }
+
+        Ok(LaunchConfig {
+            cube_count: CubeCount::Static(x as u32, y as u32, z as u32),
+            cube_dim,
+            fold,
+            num_logical_cubes: num_logical_cubes as u32,
+            line_size: self.line_size,
+        })
+    }
+}
+
+/// Spread `num_cubes` along the y and z axes, with as few cubes as possible past `num_cubes`.
+fn fold_yz(num_cubes: u64, max_y: u32, max_z: u32) -> Option<(u64, u64)> {
+    let z = num_cubes.div_ceil(max_y as u64);
+    let y = num_cubes.div_ceil(z);
+
+    (z <= max_z as u64).then_some((y, z))
+}
+
+impl LaunchConfig {
+    /// The number of cubes launched, which may be a bit more than needed when the grid is folded.
+    pub fn num_cubes(&self) -> u64 {
+        match self.cube_count {
+            CubeCount::Static(x, y, z) => x as u64 * y as u64 * z as u64,
+            CubeCount::Dynamic(_) => unreachable!("The cube count of a launch shape is static"),
+        }
+    }
+
+    /// The [fold](Self::fold) as a scalar argument of the kernel.
+    pub fn fold_arg(&self) -> ScalarArg<u32> {
+        ScalarArg::new(self.fold)
+    }
+
+    /// The [number of logical cubes](Self::num_logical_cubes) as a scalar argument of the kernel.
+    pub fn num_logical_cubes_arg(&self) -> ScalarArg<u32> {
+        ScalarArg::new(self.num_logical_cubes)
+    }
+}
+
+/// The first logical cube of the cube, out of the `total` logical cubes of a launch, with the grid
+/// folded along its axes by a [LaunchShape] or any other linear folding of the cubes.
+///
+/// It's `total` for the cubes launched past the last logical cube, which have nothing to compute.
+#[cube]
+pub fn logical_cube_pos(total: u32) -> u32 {
+    Min::min(CUBE_POS, total)
+}
+
+/// The next logical cube of the cube after `pos`, when the logical cubes are
+/// [distributed round-robin](LaunchShape::round_robin), or `total` after its last one.
+///
+/// ```ignore
+/// let mut cube = logical_cube_pos(total);
+/// while cube < total {
+///     // Compute the logical cube `cube`.
+///     cube = next_logical_cube_pos(cube, total);
+/// }
+/// ```
+#[cube]
+pub fn next_logical_cube_pos(pos: u32, total: u32) -> u32 {
+    // Without an overflow past `u32::MAX`.
+    select(total - pos > CUBE_COUNT, pos + CUBE_COUNT, total)
+}
+
+/// The position of the tile of the cube along the columns and its batch, for a kernel launched
+/// on [tiles](LaunchShape::tiles_2d) with the [fold](LaunchConfig::fold) of its configuration.
+///
+/// The batch may be past the last one when the grid is folded, and must be checked by the kernel.
+#[cube]
+pub fn unfold_cube_pos_yz(fold: u32) -> (u32, u32) {
+    let linear = CUBE_POS_Y + CUBE_POS_Z * CUBE_COUNT_Y;
+    (linear % fold, linear / fold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WGPU_LIMITS: (u32, u32, u32) = (u16::MAX as u32, u16::MAX as u32, u16::MAX as u32);
+
+    fn cube_count(config: &LaunchConfig) -> (u32, u32, u32) {
+        match config.cube_count {
+            CubeCount::Static(x, y, z) => (x, y, z),
+            CubeCount::Dynamic(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn elementwise_fits_on_x() {
+        let config = LaunchShape::elementwise(1000)
+            .line_size(4)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (1, 1, 1));
+        assert_eq!(config.cube_dim, CubeDim::new_1d(256));
+    }
+
+    #[test]
+    fn elementwise_uniform_line_size_fits_all_tensors() {
+        // Rows of 8 can be read in lines of 4, even when padded to 12.
+        let tensors = [(&[3, 8][..], &[8, 1][..]), (&[3, 8][..], &[12, 1][..])];
+        let config = LaunchShape::elementwise(24)
+            .uniform_line_size([4, 2, 1].into_iter(), tensors)
+            .units_per_cube(4)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(config.line_size, 4);
+        assert_eq!(cube_count(&config), (2, 1, 1));
+
+        // Rows of 6 can only be read in lines of 2.
+        let tensors = [(&[3, 8][..], &[8, 1][..]), (&[3, 6][..], &[6, 1][..])];
+        let config = LaunchShape::elementwise(18)
+            .uniform_line_size([4, 2, 1].into_iter(), tensors)
+            .units_per_cube(4)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(config.line_size, 2);
+        assert_eq!(cube_count(&config), (3, 1, 1));
+    }
+
+    #[test]
+    fn elementwise_empty_launches_a_cube() {
+        let config = LaunchShape::elementwise(0)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (1, 1, 1));
+    }
+
+    #[test]
+    fn elementwise_exact_limit_of_x() {
+        let len = u16::MAX as usize * 256;
+        let config = LaunchShape::elementwise(len)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (u16::MAX as u32, 1, 1));
+    }
+
+    #[test]
+    fn elementwise_overflow_of_x_folds_into_y() {
+        let len = u16::MAX as usize * 256 + 1;
+        let config = LaunchShape::elementwise(len)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (u16::MAX as u32, 2, 1));
+    }
+
+    #[test]
+    fn elementwise_more_than_2_pow_31_elements() {
+        let len = (1usize << 31) + 3;
+        let config = LaunchShape::elementwise(len)
+            .line_size(4)
+            .per_unit(2)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        let units = config.num_cubes() * 256;
+        assert!(units * 8 >= len as u64);
+        assert!(config.num_cubes() - len.div_ceil(8 * 256) as u64 < u16::MAX as u64);
+        let (x, y, z) = cube_count(&config);
+        assert_eq!((x, z), (u16::MAX as u32, 1));
+        assert!(y <= u16::MAX as u32);
+    }
+
+    #[test]
+    fn elementwise_folds_into_z() {
+        let config = LaunchShape::elementwise(16 * 16 * 2 * 256)
+            .build_with_limits((16, 16, 16))
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (16, 16, 2));
+    }
+
+    #[test]
+    fn elementwise_too_many_units() {
+        let len = (1usize << 32) + 1;
+        let error = LaunchShape::elementwise(len)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap_err();
+
+        assert!(matches!(error, LaunchShapeError::TooManyUnits { .. }));
+    }
+
+    #[test]
+    fn elementwise_too_many_cubes() {
+        let error = LaunchShape::elementwise(1 << 20)
+            .units_per_cube(1)
+            .build_with_limits((16, 16, 16))
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            LaunchShapeError::TooManyCubes {
+                num_cubes: 1 << 20,
+                max_cube_count: (16, 16, 16)
+            }
+        );
+    }
+
+    #[test]
+    fn elementwise_round_robin_past_the_capacity() {
+        let config = LaunchShape::elementwise(16 * 16 * 16 * 256 + 1)
+            .round_robin()
+            .build_with_limits((16, 16, 16))
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (16, 16, 16));
+        assert_eq!(config.num_logical_cubes, 16 * 16 * 16 + 1);
+    }
+
+    #[test]
+    fn elementwise_round_robin_within_the_capacity() {
+        let config = LaunchShape::elementwise(17 * 256)
+            .round_robin()
+            .build_with_limits((16, 16, 16))
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (16, 2, 1));
+        assert_eq!(config.num_logical_cubes, 17);
+    }
+
+    #[test]
+    fn elementwise_round_robin_too_many_units() {
+        let error = LaunchShape::elementwise((1usize << 32) + 1)
+            .units_per_cube(1)
+            .round_robin()
+            .build_with_limits((16, 16, 16))
+            .unwrap_err();
+
+        assert!(matches!(error, LaunchShapeError::TooManyUnits { .. }));
+    }
+
+    #[test]
+    fn tiles_2d_with_batches() {
+        let config = LaunchShape::tiles_2d(100, 30, 32, 8)
+            .batches(3)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        assert_eq!(cube_count(&config), (4, 4, 3));
+        assert_eq!(config.cube_dim, CubeDim::new_2d(32, 8));
+        assert_eq!(config.fold, 4);
+    }
+
+    #[test]
+    fn tiles_2d_folds_batches() {
+        let batches = u16::MAX as usize + 1;
+        let config = LaunchShape::tiles_2d(32, 8 * 10, 32, 8)
+            .batches(batches)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap();
+
+        let (x, y, z) = cube_count(&config);
+        assert_eq!(x, 1);
+        assert!(y <= u16::MAX as u32 && z <= u16::MAX as u32);
+        assert!(y as u64 * z as u64 >= 10 * batches as u64);
+        assert_eq!(config.fold, 10);
+    }
+
+    #[test]
+    fn tiles_2d_too_many_rows() {
+        let rows = (u16::MAX as usize + 1) * 32;
+        let error = LaunchShape::tiles_2d(rows, 8, 32, 8)
+            .build_with_limits(WGPU_LIMITS)
+            .unwrap_err();
+
+        assert!(matches!(error, LaunchShapeError::TooManyCubes { .. }));
+    }
+}
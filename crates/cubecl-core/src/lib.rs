@@ -32,7 +32,7 @@ pub use runtime::*;
 
 pub use cubecl_macros::*;
 pub use cubecl_runtime::benchmark;
-pub use cubecl_runtime::memory_management::MemoryUsage;
+pub use cubecl_runtime::memory_management::{MemoryScopeUsage, MemoryUsage};
 
 use frontend::LaunchArg;
 
@@ -45,6 +45,12 @@ pub use prelude::CubeDim;
 mod id;
 pub use id::*;
 
+mod launch_shape;
+pub use launch_shape::*;
+
+mod host;
+pub use host::*;
+
 /// Calculate the number of cubes required to execute an operation where one cube unit is
 /// assigned to one element.
 pub fn calculate_cube_count_elemwise(num_elems: usize, cube_dim: CubeDim) -> CubeCount {
@@ -171,6 +177,29 @@ pub fn try_tensor_line_size_perpendicular(
         .ok_or(LineSizeError::NoValidLineSize)
 }
 
+/// Find the maximum line size from the supported line sizes that all the tensors can be read with
+/// along their last axis, for kernels indexing all their tensors at the same positions, or return
+/// 1 if vectorization is impossible for one of them.
+///
+/// Each tensor is given as its shape and strides, and checked like
+/// [tensor_line_size_parallel].
+pub fn tensor_line_size_uniform<'a>(
+    supported_line_sizes: impl Iterator<Item = u8>,
+    tensors: impl IntoIterator<Item = (&'a [usize], &'a [usize])>,
+) -> u8 {
+    let tensors = tensors.into_iter().collect::<Vec<_>>();
+
+    supported_line_sizes
+        .filter(|&line_size| {
+            tensors.iter().all(|(shape, strides)| {
+                let axis = shape.len().saturating_sub(1);
+                try_tensor_line_size_parallel([line_size].into_iter(), shape, strides, axis).is_ok()
+            })
+        })
+        .max()
+        .unwrap_or(1)
+}
+
 /// Runtime arguments to launch a kernel.
 pub type RuntimeArg<'a, T, R> = <T as LaunchArg>::RuntimeArg<'a, R>;
 
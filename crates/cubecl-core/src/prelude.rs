@@ -2,7 +2,8 @@ pub use crate::{CubeLaunch, CubeType, RuntimeArg, cube};
 
 pub use crate::codegen::{KernelExpansion, KernelIntegrator, KernelSettings};
 pub use crate::compute::{
-    CompiledKernel, CubeKernel, KernelBuilder, KernelDefinition, KernelLauncher, KernelTask,
+    CompiledKernel, CubeKernel, IndexReport, IndexViolation, KernelBuilder, KernelDefinition,
+    KernelLauncher, KernelTask, LaunchError, LineSizeMismatch,
 };
 pub use crate::frontend::cmma;
 /// Elements
@@ -22,6 +23,8 @@ pub use cubecl_runtime::kernel::KernelMetadata;
 pub use cubecl_runtime::server::CubeCount;
 
 pub use crate::frontend::*;
-pub use crate::{comment, comptime, comptime_type, derive_cube_comptime, terminate};
+pub use crate::{
+    comment, comptime, comptime_assert, comptime_type, derive_cube_comptime, terminate,
+};
 pub use cubecl_common::{CubeDim, ExecutionMode, flex32, tf32};
 pub use cubecl_ir::Scope;
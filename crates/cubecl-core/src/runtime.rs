@@ -1,6 +1,6 @@
 use crate::codegen::Compiler;
 use crate::compute::CubeTask;
-use cubecl_common::device::Device;
+use cubecl_common::device::{Device, DeviceId};
 use cubecl_ir::{StorageType, TargetProperties};
 use cubecl_runtime::{channel::ComputeChannel, client::ComputeClient, server::ComputeServer};
 
@@ -23,6 +23,22 @@ pub trait Runtime: Send + Sync + 'static + core::fmt::Debug {
     /// Retrieve the compute client from the runtime device.
     fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel>;
 
+    /// Enumerate the devices of the given type, with the client of each device.
+    ///
+    /// The properties of a device are available with [ComputeClient::properties], and handles
+    /// can be moved between devices with [ComputeClient::copy_to].
+    fn enumerate_devices(
+        type_id: u16,
+    ) -> Vec<(Self::Device, ComputeClient<Self::Server, Self::Channel>)> {
+        (0..Self::Device::device_count(type_id) as u32)
+            .map(|index| {
+                let device = Self::Device::from_id(DeviceId::new(type_id, index));
+                let client = Self::client(&device);
+                (device, client)
+            })
+            .collect()
+    }
+
     /// The runtime name on the given device.
     fn name(client: &ComputeClient<Self::Server, Self::Channel>) -> &'static str;
 
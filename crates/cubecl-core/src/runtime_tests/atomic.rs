@@ -108,6 +108,46 @@ pub fn test_kernel_atomic_max<R: Runtime, F: Numeric + CubeElement>(
     assert_eq!(actual[0], F::from_int(12));
 }
 
+#[cube(launch)]
+pub fn kernel_atomic_add_u64(output: &mut Array<Atomic<u64>>) {
+    Atomic::add(&output[0], u64::from_int(1 << 26));
+}
+
+pub fn test_kernel_atomic_add_u64<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if !supports_feature::<R, u64>(&client, TypeUsage::AtomicAdd) {
+        println!("atomic<u64> Add not supported - skipped");
+        return;
+    }
+    let handle = client.create(u64::as_bytes(&[12]));
+
+    // Each of the 1024 units adds 2^26, so the counter goes past `u32::MAX`.
+    kernel_atomic_add_u64::launch::<R>(
+        &client,
+        CubeCount::Static(4, 1, 1),
+        CubeDim::new_1d(256),
+        unsafe { ArrayArg::from_raw_parts::<u64>(&handle, 1, 1) },
+    );
+
+    let actual = client.read_one(handle);
+    let actual = u64::from_bytes(&actual);
+
+    assert_eq!(actual[0], 12 + (1 << 36));
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_atomic_u64 {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_atomic_add_u64() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::atomic::test_kernel_atomic_add_u64::<TestRuntime>(client);
+        }
+    };
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_atomic_int {
@@ -92,7 +92,7 @@ fn two_loads<F: Float>(
 }
 
 #[cube(launch)]
-fn two_independent_loads<F: Float>(
+pub fn two_independent_loads<F: Float>(
     lhs: &Tensor<Line<F>>,
     rhs: &Tensor<Line<F>>,
     output: &mut Tensor<Line<F>>,
@@ -213,6 +213,121 @@ pub fn test_memcpy_two_loads<R: Runtime, F: Float + CubeElement>(
     assert_eq!(actual, expected);
 }
 
+/// Creates a barrier in each iteration, so every iteration uses the same barrier of the cube.
+#[cube(launch)]
+pub fn memcpy_in_loop<F: Float>(
+    input: &Array<Line<F>>,
+    output: &mut Array<Line<F>>,
+    #[comptime] num_iterations: u32,
+) {
+    let mut smem = SharedMemory::<F>::new_lined(2u32, 1u32);
+
+    for i in 0..num_iterations {
+        let barrier = Barrier::new(BarrierLevel::cube_manual(0u32));
+        sync_cube();
+
+        let position = i * 2 + UNIT_POS_X;
+        barrier.memcpy_async(
+            &input.slice(position, position + 1),
+            &mut smem.slice_mut(UNIT_POS_X, UNIT_POS_X + 1),
+        );
+        barrier.arrive_and_wait();
+
+        output[position] = smem[UNIT_POS_X] + smem[UNIT_POS_X];
+    }
+}
+
+/// Creates the second barrier once the first one is no longer used, so both are the same barrier
+/// of the cube.
+#[cube(launch)]
+pub fn memcpy_sequential_barriers<F: Float>(
+    lhs: &Array<Line<F>>,
+    rhs: &Array<Line<F>>,
+    output: &mut Array<Line<F>>,
+) {
+    let mut smem = SharedMemory::<F>::new_lined(2u32, 1u32);
+
+    let barrier_0 = Barrier::new(BarrierLevel::cube_manual(0u32));
+    sync_cube();
+    barrier_0.memcpy_async(
+        &lhs.slice(UNIT_POS_X, UNIT_POS_X + 1),
+        &mut smem.slice_mut(UNIT_POS_X, UNIT_POS_X + 1),
+    );
+    barrier_0.arrive_and_wait();
+    let value = smem[UNIT_POS_X];
+
+    let barrier_1 = Barrier::new(BarrierLevel::cube_manual(0u32));
+    sync_cube();
+    barrier_1.memcpy_async(
+        &rhs.slice(UNIT_POS_X, UNIT_POS_X + 1),
+        &mut smem.slice_mut(UNIT_POS_X, UNIT_POS_X + 1),
+    );
+    barrier_1.arrive_and_wait();
+
+    output[UNIT_POS_X] = value + smem[UNIT_POS_X];
+}
+
+pub fn test_memcpy_in_loop<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    if !client.properties().supports_type(SemanticType::Barrier) {
+        // We can't execute the test, skip.
+        return;
+    }
+
+    let num_iterations = 4;
+    let input_data: Vec<F> = (0..2 * num_iterations).map(|i| F::new(i as f32)).collect();
+
+    let input = client.create(F::as_bytes(&input_data));
+    let output = client.empty(input_data.len() * core::mem::size_of::<F>());
+
+    unsafe {
+        memcpy_in_loop::launch::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(2, 1, 1),
+            ArrayArg::from_raw_parts::<F>(&input, input_data.len(), 1),
+            ArrayArg::from_raw_parts::<F>(&output, input_data.len(), 1),
+            num_iterations as u32,
+        )
+    };
+
+    let actual = client.read_one(output);
+    let actual = F::from_bytes(&actual);
+    let expected: Vec<F> = input_data.iter().map(|it| *it + *it).collect();
+
+    assert_eq!(actual, expected);
+}
+
+pub fn test_memcpy_sequential_barriers<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    if !client.properties().supports_type(SemanticType::Barrier) {
+        // We can't execute the test, skip.
+        return;
+    }
+
+    let lhs = client.create(as_bytes![F: 1., 2.]);
+    let rhs = client.create(as_bytes![F: 10., 20.]);
+    let output = client.empty(2 * core::mem::size_of::<F>());
+
+    unsafe {
+        memcpy_sequential_barriers::launch::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new(2, 1, 1),
+            ArrayArg::from_raw_parts::<F>(&lhs, 2, 1),
+            ArrayArg::from_raw_parts::<F>(&rhs, 2, 1),
+            ArrayArg::from_raw_parts::<F>(&output, 2, 1),
+        )
+    };
+
+    let actual = client.read_one(output);
+    let actual = F::from_bytes(&actual);
+
+    assert_eq!(actual, [F::new(11.0), F::new(22.0)]);
+}
+
 fn dot<F: Float>(vec1: &[F], vec2: &[F]) -> F {
     let mut sum = F::from_int(0);
     for i in 0..vec1.len() {
@@ -256,5 +371,22 @@ macro_rules! testgen_barrier {
                 true, client,
             );
         }
+
+        #[test]
+        fn test_barrier_memcpy_async_in_loop() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::barrier::test_memcpy_in_loop::<TestRuntime, FloatType>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_barrier_memcpy_async_sequential_barriers() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::barrier::test_memcpy_sequential_barriers::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
     };
 }
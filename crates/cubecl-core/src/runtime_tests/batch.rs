@@ -0,0 +1,173 @@
+use crate as cubecl;
+use crate::prelude::*;
+use crate::runtime_tests::stream::kernel_add_one;
+use crate::server::Handle;
+
+#[cube(launch)]
+pub fn kernel_add_scalar(input: &Array<f32>, output: &mut Array<f32>, value: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + value;
+    }
+}
+
+fn add_one<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &Handle,
+    output: &Handle,
+    len: usize,
+) {
+    unsafe {
+        kernel_add_one::launch::<R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(len as u32),
+            ArrayArg::from_raw_parts::<f32>(input, len, 1),
+            ArrayArg::from_raw_parts::<f32>(output, len, 1),
+        )
+    };
+}
+
+fn add_scalar<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &Handle,
+    output: &Handle,
+    value: f32,
+) {
+    unsafe {
+        kernel_add_scalar::launch::<R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            ArrayArg::from_raw_parts::<f32>(input, 4, 1),
+            ArrayArg::from_raw_parts::<f32>(output, 4, 1),
+            ScalarArg::new(value),
+        )
+    };
+}
+
+pub fn test_batch_executes_in_order<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let data = [0.0, 1.0, 2.0, 3.0];
+    let input = client.create(f32::as_bytes(&data));
+
+    let output = client.batch(|batch| {
+        let mut current = input.clone();
+
+        for _ in 0..16 {
+            // Allocated inside the batch, each kernel reading the output of the previous one.
+            let next = batch.empty(data.len() * size_of::<f32>());
+            add_one::<R>(batch, &current, &next, data.len());
+            current = next;
+        }
+
+        current
+    });
+
+    let actual = client.read_one(output);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, [16.0, 17.0, 18.0, 19.0]);
+}
+
+pub fn test_batch_replay<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let data = [0.0, 1.0, 2.0, 3.0];
+    let lhs = client.create(f32::as_bytes(&data));
+    let rhs = client.empty(data.len() * size_of::<f32>());
+
+    let batch = match client.batch_reusable(|batch| {
+        add_one::<R>(batch, &lhs, &rhs, data.len());
+        add_one::<R>(batch, &rhs, &lhs, data.len());
+    }) {
+        Ok(batch) => batch,
+        // The runtime can't record batches.
+        Err(_) => return,
+    };
+
+    // Recording doesn't execute the kernels.
+    let actual = client.read_one(lhs.clone());
+    assert_eq!(f32::from_bytes(&actual), data);
+
+    for _ in 0..3 {
+        batch.replay();
+    }
+
+    let actual = client.read_one(lhs.clone());
+    assert_eq!(f32::from_bytes(&actual), [6.0, 7.0, 8.0, 9.0]);
+
+    // The batch keeps its bindings alive, so it still works once the handles are dropped.
+    drop(rhs);
+    batch.replay();
+    let actual = client.read_one(lhs);
+    assert_eq!(f32::from_bytes(&actual), [8.0, 9.0, 10.0, 11.0]);
+}
+
+pub fn test_batch_update<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let input = client.create(f32::as_bytes(&[0.0, 1.0, 2.0, 3.0]));
+    let other = client.create(f32::as_bytes(&[100.0, 101.0, 102.0, 103.0]));
+    let output = client.empty(4 * size_of::<f32>());
+    let read = |handle: &Handle| f32::from_bytes(&client.read_one(handle.clone())).to_vec();
+
+    let batch = match client.batch_reusable(|batch| add_scalar::<R>(batch, &input, &output, 1.0)) {
+        Ok(batch) => batch,
+        // The runtime can't record batches.
+        Err(_) => return,
+    };
+    batch.replay();
+    assert_eq!(read(&output), [1.0, 2.0, 3.0, 4.0]);
+
+    // Only the scalar changes.
+    batch
+        .update(|batch| add_scalar::<R>(batch, &input, &output, 10.0))
+        .unwrap();
+    for _ in 0..3 {
+        batch.replay();
+        assert_eq!(read(&output), [10.0, 11.0, 12.0, 13.0]);
+    }
+
+    // Only the bindings change.
+    batch
+        .update(|batch| add_scalar::<R>(batch, &other, &output, 10.0))
+        .unwrap();
+    for _ in 0..3 {
+        batch.replay();
+        assert_eq!(read(&output), [110.0, 111.0, 112.0, 113.0]);
+    }
+
+    // Other kernels replace the recorded ones.
+    let last = client.empty(4 * size_of::<f32>());
+    batch
+        .update(|batch| {
+            add_scalar::<R>(batch, &other, &output, 1.0);
+            add_scalar::<R>(batch, &output, &last, 1.0);
+        })
+        .unwrap();
+    for _ in 0..3 {
+        batch.replay();
+        assert_eq!(read(&last), [102.0, 103.0, 104.0, 105.0]);
+    }
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_batch {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_batch_executes_in_order() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::batch::test_batch_executes_in_order::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_batch_replay() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::batch::test_batch_replay::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_batch_update() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::batch::test_batch_update::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,107 @@
+use crate as cubecl;
+use crate::prelude::*;
+
+#[cube(launch, create_dummy_kernel)]
+pub fn kernel_lines_per_tile(
+    output: &mut Array<u32>,
+    #[comptime] tile_size: u32,
+    #[comptime] line_size: u32,
+) {
+    comptime_assert!(
+        tile_size % line_size == 0,
+        "The line size {} should divide the tile size {}",
+        line_size,
+        tile_size
+    );
+    comptime_assert!(line_size <= 16);
+
+    if UNIT_POS == 0 {
+        output[0] = comptime!(tile_size / line_size);
+    }
+}
+
+fn define_lines_per_tile<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    tile_size: u32,
+    line_size: u32,
+) -> Result<(), String> {
+    let handle = client.empty(size_of::<u32>());
+    let kernel = unsafe {
+        kernel_lines_per_tile::create_dummy_kernel::<R>(
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            ArrayArg::from_raw_parts::<u32>(&handle, 1, 1),
+            tile_size,
+            line_size,
+        )
+    };
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        kernel.define();
+    }))
+    .map_err(|err| match err.downcast::<String>() {
+        Ok(message) => *message,
+        Err(_) => "The expansion panicked without a message".to_string(),
+    })
+}
+
+pub fn test_comptime_assert_holds<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let output = client.empty(size_of::<u32>());
+
+    kernel_lines_per_tile::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(1),
+        unsafe { ArrayArg::from_raw_parts::<u32>(&output, 1, 1) },
+        8,
+        4,
+    );
+
+    let actual = client.read_one(output);
+    assert_eq!(u32::from_bytes(&actual), &[2]);
+}
+
+pub fn test_comptime_assert_fails_expansion<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    assert_eq!(define_lines_per_tile::<R>(&client, 8, 4), Ok(()));
+    assert_eq!(
+        define_lines_per_tile::<R>(&client, 6, 4),
+        Err(
+            "Comptime assertion failed in kernel `kernel_lines_per_tile`: \
+             The line size 4 should divide the tile size 6"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        define_lines_per_tile::<R>(&client, 64, 32),
+        Err(
+            "Comptime assertion failed in kernel `kernel_lines_per_tile`: line_size <= 16"
+                .to_string()
+        )
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_comptime_assert {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_comptime_assert_holds() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::comptime_assert::test_comptime_assert_holds::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_comptime_assert_fails_expansion() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::comptime_assert::test_comptime_assert_fails_expansion::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,109 @@
+use crate as cubecl;
+use crate::prelude::*;
+
+const TAG_INPUT: u32 = 0;
+const TAG_POS: u32 = 1;
+
+#[cube(launch)]
+pub fn kernel_debug_record(input: &Array<f32>, output: &mut Array<f32>, sink: &mut DebugSink) {
+    let value = input[UNIT_POS] * 2.0;
+    debug_record!(sink, TAG_INPUT, input[UNIT_POS]);
+    debug_record!(sink, TAG_POS, UNIT_POS);
+    output[UNIT_POS] = value;
+}
+
+fn launch_debug_record<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    sink: &DebugSinkHandle,
+) -> Vec<f32> {
+    let input = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(4 * size_of::<f32>());
+
+    kernel_debug_record::launch::<R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(4),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts::<f32>(&output, 4, 1) },
+        sink.as_arg(),
+    );
+
+    f32::from_bytes(&client.read_one(output)).to_vec()
+}
+
+pub fn test_debug_record<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let sink = DebugSinkHandle::new::<R>(&client, 8);
+
+    let output = launch_debug_record::<R>(&client, &sink);
+    let records = sink.read::<R>(&client);
+
+    assert_eq!(output, [2.0, 4.0, 6.0, 8.0]);
+    assert!(!records.overflowed);
+    assert_eq!(records.records.len(), 8);
+
+    let mut inputs = records
+        .tagged(TAG_INPUT)
+        .map(|record| (record.unit_pos, record.cube_pos, record.value))
+        .collect::<Vec<_>>();
+    inputs.sort_by_key(|(unit_pos, ..)| *unit_pos);
+    assert_eq!(
+        inputs,
+        [1.0, 2.0, 3.0, 4.0]
+            .into_iter()
+            .enumerate()
+            .map(|(unit, value)| (unit as u32, 0, DebugValue::F32(value)))
+            .collect::<Vec<_>>()
+    );
+
+    for record in records.tagged(TAG_POS) {
+        assert_eq!(record.value, DebugValue::U32(record.unit_pos));
+    }
+}
+
+pub fn test_debug_sink_overflow<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let sink = DebugSinkHandle::new::<R>(&client, 3);
+
+    let output = launch_debug_record::<R>(&client, &sink);
+    let records = sink.read::<R>(&client);
+
+    assert_eq!(output, [2.0, 4.0, 6.0, 8.0]);
+    assert!(records.overflowed);
+    assert_eq!(records.records.len(), 3);
+}
+
+pub fn test_debug_sink_disabled<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let sink = DebugSinkHandle::disabled::<R>(&client);
+
+    let output = launch_debug_record::<R>(&client, &sink);
+    let records = sink.read::<R>(&client);
+
+    assert_eq!(output, [2.0, 4.0, 6.0, 8.0]);
+    assert!(!records.overflowed);
+    assert!(records.records.is_empty());
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_debug_sink {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_debug_record() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::debug_sink::test_debug_record::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_debug_sink_overflow() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::debug_sink::test_debug_sink_overflow::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_debug_sink_disabled() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::debug_sink::test_debug_sink_disabled::<TestRuntime>(client);
+        }
+    };
+}
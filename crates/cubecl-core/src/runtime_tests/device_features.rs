@@ -0,0 +1,84 @@
+use crate::prelude::*;
+use cubecl_ir::{ElemType, FloatKind, SemanticType};
+use cubecl_runtime::{Plane, TypeUsage};
+
+/// The features of the device are within the bounds any device has.
+pub fn test_device_features<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let features = client.device_features();
+
+    assert!(features.plane_size_min >= 1, "{features:?}");
+    assert!(
+        features.plane_size_min <= features.plane_size_max,
+        "{features:?}"
+    );
+    assert!(features.plane_size_min.is_power_of_two(), "{features:?}");
+    assert!(features.plane_size_max.is_power_of_two(), "{features:?}");
+    assert!(features.max_shared_memory_size > 0, "{features:?}");
+
+    let max_cube_dim = features.max_cube_dim;
+    for dim in [max_cube_dim.x, max_cube_dim.y, max_cube_dim.z] {
+        assert!(dim >= 1, "{features:?}");
+        assert!(dim <= features.max_units_per_cube, "{features:?}");
+    }
+
+    for tile in features.accelerated_tiles.iter() {
+        assert!(tile.m > 0 && tile.n > 0 && tile.k > 0, "{tile:?}");
+        assert!(features.supports_accelerated_tile(tile));
+        assert!(features.accelerates(tile.a_type, tile.b_type));
+    }
+}
+
+/// The features are the ones registered in the properties of the device.
+pub fn test_device_features_match_properties<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let properties = client.properties();
+    let features = client.device_features();
+    let arithmetic = |elem: ElemType| {
+        properties
+            .type_usage(elem.into())
+            .contains(TypeUsage::Arithmetic)
+    };
+
+    assert_eq!(features.accelerated_tiles, properties.features.cmma);
+    assert_eq!(
+        features.plane_ops,
+        properties.features.plane.contains(Plane::Ops)
+    );
+    assert_eq!(
+        features.barrier,
+        properties.supports_type(SemanticType::Barrier)
+    );
+    assert_eq!(features.f16, arithmetic(ElemType::Float(FloatKind::F16)));
+    assert_eq!(features.bf16, arithmetic(ElemType::Float(FloatKind::BF16)));
+    assert_eq!(features.f64, arithmetic(ElemType::Float(FloatKind::F64)));
+    assert_eq!(features.plane_size_max, properties.hardware.plane_size_max);
+    assert_eq!(
+        features.max_shared_memory_size,
+        properties.hardware.max_shared_memory_size
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_device_features {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_device_features() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::device_features::test_device_features::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_device_features_match_properties() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::device_features::test_device_features_match_properties::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
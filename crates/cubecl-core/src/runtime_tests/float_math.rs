@@ -0,0 +1,369 @@
+use std::fmt::Display;
+
+use crate::{self as cubecl};
+
+use cubecl::prelude::*;
+
+macro_rules! kernel_float_math {
+    ($kernel:ident, $func:expr) => {
+        #[cube(launch_unchecked)]
+        pub fn $kernel<F: Float>(input: &Array<Line<F>>, output: &mut Array<Line<F>>) {
+            if ABSOLUTE_POS < input.len() {
+                output[ABSOLUTE_POS] = $func(input[ABSOLUTE_POS]);
+            }
+        }
+    };
+}
+
+kernel_float_math!(kernel_erf, Erf::erf);
+kernel_float_math!(kernel_erfc, Erfc::erfc);
+kernel_float_math!(kernel_tanh, Tanh::tanh);
+kernel_float_math!(kernel_exp2, Exp2::exp2);
+kernel_float_math!(kernel_log2, Log2::log2);
+kernel_float_math!(kernel_rsqrt, Rsqrt::rsqrt);
+kernel_float_math!(kernel_recip, Recip::recip);
+
+#[cube(launch_unchecked)]
+pub fn kernel_fma<F: Float>(
+    a: &Array<Line<F>>,
+    b: &Array<Line<F>>,
+    c: &Array<Line<F>>,
+    output: &mut Array<Line<F>>,
+) {
+    if ABSOLUTE_POS < a.len() {
+        output[ABSOLUTE_POS] = Fma::fma(a[ABSOLUTE_POS], b[ABSOLUTE_POS], c[ABSOLUTE_POS]);
+    }
+}
+
+/// The error allowed for a result, as `ulps * epsilon * max(|expected|, scale) + absolute`.
+#[derive(Clone, Copy, Debug)]
+struct Bound {
+    ulps: f64,
+    /// The magnitude under which the error is absolute rather than relative.
+    scale: f64,
+    /// The absolute error of a polyfill.
+    absolute: f64,
+}
+
+impl Bound {
+    fn relative(ulps: f64) -> Self {
+        Self {
+            ulps,
+            scale: 0.0,
+            absolute: 0.0,
+        }
+    }
+
+    fn absolute(ulps: f64, absolute: f64) -> Self {
+        Self {
+            ulps,
+            scale: 1.0,
+            absolute,
+        }
+    }
+
+    fn error(&self, expected: f64, epsilon: f64) -> f64 {
+        self.ulps * epsilon * f64::max(expected.abs(), self.scale) + self.absolute
+    }
+}
+
+/// Inputs evenly spread in `[start, end]`, followed by the edge cases of the float type.
+fn inputs<F: Float + num_traits::Float>(start: f64, end: f64, edges: &[F]) -> Vec<F> {
+    let steps = 60;
+    let mut inputs = (0..steps)
+        .map(|i| start + (end - start) * i as f64 / (steps - 1) as f64)
+        .map(|x| <F as num_traits::NumCast>::from(x).unwrap())
+        .collect::<Vec<_>>();
+    inputs.extend_from_slice(edges);
+    // Pad to a multiple of the largest line size.
+    while inputs.len() % 4 != 0 {
+        inputs.push(<F as num_traits::One>::one());
+    }
+    inputs
+}
+
+/// `±0`, `±inf`, NaN, and the largest and smallest normal magnitudes.
+fn all_edges<F: Float + num_traits::Float>() -> Vec<F> {
+    vec![
+        <F as num_traits::Float>::zero(),
+        <F as num_traits::Float>::neg_zero(),
+        <F as num_traits::Float>::infinity(),
+        <F as num_traits::Float>::neg_infinity(),
+        <F as num_traits::Float>::nan(),
+        <F as num_traits::Float>::max_value(),
+        -<F as num_traits::Float>::max_value(),
+        <F as num_traits::Float>::min_positive_value(),
+        -<F as num_traits::Float>::min_positive_value(),
+    ]
+}
+
+/// The edges of the functions only defined for positive inputs.
+fn positive_edges<F: Float + num_traits::Float>() -> Vec<F> {
+    vec![
+        <F as num_traits::Float>::infinity(),
+        <F as num_traits::Float>::nan(),
+        <F as num_traits::Float>::max_value(),
+        <F as num_traits::Float>::min_positive_value(),
+    ]
+}
+
+fn line_sizes<R: Runtime, F: Float>() -> impl Iterator<Item = u8> {
+    let supported = R::line_size_type(&F::as_type_native_unchecked()).collect::<Vec<_>>();
+    [1, 4]
+        .into_iter()
+        .filter(move |line_size| supported.contains(line_size))
+}
+
+/// Check the results of a function against its reference computed in `f64`.
+///
+/// The results of the inputs whose reference is NaN or overflows must be NaN or the infinity of the
+/// same sign, the ones of `±0` must keep its sign when the reference does, and the other ones must
+/// be within the bound, with an allowance for the flush of subnormal results to zero.
+fn assert_float_math<F: Float + num_traits::Float + CubeElement + Display>(
+    name: &str,
+    inputs: &[F],
+    actual: &[F],
+    reference: impl Fn(f64) -> f64,
+    bound: impl Fn(f64) -> Bound,
+) {
+    let epsilon = <F as num_traits::Float>::epsilon().to_f64().unwrap();
+    let max = <F as num_traits::Float>::max_value().to_f64().unwrap();
+    let min_positive = <F as num_traits::Float>::min_positive_value()
+        .to_f64()
+        .unwrap();
+
+    for (i, (input, actual)) in inputs.iter().zip(actual.iter()).enumerate() {
+        let x = input.to_f64().unwrap();
+        let value = actual.to_f64().unwrap();
+        let mut expected = reference(x);
+        if expected.abs() > max {
+            expected = f64::INFINITY.copysign(expected);
+        }
+
+        if expected.is_nan() {
+            assert!(
+                value.is_nan(),
+                "{name}({x}) at {i}: expected NaN, got {value}"
+            );
+        } else if expected.is_infinite() {
+            assert_eq!(value, expected, "{name}({x}) at {i}");
+        } else if x == 0.0 && expected == 0.0 {
+            assert!(
+                value == 0.0 && value.is_sign_negative() == expected.is_sign_negative(),
+                "{name}({x:?}) at {i}: expected {expected:?}, got {value:?}"
+            );
+        } else {
+            let error = (value - expected).abs();
+            let allowed = bound(x).error(expected, epsilon) + min_positive;
+            assert!(
+                error <= allowed,
+                "{name}({x}) at {i}: expected {expected}, got {value}, error {error} over {allowed} ({:?})",
+                bound(x)
+            );
+        }
+    }
+}
+
+macro_rules! test_float_math {
+    ($test_name:ident, $kernel:ident, $inputs:expr, $reference:expr, $bound:expr) => {
+        pub fn $test_name<R: Runtime, F: Float + num_traits::Float + CubeElement + Display>(
+            client: ComputeClient<R::Server, R::Channel>,
+        ) {
+            let inputs: Vec<F> = $inputs;
+
+            for line_size in line_sizes::<R, F>() {
+                let input_handle = client.create(F::as_bytes(&inputs));
+                let output_handle = client.empty(inputs.len() * size_of::<F>());
+
+                unsafe {
+                    $kernel::launch_unchecked::<F, R>(
+                        &client,
+                        CubeCount::Static(1, 1, 1),
+                        CubeDim::new_1d(inputs.len() as u32 / line_size as u32),
+                        ArrayArg::from_raw_parts::<F>(&input_handle, inputs.len(), line_size),
+                        ArrayArg::from_raw_parts::<F>(&output_handle, inputs.len(), line_size),
+                    )
+                };
+
+                let actual = client.read_one(output_handle);
+                let actual = F::from_bytes(&actual);
+                assert_float_math(stringify!($kernel), &inputs, actual, $reference, $bound);
+            }
+        }
+    };
+}
+
+test_float_math!(
+    test_erf,
+    kernel_erf,
+    inputs(-4.0, 4.0, &all_edges::<F>()),
+    erf_reference,
+    |_| Bound::absolute(4.0, 2e-7)
+);
+test_float_math!(
+    test_erf_small,
+    kernel_erf,
+    inputs(-0.24, 0.24, &all_edges::<F>()),
+    erf_reference,
+    |_| Bound::relative(4.0)
+);
+test_float_math!(
+    test_erfc,
+    kernel_erfc,
+    inputs(-4.0, 4.0, &all_edges::<F>()),
+    erfc_reference,
+    |_| Bound::absolute(4.0, 2e-7)
+);
+test_float_math!(
+    test_tanh,
+    kernel_tanh,
+    inputs(-10.0, 10.0, &all_edges::<F>()),
+    f64::tanh,
+    |_| Bound::absolute(8.0, 0.0)
+);
+// The bound of WGSL and SPIR-V grows with the input.
+test_float_math!(
+    test_exp2,
+    kernel_exp2,
+    inputs(-20.0, 20.0, &all_edges::<F>()),
+    f64::exp2,
+    |x: f64| Bound::relative(4.0 + 2.0 * x.abs().min(128.0))
+);
+// The bound of WGSL and SPIR-V is absolute in `[0.5, 2]`.
+test_float_math!(
+    test_log2,
+    kernel_log2,
+    inputs(0.01, 100.0, &positive_edges::<F>()),
+    f64::log2,
+    |_| Bound {
+        ulps: 4.0,
+        scale: 0.0,
+        absolute: 2f64.powi(-21),
+    }
+);
+test_float_math!(
+    test_rsqrt,
+    kernel_rsqrt,
+    inputs(0.01, 100.0, &positive_edges::<F>()),
+    |x: f64| 1.0 / x.sqrt(),
+    |_| Bound::relative(3.0)
+);
+test_float_math!(
+    test_recip,
+    kernel_recip,
+    inputs(-100.0, 100.0, &all_edges::<F>()),
+    f64::recip,
+    |_| Bound::relative(3.0)
+);
+
+/// Fused or not, the error of `a * b + c` is within an ULP of each operation.
+pub fn test_fma<R: Runtime, F: Float + num_traits::Float + CubeElement + Display>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let a: Vec<F> = inputs(-10.0, 10.0, &[]);
+    let b: Vec<F> = inputs(3.0, -2.0, &[]);
+    let c: Vec<F> = inputs(-5.0, 50.0, &[]);
+    let epsilon = <F as num_traits::Float>::epsilon().to_f64().unwrap();
+
+    for line_size in line_sizes::<R, F>() {
+        let handles = [&a, &b, &c].map(|values| client.create(F::as_bytes(values)));
+        let output_handle = client.empty(a.len() * size_of::<F>());
+
+        unsafe {
+            kernel_fma::launch_unchecked::<F, R>(
+                &client,
+                CubeCount::Static(1, 1, 1),
+                CubeDim::new_1d(a.len() as u32 / line_size as u32),
+                ArrayArg::from_raw_parts::<F>(&handles[0], a.len(), line_size),
+                ArrayArg::from_raw_parts::<F>(&handles[1], a.len(), line_size),
+                ArrayArg::from_raw_parts::<F>(&handles[2], a.len(), line_size),
+                ArrayArg::from_raw_parts::<F>(&output_handle, a.len(), line_size),
+            )
+        };
+
+        let actual = client.read_one(output_handle);
+        let actual = F::from_bytes(&actual);
+        for i in 0..a.len() {
+            let [a, b, c] = [a[i], b[i], c[i]].map(|value| value.to_f64().unwrap());
+            let expected = a * b + c;
+            let value = actual[i].to_f64().unwrap();
+            let allowed = epsilon * ((a * b).abs() + expected.abs());
+            assert!(
+                (value - expected).abs() <= allowed,
+                "fma({a}, {b}, {c}) at {i}: expected {expected}, got {value}"
+            );
+        }
+    }
+}
+
+/// The error function, from its Taylor series for small inputs and from [erfc_reference] for the
+/// other ones.
+fn erf_reference(x: f64) -> f64 {
+    if x.is_nan() || x.abs() >= 3.0 {
+        return (1.0 - erfc_reference(x.abs())).copysign(x);
+    }
+
+    let (mut term, mut sum) = (x, x);
+    let mut n = 0.0;
+    while term.abs() > 1e-17 * sum.abs() {
+        n += 1.0;
+        term *= -x * x / n;
+        sum += term / (2.0 * n + 1.0);
+    }
+    sum * 2.0 / core::f64::consts::PI.sqrt()
+}
+
+/// The complementary error function, from its continued fraction for large inputs and from
+/// [erf_reference] for the other ones.
+fn erfc_reference(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x < 0.0 {
+        return 2.0 - erfc_reference(-x);
+    }
+    if x < 3.0 {
+        return 1.0 - erf_reference(x);
+    }
+    if x.is_infinite() {
+        return 0.0;
+    }
+
+    let mut fraction = x;
+    for k in (1..=60).rev() {
+        fraction = x + (k as f64 / 2.0) / fraction;
+    }
+    (-x * x).exp() / core::f64::consts::PI.sqrt() / fraction
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_float_math {
+    () => {
+        mod float_math {
+            use super::*;
+
+            macro_rules! add_test {
+                ($test_name:ident) => {
+                    #[test]
+                    fn $test_name() {
+                        let client = TestRuntime::client(&Default::default());
+                        cubecl_core::runtime_tests::float_math::$test_name::<TestRuntime, FloatType>(
+                            client,
+                        );
+                    }
+                };
+            }
+
+            add_test!(test_erf);
+            add_test!(test_erf_small);
+            add_test!(test_erfc);
+            add_test!(test_tanh);
+            add_test!(test_exp2);
+            add_test!(test_log2);
+            add_test!(test_rsqrt);
+            add_test!(test_recip);
+            add_test!(test_fma);
+        }
+    };
+}
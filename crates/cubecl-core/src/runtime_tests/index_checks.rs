@@ -0,0 +1,132 @@
+use crate as cubecl;
+use crate::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_read_offset(input: &Array<f32>, output: &mut Array<f32>, offset: u32) {
+    output[UNIT_POS] = input[UNIT_POS + offset];
+}
+
+#[cube(launch)]
+pub fn kernel_shared_past_end(output: &mut Array<f32>) {
+    let mut shared = SharedMemory::<f32>::new(4);
+    shared[UNIT_POS + 1] = f32::cast_from(UNIT_POS);
+    sync_cube();
+    output[UNIT_POS] = shared[UNIT_POS];
+}
+
+fn launch_read_offset<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    offset: u32,
+) -> Vec<f32> {
+    let input = client.create(f32::as_bytes(&[1.0, 2.0, 3.0, 4.0]));
+    let output = client.empty(4 * size_of::<f32>());
+
+    kernel_read_offset::launch::<R>(
+        client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(4),
+        unsafe { ArrayArg::from_raw_parts::<f32>(&input, 4, 1) },
+        unsafe { ArrayArg::from_raw_parts::<f32>(&output, 4, 1) },
+        ScalarArg::new(offset),
+    );
+
+    f32::from_bytes(&client.read_one(output)).to_vec()
+}
+
+pub fn test_index_checks_in_bounds<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let report = IndexReport::new::<R>(&client);
+
+    let output = report.checked(|| launch_read_offset::<R>(&client, 0));
+
+    assert_eq!(output, [1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(report.read::<R>(&client), None);
+}
+
+pub fn test_index_checks_one_past_the_end<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let report = IndexReport::new::<R>(&client);
+
+    let output = report.checked(|| launch_read_offset::<R>(&client, 1));
+
+    // The last unit reads the last element instead.
+    assert_eq!(output, [2.0, 3.0, 4.0, 4.0]);
+    assert_eq!(
+        report.read::<R>(&client),
+        Some(IndexViolation { index: 4, count: 1 })
+    );
+}
+
+pub fn test_index_checks_shared_memory<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let report = IndexReport::new::<R>(&client);
+    let output = client.empty(4 * size_of::<f32>());
+
+    report.checked(|| {
+        kernel_shared_past_end::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            unsafe { ArrayArg::from_raw_parts::<f32>(&output, 4, 1) },
+        )
+    });
+
+    assert_eq!(
+        report.read::<R>(&client),
+        Some(IndexViolation { index: 4, count: 1 })
+    );
+}
+
+pub fn test_index_checks_cache_key<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let report = IndexReport::new::<R>(&client);
+
+    let checked = report.checked(|| launch_read_offset::<R>(&client, 1));
+    let unchecked = launch_read_offset::<R>(&client, 1);
+
+    // Without the index checks, the read out of bounds is masked to zero.
+    assert_eq!(checked, [2.0, 3.0, 4.0, 4.0]);
+    assert_eq!(unchecked, [2.0, 3.0, 4.0, 0.0]);
+    assert_eq!(
+        report.read::<R>(&client).map(|violation| violation.count),
+        Some(1)
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_index_checks {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_index_checks_in_bounds() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::index_checks::test_index_checks_in_bounds::<TestRuntime>(
+                client,
+            );
+        }
+
+        #[test]
+        fn test_index_checks_one_past_the_end() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::index_checks::test_index_checks_one_past_the_end::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_index_checks_shared_memory() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::index_checks::test_index_checks_shared_memory::<
+                TestRuntime,
+            >(client);
+        }
+
+        #[test]
+        fn test_index_checks_cache_key() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::index_checks::test_index_checks_cache_key::<TestRuntime>(
+                client,
+            );
+        }
+    };
+}
@@ -0,0 +1,97 @@
+use crate as cubecl;
+use cubecl::prelude::*;
+
+/// Moves the positive values to the start of the output, writing their count and the number of
+/// cubes needed to process them.
+#[cube(launch)]
+pub fn kernel_compact(
+    input: &Array<f32>,
+    output: &mut Array<f32>,
+    len: &mut Array<u32>,
+    cube_count: &mut Array<u32>,
+    #[comptime] cube_dim: u32,
+) {
+    if UNIT_POS == 0 {
+        let mut count = 0u32;
+
+        for i in 0..input.len() {
+            let value = input[i];
+            if value > 0.0 {
+                output[count] = value;
+                count += 1;
+            }
+        }
+
+        len[0] = count;
+        cube_count[0] = (count + cube_dim - 1) / cube_dim;
+        cube_count[1] = 1;
+        cube_count[2] = 1;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_double(input: &Array<f32>, len: &Array<u32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < len[0] {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * 2.0;
+    }
+}
+
+pub fn test_indirect_dispatch<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let cube_dim = 4;
+    let data: Vec<f32> = (0..64)
+        .map(|i| if i % 3 == 0 { i as f32 + 1.0 } else { -1.0 })
+        .collect();
+
+    let input = client.create(f32::as_bytes(&data));
+    let compacted = client.empty(data.len() * size_of::<f32>());
+    let len = client.empty(size_of::<u32>());
+    let cube_count = client.empty(3 * size_of::<u32>());
+    let output = client.create(f32::as_bytes(&vec![0.0; data.len()]));
+
+    unsafe {
+        kernel_compact::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            ArrayArg::from_raw_parts::<f32>(&input, data.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&compacted, data.len(), 1),
+            ArrayArg::from_raw_parts::<u32>(&len, 1, 1),
+            ArrayArg::from_raw_parts::<u32>(&cube_count, 3, 1),
+            cube_dim,
+        );
+        kernel_double::launch::<R>(
+            &client,
+            CubeCount::Dynamic(cube_count.binding()),
+            CubeDim::new_1d(cube_dim),
+            ArrayArg::from_raw_parts::<f32>(&compacted, data.len(), 1),
+            ArrayArg::from_raw_parts::<u32>(&len, 1, 1),
+            ArrayArg::from_raw_parts::<f32>(&output, data.len(), 1),
+        );
+    }
+
+    let mut expected: Vec<f32> = data
+        .iter()
+        .filter(|value| **value > 0.0)
+        .map(|value| value * 2.0)
+        .collect();
+    expected.resize(data.len(), 0.0);
+
+    let actual = client.read_one(output);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_indirect {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_indirect_dispatch() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::indirect::test_indirect_dispatch::<TestRuntime>(client);
+        }
+    };
+}
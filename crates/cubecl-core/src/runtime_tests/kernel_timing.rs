@@ -0,0 +1,105 @@
+use crate as cubecl;
+use cubecl::prelude::*;
+use cubecl_common::profile::TimingMethod;
+
+#[cube(launch)]
+pub fn kernel_timed(output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = output[ABSOLUTE_POS] * 2.0 + 1.0;
+    }
+}
+
+pub fn test_kernel_timing<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let handle = client.create(f32::as_bytes(&[1.0; 256]));
+    let launch = || unsafe {
+        kernel_timed::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(256),
+            ArrayArg::from_raw_parts::<f32>(&handle, 256, 1),
+        )
+    };
+
+    client.enable_kernel_timing(true);
+    {
+        let _scope = client.profile_scope("timed");
+        launch();
+        launch();
+    }
+    client.enable_kernel_timing(false);
+    launch();
+
+    let timings = cubecl_common::future::block_on(client.kernel_timings());
+    client.reset_kernel_timings();
+
+    // Other tests can share the client, so only look at the kernel launched here.
+    let (name, timing) = timings
+        .kernels
+        .iter()
+        .find(|(name, _)| name.contains("KernelTimed"))
+        .expect("The kernel should be timed");
+    assert!(name.starts_with("timed/"), "Unexpected kernel name {name}");
+    assert_eq!(timing.count, 2);
+    assert!(timing.total > core::time::Duration::ZERO);
+    assert!(timing.max < core::time::Duration::from_secs(1));
+}
+
+pub fn test_device_timestamps_are_ordered<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.create(f32::as_bytes(&[1.0; 256]));
+    let launch = || unsafe {
+        kernel_timed::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(256),
+            ArrayArg::from_raw_parts::<f32>(&handle, 256, 1),
+        )
+    };
+
+    // Warmup, so compilation isn't measured.
+    launch();
+    cubecl_common::future::block_on(client.sync());
+
+    let epoch = cubecl_common::profile::Instant::now();
+    let first = client.profile_device(launch, "first").unwrap();
+    let second = client.profile_device(launch, "second").unwrap();
+
+    if first.timing_method() != TimingMethod::Device {
+        // Timed with the system clock around the submission.
+        return;
+    }
+
+    client.flush();
+    let first = cubecl_common::future::block_on(first.resolve());
+    let second = cubecl_common::future::block_on(second.resolve());
+
+    assert!(first.duration() > core::time::Duration::ZERO);
+    assert!(second.duration() > core::time::Duration::ZERO);
+    assert!(
+        second.start_duration_since(epoch) >= first.start_duration_since(epoch),
+        "The second dispatch should start after the first one"
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_kernel_timing {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_kernel_timing() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::kernel_timing::test_kernel_timing::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_device_timestamps_are_ordered() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::kernel_timing::test_device_timestamps_are_ordered::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -1,5 +1,9 @@
 use crate::{self as cubecl, as_bytes};
 use cubecl::prelude::*;
+use cubecl::server::{Handle, IoError, KernelResource};
+use cubecl_runtime::memory_management::MemoryCleanupMode;
+
+use crate::runtime_tests::stream::kernel_add_one;
 
 #[derive(CubeLaunch, CubeType)]
 pub struct ComptimeTag {
@@ -53,6 +57,22 @@ pub fn kernel_with_max_shared(
     }
 }
 
+#[cube(launch)]
+pub fn kernel_with_scalars(output: &mut Array<u32>, values: Sequence<u32>, factor: f32) {
+    if UNIT_POS == 0 {
+        let count = comptime![values.len()];
+        let mut sum = 0u32;
+
+        #[unroll]
+        for i in 0..count {
+            sum += *values.index(i);
+        }
+
+        output[0] = sum;
+        output[1] = u32::cast_from(factor * 2.0);
+    }
+}
+
 pub fn test_kernel_with_comptime_tag<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let handle = client.create(f32::as_bytes(&[5.0]));
     let array_arg = unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 1, 1) };
@@ -119,6 +139,32 @@ pub fn test_kernel_without_generics<R: Runtime>(client: ComputeClient<R::Server,
     assert_eq!(actual[0], 5.0);
 }
 
+/// Few scalars may be packed together, while many may not fit in the space reserved for them
+/// and take a different path.
+pub fn test_kernel_with_scalars<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+    count: u32,
+) {
+    let handle = client.empty(2 * size_of::<u32>());
+    let values = SequenceArg {
+        values: (0..count).map(ScalarArg::new).collect(),
+    };
+
+    kernel_with_scalars::launch::<R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::default(),
+        unsafe { ArrayArg::from_raw_parts::<u32>(&handle, 2, 1) },
+        values,
+        ScalarArg::new(1.5),
+    );
+
+    let actual = client.read_one(handle);
+    let actual = u32::from_bytes(&actual);
+
+    assert_eq!(actual, &[(0..count).sum::<u32>(), 3]);
+}
+
 pub fn test_kernel_max_shared<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
     let total_shared_size = client.properties().hardware.max_shared_memory_size;
 
@@ -143,6 +189,46 @@ pub fn test_kernel_max_shared<R: Runtime>(client: ComputeClient<R::Server, R::Ch
     assert_eq!(actual, &[1, 9, 9, 9, 9, 9, 9, 1]);
 }
 
+fn add_one<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &Handle,
+    output: &Handle,
+    len: usize,
+) {
+    unsafe {
+        kernel_add_one::launch::<R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(len as u32),
+            ArrayArg::from_raw_parts::<f32>(input, len, 1),
+            ArrayArg::from_raw_parts::<f32>(output, len, 1),
+        )
+    };
+}
+
+pub fn test_kernel_after_cleanup<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let data = [0.0, 1.0, 2.0, 3.0];
+    let lhs = client.create(f32::as_bytes(&data));
+    let rhs = client.empty(data.len() * size_of::<f32>());
+    let temporary = (0..8).map(|_| client.empty(1024)).collect::<Vec<_>>();
+
+    // The same launches are repeated, so they can reuse the resources of the previous ones.
+    for _ in 0..2 {
+        add_one::<R>(&client, &lhs, &rhs, data.len());
+        add_one::<R>(&client, &rhs, &lhs, data.len());
+    }
+
+    // The live buffers may be moved, releasing the memory the previous launches were bound to.
+    drop(temporary);
+    client.memory_cleanup(MemoryCleanupMode::Aggressive);
+
+    add_one::<R>(&client, &lhs, &rhs, data.len());
+    add_one::<R>(&client, &rhs, &lhs, data.len());
+
+    let actual = client.read_one(lhs);
+    assert_eq!(f32::from_bytes(&actual), [6.0, 7.0, 8.0, 9.0]);
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_launch {
@@ -171,6 +257,26 @@ macro_rules! testgen_launch {
             );
         }
 
+        #[test]
+        fn test_launch_with_few_scalars() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_with_scalars::<TestRuntime>(client, 3);
+        }
+
+        #[test]
+        fn test_launch_with_many_scalars() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_with_scalars::<TestRuntime>(
+                client, 100,
+            );
+        }
+
+        #[test]
+        fn test_launch_after_cleanup() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::launch::test_kernel_after_cleanup::<TestRuntime>(client);
+        }
+
         #[ignore = "Seemingly flaky with CPU emulation"]
         #[test]
         fn test_launch_with_max_shared() {
@@ -179,3 +285,194 @@ macro_rules! testgen_launch {
         }
     };
 }
+
+fn assert_limit_exceeded(
+    result: Result<(), IoError>,
+    expected_resource: KernelResource,
+    expected_requested: u64,
+) {
+    match result {
+        Err(IoError::LimitExceeded {
+            resource,
+            requested,
+            ..
+        }) => {
+            assert_eq!(resource, expected_resource);
+            assert_eq!(requested, expected_requested);
+        }
+        other => panic!("Expected the {expected_resource} limit to be exceeded, got {other:?}"),
+    }
+}
+
+pub fn test_kernel_exceeding_shared<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let total_shared_size = client.properties().hardware.max_shared_memory_size;
+
+    let handle = client.create(u32::as_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]));
+    let output = unsafe { ArrayArg::from_raw_parts::<u32>(&handle, 8, 1) };
+
+    // The second buffer alone fills the shared memory.
+    let shared_size_1 = 1024 / size_of::<u32>();
+    let shared_size_2 = total_shared_size / size_of::<u32>();
+
+    let kernel = kernel_with_max_shared::KernelWithMaxShared::<R>::new(
+        KernelSettings::default().cube_dim(CubeDim::default()),
+        <Array<u32> as LaunchArg>::compilation_arg::<R>(&output),
+        shared_size_1 as u32,
+        shared_size_2 as u32,
+    );
+    let mut launcher = KernelLauncher::<R>::default();
+    output.register(&mut launcher);
+
+    assert_limit_exceeded(
+        launcher.try_launch(CubeCount::Static(1, 1, 1), kernel, &client),
+        KernelResource::SharedMemory,
+        (total_shared_size + 1024) as u64,
+    );
+}
+
+pub fn test_kernel_exceeding_units<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let max_units = client.properties().hardware.max_units_per_cube;
+    let cube_dim = CubeDim::new_2d(max_units, 2);
+
+    let handle = client.create(f32::as_bytes(&[0.0]));
+    let output = unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 1, 1) };
+
+    let kernel = kernel_without_generics::KernelWithoutGenerics::<R>::new(
+        KernelSettings::default().cube_dim(cube_dim),
+        <Array<f32> as LaunchArg>::compilation_arg::<R>(&output),
+    );
+    let mut launcher = KernelLauncher::<R>::default();
+    output.register(&mut launcher);
+
+    assert_limit_exceeded(
+        launcher.try_launch(CubeCount::Static(1, 1, 1), kernel, &client),
+        KernelResource::UnitsPerCube,
+        2 * max_units as u64,
+    );
+}
+
+pub fn test_kernel_exceeding_dynamic_shared<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let total_shared_size = client.properties().hardware.max_shared_memory_size;
+
+    let handle = client.create(f32::as_bytes(&[0.0]));
+    let output = unsafe { ArrayArg::from_raw_parts::<f32>(&handle, 1, 1) };
+
+    let kernel = kernel_without_generics::KernelWithoutGenerics::<R>::new(
+        KernelSettings::default()
+            .cube_dim(CubeDim::default())
+            .dynamic_shared_memory(total_shared_size as u32 + 1024),
+        <Array<f32> as LaunchArg>::compilation_arg::<R>(&output),
+    );
+    let mut launcher = KernelLauncher::<R>::default();
+    output.register(&mut launcher);
+
+    assert_limit_exceeded(
+        launcher.try_launch(CubeCount::Static(1, 1, 1), kernel, &client),
+        KernelResource::SharedMemory,
+        (total_shared_size + 1024) as u64,
+    );
+}
+
+/// A kernel using `f64` on a device without it is rejected by the backend, and the client keeps
+/// launching the valid kernels afterwards.
+pub fn test_kernel_failing_compilation<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    if client
+        .properties()
+        .supports_type(f64::as_type_native_unchecked())
+    {
+        println!("f64 is supported, so the kernel compiles - skipped");
+        return;
+    }
+
+    let handle = client.create(f32::as_bytes(&[0.0, 1.0]));
+    let output = unsafe { ArrayArg::from_raw_parts::<f64>(&handle, 1, 1) };
+
+    // The error isn't cached, so launching the kernel again fails the same way.
+    for _ in 0..2 {
+        let kernel = kernel_with_generics::KernelWithGenerics::<f64, R>::new(
+            KernelSettings::default().cube_dim(CubeDim::default()),
+            <Array<f64> as LaunchArg>::compilation_arg::<R>(&output),
+        );
+        let mut launcher = KernelLauncher::<R>::default();
+        output.register(&mut launcher);
+
+        match launcher.try_launch(CubeCount::Static(1, 1, 1), kernel, &client) {
+            Err(IoError::Compilation(err)) => {
+                assert!(!err.kernel_name.is_empty());
+                assert!(!err.backend_log.is_empty());
+            }
+            other => panic!("Expected the kernel to be rejected, got {other:?}"),
+        }
+    }
+
+    test_kernel_without_generics::<R>(client);
+}
+
+/// The backends rejecting kernels using unsupported types, checked by device validation rather
+/// than crashing the driver.
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_launch_compilation_error {
+    () => {
+        mod launch_compilation_error {
+            use super::*;
+
+            #[test]
+            fn test_launch_failing_compilation() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::launch::test_kernel_failing_compilation::<TestRuntime>(
+                    client,
+                );
+            }
+        }
+    };
+}
+
+/// Limits are checked before creating the kernel on the device, which not all runtimes do.
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_launch_limits {
+    () => {
+        mod launch_limits {
+            use super::*;
+
+            #[test]
+            fn test_launch_exceeding_shared() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::launch::test_kernel_exceeding_shared::<TestRuntime>(
+                    client,
+                );
+            }
+
+            #[test]
+            fn test_launch_exceeding_units() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::launch::test_kernel_exceeding_units::<TestRuntime>(
+                    client,
+                );
+            }
+        }
+    };
+}
+
+/// The dynamic shared memory requested is checked against the limit, for the runtimes launching
+/// kernels with dynamic shared memory.
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_launch_dynamic_shared {
+    () => {
+        mod launch_dynamic_shared {
+            use super::*;
+
+            #[test]
+            fn test_launch_exceeding_dynamic_shared() {
+                let client = TestRuntime::client(&Default::default());
+                cubecl_core::runtime_tests::launch::test_kernel_exceeding_dynamic_shared::<
+                    TestRuntime,
+                >(client);
+            }
+        }
+    };
+}
@@ -56,7 +56,11 @@ pub fn test_fp8<R: Runtime, F: Float + CubeElement>(
         return;
     }
 
-    let data = as_type![F: -2.1, 1.8, 0.4, 1.2];
+    // Out of range values saturate to the max normal, even from infinity in `f16`.
+    let data = as_type![F:
+        -2.1, 1.8, 0.4, 1.2, 1000.0, -1000.0, 0.0, -0.5,
+        3.0, 448.0, 0.015625, 6.5, -12.0, 0.25, 100.0, 100000.0
+    ];
     let num_out = vectorization as usize;
     let handle1 = client.create(F::as_bytes(&data[..num_out]));
     let handle2 = client.empty(2 * num_out * size_of::<u8>());
@@ -73,8 +77,42 @@ pub fn test_fp8<R: Runtime, F: Float + CubeElement>(
 
     let actual = client.read_one(handle2);
     let actual = u8::from_bytes(&actual);
-    let expect_0: Vec<u8> = vec![0b1_1000_000, 0b0_0111_110, 0b0_0101_101, 0b0_0111_010];
-    let expect_1: Vec<u8> = vec![0b1_10000_00, 0b0_01111_11, 0b0_01101_10, 0b0_01111_01];
+    let expect_0: Vec<u8> = vec![
+        0b1_1000_000,
+        0b0_0111_110,
+        0b0_0101_101,
+        0b0_0111_010,
+        0b0_1111_110,
+        0b1_1111_110,
+        0b0_0000_000,
+        0b1_0110_000,
+        0b0_1000_100,
+        0b0_1111_110,
+        0b0_0001_000,
+        0b0_1001_101,
+        0b1_1010_100,
+        0b0_0101_000,
+        0b0_1101_100,
+        0b0_1111_110,
+    ];
+    let expect_1: Vec<u8> = vec![
+        0b1_10000_00,
+        0b0_01111_11,
+        0b0_01101_10,
+        0b0_01111_01,
+        0b0_11001_00,
+        0b1_11001_00,
+        0b0_00000_00,
+        0b1_01110_00,
+        0b0_10000_10,
+        0b0_10111_11,
+        0b0_01001_00,
+        0b0_10001_10,
+        0b1_10010_10,
+        0b0_01101_00,
+        0b0_10101_10,
+        0b0_11110_11,
+    ];
     let mut expected = expect_0[..num_out].to_vec();
     expected.extend(expect_1[..num_out].iter().copied());
 
@@ -85,7 +123,10 @@ pub fn test_fp8<R: Runtime, F: Float + CubeElement>(
     println!("actual_2: {actual_2:?}");
 
     // Data rounded to the nearest e4m3 value
-    let expected_data = as_type![F: -2.0, 1.75, 0.40625, 1.25];
+    let expected_data = as_type![F:
+        -2.0, 1.75, 0.40625, 1.25, 448.0, -448.0, 0.0, -0.5,
+        3.0, 448.0, 0.015625, 6.5, -12.0, 0.25, 96.0, 448.0
+    ];
 
     assert_eq!(actual, &expected);
     assert_eq!(&actual_2[..num_out], &expected_data[..num_out]);
@@ -234,6 +275,14 @@ macro_rules! testgen_minifloat {
                 client.clone(),
                 4,
             );
+            cubecl_core::runtime_tests::minifloat::test_fp8::<TestRuntime, FloatType>(
+                client.clone(),
+                8,
+            );
+            cubecl_core::runtime_tests::minifloat::test_fp8::<TestRuntime, FloatType>(
+                client.clone(),
+                16,
+            );
         }
 
         #[test]
@@ -1,24 +1,36 @@
 pub mod assign;
 pub mod atomic;
 pub mod barrier;
+pub mod batch;
 pub mod binary;
 pub mod branch;
 pub mod cluster;
 pub mod cmma;
 pub mod comparison;
+pub mod comptime_assert;
 pub mod const_match;
 pub mod constants;
 pub mod debug;
+pub mod debug_sink;
+pub mod device_features;
 pub mod different_rank;
 pub mod enums;
+pub mod float_math;
 pub mod index;
+pub mod index_checks;
+pub mod indirect;
+pub mod kernel_timing;
 pub mod launch;
 pub mod line;
 pub mod metadata;
 pub mod minifloat;
+pub mod option;
 pub mod plane;
+pub mod precompile;
 pub mod sequence;
 pub mod slice;
+pub mod stream;
+pub mod sub_handle;
 pub mod synchronization;
 pub mod tensor;
 pub mod tensormap;
@@ -27,6 +39,7 @@ pub mod topology;
 pub mod traits;
 pub mod unary;
 pub mod unroll;
+pub mod validation;
 
 #[allow(missing_docs)]
 #[macro_export]
@@ -91,14 +104,17 @@ macro_rules! testgen_float {
         cubecl_core::testgen_index!();
         cubecl_core::testgen_launch!();
         cubecl_core::testgen_line!();
+        cubecl_core::testgen_option!();
         cubecl_core::testgen_plane!();
         cubecl_core::testgen_sequence!();
         cubecl_core::testgen_slice!();
         cubecl_core::testgen_unary!();
+        cubecl_core::testgen_float_math!();
         cubecl_core::testgen_atomic_float!();
         cubecl_core::testgen_tensormap!();
         cubecl_core::testgen_minifloat!();
         cubecl_core::testgen_unroll!();
+        cubecl_core::testgen_validation!();
     };
 }
 
@@ -123,14 +139,19 @@ macro_rules! testgen_uint {
 #[macro_export]
 macro_rules! testgen_untyped {
     () => {
+        cubecl_core::testgen_atomic_u64!();
         cubecl_core::testgen_cmma!();
         cubecl_core::testgen_metadata!();
         cubecl_core::testgen_topology!();
+        cubecl_core::testgen_device_features!();
 
         cubecl_core::testgen_constants!();
+        cubecl_core::testgen_comptime_assert!();
         cubecl_core::testgen_sync_plane!();
         cubecl_core::testgen_tensor_indexing!();
         cubecl_core::testgen_debug!();
+        cubecl_core::testgen_debug_sink!();
+        cubecl_core::testgen_index_checks!();
         cubecl_core::testgen_binary_untyped!();
         cubecl_core::testgen_cluster!();
 
@@ -138,6 +159,12 @@ macro_rules! testgen_untyped {
         cubecl_core::testgen_comparison!();
 
         cubecl_core::testgen_to_client!();
+        cubecl_core::testgen_stream!();
+        cubecl_core::testgen_kernel_timing!();
+        cubecl_core::testgen_sub_handle!();
+        cubecl_core::testgen_precompile!();
+        cubecl_core::testgen_batch!();
+        cubecl_core::testgen_indirect!();
     };
 }
 
@@ -0,0 +1,99 @@
+use crate::{self as cubecl, as_bytes};
+
+use cubecl::prelude::*;
+
+#[cube(launch, create_dummy_kernel)]
+pub fn kernel_optional_scale<F: Float>(
+    input: &Tensor<F>,
+    scale: Option<Tensor<F>>,
+    output: &mut Tensor<F>,
+) {
+    if ABSOLUTE_POS < output.len() {
+        if let Some(scale) = scale {
+            output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * scale[ABSOLUTE_POS];
+        } else {
+            output[ABSOLUTE_POS] = input[ABSOLUTE_POS];
+        }
+    }
+}
+
+pub fn test_kernel_optional_scale<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 2.0, 3.0, 4.0]);
+    let scale = client.create(as_bytes![F: 2.0, 0.5, 3.0, -1.0]);
+    let output = client.empty(4 * size_of::<F>());
+
+    let launch = |scale: Option<&crate::server::Handle>| unsafe {
+        kernel_optional_scale::launch::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            TensorArg::from_raw_parts::<F>(&input, &[1], &[4], 1),
+            scale.map(|scale| TensorArg::from_raw_parts::<F>(scale, &[1], &[4], 1)),
+            TensorArg::from_raw_parts::<F>(&output, &[1], &[4], 1),
+        )
+    };
+
+    launch(Some(&scale));
+    let actual = client.read_one(output.clone());
+    assert_eq!(
+        F::from_bytes(&actual),
+        [F::new(2.0), F::new(1.0), F::new(9.0), F::new(-4.0)]
+    );
+
+    launch(None);
+    let actual = client.read_one(output.clone());
+    assert_eq!(
+        F::from_bytes(&actual),
+        [F::new(1.0), F::new(2.0), F::new(3.0), F::new(4.0)]
+    );
+}
+
+/// A missing optional argument doesn't get a binding, and the kernels with and without it have
+/// different ids.
+pub fn test_kernel_optional_scale_binding<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let handle = client.empty(4 * size_of::<F>());
+    let kernel = |scale: bool| unsafe {
+        kernel_optional_scale::create_dummy_kernel::<F, R>(
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            TensorArg::from_raw_parts::<F>(&handle, &[1], &[4], 1),
+            scale.then(|| TensorArg::from_raw_parts::<F>(&handle, &[1], &[4], 1)),
+            TensorArg::from_raw_parts::<F>(&handle, &[1], &[4], 1),
+        )
+    };
+    let (with_scale, without_scale) = (kernel(true), kernel(false));
+
+    assert_eq!(with_scale.define().buffers.len(), 3);
+    assert_eq!(without_scale.define().buffers.len(), 2);
+    assert_ne!(with_scale.id(), without_scale.id());
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_option {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_optional_scale() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::option::test_kernel_optional_scale::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_optional_scale_binding() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::option::test_kernel_optional_scale_binding::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+    };
+}
@@ -109,6 +109,16 @@ pub fn kernel_broadcast<F: Float>(output: &mut Tensor<F>) {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_dynamic_broadcast<F: Float>(output: &mut Tensor<F>, lane: u32) {
+    let val = output[UNIT_POS];
+    let val2 = plane_broadcast(val, lane);
+
+    if UNIT_POS == 0 {
+        output[0] = val2;
+    }
+}
+
 #[cube(launch)]
 pub fn kernel_ballot(output: &mut Tensor<Line<u32>>) {
     let val2 = plane_ballot(UNIT_POS < 8);
@@ -626,6 +636,43 @@ pub fn test_plane_broadcast<
     );
 }
 
+pub fn test_plane_dynamic_broadcast<
+    TestRuntime: Runtime,
+    F: Float + num_traits::Float + CubeElement + Display,
+>(
+    client: ComputeClient<TestRuntime::Server, TestRuntime::Channel>,
+    vectorization: u8,
+) {
+    let plane_size = 32;
+    let lane = 5;
+    let input: Vec<f32> = (0..plane_size * vectorization as u32)
+        .map(|x| x as f32)
+        .collect();
+    let mut expected = input.clone();
+
+    for v in 0..vectorization as usize {
+        expected[v] = input[v + lane * vectorization as usize];
+    }
+    let input: Vec<F> = input.into_iter().map(|x| F::new(x)).collect();
+    let expected: Vec<F> = expected.into_iter().map(|x| F::new(x)).collect();
+
+    test_plane_operation::<TestRuntime, F, _>(
+        &input,
+        &expected,
+        vectorization,
+        client.clone(),
+        |cube_count, handle| {
+            kernel_dynamic_broadcast::launch::<F, TestRuntime>(
+                &client,
+                cube_count,
+                CubeDim::new(plane_size, 1, 1),
+                handle,
+                ScalarArg::new(lane as u32),
+            )
+        },
+    );
+}
+
 fn test_plane_operation<
     TestRuntime: Runtime,
     F: Float + num_traits::Float + CubeElement + Display,
@@ -906,6 +953,22 @@ macro_rules! testgen_plane {
             impl_test_plane_broadcast(4);
         }
 
+        fn impl_test_plane_dynamic_broadcast(vectorization: u8) {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::plane::test_plane_dynamic_broadcast::<
+                TestRuntime,
+                FloatType,
+            >(client.clone(), vectorization);
+        }
+        #[test]
+        fn test_plane_dynamic_broadcast_vec1() {
+            impl_test_plane_dynamic_broadcast(1);
+        }
+        #[test]
+        fn test_plane_dynamic_broadcast_vec4() {
+            impl_test_plane_dynamic_broadcast(4);
+        }
+
         #[test]
         fn test_plane_ballot() {
             let client = TestRuntime::client(&Default::default());
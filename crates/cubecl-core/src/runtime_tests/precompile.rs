@@ -0,0 +1,67 @@
+use crate as cubecl;
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_times_two(input: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * 2.0;
+    }
+}
+
+fn times_two<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &crate::server::Handle,
+    output: &crate::server::Handle,
+    len: usize,
+) {
+    unsafe {
+        kernel_times_two::launch::<R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(len as u32),
+            ArrayArg::from_raw_parts::<f32>(input, len, 1),
+            ArrayArg::from_raw_parts::<f32>(output, len, 1),
+        )
+    };
+}
+
+pub fn test_launch_after_precompile_does_not_compile<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let data = [0.0, 1.0, 2.0, 3.0];
+    let input = client.create(f32::as_bytes(&data));
+    let output = client.create(f32::as_bytes(&[0.0; 4]));
+
+    times_two::<R>(&client.precompile_only(), &input, &output, data.len());
+
+    let precompiled = f32::from_bytes(&client.read_one(output.clone())).to_vec();
+    assert_eq!(
+        precompiled, [0.0; 4],
+        "Precompiling shouldn't launch the kernel"
+    );
+
+    let compilations = client.compilation_count();
+    times_two::<R>(&client, &input, &output, data.len());
+
+    let actual = client.read_one(output);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, [0.0, 2.0, 4.0, 6.0]);
+    assert_eq!(client.compilation_count(), compilations);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_precompile {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_launch_after_precompile_does_not_compile() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::precompile::test_launch_after_precompile_does_not_compile::<
+                TestRuntime,
+            >(client);
+        }
+    };
+}
@@ -0,0 +1,164 @@
+use crate as cubecl;
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_add_one(input: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] + 1.0;
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_spin(output: &mut Array<f32>, iterations: u32) {
+    let mut acc = output[UNIT_POS];
+    for _ in 0..iterations {
+        acc = acc * 0.5 + 1.0;
+    }
+    output[UNIT_POS] = acc;
+}
+
+fn add_one<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &crate::server::Handle,
+    output: &crate::server::Handle,
+    len: usize,
+) {
+    unsafe {
+        kernel_add_one::launch::<R>(
+            client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(len as u32),
+            ArrayArg::from_raw_parts::<f32>(input, len, 1),
+            ArrayArg::from_raw_parts::<f32>(output, len, 1),
+        )
+    };
+}
+
+pub fn test_stream_events<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let client_a = client.with_stream(client.create_stream());
+    let client_b = client.with_stream(client.create_stream());
+    let data = [0.0, 1.0, 2.0, 3.0];
+
+    let input = client_a.create(f32::as_bytes(&data));
+    let intermediate = client_a.empty(data.len() * size_of::<f32>());
+    add_one::<R>(&client_a, &input, &intermediate, data.len());
+
+    let event = client_a.record_event();
+    client_b.wait_event(&event);
+
+    let output = client_b.empty(data.len() * size_of::<f32>());
+    add_one::<R>(&client_b, &intermediate, &output, data.len());
+    let done = client_b.record_event();
+
+    done.wait_blocking();
+    assert!(event.is_complete());
+    assert!(done.is_complete());
+
+    let actual = client_b.read_one(output);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, [2.0, 3.0, 4.0, 5.0]);
+}
+
+pub fn test_event_elapsed<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let client = client.with_stream(client.create_stream());
+    let output = client.create(f32::as_bytes(&[0.0]));
+    let spin = || unsafe {
+        kernel_spin::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            ArrayArg::from_raw_parts::<f32>(&output, 1, 1),
+            ScalarArg::new(1 << 16),
+        )
+    };
+
+    // Warmup, so compilation isn't measured.
+    spin();
+
+    let start = client.record_event();
+    spin();
+    let end = client.record_event();
+
+    let duration = match start.elapsed(&end) {
+        Ok(duration) => duration,
+        // The runtime can't time events.
+        Err(_) => return,
+    };
+    let ticks = cubecl_common::future::block_on(duration.resolve());
+
+    assert!(end.is_complete());
+    assert!(ticks.duration() > core::time::Duration::ZERO);
+}
+
+pub fn test_streams_overlap<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let streams = [client.create_stream(), client.create_stream()];
+
+    if streams.iter().any(|stream| stream.is_default()) {
+        // The runtime serializes all the work.
+        return;
+    }
+
+    let iterations = 1 << 22;
+    let spin = |client: &ComputeClient<R::Server, R::Channel>| {
+        let output = client.create(f32::as_bytes(&[0.0]));
+        unsafe {
+            kernel_spin::launch::<R>(
+                client,
+                CubeCount::Static(1, 1, 1),
+                CubeDim::new_1d(1),
+                ArrayArg::from_raw_parts::<f32>(&output, 1, 1),
+                ScalarArg::new(iterations),
+            )
+        };
+    };
+
+    // Warmup, so compilation isn't measured.
+    spin(&client);
+    cubecl_common::future::block_on(client.sync_all());
+
+    let start = std::time::Instant::now();
+    spin(&client);
+    spin(&client);
+    cubecl_common::future::block_on(client.sync_all());
+    let serialized = start.elapsed();
+
+    let start = std::time::Instant::now();
+    for stream in streams {
+        spin(&client.with_stream(stream));
+    }
+    cubecl_common::future::block_on(client.sync_all());
+    let overlapped = start.elapsed();
+
+    // Each kernel uses a single unit, so both streams should run at the same time.
+    assert!(
+        overlapped.as_secs_f64() < serialized.as_secs_f64() * 0.75,
+        "Kernels on different streams should overlap, serialized: {serialized:?}, overlapped: {overlapped:?}"
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_stream {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_stream_events() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_stream_events::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_event_elapsed() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_event_elapsed::<TestRuntime>(client);
+        }
+
+        #[test]
+        fn test_streams_overlap() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::stream::test_streams_overlap::<TestRuntime>(client);
+        }
+    };
+}
@@ -0,0 +1,67 @@
+use crate::prelude::*;
+use crate::runtime_tests::to_client::kernel_matmul;
+
+pub fn test_matmul_on_windows<R: Runtime>(client: ComputeClient<R::Server, R::Channel>) {
+    let (m, k, n) = (4, 3, 4);
+    let lhs: Vec<f32> = (0..m * k).map(|i| i as f32).collect();
+    let rhs: Vec<f32> = (0..k * n).map(|i| (i % 5) as f32).collect();
+
+    // Each window starts on an aligned offset, the padding between them is filled with a
+    // sentinel value to detect writes outside of the output window.
+    let alignment = client.properties().memory.alignment as usize / size_of::<f32>();
+    let stride = usize::max(m * k, usize::max(k * n, m * n)).next_multiple_of(alignment);
+    let sentinel = -1.0;
+
+    let mut data = vec![sentinel; 3 * stride];
+    data[..m * k].copy_from_slice(&lhs);
+    data[stride..stride + k * n].copy_from_slice(&rhs);
+
+    let buffer = client.create(f32::as_bytes(&data));
+    let window = |index: usize, len: usize| {
+        buffer.offset(
+            (index * stride * size_of::<f32>()) as u64,
+            (len * size_of::<f32>()) as u64,
+        )
+    };
+    let (lhs_window, rhs_window, out_window) =
+        (window(0, m * k), window(1, k * n), window(2, m * n));
+
+    unsafe {
+        kernel_matmul::launch::<R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_2d(n as u32, m as u32),
+            ArrayArg::from_raw_parts::<f32>(&lhs_window, m * k, 1),
+            ArrayArg::from_raw_parts::<f32>(&rhs_window, k * n, 1),
+            ArrayArg::from_raw_parts::<f32>(&out_window, m * n, 1),
+            k as u32,
+        )
+    };
+
+    let mut expected = data.clone();
+    for row in 0..m {
+        for col in 0..n {
+            expected[2 * stride + row * n + col] =
+                (0..k).map(|i| lhs[row * k + i] * rhs[i * n + col]).sum();
+        }
+    }
+
+    let actual = client.read_one(buffer);
+    let actual = f32::from_bytes(&actual);
+
+    assert_eq!(actual, expected);
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_sub_handle {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_matmul_on_windows() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::sub_handle::test_matmul_on_windows::<TestRuntime>(client);
+        }
+    };
+}
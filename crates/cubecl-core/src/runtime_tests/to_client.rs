@@ -1,5 +1,6 @@
 use cubecl_common::device::{Device, DeviceId};
 
+use crate as cubecl;
 use crate::Runtime;
 use crate::prelude::*;
 
@@ -32,6 +33,90 @@ pub fn test_to_client<R: Runtime>() {
     }
 }
 
+#[cube(launch)]
+pub fn kernel_matmul(lhs: &Array<f32>, rhs: &Array<f32>, out: &mut Array<f32>, #[comptime] k: u32) {
+    let size = CUBE_DIM_X;
+    let row = UNIT_POS_Y;
+    let col = UNIT_POS_X;
+    let mut acc = f32::new(0.0);
+
+    for i in 0..k {
+        acc += lhs[row * k + i] * rhs[i * size + col];
+    }
+
+    out[row * size + col] = acc;
+}
+
+pub fn test_to_client_matmul<R: Runtime>() {
+    let type_id = 0;
+    let devices = R::enumerate_devices(type_id);
+
+    if devices.len() < 2 {
+        return;
+    }
+
+    let (m, k, n) = (4, 3, 4);
+    let client_0 = &devices[0].1;
+    let client_1 = &devices[1].1;
+
+    let lhs: Vec<f32> = (0..m * k).map(|i| i as f32).collect();
+    let rhs: Vec<f32> = (0..k * n).map(|i| (i % 5) as f32).collect();
+    let lhs_0 = client_0.create(f32::as_bytes(&lhs));
+    let rhs_0 = client_0.create(f32::as_bytes(&rhs));
+
+    let lhs_1 = client_0.copy_to(client_1, lhs_0);
+    let rhs_1 = client_0.copy_to(client_1, rhs_0);
+    let out = client_1.empty(m * n * size_of::<f32>());
+
+    unsafe {
+        kernel_matmul::launch::<R>(
+            client_1,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_2d(n as u32, m as u32),
+            ArrayArg::from_raw_parts::<f32>(&lhs_1, m * k, 1),
+            ArrayArg::from_raw_parts::<f32>(&rhs_1, k * n, 1),
+            ArrayArg::from_raw_parts::<f32>(&out, m * n, 1),
+            k as u32,
+        )
+    };
+
+    let actual = client_1.read_one(out);
+    let actual = f32::from_bytes(&actual);
+
+    let mut expected = vec![0.0; m * n];
+    for row in 0..m {
+        for col in 0..n {
+            for i in 0..k {
+                expected[row * n + col] += lhs[row * k + i] * rhs[i * n + col];
+            }
+        }
+    }
+
+    assert_eq!(actual, expected);
+}
+
+pub fn test_handle_on_wrong_device<R: Runtime>() {
+    let type_id = 0;
+    let devices = R::enumerate_devices(type_id);
+
+    if devices.len() < 2 {
+        return;
+    }
+
+    let client_0 = &devices[0].1;
+    let client_1 = &devices[1].1;
+    let handle = client_0.create(f32::as_bytes(&[1.0, 2.0]));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        client_1.read_one(handle);
+    }));
+
+    assert!(
+        result.is_err(),
+        "Reading a handle from another device should fail"
+    );
+}
+
 fn num_combination(type_id: u16, n: u32) -> Vec<(DeviceId, DeviceId)> {
     let mut results = Vec::new();
 
@@ -54,5 +139,15 @@ macro_rules! testgen_to_client {
         fn test_to_client() {
             cubecl_core::runtime_tests::to_client::test_to_client::<TestRuntime>();
         }
+
+        #[test]
+        fn test_to_client_matmul() {
+            cubecl_core::runtime_tests::to_client::test_to_client_matmul::<TestRuntime>();
+        }
+
+        #[test]
+        fn test_handle_on_wrong_device() {
+            cubecl_core::runtime_tests::to_client::test_handle_on_wrong_device::<TestRuntime>();
+        }
     };
 }
@@ -1,6 +1,8 @@
-use crate as cubecl;
+use crate::{self as cubecl, LaunchShape, logical_cube_pos, next_logical_cube_pos};
 
 use cubecl::prelude::*;
+use cubecl_ir::StorageType;
+use cubecl_runtime::TypeUsage;
 
 #[cube(launch)]
 pub fn kernel_absolute_pos(output1: &mut Array<u32>) {
@@ -34,6 +36,77 @@ pub fn test_kernel_topology_absolute_pos<R: Runtime>(client: ComputeClient<R::Se
     assert_eq!(actual, &expect);
 }
 
+#[cube(launch)]
+pub fn kernel_visit_logical_cubes(visits: &mut Array<Atomic<u32>>, total: u32) {
+    let mut cube = logical_cube_pos(total);
+    while cube < total {
+        if UNIT_POS == 0 {
+            Atomic::add(&visits[cube], 1);
+        }
+        cube = next_logical_cube_pos(cube, total);
+    }
+}
+
+/// Launch `num_cubes` logical cubes within the `limits` of the grid, and check that each of them
+/// is visited exactly once.
+fn visit_logical_cubes<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    num_cubes: usize,
+    limits: (u32, u32, u32),
+) {
+    let config = LaunchShape::elementwise(num_cubes * 4)
+        .units_per_cube(4)
+        .round_robin()
+        .build_with_limits(limits)
+        .unwrap();
+    let handle = client.create(u32::as_bytes(&vec![0; num_cubes]));
+
+    kernel_visit_logical_cubes::launch::<R>(
+        client,
+        config.cube_count.clone(),
+        config.cube_dim,
+        unsafe { ArrayArg::from_raw_parts::<u32>(&handle, num_cubes, 1) },
+        config.num_logical_cubes_arg(),
+    );
+
+    let actual = client.read_one(handle);
+    let actual = u32::from_bytes(&actual);
+    let missed = actual.iter().position(|visits| *visits != 1);
+    assert_eq!(
+        missed, None,
+        "every logical cube should be visited once, with {num_cubes} cubes in {limits:?}"
+    );
+}
+
+/// Counts just past the limits of the x axis, of the x and y axes and of the whole grid, whether
+/// the limits are those of the runtime or small ones.
+pub fn test_kernel_topology_logical_cube_pos<R: Runtime>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let atomic = StorageType::Atomic(u32::as_type_native_unchecked().elem_type());
+    if !client
+        .properties()
+        .type_usage(atomic)
+        .contains(TypeUsage::AtomicAdd)
+    {
+        println!(
+            "{} Add not supported - skipped",
+            Atomic::<u32>::as_type_native_unchecked()
+        );
+        return;
+    }
+
+    let (max_x, max_y, max_z) = R::max_cube_count();
+    // The limit of the x axis of CUDA is too large to fill it, so it's clamped to the one of wgpu.
+    let limits = (max_x.min(u16::MAX as u32), max_y, max_z);
+    visit_logical_cubes::<R>(&client, limits.0 as usize + 1, limits);
+
+    let limits = (8, 4, 2);
+    for num_cubes in [9, 8 * 4 + 1, 8 * 4 * 2 + 1, 8 * 4 * 2 * 3 + 5] {
+        visit_logical_cubes::<R>(&client, num_cubes, limits);
+    }
+}
+
 #[allow(missing_docs)]
 #[macro_export]
 macro_rules! testgen_topology {
@@ -47,5 +120,13 @@ macro_rules! testgen_topology {
                 client,
             );
         }
+
+        #[test]
+        fn test_topology_logical_cube_pos() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::topology::test_kernel_topology_logical_cube_pos::<
+                TestRuntime,
+            >(client);
+        }
     };
 }
@@ -0,0 +1,307 @@
+use crate::{self as cubecl, LaunchShape, as_bytes};
+
+use cubecl::prelude::*;
+
+#[cube(launch)]
+pub fn kernel_copy_lines<F: Float>(input: &Tensor<Line<F>>, output: &mut Tensor<Line<F>>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS];
+    }
+}
+
+#[cube(launch)]
+pub fn kernel_depth_stride<F: Float>(input: &Tensor<F>, output: &mut Tensor<F>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS * input.stride(2)];
+    }
+}
+
+/// Reads both tensors at the same line of a row, so both must have the same line size.
+#[cube(launch, uniform_line_size)]
+pub fn kernel_copy_rows<F: Float>(input: &Tensor<Line<F>>, output: &mut Tensor<Line<F>>) {
+    let line_size = output.line_size();
+    let lines_per_row = output.shape(1) / line_size;
+    let row = ABSOLUTE_POS / lines_per_row;
+    let col = ABSOLUTE_POS % lines_per_row;
+
+    if row < output.shape(0) {
+        output[row * output.stride(0) / line_size + col] =
+            input[row * input.stride(0) / line_size + col];
+    }
+}
+
+pub fn test_launch_checked_valid<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.create(as_bytes![F: 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    let output = client.empty(8 * size_of::<F>());
+
+    let result = unsafe {
+        kernel_copy_lines::launch_checked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(2),
+            TensorArg::from_raw_parts::<F>(&input, &[4, 1], &[2, 4], 4),
+            TensorArg::from_raw_parts::<F>(&output, &[4, 1], &[2, 4], 4),
+        )
+    };
+
+    assert!(result.is_ok(), "{:?}", result.unwrap_err());
+    let actual = client.read_one(output);
+    assert_eq!(
+        F::from_bytes(&actual),
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0].map(F::new)
+    );
+}
+
+pub fn test_launch_checked_elem_size<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.empty(4 * size_of::<F>());
+    let output = client.empty(4 * size_of::<F>());
+
+    let result = unsafe {
+        kernel_copy_lines::launch_checked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            TensorArg::from_raw_parts::<u64>(&input, &[1], &[4], 1),
+            TensorArg::from_raw_parts::<F>(&output, &[1], &[4], 1),
+        )
+    };
+
+    assert!(matches!(
+        result,
+        Err(LaunchError::ElemSize { arg: "input", expected, actual: 8 }) if expected == size_of::<F>()
+    ));
+}
+
+pub fn test_launch_checked_unaligned_lines<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.empty(12 * size_of::<F>());
+    let output = client.empty(12 * size_of::<F>());
+
+    // Rows of 6 elements can't be read in lines of 4.
+    let result = unsafe {
+        kernel_copy_lines::launch_checked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(3),
+            TensorArg::from_raw_parts::<F>(&input, &[6, 1], &[2, 6], 4),
+            TensorArg::from_raw_parts::<F>(&output, &[6, 1], &[2, 6], 4),
+        )
+    };
+
+    assert!(matches!(
+        result,
+        Err(LaunchError::UnalignedLines {
+            arg: "input",
+            line_size: 4,
+            ..
+        })
+    ));
+}
+
+pub fn test_launch_checked_rank<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.empty(4 * size_of::<F>());
+    let output = client.empty(4 * size_of::<F>());
+
+    // The kernel reads the stride of the third dimension of the input.
+    let result = unsafe {
+        kernel_depth_stride::launch_checked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            TensorArg::from_raw_parts::<F>(&input, &[2, 1], &[2, 2], 1),
+            TensorArg::from_raw_parts::<F>(&output, &[1], &[4], 1),
+        )
+    };
+
+    assert!(matches!(
+        result,
+        Err(LaunchError::Rank {
+            arg: "input",
+            min_rank: 3,
+            rank: 2
+        })
+    ));
+}
+
+pub fn test_launch_checked_mismatched_ranks<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.empty(4 * size_of::<F>());
+    let output = client.empty(4 * size_of::<F>());
+
+    let result = unsafe {
+        kernel_copy_lines::launch_checked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(4),
+            TensorArg::from_raw_parts::<F>(&input, &[1], &[4], 1),
+            TensorArg::from_raw_parts::<F>(&output, &[2, 1], &[4], 1),
+        )
+    };
+
+    assert!(matches!(
+        result,
+        Err(LaunchError::MismatchedRanks {
+            arg: "output",
+            shape: 1,
+            strides: 2
+        })
+    ));
+}
+
+pub fn test_launch_checked_mixed_line_sizes<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = client.empty(12 * size_of::<F>());
+    let output = client.empty(18 * size_of::<F>());
+
+    let result = unsafe {
+        kernel_copy_rows::launch_checked::<F, R>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(6),
+            TensorArg::from_raw_parts::<F>(&input, &[4, 1], &[3, 4], 4),
+            TensorArg::from_raw_parts::<F>(&output, &[6, 1], &[3, 4], 2),
+        )
+    };
+
+    match result {
+        Err(LaunchError::LineSizes { mismatches }) => assert_eq!(
+            mismatches,
+            [LineSizeMismatch {
+                arg: "input",
+                expected: 2,
+                actual: 4
+            }]
+        ),
+        result => panic!("Expected mismatched line sizes, got {result:?}"),
+    }
+}
+
+pub fn test_launch_uniform_line_size<R: Runtime, F: Float + CubeElement>(
+    client: ComputeClient<R::Server, R::Channel>,
+) {
+    let input = (0..12).map(|i| F::new(i as f32)).collect::<Vec<_>>();
+    let input = client.create(F::as_bytes(&input));
+    let output = client.create(F::as_bytes(&[F::new(-1.0); 18]));
+    let (input_shape, input_strides) = ([3, 4], [4, 1]);
+    let (output_shape, output_strides) = ([3, 4], [6, 1]);
+
+    // The rows of the output are padded to 6 elements, so both are read in lines of 2.
+    let config = LaunchShape::elementwise(12)
+        .uniform_line_size(
+            R::line_size_type(&F::as_type_native_unchecked()),
+            [
+                (&input_shape[..], &input_strides[..]),
+                (&output_shape[..], &output_strides[..]),
+            ],
+        )
+        .units_per_cube(8)
+        .build::<R>()
+        .unwrap();
+    assert_eq!(config.line_size, 2);
+
+    let result = unsafe {
+        kernel_copy_rows::launch_checked::<F, R>(
+            &client,
+            config.cube_count,
+            config.cube_dim,
+            TensorArg::from_raw_parts::<F>(&input, &input_strides, &input_shape, config.line_size),
+            TensorArg::from_raw_parts::<F>(
+                &output,
+                &output_strides,
+                &output_shape,
+                config.line_size,
+            ),
+        )
+    };
+
+    assert!(result.is_ok(), "{:?}", result.unwrap_err());
+    let actual = client.read_one(output);
+    assert_eq!(
+        F::from_bytes(&actual),
+        [
+            0.0, 1.0, 2.0, 3.0, -1.0, -1.0, 4.0, 5.0, 6.0, 7.0, -1.0, -1.0, 8.0, 9.0, 10.0, 11.0,
+            -1.0, -1.0
+        ]
+        .map(F::new)
+    );
+}
+
+#[allow(missing_docs)]
+#[macro_export]
+macro_rules! testgen_validation {
+    () => {
+        use super::*;
+
+        #[test]
+        fn test_launch_checked_valid() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_checked_valid::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_elem_size() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_checked_elem_size::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_unaligned_lines() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_checked_unaligned_lines::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_rank() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_checked_rank::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_mixed_line_sizes() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_checked_mixed_line_sizes::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_uniform_line_size() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_uniform_line_size::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+
+        #[test]
+        fn test_launch_checked_mismatched_ranks() {
+            let client = TestRuntime::client(&Default::default());
+            cubecl_core::runtime_tests::validation::test_launch_checked_mismatched_ranks::<
+                TestRuntime,
+                FloatType,
+            >(client);
+        }
+    };
+}
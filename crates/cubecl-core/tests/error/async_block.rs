@@ -0,0 +1,9 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn async_block(x: u32) {
+    let _value = async { x };
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn let_else(x: Option<u32>) {
+    let Some(_y) = x else { return };
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn runtime_comptime_assert(size: u32) {
+    comptime_assert!(size % 4 == 0, "The size {} should be a multiple of 4", size);
+}
+
+fn main() {}
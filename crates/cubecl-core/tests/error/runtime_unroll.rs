@@ -0,0 +1,10 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn runtime_unroll(unroll: bool) {
+    #[unroll(unroll)]
+    for _i in 0..4 {}
+}
+
+fn main() {}
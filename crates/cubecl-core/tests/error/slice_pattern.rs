@@ -0,0 +1,9 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn slice_pattern(values: [u32; 2]) {
+    let [_a, _b] = values;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+
+#[cube]
+fn unsupported_macro(x: u32) {
+    println!("{}", x);
+}
+
+fn main() {}
@@ -208,7 +208,8 @@ fn cast_to_scale<D: Dialect>(
     })
 }
 
-/// Convert any float to fp8 (except e8m0)
+/// Convert any float to fp8 (except e8m0), saturating out of range values to the max normal as
+/// specified by OCP. NaN values stay NaN.
 fn cast_to_fp8<D: Dialect>(
     f: &mut fmt::Formatter,
     input: Variable<D>,
@@ -240,7 +241,7 @@ fn cast_to_fp8<D: Dialect>(
 
         write!(
             f,
-            "__nv_cvt_{in_ty}_to_{out_ty}({in_value}, __NV_NOSAT, __NV_{interpretation})",
+            "__nv_cvt_{in_ty}_to_{out_ty}({in_value}, __NV_SATFINITE, __NV_{interpretation})",
         )
     })
 }
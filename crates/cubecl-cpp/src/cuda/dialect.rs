@@ -1,6 +1,6 @@
 use std::{collections::HashSet, marker::PhantomData};
 
-use cubecl_core::ir::{Id, Processor};
+use cubecl_core::ir::{CacheHint, Id, Processor};
 
 use crate::{
     Dialect,
@@ -388,6 +388,17 @@ impl<M: DialectWmmaCompiler<Self>> DialectCubeBuiltins<Self> for CudaDialect<M>
 // Instructions
 
 impl<M: DialectWmmaCompiler<Self>> DialectInstructions<Self> for CudaDialect<M> {
+    // cache hints
+    fn cache_hint_load_intrinsic(hint: CacheHint) -> Option<&'static str> {
+        match hint {
+            CacheHint::Default => None,
+            // `ld.global.cs`, evict first.
+            CacheHint::Streaming => Some("__ldcs"),
+            // `ld.global.ca`, cache at all levels.
+            CacheHint::Persisting => Some("__ldca"),
+        }
+    }
+
     // sync
     fn compile_instruction_sync_threads(f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "__syncthreads();\n")
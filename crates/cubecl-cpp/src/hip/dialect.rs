@@ -24,6 +24,7 @@ use super::Extension;
 use super::arch::AMDArchitecture;
 use super::extension::{WmmaExtension, format_f162bf16, format_max, format_min};
 use super::mma::{WmmaCast, WmmaExecute, WmmaFill, WmmaIntrinsicCompiler, WmmaLoad, WmmaStore};
+use super::warp::{self, Combine};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct HipDialect<M> {
@@ -36,7 +37,56 @@ impl<M: DialectWmmaCompiler<Self>> Dialect for HipDialect<M> {
     type Architecture = AMDArchitecture;
 }
 
-impl<M: DialectWmmaCompiler<Self>> DialectWarpReduceCompiler<Self> for HipDialect<M> {}
+// Lowers the reductions and broadcasts to the cross-lane intrinsics of AMD GPUs, the scans
+// keeping the generic shuffles.
+impl<M: DialectWmmaCompiler<Self>> DialectWarpReduceCompiler<Self> for HipDialect<M> {
+    fn warp_reduce_sum(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<Self>,
+        out: &Variable<Self>,
+    ) -> core::fmt::Result {
+        let combine = Combine::Operator {
+            op: "+=",
+            identity: "0",
+        };
+        warp::reduce(f, input, out, combine)
+    }
+    fn warp_reduce_prod(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<Self>,
+        out: &Variable<Self>,
+    ) -> core::fmt::Result {
+        let combine = Combine::Operator {
+            op: "*=",
+            identity: "1",
+        };
+        warp::reduce(f, input, out, combine)
+    }
+    fn warp_reduce_max(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<Self>,
+        out: &Variable<Self>,
+    ) -> core::fmt::Result {
+        let max = Combine::Comparison(Self::compile_instruction_max_function_name);
+        warp::reduce(f, input, out, max)
+    }
+    fn warp_reduce_min(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<Self>,
+        out: &Variable<Self>,
+    ) -> core::fmt::Result {
+        let min = Combine::Comparison(Self::compile_instruction_min_function_name);
+        warp::reduce(f, input, out, min)
+    }
+    fn warp_broadcast(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<Self>,
+        out: &Variable<Self>,
+        id: &Variable<Self>,
+    ) -> core::fmt::Result {
+        warp::broadcast(f, input, out, id)
+    }
+}
 
 // Includes
 
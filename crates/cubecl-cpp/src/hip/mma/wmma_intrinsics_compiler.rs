@@ -62,6 +62,10 @@ impl<D: Dialect> WmmaFill<D> {
         let elem = self.frag.elem;
         let frag = self.frag;
         let name = self.fn_name();
+        let length = match frag.warp_size {
+            MFMA_WARP_SIZE => 4,
+            _ => 8,
+        };
 
         write!(
             f,
@@ -69,7 +73,7 @@ impl<D: Dialect> WmmaFill<D> {
 // Fill the fragment.
 __device__ void {name}({frag}& frag, {elem} value) {{
     #pragma unroll
-    for (uint i = 0; i < 8; ++i) {{
+    for (uint i = 0; i < {length}; ++i) {{
       frag[i] = value;
     }}
 }}
@@ -114,6 +118,10 @@ impl<D: Dialect> WmmaLoad<D> {
     /// VGPR7      | 15,1 | 15,2 | 15,3 | 15,4 | ...  | 15,13| 15,14| 15,15| ...  | 16,1 | 16,2 | ...  | 16,15| 16,16|
     /// --------------------------------------------------------------------------------------------------------------
     pub fn format_extension(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.frag.warp_size == MFMA_WARP_SIZE {
+            return self.format_mfma_extension(f);
+        }
+
         let elem = self.frag.elem;
         let frag = self.frag;
         let name = self.fn_name();
@@ -166,6 +174,50 @@ __device__ void {name}({frag}& frag, const {elem}* value_ptr, const uint stride)
       const uint index = {index_body};
       frag[i * {step}] = value_ptr[index];
     }}
+}}
+        "
+        )
+    }
+
+    /// Each lane holds 4 consecutive elements along `k` for A and B, and along `m` for C and D.
+    /// The lanes `16 * j` to `16 * j + 15` hold the elements `4 * j` to `4 * j + 3`.
+    ///
+    /// Lane index   0      1      ...    15     16     17     ...    31     ...    48     ...    63
+    /// ---------------------------------------------------------------------------------------------
+    /// A[0]       | 0,0  | 1,0  | ...  | 15,0 | 0,4  | 1,4  | ...  | 15,4 | ...  | 0,12 | ...  | 15,12 |
+    /// A[1]       | 0,1  | 1,1  | ...  | 15,1 | 0,5  | 1,5  | ...  | 15,5 | ...  | 0,13 | ...  | 15,13 |
+    /// ...
+    /// D[0]       | 0,0  | 0,1  | ...  | 0,15 | 4,0  | 4,1  | ...  | 4,15 | ...  | 12,0 | ...  | 12,15 |
+    /// D[1]       | 1,0  | 1,1  | ...  | 1,15 | 5,0  | 5,1  | ...  | 5,15 | ...  | 13,0 | ...  | 13,15 |
+    /// ...
+    fn format_mfma_extension(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let elem = self.frag.elem;
+        let frag = self.frag;
+        let name = self.fn_name();
+
+        let (row, col, layout) = match frag.ident {
+            FragmentIdent::A => ("mfmaLane", "mfmaGroup * 4 + i", frag.layout),
+            FragmentIdent::B => ("mfmaGroup * 4 + i", "mfmaLane", frag.layout),
+            FragmentIdent::Accumulator => ("mfmaGroup * 4 + i", "mfmaLane", self.layout),
+            other => panic!("unknown matrix identifier {other}"),
+        };
+        let index = match layout {
+            Some(FragmentLayout::RowMajor) => format!("({row}) * stride + {col}"),
+            Some(FragmentLayout::ColMajor) => format!("({col}) * stride + {row}"),
+            _ => panic!("cannot load data to a fragment without knowing the layout of the data"),
+        };
+
+        write!(
+            f,
+            "
+// Load the fragment.
+__device__ void {name}({frag}& frag, const {elem}* value_ptr, const uint stride) {{
+    {MFMA_LANE_DEF}
+
+    #pragma unroll
+    for (uint i = 0; i < 4; ++i) {{
+      frag[i] = value_ptr[{index}];
+    }}
 }}
         "
         )
@@ -185,6 +237,10 @@ impl<D: Dialect> WmmaStore<D> {
     }
 
     pub fn format_extension(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.frag.warp_size == MFMA_WARP_SIZE {
+            return self.format_mfma_extension(f);
+        }
+
         let elem = self.frag.elem;
         let frag = self.frag;
         let name = self.fn_name();
@@ -217,6 +273,34 @@ __device__ void {name}({frag}& frag, {elem}* output_ptr, uint stride) {{
       const uint rowIdx = elemIdx * uint(2) + threadIdx.x / uint(16);
       output_ptr[{output_idx}] = frag[{frag_idx}];
     }}
+}}
+        "
+        )
+    }
+
+    /// See [WmmaLoad::format_mfma_extension] for the layout of the accumulator.
+    fn format_mfma_extension(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let elem = self.frag.elem;
+        let frag = self.frag;
+        let name = self.fn_name();
+        let output_idx = match self.layout {
+            FragmentLayout::ColMajor => "mfmaLane * stride + rowIdx",
+            FragmentLayout::RowMajor => "mfmaLane + rowIdx * stride",
+            FragmentLayout::_Dialect(_) => "",
+        };
+
+        write!(
+            f,
+            "
+// Store the fragment.
+__device__ void {name}({frag}& frag, {elem}* output_ptr, uint stride) {{
+    {MFMA_LANE_DEF}
+
+    #pragma unroll
+    for (uint elemIdx = 0; elemIdx < uint(4); ++elemIdx) {{
+      const uint rowIdx = mfmaGroup * uint(4) + elemIdx;
+      output_ptr[{output_idx}] = frag[elemIdx];
+    }}
 }}
         "
         )
@@ -232,6 +316,7 @@ impl<D: Dialect> WmmaExecute<D> {
             k: shape.k,
             elem: ab_elem,
             layout: Some(FragmentLayout::ColMajor),
+            warp_size: 32,
         };
         let frag_b = Fragment {
             ident: FragmentIdent::B,
@@ -254,6 +339,10 @@ impl<D: Dialect> WmmaExecute<D> {
     }
 
     pub fn format_extension(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.frag_a.warp_size == MFMA_WARP_SIZE {
+            return self.format_mfma_extension(f);
+        }
+
         let name = self.fn_name();
         let ab_format = match self.frag_a.elem {
             Elem::F32 => "f32",
@@ -278,6 +367,34 @@ __device__ void {name}(const {}& frag_a, const {}& frag_b, const {}& frag_c, {}&
         ", self.frag_a, self.frag_b, self.frag_c, self.frag_d
         )
     }
+
+    fn format_mfma_extension(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = self.fn_name();
+        // The bf16 intrinsic takes the bits of the inputs as shorts.
+        let (intrinsic, frag_a, frag_b) = match (self.frag_a.elem, self.frag_c.elem) {
+            (Elem::F16, Elem::F32) => (
+                "__builtin_amdgcn_mfma_f32_16x16x16f16",
+                "frag_a".to_string(),
+                "frag_b".to_string(),
+            ),
+            (Elem::BF16, Elem::F32) => (
+                "__builtin_amdgcn_mfma_f32_16x16x16bf16_1k",
+                "__builtin_bit_cast(short4_t, frag_a)".to_string(),
+                "__builtin_bit_cast(short4_t, frag_b)".to_string(),
+            ),
+            (ab, cd) => panic!("unsupported mfma from {ab} to {cd}"),
+        };
+        write!(
+            f,
+            "
+// Execute mfma.
+__device__ void {name}(const {}& frag_a, const {}& frag_b, const {}& frag_c, {}& frag_d) {{
+    frag_d = {intrinsic}({frag_a}, {frag_b}, frag_c, 0, 0, 0);
+}}
+        ",
+            self.frag_a, self.frag_b, self.frag_c, self.frag_d
+        )
+    }
 }
 
 impl<D: Dialect> WmmaCast<D> {
@@ -295,11 +412,13 @@ impl<D: Dialect> WmmaCast<D> {
         let input = self.frag_input;
         let output = self.frag_output;
         let name = self.fn_name();
-        let step = match output.ident {
-            FragmentIdent::Accumulator => {
-                get_output_accumulator_index_step(&self.frag_input.elem, &output)
-            }
-            _ => 1,
+        let (length, step) = match output.ident {
+            _ if output.warp_size == MFMA_WARP_SIZE => (4, 1),
+            FragmentIdent::Accumulator => (
+                8,
+                get_output_accumulator_index_step(&self.frag_input.elem, &output),
+            ),
+            _ => (8, 1),
         };
 
         write!(
@@ -308,7 +427,7 @@ impl<D: Dialect> WmmaCast<D> {
 // Cast the fragment.
 __device__ void {name}({input}& input, {output}& output) {{
     #pragma unroll
-    for (uint elemIdx = 0; elemIdx < uint(8); ++elemIdx) {{
+    for (uint elemIdx = 0; elemIdx < uint({length}); ++elemIdx) {{
       output[elemIdx * {step}] = input[elemIdx];
     }}
 }}
@@ -325,11 +444,16 @@ impl DialectWmmaCompiler<HipDialect<Self>> for WmmaIntrinsicCompiler {
         if flags.elem_bf16 {
             f.write_str("typedef __bf16 bhalf8_t __attribute__((ext_vector_type(8)));\n")?;
             f.write_str("typedef __bf16 bhalf16_t __attribute__((ext_vector_type(16)));\n")?;
+            // Fragments of mfma
+            f.write_str("typedef __bf16 bhalf4_t __attribute__((ext_vector_type(4)));\n")?;
+            f.write_str("typedef short short4_t __attribute__((ext_vector_type(4)));\n")?;
         }
         if flags.elem_f16 {
             f.write_str("typedef _Float16 half8_t __attribute__((ext_vector_type(8)));\n")?;
             f.write_str("typedef _Float16 half16_t __attribute__((ext_vector_type(16)));\n")?;
+            f.write_str("typedef _Float16 half4_t __attribute__((ext_vector_type(4)));\n")?;
         }
+        f.write_str("typedef float float4_t __attribute__((ext_vector_type(4)));\n")?;
         f.write_str("typedef float float8_t __attribute__((ext_vector_type(8)));\n")
     }
 
@@ -344,6 +468,16 @@ impl DialectWmmaCompiler<HipDialect<Self>> for WmmaIntrinsicCompiler {
         f: &mut std::fmt::Formatter<'_>,
         fragment: &Fragment<HipDialect<Self>>,
     ) -> std::fmt::Result {
+        if fragment.warp_size == MFMA_WARP_SIZE {
+            return match (fragment.ident, fragment.elem) {
+                (FragmentIdent::_Dialect(_), _) => Ok(()),
+                (_, Elem::F16) => write!(f, "half4_t"),
+                (_, Elem::BF16) => write!(f, "bhalf4_t"),
+                (FragmentIdent::Accumulator, Elem::F32) => write!(f, "float4_t"),
+                (_, other) => panic!("unsupported type {other} for {fragment}"),
+            };
+        }
+
         match fragment.ident {
             FragmentIdent::A | FragmentIdent::B => match fragment.elem {
                 Elem::F16 => write!(f, "half16_t"),
@@ -392,7 +526,10 @@ impl DialectWmmaCompiler<HipDialect<Self>> for WmmaIntrinsicCompiler {
                 frag_d,
                 warp_size,
             } => {
-                assert_eq!(*warp_size, 32, "Only warp size of 32 supported");
+                assert!(
+                    [32, MFMA_WARP_SIZE].contains(warp_size),
+                    "Only warp sizes of 32 and 64 are supported"
+                );
 
                 let extension = WmmaExecute::new(
                     variable_to_frag(frag_a),
@@ -500,6 +637,28 @@ impl DialectWmmaCompiler<HipDialect<Self>> for WmmaIntrinsicCompiler {
                 .collect();
             result.extend(combinations);
         }
+        if arch.is_mfma_capable() {
+            // Reference: AMD matrix instruction calculator
+            // The bf16 mfma with a k of 16 was introduced with gfx90a.
+            let mut types = vec![(
+                gpu::ElemType::Float(gpu::FloatKind::F16),
+                gpu::ElemType::Float(gpu::FloatKind::F32),
+            )];
+            if !matches!(arch, AMDArchitecture::GFX908) {
+                types.push((
+                    gpu::ElemType::Float(gpu::FloatKind::BF16),
+                    gpu::ElemType::Float(gpu::FloatKind::F32),
+                ));
+            }
+            result.extend(types.into_iter().map(|(ab_elem, cd_elem)| MmaConfig {
+                a_type: ab_elem.into(),
+                b_type: ab_elem.into(),
+                cd_type: cd_elem.into(),
+                m: 16,
+                n: 16,
+                k: 16,
+            }));
+        }
         result
     }
 
@@ -616,3 +775,10 @@ pub(super) fn supported_mma_combinations(arch: &AMDArchitecture) -> SupportedMma
 // in other words fragments are duplicated
 // so lanes 0,16 / 1,17 / ... / 15, 31 are the same
 static WMMA_LANE_DEF: &str = "uint wmmaLane = uint(threadIdx.x % 16);";
+
+/// The wavefront size of CDNA, where matrix instructions are mfma instead of wmma.
+const MFMA_WARP_SIZE: u32 = 64;
+
+// the wavefront is split in 4 groups of 16 lanes, each group holding different elements
+static MFMA_LANE_DEF: &str =
+    "uint mfmaLane = uint(threadIdx.x % 16); uint mfmaGroup = uint((threadIdx.x % 64) / 16);";
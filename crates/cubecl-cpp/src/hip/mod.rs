@@ -2,6 +2,7 @@ pub mod arch;
 pub mod dialect;
 mod extension;
 pub mod processors;
+mod warp;
 
 pub use dialect::*;
 use extension::*;
@@ -0,0 +1,157 @@
+use std::fmt::Display;
+
+use crate::{
+    Dialect,
+    shared::{Elem, FmtLeft, Item, Variable, reduce_broadcast, reduce_comparison, reduce_operator},
+};
+
+/// The DPP controls of the steps combining each lane with a lane of its row of 16 lanes.
+///
+/// Like the steps of a butterfly, each step pairs lanes holding the results of disjoint groups
+/// of lanes: the quad permutations swap the neighbors at a distance of 1 and 2, and the
+/// mirrors pair the halves of the groups of 8 and 16 lanes.
+const ROW_STEPS: [(u32, &str); 4] = [
+    (0xB1, "quad_perm:[1,0,3,2]"),
+    (0x4E, "quad_perm:[2,3,0,1]"),
+    (0x141, "row_half_mirror"),
+    (0x140, "row_mirror"),
+];
+
+/// How the partial results of two lanes are combined.
+pub(crate) enum Combine<D: Dialect> {
+    /// With an operator, the lanes without a valid source contributing the identity.
+    Operator {
+        op: &'static str,
+        identity: &'static str,
+    },
+    /// With the comparison function written for the item, the lanes without a valid source
+    /// contributing their own result.
+    Comparison(ItemFunction<D>),
+}
+
+type ItemFunction<D> = fn(&mut core::fmt::Formatter<'_>, Item<D>) -> std::fmt::Result;
+
+/// Whether the element is handled by the 32-bit cross-lane intrinsics.
+fn is_lane_sized<D: Dialect>(elem: Elem<D>) -> bool {
+    matches!(elem, Elem::F32 | Elem::I32 | Elem::U32)
+}
+
+/// Reduce the plane with DPP within the rows of 16 lanes and with shuffles across them, up to
+/// the plane dimension, for both wave32 and wave64. The result is uniform, so it is read from
+/// the first active lane to live in a scalar register.
+///
+/// Lanes read by DPP that are inactive or past the plane dimension keep the `old` operand, so
+/// they don't contribute to the result. Elements that aren't 32-bit use the generic ladder.
+pub(crate) fn reduce<D: Dialect>(
+    f: &mut core::fmt::Formatter<'_>,
+    input: &Variable<D>,
+    out: &Variable<D>,
+    combine: Combine<D>,
+) -> core::fmt::Result {
+    let item = input.item();
+    let elem = item.elem;
+    if !is_lane_sized(elem) || out.item() != item {
+        return match combine {
+            Combine::Operator { op, .. } => reduce_operator(f, input, out, op),
+            Combine::Comparison(function) => reduce_comparison(f, input, out, function),
+        };
+    }
+
+    writeln!(f, "auto plane_{out} = [&]() -> {item} {{")?;
+    writeln!(f, "    {item} acc = {input};")?;
+    for k in 0..item.vectorization {
+        let acc = match item.vectorization {
+            1 => "acc".to_string(),
+            _ => format!("acc.i_{k}"),
+        };
+        let old = match &combine {
+            Combine::Operator { identity, .. } => format!("{elem}({identity})"),
+            Combine::Comparison(_) => acc.clone(),
+        };
+
+        for (control, pattern) in ROW_STEPS {
+            let partner = format!(
+                "__builtin_bit_cast({elem}, __builtin_amdgcn_update_dpp(\
+                 __builtin_bit_cast(int, {old}), __builtin_bit_cast(int, {acc}), \
+                 {control:#x}, 0xF, 0xF, false))"
+            );
+            write!(f, "    // {pattern}\n    ")?;
+            combine_lanes(f, &combine, item, &acc, &partner)?;
+        }
+        write!(f, "    for (uint offset = 16; offset < ")?;
+        D::compile_plane_dim_checked(f)?;
+        writeln!(f, "; offset *= 2) {{")?;
+        write!(f, "        ")?;
+        let partner = format!("__shfl_xor({acc}, offset)");
+        combine_lanes(f, &combine, item, &acc, &partner)?;
+        writeln!(f, "    }}")?;
+        writeln!(
+            f,
+            "    {acc} = __builtin_bit_cast({elem}, \
+             __builtin_amdgcn_readfirstlane(__builtin_bit_cast(int, {acc})));"
+        )?;
+    }
+    writeln!(f, "    return acc;")?;
+    writeln!(f, "}};")?;
+    writeln!(f, "{} = plane_{out}();", out.fmt_left())
+}
+
+fn combine_lanes<D: Dialect>(
+    f: &mut core::fmt::Formatter<'_>,
+    combine: &Combine<D>,
+    item: Item<D>,
+    acc: &str,
+    partner: &str,
+) -> core::fmt::Result {
+    match combine {
+        Combine::Operator { op, .. } => writeln!(f, "{acc} {op} {partner};"),
+        Combine::Comparison(function) => {
+            write!(f, "{acc} = ")?;
+            function(f, item)?;
+            writeln!(f, "({acc}, {partner});")
+        }
+    }
+}
+
+/// Broadcast the value of a lane, with `readlane` when the lane is a constant and is
+/// therefore uniform, and with `ds_bpermute` otherwise. Elements that aren't 32-bit use the
+/// generic shuffles.
+pub(crate) fn broadcast<D: Dialect>(
+    f: &mut core::fmt::Formatter<'_>,
+    input: &Variable<D>,
+    out: &Variable<D>,
+    id: &Variable<D>,
+) -> core::fmt::Result {
+    let elem = input.item().elem;
+    if !is_lane_sized(elem) {
+        return reduce_broadcast(f, input, out, id);
+    }
+
+    let out_fmt = out.fmt_left();
+    write!(f, "{out_fmt} = {{ ")?;
+    for i in 0..input.item().vectorization {
+        let comma = if i > 0 { ", " } else { "" };
+        let value = Bits(input.index(i));
+        match id {
+            Variable::ConstantScalar(..) => write!(
+                f,
+                "{comma}__builtin_bit_cast({elem}, __builtin_amdgcn_readlane({value}, {id}))"
+            )?,
+            _ => write!(
+                f,
+                "{comma}__builtin_bit_cast({elem}, \
+                 __builtin_amdgcn_ds_bpermute(int({id}) << 2, {value}))"
+            )?,
+        }
+    }
+    writeln!(f, " }};")
+}
+
+/// The bits of a 32-bit value, as expected by the cross-lane intrinsics.
+struct Bits<T: Display>(T);
+
+impl<T: Display> Display for Bits<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "__builtin_bit_cast(int, {})", self.0)
+    }
+}
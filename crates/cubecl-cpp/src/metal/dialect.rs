@@ -21,7 +21,8 @@ use super::{
     AddressSpace, Extension,
     arch::MetalArchitecture,
     extension::{format_ffs, format_mulhi},
-    format_erf, format_global_binding_arg, format_metal_builtin_binding_arg, format_safe_tanh,
+    format_erf, format_erfc, format_global_binding_arg, format_metal_builtin_binding_arg,
+    format_safe_tanh,
 };
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
@@ -147,6 +148,7 @@ using namespace metal;
         for extension in extensions {
             match extension {
                 Extension::Erf(input, output) => format_erf::<Self>(f, input, output)?,
+                Extension::Erfc(input, output) => format_erfc::<Self>(f, input, output)?,
                 Extension::Ffs(elem) => format_ffs(f, elem)?,
                 Extension::MulHi(elem) => format_mulhi(f, elem)?,
                 Extension::SafeTanh(item) => format_safe_tanh::<Self>(f, item)?,
@@ -173,6 +175,12 @@ using namespace metal;
                     instruction.out.elem(),
                 ));
             }
+            shared::Instruction::<Self>::Erfc(instruction) => {
+                register_extension(Extension::Erfc(
+                    instruction.input.elem(),
+                    instruction.out.elem(),
+                ));
+            }
             shared::Instruction::<Self>::FindFirstSet(instruction) => {
                 let input_elem = instruction.input.elem();
                 match input_elem {
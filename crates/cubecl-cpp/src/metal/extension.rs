@@ -7,6 +7,7 @@ use crate::{
 #[derive(Debug, Clone, Default, PartialEq)]
 pub enum Extension<D: Dialect> {
     Erf(Elem<D>, Elem<D>),
+    Erfc(Elem<D>, Elem<D>),
     Ffs(Elem<D>),
     MulHi(Elem<D>),
     SafeTanh(Item<D>),
@@ -40,6 +41,32 @@ inline {out_elem} erf({input_elem} x) {{
     )
 }
 
+pub fn format_erfc<D: Dialect>(
+    f: &mut core::fmt::Formatter<'_>,
+    input_elem: &Elem<D>,
+    out_elem: &Elem<D>,
+) -> core::fmt::Result {
+    write!(
+        f,
+        "
+// Abramowitz and Stegun approximation for erfc(x), computed directly to keep the small values of
+// large inputs instead of cancelling them in 1 - erf(x)
+inline {out_elem} erfc({input_elem} x) {{
+    const float a1 =  0.254829592f;
+    const float a2 = -0.284496736f;
+    const float a3 =  1.421413741f;
+    const float a4 = -1.453152027f;
+    const float a5 =  1.061405429f;
+    const float p  =  0.3275911f;
+    float z = fabs(x);
+    float t = 1.0f / (1.0f + p * z);
+    float y = (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * exp(-z * z);
+    return (x >= 0.0f) ? y : 2.0f - y;
+}}
+",
+    )
+}
+
 pub fn format_ffs<D: Dialect>(
     f: &mut core::fmt::Formatter<'_>,
     input_elem: &Elem<D>,
@@ -153,10 +180,12 @@ pub fn format_safe_tanh<D: Dialect>(
     write!(
         f,
         "
-/// Metal has a weird numerical behaviour with tanh for inputs over 43.0
+/// Metal has a weird numerical behaviour with tanh for inputs over 43.0 in magnitude
 inline {elem} safe_tanh_scalar({elem} x) {{
     if (x > 43.0) {{
         return 1.0;
+    }} else if (x < -43.0) {{
+        return -1.0;
     }} else {{
         return tanh(x);
     }}
@@ -72,6 +72,77 @@ impl<D: Dialect> BarrierOps<D> {
             BarrierOps::ExpectTx { barrier, .. } => barrier.id().unwrap(),
         }
     }
+
+    pub fn barrier(&self) -> &Variable<D> {
+        match self {
+            BarrierOps::MemCopyAsync { barrier, .. } => barrier,
+            BarrierOps::Init { barrier, .. } => barrier,
+            BarrierOps::ArriveAndWait { barrier, .. } => barrier,
+            BarrierOps::Arrive { barrier, .. } => barrier,
+            BarrierOps::ArriveTx { barrier, .. } => barrier,
+            BarrierOps::Wait { barrier, .. } => barrier,
+            BarrierOps::MemCopyAsyncTensorGlobalToShared { barrier, .. } => barrier,
+            BarrierOps::TmaLoadIm2col { barrier, .. } => barrier,
+            BarrierOps::ExpectTx { barrier, .. } => barrier,
+        }
+    }
+
+    pub fn barrier_mut(&mut self) -> &mut Variable<D> {
+        match self {
+            BarrierOps::MemCopyAsync { barrier, .. } => barrier,
+            BarrierOps::Init { barrier, .. } => barrier,
+            BarrierOps::ArriveAndWait { barrier, .. } => barrier,
+            BarrierOps::Arrive { barrier, .. } => barrier,
+            BarrierOps::ArriveTx { barrier, .. } => barrier,
+            BarrierOps::Wait { barrier, .. } => barrier,
+            BarrierOps::MemCopyAsyncTensorGlobalToShared { barrier, .. } => barrier,
+            BarrierOps::TmaLoadIm2col { barrier, .. } => barrier,
+            BarrierOps::ExpectTx { barrier, .. } => barrier,
+        }
+    }
+}
+
+/// A barrier of the cube, declared at the scope of the kernel and initialized once before the
+/// body, so that barriers created in loops don't get initialized again while in use.
+///
+/// Barriers of the same level whose live ranges don't overlap share the same shared barrier.
+#[derive(Debug, Clone)]
+pub struct SharedBarrier<D: Dialect> {
+    pub barrier: Variable<D>,
+    pub level: BarrierLevel,
+    pub with_cta_fence: bool,
+}
+
+impl<D: Dialect> Display for SharedBarrier<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let barrier = &self.barrier;
+        let proxy_fence = match self.with_cta_fence {
+            true => "cuda::device::experimental::fence_proxy_async_shared_cta();",
+            false => "",
+        };
+        let elected_unit = match self.level {
+            BarrierLevel::CubeCoop(elected_unit) => {
+                writeln!(
+                    f,
+                    "cooperative_groups::thread_block block_{barrier} = cooperative_groups::this_thread_block();"
+                )?;
+                elected_unit
+            }
+            BarrierLevel::CubeManual(elected_unit) => elected_unit,
+            BarrierLevel::Unit => unreachable!("Unit barriers are local to the units"),
+        };
+        write!(
+            f,
+            "
+__shared__ cuda::barrier<cuda::thread_scope_block> {barrier};
+cuda::barrier<cuda::thread_scope_block>::arrival_token {barrier}_token;
+if (threadIdxGlobal == {elected_unit}) {{
+   init(&{barrier}, blockDimGlobal);
+   {proxy_fence}
+}}
+"
+        )
+    }
 }
 
 impl<D: Dialect> Display for BarrierOps<D> {
@@ -97,31 +168,8 @@ init(&{barrier}, 1);
 {proxy_fence}
                 "
                     ),
-                    BarrierLevel::CubeCoop(elected_unit) => write!(
-                        f,
-                        "
-cooperative_groups::thread_block block_{barrier} = cooperative_groups::this_thread_block();
-__shared__ cuda::barrier<cuda::thread_scope_block> {barrier};
-cuda::barrier<cuda::thread_scope_block>::arrival_token {barrier}_token;
-if (threadIdxGlobal == {elected_unit}) {{
-   init(&{barrier}, blockDimGlobal);
-   {proxy_fence}
-}}
-__syncthreads();
-"
-                    ),
-                    BarrierLevel::CubeManual(elected_unit) => write!(
-                        f,
-                        "
-__shared__ cuda::barrier<cuda::thread_scope_block> {barrier};
-cuda::barrier<cuda::thread_scope_block>::arrival_token {barrier}_token;
-if (threadIdxGlobal == {elected_unit}) {{
-   init(&{barrier}, blockDimGlobal);
-   {proxy_fence}
-}}
-__syncthreads();
-"
-                    ),
+                    // Barriers of the cube are initialized with the kernel instead.
+                    BarrierLevel::CubeCoop(_) | BarrierLevel::CubeManual(_) => Ok(()),
                 }
             }
             BarrierOps::MemCopyAsync {
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use cubecl_core::ir::{BarrierLevel, Id};
+
+use super::{
+    Dialect, Instruction, Variable,
+    barrier::{BarrierOps, SharedBarrier},
+};
+
+/// Allocate the barriers of the cube used by the instructions of a kernel.
+///
+/// Barriers are declared at the scope of the kernel and initialized once, removing their
+/// [init](BarrierOps::Init) from the instructions, so that a barrier created in a loop is
+/// initialized once per kernel instead of once per iteration. Barriers of the same level whose
+/// live ranges don't overlap are renamed to share the same [shared barrier](SharedBarrier), since
+/// a barrier is ready for its next phase once all its arrivals are done.
+///
+/// # Panics
+///
+/// When a barrier is initialized more than once, or in a loop while it's still used after the
+/// loop, since the barrier would then be initialized again while in use.
+pub(crate) fn allocate_barriers<D: Dialect>(
+    instructions: &mut Vec<Instruction<D>>,
+) -> Vec<SharedBarrier<D>> {
+    let mut liveness = BarrierLiveness::default();
+    liveness.visit(instructions);
+
+    let mut ranges = liveness
+        .barriers
+        .iter()
+        .map(|(id, barrier)| (*id, liveness.live_range(*id, barrier)))
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|(id, range)| (range.0, *id));
+
+    // Greedily reuse the first barrier of the same level whose live range ended
+    let mut shared = Vec::<(SharedBarrier<D>, usize)>::new();
+    let mut renames = HashMap::new();
+    for (id, (start, end)) in ranges {
+        let barrier = &liveness.barriers[&id];
+        let reusable = shared
+            .iter_mut()
+            .find(|(it, last)| it.level == barrier.level && *last < start);
+
+        match reusable {
+            Some((it, last)) => {
+                it.with_cta_fence |= barrier.with_cta_fence;
+                *last = end;
+                renames.insert(id, it.barrier);
+            }
+            None => {
+                let variable = Variable::Barrier {
+                    id,
+                    level: barrier.level,
+                };
+                let it = SharedBarrier {
+                    barrier: variable,
+                    level: barrier.level,
+                    with_cta_fence: barrier.with_cta_fence,
+                };
+                shared.push((it, end));
+                renames.insert(id, variable);
+            }
+        }
+    }
+
+    rename(instructions, &renames);
+
+    shared.into_iter().map(|(it, _)| it).collect()
+}
+
+#[derive(Default)]
+struct BarrierLiveness {
+    position: usize,
+    /// The first and last positions of each loop.
+    loops: Vec<(usize, usize)>,
+    /// The loops enclosing the current position.
+    enclosing: Vec<usize>,
+    barriers: HashMap<Id, BarrierInfo>,
+}
+
+struct BarrierInfo {
+    level: BarrierLevel,
+    with_cta_fence: bool,
+    /// The position of the init and the loops enclosing it.
+    init: Option<(usize, Vec<usize>)>,
+    /// The position of each use and the loops enclosing it.
+    uses: Vec<(usize, Vec<usize>)>,
+}
+
+impl BarrierLiveness {
+    fn visit<D: Dialect>(&mut self, instructions: &[Instruction<D>]) {
+        for instruction in instructions {
+            self.position += 1;
+
+            match instruction {
+                Instruction::Barrier(op) => self.visit_barrier(op),
+                Instruction::RangeLoop { instructions, .. }
+                | Instruction::Loop { instructions } => {
+                    let index = self.loops.len();
+                    self.loops.push((self.position, self.position));
+                    self.enclosing.push(index);
+                    self.visit(instructions);
+                    self.enclosing.pop();
+                    self.loops[index].1 = self.position;
+                }
+                Instruction::If { instructions, .. } => self.visit(instructions),
+                Instruction::IfElse {
+                    instructions_if,
+                    instructions_else,
+                    ..
+                } => {
+                    self.visit(instructions_if);
+                    self.visit(instructions_else);
+                }
+                Instruction::Switch {
+                    instructions_default,
+                    instructions_cases,
+                    ..
+                } => {
+                    for (_, instructions) in instructions_cases {
+                        self.visit(instructions);
+                    }
+                    self.visit(instructions_default);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_barrier<D: Dialect>(&mut self, op: &BarrierOps<D>) {
+        let Variable::Barrier { id, level } = op.barrier() else {
+            return;
+        };
+        if let BarrierLevel::Unit = level {
+            return;
+        }
+
+        let barrier = self.barriers.entry(*id).or_insert(BarrierInfo {
+            level: *level,
+            with_cta_fence: false,
+            init: None,
+            uses: Vec::new(),
+        });
+        let position = (self.position, self.enclosing.clone());
+
+        match op {
+            BarrierOps::Init { with_cta_fence, .. } => {
+                if barrier.init.is_some() {
+                    panic!("Barrier {id} is initialized again while in use");
+                }
+                barrier.with_cta_fence = *with_cta_fence;
+                barrier.init = Some(position);
+            }
+            _ => barrier.uses.push(position),
+        }
+    }
+
+    /// The first and last positions where the barrier is in use.
+    ///
+    /// A barrier used in a loop it's not initialized in is in use for the whole loop, since it
+    /// carries its phases across iterations.
+    fn live_range(&self, id: Id, barrier: &BarrierInfo) -> (usize, usize) {
+        let init_loops = barrier
+            .init
+            .as_ref()
+            .map(|(_, loops)| loops.as_slice())
+            .unwrap_or_default();
+        let mut start = barrier.init.as_ref().map(|(position, _)| *position);
+        let mut end = start.unwrap_or_default();
+
+        for (position, loops) in barrier.uses.iter() {
+            if init_loops.iter().any(|it| !loops.contains(it)) {
+                panic!(
+                    "Barrier {id} is initialized in a loop but used outside of it, so it would be \
+                     initialized again while in use"
+                );
+            }
+
+            let mut first = *position;
+            let mut last = *position;
+            for outer in loops.iter().filter(|it| !init_loops.contains(it)) {
+                let (loop_first, loop_last) = self.loops[*outer];
+                first = first.min(loop_first);
+                last = last.max(loop_last);
+            }
+
+            start = Some(start.map_or(first, |start| start.min(first)));
+            end = end.max(last);
+        }
+
+        (start.unwrap_or_default(), end)
+    }
+}
+
+/// Rename the barriers of the cube to their shared barrier, and remove their init.
+fn rename<D: Dialect>(instructions: &mut Vec<Instruction<D>>, renames: &HashMap<Id, Variable<D>>) {
+    instructions.retain(|instruction| {
+        !matches!(
+            instruction,
+            Instruction::Barrier(BarrierOps::Init {
+                level: BarrierLevel::CubeCoop(_) | BarrierLevel::CubeManual(_),
+                ..
+            })
+        )
+    });
+
+    for instruction in instructions.iter_mut() {
+        match instruction {
+            Instruction::Barrier(op) => {
+                let barrier = op.barrier_mut();
+                if let Some(renamed) = barrier.id().and_then(|id| renames.get(&id)) {
+                    *barrier = *renamed;
+                }
+            }
+            Instruction::RangeLoop { instructions, .. }
+            | Instruction::Loop { instructions }
+            | Instruction::If { instructions, .. } => rename(instructions, renames),
+            Instruction::IfElse {
+                instructions_if,
+                instructions_else,
+                ..
+            } => {
+                rename(instructions_if, renames);
+                rename(instructions_else, renames);
+            }
+            Instruction::Switch {
+                instructions_default,
+                instructions_cases,
+                ..
+            } => {
+                for (_, instructions) in instructions_cases {
+                    rename(instructions, renames);
+                }
+                rename(instructions_default, renames);
+            }
+            _ => {}
+        }
+    }
+}
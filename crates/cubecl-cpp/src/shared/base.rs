@@ -1,4 +1,7 @@
-use std::{collections::HashSet, fmt::Debug};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
 
 use cubecl_common::ExecutionMode;
 use cubecl_core::CubeDim;
@@ -21,7 +24,11 @@ use super::{
     Fragment, FragmentIdent, FragmentLayout, IndexAssignInstruction, IndexInstruction, Instruction,
     Item, LocalArray, SharedMemory, UnaryInstruction, Variable, WarpInstruction, WmmaInstruction,
 };
-use super::{FP4Kind, barrier::BarrierOps};
+use super::{
+    FP4Kind,
+    barrier::{BarrierOps, SharedBarrier},
+    barrier_allocation::allocate_barriers,
+};
 use super::{FP8Kind, pipeline::PipelineOps};
 
 pub(super) static COUNTER_TMP_VAR: std::sync::atomic::AtomicU32 =
@@ -91,7 +98,8 @@ pub struct Flags {
 #[allow(clippy::too_many_arguments)]
 #[derive(Clone, Debug, Default)]
 pub struct CppCompiler<D: Dialect> {
-    barriers: Vec<BarrierOps<D>>,
+    barriers: Vec<SharedBarrier<D>>,
+    cache_hints: HashMap<gpu::Id, gpu::CacheHint>,
     compilation_options: CompilationOptions,
     const_arrays: Vec<ConstArray<D>>,
     ext_meta_positions: Vec<u32>,
@@ -143,7 +151,8 @@ impl<D: Dialect> CppCompiler<D> {
     fn compile_ir(mut self, mut value: KernelDefinition) -> ComputeKernel<D> {
         self.build_metadata(&value);
 
-        let instructions = self.compile_scope(&mut value.body);
+        let mut instructions = self.compile_scope(&mut value.body);
+        self.barriers = allocate_barriers(&mut instructions);
         let buffers = value
             .buffers
             .into_iter()
@@ -208,6 +217,8 @@ impl<D: Dialect> CppCompiler<D> {
             items: self.items,
             kernel_name: value.options.kernel_name,
             cluster_dim,
+            shared_memory_carveout: value.options.shared_memory_carveout,
+            dynamic_shared_memory: value.options.dynamic_shared_memory,
         }
     }
 
@@ -418,6 +429,11 @@ impl<D: Dialect> CppCompiler<D> {
                 gpu::NonSemantic::Comment { content } => {
                     instructions.push(Instruction::Comment { content })
                 }
+                gpu::NonSemantic::CacheHint { buffer, hint } => {
+                    if let gpu::VariableKind::GlobalInputArray(id) = buffer.kind {
+                        self.cache_hints.insert(id, hint);
+                    }
+                }
                 // Don't need to handle scopes
                 _ => {}
             },
@@ -915,12 +931,18 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Arithmetic::Exp(op) => {
                 instructions.push(Instruction::Exp(self.compile_unary(op, out)))
             }
+            gpu::Arithmetic::Exp2(op) => {
+                instructions.push(Instruction::Exp2(self.compile_unary(op, out)))
+            }
             gpu::Arithmetic::Log(op) => {
                 instructions.push(Instruction::Log(self.compile_unary(op, out)))
             }
             gpu::Arithmetic::Log1p(op) => {
                 instructions.push(Instruction::Log1p(self.compile_unary(op, out)))
             }
+            gpu::Arithmetic::Log2(op) => {
+                instructions.push(Instruction::Log2(self.compile_unary(op, out)))
+            }
             gpu::Arithmetic::Cos(op) => {
                 instructions.push(Instruction::Cos(self.compile_unary(op, out)))
             }
@@ -941,11 +963,19 @@ impl<D: Dialect> CppCompiler<D> {
             gpu::Arithmetic::Sqrt(op) => {
                 instructions.push(Instruction::Sqrt(self.compile_unary(op, out)))
             }
+            gpu::Arithmetic::Rsqrt(op) => {
+                instructions.push(Instruction::Rsqrt(self.compile_unary(op, out)))
+            }
             gpu::Arithmetic::Erf(op) => {
                 let instruction = Instruction::Erf(self.compile_unary(op, out));
                 D::register_instruction_extension(&mut self.extensions, &instruction);
                 instructions.push(instruction)
             }
+            gpu::Arithmetic::Erfc(op) => {
+                let instruction = Instruction::Erfc(self.compile_unary(op, out));
+                D::register_instruction_extension(&mut self.extensions, &instruction);
+                instructions.push(instruction)
+            }
             gpu::Arithmetic::Max(op) => {
                 let instruction = Instruction::Max(self.compile_binary(op, out));
                 D::register_instruction_extension(&mut self.extensions, &instruction);
@@ -1204,10 +1234,17 @@ impl<D: Dialect> CppCompiler<D> {
         value: gpu::IndexOperator,
         out: gpu::Variable,
     ) -> IndexInstruction<D> {
+        let cache_hint = match value.list.kind {
+            gpu::VariableKind::GlobalInputArray(id) => {
+                self.cache_hints.get(&id).copied().unwrap_or_default()
+            }
+            _ => gpu::CacheHint::Default,
+        };
         IndexInstruction {
             list: self.compile_variable(value.list),
             index: self.compile_variable(value.index),
             line_size: value.line_size,
+            cache_hint,
             out: self.compile_variable(out),
         }
     }
@@ -1449,6 +1486,7 @@ impl<D: Dialect> CppCompiler<D> {
             k: matrix.k,
             elem: self.compile_storage_type(matrix.storage),
             layout: self.compile_matrix_layout(matrix.layout),
+            warp_size: self.compilation_options.warp_size,
         }
     }
 
@@ -1,4 +1,5 @@
 use crate::shared::FmtLeft;
+use cubecl_core::ir::CacheHint;
 
 use super::{Component, Dialect, Elem, Item, Variable};
 use std::{
@@ -416,6 +417,7 @@ impl Index {
         index: &Variable<D>,
         out: &Variable<D>,
         line_size: u32,
+        cache_hint: CacheHint,
     ) -> std::fmt::Result {
         if matches!(
             list,
@@ -436,13 +438,26 @@ impl Index {
                 "{qualifier} {addr_space}{item} *{tmp} = reinterpret_cast<{qualifier} {item}*>({list});"
             )?;
 
-            return Index::format(f, &tmp, index, out, 0);
+            return Index::format(f, &tmp, index, out, 0, cache_hint);
         }
 
         let item_out = out.item();
         if let Elem::Atomic(inner) = item_out.elem {
             let addr_space = D::address_space_for_variable(list);
             writeln!(f, "{addr_space}{inner}* {out} = &{list}[{index}];")
+        } else if let Some(intrinsic) = D::cache_hint_load_intrinsic(cache_hint)
+            && let Some(bits) = Self::cache_hint_bits(item_out.size())
+            && item_out == list.item()
+        {
+            // The intrinsics are only overloaded for the builtin types, so the value is loaded as
+            // bits of the same size.
+            let tmp = Variable::<D>::tmp_declared(item_out);
+            let out = out.fmt_left();
+            writeln!(
+                f,
+                "const {bits} {tmp} = {intrinsic}(reinterpret_cast<const {bits}*>(&{list}[{index}]));"
+            )?;
+            writeln!(f, "{out} = reinterpret_cast<const {item_out}&>({tmp});")
         } else {
             let out = out.fmt_left();
             write!(f, "{out} = ")?;
@@ -451,6 +466,18 @@ impl Index {
         }
     }
 
+    /// The builtin type of `size` bytes the values are loaded as with a cache hint.
+    fn cache_hint_bits(size: usize) -> Option<&'static str> {
+        match size {
+            1 => Some("unsigned char"),
+            2 => Some("unsigned short"),
+            4 => Some("unsigned int"),
+            8 => Some("uint2"),
+            16 => Some("uint4"),
+            _ => None,
+        }
+    }
+
     fn format_scalar<D: Dialect, Lhs, Rhs>(
         f: &mut Formatter<'_>,
         lhs: Lhs,
@@ -1,4 +1,4 @@
-use super::{Dialect, Instruction, Variable, barrier::BarrierOps, pipeline::PipelineOps};
+use super::{Dialect, Instruction, Variable, barrier::SharedBarrier, pipeline::PipelineOps};
 use std::fmt::Display;
 
 /// A body is composed of a list of [instructions](Instruction).
@@ -7,7 +7,7 @@ pub struct Body<D: Dialect> {
     pub instructions: Vec<Instruction<D>>,
     pub shared_memories: Vec<super::SharedMemory<D>>,
     pub pipelines: Vec<PipelineOps<D>>,
-    pub barriers: Vec<BarrierOps<D>>,
+    pub barriers: Vec<SharedBarrier<D>>,
     pub const_arrays: Vec<super::ConstArray<D>>,
     pub local_arrays: Vec<super::LocalArray<D>>,
 }
@@ -37,6 +37,10 @@ impl<D: Dialect> Display for Body<D> {
         for barrier in self.barriers.iter() {
             writeln!(f, "{barrier}")?;
         }
+        // Every unit waits for the barriers to be initialized before using them
+        if !self.barriers.is_empty() {
+            writeln!(f, "__syncthreads();")?;
+        }
 
         for const_array in self.const_arrays.iter() {
             f.write_fmt(format_args!(
@@ -1,11 +1,12 @@
 use std::hash::Hash;
 use std::{collections::HashSet, fmt::Debug};
 
-use cubecl_core::ir::{Id, Processor};
+use cubecl_core::ir::{CacheHint, Id, Processor};
 
 use crate::shared::{
     FmtLeft, IndexedVariable, MmaShape, SupportedMmaCombinations, SupportedScaledMmaCombinations,
-    reduce_comparison, reduce_exclusive, reduce_inclusive, reduce_operator, reduce_quantifier,
+    reduce_broadcast, reduce_comparison, reduce_exclusive, reduce_inclusive, reduce_operator,
+    reduce_quantifier,
 };
 
 use super::{
@@ -616,6 +617,13 @@ pub trait DialectInstructions<D: Dialect> {
         "h2"
     }
 
+    // cache hints
+    /// The intrinsic loading a value of a global buffer with the cache `hint`, if the dialect has
+    /// one. The loads without intrinsic are plain reads.
+    fn cache_hint_load_intrinsic(_hint: CacheHint) -> Option<&'static str> {
+        None
+    }
+
     // warp
     fn compile_warp_shuffle(
         f: &mut std::fmt::Formatter<'_>,
@@ -735,6 +743,14 @@ pub trait DialectWarpReduceCompiler<D: Dialect>:
     ) -> core::fmt::Result {
         reduce_exclusive(f, input, out, "*=", "1")
     }
+    fn warp_broadcast(
+        f: &mut core::fmt::Formatter<'_>,
+        input: &Variable<D>,
+        out: &Variable<D>,
+        id: &Variable<D>,
+    ) -> core::fmt::Result {
+        reduce_broadcast(f, input, out, id)
+    }
 }
 
 pub trait DialectWmmaCompiler<D: Dialect>:
@@ -1,4 +1,5 @@
 use crate::shared::FmtLeft;
+use cubecl_core::ir::CacheHint;
 
 use super::{
     Component, Dialect, Elem, Item, Variable, WarpInstruction, WmmaInstruction,
@@ -25,6 +26,8 @@ pub struct IndexInstruction<D: Dialect> {
     pub list: Variable<D>,
     pub index: Variable<D>,
     pub line_size: u32,
+    /// How the read of a global `list` is cached.
+    pub cache_hint: CacheHint,
     pub out: Variable<D>,
 }
 
@@ -146,6 +149,7 @@ pub enum Instruction<D: Dialect> {
     LowerEqual(BinaryInstruction<D>),
     GreaterEqual(BinaryInstruction<D>),
     Erf(UnaryInstruction<D>),
+    Erfc(UnaryInstruction<D>),
     BitwiseOr(BinaryInstruction<D>),
     BitwiseAnd(BinaryInstruction<D>),
     BitwiseXor(BinaryInstruction<D>),
@@ -158,14 +162,17 @@ pub enum Instruction<D: Dialect> {
     FindFirstSet(UnaryInstruction<D>),
     Abs(UnaryInstruction<D>),
     Exp(UnaryInstruction<D>),
+    Exp2(UnaryInstruction<D>),
     Log(UnaryInstruction<D>),
     Log1p(UnaryInstruction<D>),
+    Log2(UnaryInstruction<D>),
     Cos(UnaryInstruction<D>),
     Sin(UnaryInstruction<D>),
     Tanh(UnaryInstruction<D>),
     Powf(BinaryInstruction<D>),
     Powi(BinaryInstruction<D>),
     Sqrt(UnaryInstruction<D>),
+    Rsqrt(UnaryInstruction<D>),
     Min(BinaryInstruction<D>),
     Max(BinaryInstruction<D>),
     Not(UnaryInstruction<D>),
@@ -312,7 +319,9 @@ impl<D: Dialect> Display for Instruction<D> {
             Instruction::FindFirstSet(it) => FindFirstSet::format(f, &it.input, &it.out),
             Instruction::ShiftLeft(it) => ShiftLeft::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::ShiftRight(it) => ShiftRight::format(f, &it.lhs, &it.rhs, &it.out),
-            Instruction::Index(it) => Index::format(f, &it.list, &it.index, &it.out, it.line_size),
+            Instruction::Index(it) => {
+                Index::format(f, &it.list, &it.index, &it.out, it.line_size, it.cache_hint)
+            }
             Instruction::IndexAssign(it) => {
                 IndexAssign::format(f, &it.index, &it.value, &it.out, it.line_size)
             }
@@ -497,16 +506,20 @@ for ({i_ty} {i} = {start}; {i} {cmp} {end}; {increment}) {{
             Instruction::LowerEqual(it) => LowerEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::GreaterEqual(it) => GreaterEqual::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Erf(it) => Erf::format(f, &it.input, &it.out),
+            Instruction::Erfc(it) => Erfc::format(f, &it.input, &it.out),
             Instruction::Abs(it) => Abs::format(f, &it.input, &it.out),
             Instruction::Exp(it) => Exp::format(f, &it.input, &it.out),
+            Instruction::Exp2(it) => Exp2::format(f, &it.input, &it.out),
             Instruction::Log(it) => Log::format(f, &it.input, &it.out),
             Instruction::Log1p(it) => Log1p::format(f, &it.input, &it.out),
+            Instruction::Log2(it) => Log2::format(f, &it.input, &it.out),
             Instruction::Cos(it) => Cos::format(f, &it.input, &it.out),
             Instruction::Sin(it) => Sin::format(f, &it.input, &it.out),
             Instruction::Tanh(it) => Tanh::format(f, &it.input, &it.out),
             Instruction::Powf(it) => Powf::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Powi(it) => Powi::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Sqrt(it) => Sqrt::format(f, &it.input, &it.out),
+            Instruction::Rsqrt(it) => Rsqrt::format(f, &it.input, &it.out),
             Instruction::Max(it) => Max::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Min(it) => Min::format(f, &it.lhs, &it.rhs, &it.out),
             Instruction::Not(it) => Not::format(f, &it.input, &it.out),
@@ -69,6 +69,8 @@ pub struct ComputeKernel<D: Dialect> {
     pub body: Body<D>,
     pub cube_dim: CubeDim,
     pub cluster_dim: Option<CubeDim>,
+    pub shared_memory_carveout: Option<u8>,
+    pub dynamic_shared_memory: Option<u32>,
     pub extensions: Vec<D::Extension>,
     pub flags: Flags,
     pub items: HashSet<super::Item<D>>,
@@ -66,6 +66,8 @@ pub struct Fragment<D: Dialect> {
     pub k: u32,
     pub elem: Elem<D>,
     pub layout: Option<FragmentLayout<D>>,
+    /// The number of units sharing the fragment, which decides how it is split between them.
+    pub warp_size: u32,
 }
 
 #[derive(new, Debug, Clone, PartialEq, Eq, Copy)]
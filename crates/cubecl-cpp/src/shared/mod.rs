@@ -2,6 +2,7 @@ pub mod binary;
 pub mod unary;
 
 mod barrier;
+mod barrier_allocation;
 mod base;
 mod body;
 mod dialect;
@@ -153,11 +153,15 @@ function!(Cos, "cos");
 function!(Sin, "sin");
 function!(Sqrt, "sqrt");
 function!(Exp, "exp");
+function!(Exp2, "exp2");
+function!(Log2, "log2");
+function!(Rsqrt, "rsqrt");
 function!(Ceil, "ceil");
 function!(Floor, "floor");
 function!(Round, "rint");
 
 function!(Erf, "erf", false);
+function!(Erfc, "erfc", false);
 function!(Abs, "abs", false);
 
 pub struct Log1p;
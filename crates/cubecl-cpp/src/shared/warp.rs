@@ -97,7 +97,7 @@ impl<D: Dialect> Display for WarpInstruction<D> {
                 D::compile_warp_ballot(f, input, out.item().elem())?;
                 writeln!(f, ", 0, 0, 0 }};")
             }
-            WarpInstruction::Broadcast { input, id, out } => reduce_broadcast(f, input, out, id),
+            WarpInstruction::Broadcast { input, id, out } => D::warp_broadcast(f, input, out, id),
             WarpInstruction::Elect { out } => write!(
                 f,
                 "
@@ -8,19 +8,117 @@ use tracel_llvm::melior::{
         },
     },
     ir::{
-        BlockLike, Identifier, Location, Region,
+        BlockLike, Identifier, Location, Region, Type,
         attribute::{StringAttribute, TypeAttribute},
         r#type::{FunctionType, IntegerType},
     },
 };
 
-use crate::compute::compute_task::sync_cube;
+use crate::compute::{compute_task::sync_cube, plane};
+
+/// The scalar types of the arguments and results of the external functions.
+#[derive(Clone, Copy)]
+enum Scalar {
+    I32,
+    I64,
+    F64,
+}
+
+impl Scalar {
+    fn to_type<'a>(self, context: &'a Context) -> Type<'a> {
+        match self {
+            Scalar::I32 => IntegerType::new(context, 32).into(),
+            Scalar::I64 => IntegerType::new(context, 64).into(),
+            Scalar::F64 => Type::float64(context),
+        }
+    }
+}
+
+/// A function of the runtime called by the kernels.
+struct ExternalFunction {
+    name: &'static str,
+    ptr: *mut (),
+    inputs: &'static [Scalar],
+    output: Option<Scalar>,
+}
+
+/// The typed plane operations, called with values widened to 64 bits.
+macro_rules! typed_plane_functions {
+    ($suffix:ident, $ty:expr) => {
+        paste::paste! {
+            [
+                ExternalFunction {
+                    name: stringify!([<plane_broadcast_ $suffix>]),
+                    ptr: plane::[<plane_broadcast_ $suffix>] as *mut (),
+                    inputs: &[$ty, Scalar::I32],
+                    output: Some($ty),
+                },
+                typed_plane_functions!(@unary [<plane_sum_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_inclusive_sum_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_exclusive_sum_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_prod_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_inclusive_prod_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_exclusive_prod_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_min_ $suffix>], $ty),
+                typed_plane_functions!(@unary [<plane_max_ $suffix>], $ty),
+            ]
+        }
+    };
+    (@unary $name:ident, $ty:expr) => {
+        ExternalFunction {
+            name: stringify!($name),
+            ptr: plane::$name as *mut (),
+            inputs: &[$ty],
+            output: Some($ty),
+        }
+    };
+}
+
+fn plane_functions() -> Vec<ExternalFunction> {
+    let mut functions = vec![
+        ExternalFunction {
+            name: "plane_dim",
+            ptr: plane::plane_dim as *mut (),
+            inputs: &[],
+            output: Some(Scalar::I32),
+        },
+        ExternalFunction {
+            name: "unit_pos_plane",
+            ptr: plane::unit_pos_plane as *mut (),
+            inputs: &[],
+            output: Some(Scalar::I32),
+        },
+        ExternalFunction {
+            name: "sync_plane",
+            ptr: plane::sync_plane as *mut (),
+            inputs: &[],
+            output: None,
+        },
+        ExternalFunction {
+            name: "plane_elect",
+            ptr: plane::plane_elect as *mut (),
+            inputs: &[],
+            output: Some(Scalar::I64),
+        },
+        typed_plane_functions!(@unary plane_all, Scalar::I64),
+        typed_plane_functions!(@unary plane_any, Scalar::I64),
+        typed_plane_functions!(@unary plane_ballot, Scalar::I64),
+    ];
+    functions.extend(typed_plane_functions!(f64, Scalar::F64));
+    functions.extend(typed_plane_functions!(i64, Scalar::I64));
+    functions.extend(typed_plane_functions!(u64, Scalar::I64));
+    functions
+}
 
 pub fn register_external_function(execution_engine: &ExecutionEngine) {
     unsafe {
         execution_engine.register_symbol("sync_cube", sync_cube as *mut ());
         // This is only there to fool the execution engine to generate .so for inspection even if symbol resolution will probably not work.
         execution_engine.register_symbol("_mlir_sync_cube", sync_cube as *mut ());
+
+        for function in plane_functions() {
+            execution_engine.register_symbol(function.name, function.ptr);
+        }
     }
 }
 
@@ -58,4 +156,29 @@ pub fn add_external_function_to_module<'a>(
         )],
         Location::unknown(context),
     ));
+
+    for function in plane_functions() {
+        let inputs = function
+            .inputs
+            .iter()
+            .map(|input| input.to_type(context))
+            .collect::<Vec<_>>();
+        let outputs = function
+            .output
+            .iter()
+            .map(|output| output.to_type(context))
+            .collect::<Vec<_>>();
+        let func_type = TypeAttribute::new(FunctionType::new(context, &inputs, &outputs).into());
+        module.body().append_operation(func::func(
+            context,
+            StringAttribute::new(context, function.name),
+            func_type,
+            Region::new(),
+            &[(
+                Identifier::new(context, "sym_visibility"),
+                StringAttribute::new(context, "private").into(),
+            )],
+            Location::unknown(context),
+        ));
+    }
 }
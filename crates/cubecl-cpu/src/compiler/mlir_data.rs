@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use cubecl_core::server::{Bindings, Handle, ScalarBinding};
+use cubecl_core::server::{Bindings, Handle, IoError, ScalarBinding};
 use cubecl_runtime::{memory_management::MemoryManagement, storage::BytesStorage};
 
 use crate::compiler::{builtin::BuiltinArray, memref::LineMemRef};
@@ -40,7 +40,7 @@ impl MlirData {
         bindings: Bindings,
         shared_memories: &SharedMemories,
         memory_management: &mut MemoryManagement<BytesStorage>,
-    ) -> Self {
+    ) -> Result<Self, IoError> {
         let Bindings {
             buffers,
             scalars,
@@ -90,7 +90,7 @@ impl MlirData {
 
         for shared_memory in shared_memories.0.iter() {
             let length = (shared_memory.ty.size() * shared_memory.length as usize) as u64;
-            let handle = memory_management.reserve(length).unwrap();
+            let handle = memory_management.reserve(length)?;
             let b = Handle::new(handle, None, None, length).binding();
             let handle = memory_management
                 .get_resource(b.memory, b.offset_start, b.offset_end)
@@ -118,11 +118,11 @@ impl MlirData {
 
         let shared_mlir_data = Arc::new(shared_mlir_data);
 
-        Self {
+        Ok(Self {
             shared_mlir_data,
             args_second_indirection,
             builtin,
-        }
+        })
     }
 
     pub fn push_builtin(&mut self) {
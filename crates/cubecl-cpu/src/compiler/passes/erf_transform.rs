@@ -15,6 +15,11 @@ impl IrTransformer for ErfTransform {
                 expand_erf(&mut scope, op.input, inst.out.unwrap());
                 TransformAction::Replace(scope.process([]).instructions)
             }
+            Operation::Arithmetic(Arithmetic::Erfc(op)) => {
+                let mut scope = scope.child();
+                expand_erfc(&mut scope, op.input, inst.out.unwrap());
+                TransformAction::Replace(scope.process([]).instructions)
+            }
             _ => TransformAction::Ignore,
         }
     }
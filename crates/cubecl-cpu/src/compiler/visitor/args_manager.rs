@@ -6,9 +6,13 @@ use cubecl_core::{
     ir::{Builtin, StorageType},
     prelude::KernelDefinition,
 };
-use tracel_llvm::melior::ir::{
-    Block, BlockRef, Location, Region,
-    r#type::{FunctionType, IntegerType, MemRefType},
+use tracel_llvm::melior::{
+    dialect::func,
+    ir::{
+        Block, BlockRef, Location, Region,
+        attribute::FlatSymbolRefAttribute,
+        r#type::{FunctionType, IntegerType, MemRefType},
+    },
 };
 
 use crate::compiler::{builtin::BuiltinArray, passes::shared_memories::SharedMemories};
@@ -183,6 +187,7 @@ impl<'a> ArgsManager<'a> {
 
     pub fn compute_derived_args_builtin(
         &mut self,
+        context: &'a Context,
         block: BlockRef<'a, 'a>,
         location: Location<'a>,
     ) {
@@ -217,6 +222,26 @@ impl<'a> ArgsManager<'a> {
             .addi(unit_pos_yz_corrected, self.get(Builtin::UnitPosX), location)
             .unwrap();
         self.set(Builtin::UnitPos, unit_pos);
+
+        // The plane of a unit is only known by the worker executing it.
+        let integer_type = IntegerType::new(context, 32).into();
+        for (builtin, func_name) in [
+            (Builtin::PlaneDim, "plane_dim"),
+            (Builtin::UnitPosPlane, "unit_pos_plane"),
+        ] {
+            let value = block
+                .append_operation(func::call(
+                    context,
+                    FlatSymbolRefAttribute::new(context, func_name),
+                    &[],
+                    &[integer_type],
+                    location,
+                ))
+                .result(0)
+                .unwrap()
+                .into();
+            self.set(builtin, value);
+        }
     }
 
     pub fn set(&mut self, builtin: Builtin, value: Value<'a, 'a>) {
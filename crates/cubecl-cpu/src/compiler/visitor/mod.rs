@@ -224,7 +224,7 @@ impl<'a> Visitor<'a> {
         let start = block.const_int_from_type(context, location, 0, integer_type)?;
         let step = block.const_int_from_type(context, location, 1, integer_type)?;
 
-        args.compute_derived_args_builtin(block, location);
+        args.compute_derived_args_builtin(context, block, location);
 
         let cube_count_dim_x = block.muli(
             args.get(Builtin::CubeCountX),
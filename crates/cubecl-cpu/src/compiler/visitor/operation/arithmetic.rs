@@ -129,7 +129,7 @@ impl<'a> Visitor<'a> {
                 }
                 self.insert_variable(out, operation);
             }
-            Arithmetic::Erf(_) => {
+            Arithmetic::Erf(_) | Arithmetic::Erfc(_) => {
                 unreachable!("Should have been transformed in primitive in a previous passe");
             }
             Arithmetic::Exp(exp) => {
@@ -141,6 +141,15 @@ impl<'a> Visitor<'a> {
                 ));
                 self.insert_variable(out, result);
             }
+            Arithmetic::Exp2(exp2) => {
+                let value = self.get_variable(exp2.input);
+                let result = self.append_operation_with_result(llvm_ods::intr_exp2(
+                    self.context,
+                    value,
+                    self.location,
+                ));
+                self.insert_variable(out, result);
+            }
             Arithmetic::Floor(floor) => {
                 let value = self.get_variable(floor.input);
                 let result = self.append_operation_with_result(llvm_ods::intr_floor(
@@ -175,6 +184,15 @@ impl<'a> Visitor<'a> {
                 ));
                 self.insert_variable(out, result);
             }
+            Arithmetic::Log2(log2) => {
+                let value = self.get_variable(log2.input);
+                let result = self.append_operation_with_result(llvm_ods::intr_log2(
+                    self.context,
+                    value,
+                    self.location,
+                ));
+                self.insert_variable(out, result);
+            }
             Arithmetic::Log1p(log) => {
                 let value = self.get_variable(log.input);
                 let one = self.create_float_constant_from_item(log.input.ty, 1.0);
@@ -386,6 +404,18 @@ impl<'a> Visitor<'a> {
                 ));
                 self.insert_variable(out, output);
             }
+            Arithmetic::Rsqrt(rsqrt) => {
+                let input = self.get_variable(rsqrt.input);
+                let sqrt = self.append_operation_with_result(llvm_ods::intr_sqrt(
+                    self.context,
+                    input,
+                    self.location,
+                ));
+                let one = self.create_float_constant_from_item(rsqrt.input.ty, 1.0);
+                let output =
+                    self.append_operation_with_result(arith::divf(one, sqrt, self.location));
+                self.insert_variable(out, output);
+            }
             Arithmetic::Sin(sin) => {
                 let input = self.get_variable(sin.input);
                 let output = self.append_operation_with_result(llvm_ods::intr_sin(
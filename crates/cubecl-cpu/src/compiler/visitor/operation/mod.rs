@@ -3,6 +3,7 @@ pub(super) mod bitwise;
 pub(super) mod comparison;
 pub(super) mod metadata;
 pub(super) mod operator;
+pub(super) mod plane;
 pub(super) mod synchronization;
 
 use cubecl_core::ir::{NonSemantic, Operation};
@@ -92,7 +93,10 @@ impl<'a> Visitor<'a> {
             Operation::Operator(operator) => {
                 self.visit_operator_with_out(operator, out);
             }
-            Operation::CoopMma(_) | Operation::Plane(_) | Operation::Tma(_) => {
+            Operation::Plane(plane) => {
+                self.visit_plane(plane, out);
+            }
+            Operation::CoopMma(_) | Operation::Tma(_) => {
                 panic!("{operation} is not supported on CPU.");
             }
             Operation::Branch(_) => {
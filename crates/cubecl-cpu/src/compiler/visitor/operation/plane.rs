@@ -0,0 +1,243 @@
+use cubecl_core::ir::{ElemType, Plane, StorageType};
+use tracel_llvm::melior::{
+    dialect::{
+        arith, func, index,
+        ods::{self, llvm, vector},
+    },
+    ir::{attribute::FlatSymbolRefAttribute, r#type::IntegerType},
+};
+
+use crate::compiler::visitor::prelude::*;
+
+/// The kind of the 64 bits value a plane operation is performed on.
+#[derive(Clone, Copy)]
+enum Wide {
+    Float,
+    Int,
+    UInt,
+}
+
+impl Wide {
+    fn of(ty: StorageType) -> Self {
+        match ty.elem_type() {
+            ElemType::Float(_) => Wide::Float,
+            ElemType::Int(_) => Wide::Int,
+            ElemType::UInt(_) | ElemType::Bool => Wide::UInt,
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Wide::Float => "f64",
+            Wide::Int => "i64",
+            Wide::UInt => "u64",
+        }
+    }
+
+    fn to_type<'a>(self, context: &'a Context) -> Type<'a> {
+        match self {
+            Wide::Float => Type::float64(context),
+            Wide::Int | Wide::UInt => IntegerType::new(context, 64).into(),
+        }
+    }
+}
+
+impl<'a> Visitor<'a> {
+    /// Plane operations are calls to [the runtime](crate::compute::plane), performed on each
+    /// element of a line.
+    pub fn visit_plane(&mut self, plane: &Plane, out: Variable) {
+        let value = match plane {
+            Plane::Elect => {
+                let elected = self.call_plane_function("plane_elect", &[], "i64");
+                self.narrow_from_wide(elected, out.storage_type())
+            }
+            Plane::All(op) => self.visit_plane_lanes("plane_all", op.input, None, out, false),
+            Plane::Any(op) => self.visit_plane_lanes("plane_any", op.input, None, out, false),
+            Plane::Ballot(op) => self.visit_plane_ballot(op.input, out),
+            Plane::Broadcast(op) => {
+                let lane = self.get_variable(op.rhs);
+                let lane = match lane.r#type().is_index() {
+                    true => self.append_operation_with_result(index::casts(
+                        lane,
+                        IntegerType::new(self.context, 32).into(),
+                        self.location,
+                    )),
+                    false => lane,
+                };
+                self.visit_plane_lanes("plane_broadcast", op.lhs, Some(lane), out, true)
+            }
+            Plane::Sum(op) => self.visit_plane_lanes("plane_sum", op.input, None, out, true),
+            Plane::InclusiveSum(op) => {
+                self.visit_plane_lanes("plane_inclusive_sum", op.input, None, out, true)
+            }
+            Plane::ExclusiveSum(op) => {
+                self.visit_plane_lanes("plane_exclusive_sum", op.input, None, out, true)
+            }
+            Plane::Prod(op) => self.visit_plane_lanes("plane_prod", op.input, None, out, true),
+            Plane::InclusiveProd(op) => {
+                self.visit_plane_lanes("plane_inclusive_prod", op.input, None, out, true)
+            }
+            Plane::ExclusiveProd(op) => {
+                self.visit_plane_lanes("plane_exclusive_prod", op.input, None, out, true)
+            }
+            Plane::Min(op) => self.visit_plane_lanes("plane_min", op.input, None, out, true),
+            Plane::Max(op) => self.visit_plane_lanes("plane_max", op.input, None, out, true),
+        };
+        self.insert_variable(out, value);
+    }
+
+    /// Call the plane function `name` on each element of `input`, suffixed by the kind of the
+    /// element when `typed`.
+    fn visit_plane_lanes(
+        &mut self,
+        name: &str,
+        input: Variable,
+        extra_arg: Option<Value<'a, 'a>>,
+        out: Variable,
+        typed: bool,
+    ) -> Value<'a, 'a> {
+        let value = self.get_variable(input);
+        let wide = Wide::of(input.storage_type());
+        let name = match typed {
+            true => format!("{name}_{}", wide.suffix()),
+            false => name.to_string(),
+        };
+        let result_kind = match wide {
+            Wide::Float if typed => "f64",
+            _ => "i64",
+        };
+
+        let results = self
+            .line_elements(value, input)
+            .into_iter()
+            .map(|element| {
+                let element = self.widen(element, input.storage_type());
+                let args = [Some(element), extra_arg]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                let result = self.call_plane_function(&name, &args, result_kind);
+                self.narrow_from_wide(result, out.storage_type())
+            })
+            .collect::<Vec<_>>();
+
+        self.from_line_elements(results, out)
+    }
+
+    fn visit_plane_ballot(&mut self, input: Variable, out: Variable) -> Value<'a, 'a> {
+        let value = self.get_variable(input);
+        let value = self.widen(value, input.storage_type());
+        let mask = self.call_plane_function("plane_ballot", &[value], "i64");
+
+        let u32_type: Type<'a> = IntegerType::new(self.context, 32).into();
+        let i64_type: Type<'a> = IntegerType::new(self.context, 64).into();
+        let shift = self
+            .block
+            .const_int_from_type(self.context, self.location, 32, i64_type)
+            .unwrap();
+        let zero = self
+            .block
+            .const_int_from_type(self.context, self.location, 0, u32_type)
+            .unwrap();
+        let high = self.append_operation_with_result(arith::shrui(mask, shift, self.location));
+        let low = self.append_operation_with_result(arith::trunci(mask, u32_type, self.location));
+        let high = self.append_operation_with_result(arith::trunci(high, u32_type, self.location));
+
+        self.from_line_elements(vec![low, high, zero, zero], out)
+    }
+
+    fn call_plane_function(
+        &self,
+        name: &str,
+        args: &[Value<'a, 'a>],
+        result: &str,
+    ) -> Value<'a, 'a> {
+        let result = match result {
+            "f64" => Type::float64(self.context),
+            _ => IntegerType::new(self.context, 64).into(),
+        };
+        self.append_operation_with_result(func::call(
+            self.context,
+            FlatSymbolRefAttribute::new(self.context, name),
+            args,
+            &[result],
+            self.location,
+        ))
+    }
+
+    /// The scalar elements of a value of `variable`.
+    fn line_elements(&self, value: Value<'a, 'a>, variable: Variable) -> Vec<Value<'a, 'a>> {
+        if !variable.ty.is_vectorized() {
+            return vec![value];
+        }
+
+        let element_type = variable.storage_type().to_type(self.context);
+        let i32_type = IntegerType::new(self.context, 32).into();
+        (0..variable.line_size())
+            .map(|i| {
+                let position = self
+                    .block
+                    .const_int_from_type(self.context, self.location, i as i64, i32_type)
+                    .unwrap();
+                self.append_operation_with_result(llvm::extractelement(
+                    self.context,
+                    element_type,
+                    value,
+                    position,
+                    self.location,
+                ))
+            })
+            .collect()
+    }
+
+    /// A value of `variable` from its scalar elements.
+    fn from_line_elements(
+        &self,
+        elements: Vec<Value<'a, 'a>>,
+        variable: Variable,
+    ) -> Value<'a, 'a> {
+        if elements.len() == 1 {
+            return elements[0];
+        }
+
+        self.append_operation_with_result(vector::from_elements(
+            self.context,
+            variable.ty.to_type(self.context),
+            &elements,
+            self.location,
+        ))
+    }
+
+    fn widen(&self, value: Value<'a, 'a>, ty: StorageType) -> Value<'a, 'a> {
+        let wide = Wide::of(ty);
+        if ty.size() == 8 {
+            return value;
+        }
+
+        let target = wide.to_type(self.context);
+        self.append_operation_with_result(match wide {
+            Wide::Float => arith::extf(value, target, self.location),
+            Wide::Int => arith::extsi(value, target, self.location),
+            Wide::UInt => arith::extui(value, target, self.location),
+        })
+    }
+
+    fn narrow_from_wide(&self, value: Value<'a, 'a>, ty: StorageType) -> Value<'a, 'a> {
+        if ty.size() == 8 {
+            return value;
+        }
+
+        let target = ty.to_type(self.context);
+        match Wide::of(ty) {
+            Wide::Float => self.append_operation_with_result(ods::arith::truncf(
+                self.context,
+                target,
+                value,
+                self.location,
+            )),
+            Wide::Int | Wide::UInt => {
+                self.append_operation_with_result(arith::trunci(value, target, self.location))
+            }
+        }
+    }
+}
@@ -16,7 +16,16 @@ impl<'a> Visitor<'a> {
                     self.location,
                 ));
             }
-            Synchronization::SyncPlane => {} // NOOP plane size is 1 on CPU
+            Synchronization::SyncPlane => {
+                let func_name = FlatSymbolRefAttribute::new(self.context, "sync_plane");
+                self.block.append_operation(func::call(
+                    self.context,
+                    func_name,
+                    &[],
+                    &[],
+                    self.location,
+                ));
+            }
             Synchronization::SyncStorage => {
                 panic!("SyncStorage is not supported")
             }
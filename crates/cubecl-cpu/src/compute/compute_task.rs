@@ -7,6 +7,8 @@ use cubecl_core::ExecutionMode;
 
 use crate::compiler::{mlir_data::MlirData, mlir_engine::MlirEngine};
 
+use super::plane::{self, PlaneUnit};
+
 pub static BARRIER_COUNTER: AtomicI32 = AtomicI32::new(0);
 pub static STOPPED_COUNTER: AtomicI32 = AtomicI32::new(0);
 pub static CURRENT_CUBE_DIM: AtomicI32 = AtomicI32::new(-1);
@@ -43,6 +45,7 @@ pub struct ComputeTask {
     pub mlir_engine: MlirEngine,
     pub mlir_data: MlirData,
     pub unit_pos: [u32; 3],
+    pub plane: PlaneUnit,
     pub kind: ExecutionMode,
 }
 
@@ -50,9 +53,11 @@ impl ComputeTask {
     pub fn compute(mut self) {
         self.mlir_data.push_builtin();
         self.mlir_data.builtin.set_unit_pos(self.unit_pos);
+        plane::enter(Some(self.plane));
         unsafe {
             self.mlir_engine.run_kernel(&mut self.mlir_data);
         }
+        plane::enter(None);
         CURRENT_CUBE_DIM.fetch_sub(1, Ordering::AcqRel);
     }
 }
@@ -1,4 +1,5 @@
 pub mod compute_task;
+pub mod plane;
 pub mod scheduler;
 pub mod server;
 pub mod worker;
@@ -0,0 +1,269 @@
+//! Plane operations, exchanging the values of the units of a plane running on different workers.
+//!
+//! All the units of a plane meet at each plane operation: each one publishes its value, waits
+//! for the others, then computes the result from the values of the whole plane. A plane
+//! operation must therefore be reached by all the units of the plane, as with [sync_cube].
+//!
+//! [sync_cube]: super::compute_task::sync_cube
+
+use std::{
+    cell::RefCell,
+    sync::{Arc, Barrier, Mutex},
+};
+
+/// The plane size used when none is configured.
+pub const DEFAULT_PLANE_SIZE: u32 = 32;
+
+/// The largest supported plane size, for ballots to fit in a mask of 64 bits.
+pub const MAX_PLANE_SIZE: u32 = 64;
+
+/// The values exchanged by the units of a plane.
+#[derive(Debug)]
+pub struct PlaneState {
+    barrier: Barrier,
+    values: Mutex<Vec<u64>>,
+}
+
+/// The planes of a cube of `cube_dim` units, the last one only partially filled when the size
+/// of the cube isn't a multiple of `plane_size`.
+pub fn create_planes(plane_size: u32, cube_dim: u32) -> Vec<Arc<PlaneState>> {
+    (0..cube_dim.div_ceil(plane_size))
+        .map(|plane| {
+            let size = plane_size.min(cube_dim - plane * plane_size);
+            Arc::new(PlaneState {
+                barrier: Barrier::new(size as usize),
+                values: Mutex::new(vec![0; size as usize]),
+            })
+        })
+        .collect()
+}
+
+/// The plane of the unit executed by a worker.
+#[derive(Debug, Clone)]
+pub struct PlaneUnit {
+    pub plane: Arc<PlaneState>,
+    pub lane: u32,
+    pub plane_size: u32,
+}
+
+thread_local! {
+    static CURRENT_UNIT: RefCell<Option<PlaneUnit>> = const { RefCell::new(None) };
+}
+
+/// Set the plane of the unit executed by the current worker.
+pub fn enter(unit: Option<PlaneUnit>) {
+    CURRENT_UNIT.with(|current| *current.borrow_mut() = unit);
+}
+
+fn with_unit<R>(func: impl FnOnce(&PlaneUnit) -> R) -> R {
+    CURRENT_UNIT.with(|current| {
+        let current = current.borrow();
+        func(
+            current
+                .as_ref()
+                .expect("Plane operations are only valid in a kernel"),
+        )
+    })
+}
+
+/// Publish the value of the current unit, returning the values of all the units of its plane
+/// and the lane of the unit.
+fn exchange(value: u64) -> (Vec<u64>, usize) {
+    with_unit(|unit| {
+        let lane = unit.lane as usize;
+        unit.plane.values.lock().unwrap()[lane] = value;
+        unit.plane.barrier.wait();
+        let values = unit.plane.values.lock().unwrap().clone();
+        // Values can't be overwritten by the next operation before every unit read them.
+        unit.plane.barrier.wait();
+        (values, lane)
+    })
+}
+
+pub extern "C" fn plane_dim() -> u32 {
+    with_unit(|unit| unit.plane_size)
+}
+
+pub extern "C" fn unit_pos_plane() -> u32 {
+    with_unit(|unit| unit.lane)
+}
+
+pub extern "C" fn sync_plane() {
+    exchange(0);
+}
+
+pub extern "C" fn plane_elect() -> u64 {
+    // Every unit of the plane is active, so the first lane is elected.
+    let (_, lane) = exchange(0);
+    (lane == 0) as u64
+}
+
+pub extern "C" fn plane_all(value: u64) -> u64 {
+    let (values, _) = exchange(value);
+    values.iter().all(|value| *value != 0) as u64
+}
+
+pub extern "C" fn plane_any(value: u64) -> u64 {
+    let (values, _) = exchange(value);
+    values.iter().any(|value| *value != 0) as u64
+}
+
+pub extern "C" fn plane_ballot(value: u64) -> u64 {
+    let (values, _) = exchange(value);
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value != 0)
+        .fold(0, |mask, (lane, _)| mask | (1 << lane))
+}
+
+/// Defines the reductions and scans of a type, exchanged as its bits.
+macro_rules! plane_ops {
+    ($ty:ty, $suffix:ident, $to_bits:expr, $from_bits:expr, $add:expr, $mul:expr, $min:expr, $max:expr) => {
+        paste::paste! {
+            fn [<exchange_ $suffix>](value: $ty) -> (Vec<$ty>, usize) {
+                let (values, lane) = exchange($to_bits(value));
+                (values.into_iter().map($from_bits).collect(), lane)
+            }
+
+            pub extern "C" fn [<plane_broadcast_ $suffix>](value: $ty, lane: u32) -> $ty {
+                let (values, _) = [<exchange_ $suffix>](value);
+                values[lane as usize]
+            }
+
+            pub extern "C" fn [<plane_sum_ $suffix>](value: $ty) -> $ty {
+                let (values, _) = [<exchange_ $suffix>](value);
+                values.into_iter().fold(<$ty>::from(0u8), $add)
+            }
+
+            pub extern "C" fn [<plane_inclusive_sum_ $suffix>](value: $ty) -> $ty {
+                let (values, lane) = [<exchange_ $suffix>](value);
+                values[..=lane].iter().copied().fold(<$ty>::from(0u8), $add)
+            }
+
+            pub extern "C" fn [<plane_exclusive_sum_ $suffix>](value: $ty) -> $ty {
+                let (values, lane) = [<exchange_ $suffix>](value);
+                values[..lane].iter().copied().fold(<$ty>::from(0u8), $add)
+            }
+
+            pub extern "C" fn [<plane_prod_ $suffix>](value: $ty) -> $ty {
+                let (values, _) = [<exchange_ $suffix>](value);
+                values.into_iter().fold(<$ty>::from(1u8), $mul)
+            }
+
+            pub extern "C" fn [<plane_inclusive_prod_ $suffix>](value: $ty) -> $ty {
+                let (values, lane) = [<exchange_ $suffix>](value);
+                values[..=lane].iter().copied().fold(<$ty>::from(1u8), $mul)
+            }
+
+            pub extern "C" fn [<plane_exclusive_prod_ $suffix>](value: $ty) -> $ty {
+                let (values, lane) = [<exchange_ $suffix>](value);
+                values[..lane].iter().copied().fold(<$ty>::from(1u8), $mul)
+            }
+
+            pub extern "C" fn [<plane_min_ $suffix>](value: $ty) -> $ty {
+                let (values, _) = [<exchange_ $suffix>](value);
+                values.into_iter().reduce($min).unwrap()
+            }
+
+            pub extern "C" fn [<plane_max_ $suffix>](value: $ty) -> $ty {
+                let (values, _) = [<exchange_ $suffix>](value);
+                values.into_iter().reduce($max).unwrap()
+            }
+        }
+    };
+}
+
+plane_ops!(
+    f64,
+    f64,
+    f64::to_bits,
+    f64::from_bits,
+    |acc: f64, value: f64| acc + value,
+    |acc: f64, value: f64| acc * value,
+    f64::min,
+    f64::max
+);
+plane_ops!(
+    i64,
+    i64,
+    |value: i64| value as u64,
+    |bits: u64| bits as i64,
+    i64::wrapping_add,
+    i64::wrapping_mul,
+    i64::min,
+    i64::max
+);
+plane_ops!(
+    u64,
+    u64,
+    |value: u64| value,
+    |bits: u64| bits,
+    u64::wrapping_add,
+    u64::wrapping_mul,
+    u64::min,
+    u64::max
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `func` on a unit of each lane of a plane, returning their results.
+    fn run_plane<R: Send + 'static>(size: u32, func: fn(u32) -> R) -> Vec<R> {
+        let plane = create_planes(size, size).remove(0);
+        let threads = (0..size)
+            .map(|lane| {
+                let plane = plane.clone();
+                std::thread::spawn(move || {
+                    enter(Some(PlaneUnit {
+                        plane,
+                        lane,
+                        plane_size: size,
+                    }));
+                    func(lane)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        threads.into_iter().map(|t| t.join().unwrap()).collect()
+    }
+
+    #[test]
+    fn scans_follow_the_lanes() {
+        let inclusive = run_plane(8, |lane| plane_inclusive_sum_i64(lane as i64));
+        let exclusive = run_plane(8, |lane| plane_exclusive_sum_i64(lane as i64));
+
+        assert_eq!(inclusive, [0, 1, 3, 6, 10, 15, 21, 28]);
+        assert_eq!(exclusive, [0, 0, 1, 3, 6, 10, 15, 21]);
+    }
+
+    #[test]
+    fn consecutive_operations_dont_mix_values() {
+        let results = run_plane(4, |lane| {
+            let sum = plane_sum_f64(lane as f64);
+            let max = plane_max_u64(lane as u64);
+            let broadcast = plane_broadcast_i64(lane as i64 * 10, 2);
+            (sum, max, broadcast)
+        });
+
+        assert!(results.iter().all(|result| *result == (6.0, 3, 20)));
+    }
+
+    #[test]
+    fn ballot_and_elect() {
+        let ballots = run_plane(6, |lane| plane_ballot((lane % 2) as u64));
+        let elected = run_plane(6, |_| plane_elect());
+
+        assert!(ballots.iter().all(|ballot| *ballot == 0b101010));
+        assert_eq!(elected, [1, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn partial_last_plane() {
+        let planes = create_planes(32, 40);
+
+        assert_eq!(planes.len(), 2);
+        assert_eq!(planes[1].values.lock().unwrap().len(), 8);
+    }
+}
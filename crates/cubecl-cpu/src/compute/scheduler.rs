@@ -2,8 +2,15 @@ use std::fmt::Debug;
 use std::sync::atomic::Ordering;
 use std::{collections::HashMap, sync::mpsc};
 
-use cubecl_core::{ExecutionMode, compute::CubeTask, prelude::CompiledKernel, server::Bindings};
-use cubecl_runtime::{id::KernelId, memory_management::MemoryManagement, storage::BytesStorage};
+use cubecl_core::{
+    ExecutionMode,
+    compute::CubeTask,
+    prelude::CompiledKernel,
+    server::{Bindings, IoError},
+};
+use cubecl_runtime::{
+    id::KernelId, logging::ServerLogger, memory_management::MemoryManagement, storage::BytesStorage,
+};
 
 use crate::{
     CpuCompiler,
@@ -11,11 +18,13 @@ use crate::{
 };
 
 use super::compute_task::{BARRIER_COUNTER, CURRENT_CUBE_DIM, STOPPED_COUNTER};
+use super::plane::{DEFAULT_PLANE_SIZE, PlaneUnit, create_planes};
 use super::{compute_task::ComputeTask, worker::Worker};
 
 pub struct Scheduler {
     workers: Vec<Worker>,
     compilation_cache: HashMap<KernelId, CompiledKernel<MlirCompiler>>,
+    plane_size: u32,
 }
 
 impl Debug for Scheduler {
@@ -26,6 +35,13 @@ impl Debug for Scheduler {
 
 impl Default for Scheduler {
     fn default() -> Self {
+        Self::new(DEFAULT_PLANE_SIZE)
+    }
+}
+
+impl Scheduler {
+    /// Create a scheduler executing the units of a cube in planes of `plane_size` units.
+    pub fn new(plane_size: u32) -> Self {
         let available_parallelism = std::thread::available_parallelism()
             .expect("Can't get available parallelism on this platform")
             .get();
@@ -37,36 +53,49 @@ impl Default for Scheduler {
         Scheduler {
             workers,
             compilation_cache,
+            plane_size,
         }
     }
 }
 
 impl Scheduler {
-    pub fn dispatch_execute(
+    /// Compile the kernel if it isn't already cached.
+    pub fn compile(
         &mut self,
         kernel: Box<dyn CubeTask<CpuCompiler>>,
-        cube_count: [u32; 3],
-        bindings: Bindings,
         kind: ExecutionMode,
-        memory_management: &mut MemoryManagement<BytesStorage>,
-    ) {
-        let kernel = self
-            .compilation_cache
+        logger: &ServerLogger,
+    ) -> &CompiledKernel<MlirCompiler> {
+        self.compilation_cache
             .entry(kernel.id())
             .or_insert_with(|| {
-                kernel.compile(
+                let compiled = kernel.compile(
                     &mut Default::default(),
                     &MlirCompilerOptions::default(),
                     kind,
-                )
-            });
+                );
+                logger.log_compilation(&compiled);
+                compiled
+            })
+    }
+
+    pub fn dispatch_execute(
+        &mut self,
+        kernel: Box<dyn CubeTask<CpuCompiler>>,
+        cube_count: [u32; 3],
+        bindings: Bindings,
+        kind: ExecutionMode,
+        memory_management: &mut MemoryManagement<BytesStorage>,
+        logger: &ServerLogger,
+    ) -> Result<(), IoError> {
+        let kernel = self.compile(kernel, kind, logger);
 
         let cube_dim = kernel.cube_dim;
         let cube_dim_size = cube_dim.num_elems();
 
         let mlir_engine = kernel.repr.clone().unwrap();
         let mut mlir_data =
-            MlirData::new(bindings, &mlir_engine.0.shared_memories, memory_management);
+            MlirData::new(bindings, &mlir_engine.0.shared_memories, memory_management)?;
         mlir_data.builtin.set_cube_dim(cube_dim);
         mlir_data.builtin.set_cube_count(cube_count);
 
@@ -82,11 +111,19 @@ impl Scheduler {
                 .extend((0..cube_dim_size - self.workers.len() as u32).map(|_| Worker::default()));
         }
 
+        let planes = create_planes(self.plane_size, cube_dim_size);
         let mut workers = self.workers.iter_mut();
         for unit_pos_x in 0..cube_dim.x {
             for unit_pos_y in 0..cube_dim.y {
                 for unit_pos_z in 0..cube_dim.z {
                     let unit_pos = [unit_pos_x, unit_pos_y, unit_pos_z];
+                    let unit_index =
+                        unit_pos_x + (unit_pos_y + unit_pos_z * cube_dim.y) * cube_dim.x;
+                    let plane = PlaneUnit {
+                        plane: planes[(unit_index / self.plane_size) as usize].clone(),
+                        lane: unit_index % self.plane_size,
+                        plane_size: self.plane_size,
+                    };
                     let worker = workers.next().expect("The CubeDim are too large");
                     let mlir_engine = mlir_engine.clone();
                     let mlir_data = mlir_data.clone();
@@ -95,6 +132,7 @@ impl Scheduler {
                         mlir_engine,
                         mlir_data,
                         unit_pos,
+                        plane,
                         kind,
                     };
                     msg_count += 1;
@@ -110,5 +148,7 @@ impl Scheduler {
                 break;
             }
         }
+
+        Ok(())
     }
 }
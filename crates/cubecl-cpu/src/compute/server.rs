@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use cubecl_common::{bytes::Bytes, profile::ProfileDuration};
 use cubecl_core::{
-    CubeCount, ExecutionMode, MemoryUsage,
+    CubeCount, ExecutionMode, MemoryScopeUsage, MemoryUsage,
     compute::CubeTask,
     future::DynFut,
     server::{
@@ -12,7 +12,7 @@ use cubecl_core::{
 };
 use cubecl_runtime::{
     logging::ServerLogger,
-    memory_management::{MemoryManagement, offset_handles},
+    memory_management::{MemoryCleanupMode, MemoryManagement, offset_handles},
     storage::{BindingResource, BytesStorage, ComputeStorage},
     timestamp_profiler::TimestampProfiler,
 };
@@ -31,10 +31,10 @@ pub struct CpuServer {
 impl DataTransferService for CpuServer {}
 
 impl CpuServer {
-    pub fn new(ctx: CpuContext) -> Self {
+    pub fn new(ctx: CpuContext, plane_size: u32) -> Self {
         Self {
             logger: ServerLogger::default(),
-            scheduler: Scheduler::default(),
+            scheduler: Scheduler::new(plane_size),
             ctx,
         }
     }
@@ -136,8 +136,20 @@ impl ComputeServer for CpuServer {
         self.ctx.memory_management.memory_usage()
     }
 
-    fn memory_cleanup(&mut self) {
-        self.ctx.memory_management.cleanup(true)
+    fn push_memory_scope(&mut self, tag: String) {
+        self.ctx.memory_management.push_scope(tag);
+    }
+
+    fn pop_memory_scope(&mut self) {
+        self.ctx.memory_management.pop_scope();
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.ctx.memory_management.scope_usage()
+    }
+
+    fn memory_cleanup(&mut self, mode: MemoryCleanupMode) {
+        self.ctx.memory_management.memory_cleanup(mode)
     }
 
     unsafe fn execute(
@@ -146,8 +158,8 @@ impl ComputeServer for CpuServer {
         count: CubeCount,
         bindings: Bindings,
         kind: ExecutionMode,
-        _logger: Arc<ServerLogger>,
-    ) {
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
         let cube_count = match count {
             CubeCount::Static(x, y, z) => [x, y, z],
             CubeCount::Dynamic(binding) => {
@@ -169,7 +181,18 @@ impl ComputeServer for CpuServer {
             bindings,
             kind,
             &mut self.ctx.memory_management,
-        );
+            &logger,
+        )
+    }
+
+    fn precompile(
+        &mut self,
+        kernel: Self::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        self.scheduler.compile(kernel, mode, &logger);
+        Ok(())
     }
 
     fn flush(&mut self) {}
@@ -6,7 +6,7 @@ use cubecl_core::{
     ir::{StorageType, TargetProperties},
 };
 use cubecl_runtime::{
-    ComputeRuntime, DeviceProperties,
+    ComputeRuntime, DeviceProperties, Plane,
     memory_management::{HardwareProperties, MemoryDeviceProperties, MemoryManagement},
     storage::BytesStorage,
 };
@@ -15,14 +15,27 @@ use sysinfo::System;
 
 use crate::{
     compiler::{MlirCompiler, register_supported_types},
-    compute::server::{CpuContext, CpuServer},
+    compute::{
+        plane::{DEFAULT_PLANE_SIZE, MAX_PLANE_SIZE},
+        server::{CpuContext, CpuServer},
+    },
     device::CpuDevice,
 };
 
-#[derive(Default)]
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// The number of units in a plane, at most [`MAX_PLANE_SIZE`].
+    pub plane_size: u32,
+}
+
+impl Default for RuntimeOptions {
+    fn default() -> Self {
+        Self {
+            memory_config: Default::default(),
+            plane_size: DEFAULT_PLANE_SIZE,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -35,7 +48,21 @@ pub type CpuCompiler = MlirCompiler;
 type Server = CpuServer;
 type Channel = MpscComputeChannel<Server>;
 
+/// Initialize the client of `device` with the given options, before it is first used.
+///
+/// # Panics
+///
+/// If the client of the device is already initialized.
+pub fn init(device: &CpuDevice, options: RuntimeOptions) {
+    RUNTIME.register(device, create_client(options));
+}
+
 fn create_client(options: RuntimeOptions) -> ComputeClient<Server, Channel> {
+    assert!(
+        (1..=MAX_PLANE_SIZE).contains(&options.plane_size),
+        "The plane size must be between 1 and {MAX_PLANE_SIZE}"
+    );
+
     let max_cube_dim = CubeDim::new(u32::MAX, u32::MAX, u32::MAX);
     let max_cube_count = CubeCount::Static(64, 64, 64);
     let system = System::new_all();
@@ -45,8 +72,8 @@ fn create_client(options: RuntimeOptions) -> ComputeClient<Server, Channel> {
         .unwrap_or(system.total_memory()) as usize;
 
     let topology = HardwareProperties {
-        plane_size_min: 1,
-        plane_size_max: 1,
+        plane_size_min: options.plane_size,
+        plane_size_max: options.plane_size,
         max_bindings: u32::MAX,
         max_shared_memory_size,
         max_cube_count,
@@ -74,9 +101,11 @@ fn create_client(options: RuntimeOptions) -> ComputeClient<Server, Channel> {
         TimingMethod::Device,
     );
     register_supported_types(&mut device_props);
+    device_props.features.plane.insert(Plane::Ops);
+    device_props.features.plane.insert(Plane::Sync);
 
     let ctx = CpuContext::new(memory_management);
-    let server = CpuServer::new(ctx);
+    let server = CpuServer::new(ctx, options.plane_size);
     ComputeClient::new(Channel::new(server), device_props, ())
 }
 
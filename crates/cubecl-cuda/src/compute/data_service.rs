@@ -7,6 +7,8 @@ use cudarc::driver::sys::cudaError_enum::CUDA_SUCCESS;
 use cudarc::driver::sys::{self};
 use std::{
     collections::HashMap,
+    ffi::c_void,
+    mem::MaybeUninit,
     sync::mpsc::{Receiver, SyncSender},
 };
 
@@ -15,6 +17,7 @@ static SERVICE: Mutex<Option<DataServiceClient>> = Mutex::new(None);
 pub(crate) struct DataTransferRuntime {
     recv: Receiver<DataTransferMsg>,
     transfers: HashMap<DataTransferId, DataTransferInfo>,
+    peers: PeerAccess,
 }
 
 /// A handle to a cuda resource with the context and stream.
@@ -102,6 +105,7 @@ impl DataTransferRuntime {
         let service = Self {
             recv,
             transfers: HashMap::new(),
+            peers: PeerAccess::default(),
         };
 
         std::thread::spawn(move || service.run());
@@ -131,7 +135,7 @@ impl DataTransferRuntime {
                 assert!(transfer.info_src.is_none(), "Can't send twice");
 
                 transfer.info_src = Some(info);
-                transfer.execute().unwrap();
+                transfer.execute(&mut self.peers).unwrap();
             }
             None => {
                 let transfer = DataTransferInfo {
@@ -155,7 +159,7 @@ impl DataTransferRuntime {
                 assert!(transfer.info_dest.is_none(), "Can't receive twice");
                 transfer.info_dest = Some(info);
 
-                transfer.execute().unwrap();
+                transfer.execute(&mut self.peers).unwrap();
             }
             None => {
                 let transfer = DataTransferInfo {
@@ -185,30 +189,15 @@ struct DataTransferInfoDest {
 }
 
 impl DataTransferInfo {
-    fn execute(self) -> Result<(), IoError> {
+    fn execute(self, peers: &mut PeerAccess) -> Result<(), IoError> {
         let info_src = self.info_src.expect("To be filled");
         let info_dest = self.info_dest.expect("To be filled");
+        let num_bytes = info_dest.item.resource.size as usize;
 
         unsafe {
-            cudarc::driver::result::ctx::set_current(info_dest.item.context).unwrap();
-
-            info_src.fence.wait_async(info_dest.item.stream);
-            let num_bytes = info_dest.item.resource.size as usize;
-
-            let result = sys::cuMemcpyPeerAsync(
-                info_dest.item.resource.ptr,
-                info_dest.item.context,
-                info_src.item.resource.ptr,
-                info_src.item.context,
-                num_bytes,
-                info_dest.item.stream,
-            )
-            .result();
-
-            // TODO: We should have a fallback when peer access isn't supported.
-            if let Err(_err) = result {
-                enable_one_way_peer_access(info_src.item.context)
-                    .expect("Can't enable peer access");
+            if peers.enable(info_dest.item.context, info_src.item.context) {
+                cudarc::driver::result::ctx::set_current(info_dest.item.context).unwrap();
+                info_src.fence.wait_async(info_dest.item.stream);
 
                 sys::cuMemcpyPeerAsync(
                     info_dest.item.resource.ptr,
@@ -219,7 +208,9 @@ impl DataTransferInfo {
                     info_dest.item.stream,
                 )
                 .result()
-                .expect("Peer communication is not activated for the provided GPUs");
+                .map_err(|err| IoError::Unknown(format!("CUDA peer memcpy failed: {err}")))?;
+            } else {
+                staged_copy(&info_src.item, info_src.fence, &info_dest.item, num_bytes)?;
             }
         };
 
@@ -229,11 +220,97 @@ impl DataTransferInfo {
     }
 }
 
-fn enable_one_way_peer_access(ctx_src: sys::CUcontext) -> Result<(), sys::CUresult> {
+/// Copies the data through host memory, for devices that can't access each other.
+///
+/// # Safety
+///
+/// The resources must be valid for `num_bytes` on their respective contexts.
+unsafe fn staged_copy(
+    src: &DataTransferItem,
+    fence: Fence,
+    dst: &DataTransferItem,
+    num_bytes: usize,
+) -> Result<(), IoError> {
+    let mut staging = vec![0u8; num_bytes];
+
     unsafe {
-        match sys::cuCtxEnablePeerAccess(ctx_src, 0) {
-            CUDA_SUCCESS | CUDA_ERROR_PEER_ACCESS_ALREADY_ENABLED => Ok(()),
-            err => Err(err),
+        cudarc::driver::result::ctx::set_current(src.context).unwrap();
+        fence.wait_sync();
+        sys::cuMemcpyDtoH_v2(
+            staging.as_mut_ptr() as *mut c_void,
+            src.resource.ptr,
+            num_bytes,
+        )
+        .result()
+        .map_err(|err| IoError::Unknown(format!("CUDA memcpy failed: {err}")))?;
+
+        cudarc::driver::result::ctx::set_current(dst.context).unwrap();
+        sys::cuMemcpyHtoDAsync_v2(
+            dst.resource.ptr,
+            staging.as_ptr() as *const c_void,
+            num_bytes,
+            dst.stream,
+        )
+        .result()
+        .map_err(|err| IoError::Unknown(format!("CUDA memcpy failed: {err}")))?;
+
+        // The staging buffer has to outlive the copy.
+        cudarc::driver::result::stream::synchronize(dst.stream).unwrap();
+    }
+
+    Ok(())
+}
+
+/// Keeps track of the peer access enabled between contexts.
+#[derive(Default)]
+struct PeerAccess {
+    enabled: HashMap<(usize, usize), bool>,
+}
+
+impl PeerAccess {
+    /// Enable the access of the `peer` context memory from the `ctx` context, returning whether the
+    /// devices support it.
+    fn enable(&mut self, ctx: sys::CUcontext, peer: sys::CUcontext) -> bool {
+        *self
+            .enabled
+            .entry((ctx as usize, peer as usize))
+            .or_insert_with(|| unsafe { enable_one_way_peer_access(ctx, peer) })
+    }
+}
+
+/// Enables peer access, returning whether it is supported.
+///
+/// # Safety
+///
+/// Both contexts must be valid.
+unsafe fn enable_one_way_peer_access(ctx: sys::CUcontext, peer: sys::CUcontext) -> bool {
+    unsafe {
+        let peer_device = context_device(peer);
+        let device = context_device(ctx);
+
+        let mut can_access = 0;
+        let supported = sys::cuDeviceCanAccessPeer(&mut can_access, device, peer_device)
+            .result()
+            .is_ok()
+            && can_access == 1;
+
+        if !supported {
+            return false;
+        }
+
+        match sys::cuCtxEnablePeerAccess(peer, 0) {
+            CUDA_SUCCESS | CUDA_ERROR_PEER_ACCESS_ALREADY_ENABLED => true,
+            _ => false,
         }
     }
 }
+
+/// Makes the context current and returns its device.
+unsafe fn context_device(ctx: sys::CUcontext) -> sys::CUdevice {
+    unsafe {
+        cudarc::driver::result::ctx::set_current(ctx).unwrap();
+        let mut device = MaybeUninit::uninit();
+        sys::cuCtxGetDevice(device.as_mut_ptr()).result().unwrap();
+        device.assume_init()
+    }
+}
@@ -0,0 +1,390 @@
+//! Zero-copy exchange of tensors with other frameworks using
+//! [DLPack](https://dmlc.github.io/dlpack/latest/).
+
+use std::ffi::c_void;
+
+use cubecl_core::{
+    Runtime,
+    ir::{ElemType, FloatKind, IntKind, UIntKind},
+    server::{Handle, IoError},
+};
+use cubecl_runtime::storage::BindingResource;
+
+use super::{CudaInterop, storage::gpu::GpuResource};
+use crate::{CudaDevice, CudaRuntime};
+
+/// The [types](DLDevice::device_type) of the DLPack devices.
+#[allow(missing_docs)]
+pub mod dl_device_type {
+    pub const CPU: i32 = 1;
+    pub const CUDA: i32 = 2;
+    pub const CUDA_HOST: i32 = 3;
+    pub const OPENCL: i32 = 4;
+    pub const VULKAN: i32 = 7;
+    pub const METAL: i32 = 8;
+    pub const ROCM: i32 = 10;
+    pub const CUDA_MANAGED: i32 = 13;
+    pub const WEBGPU: i32 = 15;
+}
+
+/// The device of a [DLPack tensor](DLTensor).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DLDevice {
+    /// The [type](dl_device_type) of the device.
+    pub device_type: i32,
+    /// The index of the device.
+    pub device_id: i32,
+}
+
+/// The [codes](DLDataType::code) of the DLPack data types.
+#[allow(missing_docs)]
+pub mod dl_data_type_code {
+    pub const INT: u8 = 0;
+    pub const UINT: u8 = 1;
+    pub const FLOAT: u8 = 2;
+    pub const OPAQUE_HANDLE: u8 = 3;
+    pub const BFLOAT: u8 = 4;
+    pub const COMPLEX: u8 = 5;
+    pub const BOOL: u8 = 6;
+    pub const FLOAT8_E4M3FN: u8 = 10;
+    pub const FLOAT8_E5M2: u8 = 12;
+}
+
+/// The element type of a [DLPack tensor](DLTensor).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DLDataType {
+    /// The [kind](dl_data_type_code) of the type.
+    pub code: u8,
+    /// The number of bits of each lane.
+    pub bits: u8,
+    /// The number of lanes of the type, for vector types.
+    pub lanes: u16,
+}
+
+/// A tensor described with DLPack, borrowing its memory.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLTensor {
+    /// The start of the allocation of the tensor, before [`byte_offset`](Self::byte_offset).
+    pub data: *mut c_void,
+    /// The device of the memory.
+    pub device: DLDevice,
+    /// The number of dimensions.
+    pub ndim: i32,
+    /// The element type.
+    pub dtype: DLDataType,
+    /// The `ndim` sizes of the tensor.
+    pub shape: *mut i64,
+    /// The `ndim` strides of the tensor in elements, or null for a contiguous row-major tensor.
+    pub strides: *mut i64,
+    /// The offset of the first element from [`data`](Self::data) in bytes.
+    pub byte_offset: u64,
+}
+
+/// A [DLPack tensor](DLTensor) with the context owning its memory, given from a producer to a
+/// consumer.
+///
+/// The consumer calls the [deleter](Self::deleter) once it is done with the tensor.
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLManagedTensor {
+    /// The tensor.
+    pub dl_tensor: DLTensor,
+    /// The context of the producer owning the memory of the tensor.
+    pub manager_ctx: *mut c_void,
+    /// Releases the tensor, called with a pointer to itself.
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+/// Error returned when a tensor can't be exchanged with DLPack.
+#[derive(Debug, thiserror::Error)]
+pub enum DlpackError {
+    /// The memory of the tensor isn't on the device of the client.
+    #[error("the tensor is on device {device_type}:{device_id} instead of Cuda({expected})")]
+    UnsupportedDevice {
+        /// The [type](dl_device_type) of the device of the tensor.
+        device_type: i32,
+        /// The index of the device of the tensor.
+        device_id: i32,
+        /// The index of the device of the client.
+        expected: usize,
+    },
+    /// The element type has no equivalent in cubecl.
+    #[error("unsupported data type {0:?}")]
+    UnsupportedDataType(DLDataType),
+    /// The element type has no equivalent in DLPack.
+    #[error("{0:?} has no DLPack equivalent")]
+    UnsupportedElem(ElemType),
+    /// The byte offset of the tensor isn't a multiple of the size of its elements.
+    #[error("byte offset of {byte_offset} isn't a multiple of the element size of {elem_size}")]
+    MisalignedOffset {
+        /// The byte offset of the tensor.
+        byte_offset: u64,
+        /// The size of the elements of the tensor.
+        elem_size: usize,
+    },
+    /// The tensor has negative sizes or strides.
+    #[error("the shape {shape:?} or strides {strides:?} contain negative values")]
+    NegativeLayout {
+        /// The shape of the tensor.
+        shape: Vec<i64>,
+        /// The strides of the tensor.
+        strides: Vec<i64>,
+    },
+    /// The shape and the strides of an exported tensor have different ranks.
+    #[error("the shape has {shape} dimensions but the strides have {strides}")]
+    RankMismatch {
+        /// The number of dimensions of the shape.
+        shape: usize,
+        /// The number of dimensions of the strides.
+        strides: usize,
+    },
+    /// The handle couldn't be imported or exported.
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+/// A tensor [imported](from_dlpack) from DLPack.
+#[derive(Debug)]
+pub struct DlpackTensor {
+    /// The handle of the memory of the tensor, starting at its first element.
+    pub handle: Handle,
+    /// The shape of the tensor.
+    pub shape: Vec<usize>,
+    /// The strides of the tensor in elements, possibly not contiguous.
+    pub strides: Vec<usize>,
+    /// The element type of the tensor.
+    pub elem: ElemType,
+}
+
+/// Import a DLPack tensor as a handle on the client of `device`, without copying it.
+///
+/// The tensor is owned by the handle on success: its deleter is called once the handle is
+/// dropped and the kernels using it are completed. The deleter is called from a separate thread,
+/// since the completion is detected from a CUDA host function. On error, the tensor is left
+/// untouched and still owned by the caller.
+///
+/// The work producing the tensor must be completed or ordered before the kernels of the client
+/// using it, e.g. with [`CudaInterop::wait_cuda_event`].
+///
+/// # Safety
+///
+/// The tensor must be a valid DLPack tensor, not used by the caller anymore on success.
+pub unsafe fn from_dlpack(
+    device: &CudaDevice,
+    tensor: *mut DLManagedTensor,
+) -> Result<DlpackTensor, DlpackError> {
+    let dl_tensor = unsafe { &(*tensor).dl_tensor };
+    let DLDevice {
+        device_type,
+        device_id,
+    } = dl_tensor.device;
+
+    if !matches!(
+        device_type,
+        dl_device_type::CUDA | dl_device_type::CUDA_MANAGED
+    ) || device_id as usize != device.index
+    {
+        return Err(DlpackError::UnsupportedDevice {
+            device_type,
+            device_id,
+            expected: device.index,
+        });
+    }
+
+    let elem = elem_from_dlpack(dl_tensor.dtype)?;
+    let elem_size = elem.size();
+
+    if !dl_tensor.byte_offset.is_multiple_of(elem_size as u64) {
+        return Err(DlpackError::MisalignedOffset {
+            byte_offset: dl_tensor.byte_offset,
+            elem_size,
+        });
+    }
+
+    let ndim = dl_tensor.ndim as usize;
+    let shape = unsafe { raw_slice(dl_tensor.shape, ndim) };
+    let strides = match dl_tensor.strides.is_null() {
+        true => contiguous_strides(shape),
+        false => unsafe { raw_slice(dl_tensor.strides, ndim) }.to_vec(),
+    };
+
+    if shape.iter().chain(strides.iter()).any(|value| *value < 0) {
+        return Err(DlpackError::NegativeLayout {
+            shape: shape.to_vec(),
+            strides,
+        });
+    }
+
+    let shape = shape.iter().map(|dim| *dim as usize).collect::<Vec<_>>();
+    let strides = strides.iter().map(|dim| *dim as usize).collect::<Vec<_>>();
+    let size = match shape.contains(&0) {
+        true => 0,
+        false => {
+            let last = shape
+                .iter()
+                .zip(&strides)
+                .map(|(dim, stride)| (dim - 1) * stride);
+            (last.sum::<usize>() + 1) * elem_size
+        }
+    };
+
+    let ptr = dl_tensor.data as u64 + dl_tensor.byte_offset;
+    let owner = ManagedTensor(tensor);
+    let on_release = Box::new(move || {
+        // Host functions can't call the CUDA API, which the deleter might do.
+        std::thread::spawn(move || {
+            // Moves the whole wrapper, which is `Send` unlike the pointer.
+            let owner = owner;
+            unsafe { owner.delete() }
+        });
+    });
+    let client = CudaRuntime::client(device);
+    let handle = unsafe { client.import_device_ptr(ptr, size as u64, Some(on_release)) }?;
+
+    Ok(DlpackTensor {
+        handle,
+        shape,
+        strides,
+        elem,
+    })
+}
+
+/// Export the memory of a handle on the client of `device` as a DLPack tensor, without copying
+/// it.
+///
+/// The memory isn't reused until the consumer calls the deleter of the returned tensor. The work
+/// of the client writing the tensor must be completed or ordered before the work of the
+/// consumer, e.g. with [`CudaInterop::record_cuda_event`].
+pub fn to_dlpack(
+    device: &CudaDevice,
+    handle: Handle,
+    shape: &[usize],
+    strides: &[usize],
+    elem: ElemType,
+) -> Result<*mut DLManagedTensor, DlpackError> {
+    if shape.len() != strides.len() {
+        return Err(DlpackError::RankMismatch {
+            shape: shape.len(),
+            strides: strides.len(),
+        });
+    }
+
+    let dtype = elem_to_dlpack(elem)?;
+    let client = CudaRuntime::client(device);
+    let resource = client.get_resource(handle.binding());
+
+    let mut ctx = Box::new(ExportContext {
+        shape: shape.iter().map(|dim| *dim as i64).collect(),
+        strides: strides.iter().map(|stride| *stride as i64).collect(),
+        _resource: resource,
+    });
+    let dl_tensor = DLTensor {
+        data: ctx._resource.resource().ptr as *mut c_void,
+        device: DLDevice {
+            device_type: dl_device_type::CUDA,
+            device_id: device.index as i32,
+        },
+        ndim: shape.len() as i32,
+        dtype,
+        shape: ctx.shape.as_mut_ptr(),
+        strides: ctx.strides.as_mut_ptr(),
+        byte_offset: 0,
+    };
+
+    Ok(Box::into_raw(Box::new(DLManagedTensor {
+        dl_tensor,
+        manager_ctx: Box::into_raw(ctx) as *mut c_void,
+        deleter: Some(delete_exported),
+    })))
+}
+
+/// Keeps the memory and the layout of an [exported](to_dlpack) tensor alive.
+struct ExportContext {
+    shape: Vec<i64>,
+    strides: Vec<i64>,
+    _resource: BindingResource<GpuResource>,
+}
+
+unsafe extern "C" fn delete_exported(tensor: *mut DLManagedTensor) {
+    let tensor = unsafe { Box::from_raw(tensor) };
+    drop(unsafe { Box::from_raw(tensor.manager_ctx as *mut ExportContext) });
+}
+
+/// An [imported](from_dlpack) tensor, deleted once cubecl is done with its memory.
+struct ManagedTensor(*mut DLManagedTensor);
+
+// The DLPack contract allows the deleter to be called from any thread.
+unsafe impl Send for ManagedTensor {}
+
+impl ManagedTensor {
+    unsafe fn delete(self) {
+        if let Some(deleter) = unsafe { (*self.0).deleter } {
+            unsafe { deleter(self.0) };
+        }
+    }
+}
+
+fn elem_from_dlpack(dtype: DLDataType) -> Result<ElemType, DlpackError> {
+    use dl_data_type_code::*;
+
+    let elem = match (dtype.code, dtype.bits, dtype.lanes) {
+        (INT, 8, 1) => ElemType::Int(IntKind::I8),
+        (INT, 16, 1) => ElemType::Int(IntKind::I16),
+        (INT, 32, 1) => ElemType::Int(IntKind::I32),
+        (INT, 64, 1) => ElemType::Int(IntKind::I64),
+        (UINT, 8, 1) => ElemType::UInt(UIntKind::U8),
+        (UINT, 16, 1) => ElemType::UInt(UIntKind::U16),
+        (UINT, 32, 1) => ElemType::UInt(UIntKind::U32),
+        (UINT, 64, 1) => ElemType::UInt(UIntKind::U64),
+        (FLOAT, 16, 1) => ElemType::Float(FloatKind::F16),
+        (FLOAT, 32, 1) => ElemType::Float(FloatKind::F32),
+        (FLOAT, 64, 1) => ElemType::Float(FloatKind::F64),
+        (BFLOAT, 16, 1) => ElemType::Float(FloatKind::BF16),
+        (FLOAT8_E4M3FN, 8, 1) => ElemType::Float(FloatKind::E4M3),
+        (FLOAT8_E5M2, 8, 1) => ElemType::Float(FloatKind::E5M2),
+        _ => return Err(DlpackError::UnsupportedDataType(dtype)),
+    };
+
+    Ok(elem)
+}
+
+fn elem_to_dlpack(elem: ElemType) -> Result<DLDataType, DlpackError> {
+    use dl_data_type_code::*;
+
+    let code = match elem {
+        ElemType::Int(_) => INT,
+        ElemType::UInt(_) => UINT,
+        ElemType::Float(FloatKind::F16 | FloatKind::F32 | FloatKind::F64) => FLOAT,
+        ElemType::Float(FloatKind::BF16) => BFLOAT,
+        ElemType::Float(FloatKind::E4M3) => FLOAT8_E4M3FN,
+        ElemType::Float(FloatKind::E5M2) => FLOAT8_E5M2,
+        _ => return Err(DlpackError::UnsupportedElem(elem)),
+    };
+
+    Ok(DLDataType {
+        code,
+        bits: elem.size_bits() as u8,
+        lanes: 1,
+    })
+}
+
+fn contiguous_strides(shape: &[i64]) -> Vec<i64> {
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+    strides
+}
+
+unsafe fn raw_slice<'a>(ptr: *const i64, len: usize) -> &'a [i64] {
+    match len {
+        0 => &[],
+        _ => unsafe { core::slice::from_raw_parts(ptr, len) },
+    }
+}
+
+#[cfg(test)]
+mod tests;
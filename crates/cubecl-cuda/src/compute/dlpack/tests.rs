@@ -0,0 +1,167 @@
+use std::{
+    ffi::c_void,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use cubecl_core::{
+    ir::{ElemType, FloatKind},
+    prelude::*,
+};
+use cudarc::driver::result;
+
+use super::*;
+use crate::{CudaDevice, CudaRuntime};
+
+const F32: DLDataType = DLDataType {
+    code: dl_data_type_code::FLOAT,
+    bits: 32,
+    lanes: 1,
+};
+
+/// A tensor produced outside of cubecl, counting the calls to its deleter.
+struct Producer {
+    managed: DLManagedTensor,
+    shape: Vec<i64>,
+    strides: Vec<i64>,
+    deleted: AtomicUsize,
+}
+
+unsafe extern "C" fn delete_produced(tensor: *mut DLManagedTensor) {
+    let producer = unsafe { &*((*tensor).manager_ctx as *const Producer) };
+    producer.deleted.fetch_add(1, Ordering::SeqCst);
+}
+
+impl Producer {
+    /// Allocate the data with the primary context used by the runtime.
+    fn new(data: &[f32], shape: Vec<i64>, strides: Vec<i64>) -> Box<Self> {
+        let ptr = unsafe {
+            let device = result::device::get(0).unwrap();
+            let ctx = result::primary_ctx::retain(device).unwrap();
+            result::ctx::set_current(ctx).unwrap();
+
+            let ptr = result::malloc_sync(size_of_val(data)).unwrap();
+            result::memcpy_htod_sync(ptr, data).unwrap();
+            ptr
+        };
+
+        let mut producer = Box::new(Self {
+            managed: DLManagedTensor {
+                dl_tensor: DLTensor {
+                    data: ptr as *mut c_void,
+                    device: DLDevice {
+                        device_type: dl_device_type::CUDA,
+                        device_id: 0,
+                    },
+                    ndim: shape.len() as i32,
+                    dtype: F32,
+                    shape: core::ptr::null_mut(),
+                    strides: core::ptr::null_mut(),
+                    byte_offset: 0,
+                },
+                manager_ctx: core::ptr::null_mut(),
+                deleter: Some(delete_produced),
+            },
+            shape,
+            strides,
+            deleted: AtomicUsize::new(0),
+        });
+
+        producer.managed.dl_tensor.shape = producer.shape.as_mut_ptr();
+        producer.managed.dl_tensor.strides = producer.strides.as_mut_ptr();
+        producer.managed.manager_ctx = &*producer as *const Self as *mut c_void;
+        producer
+    }
+
+    fn wait_deleted(&self) {
+        let start = Instant::now();
+        while self.deleted.load(Ordering::SeqCst) == 0 {
+            assert!(start.elapsed() < Duration::from_secs(5), "never deleted");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+impl Drop for Producer {
+    fn drop(&mut self) {
+        unsafe { result::free_sync(self.managed.dl_tensor.data as u64).unwrap() };
+    }
+}
+
+#[test]
+fn import_strided_tensor() {
+    let device = CudaDevice::default();
+    let client = CudaRuntime::client(&device);
+    let data: Vec<f32> = (0..6).map(|i| i as f32).collect();
+    // The transpose of a 2x3 row-major tensor.
+    let mut producer = Producer::new(&data, vec![3, 2], vec![1, 3]);
+
+    let tensor = unsafe { from_dlpack(&device, &mut producer.managed) }.unwrap();
+    assert_eq!(tensor.shape, [3, 2]);
+    assert_eq!(tensor.strides, [1, 3]);
+    assert_eq!(tensor.elem, ElemType::Float(FloatKind::F32));
+
+    let actual = client.read_one(tensor.handle);
+    assert_eq!(f32::from_bytes(&actual), data);
+
+    client.memory_cleanup(Default::default());
+    cubecl_core::future::block_on(client.sync());
+    producer.wait_deleted();
+    assert_eq!(producer.deleted.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn export_round_trip() {
+    let device = CudaDevice::default();
+    let client = CudaRuntime::client(&device);
+    let data = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let handle = client.create(f32::as_bytes(&data));
+    let elem = ElemType::Float(FloatKind::F32);
+
+    let exported = to_dlpack(&device, handle, &[2, 3], &[3, 1], elem).unwrap();
+    unsafe {
+        let dl_tensor = &(*exported).dl_tensor;
+        assert_eq!(dl_tensor.device.device_type, dl_device_type::CUDA);
+        assert_eq!(dl_tensor.dtype, F32);
+        assert_eq!(core::slice::from_raw_parts(dl_tensor.shape, 2), [2, 3]);
+        assert_eq!(core::slice::from_raw_parts(dl_tensor.strides, 2), [3, 1]);
+    }
+
+    // The exported memory stays valid after the handle is dropped, until the deleter is called.
+    cubecl_core::future::block_on(client.sync());
+    let tensor = unsafe { from_dlpack(&device, exported) }.unwrap();
+    assert_eq!(tensor.shape, [2, 3]);
+    assert_eq!(tensor.strides, [3, 1]);
+    assert_eq!(tensor.elem, elem);
+
+    let actual = client.read_one(tensor.handle);
+    assert_eq!(f32::from_bytes(&actual), data);
+}
+
+#[test]
+fn reject_unsupported_tensors() {
+    let device = CudaDevice::default();
+    let mut producer = Producer::new(&[0.0; 4], vec![4], vec![1]);
+
+    producer.managed.dl_tensor.device.device_type = dl_device_type::CPU;
+    let err = unsafe { from_dlpack(&device, &mut producer.managed) }.unwrap_err();
+    assert!(matches!(err, DlpackError::UnsupportedDevice { .. }));
+    producer.managed.dl_tensor.device.device_type = dl_device_type::CUDA;
+
+    producer.managed.dl_tensor.byte_offset = 2;
+    let err = unsafe { from_dlpack(&device, &mut producer.managed) }.unwrap_err();
+    assert!(matches!(err, DlpackError::MisalignedOffset { .. }));
+    producer.managed.dl_tensor.byte_offset = 0;
+
+    producer.managed.dl_tensor.dtype.code = dl_data_type_code::COMPLEX;
+    let err = unsafe { from_dlpack(&device, &mut producer.managed) }.unwrap_err();
+    assert!(matches!(err, DlpackError::UnsupportedDataType(_)));
+    producer.managed.dl_tensor.dtype = F32;
+
+    producer.strides[0] = -1;
+    let err = unsafe { from_dlpack(&device, &mut producer.managed) }.unwrap_err();
+    assert!(matches!(err, DlpackError::NegativeLayout { .. }));
+
+    // The caller keeps the tensors that failed to be imported.
+    assert_eq!(producer.deleted.load(Ordering::SeqCst), 0);
+}
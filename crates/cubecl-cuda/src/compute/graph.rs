@@ -0,0 +1,142 @@
+use cubecl_core::server::{BatchId, Binding, IoError};
+use cudarc::driver::sys::{
+    CUgraph, CUgraphExec, CUgraphExecUpdateResultInfo, CUstream, CUstreamCaptureMode,
+    cuGraphDestroy, cuGraphExecDestroy, cuGraphExecUpdate_v2, cuGraphInstantiateWithFlags,
+    cuGraphLaunch, cuStreamBeginCapture_v2, cuStreamEndCapture,
+};
+use std::{collections::HashMap, mem::MaybeUninit};
+
+/// The batches of a [context](super::CudaContext) recorded into CUDA graphs.
+#[derive(Default, Debug)]
+pub(crate) struct CudaGraphs {
+    graphs: HashMap<u64, CudaGraph>,
+    counter: u64,
+}
+
+#[derive(Debug)]
+struct CudaGraph {
+    exec: CUgraphExec,
+    /// Bindings used by the kernels of the graph, including their metadata and scalars, kept
+    /// alive as long as the graph can be launched.
+    bindings: Vec<Binding>,
+}
+
+impl CudaGraphs {
+    /// Start capturing the work submitted to the stream into a graph instead of executing it.
+    pub fn begin_capture(stream: CUstream) -> Result<(), IoError> {
+        unsafe {
+            // Relaxed, since kernels compiled for the first time during the capture are loaded
+            // into the context.
+            cuStreamBeginCapture_v2(stream, CUstreamCaptureMode::CU_STREAM_CAPTURE_MODE_RELAXED)
+                .result()
+                .map_err(|err| IoError::Unknown(format!("Failed to start the capture: {err:?}")))
+        }
+    }
+
+    /// Stop capturing the work submitted to the stream, registering the captured graph.
+    pub fn end_capture(
+        &mut self,
+        stream: CUstream,
+        bindings: Vec<Binding>,
+    ) -> Result<BatchId, IoError> {
+        let exec = with_captured_graph(stream, |graph| unsafe { instantiate(graph) })?;
+
+        let id = self.counter;
+        self.counter += 1;
+        self.graphs.insert(id, CudaGraph { exec, bindings });
+
+        Ok(BatchId { id })
+    }
+
+    /// Launch a graph on the stream, returning the bindings it uses.
+    pub fn launch(&self, batch: BatchId, stream: CUstream) -> Result<&[Binding], IoError> {
+        let graph = self.graphs.get(&batch.id).ok_or(IoError::InvalidHandle)?;
+
+        unsafe {
+            cuGraphLaunch(graph.exec, stream)
+                .result()
+                .map_err(|err| IoError::Unknown(format!("Failed to launch the batch: {err:?}")))?;
+        }
+
+        Ok(&graph.bindings)
+    }
+
+    /// Stop capturing the work submitted to the stream, updating a registered graph with the
+    /// captured one.
+    ///
+    /// The parameters of the nodes of the graph are updated in place when only they changed,
+    /// otherwise the captured graph is instantiated in place of the registered one.
+    pub fn end_update(
+        &mut self,
+        stream: CUstream,
+        batch: BatchId,
+        bindings: Vec<Binding>,
+    ) -> Result<(), IoError> {
+        let graphs = &mut self.graphs;
+
+        with_captured_graph(stream, |graph| {
+            let recorded = graphs.get_mut(&batch.id).ok_or(IoError::InvalidHandle)?;
+            let mut info = MaybeUninit::<CUgraphExecUpdateResultInfo>::zeroed();
+
+            unsafe {
+                let updated = cuGraphExecUpdate_v2(recorded.exec, graph, info.as_mut_ptr());
+                if updated.result().is_err() {
+                    let exec = instantiate(graph)?;
+                    cuGraphExecDestroy(recorded.exec).result().unwrap();
+                    recorded.exec = exec;
+                }
+            }
+
+            // The replays in flight keep the previous bindings alive until they are done.
+            recorded.bindings = bindings;
+            Ok(())
+        })
+    }
+
+    /// Release a graph, its launches in flight still completing.
+    pub fn release(&mut self, batch: BatchId) {
+        self.graphs.remove(&batch.id);
+    }
+}
+
+/// Stop capturing the work submitted to the stream, calling `func` with the captured graph.
+fn with_captured_graph<O>(
+    stream: CUstream,
+    func: impl FnOnce(CUgraph) -> Result<O, IoError>,
+) -> Result<O, IoError> {
+    let mut graph: CUgraph = std::ptr::null_mut();
+
+    unsafe {
+        let captured = cuStreamEndCapture(stream, &mut graph)
+            .result()
+            .map_err(|err| IoError::Unknown(format!("Failed to capture the batch: {err:?}")));
+        let output = captured.and_then(|_| func(graph));
+
+        // The executable graphs don't depend on the captured graph.
+        if !graph.is_null() {
+            cuGraphDestroy(graph).result().unwrap();
+        }
+
+        output
+    }
+}
+
+unsafe fn instantiate(graph: CUgraph) -> Result<CUgraphExec, IoError> {
+    let mut exec: CUgraphExec = std::ptr::null_mut();
+
+    unsafe {
+        cuGraphInstantiateWithFlags(&mut exec, graph, 0)
+            .result()
+            .map_err(|err| IoError::Unknown(format!("Failed to instantiate the batch: {err:?}")))?;
+    }
+
+    Ok(exec)
+}
+
+impl Drop for CudaGraph {
+    fn drop(&mut self) {
+        unsafe {
+            cuGraphExecDestroy(self.exec).result().unwrap();
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use cubecl_core::server::{Handle, IoError, NativeEvent};
+use cubecl_runtime::{channel::MutexComputeChannel, client::ComputeClient};
+use cudarc::driver::sys::{CUdeviceptr, CUevent};
+
+use super::{CudaServer, storage::gpu::GpuResource};
+
+/// Zero-copy interop between a [client](ComputeClient) of the CUDA runtime and CUDA code running
+/// on the same device.
+///
+/// # Synchronization
+///
+/// The kernels of the client execute on its own stream, so work submitted to other streams isn't
+/// ordered with them. Use [`wait_cuda_event`](Self::wait_cuda_event) before launching kernels
+/// using memory written on another stream, and [`record_cuda_event`](Self::record_cuda_event)
+/// before using memory written by the client on another stream.
+pub trait CudaInterop {
+    /// Import `size` bytes of device memory allocated outside of the client as a handle, without
+    /// copying them.
+    ///
+    /// The memory is never reused for other allocations nor freed by the client. `on_release` is
+    /// called once the handle is dropped and the work submitted by the client using it is
+    /// completed, after which the memory can be freed. It is called from a CUDA host function, so
+    /// it must not call the CUDA API.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for `size` bytes on the device of the client until `on_release`
+    /// is called, or for as long as the client lives without a callback.
+    unsafe fn import_device_ptr(
+        &self,
+        ptr: CUdeviceptr,
+        size: u64,
+        on_release: Option<Box<dyn FnOnce() + Send>>,
+    ) -> Result<Handle, IoError>;
+
+    /// Make the work submitted to the client from now on wait for the completion of the work
+    /// captured by `event`.
+    ///
+    /// # Safety
+    ///
+    /// The event must be valid and created in the context of the device of the client.
+    unsafe fn wait_cuda_event(&self, event: CUevent) -> Result<(), IoError>;
+
+    /// Capture the work submitted to the client so far in `event`, for other streams to wait on.
+    ///
+    /// # Safety
+    ///
+    /// The event must be valid and created in the context of the device of the client.
+    unsafe fn record_cuda_event(&self, event: CUevent) -> Result<(), IoError>;
+}
+
+impl CudaInterop for ComputeClient<CudaServer, MutexComputeChannel<CudaServer>> {
+    unsafe fn import_device_ptr(
+        &self,
+        ptr: CUdeviceptr,
+        size: u64,
+        on_release: Option<Box<dyn FnOnce() + Send>>,
+    ) -> Result<Handle, IoError> {
+        let resource = unsafe { GpuResource::external(ptr, size, on_release) };
+        self.import_resource(resource)
+    }
+
+    unsafe fn wait_cuda_event(&self, event: CUevent) -> Result<(), IoError> {
+        unsafe { self.wait_native_event(native_event(event)) }
+    }
+
+    unsafe fn record_cuda_event(&self, event: CUevent) -> Result<(), IoError> {
+        unsafe { self.record_native_event(native_event(event)) }
+    }
+}
+
+fn native_event(event: CUevent) -> NativeEvent {
+    NativeEvent {
+        handle: event as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,82 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use cubecl_core::{prelude::*, runtime_tests::to_client::kernel_matmul};
+use cudarc::driver::{result, sys::CUdeviceptr};
+
+use super::CudaInterop;
+use crate::CudaRuntime;
+
+/// Allocate device memory outside of the client, in the primary context used by the runtime.
+fn malloc(data: &[f32]) -> CUdeviceptr {
+    unsafe {
+        let device = result::device::get(0).unwrap();
+        let ctx = result::primary_ctx::retain(device).unwrap();
+        result::ctx::set_current(ctx).unwrap();
+
+        let ptr = result::malloc_sync(size_of_val(data)).unwrap();
+        result::memcpy_htod_sync(ptr, data).unwrap();
+        ptr
+    }
+}
+
+#[test]
+fn matmul_on_imported_memory() {
+    let client = CudaRuntime::client(&Default::default());
+    let (m, k, n) = (4, 3, 4);
+
+    let lhs: Vec<f32> = (0..m * k).map(|i| i as f32).collect();
+    let rhs: Vec<f32> = (0..k * n).map(|i| (i % 5) as f32).collect();
+    let ptrs = [malloc(&lhs), malloc(&rhs), malloc(&vec![0.0; m * n])];
+    let sizes = [m * k, k * n, m * n].map(|len| (len * size_of::<f32>()) as u64);
+    let released = Arc::new(AtomicUsize::new(0));
+
+    let import = |ptr, size| {
+        let released = released.clone();
+        let on_release = Box::new(move || {
+            released.fetch_add(1, Ordering::Relaxed);
+        });
+        unsafe { client.import_device_ptr(ptr, size, Some(on_release)) }.unwrap()
+    };
+    let lhs_handle = import(ptrs[0], sizes[0]);
+    let rhs_handle = import(ptrs[1], sizes[1]);
+    let out = import(ptrs[2], sizes[2]);
+
+    unsafe {
+        kernel_matmul::launch::<CudaRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_2d(n as u32, m as u32),
+            ArrayArg::from_raw_parts::<f32>(&lhs_handle, m * k, 1),
+            ArrayArg::from_raw_parts::<f32>(&rhs_handle, k * n, 1),
+            ArrayArg::from_raw_parts::<f32>(&out, m * n, 1),
+            k as u32,
+        )
+    };
+
+    let actual = client.read_one(out);
+    let actual = f32::from_bytes(&actual);
+
+    let mut expected = vec![0.0; m * n];
+    for row in 0..m {
+        for col in 0..n {
+            for i in 0..k {
+                expected[row * n + col] += lhs[row * k + i] * rhs[i * n + col];
+            }
+        }
+    }
+    assert_eq!(actual, expected);
+    assert_eq!(released.load(Ordering::Relaxed), 0);
+
+    drop(lhs_handle);
+    drop(rhs_handle);
+    client.memory_cleanup();
+    cubecl_core::future::block_on(client.sync());
+    assert_eq!(released.load(Ordering::Relaxed), 3);
+
+    for ptr in ptrs {
+        unsafe { result::free_sync(ptr).unwrap() };
+    }
+}
@@ -115,7 +115,7 @@ pub fn register_copy_to_bytes(
 /// # Returns
 ///
 /// An [Option] containing a [Bytes] instance if pinned memory is used, or [None] if regular memory should be used instead.
-fn bytes_from_managed_pinned_memory(
+pub(crate) fn bytes_from_managed_pinned_memory(
     ctx: &mut CudaContext,
     num_bytes: usize,
     marked_pinned: bool,
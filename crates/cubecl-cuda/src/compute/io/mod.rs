@@ -1,4 +1,7 @@
 pub(crate) mod controller;
 
 mod base;
+mod pinned;
+
 pub use base::*;
+pub use pinned::*;
@@ -0,0 +1,135 @@
+use super::bytes_from_managed_pinned_memory;
+use crate::compute::{CudaContext, sync::PendingTransfer, valid_strides};
+use cubecl_core::server::{Binding, CopyDescriptor, IoError, PinnedBuffer};
+use cudarc::driver::sys::{
+    CUDA_MEMCPY2D_st, CUdeviceptr, CUmemorytype, cuMemcpy2DAsync_v2, cuMemcpyAsync,
+};
+use std::ffi::c_void;
+
+/// A transfer between a [pinned buffer](PinnedBuffer) and the device enqueued on the stream.
+///
+/// The device memory binding is kept alive with the host buffer until the transfer is completed.
+pub type PinnedTransfer = PendingTransfer<(PinnedBuffer, Binding)>;
+
+/// Allocates a [pinned buffer](PinnedBuffer) of `size` bytes from the pinned memory pool.
+pub fn create_pinned(ctx: &mut CudaContext, size: usize) -> Result<PinnedBuffer, IoError> {
+    bytes_from_managed_pinned_memory(ctx, size, true)
+        .map(PinnedBuffer::new)
+        .ok_or(IoError::BufferTooBig(size))
+}
+
+/// Enqueues a copy from the [pinned buffer](PinnedBuffer) to the device.
+pub fn write_from_pinned(
+    ctx: &mut CudaContext,
+    src: PinnedBuffer,
+    dst: CopyDescriptor<'_>,
+) -> Result<PinnedTransfer, IoError> {
+    let binding = dst.binding.clone();
+    let device_ptr = device_ptr(ctx, &dst)?;
+    let host_ptr = src.as_ptr() as *mut c_void;
+
+    enqueue_copy(ctx, &dst, src.check_size(&dst)?, host_ptr, device_ptr, true)?;
+
+    Ok(PendingTransfer::new((src, binding), ctx.fence()))
+}
+
+/// Enqueues a copy from the device to the [pinned buffer](PinnedBuffer).
+pub fn read_to_pinned(
+    ctx: &mut CudaContext,
+    src: CopyDescriptor<'_>,
+    mut dst: PinnedBuffer,
+) -> Result<PinnedTransfer, IoError> {
+    let binding = src.binding.clone();
+    let device_ptr = device_ptr(ctx, &src)?;
+    let host_ptr = dst.as_mut_ptr() as *mut c_void;
+
+    enqueue_copy(
+        ctx,
+        &src,
+        dst.check_size(&src)?,
+        host_ptr,
+        device_ptr,
+        false,
+    )?;
+
+    Ok(PendingTransfer::new((dst, binding), ctx.fence()))
+}
+
+fn device_ptr(
+    ctx: &mut CudaContext,
+    descriptor: &CopyDescriptor<'_>,
+) -> Result<CUdeviceptr, IoError> {
+    if !valid_strides(descriptor.shape, descriptor.strides) {
+        return Err(IoError::UnsupportedStrides);
+    }
+
+    let binding = descriptor.binding.clone();
+    let resource = ctx
+        .memory_management_gpu
+        .get_resource(binding.memory, binding.offset_start, binding.offset_end)
+        .ok_or(IoError::InvalidHandle)?;
+
+    Ok(resource.ptr)
+}
+
+/// Enqueues the copy on the stream of the context, the host memory being packed contiguously.
+fn enqueue_copy(
+    ctx: &mut CudaContext,
+    descriptor: &CopyDescriptor<'_>,
+    num_bytes: usize,
+    host_ptr: *mut c_void,
+    device_ptr: CUdeviceptr,
+    host_to_device: bool,
+) -> Result<(), IoError> {
+    let shape = descriptor.shape;
+    let rank = shape.len();
+
+    if rank <= 1 {
+        let (dst, src) = match host_to_device {
+            true => (device_ptr, host_ptr as CUdeviceptr),
+            false => (host_ptr as CUdeviceptr, device_ptr),
+        };
+
+        // With unified addressing the driver infers the direction of the copy from the pointers.
+        return unsafe {
+            cuMemcpyAsync(dst, src, num_bytes, ctx.stream)
+                .result()
+                .map_err(|e| IoError::Unknown(format!("CUDA memcpy failed: {}", e)))
+        };
+    }
+
+    let width_bytes = shape[rank - 1] * descriptor.elem_size;
+    let height: usize = shape.iter().rev().skip(1).product();
+    let pitch = descriptor.strides[rank - 2] * descriptor.elem_size;
+
+    let cpy = match host_to_device {
+        true => CUDA_MEMCPY2D_st {
+            srcMemoryType: CUmemorytype::CU_MEMORYTYPE_HOST,
+            srcHost: host_ptr as *const c_void,
+            srcPitch: width_bytes,
+            dstMemoryType: CUmemorytype::CU_MEMORYTYPE_DEVICE,
+            dstDevice: device_ptr,
+            dstPitch: pitch,
+            WidthInBytes: width_bytes,
+            Height: height,
+            ..Default::default()
+        },
+        false => CUDA_MEMCPY2D_st {
+            srcMemoryType: CUmemorytype::CU_MEMORYTYPE_DEVICE,
+            srcDevice: device_ptr,
+            srcPitch: pitch,
+            dstMemoryType: CUmemorytype::CU_MEMORYTYPE_HOST,
+            dstHost: host_ptr,
+            dstPitch: width_bytes,
+            WidthInBytes: width_bytes,
+            Height: height,
+            ..Default::default()
+        },
+    };
+
+    unsafe {
+        cuMemcpy2DAsync_v2(&cpy, ctx.stream)
+            .result()
+            .map_err(|e| IoError::Unknown(format!("CUDA 2D memcpy failed: {}", e)))
+    }
+}
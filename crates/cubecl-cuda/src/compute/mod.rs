@@ -1,11 +1,17 @@
+pub mod dlpack;
+pub(crate) mod graph;
 pub(crate) mod io;
 pub(crate) mod storage;
+pub(crate) mod stream;
 pub(crate) mod sync;
+pub(crate) mod timings;
 
 mod data_service;
+mod interop;
 mod server;
 
 pub use data_service::*;
+pub use interop::*;
 pub use server::*;
 
 #[allow(clippy::uninit_vec)]
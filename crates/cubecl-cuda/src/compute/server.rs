@@ -1,6 +1,9 @@
 use cubecl_core::{
     compute::{CubeTask, DebugInformation},
-    server::{DataTransferService, IoError},
+    server::{
+        BatchId, BatchedKernel, CompilationError, CompilationStage, DataTransferService,
+        ExecutionStream, IoError, NativeEvent, PinnedBuffer, StreamEvent,
+    },
 };
 use cubecl_core::{
     future::{self, DynFut},
@@ -13,13 +16,17 @@ use cubecl_core::{
 use cubecl_cpp::formatter::format_cpp;
 use cubecl_cpp::{cuda::arch::CudaArchitecture, shared::CompilationOptions};
 
+use super::graph::CudaGraphs;
 use super::storage::gpu::{GpuResource, GpuStorage};
-use super::sync::{Fence, SyncStream};
+use super::stream::CudaStreams;
+use super::sync::{Fence, PendingTransfer, SyncStream, Watchdog};
+use super::timings::EventTimings;
+use crate::CudaCompiler;
 use crate::compute::{
-    DataTransferItem, DataTransferRuntime, io::register_copies_to_bytes,
+    DataTransferItem, DataTransferRuntime,
+    io::{self, register_copies_to_bytes},
     storage::cpu::PinnedMemoryStorage,
 };
-use crate::{CudaCompiler, WmmaCompiler};
 use cubecl_common::{bytes::Bytes, profile::ProfileDuration};
 use cubecl_core::ir::{ElemType, IntKind, UIntKind};
 use cubecl_core::prelude::*;
@@ -29,7 +36,9 @@ use cubecl_core::{
 };
 use cubecl_runtime::data_service::DataTransferId;
 use cubecl_runtime::logging::ServerLogger;
-use cubecl_runtime::memory_management::MemoryUsage;
+use cubecl_runtime::memory_management::{
+    HardwareProperties, MemoryCleanupMode, MemoryScopeUsage, MemoryUsage,
+};
 use cubecl_runtime::storage::BindingResource;
 use cubecl_runtime::{
     memory_management::MemoryManagement,
@@ -39,9 +48,9 @@ use cubecl_runtime::{memory_management::offset_handles, timestamp_profiler::Time
 use cudarc::driver::sys::{
     CUDA_MEMCPY2D_st, CUctx_st, CUfunction_attribute, CUmemorytype, CUtensorMap,
     CUtensorMapDataType, CUtensorMapFloatOOBfill, CUtensorMapL2promotion, CUtensorMapSwizzle,
-    cuMemcpy2DAsync_v2, cuTensorMapEncodeIm2col, cuTensorMapEncodeTiled,
+    cuMemcpy2D_v2, cuMemcpy2DAsync_v2, cuTensorMapEncodeIm2col, cuTensorMapEncodeTiled,
 };
-use cudarc::driver::sys::{CUfunc_st, CUtensorMapInterleave};
+use cudarc::driver::sys::{CUevent, CUevent_wait_flags, CUfunc_st, CUtensorMapInterleave};
 #[cfg(feature = "cuda-12080")]
 use cudarc::driver::sys::{CUtensorMapIm2ColWideMode, cuTensorMapEncodeIm2colWide};
 use std::collections::HashMap;
@@ -49,6 +58,7 @@ use std::ffi::c_char;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{ffi::CStr, os::raw::c_void};
 use std::{ffi::CString, mem::MaybeUninit};
 
@@ -69,12 +79,19 @@ pub(crate) struct CudaContext {
     pub(crate) stream: cudarc::driver::sys::CUstream,
     pub(crate) memory_management_gpu: MemoryManagement<GpuStorage>,
     pub(crate) memory_management_cpu: MemoryManagement<PinnedMemoryStorage>,
+    pub(crate) streams: CudaStreams,
+    graphs: CudaGraphs,
+    /// If the work submitted to the stream is being captured into a graph.
+    capturing: bool,
     module_names: HashMap<KernelId, CompiledKernel>,
     #[cfg(feature = "compilation-cache")]
     ptx_cache: Option<Cache<String, PtxCacheEntry>>,
     timestamps: TimestampProfiler,
+    device_timings: EventTimings,
     pub(crate) arch: CudaArchitecture,
     compilation_options: CompilationOptions,
+    hardware_properties: HardwareProperties,
+    watchdog: Watchdog,
 }
 
 #[cfg(feature = "compilation-cache")]
@@ -83,6 +100,7 @@ pub struct PtxCacheEntry {
     entrypoint_name: String,
     cube_dim: (u32, u32, u32),
     shared_mem_bytes: usize,
+    shared_memory_carveout: Option<u8>,
     cluster_dim: Option<(u32, u32, u32)>,
     ptx: Vec<c_char>,
 }
@@ -90,6 +108,7 @@ pub struct PtxCacheEntry {
 #[derive(Debug)]
 struct CompiledKernel {
     cube_dim: CubeDim,
+    /// The bytes of dynamic shared memory the kernel is launched with.
     shared_mem_bytes: usize,
     func: *mut CUfunc_st,
 }
@@ -102,13 +121,13 @@ impl CudaServer {
         descriptors: Vec<CopyDescriptor<'_>>,
     ) -> impl Future<Output = Result<Vec<Bytes>, IoError>> + Send + use<> {
         let ctx = self.get_context();
-        let result = register_copies_to_bytes(ctx, descriptors);
-        let fence = ctx.fence();
+        let result = ctx
+            .watchdog
+            .check()
+            .and_then(|_| register_copies_to_bytes(ctx, descriptors));
+        let transfer = PendingTransfer::new(result, ctx.fence(), ctx.watchdog.clone());
 
-        async move {
-            fence.wait_sync();
-            result
-        }
+        async move { transfer.await? }
     }
 
     fn sync_stream_async(&mut self) -> impl Future<Output = ()> + Send + use<> {
@@ -173,6 +192,7 @@ impl ComputeServer for CudaServer {
         }
 
         let ctx = self.get_context();
+        ctx.watchdog.check()?;
         let handle = ctx.memory_management_gpu.reserve(total_size as u64)?;
         let mem_handle = server::Handle::new(handle, None, None, total_size as u64);
 
@@ -187,6 +207,7 @@ impl ComputeServer for CudaServer {
 
     fn write(&mut self, descriptors: Vec<(CopyDescriptor<'_>, &[u8])>) -> Result<(), IoError> {
         let ctx = self.get_context();
+        ctx.watchdog.check()?;
 
         for (descriptor, data) in descriptors {
             let CopyDescriptor {
@@ -225,12 +246,22 @@ impl ComputeServer for CudaServer {
                 };
 
                 unsafe {
-                    cuMemcpy2DAsync_v2(&cpy, ctx.stream).result().unwrap();
+                    match ctx.capturing {
+                        true => cuMemcpy2D_v2(&cpy).result().unwrap(),
+                        false => cuMemcpy2DAsync_v2(&cpy, ctx.stream).result().unwrap(),
+                    }
                 }
             } else {
                 unsafe {
-                    cudarc::driver::result::memcpy_htod_async(resource.ptr, data, ctx.stream)
-                        .unwrap();
+                    match ctx.capturing {
+                        true => cudarc::driver::result::memcpy_htod_sync(resource.ptr, data),
+                        false => cudarc::driver::result::memcpy_htod_async(
+                            resource.ptr,
+                            data,
+                            ctx.stream,
+                        ),
+                    }
+                    .unwrap();
                 }
             }
         }
@@ -238,6 +269,33 @@ impl ComputeServer for CudaServer {
         Ok(())
     }
 
+    fn create_pinned(&mut self, size: usize) -> Result<PinnedBuffer, IoError> {
+        let ctx = self.get_context();
+        io::create_pinned(ctx, size)
+    }
+
+    fn write_from_pinned(
+        &mut self,
+        src: PinnedBuffer,
+        dst: CopyDescriptor<'_>,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let ctx = self.get_context();
+        let transfer = io::write_from_pinned(ctx, src, dst);
+
+        Box::pin(async move { Ok(transfer?.await?.0) })
+    }
+
+    fn read_to_pinned(
+        &mut self,
+        src: CopyDescriptor<'_>,
+        dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let ctx = self.get_context();
+        let transfer = io::read_to_pinned(ctx, src, dst);
+
+        Box::pin(async move { Ok(transfer?.await?.0) })
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
@@ -245,7 +303,9 @@ impl ComputeServer for CudaServer {
         bindings: Bindings,
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
+        self.ctx.watchdog.check()?;
+        let kernel_name = kernel.name();
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
 
@@ -260,8 +320,7 @@ impl ComputeServer for CudaServer {
                     &[3],
                     &[1],
                     4,
-                )]))
-                .unwrap();
+                )]))?;
                 let data = bytemuck::cast_slice(&data[0]);
                 assert!(
                     data.len() == 3,
@@ -292,34 +351,34 @@ impl ComputeServer for CudaServer {
             let mut handles = Vec::new();
             if bindings.metadata.static_len > 0 {
                 let dyn_meta = &bindings.metadata.data[bindings.metadata.static_len..];
-                handles.push(
-                    self.create_with_data(bytemuck::cast_slice(dyn_meta))
-                        .unwrap(),
-                );
+                handles.push(self.create_with_data(bytemuck::cast_slice(dyn_meta))?);
             }
 
             (scalars, handles)
         } else {
             let mut handles = Vec::new();
             if !bindings.metadata.data.is_empty() {
-                handles.push(
-                    self.create_with_data(bytemuck::cast_slice(&bindings.metadata.data))
-                        .unwrap(),
-                )
+                handles.push(self.create_with_data(bytemuck::cast_slice(&bindings.metadata.data))?)
+            }
+            for scalar in bindings.scalars.values() {
+                handles.push(self.create_with_data(scalar.data())?);
             }
-            handles.extend(
-                bindings
-                    .scalars
-                    .values()
-                    .map(|scalar| self.create_with_data(scalar.data()).unwrap()),
-            );
             (Vec::new(), handles)
         };
 
         let ctx = self.get_context();
 
+        ctx.streams.register_bindings(
+            bindings
+                .buffers
+                .iter()
+                .cloned()
+                .chain(bindings.tensor_maps.iter().map(|it| it.binding.clone()))
+                .chain(scalar_bindings.iter().map(|it| it.clone().binding())),
+        );
+
         if !ctx.module_names.contains_key(&kernel_id) {
-            ctx.compile_kernel(&kernel_id, kernel, mode, logger);
+            ctx.compile_kernel(&kernel_id, kernel, mode, logger)?;
         }
 
         let tensor_maps: Vec<_> = bindings
@@ -466,18 +525,269 @@ impl ComputeServer for CudaServer {
         let result = ctx.execute_task(kernel_id, count, &tensor_maps, &resources, &scalars);
 
         match result {
+            // The kernels captured in a graph aren't launched yet.
+            Ok(_) if !ctx.capturing => ctx.watchdog.launched(kernel_name, ctx.stream),
             Ok(_) => {}
             Err(err) => match ctx.timestamps.is_empty() {
                 true => panic!("{err:?}"),
                 false => ctx.timestamps.error(ProfileError::Unknown(err)),
             },
         }
+
+        Ok(())
+    }
+
+    fn precompile(
+        &mut self,
+        kernel: Self::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        let ctx = self.get_context();
+
+        // Not loaded on error, so the error is returned again when the kernel is launched.
+        if !ctx.module_names.contains_key(&kernel_id) {
+            ctx.compile_kernel(&kernel_id, kernel, mode, logger)?;
+        }
+
+        Ok(())
+    }
+
+    fn create_stream(&mut self) -> ExecutionStream {
+        let ctx = self.get_context();
+        ctx.streams.create()
+    }
+
+    unsafe fn execute_on(
+        &mut self,
+        stream: ExecutionStream,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        self.on_stream(stream, |server| unsafe {
+            server.execute(kernel, count, bindings, mode, logger)
+        })
+    }
+
+    unsafe fn execute_batch(
+        &mut self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Self::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        self.on_stream(stream, |server| {
+            for kernel in kernels {
+                unsafe {
+                    server.execute(
+                        kernel.kernel,
+                        kernel.count,
+                        kernel.bindings,
+                        kernel.mode,
+                        logger.clone(),
+                    )?
+                };
+            }
+
+            Ok(())
+        })
+    }
+
+    unsafe fn record_batch(
+        &mut self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Self::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<BatchId, IoError> {
+        check_recordable(&kernels)?;
+
+        self.on_stream(stream, |server| unsafe {
+            server.capture(kernels, logger, |ctx, bindings| {
+                ctx.graphs.end_capture(ctx.stream, bindings)
+            })
+        })
+    }
+
+    unsafe fn update_batch(
+        &mut self,
+        stream: ExecutionStream,
+        batch: BatchId,
+        kernels: Vec<BatchedKernel<Self::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        check_recordable(&kernels)?;
+
+        self.on_stream(stream, |server| unsafe {
+            server.capture(kernels, logger, |ctx, bindings| {
+                ctx.graphs.end_update(ctx.stream, batch, bindings)
+            })
+        })
+    }
+
+    fn replay_batch(&mut self, stream: ExecutionStream, batch: BatchId) -> Result<(), IoError> {
+        self.on_stream(stream, |server| {
+            let ctx = server.get_context();
+            ctx.watchdog.check()?;
+            let bindings = ctx.graphs.launch(batch, ctx.stream)?;
+            ctx.streams.register_bindings(bindings.iter().cloned());
+
+            Ok(())
+        })
+    }
+
+    fn release_batch(&mut self, batch: BatchId) {
+        self.ctx.graphs.release(batch);
+    }
+
+    fn read_on<'a>(
+        &mut self,
+        stream: ExecutionStream,
+        descriptors: Vec<CopyDescriptor<'a>>,
+    ) -> DynFut<Result<Vec<Bytes>, IoError>> {
+        self.on_stream(stream, |server| {
+            let bindings = descriptors.iter().map(|it| it.binding.clone());
+            server.ctx.streams.register_bindings(bindings);
+            server.read(descriptors)
+        })
+    }
+
+    fn write_on(
+        &mut self,
+        stream: ExecutionStream,
+        descriptors: Vec<(CopyDescriptor<'_>, &[u8])>,
+    ) -> Result<(), IoError> {
+        self.on_stream(stream, |server| {
+            let bindings = descriptors.iter().map(|(it, _)| it.binding.clone());
+            server.ctx.streams.register_bindings(bindings);
+            server.write(descriptors)
+        })
+    }
+
+    fn record_event(&mut self, stream: ExecutionStream) -> StreamEvent {
+        let ctx = self.get_context();
+        let cu_stream = ctx.streams.get(stream).unwrap_or(ctx.stream);
+        ctx.streams.record_event(cu_stream)
+    }
+
+    fn wait_event(&mut self, stream: ExecutionStream, event: StreamEvent) {
+        let ctx = self.get_context();
+        let cu_stream = ctx.streams.get(stream).unwrap_or(ctx.stream);
+        ctx.streams.wait_event(cu_stream, event);
+    }
+
+    fn is_event_complete(&mut self, event: StreamEvent) -> bool {
+        self.get_context().streams.is_event_complete(event)
+    }
+
+    fn sync_event(&mut self, event: StreamEvent) -> DynFut<()> {
+        self.get_context().streams.sync_event(event)
+    }
+
+    fn event_elapsed(
+        &mut self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.get_context().streams.event_elapsed(start, end)
+    }
+
+    fn release_event(&mut self, event: StreamEvent) {
+        self.get_context().streams.release_event(event);
+    }
+
+    unsafe fn wait_native_event(
+        &mut self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let ctx = self.get_context();
+        let cu_stream = ctx.streams.get(stream).unwrap_or(ctx.stream);
+
+        unsafe {
+            cudarc::driver::result::stream::wait_event(
+                cu_stream,
+                event.handle as CUevent,
+                CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+            )
+        }
+        .map_err(|err| IoError::Unknown(format!("Failed to wait for the event: {err:?}")))
+    }
+
+    unsafe fn record_native_event(
+        &mut self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let ctx = self.get_context();
+        let cu_stream = ctx.streams.get(stream).unwrap_or(ctx.stream);
+
+        unsafe { cudarc::driver::result::event::record(event.handle as CUevent, cu_stream) }
+            .map_err(|err| IoError::Unknown(format!("Failed to record the event: {err:?}")))
+    }
+
+    fn sync_all(&mut self) -> DynFut<()> {
+        let ctx = self.get_context();
+        if ctx.watchdog.is_enabled() {
+            let mut fences = ctx.streams.fences();
+            fences.push(ctx.fence());
+            let watchdog = ctx.watchdog.clone();
+            let started = Instant::now();
+
+            return Box::pin(async move {
+                for fence in fences {
+                    if let Err(err) = watchdog.wait(fence, started) {
+                        panic!("Failed to sync the streams: {err}");
+                    }
+                }
+            });
+        }
+
+        let streams = ctx.streams.sync_streams();
+        let default = ctx.lazy_sync_stream();
+
+        Box::pin(async move {
+            for stream in streams {
+                stream.wait();
+            }
+            default.wait();
+        })
     }
 
     fn flush(&mut self) {}
 
     fn sync(&mut self) -> DynFut<()> {
-        Box::pin(self.sync_stream_async())
+        if !self.ctx.watchdog.is_enabled() {
+            return Box::pin(self.sync_stream_async());
+        }
+        let sync = self.try_sync();
+
+        Box::pin(async move {
+            if let Err(err) = sync.await {
+                panic!("Failed to sync the server: {err}");
+            }
+        })
+    }
+
+    fn try_sync(&mut self) -> DynFut<Result<(), IoError>> {
+        let ctx = self.get_context();
+        if let Err(err) = ctx.watchdog.check() {
+            return Box::pin(async move { Err(err) });
+        }
+        if !ctx.watchdog.is_enabled() {
+            let sync = self.sync_stream_async();
+            return Box::pin(async move {
+                sync.await;
+                Ok(())
+            });
+        }
+
+        // Kernels are launched with an event recorded after them, so the fence isn't ignored.
+        Box::pin(PendingTransfer::new((), ctx.fence(), ctx.watchdog.clone()))
     }
 
     fn start_profile(&mut self) -> ProfilingToken {
@@ -491,6 +801,19 @@ impl ComputeServer for CudaServer {
         self.ctx.timestamps.stop(token)
     }
 
+    fn start_device_timing(&mut self) -> Option<ProfilingToken> {
+        let ctx = self.get_context();
+        Some(ctx.device_timings.start(ctx.stream))
+    }
+
+    fn end_device_timing(
+        &mut self,
+        token: ProfilingToken,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let ctx = self.get_context();
+        ctx.device_timings.stop(token, ctx.stream)
+    }
+
     fn get_resource(&mut self, binding: server::Binding) -> BindingResource<GpuResource> {
         let ctx = self.get_context();
         BindingResource::new(
@@ -501,12 +824,42 @@ impl ComputeServer for CudaServer {
         )
     }
 
+    fn import_resource(&mut self, resource: GpuResource) -> Result<server::Handle, IoError> {
+        // Imports are only supported for memory given to `GpuResource::external`, the bindings
+        // of the resources of the storage pointing inside of its ring buffer.
+        if !resource.binding.is_null() {
+            return Err(IoError::Unknown(String::from(
+                "Only resources created with `GpuResource::external` can be imported",
+            )));
+        }
+
+        let ctx = self.get_context();
+        let size = resource.size;
+        let storage = ctx.memory_management_gpu.storage().register(resource);
+        let slice = ctx.memory_management_gpu.register(storage);
+
+        Ok(server::Handle::new(slice, None, None, size))
+    }
+
     fn memory_usage(&self) -> MemoryUsage {
         self.ctx.memory_management_gpu.memory_usage()
     }
 
-    fn memory_cleanup(&mut self) {
-        self.ctx.memory_management_gpu.cleanup(true);
+    fn push_memory_scope(&mut self, tag: String) {
+        self.ctx.memory_management_gpu.push_scope(tag);
+    }
+
+    fn pop_memory_scope(&mut self) {
+        self.ctx.memory_management_gpu.pop_scope();
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.ctx.memory_management_gpu.scope_usage()
+    }
+
+    fn memory_cleanup(&mut self, mode: MemoryCleanupMode) {
+        let ctx = self.get_context();
+        ctx.memory_management_gpu.memory_cleanup(mode);
     }
 
     fn allocation_mode(&mut self, mode: cubecl_runtime::memory_management::MemoryAllocationMode) {
@@ -571,18 +924,24 @@ fn find_resource(ctx: &mut CudaContext, binding: server::Binding) -> GpuResource
 }
 
 impl CudaContext {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         memory_management_gpu: MemoryManagement<GpuStorage>,
         memory_management_cpu: MemoryManagement<PinnedMemoryStorage>,
         compilation_options: CompilationOptions,
+        hardware_properties: HardwareProperties,
         stream: cudarc::driver::sys::CUstream,
         context: *mut CUctx_st,
         arch: CudaArchitecture,
+        kernel_timeout: Option<Duration>,
     ) -> Self {
         Self {
             context,
             memory_management_gpu,
             memory_management_cpu,
+            streams: CudaStreams::default(),
+            graphs: CudaGraphs::default(),
+            capturing: false,
             module_names: HashMap::new(),
             #[cfg(feature = "compilation-cache")]
             ptx_cache: {
@@ -600,11 +959,14 @@ impl CudaContext {
             stream,
             arch,
             timestamps: TimestampProfiler::default(),
+            device_timings: EventTimings::default(),
             compilation_options,
+            hardware_properties,
+            watchdog: Watchdog::new(kernel_timeout),
         }
     }
 
-    fn fence(&mut self) -> Fence {
+    pub(crate) fn fence(&mut self) -> Fence {
         Fence::new(self.stream)
     }
 
@@ -624,25 +986,26 @@ impl CudaContext {
         kernel: Box<dyn CubeTask<CudaCompiler>>,
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
         #[cfg(feature = "compilation-cache")]
         let name = if let Some(cache) = &self.ptx_cache {
             let name = kernel_id.stable_format();
 
             if let Some(entry) = cache.get(&name) {
                 log::trace!("Using PTX cache");
-                self.load_ptx(
-                    entry.ptx.clone(),
+                let entry = entry.clone();
+                return self.load_ptx(
+                    entry.ptx,
                     kernel_id.clone(),
-                    entry.entrypoint_name.clone(),
+                    entry.entrypoint_name,
                     CubeDim {
                         x: entry.cube_dim.0,
                         y: entry.cube_dim.1,
                         z: entry.cube_dim.2,
                     },
                     entry.shared_mem_bytes,
+                    entry.shared_memory_carveout,
                 );
-                return;
             }
             Some(name)
         } else {
@@ -664,6 +1027,16 @@ impl CudaContext {
 
         let compute_kernel = kernel_compiled.repr.as_ref().unwrap();
         let cube_dim = kernel_compiled.cube_dim;
+        // Shared memory is collected into a single dynamic buffer, launched with at least the
+        // requested size.
+        let shared_mem_bytes = compute_kernel
+            .shared_memory_size()
+            .max(compute_kernel.dynamic_shared_memory.unwrap_or(0) as usize);
+        let shared_memory_carveout = compute_kernel.shared_memory_carveout;
+        // Checked before compiling, since exceeding the limits would only surface as an invalid
+        // value when the kernel is loaded.
+        self.hardware_properties
+            .check_kernel_limits(&cube_dim, shared_mem_bytes)?;
         let fast_math = compute_kernel.flags.inst_fast_math;
         let arch = if self.arch.version >= 90 {
             format!("--gpu-architecture=sm_{}a", self.arch)
@@ -688,32 +1061,33 @@ impl CudaContext {
 
         logger.log_compilation(&kernel_compiled);
 
+        // Rejected kernels are returned as errors rather than panicking, so the server can keep
+        // executing the other kernels.
+        let compilation_error =
+            |log: String| CompilationError::new(kernel.name(), CompilationStage::Compile, log);
         let ptx = unsafe {
             // I'd like to set the name to the kernel name, but keep getting UTF-8 errors so let's
             // leave it `None` for now
-            let source = CString::from_str(&kernel_compiled.source).unwrap();
-            let program = cudarc::nvrtc::result::create_program(source.as_c_str(), None).unwrap();
+            let source = CString::from_str(&kernel_compiled.source)
+                .map_err(|err| compilation_error(format!("Invalid source: {err}")))?;
+            let program = cudarc::nvrtc::result::create_program(source.as_c_str(), None)
+                .map_err(|err| compilation_error(format!("Can't create the program: {err:?}")))?;
             if cudarc::nvrtc::result::compile_program(program, &options).is_err() {
-                let log_raw = cudarc::nvrtc::result::get_program_log(program).unwrap();
-                let log_ptr = log_raw.as_ptr();
-                let log = CStr::from_ptr(log_ptr).to_str().unwrap();
-                let mut message = "[Compilation Error] ".to_string();
-                for line in log.split('\n') {
-                    if !line.is_empty() {
-                        message += format!("\n    {line}").as_str();
-                    }
-                }
-                let source = kernel
-                    .compile(&mut Default::default(), &self.compilation_options, mode)
-                    .source;
-                panic!("{message}\n[Source]  \n{source}");
+                let log = match cudarc::nvrtc::result::get_program_log(program) {
+                    Ok(log_raw) => CStr::from_ptr(log_raw.as_ptr())
+                        .to_string_lossy()
+                        .lines()
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    Err(err) => format!("No compilation log: {err:?}"),
+                };
+                return Err(compilation_error(log).into());
             };
-            cudarc::nvrtc::result::get_ptx(program).unwrap()
+            cudarc::nvrtc::result::get_ptx(program)
+                .map_err(|err| compilation_error(format!("Can't get the PTX: {err:?}")))?
         };
 
-        let repr: cubecl_cpp::ComputeKernel<cubecl_cpp::cuda::CudaDialect<WmmaCompiler>> =
-            kernel_compiled.repr.unwrap();
-
         #[cfg(feature = "compilation-cache")]
         if let Some(cache) = &mut self.ptx_cache {
             cache
@@ -722,7 +1096,8 @@ impl CudaContext {
                     PtxCacheEntry {
                         entrypoint_name: kernel_compiled.entrypoint_name.clone(),
                         cube_dim: (cube_dim.x, cube_dim.y, cube_dim.z),
-                        shared_mem_bytes: repr.shared_memory_size(),
+                        shared_mem_bytes,
+                        shared_memory_carveout,
                         cluster_dim: cluster_dim.map(|cluster| (cluster.x, cluster.y, cluster.z)),
                         ptx: ptx.clone(),
                     },
@@ -735,10 +1110,12 @@ impl CudaContext {
             kernel_id.clone(),
             kernel_compiled.entrypoint_name,
             cube_dim,
-            repr.shared_memory_size(),
-        );
+            shared_mem_bytes,
+            shared_memory_carveout,
+        )
     }
 
+    /// Load the kernel, with its function attributes set once for all its launches.
     fn load_ptx(
         &mut self,
         ptx: Vec<c_char>,
@@ -746,14 +1123,43 @@ impl CudaContext {
         entrypoint_name: String,
         cube_dim: CubeDim,
         shared_mem_bytes: usize,
-    ) {
-        let func_name = CString::new(entrypoint_name).unwrap();
+        shared_memory_carveout: Option<u8>,
+    ) -> Result<(), IoError> {
+        let load_error = |log: String| {
+            IoError::from(CompilationError::new(
+                entrypoint_name.as_str(),
+                CompilationStage::Load,
+                log,
+            ))
+        };
+        let func_name = CString::new(entrypoint_name.as_str())
+            .map_err(|err| load_error(format!("Invalid entrypoint name: {err}")))?;
         let func = unsafe {
-            let module =
-                cudarc::driver::result::module::load_data(ptx.as_ptr() as *const _).unwrap();
-            cudarc::driver::result::module::get_function(module, func_name).unwrap()
+            let module = cudarc::driver::result::module::load_data(ptx.as_ptr() as *const _)
+                .map_err(|err| load_error(format!("Can't load the module: {err:?}")))?;
+            cudarc::driver::result::module::get_function(module, func_name)
+                .map_err(|err| load_error(format!("Can't find the function: {err:?}")))?
         };
 
+        let mut attributes = vec![(
+            CUfunction_attribute::CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES,
+            shared_mem_bytes as i32,
+        )];
+        if let Some(percent) = shared_memory_carveout {
+            attributes.push((
+                CUfunction_attribute::CU_FUNC_ATTRIBUTE_PREFERRED_SHARED_MEMORY_CARVEOUT,
+                percent as i32,
+            ));
+        }
+        for (attribute, value) in attributes {
+            unsafe {
+                cudarc::driver::result::function::set_function_attribute(func, attribute, value)
+            }
+            .map_err(|err| {
+                IoError::Unknown(format!("Can't set {attribute:?} to {value}: {err:?}"))
+            })?;
+        }
+
         self.module_names.insert(
             kernel_id.clone(),
             CompiledKernel {
@@ -762,6 +1168,20 @@ impl CudaContext {
                 func,
             },
         );
+
+        Ok(())
+    }
+
+    /// The value of an attribute of the function of a loaded kernel.
+    #[cfg(test)]
+    pub(crate) fn function_attribute(
+        &self,
+        kernel_id: &KernelId,
+        attribute: CUfunction_attribute,
+    ) -> Option<i32> {
+        let kernel = self.module_names.get(kernel_id)?;
+        unsafe { cudarc::driver::result::function::get_function_attribute(kernel.func, attribute) }
+            .ok()
     }
 
     fn execute_task(
@@ -782,12 +1202,6 @@ impl CudaContext {
         let kernel = self.module_names.get(&kernel_id).unwrap();
         let cube_dim = kernel.cube_dim;
         unsafe {
-            cudarc::driver::result::function::set_function_attribute(
-                kernel.func,
-                CUfunction_attribute::CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES,
-                kernel.shared_mem_bytes as i32,
-            )
-            .map_err(|err| format!("{err:?}"))?;
             cudarc::driver::result::launch_kernel(
                 kernel.func,
                 dispatch_count,
@@ -811,6 +1225,69 @@ impl CudaServer {
         Self { mem_alignment, ctx }
     }
 
+    /// Run `func` with the given stream used in place of the default stream of the context,
+    /// keeping the bindings used alive until the enqueued work is done.
+    fn on_stream<O>(&mut self, stream: ExecutionStream, func: impl FnOnce(&mut Self) -> O) -> O {
+        let cu_stream = match self.ctx.streams.get(stream) {
+            Some(cu_stream) => cu_stream,
+            None => return func(self),
+        };
+
+        let default_stream = core::mem::replace(&mut self.ctx.stream, cu_stream);
+        self.ctx.streams.start_submit();
+        let output = func(self);
+        self.ctx.streams.end_submit(stream);
+        self.ctx.stream = default_stream;
+
+        output
+    }
+
+    /// Capture the execution of the kernels on the current stream into a graph, given to `end`
+    /// with the bindings of the kernels.
+    ///
+    /// # Safety
+    ///
+    /// See [execute](ComputeServer::execute).
+    unsafe fn capture<O>(
+        &mut self,
+        kernels: Vec<BatchedKernel<Box<dyn CubeTask<CudaCompiler>>>>,
+        logger: Arc<ServerLogger>,
+        end: impl FnOnce(&mut CudaContext, Vec<server::Binding>) -> Result<O, IoError>,
+    ) -> Result<O, IoError> {
+        let ctx = self.get_context();
+        ctx.watchdog.check()?;
+        // Metadata and scalars are uploaded synchronously while capturing, so the memory they
+        // reuse must not be accessed by work in flight anymore.
+        ctx.sync();
+        CudaGraphs::begin_capture(ctx.stream)?;
+        let previous = ctx.streams.start_capture();
+        ctx.capturing = true;
+
+        let mut result = Ok(());
+        for kernel in kernels {
+            result = unsafe {
+                self.execute(
+                    kernel.kernel,
+                    kernel.count,
+                    kernel.bindings,
+                    kernel.mode,
+                    logger.clone(),
+                )
+            };
+
+            if result.is_err() {
+                break;
+            }
+        }
+
+        let ctx = &mut self.ctx;
+        ctx.capturing = false;
+        let bindings = ctx.streams.end_capture(previous);
+        let output = end(ctx, bindings);
+
+        result.and(output)
+    }
+
     fn get_context(&mut self) -> &mut CudaContext {
         unsafe {
             cudarc::driver::result::ctx::set_current(self.ctx.context).unwrap();
@@ -819,6 +1296,22 @@ impl CudaServer {
     }
 }
 
+/// Check that the kernels can be captured, since reading a dynamic cube count would synchronize
+/// the stream in the middle of the capture.
+fn check_recordable(
+    kernels: &[BatchedKernel<Box<dyn CubeTask<CudaCompiler>>>],
+) -> Result<(), IoError> {
+    match kernels
+        .iter()
+        .any(|kernel| matches!(kernel.count, CubeCount::Dynamic(_)))
+    {
+        true => Err(IoError::Unknown(
+            "Kernels with a dynamic cube count can't be recorded".to_string(),
+        )),
+        false => Ok(()),
+    }
+}
+
 fn include_path() -> PathBuf {
     let mut path = cuda_path().expect("
         CUDA installation not found.
@@ -965,3 +1458,6 @@ pub fn valid_strides(shape: &[usize], strides: &[usize]) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests;
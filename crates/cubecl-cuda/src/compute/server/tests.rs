@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use cubecl_core::{
+    compute::CubeTask,
+    prelude::*,
+    server::{Bindings, ComputeServer, IoError},
+};
+use cubecl_runtime::logging::ServerLogger;
+use cudarc::driver::sys::CUfunction_attribute;
+
+use super::CudaServer;
+use crate::{CudaCompiler, CudaRuntime, WmmaCompiler, runtime::create_server};
+
+#[cube(launch)]
+fn kernel_shared() {
+    let mut shared = SharedMemory::<f32>::new(256);
+    shared[UNIT_POS] = f32::cast_from(UNIT_POS);
+}
+
+/// Launch the kernel with the given settings, returning the kernel id it's loaded with.
+fn launch(server: &mut CudaServer, settings: KernelSettings) -> Result<KernelId, IoError> {
+    let kernel =
+        kernel_shared::KernelShared::<CudaRuntime>::new(settings.cube_dim(CubeDim::new_1d(256)));
+    let kernel: Box<dyn CubeTask<CudaCompiler>> = Box::new(KernelTask::new(kernel));
+    let mut kernel_id = kernel.id();
+    kernel_id.mode(ExecutionMode::Checked);
+
+    unsafe {
+        server.execute(
+            kernel,
+            CubeCount::Static(1, 1, 1),
+            Bindings::new(),
+            ExecutionMode::Checked,
+            Arc::new(ServerLogger::default()),
+        )
+    }?;
+
+    Ok(kernel_id)
+}
+
+fn attribute(server: &CudaServer, kernel_id: &KernelId, attribute: CUfunction_attribute) -> i32 {
+    server
+        .ctx
+        .function_attribute(kernel_id, attribute)
+        .expect("The kernel should be loaded")
+}
+
+#[test]
+fn shared_memory_hints_are_applied_when_loaded() {
+    let (mut server, _) = create_server::<WmmaCompiler>(&Default::default(), Default::default());
+    let settings = KernelSettings::default()
+        .shared_memory_carveout(50)
+        .dynamic_shared_memory(16 * 1024);
+
+    let kernel_id = launch(&mut server, settings.clone()).unwrap();
+
+    assert_eq!(
+        attribute(
+            &server,
+            &kernel_id,
+            CUfunction_attribute::CU_FUNC_ATTRIBUTE_PREFERRED_SHARED_MEMORY_CARVEOUT
+        ),
+        50
+    );
+    assert_eq!(
+        attribute(
+            &server,
+            &kernel_id,
+            CUfunction_attribute::CU_FUNC_ATTRIBUTE_MAX_DYNAMIC_SHARED_SIZE_BYTES
+        ),
+        16 * 1024
+    );
+
+    // The hints are part of the kernel id, so changing them loads the kernel again.
+    let changed = launch(&mut server, settings.shared_memory_carveout(100)).unwrap();
+
+    assert_ne!(changed, kernel_id);
+    assert_eq!(
+        attribute(
+            &server,
+            &changed,
+            CUfunction_attribute::CU_FUNC_ATTRIBUTE_PREFERRED_SHARED_MEMORY_CARVEOUT
+        ),
+        100
+    );
+}
+
+#[cfg(feature = "watchdog_tests")]
+mod watchdog {
+    use super::*;
+    use crate::RuntimeOptions;
+    use cubecl_core::future;
+    use cubecl_runtime::channel::MutexComputeChannel;
+    use std::time::Duration;
+
+    /// Waits on a flag that is never set, like units waiting on a barrier that never gets enough
+    /// arrivals.
+    #[cube(launch)]
+    fn kernel_deadlock(flag: &Array<Atomic<u32>>) {
+        let mut value = Atomic::load(&flag[0]);
+        while value == 0 {
+            value = Atomic::load(&flag[0]);
+        }
+    }
+
+    /// The kernel never completes, so the device stays busy until the process exits.
+    #[test]
+    fn deadlocking_kernel_times_out() {
+        let timeout = Duration::from_millis(500);
+        let options = RuntimeOptions {
+            kernel_timeout: Some(timeout),
+            ..Default::default()
+        };
+        let (server, properties) = create_server::<WmmaCompiler>(&Default::default(), options);
+        let client = ComputeClient::new(MutexComputeChannel::new(server), properties, ());
+        let flag = client.create(u32::as_bytes(&[0]));
+
+        kernel_deadlock::launch::<CudaRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            unsafe { ArrayArg::from_raw_parts::<u32>(&flag, 1, 1) },
+        );
+
+        match future::block_on(client.try_sync()) {
+            Err(IoError::KernelTimeout {
+                kernel_name,
+                elapsed,
+            }) => {
+                assert!(
+                    kernel_name.contains("KernelDeadlock"),
+                    "the deadlocking kernel should be reported, got {kernel_name}"
+                );
+                assert!(elapsed >= timeout);
+            }
+            result => panic!("the sync should time out, got {result:?}"),
+        }
+
+        // The later calls fail without waiting for the kernel.
+        assert!(matches!(
+            future::block_on(client.try_read_async(vec![flag])),
+            Err(IoError::Poisoned { .. })
+        ));
+        assert!(matches!(client.try_empty(4), Err(IoError::Poisoned { .. })));
+        assert!(matches!(
+            future::block_on(client.try_sync()),
+            Err(IoError::Poisoned { .. })
+        ));
+
+        // The memory the kernel still uses is never released.
+        core::mem::forget(client);
+    }
+}
@@ -10,6 +10,8 @@ use std::collections::HashMap;
 /// for launching kernels.
 pub struct GpuStorage {
     memory: HashMap<StorageId, cudarc::driver::sys::CUdeviceptr>,
+    /// Memory allocated outside of the storage, which isn't freed on deallocation.
+    external: HashMap<StorageId, Option<ReleaseCallback>>,
     deallocations: Vec<StorageId>,
     stream: cudarc::driver::sys::CUstream,
     ptr_bindings: PtrBindings,
@@ -25,15 +27,59 @@ pub struct GpuResource {
     pub binding: *mut std::ffi::c_void,
     /// The size of the resource.
     pub size: u64,
+    on_release: Option<ReleaseCallback>,
 }
 
 impl GpuResource {
     /// Creates a new [GpuResource].
     pub fn new(ptr: u64, binding: *mut std::ffi::c_void, size: u64) -> Self {
-        Self { ptr, binding, size }
+        Self {
+            ptr,
+            binding,
+            size,
+            on_release: None,
+        }
+    }
+
+    /// Creates a [GpuResource] over memory allocated outside of the storage, to be
+    /// [imported](cubecl_runtime::server::ComputeServer::import_resource).
+    ///
+    /// `on_release` is called once the work using the memory is completed after the handle of
+    /// the resource is dropped. It is called from a CUDA host function, so it must not call the
+    /// CUDA API.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for `size` bytes on the device of the storage, until
+    /// `on_release` is called.
+    pub unsafe fn external(
+        ptr: cudarc::driver::sys::CUdeviceptr,
+        size: u64,
+        on_release: Option<Box<dyn FnOnce() + Send>>,
+    ) -> Self {
+        Self {
+            ptr,
+            binding: std::ptr::null_mut(),
+            size,
+            on_release: on_release.map(ReleaseCallback),
+        }
+    }
+}
+
+/// Called when memory allocated outside of the storage isn't used anymore.
+struct ReleaseCallback(Box<dyn FnOnce() + Send>);
+
+impl core::fmt::Debug for ReleaseCallback {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ReleaseCallback")
     }
 }
 
+unsafe extern "C" fn release_external(data: *mut std::ffi::c_void) {
+    let callback = unsafe { Box::from_raw(data as *mut ReleaseCallback) };
+    (callback.0)();
+}
+
 impl GpuStorage {
     /// Creates a new [GpuStorage] instance for the specified CUDA stream.
     ///
@@ -44,6 +90,7 @@ impl GpuStorage {
     pub fn new(mem_alignment: usize, stream: CUstream) -> Self {
         Self {
             memory: HashMap::new(),
+            external: HashMap::new(),
             deallocations: Vec::new(),
             stream,
             ptr_bindings: PtrBindings::new(),
@@ -56,13 +103,44 @@ impl GpuStorage {
     /// This method processes all pending deallocations by freeing the associated GPU memory.
     pub fn perform_deallocations(&mut self) {
         for id in self.deallocations.drain(..) {
-            if let Some(ptr) = self.memory.remove(&id) {
-                unsafe {
+            let Some(ptr) = self.memory.remove(&id) else {
+                continue;
+            };
+
+            match self.external.remove(&id) {
+                // Called once the work enqueued on the stream before is completed, like the free.
+                Some(Some(callback)) => unsafe {
+                    let data = Box::into_raw(Box::new(callback));
+                    cudarc::driver::sys::cuLaunchHostFunc(
+                        self.stream,
+                        Some(release_external),
+                        data as *mut std::ffi::c_void,
+                    )
+                    .result()
+                    .unwrap();
+                },
+                Some(None) => {}
+                None => unsafe {
                     cudarc::driver::result::free_async(ptr, self.stream).unwrap();
-                }
+                },
             }
         }
     }
+
+    /// Register memory allocated outside of the storage, see [GpuResource::external].
+    pub fn register(&mut self, resource: GpuResource) -> StorageHandle {
+        let id = StorageId::new();
+
+        self.memory.insert(id, resource.ptr);
+        self.external.insert(id, resource.on_release);
+        StorageHandle::new(
+            id,
+            StorageUtilization {
+                offset: 0,
+                size: resource.size,
+            },
+        )
+    }
 }
 
 unsafe impl Send for GpuResource {}
@@ -170,4 +248,24 @@ impl ComputeStorage for GpuStorage {
     fn flush(&mut self) {
         self.perform_deallocations();
     }
+
+    fn supports_copy(&self) -> bool {
+        true
+    }
+
+    fn copy(&mut self, src: &StorageHandle, dst: &StorageHandle) {
+        let src_ptr = self.memory.get(&src.id).expect("Storage handle not found") + src.offset();
+        let dst_ptr = self.memory.get(&dst.id).expect("Storage handle not found") + dst.offset();
+
+        // The copy is ordered on the stream, so it happens after kernels using the source.
+        unsafe {
+            cudarc::driver::result::memcpy_dtod_async(
+                dst_ptr,
+                src_ptr,
+                src.size() as usize,
+                self.stream,
+            )
+            .unwrap();
+        }
+    }
 }
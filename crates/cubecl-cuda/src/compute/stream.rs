@@ -0,0 +1,193 @@
+use super::sync::{Fence, SyncStream};
+use super::timings::{CudaEvent, elapsed};
+use cubecl_common::{future::DynFut, profile::ProfileDuration};
+use cubecl_core::server::{Binding, ExecutionStream, ProfileError, StreamEvent};
+use cudarc::driver::sys::CUstream;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// The streams created on top of the default stream of a [context](super::CudaContext).
+///
+/// Streams are never destroyed, which keeps [fences](Fence) created on them valid.
+#[derive(Default, Debug)]
+pub(crate) struct CudaStreams {
+    streams: Vec<CudaStream>,
+    events: HashMap<u64, Arc<CudaEvent>>,
+    event_count: u64,
+    /// Bindings used by the task currently being submitted to a stream.
+    submitted: Option<Vec<Binding>>,
+}
+
+#[derive(Debug)]
+struct CudaStream {
+    stream: CUstream,
+    /// Bindings used by work in flight on the stream, kept alive until the fence is reached so
+    /// the memory isn't reused by another stream in the meantime.
+    in_flight: VecDeque<(Fence, Vec<Binding>)>,
+}
+
+impl CudaStreams {
+    /// Create a new stream.
+    pub fn create(&mut self) -> ExecutionStream {
+        let stream = cudarc::driver::result::stream::create(
+            cudarc::driver::result::stream::StreamKind::NonBlocking,
+        )
+        .expect("Can create a new stream.");
+
+        self.streams.push(CudaStream {
+            stream,
+            in_flight: VecDeque::new(),
+        });
+
+        ExecutionStream {
+            id: self.streams.len() as u32,
+        }
+    }
+
+    /// Get the CUDA stream of an [execution stream](ExecutionStream), `None` being the default
+    /// stream of the context.
+    pub fn get(&self, stream: ExecutionStream) -> Option<CUstream> {
+        if stream.is_default() {
+            return None;
+        }
+
+        let index = stream.id as usize - 1;
+        let stream = self
+            .streams
+            .get(index)
+            .unwrap_or_else(|| panic!("Unknown execution stream {}", stream.id));
+
+        Some(stream.stream)
+    }
+
+    /// Start collecting the bindings used by a task submitted to a non-default stream.
+    pub fn start_submit(&mut self) {
+        self.submitted = Some(Vec::new());
+    }
+
+    /// Register bindings used by the task being submitted, if it is submitted to a non-default
+    /// stream.
+    pub fn register_bindings(&mut self, bindings: impl Iterator<Item = Binding>) {
+        if let Some(submitted) = self.submitted.as_mut() {
+            submitted.extend(bindings);
+        }
+    }
+
+    /// Start collecting the bindings used by the tasks submitted to any stream, returning the
+    /// bindings collected so far to [restore](Self::end_capture) them.
+    pub fn start_capture(&mut self) -> Option<Vec<Binding>> {
+        self.submitted.replace(Vec::new())
+    }
+
+    /// Return the bindings collected since [start_capture](Self::start_capture).
+    pub fn end_capture(&mut self, previous: Option<Vec<Binding>>) -> Vec<Binding> {
+        core::mem::replace(&mut self.submitted, previous).unwrap_or_default()
+    }
+
+    /// Keep the bindings collected since [start_submit](Self::start_submit) alive until the
+    /// work enqueued on the stream so far is completed.
+    pub fn end_submit(&mut self, stream: ExecutionStream) {
+        let bindings = self.submitted.take().unwrap_or_default();
+        self.release_completed();
+
+        if let Some(cu_stream) = self.get(stream) {
+            let fence = Fence::new(cu_stream);
+            self.streams[stream.id as usize - 1]
+                .in_flight
+                .push_back((fence, bindings));
+        }
+    }
+
+    /// Record an event on the CUDA stream.
+    ///
+    /// The bindings used before the event stay [in flight](CudaStream::in_flight) until their
+    /// own fence is reached, so their memory isn't reused before the event is reached either.
+    pub fn record_event(&mut self, stream: CUstream) -> StreamEvent {
+        let id = self.event_count;
+        self.event_count += 1;
+        self.events.insert(id, Arc::new(CudaEvent::record(stream)));
+
+        StreamEvent { id }
+    }
+
+    /// Make the CUDA stream wait for the event.
+    pub fn wait_event(&mut self, stream: CUstream, event: StreamEvent) {
+        self.event(event).wait_async(stream);
+    }
+
+    /// Whether the event is reached, without blocking.
+    pub fn is_event_complete(&mut self, event: StreamEvent) -> bool {
+        self.event(event).is_reached()
+    }
+
+    /// Wait until the event is reached, without waiting for the rest of the work.
+    pub fn sync_event(&mut self, event: StreamEvent) -> DynFut<()> {
+        let event = self.event(event).clone();
+
+        Box::pin(async move { event.synchronize() })
+    }
+
+    /// The time elapsed on the device between two events.
+    pub fn event_elapsed(
+        &mut self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let start = self
+            .events
+            .get(&start.id)
+            .ok_or(ProfileError::NotRegistered)?;
+        let end = self
+            .events
+            .get(&end.id)
+            .ok_or(ProfileError::NotRegistered)?;
+
+        Ok(elapsed(start.clone(), end.clone()))
+    }
+
+    /// Release the event, destroyed once the futures using it are dropped.
+    pub fn release_event(&mut self, event: StreamEvent) {
+        self.events.remove(&event.id);
+    }
+
+    fn event(&self, event: StreamEvent) -> &Arc<CudaEvent> {
+        self.events
+            .get(&event.id)
+            .unwrap_or_else(|| panic!("Unknown or released stream event {}", event.id))
+    }
+
+    /// Synchronization points for every stream.
+    pub fn sync_streams(&mut self) -> Vec<SyncStream> {
+        self.release_completed();
+
+        self.streams
+            .iter()
+            .map(|stream| SyncStream::new(stream.stream))
+            .collect()
+    }
+
+    /// Fences on every stream, reached once the work enqueued so far is completed.
+    pub fn fences(&mut self) -> Vec<Fence> {
+        self.release_completed();
+
+        self.streams
+            .iter()
+            .map(|stream| Fence::new(stream.stream))
+            .collect()
+    }
+
+    /// Release the bindings of the work that is completed.
+    fn release_completed(&mut self) {
+        for stream in self.streams.iter_mut() {
+            while stream
+                .in_flight
+                .front()
+                .is_some_and(|(fence, _)| fence.is_reached())
+            {
+                let (fence, _bindings) = stream.in_flight.pop_front().unwrap();
+                // Already reached, only releases the event.
+                fence.wait_sync();
+            }
+        }
+    }
+}
@@ -13,6 +13,7 @@ use cudarc::driver::sys::CUstream_st;
 ///   [`Fence`], as it waits for all previous operations rather than a specific point.
 /// - The stream must remain valid until [`wait`](StreamSync::wait) is called.
 /// - This operation is relatively expensive as it blocks the CPU until all GPU operations complete.
+#[derive(Debug)]
 pub struct SyncStream {
     stream: *mut CUstream_st,
 }
@@ -1,10 +1,13 @@
-use cudarc::driver::sys::{CUevent_flags, CUevent_st, CUevent_wait_flags, CUstream_st};
+use cudarc::driver::sys::{
+    CUevent_flags, CUevent_st, CUevent_wait_flags, CUresult, CUstream_st, cuEventQuery,
+};
 
 /// A fence is simply an [event](CUevent_st) created on a [stream](CUevent_st) that you can wait
 /// until completion.
 ///
 /// This is useful for doing synchronization outside of the compute server, which is normally
 /// locked by a mutex or a channel. This allows the server to continue accepting other tasks.
+#[derive(Debug)]
 pub struct Fence {
     event: *mut CUevent_st,
 }
@@ -34,6 +37,17 @@ impl Fence {
         }
     }
 
+    /// Returns whether the [Fence] was reached, without blocking.
+    pub fn is_reached(&self) -> bool {
+        unsafe {
+            match cuEventQuery(self.event) {
+                CUresult::CUDA_SUCCESS => true,
+                CUresult::CUDA_ERROR_NOT_READY => false,
+                err => panic!("Failed to query the fence event: {err:?}"),
+            }
+        }
+    }
+
     /// Wait for the [Fence] to be reached, ensuring that all previous tasks enqueued to the
     /// [stream](CUstream_st) are completed.
     pub fn wait_sync(self) {
@@ -1,5 +1,9 @@
 mod base;
 mod fence;
+mod pending;
+mod watchdog;
 
 pub use base::*;
 pub use fence::*;
+pub use pending::*;
+pub use watchdog::*;
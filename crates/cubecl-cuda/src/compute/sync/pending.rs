@@ -0,0 +1,79 @@
+use super::{Fence, Watchdog};
+use cubecl_core::server::IoError;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+/// A value produced by work enqueued on a stream, available once the [Fence] is reached.
+///
+/// Polling never blocks: the fence is queried and the future is woken again until it is reached,
+/// or until the timeout of the [Watchdog] is exceeded. Dropping the transfer before completion
+/// waits on the fence, so that memory used by the enqueued work is never released while the
+/// device still accesses it. The value is leaked instead when the wait times out.
+pub struct PendingTransfer<T> {
+    value: Option<T>,
+    fence: Option<Fence>,
+    watchdog: Watchdog,
+    started: Instant,
+}
+
+impl<T> PendingTransfer<T> {
+    /// Create a new transfer resolving to `value` once the `fence` is reached.
+    pub fn new(value: T, fence: Fence, watchdog: Watchdog) -> Self {
+        Self {
+            value: Some(value),
+            fence: Some(fence),
+            watchdog,
+            started: Instant::now(),
+        }
+    }
+
+    /// The fence may never be reached, so the value is leaked with it: the memory it holds may
+    /// still be accessed by the device.
+    fn leak(&mut self) {
+        self.fence.take();
+        core::mem::forget(self.value.take());
+    }
+}
+
+impl<T: Unpin> Future for PendingTransfer<T> {
+    type Output = Result<T, IoError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(fence) = self.fence.as_ref() {
+            match self.watchdog.query(fence, self.started) {
+                Ok(true) => {}
+                Ok(false) => {
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                Err(err) => {
+                    self.leak();
+                    return Poll::Ready(Err(err));
+                }
+            }
+        }
+
+        // Already reached, this only releases the event.
+        if let Some(fence) = self.fence.take() {
+            fence.wait_sync();
+        }
+
+        Poll::Ready(Ok(self
+            .value
+            .take()
+            .expect("Pending transfer polled after completion")))
+    }
+}
+
+impl<T> Drop for PendingTransfer<T> {
+    fn drop(&mut self) {
+        if let Some(fence) = self.fence.take()
+            && self.watchdog.wait(fence, self.started).is_err()
+        {
+            self.leak();
+        }
+    }
+}
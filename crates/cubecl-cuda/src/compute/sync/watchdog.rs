@@ -0,0 +1,139 @@
+use super::Fence;
+use cubecl_core::server::IoError;
+use cudarc::driver::sys::CUstream_st;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The name reported when the work that didn't complete isn't a kernel launched by the server,
+/// e.g. a replayed batch.
+const UNKNOWN_KERNEL: &str = "<unknown>";
+
+/// Detects the work of a server that doesn't complete within a timeout, like a kernel waiting
+/// forever on a barrier.
+///
+/// Nothing is waited for when kernels are launched: an event is recorded after each of them, and
+/// the fences of the sync and read paths are polled until the timeout is exceeded. The first
+/// kernel whose event isn't reached is then reported, and the server is poisoned: the work
+/// enqueued after the kernel may never complete, so every later call fails immediately.
+///
+/// Without a timeout, no event is recorded and the fences are waited for as usual.
+#[derive(Debug, Clone, Default)]
+pub struct Watchdog {
+    timeout: Option<Duration>,
+    state: Arc<Mutex<WatchdogState>>,
+}
+
+#[derive(Debug, Default)]
+struct WatchdogState {
+    /// The kernels launched that aren't known to be completed yet, in launch order.
+    launches: VecDeque<(&'static str, Fence)>,
+    /// The kernel that timed out, after which the server can't be used anymore.
+    poisoned: Option<String>,
+}
+
+impl Watchdog {
+    /// Create a watchdog failing the waits longer than the `timeout`, if any.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            state: Default::default(),
+        }
+    }
+
+    /// Whether the waits can time out.
+    pub fn is_enabled(&self) -> bool {
+        self.timeout.is_some()
+    }
+
+    /// Returns an error when a kernel previously timed out.
+    pub fn check(&self) -> Result<(), IoError> {
+        match &self.state.lock().unwrap().poisoned {
+            Some(kernel_name) => Err(IoError::Poisoned {
+                kernel_name: kernel_name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Register the kernel just launched on the `stream`, to know which one was running when a
+    /// wait times out.
+    pub fn launched(&self, kernel_name: &'static str, stream: *mut CUstream_st) {
+        if self.timeout.is_none() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        // Only the first ones are queried, so each launch is released once its event is reached.
+        while let Some((_, fence)) = state.launches.front()
+            && fence.is_reached()
+        {
+            let (_, fence) = state.launches.pop_front().unwrap();
+            fence.wait_sync();
+        }
+        state.launches.push_back((kernel_name, Fence::new(stream)));
+    }
+
+    /// Whether the `fence` created at `started` is reached, without blocking.
+    ///
+    /// Returns an error when the server is poisoned, or when the timeout is exceeded, in which
+    /// case the server is poisoned.
+    pub fn query(&self, fence: &Fence, started: Instant) -> Result<bool, IoError> {
+        if fence.is_reached() {
+            return Ok(true);
+        }
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return Ok(false),
+        };
+        self.check()?;
+
+        let elapsed = started.elapsed();
+        if elapsed < timeout {
+            return Ok(false);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        // Another wait may have timed out in the meantime.
+        if let Some(kernel_name) = &state.poisoned {
+            return Err(IoError::Poisoned {
+                kernel_name: kernel_name.clone(),
+            });
+        }
+        let kernel_name = state
+            .launches
+            .iter()
+            .find(|(_, fence)| !fence.is_reached())
+            .map(|(kernel_name, _)| *kernel_name)
+            .unwrap_or(UNKNOWN_KERNEL)
+            .to_string();
+        // The events of the launches are leaked, as they may never be reached.
+        state.launches.clear();
+        state.poisoned = Some(kernel_name.clone());
+
+        Err(IoError::KernelTimeout {
+            kernel_name,
+            elapsed,
+        })
+    }
+
+    /// Block until the `fence` created at `started` is reached, see [query](Self::query).
+    ///
+    /// The fence is leaked when it isn't reached.
+    pub fn wait(&self, fence: Fence, started: Instant) -> Result<(), IoError> {
+        if self.timeout.is_none() {
+            fence.wait_sync();
+            return Ok(());
+        }
+
+        while !self.query(&fence, started)? {
+            std::thread::yield_now();
+        }
+        // Already reached, this only releases the event.
+        fence.wait_sync();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use cubecl_common::profile::{Duration, Instant, ProfileDuration, ProfileTicks};
+use cubecl_core::server::{ProfileError, ProfilingToken};
+use cudarc::driver::sys::{
+    CUevent, CUevent_flags, CUevent_wait_flags, CUresult, CUstream, cuEventQuery,
+};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Times the work submitted to a stream with [events](CUevent), without synchronizing.
+#[derive(Debug, Default)]
+pub(crate) struct EventTimings {
+    starts: HashMap<ProfilingToken, Arc<CudaEvent>>,
+    counter: u64,
+}
+
+impl EventTimings {
+    /// Record the start of a timing on the stream.
+    pub fn start(&mut self, stream: CUstream) -> ProfilingToken {
+        let token = ProfilingToken { id: self.counter };
+        self.counter += 1;
+        self.starts
+            .insert(token, Arc::new(CudaEvent::record(stream)));
+        token
+    }
+
+    /// Record the end of a timing on the stream, the duration being resolved once the event is
+    /// reached.
+    pub fn stop(
+        &mut self,
+        token: ProfilingToken,
+        stream: CUstream,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let start = self
+            .starts
+            .remove(&token)
+            .ok_or(ProfileError::NotRegistered)?;
+        let end = Arc::new(CudaEvent::record(stream));
+
+        Ok(elapsed(start, end))
+    }
+}
+
+/// The time elapsed on the device between two events recorded on the same stream, resolved once
+/// the `end` event is reached.
+pub(crate) fn elapsed(start: Arc<CudaEvent>, end: Arc<CudaEvent>) -> ProfileDuration {
+    ProfileDuration::new_device_time(ElapsedTime {
+        start,
+        end,
+        anchor: Instant::now(),
+    })
+}
+
+/// An [event](CUevent) recorded on a stream, destroyed when dropped.
+#[derive(Debug)]
+pub(crate) struct CudaEvent {
+    event: CUevent,
+}
+
+// # Safety
+//
+// Streams are never closed and the event is only destroyed once, when dropped.
+unsafe impl Send for CudaEvent {}
+unsafe impl Sync for CudaEvent {}
+
+impl CudaEvent {
+    /// Record a new event on the stream.
+    pub fn record(stream: CUstream) -> Self {
+        unsafe {
+            let event =
+                cudarc::driver::result::event::create(CUevent_flags::CU_EVENT_DEFAULT).unwrap();
+            cudarc::driver::result::event::record(event, stream).unwrap();
+            Self { event }
+        }
+    }
+
+    /// Returns whether the event was reached, without blocking.
+    pub fn is_reached(&self) -> bool {
+        unsafe {
+            match cuEventQuery(self.event) {
+                CUresult::CUDA_SUCCESS => true,
+                CUresult::CUDA_ERROR_NOT_READY => false,
+                err => panic!("Failed to query the event: {err:?}"),
+            }
+        }
+    }
+
+    /// Block until the event is reached.
+    pub fn synchronize(&self) {
+        unsafe {
+            cudarc::driver::result::event::synchronize(self.event).unwrap();
+        }
+    }
+
+    /// Make the work submitted to the stream after this call wait until the event is reached.
+    pub fn wait_async(&self, stream: CUstream) {
+        unsafe {
+            cudarc::driver::result::stream::wait_event(
+                stream,
+                self.event,
+                CUevent_wait_flags::CU_EVENT_WAIT_DEFAULT,
+            )
+            .unwrap();
+        }
+    }
+}
+
+impl Drop for CudaEvent {
+    fn drop(&mut self) {
+        unsafe {
+            cudarc::driver::result::event::destroy(self.event).unwrap();
+        }
+    }
+}
+
+/// The time elapsed between two events, without blocking when polled.
+struct ElapsedTime {
+    start: Arc<CudaEvent>,
+    end: Arc<CudaEvent>,
+    /// The device doesn't report absolute times, so the ticks are anchored on the host time
+    /// when the timing was stopped.
+    anchor: Instant,
+}
+
+impl Future for ElapsedTime {
+    type Output = ProfileTicks;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.end.is_reached() {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let millis =
+            unsafe { cudarc::driver::result::event::elapsed(self.start.event, self.end.event) }
+                .expect("Timing events should be completed");
+        let elapsed = Duration::from_secs_f64(millis as f64 / 1000.0);
+
+        Poll::Ready(ProfileTicks::from_start_end(
+            self.anchor,
+            self.anchor + elapsed,
+        ))
+    }
+}
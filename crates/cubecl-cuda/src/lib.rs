@@ -6,6 +6,7 @@ mod compute;
 mod device;
 mod runtime;
 
+pub use compute::{CudaInterop, dlpack};
 pub use device::*;
 pub use runtime::*;
 
@@ -25,6 +26,7 @@ mod tests {
     cubecl_core::testgen_all!(f32: [f16, bf16, f32, f64], i32: [i8, i16, i32, i64], u32: [u8, u16, u32, u64]);
 
     cubecl_std::testgen!();
+    cubecl_core::testgen_launch_dynamic_shared!();
 
     cubecl_matmul::testgen_matmul_plane_accelerated!();
     cubecl_matmul::testgen_matmul_plane_vecmat!();
@@ -33,11 +35,42 @@ mod tests {
     cubecl_quant::testgen_quant!();
 
     // TODO: re-instate matmul quantized tests
-    cubecl_matmul::testgen_matmul_simple!([f16, bf16, f32]);
+    cubecl_matmul::testgen_matmul!([f16, bf16, f32]);
+    cubecl_matmul::testgen_trsm!([f32, f64]);
+    cubecl_matmul::testgen_syrk!([f32, f64]);
+    cubecl_matmul::testgen_matmul_heuristic!();
+    cubecl_matmul::testgen_matmul_batched_gemv!([f16, f32]);
+    cubecl_matmul::testgen_matmul_batched_tiny!([f16, f32]);
+    cubecl_matmul::testgen_matmul_degenerate!([f16, f32]);
+    cubecl_matmul::testgen_matmul_blocked!([f16, f32]);
+    cubecl_matmul::testgen_matmul_affine_int8!();
+    cubecl_matmul::testgen_matmul_scaled_fp8!();
+    cubecl_matmul::testgen_matmul_bias_gelu_residual!([f16, bf16]);
+    cubecl_matmul::testgen_matmul_stage_dump!();
     cubecl_std::testgen_tensor_identity!([f16, bf16, f32, u32]);
+    cubecl_std::testgen_tensor_contiguous!([u8, f32, f64]);
+    cubecl_std::testgen_tensor_elemwise!();
+    cubecl_std::testgen_tensor_gather!();
+    cubecl_std::testgen_tensor_cast!(
+        [u8, i8, u32, i32, f16, bf16, f32, f64],
+        [u8, i8, u32, i32, f16, bf16, f32, f64]
+    );
+    cubecl_std::testgen_tensor_pad!([f16, f32, u32]);
+    cubecl_std::testgen_tensor_slice!([u8, f32]);
+    cubecl_std::testgen_tensor_transpose!([u8, f16, f32, f64]);
     cubecl_convolution::testgen_conv2d_accelerated!([f16: f16, bf16: bf16, f32: tf32]);
     cubecl_reduce::testgen_reduce!([f16, bf16, f32, f64]);
     cubecl_random::testgen_random!();
     cubecl_attention::testgen_attention!();
     cubecl_reduce::testgen_shared_sum!([f16, bf16, f32, f64]);
+    cubecl_reduce::testgen_mean_var!([f16, bf16, f32, f64]);
+    cubecl_reduce::testgen_softmax!([f16, bf16, f32, f64]);
+    cubecl_reduce::testgen_norm!([f16, bf16, f32, f64]);
+    cubecl_reduce::testgen_topk!([f16, bf16, f32, f64]);
+    cubecl_reduce::testgen_scan!();
+    cubecl_reduce::testgen_cumsum!();
+    cubecl_reduce::testgen_radix_sort!();
+    cubecl_reduce::testgen_histogram!();
+    cubecl_reduce::testgen_segmented_reduce!();
+    cubecl_reduce::testgen_scatter_add_deterministic!();
 }
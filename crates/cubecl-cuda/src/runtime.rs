@@ -34,13 +34,20 @@ use cubecl_runtime::{
     memory_management::{HardwareProperties, MemoryDeviceProperties, MemoryManagement},
 };
 use cudarc::driver::sys::cuDeviceTotalMem_v2;
-use std::mem::MaybeUninit;
+use std::{mem::MaybeUninit, time::Duration};
 
 /// Options configuring the CUDA runtime.
 #[derive(Default)]
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// How long the sync and read operations wait for the work of the server before returning a
+    /// [kernel timeout](cubecl_runtime::server::IoError::KernelTimeout), if any.
+    ///
+    /// An event is recorded after each kernel when set, to report the kernel that didn't
+    /// complete. The server can't be used anymore after a timeout, and the memory the device may
+    /// still access is never released.
+    pub kernel_timeout: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -57,6 +64,25 @@ fn create_client<M: DialectWmmaCompiler<CudaDialect<M>>>(
     device: &CudaDevice,
     options: RuntimeOptions,
 ) -> ComputeClient<Server, Channel> {
+    let (server, device_props) = create_server::<M>(device, options);
+    ComputeClient::new(MutexComputeChannel::new(server), device_props, ())
+}
+
+/// Initialize the client of the device with the given options, instead of the default ones.
+///
+/// # Panics
+///
+/// If a client is already created for the device.
+pub fn init_device(device: &CudaDevice, options: RuntimeOptions) {
+    let client = create_client::<WmmaCompiler>(device, options);
+    RUNTIME.register(device, client);
+}
+
+/// Create a server for the device, with the properties of the device.
+pub(crate) fn create_server<M: DialectWmmaCompiler<CudaDialect<M>>>(
+    device: &CudaDevice,
+    options: RuntimeOptions,
+) -> (Server, DeviceProperties) {
     // To get the supported WMMA features, and memory properties, we have to initialize the server immediately.
     cudarc::driver::result::init().unwrap();
     let device_id = device.index as i32;
@@ -109,7 +135,7 @@ fn create_client<M: DialectWmmaCompiler<CudaDialect<M>>>(
     let mem_properties = MemoryDeviceProperties {
         max_page_size: max_memory / 4,
         alignment: mem_alignment as u64,
-        // TODO: We should have a fallback when peer access isn't supported.
+        // Falls back to a copy staged through the host when peer access isn't supported.
         data_transfer_async: true,
     };
 
@@ -186,6 +212,7 @@ fn create_client<M: DialectWmmaCompiler<CudaDialect<M>>>(
         TimingMethod::System,
     );
     register_supported_types(&mut device_props);
+    device_props.features.forward_progress = true;
     device_props.register_type_usage(ElemType::Float(FloatKind::TF32), TypeUsage::Conversion);
     if arch_version >= 60 {
         device_props.register_type_usage(
@@ -265,12 +292,14 @@ fn create_client<M: DialectWmmaCompiler<CudaDialect<M>>>(
         memory_management_gpu,
         memory_management_cpu,
         comp_opts,
+        device_props.hardware.clone(),
         stream,
         ctx,
         arch,
+        options.kernel_timeout,
     );
     let server = CudaServer::new(mem_alignment, cuda_ctx);
-    ComputeClient::new(MutexComputeChannel::new(server), device_props, ())
+    (server, device_props)
 }
 
 fn tensor_cores_per_sm(version: u32) -> Option<u32> {
@@ -306,6 +335,21 @@ impl Runtime for CudaRuntime {
         &[8, 4, 2, 1]
     }
 
+    fn line_size_type(elem: &StorageType) -> impl Iterator<Item = u8> + Clone {
+        // Lines of 16 8-bit floats still fit in a single 128-bit access.
+        let fp8 = matches!(
+            elem,
+            StorageType::Scalar(ElemType::Float(FloatKind::E4M3 | FloatKind::E5M2))
+        );
+        let size = elem.size();
+        [16].into_iter().filter(move |_| fp8).chain(
+            Self::supported_line_sizes()
+                .iter()
+                .filter(move |v| **v as usize * size <= 16)
+                .cloned(),
+        )
+    }
+
     fn max_cube_count() -> (u32, u32, u32) {
         (i32::MAX as u32, u16::MAX as u32, u16::MAX as u32)
     }
@@ -329,3 +373,6 @@ impl Runtime for CudaRuntime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
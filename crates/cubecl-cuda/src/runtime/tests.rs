@@ -0,0 +1,160 @@
+use cubecl_core::{
+    self as cubecl,
+    compute::CubeTask,
+    ir::CacheHint,
+    prelude::*,
+    runtime_tests::barrier::{memcpy_in_loop, memcpy_sequential_barriers, two_independent_loads},
+};
+
+use crate::{CudaCompiler, CudaRuntime};
+
+const ARRAY: ArrayCompilationArg = ArrayCompilationArg {
+    inplace: None,
+    line_size: 1,
+};
+const TENSOR: TensorCompilationArg = TensorCompilationArg {
+    inplace: None,
+    line_size: 1,
+};
+const DECLARATION: &str = "__shared__ cuda::barrier<cuda::thread_scope_block>";
+
+fn settings() -> KernelSettings {
+    KernelSettings::default().cube_dim(CubeDim::new_1d(2))
+}
+
+fn compile<K: CubeKernel>(kernel: K) -> String {
+    KernelTask::<CudaCompiler, _>::new(kernel)
+        .compile(
+            &mut CudaCompiler::default(),
+            &Default::default(),
+            ExecutionMode::Unchecked,
+        )
+        .source
+}
+
+/// The barriers each `memcpy_async` of the source arrives at.
+fn memcpy_barriers(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter(|line| line.contains("cuda::memcpy_async("))
+        .map(|line| line.trim_end_matches(");").rsplit(", ").next().unwrap())
+        .collect()
+}
+
+#[test]
+fn barrier_created_in_loop_is_initialized_once_before_the_loop() {
+    let kernel = memcpy_in_loop::MemcpyInLoop::<f32, CudaRuntime>::new(settings(), ARRAY, ARRAY, 4);
+    let source = compile(kernel);
+
+    assert_eq!(source.matches(DECLARATION).count(), 1);
+    assert_eq!(source.matches("init(&").count(), 1);
+
+    let init = source.find("init(&").unwrap();
+    let sync = source.find("__syncthreads();").unwrap();
+    let start_loop = source.find("for (").unwrap();
+    assert!(init < sync && sync < start_loop, "{source}");
+}
+
+#[test]
+fn barriers_used_one_after_the_other_are_the_same() {
+    let kernel = memcpy_sequential_barriers::MemcpySequentialBarriers::<f32, CudaRuntime>::new(
+        settings(),
+        ARRAY,
+        ARRAY,
+        ARRAY,
+    );
+    let source = compile(kernel);
+
+    assert_eq!(source.matches(DECLARATION).count(), 1);
+    let barriers = memcpy_barriers(&source);
+    assert_eq!(barriers.len(), 2, "{source}");
+    assert_eq!(barriers[0], barriers[1]);
+}
+
+#[test]
+fn barriers_used_at_the_same_time_are_distinct() {
+    let kernel = two_independent_loads::TwoIndependentLoads::<f32, CudaRuntime>::new(
+        settings(),
+        TENSOR,
+        TENSOR,
+        TENSOR,
+        4,
+    );
+    let source = compile(kernel);
+
+    assert_eq!(source.matches(DECLARATION).count(), 2);
+    let barriers = memcpy_barriers(&source);
+    assert_eq!(barriers.len(), 2, "{source}");
+    assert_ne!(barriers[0], barriers[1]);
+}
+
+#[cube(launch)]
+fn kernel_cache_hints(
+    lhs: &Tensor<Line<f32>>,
+    rhs: &Tensor<Line<f32>>,
+    output: &mut Tensor<Line<f32>>,
+    #[comptime] hinted: bool,
+) {
+    if comptime!(hinted) {
+        lhs.cache_hint(comptime!(CacheHint::Persisting));
+        rhs.cache_hint(comptime!(CacheHint::Streaming));
+    }
+    output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] + rhs[ABSOLUTE_POS];
+}
+
+fn cache_hints_source(line_size: u8, hinted: bool) -> String {
+    let tensor = TensorCompilationArg {
+        inplace: None,
+        line_size,
+    };
+    let kernel = kernel_cache_hints::KernelCacheHints::<CudaRuntime>::new(
+        settings(),
+        tensor.clone(),
+        tensor.clone(),
+        tensor,
+        hinted,
+    );
+    compile(kernel)
+}
+
+#[test]
+fn cache_hints_are_lowered_to_load_intrinsics() {
+    let source = cache_hints_source(1, true);
+
+    assert_eq!(source.matches("__ldca(").count(), 1, "{source}");
+    assert_eq!(source.matches("__ldcs(").count(), 1, "{source}");
+    assert!(
+        source.contains("reinterpret_cast<const unsigned int*>"),
+        "{source}"
+    );
+}
+
+#[test]
+fn cache_hints_load_lines_as_vectors_of_the_same_size() {
+    let source = cache_hints_source(4, true);
+
+    assert_eq!(
+        source
+            .matches("__ldca(reinterpret_cast<const uint4*>")
+            .count(),
+        1,
+        "{source}"
+    );
+    assert_eq!(
+        source
+            .matches("__ldcs(reinterpret_cast<const uint4*>")
+            .count(),
+        1,
+        "{source}"
+    );
+}
+
+#[test]
+fn loads_without_cache_hints_are_plain() {
+    let source = cache_hints_source(4, false);
+
+    assert!(
+        !source.contains("__ldca(") && !source.contains("__ldcs("),
+        "{source}"
+    );
+}
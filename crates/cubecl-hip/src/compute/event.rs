@@ -0,0 +1,118 @@
+use cubecl_common::profile::{Duration, Instant, ProfileDuration, ProfileTicks};
+use cubecl_hip_sys::HIP_SUCCESS;
+use std::{
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// An [event](cubecl_hip_sys::hipEvent_t) recorded on a [stream](cubecl_hip_sys::hipStream_t),
+/// destroyed when dropped.
+///
+/// Unlike a [fence](super::fence::Fence), the event can be queried, waited for and timed many
+/// times.
+#[derive(Debug)]
+pub struct HipEvent {
+    event: cubecl_hip_sys::hipEvent_t,
+}
+
+// # Safety
+//
+// Since streams are never closed and the event is only destroyed once, when dropped, it is safe.
+unsafe impl Send for HipEvent {}
+unsafe impl Sync for HipEvent {}
+
+impl HipEvent {
+    /// Record a new event on the given stream.
+    ///
+    /// # Notes
+    ///
+    /// The [stream](cubecl_hip_sys::hipStream_t) must be initialized.
+    pub fn record(stream: cubecl_hip_sys::hipStream_t) -> Self {
+        let mut event: cubecl_hip_sys::hipEvent_t = std::ptr::null_mut();
+        unsafe {
+            let status = cubecl_hip_sys::hipEventCreateWithFlags(
+                &mut event,
+                cubecl_hip_sys::hipEventDefault,
+            );
+            assert_eq!(status, HIP_SUCCESS, "Should create the stream event");
+            let status = cubecl_hip_sys::hipEventRecord(event, stream);
+            assert_eq!(status, HIP_SUCCESS, "Should record the stream event");
+        }
+
+        Self { event }
+    }
+
+    /// Returns whether the event was reached, without blocking.
+    pub fn is_reached(&self) -> bool {
+        let status = unsafe { cubecl_hip_sys::hipEventQuery(self.event) };
+        match status {
+            HIP_SUCCESS => true,
+            cubecl_hip_sys::hipError_t_hipErrorNotReady => false,
+            err => panic!("Failed to query the stream event: {err:?}"),
+        }
+    }
+
+    /// Block until the event is reached.
+    pub fn synchronize(&self) {
+        unsafe {
+            let status = cubecl_hip_sys::hipEventSynchronize(self.event);
+            assert_eq!(
+                status, HIP_SUCCESS,
+                "Should successfully wait for stream event"
+            );
+        }
+    }
+
+    /// The time elapsed on the device between two events recorded on the same stream, resolved
+    /// once the `end` event is reached.
+    pub fn elapsed(start: Arc<HipEvent>, end: Arc<HipEvent>) -> ProfileDuration {
+        ProfileDuration::new_device_time(ElapsedTime {
+            start,
+            end,
+            anchor: Instant::now(),
+        })
+    }
+}
+
+impl Drop for HipEvent {
+    fn drop(&mut self) {
+        unsafe {
+            let status = cubecl_hip_sys::hipEventDestroy(self.event);
+            assert_eq!(status, HIP_SUCCESS, "Should destroy the stream event");
+        }
+    }
+}
+
+/// The time elapsed between two events, without blocking when polled.
+struct ElapsedTime {
+    start: Arc<HipEvent>,
+    end: Arc<HipEvent>,
+    /// The device doesn't report absolute times, so the ticks are anchored on the host time
+    /// when the timing was requested.
+    anchor: Instant,
+}
+
+impl Future for ElapsedTime {
+    type Output = ProfileTicks;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.end.is_reached() {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let mut millis = 0.0f32;
+        unsafe {
+            let status =
+                cubecl_hip_sys::hipEventElapsedTime(&mut millis, self.start.event, self.end.event);
+            assert_eq!(status, HIP_SUCCESS, "Timing events should be completed");
+        }
+        let elapsed = Duration::from_secs_f64(millis as f64 / 1000.0);
+
+        Poll::Ready(ProfileTicks::from_start_end(
+            self.anchor,
+            self.anchor + elapsed,
+        ))
+    }
+}
@@ -40,6 +40,16 @@ impl Fence {
         }
     }
 
+    /// Returns whether the [Fence] was reached, without blocking.
+    pub fn is_reached(&self) -> bool {
+        let status = unsafe { cubecl_hip_sys::hipEventQuery(self.event) };
+        match status {
+            HIP_SUCCESS => true,
+            cubecl_hip_sys::hipError_t_hipErrorNotReady => false,
+            err => panic!("Failed to query the fence event: {err:?}"),
+        }
+    }
+
     /// Wait for the [Fence] to be reached, ensuring that all previous tasks enqueued to the
     /// [stream](hipStream_t) are completed.
     ///
@@ -1,8 +1,10 @@
 mod server;
 
+pub(crate) mod event;
 pub(crate) mod fence;
 pub(crate) mod io;
 pub(crate) mod storage;
+pub(crate) mod watchdog;
 
 pub use server::*;
 pub use storage::*;
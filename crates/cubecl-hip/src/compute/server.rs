@@ -1,5 +1,7 @@
+use super::event::HipEvent;
 use super::fence::{Fence, SyncStream};
 use super::storage::gpu::GpuStorage;
+use super::watchdog::Watchdog;
 use super::{storage::gpu::GpuResource, uninit_vec};
 use crate::compute::cpu::PinnedMemoryStorage;
 use crate::compute::io::register_copies_to_bytes;
@@ -12,8 +14,8 @@ use cubecl_core::compute::DebugInformation;
 use cubecl_core::prelude::*;
 use cubecl_core::server::Bindings;
 use cubecl_core::server::{
-    Allocation, AllocationKind, CopyDescriptor, DataTransferService, IoError, ProfileError,
-    ProfilingToken,
+    Allocation, AllocationKind, CompilationError, CompilationStage, CopyDescriptor,
+    DataTransferService, ExecutionStream, IoError, ProfileError, ProfilingToken, StreamEvent,
 };
 use cubecl_cpp::formatter::format_cpp;
 use cubecl_cpp::shared::CompilationOptions;
@@ -22,8 +24,10 @@ use cubecl_hip_sys::{
     hiprtcResult_HIPRTC_SUCCESS,
 };
 use cubecl_runtime::logging::ServerLogger;
-use cubecl_runtime::memory_management::MemoryUsage;
 use cubecl_runtime::memory_management::offset_handles;
+use cubecl_runtime::memory_management::{
+    HardwareProperties, MemoryCleanupMode, MemoryScopeUsage, MemoryUsage,
+};
 use cubecl_runtime::storage::BindingResource;
 use cubecl_runtime::timestamp_profiler::TimestampProfiler;
 use cubecl_runtime::{
@@ -35,6 +39,7 @@ use std::ffi::CStr;
 use std::ffi::CString;
 use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "compilation-cache")]
 use cubecl_common::cache::{Cache, CacheOption};
@@ -52,7 +57,12 @@ pub(crate) struct HipContext {
     pub(crate) memory_management_cpu: MemoryManagement<PinnedMemoryStorage>,
     module_names: HashMap<KernelId, HipCompiledKernel>,
     timestamps: TimestampProfiler,
+    /// The [recorded events](ComputeServer::record_event) until they are released.
+    events: HashMap<u64, Arc<HipEvent>>,
+    event_count: u64,
     compilation_options: CompilationOptions,
+    hardware_properties: HardwareProperties,
+    watchdog: Watchdog,
     #[cfg(feature = "compilation-cache")]
     compilation_cache: Cache<String, CompilationCacheEntry>,
 }
@@ -62,6 +72,7 @@ pub(crate) struct HipContext {
 pub struct CompilationCacheEntry {
     entrypoint_name: String,
     cube_dim: (u32, u32, u32),
+    shared_mem_bytes: u32,
     binary: Vec<i8>,
 }
 
@@ -70,6 +81,8 @@ struct HipCompiledKernel {
     _module: cubecl_hip_sys::hipModule_t,
     func: cubecl_hip_sys::hipFunction_t,
     cube_dim: CubeDim,
+    /// The bytes of dynamic shared memory the kernel is launched with.
+    shared_mem_bytes: u32,
 }
 
 unsafe impl Send for HipServer {}
@@ -101,13 +114,25 @@ impl HipServer {
         &mut self,
         descriptors: Vec<server::CopyDescriptor>,
     ) -> impl Future<Output = Result<Vec<Bytes>, IoError>> + Send + use<> {
-        let result = register_copies_to_bytes(&mut self.ctx, descriptors);
+        let result = self
+            .ctx
+            .watchdog
+            .check()
+            .and_then(|_| register_copies_to_bytes(&mut self.ctx, descriptors));
         let ctx = self.get_context();
+        let started = Instant::now();
         let fence = ctx.fence();
+        let watchdog = ctx.watchdog.clone();
 
         async move {
-            fence.wait_sync();
-            result
+            match watchdog.wait(fence, started) {
+                Ok(_) => result,
+                Err(err) => {
+                    // The memory of the copies may still be accessed by the device.
+                    core::mem::forget(result);
+                    Err(err)
+                }
+            }
         }
     }
 
@@ -168,6 +193,7 @@ impl ComputeServer for HipServer {
         }
 
         let ctx = self.get_context();
+        ctx.watchdog.check()?;
         let handle = ctx.memory_management_gpu.reserve(total_size as u64)?;
         let mem_handle = server::Handle::new(handle, None, None, total_size as u64);
         let handles = offset_handles(mem_handle, &sizes, self.mem_alignment);
@@ -190,6 +216,8 @@ impl ComputeServer for HipServer {
         &mut self,
         descriptors: Vec<(server::CopyDescriptor<'_>, &[u8])>,
     ) -> Result<(), IoError> {
+        self.ctx.watchdog.check()?;
+
         for (descriptor, data) in descriptors {
             let CopyDescriptor {
                 binding,
@@ -219,9 +247,21 @@ impl ComputeServer for HipServer {
         self.ctx.memory_usage()
     }
 
-    fn memory_cleanup(&mut self) {
+    fn push_memory_scope(&mut self, tag: String) {
+        self.ctx.memory_management_gpu.push_scope(tag);
+    }
+
+    fn pop_memory_scope(&mut self) {
+        self.ctx.memory_management_gpu.pop_scope();
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.ctx.memory_management_gpu.scope_usage()
+    }
+
+    fn memory_cleanup(&mut self, mode: MemoryCleanupMode) {
         let ctx = self.get_context();
-        ctx.memory_management_gpu.cleanup(true);
+        ctx.memory_management_gpu.memory_cleanup(mode);
     }
 
     unsafe fn execute(
@@ -231,7 +271,9 @@ impl ComputeServer for HipServer {
         bindings: Bindings,
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
+        self.ctx.watchdog.check()?;
+        let kernel_name = kernel.name();
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
 
@@ -259,18 +301,16 @@ impl ComputeServer for HipServer {
         } = bindings;
 
         debug_assert!(tensor_maps.is_empty(), "Can't use tensor maps on HIP");
-        let info = self
-            .create_with_data(bytemuck::cast_slice(&metadata.data))
-            .unwrap();
-        let scalars: Vec<_> = scalars
+        let info = self.create_with_data(bytemuck::cast_slice(&metadata.data))?;
+        let scalars = scalars
             .values()
-            .map(|s| self.create_with_data(s.data()).unwrap())
-            .collect();
+            .map(|s| self.create_with_data(s.data()))
+            .collect::<Result<Vec<_>, IoError>>()?;
 
         let ctx = self.get_context();
 
         if !ctx.module_names.contains_key(&kernel_id) {
-            ctx.compile_kernel(&kernel_id, kernel, mode, logger);
+            ctx.compile_kernel(&kernel_id, kernel, mode, logger)?;
         }
 
         let mut resources: Vec<_> = buffers.into_iter().map(|b| find_resource(ctx, b)).collect();
@@ -278,12 +318,103 @@ impl ComputeServer for HipServer {
         resources.extend(scalars.into_iter().map(|s| find_resource(ctx, s.binding())));
 
         ctx.execute_task(kernel_id, count, resources);
+        ctx.watchdog.launched(kernel_name, ctx.stream);
+
+        Ok(())
     }
 
     fn flush(&mut self) {}
 
+    fn precompile(
+        &mut self,
+        kernel: Self::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        let mut kernel_id = kernel.id();
+        kernel_id.mode(mode);
+
+        let ctx = self.get_context();
+
+        // Not loaded on error, so the error is returned again when the kernel is launched.
+        if !ctx.module_names.contains_key(&kernel_id) {
+            ctx.compile_kernel(&kernel_id, kernel, mode, logger)?;
+        }
+
+        Ok(())
+    }
+
     fn sync(&mut self) -> DynFut<()> {
-        Box::pin(self.sync_stream_async())
+        if !self.ctx.watchdog.is_enabled() {
+            return Box::pin(self.sync_stream_async());
+        }
+        let sync = self.try_sync();
+
+        Box::pin(async move {
+            if let Err(err) = sync.await {
+                panic!("Failed to sync the server: {err}");
+            }
+        })
+    }
+
+    fn try_sync(&mut self) -> DynFut<Result<(), IoError>> {
+        let ctx = self.get_context();
+        if let Err(err) = ctx.watchdog.check() {
+            return Box::pin(async move { Err(err) });
+        }
+        if !ctx.watchdog.is_enabled() {
+            let sync = self.sync_stream_async();
+            return Box::pin(async move {
+                sync.await;
+                Ok(())
+            });
+        }
+
+        let started = Instant::now();
+        let fence = ctx.fence();
+        let watchdog = ctx.watchdog.clone();
+
+        Box::pin(async move { watchdog.wait(fence, started) })
+    }
+
+    // All the work is submitted to a single stream, so streams never have to wait for events.
+    fn record_event(&mut self, _stream: ExecutionStream) -> StreamEvent {
+        let ctx = self.get_context();
+        let id = ctx.event_count;
+        ctx.event_count += 1;
+        ctx.events
+            .insert(id, Arc::new(HipEvent::record(ctx.stream)));
+
+        StreamEvent { id }
+    }
+
+    fn is_event_complete(&mut self, event: StreamEvent) -> bool {
+        self.get_context().event(event).is_reached()
+    }
+
+    fn sync_event(&mut self, event: StreamEvent) -> DynFut<()> {
+        let event = self.get_context().event(event).clone();
+
+        Box::pin(async move { event.synchronize() })
+    }
+
+    fn event_elapsed(
+        &mut self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let ctx = self.get_context();
+        let start = ctx
+            .events
+            .get(&start.id)
+            .ok_or(ProfileError::NotRegistered)?;
+        let end = ctx.events.get(&end.id).ok_or(ProfileError::NotRegistered)?;
+
+        Ok(HipEvent::elapsed(start.clone(), end.clone()))
+    }
+
+    fn release_event(&mut self, event: StreamEvent) {
+        self.get_context().events.remove(&event.id);
     }
 
     fn start_profile(&mut self) -> ProfilingToken {
@@ -323,7 +454,9 @@ impl HipContext {
         memory_management_gpu: MemoryManagement<GpuStorage>,
         memory_management_cpu: MemoryManagement<PinnedMemoryStorage>,
         compilation_options: CompilationOptions,
+        hardware_properties: HardwareProperties,
         stream: cubecl_hip_sys::hipStream_t,
+        kernel_timeout: Option<Duration>,
     ) -> Self {
         Self {
             memory_management_gpu,
@@ -331,7 +464,11 @@ impl HipContext {
             module_names: HashMap::new(),
             stream,
             timestamps: TimestampProfiler::default(),
+            events: HashMap::new(),
+            event_count: 0,
             compilation_options,
+            hardware_properties,
+            watchdog: Watchdog::new(kernel_timeout),
             #[cfg(feature = "compilation-cache")]
             compilation_cache: Cache::new("hip/compilation", CacheOption::default()),
         }
@@ -345,6 +482,12 @@ impl HipContext {
         SyncStream::new(self.stream)
     }
 
+    fn event(&self, event: StreamEvent) -> &Arc<HipEvent> {
+        self.events
+            .get(&event.id)
+            .unwrap_or_else(|| panic!("Unknown or released stream event {}", event.id))
+    }
+
     fn sync(&mut self) {
         unsafe {
             let status = cubecl_hip_sys::hipStreamSynchronize(self.stream);
@@ -365,7 +508,7 @@ impl HipContext {
         cube_kernel: Box<dyn CubeTask<HipCompiler>>,
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
         #[cfg(feature = "compilation-cache")]
         let name = kernel_id.stable_format();
         #[cfg(feature = "compilation-cache")]
@@ -380,8 +523,9 @@ impl HipContext {
                     y: entry.cube_dim.1,
                     z: entry.cube_dim.2,
                 },
-            );
-            return;
+                entry.shared_mem_bytes,
+            )?;
+            return Ok(());
         }
 
         // CubeCL compilation
@@ -398,9 +542,32 @@ impl HipContext {
         }
         logger.log_compilation(&jitc_kernel);
 
+        // Shared memory is declared statically in the kernel, so only the bytes requested beyond
+        // its shared memories are launched as dynamic shared memory. The preferred carveout has
+        // no equivalent, since the LDS isn't shared with the L1 cache.
+        let repr = jitc_kernel.repr.as_ref().unwrap();
+        let static_shared_mem_bytes = repr.shared_memory_size();
+        let shared_mem_bytes = (repr.dynamic_shared_memory.unwrap_or(0) as usize)
+            .saturating_sub(static_shared_mem_bytes);
+        self.hardware_properties.check_kernel_limits(
+            &jitc_kernel.cube_dim,
+            static_shared_mem_bytes + shared_mem_bytes,
+        )?;
+
+        // Rejected kernels are returned as errors rather than panicking, so the server can keep
+        // executing the other kernels.
+        let compilation_error = |log: String| {
+            IoError::from(CompilationError::new(
+                cube_kernel.name(),
+                CompilationStage::Compile,
+                log,
+            ))
+        };
+
         // Create HIP Program
         let program = unsafe {
-            let source = CString::new(jitc_kernel.source.clone()).unwrap();
+            let source = CString::new(jitc_kernel.source.clone())
+                .map_err(|err| compilation_error(format!("Invalid source: {err}")))?;
             let mut program: cubecl_hip_sys::hiprtcProgram = std::ptr::null_mut();
             let status = cubecl_hip_sys::hiprtcCreateProgram(
                 &mut program,
@@ -410,10 +577,11 @@ impl HipContext {
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
             );
-            assert_eq!(
-                status, hiprtcResult_HIPRTC_SUCCESS,
-                "Should create the program"
-            );
+            if status != hiprtcResult_HIPRTC_SUCCESS {
+                return Err(compilation_error(format!(
+                    "Can't create the program, status {status}"
+                )));
+            }
             program
         };
         // Compile HIP program
@@ -447,18 +615,16 @@ impl HipContext {
                     status, hiprtcResult_HIPRTC_SUCCESS,
                     "Should retrieve the compilation log contents"
                 );
-                let log = CStr::from_ptr(log_buffer.as_ptr());
-                let mut message = "[Compilation Error] ".to_string();
-                if log_size > 0 {
-                    for line in log.to_string_lossy().split('\n') {
-                        if !line.is_empty() {
-                            message += format!("\n    {line}").as_str();
-                        }
-                    }
-                } else {
-                    message += "\n No compilation logs found!";
-                }
-                panic!("{message}\n[Source]  \n{}", jitc_kernel.source);
+                let log = match log_size {
+                    0 => "No compilation logs found!".to_string(),
+                    _ => CStr::from_ptr(log_buffer.as_ptr())
+                        .to_string_lossy()
+                        .lines()
+                        .filter(|line| !line.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                };
+                return Err(compilation_error(log));
             }
             assert_eq!(
                 status, hiprtcResult_HIPRTC_SUCCESS,
@@ -494,6 +660,7 @@ impl HipContext {
                         jitc_kernel.cube_dim.y,
                         jitc_kernel.cube_dim.z,
                     ),
+                    shared_mem_bytes: shared_mem_bytes as u32,
                     binary: code.clone(),
                 },
             )
@@ -504,7 +671,8 @@ impl HipContext {
             kernel_id.clone(),
             jitc_kernel.entrypoint_name,
             jitc_kernel.cube_dim,
-        );
+            shared_mem_bytes as u32,
+        )
     }
 
     fn load_compiled_binary(
@@ -513,22 +681,39 @@ impl HipContext {
         kernel_id: KernelId,
         entrypoint_name: String,
         cube_dim: CubeDim,
-    ) {
-        let func_name = CString::new(entrypoint_name.clone()).unwrap();
+        shared_mem_bytes: u32,
+    ) -> Result<(), IoError> {
+        let load_error = |log: String| {
+            IoError::from(CompilationError::new(
+                entrypoint_name.as_str(),
+                CompilationStage::Load,
+                log,
+            ))
+        };
+        let func_name = CString::new(entrypoint_name.clone())
+            .map_err(|err| load_error(format!("Invalid entrypoint name: {err}")))?;
 
         // Create the HIP module
         let mut module: cubecl_hip_sys::hipModule_t = std::ptr::null_mut();
         unsafe {
             let codeptr = code.as_ptr();
             let status = cubecl_hip_sys::hipModuleLoadData(&mut module, codeptr as *const _);
-            assert_eq!(status, HIP_SUCCESS, "Should load compiled code into module");
+            if status != HIP_SUCCESS {
+                return Err(load_error(format!(
+                    "Can't load the module, status {status}"
+                )));
+            }
         }
         // Retrieve the HIP module function
         let mut func: cubecl_hip_sys::hipFunction_t = std::ptr::null_mut();
         unsafe {
             let status =
                 cubecl_hip_sys::hipModuleGetFunction(&mut func, module, func_name.as_ptr());
-            assert_eq!(status, HIP_SUCCESS, "Should return module function");
+            if status != HIP_SUCCESS {
+                return Err(load_error(format!(
+                    "Can't find the function, status {status}"
+                )));
+            }
         }
 
         // register module
@@ -538,8 +723,11 @@ impl HipContext {
                 _module: module,
                 func,
                 cube_dim,
+                shared_mem_bytes,
             },
         );
+
+        Ok(())
     }
 
     fn execute_task(
@@ -565,10 +753,9 @@ impl HipContext {
                 cube_dim.x,
                 cube_dim.y,
                 cube_dim.z,
-                // Shared memory is specified statically in the kernel, and no dynamic shared
-                // memory is supported yet in the kernel, which would be that value for the
-                // current kernel launch.
-                0,
+                // Shared memory is specified statically in the kernel, this is only the
+                // additional dynamic shared memory requested.
+                kernel.shared_mem_bytes,
                 self.stream,
                 bindings.as_mut_ptr(),
                 std::ptr::null_mut(),
@@ -128,6 +128,9 @@ impl ComputeStorage for GpuStorage {
 
             match status {
                 HIP_SUCCESS => {}
+                cubecl_hip_sys::hipError_t_hipErrorOutOfMemory => {
+                    return Err(IoError::BufferTooBig(size as usize));
+                }
                 other => {
                     return Err(IoError::Unknown(format!("HIP allocation error: {}", other)));
                 }
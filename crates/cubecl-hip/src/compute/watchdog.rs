@@ -0,0 +1,199 @@
+use super::{event::HipEvent, fence::Fence};
+use cubecl_core::server::IoError;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The name reported when the work that didn't complete isn't a kernel launched by the server.
+const UNKNOWN_KERNEL: &str = "<unknown>";
+
+/// Detects the work of a server that doesn't complete within a timeout, like a kernel waiting
+/// forever on a barrier.
+///
+/// Nothing is waited for when kernels are launched: an event is recorded after each of them, and
+/// the fences of the sync and read paths are polled until the timeout is exceeded. The first
+/// kernel whose event isn't reached is then reported, and the server is poisoned: the work
+/// enqueued after the kernel may never complete, so every later call fails immediately.
+///
+/// Without a timeout, no event is recorded and the work is waited for as usual.
+#[derive(Debug, Clone, Default)]
+pub struct Watchdog {
+    timeout: Option<Duration>,
+    state: Arc<Mutex<WatchdogState>>,
+}
+
+#[derive(Debug, Default)]
+struct WatchdogState {
+    /// The kernels launched that aren't known to be completed yet, in launch order.
+    launches: VecDeque<(&'static str, HipEvent)>,
+    /// The kernel that timed out, after which the server can't be used anymore.
+    poisoned: Option<String>,
+}
+
+impl Watchdog {
+    /// Create a watchdog failing the waits longer than the `timeout`, if any.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            state: Default::default(),
+        }
+    }
+
+    /// Whether the waits can time out.
+    pub fn is_enabled(&self) -> bool {
+        self.timeout.is_some()
+    }
+
+    /// Returns an error when a kernel previously timed out.
+    pub fn check(&self) -> Result<(), IoError> {
+        match &self.state.lock().unwrap().poisoned {
+            Some(kernel_name) => Err(IoError::Poisoned {
+                kernel_name: kernel_name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Register the kernel just launched on the `stream`, to know which one was running when a
+    /// wait times out.
+    pub fn launched(&self, kernel_name: &'static str, stream: cubecl_hip_sys::hipStream_t) {
+        if self.timeout.is_none() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        // Only the first ones are queried, so each launch is released once its event is reached.
+        while state
+            .launches
+            .front()
+            .is_some_and(|(_, event)| event.is_reached())
+        {
+            state.launches.pop_front();
+        }
+        state
+            .launches
+            .push_back((kernel_name, HipEvent::record(stream)));
+    }
+
+    /// Block until the `fence` created at `started` is reached.
+    ///
+    /// Returns an error when the server is poisoned, or when the timeout is exceeded, in which
+    /// case the server is poisoned and the fence is leaked.
+    pub fn wait(&self, fence: Fence, started: Instant) -> Result<(), IoError> {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => {
+                fence.wait_sync();
+                return Ok(());
+            }
+        };
+
+        while !fence.is_reached() {
+            self.check()?;
+
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                return Err(self.poison(elapsed));
+            }
+            std::thread::yield_now();
+        }
+        // Already reached, this only releases the event.
+        fence.wait_sync();
+
+        Ok(())
+    }
+
+    fn poison(&self, elapsed: Duration) -> IoError {
+        let mut state = self.state.lock().unwrap();
+        // Another wait may have timed out in the meantime.
+        if let Some(kernel_name) = &state.poisoned {
+            return IoError::Poisoned {
+                kernel_name: kernel_name.clone(),
+            };
+        }
+
+        let kernel_name = state
+            .launches
+            .iter()
+            .find(|(_, event)| !event.is_reached())
+            .map(|(kernel_name, _)| *kernel_name)
+            .unwrap_or(UNKNOWN_KERNEL)
+            .to_string();
+        state.launches.clear();
+        state.poisoned = Some(kernel_name.clone());
+
+        IoError::KernelTimeout {
+            kernel_name,
+            elapsed,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "watchdog_tests"))]
+mod tests {
+    use crate::{
+        HipRuntime, HipWmmaCompiler,
+        runtime::{RuntimeOptions, create_client},
+    };
+    use cubecl_core::{future, prelude::*, server::IoError};
+    use std::time::Duration;
+
+    /// Waits on a flag that is never set, like units waiting on a barrier that never gets enough
+    /// arrivals.
+    #[cube(launch)]
+    fn kernel_deadlock(flag: &Array<Atomic<u32>>) {
+        let mut value = Atomic::load(&flag[0]);
+        while value == 0 {
+            value = Atomic::load(&flag[0]);
+        }
+    }
+
+    /// The kernel never completes, so the device stays busy until the process exits.
+    #[test]
+    fn deadlocking_kernel_times_out() {
+        let timeout = Duration::from_millis(500);
+        let options = RuntimeOptions {
+            kernel_timeout: Some(timeout),
+            ..Default::default()
+        };
+        let client = create_client::<HipWmmaCompiler>(&Default::default(), options);
+        let flag = client.create(u32::as_bytes(&[0]));
+
+        kernel_deadlock::launch::<HipRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(1),
+            unsafe { ArrayArg::from_raw_parts::<u32>(&flag, 1, 1) },
+        );
+
+        match future::block_on(client.try_sync()) {
+            Err(IoError::KernelTimeout {
+                kernel_name,
+                elapsed,
+            }) => {
+                assert!(
+                    kernel_name.contains("KernelDeadlock"),
+                    "the deadlocking kernel should be reported, got {kernel_name}"
+                );
+                assert!(elapsed >= timeout);
+            }
+            result => panic!("the sync should time out, got {result:?}"),
+        }
+
+        // The later calls fail without waiting for the kernel.
+        assert!(matches!(
+            future::block_on(client.try_read_async(vec![flag])),
+            Err(IoError::Poisoned { .. })
+        ));
+        assert!(matches!(client.try_empty(4), Err(IoError::Poisoned { .. })));
+        assert!(matches!(
+            future::block_on(client.try_sync()),
+            Err(IoError::Poisoned { .. })
+        ));
+
+        // The memory the kernel still uses is never released.
+        core::mem::forget(client);
+    }
+}
@@ -21,6 +21,17 @@ mod tests {
     pub type TestRuntime = crate::HipRuntime;
 
     cubecl_std::testgen!();
+    cubecl_core::testgen_launch_dynamic_shared!();
+    cubecl_std::testgen_tensor_contiguous!();
+    cubecl_std::testgen_tensor_elemwise!();
+    cubecl_std::testgen_tensor_gather!();
+    cubecl_std::testgen_tensor_cast!(
+        [u8, i8, u32, i32, f16, bf16, f32],
+        [u8, i8, u32, i32, f16, bf16, f32]
+    );
+    cubecl_std::testgen_tensor_pad!();
+    cubecl_std::testgen_tensor_slice!();
+    cubecl_std::testgen_tensor_transpose!([u8, f16, f32, f64]);
     cubecl_core::testgen_all!(f32: [f16, f32], i32: [i16, i32], u32: [u16, u32]);
     cubecl_quant::testgen_quant!();
 
@@ -29,8 +40,29 @@ mod tests {
     #[cfg(all(feature = "matmul_tests_plane", feature = "matmul_tests_vecmat"))]
     cubecl_matmul::testgen_matmul_vecmat_accelerated!();
     #[cfg(feature = "matmul_tests_simple")]
-    cubecl_matmul::testgen_matmul_simple!([f16, f32]);
+    cubecl_matmul::testgen_matmul!([f16, f32]);
+    cubecl_matmul::testgen_trsm!([f32, f64]);
+    cubecl_matmul::testgen_syrk!([f32, f64]);
+    cubecl_matmul::testgen_matmul_heuristic!();
+    cubecl_matmul::testgen_matmul_batched_gemv!([f16, f32]);
+    cubecl_matmul::testgen_matmul_batched_tiny!([f16, f32]);
+    cubecl_matmul::testgen_matmul_degenerate!([f16, f32]);
+    cubecl_matmul::testgen_matmul_blocked!([f16, f32]);
+    cubecl_matmul::testgen_matmul_affine_int8!();
+    cubecl_matmul::testgen_matmul_scaled_fp8!();
+    cubecl_matmul::testgen_matmul_bias_gelu_residual!([f16, bf16]);
+    cubecl_matmul::testgen_matmul_stage_dump!();
 
     cubecl_reduce::testgen_reduce!([f16, bf16, f32, f64]);
     cubecl_reduce::testgen_shared_sum!([f32]);
+    cubecl_reduce::testgen_mean_var!([f16, f32]);
+    cubecl_reduce::testgen_softmax!([f16, f32]);
+    cubecl_reduce::testgen_norm!([f16, f32]);
+    cubecl_reduce::testgen_topk!([f16, f32]);
+    cubecl_reduce::testgen_scan!();
+    cubecl_reduce::testgen_cumsum!();
+    cubecl_reduce::testgen_radix_sort!();
+    cubecl_reduce::testgen_histogram!();
+    cubecl_reduce::testgen_segmented_reduce!();
+    cubecl_reduce::testgen_scatter_add_deterministic!();
 }
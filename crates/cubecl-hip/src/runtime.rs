@@ -1,4 +1,4 @@
-use std::{ffi::CStr, mem::MaybeUninit};
+use std::{ffi::CStr, mem::MaybeUninit, time::Duration};
 
 use cubecl_cpp::{
     hip::{HipDialect, arch::AMDArchitecture},
@@ -37,6 +37,13 @@ use crate::{
 pub struct RuntimeOptions {
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// How long the sync and read operations wait for the work of the server before returning a
+    /// [kernel timeout](cubecl_runtime::server::IoError::KernelTimeout), if any.
+    ///
+    /// An event is recorded after each kernel when set, to report the kernel that didn't
+    /// complete. The server can't be used anymore after a timeout, and the memory the device may
+    /// still access is never released.
+    pub kernel_timeout: Option<Duration>,
 }
 
 #[derive(Debug)]
@@ -50,7 +57,7 @@ pub type HipCompiler = CppCompiler<HipDialect<HipWmmaCompiler>>;
 type Server = HipServer;
 type Channel = MutexComputeChannel<Server>;
 
-fn create_client<M: DialectWmmaCompiler<HipDialect<M>>>(
+pub(crate) fn create_client<M: DialectWmmaCompiler<HipDialect<M>>>(
     device: &AmdDevice,
     options: RuntimeOptions,
 ) -> ComputeClient<Server, Channel> {
@@ -197,12 +204,24 @@ fn create_client<M: DialectWmmaCompiler<HipDialect<M>>>(
         memory_management_gpu,
         memory_management_cpu,
         comp_opts,
+        device_props.hardware.clone(),
         stream,
+        options.kernel_timeout,
     );
     let server = HipServer::new(mem_alignment, hip_ctx);
     ComputeClient::new(MutexComputeChannel::new(server), device_props, ())
 }
 
+/// Initialize the client of the device with the given options, instead of the default ones.
+///
+/// # Panics
+///
+/// If a client is already created for the device.
+pub fn init_device(device: &AmdDevice, options: RuntimeOptions) {
+    let client = create_client::<HipWmmaCompiler>(device, options);
+    RUNTIME.register(device, client);
+}
+
 impl Runtime for HipRuntime {
     type Compiler = HipCompiler;
     type Server = HipServer;
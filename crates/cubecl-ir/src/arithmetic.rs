@@ -18,18 +18,22 @@ pub enum Arithmetic {
     Div(BinaryOperator),
     Abs(UnaryOperator),
     Exp(UnaryOperator),
+    Exp2(UnaryOperator),
     Log(UnaryOperator),
     Log1p(UnaryOperator),
+    Log2(UnaryOperator),
     Cos(UnaryOperator),
     Sin(UnaryOperator),
     Tanh(UnaryOperator),
     Powf(BinaryOperator),
     Powi(BinaryOperator),
     Sqrt(UnaryOperator),
+    Rsqrt(UnaryOperator),
     Round(UnaryOperator),
     Floor(UnaryOperator),
     Ceil(UnaryOperator),
     Erf(UnaryOperator),
+    Erfc(UnaryOperator),
     Recip(UnaryOperator),
     Clamp(ClampOperator),
     Modulo(BinaryOperator),
@@ -57,18 +61,22 @@ impl Display for Arithmetic {
             Arithmetic::Div(op) => write!(f, "{} / {}", op.lhs, op.rhs),
             Arithmetic::Abs(op) => write!(f, "{}.abs()", op.input),
             Arithmetic::Exp(op) => write!(f, "{}.exp()", op.input),
+            Arithmetic::Exp2(op) => write!(f, "{}.exp2()", op.input),
             Arithmetic::Log(op) => write!(f, "{}.log()", op.input),
             Arithmetic::Log1p(op) => write!(f, "{}.log_1p()", op.input),
+            Arithmetic::Log2(op) => write!(f, "{}.log2()", op.input),
             Arithmetic::Cos(op) => write!(f, "{}.cos()", op.input),
             Arithmetic::Sin(op) => write!(f, "{}.sin()", op.input),
             Arithmetic::Tanh(op) => write!(f, "{}.tanh()", op.input),
             Arithmetic::Powf(op) => write!(f, "{}.powf({})", op.lhs, op.rhs),
             Arithmetic::Powi(op) => write!(f, "{}.powi({})", op.lhs, op.rhs),
             Arithmetic::Sqrt(op) => write!(f, "{}.sqrt()", op.input),
+            Arithmetic::Rsqrt(op) => write!(f, "{}.rsqrt()", op.input),
             Arithmetic::Round(op) => write!(f, "{}.round()", op.input),
             Arithmetic::Floor(op) => write!(f, "{}.floor()", op.input),
             Arithmetic::Ceil(op) => write!(f, "{}.ceil()", op.input),
             Arithmetic::Erf(op) => write!(f, "{}.erf()", op.input),
+            Arithmetic::Erfc(op) => write!(f, "{}.erfc()", op.input),
             Arithmetic::Recip(op) => write!(f, "{}.recip()", op.input),
             Arithmetic::Clamp(op) => {
                 write!(f, "{}.clamp({}, {})", op.input, op.min_value, op.max_value)
@@ -30,6 +30,32 @@ pub enum NonSemantic {
     },
     /// Insert a comment into the compiled source
     Comment { content: String },
+    /// Hint how the following reads of a global `buffer` should be cached. Only changes the
+    /// performance of the kernel, backends without cache control ignore it.
+    CacheHint { buffer: Variable, hint: CacheHint },
+}
+
+/// How the reads of a global buffer should go through the caches.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, TypeHash, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub enum CacheHint {
+    /// Let the backend decide.
+    #[default]
+    Default,
+    /// Read once, so the lines can be evicted first to keep the caches for the other buffers.
+    Streaming,
+    /// Read many times, so the lines should be kept in the caches as long as possible.
+    Persisting,
+}
+
+impl Display for CacheHint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CacheHint::Default => f.write_str("default"),
+            CacheHint::Streaming => f.write_str("streaming"),
+            CacheHint::Persisting => f.write_str("persisting"),
+        }
+    }
 }
 
 impl OperationReflect for NonSemantic {
@@ -50,6 +76,7 @@ impl Display for NonSemantic {
                 write!(f, "print({format_string:?}, {})", fmt_vararg(args))
             }
             NonSemantic::Comment { content } => write!(f, "//{content}"),
+            NonSemantic::CacheHint { buffer, hint } => write!(f, "cache_hint({buffer}, {hint})"),
             // Scopes don't have meaning to the user
             _ => Ok(()),
         }
@@ -236,6 +236,33 @@ impl Expression {
         }
     }
 
+    /// Whether the expression only depends on comptime values, so it can be evaluated as plain
+    /// Rust during expansion.
+    pub fn is_comptime(&self) -> bool {
+        match self {
+            Expression::Binary { left, right, .. }
+            | Expression::Index {
+                expr: left,
+                index: right,
+                ..
+            } => left.is_comptime() && right.is_comptime(),
+            Expression::Unary { input, .. } => input.is_comptime(),
+            Expression::Cast { from, .. } => from.is_comptime(),
+            Expression::FieldAccess { base, .. } => base.is_comptime(),
+            Expression::Reference { inner } => inner.is_comptime(),
+            Expression::MethodCall { receiver, args, .. } => {
+                receiver.is_comptime() && args.iter().all(Self::is_comptime)
+            }
+            Expression::FunctionCall { func, args, .. } => {
+                func.is_comptime() && args.iter().all(Self::is_comptime)
+            }
+            Expression::Array { elements, .. } | Expression::Tuple { elements, .. } => {
+                elements.iter().all(Self::is_comptime)
+            }
+            expr => expr.is_const(),
+        }
+    }
+
     pub fn as_const_primitive(&self, _context: &mut Context) -> Option<TokenStream> {
         match self {
             Expression::Literal { value, .. } => match value {
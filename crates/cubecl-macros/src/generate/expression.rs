@@ -41,7 +41,7 @@ impl Expression {
                 let expand = with_span(
                     context,
                     *span,
-                    quote![#frontend_path::#op::expand(scope, _array, _index.into(), _value.into())],
+                    quote_spanned![*span=> #frontend_path::#op::expand(scope, _array, _index.into(), _value.into())],
                 );
                 quote! {
                     {
@@ -66,7 +66,7 @@ impl Expression {
                 let expand = with_span(
                     context,
                     *span,
-                    quote![#frontend_path::#op::expand(scope, _lhs.into(), _rhs.into())],
+                    quote_spanned![*span=> #frontend_path::#op::expand(scope, _lhs.into(), _rhs.into())],
                 );
                 quote! {
                     {
@@ -93,7 +93,7 @@ impl Expression {
                 let expand = with_span(
                     context,
                     *span,
-                    quote![#frontend_path::#op::expand(scope, _inner.into())],
+                    quote_spanned![*span=> #frontend_path::#op::expand(scope, _inner.into())],
                 );
                 quote! {
                     {
@@ -166,7 +166,7 @@ impl Expression {
                 let expand = with_span(
                     context,
                     *span,
-                    quote![#index_fn::expand(scope, _array, _index.into())],
+                    quote_spanned![*span=> #index_fn::expand(scope, _array, _index.into())],
                 );
                 quote! {
                     {
@@ -249,7 +249,8 @@ impl Expression {
                 span,
                 ..
             } => {
-                let method = format_ident!("__expand_{method}_method");
+                // Keep the span of the method, so an unsupported method is reported on its name.
+                let method = format_ident!("__expand_{method}_method", span = method.span());
                 let receiver = receiver
                     .as_const(context)
                     .unwrap_or_else(|| receiver.to_tokens(context));
@@ -645,8 +646,13 @@ impl MatchArm {
 }
 
 // Replace something like `some_path::Enum::Variant` with
-// `some_path::EnumExpand::Variant`.
+// `some_path::EnumExpand::Variant`. The variants of `Option` are kept, since the expand type of an
+// `Option` is an `Option` of expand types.
 fn append_expand_to_enum_name(path: &mut Path) {
+    if is_option_variant(path) {
+        return;
+    }
+
     if path.segments.len() >= 2 {
         let segment = path.segments.get_mut(path.segments.len() - 2).unwrap(); // Safe because of the if
         segment.ident = Ident::new(&format!("{}Expand", segment.ident), Span::call_site());
@@ -655,6 +661,17 @@ fn append_expand_to_enum_name(path: &mut Path) {
     }
 }
 
+fn is_option_variant(path: &Path) -> bool {
+    let mut segments = path.segments.iter().rev();
+    let variant = segments.next().map(|segment| segment.ident.to_string());
+    let is_variant = matches!(variant.as_deref(), Some("Some") | Some("None"));
+
+    is_variant
+        && segments
+            .next()
+            .is_none_or(|segment| segment.ident == "Option")
+}
+
 impl Block {
     pub fn to_tokens(&self, context: &mut Context) -> TokenStream {
         let inner: Vec<_> = self.inner.iter().map(|it| it.to_tokens(context)).collect();
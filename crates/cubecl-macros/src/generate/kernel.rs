@@ -7,7 +7,7 @@ use crate::{
     parse::kernel::{
         KernelBody, KernelFn, KernelParam, KernelReturns, KernelSignature, Launch, strip_ref,
     },
-    paths::{frontend_type, prelude_path, prelude_type},
+    paths::{frontend_path, frontend_type, prelude_path, prelude_type},
 };
 
 impl KernelFn {
@@ -229,13 +229,19 @@ impl Launch {
         let runtime_args = self.runtime_params().map(|it| &it.name);
         let comptime_args = self.comptime_params().map(|it| &it.name);
         let generics = self.analysis.process_generics(&self.func.sig.generics);
+        let frontend_path = frontend_path();
 
         quote! {
             let mut builder = #kernel_builder::default();
             builder.runtime_properties(__R::target_properties());
+            if self.settings.options.debug_symbols {
+                builder.debug_symbols();
+            }
             #register_type
             #io_map
-            expand #generics(&mut builder.scope, #(#runtime_args.clone(),)* #(self.#comptime_args.clone()),*);
+            #frontend_path::comptime_assert::in_kernel(&self.settings.options.kernel_name, || {
+                expand #generics(&mut builder.scope, #(#runtime_args.clone(),)* #(self.#comptime_args.clone()),*);
+            });
             builder.build(self.settings.clone())
         }
     }
@@ -361,6 +367,12 @@ impl Launch {
             if let Some(cluster_dim) = &self.args.cluster_dim {
                 settings.extend(quote![.cluster_dim(#cluster_dim)]);
             }
+            if let Some(carveout) = &self.args.shared_memory_carveout {
+                settings.extend(quote![.shared_memory_carveout(#carveout)]);
+            }
+            if let Some(bytes) = &self.args.dynamic_shared_memory {
+                settings.extend(quote![.dynamic_shared_memory(#bytes)]);
+            }
 
             quote! {
                 #[doc = #kernel_doc]
@@ -385,9 +397,17 @@ impl Launch {
 
                 impl #generics #kernel_metadata for #kernel_name #generic_names #where_clause {
                     fn id(&self) -> #kernel_id {
-                        // We don't use any other kernel settings with the macro.
+                        // We don't use any other kernel settings with the macro, the index
+                        // checks add a buffer to the kernel, and the shared memory hints are
+                        // applied when the kernel is loaded.
                         let cube_dim = self.settings.cube_dim.clone();
-                        #kernel_id::new::<Self>().info((cube_dim, #(self.#info.clone()),* ))
+                        let index_checks = self.settings.options.index_checks;
+                        let shared_memory = (
+                            self.settings.options.shared_memory_carveout,
+                            self.settings.options.dynamic_shared_memory,
+                        );
+                        #kernel_id::new::<Self>()
+                            .info((cube_dim, index_checks, shared_memory, #(self.#info.clone()),* ))
                     }
                 }
 
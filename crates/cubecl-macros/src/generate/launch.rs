@@ -14,6 +14,7 @@ impl ToTokens for Launch {
 
         let name = &self.func.sig.name;
         let launch = self.launch();
+        let launch_checked = self.launch_checked();
         let launch_unchecked = self.launch_unchecked();
         let dummy = self.create_dummy_kernel();
         let kernel = self.kernel_definition();
@@ -30,6 +31,7 @@ impl ToTokens for Launch {
 
                 #kernel
                 #launch
+                #launch_checked
                 #launch_unchecked
                 #dummy
             }
@@ -57,7 +59,7 @@ impl Launch {
             );
             let generics = &self.launch_generics;
             let args = self.launch_args();
-            let body = self.launch_body();
+            let body = self.launch_body(false);
 
             quote! {
                 #[allow(clippy::too_many_arguments)]
@@ -77,6 +79,40 @@ impl Launch {
         }
     }
 
+    fn launch_checked(&self) -> TokenStream {
+        if self.args.launch.is_present() {
+            let compute_client = prelude_type("ComputeClient");
+            let cube_count = prelude_type("CubeCount");
+            let cube_dim = prelude_type("CubeDim");
+            let launch_error = prelude_type("LaunchError");
+
+            let kernel_doc = format!(
+                "Launch the kernel [{}()] on the given runtime, after checking the tensor arguments \
+                 against the ones it's compiled for",
+                self.func.sig.name
+            );
+            let generics = &self.launch_generics;
+            let args = self.launch_args();
+            let body = self.launch_body(true);
+
+            quote! {
+                #[allow(clippy::too_many_arguments)]
+                #[doc = #kernel_doc]
+                pub fn launch_checked #generics(
+                    __client: &#compute_client<__R::Server, __R::Channel>,
+                    __cube_count: #cube_count,
+                    __cube_dim: #cube_dim,
+                    #(#args),*
+                ) -> ::core::result::Result<(), #launch_error> {
+                    #body
+                    launcher.launch_checked(__cube_count, __kernel, __client)
+                }
+            }
+        } else {
+            TokenStream::new()
+        }
+    }
+
     fn launch_unchecked(&self) -> TokenStream {
         if self.args.launch_unchecked.is_present() {
             let compute_client = prelude_type("ComputeClient");
@@ -89,7 +125,7 @@ impl Launch {
             );
             let generics = &self.launch_generics;
             let args = self.launch_args();
-            let body = self.launch_body();
+            let body = self.launch_body(false);
 
             quote! {
                 #[allow(clippy::too_many_arguments)]
@@ -109,13 +145,28 @@ impl Launch {
         }
     }
 
-    fn launch_body(&self) -> TokenStream {
+    fn launch_body(&self, checked: bool) -> TokenStream {
         let kernel_launcher = prelude_type("KernelLauncher");
+        let index_report = prelude_type("IndexReport");
 
         let registers = self.runtime_params().map(|arg| {
             let name = &arg.name;
-            quote![#name.register(&mut launcher);]
+            if checked {
+                let arg_name = name.to_string();
+                quote! {
+                    launcher.arg_name(#arg_name);
+                    #name.register(&mut launcher);
+                }
+            } else {
+                quote![#name.register(&mut launcher);]
+            }
         });
+        let launcher = match checked {
+            true => quote![#kernel_launcher::<__R>::checked()],
+            false => quote![#kernel_launcher::<__R>::default()],
+        };
+        let uniform_line_size = (checked && self.args.uniform_line_size.is_present())
+            .then(|| quote![launcher.uniform_line_size();]);
 
         let settings = self.configure_settings();
         let kernel_name = self.kernel_name();
@@ -129,13 +180,16 @@ impl Launch {
             use #core_path::frontend::ArgSettings as _;
 
             #settings
+            __settings = __settings.index_checks(#index_report::is_active());
             #compilation_args
 
             let __kernel = #kernel_name #kernel_generics::new(__settings, #args #(#comptime_args),*);
 
-            let mut launcher = #kernel_launcher::<__R>::default();
+            let mut launcher = #launcher;
+            #uniform_line_size
 
             #(#registers)*
+            launcher.register_index_report();
         }
     }
 
@@ -9,7 +9,7 @@ use parse::{
     cube_impl::CubeImpl,
     cube_trait::{CubeTrait, CubeTraitImpl},
     cube_type::CubeType,
-    helpers::{RemoveHelpers, ReplaceIndices},
+    helpers::{Intrinsic, RemoveHelpers, ReplaceBuiltins, ReplaceIndices},
     kernel::{Launch, from_tokens},
 };
 use proc_macro::TokenStream;
@@ -27,12 +27,22 @@ mod statement;
 
 /// Mark a cube function, trait or implementation for expansion.
 ///
+/// The item itself is kept as plain Rust, with the builtins like `UNIT_POS` reading their value
+/// from the `HostContext` being evaluated, so functions that don't launch can be called on the
+/// host.
+///
 /// # Arguments
 /// * `launch` - generates a function to launch the kernel
 /// * `launch_unchecked` - generates a launch function without checks
 /// * `debug` - panics after generation to print the output to console
 /// * `create_dummy_kernel` - Generates a function to create a kernel without launching it. Used for
 ///   testing.
+/// * `uniform_line_size` - the kernel indexes all its buffers at the same positions, so
+///   `launch_checked` rejects arguments with different line sizes.
+/// * `shared_memory_carveout` - the preferred percentage of the L1 cache carved out as shared
+///   memory, a `u8` or `Option<u8>` expression that can use the comptime arguments.
+/// * `dynamic_shared_memory` - the bytes of shared memory to launch the kernel with, a `u32` or
+///   `Option<u32>` expression that can use the comptime arguments.
 ///
 /// # Trait arguments
 /// * `expand_base_traits` - base traits for the expanded "second half" of a trait with methods.
@@ -66,6 +76,7 @@ fn cube_impl(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream>
             let kernel = Launch::from_item_fn(kernel, args)?;
             RemoveHelpers.visit_item_mut(&mut item);
             ReplaceIndices.visit_item_mut(&mut item);
+            ReplaceBuiltins.visit_item_mut(&mut item);
 
             return Ok(TokenStream::from(quote! {
                 #[allow(dead_code, clippy::too_many_arguments)]
@@ -172,9 +183,39 @@ pub fn comptime(input: TokenStream) -> TokenStream {
     quote![{ #tokens }].into()
 }
 
+/// Assert a condition on comptime values while the kernel is expanded, with an optional formatted
+/// message like [assert].
+///
+/// A failed assertion stops the compilation of the kernel with the message and the name of the
+/// kernel, and a condition depending on runtime values doesn't compile. On the host, it's a
+/// regular assertion.
+///
+/// # Example
+/// ```ignored
+/// #use cubecl_macros::cube;
+/// #[cube]
+/// fn lines_per_tile(#[comptime] tile_size: u32, #[comptime] line_size: u32) -> comptime_type!(u32) {
+///     comptime_assert!(
+///         tile_size % line_size == 0,
+///         "The line size {} should divide the tile size {}",
+///         line_size,
+///         tile_size
+///     );
+///     comptime!(tile_size / line_size)
+/// }
+/// ```
+#[proc_macro]
+pub fn comptime_assert(input: TokenStream) -> TokenStream {
+    let tokens: proc_macro2::TokenStream = input.into();
+    quote![{ assert!(#tokens) }].into()
+}
+
 /// Mark the contents of this macro as an intrinsic, turning off all expansion
 /// for this code and calling it with the scope
 ///
+/// The plain Rust version panics when called, unless an expression evaluating it on the host
+/// follows the closure with `host: <expr>`.
+///
 /// # Example
 /// ```ignored
 /// #use cubecl_macros::cube;
@@ -185,8 +226,16 @@ pub fn comptime(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 #[proc_macro]
-pub fn intrinsic(_input: TokenStream) -> TokenStream {
-    quote![{ cubecl::unexpanded!() }].into()
+pub fn intrinsic(input: TokenStream) -> TokenStream {
+    let intrinsic = match syn::parse::<Intrinsic>(input) {
+        Ok(intrinsic) => intrinsic,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    match intrinsic.host {
+        Some(host) => quote![{ #host }].into(),
+        None => quote![{ cubecl::unexpanded!() }].into(),
+    }
 }
 
 /// Makes the function return a compile time value
@@ -1,13 +1,16 @@
 use quote::quote;
-use syn::{Expr, ExprForLoop, ExprIf, ExprLoop, ExprMatch, Ident, Lit, Pat, spanned::Spanned};
+use syn::{
+    Expr, ExprForLoop, ExprIf, ExprLet, ExprLoop, ExprMatch, Ident, Lit, Pat, parse_quote,
+    spanned::Spanned,
+};
 
 use crate::{
-    expression::{Block, Expression},
+    expression::{Block, Expression, MatchArm},
     scope::Context,
     statement::Statement,
 };
 
-use super::{helpers::Unroll, statement::parse_pat};
+use super::{expression::add_variables_from_pat, helpers::Unroll, statement::parse_pat};
 
 pub fn expand_for_loop(for_loop: ExprForLoop, context: &mut Context) -> syn::Result<Expression> {
     let span = for_loop.span();
@@ -73,6 +76,14 @@ pub fn expand_loop(loop_expr: ExprLoop, context: &mut Context) -> syn::Result<Ex
 
 pub fn expand_if(if_expr: ExprIf, context: &mut Context) -> syn::Result<Expression> {
     let span = if_expr.span();
+    if let Expr::Let(let_expr) = if_expr.cond.as_ref() {
+        let let_expr = let_expr.clone();
+        let value = Expression::from_expr(*let_expr.expr.clone(), context)?;
+        if !value.is_const() {
+            return expand_if_let(let_expr, if_expr, context);
+        }
+    }
+
     let condition = Expression::from_expr(*if_expr.cond, context)
         .map_err(|_| syn::Error::new(span, "Unsupported while condition"))?;
 
@@ -90,6 +101,42 @@ pub fn expand_if(if_expr: ExprIf, context: &mut Context) -> syn::Result<Expressi
     })
 }
 
+/// An `if let` on a value whose variant is known at comptime, like an `Option` of runtime values,
+/// is a match where only the taken branch is expanded.
+fn expand_if_let(
+    let_expr: ExprLet,
+    if_expr: ExprIf,
+    context: &mut Context,
+) -> syn::Result<Expression> {
+    let (then_block, _) = context.in_scope(|ctx| {
+        add_variables_from_pat(&let_expr.pat, ctx);
+        Block::from_block(if_expr.then_branch, ctx)
+    })?;
+    let else_branch = match if_expr.else_branch {
+        Some((_, else_branch)) => {
+            context
+                .in_scope(|ctx| Expression::from_expr(*else_branch, ctx))?
+                .0
+        }
+        None => Expression::Block(Block::default()),
+    };
+
+    Ok(Expression::Match {
+        runtime_variants: true,
+        expr: *let_expr.expr,
+        arms: vec![
+            MatchArm {
+                pat: *let_expr.pat,
+                expr: Box::new(Expression::Block(then_block)),
+            },
+            MatchArm {
+                pat: parse_quote![_],
+                expr: Box::new(else_branch),
+            },
+        ],
+    })
+}
+
 pub fn numeric_match(mat: ExprMatch, context: &mut Context) -> Option<Expression> {
     fn parse_pat(pat: Pat) -> Option<Vec<Lit>> {
         match pat {
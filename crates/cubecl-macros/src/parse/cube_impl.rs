@@ -10,7 +10,7 @@ use crate::{
 };
 
 use super::{
-    helpers::{RemoveHelpers, ReplaceIndices},
+    helpers::{RemoveHelpers, ReplaceBuiltins, ReplaceIndices},
     kernel::KernelFn,
 };
 
@@ -221,6 +221,7 @@ impl CubeImpl {
 
         RemoveHelpers.visit_item_impl_mut(&mut item_impl);
         ReplaceIndices.visit_item_impl_mut(&mut item_impl);
+        ReplaceBuiltins.visit_item_impl_mut(&mut item_impl);
 
         let mut attrs = item_impl.attrs;
         attrs.retain(|attr| !attr.path().is_ident("cube"));
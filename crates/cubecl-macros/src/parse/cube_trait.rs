@@ -10,7 +10,7 @@ use crate::parse::kernel::{KernelArgs, KernelParam};
 
 use super::{
     StripBounds, StripDefault,
-    helpers::{RemoveHelpers, ReplaceIndices},
+    helpers::{RemoveHelpers, ReplaceBuiltins, ReplaceIndices},
     kernel::{KernelFn, KernelSignature},
 };
 
@@ -175,6 +175,7 @@ impl CubeTrait {
     pub fn from_item_trait(item: ItemTrait, args: KernelArgs) -> syn::Result<Self> {
         let mut original_trait = item.clone();
         RemoveHelpers.visit_item_trait_mut(&mut original_trait);
+        ReplaceBuiltins.visit_item_trait_mut(&mut original_trait);
 
         let mut attrs = item.attrs;
         attrs.retain(|attr| !attr.path().is_ident("cube"));
@@ -232,6 +233,7 @@ impl CubeTraitImpl {
 
         RemoveHelpers.visit_item_impl_mut(&mut item_impl);
         ReplaceIndices.visit_item_impl_mut(&mut item_impl);
+        ReplaceBuiltins.visit_item_impl_mut(&mut item_impl);
 
         let struct_name = *item_impl.self_ty;
         let trait_name = item_impl.trait_.unwrap().1;
@@ -32,13 +32,13 @@ fn desugar_pats(stmts: Vec<Stmt>) -> Vec<Stmt> {
                 pat: Pat::Struct(pat),
                 init: Some(init),
                 ..
-            }) => desugar_struct_destructure(pat, init),
+            }) if init.diverge.is_none() => desugar_struct_destructure(pat, init),
             Stmt::Local(Local {
                 pat:
                     Pat::Tuple(PatTuple { elems, .. }) | Pat::TupleStruct(PatTupleStruct { elems, .. }),
                 init: Some(init),
                 ..
-            }) => desugar_tuple_destructure(elems, init),
+            }) if init.diverge.is_none() => desugar_tuple_destructure(elems, init),
             stmt => vec![stmt],
         }
     }).collect()
@@ -258,7 +258,7 @@ impl Expression {
                 if !len.is_const() {
                     Err(syn::Error::new(
                         span,
-                        "Array initializer length must be known at compile time",
+                        "Array initializer length must be known at compile time. Consider marking the parameters it depends on with `#[comptime]`",
                     ))?
                 }
                 Expression::ArrayInit {
@@ -374,14 +374,14 @@ impl Expression {
             ))?,
             e => Err(syn::Error::new_spanned(
                 expr,
-                format!("Unsupported expression {e:?}"),
+                format!("{} aren't supported in cube functions", expr_construct(&e)),
             ))?,
         };
         Ok(result)
     }
 }
 
-fn add_variables_from_pat(pat: &Pat, context: &mut Context) {
+pub fn add_variables_from_pat(pat: &Pat, context: &mut Context) {
     match pat {
         Pat::Ident(pat) => {
             context.push_variable(
@@ -421,12 +421,33 @@ fn lit_ty(lit: &Lit) -> syn::Result<Type> {
         Lit::Bool(_) => parse_quote![bool],
         lit => Err(syn::Error::new_spanned(
             lit,
-            format!("Unsupported literal type: {lit:?}"),
+            format!("{} aren't supported in cube functions", lit_construct(lit)),
         ))?,
     };
     Ok(res)
 }
 
+/// The name of an unsupported expression, for error messages.
+fn expr_construct(expr: &Expr) -> &'static str {
+    match expr {
+        Expr::Async(_) => "Async blocks",
+        Expr::Await(_) => "Await expressions",
+        Expr::Yield(_) => "Yield expressions",
+        _ => "These expressions",
+    }
+}
+
+/// The name of an unsupported literal, for error messages.
+fn lit_construct(lit: &Lit) -> &'static str {
+    match lit {
+        Lit::Byte(_) => "Byte literals",
+        Lit::ByteStr(_) => "Byte string literals",
+        Lit::CStr(_) => "C string literals",
+        Lit::Char(_) => "Char literals",
+        _ => "These literals",
+    }
+}
+
 fn generate_strided_index(
     tensor: &Expression,
     elements: Vec<Expression>,
@@ -1,10 +1,16 @@
 use darling::FromMeta;
 use syn::{
-    Attribute, Expr, ExprReference, parse_quote,
+    Attribute, Expr, ExprClosure, ExprReference, Token,
+    parse::{Parse, ParseStream},
+    parse_quote,
     visit_mut::{self, VisitMut},
 };
 
-use crate::{expression::Expression, paths::prelude_path, scope::Context};
+use crate::{
+    expression::Expression,
+    paths::{frontend_path, prelude_path},
+    scope::{Context, KEYWORDS},
+};
 
 pub struct Unroll {
     pub value: Expression,
@@ -41,6 +47,12 @@ impl Unroll {
                 Self { value: expr }
             }
         };
+        if !res.value.is_const() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "The unroll value must be known at compile time. Consider marking the parameters it depends on with `#[comptime]`",
+            ));
+        }
         Ok(Some(res))
     }
 
@@ -60,6 +72,36 @@ impl Unroll {
     }
 }
 
+/// The arguments of `intrinsic!`: the closure expanding it, and optionally the expression
+/// evaluating it on the host, as in `intrinsic!(|scope| ..., host: ...)`.
+pub struct Intrinsic {
+    pub closure: ExprClosure,
+    pub host: Option<Expr>,
+}
+
+impl Parse for Intrinsic {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let closure = input.parse()?;
+        let host = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: syn::Ident = input.parse()?;
+            if name != "host" {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Expected `host: <expr>` after the closure of `intrinsic!`",
+                ));
+            }
+            input.parse::<Token![:]>()?;
+            let host = input.parse()?;
+            input.parse::<Option<Token![,]>>()?;
+            Some(host)
+        } else {
+            None
+        };
+
+        Ok(Self { closure, host })
+    }
+}
+
 pub struct RemoveHelpers;
 
 impl VisitMut for RemoveHelpers {
@@ -83,6 +125,25 @@ impl VisitMut for RemoveHelpers {
     }
 }
 
+/// Replaces the builtins like `UNIT_POS` with their value on the host, so the plain Rust
+/// version of a cube function can be evaluated with a `HostContext`.
+pub struct ReplaceBuiltins;
+
+impl VisitMut for ReplaceBuiltins {
+    fn visit_expr_mut(&mut self, i: &mut Expr) {
+        if let Expr::Path(path) = i
+            && path.qself.is_none()
+            && let Some(ident) = path.path.get_ident()
+            && KEYWORDS.contains(&ident.to_string().as_str())
+        {
+            let frontend_path = frontend_path();
+            *i = parse_quote![#frontend_path::#ident::host()];
+            return;
+        }
+        visit_mut::visit_expr_mut(self, i);
+    }
+}
+
 pub struct ReplaceIndices;
 pub struct ReplaceIndex;
 pub struct ReplaceIndexMut;
@@ -24,7 +24,10 @@ pub(crate) struct KernelArgs {
     pub fast_math: Option<Expr>,
     pub debug: Flag,
     pub create_dummy_kernel: Flag,
+    pub uniform_line_size: Flag,
     pub cluster_dim: Option<Expr>,
+    pub shared_memory_carveout: Option<Expr>,
+    pub dynamic_shared_memory: Option<Expr>,
     pub src_file: Option<LitStr>,
     /// Base traits for a split expand trait
     pub expand_base_traits: Option<String>,
@@ -1,8 +1,13 @@
-use quote::{format_ident, quote};
-use syn::{ExprArray, LitStr, Macro, Pat, Stmt, Type, TypeReference, parse_quote};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Expr, ExprArray, LitStr, Macro, Pat, Stmt, Token, Type, TypeReference, parse_quote,
+    punctuated::Punctuated,
+};
 
 use crate::{
     expression::Expression,
+    parse::helpers::Intrinsic,
+    paths::frontend_path,
     scope::Context,
     statement::{Pattern, Statement},
 };
@@ -11,6 +16,16 @@ impl Statement {
     pub fn from_stmt(stmt: Stmt, context: &mut Context) -> syn::Result<Self> {
         let statement = match stmt {
             Stmt::Local(local) => {
+                if local
+                    .init
+                    .as_ref()
+                    .is_some_and(|init| init.diverge.is_some())
+                {
+                    return Err(syn::Error::new_spanned(
+                        local,
+                        "Let-else statements aren't supported in cube functions. Consider using `if let` or `match` instead",
+                    ));
+                }
                 let init = local
                     .init
                     .map(|init| Expression::from_expr(*init.expr, context))
@@ -81,13 +96,33 @@ pub fn parse_pat(pat: Pat) -> syn::Result<Pattern> {
             is_mut: false,
         },
         pat => Err(syn::Error::new_spanned(
-            pat.clone(),
-            format!("Unsupported local pat: {pat:?}"),
+            &pat,
+            format!("{} aren't supported in cube functions", pat_construct(&pat)),
         ))?,
     };
     Ok(res)
 }
 
+/// The name of an unsupported pattern, for error messages.
+fn pat_construct(pat: &Pat) -> &'static str {
+    match pat {
+        Pat::Const(_) => "Const block patterns",
+        Pat::Lit(_) => "Literal patterns",
+        Pat::Macro(_) => "Macro patterns",
+        Pat::Or(_) => "Or patterns",
+        Pat::Paren(_) => "Parenthesized patterns",
+        Pat::Path(_) => "Path patterns",
+        Pat::Range(_) => "Range patterns",
+        Pat::Reference(_) => "Reference patterns",
+        Pat::Rest(_) => "Rest patterns",
+        Pat::Slice(_) => "Slice patterns",
+        Pat::Struct(_) => "Struct patterns",
+        Pat::Tuple(_) => "Tuple patterns",
+        Pat::TupleStruct(_) => "Tuple struct patterns",
+        _ => "These patterns",
+    }
+}
+
 pub fn parse_macros(mac: Macro, context: &mut Context) -> syn::Result<Expression> {
     if mac.path.is_ident("comptime") {
         let tokens = &mac.tokens;
@@ -121,13 +156,17 @@ pub fn parse_macros(mac: Macro, context: &mut Context) -> syn::Result<Expression
             ident: mac.path.get_ident().cloned().unwrap(),
             args,
         })
+    } else if mac.path.is_ident("debug_record") {
+        parse_debug_record(mac, context)
+    } else if mac.path.is_ident("comptime_assert") {
+        parse_comptime_assert(mac, context)
     } else if mac.path.is_ident("comment") {
         let content = syn::parse2::<LitStr>(mac.tokens)?;
         Ok(Expression::Comment { content })
     } else if mac.path.is_ident("terminate") {
         Ok(Expression::Terminate)
     } else if mac.path.is_ident("intrinsic") {
-        let closure: syn::ExprClosure = mac.parse_body()?;
+        let closure = mac.parse_body::<Intrinsic>()?.closure;
         let arg = &closure.inputs[0];
         let block = *closure.body;
         let tokens = quote! {{
@@ -137,9 +176,62 @@ pub fn parse_macros(mac: Macro, context: &mut Context) -> syn::Result<Expression
 
         Ok(Expression::Verbatim { tokens })
     } else {
+        let name = mac.path.segments.last().unwrap().ident.to_string();
         Err(syn::Error::new_spanned(
             mac,
-            "Unsupported macro".to_string().as_str(),
+            format!(
+                "Unsupported macro `{name}!`. Cube functions only support `comptime!`, `comptime_assert!`, `comment!`, `debug_print!`, `debug_record!`, `terminate!`, `intrinsic!` and the panicking macros"
+            ),
         ))
     }
 }
+
+/// `debug_record!(sink, tag, value)`, recorded with the method of the sink.
+fn parse_debug_record(mac: Macro, context: &mut Context) -> syn::Result<Expression> {
+    let args = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    let [sink, tag, value] = args.iter().collect::<Vec<_>>()[..] else {
+        return Err(syn::Error::new_spanned(
+            mac,
+            "`debug_record!` takes a debug sink, a tag and a value",
+        ));
+    };
+
+    Expression::from_expr(parse_quote![#sink.record(#tag, #value)], context)
+}
+
+/// `comptime_assert!(condition, "message {}", args...)`, with the condition and the arguments of
+/// the message evaluated as plain Rust during expansion.
+fn parse_comptime_assert(mac: Macro, context: &mut Context) -> syn::Result<Expression> {
+    let args = mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
+    let Some(condition) = args.first() else {
+        return Err(syn::Error::new_spanned(
+            mac,
+            "`comptime_assert!` needs a condition",
+        ));
+    };
+
+    for arg in args.iter() {
+        if !Expression::from_expr(arg.clone(), context)?.is_comptime() {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "`comptime_assert!` is checked during expansion, so its condition and message must be known at compile time. Consider marking the parameters they depend on with `#[comptime]`",
+            ));
+        }
+    }
+
+    let frontend_path = frontend_path();
+    let message = match args.len() {
+        1 => {
+            let condition = condition.to_token_stream().to_string();
+            quote![#condition.to_string()]
+        }
+        _ => {
+            let message = args.iter().skip(1);
+            quote![format!(#(#message),*)]
+        }
+    };
+
+    Ok(Expression::Verbatim {
+        tokens: quote![#frontend_path::comptime_assert::expand(scope, #condition, || #message)],
+    })
+}
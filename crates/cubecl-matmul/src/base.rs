@@ -4,11 +4,11 @@ use cubecl_core::{
     prelude::{Numeric, TensorHandleRef},
 };
 
-use cubecl_std::tensor::TensorHandle;
+use cubecl_std::tensor::{TensorHandle, cast, cast_ref};
 
 use crate::{
     components::{
-        AccG, LhsG, MatmulSetupError, RhsG,
+        AccG, AccR, LhsG, MatmulSetupError, RhsG,
         tile::{accelerated::AcceleratedMatmul, loader::Filled},
     },
     kernels::layered::{
@@ -32,6 +32,7 @@ use super::{
         stage::{ColMajorTilingOrder, RowMajorTilingOrder},
     },
     kernels::{
+        batched_gemv, batched_tiny, degenerate, heuristic,
         layered::{
             self,
             double_buffering::{
@@ -63,8 +64,13 @@ pub enum Strategy {
     DoubleVecMat(Selection<()>),
     OrderedDoubleBuffering(Selection<OrderedSelectionArgs>),
     Naive,
+    /// Batches of tiny matrices, each computed by a plane without a shared memory stage
+    BatchedTiny,
+    /// Batched matrix-vector products, with the vector broadcast over the batches of the matrix
+    BatchedGemv,
     #[default]
-    /// Tries using a Simple matmul, then a SimpleUnit if the former failed
+    /// Launches the strategy [selected](crate::kernels::heuristic::select_strategy) from the
+    /// problem and the device, then a SimpleUnit if the former is unavailable
     Auto,
 }
 
@@ -191,6 +197,70 @@ pub fn launch<R: Runtime, MP: MatmulPrecision>(
     )
 }
 
+/// Launch the matmul of operands and an output whose elements may differ from the global elements
+/// of `MP`, casting them around the matmul.
+///
+/// Operands of other elements are cast into contiguous temporaries, and an output of another
+/// element is computed in a temporary then cast into `out`. Quantized operands are launched as is.
+///
+/// # Panics
+///
+/// When the output is cast and isn't contiguous.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref_with_casts<
+    R: Runtime,
+    MP: MatmulPrecision,
+    EL: Numeric,
+    ER: Numeric,
+    EO: Numeric,
+>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &MatmulInputHandleRef<R>,
+    rhs: &MatmulInputHandleRef<R>,
+    out: &TensorHandleRef<R>,
+) -> Result<(), MatmulSetupError> {
+    let lhs_cast = cast_operand::<R, EL, LhsG<MP>>(client, lhs);
+    let rhs_cast = cast_operand::<R, ER, RhsG<MP>>(client, rhs);
+    let lhs_ref = lhs_cast
+        .as_ref()
+        .map(|tensor| MatmulInputHandleRef::new(tensor.as_ref()));
+    let rhs_ref = rhs_cast
+        .as_ref()
+        .map(|tensor| MatmulInputHandleRef::new(tensor.as_ref()));
+    let lhs = lhs_ref.as_ref().unwrap_or(lhs);
+    let rhs = rhs_ref.as_ref().unwrap_or(rhs);
+
+    if EO::as_type_native_unchecked() == AccG::<MP>::as_type_native_unchecked() {
+        return launch_ref::<R, MP>(strategy, client, lhs, rhs, out);
+    }
+
+    let num_elems = out.shape.iter().product::<usize>();
+    let acc = TensorHandle::<R, AccG<MP>>::new_contiguous(
+        out.shape.to_vec(),
+        client.empty(num_elems * size_of::<AccG<MP>>()),
+    );
+    launch_ref::<R, MP>(strategy, client, lhs, rhs, &acc.as_ref())?;
+    cast_ref::<R, AccG<MP>, EO>(client, &acc.as_ref(), out);
+
+    Ok(())
+}
+
+/// The operand cast to `EG`, unless it is already of `EG` or quantized.
+fn cast_operand<R: Runtime, E: Numeric, EG: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &MatmulInputHandleRef<R>,
+) -> Option<TensorHandle<R, EG>> {
+    match input {
+        MatmulInputHandleRef::Normal(data)
+            if E::as_type_native_unchecked() != EG::as_type_native_unchecked() =>
+        {
+            Some(cast::<R, E, EG>(client, data))
+        }
+        _ => None,
+    }
+}
+
 #[allow(clippy::result_large_err)]
 pub fn launch_ref<R: Runtime, MP: MatmulPrecision>(
     strategy: &Strategy,
@@ -201,6 +271,11 @@ pub fn launch_ref<R: Runtime, MP: MatmulPrecision>(
 ) -> Result<(), MatmulSetupError> {
     type Accelerated = AcceleratedMatmul<Filled>;
 
+    // Empty outputs and reductions are computed the same way whatever the strategy.
+    if degenerate::launch_ref::<R, AccG<MP>>(client, lhs.data(), rhs.data(), out) {
+        return Ok(());
+    }
+
     match strategy {
         Strategy::Simple(loading_strategy, selection) => match loading_strategy {
             SyncLoadingStrategy::Cyclic => {
@@ -313,27 +388,30 @@ pub fn launch_ref<R: Runtime, MP: MatmulPrecision>(
             naive::launch_ref::<R, LhsG<MP>, AccG<MP>>(client, lhs.data(), rhs.data(), out)?;
             Ok(())
         }
-        Strategy::Auto => {
-            if let Err(err) = layered::launch_ref::<R, MP, SimpleAlgorithm<Accelerated>>(
+        Strategy::BatchedTiny => {
+            batched_tiny::launch_ref::<R, LhsG<MP>, AccG<MP>>(client, lhs.data(), rhs.data(), out)
+        }
+        Strategy::BatchedGemv => {
+            batched_gemv::launch_matmul_ref::<R, LhsG<MP>, AccR<MP>, AccG<MP>>(
                 client,
-                lhs,
-                rhs,
+                lhs.data(),
+                rhs.data(),
                 out,
-                &Default::default(),
-            ) {
-                match err {
-                    MatmulSetupError::Unavailable(_) => {
-                        layered::launch_ref::<R, MP, SimpleUnitAlgorithm>(
-                            client,
-                            lhs,
-                            rhs,
-                            out,
-                            &Default::default(),
-                        )
-                        .unwrap();
-                    }
-                    _ => panic!("{err:?}"),
-                }
+            )
+        }
+        Strategy::Auto => {
+            let selection = heuristic::select_strategy::<R, MP>(client, lhs, rhs, out);
+
+            // The heuristic only looks at the problem, so the selected strategy may still not
+            // support it
+            if launch_ref::<R, MP>(&selection.strategy, client, lhs, rhs, out).is_err() {
+                layered::launch_ref::<R, MP, SimpleUnitAlgorithm>(
+                    client,
+                    lhs,
+                    rhs,
+                    out,
+                    &Default::default(),
+                )?;
             }
 
             Ok(())
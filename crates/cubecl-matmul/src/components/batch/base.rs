@@ -1,5 +1,5 @@
 use crate::components::{
-    AccG, AvailableLineSizes, InputRuntimeArg, LhsG, MatmulLineSizes, MatmulPrecision,
+    AccG, AvailableLineSizes, InputRuntimeArg, LhsG, MatmulIdent, MatmulLineSizes, MatmulPrecision,
     MatmulProblem, MatmulSelection, MatmulSpec, OutputRuntimeArg, RhsG, TilingScheme,
     batch::{CubeCountInput, CubeCountInputArgs, HypercubeConfig},
     error::MatmulSetupError,
@@ -109,4 +109,23 @@ pub trait BatchConfig:
 
     /// Whether it may launch more cubes than the minimum required
     fn can_yield_extra_cubes(&self) -> bool;
+
+    /// The preferred percentage of the L1 cache carved out as shared memory, the maximum when the
+    /// stages of `LhsS` and `RhsS` are large enough for the default carveout to limit the number
+    /// of cubes per SM
+    fn shared_memory_carveout<LhsS: Numeric, RhsS: Numeric>(&self) -> Option<u8> {
+        let config = self.global_config();
+        let tiling_scheme = config.tiling_scheme();
+        let lhs_size = tiling_scheme.elements_in_stage_mk()
+            * config.num_stages(MatmulIdent::Lhs)
+            * LhsS::elem_size();
+        let rhs_size = tiling_scheme.elements_in_stage_nk()
+            * config.num_stages(MatmulIdent::Rhs)
+            * RhsS::elem_size();
+
+        (lhs_size + rhs_size >= LARGE_STAGE_SIZE).then_some(100)
+    }
 }
+
+/// The bytes of shared memory of the stages above which the matmul prefers the maximum carveout.
+const LARGE_STAGE_SIZE: u32 = 32 * 1024;
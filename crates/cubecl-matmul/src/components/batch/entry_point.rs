@@ -1,18 +1,25 @@
 use crate::components::batch::base::BatchMatmul;
 use crate::components::global::args::{TensorLhs, TensorRhs};
-use crate::components::{batch::CubeCountInput, global::args::TensorAcc};
 use crate::components::{
+    MatmulIdent,
     batch::{BatchConfig, BatchMatmulFamily},
-    global::args::{MatmulArgs, TensorOutput},
+    global::{
+        GlobalConfig,
+        args::{MatmulArgs, TensorOutput},
+    },
 };
-use cubecl_core as cubecl;
+use crate::components::{batch::CubeCountInput, global::args::TensorAcc};
 use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, logical_cube_pos};
 use cubecl_std::{CubeOption, CubeOptionExpand, tensor::r#virtual::VirtualTensor};
 
 type Input<Args, Lhs, Rhs, AccG> = <Args as MatmulArgs>::Input<Lhs, Rhs, AccG>;
 type Output<Args, AccG> = <Args as MatmulArgs>::Output<AccG>;
 
-#[cube(launch_unchecked)]
+#[cube(
+    launch_unchecked,
+    shared_memory_carveout = config.shared_memory_carveout::<LhsS, RhsS>()
+)]
 /// Launches the matmul kernel
 pub(crate) fn matmul<
     Args: MatmulArgs,
@@ -29,14 +36,21 @@ pub(crate) fn matmul<
     cube_count_args: CubeCountInput,
     #[comptime] config: BMMF::Config,
 ) {
-    #[allow(clippy::collapsible_if)]
     if comptime!(config.can_yield_extra_cubes()) {
-        if CUBE_POS >= cube_count_args.num_valid_cubes() {
+        // The cubes launched past the last one when the grid is folded have nothing to compute.
+        let num_cubes = cube_count_args.num_valid_cubes();
+        if logical_cube_pos(num_cubes) >= num_cubes {
             terminate!()
         }
     }
 
     let mut state = Args::init_state(inputs, output);
+    let global_config = config.global_config();
+    Args::cache_hints(
+        &state,
+        global_config.cache_hint(MatmulIdent::Lhs),
+        global_config.cache_hint(MatmulIdent::Rhs),
+    );
 
     let lhs = TensorLhs::<LhsG, RhsG, AccG, Args>::new(&state);
     let rhs = TensorRhs::<LhsG, RhsG, AccG, Args>::new(&state);
@@ -1,5 +1,5 @@
-use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, logical_cube_pos};
 
 use crate::components::MatmulProblem;
 use crate::components::batch::partitioned_matmul::hypercube::global_order::{GlobalOrder, swizzle};
@@ -304,6 +304,9 @@ impl CubeCountPlanConfig {
 
 /// Heuristic algorithm to factor the total number of cubes into (x, y, z) dimensions
 /// such that no dimension surpasses its maximum.
+///
+/// The cubes are folded linearly along x, then y, then z, so the kernel recovers its cube with
+/// [logical_cube_pos].
 pub(crate) fn spread_cube_count_plan(
     m_cubes: u32,
     n_cubes: u32,
@@ -472,8 +475,15 @@ impl CubeCountInput {
                 self.absolute_index_to_m_n_batch(CUBE_POS_X, *m_cubes, *n_cubes, global_order)
             }
             CubeCountInput::Spread {
-                m_cubes, n_cubes, ..
-            } => self.absolute_index_to_m_n_batch(CUBE_POS, *m_cubes, *n_cubes, global_order),
+                m_cubes,
+                n_cubes,
+                batch_cubes,
+            } => self.absolute_index_to_m_n_batch(
+                logical_cube_pos(*m_cubes * *n_cubes * *batch_cubes),
+                *m_cubes,
+                *n_cubes,
+                global_order,
+            ),
         }
     }
 
@@ -1,4 +1,4 @@
-use cubecl_core::{CubeCount, CubeDim, LineSizeError, ir::StorageType};
+use cubecl_core::{CubeCount, CubeDim, LaunchShapeError, LineSizeError, ir::StorageType};
 use std::fmt::{Debug, Display};
 
 use crate::components::TileSize;
@@ -71,6 +71,12 @@ impl From<LineSizeError> for MatmulSetupError {
     }
 }
 
+impl From<LaunchShapeError> for MatmulSetupError {
+    fn from(value: LaunchShapeError) -> Self {
+        Self::InvalidConfig(Box::new(value))
+    }
+}
+
 impl Display for MatmulSetupError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -1,7 +1,7 @@
 use std::any::TypeId;
 
 use cubecl::prelude::*;
-use cubecl_core::{self as cubecl, intrinsic, server::TensorMapMeta};
+use cubecl_core::{self as cubecl, intrinsic, ir::CacheHint, server::TensorMapMeta};
 use cubecl_std::{
     CubeOption, CubeOptionArgs, CubeOptionExpand,
     tensor::r#virtual::{VirtualTensorOperations, VirtualTensorOperationsExpand},
@@ -58,6 +58,14 @@ pub trait MatmulArgs: Send + Sync + 'static + Clone {
         state: &Self::State<Lhs, Rhs, EO>,
     ) -> CubeOption<()>;
 
+    /// Hint how the following reads of the lhs and the rhs should be cached, when they are read
+    /// from global memory.
+    fn cache_hints<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        #[comptime] lhs: CacheHint,
+        #[comptime] rhs: CacheHint,
+    );
+
     /// Read the line of the lhs tensor using the state at the given coordinate.
     fn read_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
         state: &Self::State<Lhs, Rhs, EO>,
@@ -900,6 +908,17 @@ impl MatmulArgs for TensorArgs {
         }
     }
 
+    fn cache_hints<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        #[comptime] lhs: CacheHint,
+        #[comptime] rhs: CacheHint,
+    ) {
+        unsafe {
+            (*state.0).cache_hint(lhs);
+            (*state.1).cache_hint(rhs);
+        }
+    }
+
     fn read_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
         state: &Self::State<Lhs, Rhs, EO>,
         coordinate: u32,
@@ -1311,6 +1330,14 @@ impl MatmulArgs for TensorMapArgs {
         }
     }
 
+    fn cache_hints<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        _state: &Self::State<Lhs, Rhs, EO>,
+        #[comptime] _lhs: CacheHint,
+        #[comptime] _rhs: CacheHint,
+    ) {
+        // The tensor maps are loaded by TMA, not by reads.
+    }
+
     fn read_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
         _state: &Self::State<Lhs, Rhs, EO>,
         _coordinate: u32,
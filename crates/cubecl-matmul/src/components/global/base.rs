@@ -1,12 +1,12 @@
 use cubecl_core::prelude::*;
-use cubecl_core::{self as cubecl};
+use cubecl_core::{self as cubecl, ir::CacheHint};
 
 use crate::components::global::RoleRuleConfig;
 use crate::components::global::memory::GlobalMemoryConfig;
 use crate::components::stage::StageMemoryConfig;
 use crate::components::{AccG, error::MatmulSetupError};
 use crate::components::{
-    AvailableLineSizes, MatmulPrecision, MatmulProblem, MatrixLayout, TilingScheme,
+    AvailableLineSizes, MatmulPrecision, MatmulProblem, MatrixLayout, MemoryFormat, TilingScheme,
     global::{PlaneRoleConfig, SpecializedLoadingSides, multi_stage::EventLoadingMode},
     stage::StageConfig,
 };
@@ -160,6 +160,7 @@ pub trait GlobalConfig:
             check_row_bounds: self.check_row_bounds(ident),
             check_col_bounds: self.check_col_bounds(ident),
             matrix_layout: self.matrix_layout(ident),
+            memory_format: self.memory_format(ident),
         }
     }
 
@@ -174,6 +175,18 @@ pub trait GlobalConfig:
     /// Returns the [MatrixLayout] for the given ident
     fn matrix_layout(&self, ident: MatmulIdent) -> MatrixLayout;
 
+    /// Returns the [MemoryFormat] of the tensor of the given ident, strided unless the global
+    /// matmul supports other formats
+    fn memory_format(&self, _ident: MatmulIdent) -> MemoryFormat {
+        MemoryFormat::Strided
+    }
+
+    /// Returns the [CacheHint] of the reads of the tensor of the given ident, the default unless
+    /// the global matmul reads it with its loaders
+    fn cache_hint(&self, _ident: MatmulIdent) -> CacheHint {
+        CacheHint::Default
+    }
+
     /// Returns the number of planes participating in loading `ident`
     fn num_loading_planes(&self, ident: MatmulIdent) -> u32;
 
@@ -0,0 +1,335 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, intrinsic, ir::CacheHint};
+use cubecl_std::{CubeOption, CubeOptionExpand};
+
+use crate::components::global::args::MatmulArgs;
+
+#[derive(Clone)]
+/// Type implementing [MatmulArgs] for the epilogue `out = GELU(lhs · rhs + bias) + residual` of
+/// the feed-forward blocks of transformers, fused into the write of the output.
+///
+/// The `bias` is a vector of a value per column of the output, added to each of its rows. The
+/// `residual`, if any, has the shape and the strides of the output, and may be the output itself
+/// to add the matmul to it in place. The epilogue is computed in `f32` on the lines written to the
+/// output, once the accumulator is cast to its element, and the result is cast back. GELU is the
+/// exact `x · (1 + erf(x / √2)) / 2`.
+///
+/// Since only the lines within the output are written, the residual is read with the same bound
+/// checks as the output.
+pub struct BiasGeluResidualArgs;
+
+#[derive(CubeLaunch, CubeType)]
+/// Input representation for [BiasGeluResidualArgs] implementing [MatmulArgs].
+pub struct BiasGeluResidualInputs<Lhs: Numeric, Rhs: Numeric, EO: Numeric> {
+    /// The lhs tensor.
+    pub lhs: Tensor<Line<Lhs>>,
+    /// The rhs tensor.
+    pub rhs: Tensor<Line<Rhs>>,
+    /// The bias added to each row, with the line size of the output.
+    pub bias: Tensor<Line<EO>>,
+    /// The tensor added after the activation, if present.
+    pub residual: CubeOption<Tensor<Line<EO>>>,
+}
+
+#[cube]
+impl MatmulArgs for BiasGeluResidualArgs {
+    type Output<EO: Numeric> = Tensor<Line<EO>>;
+    type Input<Lhs: Numeric, Rhs: Numeric, EO: Numeric> = BiasGeluResidualInputs<Lhs, Rhs, EO>;
+    type State<Lhs: Numeric, Rhs: Numeric, EO: Numeric> = (
+        *const Tensor<Line<Lhs>>,
+        *const Tensor<Line<Rhs>>,
+        CubeOption<*const Tensor<Line<EO>>>,
+        *mut Tensor<Line<EO>>,
+        *const Tensor<Line<EO>>,
+        CubeOption<*const Tensor<Line<EO>>>,
+    );
+
+    fn init_state<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        input: &Self::Input<Lhs, Rhs, EO>,
+        output: &mut Self::Output<EO>,
+    ) -> Self::State<Lhs, Rhs, EO> {
+        // The matmul always starts from zero, the residual being added after the activation.
+        let residual = match &input.residual {
+            CubeOption::None => CubeOption::new_None(),
+            CubeOption::Some(residual) => {
+                let ptr: *const Tensor<Line<EO>> = residual;
+                CubeOption::new_Some(ptr)
+            }
+        };
+        (
+            &input.lhs,
+            &input.rhs,
+            CubeOption::new_None(),
+            output,
+            &input.bias,
+            residual,
+        )
+    }
+
+    fn has_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> CubeOption<()> {
+        match state.2 {
+            CubeOption::None => CubeOption::new_None(),
+            CubeOption::Some(_) => CubeOption::new_Some(()),
+        }
+    }
+
+    fn cache_hints<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        #[comptime] lhs: CacheHint,
+        #[comptime] rhs: CacheHint,
+    ) {
+        unsafe {
+            (*state.0).cache_hint(lhs);
+            (*state.1).cache_hint(rhs);
+        }
+    }
+
+    fn read_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        coordinate: u32,
+    ) -> Line<Lhs> {
+        unsafe { (*state.0)[coordinate] }
+    }
+
+    fn read_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        coordinate: u32,
+    ) -> Line<Rhs> {
+        unsafe { (*state.1)[coordinate] }
+    }
+
+    fn read_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        coordinate: u32,
+    ) -> Line<EO> {
+        unsafe { (*state.2.unwrap())[coordinate] }
+    }
+
+    fn read_window_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<Lhs>> {
+        unsafe { (*state.0).slice(start, end) }
+    }
+
+    fn read_window_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<Rhs>> {
+        unsafe { (*state.1).slice(start, end) }
+    }
+
+    fn read_window_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        start: u32,
+        end: u32,
+    ) -> Slice<Line<EO>> {
+        unsafe { (*state.2.unwrap()).slice(start, end) }
+    }
+
+    fn as_tensor_map_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        _state: &Self::State<Lhs, Rhs, EO>,
+    ) -> TensorMap<Lhs> {
+        comptime!(unimplemented!(
+            "Can't use `BiasGeluResidualArgs` as `TensorMap`"
+        ));
+        #[allow(unreachable_code)]
+        TensorMap::dummy()
+    }
+
+    fn as_tensor_map_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        _state: &Self::State<Lhs, Rhs, EO>,
+    ) -> TensorMap<Rhs> {
+        comptime!(unimplemented!(
+            "Can't use `BiasGeluResidualArgs` as `TensorMap`"
+        ));
+        #[allow(unreachable_code)]
+        TensorMap::dummy()
+    }
+
+    fn as_tensor_map_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        _state: &Self::State<Lhs, Rhs, EO>,
+    ) -> TensorMap<EO> {
+        comptime!(unimplemented!(
+            "Can't use `BiasGeluResidualArgs` as `TensorMap`"
+        ));
+        #[allow(unreachable_code)]
+        TensorMap::dummy()
+    }
+
+    fn shape_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.0).shape(dim) }
+    }
+
+    fn shape_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.1).shape(dim) }
+    }
+
+    fn shape_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.2.unwrap()).shape(dim) }
+    }
+
+    fn shape_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.3).shape(dim) }
+    }
+
+    fn stride_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.0).stride(dim) }
+    }
+
+    fn stride_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.1).stride(dim) }
+    }
+
+    fn stride_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.2.unwrap()).stride(dim) }
+    }
+
+    fn stride_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+        dim: u32,
+    ) -> u32 {
+        unsafe { (*state.3).stride(dim) }
+    }
+
+    fn write_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &mut Self::State<Lhs, Rhs, EO>,
+        coordinate: u32,
+        value: Line<EO>,
+    ) {
+        // The output is contiguous and its lines never cross a row, so the line of the bias is
+        // the one of the column of the first element.
+        let line_size = unsafe { (*state.3).line_size() };
+        let rank = unsafe { (*state.3).rank() };
+        let n = unsafe { (*state.3).shape(rank - 1) };
+        let bias = unsafe { (*state.4)[((coordinate * line_size) % n) / line_size] };
+
+        let x = Line::<f32>::cast_from(value) + Line::<f32>::cast_from(bias);
+        let erf = Erf::erf(x * Line::empty(line_size).fill(f32::new(FRAC_1_SQRT_2)));
+        let mut result = x
+            * Line::empty(line_size).fill(f32::new(0.5))
+            * (Line::empty(line_size).fill(f32::new(1.0)) + erf);
+
+        // The residual is read at the coordinate written, so it can be the output itself.
+        match state.5 {
+            CubeOption::Some(residual) => {
+                result += Line::<f32>::cast_from(unsafe { (*residual)[coordinate] });
+            }
+            CubeOption::None => {}
+        }
+
+        unsafe { (*state.3)[coordinate] = Line::cast_from(result) }
+    }
+
+    fn rank_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.0).rank() }
+    }
+
+    fn rank_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.1).rank() }
+    }
+
+    fn rank_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.2.unwrap()).rank() }
+    }
+
+    fn rank_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.3).rank() }
+    }
+
+    fn len_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.0).len() }
+    }
+
+    fn len_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.1).len() }
+    }
+
+    fn len_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.2.unwrap()).len() }
+    }
+
+    fn len_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(state: &Self::State<Lhs, Rhs, EO>) -> u32 {
+        unsafe { (*state.3).len() }
+    }
+
+    fn buffer_len_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> u32 {
+        unsafe { (*state.0).buffer_len() }
+    }
+
+    fn buffer_len_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> u32 {
+        unsafe { (*state.1).buffer_len() }
+    }
+
+    fn buffer_len_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> u32 {
+        unsafe { (*state.2.unwrap()).buffer_len() }
+    }
+
+    fn buffer_len_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> u32 {
+        unsafe { (*state.3).buffer_len() }
+    }
+
+    fn line_size_lhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> comptime_type!(u32) {
+        unsafe { (*state.0).line_size() }
+    }
+    fn line_size_rhs<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> comptime_type!(u32) {
+        unsafe { (*state.1).line_size() }
+    }
+
+    #[allow(unused_variables)]
+    fn line_size_acc<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> comptime_type!(u32) {
+        intrinsic!(|scope| {
+            match state.2 {
+                CubeOptionExpand::None => 1,
+                CubeOptionExpand::Some(t) => t.__expand_line_size_method(scope),
+            }
+        })
+    }
+
+    fn line_size_out<Lhs: Numeric, Rhs: Numeric, EO: Numeric>(
+        state: &Self::State<Lhs, Rhs, EO>,
+    ) -> comptime_type!(u32) {
+        unsafe { (*state.3).line_size() }
+    }
+}
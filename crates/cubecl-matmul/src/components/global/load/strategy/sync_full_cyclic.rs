@@ -4,7 +4,9 @@ use crate::components::global::load::SyncFullLoadingStrategy;
 use crate::components::global::memory::TensorReader;
 use crate::components::global::multi_stage::LoadMaxRoundPlaneCount;
 use crate::components::global::{GlobalConfig, RoleRule};
-use crate::components::stage::{ContiguousTilingLayout, StageMemory, TilingOrder};
+use crate::components::stage::{
+    ContiguousTilingLayout, StageMemory, StageMemoryConfig, StageSwizzle, TilingOrder,
+};
 use crate::components::{InputPrecision, TilingScheme};
 use crate::components::{InvalidConfigError, MatmulIdent};
 use cubecl_core as cubecl;
@@ -65,11 +67,18 @@ impl<TO: TilingOrder> SyncFullLoadingStrategy for SyncFullCyclicLoading<TO> {
         let line_size = config.global_line_size(ident);
         let num_stage_elements = config.tiling_scheme().elements_in_stage(ident);
 
-        let num_stage_lines = num_stage_elements.div_ceil(line_size);
+        let num_stage_lines = comptime!(num_stage_elements.div_ceil(line_size));
         let total_units = comptime!(config.num_loading_planes(ident) * config.plane_dim());
         let num_tasks_per_unit = comptime!(num_stage_lines.div_ceil(total_units));
         let balanced_workload = comptime!(num_stage_lines.is_multiple_of(total_units));
         let jump_length = comptime!(total_units * line_size);
+        // The tasks of strict loaders are never out of the stage
+        comptime_assert!(
+            config.loader_mode() != LoaderMode::Strict || balanced_workload,
+            "The {} units should divide the {} lines of the stage with a strict loader",
+            total_units,
+            num_stage_lines
+        );
 
         let unit_id = RoleRule::new(config.role_rule_config())
             .load_index(ident, config.specialized_loading_sides())
@@ -165,5 +174,9 @@ pub(crate) fn load_and_store_line<IP: InputPrecision, TO: TilingOrder, G: Global
         comptime!(config.global_memory_config(job.ident)),
     );
 
-    stage.as_slice_mut(job.line_size)[unit_position / job.line_size] = Line::cast_from(line_read);
+    let offset = StageSwizzle::apply(
+        unit_position / job.line_size,
+        comptime!(config.stage_memory_config().swizzle(job.ident.into_stage())),
+    );
+    stage.as_slice_mut(job.line_size)[offset] = Line::cast_from(line_read);
 }
@@ -8,7 +8,7 @@ use crate::components::{
 };
 use crate::components::{
     global::{GlobalConfig, memory::TensorReader},
-    stage::{ContiguousTilingLayout, StageMemory, TilingOrder},
+    stage::{ContiguousTilingLayout, StageMemory, StageMemoryConfig, StageSwizzle, TilingOrder},
 };
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -183,7 +183,14 @@ impl SyncFullTilewiseJob {
             comptime!(config.global_memory_config(this.ident)),
         );
 
-        let offset = this.num_lines_to_skip + line_index_within_tile + num_lines_to_skip_local;
+        let offset = StageSwizzle::apply(
+            this.num_lines_to_skip + line_index_within_tile + num_lines_to_skip_local,
+            comptime!(
+                config
+                    .stage_memory_config()
+                    .swizzle(this.ident.into_stage())
+            ),
+        );
 
         stage.as_slice_mut(this.line_size)[offset] = Line::cast_from(line_read);
     }
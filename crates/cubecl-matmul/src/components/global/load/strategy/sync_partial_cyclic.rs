@@ -4,7 +4,9 @@ use crate::components::global::load::SyncPartialLoadingStrategy;
 use crate::components::global::memory::TensorReader;
 use crate::components::global::multi_stage::LoadMaxRoundPlaneCount;
 use crate::components::global::{GlobalConfig, RoleRule};
-use crate::components::stage::{ContiguousTilingLayout, StageMemory, TilingOrder};
+use crate::components::stage::{
+    ContiguousTilingLayout, StageMemory, StageMemoryConfig, StageSwizzle, TilingOrder,
+};
 use crate::components::{InputPrecision, InvalidConfigError, MatmulIdent, TilingScheme};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -80,11 +82,18 @@ impl<TO: TilingOrder> SyncPartialLoadingStrategy for SyncPartialCyclicLoading<TO
         let tile_count_col = config.tiling_scheme().tiles_in_stage_col(ident);
 
         let num_lines_per_tile = tile_size / line_size;
-        let total_units = config.plane_dim() * config.num_loading_planes(ident);
+        let total_units = comptime!(config.plane_dim() * config.num_loading_planes(ident));
 
         let num_tiles_in_stage = tile_count_row * tile_count_col;
-        let total_num_lines = num_tiles_in_stage * num_lines_per_tile;
-        let balanced_workload = total_num_lines.is_multiple_of(total_units);
+        let total_num_lines = comptime!(num_tiles_in_stage * num_lines_per_tile);
+        let balanced_workload = comptime!(total_num_lines.is_multiple_of(total_units));
+        // The tasks of strict loaders are never out of the stage
+        comptime_assert!(
+            config.loader_mode() != LoaderMode::Strict || balanced_workload,
+            "The {} units should divide the {} lines of the stage with a strict loader",
+            total_units,
+            total_num_lines
+        );
         let num_tasks_per_unit = total_num_lines.div_ceil(total_units);
         let jump_length = total_units * line_size;
 
@@ -228,10 +237,10 @@ pub(crate) fn load_and_store_line<IP: InputPrecision, TO: TilingOrder, G: Global
     );
 
     let tile_start = nth_tile_in_stage * job.num_lines_per_tile;
-    let tile_end = tile_start + job.num_lines_per_tile;
-    let mut tile_slice = stage
-        .as_slice_mut(line_size)
-        .slice_mut(tile_start, tile_end);
+    let offset = StageSwizzle::apply(
+        tile_start + pos_within_tile / line_size,
+        comptime!(config.stage_memory_config().swizzle(stage_ident)),
+    );
 
-    tile_slice[pos_within_tile / line_size] = Line::cast_from(line_read);
+    stage.as_slice_mut(line_size)[offset] = Line::cast_from(line_read);
 }
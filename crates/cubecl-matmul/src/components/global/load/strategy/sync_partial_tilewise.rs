@@ -9,7 +9,7 @@ use crate::components::{
 };
 use crate::components::{
     global::{GlobalConfig, memory::TensorReader},
-    stage::{ContiguousTilingLayout, StageMemory, TilingOrder},
+    stage::{ContiguousTilingLayout, StageMemory, StageMemoryConfig, StageSwizzle, TilingOrder},
 };
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -239,7 +239,14 @@ impl SyncPartialTilewiseJob {
             comptime!(config.global_memory_config(this.ident)),
         );
 
-        let offset = line_index_within_tile + num_lines_to_skip_global;
+        let offset = StageSwizzle::apply(
+            line_index_within_tile + num_lines_to_skip_global,
+            comptime!(
+                config
+                    .stage_memory_config()
+                    .swizzle(this.ident.into_stage())
+            ),
+        );
 
         stage.as_slice_mut(this.line_size)[offset] = Line::cast_from(line_read);
     }
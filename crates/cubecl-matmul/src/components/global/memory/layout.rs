@@ -5,9 +5,18 @@ use cubecl_std::tensor::{
     r#virtual::VirtualTensor,
 };
 
-use crate::components::global::memory::GlobalMemoryConfig;
+use crate::components::{BlockedDim, MemoryFormat, global::memory::GlobalMemoryConfig};
 
 /// Global layout that uses the last two dimensions and ignores all others.
+///
+/// Only the dimensions whose bounds are checked are read from the metadata of the tensor, the
+/// others being reported as [u32::MAX] by the shape since they are never out of bounds. The
+/// checks are those of the [config](GlobalMemoryConfig) of the operand, so the rows of the rhs
+/// and the columns of the lhs along `k` aren't read when `k` is a multiple of the stage size.
+///
+/// The positions are mapped to offsets according to the [memory format](MemoryFormat) of the
+/// config: through the strides of the tensor, or to the blocks of a blocked tensor, in which case
+/// the size of the dimension between the blocks is read as well.
 #[derive(CubeType, Clone, Copy)]
 pub struct SimpleGlobalLayout {
     rows: u32,
@@ -28,12 +37,33 @@ impl SimpleGlobalLayout {
         #[comptime] config: GlobalMemoryConfig,
     ) -> Self {
         let rank = tensor.rank();
+        let (read_rows, read_columns) = comptime! {match config.memory_format {
+            MemoryFormat::Strided => (config.check_row_bounds, config.check_col_bounds),
+            MemoryFormat::Blocked { dim, .. } => (
+                config.check_row_bounds || dim == BlockedDim::Col,
+                config.check_col_bounds || dim == BlockedDim::Row,
+            ),
+        }};
+        let rows = if comptime![read_rows] {
+            tensor.shape(rank - 2)
+        } else {
+            u32::MAX.runtime()
+        };
+        let columns = if comptime![read_columns] {
+            tensor.shape(rank - 1)
+        } else {
+            u32::MAX.runtime()
+        };
+        let (stride_row, stride_col) = match comptime![config.memory_format] {
+            MemoryFormat::Strided => (tensor.stride(rank - 2), tensor.stride(rank - 1)),
+            MemoryFormat::Blocked { .. } => (0u32, 0u32).runtime(),
+        };
 
         SimpleGlobalLayout {
-            rows: tensor.shape(rank - 2),
-            stride_row: tensor.stride(rank - 2),
-            columns: tensor.shape(rank - 1),
-            stride_col: tensor.stride(rank - 1),
+            rows,
+            stride_row,
+            columns,
+            stride_col,
             batch_offset,
             config,
         }
@@ -48,9 +78,14 @@ impl Layout for SimpleGlobalLayout {
     fn to_source_pos(&self, coords: Self::Coordinates) -> u32 {
         let line_size = comptime![self.config.global_line_size];
         let (row, col) = coords;
-        let idx = self.batch_offset + row * self.stride_row + col * self.stride_col;
+        let offset = match comptime![self.config.memory_format] {
+            MemoryFormat::Strided => row * self.stride_row + col * self.stride_col,
+            MemoryFormat::Blocked { block_size, dim } => {
+                blocked_offset(row, col, self.rows, self.columns, block_size, dim)
+            }
+        };
 
-        idx / line_size
+        (self.batch_offset + offset) / line_size
     }
 
     fn to_source_pos_checked(&self, coords: Self::Coordinates) -> (u32, bool) {
@@ -77,3 +112,68 @@ impl Layout for SimpleGlobalLayout {
         }
     }
 }
+
+#[cube]
+/// The offset of the element at (`row`, `col`) in a matrix of `rows x columns` split along `dim`
+/// into blocks of `block_size`, relative to the start of the matrix.
+///
+/// The elements of the blocked dimension are contiguous within a block, so lines whose size
+/// divides `block_size` never cross blocks. Only the size of the dimension that isn't blocked is
+/// used, as the stride between blocks.
+pub(crate) fn blocked_offset(
+    row: u32,
+    col: u32,
+    rows: u32,
+    columns: u32,
+    #[comptime] block_size: u32,
+    #[comptime] dim: BlockedDim,
+) -> u32 {
+    match dim {
+        BlockedDim::Col => {
+            (col / block_size) * rows * block_size + row * block_size + col % block_size
+        }
+        BlockedDim::Row => {
+            (row / block_size) * columns * block_size + col * block_size + row % block_size
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The offsets of all the elements of a `rows x columns` matrix, in row-major order.
+    fn offsets(rows: u32, columns: u32, block_size: u32, dim: BlockedDim) -> Vec<u32> {
+        (0..rows * columns)
+            .map(|index| {
+                let (row, col) = (index / columns, index % columns);
+                blocked_offset(row, col, rows, columns, block_size, dim)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn col_blocks_hold_consecutive_columns_of_each_row() {
+        // `[2, 3, 4]`: two blocks of four columns, each with the three rows one after the other.
+        let offsets = offsets(3, 8, 4, BlockedDim::Col);
+
+        assert_eq!(&offsets[..8], &[0, 1, 2, 3, 12, 13, 14, 15]);
+        assert_eq!(&offsets[8..12], &[4, 5, 6, 7]);
+        assert_eq!(offsets[2 * 8 + 7], 23);
+    }
+
+    #[test]
+    fn row_blocks_hold_consecutive_rows_of_each_column() {
+        let offsets = offsets(8, 3, 4, BlockedDim::Row);
+
+        // The first rows of the first column, then of the second one.
+        assert_eq!(&offsets[..2], &[0, 4]);
+        assert_eq!(offsets[3], 1);
+        // The first row of the second block of rows starts after the three columns of the first.
+        assert_eq!(offsets[4 * 3], 12);
+
+        let mut sorted = offsets.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..24).collect::<Vec<_>>());
+    }
+}
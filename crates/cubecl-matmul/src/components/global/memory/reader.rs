@@ -1,5 +1,5 @@
-use crate::components::MatrixLayout;
 use crate::components::global::memory::GlobalMemoryConfig;
+use crate::components::{MatrixLayout, MemoryFormat};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_std::tensor::{View, layout::Coords2d};
@@ -76,10 +76,17 @@ impl<EG: Numeric> TensorReader<EG> {
         let tile_size_x = config.elements_in_tile_row;
         let tile_size_y = config.elements_in_tile_col;
 
-        let num_lines_in_window = comptime! {match matrix_layout {
-            MatrixLayout::RowMajor => tile_size_y / line_size,
-            MatrixLayout::ColMajor => tile_size_x / line_size,
+        let window_size = comptime! {match matrix_layout {
+            MatrixLayout::RowMajor => tile_size_y,
+            MatrixLayout::ColMajor => tile_size_x,
         }};
+        comptime_assert!(
+            window_size % line_size == 0,
+            "The line size {} should divide the width {} of the tiles",
+            line_size,
+            window_size
+        );
+        let num_lines_in_window = comptime!(window_size / line_size);
 
         self.load_window(
             nth_window,
@@ -105,14 +112,17 @@ impl<EG: Numeric> TensorReader<EG> {
         let line_size = config.global_line_size;
         let matrix_layout = config.matrix_layout;
 
-        let num_lines_in_window = comptime! {match matrix_layout {
-            MatrixLayout::RowMajor =>
-                config.elements_in_stage_col / line_size
-            ,
-            MatrixLayout::ColMajor =>
-                config.elements_in_stage_row / line_size
-            ,
+        let window_size = comptime! {match matrix_layout {
+            MatrixLayout::RowMajor => config.elements_in_stage_col,
+            MatrixLayout::ColMajor => config.elements_in_stage_row,
         }};
+        comptime_assert!(
+            window_size % line_size == 0,
+            "The line size {} should divide the width {} of the stage",
+            line_size,
+            window_size
+        );
+        let num_lines_in_window = comptime!(window_size / line_size);
 
         self.load_window(
             nth_window,
@@ -131,6 +141,11 @@ impl<EG: Numeric> TensorReader<EG> {
     ) -> Window<EG> {
         let line_size = config.global_line_size;
         let matrix_layout = config.matrix_layout;
+        comptime_assert!(
+            config.memory_format == MemoryFormat::Strided,
+            "Windows are contiguous slices of strided tensors, not of {:?} tensors",
+            config.memory_format
+        );
 
         let (load_row, load_col) = match matrix_layout {
             MatrixLayout::RowMajor => (nth_window, 0),
@@ -182,18 +197,10 @@ impl<EG: Numeric> TensorReader<EG> {
         position: u32,
         #[comptime] config: GlobalMemoryConfig,
     ) -> Line<EG> {
-        let tile_size_x = config.elements_in_tile_row;
-        let tile_size_y = config.elements_in_tile_col;
-
-        let view_tile_x = tile_x * tile_size_x;
-        let view_tile_y = tile_y * tile_size_y;
-
-        let (load_x, load_y) = match config.matrix_layout {
-            MatrixLayout::RowMajor => (position / tile_size_y, position % tile_size_y),
-            MatrixLayout::ColMajor => (position % tile_size_x, position / tile_size_x),
-        };
-
-        self.load_coalesced((load_x + view_tile_x, load_y + view_tile_y))
+        self.load_coalesced(
+            coalesced_offsets_in_tile(tile_x, tile_y, position, config),
+            config,
+        )
     }
 
     /// Reads data from the tensor view at the specified index within the whole view,
@@ -219,19 +226,115 @@ impl<EG: Numeric> TensorReader<EG> {
             MatrixLayout::ColMajor => (position % stage_shape_x, position / stage_shape_x),
         };
 
-        self.load_coalesced(load_offsets)
+        self.load_coalesced(load_offsets, config)
     }
 
-    fn load_coalesced(&self, load_offsets: (u32, u32)) -> Line<EG> {
+    fn load_coalesced(
+        &self,
+        load_offsets: (u32, u32),
+        #[comptime] config: GlobalMemoryConfig,
+    ) -> Line<EG> {
         let view_x = load_offsets.0 + self.row_offset.read();
         let view_y = load_offsets.1 + self.col_offset.read();
 
-        self.view.read_checked((view_x, view_y))
+        if comptime![config.check_row_bounds || config.check_col_bounds] {
+            let in_bounds = self.view.is_in_bounds((view_x, view_y));
+            // The read isn't skipped when it's out of bounds, so it's moved to the start of
+            // the view, which is always in the tensor.
+            let read_x = select(in_bounds, view_x, 0);
+            let read_y = select(in_bounds, view_y, 0);
+            let value = self.view.read_unchecked((read_x, read_y));
+
+            select(in_bounds, value, Line::cast_from(0u32))
+        } else {
+            self.view.read_unchecked((view_x, view_y))
+        }
     }
 }
 
+#[cube]
+/// The offsets in the view, before its own offsets, of the element at `position` in the tile at
+/// (`tile_x`, `tile_y`), with subsequent positions along the contiguous dimension of the tile.
+pub(crate) fn coalesced_offsets_in_tile(
+    tile_x: u32,
+    tile_y: u32,
+    position: u32,
+    #[comptime] config: GlobalMemoryConfig,
+) -> (u32, u32) {
+    let tile_size_x = config.elements_in_tile_row;
+    let tile_size_y = config.elements_in_tile_col;
+
+    let view_tile_x = tile_x * tile_size_x;
+    let view_tile_y = tile_y * tile_size_y;
+
+    let (load_x, load_y) = match config.matrix_layout {
+        MatrixLayout::RowMajor => (position / tile_size_y, position % tile_size_y),
+        MatrixLayout::ColMajor => (position % tile_size_x, position / tile_size_x),
+    };
+
+    (load_x + view_tile_x, load_y + view_tile_y)
+}
+
 #[cube]
 /// Gives the largest slice starting at offset and not exceeding shape
 fn slice_length_clamp(shape: u32, offset: u32, max_length: u32) -> u32 {
-    Min::min(select(shape > offset, shape - offset, 0), max_length)
+    Min::min(Max::max(shape, offset) - offset, max_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cubecl_core::HostContext;
+
+    /// Tiles of `8x16` loaded in lines of 4 elements.
+    fn config(matrix_layout: MatrixLayout) -> GlobalMemoryConfig {
+        GlobalMemoryConfig {
+            elements_in_tile_row: 8,
+            elements_in_tile_col: 16,
+            elements_in_stage_row: 16,
+            elements_in_stage_col: 32,
+            global_line_size: 4,
+            check_row_bounds: false,
+            check_col_bounds: false,
+            matrix_layout,
+            memory_format: MemoryFormat::Strided,
+        }
+    }
+
+    /// Each unit loads the line at its own position of the tile, as in the cyclic loaders.
+    #[cube]
+    fn unit_offsets(
+        tile_x: u32,
+        tile_y: u32,
+        #[comptime] config: GlobalMemoryConfig,
+    ) -> (u32, u32) {
+        coalesced_offsets_in_tile(tile_x, tile_y, UNIT_POS * config.global_line_size, config)
+    }
+
+    fn offsets_of_each_unit(matrix_layout: MatrixLayout) -> Vec<(u32, u32)> {
+        let config = config(matrix_layout);
+
+        HostContext::new(CubeDim::new_1d(32))
+            .units()
+            .map(|unit| unit.eval(|| unit_offsets(1, 2, config)))
+            .collect()
+    }
+
+    #[test]
+    fn row_major_units_load_consecutive_lines_of_a_row() {
+        let expected = (0..32)
+            .map(|unit| (8 + unit / 4, 32 + (unit % 4) * 4))
+            .collect::<Vec<_>>();
+
+        assert_eq!(offsets_of_each_unit(MatrixLayout::RowMajor), expected);
+    }
+
+    #[test]
+    fn col_major_units_load_consecutive_lines_of_a_column() {
+        let expected = (0..32)
+            .map(|unit| (8 + (unit % 2) * 4, 32 + unit / 2))
+            .collect::<Vec<_>>();
+
+        assert_eq!(offsets_of_each_unit(MatrixLayout::ColMajor), expected);
+    }
 }
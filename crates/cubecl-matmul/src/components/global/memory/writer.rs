@@ -1,3 +1,4 @@
+use crate::components::MatrixLayout;
 use crate::components::global::memory::GlobalMemoryConfig;
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
@@ -23,7 +24,8 @@ impl<EG: Numeric> TensorWriter<EG> {
 
     /// Writes data into the tensor view at the specified coordinates (tile_x, tile_y).
     ///
-    /// Each unit writes one line in a coalesced manner for improved efficiency, assuming row-major layout.
+    /// Each unit writes one line in a coalesced manner for improved efficiency.
+    /// Lines are along the rows of a row-major output, and along the columns of a column-major one.
     pub fn write_coalesced(
         &mut self,
         tile_x: u32,
@@ -35,8 +37,12 @@ impl<EG: Numeric> TensorWriter<EG> {
         let tile_size_m = out_config.elements_in_tile_row;
         let tile_size_n = out_config.elements_in_tile_col;
 
-        let view_x = tile_x * tile_size_m + unit_id / tile_size_n;
-        let view_y = tile_y * tile_size_n + unit_id % tile_size_n;
+        let (unit_x, unit_y) = match out_config.matrix_layout {
+            MatrixLayout::RowMajor => (unit_id / tile_size_n, unit_id % tile_size_n),
+            MatrixLayout::ColMajor => (unit_id % tile_size_m, unit_id / tile_size_m),
+        };
+        let view_x = tile_x * tile_size_m + unit_x;
+        let view_y = tile_y * tile_size_n + unit_y;
 
         self.view
             .write_checked((view_x, view_y), Line::cast_from(value));
@@ -2,6 +2,7 @@
 //! Handles memory movement, bound checks, plane specialization.
 
 pub mod args;
+pub mod bias_gelu_residual;
 pub mod load;
 pub mod memory;
 pub mod multi_stage;
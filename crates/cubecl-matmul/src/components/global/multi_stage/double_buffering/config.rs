@@ -1,7 +1,10 @@
 use cubecl_core::{CubeDim, Runtime, client::ComputeClient};
 
+use cubecl_core::ir::CacheHint;
+
 use crate::components::{
-    LoadingPrecomputeStrategy, MatmulIdent, MatmulPrecision, MatrixLayout,
+    CacheHints, LoadingPrecomputeStrategy, MatmulIdent, MatmulPrecision, MatrixLayout,
+    MemoryFormat,
     error::MatmulSetupError,
     global::{
         GlobalConfig, PlaneRoleConfig, SpecializedLoadingSides,
@@ -20,6 +23,10 @@ pub struct DoubleBufferingGlobalConfig<S: StageConfig> {
     pub check_m_bounds: bool,
     pub check_n_bounds: bool,
     pub check_k_bounds: bool,
+    out_layout: MatrixLayout,
+    lhs_format: MemoryFormat,
+    rhs_format: MemoryFormat,
+    cache_hints: CacheHints,
     precompute_job: LoadingPrecomputeStrategy,
     loader_mode: LoaderMode,
     specialized_loading_sides: SpecializedLoadingSides,
@@ -42,7 +49,22 @@ impl<S: StageConfig> GlobalConfig for DoubleBufferingGlobalConfig<S> {
     }
 
     fn matrix_layout(&self, ident: MatmulIdent) -> MatrixLayout {
-        self.stage_config.matrix_layout(ident.into_stage())
+        match ident {
+            MatmulIdent::Out => self.out_layout,
+            _ => self.stage_config.matrix_layout(ident.into_stage()),
+        }
+    }
+
+    fn memory_format(&self, ident: MatmulIdent) -> MemoryFormat {
+        match ident {
+            MatmulIdent::Lhs => self.lhs_format,
+            MatmulIdent::Rhs => self.rhs_format,
+            MatmulIdent::Out => MemoryFormat::Strided,
+        }
+    }
+
+    fn cache_hint(&self, ident: MatmulIdent) -> CacheHint {
+        self.cache_hints.get(ident)
     }
 
     fn plane_dim(&self) -> u32 {
@@ -120,6 +142,10 @@ impl<S: StageConfig> DoubleBufferingGlobalConfig<S> {
         check_m_bounds: bool,
         check_n_bounds: bool,
         check_k_bounds: bool,
+        out_layout: MatrixLayout,
+        lhs_format: MemoryFormat,
+        rhs_format: MemoryFormat,
+        cache_hints: CacheHints,
         precompute_job: LoadingPrecomputeStrategy,
         loader_mode: LoaderMode,
         specialized_loading_sides: SpecializedLoadingSides,
@@ -130,6 +156,10 @@ impl<S: StageConfig> DoubleBufferingGlobalConfig<S> {
             check_m_bounds,
             check_n_bounds,
             check_k_bounds,
+            out_layout,
+            lhs_format,
+            rhs_format,
+            cache_hints,
             precompute_job,
             loader_mode,
             specialized_loading_sides,
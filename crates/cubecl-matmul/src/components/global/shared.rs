@@ -1,6 +1,6 @@
 use crate::components::{
-    MatmulIdent, MatmulLineSizes, TilingScheme,
-    error::MatmulSetupError,
+    MatmulIdent, MatmulLineSizes, MatmulProblem, MatrixLayout, MemoryFormat, TilingScheme,
+    error::{FormattedConfigError, MatmulSetupError},
     global::{GlobalConfig, multi_stage::LoadMaxRoundPlaneCount},
 };
 
@@ -19,9 +19,66 @@ pub(crate) fn shared_global_config_validation<G: GlobalConfig>(
         }
     }
 
+    // Column-major lines are gathered from the row-major tile in shared memory, which is lined
+    // along the columns too
+    if config.matrix_layout(MatmulIdent::Out) == MatrixLayout::ColMajor {
+        let line_size = config.global_line_size(MatmulIdent::Out);
+        let tile_m = config
+            .tiling_scheme()
+            .elements_in_tile_row(MatmulIdent::Out);
+        let tile_n = config
+            .tiling_scheme()
+            .elements_in_tile_col(MatmulIdent::Out);
+
+        if !tile_m.is_multiple_of(line_size) || !tile_n.is_multiple_of(line_size) {
+            return Err(MatmulSetupError::InvalidConfig(FormattedConfigError::new(
+                move || {
+                    format!(
+                        "Output line size {line_size} must divide the tile size {tile_m}x{tile_n} to write a column-major output."
+                    )
+                },
+            )));
+        }
+    }
+
+    // Lines are read along the blocked dimension, and never cross blocks
+    for ident in [MatmulIdent::Lhs, MatmulIdent::Rhs] {
+        let memory_format = config.memory_format(ident);
+        let (MemoryFormat::Blocked { block_size, .. }, Some(expected_layout)) =
+            (memory_format, memory_format.matrix_layout())
+        else {
+            continue;
+        };
+        let line_size = config.global_line_size(ident);
+        let matrix_layout = config.matrix_layout(ident);
+
+        if expected_layout != matrix_layout || !block_size.is_multiple_of(line_size) {
+            return Err(MatmulSetupError::InvalidConfig(FormattedConfigError::new(
+                move || {
+                    format!(
+                        "{ident:?} of format {memory_format:?} must be read {expected_layout:?} \
+                         with lines dividing the blocks, got {matrix_layout:?} with lines of \
+                         {line_size}."
+                    )
+                },
+            )));
+        }
+    }
+
     Ok(config)
 }
 
+/// Reject the problems with inputs that aren't strided, for global matmuls that only read
+/// strided tensors.
+pub(crate) fn strided_formats_validation(problem: &MatmulProblem) -> Result<(), MatmulSetupError> {
+    match (problem.lhs_format, problem.rhs_format) {
+        (MemoryFormat::Strided, MemoryFormat::Strided) => Ok(()),
+        (lhs, rhs) => Err(MatmulSetupError::InvalidConfig(FormattedConfigError::new(
+            move || format!("Only strided inputs are supported, got {lhs:?} and {rhs:?}."),
+        ))),
+    }
+}
+
 /// Maximal number of planes each loader can handle to divide its workload evenly
 pub struct MaxLoaderPlanes {
     pub lhs: u32,
@@ -1,4 +1,4 @@
-use cubecl_core::{CubeDim, Runtime, client::ComputeClient, ir::SemanticType};
+use cubecl_core::{CubeDim, Runtime, client::ComputeClient};
 
 use crate::components::{
     LoadingPrecomputeStrategy, MatmulIdent, MatrixLayout,
@@ -20,6 +20,7 @@ pub struct SimpleBarrierConfig<S: stage::StageConfig> {
     check_m_bounds: bool,
     check_n_bounds: bool,
     check_k_bounds: bool,
+    out_layout: MatrixLayout,
     pub k_step: u32,
     precompute_job: LoadingPrecomputeStrategy,
     loader_mode: LoaderMode,
@@ -42,7 +43,10 @@ impl<S: stage::StageConfig> global::GlobalConfig for SimpleBarrierConfig<S> {
     }
 
     fn matrix_layout(&self, ident: MatmulIdent) -> MatrixLayout {
-        self.stage_config.matrix_layout(ident.into_stage())
+        match ident {
+            MatmulIdent::Out => self.out_layout,
+            _ => self.stage_config.matrix_layout(ident.into_stage()),
+        }
     }
 
     fn plane_dim(&self) -> u32 {
@@ -122,6 +126,7 @@ impl<S: stage::StageConfig> SimpleBarrierConfig<S> {
         check_m_bounds: bool,
         check_n_bounds: bool,
         check_k_bounds: bool,
+        out_layout: MatrixLayout,
         k_step: u32,
         precompute_job: LoadingPrecomputeStrategy,
         loader_mode: LoaderMode,
@@ -132,6 +137,7 @@ impl<S: stage::StageConfig> SimpleBarrierConfig<S> {
             check_m_bounds,
             check_n_bounds,
             check_k_bounds,
+            out_layout,
             k_step,
             precompute_job,
             loader_mode,
@@ -154,7 +160,7 @@ impl<S: stage::StageConfig> SimpleBarrierConfig<S> {
         self,
         client: &ComputeClient<R::Server, R::Channel>,
     ) -> Result<Self, MatmulSetupError> {
-        if !client.properties().supports_type(SemanticType::Barrier) {
+        if !client.device_features().barrier {
             return Err(MatmulSetupError::Unavailable(
                 MatmulAvailabilityError::BarrierUnavailable,
             ));
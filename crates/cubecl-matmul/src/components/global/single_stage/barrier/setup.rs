@@ -4,6 +4,7 @@ use crate::components::MatmulPrecision;
 use crate::components::MatmulSelection;
 use crate::components::error::MatmulSetupError;
 use crate::components::global::load::AsyncFullLoadingStrategy;
+use crate::components::global::shared::strided_formats_validation;
 use crate::components::global::single_stage::barrier::SimpleBarrierConfig;
 use crate::components::global::single_stage::barrier::matmul::SimpleBarrierMatmul;
 use crate::components::stage::FullStageReaderFamily;
@@ -52,6 +53,9 @@ where
         selection: &MatmulSelection,
         line_sizes: &MatmulLineSizes,
     ) -> Result<Self::Config, MatmulSetupError> {
+        // Barriers copy contiguous slices of the inputs
+        strided_formats_validation(problem)?;
+
         let stage_config = SMM::setup::<MP, R>(
             client,
             problem,
@@ -81,6 +85,7 @@ where
             !(problem.m as u32).is_multiple_of(stage_shape_m),
             !(problem.n as u32).is_multiple_of(stage_shape_n),
             !(problem.k as u32).is_multiple_of(stage_shape_k),
+            problem.out_layout,
             stage_shape_k,
             selection.loading_precompute_strategy,
             selection.loader_mode,
@@ -82,6 +82,10 @@ where
             !(problem.m as u32).is_multiple_of(stage_shape_m),
             !(problem.n as u32).is_multiple_of(stage_shape_n),
             !(problem.k as u32).is_multiple_of(stage_shape_k),
+            problem.out_layout,
+            problem.lhs_format,
+            problem.rhs_format,
+            selection.cache_hints,
             stage_shape_k,
             selection.loading_precompute_strategy,
             selection.loader_mode,
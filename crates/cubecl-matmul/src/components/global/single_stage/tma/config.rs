@@ -1,7 +1,6 @@
 use std::any::TypeId;
 
 use cubecl_core::{CubeDim, Runtime, client::ComputeClient, tf32};
-use cubecl_runtime::Tma;
 
 use crate::components::{
     LhsG, LhsS, LoadingPrecomputeStrategy, MatmulIdent, MatmulPrecision, MatrixLayout, RhsG, RhsS,
@@ -23,6 +22,7 @@ pub struct SimpleTmaConfig<S: stage::StageConfig> {
     check_m_bounds: bool,
     check_n_bounds: bool,
     check_k_bounds: bool,
+    out_layout: MatrixLayout,
     pub k_step: u32,
     precompute_job: LoadingPrecomputeStrategy,
     loader_mode: LoaderMode,
@@ -45,7 +45,10 @@ impl<S: stage::StageConfig> global::GlobalConfig for SimpleTmaConfig<S> {
     }
 
     fn matrix_layout(&self, ident: MatmulIdent) -> MatrixLayout {
-        self.stage_config.matrix_layout(ident.into_stage())
+        match ident {
+            MatmulIdent::Out => self.out_layout,
+            _ => self.stage_config.matrix_layout(ident.into_stage()),
+        }
     }
 
     fn plane_dim(&self) -> u32 {
@@ -125,6 +128,7 @@ impl<S: stage::StageConfig> SimpleTmaConfig<S> {
         check_m_bounds: bool,
         check_n_bounds: bool,
         check_k_bounds: bool,
+        out_layout: MatrixLayout,
         k_step: u32,
         precompute_job: LoadingPrecomputeStrategy,
         loader_mode: LoaderMode,
@@ -135,6 +139,7 @@ impl<S: stage::StageConfig> SimpleTmaConfig<S> {
             check_m_bounds,
             check_n_bounds,
             check_k_bounds,
+            out_layout,
             k_step,
             precompute_job,
             loader_mode,
@@ -171,7 +176,7 @@ impl<S: stage::StageConfig> SimpleTmaConfig<S> {
             ));
         }
 
-        if !client.properties().features.tma.contains(Tma::Base) {
+        if !client.device_features().tma {
             return Err(MatmulSetupError::Unavailable(
                 MatmulAvailabilityError::TmaUnavailable,
             ));
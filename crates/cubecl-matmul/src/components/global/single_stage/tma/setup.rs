@@ -3,6 +3,7 @@ use crate::components::MatmulSelection;
 use crate::components::error::MatmulSetupError;
 use crate::components::global::load::NoLoadingValidation;
 use crate::components::global::load::TmaTiling;
+use crate::components::global::shared::strided_formats_validation;
 use crate::components::global::single_stage::tma::SimpleTmaConfig;
 use crate::components::global::single_stage::tma::matmul::SimpleTmaMatmul;
 use crate::components::stage::StageConfig;
@@ -46,6 +47,8 @@ where
     ) -> Result<Self::Config, MatmulSetupError> {
         assert!(line_sizes.lhs == 1);
         assert!(line_sizes.rhs == 1);
+        // Tensor maps are described by the strides of the inputs
+        strided_formats_validation(problem)?;
 
         let stage_config = SMM::setup::<MP, R>(
             client,
@@ -76,6 +79,7 @@ where
             !(problem.m as u32).is_multiple_of(stage_shape_m),
             !(problem.n as u32).is_multiple_of(stage_shape_n),
             !(problem.k as u32).is_multiple_of(stage_shape_k),
+            problem.out_layout,
             stage_shape_k,
             selection.loading_precompute_strategy,
             selection.loader_mode,
@@ -1,4 +1,4 @@
-use crate::components::global::GlobalConfig;
+use crate::components::global::{GlobalConfig, memory::GlobalMemoryConfig};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_std::tensor::layout::Coordinates;
@@ -20,3 +20,27 @@ pub trait StageUnloader<EO: Numeric>: CubeType + 'static + Send + Sync {
         #[comptime] config: G,
     );
 }
+
+#[cube]
+/// Reads the line starting at `position` of a column-major output from the row-major tile in
+/// `out_smem_slice`, gathering one element from each row
+pub(crate) fn read_col_major_line<EG: Numeric>(
+    out_smem_slice: &Slice<Line<EG>>,
+    position: u32,
+    #[comptime] output_line_size: u32,
+    #[comptime] out_config: GlobalMemoryConfig,
+) -> Line<EG> {
+    let tile_size_m = out_config.elements_in_tile_row;
+    let tile_size_n = out_config.elements_in_tile_col;
+
+    let elements = out_smem_slice.with_line_size(1);
+    let row = position % tile_size_m;
+    let col = position / tile_size_m;
+
+    let mut value = Line::empty(output_line_size);
+    #[unroll]
+    for i in 0..output_line_size {
+        value[i] = elements[(row + i) * tile_size_n + col][0];
+    }
+    value
+}
@@ -1,14 +1,14 @@
 use crate::components::global::GlobalConfig;
 use crate::components::global::memory::TensorWriter;
 use crate::components::{
-    MatmulIdent, StageIdent, global::memory::GlobalMemoryConfig, stage::StageConfig,
+    MatmulIdent, MatrixLayout, StageIdent, global::memory::GlobalMemoryConfig, stage::StageConfig,
 };
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_std::tensor::View;
 use cubecl_std::{div_ceil, tensor::layout::Coords2d};
 
-use super::StageUnloader;
+use super::{StageUnloader, read_col_major_line};
 
 #[derive(CubeType)]
 /// Writes tiles from out shared memory to output global memory
@@ -92,7 +92,10 @@ fn write_line<EG: Numeric>(
     #[comptime] out_smem_line_size: u32,
     #[comptime] out_config: GlobalMemoryConfig,
 ) {
-    let value = if comptime!(output_line_size == out_smem_line_size) {
+    // The tile is always row-major in shared memory, so a column-major line is gathered
+    let value = if comptime!(out_config.matrix_layout == MatrixLayout::ColMajor) {
+        read_col_major_line(out_smem_slice, unit_write, output_line_size, out_config)
+    } else if comptime!(output_line_size == out_smem_line_size) {
         out_smem_slice[unit_write / output_line_size]
     } else if comptime!(
         out_smem_line_size < output_line_size
@@ -1,10 +1,10 @@
 use crate::components::global::memory::TensorWriter;
-use crate::components::{MatmulIdent, global::GlobalConfig};
+use crate::components::{MatmulIdent, MatrixLayout, global::GlobalConfig};
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 use cubecl_std::tensor::{View, layout::Coords2d};
 
-use super::StageUnloader;
+use super::{StageUnloader, read_col_major_line};
 
 #[derive(CubeType)]
 /// Writes tiles from out shared memory to output global memory
@@ -35,18 +35,29 @@ impl<EG: Numeric> StageUnloader<EG> for UnitWriter<EG> {
     ) {
         let tile_size = config.tiling_scheme().elements_in_tile_mn();
         let output_line_size = config.global_line_size(MatmulIdent::Out);
-        let out_smem_slice = out_smem_slice.with_line_size(output_line_size);
+        let out_config = config.global_memory_config(MatmulIdent::Out);
+        let lined_smem_slice = out_smem_slice.with_line_size(output_line_size);
 
         let num_lines = tile_size / output_line_size;
 
         for i in 0..num_lines {
-            let value = out_smem_slice[i];
+            // The tile is always row-major in shared memory, so a column-major line is gathered
+            let value = if comptime!(out_config.matrix_layout == MatrixLayout::ColMajor) {
+                read_col_major_line(
+                    &out_smem_slice,
+                    i * output_line_size,
+                    output_line_size,
+                    out_config,
+                )
+            } else {
+                lined_smem_slice[i]
+            };
             this.tensor_view.write_coalesced(
                 tile_row,
                 tile_col,
                 i * output_line_size,
                 value,
-                config.global_memory_config(MatmulIdent::Out),
+                out_config,
             );
         }
     }
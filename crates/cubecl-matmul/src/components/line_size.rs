@@ -83,11 +83,24 @@ impl AvailableLineSizes {
     }
 
     /// Filter available line sizes considering tensor shapes and strides for output
-    pub fn filter_out_with_tensor(self, strides: &[usize], shape: &[usize]) -> Self {
+    pub fn filter_out_with_tensor(
+        self,
+        strides: &[usize],
+        shape: &[usize],
+        layout: MatrixLayout,
+    ) -> Self {
         let out_vec: Vec<u8> = self.out.to_vec();
         let rank = strides.len();
 
-        let target = tensor_line_size_parallel(out_vec.iter().copied(), shape, strides, rank - 1);
+        let target = tensor_line_size_parallel(
+            out_vec.iter().copied(),
+            shape,
+            strides,
+            match layout {
+                MatrixLayout::RowMajor => rank - 1,
+                MatrixLayout::ColMajor => rank - 2,
+            },
+        );
 
         self.filter_out(move |x| *x == target)
     }
@@ -21,6 +21,13 @@ pub struct MatmulProblem {
     pub lhs_layout: MatrixLayout,
     /// Memory layout of the Rhs matrix.
     pub rhs_layout: MatrixLayout,
+    /// Memory layout of the output matrix, column-major writes the transposed product to a
+    /// row-major tensor.
+    pub out_layout: MatrixLayout,
+    /// How the elements of the Lhs matrix are arranged in memory.
+    pub lhs_format: MemoryFormat,
+    /// How the elements of the Rhs matrix are arranged in memory.
+    pub rhs_format: MemoryFormat,
 }
 
 impl MatmulProblem {
@@ -161,3 +168,45 @@ pub fn as_cmma_layout(#[comptime] layout: MatrixLayout) -> cmma::MatrixLayout {
         MatrixLayout::ColMajor => cmma::MatrixLayout::ColMajor,
     }
 }
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// Arrangement in global memory of the elements of a matrix, mapping the position of an element
+/// in the matrix to its offset in the tensor.
+pub enum MemoryFormat {
+    /// The elements are found through the strides of the tensor.
+    #[default]
+    Strided,
+    /// The matrix is split along `dim` into blocks of `block_size` rows or columns, stored one
+    /// after the other, each block holding its elements with the `block_size` elements of the
+    /// blocked dimension contiguous, like the channels of `NC4HW4`.
+    ///
+    /// A matrix of `rows x cols` blocked along its columns is stored as a row-major
+    /// `[cols / block_size, rows, block_size]` tensor. A last partial block is padded to
+    /// `block_size`, and only the strides of the batch dimensions are read from the tensor.
+    Blocked { block_size: u32, dim: BlockedDim },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+/// Dimension of a matrix split into blocks by a [blocked](MemoryFormat::Blocked) format.
+pub enum BlockedDim {
+    Row,
+    Col,
+}
+
+impl MemoryFormat {
+    /// The layout of the lines read from a matrix of this format, lines being contiguous along
+    /// the blocked dimension, or `None` when it is given by the strides.
+    pub fn matrix_layout(&self) -> Option<MatrixLayout> {
+        match self {
+            MemoryFormat::Strided => None,
+            MemoryFormat::Blocked {
+                dim: BlockedDim::Col,
+                ..
+            } => Some(MatrixLayout::RowMajor),
+            MemoryFormat::Blocked {
+                dim: BlockedDim::Row,
+                ..
+            } => Some(MatrixLayout::ColMajor),
+        }
+    }
+}
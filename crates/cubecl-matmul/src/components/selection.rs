@@ -1,5 +1,7 @@
+use cubecl_core::ir::CacheHint;
+
 use crate::components::{
-    TilingScheme,
+    MatmulIdent, TilingScheme,
     batch::HypercubeSelection,
     global::{LoadSpecializationConfig, load::LoaderMode},
     stage::PartitionBuffering,
@@ -15,6 +17,10 @@ pub struct MatmulSelection {
     pub loader_mode: LoaderMode,
     pub load_specialization_config: LoadSpecializationConfig,
     pub hypercube_selection: HypercubeSelection,
+    pub cache_hints: CacheHints,
+    /// Whether the lines of the stages are swizzled to spread the reads over the banks, see
+    /// [StageSwizzle](crate::components::stage::StageSwizzle).
+    pub stage_swizzle: bool,
 }
 
 impl MatmulSelection {
@@ -36,6 +42,8 @@ pub struct MatmulSelectionBuilder {
     loading_precompute_strategy: LoadingPrecomputeStrategy,
     loader_mode: LoaderMode,
     load_specialization_config: LoadSpecializationConfig,
+    cache_hints: CacheHints,
+    stage_swizzle: bool,
 }
 
 impl MatmulSelectionBuilder {
@@ -49,6 +57,8 @@ impl MatmulSelectionBuilder {
             loading_precompute_strategy: LoadingPrecomputeStrategy::default(),
             loader_mode: LoaderMode::default(),
             load_specialization_config: LoadSpecializationConfig::default(),
+            cache_hints: CacheHints::default(),
+            stage_swizzle: false,
         }
     }
 
@@ -98,6 +108,16 @@ impl MatmulSelectionBuilder {
         self
     }
 
+    pub fn cache_hints(mut self, cache_hints: CacheHints) -> Self {
+        self.cache_hints = cache_hints;
+        self
+    }
+
+    pub fn stage_swizzle(mut self, stage_swizzle: bool) -> Self {
+        self.stage_swizzle = stage_swizzle;
+        self
+    }
+
     pub fn build(self) -> MatmulSelection {
         MatmulSelection {
             plane_dim: self.plane_dim.unwrap(),
@@ -108,6 +128,8 @@ impl MatmulSelectionBuilder {
             loading_precompute_strategy: self.loading_precompute_strategy,
             loader_mode: self.loader_mode,
             load_specialization_config: self.load_specialization_config,
+            cache_hints: self.cache_hints,
+            stage_swizzle: self.stage_swizzle,
         }
     }
 }
@@ -123,6 +145,35 @@ pub enum MultiRowStrategy {
     Adaptive { minimum_stage_count: u32 },
 }
 
+/// How the loaders read the lhs and the rhs through the caches.
+///
+/// On large `k`, the tiles of the lhs are reused across the cubes along `n` while the rhs streams
+/// through once, so keeping the lhs in the caches at the expense of the rhs helps.
+#[derive(Default, Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct CacheHints {
+    pub lhs: CacheHint,
+    pub rhs: CacheHint,
+}
+
+impl CacheHints {
+    /// Persist the lhs and stream the rhs.
+    pub fn persisting_lhs() -> Self {
+        Self {
+            lhs: CacheHint::Persisting,
+            rhs: CacheHint::Streaming,
+        }
+    }
+
+    /// Returns the hint of the input of the given ident, the output isn't read.
+    pub fn get(&self, ident: MatmulIdent) -> CacheHint {
+        match ident {
+            MatmulIdent::Lhs => self.lhs,
+            MatmulIdent::Rhs => self.rhs,
+            MatmulIdent::Out => CacheHint::Default,
+        }
+    }
+}
+
 #[derive(Default, Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum LoadingPrecomputeStrategy {
     /// Don't precompute anything in loading jobs
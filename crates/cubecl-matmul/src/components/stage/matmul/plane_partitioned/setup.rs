@@ -67,6 +67,14 @@ impl<
         max_loaders: Option<MaxLoaderPlanes>,
         ordered: bool,
     ) -> Result<Self::Config, MatmulSetupError> {
+        if selection.stage_swizzle {
+            return Err(MatmulSetupError::InvalidConfig(Box::new(
+                "Error: Tried to swizzle the stages of a plane stage matmul, whose tile matmuls \
+                 read the stages without undoing it."
+                    .to_string(),
+            )));
+        }
+
         let tile_config =
             TM::setup::<LhsR<MP>, RhsR<MP>, AccR<MP>, R>(client, problem, selection, line_sizes)?;
 
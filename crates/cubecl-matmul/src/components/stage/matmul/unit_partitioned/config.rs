@@ -4,6 +4,7 @@ use crate::components::{
     global::{PlaneRoleConfig, RoleRuleConfig},
     stage::{
         NumStages, PartitionBuffering, PartitionSchedulerScheme, StageConfig, StageMemoryConfig,
+        StageSwizzle,
     },
     tile::TileConfig,
 };
@@ -18,6 +19,8 @@ pub struct UnitPartitionedStageConfig<T: TileConfig> {
     pub num_stages: NumStages,
     plane_role_config: PlaneRoleConfig,
     ordered: bool,
+    lhs_swizzle: StageSwizzle,
+    rhs_swizzle: StageSwizzle,
 }
 
 impl<T: TileConfig> StageConfig for UnitPartitionedStageConfig<T> {
@@ -121,6 +124,14 @@ impl<T: TileConfig> StageMemoryConfig for UnitPartitionedStageConfig<T> {
             StageIdent::Acc => unreachable!(),
         }
     }
+
+    fn swizzle(&self, ident: StageIdent) -> StageSwizzle {
+        match ident {
+            StageIdent::Lhs => self.lhs_swizzle,
+            StageIdent::Rhs => self.rhs_swizzle,
+            StageIdent::Acc => StageSwizzle::none(),
+        }
+    }
 }
 
 impl<T: TileConfig> UnitPartitionedStageConfig<T> {
@@ -131,6 +142,9 @@ impl<T: TileConfig> UnitPartitionedStageConfig<T> {
     /// - the number of computing units is different from the number of partitions
     /// - double buffering is enabled but there is only one tile in n
     /// - the required shared memory exceeds the available limit
+    ///
+    /// With `swizzle`, the lines of the stages are swizzled when it spreads the reads over more
+    /// banks, see [StageSwizzle].
     pub fn new(
         tile_config: T,
         tiling_scheme: TilingScheme,
@@ -143,7 +157,19 @@ impl<T: TileConfig> UnitPartitionedStageConfig<T> {
         eo_size: u32,
         smem_limit: u32,
         ordered: bool,
+        swizzle: bool,
     ) -> Result<Self, MatmulSetupError> {
+        let swizzle_of = |ident: StageIdent, elem_size: u32| {
+            if !swizzle {
+                return StageSwizzle::none();
+            }
+            StageSwizzle::new(
+                tiling_scheme.elements_in_tile(ident),
+                tile_config.stage_line_size(ident),
+                elem_size,
+            )
+        };
+
         Self {
             tile_config,
             tiling_scheme,
@@ -152,6 +178,8 @@ impl<T: TileConfig> UnitPartitionedStageConfig<T> {
             num_stages,
             plane_role_config,
             ordered,
+            lhs_swizzle: swizzle_of(StageIdent::Lhs, lhs_s_size),
+            rhs_swizzle: swizzle_of(StageIdent::Rhs, rhs_s_size),
         }
         .validate(lhs_s_size, rhs_s_size, eo_size, smem_limit)
     }
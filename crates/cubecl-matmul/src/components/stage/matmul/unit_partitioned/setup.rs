@@ -93,6 +93,7 @@ impl<
             AccS::<MP>::elem_size(),
             client.properties().hardware.max_shared_memory_size as u32,
             ordered,
+            selection.stage_swizzle,
         )
     }
 }
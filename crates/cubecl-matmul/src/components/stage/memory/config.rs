@@ -1,6 +1,8 @@
 use std::{fmt::Debug, hash::Hash};
 
-use crate::components::{MatrixLayout, StageIdent, TilingScheme, tile::TileConfig};
+use crate::components::{
+    MatrixLayout, StageIdent, TilingScheme, stage::StageSwizzle, tile::TileConfig,
+};
 
 pub trait StageMemoryConfig:
     Copy + Clone + Eq + PartialEq + Hash + Debug + Send + Sync + 'static
@@ -23,4 +25,9 @@ pub trait StageMemoryConfig:
 
     /// Returns the number of stages for the given input
     fn num_stages(&self, ident: StageIdent) -> u32;
+
+    /// Returns the [StageSwizzle] of the contiguous tiles for the given ident, none by default
+    fn swizzle(&self, _ident: StageIdent) -> StageSwizzle {
+        StageSwizzle::none()
+    }
 }
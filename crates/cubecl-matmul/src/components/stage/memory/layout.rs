@@ -346,6 +346,8 @@ impl<TO: TilingOrder> TilingLayout for ContiguousTilingLayout<TO> {
             stage_memory
                 .as_slice(stage_line_size)
                 .slice(start, start + tile_slice_length),
+            start,
+            config.swizzle(ident),
             ident,
             config.tile_config(),
         )
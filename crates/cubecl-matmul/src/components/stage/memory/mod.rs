@@ -2,8 +2,10 @@ mod config;
 mod layout;
 mod reader;
 mod stage_memory;
+mod swizzle;
 
 pub use config::*;
 pub use layout::*;
 pub use reader::*;
 pub use stage_memory::StageMemory;
+pub use swizzle::StageSwizzle;
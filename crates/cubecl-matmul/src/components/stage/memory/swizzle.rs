@@ -0,0 +1,156 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+/// Width of a row of shared memory banks, in bytes: 32 banks of 4 bytes.
+const BANK_ROW_SIZE: u32 = 128;
+
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+/// XOR swizzle of the lines of a stage, so that the units reading lines a multiple of a row of
+/// banks apart, like the same line of neighbouring tiles or a column of a tile, hit distinct
+/// banks.
+///
+/// The line at `offset` in the stage is stored at `offset ^ ((offset >> shift) & mask)`: the bits
+/// of the row of banks permute the lines within aligned groups of `mask + 1` lines. The bits of the
+/// key are left untouched, which makes the swizzle its own inverse. Lines are never split, so it
+/// composes with vectorized accesses.
+///
+/// Only applies to contiguous tiles, which span whole groups so that lines never leave their tile.
+pub struct StageSwizzle {
+    /// Number of bits of the offset below the key, those of a row of banks.
+    shift: u32,
+    /// Bits of the key applied to the line within its group, zero when disabled.
+    mask: u32,
+}
+
+impl StageSwizzle {
+    /// Lines are stored where they are written.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// The swizzle of contiguous tiles of `tile_size` elements, stored in lines of `line_size`
+    /// elements of `elem_size` bytes.
+    ///
+    /// Falls back to no swizzle when a single line already spans every bank, or when a tile holds
+    /// a single line.
+    pub fn new(tile_size: u32, line_size: u32, elem_size: u32) -> Self {
+        let lines_per_tile = tile_size / line_size;
+        let lines_per_bank_row = BANK_ROW_SIZE / (line_size * elem_size);
+        if lines_per_bank_row < 2 || !lines_per_bank_row.is_power_of_two() {
+            return Self::none();
+        }
+
+        // Groups must be aligned within the tiles, which start at multiples of their size.
+        let num_keys = lines_per_bank_row.min(1 << lines_per_tile.trailing_zeros());
+        if num_keys < 2 {
+            return Self::none();
+        }
+
+        Self {
+            shift: lines_per_bank_row.trailing_zeros(),
+            mask: num_keys - 1,
+        }
+    }
+
+    /// Whether lines are moved at all.
+    pub fn is_enabled(&self) -> bool {
+        self.mask != 0
+    }
+}
+
+#[cube]
+impl StageSwizzle {
+    /// Returns where the line at `offset` in the stage is stored, which is also where the line
+    /// stored at `offset` comes from.
+    pub fn apply(offset: u32, #[comptime] swizzle: StageSwizzle) -> u32 {
+        if comptime!(swizzle.is_enabled()) {
+            let (shift, mask) = comptime!((swizzle.shift, swizzle.mask));
+            offset ^ ((offset >> shift) & mask)
+        } else {
+            offset
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(offset: u32, swizzle: StageSwizzle) -> u32 {
+        offset ^ ((offset >> swizzle.shift) & swizzle.mask)
+    }
+
+    fn num_banks(offsets: impl Iterator<Item = u32>, lines_per_bank_row: u32) -> usize {
+        let mut banks = offsets
+            .map(|offset| offset % lines_per_bank_row)
+            .collect::<Vec<_>>();
+        banks.sort();
+        banks.dedup();
+        banks.len()
+    }
+
+    #[test]
+    fn swizzle_is_a_permutation_within_tiles() {
+        for (tile_size, line_size, elem_size) in [
+            (16, 4, 4),
+            (16, 1, 4),
+            (64, 4, 4),
+            (64, 8, 2),
+            (24, 2, 4),
+            (256, 1, 2),
+        ] {
+            let swizzle = StageSwizzle::new(tile_size, line_size, elem_size);
+            assert!(swizzle.is_enabled());
+
+            let lines_per_tile = tile_size / line_size;
+            let num_lines = 64 * lines_per_tile;
+            let mut seen = vec![false; num_lines as usize];
+            for offset in 0..num_lines {
+                let swizzled = apply(offset, swizzle);
+                assert_eq!(swizzled / lines_per_tile, offset / lines_per_tile);
+                assert_eq!(apply(swizzled, swizzle), offset);
+                seen[swizzled as usize] = true;
+            }
+            assert!(seen.into_iter().all(|seen| seen));
+        }
+    }
+
+    #[test]
+    fn swizzle_spreads_neighbouring_tiles_over_the_banks() {
+        // Units reading the same line of consecutive 4x4 f32 tiles in lines of 4, so a tile is
+        // half a row of banks.
+        let (line_size, elem_size, lines_per_tile) = (4, 4, 4);
+        let swizzle = StageSwizzle::new(16, line_size, elem_size);
+        let lines_per_bank_row = BANK_ROW_SIZE / (line_size * elem_size);
+
+        for line in 0..lines_per_tile {
+            let offsets = (0..8).map(|tile| tile * lines_per_tile + line);
+            assert_eq!(num_banks(offsets.clone(), lines_per_bank_row), 2);
+            let swizzled = offsets.map(|offset| apply(offset, swizzle));
+            assert_eq!(num_banks(swizzled, lines_per_bank_row), 8);
+        }
+    }
+
+    #[test]
+    fn swizzle_spreads_a_column_over_the_banks() {
+        // A column of a 32x32 f32 tile in lines of 4, so eight lines per row, which is a row of
+        // banks.
+        let (line_size, elem_size, lines_per_row) = (4, 4, 8);
+        let swizzle = StageSwizzle::new(32 * 32, line_size, elem_size);
+        let lines_per_bank_row = BANK_ROW_SIZE / (line_size * elem_size);
+
+        let offsets = (0..8).map(|row| row * lines_per_row);
+        assert_eq!(num_banks(offsets.clone(), lines_per_bank_row), 1);
+        let swizzled = offsets.map(|offset| apply(offset, swizzle));
+        assert_eq!(num_banks(swizzled, lines_per_bank_row), 8);
+    }
+
+    #[test]
+    fn swizzle_is_disabled_without_lines_to_permute() {
+        // A line per tile.
+        assert!(!StageSwizzle::new(4, 4, 4).is_enabled());
+        assert!(!StageSwizzle::new(9, 1, 4).is_enabled());
+        // A line per row of banks.
+        assert!(!StageSwizzle::new(64, 8, 16).is_enabled());
+    }
+}
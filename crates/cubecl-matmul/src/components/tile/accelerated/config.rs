@@ -118,14 +118,17 @@ impl AcceleratedConfig {
         };
 
         let size = self.tile_size();
-        if !client.properties().features.cmma.contains(&MmaConfig {
-            a_type: lhs,
-            b_type: rhs,
-            cd_type: ea,
-            m: size.m(),
-            k: size.k(),
-            n: size.n(),
-        }) {
+        if !client
+            .device_features()
+            .supports_accelerated_tile(&MmaConfig {
+                a_type: lhs,
+                b_type: rhs,
+                cd_type: ea,
+                m: size.m(),
+                k: size.k(),
+                n: size.n(),
+            })
+        {
             return Err(MatmulSetupError::Unavailable(
                 MatmulAvailabilityError::CmmaInstructionUnavailable {
                     lhs,
@@ -2,7 +2,7 @@ use cubecl_core::Runtime;
 use cubecl_core::ir::{ElemType, FloatKind};
 use cubecl_core::prelude::Numeric;
 use cubecl_core::{client::ComputeClient, ir::StorageType};
-use cubecl_runtime::{Plane, TypeUsage};
+use cubecl_runtime::TypeUsage;
 
 use crate::components::error::{MatmulAvailabilityError, MatmulSetupError};
 use crate::components::tile::TileConfig;
@@ -153,7 +153,7 @@ impl PlaneVecMatInnerProductConfig {
         self,
         client: &ComputeClient<R::Server, R::Channel>,
     ) -> Result<Self, MatmulSetupError> {
-        if !client.properties().features.plane.contains(Plane::Ops) {
+        if !client.device_features().plane_ops {
             return Err(MatmulSetupError::Unavailable(
                 MatmulAvailabilityError::PlaneOpsUnavailable,
             ));
@@ -1,7 +1,7 @@
 use cubecl_core as cubecl;
 use cubecl_core::prelude::*;
 
-use crate::components::{MatrixLayout, StageIdent, tile::TileConfig};
+use crate::components::{MatrixLayout, StageIdent, stage::StageSwizzle, tile::TileConfig};
 
 #[derive(CubeType, Clone)]
 /// Data to be handed to the Tile Matmul
@@ -13,15 +13,23 @@ pub struct Tile<ES: Numeric> {
     #[cube(comptime)]
     /// Layout of the tile (row-major or column-major).
     pub layout: MatrixLayout,
+    /// Offset of the slice in the stage, in lines, which the swizzle depends on
+    pub swizzle_offset: u32,
+    #[cube(comptime)]
+    /// Swizzle of the lines of the stage, undone by [get_line](Tile::get_line).
+    pub swizzle: StageSwizzle,
 }
 
 #[cube]
 impl<ES: Numeric> Tile<ES> {
     /// Creates a tile from a contiguous slice of data.
     ///
-    /// The slice length must exactly match the tile size.
+    /// The slice length must exactly match the tile size. Its lines are permuted by `swizzle`,
+    /// the slice starting at `offset` in the stage.
     pub fn new_contiguous<T: TileConfig>(
         slice: Slice<Line<ES>>,
+        offset: u32,
+        #[comptime] swizzle: StageSwizzle,
         #[comptime] ident: StageIdent,
         #[comptime] config: T,
     ) -> Tile<ES> {
@@ -43,6 +51,8 @@ impl<ES: Numeric> Tile<ES> {
             slice,
             stride,
             layout,
+            swizzle_offset: offset,
+            swizzle,
         }
     }
 
@@ -58,11 +68,15 @@ impl<ES: Numeric> Tile<ES> {
             slice,
             stride,
             layout,
+            swizzle_offset: 0u32.runtime(),
+            swizzle: comptime!(StageSwizzle::none()),
         }
     }
 
     /// Returns the tile as an unlined (scalar) slice.
     ///
+    /// The lines must not be swizzled.
+    ///
     /// Returns:
     /// - The unlined slice
     /// - The updated stride to account for line width removal
@@ -75,6 +89,12 @@ impl<ES: Numeric> Tile<ES> {
 
     /// Returns a specific line from the tile based on coordinates.
     pub fn get_line(&self, coor_strided: u32, coor_contiguous: u32) -> Line<ES> {
-        self.slice[coor_strided * self.stride + coor_contiguous]
+        let offset = coor_strided * self.stride + coor_contiguous;
+        if comptime!(self.swizzle.is_enabled()) {
+            let swizzled = StageSwizzle::apply(self.swizzle_offset + offset, self.swizzle);
+            self.slice[swizzled - self.swizzle_offset]
+        } else {
+            self.slice[offset]
+        }
     }
 }
@@ -0,0 +1,315 @@
+//! Matmul of `i8` operands quantized with scales and zero points, like the activations and the
+//! weights of a fully quantized model.
+//!
+//! Each operand is quantized with an affine scheme, `value = scale · (quantized - zero_point)`,
+//! per tensor or per row of the lhs and per column of the rhs. Expanding the product of the values
+//! gives
+//!
+//! `out = s_a · s_b · (Σ a·b - z_b · Σ a - z_a · Σ b + k · z_a · z_b)`
+//!
+//! so the matmul of the quantized values is computed as is, accumulated in `i32`, and the sums of
+//! each row of the lhs and each column of the rhs are computed by a pre-pass. An epilogue applies
+//! the correction terms, exactly in `i32`, then the scales.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_std::tensor::TensorHandle;
+
+use crate::{
+    MatmulInputHandleRef, Strategy, components::MatmulSetupError, launch_ref as launch_matmul_ref,
+};
+
+/// An `i8` tensor quantized with `value = scale · (quantized - zero_point)`.
+///
+/// The `scales`, of `f32`, and the `zero_points`, of `i32`, are vectors with a single element for
+/// the whole tensor, or with an element per row of the lhs or per column of the rhs.
+#[derive(Debug)]
+pub struct AffineQuantizedRef<'a, R: Runtime> {
+    pub data: TensorHandleRef<'a, R>,
+    pub scales: TensorHandleRef<'a, R>,
+    pub zero_points: TensorHandleRef<'a, R>,
+}
+
+impl<'a, R: Runtime> AffineQuantizedRef<'a, R> {
+    pub fn new(
+        data: TensorHandleRef<'a, R>,
+        scales: TensorHandleRef<'a, R>,
+        zero_points: TensorHandleRef<'a, R>,
+    ) -> Self {
+        Self {
+            data,
+            scales,
+            zero_points,
+        }
+    }
+
+    /// Whether there is a scale and a zero point per channel rather than for the whole tensor.
+    fn per_channel(&self, channels: usize) -> Result<bool, MatmulSetupError> {
+        let (scales, zero_points) = (self.scales.shape, self.zero_points.shape);
+        if scales.len() != 1 || scales != zero_points || (scales[0] != 1 && scales[0] != channels) {
+            return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+                "Expected the same number of scales and zero points, either 1 or {channels}, got shapes {:?} and {:?}",
+                self.scales.shape, self.zero_points.shape
+            ))));
+        }
+        Ok(scales[0] == channels && channels != 1)
+    }
+}
+
+/// Compute the matmul `out = lhs · rhs` of the values of operands quantized with zero points.
+///
+/// The `lhs` is of shape `[batch.., m, k]`, the `rhs` of shape `[batch.., k, n]`, or `[k, n]` to
+/// broadcast it to each matrix of the batch, and the `out` of shape `[batch.., m, n]`. Any strides
+/// are supported. The matmul of the quantized values is launched with the `strategy`.
+///
+/// A float output receives the values scaled back, while an integer output receives the exact
+/// matmul of the quantized values minus their zero points, before the scales.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref<R: Runtime, EO: Numeric>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &AffineQuantizedRef<'_, R>,
+    rhs: &AffineQuantizedRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulSetupError> {
+    let rank = lhs.data.shape.len();
+    let rhs_rank = rhs.data.shape.len();
+    if rank < 2 || out.shape.len() != rank || (rhs_rank != rank && rhs_rank != 2) {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a lhs [batch.., m, k], a rhs [batch.., k, n] or [k, n] and an output [batch.., m, n], got shapes {:?}, {:?} and {:?}",
+            lhs.data.shape, rhs.data.shape, out.shape
+        ))));
+    }
+
+    let (m, k) = (lhs.data.shape[rank - 2], lhs.data.shape[rank - 1]);
+    let n = rhs.data.shape[rhs_rank - 1];
+    let batches = &lhs.data.shape[..rank - 2];
+    let rhs_batches = &rhs.data.shape[..rhs_rank - 2];
+    let rhs_broadcast = rhs_batches.iter().product::<usize>() == 1;
+    if rhs.data.shape[rhs_rank - 2] != k
+        || out.shape[..rank - 2] != *batches
+        || out.shape[rank - 2..] != [m, n]
+        || (!rhs_broadcast && rhs_batches != batches)
+    {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "The shapes of the lhs {:?}, the rhs {:?} and the output {:?} don't match",
+            lhs.data.shape, rhs.data.shape, out.shape
+        ))));
+    }
+    let lhs_per_row = lhs.per_channel(m)?;
+    let rhs_per_col = rhs.per_channel(n)?;
+
+    let num_elems = out.shape.iter().product::<usize>();
+    if num_elems == 0 {
+        return Ok(());
+    }
+
+    // The rhs with the rank of the lhs, so it can be broadcast by the matmul.
+    let (rhs_shape, rhs_strides) = match rhs_rank == rank {
+        true => (rhs.data.shape.to_vec(), rhs.data.strides.to_vec()),
+        false => {
+            let mut shape = vec![1; rank - 2];
+            let mut strides = vec![k * n; rank - 2];
+            shape.extend_from_slice(rhs.data.shape);
+            strides.extend_from_slice(rhs.data.strides);
+            (shape, strides)
+        }
+    };
+    let rhs_data = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            rhs.data.handle,
+            &rhs_strides,
+            &rhs_shape,
+            rhs.data.elem_size,
+        )
+    };
+
+    let acc = TensorHandle::<R, i32>::new_contiguous(
+        out.shape.to_vec(),
+        client.empty(num_elems * size_of::<i32>()),
+    );
+    launch_matmul_ref::<R, i8>(
+        strategy,
+        client,
+        &MatmulInputHandleRef::new(lhs.data),
+        &MatmulInputHandleRef::new(rhs_data),
+        &acc.as_ref(),
+    )?;
+
+    // The sums along `k` of each row of the lhs and each column of the rhs.
+    let lhs_sums = operand_sums::<R>(client, &lhs.data, rank - 1);
+    let rhs_sums = operand_sums::<R>(client, &rhs.data, rhs_rank - 2);
+    let rhs_sums_batch_stride = match rhs_broadcast {
+        true => 0,
+        false => n,
+    };
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems, cube_dim);
+    unsafe {
+        affine_epilogue_kernel::launch_unchecked::<EO, R>(
+            client,
+            cube_count,
+            cube_dim,
+            acc.as_arg(1),
+            lhs_sums.as_arg(1),
+            rhs_sums.as_arg(1),
+            lhs.scales.as_tensor_arg(1),
+            lhs.zero_points.as_tensor_arg(1),
+            rhs.scales.as_tensor_arg(1),
+            rhs.zero_points.as_tensor_arg(1),
+            out.as_tensor_arg(1),
+            ScalarArg::new(k as u32),
+            ScalarArg::new(rhs_sums_batch_stride as u32),
+            AffineEpilogueConfig {
+                rank: rank as u32,
+                lhs_per_row,
+                rhs_per_col,
+                scaled: EO::as_type_native_unchecked().is_float(),
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// The sums of the `i8` elements of `input` along the dimension `reduced`, accumulated in `i32`,
+/// in a contiguous tensor of the other dimensions.
+fn operand_sums<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    reduced: usize,
+) -> TensorHandle<R, i32> {
+    let shape = input
+        .shape
+        .iter()
+        .enumerate()
+        .filter(|(dim, _)| *dim != reduced)
+        .map(|(_, size)| *size)
+        .collect::<Vec<_>>();
+    let num_sums = shape.iter().product::<usize>();
+    let sums =
+        TensorHandle::<R, i32>::new_contiguous(shape, client.empty(num_sums * size_of::<i32>()));
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_sums, cube_dim);
+    unsafe {
+        operand_sums_kernel::launch_unchecked::<R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            sums.as_arg(1),
+            ScalarArg::new(num_sums as u32),
+            input.shape.len() as u32,
+            reduced as u32,
+        );
+    }
+
+    sums
+}
+
+/// Each unit sums the elements of `input` along `reduced` at its position among the other
+/// dimensions.
+#[cube(launch_unchecked)]
+fn operand_sums_kernel(
+    input: &Tensor<i8>,
+    sums: &mut Tensor<i32>,
+    num_sums: u32,
+    #[comptime] rank: u32,
+    #[comptime] reduced: u32,
+) {
+    if ABSOLUTE_POS >= num_sums {
+        terminate!();
+    }
+
+    let mut remainder = ABSOLUTE_POS;
+    let mut offset = 0;
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        if dim != reduced {
+            offset += (remainder % input.shape(dim)) * input.stride(dim);
+            remainder /= input.shape(dim);
+        }
+    }
+
+    let stride = input.stride(reduced);
+    let mut sum = 0i32;
+    for i in 0..input.shape(reduced) {
+        sum += i32::cast_from(input[offset + i * stride]);
+    }
+    sums[ABSOLUTE_POS] = sum;
+}
+
+/// How the correction terms and the scales are applied, known at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct AffineEpilogueConfig {
+    rank: u32,
+    /// A scale and a zero point per row of the lhs, else a single one.
+    lhs_per_row: bool,
+    /// A scale and a zero point per column of the rhs, else a single one.
+    rhs_per_col: bool,
+    /// Multiply the corrected accumulator by the scales.
+    scaled: bool,
+}
+
+/// Each unit corrects the element at its position of the contiguous `acc`, and writes it to the
+/// same position of the `output`.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn affine_epilogue_kernel<EO: Numeric>(
+    acc: &Tensor<i32>,
+    lhs_sums: &Tensor<i32>,
+    rhs_sums: &Tensor<i32>,
+    lhs_scales: &Tensor<f32>,
+    lhs_zero_points: &Tensor<i32>,
+    rhs_scales: &Tensor<f32>,
+    rhs_zero_points: &Tensor<i32>,
+    output: &mut Tensor<EO>,
+    k: u32,
+    rhs_sums_batch_stride: u32,
+    #[comptime] config: AffineEpilogueConfig,
+) {
+    if ABSOLUTE_POS >= acc.len() {
+        terminate!();
+    }
+
+    let rank = config.rank;
+    let m = acc.shape(rank - 2);
+    let n = acc.shape(rank - 1);
+    let col = ABSOLUTE_POS % n;
+    let row = (ABSOLUTE_POS / n) % m;
+    let batch = ABSOLUTE_POS / (m * n);
+
+    let mut lhs_channel = 0;
+    if comptime!(config.lhs_per_row) {
+        lhs_channel = row;
+    }
+    let mut rhs_channel = 0;
+    if comptime!(config.rhs_per_col) {
+        rhs_channel = col;
+    }
+    let lhs_zero_point = lhs_zero_points[lhs_channel];
+    let rhs_zero_point = rhs_zero_points[rhs_channel];
+
+    let corrected = acc[ABSOLUTE_POS]
+        - rhs_zero_point * lhs_sums[batch * m + row]
+        - lhs_zero_point * rhs_sums[batch * rhs_sums_batch_stride + col]
+        + i32::cast_from(k) * lhs_zero_point * rhs_zero_point;
+
+    let mut remainder = ABSOLUTE_POS;
+    let mut offset = 0;
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        offset += (remainder % acc.shape(dim)) * output.stride(dim);
+        remainder /= acc.shape(dim);
+    }
+
+    if comptime!(config.scaled) {
+        let scale = lhs_scales[lhs_channel] * rhs_scales[rhs_channel];
+        output[offset] = EO::cast_from(f32::cast_from(corrected) * scale);
+    } else {
+        output[offset] = EO::cast_from(corrected);
+    }
+}
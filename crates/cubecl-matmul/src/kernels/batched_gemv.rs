@@ -0,0 +1,349 @@
+//! Batched matrix-vector product, with the vector broadcast over the batches of the matrix.
+//!
+//! Each cube computes a slab of rows of one matrix of the batch, each of its planes a few rows. The
+//! rows are read in lines along `k`, and the plane reduces the inner products of its units at the
+//! end. When the vector fits in a line per unit of a plane, each unit keeps its line in registers
+//! for all its rows, else the cube loads the vector in chunks in shared memory. The products are
+//! accumulated in `EA`, e.g. in `f32` for `f16` inputs.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, tensor_line_size_parallel};
+
+use crate::components::{MatmulAvailabilityError, MatmulSetupError};
+
+/// The number of planes of a cube.
+const PLANES_PER_CUBE: u32 = 8;
+/// The number of rows of the matrix computed by each plane.
+const ROWS_PER_PLANE: u32 = 4;
+/// The most lines of the vector held in shared memory at once.
+const MAX_SHARED_LINES: usize = 2048;
+
+/// Compute the batched matrix-vector product `out = matrix · vector`.
+///
+/// The `matrix` is of shape `[batch.., m, k]` and the `out` of shape `[batch.., m]`. The `vector`
+/// is either of shape `[k]`, broadcast to each matrix of the batch, or of shape `[batch.., k]` with
+/// a vector per matrix, and a vector with a single batch is broadcast too.
+///
+/// Any strides are supported as long as the batch dimensions of each tensor can be traversed with
+/// a single stride, and the rows are read in lines when both inputs are contiguous along `k`.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref<R: Runtime, EI: Numeric, EA: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    matrix: &TensorHandleRef<'_, R>,
+    vector: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulSetupError> {
+    let rank = matrix.shape.len();
+    let vector_rank = vector.shape.len();
+    if rank < 2 || out.shape.len() != rank - 1 || vector_rank == 0 || vector_rank > rank - 1 {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a matrix [batch.., m, k], a vector [k] or [batch.., k] and an output [batch.., m], got shapes {:?}, {:?} and {:?}",
+            matrix.shape, vector.shape, out.shape
+        ))));
+    }
+
+    let (m, k) = (matrix.shape[rank - 2], matrix.shape[rank - 1]);
+    let batches = &matrix.shape[..rank - 2];
+    let vector_batches = &vector.shape[..vector_rank - 1];
+    let vector_broadcast = vector_batches.iter().product::<usize>() == 1;
+    if vector.shape[vector_rank - 1] != k
+        || out.shape[..rank - 2] != *batches
+        || out.shape[rank - 2] != m
+        || (!vector_broadcast && vector_batches != batches)
+    {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "The shapes of the matrix {:?}, the vector {:?} and the output {:?} don't match",
+            matrix.shape, vector.shape, out.shape
+        ))));
+    }
+
+    let num_batches = batches.iter().product::<usize>();
+    if num_batches == 0 || m == 0 {
+        return Ok(());
+    }
+
+    let flattened = |tensor: &TensorHandleRef<'_, R>, num_dims: usize| {
+        batch_stride(&tensor.shape[..num_dims], &tensor.strides[..num_dims]).ok_or_else(|| {
+            MatmulSetupError::InvalidConfig(Box::new(format!(
+                "The batch dimensions of shape {:?} and strides {:?} can't be flattened",
+                tensor.shape, tensor.strides
+            )))
+        })
+    };
+    let matrix_batch_stride = flattened(matrix, rank - 2)?;
+    let out_batch_stride = flattened(out, rank - 2)?;
+    let vector_batch_stride = match vector_broadcast {
+        true => 0,
+        false => flattened(vector, vector_rank - 1)?,
+    };
+
+    let features = client.device_features();
+    let hardware = &client.properties().hardware;
+    if !features.plane_ops {
+        return Err(MatmulSetupError::Unavailable(
+            MatmulAvailabilityError::PlaneOpsUnavailable,
+        ));
+    }
+    if hardware.plane_size_min != hardware.plane_size_max {
+        return Err(MatmulSetupError::Unavailable(
+            MatmulAvailabilityError::PlaneDimUnsupported {
+                plane_dim: hardware.plane_size_max,
+            },
+        ));
+    }
+    let plane_dim = hardware.plane_size_max;
+
+    // Lines along `k` need both the matrix and the vector to be contiguous along it.
+    let line_size = Ord::min(
+        tensor_line_size_parallel(
+            R::line_size_type(&EI::as_type_native_unchecked()),
+            matrix.shape,
+            matrix.strides,
+            rank - 1,
+        ),
+        tensor_line_size_parallel(
+            R::line_size_type(&EI::as_type_native_unchecked()),
+            vector.shape,
+            vector.strides,
+            vector_rank - 1,
+        ),
+    );
+    let k_lines = k / line_size as usize;
+    let config = GemvConfig {
+        rows_per_plane: ROWS_PER_PLANE,
+        vector_in_registers: k_lines <= plane_dim as usize,
+        shared_lines: Ord::min(k_lines, MAX_SHARED_LINES).max(1) as u32,
+    };
+
+    let layout = GemvLayoutLaunch::new(
+        ScalarArg::new(m as u32),
+        ScalarArg::new(k_lines as u32),
+        ScalarArg::new(matrix_batch_stride as u32),
+        ScalarArg::new(matrix.strides[rank - 2] as u32),
+        ScalarArg::new(matrix.strides[rank - 1] as u32),
+        ScalarArg::new(vector_batch_stride as u32),
+        ScalarArg::new(vector.strides[vector_rank - 1] as u32),
+        ScalarArg::new(out_batch_stride as u32),
+        ScalarArg::new(out.strides[rank - 2] as u32),
+    );
+    let rows_per_cube = (ROWS_PER_PLANE * PLANES_PER_CUBE) as usize;
+    let cube_count = CubeCount::Static(m.div_ceil(rows_per_cube) as u32, num_batches as u32, 1);
+
+    unsafe {
+        batched_gemv_kernel::launch_unchecked::<EI, EA, EO, R>(
+            client,
+            cube_count,
+            CubeDim::new_2d(plane_dim, PLANES_PER_CUBE),
+            matrix.as_tensor_arg(line_size),
+            vector.as_tensor_arg(line_size),
+            out.as_tensor_arg(1),
+            layout,
+            config,
+        );
+    }
+
+    Ok(())
+}
+
+/// Compute the batched matmul `out = lhs · rhs` where the rhs is a single column, with the
+/// batches of the rhs broadcast when it has a single one.
+#[allow(clippy::result_large_err)]
+pub fn launch_matmul_ref<R: Runtime, EI: Numeric, EA: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulSetupError> {
+    let rank = out.shape.len();
+    if rhs.shape[rank - 1] != 1 || out.shape[rank - 1] != 1 {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "The rhs should be a single column, got shape {:?}",
+            rhs.shape
+        ))));
+    }
+
+    // Drop the single column of the rhs and of the output.
+    let (vector_shape, vector_strides) = match rhs.shape[..rank - 2].iter().product::<usize>() {
+        1 => (
+            &rhs.shape[rank - 2..rank - 1],
+            &rhs.strides[rank - 2..rank - 1],
+        ),
+        _ => (&rhs.shape[..rank - 1], &rhs.strides[..rank - 1]),
+    };
+    let (vector, out) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(
+                rhs.handle,
+                vector_strides,
+                vector_shape,
+                rhs.elem_size,
+            ),
+            TensorHandleRef::<R>::from_raw_parts(
+                out.handle,
+                &out.strides[..rank - 1],
+                &out.shape[..rank - 1],
+                out.elem_size,
+            ),
+        )
+    };
+
+    launch_ref::<R, EI, EA, EO>(client, lhs, &vector, &out)
+}
+
+/// The stride between consecutive batches once the batch dimensions are flattened, if they can be
+/// traversed with a single stride.
+fn batch_stride(shape: &[usize], strides: &[usize]) -> Option<usize> {
+    let dims = shape
+        .iter()
+        .zip(strides)
+        .filter(|(size, _)| **size != 1)
+        .collect::<Vec<_>>();
+
+    let collapsible = dims
+        .windows(2)
+        .all(|pair| *pair[0].1 == pair[1].0 * pair[1].1);
+
+    match collapsible {
+        true => Some(dims.last().map(|(_, stride)| **stride).unwrap_or(0)),
+        false => None,
+    }
+}
+
+/// The sizes and strides of the operands, in elements, with the batch dimensions flattened.
+#[derive(CubeLaunch, CubeType)]
+struct GemvLayout {
+    m: u32,
+    /// The number of lines along `k`.
+    k_lines: u32,
+    matrix_batch_stride: u32,
+    matrix_row_stride: u32,
+    matrix_k_stride: u32,
+    vector_batch_stride: u32,
+    vector_k_stride: u32,
+    out_batch_stride: u32,
+    out_row_stride: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GemvConfig {
+    rows_per_plane: u32,
+    /// Whether each unit of a plane holds a line of the vector in registers, else the vector is
+    /// loaded in shared memory in chunks of `shared_lines`.
+    vector_in_registers: bool,
+    shared_lines: u32,
+}
+
+/// Each plane computes `rows_per_plane` consecutive rows of a matrix of the batch.
+///
+/// Every unit reaches the synchronizations of the cube, even the ones past the last row.
+#[cube(launch_unchecked)]
+fn batched_gemv_kernel<EI: Numeric, EA: Numeric, EO: Numeric>(
+    matrix: &Tensor<Line<EI>>,
+    vector: &Tensor<Line<EI>>,
+    out: &mut Tensor<EO>,
+    layout: GemvLayout,
+    #[comptime] config: GemvConfig,
+) {
+    let line_size = matrix.line_size();
+    let batch = CUBE_POS_Y;
+    let first_row = (CUBE_POS_X * CUBE_DIM_Y + UNIT_POS_Y) * config.rows_per_plane;
+    let matrix_offset = batch * layout.matrix_batch_stride;
+    let vector_offset = (batch * layout.vector_batch_stride) / line_size;
+
+    let mut acc = Array::<EA>::new(config.rows_per_plane);
+    #[unroll]
+    for row in 0..config.rows_per_plane {
+        acc[row] = EA::from_int(0);
+    }
+
+    if comptime!(config.vector_in_registers) {
+        let line = UNIT_POS_X;
+        if line < layout.k_lines {
+            let value = vector[vector_offset + line * layout.vector_k_stride];
+            accumulate_rows::<EI, EA>(
+                matrix,
+                &layout,
+                value,
+                line,
+                matrix_offset,
+                first_row,
+                &mut acc,
+                config.rows_per_plane,
+            );
+        }
+    } else {
+        let mut shared = SharedMemory::<EI>::new_lined(config.shared_lines, line_size);
+        let mut chunk = 0;
+
+        while chunk < layout.k_lines {
+            for i in range_stepped(UNIT_POS, config.shared_lines, CUBE_DIM) {
+                let line = chunk + i;
+                let mut value = Line::empty(line_size).fill(EI::from_int(0));
+                if line < layout.k_lines {
+                    value = vector[vector_offset + line * layout.vector_k_stride];
+                }
+                shared[i] = value;
+            }
+            sync_cube();
+
+            let end = Min::min(chunk + config.shared_lines, layout.k_lines);
+            for line in range_stepped(chunk + UNIT_POS_X, end, PLANE_DIM) {
+                accumulate_rows::<EI, EA>(
+                    matrix,
+                    &layout,
+                    shared[line - chunk],
+                    line,
+                    matrix_offset,
+                    first_row,
+                    &mut acc,
+                    config.rows_per_plane,
+                );
+            }
+            sync_cube();
+
+            chunk += config.shared_lines;
+        }
+    }
+
+    let out_offset = batch * layout.out_batch_stride;
+    #[unroll]
+    for i in 0..config.rows_per_plane {
+        let sum = plane_sum(acc[i]);
+        let row = first_row + i;
+        if UNIT_POS_X == 0 && row < layout.m {
+            out[out_offset + row * layout.out_row_stride] = EO::cast_from(sum);
+        }
+    }
+}
+
+/// Accumulate the inner products of a line of the vector with the same line of each row of the
+/// plane.
+#[cube]
+#[allow(clippy::too_many_arguments)]
+fn accumulate_rows<EI: Numeric, EA: Numeric>(
+    matrix: &Tensor<Line<EI>>,
+    layout: &GemvLayout,
+    value: Line<EI>,
+    line: u32,
+    matrix_offset: u32,
+    first_row: u32,
+    acc: &mut Array<EA>,
+    #[comptime] rows_per_plane: u32,
+) {
+    let line_size = matrix.line_size();
+    let value = Line::<EA>::cast_from(value);
+
+    #[unroll]
+    for i in 0..rows_per_plane {
+        let row = first_row + i;
+        if row < layout.m {
+            let index = (matrix_offset + row * layout.matrix_row_stride) / line_size
+                + line * layout.matrix_k_stride;
+            let product = Line::<EA>::cast_from(matrix[index]) * value;
+
+            #[unroll]
+            for j in 0..line_size {
+                acc[i] += product[j];
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Matmul of batches of tiny matrices, without a shared memory stage.
+//!
+//! Each plane computes an entire output matrix, or each unit for the smallest ones, and a cube packs
+//! many matrices to keep the occupancy up. The operands are read from global memory straight into
+//! registers, in lines along `n` for the rhs and the output, and multiplied with FMAs. When the
+//! matrices are contiguous `16x16x16` ones supported by the tensor cores, each plane computes its
+//! matrix with a single fragment instead.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape, tensor_line_size_parallel};
+use cubecl_runtime::MmaConfig;
+
+use crate::components::MatmulSetupError;
+
+/// The largest `m`, `n` and `k` of the matrices.
+pub const MAX_TINY_SIZE: usize = 32;
+/// The largest `m` and `n` of the matrices computed by a single unit.
+const MAX_UNIT_SIZE: usize = 4;
+/// The number of units of a cube.
+const CUBE_SIZE: u32 = 256;
+/// The size of the matrices computed with a tensor core fragment.
+const FRAGMENT_SIZE: usize = 16;
+
+/// Compute the batched matmul `out = lhs · rhs` of matrices of at most [`MAX_TINY_SIZE`] along each
+/// dimension.
+///
+/// The batch dimensions are broadcast, and any strides are supported.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref<R: Runtime, EI: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulSetupError> {
+    let rank = out.shape.len();
+    let (m, n, k) = (
+        lhs.shape[rank - 2],
+        rhs.shape[rank - 1],
+        lhs.shape[rank - 1],
+    );
+    if m > MAX_TINY_SIZE || n > MAX_TINY_SIZE || k > MAX_TINY_SIZE {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "The matrices should be at most {MAX_TINY_SIZE} along each dimension, got m={m}, n={n}, k={k}"
+        ))));
+    }
+    let num_matrices = out.shape[..rank - 2].iter().product::<usize>();
+    if num_matrices == 0 || m == 0 || n == 0 {
+        return Ok(());
+    }
+
+    if fragment_supported::<R, EI, EO>(client, lhs, rhs, out) {
+        let plane_dim = client.properties().hardware.plane_size_max;
+        let planes_per_cube = CUBE_SIZE / plane_dim;
+        // One plane per matrix.
+        let cube_count = LaunchShape::elementwise(num_matrices)
+            .units_per_cube(planes_per_cube)
+            .build::<R>()?
+            .cube_count;
+
+        unsafe {
+            batched_tiny_fragment_kernel::launch_unchecked::<EI, EO, R>(
+                client,
+                cube_count,
+                CubeDim::new_2d(plane_dim, planes_per_cube),
+                lhs.as_tensor_arg(1),
+                rhs.as_tensor_arg(1),
+                out.as_tensor_arg(1),
+                ScalarArg::new(num_matrices as u32),
+            );
+        }
+
+        return Ok(());
+    }
+
+    // Lines along `n` need both the rhs and the output to be contiguous along it.
+    let line_size = Ord::min(
+        tensor_line_size_parallel(
+            R::line_size_type(&EI::as_type_native_unchecked()),
+            rhs.shape,
+            rhs.strides,
+            rank - 1,
+        ),
+        tensor_line_size_parallel(
+            R::line_size_type(&EO::as_type_native_unchecked()),
+            out.shape,
+            out.strides,
+            rank - 1,
+        ),
+    );
+    let units_per_matrix = match m <= MAX_UNIT_SIZE && n <= MAX_UNIT_SIZE {
+        true => 1,
+        false => client.properties().hardware.plane_size_max,
+    };
+    let config = LaunchShape::elementwise(num_matrices * units_per_matrix as usize)
+        .units_per_cube(CUBE_SIZE)
+        .build::<R>()?;
+
+    unsafe {
+        batched_tiny_kernel::launch_unchecked::<EI, EO, R>(
+            client,
+            config.cube_count,
+            config.cube_dim,
+            lhs.as_tensor_arg(1),
+            rhs.as_tensor_arg(line_size),
+            out.as_tensor_arg(line_size),
+            ScalarArg::new(num_matrices as u32),
+            units_per_matrix,
+        );
+    }
+
+    Ok(())
+}
+
+/// Whether the matrices are contiguous `16x16x16` ones supported by the tensor cores.
+fn fragment_supported<R: Runtime, EI: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> bool {
+    let size = FRAGMENT_SIZE as u32;
+    let supported = client
+        .device_features()
+        .supports_accelerated_tile(&MmaConfig {
+            a_type: EI::as_type_native_unchecked(),
+            b_type: EI::as_type_native_unchecked(),
+            cd_type: EO::as_type_native_unchecked(),
+            m: size,
+            n: size,
+            k: size,
+        });
+    let contiguous = |tensor: &TensorHandleRef<'_, R>| {
+        let rank = tensor.shape.len();
+        tensor.shape[rank - 2..] == [FRAGMENT_SIZE, FRAGMENT_SIZE]
+            && tensor.strides[rank - 2..] == [FRAGMENT_SIZE, 1]
+    };
+
+    supported && contiguous(lhs) && contiguous(rhs) && contiguous(out)
+}
+
+/// The offsets of the matrices of a batch in each tensor, with the batch dimensions of the inputs
+/// broadcast to the ones of the output.
+#[cube]
+pub(crate) fn batch_offsets<A: CubePrimitive, B: CubePrimitive, C: CubePrimitive>(
+    lhs: &Tensor<A>,
+    rhs: &Tensor<B>,
+    out: &Tensor<C>,
+    batch: u32,
+) -> (u32, u32, u32) {
+    let rank = out.rank();
+    let mut remainder = batch;
+    let mut lhs_offset = 0;
+    let mut rhs_offset = 0;
+    let mut out_offset = 0;
+
+    for i in 0..rank - 2 {
+        let axis = rank - 3 - i;
+        let coordinate = remainder % out.shape(axis);
+        remainder /= out.shape(axis);
+
+        lhs_offset += (coordinate % lhs.shape(axis)) * lhs.stride(axis);
+        rhs_offset += (coordinate % rhs.shape(axis)) * rhs.stride(axis);
+        out_offset += coordinate * out.stride(axis);
+    }
+
+    (lhs_offset, rhs_offset, out_offset)
+}
+
+/// Each group of `units_per_matrix` units computes a matrix of the output, each unit a line of
+/// the output at a time.
+#[cube(launch_unchecked)]
+fn batched_tiny_kernel<EI: Numeric, EO: Numeric>(
+    lhs: &Tensor<EI>,
+    rhs: &Tensor<Line<EI>>,
+    out: &mut Tensor<Line<EO>>,
+    num_matrices: u32,
+    #[comptime] units_per_matrix: u32,
+) {
+    let batch = ABSOLUTE_POS / units_per_matrix;
+    if batch >= num_matrices {
+        terminate!();
+    }
+
+    let rank = out.rank();
+    let line_size = out.line_size();
+    let (m, n, k) = (
+        lhs.shape(rank - 2),
+        rhs.shape(rank - 1),
+        lhs.shape(rank - 1),
+    );
+    let lines_per_row = n / line_size;
+    let (lhs_offset, rhs_offset, out_offset) = batch_offsets(lhs, rhs, out, batch);
+
+    let mut index = ABSOLUTE_POS % units_per_matrix;
+    while index < m * lines_per_row {
+        let row = index / lines_per_row;
+        let col = (index % lines_per_row) * line_size;
+
+        let mut acc = Line::empty(line_size).fill(EO::from_int(0));
+        for i in 0..k {
+            let a = lhs[lhs_offset + row * lhs.stride(rank - 2) + i * lhs.stride(rank - 1)];
+            let b = rhs[(rhs_offset + i * rhs.stride(rank - 2) + col) / line_size];
+            acc += Line::empty(line_size).fill(EO::cast_from(a)) * Line::cast_from(b);
+        }
+        out[(out_offset + row * out.stride(rank - 2) + col) / line_size] = acc;
+
+        index += units_per_matrix;
+    }
+}
+
+/// Each plane computes a contiguous `16x16` matrix of the output with a single fragment.
+#[cube(launch_unchecked)]
+fn batched_tiny_fragment_kernel<EI: Numeric, EO: Numeric>(
+    lhs: &Tensor<EI>,
+    rhs: &Tensor<EI>,
+    out: &mut Tensor<EO>,
+    num_matrices: u32,
+) {
+    let batch = CUBE_POS * CUBE_DIM_Y + UNIT_POS_Y;
+    if batch >= num_matrices {
+        terminate!();
+    }
+
+    let size = comptime!(FRAGMENT_SIZE as u32);
+    let (lhs_offset, rhs_offset, out_offset) = batch_offsets(lhs, rhs, out, batch);
+
+    let a = cmma::Matrix::<EI>::from_slice(
+        cmma::MatrixIdent::A,
+        size,
+        size,
+        size,
+        cmma::MatrixLayout::RowMajor,
+        &lhs.slice(lhs_offset, lhs_offset + size * size),
+        size,
+    );
+    let b = cmma::Matrix::<EI>::from_slice(
+        cmma::MatrixIdent::B,
+        size,
+        size,
+        size,
+        cmma::MatrixLayout::RowMajor,
+        &rhs.slice(rhs_offset, rhs_offset + size * size),
+        size,
+    );
+    let c = cmma::Matrix::<EO>::from_value(
+        cmma::MatrixIdent::Accumulator,
+        size,
+        size,
+        size,
+        cmma::MatrixLayout::Undefined,
+        EO::from_int(0),
+    );
+
+    cmma::execute::<EI, EI, EO, EO>(&a, &b, &c, &c);
+    cmma::store(
+        &mut out.slice_mut(out_offset, out_offset + size * size),
+        &c,
+        size,
+        cmma::MatrixLayout::RowMajor,
+    );
+}
@@ -0,0 +1,182 @@
+use cubecl_core::prelude::*;
+use cubecl_runtime::TypeUsage;
+use cubecl_std::tensor::{
+    MatrixBatchLayout, into_contiguous_pitched, is_contiguous, matrix_batch_layout,
+};
+
+use crate::components::{
+    AccG, AvailableLineSizes, InputRuntimeArg, LhsG, MatmulAvailabilityError, MatmulElems,
+    MatmulPrecision, MatmulProblem, MatmulSetupError, MatrixLayout, MemoryFormat, RhsG,
+    batch::BatchConfig,
+    global::bias_gelu_residual::{BiasGeluResidualArgs, BiasGeluResidualInputsLaunch},
+};
+use crate::kernels::layered::{Algorithm, Selection, launch_with_config};
+
+/// Compute `out = GELU(lhs · rhs + bias) + residual` with a single matmul kernel, the epilogue
+/// being applied by [`BiasGeluResidualArgs`] as the output is written.
+///
+/// The `lhs` is of shape `[batch.., m, k]`, the `rhs` of shape `[batch.., k, n]`, the `bias` of
+/// shape `[n]` and the `out` of shape `[batch.., m, n]`. The `residual`, if any, has the shape and
+/// the strides of the output, and may be the output itself to accumulate into it. The output and
+/// the bias must be contiguous; permuted inputs are made contiguous first.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    bias: &TensorHandleRef<'_, R>,
+    residual: Option<&TensorHandleRef<'_, R>>,
+    out: &TensorHandleRef<'_, R>,
+    selection: &Selection<A::SelectionArgs>,
+) -> Result<(), MatmulSetupError> {
+    let rank = out.shape.len();
+    if rank < 2 || lhs.shape.len() != rank || rhs.shape.len() != rank {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a lhs [batch.., m, k], a rhs [batch.., k, n] and an output [batch.., m, n], got shapes {:?}, {:?} and {:?}",
+            lhs.shape, rhs.shape, out.shape
+        ))));
+    }
+
+    let (m, k) = (lhs.shape[rank - 2], lhs.shape[rank - 1]);
+    let n = rhs.shape[rank - 1];
+    if rhs.shape[rank - 2] != k || out.shape[rank - 2..] != [m, n] {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "The shapes of the lhs {:?}, the rhs {:?} and the output {:?} don't match",
+            lhs.shape, rhs.shape, out.shape
+        ))));
+    }
+    if !is_contiguous(out.shape, out.strides) {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(
+            "The output of the fused epilogue should be contiguous",
+        )));
+    }
+    if bias.shape != [n] || !is_contiguous(bias.shape, bias.strides) {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a contiguous bias of shape [{n}], got shape {:?} and strides {:?}",
+            bias.shape, bias.strides
+        ))));
+    }
+    if let Some(residual) = residual
+        && (residual.shape != out.shape || residual.strides != out.strides)
+    {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a residual of the shape {:?} and the strides {:?} of the output, got {:?} and {:?}",
+            out.shape, out.strides, residual.shape, residual.strides
+        ))));
+    }
+
+    if out.shape.iter().product::<usize>() == 0 {
+        return Ok(());
+    }
+    // Without a reduction there is no matmul kernel to fuse the epilogue into.
+    if k == 0 {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(
+            "The fused epilogue needs a matmul with a non-empty reduction",
+        )));
+    }
+
+    let lhs_elem = LhsG::<MP>::as_type_native_unchecked();
+    let rhs_elem = RhsG::<MP>::as_type_native_unchecked();
+    let acc_elem = AccG::<MP>::as_type_native_unchecked();
+    if !LhsG::<MP>::supported_uses(client).contains(TypeUsage::Conversion)
+        || !RhsG::<MP>::supported_uses(client).contains(TypeUsage::Conversion)
+        || !AccG::<MP>::supported_uses(client).contains(TypeUsage::Conversion)
+    {
+        return Err(MatmulSetupError::Unavailable(
+            MatmulAvailabilityError::TypesUnavailable {
+                lhs: lhs_elem,
+                rhs: rhs_elem,
+                output: acc_elem,
+            },
+        ));
+    }
+
+    // Highly permuted inputs are made contiguous, the others are read with their layout.
+    let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_batch_layout(tensor.strides) {
+        MatrixBatchLayout::Contiguous => (false, false),
+        MatrixBatchLayout::MildlyPermuted { transposed, .. } => (false, transposed),
+        MatrixBatchLayout::HighlyPermuted => (true, false),
+    };
+    let (lhs_make_contiguous, lhs_transposed) = check_layout(lhs);
+    let (rhs_make_contiguous, rhs_transposed) = check_layout(rhs);
+
+    let lhs_owned;
+    let rhs_owned;
+    let lhs = if lhs_make_contiguous {
+        lhs_owned = into_contiguous_pitched::<R, LhsG<MP>>(client, lhs);
+        &lhs_owned.as_ref()
+    } else {
+        lhs
+    };
+    let rhs = if rhs_make_contiguous {
+        rhs_owned = into_contiguous_pitched::<R, RhsG<MP>>(client, rhs);
+        &rhs_owned.as_ref()
+    } else {
+        rhs
+    };
+    let layout = |transposed: bool| match transposed {
+        true => MatrixLayout::ColMajor,
+        false => MatrixLayout::RowMajor,
+    };
+
+    let problem = MatmulProblem {
+        m,
+        n,
+        k,
+        lhs_batches: lhs.shape[..rank - 2].to_vec(),
+        rhs_batches: rhs.shape[..rank - 2].to_vec(),
+        lhs_layout: layout(lhs_transposed),
+        rhs_layout: layout(rhs_transposed),
+        out_layout: MatrixLayout::RowMajor,
+        lhs_format: MemoryFormat::Strided,
+        rhs_format: MemoryFormat::Strided,
+    };
+
+    // The bias and the residual are read with the line size of the output.
+    let line_sizes = AvailableLineSizes::from_types::<R>(&lhs_elem, &rhs_elem, &acc_elem);
+    let line_sizes = A::filter_line_sizes(line_sizes)
+        .filter_lhs_with_tensor(lhs.strides, lhs.shape, problem.lhs_layout)
+        .filter_rhs_with_tensor(rhs.strides, rhs.shape, problem.rhs_layout)
+        .filter_out_with_tensor(out.strides, out.shape, problem.out_layout)
+        .pick_max()?;
+
+    let plane_dim = match A::select_plane_dim::<R>(client) {
+        0 => 32,
+        plane_dim => plane_dim,
+    };
+    let selection = match selection {
+        Selection::Forced(selection) => selection.clone(),
+        Selection::Inferred(args) => A::selection::<R>(
+            client,
+            &problem,
+            plane_dim,
+            &line_sizes,
+            MatmulElems::new::<MP>(),
+            args,
+        )?,
+    };
+    let config = A::setup::<MP, R>(client, &problem, &selection, &line_sizes)?;
+    let cube_count_plan = config.hypercube_config().cube_count_plan(
+        &problem,
+        client.properties().hardware.max_cube_count.clone(),
+    );
+    let line_sizes = config.line_sizes();
+
+    let input: InputRuntimeArg<'_, (MP, BiasGeluResidualArgs), R> =
+        BiasGeluResidualInputsLaunch::new(
+            lhs.as_tensor_arg(line_sizes.lhs),
+            rhs.as_tensor_arg(line_sizes.rhs),
+            bias.as_tensor_arg(line_sizes.out),
+            residual.map(|it| it.as_tensor_arg(line_sizes.out)).into(),
+        );
+
+    launch_with_config::<(MP, BiasGeluResidualArgs), R, A>(
+        client,
+        config.cube_dim(),
+        cube_count_plan.resolve(),
+        input,
+        out.as_tensor_arg(line_sizes.out),
+        cube_count_plan.as_args(),
+        config,
+    )
+}
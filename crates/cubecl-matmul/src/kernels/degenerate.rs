@@ -0,0 +1,74 @@
+//! Matmuls whose output or reduction is empty.
+//!
+//! An empty output, when `m`, `n` or a batch dimension is zero, leaves nothing to write, and an
+//! empty reduction, when `k` is zero, leaves an output of zeros. Neither launches a matmul kernel,
+//! whose cube counts and tilings aren't defined for empty dimensions.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+
+use cubecl_std::tensor::index_offset_contiguous;
+
+/// Each unit writes a zero to the element at its position in the order of the dimensions, so
+/// the output may have any strides.
+#[cube(launch_unchecked)]
+fn zeros_kernel<E: Numeric>(output: &mut Tensor<Line<E>>, num_elems: u32) {
+    if ABSOLUTE_POS >= num_elems {
+        terminate!();
+    }
+
+    let offset = index_offset_contiguous(output, ABSOLUTE_POS, None);
+    output[offset] = Line::cast_from(E::from_int(0));
+}
+
+/// Whether the matmul of `lhs` and `rhs` into `out` has an empty output or reduction.
+///
+/// Only the shapes of inputs consistent along `k` are degenerate, so that invalid shapes are
+/// still reported by the launch of the matmul.
+pub fn is_degenerate(lhs_shape: &[usize], rhs_shape: &[usize], out_shape: &[usize]) -> bool {
+    let rank = lhs_shape.len();
+    if rank < 2 || rhs_shape.len() != rank || out_shape.len() != rank {
+        return false;
+    }
+    let k = lhs_shape[rank - 1];
+    if rhs_shape[rank - 2] != k {
+        return false;
+    }
+
+    k == 0 || out_shape.contains(&0)
+}
+
+/// Compute the matmul of `lhs` and `rhs` into `out` if it is [degenerate](is_degenerate),
+/// returning whether it was.
+///
+/// Nothing is launched when the output is empty, and a single kernel writing zeros is launched
+/// otherwise, since the output of an empty reduction is all zeros.
+pub fn launch_ref<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> bool {
+    if !is_degenerate(lhs.shape, rhs.shape, out.shape) {
+        return false;
+    }
+
+    let num_elems: usize = out.shape.iter().product();
+    if num_elems == 0 {
+        return true;
+    }
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems, cube_dim);
+
+    unsafe {
+        zeros_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            out.as_tensor_arg(1),
+            ScalarArg::new(num_elems as u32),
+        );
+    }
+
+    true
+}
@@ -0,0 +1,457 @@
+//! Selection of the strategy launched by [`Strategy::Auto`](crate::Strategy::Auto).
+//!
+//! The strategy is picked immediately from the shapes, the layouts inferred from the strides, the
+//! element sizes and the properties of the device. The decision comes with the reason it was made,
+//! and with the alternatives worth benchmarking when autotuning a hot shape.
+use std::fmt::Display;
+
+use cubecl_core::{Runtime, client::ComputeClient, ir::StorageType, prelude::*, tf32};
+use cubecl_runtime::DeviceFeatures;
+use cubecl_std::tensor::{MatrixBatchLayout, matrix_batch_layout};
+
+use crate::{
+    AsyncLoadingStrategy, MatmulInputHandleRef, Strategy, SyncLoadingStrategy,
+    SyncPartialLoadingStrategy,
+    components::{
+        LhsS, MatmulKind, MatmulPrecision, MatmulProblem, MatmulProblemSize, MatrixLayout,
+        MemoryFormat, RhsS,
+    },
+    kernels::{
+        batched_tiny::MAX_TINY_SIZE,
+        layered::{
+            Selection, double_unit::DoubleUnitSelectionArgs, simple::SimpleArgs,
+            simple_unit::SimpleUnitSelectionArgs,
+        },
+    },
+};
+
+/// The ratio between the largest and the smallest output dimensions of a tall-skinny output.
+const SKINNY_RATIO: usize = 16;
+/// The ratio between `k` and the largest output dimension when the reduction dominates.
+const LONG_K_RATIO: usize = 8;
+/// The smallest `k` considered long enough to pipeline the loads of the stages.
+const LONG_K: usize = 4096;
+/// The size of a stage along each dimension, used to estimate the number of cubes and the shared
+/// memory of a stage.
+const STAGE_SIZE: usize = 128;
+const STAGE_SIZE_K: usize = 32;
+
+/// The properties of the device considered when selecting a strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceCapabilities {
+    /// Whether tensor cores support the stage elements.
+    pub accelerated: bool,
+    /// Whether planes support the operations of the vector-matrix algorithms.
+    pub plane_ops: bool,
+    /// Whether stages can be loaded asynchronously behind barriers.
+    pub barrier: bool,
+    /// Whether stages can be loaded with the Tensor Memory Accelerator.
+    pub tma: bool,
+    /// Maximum amount of shared memory, in bytes.
+    pub max_shared_memory_size: usize,
+    /// Number of streaming multiprocessors, if available.
+    pub num_streaming_multiprocessors: Option<u32>,
+}
+
+impl DeviceCapabilities {
+    /// The capabilities of the device of the `client` for the precision `MP`.
+    ///
+    /// An `f32` stage is accelerated when tensor cores support `tf32`, like the layered matmuls.
+    pub fn new<R: Runtime, MP: MatmulPrecision>(
+        client: &ComputeClient<R::Server, R::Channel>,
+    ) -> Self {
+        Self::from_features::<MP>(&client.device_features())
+    }
+
+    /// The capabilities of a device with the given features for the precision `MP`.
+    pub fn from_features<MP: MatmulPrecision>(features: &DeviceFeatures) -> Self {
+        let f32_ty = f32::as_type_native_unchecked();
+        let stage_type = |ty: StorageType| match ty == f32_ty {
+            true => [ty, tf32::as_type_native_unchecked()],
+            false => [ty, ty],
+        };
+        let lhs = stage_type(LhsS::<MP>::as_type_native_unchecked());
+        let rhs = stage_type(RhsS::<MP>::as_type_native_unchecked());
+        let accelerated = lhs
+            .iter()
+            .any(|lhs| rhs.iter().any(|rhs| features.accelerates(*lhs, *rhs)));
+
+        Self {
+            accelerated,
+            plane_ops: features.plane_ops,
+            barrier: features.barrier,
+            tma: features.tma,
+            max_shared_memory_size: features.max_shared_memory_size,
+            num_streaming_multiprocessors: features.num_streaming_multiprocessors,
+        }
+    }
+
+    /// Whether the device has what the strategy needs, so that the strategies it can't launch are
+    /// discarded before being compiled.
+    pub fn supports(&self, strategy: &Strategy) -> bool {
+        match strategy {
+            Strategy::Simple(..)
+            | Strategy::DoubleBuffering(..)
+            | Strategy::OrderedDoubleBuffering(_) => self.accelerated,
+            Strategy::SimpleBarrier(AsyncLoadingStrategy::Tma) => self.accelerated && self.tma,
+            Strategy::SimpleBarrier(_) => self.accelerated && self.barrier,
+            Strategy::SimpleVecMat(_) | Strategy::DoubleVecMat(_) | Strategy::BatchedGemv => {
+                self.plane_ops
+            }
+            Strategy::SimpleUnit(_)
+            | Strategy::DoubleUnit(_)
+            | Strategy::Naive
+            | Strategy::BatchedTiny
+            | Strategy::Auto => true,
+        }
+    }
+}
+
+/// Why a strategy was selected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionReason {
+    /// The output or the reduction is empty, which every strategy computes without a matmul
+    /// kernel.
+    Degenerate {
+        num_batches: usize,
+        m: usize,
+        n: usize,
+        k: usize,
+    },
+    /// The lhs is a single row, and both inputs are contiguous along `k`, so planes compute inner
+    /// products directly.
+    VecMat { k: usize, long_k: bool },
+    /// The rhs is a single column broadcast over the batches or given per batch, so planes compute
+    /// inner products of the rows of the lhs with it.
+    BatchedGemv {
+        num_batches: usize,
+        m: usize,
+        k: usize,
+    },
+    /// A dimension of the output or of the reduction is one, so accelerated tiles would be mostly
+    /// padding.
+    Vector { kind: MatmulKind },
+    /// Tensor cores don't support the stage elements.
+    Unaccelerated { long_k: bool },
+    /// Many tiny matrices, each computed by a plane without a shared memory stage, which would be
+    /// mostly padding.
+    BatchOfSmall {
+        num_batches: usize,
+        m: usize,
+        n: usize,
+        k: usize,
+    },
+    /// The output has many more rows than columns, or columns than rows.
+    TallSkinny { m: usize, n: usize },
+    /// The reduction dominates and the output alone doesn't fill the device, so the loads of the
+    /// stages along `k` are pipelined.
+    LongK {
+        k: usize,
+        num_cubes: usize,
+        num_streaming_multiprocessors: Option<u32>,
+    },
+    /// A general matmul, with no property calling for anything else.
+    General,
+}
+
+impl Display for SelectionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectionReason::Degenerate {
+                num_batches,
+                m,
+                n,
+                k,
+            } => write!(
+                f,
+                "{num_batches} batches of empty {m}x{n}x{k} matrices, computed without a kernel"
+            ),
+            SelectionReason::VecMat { k, long_k } => write!(
+                f,
+                "vector-matrix product with k={k} contiguous in both inputs, {} buffered",
+                match long_k {
+                    true => "double",
+                    false => "single",
+                }
+            ),
+            SelectionReason::BatchedGemv { num_batches, m, k } => write!(
+                f,
+                "{num_batches} batches of {m}x{k} matrix-vector products, rows reduced by planes"
+            ),
+            SelectionReason::Vector { kind } => {
+                write!(
+                    f,
+                    "{kind:?} matmul, accelerated tiles would be mostly padding"
+                )
+            }
+            SelectionReason::Unaccelerated { long_k } => write!(
+                f,
+                "no tensor cores for the stage elements, tiles of units {} buffered",
+                match long_k {
+                    true => "double",
+                    false => "single",
+                }
+            ),
+            SelectionReason::BatchOfSmall {
+                num_batches,
+                m,
+                n,
+                k,
+            } => write!(
+                f,
+                "{num_batches} batches of tiny {m}x{n}x{k} matrices, each computed without a stage"
+            ),
+            SelectionReason::TallSkinny { m, n } => write!(
+                f,
+                "tall-skinny {m}x{n} output, stages with multiple rows of partitions"
+            ),
+            SelectionReason::LongK {
+                k,
+                num_cubes,
+                num_streaming_multiprocessors,
+            } => {
+                write!(f, "k={k} dominates and the output has {num_cubes} stages")?;
+                if let Some(num_sms) = num_streaming_multiprocessors {
+                    write!(f, " for {num_sms} SMs")?;
+                }
+                write!(f, ", loads along k double buffered")
+            }
+            SelectionReason::General => write!(f, "general matmul with tensor cores"),
+        }
+    }
+}
+
+/// The strategy selected for a matmul, along with why.
+#[derive(Debug, Clone)]
+pub struct StrategySelection {
+    /// The strategy to launch.
+    pub strategy: Strategy,
+    /// Why the strategy was selected.
+    pub reason: SelectionReason,
+    /// Strategies worth benchmarking against the selected one when autotuning the shape.
+    pub candidates: Vec<Strategy>,
+}
+
+impl Display for StrategySelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.strategy, self.reason)
+    }
+}
+
+/// Select the strategy of a matmul of the given inputs into `out`, on the device of the `client`.
+///
+/// This is what [`Strategy::Auto`](crate::Strategy::Auto) launches, exposed so the choice can be
+/// inspected, for instance to explain a performance report.
+pub fn select_strategy<R: Runtime, MP: MatmulPrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &MatmulInputHandleRef<'_, R>,
+    rhs: &MatmulInputHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> StrategySelection {
+    let problem = problem_from_handles(lhs.data(), rhs.data(), out);
+    let elem_size = Ord::max(size_of::<LhsS<MP>>(), size_of::<RhsS<MP>>());
+
+    select_strategy_for(
+        &problem,
+        elem_size,
+        &DeviceCapabilities::new::<R, MP>(client),
+    )
+}
+
+/// Select the strategy of the `problem` whose stage elements are of `elem_size` bytes, on a device
+/// with the given capabilities.
+pub fn select_strategy_for(
+    problem: &MatmulProblem,
+    elem_size: usize,
+    device: &DeviceCapabilities,
+) -> StrategySelection {
+    let (m, n, k) = (problem.m, problem.n, problem.k);
+    let kind = MatmulKind::from(MatmulProblemSize {
+        m: m as u32,
+        n: n as u32,
+        k: k as u32,
+    });
+    let long_k = k >= LONG_K && k >= LONG_K_RATIO * Ord::max(m, n);
+
+    let simple = Strategy::Simple(SyncLoadingStrategy::Cyclic, Default::default());
+    let simple_unit = Strategy::SimpleUnit(Default::default());
+    let double_unit = Strategy::DoubleUnit(Default::default());
+    let double_buffering =
+        Strategy::DoubleBuffering(SyncPartialLoadingStrategy::Hybrid, Default::default());
+
+    let selection = |strategy, reason, mut candidates: Vec<Strategy>| {
+        candidates.retain(|candidate| device.supports(candidate));
+
+        StrategySelection {
+            strategy,
+            reason,
+            candidates,
+        }
+    };
+
+    let num_batches = problem.num_batches();
+    if num_batches == 0 || m == 0 || n == 0 || k == 0 {
+        return selection(
+            Strategy::Naive,
+            SelectionReason::Degenerate {
+                num_batches,
+                m,
+                n,
+                k,
+            },
+            Vec::new(),
+        );
+    }
+
+    if m == 1
+        && k > 1
+        && device.plane_ops
+        && problem.lhs_layout == MatrixLayout::RowMajor
+        && problem.rhs_layout == MatrixLayout::ColMajor
+    {
+        let (strategy, other) = match long_k {
+            true => (
+                Strategy::DoubleVecMat(Default::default()),
+                Strategy::SimpleVecMat(Default::default()),
+            ),
+            false => (
+                Strategy::SimpleVecMat(Default::default()),
+                Strategy::DoubleVecMat(Default::default()),
+            ),
+        };
+        return selection(
+            strategy,
+            SelectionReason::VecMat { k, long_k },
+            vec![other, simple_unit],
+        );
+    }
+
+    let rhs_broadcast = problem.rhs_batches.iter().product::<usize>() == 1;
+    if n == 1
+        && k > 1
+        && device.plane_ops
+        && (rhs_broadcast || problem.rhs_batches == problem.lhs_batches)
+    {
+        return selection(
+            Strategy::BatchedGemv,
+            SelectionReason::BatchedGemv { num_batches, m, k },
+            vec![simple_unit, Strategy::Naive],
+        );
+    }
+
+    if kind != MatmulKind::General {
+        return selection(
+            simple_unit,
+            SelectionReason::Vector { kind },
+            vec![double_unit, Strategy::Naive],
+        );
+    }
+
+    if num_batches > 1 && m <= MAX_TINY_SIZE && n <= MAX_TINY_SIZE && k <= MAX_TINY_SIZE {
+        return selection(
+            Strategy::BatchedTiny,
+            SelectionReason::BatchOfSmall {
+                num_batches,
+                m,
+                n,
+                k,
+            },
+            vec![simple_unit, simple],
+        );
+    }
+
+    if !device.accelerated {
+        // The same unit matmuls with swizzled stages, which only pay off once the stages are read
+        // enough for the bank conflicts to matter.
+        let swizzled = match long_k {
+            true => Strategy::DoubleUnit(Selection::Inferred(DoubleUnitSelectionArgs {
+                stage_swizzle: true,
+                ..Default::default()
+            })),
+            false => Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
+                stage_swizzle: true,
+                ..Default::default()
+            })),
+        };
+        let (strategy, other) = match long_k {
+            true => (double_unit, simple_unit),
+            false => (simple_unit, double_unit),
+        };
+        return selection(
+            strategy,
+            SelectionReason::Unaccelerated { long_k },
+            vec![swizzled, other, Strategy::Naive],
+        );
+    }
+
+    if Ord::max(m, n) >= SKINNY_RATIO * Ord::min(m, n) {
+        return selection(
+            Strategy::Simple(
+                SyncLoadingStrategy::Cyclic,
+                Selection::Inferred(SimpleArgs { multi_rows: true }),
+            ),
+            SelectionReason::TallSkinny { m, n },
+            vec![simple, double_buffering],
+        );
+    }
+
+    // Two stages of both inputs are in shared memory when double buffering.
+    let double_buffering_shared_memory = 2 * 2 * STAGE_SIZE * STAGE_SIZE_K * elem_size;
+    let num_cubes = num_batches * m.div_ceil(STAGE_SIZE) * n.div_ceil(STAGE_SIZE);
+    let fills_device = device
+        .num_streaming_multiprocessors
+        .is_some_and(|num_sms| num_cubes >= num_sms as usize);
+    if long_k && !fills_device && double_buffering_shared_memory <= device.max_shared_memory_size {
+        return selection(
+            double_buffering,
+            SelectionReason::LongK {
+                k,
+                num_cubes,
+                num_streaming_multiprocessors: device.num_streaming_multiprocessors,
+            },
+            vec![simple, Strategy::OrderedDoubleBuffering(Default::default())],
+        );
+    }
+
+    selection(
+        simple,
+        SelectionReason::General,
+        vec![
+            double_buffering,
+            Strategy::OrderedDoubleBuffering(Default::default()),
+            Strategy::Simple(
+                SyncLoadingStrategy::Cyclic,
+                Selection::Inferred(SimpleArgs { multi_rows: true }),
+            ),
+        ],
+    )
+}
+
+/// The problem of the matmul of the given tensors, with the layouts the layered matmuls infer from
+/// the strides. Highly permuted inputs are made contiguous before being multiplied.
+fn problem_from_handles<R: Runtime>(
+    lhs: &TensorHandleRef<'_, R>,
+    rhs: &TensorHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> MatmulProblem {
+    let layout = |strides: &[usize]| match matrix_batch_layout(strides) {
+        MatrixBatchLayout::MildlyPermuted {
+            transposed: true, ..
+        } => MatrixLayout::ColMajor,
+        _ => MatrixLayout::RowMajor,
+    };
+    let rank = lhs.shape.len();
+
+    MatmulProblem {
+        m: lhs.shape[rank - 2],
+        n: rhs.shape[rank - 1],
+        k: lhs.shape[rank - 1],
+        lhs_batches: lhs.shape[..rank - 2].to_vec(),
+        rhs_batches: rhs.shape[..rank - 2].to_vec(),
+        lhs_layout: layout(lhs.strides),
+        rhs_layout: layout(rhs.strides),
+        out_layout: layout(out.strides),
+        lhs_format: MemoryFormat::Strided,
+        rhs_format: MemoryFormat::Strided,
+    }
+}
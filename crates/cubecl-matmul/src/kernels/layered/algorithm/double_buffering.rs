@@ -8,7 +8,9 @@ use crate::components::global::multi_stage::double_buffering::DoubleBufferingMat
 use crate::components::stage::{
     ColMajorTilingOrder, PartialStageReaderFamily, PlaneMatmulFamily, RowMajorTilingOrder,
 };
-use crate::components::{MatmulElems, MatmulLineSizes, MatmulSelection, MatmulSetupError};
+use crate::components::{
+    CacheHints, MatmulElems, MatmulLineSizes, MatmulSelection, MatmulSetupError,
+};
 use crate::components::{MatmulProblem, MultiRowStrategy, tile};
 use crate::components::{
     batch::{PartitionedBatchMatmulFamily, RowMajorGlobalPartitionMatmul},
@@ -39,6 +41,8 @@ pub struct HybridDoubleBufferingAlgorithm<TMM> {
 #[derive(Default, Debug, Clone, Copy)]
 pub struct DoubleBufferingArgs {
     pub specialized: bool,
+    /// How the loaders read the lhs and the rhs through the caches.
+    pub cache_hints: CacheHints,
 }
 
 impl<TMM> base::Algorithm for CyclicDoubleBufferingAlgorithm<TMM>
@@ -82,6 +86,10 @@ where
                 ..Default::default()
             },
         )
+        .map(|selection| MatmulSelection {
+            cache_hints: args.cache_hints,
+            ..selection
+        })
     }
 }
 
@@ -127,6 +135,10 @@ where
                 ..Default::default()
             },
         )
+        .map(|selection| MatmulSelection {
+            cache_hints: args.cache_hints,
+            ..selection
+        })
     }
 }
 
@@ -171,5 +183,9 @@ where
                 ..Default::default()
             },
         )
+        .map(|selection| MatmulSelection {
+            cache_hints: args.cache_hints,
+            ..selection
+        })
     }
 }
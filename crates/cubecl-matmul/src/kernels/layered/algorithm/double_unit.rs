@@ -25,6 +25,8 @@ pub struct DoubleUnitAlgorithm {}
 #[derive(Default, Clone, Debug)]
 pub struct DoubleUnitSelectionArgs {
     pub tile_size: TileSizeSelection,
+    /// Whether the lines of the stages are swizzled to spread the reads over the banks.
+    pub stage_swizzle: bool,
 }
 
 impl Algorithm for DoubleUnitAlgorithm {
@@ -48,7 +50,7 @@ impl Algorithm for DoubleUnitAlgorithm {
         _elems: MatmulElems,
         args: &Self::SelectionArgs,
     ) -> Result<MatmulSelection, MatmulSetupError> {
-        Ok(unit_matmul_selection::<R>(
+        let selection = unit_matmul_selection::<R>(
             client,
             problem,
             plane_dim,
@@ -57,7 +59,12 @@ impl Algorithm for DoubleUnitAlgorithm {
                 tile: args.tile_size,
                 ..Default::default()
             },
-        ))
+        );
+
+        Ok(MatmulSelection {
+            stage_swizzle: args.stage_swizzle,
+            ..selection
+        })
     }
 
     fn select_plane_dim<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> u32 {
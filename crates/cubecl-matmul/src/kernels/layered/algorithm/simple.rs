@@ -96,8 +96,9 @@ fn selection_multi_rows<R: Runtime, TMM: TileMatmulFamily>(
     plane_dim: u32,
     elems: MatmulElems,
 ) -> Result<MatmulSelection, MatmulSetupError> {
+    let features = client.device_features();
     let supported = |m: u32, n: u32, k: u32| {
-        client.properties().features.cmma.contains(&MmaConfig {
+        features.supports_accelerated_tile(&MmaConfig {
             a_type: elems.lhs_register,
             b_type: elems.rhs_register,
             cd_type: elems.acc_register,
@@ -38,6 +38,8 @@ pub struct SimpleUnitAlgorithm<
 #[derive(Default, Clone, Debug)]
 pub struct SimpleUnitSelectionArgs {
     pub tile_size: TileSizeSelection,
+    /// Whether the lines of the stages are swizzled to spread the reads over the banks.
+    pub stage_swizzle: bool,
 }
 
 impl<LL, RL> Algorithm for SimpleUnitAlgorithm<LL, RL>
@@ -62,7 +64,7 @@ where
         _elems: MatmulElems,
         args: &Self::SelectionArgs,
     ) -> Result<MatmulSelection, MatmulSetupError> {
-        Ok(unit_matmul_selection::<R>(
+        let selection = unit_matmul_selection::<R>(
             client,
             problem,
             plane_dim,
@@ -78,7 +80,12 @@ where
                     TileSizeSelection::MaxTileSize => PartitionScaling::Enabled,
                 },
             },
-        ))
+        );
+
+        Ok(MatmulSelection {
+            stage_swizzle: args.stage_swizzle,
+            ..selection
+        })
     }
 
     fn select_plane_dim<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> u32 {
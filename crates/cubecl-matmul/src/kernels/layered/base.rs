@@ -4,12 +4,12 @@ use crate::components::{
     batch::{BatchMatmulFamily, CubeCountInputArgs},
 };
 use crate::components::{
-    AvailableLineSizes, InputRuntimeArg, LhsG, LhsS, MatmulAvailabilityError, MatmulLineSizes,
-    MatmulPrecision, MatmulProblem, MatmulSelection, MatmulSetupError, MatmulSpec, MatrixLayout,
-    OutputRuntimeArg, RhsG, RhsS,
+    AvailableLineSizes, BlockedDim, InputRuntimeArg, LhsG, LhsS, MatmulAvailabilityError,
+    MatmulLineSizes, MatmulPrecision, MatmulProblem, MatmulSelection, MatmulSetupError, MatmulSpec,
+    MatrixLayout, MemoryFormat, OutputRuntimeArg, RhsG, RhsS,
 };
 use crate::components::{global::args::TensorMapArgs, tile::TileMatmulFamily};
-use crate::kernels::layered::selector::launch_kernel_concrete;
+use crate::kernels::{degenerate, layered::selector::launch_kernel_concrete};
 use crate::{MatmulInputHandle, MatmulInputHandleRef};
 use core::any::TypeId;
 use cubecl_core::{Runtime, client::ComputeClient, frontend::TensorHandleRef};
@@ -86,17 +86,52 @@ pub fn launch_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
     out: &TensorHandleRef<'_, R>,
     selection: &Selection<A::SelectionArgs>,
 ) -> Result<(), MatmulSetupError> {
-    let check_layout = |tensor: &TensorHandleRef<'_, R>| match matrix_batch_layout(tensor.strides) {
-        MatrixBatchLayout::Contiguous => (false, false),
-        MatrixBatchLayout::MildlyPermuted {
-            transposed,
-            batch_swap: _,
-        } => (false, transposed),
-        MatrixBatchLayout::HighlyPermuted => (true, false),
+    launch_ref_with_formats::<R, MP, A>(
+        client,
+        lhs,
+        rhs,
+        out,
+        (MemoryFormat::Strided, MemoryFormat::Strided),
+        selection,
+    )
+}
+
+/// Launch a matrix multiplication kernel on inputs of the given [memory formats](MemoryFormat).
+///
+/// The shapes of blocked inputs are the shapes of their matrices, whose elements are found in the
+/// blocks, the strides only giving the offsets of the batches. Only the algorithms whose global
+/// matmuls load inputs line by line support blocked inputs, the others returning an error.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref_with_formats<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &MatmulInputHandleRef<'_, R>,
+    rhs: &MatmulInputHandleRef<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+    formats: (MemoryFormat, MemoryFormat),
+    selection: &Selection<A::SelectionArgs>,
+) -> Result<(), MatmulSetupError> {
+    if degenerate::launch_ref::<R, AccG<MP>>(client, lhs.data(), rhs.data(), out) {
+        return Ok(());
+    }
+
+    // Blocked inputs are read along their blocked dimension, and are never made contiguous
+    let check_layout = |tensor: &TensorHandleRef<'_, R>, format: MemoryFormat| {
+        if let Some(layout) = format.matrix_layout() {
+            return (false, layout == MatrixLayout::ColMajor);
+        }
+
+        match matrix_batch_layout(tensor.strides) {
+            MatrixBatchLayout::Contiguous => (false, false),
+            MatrixBatchLayout::MildlyPermuted {
+                transposed,
+                batch_swap: _,
+            } => (false, transposed),
+            MatrixBatchLayout::HighlyPermuted => (true, false),
+        }
     };
 
-    let (lhs_make_contiguous, lhs_transposed) = check_layout(lhs.data());
-    let (rhs_make_contiguous, rhs_transposed) = check_layout(rhs.data());
+    let (lhs_make_contiguous, lhs_transposed) = check_layout(lhs.data(), formats.0);
+    let (rhs_make_contiguous, rhs_transposed) = check_layout(rhs.data(), formats.1);
 
     let lhs_owned;
     let rhs_owned;
@@ -129,6 +164,7 @@ pub fn launch_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
         rhs,
         out,
         (lhs_transposed, rhs_transposed),
+        formats,
         selection,
     )
 }
@@ -140,6 +176,7 @@ fn launch_inner_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
     rhs_handle: &MatmulInputHandleRef<'_, R>,
     out: &TensorHandleRef<'_, R>,
     transposed: (bool, bool),
+    formats: (MemoryFormat, MemoryFormat),
     selection: &Selection<A::SelectionArgs>,
 ) -> Result<(), MatmulSetupError> {
     let lhs = lhs_handle.data();
@@ -177,6 +214,16 @@ fn launch_inner_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
         false => MatrixLayout::RowMajor,
     };
 
+    // A transposed output is written with lines along `m`, other permutations are written
+    // element-wise through the strides
+    let out_layout = match matrix_batch_layout(out.strides) {
+        MatrixBatchLayout::MildlyPermuted {
+            transposed: true,
+            batch_swap: _,
+        } => MatrixLayout::ColMajor,
+        _ => MatrixLayout::RowMajor,
+    };
+
     let problem = MatmulProblem {
         m: m as usize,
         n: n as usize,
@@ -185,14 +232,23 @@ fn launch_inner_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
         rhs_batches: rhs.shape[..rhs.shape.len() - 2].to_vec(),
         lhs_layout,
         rhs_layout,
+        out_layout,
+        lhs_format: formats.0,
+        rhs_format: formats.1,
     };
 
     let line_sizes = AvailableLineSizes::from_types::<R>(&lhs_elem, &rhs_elem, &acc_elem);
     let line_sizes = A::filter_line_sizes(line_sizes);
+    let line_sizes = match blocked_line_size_divisor(lhs.shape, formats.0) {
+        Some(divisor) => line_sizes.filter_lhs(|x| divisor.is_multiple_of(*x as usize)),
+        None => line_sizes.filter_lhs_with_tensor(lhs.strides, lhs.shape, problem.lhs_layout),
+    };
+    let line_sizes = match blocked_line_size_divisor(rhs.shape, formats.1) {
+        Some(divisor) => line_sizes.filter_rhs(|x| divisor.is_multiple_of(*x as usize)),
+        None => line_sizes.filter_rhs_with_tensor(rhs.strides, rhs.shape, problem.rhs_layout),
+    };
     let line_sizes = line_sizes
-        .filter_lhs_with_tensor(lhs.strides, lhs.shape, problem.lhs_layout)
-        .filter_rhs_with_tensor(rhs.strides, rhs.shape, problem.rhs_layout)
-        .filter_out_with_tensor(out.strides, out.shape)
+        .filter_out_with_tensor(out.strides, out.shape, problem.out_layout)
         .pick_max()?;
 
     let fix_plane_dim = |plane_dim: u32| {
@@ -211,6 +267,28 @@ fn launch_inner_ref<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
     )
 }
 
+/// The number the line sizes of a blocked input must divide, so that lines never cross blocks or
+/// the end of the blocked dimension, or `None` for strided inputs.
+fn blocked_line_size_divisor(shape: &[usize], format: MemoryFormat) -> Option<usize> {
+    let MemoryFormat::Blocked { block_size, dim } = format else {
+        return None;
+    };
+    let rank = shape.len();
+    let blocked = match dim {
+        BlockedDim::Row => shape[rank - 2],
+        BlockedDim::Col => shape[rank - 1],
+    };
+
+    Some(gcd(block_size as usize, blocked))
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
 #[allow(clippy::result_large_err, clippy::too_many_arguments)]
 fn launch_inner_ref_fix_dtype<R: Runtime, MP: MatmulPrecision, A: Algorithm>(
     client: &ComputeClient<R::Server, R::Channel>,
@@ -317,6 +395,9 @@ pub fn matmul_cmma_tma_ref_no_check<R: Runtime, MP: MatmulPrecision, A: Algorith
         rhs_batches: [batch_rhs].to_vec(),
         lhs_layout,
         rhs_layout,
+        out_layout: MatrixLayout::RowMajor,
+        lhs_format: MemoryFormat::Strided,
+        rhs_format: MemoryFormat::Strided,
     };
 
     let plane_size = client.properties().hardware.plane_size_max;
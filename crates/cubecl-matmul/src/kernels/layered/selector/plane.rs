@@ -75,9 +75,17 @@ pub fn plane_matmul_selection<TMM: TileMatmulFamily, R: Runtime>(
         problem.m,
     );
 
+    // With 8x8 instructions, like the simdgroup matrices of Apple GPUs, each plane computes
+    // twice as many tiles along m and n, so the stages cover the same area as with 16x16 ones.
+    let tiles_factor = if tile_size.m() == 8 && tile_size.n() == 8 {
+        2
+    } else {
+        1
+    };
+
     let tiles_per_partition = PartitionSize::new(
-        rows_per_plane as u32,
-        partition_shape_n as u32,
+        tiles_factor * rows_per_plane as u32,
+        tiles_factor * partition_shape_n as u32,
         options
             .partition_k
             .unwrap_or_else(|| plane_dim / tile_size.k()),
@@ -156,7 +164,8 @@ fn select_size(
 
 /// A heuristic to choose the instruction to use, based on input shape
 ///
-/// Will use 16x16 for balanced matrices, and 32x8 or 8x32 for degenerated ones.
+/// Will use 16x16 for balanced matrices, and 32x8 or 8x32 for degenerated ones. When none of
+/// those are supported, the largest instruction reported by the device for the types is used.
 #[allow(clippy::type_complexity)]
 pub fn find_instruction_size(
     properties: Option<(&DeviceProperties, (StorageType, StorageType, StorageType))>,
@@ -180,17 +189,35 @@ pub fn find_instruction_size(
 
     if m >= 4 * n && supported(32, 8, 16) {
         (32, 8, 16).into()
-    } else if n >= 4 * n && supported(8, 32, 16) {
+    } else if n >= 4 * m && supported(8, 32, 16) {
         (8, 32, 16).into()
     } else if supported(16, 16, 16) {
         (16, 16, 16).into()
     } else if supported(8, 8, 8) {
         (8, 8, 8).into()
+    } else if let Some(size) = largest_instruction_size(properties) {
+        size
     } else {
         (16, 16, 8).into()
     }
 }
 
+fn largest_instruction_size(
+    properties: Option<(&DeviceProperties, (StorageType, StorageType, StorageType))>,
+) -> Option<TileSize> {
+    let (props, (a_type, b_type, cd_type)) = properties?;
+
+    props
+        .features
+        .cmma
+        .iter()
+        .filter(|config| {
+            config.a_type == a_type && config.b_type == b_type && config.cd_type == cd_type
+        })
+        .max_by_key(|config| config.m * config.n * config.k)
+        .map(|config| (config.m, config.n, config.k).into())
+}
+
 fn selection_tiny<R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     problem: &MatmulProblem,
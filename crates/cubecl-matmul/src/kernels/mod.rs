@@ -1,5 +1,35 @@
 /// The layered matmul combines multiple component-based algorithm implementations with selection logic to pick the optimal kernel for a set of parameters.
 pub mod layered;
 
+/// Matmul of batches of tiny matrices, each computed by a plane without a shared memory stage.
+pub mod batched_tiny;
+
+/// Batched matrix-vector product, with the vector broadcast over the batches of the matrix.
+pub mod batched_gemv;
+
+/// Matmul of `i8` operands quantized with scales and zero points, correcting the matmul of the
+/// quantized values with the sums of their rows and columns.
+pub mod affine_int8;
+
+/// Matmul of 8-bit float operands scaled per tensor, on the FP8 tensor cores when available, with
+/// the scales applied in the epilogue.
+pub mod scaled_fp8;
+
+/// Matmul with the bias, the GELU and the residual of the feed-forward blocks of transformers
+/// fused into the write of the output.
+pub mod bias_gelu_residual;
+
+/// Selection of the strategy of a matmul from its problem and the device, with the reason of the choice.
+pub mod heuristic;
+
+/// Matmuls with an empty output or reduction, computed without a matmul kernel.
+pub mod degenerate;
+
 /// Naive non-cooperative matmul without tiling that can be very fast on small matrices.
 pub mod naive;
+
+/// Symmetric rank-k update computing a single triangle of the output with matmuls stopping at the diagonal.
+pub mod syrk;
+
+/// Blocked triangular solve with multiple right-hand sides, updating the rows left to solve with matmuls.
+pub mod trsm;
@@ -3,19 +3,26 @@
 //! Each local unit will compute a single element of the output matrix.
 use cubecl::prelude::*;
 use cubecl_core::{
-    self as cubecl,
+    self as cubecl, LaunchShape,
     ir::{ElemType, IntKind, UIntKind},
+    unfold_cube_pos_yz,
 };
 
-use cubecl_std::tensor::{MatrixBatchLayout, TensorHandle, into_contiguous, matrix_batch_layout};
+use cubecl_std::tensor::{
+    MatrixBatchLayout, TensorHandle, into_contiguous_matrix, matrix_batch_layout,
+};
 
-use crate::components::{MatmulAvailabilityError, MatmulSetupError};
+use crate::components::MatmulSetupError;
 
 #[cube(launch_unchecked)]
 fn matmul_kernel<I: Numeric, M: Numeric, O: Numeric>(
     lhs: &Tensor<Line<I>>,
     rhs: &Tensor<Line<I>>,
     out: &mut Tensor<O>,
+    // number of tiles along the columns, with the batches folded with them
+    fold: u32,
+    // number of matrices of the output
+    batch_count: u32,
     // number of dimensions not involved in the matmul
     #[comptime] num_batches: Option<u32>,
 ) {
@@ -27,11 +34,11 @@ fn matmul_kernel<I: Numeric, M: Numeric, O: Numeric>(
     let n_cols = rhs.shape(rank - 1);
     let mut k = rhs.shape(rank - 2);
 
-    let batch_pos = ABSOLUTE_POS_Z;
+    let (tile_col, batch_pos) = unfold_cube_pos_yz(fold);
     let row = CUBE_DIM_X * CUBE_POS_X + UNIT_POS_X;
-    let col = CUBE_DIM_Y * CUBE_POS_Y + UNIT_POS_Y;
+    let col = CUBE_DIM_Y * tile_col + UNIT_POS_Y;
 
-    if row >= n_rows || col >= n_cols {
+    if row >= n_rows || col >= n_cols || batch_pos >= batch_count {
         terminate!();
     }
 
@@ -114,7 +121,7 @@ pub fn launch<R: Runtime, EI: Numeric, EO: Numeric>(
     let rhs_layout = matrix_batch_layout(&rhs.strides);
 
     let lhs = if !matches!(lhs_layout, MatrixBatchLayout::Contiguous) {
-        into_contiguous::<R, EI>(client, &lhs.as_ref())
+        into_contiguous_matrix::<R, EI>(client, &lhs.as_ref())
     } else {
         lhs
     };
@@ -127,7 +134,7 @@ pub fn launch<R: Runtime, EI: Numeric, EO: Numeric>(
         rhs.strides.swap(dim1, dim2);
         rhs.shape.swap(dim1, dim2);
 
-        let mut rhs = into_contiguous::<R, EI>(client, &rhs.as_ref());
+        let mut rhs = into_contiguous_matrix::<R, EI>(client, &rhs.as_ref());
 
         rhs.strides.swap(dim1, dim2);
         rhs.shape.swap(dim1, dim2);
@@ -151,13 +158,15 @@ pub fn launch<R: Runtime, EI: Numeric, EO: Numeric>(
         MatrixBatchLayout::HighlyPermuted => correct_rhs_layout(rhs),
     };
 
-    let cube_count = simple_cube_count(
-        &lhs.shape,
-        &rhs_original_shape,
-        out.shape,
+    let num_batches = out.shape[..ndims - 2].iter().product::<usize>();
+    let config = LaunchShape::tiles_2d(
+        lhs.shape[dim2],
+        rhs_original_shape[dim1],
         cube_dim_x,
         cube_dim_y,
-    )?;
+    )
+    .batches(num_batches)
+    .build::<R>()?;
 
     let vectorization_factor = match lhs.shape[ndims - 1] % 4 == 0 {
         true => 4,
@@ -176,47 +185,16 @@ pub fn launch<R: Runtime, EI: Numeric, EO: Numeric>(
     unsafe {
         launch(
             client,
-            cube_count,
-            CubeDim::new(cube_dim_x as u32, cube_dim_y as u32, 1),
+            config.cube_count,
+            config.cube_dim,
             lhs.as_arg(vectorization_factor),
             rhs.as_arg(vectorization_factor),
             out.as_tensor_arg(1),
+            config.fold_arg(),
+            ScalarArg::new(num_batches as u32),
             Some(ndims as u32 - 2),
         );
     };
 
     Ok(())
 }
-
-#[allow(clippy::result_large_err)]
-fn simple_cube_count(
-    lhs_shape: &[usize],
-    rhs_shape: &[usize],
-    output_shape: &[usize],
-    cube_dim_x: usize,
-    cube_dim_y: usize,
-) -> Result<CubeCount, MatmulSetupError> {
-    let ndims = lhs_shape.len();
-    let num_rows = lhs_shape[ndims - 2];
-    let num_cols = rhs_shape[ndims - 1];
-
-    let cubes_x = f32::ceil(num_rows as f32 / cube_dim_x as f32) as u32;
-    let cubes_y = f32::ceil(num_cols as f32 / cube_dim_y as f32) as u32;
-    let mut num_iter = 1u32;
-
-    #[allow(clippy::needless_range_loop)]
-    for i in 0..ndims - 2 {
-        num_iter *= output_shape[i] as u32;
-    }
-
-    let result = CubeCount::Static(cubes_x, cubes_y, num_iter);
-    let max_cube_count = u16::MAX as u32;
-
-    if cubes_x > max_cube_count || cubes_y > max_cube_count || num_iter > max_cube_count {
-        return Err(MatmulSetupError::Unavailable(
-            MatmulAvailabilityError::CubeCountTooBig(result),
-        ));
-    }
-
-    Ok(result)
-}
@@ -0,0 +1,385 @@
+//! Matmul of 8-bit float operands with a scale per tensor, like the activations and the weights of
+//! FP8 inference.
+//!
+//! Each operand stores its values divided by its scale, in `e4m3` or `e5m2`, so that
+//!
+//! `out = s_a · s_b · Σ a·b`
+//!
+//! The products of the 8-bit values are accumulated in `f32`, and the scales are applied in the
+//! epilogue, before the output is narrowed to its element type.
+//!
+//! On devices with FP8 tensor cores, from sm_89, each plane computes `16x8` tiles of the output
+//! with the `m16n8k32` MMA, its registers read straight from global memory. Other devices storing
+//! 8-bit floats widen the elements to `f32` and multiply them with FMAs, in lines along `n`, and
+//! the devices that can't store them reject the matmul.
+use cubecl::prelude::*;
+use cubecl_core::{
+    self as cubecl, LaunchShape,
+    ir::{ElemType, FloatKind, MatrixIdent, StorageType},
+    tensor_line_size_parallel,
+};
+use cubecl_runtime::{MmaConfig, TypeUsage};
+
+use crate::{
+    components::{MatmulAvailabilityError, MatmulSetupError},
+    kernels::batched_tiny::batch_offsets,
+};
+
+/// The largest difference from a matmul of the operands widened to `f64`, relative to the sum of
+/// the magnitudes of the scaled products, before the output is narrowed.
+///
+/// Looser than the rounding of `f32`, as the tensor cores accumulate the FP8 products with fewer
+/// bits of mantissa.
+pub const TOLERANCE: f32 = 1e-3;
+
+/// The size of the tiles of the output computed by a plane with the tensor cores.
+const TILE_M: u32 = 16;
+const TILE_N: u32 = 8;
+/// The size of the reduction of each MMA.
+const TILE_K: u32 = 32;
+/// The number of units of a cube.
+const CUBE_SIZE: u32 = 256;
+
+/// An 8-bit float tensor storing its values divided by a scale.
+///
+/// The `scale`, of `f32`, is a vector with a single element for the whole tensor.
+#[derive(Debug)]
+pub struct ScaledFp8Ref<'a, R: Runtime> {
+    pub data: TensorHandleRef<'a, R>,
+    pub scale: TensorHandleRef<'a, R>,
+}
+
+impl<'a, R: Runtime> ScaledFp8Ref<'a, R> {
+    pub fn new(data: TensorHandleRef<'a, R>, scale: TensorHandleRef<'a, R>) -> Self {
+        Self { data, scale }
+    }
+}
+
+/// Compute the matmul `out = (s_a · lhs) · (s_b · rhs)` of 8-bit float operands scaled per
+/// tensor.
+///
+/// The `lhs` is of shape `[batch.., m, k]`, the `rhs` of shape `[batch.., k, n]` and the `out` of
+/// shape `[batch.., m, n]`, with the batch dimensions of the operands broadcast to the ones of the
+/// output. Any strides are supported.
+///
+/// The output is within [`TOLERANCE`] of the matmul of the scaled operands. Returns
+/// [`MatmulAvailabilityError::TypesUnavailable`] when the device can't store the operands.
+#[allow(clippy::result_large_err)]
+pub fn launch_ref<R: Runtime, EL: Numeric, ER: Numeric, EO: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    lhs: &ScaledFp8Ref<'_, R>,
+    rhs: &ScaledFp8Ref<'_, R>,
+    out: &TensorHandleRef<'_, R>,
+) -> Result<(), MatmulSetupError> {
+    let (lhs_type, rhs_type) = (
+        EL::as_type_native_unchecked(),
+        ER::as_type_native_unchecked(),
+    );
+    let is_fp8 = |ty: StorageType| {
+        matches!(
+            ty,
+            StorageType::Scalar(ElemType::Float(FloatKind::E4M3 | FloatKind::E5M2))
+        )
+    };
+    if !is_fp8(lhs_type) || !is_fp8(rhs_type) {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected e4m3 or e5m2 operands, got {lhs_type:?} and {rhs_type:?}"
+        ))));
+    }
+
+    let rank = out.shape.len();
+    if rank < 2 || lhs.data.shape.len() != rank || rhs.data.shape.len() != rank {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a lhs [batch.., m, k], a rhs [batch.., k, n] and an output [batch.., m, n], got shapes {:?}, {:?} and {:?}",
+            lhs.data.shape, rhs.data.shape, out.shape
+        ))));
+    }
+    let (m, n, k) = (
+        out.shape[rank - 2],
+        out.shape[rank - 1],
+        lhs.data.shape[rank - 1],
+    );
+    let broadcasts = |shape: &[usize]| {
+        shape[..rank - 2]
+            .iter()
+            .zip(&out.shape[..rank - 2])
+            .all(|(size, out_size)| *size == *out_size || *size == 1)
+    };
+    if lhs.data.shape[rank - 2] != m
+        || rhs.data.shape[rank - 2..] != [k, n]
+        || !broadcasts(lhs.data.shape)
+        || !broadcasts(rhs.data.shape)
+    {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "The shapes of the lhs {:?}, the rhs {:?} and the output {:?} don't match",
+            lhs.data.shape, rhs.data.shape, out.shape
+        ))));
+    }
+    if lhs.scale.shape != [1] || rhs.scale.shape != [1] {
+        return Err(MatmulSetupError::InvalidConfig(Box::new(format!(
+            "Expected a single scale per tensor, got shapes {:?} and {:?}",
+            lhs.scale.shape, rhs.scale.shape
+        ))));
+    }
+
+    let storage = TypeUsage::Conversion | TypeUsage::Buffer;
+    if !EL::supported_uses(client).is_superset(storage)
+        || !ER::supported_uses(client).is_superset(storage)
+    {
+        return Err(MatmulAvailabilityError::TypesUnavailable {
+            lhs: lhs_type,
+            rhs: rhs_type,
+            output: EO::as_type_native_unchecked(),
+        }
+        .into());
+    }
+
+    let num_matrices = out.shape[..rank - 2].iter().product::<usize>();
+    if num_matrices == 0 || m == 0 || n == 0 {
+        return Ok(());
+    }
+
+    if mma_supported::<R, EL, ER>(client) {
+        let num_tiles = num_matrices * m.div_ceil(TILE_M as usize) * n.div_ceil(TILE_N as usize);
+        let plane_dim = client.properties().hardware.plane_size_max;
+        let planes_per_cube = CUBE_SIZE / plane_dim;
+        // One plane per tile.
+        let cube_count = LaunchShape::elementwise(num_tiles)
+            .units_per_cube(planes_per_cube)
+            .build::<R>()?
+            .cube_count;
+
+        unsafe {
+            scaled_fp8_mma_kernel::launch_unchecked::<EL, ER, EO, R>(
+                client,
+                cube_count,
+                CubeDim::new_2d(plane_dim, planes_per_cube),
+                lhs.data.as_tensor_arg(1),
+                rhs.data.as_tensor_arg(1),
+                lhs.scale.as_tensor_arg(1),
+                rhs.scale.as_tensor_arg(1),
+                out.as_tensor_arg(1),
+                ScalarArg::new(num_tiles as u32),
+            );
+        }
+
+        return Ok(());
+    }
+
+    // Lines along `n` need both the rhs and the output to be contiguous along it.
+    let line_size = Ord::min(
+        tensor_line_size_parallel(
+            R::line_size_type(&rhs_type),
+            rhs.data.shape,
+            rhs.data.strides,
+            rank - 1,
+        ),
+        tensor_line_size_parallel(
+            R::line_size_type(&EO::as_type_native_unchecked()),
+            out.shape,
+            out.strides,
+            rank - 1,
+        ),
+    );
+    let num_lines = num_matrices * m * n / line_size as usize;
+    let config = LaunchShape::elementwise(num_lines)
+        .units_per_cube(CUBE_SIZE)
+        .build::<R>()?;
+
+    unsafe {
+        scaled_fp8_widened_kernel::launch_unchecked::<EL, ER, EO, R>(
+            client,
+            config.cube_count,
+            config.cube_dim,
+            lhs.data.as_tensor_arg(1),
+            rhs.data.as_tensor_arg(line_size),
+            lhs.scale.as_tensor_arg(1),
+            rhs.scale.as_tensor_arg(1),
+            out.as_tensor_arg(line_size),
+            ScalarArg::new(num_lines as u32),
+        );
+    }
+
+    Ok(())
+}
+
+/// Whether the tensor cores multiply the 8-bit floats of the operands.
+fn mma_supported<R: Runtime, EL: Numeric, ER: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+) -> bool {
+    client.properties().features.mma.contains(&MmaConfig {
+        a_type: EL::as_type_native_unchecked(),
+        b_type: ER::as_type_native_unchecked(),
+        cd_type: f32::as_type_native_unchecked(),
+        m: TILE_M,
+        n: TILE_N,
+        k: TILE_K,
+    })
+}
+
+/// Each plane computes a `16x8` tile of the output with the tensor cores, the elements of the
+/// registers outside of the matrices set to zero.
+#[cube(launch_unchecked)]
+fn scaled_fp8_mma_kernel<EL: Numeric, ER: Numeric, EO: Numeric>(
+    lhs: &Tensor<EL>,
+    rhs: &Tensor<ER>,
+    lhs_scale: &Tensor<f32>,
+    rhs_scale: &Tensor<f32>,
+    out: &mut Tensor<EO>,
+    num_tiles: u32,
+) {
+    let tile = CUBE_POS * CUBE_DIM_Y + UNIT_POS_Y;
+    if tile >= num_tiles {
+        terminate!();
+    }
+
+    let (tile_m, tile_n, tile_k) = comptime!((TILE_M, TILE_N, TILE_K));
+    let rank = out.rank();
+    let (m, n, k) = (
+        out.shape(rank - 2),
+        out.shape(rank - 1),
+        lhs.shape(rank - 1),
+    );
+    let tiles_n = (n + tile_n - 1) / tile_n;
+    let tiles_per_matrix = ((m + tile_m - 1) / tile_m) * tiles_n;
+    let batch = tile / tiles_per_matrix;
+    let row_start = ((tile % tiles_per_matrix) / tiles_n) * tile_m;
+    let col_start = (tile % tiles_n) * tile_n;
+    let (lhs_offset, rhs_offset, out_offset) = batch_offsets(lhs, rhs, out, batch);
+
+    let def = cmma::MmaDefinition::<EL, ER, f32>::new(tile_m, tile_n, tile_k);
+    let lane_id = UNIT_POS_PLANE;
+
+    let elem_count_a = def.elems_per_lane(MatrixIdent::A);
+    let line_size_a = def.line_size(MatrixIdent::A);
+    let line_count_a = comptime!(elem_count_a / line_size_a);
+
+    let elem_count_b = def.elems_per_lane(MatrixIdent::B);
+    let line_size_b = def.line_size(MatrixIdent::B);
+    let line_count_b = comptime!(elem_count_b / line_size_b);
+
+    let elem_count_acc = def.elems_per_lane(MatrixIdent::Accumulator);
+    let line_size_acc = def.line_size(MatrixIdent::Accumulator);
+    let line_count_acc = comptime!(elem_count_acc / line_size_acc);
+
+    let mut acc = Array::<Line<f32>>::vectorized(line_count_acc, line_size_acc);
+    #[unroll]
+    for i in 0..line_count_acc {
+        acc[i] = Line::empty(line_size_acc).fill(f32::new(0.0));
+    }
+
+    let zero_a = EL::cast_from(f32::new(0.0));
+    let zero_b = ER::cast_from(f32::new(0.0));
+    let num_steps = (k + tile_k - 1) / tile_k;
+
+    for step in 0..num_steps {
+        let k_start = step * tile_k;
+
+        let mut registers_a = Sequence::<Line<EL>>::new();
+        #[unroll]
+        for i in 0..line_count_a {
+            let mut reg = Line::empty(line_size_a);
+            #[unroll]
+            for j in 0..line_size_a {
+                let (row, col) = def.indices_of_nth(lane_id, i * line_size_a + j, MatrixIdent::A);
+                let (row, col) = (row_start + row, k_start + col);
+                let mut value = zero_a;
+                if row < m && col < k {
+                    value =
+                        lhs[lhs_offset + row * lhs.stride(rank - 2) + col * lhs.stride(rank - 1)];
+                }
+                reg[j] = value;
+            }
+            registers_a.push(reg);
+        }
+
+        let mut registers_b = Sequence::<Line<ER>>::new();
+        #[unroll]
+        for i in 0..line_count_b {
+            let mut reg = Line::empty(line_size_b);
+            #[unroll]
+            for j in 0..line_size_b {
+                let (row, col) = def.indices_of_nth(lane_id, i * line_size_b + j, MatrixIdent::B);
+                let (row, col) = (k_start + row, col_start + col);
+                let mut value = zero_b;
+                if row < k && col < n {
+                    value =
+                        rhs[rhs_offset + row * rhs.stride(rank - 2) + col * rhs.stride(rank - 1)];
+                }
+                reg[j] = value;
+            }
+            registers_b.push(reg);
+        }
+
+        let mut registers_c = Sequence::<Line<f32>>::new();
+        #[unroll]
+        for i in 0..line_count_acc {
+            registers_c.push(acc[i]);
+        }
+
+        let registers_d = def.execute(&registers_a, &registers_b, &registers_c);
+        #[unroll]
+        for i in 0..line_count_acc {
+            acc[i] = registers_d[i];
+        }
+    }
+
+    // Epilogue: the scales are applied to the accumulator, then the output is narrowed.
+    let scale = lhs_scale[0] * rhs_scale[0];
+    #[unroll]
+    for i in 0..line_count_acc {
+        let reg = acc[i];
+        #[unroll]
+        for j in 0..line_size_acc {
+            let (row, col) =
+                def.indices_of_nth(lane_id, i * line_size_acc + j, MatrixIdent::Accumulator);
+            let (row, col) = (row_start + row, col_start + col);
+            if row < m && col < n {
+                out[out_offset + row * out.stride(rank - 2) + col * out.stride(rank - 1)] =
+                    EO::cast_from(reg[j] * scale);
+            }
+        }
+    }
+}
+
+/// Each unit computes a line of the output, with the elements of the operands widened to `f32`.
+#[cube(launch_unchecked)]
+fn scaled_fp8_widened_kernel<EL: Numeric, ER: Numeric, EO: Numeric>(
+    lhs: &Tensor<EL>,
+    rhs: &Tensor<Line<ER>>,
+    lhs_scale: &Tensor<f32>,
+    rhs_scale: &Tensor<f32>,
+    out: &mut Tensor<Line<EO>>,
+    num_lines: u32,
+) {
+    if ABSOLUTE_POS >= num_lines {
+        terminate!();
+    }
+
+    let rank = out.rank();
+    let line_size = out.line_size();
+    let (m, n, k) = (
+        out.shape(rank - 2),
+        out.shape(rank - 1),
+        lhs.shape(rank - 1),
+    );
+    let lines_per_row = n / line_size;
+    let lines_per_matrix = m * lines_per_row;
+    let batch = ABSOLUTE_POS / lines_per_matrix;
+    let row = (ABSOLUTE_POS % lines_per_matrix) / lines_per_row;
+    let col = (ABSOLUTE_POS % lines_per_row) * line_size;
+    let (lhs_offset, rhs_offset, out_offset) = batch_offsets(lhs, rhs, out, batch);
+
+    let mut acc = Line::empty(line_size).fill(f32::new(0.0));
+    for i in 0..k {
+        let a = lhs[lhs_offset + row * lhs.stride(rank - 2) + i * lhs.stride(rank - 1)];
+        let b =
+            rhs[(rhs_offset + i * rhs.stride(rank - 2) + col * rhs.stride(rank - 1)) / line_size];
+        acc += Line::empty(line_size).fill(f32::cast_from(a)) * Line::<f32>::cast_from(b);
+    }
+
+    // Epilogue: the scales are applied to the accumulator, then the output is narrowed.
+    let scale = Line::empty(line_size).fill(lhs_scale[0] * rhs_scale[0]);
+    out[(out_offset + row * out.stride(rank - 2) + col * out.stride(rank - 1)) / line_size] =
+        Line::<EO>::cast_from(acc * scale);
+}
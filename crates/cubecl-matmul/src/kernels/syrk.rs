@@ -0,0 +1,253 @@
+//! Symmetric rank-k update computing a single triangle of the output.
+//!
+//! The output is split into panels of rows for the lower triangle, or of columns for the upper
+//! triangle, each computed with a single matmul stopping at the diagonal, so only the tiles on or
+//! before the diagonal are launched. The cells of the diagonal tiles in the other triangle are masked
+//! when the products are written.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_std::tensor::TensorHandle;
+
+use super::trsm::{Triangle, copy_panel};
+use crate::{
+    MatmulInputHandleRef, Strategy,
+    components::{AccG, LhsG, MatmulPrecision, MatmulSetupError, RhsG},
+    launch_ref,
+};
+
+/// The number of rows or columns of the output computed by a matmul.
+const PANEL_SIZE: usize = 128;
+
+/// The rank-k update computed by [`syrk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SyrkOptions {
+    /// The half of the output that is computed, the other half is left untouched.
+    pub triangle: Triangle,
+    /// The input is given as `aᵀ`, a `[k, n]` matrix, to compute `aᵀ · a`.
+    pub transposed: bool,
+    /// Copy the computed half into the other half after the update, for a full symmetric output.
+    pub mirror: bool,
+}
+
+impl SyrkOptions {
+    /// Compute the given half of `a · aᵀ`.
+    pub fn new(triangle: Triangle) -> Self {
+        Self {
+            triangle,
+            transposed: false,
+            mirror: false,
+        }
+    }
+
+    /// Compute `aᵀ · a`, with `a` given as a `[k, n]` matrix.
+    pub fn with_transposed(mut self) -> Self {
+        self.transposed = true;
+        self
+    }
+
+    /// Copy the computed half into the other half.
+    pub fn with_mirror(mut self) -> Self {
+        self.mirror = true;
+        self
+    }
+}
+
+/// Compute `c = alpha · a · aᵀ + beta · c` on the half of the `[n, n]` matrix `c` given by the options,
+/// for the `[n, k]` matrix `a`.
+///
+/// The other half of `c` is neither read nor written, unless it is mirrored from the computed half.
+/// When `beta` is zero, `c` isn't read, so it may be uninitialized. Any strides are supported for both
+/// tensors. The products are matmuls launched with the `strategy`, in the stage precision of `MP`.
+/// The global lhs, rhs and accumulator elements of `MP` must be the same, like for `f32` and `f64`.
+#[allow(clippy::result_large_err)]
+pub fn syrk<R: Runtime, MP: MatmulPrecision>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    a: &TensorHandleRef<'_, R>,
+    c: &TensorHandleRef<'_, R>,
+    alpha: AccG<MP>,
+    beta: AccG<MP>,
+    options: SyrkOptions,
+) -> Result<(), MatmulSetupError> {
+    let elem = AccG::<MP>::as_type_native_unchecked();
+    assert!(
+        LhsG::<MP>::as_type_native_unchecked() == elem
+            && RhsG::<MP>::as_type_native_unchecked() == elem,
+        "the global elements of the precision should be the same"
+    );
+    assert!(
+        a.shape.len() == 2 && c.shape.len() == 2,
+        "a and c should be matrices"
+    );
+
+    // The `[n, k]` matrix multiplied by its transpose.
+    let (shape, strides) = match options.transposed {
+        true => ([a.shape[1], a.shape[0]], [a.strides[1], a.strides[0]]),
+        false => ([a.shape[0], a.shape[1]], [a.strides[0], a.strides[1]]),
+    };
+    let (n, k) = (shape[0], shape[1]);
+    assert_eq!(
+        c.shape,
+        &[n, n],
+        "c should have a row and a column for each row of a"
+    );
+    assert!(k > 0, "a should have at least one column");
+    if n == 0 {
+        return Ok(());
+    }
+    let a =
+        unsafe { TensorHandleRef::<R>::from_raw_parts(a.handle, &strides, &shape, a.elem_size) };
+    // Any stride larger than the others works for the batch of one of the matmuls.
+    let batch_stride = strides[0] * n + strides[1] * k;
+
+    let lower = options.triangle == Triangle::Lower;
+    for start in (0..n).step_by(PANEL_SIZE) {
+        let end = Ord::min(start + PANEL_SIZE, n);
+        let size = end - start;
+
+        // The rows of the panel, and the rows up to the end of the panel, which start at the
+        // beginning of the buffer so they don't need a copy.
+        let panel = TensorHandle::<R, AccG<MP>>::empty(client, vec![1, size, k]);
+        copy_panel::<R, AccG<MP>>(client, &a, &panel.as_ref(), start, 0);
+        let (prefix_shape, prefix_strides) = ([1, end, k], [batch_stride, strides[0], strides[1]]);
+        let (prefix_transposed_shape, prefix_transposed_strides) =
+            ([1, k, end], [batch_stride, strides[1], strides[0]]);
+        let (panel_transposed_shape, panel_transposed_strides) = ([1, k, size], [size * k, 1, k]);
+
+        let (lhs, rhs, product_shape, row_start, col_start) = unsafe {
+            match lower {
+                // The rows of the panel, up to the diagonal.
+                true => (
+                    panel.as_ref(),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        a.handle,
+                        &prefix_transposed_strides,
+                        &prefix_transposed_shape,
+                        a.elem_size,
+                    ),
+                    vec![1, size, end],
+                    start,
+                    0,
+                ),
+                // The columns of the panel, up to the diagonal.
+                false => (
+                    TensorHandleRef::<R>::from_raw_parts(
+                        a.handle,
+                        &prefix_strides,
+                        &prefix_shape,
+                        a.elem_size,
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &panel.handle,
+                        &panel_transposed_strides,
+                        &panel_transposed_shape,
+                        a.elem_size,
+                    ),
+                    vec![1, end, size],
+                    0,
+                    start,
+                ),
+            }
+        };
+
+        let product = TensorHandle::<R, AccG<MP>>::empty(client, product_shape);
+        launch_ref::<R, MP>(
+            strategy,
+            client,
+            &MatmulInputHandleRef::new(lhs),
+            &MatmulInputHandleRef::new(rhs),
+            &product.as_ref(),
+        )?;
+
+        let num_elems = product.shape.iter().product::<usize>();
+        let cube_dim = CubeDim::default();
+        unsafe {
+            syrk_write_kernel::launch_unchecked::<AccG<MP>, R>(
+                client,
+                calculate_cube_count_elemwise(num_elems, cube_dim),
+                cube_dim,
+                product.as_arg(1),
+                c.as_tensor_arg(1),
+                ScalarArg::new(row_start as u32),
+                ScalarArg::new(col_start as u32),
+                ScalarArg::new(product.shape[2] as u32),
+                ScalarArg::new(alpha),
+                ScalarArg::new(beta),
+                lower,
+                beta != AccG::<MP>::from_int(0),
+            );
+        }
+    }
+
+    if options.mirror {
+        let cube_dim = CubeDim::default();
+        unsafe {
+            syrk_mirror_kernel::launch_unchecked::<AccG<MP>, R>(
+                client,
+                calculate_cube_count_elemwise(n * n, cube_dim),
+                cube_dim,
+                c.as_tensor_arg(1),
+                lower,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Each unit writes an element of the contiguous `product` to `c` from `row_start` and `col_start`,
+/// unless it is in the other triangle.
+#[cube(launch_unchecked)]
+fn syrk_write_kernel<E: Numeric>(
+    product: &Tensor<E>,
+    c: &mut Tensor<E>,
+    row_start: u32,
+    col_start: u32,
+    cols: u32,
+    alpha: E,
+    beta: E,
+    #[comptime] lower: bool,
+    #[comptime] read_c: bool,
+) {
+    if ABSOLUTE_POS >= product.len() {
+        terminate!();
+    }
+
+    let row = row_start + ABSOLUTE_POS / cols;
+    let col = col_start + ABSOLUTE_POS % cols;
+    let masked = if comptime!(lower) {
+        col > row
+    } else {
+        col < row
+    };
+
+    if !masked {
+        let index = row * c.stride(0) + col * c.stride(1);
+        let mut value = alpha * product[ABSOLUTE_POS];
+        if comptime!(read_c) {
+            value += beta * c[index];
+        }
+        c[index] = value;
+    }
+}
+
+/// Each unit of the other triangle copies its element from the transposed position.
+#[cube(launch_unchecked)]
+fn syrk_mirror_kernel<E: Numeric>(c: &mut Tensor<E>, #[comptime] lower: bool) {
+    let n = c.shape(0);
+    if ABSOLUTE_POS >= n * n {
+        terminate!();
+    }
+
+    let row = ABSOLUTE_POS / n;
+    let col = ABSOLUTE_POS % n;
+    let other = if comptime!(lower) {
+        col > row
+    } else {
+        col < row
+    };
+
+    if other {
+        c[row * c.stride(0) + col * c.stride(1)] = c[col * c.stride(0) + row * c.stride(1)];
+    }
+}
@@ -0,0 +1,298 @@
+//! Blocked triangular solve with multiple right-hand sides.
+//!
+//! The diagonal blocks of the triangular matrix are solved by substitution by single cubes, with
+//! the block in shared memory, and the rows left to solve are updated with a matmul of the panel
+//! of the matrix below or above the block by the solved rows.
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_std::tensor::TensorHandle;
+
+use crate::{
+    MatmulInputHandleRef, Strategy,
+    components::{AccG, LhsG, MatmulPrecision, MatmulSetupError, RhsG},
+    launch_ref,
+};
+
+/// The number of rows and columns of the diagonal blocks solved by substitution.
+const BLOCK_SIZE: u32 = 32;
+/// The number of right-hand sides solved by a cube, one for each unit.
+const CUBE_SIZE: u32 = 256;
+
+/// The half of the matrix holding the coefficients of a triangular system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Triangle {
+    /// The coefficients are on and below the diagonal, solved from the first row.
+    #[default]
+    Lower,
+    /// The coefficients are on and above the diagonal, solved from the last row.
+    Upper,
+}
+
+/// The triangular system solved by [`trsm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TrsmOptions {
+    pub triangle: Triangle,
+    /// Assume ones on the diagonal without reading it, like the factors of an LU decomposition.
+    pub unit_diagonal: bool,
+}
+
+impl TrsmOptions {
+    /// Solve with the given half of the matrix, reading its diagonal.
+    pub fn new(triangle: Triangle) -> Self {
+        Self {
+            triangle,
+            unit_diagonal: false,
+        }
+    }
+
+    /// Assume ones on the diagonal.
+    pub fn with_unit_diagonal(mut self) -> Self {
+        self.unit_diagonal = true;
+        self
+    }
+}
+
+/// Solve `a · x = b` in place of `b`, for the triangular `[n, n]` matrix `a` and the `[n, m]`
+/// right-hand sides `b`.
+///
+/// Only the half of `a` given by the `options` is read, any strides are supported for both tensors.
+/// The updates of the rows left to solve are matmuls launched with the `strategy`, so their precision
+/// is the stage precision of `MP`: [`Strategy::Naive`] keeps the `f64` of `f64` solves.
+/// The global lhs, rhs and accumulator elements of `MP` must be the same, like for `f32` and `f64`.
+#[allow(clippy::result_large_err)]
+pub fn trsm<R: Runtime, MP: MatmulPrecision>(
+    strategy: &Strategy,
+    client: &ComputeClient<R::Server, R::Channel>,
+    a: &TensorHandleRef<'_, R>,
+    b: &TensorHandleRef<'_, R>,
+    options: TrsmOptions,
+) -> Result<(), MatmulSetupError> {
+    let elem = AccG::<MP>::as_type_native_unchecked();
+    assert!(
+        LhsG::<MP>::as_type_native_unchecked() == elem
+            && RhsG::<MP>::as_type_native_unchecked() == elem,
+        "the global elements of the precision should be the same"
+    );
+    assert!(
+        a.shape.len() == 2 && b.shape.len() == 2,
+        "a and b should be matrices"
+    );
+    let n = a.shape[0];
+    assert_eq!(a.shape[1], n, "a should be square");
+    assert_eq!(b.shape[0], n, "b should have a row for each row of a");
+    let m = b.shape[1];
+    if n * m == 0 {
+        return Ok(());
+    }
+
+    let block_size = BLOCK_SIZE as usize;
+    let num_blocks = n.div_ceil(block_size);
+    for step in 0..num_blocks {
+        let block = match options.triangle {
+            Triangle::Lower => step,
+            Triangle::Upper => num_blocks - 1 - step,
+        };
+        let start = block * block_size;
+        let end = Ord::min(start + block_size, n);
+        let solved = solve_diagonal_block::<R, AccG<MP>>(client, a, b, start, end, options);
+
+        // The rows after a lower block and before an upper block depend on the rows just solved.
+        let (rows_start, rows_end) = match options.triangle {
+            Triangle::Lower => (end, n),
+            Triangle::Upper => (0, start),
+        };
+        if rows_start == rows_end {
+            continue;
+        }
+
+        let panel =
+            TensorHandle::<R, AccG<MP>>::empty(client, vec![1, rows_end - rows_start, end - start]);
+        let product = TensorHandle::<R, AccG<MP>>::empty(client, vec![1, rows_end - rows_start, m]);
+        copy_panel::<R, AccG<MP>>(client, a, &panel.as_ref(), rows_start, start);
+        launch_ref::<R, MP>(
+            strategy,
+            client,
+            &MatmulInputHandleRef::new(panel.as_ref()),
+            &MatmulInputHandleRef::new(solved.as_ref()),
+            &product.as_ref(),
+        )?;
+        subtract_rows::<R, AccG<MP>>(client, b, &product.as_ref(), rows_start);
+    }
+
+    Ok(())
+}
+
+/// Solve the rows `start..end` of `b` with the diagonal block of `a` at the same rows, in place,
+/// returning a contiguous copy of the solved rows.
+fn solve_diagonal_block<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    a: &TensorHandleRef<'_, R>,
+    b: &TensorHandleRef<'_, R>,
+    start: usize,
+    end: usize,
+    options: TrsmOptions,
+) -> TensorHandle<R, E> {
+    let cols = b.shape[1];
+    let solved = TensorHandle::<R, E>::empty(client, vec![1, end - start, cols]);
+    let cube_count = CubeCount::Static(cols.div_ceil(CUBE_SIZE as usize) as u32, 1, 1);
+
+    unsafe {
+        trsm_diagonal_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            CubeDim::new_1d(CUBE_SIZE),
+            a.as_tensor_arg(1),
+            b.as_tensor_arg(1),
+            solved.as_arg(1),
+            ScalarArg::new(start as u32),
+            ScalarArg::new((end - start) as u32),
+            options.triangle == Triangle::Lower,
+            options.unit_diagonal,
+        );
+    }
+
+    solved
+}
+
+/// Copy the columns of the matrix `a` from `col_start` and the rows from `row_start` into the
+/// contiguous `[1, rows, cols]` `panel`.
+pub(crate) fn copy_panel<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    a: &TensorHandleRef<'_, R>,
+    panel: &TensorHandleRef<'_, R>,
+    row_start: usize,
+    col_start: usize,
+) {
+    let num_elems = panel.shape.iter().product::<usize>();
+    let cube_dim = CubeDim::default();
+
+    unsafe {
+        copy_panel_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(num_elems, cube_dim),
+            cube_dim,
+            a.as_tensor_arg(1),
+            panel.as_tensor_arg(1),
+            ScalarArg::new(row_start as u32),
+            ScalarArg::new(col_start as u32),
+            ScalarArg::new(panel.shape[2] as u32),
+        );
+    }
+}
+
+/// Subtract the contiguous `product` from the rows of `b` from `row_start`.
+fn subtract_rows<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    b: &TensorHandleRef<'_, R>,
+    product: &TensorHandleRef<'_, R>,
+    row_start: usize,
+) {
+    let num_elems = product.shape.iter().product::<usize>();
+    let cube_dim = CubeDim::default();
+
+    unsafe {
+        subtract_rows_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(num_elems, cube_dim),
+            cube_dim,
+            b.as_tensor_arg(1),
+            product.as_tensor_arg(1),
+            ScalarArg::new(row_start as u32),
+            ScalarArg::new(product.shape[2] as u32),
+        );
+    }
+}
+
+/// Each unit solves a column of the rows of `b` from `block_start`, reading the diagonal block of `a`
+/// from shared memory, and writes it to `b` and to the contiguous `solved`.
+#[cube(launch_unchecked)]
+fn trsm_diagonal_kernel<E: Numeric>(
+    a: &Tensor<E>,
+    b: &mut Tensor<E>,
+    solved: &mut Tensor<E>,
+    block_start: u32,
+    block_size: u32,
+    #[comptime] lower: bool,
+    #[comptime] unit_diagonal: bool,
+) {
+    let mut tile = SharedMemory::<E>::new(comptime!(BLOCK_SIZE * BLOCK_SIZE));
+    for i in range_stepped(UNIT_POS, comptime!(BLOCK_SIZE * BLOCK_SIZE), CUBE_DIM) {
+        let row = i / BLOCK_SIZE;
+        let col = i % BLOCK_SIZE;
+        if row < block_size && col < block_size {
+            tile[i] = a[(block_start + row) * a.stride(0) + (block_start + col) * a.stride(1)];
+        }
+    }
+    sync_cube();
+
+    let col = ABSOLUTE_POS_X;
+    let cols = b.shape(1);
+    if col >= cols {
+        terminate!();
+    }
+
+    let mut x = Array::<E>::new(BLOCK_SIZE);
+    for i in 0..block_size {
+        x[i] = b[(block_start + i) * b.stride(0) + col * b.stride(1)];
+    }
+
+    // The rows of a lower block are solved forward from the known values before them,
+    // the rows of an upper block backward from the known values after them.
+    for step in 0..block_size {
+        let i = if comptime!(lower) {
+            step
+        } else {
+            block_size - 1 - step
+        };
+        let known_start = if comptime!(lower) { 0u32 } else { i + 1 };
+        let known_end = if comptime!(lower) { i } else { block_size };
+
+        let mut value = x[i];
+        for p in known_start..known_end {
+            value -= tile[i * BLOCK_SIZE + p] * x[p];
+        }
+        if comptime!(!unit_diagonal) {
+            value /= tile[i * BLOCK_SIZE + i];
+        }
+        x[i] = value;
+    }
+
+    for i in 0..block_size {
+        b[(block_start + i) * b.stride(0) + col * b.stride(1)] = x[i];
+        solved[i * cols + col] = x[i];
+    }
+}
+
+#[cube(launch_unchecked)]
+fn copy_panel_kernel<E: Numeric>(
+    a: &Tensor<E>,
+    panel: &mut Tensor<E>,
+    row_start: u32,
+    col_start: u32,
+    cols: u32,
+) {
+    if ABSOLUTE_POS >= panel.len() {
+        terminate!();
+    }
+
+    let row = row_start + ABSOLUTE_POS / cols;
+    let col = col_start + ABSOLUTE_POS % cols;
+    panel[ABSOLUTE_POS] = a[row * a.stride(0) + col * a.stride(1)];
+}
+
+#[cube(launch_unchecked)]
+fn subtract_rows_kernel<E: Numeric>(
+    b: &mut Tensor<E>,
+    product: &Tensor<E>,
+    row_start: u32,
+    cols: u32,
+) {
+    if ABSOLUTE_POS >= product.len() {
+        terminate!();
+    }
+
+    let row = row_start + ABSOLUTE_POS / cols;
+    let col = ABSOLUTE_POS % cols;
+    let index = row * b.stride(0) + col * b.stride(1);
+    b[index] -= product[ABSOLUTE_POS];
+}
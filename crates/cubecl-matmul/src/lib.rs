@@ -9,8 +9,10 @@ pub mod kernels;
 /// Tests for matmul kernels
 #[cfg(feature = "export_tests")]
 pub mod tests;
+mod warmup;
 
 pub use base::*;
+pub use warmup::*;
 
 /// Autotune key for matmul.
 pub mod tune_key;
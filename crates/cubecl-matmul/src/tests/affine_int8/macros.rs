@@ -0,0 +1,52 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_affine_int8 {
+    () => {
+        mod affine_int8 {
+            use super::*;
+
+            #[test]
+            pub fn test_per_tensor() {
+                cubecl_matmul::tests::affine_int8::tests::test_per_tensor::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_per_channel() {
+                cubecl_matmul::tests::affine_int8::tests::test_per_channel::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_per_channel_scaled() {
+                cubecl_matmul::tests::affine_int8::tests::test_per_channel_scaled::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_broadcast_rhs() {
+                cubecl_matmul::tests::affine_int8::tests::test_broadcast_rhs::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_batched_transposed_rhs() {
+                cubecl_matmul::tests::affine_int8::tests::test_batched_transposed_rhs::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_mismatched_zero_points() {
+                cubecl_matmul::tests::affine_int8::tests::test_mismatched_zero_points::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+        }
+    };
+}
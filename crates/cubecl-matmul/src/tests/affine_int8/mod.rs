@@ -1,3 +1,2 @@
 mod macros;
 pub mod tests;
-mod utils;
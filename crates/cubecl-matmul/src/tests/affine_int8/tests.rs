@@ -0,0 +1,289 @@
+use cubecl_core::{CubeElement, Runtime, prelude::TensorHandleRef};
+
+use crate::{
+    Strategy,
+    components::MatmulSetupError,
+    kernels::affine_int8::{self, AffineQuantizedRef},
+    tests::test_utils::pseudo_random,
+};
+
+/// A single scale and zero point for each operand, into the corrected accumulator.
+pub fn test_per_tensor<R: Runtime>(device: &R::Device) {
+    AffineInt8TestCase::new(1, 64, 48, 96).test::<R>(device);
+}
+
+/// A scale and a zero point per row of the lhs and per column of the rhs.
+pub fn test_per_channel<R: Runtime>(device: &R::Device) {
+    let mut case = AffineInt8TestCase::new(1, 64, 48, 96);
+    case.per_channel = true;
+    case.test::<R>(device);
+}
+
+/// The corrected accumulator scaled back to floats.
+pub fn test_per_channel_scaled<R: Runtime>(device: &R::Device) {
+    let mut case = AffineInt8TestCase::new(1, 40, 72, 128);
+    case.per_channel = true;
+    case.scaled = true;
+    case.test::<R>(device);
+}
+
+/// A batch of activations multiplied by the same weights.
+pub fn test_broadcast_rhs<R: Runtime>(device: &R::Device) {
+    let mut case = AffineInt8TestCase::new(3, 33, 20, 70);
+    case.per_channel = true;
+    case.broadcast_rhs = true;
+    case.test::<R>(device);
+}
+
+/// A rhs per matrix of the batch, stored transposed.
+pub fn test_batched_transposed_rhs<R: Runtime>(device: &R::Device) {
+    let mut case = AffineInt8TestCase::new(2, 31, 45, 64);
+    case.rhs_transposed = true;
+    case.scaled = true;
+    case.test::<R>(device);
+}
+
+/// Zero points that don't match the scales are rejected.
+pub fn test_mismatched_zero_points<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (m, n, k) = (8, 4, 16);
+    let lhs = client.empty(m * k);
+    let rhs = client.empty(k * n);
+    let scales = client.create(f32::as_bytes(&[1.0; 8]));
+    let zero_points = client.create(i32::as_bytes(&[0; 8]));
+    let out = client.empty(m * n * size_of::<f32>());
+
+    let (lhs, rhs, out) = unsafe {
+        (
+            AffineQuantizedRef::new(
+                TensorHandleRef::<R>::from_raw_parts(&lhs, &[k, 1], &[m, k], 1),
+                TensorHandleRef::<R>::from_raw_parts(&scales, &[1], &[m], size_of::<f32>()),
+                TensorHandleRef::<R>::from_raw_parts(&zero_points, &[1], &[1], size_of::<i32>()),
+            ),
+            AffineQuantizedRef::new(
+                TensorHandleRef::<R>::from_raw_parts(&rhs, &[n, 1], &[k, n], 1),
+                TensorHandleRef::<R>::from_raw_parts(&scales, &[1], &[1], size_of::<f32>()),
+                TensorHandleRef::<R>::from_raw_parts(&zero_points, &[1], &[1], size_of::<i32>()),
+            ),
+            TensorHandleRef::<R>::from_raw_parts(&out, &[n, 1], &[m, n], size_of::<f32>()),
+        )
+    };
+
+    let result = affine_int8::launch_ref::<R, f32>(&Strategy::Auto, &client, &lhs, &rhs, &out);
+    assert!(matches!(result, Err(MatmulSetupError::InvalidConfig(_))));
+}
+
+struct AffineInt8TestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    /// A scale and a zero point per row of the lhs and per column of the rhs.
+    per_channel: bool,
+    /// Write the values scaled back in `f32`, else the corrected accumulator in `i32`.
+    scaled: bool,
+    /// A single rhs of rank 2 for all the matrices of the lhs.
+    broadcast_rhs: bool,
+    /// Store the rhs transposed in memory.
+    rhs_transposed: bool,
+}
+
+impl AffineInt8TestCase {
+    fn new(batches: usize, m: usize, n: usize, k: usize) -> Self {
+        Self {
+            batches,
+            m,
+            n,
+            k,
+            per_channel: false,
+            scaled: false,
+            broadcast_rhs: false,
+            rhs_transposed: false,
+        }
+    }
+
+    /// Multiply random operands with random zero points, and check the output against the
+    /// fixed-point matmul of the operands minus their zero points on the CPU.
+    fn test<R: Runtime>(&self, device: &R::Device) {
+        let client = R::client(device);
+        if !client
+            .properties()
+            .supports_type(i8::as_type_native_unchecked())
+        {
+            println!("i8 not supported - skipped");
+            return;
+        }
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+        let rhs_batches = match self.broadcast_rhs {
+            true => 1,
+            false => batches,
+        };
+        let (lhs_channels, rhs_channels) = match self.per_channel {
+            true => (m, n),
+            false => (1, 1),
+        };
+
+        let random_i8 = |i, seed| (pseudo_random(i, seed) * 256.0).floor() as i32 as i8;
+        let lhs = (0..batches * m * k)
+            .map(|i| random_i8(i, 1))
+            .collect::<Vec<_>>();
+        let rhs = (0..rhs_batches * k * n)
+            .map(|i| random_i8(i, 2))
+            .collect::<Vec<_>>();
+        let zero_point = |i, seed| (pseudo_random(i, seed) * 41.0).floor() as i32 - 20;
+        let lhs_zero_points = (0..lhs_channels)
+            .map(|i| zero_point(i, 3))
+            .collect::<Vec<_>>();
+        let rhs_zero_points = (0..rhs_channels)
+            .map(|i| zero_point(i, 4))
+            .collect::<Vec<_>>();
+        let lhs_scales = (0..lhs_channels)
+            .map(|i| 0.01 + pseudo_random(i, 5) as f32 * 0.05)
+            .collect::<Vec<_>>();
+        let rhs_scales = (0..rhs_channels)
+            .map(|i| 0.01 + pseudo_random(i, 6) as f32 * 0.05)
+            .collect::<Vec<_>>();
+        let rhs_strides = match self.rhs_transposed {
+            true => [k * n, 1, k],
+            false => [k * n, n, 1],
+        };
+
+        let (lhs_shape, lhs_strides) = ([batches, m, k], [m * k, k, 1]);
+        let (out_shape, out_strides) = ([batches, m, n], [m * n, n, 1]);
+        let rhs_shape = [rhs_batches, k, n];
+        let rank_2_rhs = [k, n];
+        let (rhs_view_shape, rhs_view_strides) = match self.broadcast_rhs {
+            true => (&rank_2_rhs[..], &rhs_strides[1..]),
+            false => (&rhs_shape[..], &rhs_strides[..]),
+        };
+        let out_elem_size = match self.scaled {
+            true => size_of::<f32>(),
+            false => size_of::<i32>(),
+        };
+
+        let lhs_handle = client.create(i8::as_bytes(&lhs));
+        let rhs_handle = client.create(i8::as_bytes(&rhs));
+        let lhs_scales_handle = client.create(f32::as_bytes(&lhs_scales));
+        let rhs_scales_handle = client.create(f32::as_bytes(&rhs_scales));
+        let lhs_zero_points_handle = client.create(i32::as_bytes(&lhs_zero_points));
+        let rhs_zero_points_handle = client.create(i32::as_bytes(&rhs_zero_points));
+        let out_handle = client.empty(batches * m * n * out_elem_size);
+
+        let (lhs_ref, rhs_ref, out_ref) = unsafe {
+            (
+                AffineQuantizedRef::new(
+                    TensorHandleRef::<R>::from_raw_parts(&lhs_handle, &lhs_strides, &lhs_shape, 1),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &lhs_scales_handle,
+                        &[1],
+                        &[lhs_channels],
+                        size_of::<f32>(),
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &lhs_zero_points_handle,
+                        &[1],
+                        &[lhs_channels],
+                        size_of::<i32>(),
+                    ),
+                ),
+                AffineQuantizedRef::new(
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &rhs_handle,
+                        rhs_view_strides,
+                        rhs_view_shape,
+                        1,
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &rhs_scales_handle,
+                        &[1],
+                        &[rhs_channels],
+                        size_of::<f32>(),
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &rhs_zero_points_handle,
+                        &[1],
+                        &[rhs_channels],
+                        size_of::<i32>(),
+                    ),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &out_handle,
+                    &out_strides,
+                    &out_shape,
+                    out_elem_size,
+                ),
+            )
+        };
+
+        let result = match self.scaled {
+            true => affine_int8::launch_ref::<R, f32>(
+                &Strategy::Auto,
+                &client,
+                &lhs_ref,
+                &rhs_ref,
+                &out_ref,
+            ),
+            false => affine_int8::launch_ref::<R, i32>(
+                &Strategy::Auto,
+                &client,
+                &lhs_ref,
+                &rhs_ref,
+                &out_ref,
+            ),
+        };
+        match result {
+            Ok(()) => {}
+            Err(MatmulSetupError::Unavailable(err)) => {
+                println!("Skipping the test, the int8 matmul is unavailable: {err:?}");
+                return;
+            }
+            Err(err) => panic!("Can't launch the int8 matmul: {err}"),
+        }
+
+        let actual = client.read_one(out_handle);
+        for b in 0..batches {
+            let rhs_batch = b % rhs_batches;
+            for row in 0..m {
+                for col in 0..n {
+                    let (lhs_channel, rhs_channel) = match self.per_channel {
+                        true => (row, col),
+                        false => (0, 0),
+                    };
+                    let expected = (0..k)
+                        .map(|i| {
+                            let lhs = lhs[(b * m + row) * k + i] as i64;
+                            let rhs_index = rhs_batch * rhs_strides[0]
+                                + i * rhs_strides[1]
+                                + col * rhs_strides[2];
+                            let rhs = rhs[rhs_index] as i64;
+                            (lhs - lhs_zero_points[lhs_channel] as i64)
+                                * (rhs - rhs_zero_points[rhs_channel] as i64)
+                        })
+                        .sum::<i64>();
+                    let index = (b * m + row) * n + col;
+
+                    match self.scaled {
+                        true => {
+                            let value = f32::from_bytes(&actual)[index] as f64;
+                            let scale =
+                                lhs_scales[lhs_channel] as f64 * rhs_scales[rhs_channel] as f64;
+                            let expected = expected as f64 * scale;
+                            let difference = f64::abs(value - expected);
+                            assert!(
+                                difference <= 1e-5 * f64::abs(expected).max(1.0),
+                                "Values differ: batch={b}, row={row}, col={col}, actual={value}, expected={expected}, difference={difference}"
+                            );
+                        }
+                        false => {
+                            let value = i32::from_bytes(&actual)[index] as i64;
+                            assert_eq!(
+                                value, expected,
+                                "Values differ: batch={b}, row={row}, col={col}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_batched_gemv {
+    () => {
+        mod batched_gemv {
+            $crate::testgen_matmul_batched_gemv!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_broadcast_vector() {
+                cubecl_matmul::tests::batched_gemv::tests::test_broadcast_vector::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_broadcast_vector_shared() {
+                cubecl_matmul::tests::batched_gemv::tests::test_broadcast_vector_shared::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_batched_vector() {
+                cubecl_matmul::tests::batched_gemv::tests::test_batched_vector::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_batched_vector_shared() {
+                cubecl_matmul::tests::batched_gemv::tests::test_batched_vector_shared::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_long_k() {
+                cubecl_matmul::tests::batched_gemv::tests::test_long_k::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_odd_sizes() {
+                cubecl_matmul::tests::batched_gemv::tests::test_odd_sizes::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_transposed_matrix() {
+                cubecl_matmul::tests::batched_gemv::tests::test_transposed_matrix::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_matmul_broadcast() {
+                cubecl_matmul::tests::batched_gemv::tests::test_matmul_broadcast::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_matmul_batched() {
+                cubecl_matmul::tests::batched_gemv::tests::test_matmul_batched::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_mismatched_batches() {
+                cubecl_matmul::tests::batched_gemv::tests::test_mismatched_batches::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod batched_gemv {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_batched_gemv!($float);
+                })*
+            }
+        }
+    };
+}
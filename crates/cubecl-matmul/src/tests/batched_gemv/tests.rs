@@ -0,0 +1,259 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    MatmulInputHandleRef, Strategy,
+    components::{MatmulPrecision, MatmulSetupError},
+    kernels::batched_gemv,
+    launch_ref,
+    tests::test_utils::pseudo_random,
+};
+
+pub trait BatchedGemvFloat: Float + CubeElement + Display + MatmulPrecision {}
+
+impl<F: Float + CubeElement + Display + MatmulPrecision> BatchedGemvFloat for F {}
+
+/// A vector of rank 1 broadcast to each matrix, short enough to be held in registers.
+pub fn test_broadcast_vector<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    BatchedGemvTestCase::new(16, 300, 64).test::<R, F>(device);
+}
+
+/// A vector of rank 1 broadcast to each matrix, loaded in shared memory.
+pub fn test_broadcast_vector_shared<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    BatchedGemvTestCase::new(16, 300, 1024).test::<R, F>(device);
+}
+
+/// A vector per matrix of the batch.
+pub fn test_batched_vector<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let mut case = BatchedGemvTestCase::new(16, 300, 64);
+    case.broadcast = false;
+    case.test::<R, F>(device);
+}
+
+/// A vector per matrix of the batch, loaded in shared memory.
+pub fn test_batched_vector_shared<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let mut case = BatchedGemvTestCase::new(16, 300, 1024);
+    case.broadcast = false;
+    case.test::<R, F>(device);
+}
+
+/// A vector loaded in shared memory in multiple chunks.
+pub fn test_long_k<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    BatchedGemvTestCase::new(2, 40, 10000).test::<R, F>(device);
+}
+
+/// Sizes that can't be read in lines, with rows left over in the last plane.
+pub fn test_odd_sizes<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let mut case = BatchedGemvTestCase::new(3, 37, 131);
+    case.broadcast = false;
+    case.test::<R, F>(device);
+}
+
+pub fn test_transposed_matrix<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let mut case = BatchedGemvTestCase::new(4, 100, 96);
+    case.matrix_transposed = true;
+    case.test::<R, F>(device);
+}
+
+/// The matmul of a single column broadcast over the batches, launched with its strategy.
+pub fn test_matmul_broadcast<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let mut case = BatchedGemvTestCase::new(8, 200, 256);
+    case.through_matmul = true;
+    case.test::<R, F>(device);
+}
+
+/// The matmul of a column per batch, launched with its strategy.
+pub fn test_matmul_batched<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let mut case = BatchedGemvTestCase::new(8, 200, 256);
+    case.broadcast = false;
+    case.through_matmul = true;
+    case.test::<R, F>(device);
+}
+
+/// A vector whose batches don't match the ones of the matrix is rejected.
+pub fn test_mismatched_batches<R: Runtime, F: BatchedGemvFloat>(device: &R::Device) {
+    let client = R::client(device);
+    let matrix = client.empty(4 * 8 * 16 * size_of::<F>());
+    let vector = client.empty(2 * 16 * size_of::<F>());
+    let out = client.empty(4 * 8 * size_of::<F>());
+    let (matrix_ref, vector_ref, out_ref) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(
+                &matrix,
+                &[128, 16, 1],
+                &[4, 8, 16],
+                size_of::<F>(),
+            ),
+            TensorHandleRef::<R>::from_raw_parts(&vector, &[16, 1], &[2, 16], size_of::<F>()),
+            TensorHandleRef::<R>::from_raw_parts(&out, &[8, 1], &[4, 8], size_of::<F>()),
+        )
+    };
+
+    let result =
+        batched_gemv::launch_ref::<R, F, f32, F>(&client, &matrix_ref, &vector_ref, &out_ref);
+    assert!(matches!(result, Err(MatmulSetupError::InvalidConfig(_))));
+}
+
+struct BatchedGemvTestCase {
+    batches: usize,
+    m: usize,
+    k: usize,
+    /// Broadcast a single vector to each matrix, else there is a vector per matrix.
+    broadcast: bool,
+    /// Store the matrices transposed in memory.
+    matrix_transposed: bool,
+    /// Launch the matmul of the matrices by the vector as a single column, instead of the
+    /// matrix-vector product directly.
+    through_matmul: bool,
+}
+
+impl BatchedGemvTestCase {
+    fn new(batches: usize, m: usize, k: usize) -> Self {
+        Self {
+            batches,
+            m,
+            k,
+            broadcast: true,
+            matrix_transposed: false,
+            through_matmul: false,
+        }
+    }
+
+    /// Multiply random matrices and vectors in `[-1, 1]`, and check the output against a
+    /// reference.
+    fn test<R: Runtime, F: BatchedGemvFloat>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let (batches, m, k) = (self.batches, self.m, self.k);
+        let vector_batches = match self.broadcast {
+            true => 1,
+            false => batches,
+        };
+
+        let matrix = (0..batches * m * k)
+            .map(|i| F::new((2.0 * pseudo_random(i, 1) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let vector = (0..vector_batches * k)
+            .map(|i| F::new((2.0 * pseudo_random(i, 2) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let matrix_strides = match self.matrix_transposed {
+            true => [m * k, 1, m],
+            false => [m * k, k, 1],
+        };
+
+        let matrix_handle = client.create(F::as_bytes(&matrix));
+        let vector_handle = client.create(F::as_bytes(&vector));
+        let out_handle = client.empty(batches * m * size_of::<F>());
+        let matrix_shape = [batches, m, k];
+
+        let result = match self.through_matmul {
+            true => {
+                let (rhs_shape, rhs_strides) = ([vector_batches, k, 1], [k, 1, 1]);
+                let (out_shape, out_strides) = ([batches, m, 1], [m, 1, 1]);
+                let (lhs_ref, rhs_ref, out_ref) = unsafe {
+                    (
+                        TensorHandleRef::<R>::from_raw_parts(
+                            &matrix_handle,
+                            &matrix_strides,
+                            &matrix_shape,
+                            size_of::<F>(),
+                        ),
+                        TensorHandleRef::<R>::from_raw_parts(
+                            &vector_handle,
+                            &rhs_strides,
+                            &rhs_shape,
+                            size_of::<F>(),
+                        ),
+                        TensorHandleRef::<R>::from_raw_parts(
+                            &out_handle,
+                            &out_strides,
+                            &out_shape,
+                            size_of::<F>(),
+                        ),
+                    )
+                };
+                launch_ref::<R, F>(
+                    &Strategy::BatchedGemv,
+                    &client,
+                    &MatmulInputHandleRef::new(lhs_ref),
+                    &MatmulInputHandleRef::new(rhs_ref),
+                    &out_ref,
+                )
+            }
+            false => {
+                let (vector_shape, vector_strides) = match self.broadcast {
+                    true => (vec![k], vec![1]),
+                    false => (vec![batches, k], vec![k, 1]),
+                };
+                let (out_shape, out_strides) = ([batches, m], [m, 1]);
+                let (matrix_ref, vector_ref, out_ref) = unsafe {
+                    (
+                        TensorHandleRef::<R>::from_raw_parts(
+                            &matrix_handle,
+                            &matrix_strides,
+                            &matrix_shape,
+                            size_of::<F>(),
+                        ),
+                        TensorHandleRef::<R>::from_raw_parts(
+                            &vector_handle,
+                            &vector_strides,
+                            &vector_shape,
+                            size_of::<F>(),
+                        ),
+                        TensorHandleRef::<R>::from_raw_parts(
+                            &out_handle,
+                            &out_strides,
+                            &out_shape,
+                            size_of::<F>(),
+                        ),
+                    )
+                };
+                batched_gemv::launch_ref::<R, F, f32, F>(
+                    &client,
+                    &matrix_ref,
+                    &vector_ref,
+                    &out_ref,
+                )
+            }
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(MatmulSetupError::Unavailable(err)) => {
+                println!("Skipping the test, the batched gemv is unavailable: {err:?}");
+                return;
+            }
+            Err(err) => panic!("Can't launch the batched gemv: {err}"),
+        }
+
+        let actual = client.read_one(out_handle);
+        let actual = F::from_bytes(&actual);
+        // The products are accumulated in `f32`, then rounded to the output.
+        let tolerance = 4.0 * F::EPSILON.to_f64().unwrap() + k as f64 * f32::EPSILON as f64;
+
+        for b in 0..batches {
+            let vector_batch = b % vector_batches;
+            for row in 0..m {
+                let (mut expected, mut scale) = (0.0, 0.0);
+                for i in 0..k {
+                    let matrix_index =
+                        b * matrix_strides[0] + row * matrix_strides[1] + i * matrix_strides[2];
+                    let lhs = F::to_f64(&matrix[matrix_index]).unwrap();
+                    let rhs = F::to_f64(&vector[vector_batch * k + i]).unwrap();
+                    expected += lhs * rhs;
+                    scale += f64::abs(lhs * rhs);
+                }
+
+                let value = F::to_f64(&actual[b * m + row]).unwrap();
+                let difference = f64::abs(value - expected);
+                assert!(
+                    difference <= tolerance * scale.max(1.0),
+                    "Values differ: batch={b}, row={row}, actual={value}, expected={expected}, difference={difference}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_batched_tiny {
+    () => {
+        mod batched_tiny {
+            $crate::testgen_matmul_batched_tiny!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_unit() {
+                cubecl_matmul::tests::batched_tiny::tests::test_unit::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_8x8x8() {
+                cubecl_matmul::tests::batched_tiny::tests::test_8x8x8::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_16x16x16() {
+                cubecl_matmul::tests::batched_tiny::tests::test_16x16x16::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_32x32x32() {
+                cubecl_matmul::tests::batched_tiny::tests::test_32x32x32::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_odd_sizes() {
+                cubecl_matmul::tests::batched_tiny::tests::test_odd_sizes::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_broadcast_lhs() {
+                cubecl_matmul::tests::batched_tiny::tests::test_broadcast_lhs::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_transposed_rhs() {
+                cubecl_matmul::tests::batched_tiny::tests::test_transposed_rhs::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_too_large() {
+                cubecl_matmul::tests::batched_tiny::tests::test_too_large::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod batched_tiny {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_batched_tiny!($float);
+                })*
+            }
+        }
+    };
+}
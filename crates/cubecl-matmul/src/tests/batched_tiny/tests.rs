@@ -0,0 +1,181 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    MatmulInputHandleRef, Strategy, components::MatmulPrecision, launch_ref,
+    tests::test_utils::pseudo_random,
+};
+
+pub trait BatchedTinyFloat: Float + CubeElement + Display + MatmulPrecision {}
+
+impl<F: Float + CubeElement + Display + MatmulPrecision> BatchedTinyFloat for F {}
+
+/// Matrices computed by a single unit.
+pub fn test_unit<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    BatchedTinyTestCase::new(100, 3, 4, 2).test::<R, F>(device);
+}
+
+pub fn test_8x8x8<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    BatchedTinyTestCase::new(300, 8, 8, 8).test::<R, F>(device);
+}
+
+/// Computed with a tensor core fragment when supported.
+pub fn test_16x16x16<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    BatchedTinyTestCase::new(1000, 16, 16, 16).test::<R, F>(device);
+}
+
+pub fn test_32x32x32<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    BatchedTinyTestCase::new(50, 32, 32, 32).test::<R, F>(device);
+}
+
+/// Sizes that can't be read in lines.
+pub fn test_odd_sizes<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    BatchedTinyTestCase::new(70, 17, 5, 31).test::<R, F>(device);
+}
+
+/// The same lhs multiplied by each matrix of the rhs.
+pub fn test_broadcast_lhs<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    let mut case = BatchedTinyTestCase::new(40, 12, 8, 20);
+    case.lhs_batches = 1;
+    case.test::<R, F>(device);
+}
+
+pub fn test_transposed_rhs<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    let mut case = BatchedTinyTestCase::new(40, 16, 12, 8);
+    case.rhs_transposed = true;
+    case.test::<R, F>(device);
+}
+
+/// Matrices too large for the tiny matmul are rejected.
+pub fn test_too_large<R: Runtime, F: BatchedTinyFloat>(device: &R::Device) {
+    let client = R::client(device);
+    let lhs = client.empty(2 * 33 * 8 * size_of::<F>());
+    let rhs = client.empty(2 * 8 * 8 * size_of::<F>());
+    let out = client.empty(2 * 33 * 8 * size_of::<F>());
+    let (lhs_ref, rhs_ref, out_ref) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(&lhs, &[264, 8, 1], &[2, 33, 8], size_of::<F>()),
+            TensorHandleRef::<R>::from_raw_parts(&rhs, &[64, 8, 1], &[2, 8, 8], size_of::<F>()),
+            TensorHandleRef::<R>::from_raw_parts(&out, &[264, 8, 1], &[2, 33, 8], size_of::<F>()),
+        )
+    };
+
+    let result = launch_ref::<R, F>(
+        &Strategy::BatchedTiny,
+        &client,
+        &MatmulInputHandleRef::new(lhs_ref),
+        &MatmulInputHandleRef::new(rhs_ref),
+        &out_ref,
+    );
+    assert!(result.is_err());
+}
+
+struct BatchedTinyTestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    /// The number of matrices of the lhs, either one or the number of batches.
+    lhs_batches: usize,
+    /// Store the matrices of the rhs transposed in memory.
+    rhs_transposed: bool,
+}
+
+impl BatchedTinyTestCase {
+    fn new(batches: usize, m: usize, n: usize, k: usize) -> Self {
+        Self {
+            batches,
+            m,
+            n,
+            k,
+            lhs_batches: batches,
+            rhs_transposed: false,
+        }
+    }
+
+    /// Multiply random matrices in `[-1, 1]`, and check the output against a reference.
+    fn test<R: Runtime, F: BatchedTinyFloat>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+
+        let lhs = (0..self.lhs_batches * m * k)
+            .map(|i| F::new((2.0 * pseudo_random(i, 1) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let rhs = (0..batches * k * n)
+            .map(|i| F::new((2.0 * pseudo_random(i, 2) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let rhs_strides = match self.rhs_transposed {
+            true => [k * n, 1, k],
+            false => [k * n, n, 1],
+        };
+
+        let lhs_handle = client.create(F::as_bytes(&lhs));
+        let rhs_handle = client.create(F::as_bytes(&rhs));
+        let out_handle = client.empty(batches * m * n * size_of::<F>());
+        let lhs_shape = [self.lhs_batches, m, k];
+        let (rhs_shape, out_shape) = ([batches, k, n], [batches, m, n]);
+        let (lhs_strides, out_strides) = ([m * k, k, 1], [m * n, n, 1]);
+        let (lhs_ref, rhs_ref, out_ref) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &lhs_handle,
+                    &lhs_strides,
+                    &lhs_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &rhs_handle,
+                    &rhs_strides,
+                    &rhs_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &out_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<F>(),
+                ),
+            )
+        };
+        launch_ref::<R, F>(
+            &Strategy::BatchedTiny,
+            &client,
+            &MatmulInputHandleRef::new(lhs_ref),
+            &MatmulInputHandleRef::new(rhs_ref),
+            &out_ref,
+        )
+        .unwrap();
+
+        let actual = client.read_one(out_handle);
+        let actual = F::from_bytes(&actual);
+        let tolerance = 4.0 * k as f64 * F::EPSILON.to_f64().unwrap();
+
+        for b in 0..batches {
+            let lhs_batch = b % self.lhs_batches;
+            for row in 0..m {
+                for col in 0..n {
+                    let (mut expected, mut scale) = (0.0, 0.0);
+                    for i in 0..k {
+                        let lhs = F::to_f64(&lhs[(lhs_batch * m + row) * k + i]).unwrap();
+                        let rhs_index =
+                            b * rhs_strides[0] + i * rhs_strides[1] + col * rhs_strides[2];
+                        let rhs = F::to_f64(&rhs[rhs_index]).unwrap();
+                        expected += lhs * rhs;
+                        scale += f64::abs(lhs * rhs);
+                    }
+
+                    let value = F::to_f64(&actual[(b * m + row) * n + col]).unwrap();
+                    let difference = f64::abs(value - expected);
+                    assert!(
+                        difference <= tolerance * scale.max(1.0),
+                        "Values differ: batch={b}, row={row}, col={col}, actual={value}, expected={expected}, difference={difference}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_bias_gelu_residual {
+    () => {
+        mod bias_gelu_residual {
+            $crate::testgen_matmul_bias_gelu_residual!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_with_residual() {
+                cubecl_matmul::tests::bias_gelu_residual::tests::test_with_residual::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_without_residual() {
+                cubecl_matmul::tests::bias_gelu_residual::tests::test_without_residual::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_residual_in_place() {
+                cubecl_matmul::tests::bias_gelu_residual::tests::test_residual_in_place::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_partial_tiles() {
+                cubecl_matmul::tests::bias_gelu_residual::tests::test_partial_tiles::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod bias_gelu_residual {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_bias_gelu_residual!($float);
+                })*
+            }
+        }
+    };
+}
@@ -0,0 +1,213 @@
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    MatmulInputHandleRef,
+    components::MatmulPrecision,
+    kernels::{
+        bias_gelu_residual,
+        layered::{self, Selection, simple_unit::SimpleUnitAlgorithm},
+    },
+    tests::test_utils::{assert_equals_approx, pseudo_random},
+};
+
+/// A residual of its own, as in a feed-forward block.
+pub fn test_with_residual<R: Runtime, F: Float + CubeElement + MatmulPrecision>(
+    device: &R::Device,
+) {
+    BiasGeluResidualTestCase::new(2, 32, 64, 48, Residual::Separate).test::<R, F>(device);
+}
+
+/// Only the bias and the activation.
+pub fn test_without_residual<R: Runtime, F: Float + CubeElement + MatmulPrecision>(
+    device: &R::Device,
+) {
+    BiasGeluResidualTestCase::new(1, 32, 64, 48, Residual::None).test::<R, F>(device);
+}
+
+/// The residual is the output, which the matmul is added to.
+pub fn test_residual_in_place<R: Runtime, F: Float + CubeElement + MatmulPrecision>(
+    device: &R::Device,
+) {
+    BiasGeluResidualTestCase::new(2, 32, 64, 48, Residual::InPlace).test::<R, F>(device);
+}
+
+/// Sizes that aren't multiples of the tiles, so the residual is read within the bound checks of
+/// the output.
+pub fn test_partial_tiles<R: Runtime, F: Float + CubeElement + MatmulPrecision>(
+    device: &R::Device,
+) {
+    BiasGeluResidualTestCase::new(3, 33, 20, 45, Residual::InPlace).test::<R, F>(device);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Residual {
+    None,
+    Separate,
+    InPlace,
+}
+
+struct BiasGeluResidualTestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    residual: Residual,
+}
+
+impl BiasGeluResidualTestCase {
+    fn new(batches: usize, m: usize, n: usize, k: usize, residual: Residual) -> Self {
+        Self {
+            batches,
+            m,
+            n,
+            k,
+            residual,
+        }
+    }
+
+    /// Compare the fused matmul to the unfused sequence: the same matmul, then the bias, the
+    /// activation and the residual applied element-wise to its output on the CPU.
+    fn test<R: Runtime, F: Float + CubeElement + MatmulPrecision>(&self, device: &R::Device) {
+        let client = R::client(device);
+        if !client
+            .properties()
+            .supports_type(F::as_type_native_unchecked())
+        {
+            println!("{} not supported - skipped", F::as_type_native_unchecked());
+            return;
+        }
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+
+        let random = |len, seed| {
+            (0..len)
+                .map(|i| F::new((pseudo_random(i, seed) * 2.0 - 1.0) as f32))
+                .collect::<Vec<_>>()
+        };
+        let lhs = random(batches * m * k, 1);
+        let rhs = random(batches * k * n, 2);
+        let bias = random(n, 3);
+        let residual = random(batches * m * n, 4);
+
+        let (lhs_shape, lhs_strides) = ([batches, m, k], [m * k, k, 1]);
+        let (rhs_shape, rhs_strides) = ([batches, k, n], [k * n, n, 1]);
+        let (out_shape, out_strides) = ([batches, m, n], [m * n, n, 1]);
+
+        let lhs_handle = client.create(F::as_bytes(&lhs));
+        let rhs_handle = client.create(F::as_bytes(&rhs));
+        let bias_handle = client.create(F::as_bytes(&bias));
+        let matmul_handle = client.empty(batches * m * n * size_of::<F>());
+        // The output starts as the residual when it is added in place.
+        let residual_handle = client.create(F::as_bytes(&residual));
+        let out_handle = match self.residual {
+            Residual::InPlace => residual_handle.clone(),
+            _ => client.empty(batches * m * n * size_of::<F>()),
+        };
+
+        let (lhs_ref, rhs_ref, bias_ref, matmul_ref, residual_ref, out_ref) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &lhs_handle,
+                    &lhs_strides,
+                    &lhs_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &rhs_handle,
+                    &rhs_strides,
+                    &rhs_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(&bias_handle, &[1], &[n], size_of::<F>()),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &matmul_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &residual_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &out_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<F>(),
+                ),
+            )
+        };
+
+        let matmul = layered::launch_ref::<R, F, SimpleUnitAlgorithm>(
+            &client,
+            &MatmulInputHandleRef::new(lhs_ref),
+            &MatmulInputHandleRef::new(rhs_ref),
+            &matmul_ref,
+            &Selection::default(),
+        );
+        if let Err(err) = matmul {
+            println!("Can't launch the test: {err}");
+            return;
+        }
+
+        let matmul = client.read_one_tensor(matmul_handle.copy_descriptor(
+            &out_shape,
+            &out_strides,
+            size_of::<F>(),
+        ));
+        let expected = F::from_bytes(&matmul)
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let x = value.to_f32().unwrap() + bias[i % n].to_f32().unwrap();
+                let residual = match self.residual {
+                    Residual::None => 0.0,
+                    _ => residual[i].to_f32().unwrap(),
+                };
+                F::new(gelu(x) + residual)
+            })
+            .collect::<Vec<F>>();
+
+        let residual_ref = match self.residual {
+            Residual::None => None,
+            _ => Some(&residual_ref),
+        };
+        bias_gelu_residual::launch_ref::<R, F, SimpleUnitAlgorithm>(
+            &client,
+            &lhs_ref,
+            &rhs_ref,
+            &bias_ref,
+            residual_ref,
+            &out_ref,
+            &Selection::default(),
+        )
+        .unwrap();
+
+        if let Err(e) = assert_equals_approx::<R, F>(
+            &client,
+            out_handle,
+            &out_shape,
+            &out_strides,
+            &expected,
+            4.0 * f32::EPSILON,
+        ) {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// The exact GELU, with the approximation of `erf` of Abramowitz and Stegun, of an error below
+/// `1.5e-7`.
+fn gelu(x: f32) -> f32 {
+    let z = (x as f64 / core::f64::consts::SQRT_2).abs();
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = (1.0 - poly * (-z * z).exp()).copysign(x as f64);
+    (x as f64 * 0.5 * (1.0 + erf)) as f32
+}
@@ -0,0 +1,56 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_blocked {
+    () => {
+        mod blocked {
+            $crate::testgen_matmul_blocked!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+            pub type Precision = (FloatT, FloatT);
+
+            #[test]
+            pub fn test_rhs_blocked_cols() {
+                cubecl_matmul::tests::blocked::tests::test_rhs_blocked_cols::<TestRuntime, Precision>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_lhs_blocked_rows() {
+                cubecl_matmul::tests::blocked::tests::test_lhs_blocked_rows::<TestRuntime, Precision>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_both_blocked_along_k() {
+                cubecl_matmul::tests::blocked::tests::test_both_blocked_along_k::<TestRuntime, Precision>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_partial_last_block() {
+                cubecl_matmul::tests::blocked::tests::test_partial_last_block::<TestRuntime, Precision>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod blocked {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_blocked!($float);
+                })*
+            }
+        }
+    };
+}
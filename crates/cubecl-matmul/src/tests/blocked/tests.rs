@@ -0,0 +1,249 @@
+use cubecl_core::{CubeElement, Runtime, prelude::*};
+
+use crate::{
+    MatmulInputHandleRef,
+    components::{BlockedDim, MatmulIdent, MatmulProblem, MatrixLayout, MemoryFormat},
+    kernels::layered::{
+        Algorithm, Selection, double_unit::DoubleUnitAlgorithm, launch_ref_with_formats,
+        simple_unit::SimpleUnitAlgorithm,
+    },
+    tests::test_utils::{Sample, TestPrecision},
+};
+
+/// Weights whose columns are packed by four, like the channels of `NC4HW4`.
+pub fn test_rhs_blocked_cols<R: Runtime, P: TestPrecision>(device: &R::Device) {
+    BlockedTestCase {
+        batches: 2,
+        m: 24,
+        n: 32,
+        k: 40,
+        lhs_format: MemoryFormat::Strided,
+        rhs_format: blocked(4, BlockedDim::Col),
+    }
+    .test::<R, P>(device);
+}
+
+/// The rows of a blocked lhs are read column by column.
+pub fn test_lhs_blocked_rows<R: Runtime, P: TestPrecision>(device: &R::Device) {
+    BlockedTestCase {
+        batches: 2,
+        m: 32,
+        n: 24,
+        k: 40,
+        lhs_format: blocked(4, BlockedDim::Row),
+        rhs_format: MemoryFormat::Strided,
+    }
+    .test::<R, P>(device);
+}
+
+/// Both inputs blocked along `k`.
+pub fn test_both_blocked_along_k<R: Runtime, P: TestPrecision>(device: &R::Device) {
+    BlockedTestCase {
+        batches: 1,
+        m: 32,
+        n: 32,
+        k: 64,
+        lhs_format: blocked(8, BlockedDim::Col),
+        rhs_format: blocked(8, BlockedDim::Row),
+    }
+    .test::<R, P>(device);
+}
+
+/// The last block of columns is padded, so lines can't be larger than the columns it holds.
+pub fn test_partial_last_block<R: Runtime, P: TestPrecision>(device: &R::Device) {
+    BlockedTestCase {
+        batches: 2,
+        m: 16,
+        n: 20,
+        k: 24,
+        lhs_format: MemoryFormat::Strided,
+        rhs_format: blocked(8, BlockedDim::Col),
+    }
+    .test::<R, P>(device);
+}
+
+fn blocked(block_size: u32, dim: BlockedDim) -> MemoryFormat {
+    MemoryFormat::Blocked { block_size, dim }
+}
+
+struct BlockedTestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    lhs_format: MemoryFormat,
+    rhs_format: MemoryFormat,
+}
+
+impl BlockedTestCase {
+    fn problem(&self) -> MatmulProblem {
+        let layout =
+            |format: MemoryFormat| format.matrix_layout().unwrap_or(MatrixLayout::RowMajor);
+
+        MatmulProblem {
+            m: self.m,
+            n: self.n,
+            k: self.k,
+            lhs_batches: vec![self.batches],
+            rhs_batches: vec![self.batches],
+            lhs_layout: layout(self.lhs_format),
+            rhs_layout: layout(self.rhs_format),
+            out_layout: MatrixLayout::RowMajor,
+            lhs_format: self.lhs_format,
+            rhs_format: self.rhs_format,
+        }
+    }
+
+    /// Sample both inputs, pack the blocked ones on the host, and compare the matmul of each
+    /// algorithm to the reference on the unpacked inputs.
+    fn test<R: Runtime, P: TestPrecision>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let problem = self.problem();
+
+        let lhs = sample::<R, P::EG>(&client, &problem.shape(MatmulIdent::Lhs), 1234);
+        let rhs = sample::<R, P::EG>(&client, &problem.shape(MatmulIdent::Rhs), 5678);
+
+        self.launch::<R, P, SimpleUnitAlgorithm>(&client, &problem, &lhs, &rhs);
+        self.launch::<R, P, DoubleUnitAlgorithm>(&client, &problem, &lhs, &rhs);
+    }
+
+    fn launch<R: Runtime, P: TestPrecision, A: Algorithm>(
+        &self,
+        client: &ComputeClient<R::Server, R::Channel>,
+        problem: &MatmulProblem,
+        lhs: &[P::EG],
+        rhs: &[P::EG],
+    ) {
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+        let lhs_input = Input::new(lhs, [batches, m, k], self.lhs_format);
+        let rhs_input = Input::new(rhs, [batches, k, n], self.rhs_format);
+
+        let lhs_handle = client.create(P::EG::as_bytes(&lhs_input.data));
+        let rhs_handle = client.create(P::EG::as_bytes(&rhs_input.data));
+        let zeros = vec![P::EG::from_int(0); batches * m * n];
+        let out_handle = client.create(P::EG::as_bytes(&zeros));
+        let (out_shape, out_strides) = ([batches, m, n], [m * n, n, 1]);
+
+        let (lhs_ref, rhs_ref, out_ref) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &lhs_handle,
+                    &lhs_input.strides,
+                    &lhs_input.shape,
+                    size_of::<P::EG>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &rhs_handle,
+                    &rhs_input.strides,
+                    &rhs_input.shape,
+                    size_of::<P::EG>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &out_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<P::EG>(),
+                ),
+            )
+        };
+
+        let result = launch_ref_with_formats::<R, P::MP, A>(
+            client,
+            &MatmulInputHandleRef::new(lhs_ref),
+            &MatmulInputHandleRef::new(rhs_ref),
+            &out_ref,
+            (self.lhs_format, self.rhs_format),
+            &Selection::default(),
+        );
+
+        if let Err(err) = result {
+            println!("Can't launch the test: {err}");
+            return;
+        }
+
+        P::assert_result::<R>(
+            lhs,
+            rhs,
+            problem,
+            client,
+            out_handle,
+            &out_shape,
+            &out_strides,
+        );
+    }
+}
+
+fn sample<R: Runtime, E: Sample + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shape: &[usize],
+    seed: u64,
+) -> Vec<E> {
+    let handle = E::sample::<R>(client, shape, seed);
+    let data = client.read_one_tensor(handle.as_copy_descriptor());
+
+    E::from_bytes(&data).to_vec()
+}
+
+/// An input as uploaded: the batches of row-major matrices of `shape`, packed according to the
+/// format.
+struct Input<E> {
+    data: Vec<E>,
+    shape: [usize; 3],
+    strides: [usize; 3],
+}
+
+impl<E: CubeElement + Numeric> Input<E> {
+    fn new(matrices: &[E], shape: [usize; 3], format: MemoryFormat) -> Self {
+        let [batches, rows, cols] = shape;
+
+        let MemoryFormat::Blocked { block_size, dim } = format else {
+            return Self {
+                data: matrices.to_vec(),
+                shape,
+                strides: [rows * cols, cols, 1],
+            };
+        };
+
+        // The blocks are `block_size` rows or columns of the matrix, the last one being padded
+        let block_size = block_size as usize;
+        let (blocked, other) = match dim {
+            BlockedDim::Row => (rows, cols),
+            BlockedDim::Col => (cols, rows),
+        };
+        let num_blocks = blocked.div_ceil(block_size);
+        let batch_stride = num_blocks * other * block_size;
+
+        let mut data = vec![E::from_int(0); batches * batch_stride];
+        for batch in 0..batches {
+            for block in 0..num_blocks {
+                for index in 0..other {
+                    for elem in 0..block_size {
+                        let position = block * block_size + elem;
+                        if position >= blocked {
+                            continue;
+                        }
+                        let (row, col) = match dim {
+                            BlockedDim::Row => (position, index),
+                            BlockedDim::Col => (index, position),
+                        };
+
+                        data[batch * batch_stride + (block * other + index) * block_size + elem] =
+                            matrices[batch * rows * cols + row * cols + col];
+                    }
+                }
+            }
+        }
+
+        // The strides of the matrices are those of each block
+        let strides = match dim {
+            BlockedDim::Row => [batch_stride, 1, block_size],
+            BlockedDim::Col => [batch_stride, block_size, 1],
+        };
+
+        Self {
+            data,
+            shape,
+            strides,
+        }
+    }
+}
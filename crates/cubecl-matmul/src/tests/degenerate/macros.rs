@@ -0,0 +1,69 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_degenerate {
+    () => {
+        mod degenerate {
+            $crate::testgen_matmul_degenerate!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_k_zero() {
+                cubecl_matmul::tests::degenerate::tests::test_k_zero::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_k_zero_col_major_output() {
+                cubecl_matmul::tests::degenerate::tests::test_k_zero_col_major_output::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_m_zero() {
+                cubecl_matmul::tests::degenerate::tests::test_m_zero::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_n_zero() {
+                cubecl_matmul::tests::degenerate::tests::test_n_zero::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_batch_zero() {
+                cubecl_matmul::tests::degenerate::tests::test_batch_zero::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_all_zero() {
+                cubecl_matmul::tests::degenerate::tests::test_all_zero::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod degenerate {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_degenerate!($float);
+                })*
+            }
+        }
+    };
+}
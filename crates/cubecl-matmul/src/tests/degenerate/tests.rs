@@ -0,0 +1,154 @@
+use std::fmt::Debug;
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    compute::count_launches,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    MatmulInputHandleRef, Strategy, SyncLoadingStrategy, SyncPartialLoadingStrategy,
+    components::MatmulPrecision, launch_ref,
+};
+
+pub trait DegenerateFloat: Float + CubeElement + Debug + MatmulPrecision {}
+
+impl<F: Float + CubeElement + Debug + MatmulPrecision> DegenerateFloat for F {}
+
+/// The value the output is filled with before the matmul, to tell the elements it wrote.
+const SENTINEL: f32 = 7.0;
+
+/// An empty reduction writes zeros to the whole output.
+pub fn test_k_zero<R: Runtime, F: DegenerateFloat>(device: &R::Device) {
+    DegenerateTestCase::new(2, 8, 12, 0).test::<R, F>(device);
+}
+
+/// The zeros are written through the strides of the output.
+pub fn test_k_zero_col_major_output<R: Runtime, F: DegenerateFloat>(device: &R::Device) {
+    let mut case = DegenerateTestCase::new(3, 5, 4, 0);
+    case.out_col_major = true;
+    case.test::<R, F>(device);
+}
+
+pub fn test_m_zero<R: Runtime, F: DegenerateFloat>(device: &R::Device) {
+    DegenerateTestCase::new(2, 0, 12, 8).test::<R, F>(device);
+}
+
+pub fn test_n_zero<R: Runtime, F: DegenerateFloat>(device: &R::Device) {
+    DegenerateTestCase::new(2, 8, 0, 8).test::<R, F>(device);
+}
+
+pub fn test_batch_zero<R: Runtime, F: DegenerateFloat>(device: &R::Device) {
+    DegenerateTestCase::new(0, 8, 12, 8).test::<R, F>(device);
+}
+
+/// An empty output with an empty reduction has nothing to write either.
+pub fn test_all_zero<R: Runtime, F: DegenerateFloat>(device: &R::Device) {
+    DegenerateTestCase::new(0, 0, 0, 0).test::<R, F>(device);
+}
+
+struct DegenerateTestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    /// Store the matrices of the output transposed in memory.
+    out_col_major: bool,
+}
+
+impl DegenerateTestCase {
+    fn new(batches: usize, m: usize, n: usize, k: usize) -> Self {
+        Self {
+            batches,
+            m,
+            n,
+            k,
+            out_col_major: false,
+        }
+    }
+
+    /// The strategies launched on the case, including the ones with requirements the device may
+    /// not meet, since degenerate matmuls don't launch them.
+    fn strategies() -> Vec<Strategy> {
+        vec![
+            Strategy::Auto,
+            Strategy::Naive,
+            Strategy::BatchedTiny,
+            Strategy::BatchedGemv,
+            Strategy::SimpleUnit(Default::default()),
+            Strategy::DoubleUnit(Default::default()),
+            Strategy::Simple(SyncLoadingStrategy::Cyclic, Default::default()),
+            Strategy::DoubleBuffering(SyncPartialLoadingStrategy::Hybrid, Default::default()),
+        ]
+    }
+
+    /// Launch every strategy on an output filled with a sentinel, and check that an empty output
+    /// launches nothing and that an empty reduction only launches the kernel writing zeros.
+    fn test<R: Runtime, F: DegenerateFloat>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+        let num_out = batches * m * n;
+
+        // Empty tensors still get an element, so that every handle has memory.
+        let lhs = vec![F::new(1.0); (batches * m * k).max(1)];
+        let rhs = vec![F::new(1.0); (batches * k * n).max(1)];
+        let sentinel = vec![F::new(SENTINEL); num_out.max(1)];
+
+        let (lhs_shape, rhs_shape, out_shape) = ([batches, m, k], [batches, k, n], [batches, m, n]);
+        let (lhs_strides, rhs_strides) = ([m * k, k, 1], [k * n, n, 1]);
+        let out_strides = match self.out_col_major {
+            true => [m * n, 1, m],
+            false => [m * n, n, 1],
+        };
+
+        for strategy in Self::strategies() {
+            let lhs_handle = client.create(F::as_bytes(&lhs));
+            let rhs_handle = client.create(F::as_bytes(&rhs));
+            let out_handle = client.create(F::as_bytes(&sentinel));
+            let (lhs_ref, rhs_ref, out_ref) = unsafe {
+                (
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &lhs_handle,
+                        &lhs_strides,
+                        &lhs_shape,
+                        size_of::<F>(),
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &rhs_handle,
+                        &rhs_strides,
+                        &rhs_shape,
+                        size_of::<F>(),
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &out_handle,
+                        &out_strides,
+                        &out_shape,
+                        size_of::<F>(),
+                    ),
+                )
+            };
+
+            let (result, launches) = count_launches(|| {
+                launch_ref::<R, F>(
+                    &strategy,
+                    &client,
+                    &MatmulInputHandleRef::new(lhs_ref),
+                    &MatmulInputHandleRef::new(rhs_ref),
+                    &out_ref,
+                )
+            });
+            assert!(result.is_ok(), "{strategy:?}: {result:?}");
+
+            let actual = client.read_one(out_handle);
+            let actual = F::from_bytes(&actual);
+            let (expected, expected_launches) = match num_out {
+                0 => (F::new(SENTINEL), 0),
+                _ => (F::new(0.0), 1),
+            };
+            assert_eq!(launches, expected_launches, "{strategy:?}");
+            for (index, value) in actual[..num_out.max(1)].iter().enumerate() {
+                assert_eq!(*value, expected, "{strategy:?}: index={index}");
+            }
+        }
+    }
+}
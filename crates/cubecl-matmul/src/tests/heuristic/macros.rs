@@ -0,0 +1,143 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_heuristic {
+    () => {
+        mod heuristic {
+            $crate::testgen_matmul_heuristic!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_select_tall_skinny() {
+                cubecl_matmul::tests::heuristic::tests::test_select_tall_skinny()
+            }
+
+            #[test]
+            pub fn test_select_degenerate() {
+                cubecl_matmul::tests::heuristic::tests::test_select_degenerate()
+            }
+
+            #[test]
+            pub fn test_select_square_large() {
+                cubecl_matmul::tests::heuristic::tests::test_select_square_large()
+            }
+
+            #[test]
+            pub fn test_select_batch_of_small() {
+                cubecl_matmul::tests::heuristic::tests::test_select_batch_of_small()
+            }
+
+            #[test]
+            pub fn test_select_k_huge() {
+                cubecl_matmul::tests::heuristic::tests::test_select_k_huge()
+            }
+
+            #[test]
+            pub fn test_select_k_huge_small_shared_memory() {
+                cubecl_matmul::tests::heuristic::tests::test_select_k_huge_small_shared_memory()
+            }
+
+            #[test]
+            pub fn test_select_vecmat() {
+                cubecl_matmul::tests::heuristic::tests::test_select_vecmat()
+            }
+
+            #[test]
+            pub fn test_select_vecmat_row_major_rhs() {
+                cubecl_matmul::tests::heuristic::tests::test_select_vecmat_row_major_rhs()
+            }
+
+            #[test]
+            pub fn test_select_batched_gemv() {
+                cubecl_matmul::tests::heuristic::tests::test_select_batched_gemv()
+            }
+
+            #[test]
+            pub fn test_select_batched_gemv_without_plane_ops() {
+                cubecl_matmul::tests::heuristic::tests::test_select_batched_gemv_without_plane_ops()
+            }
+
+            #[test]
+            pub fn test_select_unaccelerated() {
+                cubecl_matmul::tests::heuristic::tests::test_select_unaccelerated()
+            }
+
+            #[test]
+            pub fn test_select_batch_of_small_unaccelerated() {
+                cubecl_matmul::tests::heuristic::tests::test_select_batch_of_small_unaccelerated()
+            }
+
+            #[test]
+            pub fn test_capabilities_from_device_features() {
+                cubecl_matmul::tests::heuristic::tests::test_capabilities_from_device_features::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_tall_skinny() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_tall_skinny::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_square_large() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_square_large::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_batch_of_small() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_batch_of_small::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_k_huge() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_k_huge::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_vecmat() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_vecmat::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_gemv() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_gemv::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_launch_with_casts() {
+                cubecl_matmul::tests::heuristic::tests::test_launch_with_casts::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod heuristic {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_matmul_heuristic!($float);
+                })*
+            }
+        }
+    };
+}
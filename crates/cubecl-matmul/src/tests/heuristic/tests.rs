@@ -0,0 +1,452 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    MatmulInputHandleRef, Strategy,
+    components::{MatmulKind, MatmulPrecision, MatmulProblem, MatrixLayout, MemoryFormat},
+    kernels::heuristic::{
+        DeviceCapabilities, SelectionReason, StrategySelection, select_strategy,
+        select_strategy_for,
+    },
+    launch_ref, launch_ref_with_casts,
+    tests::test_utils::pseudo_random,
+};
+
+pub trait HeuristicFloat: Float + CubeElement + Display + MatmulPrecision {}
+
+impl<F: Float + CubeElement + Display + MatmulPrecision> HeuristicFloat for F {}
+
+/// A device with tensor cores, 48KB of shared memory and 80 SMs.
+fn accelerated_device() -> DeviceCapabilities {
+    DeviceCapabilities {
+        accelerated: true,
+        plane_ops: true,
+        barrier: true,
+        tma: false,
+        max_shared_memory_size: 48 * 1024,
+        num_streaming_multiprocessors: Some(80),
+    }
+}
+
+fn select(case: &HeuristicTestCase, device: &DeviceCapabilities) -> StrategySelection {
+    select_strategy_for(&case.problem(), size_of::<half::f16>(), device)
+}
+
+pub fn test_select_tall_skinny() {
+    let selection = select(&HeuristicTestCase::tall_skinny(), &accelerated_device());
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::TallSkinny { m: 2048, n: 16 },
+        "{selection}"
+    );
+    assert!(matches!(selection.strategy, Strategy::Simple(_, _)));
+}
+
+pub fn test_select_degenerate() {
+    for (batches, m, n, k) in [
+        (1, 0, 64, 64),
+        (1, 64, 0, 64),
+        (1, 64, 64, 0),
+        (0, 64, 64, 64),
+    ] {
+        let selection = select(
+            &HeuristicTestCase::new(batches, m, n, k),
+            &accelerated_device(),
+        );
+
+        assert_eq!(
+            selection.reason,
+            SelectionReason::Degenerate {
+                num_batches: batches,
+                m,
+                n,
+                k
+            },
+            "{selection}"
+        );
+        assert!(selection.candidates.is_empty());
+    }
+}
+
+pub fn test_select_square_large() {
+    let selection = select(&HeuristicTestCase::square_large(), &accelerated_device());
+
+    assert_eq!(selection.reason, SelectionReason::General, "{selection}");
+    assert!(matches!(selection.strategy, Strategy::Simple(_, _)));
+}
+
+pub fn test_select_batch_of_small() {
+    let selection = select(&HeuristicTestCase::batch_of_small(), &accelerated_device());
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::BatchOfSmall {
+            num_batches: 64,
+            m: 16,
+            n: 16,
+            k: 16
+        },
+        "{selection}"
+    );
+    assert!(matches!(selection.strategy, Strategy::BatchedTiny));
+}
+
+pub fn test_select_k_huge() {
+    let selection = select(&HeuristicTestCase::k_huge(), &accelerated_device());
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::LongK {
+            k: 8192,
+            num_cubes: 1,
+            num_streaming_multiprocessors: Some(80)
+        },
+        "{selection}"
+    );
+    assert!(matches!(
+        selection.strategy,
+        Strategy::DoubleBuffering(_, _)
+    ));
+}
+
+/// Without enough shared memory for two stages, the long reduction isn't double buffered.
+pub fn test_select_k_huge_small_shared_memory() {
+    let mut device = accelerated_device();
+    device.max_shared_memory_size = 16 * 1024;
+    let selection = select(&HeuristicTestCase::k_huge(), &device);
+
+    assert_eq!(selection.reason, SelectionReason::General, "{selection}");
+}
+
+pub fn test_select_vecmat() {
+    let selection = select(&HeuristicTestCase::vecmat(), &accelerated_device());
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::VecMat {
+            k: 512,
+            long_k: false
+        },
+        "{selection}"
+    );
+    assert!(matches!(selection.strategy, Strategy::SimpleVecMat(_)));
+}
+
+/// A vector-matrix product whose rhs isn't contiguous along `k` can't use inner products.
+pub fn test_select_vecmat_row_major_rhs() {
+    let mut case = HeuristicTestCase::vecmat();
+    case.rhs_layout = MatrixLayout::RowMajor;
+    let selection = select(&case, &accelerated_device());
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::Vector {
+            kind: MatmulKind::VecMat
+        },
+        "{selection}"
+    );
+    assert!(matches!(selection.strategy, Strategy::SimpleUnit(_)));
+}
+
+pub fn test_select_batched_gemv() {
+    let selection = select(&HeuristicTestCase::gemv(), &accelerated_device());
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::BatchedGemv {
+            num_batches: 8,
+            m: 1024,
+            k: 512
+        },
+        "{selection}"
+    );
+    assert!(matches!(selection.strategy, Strategy::BatchedGemv));
+}
+
+/// Without plane operations, the rows can't be reduced by planes.
+pub fn test_select_batched_gemv_without_plane_ops() {
+    let mut device = accelerated_device();
+    device.plane_ops = false;
+    let selection = select(&HeuristicTestCase::gemv(), &device);
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::Vector {
+            kind: MatmulKind::MatVec
+        },
+        "{selection}"
+    );
+}
+
+pub fn test_select_unaccelerated() {
+    let mut device = accelerated_device();
+    device.accelerated = false;
+    let selection = select(&HeuristicTestCase::square_large(), &device);
+
+    assert_eq!(
+        selection.reason,
+        SelectionReason::Unaccelerated { long_k: false },
+        "{selection}"
+    );
+    assert!(matches!(selection.strategy, Strategy::SimpleUnit(_)));
+}
+
+/// Without tensor cores, the candidates needing them are discarded before being compiled.
+pub fn test_select_batch_of_small_unaccelerated() {
+    let mut device = accelerated_device();
+    device.accelerated = false;
+    let selection = select(&HeuristicTestCase::batch_of_small(), &device);
+
+    assert!(matches!(selection.strategy, Strategy::BatchedTiny));
+    assert!(!selection.candidates.is_empty(), "{selection}");
+    for candidate in selection.candidates.iter() {
+        assert!(
+            device.supports(candidate),
+            "{candidate:?} isn't supported by the device"
+        );
+    }
+}
+
+/// The capabilities of the device of the client are the ones of its features.
+pub fn test_capabilities_from_device_features<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    let client = R::client(device);
+    let features = client.device_features();
+    let capabilities = DeviceCapabilities::new::<R, F>(&client);
+
+    assert_eq!(capabilities.plane_ops, features.plane_ops);
+    assert_eq!(capabilities.barrier, features.barrier);
+    assert_eq!(capabilities.tma, features.tma);
+    assert_eq!(
+        capabilities.max_shared_memory_size,
+        features.max_shared_memory_size
+    );
+    if capabilities.accelerated {
+        assert!(!features.accelerated_tiles.is_empty());
+    }
+    assert_eq!(
+        capabilities.supports(&Strategy::SimpleVecMat(Default::default())),
+        features.plane_ops
+    );
+}
+
+pub fn test_launch_tall_skinny<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    HeuristicTestCase::tall_skinny().test::<R, F>(device);
+}
+
+pub fn test_launch_square_large<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    HeuristicTestCase::square_large().test::<R, F>(device);
+}
+
+pub fn test_launch_batch_of_small<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    HeuristicTestCase::batch_of_small().test::<R, F>(device);
+}
+
+pub fn test_launch_k_huge<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    HeuristicTestCase::k_huge().test::<R, F>(device);
+}
+
+pub fn test_launch_vecmat<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    HeuristicTestCase::vecmat().test::<R, F>(device);
+}
+
+pub fn test_launch_gemv<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    HeuristicTestCase::gemv().test::<R, F>(device);
+}
+
+/// Operands of `u8` multiplied in the precision of `F` into an output of `i32`, cast around the
+/// selected strategy.
+pub fn test_launch_with_casts<R: Runtime, F: HeuristicFloat>(device: &R::Device) {
+    let client = R::client(device);
+    let (m, n, k) = (32, 24, 40);
+    // Small integers, so the products are exact whatever the precision.
+    let lhs = (0..m * k).map(|i| (i % 4) as u8).collect::<Vec<_>>();
+    let rhs = (0..k * n).map(|i| (i % 3) as u8).collect::<Vec<_>>();
+
+    let lhs_handle = client.create(u8::as_bytes(&lhs));
+    let rhs_handle = client.create(u8::as_bytes(&rhs));
+    let out_handle = client.empty(m * n * size_of::<i32>());
+    let (lhs_ref, rhs_ref, out_ref) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(&lhs_handle, &[k, 1], &[m, k], size_of::<u8>()),
+            TensorHandleRef::<R>::from_raw_parts(&rhs_handle, &[n, 1], &[k, n], size_of::<u8>()),
+            TensorHandleRef::<R>::from_raw_parts(&out_handle, &[n, 1], &[m, n], size_of::<i32>()),
+        )
+    };
+
+    if let Err(err) = launch_ref_with_casts::<R, F, u8, u8, i32>(
+        &Strategy::Auto,
+        &client,
+        &MatmulInputHandleRef::new(lhs_ref),
+        &MatmulInputHandleRef::new(rhs_ref),
+        &out_ref,
+    ) {
+        panic!("Can't launch the matmul with casts: {err}");
+    }
+
+    let actual = client.read_one(out_handle);
+    let actual = i32::from_bytes(&actual);
+    for row in 0..m {
+        for col in 0..n {
+            let expected = (0..k)
+                .map(|i| lhs[row * k + i] as i32 * rhs[i * n + col] as i32)
+                .sum::<i32>();
+            assert_eq!(
+                actual[row * n + col],
+                expected,
+                "Values differ: row={row}, col={col}"
+            );
+        }
+    }
+}
+
+struct HeuristicTestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    /// Store the rhs transposed in memory.
+    rhs_layout: MatrixLayout,
+}
+
+impl HeuristicTestCase {
+    fn new(batches: usize, m: usize, n: usize, k: usize) -> Self {
+        Self {
+            batches,
+            m,
+            n,
+            k,
+            rhs_layout: MatrixLayout::RowMajor,
+        }
+    }
+
+    fn tall_skinny() -> Self {
+        Self::new(1, 2048, 16, 64)
+    }
+
+    fn square_large() -> Self {
+        Self::new(1, 512, 512, 512)
+    }
+
+    fn batch_of_small() -> Self {
+        Self::new(64, 16, 16, 16)
+    }
+
+    fn k_huge() -> Self {
+        Self::new(1, 64, 64, 8192)
+    }
+
+    fn vecmat() -> Self {
+        let mut case = Self::new(2, 1, 256, 512);
+        case.rhs_layout = MatrixLayout::ColMajor;
+        case
+    }
+
+    fn gemv() -> Self {
+        Self::new(8, 1024, 1, 512)
+    }
+
+    fn problem(&self) -> MatmulProblem {
+        MatmulProblem {
+            m: self.m,
+            n: self.n,
+            k: self.k,
+            lhs_batches: vec![self.batches],
+            rhs_batches: vec![self.batches],
+            lhs_layout: MatrixLayout::RowMajor,
+            rhs_layout: self.rhs_layout,
+            out_layout: MatrixLayout::RowMajor,
+            lhs_format: MemoryFormat::Strided,
+            rhs_format: MemoryFormat::Strided,
+        }
+    }
+
+    /// Launch the strategy selected for random inputs in `[-1, 1]` on the device, and check the
+    /// output against a reference.
+    fn test<R: Runtime, F: HeuristicFloat>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+
+        let lhs = (0..batches * m * k)
+            .map(|i| F::new((2.0 * pseudo_random(i, 1) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let rhs = (0..batches * k * n)
+            .map(|i| F::new((2.0 * pseudo_random(i, 2) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let rhs_strides = match self.rhs_layout {
+            MatrixLayout::RowMajor => [k * n, n, 1],
+            MatrixLayout::ColMajor => [k * n, 1, k],
+        };
+        // The rhs element at `(b, i, j)`, wherever it is stored.
+        let rhs_at = |b: usize, i: usize, j: usize| {
+            rhs[b * rhs_strides[0] + i * rhs_strides[1] + j * rhs_strides[2]]
+        };
+
+        let lhs_handle = client.create(F::as_bytes(&lhs));
+        let rhs_handle = client.create(F::as_bytes(&rhs));
+        let out_handle = client.empty(batches * m * n * size_of::<F>());
+        let (lhs_shape, rhs_shape, out_shape) = ([batches, m, k], [batches, k, n], [batches, m, n]);
+        let (lhs_strides, out_strides) = ([m * k, k, 1], [m * n, n, 1]);
+        let (lhs_ref, rhs_ref, out_ref) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &lhs_handle,
+                    &lhs_strides,
+                    &lhs_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &rhs_handle,
+                    &rhs_strides,
+                    &rhs_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &out_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<F>(),
+                ),
+            )
+        };
+        let lhs_ref = MatmulInputHandleRef::new(lhs_ref);
+        let rhs_ref = MatmulInputHandleRef::new(rhs_ref);
+
+        let selection = select_strategy::<R, F>(&client, &lhs_ref, &rhs_ref, &out_ref);
+        if let Err(err) =
+            launch_ref::<R, F>(&selection.strategy, &client, &lhs_ref, &rhs_ref, &out_ref)
+        {
+            panic!("The selected strategy {selection} can't be launched: {err}");
+        }
+
+        let actual = client.read_one(out_handle);
+        let actual = F::from_bytes(&actual);
+        // The stage may be in a lower precision than the inputs.
+        let tolerance = 1e-2;
+
+        for b in 0..batches {
+            for row in 0..m {
+                for col in 0..n {
+                    let (mut expected, mut scale) = (0.0, 0.0);
+                    for i in 0..k {
+                        let lhs = F::to_f64(&lhs[(b * m + row) * k + i]).unwrap();
+                        let rhs = F::to_f64(&rhs_at(b, i, col)).unwrap();
+                        expected += lhs * rhs;
+                        scale += f64::abs(lhs * rhs);
+                    }
+
+                    let value = F::to_f64(&actual[(b * m + row) * n + col]).unwrap();
+                    let difference = f64::abs(value - expected);
+                    assert!(
+                        difference <= tolerance * scale.max(1.0),
+                        "Values differ with {selection}: batch={b}, row={row}, col={col}, actual={value}, expected={expected}, difference={difference}"
+                    );
+                }
+            }
+        }
+    }
+}
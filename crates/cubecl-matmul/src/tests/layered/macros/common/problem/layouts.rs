@@ -75,5 +75,12 @@ macro_rules! testgen_matmul_layouts {
                 (MatrixLayout::ColMajor, MatrixLayout::ColMajor)
             );
         }
+
+        #[cfg(feature = "matmul_tests_layouts")]
+        mod transposed_out {
+            use super::*;
+
+            $crate::testgen_matmul_transposed_out!($kind, $algorithm, $precision, $selection);
+        }
     };
 }
@@ -19,6 +19,9 @@ macro_rules! testgen_matmul_problem_size {
                     rhs_batches: vec![2],
                     lhs_layout: $layouts.0,
                     rhs_layout: $layouts.1,
+                    out_layout: $crate::components::MatrixLayout::RowMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
                 }
             );
         }
@@ -39,6 +42,9 @@ macro_rules! testgen_matmul_problem_size {
                     rhs_batches: vec![2],
                     lhs_layout: $layouts.0,
                     rhs_layout: $layouts.1,
+                    out_layout: $crate::components::MatrixLayout::RowMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
                 }
             );
         }
@@ -60,6 +66,9 @@ macro_rules! testgen_matmul_problem_size {
                     rhs_batches: vec![2],
                     lhs_layout: $layouts.0,
                     rhs_layout: $layouts.1,
+                    out_layout: $crate::components::MatrixLayout::RowMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
                 }
             );
         }
@@ -81,6 +90,9 @@ macro_rules! testgen_matmul_problem_size {
                     rhs_batches: vec![2],
                     lhs_layout: $layouts.0,
                     rhs_layout: $layouts.1,
+                    out_layout: $crate::components::MatrixLayout::RowMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
                 }
             );
         }
@@ -101,6 +113,9 @@ macro_rules! testgen_matmul_problem_size {
                     rhs_batches: vec![2],
                     lhs_layout: $layouts.0,
                     rhs_layout: $layouts.1,
+                    out_layout: $crate::components::MatrixLayout::RowMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
                 }
             );
         }
@@ -121,6 +136,86 @@ macro_rules! testgen_matmul_problem_size {
                     rhs_batches: vec![2],
                     lhs_layout: $layouts.0,
                     rhs_layout: $layouts.1,
+                    out_layout: $crate::components::MatrixLayout::RowMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
+                }
+            );
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! testgen_matmul_transposed_out {
+    // The TMA matmul only writes row-major outputs
+    (Tma, $algorithm: ty, $precision: ty, $selection: expr) => {};
+
+    ($kind: ident, $algorithm: ty, $precision: ty, $selection: expr) => {
+        use $crate::components::MatmulProblem;
+
+        mod g256x256x256 {
+            use super::*;
+            $crate::testgen_matmul_launch!(
+                $kind,
+                $algorithm,
+                $precision,
+                $selection,
+                MatmulProblem {
+                    m: 256,
+                    n: 256,
+                    k: 256,
+                    lhs_batches: vec![2],
+                    rhs_batches: vec![2],
+                    lhs_layout: MatrixLayout::RowMajor,
+                    rhs_layout: MatrixLayout::RowMajor,
+                    out_layout: MatrixLayout::ColMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
+                }
+            );
+        }
+
+        // Partial tiles along m and n
+        mod g100x60x40 {
+            use super::*;
+            $crate::testgen_matmul_launch!(
+                $kind,
+                $algorithm,
+                $precision,
+                $selection,
+                MatmulProblem {
+                    m: 100,
+                    n: 60,
+                    k: 40,
+                    lhs_batches: vec![2],
+                    rhs_batches: vec![2],
+                    lhs_layout: MatrixLayout::RowMajor,
+                    rhs_layout: MatrixLayout::RowMajor,
+                    out_layout: MatrixLayout::ColMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
+                }
+            );
+        }
+
+        mod g36x132x64 {
+            use super::*;
+            $crate::testgen_matmul_launch!(
+                $kind,
+                $algorithm,
+                $precision,
+                $selection,
+                MatmulProblem {
+                    m: 36,
+                    n: 132,
+                    k: 64,
+                    lhs_batches: vec![2],
+                    rhs_batches: vec![2],
+                    lhs_layout: MatrixLayout::RowMajor,
+                    rhs_layout: MatrixLayout::ColMajor,
+                    out_layout: MatrixLayout::ColMajor,
+                    lhs_format: $crate::components::MemoryFormat::Strided,
+                    rhs_format: $crate::components::MemoryFormat::Strided,
                 }
             );
         }
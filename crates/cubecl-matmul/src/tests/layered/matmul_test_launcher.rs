@@ -12,6 +12,7 @@ use crate::components::{MatmulProblem, MatmulSelection};
 use crate::kernels::layered::Algorithm;
 use crate::tests::test_utils::Sample;
 use crate::tests::test_utils::TestPrecision;
+use crate::tests::test_utils::panic_on_launch_err;
 
 #[derive(Debug)]
 pub struct TensorRawParts<N: Numeric + CubeElement> {
@@ -33,16 +34,7 @@ pub fn test_matmul_algorithm<A, P, R>(
     P: TestPrecision,
     R: Runtime,
 {
-    let env = std::env::var("MATMUL_TEST_MODE");
-
-    let panic_on_launch_err = match env {
-        Ok(val) => match val.as_str() {
-            "panic" => true,
-            "skip" => false,
-            _ => false,
-        },
-        Err(_) => false,
-    };
+    let panic_on_launch_err = panic_on_launch_err();
     let lhs = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Lhs);
     let rhs = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Rhs);
     let out = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Out);
@@ -56,7 +48,7 @@ pub fn test_matmul_algorithm<A, P, R>(
     let line_sizes = line_sizes
         .filter_lhs_with_tensor(&lhs.strides, &lhs.shape, problem.lhs_layout)
         .filter_rhs_with_tensor(&rhs.strides, &rhs.shape, problem.rhs_layout)
-        .filter_out_with_tensor(&out.strides, &out.shape)
+        .filter_out_with_tensor(&out.strides, &out.shape, problem.out_layout)
         .pick_max()
         .unwrap();
 
@@ -130,18 +122,26 @@ pub fn test_matmul_algorithm<A, P, R>(
         );
     }
 
+    // The transposed output is read in its memory order
+    let (mut out_shape, mut out_strides) = (out.shape, out.strides);
+    if matches!(problem.out_layout, MatrixLayout::ColMajor) {
+        let rank = out_shape.len();
+        out_shape.swap(rank - 1, rank - 2);
+        out_strides.swap(rank - 1, rank - 2);
+    }
+
     P::assert_result::<R>(
         &lhs.original_data.unwrap(),
         &rhs.original_data.unwrap(),
         &problem,
         &client,
         out.handle,
-        &out.shape,
-        &out.strides,
+        &out_shape,
+        &out_strides,
     );
 }
 
-fn tensor_raw_parts<P: TestPrecision, R: Runtime>(
+pub(crate) fn tensor_raw_parts<P: TestPrecision, R: Runtime>(
     client: &ComputeClient<R::Server, R::Channel>,
     problem: &MatmulProblem,
     ident: MatmulIdent,
@@ -242,7 +242,12 @@ fn tensor_raw_parts<P: TestPrecision, R: Runtime>(
 
             let data = vec![zero; tensor_size(problem, MatmulIdent::Out)];
 
-            let tensor_shape = problem.shape(MatmulIdent::Out);
+            let mut tensor_shape = problem.shape(MatmulIdent::Out);
+            let rank = tensor_shape.len();
+
+            if matches!(problem.out_layout, MatrixLayout::ColMajor) {
+                tensor_shape.swap(rank - 1, rank - 2);
+            }
 
             let descriptors = vec![(
                 AllocationDescriptor::optimized(tensor_shape.as_slice(), size_of::<P::EG>()),
@@ -250,7 +255,15 @@ fn tensor_raw_parts<P: TestPrecision, R: Runtime>(
             )];
 
             let mut tensors = client.create_tensors(descriptors);
-            let Allocation { handle, strides } = tensors.remove(0);
+            let Allocation {
+                handle,
+                mut strides,
+            } = tensors.remove(0);
+
+            if matches!(problem.out_layout, MatrixLayout::ColMajor) {
+                tensor_shape.swap(rank - 1, rank - 2);
+                strides.swap(rank - 1, rank - 2);
+            }
             let _offs = tensors.pop();
             let scale = tensors.pop().map(|it| it.handle);
 
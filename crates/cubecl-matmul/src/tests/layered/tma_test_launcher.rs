@@ -16,6 +16,7 @@ use crate::components::global::args::{ConcreteInputsFactory, TensorMapInputs};
 use crate::kernels::layered::Algorithm;
 use crate::tests::test_utils::Sample;
 use crate::tests::test_utils::TestPrecision;
+use crate::tests::test_utils::panic_on_launch_err;
 
 use super::matmul_test_launcher::{TensorRawParts, tensor_size, transpose};
 
@@ -30,16 +31,7 @@ pub fn test_tma_matmul_algorithm<A, P, R>(
     P: TestPrecision,
     R: Runtime,
 {
-    let env = std::env::var("MATMUL_TEST_MODE");
-
-    let panic_on_launch_err = match env {
-        Ok(val) => match val.as_str() {
-            "panic" => true,
-            "skip" => false,
-            _ => false,
-        },
-        Err(_) => false,
-    };
+    let panic_on_launch_err = panic_on_launch_err();
     let lhs = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Lhs);
     let rhs = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Rhs);
     let out = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Out);
@@ -0,0 +1,134 @@
+#![allow(missing_docs)]
+
+/// Generate the correctness suite of the matmul for a runtime: every strategy of
+/// [MatrixStrategy](crate::tests::matrix::tests::MatrixStrategy), on each combination of the
+/// layouts of the inputs, on each shape of the matrix, for each element type.
+///
+/// Combinations the runtime doesn't support are skipped instead of failing.
+///
+/// ```ignore
+/// cubecl_matmul::testgen_matmul!([f16, bf16, f32]);
+/// ```
+#[macro_export]
+macro_rules! testgen_matmul {
+    () => {
+        $crate::testgen_matmul!([f32]);
+    };
+    ([$($float:ident),*]) => {
+        mod matmul {
+            use super::*;
+            #[allow(unused)]
+            use cubecl_core::flex32;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    pub type Precision = ($float, $float);
+
+                    $crate::testgen_matmul!(@strategies);
+                })*
+            }
+        }
+    };
+    (@strategies) => {
+        mod auto {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, Auto);
+        }
+        mod naive {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, Naive);
+        }
+        mod simple_unit {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, SimpleUnit);
+        }
+        mod double_unit {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, DoubleUnit);
+        }
+        mod simple_unit_swizzled {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, SimpleUnitSwizzled);
+        }
+        mod double_unit_swizzled {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, DoubleUnitSwizzled);
+        }
+        mod simple_cyclic {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, SimpleCyclic);
+        }
+        mod double_buffering_hybrid {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, DoubleBufferingHybrid);
+        }
+        mod double_buffering_cache_hints {
+            use super::*;
+            $crate::testgen_matmul!(@layouts, DoubleBufferingCacheHints);
+        }
+    };
+    (@layouts, $strategy:ident) => {
+        mod rr {
+            use super::*;
+            $crate::testgen_matmul!(@shapes, $strategy, RowMajor, RowMajor);
+        }
+        mod rc {
+            use super::*;
+            $crate::testgen_matmul!(@shapes, $strategy, RowMajor, ColMajor);
+        }
+        mod cr {
+            use super::*;
+            $crate::testgen_matmul!(@shapes, $strategy, ColMajor, RowMajor);
+        }
+        mod cc {
+            use super::*;
+            $crate::testgen_matmul!(@shapes, $strategy, ColMajor, ColMajor);
+        }
+    };
+    (@shapes, $strategy:ident, $lhs:ident, $rhs:ident) => {
+        // A single element
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g1x1x1, 1, 1, 1, 1);
+        // An exact tile, and a tile and an element
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g16x16x16, 1, 16, 16, 16);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g17x17x17, 1, 17, 17, 17);
+        // An exact stage, and partial stages along every dimension
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g64x64x64, 1, 64, 64, 64);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g60x60x60, 1, 60, 60, 60);
+        // Prime dimensions
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g61x37x53, 1, 61, 37, 53);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g7x131x3, 1, 7, 131, 3);
+        // Vectors and an outer product
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g1x64x64, 1, 1, 64, 64);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g64x1x64, 1, 64, 1, 64);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g33x47x1, 1, 33, 47, 1);
+        // Large matrices, and a large batch
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, g256x256x256, 1, 256, 256, 256);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, b3_g64x64x64, 3, 64, 64, 64);
+        $crate::testgen_matmul!(@shape, $strategy, $lhs, $rhs, b128_g16x24x32, 128, 16, 24, 32);
+    };
+    (
+        @shape,
+        $strategy:ident,
+        $lhs:ident,
+        $rhs:ident,
+        $name:ident,
+        $batches:expr,
+        $m:expr,
+        $n:expr,
+        $k:expr
+    ) => {
+        #[test]
+        pub fn $name() {
+            cubecl_matmul::tests::matrix::tests::test_matmul_matrix::<TestRuntime, Precision>(
+                cubecl_matmul::tests::matrix::tests::MatrixStrategy::$strategy,
+                (
+                    cubecl_matmul::components::MatrixLayout::$lhs,
+                    cubecl_matmul::components::MatrixLayout::$rhs,
+                ),
+                cubecl_matmul::tests::matrix::tests::MatrixShape::new($batches, $m, $n, $k),
+                &Default::default(),
+            )
+        }
+    };
+}
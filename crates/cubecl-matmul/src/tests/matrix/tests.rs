@@ -0,0 +1,177 @@
+use cubecl_core::{Runtime, prelude::*};
+use cubecl_runtime::TypeUsage;
+
+use crate::{
+    MatmulInputHandleRef, Strategy, SyncLoadingStrategy, SyncPartialLoadingStrategy,
+    components::{CacheHints, MatmulIdent, MatmulProblem, MatmulSetupError, MatrixLayout},
+    kernels::layered::{
+        Selection, double_buffering::DoubleBufferingArgs, double_unit::DoubleUnitSelectionArgs,
+        simple_unit::SimpleUnitSelectionArgs,
+    },
+    launch_ref,
+    tests::{
+        layered::matmul_test_launcher::tensor_raw_parts,
+        test_utils::{TestPrecision, panic_on_launch_err},
+    },
+};
+
+/// The strategies every runtime is tested with, each on all the layouts and shapes of the matrix.
+#[derive(Clone, Copy, Debug)]
+pub enum MatrixStrategy {
+    Auto,
+    Naive,
+    SimpleUnit,
+    DoubleUnit,
+    /// The unit matmuls with the lines of the stages swizzled.
+    SimpleUnitSwizzled,
+    DoubleUnitSwizzled,
+    SimpleCyclic,
+    DoubleBufferingHybrid,
+    /// The double buffering with the lhs persisting in the caches and the rhs streaming.
+    DoubleBufferingCacheHints,
+}
+
+impl MatrixStrategy {
+    fn strategy(&self) -> Strategy {
+        match self {
+            MatrixStrategy::Auto => Strategy::Auto,
+            MatrixStrategy::Naive => Strategy::Naive,
+            MatrixStrategy::SimpleUnit => Strategy::SimpleUnit(Default::default()),
+            MatrixStrategy::DoubleUnit => Strategy::DoubleUnit(Default::default()),
+            MatrixStrategy::SimpleUnitSwizzled => {
+                Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
+                    stage_swizzle: true,
+                    ..Default::default()
+                }))
+            }
+            MatrixStrategy::DoubleUnitSwizzled => {
+                Strategy::DoubleUnit(Selection::Inferred(DoubleUnitSelectionArgs {
+                    stage_swizzle: true,
+                    ..Default::default()
+                }))
+            }
+            MatrixStrategy::SimpleCyclic => {
+                Strategy::Simple(SyncLoadingStrategy::Cyclic, Default::default())
+            }
+            MatrixStrategy::DoubleBufferingHybrid => {
+                Strategy::DoubleBuffering(SyncPartialLoadingStrategy::Hybrid, Default::default())
+            }
+            MatrixStrategy::DoubleBufferingCacheHints => Strategy::DoubleBuffering(
+                SyncPartialLoadingStrategy::Hybrid,
+                Selection::Inferred(DoubleBufferingArgs {
+                    specialized: false,
+                    cache_hints: CacheHints::persisting_lhs(),
+                }),
+            ),
+        }
+    }
+}
+
+/// A shape of the matrix: `batches` products of `m x k` by `k x n` matrices.
+#[derive(Clone, Copy, Debug)]
+pub struct MatrixShape {
+    pub batches: usize,
+    pub m: usize,
+    pub n: usize,
+    pub k: usize,
+}
+
+impl MatrixShape {
+    pub const fn new(batches: usize, m: usize, n: usize, k: usize) -> Self {
+        Self { batches, m, n, k }
+    }
+}
+
+/// Multiply random inputs of the given layouts and shape with the strategy, and compare the
+/// output to the CPU reference with the tolerance of the precision.
+///
+/// Combinations the runtime doesn't support are skipped: elements without arithmetic, and
+/// strategies whose features are [unavailable](MatmulSetupError::Unavailable). Other launch
+/// errors are skipped too, unless `MATMUL_TEST_MODE` is `panic`.
+pub fn test_matmul_matrix<R: Runtime, P: TestPrecision>(
+    strategy: MatrixStrategy,
+    layouts: (MatrixLayout, MatrixLayout),
+    shape: MatrixShape,
+    device: &R::Device,
+) {
+    let client = R::client(device);
+
+    if !P::EG::supported_uses(&client).contains(TypeUsage::Arithmetic) {
+        println!("Skipping the test, the element isn't supported by the runtime");
+        return;
+    }
+
+    let problem = MatmulProblem {
+        m: shape.m,
+        n: shape.n,
+        k: shape.k,
+        lhs_batches: vec![shape.batches],
+        rhs_batches: vec![shape.batches],
+        lhs_layout: layouts.0,
+        rhs_layout: layouts.1,
+        out_layout: MatrixLayout::RowMajor,
+        lhs_format: Default::default(),
+        rhs_format: Default::default(),
+    };
+
+    let lhs = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Lhs);
+    let rhs = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Rhs);
+    let out = tensor_raw_parts::<P, R>(&client, &problem, MatmulIdent::Out);
+
+    let (lhs_ref, rhs_ref, out_ref) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(
+                &lhs.handle,
+                &lhs.strides,
+                &lhs.shape,
+                size_of::<P::EG>(),
+            ),
+            TensorHandleRef::<R>::from_raw_parts(
+                &rhs.handle,
+                &rhs.strides,
+                &rhs.shape,
+                size_of::<P::EG>(),
+            ),
+            TensorHandleRef::<R>::from_raw_parts(
+                &out.handle,
+                &out.strides,
+                &out.shape,
+                size_of::<P::EG>(),
+            ),
+        )
+    };
+
+    let result = launch_ref::<R, P::MP>(
+        &strategy.strategy(),
+        &client,
+        &MatmulInputHandleRef::new(lhs_ref),
+        &MatmulInputHandleRef::new(rhs_ref),
+        &out_ref,
+    );
+
+    match result {
+        Ok(()) => {}
+        Err(MatmulSetupError::Unavailable(err)) => {
+            println!("Skipping the test, {strategy:?} is unavailable: {err:?}");
+            return;
+        }
+        Err(err) => {
+            let msg = format!("Can't launch the test: {err}");
+            if panic_on_launch_err() {
+                panic!("{msg}");
+            }
+            println!("{msg}");
+            return;
+        }
+    }
+
+    P::assert_result::<R>(
+        &lhs.original_data.unwrap(),
+        &rhs.original_data.unwrap(),
+        &problem,
+        &client,
+        out.handle,
+        &out.shape,
+        &out.strides,
+    );
+}
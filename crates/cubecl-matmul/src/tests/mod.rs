@@ -1,5 +1,16 @@
 #![allow(missing_docs)]
 
+pub mod affine_int8;
+pub mod batched_gemv;
+pub mod batched_tiny;
+pub mod bias_gelu_residual;
+pub mod blocked;
+pub mod degenerate;
+pub mod heuristic;
 pub mod layered;
-pub mod naive;
+pub mod matrix;
+pub mod scaled_fp8;
+pub mod stage_dump;
+pub mod syrk;
 pub mod test_utils;
+pub mod trsm;
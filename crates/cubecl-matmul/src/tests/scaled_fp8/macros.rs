@@ -0,0 +1,52 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_scaled_fp8 {
+    () => {
+        mod scaled_fp8 {
+            use super::*;
+
+            #[test]
+            pub fn test_e4m3() {
+                cubecl_matmul::tests::scaled_fp8::tests::test_e4m3::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_e4m3_e5m2() {
+                cubecl_matmul::tests::scaled_fp8::tests::test_e4m3_e5m2::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_f16_output() {
+                cubecl_matmul::tests::scaled_fp8::tests::test_f16_output::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_broadcast_rhs() {
+                cubecl_matmul::tests::scaled_fp8::tests::test_broadcast_rhs::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_transposed_rhs() {
+                cubecl_matmul::tests::scaled_fp8::tests::test_transposed_rhs::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_invalid_scales() {
+                cubecl_matmul::tests::scaled_fp8::tests::test_invalid_scales::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+        }
+    };
+}
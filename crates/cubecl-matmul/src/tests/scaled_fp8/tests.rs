@@ -0,0 +1,233 @@
+use cubecl_common::{e4m3, e5m2};
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, Numeric, TensorHandleRef},
+};
+use half::f16;
+
+use crate::{
+    components::MatmulSetupError,
+    kernels::scaled_fp8::{self, ScaledFp8Ref, TOLERANCE},
+    tests::test_utils::pseudo_random,
+};
+
+/// An 8-bit float the values of an operand are quantized to.
+pub trait Fp8Operand: Numeric + CubeElement {
+    /// The largest finite value.
+    const MAX: f64;
+
+    fn quantize(value: f32) -> Self;
+}
+
+impl Fp8Operand for e4m3 {
+    const MAX: f64 = e4m3::MAX;
+
+    fn quantize(value: f32) -> Self {
+        e4m3::from_f32_saturating(value)
+    }
+}
+
+impl Fp8Operand for e5m2 {
+    const MAX: f64 = e5m2::MAX;
+
+    fn quantize(value: f32) -> Self {
+        e5m2::from_f32_saturating(value)
+    }
+}
+
+/// Sizes that aren't multiples of the tiles of the tensor cores.
+pub fn test_e4m3<R: Runtime>(device: &R::Device) {
+    ScaledFp8TestCase::new(1, 37, 29, 70).test::<R, e4m3, e4m3, f32>(device);
+}
+
+/// Activations in `e4m3` and gradients in `e5m2`, as in the backward pass of FP8 training.
+pub fn test_e4m3_e5m2<R: Runtime>(device: &R::Device) {
+    ScaledFp8TestCase::new(2, 48, 40, 96).test::<R, e4m3, e5m2, f32>(device);
+}
+
+/// The output narrowed to `f16` after the scales are applied.
+pub fn test_f16_output<R: Runtime>(device: &R::Device) {
+    ScaledFp8TestCase::new(1, 64, 32, 128).test::<R, e4m3, e4m3, f16>(device);
+}
+
+/// A batch of activations multiplied by the same weights.
+pub fn test_broadcast_rhs<R: Runtime>(device: &R::Device) {
+    let mut case = ScaledFp8TestCase::new(3, 33, 20, 64);
+    case.broadcast_rhs = true;
+    case.test::<R, e4m3, e4m3, f32>(device);
+}
+
+/// Weights stored transposed, so they can't be read in lines along `n`.
+pub fn test_transposed_rhs<R: Runtime>(device: &R::Device) {
+    let mut case = ScaledFp8TestCase::new(2, 31, 45, 64);
+    case.rhs_transposed = true;
+    case.test::<R, e4m3, e4m3, f32>(device);
+}
+
+/// Scales that aren't a single value per tensor are rejected.
+pub fn test_invalid_scales<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (m, n, k) = (8, 4, 16);
+    let lhs = client.empty(m * k);
+    let rhs = client.empty(k * n);
+    let scales = client.create(f32::as_bytes(&[1.0; 8]));
+    let out = client.empty(m * n * size_of::<f32>());
+
+    let (lhs, rhs, out) = unsafe {
+        (
+            ScaledFp8Ref::new(
+                TensorHandleRef::<R>::from_raw_parts(&lhs, &[k, 1], &[m, k], 1),
+                TensorHandleRef::<R>::from_raw_parts(&scales, &[1], &[m], size_of::<f32>()),
+            ),
+            ScaledFp8Ref::new(
+                TensorHandleRef::<R>::from_raw_parts(&rhs, &[n, 1], &[k, n], 1),
+                TensorHandleRef::<R>::from_raw_parts(&scales, &[1], &[1], size_of::<f32>()),
+            ),
+            TensorHandleRef::<R>::from_raw_parts(&out, &[n, 1], &[m, n], size_of::<f32>()),
+        )
+    };
+
+    let result = scaled_fp8::launch_ref::<R, e4m3, e4m3, f32>(&client, &lhs, &rhs, &out);
+    assert!(matches!(result, Err(MatmulSetupError::InvalidConfig(_))));
+}
+
+struct ScaledFp8TestCase {
+    batches: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    /// A single rhs of rank 2 for all the matrices of the lhs.
+    broadcast_rhs: bool,
+    /// Store the rhs transposed in memory.
+    rhs_transposed: bool,
+}
+
+impl ScaledFp8TestCase {
+    fn new(batches: usize, m: usize, n: usize, k: usize) -> Self {
+        Self {
+            batches,
+            m,
+            n,
+            k,
+            broadcast_rhs: false,
+            rhs_transposed: false,
+        }
+    }
+
+    /// Quantize random values in `[-3, 3]` with the scales mapping `3` to the largest value of
+    /// each operand, and check the output against the matmul of the quantized values times their
+    /// scales on the CPU.
+    fn test<R: Runtime, EL: Fp8Operand, ER: Fp8Operand, EO: Float + CubeElement>(
+        &self,
+        device: &R::Device,
+    ) {
+        let client = R::client(device);
+        let (batches, m, n, k) = (self.batches, self.m, self.n, self.k);
+        let rhs_batches = match self.broadcast_rhs {
+            true => 1,
+            false => batches,
+        };
+
+        let (lhs_scale, rhs_scale) = ((3.0 / EL::MAX) as f32, (3.0 / ER::MAX) as f32);
+        let random = |i, seed| (6.0 * pseudo_random(i, seed) - 3.0) as f32;
+        let lhs = (0..batches * m * k)
+            .map(|i| EL::quantize(random(i, 1) / lhs_scale))
+            .collect::<Vec<_>>();
+        let rhs = (0..rhs_batches * k * n)
+            .map(|i| ER::quantize(random(i, 2) / rhs_scale))
+            .collect::<Vec<_>>();
+        let rhs_strides = match self.rhs_transposed {
+            true => [k * n, 1, k],
+            false => [k * n, n, 1],
+        };
+
+        let (lhs_shape, lhs_strides) = ([batches, m, k], [m * k, k, 1]);
+        let (out_shape, out_strides) = ([batches, m, n], [m * n, n, 1]);
+        let rhs_shape = [rhs_batches, k, n];
+        let rank_2_rhs = [k, n];
+        let (rhs_view_shape, rhs_view_strides) = match self.broadcast_rhs {
+            true => (&rank_2_rhs[..], &rhs_strides[1..]),
+            false => (&rhs_shape[..], &rhs_strides[..]),
+        };
+
+        let lhs_handle = client.create(EL::as_bytes(&lhs));
+        let rhs_handle = client.create(ER::as_bytes(&rhs));
+        let lhs_scale_handle = client.create(f32::as_bytes(&[lhs_scale]));
+        let rhs_scale_handle = client.create(f32::as_bytes(&[rhs_scale]));
+        let out_handle = client.empty(batches * m * n * size_of::<EO>());
+
+        let (lhs_ref, rhs_ref, out_ref) = unsafe {
+            (
+                ScaledFp8Ref::new(
+                    TensorHandleRef::<R>::from_raw_parts(&lhs_handle, &lhs_strides, &lhs_shape, 1),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &lhs_scale_handle,
+                        &[1],
+                        &[1],
+                        size_of::<f32>(),
+                    ),
+                ),
+                ScaledFp8Ref::new(
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &rhs_handle,
+                        rhs_view_strides,
+                        rhs_view_shape,
+                        1,
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &rhs_scale_handle,
+                        &[1],
+                        &[1],
+                        size_of::<f32>(),
+                    ),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &out_handle,
+                    &out_strides,
+                    &out_shape,
+                    size_of::<EO>(),
+                ),
+            )
+        };
+
+        let result = scaled_fp8::launch_ref::<R, EL, ER, EO>(&client, &lhs_ref, &rhs_ref, &out_ref);
+        match result {
+            Ok(()) => {}
+            Err(MatmulSetupError::Unavailable(err)) => {
+                println!("Skipping the test, the fp8 matmul is unavailable: {err:?}");
+                return;
+            }
+            Err(err) => panic!("Can't launch the fp8 matmul: {err}"),
+        }
+
+        let actual = client.read_one(out_handle);
+        let actual = EO::from_bytes(&actual);
+        let scale = lhs_scale as f64 * rhs_scale as f64;
+        let epsilon = EO::EPSILON.to_f64().unwrap();
+
+        for b in 0..batches {
+            let rhs_batch = b % rhs_batches;
+            for row in 0..m {
+                for col in 0..n {
+                    let (mut expected, mut magnitude) = (0.0, 0.0);
+                    for i in 0..k {
+                        let lhs = EL::to_f64(&lhs[(b * m + row) * k + i]).unwrap();
+                        let rhs_index =
+                            rhs_batch * rhs_strides[0] + i * rhs_strides[1] + col * rhs_strides[2];
+                        let rhs = ER::to_f64(&rhs[rhs_index]).unwrap();
+                        expected += lhs * rhs * scale;
+                        magnitude += f64::abs(lhs * rhs * scale);
+                    }
+
+                    let value = EO::to_f64(&actual[(b * m + row) * n + col]).unwrap();
+                    let difference = f64::abs(value - expected);
+                    let tolerance = TOLERANCE as f64 * magnitude + epsilon * f64::abs(expected);
+                    assert!(
+                        difference <= tolerance,
+                        "Values differ: batch={b}, row={row}, col={col}, actual={value}, expected={expected}, difference={difference}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_matmul_stage_dump {
+    () => {
+        mod stage_dump {
+            use super::*;
+
+            #[test]
+            pub fn test_dump_lhs_stage_tile() {
+                cubecl_matmul::tests::stage_dump::tests::test_dump_lhs_stage_tile::<TestRuntime>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_layout_reads_checked_shapes() {
+                cubecl_matmul::tests::stage_dump::tests::test_layout_reads_checked_shapes::<
+                    TestRuntime,
+                >(&Default::default())
+            }
+        }
+    };
+}
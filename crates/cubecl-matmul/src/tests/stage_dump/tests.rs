@@ -0,0 +1,172 @@
+use cubecl_core::{
+    self as cubecl,
+    ir::{Branch, Metadata, Operation, Scope},
+    prelude::*,
+};
+use cubecl_std::tensor::r#virtual::VirtualTensor;
+
+use crate::components::{
+    MatrixLayout, MemoryFormat,
+    global::memory::{GlobalMemoryConfig, SimpleGlobalLayout, TensorReader},
+};
+
+const M: u32 = 16;
+const K: u32 = 32;
+const TILE_ROW: u32 = 1;
+const TILE_COL: u32 = 1;
+
+/// Tiles of `8x16` in an lhs of `16x32`, loaded in lines of 4 elements.
+fn config() -> GlobalMemoryConfig {
+    GlobalMemoryConfig {
+        elements_in_tile_row: 8,
+        elements_in_tile_col: 16,
+        elements_in_stage_row: M,
+        elements_in_stage_col: K,
+        global_line_size: 4,
+        check_row_bounds: false,
+        check_col_bounds: false,
+        matrix_layout: MatrixLayout::RowMajor,
+        memory_format: MemoryFormat::Strided,
+    }
+}
+
+/// Each unit loads a line of a tile of the lhs into the stage as the cyclic loaders do, then
+/// records the stage tile, tagged with the position of each element in the tile.
+#[cube(launch, create_dummy_kernel)]
+fn dump_lhs_stage_tile<E: Numeric>(
+    lhs: &Tensor<Line<E>>,
+    sink: &mut DebugSink,
+    #[comptime] config: GlobalMemoryConfig,
+) {
+    let line_size = config.global_line_size;
+    let num_lines =
+        comptime!(config.elements_in_tile_row * config.elements_in_tile_col / line_size);
+
+    let lhs = VirtualTensor::<E>::new::<Tensor<Line<E>>>(lhs);
+    let layout = SimpleGlobalLayout::new(&lhs, 0, config);
+    let reader = TensorReader::new(lhs.view(layout));
+    let mut stage = SharedMemory::<E>::new_lined(num_lines, line_size);
+
+    if UNIT_POS < num_lines {
+        stage[UNIT_POS] =
+            reader.load_coalesced_in_tile(TILE_ROW, TILE_COL, UNIT_POS * line_size, config);
+    }
+    sync_cube();
+
+    if UNIT_POS < num_lines {
+        let line = stage[UNIT_POS];
+        #[unroll]
+        for i in 0..line_size {
+            debug_record!(sink, UNIT_POS * line_size + i, line[i]);
+        }
+    }
+}
+
+/// The values of the stage tile match the tile of the lhs.
+pub fn test_dump_lhs_stage_tile<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let config = config();
+    let tile_size = config.elements_in_tile_row * config.elements_in_tile_col;
+
+    let data = (0..M * K).map(|i| i as f32).collect::<Vec<_>>();
+    let lhs = client.create(f32::as_bytes(&data));
+    let sink = DebugSinkHandle::new::<R>(&client, tile_size as usize);
+
+    dump_lhs_stage_tile::launch::<f32, R>(
+        &client,
+        CubeCount::Static(1, 1, 1),
+        CubeDim::new_1d(tile_size / config.global_line_size),
+        unsafe {
+            TensorArg::from_raw_parts::<f32>(
+                &lhs,
+                &[K as usize, 1],
+                &[M as usize, K as usize],
+                config.global_line_size as u8,
+            )
+        },
+        sink.as_arg(),
+        config,
+    );
+
+    let records = sink.read::<R>(&client);
+    assert!(!records.overflowed);
+
+    let mut stage_tile = vec![None; tile_size as usize];
+    for record in records.records.iter() {
+        stage_tile[record.tag as usize] = Some(record.value);
+    }
+
+    let expected = (0..tile_size)
+        .map(|position| {
+            let row =
+                TILE_ROW * config.elements_in_tile_row + position / config.elements_in_tile_col;
+            let col =
+                TILE_COL * config.elements_in_tile_col + position % config.elements_in_tile_col;
+            Some(DebugValue::F32((row * K + col) as f32))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(stage_tile, expected);
+}
+
+/// The layout only reads the shapes of the dimensions whose bounds are checked.
+pub fn test_layout_reads_checked_shapes<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let handle = client.empty((M * K) as usize * size_of::<f32>());
+    let sink = DebugSinkHandle::new::<R>(&client, 1);
+
+    let num_shape_reads = |check_row_bounds, check_col_bounds| {
+        let config = GlobalMemoryConfig {
+            check_row_bounds,
+            check_col_bounds,
+            ..config()
+        };
+        let kernel = dump_lhs_stage_tile::create_dummy_kernel::<f32, R>(
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(32),
+            unsafe {
+                TensorArg::from_raw_parts::<f32>(
+                    &handle,
+                    &[K as usize, 1],
+                    &[M as usize, K as usize],
+                    config.global_line_size as u8,
+                )
+            },
+            sink.as_arg(),
+            config,
+        );
+        count_shape_reads(&kernel.define().body)
+    };
+
+    assert_eq!(num_shape_reads(false, false), 0);
+    assert_eq!(num_shape_reads(true, false), 1);
+    assert_eq!(num_shape_reads(false, true), 1);
+    assert_eq!(num_shape_reads(true, true), 2);
+}
+
+/// The number of shape reads in the scope and its nested scopes.
+fn count_shape_reads(scope: &Scope) -> usize {
+    scope
+        .instructions
+        .iter()
+        .map(|instruction| match &instruction.operation {
+            Operation::Metadata(Metadata::Shape { .. }) => 1,
+            Operation::Branch(branch) => match branch {
+                Branch::If(op) => count_shape_reads(&op.scope),
+                Branch::IfElse(op) => {
+                    count_shape_reads(&op.scope_if) + count_shape_reads(&op.scope_else)
+                }
+                Branch::Switch(op) => {
+                    count_shape_reads(&op.scope_default)
+                        + op.cases
+                            .iter()
+                            .map(|(_, case)| count_shape_reads(case))
+                            .sum::<usize>()
+                }
+                Branch::RangeLoop(op) => count_shape_reads(&op.scope),
+                Branch::Loop(op) => count_shape_reads(&op.scope),
+                Branch::Return | Branch::Break => 0,
+            },
+            _ => 0,
+        })
+        .sum()
+}
@@ -0,0 +1,76 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_syrk {
+    () => {
+        mod syrk {
+            $crate::testgen_syrk!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_lower() {
+                cubecl_matmul::tests::syrk::tests::test_lower::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_upper() {
+                cubecl_matmul::tests::syrk::tests::test_upper::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_transposed() {
+                cubecl_matmul::tests::syrk::tests::test_transposed::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_beta_zero() {
+                cubecl_matmul::tests::syrk::tests::test_beta_zero::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_mirror() {
+                cubecl_matmul::tests::syrk::tests::test_mirror::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_single_panel() {
+                cubecl_matmul::tests::syrk::tests::test_single_panel::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_lower_auto() {
+                cubecl_matmul::tests::syrk::tests::test_lower_auto::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod syrk {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_syrk!($float);
+                })*
+            }
+        }
+    };
+}
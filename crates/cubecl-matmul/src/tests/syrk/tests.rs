@@ -0,0 +1,214 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    Strategy,
+    components::MatmulPrecision,
+    kernels::{
+        syrk::{SyrkOptions, syrk},
+        trsm::Triangle,
+    },
+    tests::test_utils::pseudo_random,
+};
+
+pub trait SyrkFloat: Float + CubeElement + Display + MatmulPrecision {}
+
+impl<F: Float + CubeElement + Display + MatmulPrecision> SyrkFloat for F {}
+
+pub fn test_lower<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    SyrkTestCase::new(300, 40, SyrkOptions::new(Triangle::Lower)).test::<R, F>(device);
+}
+
+pub fn test_upper<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    SyrkTestCase::new(300, 40, SyrkOptions::new(Triangle::Upper)).test::<R, F>(device);
+}
+
+/// `aᵀ · a` into a column-major output.
+pub fn test_transposed<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    let options = SyrkOptions::new(Triangle::Lower).with_transposed();
+    let mut case = SyrkTestCase::new(200, 33, options);
+    case.c_column_major = true;
+    case.test::<R, F>(device);
+}
+
+/// The computed half of the output is NaN, and must not be read.
+pub fn test_beta_zero<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    let mut case = SyrkTestCase::new(150, 20, SyrkOptions::new(Triangle::Upper));
+    case.beta = 0.0;
+    case.test::<R, F>(device);
+}
+
+pub fn test_mirror<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    let options = SyrkOptions::new(Triangle::Lower).with_mirror();
+    SyrkTestCase::new(150, 20, options).test::<R, F>(device);
+}
+
+pub fn test_single_panel<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    SyrkTestCase::new(5, 3, SyrkOptions::new(Triangle::Upper)).test::<R, F>(device);
+}
+
+/// The products launched with the default matmul, which may compute in a lower precision.
+pub fn test_lower_auto<R: Runtime, F: SyrkFloat>(device: &R::Device) {
+    let mut case = SyrkTestCase::new(256, 64, SyrkOptions::new(Triangle::Lower));
+    case.strategy = Strategy::Auto;
+    case.tolerance = Some(1e-2);
+    case.test::<R, F>(device);
+}
+
+struct SyrkTestCase {
+    n: usize,
+    k: usize,
+    options: SyrkOptions,
+    alpha: f64,
+    beta: f64,
+    /// Store `c` transposed in memory.
+    c_column_major: bool,
+    strategy: Strategy,
+    /// The largest error relative to the sum of the magnitudes of the terms of an element,
+    /// a multiple of the epsilon of the element by default.
+    tolerance: Option<f64>,
+}
+
+impl SyrkTestCase {
+    fn new(n: usize, k: usize, options: SyrkOptions) -> Self {
+        Self {
+            n,
+            k,
+            options,
+            alpha: 1.5,
+            beta: 0.5,
+            c_column_major: false,
+            strategy: Strategy::Naive,
+            tolerance: None,
+        }
+    }
+
+    /// Update a random `c` with a random `a` in `[-1, 1]`, and check the computed half against a reference
+    /// and the other half against its initial values, unless it is mirrored.
+    fn test<R: Runtime, F: SyrkFloat>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let (n, k) = (self.n, self.k);
+        let lower = self.options.triangle == Triangle::Lower;
+        let computed = |row: usize, col: usize| match lower {
+            true => col <= row,
+            false => col >= row,
+        };
+
+        // `a` is stored as `[k, n]` when it is given transposed.
+        let a_memory = (0..n * k)
+            .map(|i| F::new((2.0 * pseudo_random(i, 1) - 1.0) as f32))
+            .collect::<Vec<_>>();
+        let a_strides = match self.options.transposed {
+            true => [1, n],
+            false => [k, 1],
+        };
+        let a = (0..n * k)
+            .map(|i| {
+                let value = a_memory[(i / k) * a_strides[0] + (i % k) * a_strides[1]];
+                F::to_f64(&value).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let c_strides = match self.c_column_major {
+            true => [1, n],
+            false => [n, 1],
+        };
+        let mut c_memory = vec![F::new(0.0); n * n];
+        for row in 0..n {
+            for col in 0..n {
+                let value = match computed(row, col) && self.beta == 0.0 {
+                    true => f32::NAN,
+                    false => (2.0 * pseudo_random(row * n + col, 2) - 1.0) as f32,
+                };
+                c_memory[row * c_strides[0] + col * c_strides[1]] = F::new(value);
+            }
+        }
+        let initial = |row: usize, col: usize| {
+            F::to_f64(&c_memory[row * c_strides[0] + col * c_strides[1]]).unwrap()
+        };
+
+        let a_handle = client.create(F::as_bytes(&a_memory));
+        let c_handle = client.create(F::as_bytes(&c_memory));
+        let a_shape = match self.options.transposed {
+            true => [k, n],
+            false => [n, k],
+        };
+        let a_memory_strides = match self.options.transposed {
+            true => [n, 1],
+            false => [k, 1],
+        };
+        let c_shape = [n, n];
+        let (a_ref, c_ref) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &a_handle,
+                    &a_memory_strides,
+                    &a_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &c_handle,
+                    &c_strides,
+                    &c_shape,
+                    size_of::<F>(),
+                ),
+            )
+        };
+        syrk::<R, F>(
+            &self.strategy,
+            &client,
+            &a_ref,
+            &c_ref,
+            F::new(self.alpha as f32),
+            F::new(self.beta as f32),
+            self.options,
+        )
+        .unwrap();
+
+        let actual = client.read_one(c_handle);
+        let actual = F::from_bytes(&actual);
+        let tolerance = self
+            .tolerance
+            .unwrap_or(4.0 * k as f64 * F::EPSILON.to_f64().unwrap());
+
+        for row in 0..n {
+            for col in 0..n {
+                let value = F::to_f64(&actual[row * c_strides[0] + col * c_strides[1]]).unwrap();
+                let (source_row, source_col) = match computed(row, col) {
+                    true => (row, col),
+                    false if self.options.mirror => (col, row),
+                    false => {
+                        assert_eq!(
+                            value.to_bits(),
+                            initial(row, col).to_bits(),
+                            "The other triangle was written: row={row}, col={col}"
+                        );
+                        continue;
+                    }
+                };
+
+                let (mut expected, mut scale) = (0.0, 0.0);
+                for i in 0..k {
+                    let term = self.alpha * a[source_row * k + i] * a[source_col * k + i];
+                    expected += term;
+                    scale += f64::abs(term);
+                }
+                if self.beta != 0.0 {
+                    let term = self.beta * initial(source_row, source_col);
+                    expected += term;
+                    scale += f64::abs(term);
+                }
+
+                let difference = f64::abs(value - expected);
+                assert!(
+                    difference <= tolerance * scale.max(1.0),
+                    "Values differ: row={row}, col={col}, actual={value}, expected={expected}, difference={difference}"
+                );
+            }
+        }
+    }
+}
@@ -11,8 +11,8 @@ use cubecl_core::{
 use cubecl_runtime::MmaConfig;
 
 use crate::{
-    components::{MatmulIdent, MatmulPrecision, MatmulProblem},
-    tests::layered::matmul_test_launcher::strides,
+    components::{MatmulIdent, MatmulPrecision, MatmulProblem, MatrixLayout},
+    tests::layered::matmul_test_launcher::{strides, transpose},
 };
 use cubecl_std::tensor::TensorHandle;
 
@@ -81,6 +81,12 @@ where
             .into_iter()
             .map(|x| x.cast_into())
             .collect::<Vec<EG>>();
+        let expected = match problem.out_layout {
+            MatrixLayout::RowMajor => expected,
+            MatrixLayout::ColMajor => {
+                transpose(&expected, problem.num_batches(), problem.m, problem.n)
+            }
+        };
 
         if let Err(e) =
             assert_equals_approx::<R, EG>(client, out, shape, strides, &expected, epsilon)
@@ -90,6 +96,19 @@ where
     }
 }
 
+/// Whether tests panic when their kernel can't be launched, with `MATMUL_TEST_MODE=panic`,
+/// instead of being skipped.
+pub(crate) fn panic_on_launch_err() -> bool {
+    match std::env::var("MATMUL_TEST_MODE") {
+        Ok(val) => match val.as_str() {
+            "panic" => true,
+            "skip" => false,
+            _ => false,
+        },
+        Err(_) => false,
+    }
+}
+
 /// Compares the content of a handle to a given slice of f32.
 pub(crate) fn assert_equals_approx<R: Runtime, F: Float + CubeElement + Display>(
     client: &ComputeClient<R::Server, R::Channel>,
@@ -460,3 +479,12 @@ where {
         out
     }
 }
+
+/// A value in `[0, 1)` hashed from the `index` and the `seed`.
+pub(crate) fn pseudo_random(index: usize, seed: usize) -> f64 {
+    let hash = (index as u64 ^ (seed as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .wrapping_mul(0xBF58_476D_1CE4_E5B9)
+        .rotate_right(29)
+        .wrapping_mul(0x94D0_49BB_1331_11EB);
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,86 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_trsm {
+    () => {
+        mod trsm {
+            $crate::testgen_trsm!(f32);
+        }
+    };
+    ($float:ident) => {
+            use super::*;
+
+            pub type FloatT = $float;
+
+            #[test]
+            pub fn test_lower() {
+                cubecl_matmul::tests::trsm::tests::test_lower::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_upper() {
+                cubecl_matmul::tests::trsm::tests::test_upper::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_lower_unit_diagonal() {
+                cubecl_matmul::tests::trsm::tests::test_lower_unit_diagonal::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_upper_unit_diagonal() {
+                cubecl_matmul::tests::trsm::tests::test_upper_unit_diagonal::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_single_block() {
+                cubecl_matmul::tests::trsm::tests::test_single_block::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_many_right_hand_sides() {
+                cubecl_matmul::tests::trsm::tests::test_many_right_hand_sides::<
+                    TestRuntime,
+                    FloatT,
+                >(&Default::default())
+            }
+
+            #[test]
+            pub fn test_column_major() {
+                cubecl_matmul::tests::trsm::tests::test_column_major::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+
+            #[test]
+            pub fn test_lower_auto() {
+                cubecl_matmul::tests::trsm::tests::test_lower_auto::<TestRuntime, FloatT>(
+                    &Default::default(),
+                )
+            }
+    };
+    ([$($float:ident),*]) => {
+        mod trsm {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_trsm!($float);
+                })*
+            }
+        }
+    };
+}
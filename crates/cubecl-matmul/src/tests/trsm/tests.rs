@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use cubecl_core::{
+    CubeElement, Runtime,
+    prelude::{Float, TensorHandleRef},
+};
+
+use crate::{
+    Strategy,
+    components::MatmulPrecision,
+    kernels::trsm::{Triangle, TrsmOptions, trsm},
+    tests::test_utils::pseudo_random,
+};
+
+pub trait TrsmFloat: Float + CubeElement + Display + MatmulPrecision {}
+
+impl<F: Float + CubeElement + Display + MatmulPrecision> TrsmFloat for F {}
+
+pub fn test_lower<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    TrsmTestCase::new(100, 37, TrsmOptions::new(Triangle::Lower)).test::<R, F>(device);
+}
+
+pub fn test_upper<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    TrsmTestCase::new(100, 37, TrsmOptions::new(Triangle::Upper)).test::<R, F>(device);
+}
+
+pub fn test_lower_unit_diagonal<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    let options = TrsmOptions::new(Triangle::Lower).with_unit_diagonal();
+    TrsmTestCase::new(70, 5, options).test::<R, F>(device);
+}
+
+pub fn test_upper_unit_diagonal<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    let options = TrsmOptions::new(Triangle::Upper).with_unit_diagonal();
+    TrsmTestCase::new(70, 5, options).test::<R, F>(device);
+}
+
+pub fn test_single_block<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    TrsmTestCase::new(19, 3, TrsmOptions::new(Triangle::Upper)).test::<R, F>(device);
+}
+
+/// More right-hand sides than the units of a cube.
+pub fn test_many_right_hand_sides<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    TrsmTestCase::new(65, 300, TrsmOptions::new(Triangle::Lower)).test::<R, F>(device);
+}
+
+pub fn test_column_major<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    let mut case = TrsmTestCase::new(96, 40, TrsmOptions::new(Triangle::Upper));
+    case.column_major = true;
+    case.test::<R, F>(device);
+}
+
+/// The updates launched with the default matmul, which may compute in a lower precision.
+pub fn test_lower_auto<R: Runtime, F: TrsmFloat>(device: &R::Device) {
+    let mut case = TrsmTestCase::new(256, 64, TrsmOptions::new(Triangle::Lower));
+    case.strategy = Strategy::Auto;
+    case.tolerance = Some(1e-3);
+    case.test::<R, F>(device);
+}
+
+struct TrsmTestCase {
+    n: usize,
+    m: usize,
+    options: TrsmOptions,
+    /// Store `a` and `b` transposed in memory.
+    column_major: bool,
+    strategy: Strategy,
+    /// The largest relative residual, a multiple of the epsilon of the element by default.
+    tolerance: Option<f64>,
+}
+
+impl TrsmTestCase {
+    fn new(n: usize, m: usize, options: TrsmOptions) -> Self {
+        Self {
+            n,
+            m,
+            options,
+            column_major: false,
+            strategy: Strategy::Naive,
+            tolerance: None,
+        }
+    }
+
+    /// Solve a random well-conditioned system and check the residual `‖a · x - b‖ / ‖b‖`.
+    ///
+    /// The diagonal is in `[2, 3]` and the other coefficients of the triangle in `[-1, 1] / n`. The unread
+    /// half of `a`, and the diagonal when it is assumed to be ones, are NaN.
+    fn test<R: Runtime, F: TrsmFloat>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let (n, m) = (self.n, self.m);
+        let lower = self.options.triangle == Triangle::Lower;
+
+        let mut a = vec![f64::NAN; n * n];
+        for row in 0..n {
+            for col in 0..n {
+                let value = if row == col {
+                    match self.options.unit_diagonal {
+                        true => continue,
+                        false => 2.0 + pseudo_random(row * n + col, 1),
+                    }
+                } else if (row > col) == lower {
+                    (2.0 * pseudo_random(row * n + col, 2) - 1.0) / n as f64
+                } else {
+                    continue;
+                };
+                a[row * n + col] = value;
+            }
+        }
+        let b = (0..n * m)
+            .map(|i| 2.0 * pseudo_random(i, 3) - 1.0)
+            .collect::<Vec<_>>();
+
+        let (a_strides, b_strides) = match self.column_major {
+            true => ([1, n], [1, n]),
+            false => ([n, 1], [m, 1]),
+        };
+        let to_memory = |values: &[f64], rows: usize, cols: usize, strides: [usize; 2]| {
+            let mut memory = vec![F::new(0.0); values.len()];
+            for row in 0..rows {
+                for col in 0..cols {
+                    memory[row * strides[0] + col * strides[1]] =
+                        F::new(values[row * cols + col] as f32);
+                }
+            }
+            memory
+        };
+        let from_memory = |memory: &[F], rows: usize, cols: usize, strides: [usize; 2]| {
+            (0..rows * cols)
+                .map(|i| {
+                    let value = memory[(i / cols) * strides[0] + (i % cols) * strides[1]];
+                    F::to_f64(&value).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        let a_memory = to_memory(&a, n, n, a_strides);
+        let b_memory = to_memory(&b, n, m, b_strides);
+        // The operands as seen by the device, so the rounding to `F` isn't part of the residual.
+        let a = from_memory(&a_memory, n, n, a_strides);
+        let b = from_memory(&b_memory, n, m, b_strides);
+
+        let a_handle = client.create(F::as_bytes(&a_memory));
+        let b_handle = client.create(F::as_bytes(&b_memory));
+        let (a_shape, b_shape) = ([n, n], [n, m]);
+        let (a_ref, b_ref) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &a_handle,
+                    &a_strides,
+                    &a_shape,
+                    size_of::<F>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &b_handle,
+                    &b_strides,
+                    &b_shape,
+                    size_of::<F>(),
+                ),
+            )
+        };
+        trsm::<R, F>(&self.strategy, &client, &a_ref, &b_ref, self.options).unwrap();
+
+        let actual = client.read_one(b_handle);
+        let x = from_memory(F::from_bytes(&actual), n, m, b_strides);
+
+        let mut residual = 0.0;
+        for row in 0..n {
+            for col in 0..m {
+                let mut sum = -b[row * m + col];
+                for k in 0..n {
+                    let coefficient = match (k == row, self.options.unit_diagonal) {
+                        (true, true) => 1.0,
+                        (true, false) => a[row * n + k],
+                        _ if (row > k) == lower => a[row * n + k],
+                        _ => continue,
+                    };
+                    sum += coefficient * x[k * m + col];
+                }
+                residual += sum * sum;
+            }
+        }
+        let norm = b.iter().map(|b| b * b).sum::<f64>();
+        let relative_residual = (residual / norm).sqrt();
+
+        let tolerance = self
+            .tolerance
+            .unwrap_or(1000.0 * F::EPSILON.to_f64().unwrap());
+        assert!(
+            relative_residual <= tolerance,
+            "Relative residual too large: residual={relative_residual}, tolerance={tolerance}"
+        );
+    }
+}
@@ -67,6 +67,25 @@ pub fn should_tune_double_buffering(fused: bool, key: &MatmulAutotuneKey) -> boo
         }
 }
 
+/// Whether it's a good idea to try and run the double-buffered matmul with the lhs persisting in
+/// the caches and the rhs streaming through them, see
+/// [CacheHints::persisting_lhs](crate::components::CacheHints::persisting_lhs).
+///
+/// Only a large `k` reuses the tiles of the lhs enough for it to matter.
+pub fn should_tune_cache_hints(key: &MatmulAutotuneKey) -> bool {
+    matches!(key.analysis.kind, MatmulKind::General)
+        && matches!(key.analysis.scale_global, MatmulGlobalScale::Large)
+        && key.definition.k >= 4096
+}
+
+/// Whether it's a good idea to try and run the unit matmuls with swizzled stages, see
+/// [StageSwizzle](crate::components::stage::StageSwizzle).
+///
+/// Small problems spend too little time reading the stages for the bank conflicts to matter.
+pub fn should_tune_stage_swizzle(key: &MatmulAutotuneKey) -> bool {
+    !matches!(key.analysis.scale_global, MatmulGlobalScale::Small)
+}
+
 impl MatmulAutotuneKey {
     /// Create the autotune key based on the shape of both lhs and rhs as well as the element type
     /// used for the calculation.
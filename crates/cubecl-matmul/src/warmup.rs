@@ -0,0 +1,98 @@
+use cubecl_core::{Runtime, client::ComputeClient};
+use cubecl_std::tensor::TensorHandle;
+
+use crate::{
+    AsyncLoadingStrategy, MatmulInputHandleRef, Strategy, SyncLoadingStrategy,
+    SyncPartialLoadingStrategy,
+    components::{AccG, CacheHints, LhsG, MatmulPrecision, RhsG},
+    kernels::layered::{
+        Selection, double_buffering::DoubleBufferingArgs, double_unit::DoubleUnitSelectionArgs,
+        simple::SimpleArgs, simple_unit::SimpleUnitSelectionArgs,
+    },
+};
+
+/// The strategies compiled by [warmup], skipping the ones needing tensor maps.
+pub fn warmup_strategies() -> Vec<Strategy> {
+    vec![
+        Strategy::Simple(SyncLoadingStrategy::Cyclic, Default::default()),
+        Strategy::Simple(
+            SyncLoadingStrategy::Cyclic,
+            Selection::Inferred(SimpleArgs { multi_rows: true }),
+        ),
+        Strategy::Simple(SyncLoadingStrategy::Strided, Default::default()),
+        Strategy::Simple(SyncLoadingStrategy::Tilewise, Default::default()),
+        Strategy::SimpleBarrier(AsyncLoadingStrategy::Cooperative),
+        Strategy::SimpleBarrier(AsyncLoadingStrategy::Cyclic),
+        Strategy::DoubleBuffering(SyncPartialLoadingStrategy::Cyclic, Default::default()),
+        Strategy::DoubleBuffering(SyncPartialLoadingStrategy::Tilewise, Default::default()),
+        Strategy::DoubleBuffering(SyncPartialLoadingStrategy::Hybrid, Default::default()),
+        Strategy::DoubleBuffering(
+            SyncPartialLoadingStrategy::Hybrid,
+            Selection::Inferred(DoubleBufferingArgs {
+                specialized: true,
+                ..Default::default()
+            }),
+        ),
+        Strategy::DoubleBuffering(
+            SyncPartialLoadingStrategy::Hybrid,
+            Selection::Inferred(DoubleBufferingArgs {
+                specialized: false,
+                cache_hints: CacheHints::persisting_lhs(),
+            }),
+        ),
+        Strategy::OrderedDoubleBuffering(Default::default()),
+        Strategy::SimpleUnit(Default::default()),
+        Strategy::DoubleUnit(Default::default()),
+        Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
+            stage_swizzle: true,
+            ..Default::default()
+        })),
+        Strategy::DoubleUnit(Selection::Inferred(DoubleUnitSelectionArgs {
+            stage_swizzle: true,
+            ..Default::default()
+        })),
+        Strategy::SimpleVecMat(Default::default()),
+        Strategy::DoubleVecMat(Default::default()),
+        Strategy::Naive,
+        Strategy::BatchedTiny,
+        Strategy::BatchedGemv,
+    ]
+}
+
+/// Compiles the matmul kernels of every [candidate strategy](warmup_strategies) for the given
+/// `(m, n, k)` shapes, without launching them.
+///
+/// The first matmuls of these shapes won't have to compile anything, whichever strategy they
+/// use. Strategies that aren't available for a shape on the device are skipped.
+///
+/// Returns the number of kernels that were compiled or were already cached.
+pub fn warmup<R: Runtime, MP: MatmulPrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    shapes: &[(usize, usize, usize)],
+) -> usize {
+    let precompile = client.precompile_only();
+    let strategies = warmup_strategies();
+    let mut count = 0;
+
+    for &(m, n, k) in shapes {
+        let lhs = TensorHandle::<R, LhsG<MP>>::empty(client, vec![m, k]);
+        let rhs = TensorHandle::<R, RhsG<MP>>::empty(client, vec![k, n]);
+        let out = TensorHandle::<R, AccG<MP>>::empty(client, vec![m, n]);
+
+        for strategy in strategies.iter() {
+            let result = crate::launch_ref::<R, MP>(
+                strategy,
+                &precompile,
+                &MatmulInputHandleRef::new(lhs.as_ref()),
+                &MatmulInputHandleRef::new(rhs.as_ref()),
+                &out.as_ref(),
+            );
+
+            if result.is_ok() {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
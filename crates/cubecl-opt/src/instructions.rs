@@ -85,16 +85,20 @@ impl Optimizer {
 
             Arithmetic::Abs(unary_operator)
             | Arithmetic::Exp(unary_operator)
+            | Arithmetic::Exp2(unary_operator)
             | Arithmetic::Log(unary_operator)
             | Arithmetic::Log1p(unary_operator)
+            | Arithmetic::Log2(unary_operator)
             | Arithmetic::Cos(unary_operator)
             | Arithmetic::Sin(unary_operator)
             | Arithmetic::Tanh(unary_operator)
             | Arithmetic::Sqrt(unary_operator)
+            | Arithmetic::Rsqrt(unary_operator)
             | Arithmetic::Round(unary_operator)
             | Arithmetic::Floor(unary_operator)
             | Arithmetic::Ceil(unary_operator)
             | Arithmetic::Erf(unary_operator)
+            | Arithmetic::Erfc(unary_operator)
             | Arithmetic::Recip(unary_operator)
             | Arithmetic::Neg(unary_operator)
             | Arithmetic::Magnitude(unary_operator)
@@ -468,6 +472,7 @@ impl Optimizer {
                     visit_read(self, arg);
                 }
             }
+            NonSemantic::CacheHint { buffer, .. } => visit_read(self, buffer),
         }
     }
 
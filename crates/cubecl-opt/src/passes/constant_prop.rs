@@ -368,8 +368,10 @@ fn try_const_eval_arithmetic(op: &mut Arithmetic) -> Option<ConstantScalarValue>
             })
         }
         Arithmetic::Exp(op) => const_eval_float!(op.input; num::Float::exp),
+        Arithmetic::Exp2(op) => const_eval_float!(op.input; num::Float::exp2),
         Arithmetic::Log(op) => const_eval_float!(op.input; num::Float::ln),
         Arithmetic::Log1p(op) => const_eval_float!(op.input; num::Float::ln_1p),
+        Arithmetic::Log2(op) => const_eval_float!(op.input; num::Float::log2),
         Arithmetic::Cos(op) => const_eval_float!(op.input; num::Float::cos),
         Arithmetic::Sin(op) => const_eval_float!(op.input; num::Float::sin),
         Arithmetic::Tanh(op) => const_eval_float!(op.input; num::Float::tanh),
@@ -427,7 +429,11 @@ fn try_const_eval_arithmetic(op: &mut Arithmetic) -> Option<ConstantScalarValue>
                 }
             })
         }
-        Arithmetic::Erf(_) | Arithmetic::Magnitude(_) | Arithmetic::Normalize(_) => None,
+        Arithmetic::Erf(_)
+        | Arithmetic::Erfc(_)
+        | Arithmetic::Rsqrt(_)
+        | Arithmetic::Magnitude(_)
+        | Arithmetic::Normalize(_) => None,
     }
 }
 
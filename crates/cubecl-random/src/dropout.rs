@@ -0,0 +1,187 @@
+use cubecl::prelude::*;
+use cubecl_core::{
+    self as cubecl, calculate_cube_count_elemwise, server::Handle, tensor_line_size_parallel,
+};
+use cubecl_std::tensor::is_contiguous;
+
+use crate::{PhiloxSeed, philox_reference, words_line_at};
+
+/// The Philox stream of the random words deciding which elements [`dropout`] keeps, so its masks
+/// are independent of the values generated for the same seed in other streams.
+pub const DROPOUT_STREAM: u32 = 0xD809_0017;
+
+/// The number of elements whose bits are packed in a word of the mask.
+const ELEMENTS_PER_WORD: usize = 32;
+
+/// How the elements are kept, known at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DropoutMode {
+    /// Keep each element with the probability `1 - p`.
+    Random,
+    /// Keep all the elements, when `p = 0`.
+    KeepAll,
+    /// Drop all the elements, when `p = 1`.
+    DropAll,
+}
+
+/// Zero each element of `input` with the probability `p` and scale the others by `1 / (1 - p)`,
+/// into the `output` of the same shape.
+///
+/// Whether an element is kept only depends on the `seed` and its position in the tensor, never on
+/// the line size or the launch configuration, so the same seed gives the same output on every
+/// backend. The element `e` is kept when the upper 24 bits of the word `e % 4` of the Philox block
+/// at the counter `e / 4 | DROPOUT_STREAM << 32` are at least `round(p · 2²⁴)`, see
+/// [`dropout_reference`]. When `p` is 0 or 1 the elements are copied or zeroed without generating
+/// random words.
+///
+/// The elements kept are written to the `mask`, if any, for the backward pass: the bit `e % 32` of
+/// the `u32` word `e / 32` is set when the element `e` is kept. It must hold at least
+/// `ceil(num_elems / 32)` words, and the bits of the last word after the last element are zero.
+///
+/// # Panics
+///
+/// When `p` isn't in `[0, 1]`, when the shapes differ, or when a tensor isn't contiguous.
+pub fn dropout<R: Runtime, F: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    p: f32,
+    seed: u64,
+    mask: Option<&Handle>,
+) {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "the probability {p} should be in [0, 1]"
+    );
+    assert_eq!(
+        input.shape, output.shape,
+        "the input and the output should have the same shape"
+    );
+    assert!(
+        is_contiguous(input.shape, input.strides) && is_contiguous(output.shape, output.strides),
+        "the input and the output should be contiguous"
+    );
+
+    let num_elems = input.shape.iter().product::<usize>();
+    if num_elems == 0 {
+        return;
+    }
+    let num_words = num_elems.div_ceil(ELEMENTS_PER_WORD);
+    if let Some(mask) = mask {
+        assert!(
+            mask.size() as usize >= num_words * size_of::<u32>(),
+            "the mask should hold a bit per element"
+        );
+    }
+
+    let mode = if p == 0.0 {
+        DropoutMode::KeepAll
+    } else if p == 1.0 {
+        DropoutMode::DropAll
+    } else {
+        DropoutMode::Random
+    };
+    // A line never straddles two words of the mask.
+    let rank = input.shape.len();
+    let supported = R::line_size_type(&F::as_type_native_unchecked())
+        .filter(|line_size| ELEMENTS_PER_WORD.is_multiple_of(*line_size as usize));
+    let line_size = tensor_line_size_parallel(supported, input.shape, input.strides, rank - 1);
+
+    // The kernel still takes a mask when it isn't written.
+    let unused_mask;
+    let (mask, write_mask) = match mask {
+        Some(mask) => (mask, true),
+        None => {
+            unused_mask = client.empty(size_of::<u32>());
+            (&unused_mask, false)
+        }
+    };
+
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_words, cube_dim);
+    unsafe {
+        dropout_kernel::launch_unchecked::<F, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            output.as_tensor_arg(line_size),
+            TensorArg::from_raw_parts::<u32>(mask, &[1], &[num_words], 1),
+            PhiloxSeed::new(seed).as_arg(),
+            ScalarArg::new(dropout_threshold(p)),
+            ScalarArg::new(1.0 / (1.0 - p)),
+            ScalarArg::new((num_elems / line_size as usize) as u32),
+            mode,
+            write_mask,
+        );
+    }
+}
+
+/// The value the upper 24 bits of the random word of an element are compared with.
+fn dropout_threshold(p: f32) -> u32 {
+    (p as f64 * (1u32 << 24) as f64).round() as u32
+}
+
+/// Host reference of whether [`dropout`] keeps the element `element` for the probability `p`.
+pub fn dropout_reference(seed: u64, p: f32, element: u32) -> bool {
+    let counter = ((DROPOUT_STREAM as u64) << 32) | (element / 4) as u64;
+    let word = philox_reference(seed, counter)[element as usize % 4];
+    (word >> 8) >= dropout_threshold(p)
+}
+
+/// Each unit computes the elements of a word of the `mask`, in lines of the `input`.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn dropout_kernel<F: Float>(
+    input: &Tensor<Line<F>>,
+    output: &mut Tensor<Line<F>>,
+    mask: &mut Tensor<u32>,
+    seed: PhiloxSeed,
+    threshold: u32,
+    scale: f32,
+    num_lines: u32,
+    #[comptime] mode: DropoutMode,
+    #[comptime] write_mask: bool,
+) {
+    let line_size = input.line_size();
+    let lines_per_word = comptime!(ELEMENTS_PER_WORD as u32 / line_size);
+    let first_line = ABSOLUTE_POS * lines_per_word;
+    if first_line >= num_lines {
+        terminate!();
+    }
+
+    let mut bits = 0u32;
+    #[unroll]
+    for i in 0..lines_per_word {
+        let index = first_line + i;
+        if index < num_lines {
+            let value = input[index];
+            let mut dropped = Line::empty(line_size);
+
+            if comptime!(mode == DropoutMode::Random) {
+                let words = words_line_at(seed, DROPOUT_STREAM, index, line_size);
+                #[unroll]
+                for j in 0..line_size {
+                    let kept = (words[j] >> 8) >= threshold;
+                    let scaled = F::cast_from(f32::cast_from(value[j]) * scale);
+                    dropped[j] = select(kept, scaled, F::new(0.0));
+                    bits |= select(kept, 1u32 << (i * line_size + j), 0u32);
+                }
+            } else if comptime!(mode == DropoutMode::KeepAll) {
+                dropped = value;
+                #[unroll]
+                for j in 0..line_size {
+                    bits |= 1u32 << (i * line_size + j);
+                }
+            } else {
+                dropped = Line::empty(line_size).fill(F::new(0.0));
+            }
+
+            output[index] = dropped;
+        }
+    }
+
+    if comptime!(write_mask) {
+        mask[ABSOLUTE_POS] = bits;
+    }
+}
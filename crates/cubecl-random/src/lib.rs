@@ -1,12 +1,16 @@
 mod base;
 mod bernoulli;
+mod dropout;
 mod normal;
+mod philox;
 mod tests_utils;
 mod uniform;
 
 pub use base::*;
 pub use bernoulli::*;
+pub use dropout::*;
 pub use normal::*;
+pub use philox::*;
 pub use tests_utils::*;
 pub use uniform::*;
 
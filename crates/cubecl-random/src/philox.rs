@@ -0,0 +1,364 @@
+use cubecl::prelude::*;
+use cubecl_core as cubecl;
+use cubecl_core::ir::{ElemType, FloatKind};
+use std::f32::consts::PI;
+
+use crate::{to_unit_interval_closed_open, to_unit_interval_open};
+
+// The multipliers and the key increments of Philox-4x32, from "Parallel random numbers: as easy as 1, 2, 3".
+const PHILOX_M0: u32 = 0xD2511F53;
+const PHILOX_M1: u32 = 0xCD9E8D57;
+const PHILOX_W0: u32 = 0x9E3779B9;
+const PHILOX_W1: u32 = 0xBB67AE85;
+const PHILOX_ROUNDS: usize = 10;
+
+/// The 64-bit seed of the Philox generator, split in two words so it can be used on devices
+/// without 64-bit integers.
+#[derive(CubeLaunch, CubeType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhiloxSeed {
+    pub lo: u32,
+    pub hi: u32,
+}
+
+impl PhiloxSeed {
+    /// Split a 64-bit seed, the low word first.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            lo: seed as u32,
+            hi: (seed >> 32) as u32,
+        }
+    }
+
+    /// The launch argument of the seed for kernels taking a [`PhiloxSeed`].
+    pub fn as_arg<'a, R: Runtime>(&self) -> PhiloxSeedLaunch<'a, R> {
+        PhiloxSeedLaunch::new(ScalarArg::new(self.lo), ScalarArg::new(self.hi))
+    }
+}
+
+/// Generate the four random words of the Philox-4x32-10 block at the 64-bit counter
+/// `counter_lo | counter_hi << 32`.
+///
+/// The generator is stateless: the same seed and counter always give the same words on every backend,
+/// so each unit can derive its own random numbers from the position of the values it computes.
+#[cube]
+pub fn philox(seed: PhiloxSeed, counter_lo: u32, counter_hi: u32) -> Line<u32> {
+    let m0 = 0xD2511F53u32;
+    let m1 = 0xCD9E8D57u32;
+
+    let mut c0 = counter_lo;
+    let mut c1 = counter_hi;
+    let mut c2 = 0u32;
+    let mut c3 = 0u32;
+    let mut k0 = seed.lo;
+    let mut k1 = seed.hi;
+
+    #[unroll]
+    for _round in 0..10 {
+        let hi0 = u32::mul_hi(m0, c0);
+        let lo0 = m0 * c0;
+        let hi1 = u32::mul_hi(m1, c2);
+        let lo1 = m1 * c2;
+
+        c0 = hi1 ^ c1 ^ k0;
+        c1 = lo1;
+        c2 = hi0 ^ c3 ^ k1;
+        c3 = lo0;
+
+        // The key bumped after the last round isn't used.
+        k0 += 0x9E3779B9u32;
+        k1 += 0xBB67AE85u32;
+    }
+
+    let mut words = Line::empty(4u32);
+    words[0] = c0;
+    words[1] = c1;
+    words[2] = c2;
+    words[3] = c3;
+    words
+}
+
+/// Generate four uniform floats in `[0, 1)` from the Philox block at the given counter.
+#[cube]
+pub fn philox_uniform(seed: PhiloxSeed, counter_lo: u32, counter_hi: u32) -> Line<f32> {
+    let words = philox(seed, counter_lo, counter_hi);
+
+    let mut uniforms = Line::empty(4u32);
+    #[unroll]
+    for i in 0..4 {
+        uniforms[i] = to_unit_interval_closed_open(words[i]);
+    }
+    uniforms
+}
+
+/// Generate four standard normal floats from the Philox block at the given counter,
+/// with the Box-Muller transform of each pair of words.
+#[cube]
+pub fn philox_normal(seed: PhiloxSeed, counter_lo: u32, counter_hi: u32) -> Line<f32> {
+    let words = philox(seed, counter_lo, counter_hi);
+
+    let mut normals = Line::empty(4u32);
+    #[unroll]
+    for i in 0..2 {
+        let unit_0 = to_unit_interval_open(words[2 * i]);
+        let unit_1 = to_unit_interval_open(words[2 * i + 1]);
+
+        let radius = Sqrt::sqrt(Log::log(unit_0) * -2.0);
+        let angle = 2.0 * PI * unit_1;
+        normals[2 * i] = f32::cos(angle) * radius;
+        normals[2 * i + 1] = f32::sin(angle) * radius;
+    }
+    normals
+}
+
+/// Generate the uniform floats in `[0, 1)` of the line at `ABSOLUTE_POS` in the given `stream`.
+///
+/// See [`uniform_line_at`].
+#[cube]
+pub fn uniform_line<F: Float>(
+    seed: PhiloxSeed,
+    stream: u32,
+    #[comptime] line_size: u32,
+) -> Line<F> {
+    uniform_line_at::<F>(seed, stream, ABSOLUTE_POS, line_size)
+}
+
+/// Generate the uniform floats in `[0, 1)` of the line at `index` in the given `stream`.
+///
+/// The element `e` of the tensor gets the word `e % 4` of the block at the counter `e / 4 | stream << 32`,
+/// so its value only depends on the seed, the stream and its position, and not on the line size or the launch
+/// configuration. Different streams give independent values for the same elements.
+#[cube]
+pub fn uniform_line_at<F: Float>(
+    seed: PhiloxSeed,
+    stream: u32,
+    index: u32,
+    #[comptime] line_size: u32,
+) -> Line<F> {
+    let mut line = Line::empty(line_size);
+    if comptime!(line_size % 4 == 0) {
+        #[unroll]
+        for block in 0..line_size / 4 {
+            let uniforms = philox_uniform(seed, index * comptime!(line_size / 4) + block, stream);
+            #[unroll]
+            for i in 0..4 {
+                line[block * 4 + i] = F::cast_from(uniforms[i]);
+            }
+        }
+    } else {
+        #[unroll]
+        for i in 0..line_size {
+            let element = index * line_size + i;
+            let uniforms = philox_uniform(seed, element / 4, stream);
+            line[i] = F::cast_from(select_word::<f32>(uniforms, element % 4));
+        }
+    }
+    line
+}
+
+/// Generate the standard normal floats of the line at `ABSOLUTE_POS` in the given `stream`.
+///
+/// See [`normal_line_at`].
+#[cube]
+pub fn normal_line<F: Float>(seed: PhiloxSeed, stream: u32, #[comptime] line_size: u32) -> Line<F> {
+    normal_line_at::<F>(seed, stream, ABSOLUTE_POS, line_size)
+}
+
+/// Generate the standard normal floats of the line at `index` in the given `stream`,
+/// with the same counters as [`uniform_line_at`].
+#[cube]
+pub fn normal_line_at<F: Float>(
+    seed: PhiloxSeed,
+    stream: u32,
+    index: u32,
+    #[comptime] line_size: u32,
+) -> Line<F> {
+    let mut line = Line::empty(line_size);
+    if comptime!(line_size % 4 == 0) {
+        #[unroll]
+        for block in 0..line_size / 4 {
+            let normals = philox_normal(seed, index * comptime!(line_size / 4) + block, stream);
+            #[unroll]
+            for i in 0..4 {
+                line[block * 4 + i] = F::cast_from(normals[i]);
+            }
+        }
+    } else {
+        #[unroll]
+        for i in 0..line_size {
+            let element = index * line_size + i;
+            let normals = philox_normal(seed, element / 4, stream);
+            line[i] = F::cast_from(select_word::<f32>(normals, element % 4));
+        }
+    }
+    line
+}
+
+/// Cast the line of `value` at `index` to `F` with stochastic rounding, using the random words of
+/// the elements in the given `stream`.
+///
+/// A random offset below the ulp of `F` is added to the bits of each element before they are
+/// truncated to the precision of `F`, so each element rounds up with a probability proportional to
+/// its distance to the value below, and the rounding is unbiased on average. The words have the
+/// same counters as [`uniform_line_at`], so the result only depends on the seed, the stream and the
+/// position of the elements, and a stream different from the ones used for other random values
+/// should be used.
+///
+/// Types at least as precise as `f32` are cast normally, and infinities and NaN are kept as is.
+#[cube]
+pub fn cast_stochastic<F: Float>(
+    value: Line<f32>,
+    seed: PhiloxSeed,
+    stream: u32,
+    index: u32,
+) -> Line<F> {
+    let line_size = value.size();
+    let dropped_bits = comptime!(dropped_mantissa_bits::<F>());
+
+    if comptime!(dropped_bits == 0) {
+        Line::cast_from(value)
+    } else {
+        let words = words_line_at(seed, stream, index, line_size);
+        let offset_shift = comptime!(32 - dropped_bits);
+        let truncation_mask = comptime!(!((1u32 << dropped_bits) - 1));
+
+        let mut rounded = Line::empty(line_size);
+        #[unroll]
+        for i in 0..line_size {
+            let bits = u32::reinterpret(value[i]);
+            let is_finite = (bits & 0x7F800000u32) != 0x7F800000u32;
+            let truncated = (bits + (words[i] >> offset_shift)) & truncation_mask;
+            rounded[i] = F::cast_from(f32::reinterpret(select(is_finite, truncated, bits)));
+        }
+        rounded
+    }
+}
+
+/// The random words of the line at `index` in the given `stream`, with the counters of
+/// [`uniform_line_at`].
+#[cube]
+pub(crate) fn words_line_at(
+    seed: PhiloxSeed,
+    stream: u32,
+    index: u32,
+    #[comptime] line_size: u32,
+) -> Line<u32> {
+    let mut line = Line::empty(line_size);
+    if comptime!(line_size % 4 == 0) {
+        #[unroll]
+        for block in 0..line_size / 4 {
+            let words = philox(seed, index * comptime!(line_size / 4) + block, stream);
+            #[unroll]
+            for i in 0..4 {
+                line[block * 4 + i] = words[i];
+            }
+        }
+    } else {
+        #[unroll]
+        for i in 0..line_size {
+            let element = index * line_size + i;
+            let words = philox(seed, element / 4, stream);
+            line[i] = select_word::<u32>(words, element % 4);
+        }
+    }
+    line
+}
+
+/// The number of low mantissa bits of an `f32` that `F` doesn't have.
+fn dropped_mantissa_bits<F: Float>() -> u32 {
+    match F::as_type_native_unchecked().elem_type() {
+        ElemType::Float(FloatKind::BF16) => 16,
+        ElemType::Float(FloatKind::F16 | FloatKind::TF32) => 13,
+        ElemType::Float(FloatKind::E4M3) => 20,
+        ElemType::Float(FloatKind::E5M2) => 21,
+        _ => 0,
+    }
+}
+
+/// The word of a block at a position only known at runtime, without indexing the line dynamically.
+#[cube]
+fn select_word<E: CubePrimitive>(words: Line<E>, position: u32) -> E {
+    select(
+        position == 0,
+        words[0],
+        select(
+            position == 1,
+            words[1],
+            select(position == 2, words[2], words[3]),
+        ),
+    )
+}
+
+/// Host implementation of the Philox-4x32-10 block with a full 128-bit counter.
+pub fn philox_4x32(key: [u32; 2], counter: [u32; 4]) -> [u32; 4] {
+    let [mut c0, mut c1, mut c2, mut c3] = counter;
+    let [mut k0, mut k1] = key;
+
+    for _ in 0..PHILOX_ROUNDS {
+        let product_0 = PHILOX_M0 as u64 * c0 as u64;
+        let product_1 = PHILOX_M1 as u64 * c2 as u64;
+
+        c0 = (product_1 >> 32) as u32 ^ c1 ^ k0;
+        c1 = product_1 as u32;
+        c2 = (product_0 >> 32) as u32 ^ c3 ^ k1;
+        c3 = product_0 as u32;
+
+        k0 = k0.wrapping_add(PHILOX_W0);
+        k1 = k1.wrapping_add(PHILOX_W1);
+    }
+
+    [c0, c1, c2, c3]
+}
+
+/// Host reference of [`philox`].
+pub fn philox_reference(seed: u64, counter: u64) -> [u32; 4] {
+    let seed = PhiloxSeed::new(seed);
+    philox_4x32(
+        [seed.lo, seed.hi],
+        [counter as u32, (counter >> 32) as u32, 0, 0],
+    )
+}
+
+/// Host reference of the element `element` generated by [`uniform_line_at`].
+pub fn uniform_reference(seed: u64, stream: u32, element: u32) -> f32 {
+    let words = philox_reference(seed, block_counter(stream, element));
+    let word = words[element as usize % 4];
+    (word >> 8) as f32 / 16777216.0
+}
+
+/// Host reference of the element `element` generated by [`normal_line_at`].
+///
+/// The device may use less precise transcendental functions, so the values only match approximately.
+pub fn normal_reference(seed: u64, stream: u32, element: u32) -> f32 {
+    let words = philox_reference(seed, block_counter(stream, element));
+    let pair = element as usize % 4 / 2;
+    let unit_0 = ((words[2 * pair] >> 9) as f32 + 1.0) / 8388609.0;
+    let unit_1 = ((words[2 * pair + 1] >> 9) as f32 + 1.0) / 8388609.0;
+
+    let radius = (unit_0.ln() * -2.0).sqrt();
+    let angle = 2.0 * PI * unit_1;
+    match element % 2 {
+        0 => angle.cos() * radius,
+        _ => angle.sin() * radius,
+    }
+}
+
+/// Host reference of the element `element` cast to `bf16` by [`cast_stochastic`].
+pub fn cast_stochastic_bf16_reference(
+    seed: u64,
+    stream: u32,
+    element: u32,
+    value: f32,
+) -> half::bf16 {
+    let words = philox_reference(seed, block_counter(stream, element));
+    let bits = value.to_bits();
+    if bits & 0x7F800000 == 0x7F800000 {
+        return half::bf16::from_f32(value);
+    }
+
+    let truncated = (bits + (words[element as usize % 4] >> 16)) & 0xFFFF0000;
+    half::bf16::from_bits((truncated >> 16) as u16)
+}
+
+fn block_counter(stream: u32, element: u32) -> u64 {
+    ((stream as u64) << 32) | (element / 4) as u64
+}
@@ -0,0 +1,114 @@
+#[macro_export]
+macro_rules! testgen_random_dropout {
+    () => {
+        mod test_random_dropout {
+            use super::*;
+
+            const SEED: u64 = 0xfeed_5eed_0bad_cafe;
+
+            /// The input `1 + i % 7`, its dropout with the probability `p` and its mask.
+            fn get_dropout_data(length: usize, p: f32) -> (Vec<f32>, Vec<f32>, Vec<u32>) {
+                let client = TestRuntime::client(&Default::default());
+                let input = (0..length).map(|i| (1 + i % 7) as f32).collect::<Vec<_>>();
+                let input_handle = client.create(f32::as_bytes(&input));
+                let output_handle = client.empty(length * size_of::<f32>());
+                let mask_handle = client.empty(length.div_ceil(32) * size_of::<u32>());
+
+                let (input_ref, output_ref) = unsafe {
+                    (
+                        TensorHandleRef::<TestRuntime>::from_raw_parts(
+                            &input_handle,
+                            &[1],
+                            &[length],
+                            size_of::<f32>(),
+                        ),
+                        TensorHandleRef::<TestRuntime>::from_raw_parts(
+                            &output_handle,
+                            &[1],
+                            &[length],
+                            size_of::<f32>(),
+                        ),
+                    )
+                };
+                dropout::<TestRuntime, f32>(
+                    &client,
+                    &input_ref,
+                    &output_ref,
+                    p,
+                    SEED,
+                    Some(&mask_handle),
+                );
+
+                let output = f32::from_bytes(&client.read_one(output_handle)).to_owned();
+                let mask = u32::from_bytes(&client.read_one(mask_handle)).to_owned();
+                (input, output, mask)
+            }
+
+            fn is_kept(mask: &[u32], element: usize) -> bool {
+                mask[element / 32] >> (element % 32) & 1 == 1
+            }
+
+            #[test]
+            fn survival_rate() {
+                let length = 1 << 20;
+                let p = 0.3;
+                let (input, output, mask) = get_dropout_data(length, p);
+
+                let kept = (0..length)
+                    .filter(|element| is_kept(&mask, *element))
+                    .count();
+                let rate = kept as f64 / length as f64;
+                assert!((rate - 0.7).abs() < 3e-3, "rate={rate}");
+
+                for (element, (input, output)) in input.iter().zip(&output).enumerate() {
+                    let expected = match is_kept(&mask, element) {
+                        true => input * (1.0 / (1.0 - p)),
+                        false => 0.0,
+                    };
+                    assert_eq!(*output, expected, "Element {element}");
+                }
+            }
+
+            #[test]
+            fn mask_matches_reference() {
+                // A length without lines, with a partial last word.
+                for length in [4096, 1003] {
+                    let p = 0.6;
+                    let (_, _, mask) = get_dropout_data(length, p);
+                    for element in 0..length {
+                        assert_eq!(
+                            is_kept(&mask, element),
+                            dropout_reference(SEED, p, element as u32),
+                            "Element {element} with length {length}"
+                        );
+                    }
+                    assert_eq!(mask[(length - 1) / 32] >> 1 >> ((length - 1) % 32), 0);
+                }
+            }
+
+            #[test]
+            fn reproducible() {
+                let (_, first, first_mask) = get_dropout_data(10_000, 0.5);
+                let (_, second, second_mask) = get_dropout_data(10_000, 0.5);
+                assert_eq!(first, second);
+                assert_eq!(first_mask, second_mask);
+            }
+
+            #[test]
+            fn keep_all() {
+                let length = 1000;
+                let (input, output, mask) = get_dropout_data(length, 0.0);
+                assert_eq!(input, output);
+                assert!((0..length).all(|element| is_kept(&mask, element)));
+            }
+
+            #[test]
+            fn drop_all() {
+                let length = 1000;
+                let (_, output, mask) = get_dropout_data(length, 1.0);
+                assert!(output.iter().all(|value| *value == 0.0));
+                assert!(mask.iter().all(|word| *word == 0));
+            }
+        }
+    };
+}
@@ -1,6 +1,9 @@
 pub mod bernoulli;
+pub mod dropout;
 pub mod interval;
 pub mod normal;
+pub mod philox;
+pub mod rounding;
 pub mod uniform;
 
 #[allow(missing_docs)]
@@ -17,8 +20,11 @@ macro_rules! testgen_random {
         use core::f32;
 
         cubecl_random::testgen_random_bernoulli!();
+        cubecl_random::testgen_random_dropout!();
         cubecl_random::testgen_random_normal!();
         cubecl_random::testgen_random_uniform!();
         cubecl_random::testgen_random_interval!();
+        cubecl_random::testgen_random_philox!();
+        cubecl_random::testgen_random_rounding!();
     };
 }
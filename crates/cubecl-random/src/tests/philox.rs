@@ -0,0 +1,210 @@
+#[macro_export]
+macro_rules! testgen_random_philox {
+    () => {
+        mod test_random_philox {
+            use super::*;
+
+            const SEED: u64 = 0x0123_4567_89ab_cdef;
+
+            #[cube(launch)]
+            pub(crate) fn kernel_philox(output: &mut Array<u32>, seed: PhiloxSeed) {
+                let block = ABSOLUTE_POS;
+                if block * 4 < output.len() {
+                    let words = super::philox(seed, block, 7u32);
+                    #[unroll]
+                    for i in 0..4 {
+                        output[block * 4 + i] = words[i];
+                    }
+                }
+            }
+
+            #[cube(launch)]
+            pub(crate) fn kernel_uniform_line(
+                output: &mut Array<Line<f32>>,
+                seed: PhiloxSeed,
+                stream: u32,
+                #[comptime] line_size: u32,
+            ) {
+                if ABSOLUTE_POS < output.len() {
+                    output[ABSOLUTE_POS] = super::uniform_line::<f32>(seed, stream, line_size);
+                }
+            }
+
+            #[cube(launch)]
+            pub(crate) fn kernel_normal_line(
+                output: &mut Array<Line<f32>>,
+                seed: PhiloxSeed,
+                stream: u32,
+                #[comptime] line_size: u32,
+            ) {
+                if ABSOLUTE_POS < output.len() {
+                    output[ABSOLUTE_POS] = super::normal_line::<f32>(seed, stream, line_size);
+                }
+            }
+
+            fn get_line_data(
+                length: usize,
+                line_size: u32,
+                cube_dim: CubeDim,
+                stream: u32,
+                normal: bool,
+            ) -> Vec<f32> {
+                let client = TestRuntime::client(&Default::default());
+                let output = client.empty(length * size_of::<f32>());
+                let num_lines = length / line_size as usize;
+                let cube_count =
+                    CubeCount::Static((num_lines as u32).div_ceil(cube_dim.num_elems()), 1, 1);
+                let seed = PhiloxSeed::new(SEED);
+                let output_arg =
+                    unsafe { ArrayArg::from_raw_parts::<f32>(&output, num_lines, line_size as u8) };
+
+                match normal {
+                    false => kernel_uniform_line::launch::<TestRuntime>(
+                        &client,
+                        cube_count,
+                        cube_dim,
+                        output_arg,
+                        seed.as_arg(),
+                        ScalarArg::new(stream),
+                        line_size,
+                    ),
+                    true => kernel_normal_line::launch::<TestRuntime>(
+                        &client,
+                        cube_count,
+                        cube_dim,
+                        output_arg,
+                        seed.as_arg(),
+                        ScalarArg::new(stream),
+                        line_size,
+                    ),
+                }
+
+                let actual = client.read_one(output);
+                f32::from_bytes(&actual).to_owned()
+            }
+
+            #[test]
+            fn known_answers() {
+                // Test vectors of the Random123 reference implementation.
+                assert_eq!(
+                    philox_4x32([0, 0], [0, 0, 0, 0]),
+                    [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+                );
+                assert_eq!(
+                    philox_4x32([u32::MAX; 2], [u32::MAX; 4]),
+                    [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+                );
+                assert_eq!(
+                    philox_4x32(
+                        [0xa4093822, 0x299f31d0],
+                        [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344]
+                    ),
+                    [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+                );
+            }
+
+            #[test]
+            fn bits_match_reference() {
+                let client = TestRuntime::client(&Default::default());
+                let num_blocks = 4096;
+                let output = client.empty(num_blocks * 4 * size_of::<u32>());
+
+                kernel_philox::launch::<TestRuntime>(
+                    &client,
+                    CubeCount::Static(num_blocks as u32 / 256, 1, 1),
+                    CubeDim::new_1d(256),
+                    unsafe { ArrayArg::from_raw_parts::<u32>(&output, num_blocks * 4, 1) },
+                    PhiloxSeed::new(SEED).as_arg(),
+                );
+
+                let actual = client.read_one(output);
+                let actual = u32::from_bytes(&actual);
+                for block in 0..num_blocks {
+                    let expected = philox_reference(SEED, (7 << 32) | block as u64);
+                    assert_eq!(
+                        &actual[block * 4..block * 4 + 4],
+                        &expected,
+                        "Block {block} doesn't match the reference"
+                    );
+                }
+            }
+
+            #[test]
+            fn uniform_independent_of_launch_configuration() {
+                let length = 1 << 16;
+                let expected = (0..length as u32)
+                    .map(|element| uniform_reference(SEED, 3, element))
+                    .collect::<Vec<_>>();
+
+                for (line_size, cube_dim) in [
+                    (1, CubeDim::new_1d(256)),
+                    (2, CubeDim::new_1d(64)),
+                    (4, CubeDim::new_1d(128)),
+                    (4, CubeDim::new_2d(16, 4)),
+                ] {
+                    let actual = get_line_data(length, line_size, cube_dim, 3, false);
+                    assert_eq!(actual, expected, "Line size {line_size} with {cube_dim:?}");
+                }
+            }
+
+            #[test]
+            fn streams_are_different() {
+                let length = 4096;
+                let stream_0 = get_line_data(length, 4, CubeDim::new_1d(256), 0, false);
+                let stream_1 = get_line_data(length, 4, CubeDim::new_1d(256), 1, false);
+
+                let equal = stream_0
+                    .iter()
+                    .zip(stream_1.iter())
+                    .filter(|(a, b)| a == b)
+                    .count();
+                assert!(equal < 4, "{equal} values are the same in both streams");
+            }
+
+            #[test]
+            fn normal_matches_reference() {
+                let length = 1 << 16;
+                for line_size in [1, 4] {
+                    let actual = get_line_data(length, line_size, CubeDim::new_1d(256), 5, true);
+                    for (element, actual) in actual.iter().enumerate() {
+                        let expected = normal_reference(SEED, 5, element as u32);
+                        assert!(
+                            (actual - expected).abs() < 1e-4 * expected.abs().max(1.0),
+                            "Element {element}: actual={actual}, expected={expected}"
+                        );
+                    }
+                }
+            }
+
+            #[test]
+            fn uniform_mean_and_variance() {
+                let data = get_line_data(10_000_000, 4, CubeDim::new_1d(256), 0, false);
+                let (mean, variance) = mean_and_variance(&data);
+
+                assert!(data.iter().all(|value| (0.0..1.0).contains(value)));
+                assert!((mean - 0.5).abs() < 1e-3, "mean={mean}");
+                assert!((variance - 1.0 / 12.0).abs() < 1e-3, "variance={variance}");
+            }
+
+            #[test]
+            fn normal_mean_and_variance() {
+                let data = get_line_data(10_000_000, 4, CubeDim::new_1d(256), 0, true);
+                let (mean, variance) = mean_and_variance(&data);
+
+                assert!(mean.abs() < 2e-3, "mean={mean}");
+                assert!((variance - 1.0).abs() < 2e-3, "variance={variance}");
+                assert_normal_respects_68_95_99_rule(&data[..1_000_000], 0., 1.);
+            }
+
+            fn mean_and_variance(data: &[f32]) -> (f64, f64) {
+                let mean = data.iter().map(|value| *value as f64).sum::<f64>() / data.len() as f64;
+                let variance = data
+                    .iter()
+                    .map(|value| (*value as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / (data.len() - 1) as f64;
+                (mean, variance)
+            }
+        }
+    };
+}
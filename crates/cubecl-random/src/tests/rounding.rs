@@ -0,0 +1,133 @@
+#[macro_export]
+macro_rules! testgen_random_rounding {
+    () => {
+        mod test_random_rounding {
+            use super::*;
+
+            const SEED: u64 = 0x0123_4567_89ab_cdef;
+
+            #[cube(launch)]
+            pub(crate) fn kernel_cast_stochastic<F: Float>(
+                input: &Array<Line<f32>>,
+                output: &mut Array<Line<F>>,
+                seed: PhiloxSeed,
+            ) {
+                if ABSOLUTE_POS < output.len() {
+                    output[ABSOLUTE_POS] =
+                        super::cast_stochastic::<F>(input[ABSOLUTE_POS], seed, 2u32, ABSOLUTE_POS);
+                }
+            }
+
+            fn supports<F: Float>() -> bool {
+                let client = TestRuntime::client(&Default::default());
+                client
+                    .properties()
+                    .supports_type(F::as_type_native_unchecked())
+            }
+
+            fn cast_stochastic<F: Float + CubeElement>(
+                input: &[f32],
+                seed: u64,
+                line_size: u8,
+            ) -> Vec<F> {
+                let client = TestRuntime::client(&Default::default());
+                let input_handle = client.create(f32::as_bytes(input));
+                let output = client.empty(input.len() * size_of::<F>());
+                let num_lines = input.len() / line_size as usize;
+
+                kernel_cast_stochastic::launch::<F, TestRuntime>(
+                    &client,
+                    CubeCount::Static((num_lines as u32).div_ceil(256), 1, 1),
+                    CubeDim::new_1d(256),
+                    unsafe { ArrayArg::from_raw_parts::<f32>(&input_handle, num_lines, line_size) },
+                    unsafe { ArrayArg::from_raw_parts::<F>(&output, num_lines, line_size) },
+                    PhiloxSeed::new(seed).as_arg(),
+                );
+
+                F::from_bytes(&client.read_one(output)).to_vec()
+            }
+
+            #[test]
+            fn bf16_matches_reference() {
+                if !supports::<half::bf16>() {
+                    println!("bf16 not supported - skipped");
+                    return;
+                }
+                let input = (0..4096)
+                    .map(|i| (i as f32 - 2048.0) * 0.0123 + 1e-3)
+                    .chain([f32::INFINITY, f32::NEG_INFINITY, f32::MAX, 0.0])
+                    .collect::<Vec<_>>();
+
+                for line_size in [1, 4] {
+                    let actual = cast_stochastic::<half::bf16>(&input, SEED, line_size);
+                    for (element, (actual, value)) in actual.iter().zip(input.iter()).enumerate() {
+                        let expected =
+                            cast_stochastic_bf16_reference(SEED, 2, element as u32, *value);
+                        assert_eq!(
+                            actual.to_bits(),
+                            expected.to_bits(),
+                            "Element {element} of {value} with line size {line_size}"
+                        );
+                    }
+                }
+            }
+
+            #[test]
+            fn deterministic_given_seed() {
+                if !supports::<half::bf16>() {
+                    println!("bf16 not supported - skipped");
+                    return;
+                }
+                let input = vec![1.0 + 1.0 / 3.0 * 2f32.powi(-7); 4096];
+
+                let first = cast_stochastic::<half::bf16>(&input, SEED, 4);
+                let second = cast_stochastic::<half::bf16>(&input, SEED, 4);
+                let other_seed = cast_stochastic::<half::bf16>(&input, SEED + 1, 4);
+
+                assert_eq!(first, second);
+                assert_ne!(first, other_seed);
+            }
+
+            #[test]
+            fn bf16_mean_converges_to_value() {
+                if !supports::<half::bf16>() {
+                    println!("bf16 not supported - skipped");
+                    return;
+                }
+                // A third of the way between two values of bf16.
+                let value = 1.0 + 1.0 / 3.0 * 2f32.powi(-7);
+                let rounded = cast_stochastic::<half::bf16>(&vec![value; 1 << 20], SEED, 4);
+
+                let mean =
+                    rounded.iter().map(|value| value.to_f64()).sum::<f64>() / rounded.len() as f64;
+                assert!(
+                    (mean - value as f64).abs() < 2e-5,
+                    "mean={mean}, value={value}"
+                );
+                assert!(
+                    rounded
+                        .iter()
+                        .all(|rounded| [1.0, 1.0 + 2f32.powi(-7)].contains(&rounded.to_f32()))
+                );
+            }
+
+            #[test]
+            fn f16_mean_converges_to_value() {
+                if !supports::<half::f16>() {
+                    println!("f16 not supported - skipped");
+                    return;
+                }
+                // Three quarters of the way between two values of f16.
+                let value = -(3.0 + 0.75 * 2f32.powi(-9));
+                let rounded = cast_stochastic::<half::f16>(&vec![value; 1 << 20], SEED, 4);
+
+                let mean =
+                    rounded.iter().map(|value| value.to_f64()).sum::<f64>() / rounded.len() as f64;
+                assert!(
+                    (mean - value as f64).abs() < 1e-5,
+                    "mean={mean}, value={value}"
+                );
+            }
+        }
+    };
+}
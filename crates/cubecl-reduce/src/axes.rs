@@ -0,0 +1,298 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape, logical_cube_pos, next_logical_cube_pos};
+
+use crate::ReduceError;
+use crate::instructions::*;
+use crate::precision::ReducePrecision;
+use crate::primitives::reduce_tree;
+
+/// The number of units in a cube for both strategies of [`reduce_axes`].
+const CUBE_SIZE: u32 = 256;
+
+/// Reduce all the given `axes` of the `input` tensor at once using the instruction `Inst` and write the result into `output`.
+///
+/// The reduced axes are collapsed into a single iteration space for each element of `output`,
+/// so no intermediate tensor is allocated when reducing multiple axes, e.g. the batch and spatial axes of an image.
+/// When `keep_dims` is true, the shape of `output` must be the same as input with a value of 1 for each reduced axis.
+/// Otherwise, the reduced axes are removed from the shape of `output`.
+///
+/// Each element of `output` is reduced by a single unit when its reduction is small compared to the number of outputs,
+/// and by a whole cube otherwise. A reduction isn't distributed over multiple cubes,
+/// so reducing a very large tensor to a few values is limited by the throughput of a single cube per value.
+///
+/// For [`ArgMax`](crate::instructions::ArgMax) and [`ArgMin`](crate::instructions::ArgMin), the coordinate written
+/// is the index in the collapsed space, where the last reduced axis varies the fastest.
+/// For example, reducing the axes `[0, 2]` of a tensor of shape `[2, 5, 3]` outputs `3 * i + k`
+/// for the element at coordinate `[i, j, k]`.
+///
+/// Return an error if an axis is larger than the `input` rank or given twice,
+/// or if the shape of `output` is invalid.
+///
+/// # Example
+///
+/// This examples show how to compute the mean of the channels of a `NHWC` tensor.
+///
+/// ```ignore
+/// use cubecl_reduce::instructions::Mean;
+///
+/// let client = /* ... */;
+/// let input = /* a tensor of shape [n, h, w, c] */;
+/// let output = /* a tensor of shape [c] */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let result = reduce_axes::<R, f32, f32, Mean>(&client, input, output, &[0, 1, 2], false, ());
+/// ```
+pub fn reduce_axes<R: Runtime, P: ReducePrecision, Out: Numeric, Inst: ReduceFamily>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    axes: &[usize],
+    keep_dims: bool,
+    inst_config: Inst::Config,
+) -> Result<(), ReduceError> {
+    let rank = input.shape.len();
+    let mut reduced_axes = 0u32;
+    for &axis in axes {
+        if axis >= rank {
+            return Err(ReduceError::InvalidAxis { axis, rank });
+        }
+        if reduced_axes & (1 << axis) != 0 {
+            return Err(ReduceError::DuplicateAxis { axis });
+        }
+        reduced_axes |= 1 << axis;
+    }
+    let is_reduced = |axis: usize| reduced_axes & (1 << axis) != 0;
+
+    let expected_shape = (0..rank)
+        .filter(|axis| keep_dims || !is_reduced(*axis))
+        .map(|axis| match is_reduced(axis) {
+            true => 1,
+            false => input.shape[axis],
+        })
+        .collect::<Vec<_>>();
+    if output.shape != expected_shape {
+        return Err(ReduceError::MismatchShape {
+            expected_shape,
+            output_shape: output.shape.to_vec(),
+        });
+    }
+
+    // The kernel always works on an output of the same rank as the input.
+    let mut output_shape = Vec::with_capacity(rank);
+    let mut output_strides = Vec::with_capacity(rank);
+    let mut kept = output.shape.iter().zip(output.strides.iter());
+    for axis in 0..rank {
+        if is_reduced(axis) {
+            output_shape.push(1);
+            output_strides.push(1);
+            if keep_dims {
+                kept.next();
+            }
+        } else {
+            let (shape, stride) = kept.next().unwrap();
+            output_shape.push(*shape);
+            output_strides.push(*stride);
+        }
+    }
+    let output = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            output.handle,
+            &output_strides,
+            &output_shape,
+            output.elem_size,
+        )
+    };
+
+    let output_size = output_shape.iter().product::<usize>() as u32;
+    let reduced_size = (0..rank)
+        .filter(|axis| is_reduced(*axis))
+        .map(|axis| input.shape[axis])
+        .product::<usize>() as u32;
+
+    // A cube for each output element when it's reduced by a whole cube, the cubes past the
+    // capacity of the grid being computed in turn by the cubes launched.
+    let (shared, launch) = if reduced_size >= CUBE_SIZE || reduced_size > output_size {
+        let launch = LaunchShape::elementwise(output_size as usize * CUBE_SIZE as usize);
+        (Some(CUBE_SIZE), launch)
+    } else {
+        (None, LaunchShape::elementwise(output_size as usize))
+    };
+    let config = launch
+        .units_per_cube(CUBE_SIZE)
+        .round_robin()
+        .build::<R>()?;
+
+    unsafe {
+        reduce_axes_kernel::launch_unchecked::<P::EI, Out, P::EA, Inst, R>(
+            client,
+            config.cube_count.clone(),
+            config.cube_dim,
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(reduced_axes),
+            ScalarArg::new(reduced_size),
+            config.num_logical_cubes_arg(),
+            shared,
+            inst_config,
+        );
+    }
+    Ok(())
+}
+
+/// Reduce the axes set in the `reduced_axes` bit mask, where `output` has the same rank as `input`.
+///
+/// If `shared` is `Some(size)`, each output element is reduced by a cube of `size` units.
+/// Else, each output element is reduced by a single unit.
+/// Each cube computes its logical cubes in turn, out of the `num_cubes` of the launch.
+#[cube(launch_unchecked)]
+pub fn reduce_axes_kernel<In: Numeric, Out: Numeric, Acc: Numeric, R: ReduceFamily>(
+    input: &Tensor<Line<In>>,
+    output: &mut Tensor<Line<Out>>,
+    reduced_axes: u32,
+    reduced_size: u32,
+    num_cubes: u32,
+    #[comptime] shared: Option<u32>,
+    #[comptime] config: R::Config,
+) {
+    let mut cube = logical_cube_pos(num_cubes);
+    while cube < num_cubes {
+        let reduce_index = match comptime!(shared) {
+            Some(_) => cube,
+            None => cube * CUBE_DIM + UNIT_POS,
+        };
+        if reduce_index < output.len() {
+            reduce_axes_kernel_inner::<(In, Acc), Out, R>(
+                input,
+                output,
+                reduce_index,
+                reduced_axes,
+                reduced_size,
+                shared,
+                config,
+            );
+        }
+
+        // The shared accumulators are reused by the next logical cube.
+        if comptime!(shared.is_some()) {
+            sync_cube();
+        }
+        cube = next_logical_cube_pos(cube, num_cubes);
+    }
+}
+
+#[cube]
+fn reduce_axes_kernel_inner<P: ReducePrecision, Out: Numeric, R: ReduceFamily>(
+    input: &Tensor<Line<P::EI>>,
+    output: &mut Tensor<Line<Out>>,
+    reduce_index: u32,
+    reduced_axes: u32,
+    reduced_size: u32,
+    #[comptime] shared: Option<u32>,
+    #[comptime] config: R::Config,
+) {
+    // The offset of the first reduced element, where all the reduced axes have a coordinate of 0.
+    let mut offset = 0;
+    for axis in 0..input.rank() {
+        offset += output.coordinate(reduce_index, axis) * input.stride(axis);
+    }
+
+    let inst = &R::Instruction::<P>::from_config(config);
+    let accumulator = match comptime!(shared) {
+        Some(accumulator_size) => reduce_axes_shared::<P, R::Instruction<P>>(
+            input,
+            inst,
+            offset,
+            reduced_axes,
+            reduced_size,
+            accumulator_size,
+        ),
+        None => reduce_axes_unit::<P, R::Instruction<P>>(
+            input,
+            inst,
+            offset,
+            reduced_axes,
+            reduced_size,
+        ),
+    };
+
+    let elected = match comptime!(shared) {
+        Some(_) => UNIT_POS == 0,
+        None => true.runtime(),
+    };
+    if elected {
+        let result = R::Instruction::<P>::merge_line::<Out>(inst, accumulator, reduced_size);
+        output[reduce_index] = Line::cast_from(result);
+    }
+}
+
+#[cube]
+fn reduce_axes_unit<P: ReducePrecision, R: ReduceInstruction<P>>(
+    input: &Tensor<Line<P::EI>>,
+    inst: &R,
+    offset: u32,
+    reduced_axes: u32,
+    reduced_size: u32,
+) -> R::AccumulatorItem {
+    let requirements = R::requirements(inst);
+    let mut accumulator = R::null_accumulator(inst, 1);
+
+    for coordinate in 0..reduced_size {
+        let item = input[offset + reduced_offset(input, reduced_axes, coordinate)];
+        let coordinates = if comptime![requirements.coordinates] {
+            ReduceCoordinate::new_Required(Line::new(coordinate))
+        } else {
+            ReduceCoordinate::new_NotRequired()
+        };
+        reduce_inplace::<P, R>(inst, &mut accumulator, item, coordinates, false);
+    }
+
+    accumulator
+}
+
+#[cube]
+fn reduce_axes_shared<P: ReducePrecision, R: ReduceInstruction<P>>(
+    input: &Tensor<Line<P::EI>>,
+    inst: &R,
+    offset: u32,
+    reduced_axes: u32,
+    reduced_size: u32,
+    #[comptime] accumulator_size: u32,
+) -> R::AccumulatorItem {
+    let requirements = R::requirements(inst);
+    let mut accumulator =
+        R::SharedAccumulator::allocate(accumulator_size, 1, requirements.coordinates);
+    R::SharedAccumulator::write(&mut accumulator, UNIT_POS, R::null_accumulator(inst, 1));
+
+    for coordinate in range_stepped(UNIT_POS, reduced_size, CUBE_DIM) {
+        let item = input[offset + reduced_offset(input, reduced_axes, coordinate)];
+        let coordinates = if comptime![requirements.coordinates] {
+            ReduceCoordinate::new_Required(Line::new(coordinate))
+        } else {
+            ReduceCoordinate::new_NotRequired()
+        };
+        reduce_shared_inplace::<P, R>(inst, &mut accumulator, UNIT_POS, item, coordinates, false);
+    }
+
+    sync_cube();
+    reduce_tree::<P, R>(inst, &mut accumulator, accumulator_size)
+}
+
+/// The offset in `input` of the element at `coordinate` in the collapsed space of the reduced axes,
+/// relative to the first reduced element.
+#[cube]
+fn reduced_offset<E: Numeric>(input: &Tensor<Line<E>>, reduced_axes: u32, coordinate: u32) -> u32 {
+    let rank = input.rank();
+    let mut remainder = coordinate;
+    let mut offset = 0;
+
+    for i in 0..rank {
+        let axis = rank - 1 - i;
+        if (reduced_axes >> axis) & 1 == 1 {
+            let shape = input.shape(axis);
+            offset += (remainder % shape) * input.stride(axis);
+            remainder /= shape;
+        }
+    }
+
+    offset
+}
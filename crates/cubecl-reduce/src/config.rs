@@ -1,14 +1,18 @@
 use cubecl_core::{
-    channel::ComputeChannel, prelude::*, server::ComputeServer, tensor_line_size_parallel,
-    tensor_line_size_perpendicular,
+    LaunchShape, channel::ComputeChannel, prelude::*, server::ComputeServer,
+    tensor_line_size_parallel, tensor_line_size_perpendicular,
 };
 use cubecl_std::tensor::is_contiguous;
 
-use crate::ReduceStrategy;
+use crate::{ReduceError, ReduceStrategy};
 
 // TODO: Should we allows the user to change that?
 const DEFAULT_PLANE_COUNT: u32 = 8;
 
+/// The maximum number of short rows reduced by the segments of a single plane.
+/// Each row needs its own plane collective, so more rows per plane stop paying off.
+const MAX_ROWS_PER_PLANE: u32 = 4;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum LineMode {
     Parallel,
@@ -39,6 +43,9 @@ pub struct ReduceConfig {
     pub line_size_output: u32,
     pub bound_checks: bool,
     pub bound_checks_inner: BoundChecksInner,
+    /// The number of rows reduced by each plane when planes reduce the contiguous rows without shared memory.
+    /// The plane is split into that many segments of consecutive units, one per row.
+    pub rows_per_plane: u32,
 }
 
 impl ReduceConfig {
@@ -48,12 +55,13 @@ impl ReduceConfig {
         output: &TensorHandleRef<R>,
         axis: usize,
         strategy: &ReduceStrategy,
-    ) -> ReduceConfig {
+    ) -> Result<ReduceConfig, ReduceError> {
         let reduce_count = output.size() as u32;
         ReduceConfig::new()
             .generate_line_mode(input, axis)
             .generate_line_size::<R, In>(input, output, axis)
             .generate_cube_dim(client, strategy.use_planes)
+            .generate_rows_per_plane(input.shape[axis], strategy)
             .generate_cube_count::<R>(reduce_count, strategy)
     }
 
@@ -67,6 +75,7 @@ impl ReduceConfig {
             line_size_output: 1,
             bound_checks: true,
             bound_checks_inner: BoundChecksInner::Mask,
+            rows_per_plane: 1,
         }
     }
 
@@ -182,15 +191,37 @@ impl ReduceConfig {
         self
     }
 
+    /// Size the segments of the planes to the lines of a row, so the short rows don't leave most of
+    /// a plane idle.
+    pub fn generate_rows_per_plane(mut self, shape_axis: usize, strategy: &ReduceStrategy) -> Self {
+        let plane_dim = self.cube_dim.x;
+        let segmented = strategy.use_planes
+            && !strategy.shared
+            && self.line_mode == LineMode::Parallel
+            && plane_dim.is_power_of_two();
+
+        self.rows_per_plane = if segmented {
+            let row_lines = (shape_axis as u32).div_ceil(self.line_size_input);
+            let min_segment_dim = (plane_dim / MAX_ROWS_PER_PLANE).max(1);
+            let segment_dim = row_lines
+                .next_power_of_two()
+                .clamp(min_segment_dim, plane_dim);
+            plane_dim / segment_dim
+        } else {
+            1
+        };
+        self
+    }
+
     pub fn generate_cube_count<R: Runtime>(
         mut self,
         reduce_count: u32,
         strategy: &ReduceStrategy,
-    ) -> Self {
+    ) -> Result<Self, ReduceError> {
         let agent_count_per_cube =  // An agent is either a unit, a plane or a whole cube depending on the strategy.
             match strategy {
                 ReduceStrategy { shared: true, .. } => 1,
-                ReduceStrategy { use_planes: true, .. } => self.cube_dim.y,
+                ReduceStrategy { use_planes: true, .. } => self.cube_dim.y * self.rows_per_plane,
                 ReduceStrategy { use_planes: false, .. } => self.cube_dim.num_elems(),
             };
         let reduce_count_per_cube = match self.line_mode {
@@ -198,27 +229,18 @@ impl ReduceConfig {
             LineMode::Perpendicular => agent_count_per_cube * self.line_size_input,
         };
 
-        let cube_count = reduce_count.div_ceil(reduce_count_per_cube);
+        // The cubes past the runtime limitation are folded into the next axes, launching a few
+        // more cubes than needed.
+        let launch = LaunchShape::elementwise(reduce_count as usize)
+            .units_per_cube(reduce_count_per_cube)
+            .build::<R>()?;
 
-        self.do_bound_checks_if(reduce_count_per_cube * cube_count > reduce_count);
+        self.do_bound_checks_if(
+            launch.num_cubes() * reduce_count_per_cube as u64 != reduce_count as u64,
+        );
+        self.cube_count = launch.cube_count;
 
-        // If needed, we decompose the cube count to be within runtime limitation.
-        let (max_x, max_y, _) = R::max_cube_count();
-        let mut cube_count_x = cube_count;
-        let mut cube_count_y = 1;
-        let mut cube_count_z = 1;
-        while cube_count_x > max_x {
-            cube_count_x /= 2;
-            cube_count_y *= 2;
-        }
-        while cube_count_y > max_y {
-            cube_count_y /= 2;
-            cube_count_z *= 2;
-        }
-        self.cube_count = CubeCount::new_3d(cube_count_x, cube_count_y, cube_count_z);
-        self.do_bound_checks_if(cube_count_x * cube_count_y != cube_count);
-
-        self
+        Ok(self)
     }
 
     fn do_bound_checks_if(&mut self, condition: bool) {
@@ -0,0 +1,359 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape};
+use cubecl_runtime::Plane;
+
+use crate::ReduceError;
+use crate::instructions::Sum;
+use crate::scan::scan_cube;
+
+/// The number of units in a cube scanning a tile of a row.
+const CUBE_SIZE: u32 = 256;
+/// The number of consecutive elements of a row scanned by each unit of a tile.
+const ITEMS_PER_UNIT: u32 = 4;
+
+/// The options of [`cumsum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CumsumOptions {
+    /// Whether each element is included in its own sum.
+    pub inclusive: bool,
+    /// Sum the elements from the end of the axis instead of its start.
+    pub reverse: bool,
+}
+
+impl Default for CumsumOptions {
+    fn default() -> Self {
+        Self {
+            inclusive: true,
+            reverse: false,
+        }
+    }
+}
+
+/// Compute the running totals of the `input` tensor along the given `axis` and write them into `output`.
+///
+/// Each row along the `axis` is scanned independently. The rows that fit in a tile of 1024 elements are
+/// scanned by a single cube, combining the plane prefix sums through shared memory. The longer rows are
+/// split in tiles: each tile is scanned by its own cube, the totals of the tiles are scanned in turn,
+/// then each tile is offset by the total of the tiles before it.
+///
+/// When `options.inclusive` is false, each output element is the sum of the elements before it, so the first
+/// one is 0. When `options.reverse` is true, the elements are summed from the end of the axis.
+///
+/// Both tensors can have any strides, but they must not share a handle.
+/// Return an error if the `axis` is larger than the rank or if the shapes of `input` and `output` differ.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let probabilities = /* a tensor of shape [batch, classes] */;
+/// let cdf = /* a tensor of the same shape */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// cumsum::<R, f32>(&client, probabilities, cdf, 1, CumsumOptions::default())?;
+/// ```
+pub fn cumsum<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    axis: usize,
+    options: CumsumOptions,
+) -> Result<(), ReduceError> {
+    let rank = input.shape.len();
+    if axis >= rank {
+        return Err(ReduceError::InvalidAxis { axis, rank });
+    }
+    if output.shape != input.shape {
+        return Err(ReduceError::MismatchShape {
+            expected_shape: input.shape.to_vec(),
+            output_shape: output.shape.to_vec(),
+        });
+    }
+
+    let length = input.shape[axis];
+    let num_rows = input.shape.iter().product::<usize>() / length.max(1);
+    if length * num_rows == 0 {
+        return Ok(());
+    }
+
+    launch_cumsum::<R, N>(client, &input, &output, axis, num_rows, options)
+}
+
+fn launch_cumsum<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    axis: usize,
+    num_rows: usize,
+    options: CumsumOptions,
+) -> Result<(), ReduceError> {
+    let length = input.shape[axis];
+    let tile_length = (CUBE_SIZE * ITEMS_PER_UNIT) as usize;
+    let tiles_per_row = length.div_ceil(tile_length);
+
+    let hw_props = &client.properties().hardware;
+    let plane_size = hw_props.plane_size_max;
+    let use_planes = client.properties().features.plane.contains(Plane::Ops)
+        && hw_props.plane_size_min == plane_size
+        && CUBE_SIZE.is_multiple_of(plane_size);
+    let cube_dim = match use_planes {
+        true => CubeDim::new_2d(plane_size, CUBE_SIZE / plane_size),
+        false => CubeDim::new_1d(CUBE_SIZE),
+    };
+
+    let num_tiles = num_rows * tiles_per_row;
+    let cube_count = LaunchShape::elementwise(num_tiles)
+        .units_per_cube(1)
+        .build::<R>()?
+        .cube_count;
+
+    let params = CumsumParams {
+        items_per_unit: ITEMS_PER_UNIT,
+        cube_size: CUBE_SIZE,
+        use_planes,
+        inclusive: options.inclusive,
+        reverse: options.reverse,
+        write_totals: tiles_per_row > 1,
+    };
+
+    // The totals of the tiles of each row, or a placeholder when the rows fit in a single tile.
+    let elem_size = size_of::<N>();
+    let num_totals = if tiles_per_row > 1 { num_tiles } else { 1 };
+    let totals_handle = client.empty(num_totals * elem_size);
+    let totals_shape = [num_rows, tiles_per_row];
+    let totals_strides = [tiles_per_row, 1];
+    let totals = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(
+            &totals_handle,
+            &totals_strides,
+            &totals_shape,
+            elem_size,
+        )
+    };
+
+    unsafe {
+        cumsum_tiles_kernel::launch_unchecked::<N, R>(
+            client,
+            cube_count.clone(),
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            totals.as_tensor_arg(1),
+            ScalarArg::new(axis as u32),
+            ScalarArg::new(num_rows as u32),
+            ScalarArg::new(tiles_per_row as u32),
+            params,
+        );
+    }
+
+    if tiles_per_row > 1 {
+        // The tiles are numbered in the order they are scanned, so their offsets are always
+        // the exclusive forward sums of their totals.
+        let offsets_handle = client.empty(num_tiles * elem_size);
+        let offsets = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &offsets_handle,
+                &totals_strides,
+                &totals_shape,
+                elem_size,
+            )
+        };
+        launch_cumsum::<R, N>(
+            client,
+            &totals,
+            &offsets,
+            1,
+            num_rows,
+            CumsumOptions {
+                inclusive: false,
+                reverse: false,
+            },
+        )?;
+
+        unsafe {
+            cumsum_add_offsets_kernel::launch_unchecked::<N, R>(
+                client,
+                cube_count,
+                cube_dim,
+                output.as_tensor_arg(1),
+                offsets.as_tensor_arg(1),
+                ScalarArg::new(axis as u32),
+                ScalarArg::new(num_rows as u32),
+                ScalarArg::new(tiles_per_row as u32),
+                params,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The comptime parameters of the cumsum kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CumsumParams {
+    /// The number of consecutive elements of a row scanned by each unit.
+    pub items_per_unit: u32,
+    /// The number of units in a cube, which must be the number of units of the cube dimension.
+    pub cube_size: u32,
+    /// Whether the cube dimension is `(plane_dim, cube_size / plane_dim)` and the prefixes
+    /// within a plane are computed with plane operations.
+    pub use_planes: bool,
+    /// Whether each element is included in its own sum.
+    pub inclusive: bool,
+    /// Whether the elements are summed from the end of the axis.
+    pub reverse: bool,
+    /// Whether the total of each tile is written, when the rows span multiple tiles.
+    pub write_totals: bool,
+}
+
+/// Scan each tile of the rows of `input` into `output`, each cube scanning the tile `CUBE_POS % tiles_per_row`
+/// of the row `CUBE_POS / tiles_per_row`, and write the total of each tile into `totals`.
+#[cube(launch_unchecked)]
+fn cumsum_tiles_kernel<N: Numeric>(
+    input: &Tensor<N>,
+    output: &mut Tensor<N>,
+    totals: &mut Tensor<N>,
+    axis: u32,
+    num_rows: u32,
+    tiles_per_row: u32,
+    #[comptime] params: CumsumParams,
+) {
+    if CUBE_POS >= num_rows * tiles_per_row {
+        terminate!();
+    }
+
+    let row = CUBE_POS / tiles_per_row;
+    let tile = CUBE_POS % tiles_per_row;
+    let length = input.shape(axis);
+    let input_offset = row_offset(input, row, axis);
+    let output_offset = row_offset(output, row, axis);
+    let first_position = (tile * params.cube_size + UNIT_POS) * params.items_per_unit;
+
+    // Each unit keeps the inclusive sums of its elements.
+    let mut items = Array::<N>::new(params.items_per_unit);
+    let mut aggregate = N::from_int(0);
+    #[unroll]
+    for i in 0..params.items_per_unit {
+        let position = first_position + i;
+        if position < length {
+            let index = axis_index(position, length, params.reverse);
+            aggregate += input[input_offset + index * input.stride(axis)];
+        }
+        items[i] = aggregate;
+    }
+
+    let (exclusive, total) = cube_exclusive_sum::<N>(aggregate, params);
+
+    let mut previous = exclusive;
+    #[unroll]
+    for i in 0..params.items_per_unit {
+        let position = first_position + i;
+        let inclusive = exclusive + items[i];
+        if position < length {
+            let index = axis_index(position, length, params.reverse);
+            let value = if comptime![params.inclusive] {
+                inclusive
+            } else {
+                previous
+            };
+            output[output_offset + index * output.stride(axis)] = value;
+        }
+        previous = inclusive;
+    }
+
+    #[allow(clippy::collapsible_if)]
+    if comptime![params.write_totals] {
+        if UNIT_POS == 0 {
+            totals[CUBE_POS] = total;
+        }
+    }
+}
+
+/// Add to each tile of the rows of `output` the sum of the tiles before it in `offsets`.
+#[cube(launch_unchecked)]
+fn cumsum_add_offsets_kernel<N: Numeric>(
+    output: &mut Tensor<N>,
+    offsets: &Tensor<N>,
+    axis: u32,
+    num_rows: u32,
+    tiles_per_row: u32,
+    #[comptime] params: CumsumParams,
+) {
+    // The first tile of each row has no offset.
+    if CUBE_POS >= num_rows * tiles_per_row || CUBE_POS % tiles_per_row == 0 {
+        terminate!();
+    }
+
+    let row = CUBE_POS / tiles_per_row;
+    let tile = CUBE_POS % tiles_per_row;
+    let length = output.shape(axis);
+    let output_offset = row_offset(output, row, axis);
+    let first_position = (tile * params.cube_size + UNIT_POS) * params.items_per_unit;
+    let offset = offsets[CUBE_POS];
+
+    #[unroll]
+    for i in 0..params.items_per_unit {
+        let position = first_position + i;
+        if position < length {
+            let index =
+                output_offset + axis_index(position, length, params.reverse) * output.stride(axis);
+            output[index] += offset;
+        }
+    }
+}
+
+/// The sum of the aggregates of the units before this unit in the cube, and the sum of all of them.
+#[cube]
+fn cube_exclusive_sum<N: Numeric>(aggregate: N, #[comptime] params: CumsumParams) -> (N, N) {
+    let mut shared = SharedMemory::<N>::new(params.cube_size);
+
+    if comptime![params.use_planes] {
+        let exclusive = plane_exclusive_sum(aggregate);
+        if UNIT_POS_X == CUBE_DIM_X - 1 {
+            shared[UNIT_POS_Y] = exclusive + aggregate;
+        }
+        sync_cube();
+
+        // There are few planes in a cube, so each unit sums the totals of the planes before its own.
+        let mut prefix = N::from_int(0);
+        let mut total = N::from_int(0);
+        for plane in 0..CUBE_DIM_Y {
+            let plane_total = shared[plane];
+            if plane < UNIT_POS_Y {
+                prefix += plane_total;
+            }
+            total += plane_total;
+        }
+        (prefix + exclusive, total)
+    } else {
+        let exclusive = scan_cube::<N, Sum>(&mut shared, aggregate, params.cube_size);
+        (exclusive, shared[params.cube_size - 1])
+    }
+}
+
+/// The offset of the first element of the `row` of `tensor`, its rows being the lines along `axis`
+/// in the row-major order of the other axes.
+#[cube]
+fn row_offset<N: Numeric>(tensor: &Tensor<N>, row: u32, axis: u32) -> u32 {
+    let rank = tensor.rank();
+    let mut offset = 0;
+    let mut remainder = row;
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        if dim != axis {
+            offset += (remainder % tensor.shape(dim)) * tensor.stride(dim);
+            remainder /= tensor.shape(dim);
+        }
+    }
+    offset
+}
+
+/// The index along the axis of the element scanned at `position`.
+#[cube]
+fn axis_index(position: u32, length: u32, #[comptime] reverse: bool) -> u32 {
+    if comptime![reverse] {
+        length - 1 - position
+    } else {
+        position
+    }
+}
@@ -1,6 +1,6 @@
 use core::fmt;
 
-use cubecl_core::ir::StorageType;
+use cubecl_core::{LaunchShapeError, ir::StorageType};
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum ReduceError {
@@ -12,6 +12,8 @@ pub enum ReduceError {
     ImprecisePlaneDim,
     /// Indicate the axis is too large.
     InvalidAxis { axis: usize, rank: usize },
+    /// Indicate that an axis is given more than once in a multi-axis reduction.
+    DuplicateAxis { axis: usize },
     /// Indicate that the shape of the output tensor is invalid for the given input and axis.
     MismatchShape {
         expected_shape: Vec<usize>,
@@ -19,6 +21,21 @@ pub enum ReduceError {
     },
     /// Indicate that we can't launch a shared sum because the atomic addition is not supported.
     MissingAtomicAdd(StorageType),
+    /// Indicate that the number of values selected by a top-k is 0 or too large for the length of the axis.
+    InvalidTopK { k: usize, length: usize },
+    /// Indicate that a single-pass scan can't be launched because the client doesn't guarantee forward progress
+    /// between cubes or doesn't support 64-bit atomics, or because the elements aren't 32 bits.
+    LookbackUnavailable,
+    /// Indicate that a histogram is computed without any bin.
+    EmptyHistogram,
+    /// Indicate that a histogram of floats is computed without a range, or with a range that is empty or not finite.
+    InvalidHistogramRange,
+    /// Indicate that the values of a segmented reduction aren't a vector, or that its offsets aren't a vector
+    /// with at least one element.
+    InvalidSegmentOffsets {
+        values_shape: Vec<usize>,
+        offsets_shape: Vec<usize>,
+    },
 }
 
 impl fmt::Display for ReduceError {
@@ -39,6 +56,9 @@ impl fmt::Display for ReduceError {
                 f,
                 "The provided axis ({axis}) must be smaller than the input tensor rank ({rank})."
             ),
+            Self::DuplicateAxis { axis } => {
+                write!(f, "The axis {axis} is reduced more than once.")
+            }
             Self::MismatchShape {
                 expected_shape,
                 output_shape,
@@ -51,6 +71,33 @@ impl fmt::Display for ReduceError {
             Self::MissingAtomicAdd(elem) => {
                 write!(f, "Atomic add not supported by the client for {elem}")
             }
+            Self::InvalidTopK { k, length } => write!(
+                f,
+                "The number of selected values ({k}) must be between 1 and the length of the axis ({length}), at most {}.",
+                crate::MAX_TOPK
+            ),
+            Self::LookbackUnavailable => write!(
+                f,
+                "Trying to launch a decoupled lookback scan, but it isn't supported by the client for this element type."
+            ),
+            Self::EmptyHistogram => write!(f, "A histogram must have at least one bin."),
+            Self::InvalidHistogramRange => write!(
+                f,
+                "A histogram of floats must have a finite range with a minimum smaller than the maximum."
+            ),
+            Self::InvalidSegmentOffsets {
+                values_shape,
+                offsets_shape,
+            } => write!(
+                f,
+                "A segmented reduction needs vectors of values and of at least one offset, got shapes {values_shape:?} and {offsets_shape:?}."
+            ),
         }
     }
 }
+
+impl From<LaunchShapeError> for ReduceError {
+    fn from(_: LaunchShapeError) -> Self {
+        Self::CubeCountTooLarge
+    }
+}
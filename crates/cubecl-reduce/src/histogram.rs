@@ -0,0 +1,338 @@
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+use cubecl_std::tensor::{TensorHandle, into_contiguous, is_contiguous};
+
+use crate::ReduceError;
+
+/// The number of units in a cube counting the values of the input.
+const CUBE_SIZE: u32 = 256;
+/// The smallest number of lines counted by each unit before merging its cube into the output.
+const MIN_LINES_PER_UNIT: usize = 16;
+/// The largest number of cubes launched, each unit counting more lines for larger inputs.
+const MAX_CUBE_COUNT: usize = 1024;
+/// The largest number of copies of the bins in a cube, one for each unit of a plane of 32.
+const MAX_COPIES: usize = 32;
+/// The shared memory in bytes used for the copies of the bins, leaving room for other cubes
+/// on the same multiprocessor. A single copy may use more, up to the shared memory of the hardware.
+const SHARED_MEMORY_BUDGET: usize = 16 * 1024;
+
+/// How the values outside of the bins of a histogram are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HistogramOutliers {
+    /// The values below the first bin are counted in the first bin and the values above the last bin in the last bin.
+    /// NaNs aren't counted.
+    #[default]
+    Clamp,
+    /// The values outside of the bins and the NaNs are counted in an extra overflow bin after the last bin.
+    Overflow,
+}
+
+/// The bins of a [`histogram`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramOptions {
+    /// The number of bins, without the overflow bin.
+    pub num_bins: usize,
+    /// The range `[min, max)` split into bins of the same width, required for float inputs.
+    ///
+    /// Integer inputs are ignoring it and counted in the bin of their value.
+    pub range: Option<(f32, f32)>,
+    /// How the values outside of the bins are counted.
+    pub outliers: HistogramOutliers,
+}
+
+impl HistogramOptions {
+    /// The bins of integer values from 0 to `num_bins - 1`.
+    pub fn new(num_bins: usize) -> Self {
+        Self {
+            num_bins,
+            range: None,
+            outliers: HistogramOutliers::default(),
+        }
+    }
+
+    /// Split the range `[min, max)` of float values into the bins.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set how the values outside of the bins are counted.
+    pub fn with_outliers(mut self, outliers: HistogramOutliers) -> Self {
+        self.outliers = outliers;
+        self
+    }
+
+    /// The number of counters of the histogram, including the overflow bin.
+    pub fn num_counters(&self) -> usize {
+        match self.outliers {
+            HistogramOutliers::Clamp => self.num_bins,
+            HistogramOutliers::Overflow => self.num_bins + 1,
+        }
+    }
+}
+
+/// An element whose values can be counted by a [`histogram`].
+#[cube]
+pub trait HistogramElement: Numeric + CubeElement {
+    /// The bin of the `value`, or `num_bins` when it is outside of the bins.
+    ///
+    /// When `clamp` is true, only the values that can't be clamped to a bin, like NaNs, are outside of the bins.
+    fn bin(
+        value: Self,
+        range_min: f32,
+        bin_width: f32,
+        #[comptime] num_bins: u32,
+        #[comptime] clamp: bool,
+    ) -> u32;
+}
+
+#[cube]
+impl HistogramElement for u8 {
+    fn bin(
+        value: Self,
+        _range_min: f32,
+        _bin_width: f32,
+        #[comptime] num_bins: u32,
+        #[comptime] clamp: bool,
+    ) -> u32 {
+        integer_bin(u32::cast_from(value), num_bins, clamp)
+    }
+}
+
+#[cube]
+impl HistogramElement for u32 {
+    fn bin(
+        value: Self,
+        _range_min: f32,
+        _bin_width: f32,
+        #[comptime] num_bins: u32,
+        #[comptime] clamp: bool,
+    ) -> u32 {
+        integer_bin(value, num_bins, clamp)
+    }
+}
+
+#[cube]
+impl HistogramElement for f32 {
+    fn bin(
+        value: Self,
+        range_min: f32,
+        bin_width: f32,
+        #[comptime] num_bins: u32,
+        #[comptime] clamp: bool,
+    ) -> u32 {
+        let position = (value - range_min) / bin_width;
+
+        // Clamping before the cast keeps the infinities in range, and rounding can't place
+        // the values just below the maximum past the last bin.
+        let bin = u32::cast_from(Min::min(
+            Max::max(position, 0.0),
+            f32::cast_from(num_bins - 1),
+        ));
+        if clamp {
+            select(value != value, num_bins, bin)
+        } else {
+            let in_range = position >= 0.0 && position < f32::cast_from(num_bins);
+            select(in_range, bin, num_bins)
+        }
+    }
+}
+
+#[cube]
+fn integer_bin(value: u32, #[comptime] num_bins: u32, #[comptime] clamp: bool) -> u32 {
+    if clamp {
+        Min::min(value, num_bins - 1)
+    } else {
+        select(value < num_bins, value, num_bins)
+    }
+}
+
+/// Count the values of the `input` tensor in the bins given by the `options`.
+///
+/// Return a contiguous tensor with one counter for each bin, followed by the overflow bin
+/// for [`HistogramOutliers::Overflow`].
+///
+/// Each cube counts its values in copies of the bins privatized in shared memory, with shared atomics,
+/// then adds each bin to the output with a single global atomic. When the bins are few, multiple copies
+/// spread the units of a plane counting the same value over different banks,
+/// so skewed inputs don't serialize on a single counter.
+/// When the bins don't fit in shared memory, the values are counted directly with global atomics,
+/// which is much slower under contention.
+///
+/// Return an error if there are no bins, if the client doesn't support atomic additions of `u32`,
+/// or if the input is a float without a finite and non-empty range.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let input = /* a tensor of f32 */;
+///
+/// // Count the values in 256 bins between -1 and 1, NaNs and values outside the range in an extra bin.
+/// let options = HistogramOptions::new(256)
+///     .with_range(-1.0, 1.0)
+///     .with_outliers(HistogramOutliers::Overflow);
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let counts = histogram::<R, f32>(&client, input, options)?;
+/// ```
+pub fn histogram<R: Runtime, E: HistogramElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    options: HistogramOptions,
+) -> Result<TensorHandle<R, u32>, ReduceError> {
+    if options.num_bins == 0 {
+        return Err(ReduceError::EmptyHistogram);
+    }
+    let elem = E::as_type_native_unchecked();
+    let (range_min, bin_width) = match (elem.is_float(), options.range) {
+        (false, _) => (0.0, 1.0),
+        (true, Some((min, max))) if min.is_finite() && max.is_finite() && min < max => {
+            (min, (max - min) / options.num_bins as f32)
+        }
+        (true, _) => return Err(ReduceError::InvalidHistogramRange),
+    };
+    let atomic_elem = Atomic::<u32>::as_type_native_unchecked();
+    if !client
+        .properties()
+        .type_usage(atomic_elem)
+        .contains(TypeUsage::AtomicAdd)
+    {
+        return Err(ReduceError::MissingAtomicAdd(
+            u32::as_type_native_unchecked(),
+        ));
+    }
+
+    let num_counters = options.num_counters();
+    let output = TensorHandle::<R, u32>::new_contiguous(
+        vec![num_counters],
+        client.create(u32::as_bytes(&vec![0; num_counters])),
+    );
+
+    let length = input.shape.iter().product::<usize>();
+    if length == 0 {
+        return Ok(output);
+    }
+
+    // The values are counted in any order, so only the layout of the input matters.
+    let contiguous;
+    let input = match is_contiguous(input.shape, input.strides) {
+        true => input,
+        false => {
+            contiguous = into_contiguous::<R, E>(client, &input);
+            contiguous.as_ref()
+        }
+    };
+
+    let line_size = R::line_size_type(&elem)
+        .filter(|line_size| length.is_multiple_of(*line_size as usize))
+        .max()
+        .unwrap_or(1);
+    let num_lines = length / line_size as usize;
+
+    let max_counters = client.properties().hardware.max_shared_memory_size / size_of::<u32>();
+    let copies = match num_counters <= max_counters {
+        true => (SHARED_MEMORY_BUDGET / size_of::<u32>() / num_counters).clamp(1, MAX_COPIES),
+        false => {
+            log::warn!(
+                "The {num_counters} bins of the histogram don't fit in shared memory, counting with global atomics."
+            );
+            0
+        }
+    };
+    let params = HistogramParams {
+        num_bins: options.num_bins as u32,
+        overflow: options.outliers == HistogramOutliers::Overflow,
+        copies: copies as u32,
+        line_size: line_size as u32,
+    };
+
+    let cube_count = num_lines
+        .div_ceil(CUBE_SIZE as usize * MIN_LINES_PER_UNIT)
+        .min(MAX_CUBE_COUNT);
+
+    unsafe {
+        histogram_kernel::launch_unchecked::<E, R>(
+            client,
+            CubeCount::new_1d(cube_count as u32),
+            CubeDim::new_1d(CUBE_SIZE),
+            input.as_tensor_arg(line_size),
+            output.as_ref().as_tensor_arg(1),
+            ScalarArg::new(range_min),
+            ScalarArg::new(bin_width),
+            params,
+        );
+    }
+
+    Ok(output)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HistogramParams {
+    /// The number of bins, without the overflow bin.
+    pub num_bins: u32,
+    /// Whether the values outside of the bins are counted in an overflow bin rather than clamped.
+    pub overflow: bool,
+    /// The number of copies of the bins in shared memory, or 0 to count with global atomics.
+    pub copies: u32,
+    pub line_size: u32,
+}
+
+/// Count the values of `input` into the bins of `output`, which must be zeroed before the first launch.
+///
+/// The copies of the bins are interleaved in shared memory, so the copies of a bin are in consecutive banks.
+#[cube(launch_unchecked)]
+pub fn histogram_kernel<E: HistogramElement>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Atomic<u32>>,
+    range_min: f32,
+    bin_width: f32,
+    #[comptime] params: HistogramParams,
+) {
+    let num_counters = comptime!(params.num_bins + params.overflow as u32);
+    let clamp = comptime!(!params.overflow);
+    let num_units = CUBE_COUNT * CUBE_DIM;
+
+    if comptime!(params.copies == 0) {
+        for i in range_stepped(ABSOLUTE_POS, input.len(), num_units) {
+            let line = input[i];
+            #[unroll]
+            for k in 0..params.line_size {
+                let bin = E::bin(line[k], range_min, bin_width, params.num_bins, clamp);
+                if bin < num_counters {
+                    Atomic::add(&output[bin], 1u32);
+                }
+            }
+        }
+    } else {
+        let size = comptime!(num_counters * params.copies);
+        let bins = SharedMemory::<Atomic<u32>>::new(size);
+        for i in range_stepped(UNIT_POS, size, CUBE_DIM) {
+            Atomic::store(&bins[i], 0u32);
+        }
+        sync_cube();
+
+        let copy = UNIT_POS % params.copies;
+        for i in range_stepped(ABSOLUTE_POS, input.len(), num_units) {
+            let line = input[i];
+            #[unroll]
+            for k in 0..params.line_size {
+                let bin = E::bin(line[k], range_min, bin_width, params.num_bins, clamp);
+                if bin < num_counters {
+                    Atomic::add(&bins[bin * params.copies + copy], 1u32);
+                }
+            }
+        }
+        sync_cube();
+
+        for bin in range_stepped(UNIT_POS, num_counters, CUBE_DIM) {
+            let mut count = 0u32;
+            for copy in 0..params.copies {
+                count += Atomic::load(&bins[bin * params.copies + copy]);
+            }
+            if count > 0 {
+                Atomic::add(&output[bin], count);
+            }
+        }
+    }
+}
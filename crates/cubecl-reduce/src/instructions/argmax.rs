@@ -5,10 +5,12 @@ use crate::{instructions::ReduceRequirements, precision::ReducePrecision};
 
 use super::{
     ArgAccumulator, ReduceCoordinate, ReduceCoordinateExpand, ReduceFamily, ReduceInstruction,
-    lowest_coordinate_matching,
+    lowest_coordinate_matching, replace_nan,
 };
 
 /// Compute the coordinate of the maximum item returning the smallest coordinate in case of equality.
+///
+/// NaNs never win: they are ignored, and an axis made only of NaNs gives the coordinate `0`.
 #[derive(Debug, CubeType, Clone)]
 pub struct ArgMax {}
 
@@ -74,7 +76,7 @@ impl<P: ReducePrecision> ReduceInstruction<P> for ArgMax {
     }
 
     fn reduce(
-        _this: &Self,
+        this: &Self,
         accumulator: &Self::AccumulatorItem,
         item: Line<P::EI>,
         coordinate: ReduceCoordinate,
@@ -89,6 +91,11 @@ impl<P: ReducePrecision> ReduceInstruction<P> for ArgMax {
             }
         };
 
+        let item = replace_nan(
+            item,
+            <Self as ReduceInstruction<P>>::null_input(this, item.size()),
+        );
+
         let (candidate_item, candidate_coordinate) = if use_planes {
             let candidate_item = plane_max(item);
             let candidate_coordinate = lowest_coordinate_matching(candidate_item, item, coordinate);
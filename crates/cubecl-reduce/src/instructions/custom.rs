@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+
+use cubecl_core as cubecl;
+use cubecl_core::prelude::*;
+
+use crate::precision::ReducePrecision;
+
+use super::{
+    ReduceCoordinate, ReduceFamily, ReduceInstruction, ReduceRequirements, SharedAccumulator,
+};
+
+/// The [`ReduceOp`] of every precision, as [`ReduceFamily`] is for [`ReduceInstruction`].
+pub trait ReduceOpFamily: Send + Sync + 'static + std::fmt::Debug {
+    type Op<P: ReducePrecision>: ReduceOp<P>;
+}
+
+/// A reduction defined by an associative combination of accumulators, reduced by
+/// [`reduce_custom`](crate::reduce_custom) with any [`ReduceStrategy`](crate::ReduceStrategy).
+///
+/// Each line of inputs is mapped to an accumulator, the accumulators are combined in the order chosen by
+/// the strategy, and the last one is finalized into the output. [`Sum`](super::Sum), [`Prod`](super::Prod),
+/// [`Max`](super::Max) and [`Min`](super::Min) accumulate a single line, but an accumulator can be any
+/// [`CubeType`], like a struct of lines, with its own [`SharedAccumulator`].
+#[cube]
+pub trait ReduceOp<P: ReducePrecision>: Send + Sync + 'static {
+    /// The state into which the inputs are accumulated.
+    type Accumulator: CubeType;
+
+    /// The accumulators of all the units or planes of a cube, for the shared strategies.
+    type SharedAccumulator: SharedAccumulator<Item = Self::Accumulator>;
+
+    /// The accumulator left unchanged when combined with any other.
+    fn identity(#[comptime] line_size: u32) -> Self::Accumulator;
+
+    /// An input whose mapped accumulator leaves any other unchanged, read in place of the out-of-bound inputs.
+    fn neutral_input(#[comptime] line_size: u32) -> Line<P::EI>;
+
+    /// The accumulator of a single line of inputs.
+    fn map(item: Line<P::EI>) -> Self::Accumulator;
+
+    /// Combine two accumulators. The combination must be associative.
+    fn combine(lhs: Self::Accumulator, rhs: Self::Accumulator) -> Self::Accumulator;
+
+    /// Combine the accumulators of all the units of a plane, for the strategies using planes.
+    /// Every unit of the plane gets the combined accumulator.
+    fn plane_combine(accumulator: Self::Accumulator) -> Self::Accumulator;
+
+    /// Assign `source` into `destination`, which is `*destination = *source` for a line.
+    fn assign(destination: &mut Self::Accumulator, source: &Self::Accumulator);
+
+    /// The outputs of the accumulator, one for each of the elements of its lines.
+    fn finalize<Out: Numeric>(accumulator: Self::Accumulator) -> Line<Out>;
+}
+
+/// The [`ReduceInstruction`] of the [`ReduceOp`] of `F`.
+///
+/// The lanes of an accumulator can't be combined with each other, so [`reduce_custom`](crate::reduce_custom)
+/// only vectorizes the reduction across the reduced axis.
+#[derive(Debug, CubeType)]
+pub struct CustomReduce<F: ReduceOpFamily> {
+    #[cube(comptime)]
+    _op: PhantomData<F>,
+}
+
+impl<F: ReduceOpFamily> ReduceFamily for CustomReduce<F> {
+    type Instruction<P: ReducePrecision> = Self;
+    type Config = ();
+}
+
+#[cube]
+impl<P: ReducePrecision, F: ReduceOpFamily> ReduceInstruction<P> for CustomReduce<F> {
+    type AccumulatorItem = <F::Op<P> as ReduceOp<P>>::Accumulator;
+    type SharedAccumulator = <F::Op<P> as ReduceOp<P>>::SharedAccumulator;
+    type Config = ();
+
+    fn requirements(_this: &Self) -> ReduceRequirements {
+        ReduceRequirements { coordinates: false }
+    }
+
+    fn from_config(_config: Self::Config) -> Self {
+        CustomReduce::<F> { _op: PhantomData }
+    }
+
+    fn null_input(_this: &Self, #[comptime] line_size: u32) -> Line<P::EI> {
+        <F::Op<P> as ReduceOp<P>>::neutral_input(line_size)
+    }
+
+    fn null_accumulator(_this: &Self, #[comptime] line_size: u32) -> Self::AccumulatorItem {
+        <F::Op<P> as ReduceOp<P>>::identity(line_size)
+    }
+
+    fn assign_accumulator(
+        _this: &Self,
+        destination: &mut Self::AccumulatorItem,
+        source: &Self::AccumulatorItem,
+    ) {
+        <F::Op<P> as ReduceOp<P>>::assign(destination, source);
+    }
+
+    fn reduce(
+        _this: &Self,
+        accumulator: &Self::AccumulatorItem,
+        item: Line<P::EI>,
+        _coordinate: ReduceCoordinate,
+        #[comptime] use_planes: bool,
+    ) -> Self::AccumulatorItem {
+        let item = <F::Op<P> as ReduceOp<P>>::map(item);
+        if use_planes {
+            let item = <F::Op<P> as ReduceOp<P>>::plane_combine(item);
+            <F::Op<P> as ReduceOp<P>>::combine(*accumulator, item)
+        } else {
+            <F::Op<P> as ReduceOp<P>>::combine(*accumulator, item)
+        }
+    }
+
+    fn fuse_accumulators(
+        _this: &Self,
+        lhs: Self::AccumulatorItem,
+        rhs: Self::AccumulatorItem,
+    ) -> Self::AccumulatorItem {
+        <F::Op<P> as ReduceOp<P>>::combine(lhs, rhs)
+    }
+
+    fn merge_line<Out: Numeric>(
+        _this: &Self,
+        accumulator: Self::AccumulatorItem,
+        _shape_axis_reduce: u32,
+    ) -> Out {
+        // The reduced axis isn't vectorized, so the accumulator has a single lane.
+        let output = <F::Op<P> as ReduceOp<P>>::finalize::<Out>(accumulator);
+        output[0]
+    }
+
+    fn to_output_perpendicular<Out: Numeric>(
+        _this: &Self,
+        accumulator: Self::AccumulatorItem,
+        _shape_axis_reduce: u32,
+    ) -> Line<Out> {
+        <F::Op<P> as ReduceOp<P>>::finalize::<Out>(accumulator)
+    }
+}
@@ -3,7 +3,7 @@ use cubecl_core::prelude::*;
 
 use crate::{instructions::ReduceRequirements, precision::ReducePrecision};
 
-use super::{ReduceCoordinate, ReduceFamily, ReduceInstruction};
+use super::{ReduceCoordinate, ReduceFamily, ReduceInstruction, ReduceOp, ReduceOpFamily};
 
 // TODO Add to test framework.
 /// Return the item with the maximum absolute value.
@@ -15,6 +15,10 @@ impl ReduceFamily for Min {
     type Config = ();
 }
 
+impl ReduceOpFamily for Min {
+    type Op<P: ReducePrecision> = Self;
+}
+
 #[cube]
 impl<P: ReducePrecision> ReduceInstruction<P> for Min {
     type AccumulatorItem = Line<P::EA>;
@@ -94,3 +98,37 @@ impl<P: ReducePrecision> ReduceInstruction<P> for Min {
         Line::cast_from(accumulator)
     }
 }
+
+#[cube]
+impl<P: ReducePrecision> ReduceOp<P> for Min {
+    type Accumulator = Line<P::EA>;
+    type SharedAccumulator = SharedMemory<Line<P::EA>>;
+
+    fn identity(#[comptime] line_size: u32) -> Self::Accumulator {
+        Line::empty(line_size).fill(P::EA::max_value())
+    }
+
+    fn neutral_input(#[comptime] line_size: u32) -> Line<P::EI> {
+        Line::empty(line_size).fill(P::EI::max_value())
+    }
+
+    fn map(item: Line<P::EI>) -> Self::Accumulator {
+        Line::cast_from(item)
+    }
+
+    fn combine(lhs: Self::Accumulator, rhs: Self::Accumulator) -> Self::Accumulator {
+        select_many(lhs.less_than(rhs), lhs, rhs)
+    }
+
+    fn plane_combine(accumulator: Self::Accumulator) -> Self::Accumulator {
+        plane_min(accumulator)
+    }
+
+    fn assign(destination: &mut Self::Accumulator, source: &Self::Accumulator) {
+        *destination = *source;
+    }
+
+    fn finalize<Out: Numeric>(accumulator: Self::Accumulator) -> Line<Out> {
+        Line::cast_from(accumulator)
+    }
+}
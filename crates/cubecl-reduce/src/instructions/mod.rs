@@ -1,6 +1,7 @@
 mod argmax;
 mod argmin;
 mod base;
+mod custom;
 mod max;
 mod maxabs;
 mod mean;
@@ -13,6 +14,7 @@ mod utils;
 pub use argmax::*;
 pub use argmin::*;
 pub use base::*;
+pub use custom::*;
 pub use max::*;
 pub use maxabs::*;
 pub use mean::*;
@@ -3,7 +3,7 @@ use cubecl_core::prelude::*;
 
 use crate::{instructions::ReduceRequirements, precision::ReducePrecision};
 
-use super::{ReduceCoordinate, ReduceFamily, ReduceInstruction};
+use super::{ReduceCoordinate, ReduceFamily, ReduceInstruction, ReduceOp, ReduceOpFamily};
 
 #[derive(Debug, CubeType, Clone)]
 pub struct Prod {}
@@ -13,6 +13,10 @@ impl ReduceFamily for Prod {
     type Config = ();
 }
 
+impl ReduceOpFamily for Prod {
+    type Op<P: ReducePrecision> = Self;
+}
+
 #[cube]
 impl<P: ReducePrecision> ReduceInstruction<P> for Prod {
     type AccumulatorItem = Line<P::EA>;
@@ -86,3 +90,37 @@ impl<P: ReducePrecision> ReduceInstruction<P> for Prod {
         Line::cast_from(accumulator)
     }
 }
+
+#[cube]
+impl<P: ReducePrecision> ReduceOp<P> for Prod {
+    type Accumulator = Line<P::EA>;
+    type SharedAccumulator = SharedMemory<Line<P::EA>>;
+
+    fn identity(#[comptime] line_size: u32) -> Self::Accumulator {
+        Line::empty(line_size).fill(P::EA::from_int(1))
+    }
+
+    fn neutral_input(#[comptime] line_size: u32) -> Line<P::EI> {
+        Line::empty(line_size).fill(P::EI::from_int(1))
+    }
+
+    fn map(item: Line<P::EI>) -> Self::Accumulator {
+        Line::cast_from(item)
+    }
+
+    fn combine(lhs: Self::Accumulator, rhs: Self::Accumulator) -> Self::Accumulator {
+        lhs * rhs
+    }
+
+    fn plane_combine(accumulator: Self::Accumulator) -> Self::Accumulator {
+        plane_prod(accumulator)
+    }
+
+    fn assign(destination: &mut Self::Accumulator, source: &Self::Accumulator) {
+        *destination = *source;
+    }
+
+    fn finalize<Out: Numeric>(accumulator: Self::Accumulator) -> Line<Out> {
+        Line::cast_from(accumulator)
+    }
+}
@@ -3,7 +3,9 @@ use cubecl_core::prelude::*;
 
 use crate::precision::ReducePrecision;
 
-use super::{ReduceCoordinate, ReduceFamily, ReduceInstruction, ReduceRequirements};
+use super::{
+    ReduceCoordinate, ReduceFamily, ReduceInstruction, ReduceOp, ReduceOpFamily, ReduceRequirements,
+};
 
 #[derive(Debug, CubeType, Clone)]
 pub struct Sum {}
@@ -13,6 +15,10 @@ impl ReduceFamily for Sum {
     type Config = ();
 }
 
+impl ReduceOpFamily for Sum {
+    type Op<P: ReducePrecision> = Self;
+}
+
 #[cube]
 impl<P: ReducePrecision> ReduceInstruction<P> for Sum {
     type AccumulatorItem = Line<P::EA>;
@@ -85,3 +91,37 @@ impl<P: ReducePrecision> ReduceInstruction<P> for Sum {
         Line::cast_from(accumulator)
     }
 }
+
+#[cube]
+impl<P: ReducePrecision> ReduceOp<P> for Sum {
+    type Accumulator = Line<P::EA>;
+    type SharedAccumulator = SharedMemory<Line<P::EA>>;
+
+    fn identity(#[comptime] line_size: u32) -> Self::Accumulator {
+        Line::empty(line_size).fill(P::EA::from_int(0))
+    }
+
+    fn neutral_input(#[comptime] line_size: u32) -> Line<P::EI> {
+        Line::empty(line_size).fill(P::EI::from_int(0))
+    }
+
+    fn map(item: Line<P::EI>) -> Self::Accumulator {
+        Line::cast_from(item)
+    }
+
+    fn combine(lhs: Self::Accumulator, rhs: Self::Accumulator) -> Self::Accumulator {
+        lhs + rhs
+    }
+
+    fn plane_combine(accumulator: Self::Accumulator) -> Self::Accumulator {
+        plane_sum(accumulator)
+    }
+
+    fn assign(destination: &mut Self::Accumulator, source: &Self::Accumulator) {
+        *destination = *source;
+    }
+
+    fn finalize<Out: Numeric>(accumulator: Self::Accumulator) -> Line<Out> {
+        Line::cast_from(accumulator)
+    }
+}
@@ -18,3 +18,10 @@ pub(crate) fn lowest_coordinate_matching<E: CubePrimitive>(
     );
     plane_min(candidate_coordinate)
 }
+
+// Replace the NaNs of the item by the null input, so they never win a comparison
+// and never poison a plane reduction.
+#[cube]
+pub(crate) fn replace_nan<E: CubePrimitive>(item: Line<E>, null_input: Line<E>) -> Line<E> {
+    select_many(item.not_equal(item), null_input, item)
+}
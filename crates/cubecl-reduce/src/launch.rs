@@ -37,6 +37,7 @@ pub(crate) fn launch_reduce<Run: Runtime, P: ReducePrecision, Out: Numeric, Rd:
         line_mode: config.line_mode,
         bound_checks: config.bound_checks,
         bound_checks_inner: config.bound_checks_inner,
+        rows_per_plane: config.rows_per_plane,
     };
     unsafe {
         reduce_kernel::launch_unchecked::<P::EI, Out, P::EA, Rd, TensorArgs, Run>(
@@ -61,6 +62,7 @@ pub struct ReduceParams {
     pub line_mode: LineMode,
     pub bound_checks: bool,
     pub bound_checks_inner: BoundChecksInner,
+    pub rows_per_plane: u32, // Only more than 1 when planes reduce short rows without shared memory.
 }
 
 #[cube(launch_unchecked)]
@@ -83,11 +85,20 @@ pub fn reduce_kernel_virtual<In: Numeric, Out: Numeric, Acc: Numeric, R: ReduceF
     #[comptime] params: ReduceParams,
     #[comptime] config: R::Config,
 ) {
-    let reduce_index = get_reduce_index(params);
+    let mut reduce_index = get_reduce_index(params);
+    let mut write_output = true.runtime();
 
-    #[allow(clippy::collapsible_if)]
     if comptime![params.bound_checks] {
-        if reduce_index >= get_reduce_count(output.len() * params.line_size_output, params) {
+        let reduce_count = get_reduce_count(output.len() * params.line_size_output, params);
+        if comptime![params.rows_per_plane > 1] {
+            // The segments of a plane take part in the collectives of all its rows,
+            // so a segment past the last row reduces the last row again without writing it.
+            if reduce_index - reduce_index % params.rows_per_plane >= reduce_count {
+                terminate!();
+            }
+            write_output = reduce_index < reduce_count;
+            reduce_index = Min::min(reduce_index, reduce_count - 1);
+        } else if reduce_index >= reduce_count {
             terminate!();
         }
     }
@@ -97,6 +108,7 @@ pub fn reduce_kernel_virtual<In: Numeric, Out: Numeric, Acc: Numeric, R: ReduceF
         output,
         axis_reduce,
         reduce_index,
+        write_output,
         params,
         config,
     )
@@ -108,6 +120,7 @@ fn reduce_kernel_inner<P: ReducePrecision, Out: Numeric, R: ReduceFamily>(
     output: &mut VirtualTensor<Out, ReadWrite>,
     axis_reduce: u32,
     reduce_index: u32,
+    write_output: bool,
     #[comptime] params: ReduceParams,
     #[comptime] config: R::Config,
 ) {
@@ -129,14 +142,27 @@ fn reduce_kernel_inner<P: ReducePrecision, Out: Numeric, R: ReduceFamily>(
             sync_cube();
             reduce_tree::<P, R::Instruction<P>>(inst, &mut accumulator, accumulator_size)
         }
-        (None, true) => reduce_slice_plane::<P, VirtualTensor<P::EI>, R::Instruction<P>>(
-            input,
-            inst,
-            range,
-            params.line_size_input,
-            params.line_mode,
-            params.bound_checks_inner,
-        ),
+        (None, true) => {
+            if comptime![params.rows_per_plane > 1] {
+                reduce_slice_plane_rows::<P, VirtualTensor<P::EI>, R::Instruction<P>>(
+                    input,
+                    inst,
+                    range,
+                    params.rows_per_plane,
+                    params.line_size_input,
+                    params.bound_checks_inner,
+                )
+            } else {
+                reduce_slice_plane::<P, VirtualTensor<P::EI>, R::Instruction<P>>(
+                    input,
+                    inst,
+                    range,
+                    params.line_size_input,
+                    params.line_mode,
+                    params.bound_checks_inner,
+                )
+            }
+        }
         (None, false) => reduce_slice::<P, VirtualTensor<P::EI>, R::Instruction<P>>(
             input,
             range,
@@ -146,7 +172,7 @@ fn reduce_kernel_inner<P: ReducePrecision, Out: Numeric, R: ReduceFamily>(
         ),
     };
 
-    if elected_writer(params) {
+    if elected_writer(params) && write_output {
         write_to_output::<P, Out, R::Instruction<P>>(
             output,
             accumulator,
@@ -163,7 +189,12 @@ fn get_reduce_index(#[comptime] params: ReduceParams) -> u32 {
     if params.shared.is_some() {
         CUBE_POS
     } else if params.use_planes {
-        CUBE_POS * CUBE_DIM_Y + UNIT_POS_Y
+        if comptime![params.rows_per_plane > 1] {
+            let segment = UNIT_POS_X / (CUBE_DIM_X / params.rows_per_plane);
+            (CUBE_POS * CUBE_DIM_Y + UNIT_POS_Y) * params.rows_per_plane + segment
+        } else {
+            CUBE_POS * CUBE_DIM_Y + UNIT_POS_Y
+        }
     } else {
         ABSOLUTE_POS
     }
@@ -182,7 +213,11 @@ fn elected_writer(#[comptime] settings: ReduceParams) -> bool {
     if settings.shared.is_some() {
         UNIT_POS == 0
     } else if settings.use_planes {
-        UNIT_POS_X == 0
+        if comptime![settings.rows_per_plane > 1] {
+            UNIT_POS_X % (CUBE_DIM_X / settings.rows_per_plane) == 0
+        } else {
+            UNIT_POS_X == 0
+        }
     } else {
         true.runtime()
     }
@@ -8,6 +8,17 @@
 //! This crate provides a main entrypoint as the [`reduce`] function which allows to automatically
 //! perform a reduction for a given instruction implementing the [`ReduceInstruction`] trait and a given [`ReduceStrategy`].
 //! It also provides implementation of the [`ReduceInstruction`] trait for common operations in the [`instructions`] module.
+//! Custom reductions only defining how their accumulators are combined implement the simpler [`ReduceOp`] trait
+//! and are reduced by the [`reduce_custom`] function.
+//! Multiple axes can be reduced at once with the [`reduce_axes`] function,
+//! the mean and variance of an axis are computed together by the [`mean_var`] function,
+//! the rows of a tensor are normalized by the fused [`softmax`], [`layer_norm`] and [`rms_norm`] kernels,
+//! the prefixes of all the elements of a tensor are computed by the [`scan`] function,
+//! the running totals of each row along an axis by the [`cumsum`] function,
+//! keys are sorted on the device by the [`radix_sort`] function built on top of it,
+//! the largest values of each row are selected by the [`topk`] function,
+//! the segments delimited by an array of offsets are reduced by the [`segmented_reduce`] function,
+//! and the values of a tensor are counted in bins by the [`histogram`] function.
 //! Finally, it provides many reusable primitives to perform different general reduction algorithms in the [`primitives`] module.
 
 pub mod args;
@@ -15,20 +26,43 @@ pub mod instructions;
 pub mod primitives;
 pub mod tune_key;
 
+mod axes;
 mod config;
+mod cumsum;
 mod error;
+mod histogram;
 mod launch;
+mod mean_var;
+mod norm;
 mod precision;
+mod scan;
+mod scatter;
+mod segmented;
 mod shared_sum;
+mod softmax;
+mod sort;
 mod strategy;
+mod topk;
 
+pub use axes::*;
 pub use config::*;
+pub use cumsum::*;
 pub use error::*;
+pub use histogram::*;
 pub use instructions::ReduceFamily;
 pub use instructions::ReduceInstruction;
+pub use instructions::{ReduceOp, ReduceOpFamily};
+pub use mean_var::*;
+pub use norm::*;
 pub use precision::ReducePrecision;
+pub use scan::*;
+pub use scatter::*;
+pub use segmented::*;
 pub use shared_sum::*;
+pub use softmax::*;
+pub use sort::*;
 pub use strategy::*;
+pub use topk::*;
 
 use launch::*;
 
@@ -43,7 +77,7 @@ use cubecl_core::prelude::*;
 /// Reduce the given `axis` of the `input` tensor using the instruction `Inst` and write the result into `output`.
 ///
 /// An optional [`ReduceStrategy`] can be provided to force the reduction to use a specific algorithm. If omitted, a best effort
-/// is done to try and pick the best strategy supported for the provided `client`.
+/// is done to try and pick the best strategy supported for the provided `client` and `input` with [`ReduceStrategy::for_input`].
 ///
 /// Return an error if `strategy` is `Some(strategy)` and the specified strategy is not supported by the `client`.
 /// Also returns an error if the `axis` is larger than the `input` rank or if the shape of `output` is invalid.
@@ -101,19 +135,10 @@ pub fn reduce<R: Runtime, P: ReducePrecision, Out: Numeric, Inst: ReduceFamily>(
     strategy: Option<ReduceStrategy>,
     inst_config: Inst::Config,
 ) -> Result<(), ReduceError> {
-    validate_axis(input.shape.len(), axis)?;
-    valid_output_shape(input.shape, output.shape, axis)?;
-    let strategy = strategy
-        .map(|s| s.validate::<R>(client))
-        .unwrap_or(Ok(ReduceStrategy::new::<R>(client, true)))?;
-    let config = ReduceConfig::generate::<R, P::EI>(client, &input, &output, axis, &strategy);
-
-    if let CubeCount::Static(x, y, z) = config.cube_count {
-        let (max_x, max_y, max_z) = R::max_cube_count();
-        if x > max_x || y > max_y || z > max_z {
-            return Err(ReduceError::CubeCountTooLarge);
-        }
-    }
+    let Some((config, strategy)) = reduce_config::<R, P>(client, &input, &output, axis, strategy)?
+    else {
+        return Ok(());
+    };
 
     launch_reduce::<R, P, Out, Inst>(
         client,
@@ -127,9 +152,74 @@ pub fn reduce<R: Runtime, P: ReducePrecision, Out: Numeric, Inst: ReduceFamily>(
     Ok(())
 }
 
+/// Reduce the given `axis` of the `input` tensor with the [`ReduceOp`] of `Op` and write the result into `output`.
+///
+/// This supports the same strategies and returns the same errors as [`reduce`], with the [`CustomReduce`](instructions::CustomReduce)
+/// instruction of `Op`. The lanes of the accumulators are never combined with each other, so the lines
+/// are only used when the reduced axis isn't contiguous.
+///
+/// # Example
+///
+/// This sums the rows of a matrix with the [`ReduceOp`] implementation of [`Sum`](instructions::Sum).
+///
+/// ```ignore
+/// use cubecl_reduce::instructions::Sum;
+///
+/// let result = reduce_custom::<R, f32, f32, Sum>(&client, input, output, 0, None);
+/// ```
+pub fn reduce_custom<R: Runtime, P: ReducePrecision, Out: Numeric, Op: ReduceOpFamily>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    axis: usize,
+    strategy: Option<ReduceStrategy>,
+) -> Result<(), ReduceError> {
+    let Some((mut config, strategy)) =
+        reduce_config::<R, P>(client, &input, &output, axis, strategy)?
+    else {
+        return Ok(());
+    };
+    if config.line_mode == LineMode::Parallel {
+        config.line_size_input = 1;
+    }
+
+    launch_reduce::<R, P, Out, instructions::CustomReduce<Op>>(
+        client,
+        input,
+        output,
+        axis as u32,
+        config,
+        strategy,
+        (),
+    );
+    Ok(())
+}
+
+// Validate the arguments of a reduction and pick its strategy and launch configuration.
+// An empty output leaves nothing to launch, so it has no configuration.
+fn reduce_config<R: Runtime, P: ReducePrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    axis: usize,
+    strategy: Option<ReduceStrategy>,
+) -> Result<Option<(ReduceConfig, ReduceStrategy)>, ReduceError> {
+    validate_axis(input.shape.len(), axis)?;
+    valid_output_shape(input.shape, output.shape, axis)?;
+    if output.shape.contains(&0) {
+        return Ok(None);
+    }
+    let strategy = strategy
+        .map(|s| s.validate::<R>(client))
+        .unwrap_or(Ok(ReduceStrategy::for_input::<R>(client, input, axis)))?;
+    let config = ReduceConfig::generate::<R, P::EI>(client, input, output, axis, &strategy)?;
+
+    Ok(Some((config, strategy)))
+}
+
 // Check that the given axis is less than the rank of the input.
 fn validate_axis(rank: usize, axis: usize) -> Result<(), ReduceError> {
-    if axis > rank {
+    if axis >= rank {
         return Err(ReduceError::InvalidAxis { axis, rank });
     }
     Ok(())
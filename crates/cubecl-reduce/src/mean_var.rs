@@ -0,0 +1,358 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape};
+use cubecl_runtime::Plane;
+use cubecl_std::tensor::TensorHandle;
+
+use crate::ReduceError;
+use crate::precision::ReducePrecision;
+
+/// The number of units in a cube reducing a partition.
+const CUBE_SIZE: u32 = 256;
+/// The minimum number of elements reduced by each unit before a reduction is split over multiple cubes.
+const ELEMENTS_PER_UNIT: usize = 8;
+/// The number of cubes to launch when the reduction is split over multiple cubes.
+const TARGET_CUBE_COUNT: usize = 1024;
+
+/// Compute the mean and the variance of the given `axis` of the `input` tensor in a single read of the tensor.
+///
+/// The statistics are accumulated with Welford's algorithm in `P::EA`, e.g. in `f32` for `f16` inputs,
+/// which stays accurate for data with a large mean compared to its deviation,
+/// unlike computing `E[x²] - E[x]²`.
+/// When `unbiased` is true, the variance is divided by `n - 1` instead of `n`.
+///
+/// Return the mean and the variance, both with the shape of `input` except for a value of 1 for the given `axis`.
+/// Return an error if the `axis` is larger than the `input` rank.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let input = /* a tensor of shape [batch, features] */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let (mean, var) = mean_var::<R, f32>(&client, input, 1, true)?;
+/// ```
+pub fn mean_var<R: Runtime, P: ReducePrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    axis: usize,
+    unbiased: bool,
+) -> Result<(TensorHandle<R, P::EI>, TensorHandle<R, P::EI>), ReduceError>
+where
+    P::EI: Float,
+    P::EA: Float,
+{
+    let rank = input.shape.len();
+    if axis >= rank {
+        return Err(ReduceError::InvalidAxis { axis, rank });
+    }
+
+    let mut output_shape = input.shape.to_vec();
+    output_shape[axis] = 1;
+    let mean = TensorHandle::<R, P::EI>::empty(client, output_shape.clone());
+    let variance = TensorHandle::<R, P::EI>::empty(client, output_shape);
+
+    let length = input.shape[axis];
+    let num_rows = mean.shape.iter().product::<usize>();
+    let splits = if num_rows >= TARGET_CUBE_COUNT {
+        1
+    } else {
+        let max_splits = length.div_ceil(CUBE_SIZE as usize * ELEMENTS_PER_UNIT);
+        TARGET_CUBE_COUNT.div_ceil(num_rows).min(max_splits).max(1)
+    };
+    let num_partitions = num_rows * splits;
+
+    let hw_props = &client.properties().hardware;
+    let use_planes = client.properties().features.plane.contains(Plane::Ops)
+        && hw_props.plane_size_min == hw_props.plane_size_max
+        && CUBE_SIZE % hw_props.plane_size_max == 0;
+    let (cube_dim, accumulator_size) = match use_planes {
+        true => {
+            let plane_size = hw_props.plane_size_max;
+            let num_planes = CUBE_SIZE / plane_size;
+            (CubeDim::new_2d(plane_size, num_planes), num_planes)
+        }
+        false => (CubeDim::new_1d(CUBE_SIZE), CUBE_SIZE),
+    };
+    let partition_cube_count = LaunchShape::elementwise(num_partitions)
+        .units_per_cube(1)
+        .build::<R>()?
+        .cube_count;
+    let finalize = LaunchShape::elementwise(num_rows).build::<R>()?;
+
+    let partial_size = num_partitions * size_of::<P::EA>();
+    let partial_count = client.empty(partial_size);
+    let partial_mean = client.empty(partial_size);
+    let partial_m2 = client.empty(partial_size);
+
+    unsafe {
+        welford_partition_kernel::launch_unchecked::<P::EI, P::EA, R>(
+            client,
+            partition_cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            ArrayArg::from_raw_parts::<P::EA>(&partial_count, num_partitions, 1),
+            ArrayArg::from_raw_parts::<P::EA>(&partial_mean, num_partitions, 1),
+            ArrayArg::from_raw_parts::<P::EA>(&partial_m2, num_partitions, 1),
+            ScalarArg::new(axis as u32),
+            ScalarArg::new(splits as u32),
+            WelfordParams {
+                use_planes,
+                accumulator_size,
+            },
+        );
+
+        welford_finalize_kernel::launch_unchecked::<P::EI, P::EA, R>(
+            client,
+            finalize.cube_count,
+            finalize.cube_dim,
+            ArrayArg::from_raw_parts::<P::EA>(&partial_count, num_partitions, 1),
+            ArrayArg::from_raw_parts::<P::EA>(&partial_mean, num_partitions, 1),
+            ArrayArg::from_raw_parts::<P::EA>(&partial_m2, num_partitions, 1),
+            mean.as_ref().as_tensor_arg(1),
+            variance.as_ref().as_tensor_arg(1),
+            ScalarArg::new(splits as u32),
+            unbiased,
+        );
+    }
+
+    Ok((mean, variance))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WelfordParams {
+    pub use_planes: bool,
+    /// The number of partial states combined within a cube, one per plane or one per unit.
+    pub accumulator_size: u32,
+}
+
+/// The count, mean and sum of squared deviations from the mean (`M2`) of a partition of the reduced elements.
+///
+/// An empty partition has all its fields equal to 0.
+#[derive(CubeType)]
+pub struct WelfordState<F: Float> {
+    pub count: F,
+    pub mean: F,
+    pub m2: F,
+}
+
+#[cube]
+impl<F: Float> WelfordState<F> {
+    pub fn empty() -> WelfordState<F> {
+        WelfordState::<F> {
+            count: F::new(0.0),
+            mean: F::new(0.0),
+            m2: F::new(0.0),
+        }
+    }
+
+    /// Add a single element to the partition.
+    pub fn update(&mut self, value: F) {
+        self.count += F::new(1.0);
+        let delta = value - self.mean;
+        self.mean += delta / self.count;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// The state of the union of two partitions with the parallel formula of Chan et al.
+    ///
+    /// Either partition can be empty: its weight is then 0 and the other state is returned unchanged.
+    pub fn combine(lhs: &WelfordState<F>, rhs: &WelfordState<F>) -> WelfordState<F> {
+        let count = lhs.count + rhs.count;
+        let weight = rhs.count / select(count > F::new(0.0), count, F::new(1.0));
+        let delta = rhs.mean - lhs.mean;
+
+        WelfordState::<F> {
+            count,
+            mean: lhs.mean + delta * weight,
+            m2: lhs.m2 + rhs.m2 + delta * delta * lhs.count * weight,
+        }
+    }
+
+    /// The state of the union of the partitions of all the units in the plane.
+    ///
+    /// The means are shifted by the mean of the first unit before being summed, and the deviations
+    /// are taken from the mean of the plane, so they stay small regardless of the mean.
+    pub fn combine_plane(this: &WelfordState<F>) -> WelfordState<F> {
+        let count = plane_sum(this.count);
+        let shift = plane_broadcast(this.mean, 0);
+        let mean = shift
+            + plane_sum(this.count * (this.mean - shift))
+                / select(count > F::new(0.0), count, F::new(1.0));
+        let delta = this.mean - mean;
+        let m2 = plane_sum(this.m2 + this.count * delta * delta);
+
+        WelfordState::<F> { count, mean, m2 }
+    }
+
+    /// The state of the union of the partitions of all the units in the cube, returned to every unit.
+    ///
+    /// The states are combined within each plane with [`combine_plane`](Self::combine_plane) when `use_planes`
+    /// is true, then the `size` states of the planes or of the units are combined in shared memory.
+    pub fn combine_cube(
+        this: &WelfordState<F>,
+        #[comptime] use_planes: bool,
+        #[comptime] size: u32,
+    ) -> WelfordState<F> {
+        // With planes, the first unit of each plane holds the state of its plane.
+        let state = match comptime!(use_planes) {
+            true => WelfordState::<F>::combine_plane(this),
+            false => WelfordState::<F> {
+                count: this.count,
+                mean: this.mean,
+                m2: this.m2,
+            },
+        };
+        let index = match comptime!(use_planes) {
+            true => UNIT_POS_Y,
+            false => UNIT_POS,
+        };
+        let is_writer = match comptime!(use_planes) {
+            true => UNIT_POS_X == 0,
+            false => true.runtime(),
+        };
+
+        let mut counts = SharedMemory::<F>::new(size);
+        let mut means = SharedMemory::<F>::new(size);
+        let mut m2s = SharedMemory::<F>::new(size);
+        if is_writer {
+            counts[index] = state.count;
+            means[index] = state.mean;
+            m2s[index] = state.m2;
+        }
+        sync_cube();
+
+        // Fuse the upper half of the states into the lower half until a single state remains.
+        let mut stride = comptime!(size.next_power_of_two() / 2).runtime();
+        while stride > 0 {
+            if is_writer && index < stride && index + stride < size {
+                let lhs = WelfordState::<F> {
+                    count: counts[index],
+                    mean: means[index],
+                    m2: m2s[index],
+                };
+                let rhs = WelfordState::<F> {
+                    count: counts[index + stride],
+                    mean: means[index + stride],
+                    m2: m2s[index + stride],
+                };
+                let fused = WelfordState::<F>::combine(&lhs, &rhs);
+                counts[index] = fused.count;
+                means[index] = fused.mean;
+                m2s[index] = fused.m2;
+            }
+            sync_cube();
+            stride /= 2;
+        }
+
+        WelfordState::<F> {
+            count: counts[0],
+            mean: means[0],
+            m2: m2s[0],
+        }
+    }
+}
+
+/// Reduce a partition of the reduced axis for each cube,
+/// where each row of `input` along `axis` is split into `splits` partitions.
+#[cube(launch_unchecked)]
+pub fn welford_partition_kernel<In: Float, Acc: Float>(
+    input: &Tensor<In>,
+    partial_count: &mut Array<Acc>,
+    partial_mean: &mut Array<Acc>,
+    partial_m2: &mut Array<Acc>,
+    axis: u32,
+    splits: u32,
+    #[comptime] params: WelfordParams,
+) {
+    let partition = CUBE_POS;
+    if partition >= partial_count.len() {
+        terminate!();
+    }
+
+    let row = partition / splits;
+    let split = partition % splits;
+
+    // The offset of the first element of the row, iterating over the other axes in row-major order.
+    let mut offset = 0;
+    let mut remainder = row;
+    let rank = input.rank();
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        if dim != axis {
+            offset += (remainder % input.shape(dim)) * input.stride(dim);
+            remainder /= input.shape(dim);
+        }
+    }
+
+    let length = input.shape(axis);
+    let chunk = (length + splits - 1) / splits;
+    let start = split * chunk;
+    let end = Min::min(start + chunk, length);
+
+    let mut state = WelfordState::<Acc>::empty();
+    for i in range_stepped(start + UNIT_POS, end, CUBE_DIM) {
+        state.update(Acc::cast_from(input[offset + i * input.stride(axis)]));
+    }
+
+    let state =
+        WelfordState::<Acc>::combine_cube(&state, params.use_planes, params.accumulator_size);
+    if UNIT_POS == 0 {
+        partial_count[partition] = state.count;
+        partial_mean[partition] = state.mean;
+        partial_m2[partition] = state.m2;
+    }
+}
+
+/// Combine the partitions of each row and write its mean and variance.
+#[cube(launch_unchecked)]
+pub fn welford_finalize_kernel<Out: Float, Acc: Float>(
+    partial_count: &Array<Acc>,
+    partial_mean: &Array<Acc>,
+    partial_m2: &Array<Acc>,
+    mean: &mut Tensor<Out>,
+    variance: &mut Tensor<Out>,
+    splits: u32,
+    #[comptime] unbiased: bool,
+) {
+    let row = ABSOLUTE_POS;
+    let num_rows = partial_count.len() / splits;
+    if row >= num_rows {
+        terminate!();
+    }
+
+    let mut state = WelfordState::<Acc>::empty();
+    for split in 0..splits {
+        let partition = row * splits + split;
+        let partial = WelfordState::<Acc> {
+            count: partial_count[partition],
+            mean: partial_mean[partition],
+            m2: partial_m2[partition],
+        };
+        let fused = WelfordState::<Acc>::combine(&state, &partial);
+        state.count = fused.count;
+        state.mean = fused.mean;
+        state.m2 = fused.m2;
+    }
+
+    // The rows are numbered in row-major order, whatever the strides of the outputs.
+    let mut mean_offset = 0;
+    let mut variance_offset = 0;
+    let mut remainder = row;
+    let rank = mean.rank();
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        let coordinate = remainder % mean.shape(dim);
+        mean_offset += coordinate * mean.stride(dim);
+        variance_offset += coordinate * variance.stride(dim);
+        remainder /= mean.shape(dim);
+    }
+
+    let divisor = if comptime!(unbiased) {
+        state.count - Acc::new(1.0)
+    } else {
+        state.count
+    };
+    mean[mean_offset] = Out::cast_from(state.mean);
+    variance[variance_offset] = Out::cast_from(state.m2 / divisor);
+}
@@ -0,0 +1,340 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape, tensor_line_size_parallel};
+use cubecl_runtime::Plane;
+use cubecl_std::{CubeOption, CubeOptionArgs, CubeOptionExpand};
+
+use crate::precision::ReducePrecision;
+use crate::softmax::{row_offset, validate_shape};
+use crate::{ReduceError, WelfordState};
+
+/// The maximum number of units in a cube normalizing rows.
+const MAX_CUBE_SIZE: u32 = 256;
+/// The most lines read by each unit of a plane for a row to be normalized by a single plane,
+/// so a cube normalizes multiple short rows at once.
+const MAX_LINES_PER_PLANE_UNIT: usize = 4;
+/// The most elements of the rows kept in shared memory by a cube, so it doesn't occupy a whole multiprocessor.
+const MAX_RESIDENT_LENGTH: usize = 8192;
+
+/// Normalize each row of the `input` tensor along its last axis to a zero mean and a unit variance,
+/// then scale it by `gamma` and shift it by `beta`, and write it into `output`.
+///
+/// The output is `(x - mean) / sqrt(var + epsilon) * gamma + beta` with the biased variance of the row.
+/// Without `gamma`, the row isn't scaled, and without `beta`, it isn't shifted.
+///
+/// Each row is normalized in one kernel: the row is read once to compute its statistics with Welford's algorithm,
+/// then normalized. The row is kept in shared memory between both steps when it fits, else it is read again
+/// from global memory. Short rows are normalized by a single plane, so a cube normalizes multiple rows.
+/// The math is performed in `P::EA`, e.g. in `f32` for `f16` inputs.
+///
+/// Return an error if the `input` is a scalar, if the shape of `output` differs from the shape of `input`,
+/// or if `gamma` or `beta` aren't vectors of the length of the rows.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let hidden = /* a tensor of shape [batch, seq, dim] */;
+/// let normalized = /* a tensor of the same shape */;
+/// let (gamma, beta) = /* two tensors of shape [dim] */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// layer_norm::<R, half::f16>(&client, hidden, normalized, Some(gamma), Some(beta), 1e-5)?;
+/// ```
+pub fn layer_norm<R: Runtime, P: ReducePrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    gamma: Option<TensorHandleRef<R>>,
+    beta: Option<TensorHandleRef<R>>,
+    epsilon: f32,
+) -> Result<(), ReduceError>
+where
+    P::EI: Float,
+    P::EA: Float,
+{
+    launch_norm::<R, P>(
+        client,
+        input,
+        output,
+        gamma,
+        beta,
+        epsilon,
+        NormKind::LayerNorm,
+    )
+}
+
+/// Divide each row of the `input` tensor along its last axis by its root mean square,
+/// then scale it by `gamma`, and write it into `output`.
+///
+/// The output is `x / sqrt(mean(x²) + epsilon) * gamma`, and the row isn't scaled without `gamma`.
+/// The rows are normalized like in [`layer_norm`].
+///
+/// Return an error if the `input` is a scalar, if the shape of `output` differs from the shape of `input`,
+/// or if `gamma` isn't a vector of the length of the rows.
+pub fn rms_norm<R: Runtime, P: ReducePrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    gamma: Option<TensorHandleRef<R>>,
+    epsilon: f32,
+) -> Result<(), ReduceError>
+where
+    P::EI: Float,
+    P::EA: Float,
+{
+    launch_norm::<R, P>(
+        client,
+        input,
+        output,
+        gamma,
+        None,
+        epsilon,
+        NormKind::RmsNorm,
+    )
+}
+
+fn launch_norm<R: Runtime, P: ReducePrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    gamma: Option<TensorHandleRef<R>>,
+    beta: Option<TensorHandleRef<R>>,
+    epsilon: f32,
+    kind: NormKind,
+) -> Result<(), ReduceError>
+where
+    P::EI: Float,
+    P::EA: Float,
+{
+    let rank = input.shape.len();
+    if rank == 0 {
+        return Err(ReduceError::InvalidAxis { axis: 0, rank });
+    }
+    validate_shape(input.shape, output.shape)?;
+    let length = input.shape[rank - 1];
+    for param in [&gamma, &beta].into_iter().flatten() {
+        validate_shape(&[length], param.shape)?;
+    }
+
+    let num_rows = input.shape[..rank - 1].iter().product::<usize>();
+    if num_rows * length == 0 {
+        return Ok(());
+    }
+
+    // All the tensors are read and written with the same lines along the rows.
+    let elem = P::EI::as_type_native_unchecked();
+    let line_size = [Some(&input), Some(&output), gamma.as_ref(), beta.as_ref()]
+        .into_iter()
+        .flatten()
+        .map(|tensor| {
+            tensor_line_size_parallel(
+                R::line_size_type(&elem),
+                tensor.shape,
+                tensor.strides,
+                tensor.shape.len() - 1,
+            )
+        })
+        .min()
+        .unwrap_or(1);
+    let num_lines = length / line_size as usize;
+
+    let hw_props = &client.properties().hardware;
+    let plane_size = hw_props.plane_size_max;
+    let planes_available = client.properties().features.plane.contains(Plane::Ops)
+        && hw_props.plane_size_min == hw_props.plane_size_max;
+    let plane_rows =
+        planes_available && num_lines <= plane_size as usize * MAX_LINES_PER_PLANE_UNIT;
+    let (cube_dim, rows_per_cube, use_planes, accumulator_size) = match plane_rows {
+        true => {
+            let rows_per_cube = (MAX_CUBE_SIZE / plane_size).max(1);
+            (
+                CubeDim::new_2d(plane_size, rows_per_cube),
+                rows_per_cube,
+                true,
+                1,
+            )
+        }
+        false => {
+            let cube_size = (num_lines as u32)
+                .next_power_of_two()
+                .clamp(plane_size, MAX_CUBE_SIZE.max(plane_size));
+            match planes_available && cube_size % plane_size == 0 {
+                true => (
+                    CubeDim::new_2d(plane_size, cube_size / plane_size),
+                    1,
+                    true,
+                    cube_size / plane_size,
+                ),
+                false => (CubeDim::new_1d(cube_size), 1, false, cube_size),
+            }
+        }
+    };
+
+    let capacity = num_lines.next_power_of_two();
+    let cached_length = rows_per_cube as usize * capacity * line_size as usize;
+    let shared_size = (cached_length + 3 * accumulator_size as usize) * size_of::<P::EA>();
+    let resident = (cached_length <= MAX_RESIDENT_LENGTH
+        && shared_size <= hw_props.max_shared_memory_size)
+        .then_some(capacity as u32);
+
+    let cube_count = LaunchShape::elementwise(num_rows.div_ceil(rows_per_cube as usize))
+        .units_per_cube(1)
+        .build::<R>()?
+        .cube_count;
+
+    let gamma = match &gamma {
+        Some(gamma) => CubeOptionArgs::Some(gamma.as_tensor_arg(line_size)),
+        None => CubeOptionArgs::None,
+    };
+    let beta = match &beta {
+        Some(beta) => CubeOptionArgs::Some(beta.as_tensor_arg(line_size)),
+        None => CubeOptionArgs::None,
+    };
+
+    unsafe {
+        norm_kernel::launch_unchecked::<P::EI, P::EA, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(line_size),
+            output.as_tensor_arg(line_size),
+            gamma,
+            beta,
+            ScalarArg::new(P::EA::new(epsilon)),
+            ScalarArg::new(num_rows as u32),
+            NormParams {
+                kind,
+                line_size: line_size as u32,
+                plane_rows,
+                rows_per_cube,
+                use_planes,
+                accumulator_size,
+                resident,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// The statistics a row is normalized by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NormKind {
+    /// Subtract the mean and divide by the standard deviation.
+    LayerNorm,
+    /// Divide by the root mean square.
+    RmsNorm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NormParams {
+    pub kind: NormKind,
+    pub line_size: u32,
+    /// Each plane normalizes its own row, else the whole cube normalizes a single row.
+    pub plane_rows: bool,
+    pub rows_per_cube: u32,
+    pub use_planes: bool,
+    /// The number of partial states combined within a cube normalizing a single row, one per plane or one per unit.
+    pub accumulator_size: u32,
+    /// Each row is kept in a shared memory of the given number of lines between computing its statistics
+    /// and normalizing it. Else, the row is read again from global memory.
+    pub resident: Option<u32>,
+}
+
+/// Normalize the rows of `input` along its last axis, one row for each cube or for each plane of a cube.
+#[cube(launch_unchecked)]
+pub fn norm_kernel<In: Float, Acc: Float>(
+    input: &Tensor<Line<In>>,
+    output: &mut Tensor<Line<In>>,
+    gamma: &CubeOption<Tensor<Line<In>>>,
+    beta: &CubeOption<Tensor<Line<In>>>,
+    epsilon: Acc,
+    num_rows: u32,
+    #[comptime] params: NormParams,
+) {
+    let line_size = params.line_size;
+    let row = match comptime!(params.plane_rows) {
+        true => CUBE_POS * params.rows_per_cube + UNIT_POS_Y,
+        false => CUBE_POS,
+    };
+    if row >= num_rows {
+        terminate!();
+    }
+    let unit = match comptime!(params.plane_rows) {
+        true => UNIT_POS_X,
+        false => UNIT_POS,
+    };
+    let num_units = match comptime!(params.plane_rows) {
+        true => CUBE_DIM_X,
+        false => CUBE_DIM,
+    };
+
+    let axis = input.rank() - 1;
+    let num_lines = input.shape(axis) / line_size;
+    let input_offset = row_offset(input, row);
+    let output_offset = row_offset(output, row);
+
+    // Each unit reads back the lines it cached, so the cache needs no synchronization.
+    let capacity = comptime!(params.resident.unwrap_or(1));
+    let mut cache =
+        SharedMemory::<Acc>::new_lined(comptime!(params.rows_per_cube * capacity), line_size);
+    let cache_offset = match comptime!(params.plane_rows) {
+        true => UNIT_POS_Y * capacity,
+        false => 0,
+    };
+
+    let mut state = WelfordState::<Acc>::empty();
+    for i in range_stepped(unit, num_lines, num_units) {
+        let line = Line::<Acc>::cast_from(
+            input[(input_offset + i * line_size * input.stride(axis)) / line_size],
+        );
+        if comptime!(params.resident.is_some()) {
+            cache[cache_offset + i] = line;
+        }
+        #[unroll]
+        for k in 0..line_size {
+            state.update(line[k]);
+        }
+    }
+
+    let state = match comptime!(params.plane_rows) {
+        true => WelfordState::<Acc>::combine_plane(&state),
+        false => {
+            WelfordState::<Acc>::combine_cube(&state, params.use_planes, params.accumulator_size)
+        }
+    };
+    let variance = state.m2 / state.count;
+    let mut shift = Acc::new(0.0);
+    let mut second_moment = variance;
+    if comptime!(params.kind == NormKind::LayerNorm) {
+        shift = state.mean;
+    } else {
+        // The mean square adds two non-negative terms, so it's as accurate as the variance.
+        second_moment = variance + state.mean * state.mean;
+    }
+    let shift = Line::empty(line_size).fill(shift);
+    let scale = Line::empty(line_size).fill(Acc::new(1.0) / Sqrt::sqrt(second_moment + epsilon));
+
+    for i in range_stepped(unit, num_lines, num_units) {
+        let line = match comptime!(params.resident) {
+            Some(_) => cache[cache_offset + i],
+            None => Line::<Acc>::cast_from(
+                input[(input_offset + i * line_size * input.stride(axis)) / line_size],
+            ),
+        };
+        let normalized = (line - shift) * scale;
+        let normalized = match gamma {
+            CubeOption::Some(gamma) => {
+                normalized * Line::cast_from(gamma[(i * line_size * gamma.stride(0)) / line_size])
+            }
+            CubeOption::None => normalized,
+        };
+        let normalized = match beta {
+            CubeOption::Some(beta) => {
+                normalized + Line::cast_from(beta[(i * line_size * beta.stride(0)) / line_size])
+            }
+            CubeOption::None => normalized,
+        };
+        output[(output_offset + i * line_size * output.stride(axis)) / line_size] =
+            Line::cast_from(normalized);
+    }
+}
@@ -58,7 +58,7 @@ impl ReduceRange {
         let coordinate_step = if params.shared.is_some() {
             CUBE_DIM * params.line_size_input
         } else if params.use_planes {
-            CUBE_DIM_X * params.line_size_input
+            CUBE_DIM_X / params.rows_per_plane * params.line_size_input
         } else {
             params.line_size_input.runtime()
         };
@@ -225,6 +225,85 @@ pub fn reduce_slice_plane<P: ReducePrecision, I: List<Line<P::EI>>, R: ReduceIns
     accumulator
 }
 
+/// Use the segments of an individual plane to reduce `rows_per_plane` contiguous rows of the `items`,
+/// each segment of `CUBE_DIM_X / rows_per_plane` consecutive units reducing its own row with its own `range`.
+///
+/// This is [`reduce_slice_plane`] for rows too short to keep the whole plane busy.
+/// All segments take part in the plane collectives of every row, contributing the null input
+/// to the rows of the other segments, so they must all iterate the same number of times.
+/// Only the accumulator of the row of its segment is kept by each unit.
+///
+/// This assumes the parallel line mode, with `UNIT_POS_X` providing the index of a unit within its plane
+/// and `CUBE_DIM_X` being the plane dimension.
+#[cube]
+pub fn reduce_slice_plane_rows<
+    P: ReducePrecision,
+    I: List<Line<P::EI>>,
+    R: ReduceInstruction<P>,
+>(
+    items: &I,
+    inst: &R,
+    range: ReduceRange,
+    #[comptime] rows_per_plane: u32,
+    #[comptime] line_size: u32,
+    #[comptime] bound_checks: BoundChecksInner,
+) -> R::AccumulatorItem {
+    let segment_dim = CUBE_DIM_X / rows_per_plane;
+    let segment = UNIT_POS_X / segment_dim;
+    let unit_pos = UNIT_POS_X % segment_dim;
+
+    let mut accumulator = R::null_accumulator(inst, line_size);
+
+    let mut first_index = range.index_start;
+    for first_coordinate in range_stepped(
+        range.coordinate_start,
+        range.coordinate_end,
+        range.coordinate_step,
+    ) {
+        let unit_coordinate = first_coordinate + unit_pos * line_size;
+
+        let requirements = R::requirements(inst);
+        let coordinates = if comptime![requirements.coordinates] {
+            ReduceCoordinate::new_Required(fill_coordinate_line(
+                unit_coordinate,
+                line_size,
+                LineMode::Parallel,
+            ))
+        } else {
+            ReduceCoordinate::new_NotRequired()
+        };
+
+        let index = first_index + unit_pos * range.index_step;
+        let item = match bound_checks {
+            BoundChecksInner::None => items.read(index),
+            BoundChecksInner::Mask => {
+                let mask = unit_coordinate < range.coordinate_end;
+                let index = index * u32::cast_from(mask);
+                select(mask, items.read(index), R::null_input(inst, line_size))
+            }
+            BoundChecksInner::Branch => {
+                if unit_coordinate < range.coordinate_end {
+                    items.read(index)
+                } else {
+                    R::null_input(inst, line_size)
+                }
+            }
+        };
+
+        #[unroll]
+        for row in 0..rows_per_plane {
+            let row_item = select(segment == row, item, R::null_input(inst, line_size));
+            let reduction = R::reduce(inst, &accumulator, row_item, coordinates, true);
+            if segment == row {
+                R::assign_accumulator(inst, &mut accumulator, &reduction);
+            }
+        }
+
+        first_index += segment_dim * range.index_step;
+    }
+    accumulator
+}
+
 /// Use an individual cube to reduce the `items` with the specified range.
 /// That is, this will reduces `items[range.start]`, `items[range.start + range.step]`
 /// until `items[range.end]` (exclusive). Inside a cube, the reduction will use plane operations
@@ -0,0 +1,536 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape};
+use cubecl_runtime::TypeUsage;
+use cubecl_std::{CubeOption, CubeOptionArgs, CubeOptionExpand};
+
+use crate::ReduceError;
+use crate::instructions::{Max, Min, Prod, Sum};
+
+/// The number of units in a cube scanning a tile.
+const CUBE_SIZE: u32 = 256;
+/// The number of consecutive lines scanned by each unit of a tile.
+const LINES_PER_UNIT: u32 = 4;
+
+/// An associative operation with an identity element, used to compute the prefixes of a [`scan`].
+///
+/// It is implemented for the [`Sum`], [`Prod`], [`Max`] and [`Min`] instructions.
+#[cube]
+pub trait ScanInstruction: Send + Sync + 'static {
+    /// The element that leaves any other element unchanged when combined with it.
+    fn identity<N: Numeric>() -> N;
+
+    /// Combine the prefix `lhs` with the element `rhs` that follows it.
+    fn combine<N: Numeric>(lhs: N, rhs: N) -> N;
+}
+
+#[cube]
+impl ScanInstruction for Sum {
+    fn identity<N: Numeric>() -> N {
+        N::from_int(0)
+    }
+
+    fn combine<N: Numeric>(lhs: N, rhs: N) -> N {
+        lhs + rhs
+    }
+}
+
+#[cube]
+impl ScanInstruction for Prod {
+    fn identity<N: Numeric>() -> N {
+        N::from_int(1)
+    }
+
+    fn combine<N: Numeric>(lhs: N, rhs: N) -> N {
+        lhs * rhs
+    }
+}
+
+#[cube]
+impl ScanInstruction for Max {
+    fn identity<N: Numeric>() -> N {
+        N::min_value()
+    }
+
+    fn combine<N: Numeric>(lhs: N, rhs: N) -> N {
+        select(rhs > lhs, rhs, lhs)
+    }
+}
+
+#[cube]
+impl ScanInstruction for Min {
+    fn identity<N: Numeric>() -> N {
+        N::max_value()
+    }
+
+    fn combine<N: Numeric>(lhs: N, rhs: N) -> N {
+        select(rhs < lhs, rhs, lhs)
+    }
+}
+
+/// The algorithm used by a [`scan`] to carry the prefixes from one tile to the next.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ScanStrategy {
+    /// A single pass where each tile looks back at the tiles before it for its prefix.
+    /// It requires cubes to make forward progress while waiting on each other, and 64-bit atomics.
+    DecoupledLookback,
+    /// Reduce the tiles, scan their aggregates, then scan the tiles starting from their prefix.
+    /// It reads the input twice, but is supported by all runtimes.
+    ReduceThenScan,
+}
+
+/// Compute the prefixes of all the elements of `input` combined with the instruction `I`
+/// and write them into `output`, in the row-major order of the tensors.
+///
+/// When `inclusive` is true, each output element is the combination of the input elements up to itself.
+/// Otherwise, it is the combination of the elements before it, and the first output element is the
+/// [identity](ScanInstruction::identity) of `I`.
+///
+/// Both tensors must be contiguous and have the same shape. The scan is done in place when
+/// `input` and `output` share the same handle.
+///
+/// An optional [`ScanStrategy`] can be provided to force the algorithm used. If omitted, the
+/// [decoupled lookback](ScanStrategy::DecoupledLookback) is used when the `client` supports it.
+/// Return an error if the decoupled lookback is requested but isn't supported by the `client`
+/// or the elements aren't 32 bits, or if the shape of `output` is invalid.
+///
+/// # Example
+///
+/// This examples show how to compute the offsets where each segment of a list starts from their lengths.
+///
+/// ```ignore
+/// use cubecl_reduce::instructions::Sum;
+///
+/// let client = /* ... */;
+/// let lengths = /* a u32 tensor of shape [n] */;
+/// let offsets = /* a u32 tensor of shape [n] */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let result = scan::<R, u32, Sum>(&client, lengths, offsets, false, None);
+/// ```
+pub fn scan<R: Runtime, N: Numeric + CubeElement, I: ScanInstruction>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    inclusive: bool,
+    strategy: Option<ScanStrategy>,
+) -> Result<(), ReduceError> {
+    if output.shape != input.shape {
+        return Err(ReduceError::MismatchShape {
+            expected_shape: input.shape.to_vec(),
+            output_shape: output.shape.to_vec(),
+        });
+    }
+
+    // The status of a tile packs its 32-bit prefix with a flag in a single atomic.
+    let lookback_supported = client.properties().features.forward_progress
+        && client
+            .properties()
+            .type_usage(Atomic::<u64>::as_type_native_unchecked())
+            .contains(TypeUsage::AtomicLoadStore)
+        && size_of::<N>() == 4;
+    let strategy = match strategy {
+        Some(ScanStrategy::DecoupledLookback) if !lookback_supported => {
+            return Err(ReduceError::LookbackUnavailable);
+        }
+        Some(strategy) => strategy,
+        None if lookback_supported => ScanStrategy::DecoupledLookback,
+        None => ScanStrategy::ReduceThenScan,
+    };
+
+    let length = input.shape.iter().product::<usize>();
+    if length == 0 {
+        return Ok(());
+    }
+
+    let elem = N::as_type_native_unchecked();
+    let line_size = R::line_size_type(&elem)
+        .filter(|line_size| length % *line_size as usize == 0)
+        .max()
+        .unwrap_or(1);
+    let params = ScanParams {
+        line_size: line_size as u32,
+        lines_per_unit: LINES_PER_UNIT,
+        cube_size: CUBE_SIZE,
+        inclusive,
+    };
+
+    let num_tiles = (length / line_size as usize).div_ceil((CUBE_SIZE * LINES_PER_UNIT) as usize);
+    let cube_dim = CubeDim::new_1d(CUBE_SIZE);
+    let cube_count = LaunchShape::elementwise(num_tiles)
+        .units_per_cube(1)
+        .build::<R>()?
+        .cube_count;
+
+    // The kernels read the output when scanning in place, as a buffer can't be bound twice.
+    let in_place = input.handle == output.handle;
+    let input_arg = match in_place {
+        true => CubeOptionArgs::None,
+        false => CubeOptionArgs::Some(input.as_tensor_arg(line_size)),
+    };
+
+    match strategy {
+        ScanStrategy::DecoupledLookback => {
+            let status_handle = client.create(u64::as_bytes(&vec![0; num_tiles]));
+            let counter_handle = client.create(u32::as_bytes(&[0]));
+
+            unsafe {
+                scan_lookback_kernel::launch_unchecked::<N, I, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    input_arg,
+                    output.as_tensor_arg(line_size),
+                    ArrayArg::from_raw_parts::<u64>(&status_handle, num_tiles, 1),
+                    ArrayArg::from_raw_parts::<u32>(&counter_handle, 1, 1),
+                    params,
+                );
+            }
+        }
+        ScanStrategy::ReduceThenScan => {
+            let elem_size = size_of::<N>();
+            let aggregates_handle = client.empty(num_tiles * elem_size);
+            let aggregates = unsafe {
+                TensorHandleRef::<R>::from_raw_parts(
+                    &aggregates_handle,
+                    &[1],
+                    &[num_tiles],
+                    elem_size,
+                )
+            };
+            let data = match in_place {
+                true => output.as_tensor_arg(line_size),
+                false => input.as_tensor_arg(line_size),
+            };
+
+            unsafe {
+                scan_reduce_tiles_kernel::launch_unchecked::<N, I, R>(
+                    client,
+                    cube_count.clone(),
+                    cube_dim,
+                    data,
+                    aggregates.as_tensor_arg(1),
+                    params,
+                );
+                // The aggregates are few enough to be scanned by a single cube, tile after tile.
+                scan_single_cube_kernel::launch_unchecked::<N, I, R>(
+                    client,
+                    CubeCount::Static(1, 1, 1),
+                    cube_dim,
+                    aggregates.as_tensor_arg(1),
+                    ScanParams {
+                        line_size: 1,
+                        inclusive: false,
+                        ..params
+                    },
+                );
+                scan_tiles_kernel::launch_unchecked::<N, I, R>(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    input_arg,
+                    output.as_tensor_arg(line_size),
+                    aggregates.as_tensor_arg(1),
+                    params,
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The comptime parameters of the scan kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScanParams {
+    /// The number of elements in a line of the input and output.
+    pub line_size: u32,
+    /// The number of consecutive lines scanned by each unit.
+    pub lines_per_unit: u32,
+    /// The number of units in a cube, which must be the cube dimension.
+    pub cube_size: u32,
+    /// Whether each element is included in its own prefix.
+    pub inclusive: bool,
+}
+
+/// Scan the tiles in a single pass, where each cube takes the next tile and waits for the prefix
+/// of the tiles before it.
+///
+/// The `status` of each tile and the `tile_counter` must be zero initialized.
+#[cube(launch_unchecked)]
+pub fn scan_lookback_kernel<N: Numeric, I: ScanInstruction>(
+    input: &CubeOption<Tensor<Line<N>>>,
+    output: &mut Tensor<Line<N>>,
+    status: &mut Array<Atomic<u64>>,
+    tile_counter: &mut Array<Atomic<u32>>,
+    #[comptime] params: ScanParams,
+) {
+    // Tiles are numbered in the order cubes start rather than by cube position,
+    // so the tiles a cube waits on are always running or done.
+    let mut ticket = SharedMemory::<u32>::new(1);
+    if UNIT_POS == 0 {
+        ticket[0] = Atomic::add(&tile_counter[0], 1);
+    }
+    sync_cube();
+    let tile = ticket[0];
+
+    if tile >= status.len() {
+        terminate!();
+    }
+
+    let first_line = (tile * params.cube_size + UNIT_POS) * params.lines_per_unit;
+    let mut items = Array::<Line<N>>::vectorized(params.lines_per_unit, params.line_size);
+    let aggregate = match input {
+        CubeOption::Some(input) => load_unit::<N, I>(input, &mut items, first_line, params),
+        CubeOption::None => load_unit::<N, I>(output, &mut items, first_line, params),
+    };
+
+    let mut shared = SharedMemory::<N>::new(params.cube_size);
+    let exclusive = scan_cube::<N, I>(&mut shared, aggregate, params.cube_size);
+
+    let mut prefix = SharedMemory::<N>::new(1);
+    if UNIT_POS == 0 {
+        prefix[0] = lookback::<N, I>(status, tile, shared[params.cube_size - 1]);
+    }
+    sync_cube();
+
+    write_unit::<N, I>(
+        output,
+        &items,
+        first_line,
+        I::combine::<N>(prefix[0], exclusive),
+        params,
+    );
+}
+
+/// Reduce each tile of `input` into `aggregates`.
+#[cube(launch_unchecked)]
+pub fn scan_reduce_tiles_kernel<N: Numeric, I: ScanInstruction>(
+    input: &Tensor<Line<N>>,
+    aggregates: &mut Tensor<Line<N>>,
+    #[comptime] params: ScanParams,
+) {
+    let tile = CUBE_POS;
+    if tile >= aggregates.len() {
+        terminate!();
+    }
+
+    let first_line = (tile * params.cube_size + UNIT_POS) * params.lines_per_unit;
+    let mut items = Array::<Line<N>>::vectorized(params.lines_per_unit, params.line_size);
+    let aggregate = load_unit::<N, I>(input, &mut items, first_line, params);
+
+    let mut shared = SharedMemory::<N>::new(params.cube_size);
+    scan_cube::<N, I>(&mut shared, aggregate, params.cube_size);
+
+    if UNIT_POS == 0 {
+        aggregates[tile] = Line::empty(1).fill(shared[params.cube_size - 1]);
+    }
+}
+
+/// Scan `data` in place with a single cube, carrying the prefix from one tile to the next.
+#[cube(launch_unchecked)]
+pub fn scan_single_cube_kernel<N: Numeric, I: ScanInstruction>(
+    data: &mut Tensor<Line<N>>,
+    #[comptime] params: ScanParams,
+) {
+    let lines_per_tile = comptime!(params.cube_size * params.lines_per_unit);
+    let num_tiles = (data.len() + lines_per_tile - 1) / lines_per_tile;
+
+    let mut items = Array::<Line<N>>::vectorized(params.lines_per_unit, params.line_size);
+    let mut shared = SharedMemory::<N>::new(params.cube_size);
+    let mut carry = I::identity::<N>();
+
+    for tile in 0..num_tiles {
+        let first_line = (tile * params.cube_size + UNIT_POS) * params.lines_per_unit;
+        let aggregate = load_unit::<N, I>(data, &mut items, first_line, params);
+        let exclusive = scan_cube::<N, I>(&mut shared, aggregate, params.cube_size);
+        let total = shared[params.cube_size - 1];
+
+        write_unit::<N, I>(
+            data,
+            &items,
+            first_line,
+            I::combine::<N>(carry, exclusive),
+            params,
+        );
+        carry = I::combine::<N>(carry, total);
+
+        // The shared memory is overwritten by the next tile.
+        sync_cube();
+    }
+}
+
+/// Scan each tile of `input` starting from its exclusive prefix in `prefixes`.
+#[cube(launch_unchecked)]
+pub fn scan_tiles_kernel<N: Numeric, I: ScanInstruction>(
+    input: &CubeOption<Tensor<Line<N>>>,
+    output: &mut Tensor<Line<N>>,
+    prefixes: &Tensor<Line<N>>,
+    #[comptime] params: ScanParams,
+) {
+    let tile = CUBE_POS;
+    if tile >= prefixes.len() {
+        terminate!();
+    }
+
+    let first_line = (tile * params.cube_size + UNIT_POS) * params.lines_per_unit;
+    let mut items = Array::<Line<N>>::vectorized(params.lines_per_unit, params.line_size);
+    let aggregate = match input {
+        CubeOption::Some(input) => load_unit::<N, I>(input, &mut items, first_line, params),
+        CubeOption::None => load_unit::<N, I>(output, &mut items, first_line, params),
+    };
+
+    let mut shared = SharedMemory::<N>::new(params.cube_size);
+    let exclusive = scan_cube::<N, I>(&mut shared, aggregate, params.cube_size);
+
+    write_unit::<N, I>(
+        output,
+        &items,
+        first_line,
+        I::combine::<N>(prefixes[tile][0], exclusive),
+        params,
+    );
+}
+
+/// Read the lines of a unit starting at `first_line` into `items`, replacing each element by its
+/// inclusive prefix within the unit, and return the aggregate of the unit.
+///
+/// The lines past the end of `input` are filled with the identity.
+#[cube]
+fn load_unit<N: Numeric, I: ScanInstruction>(
+    input: &Tensor<Line<N>>,
+    items: &mut Array<Line<N>>,
+    first_line: u32,
+    #[comptime] params: ScanParams,
+) -> N {
+    let mut aggregate = I::identity::<N>();
+
+    #[unroll]
+    for i in 0..params.lines_per_unit {
+        let index = first_line + i;
+        let mut line = Line::empty(params.line_size).fill(I::identity::<N>());
+        if index < input.len() {
+            line = input[index];
+        }
+
+        #[unroll]
+        for k in 0..params.line_size {
+            aggregate = I::combine::<N>(aggregate, line[k]);
+            line[k] = aggregate;
+        }
+        items[i] = line;
+    }
+
+    aggregate
+}
+
+/// Scan the aggregates of all the units of the cube, returning the exclusive prefix of the unit.
+///
+/// Afterward, the last element of `shared` is the aggregate of the whole cube.
+#[cube]
+pub(crate) fn scan_cube<N: Numeric, I: ScanInstruction>(
+    shared: &mut SharedMemory<N>,
+    aggregate: N,
+    #[comptime] cube_size: u32,
+) -> N {
+    shared[UNIT_POS] = aggregate;
+    sync_cube();
+
+    let mut offset = 1;
+    while offset < cube_size {
+        let mut value = shared[UNIT_POS];
+        if UNIT_POS >= offset {
+            value = I::combine::<N>(shared[UNIT_POS - offset], value);
+        }
+        sync_cube();
+        shared[UNIT_POS] = value;
+        sync_cube();
+        offset *= 2;
+    }
+
+    let mut exclusive = I::identity::<N>();
+    if UNIT_POS > 0 {
+        exclusive = shared[UNIT_POS - 1];
+    }
+    exclusive
+}
+
+/// Write the lines of a unit starting at `first_line`, combining the prefixes within the unit
+/// stored in `items` with the `prefix` of the unit.
+#[cube]
+fn write_unit<N: Numeric, I: ScanInstruction>(
+    output: &mut Tensor<Line<N>>,
+    items: &Array<Line<N>>,
+    first_line: u32,
+    prefix: N,
+    #[comptime] params: ScanParams,
+) {
+    let mut previous = prefix;
+
+    #[unroll]
+    for i in 0..params.lines_per_unit {
+        let index = first_line + i;
+        let line = items[i];
+        let mut result = Line::<N>::empty(params.line_size);
+
+        #[unroll]
+        for k in 0..params.line_size {
+            let inclusive = I::combine::<N>(prefix, line[k]);
+            if comptime![params.inclusive] {
+                result[k] = inclusive;
+            } else {
+                result[k] = previous;
+            }
+            previous = inclusive;
+        }
+
+        if index < output.len() {
+            output[index] = result;
+        }
+    }
+}
+
+/// Publish the `aggregate` of `tile`, then combine the aggregates of the previous tiles until one
+/// of them has published its inclusive prefix. Return the exclusive prefix of `tile` after
+/// publishing its inclusive prefix.
+///
+/// The status of a tile holds the bits of its value in the high 32 bits and a flag in the low bits:
+/// 0 when nothing is published, 1 for the aggregate of the tile and 2 for its inclusive prefix.
+#[cube]
+fn lookback<N: Numeric, I: ScanInstruction>(
+    status: &Array<Atomic<u64>>,
+    tile: u32,
+    aggregate: N,
+) -> N {
+    let mut prefix = I::identity::<N>();
+
+    if tile == 0 {
+        Atomic::store(&status[0], pack_status::<N>(aggregate, 2));
+    } else {
+        Atomic::store(&status[tile], pack_status::<N>(aggregate, 1));
+
+        let mut predecessor = tile;
+        let mut done = false;
+        while !done {
+            predecessor -= 1;
+            let mut packed = Atomic::load(&status[predecessor]);
+            while (packed & 3u64) == 0u64 {
+                packed = Atomic::load(&status[predecessor]);
+            }
+
+            let value = N::reinterpret(u32::cast_from(packed >> 32u64));
+            prefix = I::combine::<N>(value, prefix);
+            done = (packed & 3u64) == 2u64;
+        }
+
+        Atomic::store(
+            &status[tile],
+            pack_status::<N>(I::combine::<N>(prefix, aggregate), 2),
+        );
+    }
+
+    prefix
+}
+
+#[cube]
+fn pack_status<N: Numeric>(value: N, flag: u32) -> u64 {
+    (u64::cast_from(u32::reinterpret(value)) << 32u64) | u64::cast_from(flag)
+}
@@ -0,0 +1,141 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_std::tensor::{
+    IndexFlag, IndexOptions, TensorHandle, check_scatter_add, into_contiguous, resolve_index,
+};
+
+use crate::{ReduceError, radix_sort};
+
+/// Add the slices of `updates` to the slices of `target` at the `indices` along the given `axis`,
+/// with the same result on every run.
+///
+/// This is [`scatter_add`](cubecl_std::tensor::scatter_add) without atomics: the resolved indices
+/// are [sorted](radix_sort) with their positions, then a single unit sums the updates of each
+/// run of equal indices in the order of their positions and adds the sum to the target. The
+/// updates of an index are therefore always added in the order of the indices, at the cost of
+/// the sort and of summing the duplicates of an index serially.
+///
+/// The indices are `i32` and resolved with the given `options`. The `target` must be contiguous.
+///
+/// Return an error if the indices can't be sorted.
+pub fn scatter_add_deterministic<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    target: &TensorHandleRef<R>,
+    indices: &TensorHandleRef<R>,
+    updates: &TensorHandleRef<R>,
+    axis: usize,
+    options: IndexOptions,
+) -> Result<IndexFlag, ReduceError> {
+    let (outer, length, inner) = check_scatter_add(target, indices, updates, axis, options);
+    let num_indices = indices.shape.iter().product::<usize>();
+
+    let flag = IndexFlag::new::<R>(client);
+    let num_elems = outer * num_indices * inner;
+    if num_elems == 0 {
+        return Ok(flag);
+    }
+
+    let updates = into_contiguous::<R, E>(client, updates);
+    let indices = into_contiguous::<R, i32>(client, indices);
+    let keys = TensorHandle::<R, u32>::empty(client, vec![num_indices]);
+    let positions = TensorHandle::<R, u32>::empty(client, vec![num_indices]);
+    let cube_dim = CubeDim::default();
+
+    unsafe {
+        scatter_keys_kernel::launch_unchecked::<R>(
+            client,
+            calculate_cube_count_elemwise(num_indices, cube_dim),
+            cube_dim,
+            indices.as_arg(1),
+            keys.as_arg(1),
+            positions.as_arg(1),
+            flag.as_arg(),
+            ScalarArg::new(length as u32),
+            options,
+        );
+    }
+
+    // The sort is stable, so the positions of equal keys stay in ascending order.
+    radix_sort::<R, u32>(client, keys.as_ref(), Some(positions.as_ref()), false)?;
+
+    unsafe {
+        segmented_add_kernel::launch_unchecked::<E, R>(
+            client,
+            calculate_cube_count_elemwise(num_elems, cube_dim),
+            cube_dim,
+            target.as_tensor_arg(1),
+            keys.as_arg(1),
+            positions.as_arg(1),
+            updates.as_arg(1),
+            ScalarArg::new(length as u32),
+            ScalarArg::new(inner as u32),
+        );
+    }
+
+    Ok(flag)
+}
+
+/// Each unit writes the resolved index at its position of `indices` to the `keys`, `u32::MAX`
+/// when it is skipped, and the position itself to the `positions`.
+#[cube(launch_unchecked)]
+fn scatter_keys_kernel(
+    indices: &Tensor<i32>,
+    keys: &mut Tensor<u32>,
+    positions: &mut Tensor<u32>,
+    flag: &mut Tensor<u32>,
+    length: u32,
+    #[comptime] options: IndexOptions,
+) {
+    if ABSOLUTE_POS >= indices.len() {
+        terminate!();
+    }
+
+    let key = resolve_index(indices[ABSOLUTE_POS], length, options);
+    if key == u32::MAX {
+        flag[0] = 1;
+    }
+    keys[ABSOLUTE_POS] = key;
+    positions[ABSOLUTE_POS] = ABSOLUTE_POS;
+}
+
+/// Each unit at the start of a run of equal sorted `keys` sums the elements of the contiguous
+/// `updates`, seen as `[outer, num_indices, inner]`, at the positions of the run, and adds the sum
+/// to the contiguous `target`, seen as `[outer, length, inner]`. The skipped indices are sorted
+/// last and ignored.
+#[cube(launch_unchecked)]
+fn segmented_add_kernel<E: Numeric>(
+    target: &mut Tensor<E>,
+    keys: &Tensor<u32>,
+    positions: &Tensor<u32>,
+    updates: &Tensor<E>,
+    length: u32,
+    inner: u32,
+) {
+    if ABSOLUTE_POS >= updates.len() {
+        terminate!();
+    }
+
+    let num_indices = keys.len();
+    let element = ABSOLUTE_POS % inner;
+    let start = (ABSOLUTE_POS / inner) % num_indices;
+    let outer = ABSOLUTE_POS / inner / num_indices;
+
+    let key = keys[start];
+    let previous = keys[Max::max(start, 1) - 1];
+    if key == u32::MAX || (start > 0 && previous == key) {
+        terminate!();
+    }
+
+    let mut sum = E::from_int(0);
+    let mut end = start;
+    loop {
+        sum += updates[(outer * num_indices + positions[end]) * inner + element];
+        end += 1;
+        if end >= num_indices || keys[Min::min(end, num_indices - 1)] != key {
+            break;
+        }
+    }
+
+    let offset = (outer * length + key) * inner + element;
+    target[offset] += sum;
+}
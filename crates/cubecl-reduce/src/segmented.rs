@@ -0,0 +1,366 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise};
+use cubecl_std::tensor::{TensorHandle, into_contiguous, is_contiguous};
+
+use crate::{ReduceError, instructions::Sum, precision::ReducePrecision, scan};
+
+/// The largest number of consecutive values of a segment reduced by a unit.
+const CHUNK_SIZE: u32 = 64;
+
+/// How the values of each segment of a [`segmented_reduce`] are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentedReduceOp {
+    /// The sum of the values, 0 for an empty segment.
+    Sum,
+    /// The smallest value, the largest value of the accumulator for an empty segment.
+    Min,
+    /// The largest value, the smallest value of the accumulator for an empty segment.
+    Max,
+    /// The sum of the values divided by their number, 0 for an empty segment.
+    Mean,
+}
+
+/// The integer type of the offsets of the segments of a [`segmented_reduce`].
+pub trait SegmentOffset: Int + CubeElement {}
+
+impl SegmentOffset for u32 {}
+impl SegmentOffset for u64 {}
+
+/// Reduce each segment `values[offsets[i]..offsets[i + 1]]` of the `values` with the `op`.
+///
+/// The `offsets` are a vector of `num_segments + 1` non-decreasing positions of the `values`,
+/// like the row offsets of a CSR matrix. The values are accumulated in `P::EA`, so a reduction of
+/// `f16` values with [`half::f16`] as precision accumulates and outputs `f32`.
+///
+/// The work is balanced between the units whatever the lengths of the segments: each segment is
+/// split in chunks of at most 64 values, all reduced by a unit, so the short segments
+/// are packed side by side in the planes while a long one is spread over many cubes. Each unit
+/// finds its chunk by a binary search over the exclusive scan of the number of chunks of each
+/// segment. The partial results of the segments of multiple chunks are reduced again by the same
+/// kernel, the offsets of their partial results being the scan, until every segment fits in a
+/// single chunk.
+///
+/// The values are indexed with `u32`, so `u64` offsets must still be smaller than `u32::MAX`.
+///
+/// Return a contiguous vector with the result of each segment. Return an error if the values or
+/// the offsets aren't vectors, or if there is no offset.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let values = /* the f16 values of the non-zero elements of a CSR matrix */;
+/// let offsets = /* the u32 offsets of its rows */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let op = SegmentedReduceOp::Sum;
+/// let sums = segmented_reduce::<R, half::f16, u32>(&client, values, offsets, op)?;
+/// ```
+pub fn segmented_reduce<R: Runtime, P: ReducePrecision, O: SegmentOffset>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: TensorHandleRef<R>,
+    offsets: TensorHandleRef<R>,
+    op: SegmentedReduceOp,
+) -> Result<TensorHandle<R, P::EA>, ReduceError> {
+    if values.shape.len() != 1 || offsets.shape.len() != 1 || offsets.shape[0] == 0 {
+        return Err(ReduceError::InvalidSegmentOffsets {
+            values_shape: values.shape.to_vec(),
+            offsets_shape: offsets.shape.to_vec(),
+        });
+    }
+
+    let num_segments = offsets.shape[0] - 1;
+    let output = TensorHandle::<R, P::EA>::new_contiguous(
+        vec![num_segments],
+        client.empty(num_segments.max(1) * size_of::<P::EA>()),
+    );
+    if num_segments == 0 {
+        return Ok(output);
+    }
+
+    let values_contiguous;
+    let values = match is_contiguous(values.shape, values.strides) {
+        true => values,
+        false => {
+            values_contiguous = into_contiguous::<R, P::EI>(client, &values);
+            values_contiguous.as_ref()
+        }
+    };
+    let offsets_contiguous;
+    let offsets = match is_contiguous(offsets.shape, offsets.strides) {
+        true => offsets,
+        false => {
+            offsets_contiguous = into_contiguous::<R, O>(client, &offsets);
+            offsets_contiguous.as_ref()
+        }
+    };
+
+    // The means are the sums divided by the lengths of the segments.
+    let reduce_op = match op {
+        SegmentedReduceOp::Mean => SegmentedReduceOp::Sum,
+        op => op,
+    };
+
+    // A segment can't have more values than the tensor, so the first pass is the last one when
+    // no segment can be split.
+    let max_length = values.shape[0];
+    if max_length <= CHUNK_SIZE as usize {
+        launch_segment_pass::<R, P::EI, P::EA, O>(
+            client,
+            &values,
+            &offsets,
+            &output.as_ref(),
+            num_segments,
+            reduce_op,
+            None,
+        );
+    } else {
+        let (mut partials, mut work_offsets) = launch_split_pass::<R, P::EI, P::EA, O>(
+            client,
+            &values,
+            &offsets,
+            num_segments,
+            reduce_op,
+        )?;
+        let mut max_length = max_length.div_ceil(CHUNK_SIZE as usize);
+
+        while max_length > CHUNK_SIZE as usize {
+            let (next_partials, next_offsets) = launch_split_pass::<R, P::EA, P::EA, u32>(
+                client,
+                &partials.as_ref(),
+                &work_offsets.as_ref(),
+                num_segments,
+                reduce_op,
+            )?;
+            partials = next_partials;
+            work_offsets = next_offsets;
+            max_length = max_length.div_ceil(CHUNK_SIZE as usize);
+        }
+
+        launch_segment_pass::<R, P::EA, P::EA, u32>(
+            client,
+            &partials.as_ref(),
+            &work_offsets.as_ref(),
+            &output.as_ref(),
+            num_segments,
+            reduce_op,
+            None,
+        );
+    }
+
+    if op == SegmentedReduceOp::Mean {
+        let cube_dim = CubeDim::default();
+        unsafe {
+            segment_mean_kernel::launch_unchecked::<P::EA, O, R>(
+                client,
+                calculate_cube_count_elemwise(num_segments, cube_dim),
+                cube_dim,
+                output.as_ref().as_tensor_arg(1),
+                offsets.as_tensor_arg(1),
+            );
+        }
+    }
+
+    Ok(output)
+}
+
+/// Split the segments in chunks and reduce each chunk into a partial result, returning the
+/// partial results and the offsets of the ones of each segment.
+fn launch_split_pass<R: Runtime, I: Numeric, A: Numeric, O: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: &TensorHandleRef<R>,
+    offsets: &TensorHandleRef<R>,
+    num_segments: usize,
+    op: SegmentedReduceOp,
+) -> Result<(TensorHandle<R, A>, TensorHandle<R, u32>), ReduceError> {
+    let cube_dim = CubeDim::default();
+
+    // The last count is never read, so the scan ends with the total number of chunks.
+    let counts = TensorHandle::<R, u32>::new_contiguous(
+        vec![num_segments + 1],
+        client.empty((num_segments + 1) * size_of::<u32>()),
+    );
+    unsafe {
+        segment_chunks_kernel::launch_unchecked::<O, R>(
+            client,
+            calculate_cube_count_elemwise(num_segments + 1, cube_dim),
+            cube_dim,
+            offsets.as_tensor_arg(1),
+            counts.as_ref().as_tensor_arg(1),
+            CHUNK_SIZE,
+        );
+    }
+    scan::<R, u32, Sum>(client, counts.as_ref(), counts.as_ref(), false, None)?;
+
+    // Each segment has one more chunk than its share of the values at most.
+    let max_chunks = num_segments + values.shape[0].div_ceil(CHUNK_SIZE as usize);
+    let partials = TensorHandle::<R, A>::new_contiguous(
+        vec![max_chunks],
+        client.empty(max_chunks * size_of::<A>()),
+    );
+    launch_segment_pass::<R, I, A, O>(
+        client,
+        values,
+        offsets,
+        &partials.as_ref(),
+        num_segments,
+        op,
+        Some((&counts.as_ref(), max_chunks)),
+    );
+
+    Ok((partials, counts))
+}
+
+/// Reduce the chunks of the segments, one per unit, into the `output`.
+///
+/// With the `work_offsets` of the chunks and their maximum number, each chunk is reduced into a
+/// partial result. Without, each segment is reduced as a single chunk into its result.
+fn launch_segment_pass<R: Runtime, I: Numeric, A: Numeric, O: Int>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    values: &TensorHandleRef<R>,
+    offsets: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    num_segments: usize,
+    op: SegmentedReduceOp,
+    work_offsets: Option<(&TensorHandleRef<R>, usize)>,
+) {
+    let cube_dim = CubeDim::default();
+    // The kernel still takes the offsets of the chunks when each segment is a single chunk.
+    let (work_offsets, num_units, split) = match work_offsets {
+        Some((work_offsets, max_chunks)) => (work_offsets, max_chunks, true),
+        None => (offsets, num_segments, false),
+    };
+
+    unsafe {
+        segmented_reduce_kernel::launch_unchecked::<I, A, O, R>(
+            client,
+            calculate_cube_count_elemwise(num_units, cube_dim),
+            cube_dim,
+            values.as_tensor_arg(1),
+            offsets.as_tensor_arg(1),
+            work_offsets.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(num_segments as u32),
+            SegmentedReduceParams {
+                chunk_size: CHUNK_SIZE,
+                op,
+                split,
+            },
+        );
+    }
+}
+
+/// The comptime parameters of the [`segmented_reduce_kernel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SegmentedReduceParams {
+    /// The largest number of values reduced by a unit.
+    pub chunk_size: u32,
+    /// How the values are combined.
+    pub op: SegmentedReduceOp,
+    /// Whether the segments are split in chunks found in the `work_offsets`, each reduced into a
+    /// partial result, rather than each reduced by a unit into its result.
+    pub split: bool,
+}
+
+/// Count the chunks of each segment, at least one so empty segments get a result.
+#[cube(launch_unchecked)]
+pub fn segment_chunks_kernel<O: Int>(
+    offsets: &Tensor<O>,
+    counts: &mut Tensor<u32>,
+    #[comptime] chunk_size: u32,
+) {
+    let num_segments = offsets.len() - 1;
+    if ABSOLUTE_POS > num_segments {
+        terminate!();
+    }
+
+    if ABSOLUTE_POS == num_segments {
+        counts[ABSOLUTE_POS] = 0;
+    } else {
+        let length = u32::cast_from(offsets[ABSOLUTE_POS + 1] - offsets[ABSOLUTE_POS]);
+        counts[ABSOLUTE_POS] = Max::max((length + chunk_size - 1) / chunk_size, 1);
+    }
+}
+
+/// Each unit reduces a chunk of the `values` of a segment delimited by the `offsets`.
+#[cube(launch_unchecked)]
+pub fn segmented_reduce_kernel<I: Numeric, A: Numeric, O: Int>(
+    values: &Tensor<I>,
+    offsets: &Tensor<O>,
+    work_offsets: &Tensor<u32>,
+    output: &mut Tensor<A>,
+    num_segments: u32,
+    #[comptime] params: SegmentedReduceParams,
+) {
+    let mut segment = ABSOLUTE_POS;
+    let mut chunk = 0;
+    if comptime!(params.split) {
+        if ABSOLUTE_POS >= work_offsets[num_segments] {
+            terminate!();
+        }
+
+        // The last segment whose first chunk is at most the chunk of the unit, the segments
+        // having at least one chunk.
+        let mut low = 0;
+        let mut high = num_segments;
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            if work_offsets[middle] <= ABSOLUTE_POS {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        segment = low;
+        chunk = ABSOLUTE_POS - work_offsets[low];
+    } else if ABSOLUTE_POS >= num_segments {
+        terminate!();
+    }
+
+    let segment_start = u32::cast_from(offsets[segment]);
+    let segment_end = u32::cast_from(offsets[segment + 1]);
+    let start = segment_start + chunk * params.chunk_size;
+    let end = Min::min(start + params.chunk_size, segment_end);
+
+    let mut accumulator = segment_identity::<A>(params.op);
+    for i in start..end {
+        accumulator = segment_combine::<A>(accumulator, A::cast_from(values[i]), params.op);
+    }
+    output[ABSOLUTE_POS] = accumulator;
+}
+
+/// Divide the sum of each segment by its length, leaving the empty segments at 0.
+#[cube(launch_unchecked)]
+pub fn segment_mean_kernel<A: Numeric, O: Int>(output: &mut Tensor<A>, offsets: &Tensor<O>) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let length = u32::cast_from(offsets[ABSOLUTE_POS + 1] - offsets[ABSOLUTE_POS]);
+    if length > 0 {
+        output[ABSOLUTE_POS] = output[ABSOLUTE_POS] / A::cast_from(length);
+    }
+}
+
+/// The result of an empty segment, left unchanged when combined with a value.
+#[cube]
+fn segment_identity<A: Numeric>(#[comptime] op: SegmentedReduceOp) -> A {
+    if comptime!(op == SegmentedReduceOp::Min) {
+        A::max_value()
+    } else if comptime!(op == SegmentedReduceOp::Max) {
+        A::min_value()
+    } else {
+        A::from_int(0)
+    }
+}
+
+#[cube]
+fn segment_combine<A: Numeric>(accumulator: A, value: A, #[comptime] op: SegmentedReduceOp) -> A {
+    if comptime!(op == SegmentedReduceOp::Min) {
+        Min::min(accumulator, value)
+    } else if comptime!(op == SegmentedReduceOp::Max) {
+        Max::max(accumulator, value)
+    } else {
+        accumulator + value
+    }
+}
@@ -1,6 +1,7 @@
 use cubecl_core::prelude::*;
 use cubecl_core::{self as cubecl};
 use cubecl_runtime::TypeUsage;
+use cubecl_std::tensor::TensorHandle;
 
 use crate::ReduceError;
 
@@ -8,6 +9,8 @@ use crate::ReduceError;
 ///
 /// This is an optimized version for summing large tensors using multiple cubes.
 /// For summing a single axis, the regular [reduce] entry point is preferred.
+/// The sums of the cubes are added with atomics in the order the cubes finish, so the rounding
+/// of floats can change between runs, unlike with [shared_sum_deterministic].
 ///
 /// Return an error if atomic addition is not supported for the type `N`.
 ///
@@ -68,26 +71,13 @@ pub fn shared_sum<R: Runtime, N: Numeric + CubeElement>(
         return Err(ReduceError::MissingAtomicAdd(N::as_type_native_unchecked()));
     }
 
-    let input_len = input.shape.iter().map(|s| *s as u32).product::<u32>();
-
-    // Compute the optimal line size.
-    let elem = N::as_type_native_unchecked();
-    let line_size = R::line_size_type(&elem)
-        .filter(|line_size| input_len % *line_size as u32 == 0)
-        .max()
-        .unwrap_or(1) as u32;
-
-    // Compute extra parameters.
-    let cube_dim = CubeDim::new_2d(32, 8); // NOTE: If you change that, keep the unit count a power of 2.
-    let num_units = cube_count * cube_dim.num_elems();
-    let num_lines_per_unit = input_len.div_ceil(num_units * line_size);
-    let cube_count = CubeCount::new_1d(cube_count);
+    let (cube_dim, line_size, num_lines_per_unit) = launch_params::<R, N>(&input, cube_count);
 
     // Launch kernel
     unsafe {
         shared_sum_kernel::launch_unchecked::<N, R>(
             client,
-            cube_count,
+            CubeCount::new_1d(cube_count),
             cube_dim,
             input.as_tensor_arg(line_size as u8),
             output.as_tensor_arg(1),
@@ -100,6 +90,78 @@ pub fn shared_sum<R: Runtime, N: Numeric + CubeElement>(
     Ok(())
 }
 
+/// Sum all the elements of the input tensor distributed over `cube_count` cubes, with the same
+/// result on every run.
+///
+/// This is [shared_sum] without atomics: each cube writes its sum to a workspace of `cube_count`
+/// elements, which a single cube then sums in a fixed order and adds to the output. The order of
+/// the additions only depends on the shape of the input, on `cube_count` and on the line sizes
+/// of the runtime, at the cost of a second launch and of the workspace.
+///
+/// # Important
+///
+/// As with [shared_sum], the sum is added to the value of output instead of overwriting it.
+pub fn shared_sum_deterministic<R: Runtime, N: Numeric + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    cube_count: u32,
+) {
+    let workspace = TensorHandle::<R, N>::empty(client, vec![cube_count as usize]);
+
+    launch_partial_sums::<R, N>(client, input, workspace.as_ref(), cube_count, false);
+    launch_partial_sums::<R, N>(client, workspace.as_ref(), output, 1, true);
+}
+
+/// Write the sum of the elements handled by each of the `cube_count` cubes to its position of
+/// `output`, or add it when `accumulate` is true.
+fn launch_partial_sums<R: Runtime, N: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    cube_count: u32,
+    accumulate: bool,
+) {
+    let (cube_dim, line_size, num_lines_per_unit) = launch_params::<R, N>(&input, cube_count);
+
+    unsafe {
+        partial_sum_kernel::launch_unchecked::<N, R>(
+            client,
+            CubeCount::new_1d(cube_count),
+            cube_dim,
+            input.as_tensor_arg(line_size as u8),
+            output.as_tensor_arg(1),
+            cube_dim.num_elems(),
+            line_size,
+            num_lines_per_unit,
+            accumulate,
+        );
+    }
+}
+
+/// The cube dimension, the line size and the number of lines summed by each unit of the
+/// `cube_count` cubes summing `input`.
+fn launch_params<R: Runtime, N: Numeric>(
+    input: &TensorHandleRef<R>,
+    cube_count: u32,
+) -> (CubeDim, u32, u32) {
+    let input_len = input.shape.iter().map(|s| *s as u32).product::<u32>();
+
+    // Compute the optimal line size.
+    let elem = N::as_type_native_unchecked();
+    let line_size = R::line_size_type(&elem)
+        .filter(|line_size| input_len % *line_size as u32 == 0)
+        .max()
+        .unwrap_or(1) as u32;
+
+    // Compute extra parameters.
+    let cube_dim = CubeDim::new_2d(32, 8); // NOTE: If you change that, keep the unit count a power of 2.
+    let num_units = cube_count * cube_dim.num_elems();
+    let num_lines_per_unit = input_len.div_ceil(num_units * line_size);
+
+    (cube_dim, line_size, num_lines_per_unit)
+}
+
 #[cube(launch_unchecked)]
 fn shared_sum_kernel<N: Numeric>(
     input: &Tensor<Line<N>>,
@@ -108,6 +170,43 @@ fn shared_sum_kernel<N: Numeric>(
     #[comptime] line_size: u32,
     #[comptime] num_lines_per_unit: u32,
 ) {
+    let sum = cube_sum(input, shared_memory_size, line_size, num_lines_per_unit);
+
+    // Add the sum for the current cube to the output.
+    if UNIT_POS == 0 {
+        Atomic::add(&output[0], sum);
+    }
+}
+
+#[cube(launch_unchecked)]
+fn partial_sum_kernel<N: Numeric>(
+    input: &Tensor<Line<N>>,
+    output: &mut Tensor<N>,
+    #[comptime] shared_memory_size: u32,
+    #[comptime] line_size: u32,
+    #[comptime] num_lines_per_unit: u32,
+    #[comptime] accumulate: bool,
+) {
+    let sum = cube_sum(input, shared_memory_size, line_size, num_lines_per_unit);
+
+    if UNIT_POS == 0 {
+        if comptime!(accumulate) {
+            output[CUBE_POS] += sum;
+        } else {
+            output[CUBE_POS] = sum;
+        }
+    }
+}
+
+/// The sum of the lines of `input` handled by the current cube, in an order fixed by the shape of
+/// the launch.
+#[cube]
+fn cube_sum<N: Numeric>(
+    input: &Tensor<Line<N>>,
+    #[comptime] shared_memory_size: u32,
+    #[comptime] line_size: u32,
+    #[comptime] num_lines_per_unit: u32,
+) -> N {
     let mut shared_memory = SharedMemory::new_lined(shared_memory_size, line_size);
     shared_memory[UNIT_POS] = Line::empty(line_size).fill(N::from_int(0));
 
@@ -135,10 +234,7 @@ fn shared_sum_kernel<N: Numeric>(
         sum.store(update);
     }
 
-    // Add the sum for the current cube to the output.
-    if UNIT_POS == 0 {
-        Atomic::add(&output[0], sum.consume());
-    }
+    sum.consume()
 }
 
 // This is a simplified version of [tree_reduce].
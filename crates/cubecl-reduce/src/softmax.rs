@@ -0,0 +1,374 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape};
+use cubecl_runtime::Plane;
+use cubecl_std::{CubeOption, CubeOptionArgs, CubeOptionExpand};
+
+use crate::ReduceError;
+use crate::precision::ReducePrecision;
+
+/// The maximum number of units in a cube normalizing a row.
+const MAX_CUBE_SIZE: u32 = 256;
+/// The longest row kept in shared memory, so a cube doesn't occupy a whole multiprocessor.
+const MAX_RESIDENT_LENGTH: usize = 8192;
+
+/// How the logits of a row are masked before the softmax.
+pub enum SoftmaxMask<'a, R: Runtime> {
+    /// A tensor with the shape of the input added to the logits, with `-inf` for the masked columns.
+    /// A bias row shared by all rows is given with a stride of 0 for the other axes.
+    Bias(TensorHandleRef<'a, R>),
+    /// A `u32` tensor with the shape of the input, where the columns with a non-zero value are skipped.
+    Columns(TensorHandleRef<'a, R>),
+}
+
+/// The options of [`softmax`].
+pub struct SoftmaxOptions<'a, R: Runtime> {
+    /// The logits are divided by the temperature before the softmax.
+    pub temperature: f32,
+    pub mask: Option<SoftmaxMask<'a, R>>,
+}
+
+impl<R: Runtime> Default for SoftmaxOptions<'_, R> {
+    fn default() -> Self {
+        Self {
+            temperature: 1.0,
+            mask: None,
+        }
+    }
+}
+
+/// Compute the softmax of each row of the `input` tensor along its last axis and write it into `output`.
+///
+/// Each row is normalized by a single cube in one kernel: the row is read once to compute its max and the sum
+/// of its exponentials with the streaming rescaling trick, then normalized.
+/// The row is kept in shared memory between both steps when it fits, else it is read again from global memory.
+/// The math is performed in `P::EA`, e.g. in `f32` for `f16` inputs.
+///
+/// The logits are divided by `options.temperature` before the mask is applied.
+/// A row where all the columns are masked is set to 0.
+///
+/// Return an error if the `input` is a scalar or if the shapes of `output` or of the mask differ from the shape of `input`.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let scores = /* a tensor of shape [batch, heads, seq_q, seq_k] */;
+/// let probabilities = /* a tensor of the same shape */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// softmax::<R, half::f16>(&client, scores, probabilities, SoftmaxOptions::default())?;
+/// ```
+pub fn softmax<R: Runtime, P: ReducePrecision>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    options: SoftmaxOptions<R>,
+) -> Result<(), ReduceError>
+where
+    P::EI: Float,
+    P::EA: Float,
+{
+    let rank = input.shape.len();
+    if rank == 0 {
+        return Err(ReduceError::InvalidAxis { axis: 0, rank });
+    }
+    validate_shape(input.shape, output.shape)?;
+    match &options.mask {
+        Some(SoftmaxMask::Bias(mask) | SoftmaxMask::Columns(mask)) => {
+            validate_shape(input.shape, mask.shape)?
+        }
+        None => {}
+    }
+
+    let length = input.shape[rank - 1];
+    let num_rows = input.shape[..rank - 1].iter().product::<usize>();
+
+    let hw_props = &client.properties().hardware;
+    let plane_size = hw_props.plane_size_max;
+    let cube_size = (length as u32)
+        .next_power_of_two()
+        .clamp(plane_size, MAX_CUBE_SIZE.max(plane_size));
+    let use_planes = client.properties().features.plane.contains(Plane::Ops)
+        && hw_props.plane_size_min == hw_props.plane_size_max
+        && cube_size % plane_size == 0;
+    let (cube_dim, accumulator_size) = match use_planes {
+        true => (
+            CubeDim::new_2d(plane_size, cube_size / plane_size),
+            cube_size / plane_size,
+        ),
+        false => (CubeDim::new_1d(cube_size), cube_size),
+    };
+
+    let capacity = length.next_power_of_two();
+    let shared_size = (capacity + 2 * accumulator_size as usize) * size_of::<P::EA>();
+    let resident = (capacity <= MAX_RESIDENT_LENGTH
+        && shared_size <= hw_props.max_shared_memory_size)
+        .then_some(capacity as u32);
+
+    let cube_count = LaunchShape::elementwise(num_rows)
+        .units_per_cube(1)
+        .build::<R>()?
+        .cube_count;
+
+    let (bias, mask) = match &options.mask {
+        Some(SoftmaxMask::Bias(bias)) => (
+            CubeOptionArgs::Some(bias.as_tensor_arg(1)),
+            CubeOptionArgs::None,
+        ),
+        Some(SoftmaxMask::Columns(mask)) => (
+            CubeOptionArgs::None,
+            CubeOptionArgs::Some(mask.as_tensor_arg(1)),
+        ),
+        None => (CubeOptionArgs::None, CubeOptionArgs::None),
+    };
+
+    unsafe {
+        softmax_kernel::launch_unchecked::<P::EI, P::EA, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            bias,
+            mask,
+            ScalarArg::new(P::EA::new(1.0 / options.temperature)),
+            ScalarArg::new(num_rows as u32),
+            SoftmaxParams {
+                resident,
+                use_planes,
+                accumulator_size,
+            },
+        );
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_shape(input_shape: &[usize], shape: &[usize]) -> Result<(), ReduceError> {
+    if input_shape != shape {
+        return Err(ReduceError::MismatchShape {
+            expected_shape: input_shape.to_vec(),
+            output_shape: shape.to_vec(),
+        });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SoftmaxParams {
+    /// The row is kept in a shared memory of the given length between computing its statistics and normalizing it.
+    /// Else, the row is read again from global memory.
+    pub resident: Option<u32>,
+    pub use_planes: bool,
+    /// The number of partial states combined within a cube, one per plane or one per unit.
+    pub accumulator_size: u32,
+}
+
+/// The max and the sum of the exponentials shifted by that max of a partition of a row.
+///
+/// The max starts at the lowest finite value, so masked columns of `-inf` never produce a `NaN`
+/// and an empty or fully masked partition has a sum of 0.
+#[derive(CubeType)]
+pub struct SoftmaxState<F: Float> {
+    pub max: F,
+    pub sum: F,
+}
+
+#[cube]
+impl<F: Float> SoftmaxState<F> {
+    pub fn empty() -> SoftmaxState<F> {
+        SoftmaxState::<F> {
+            max: F::min_value(),
+            sum: F::new(0.0),
+        }
+    }
+
+    /// Add a logit to the partition, rescaling the sum when the max changes.
+    pub fn update(&mut self, logit: F) {
+        let max = Max::max(self.max, logit);
+        self.sum = self.sum * Exp::exp(self.max - max) + Exp::exp(logit - max);
+        self.max = max;
+    }
+
+    pub fn combine(lhs: &SoftmaxState<F>, rhs: &SoftmaxState<F>) -> SoftmaxState<F> {
+        let max = Max::max(lhs.max, rhs.max);
+        SoftmaxState::<F> {
+            max,
+            sum: lhs.sum * Exp::exp(lhs.max - max) + rhs.sum * Exp::exp(rhs.max - max),
+        }
+    }
+
+    /// The state of the union of the partitions of all the units in the plane.
+    pub fn combine_plane(this: &SoftmaxState<F>) -> SoftmaxState<F> {
+        let max = plane_max(this.max);
+        SoftmaxState::<F> {
+            max,
+            sum: plane_sum(this.sum * Exp::exp(this.max - max)),
+        }
+    }
+}
+
+/// Normalize a row of `input` along its last axis for each cube.
+#[cube(launch_unchecked)]
+pub fn softmax_kernel<In: Float, Acc: Float>(
+    input: &Tensor<In>,
+    output: &mut Tensor<In>,
+    bias: &CubeOption<Tensor<In>>,
+    mask: &CubeOption<Tensor<u32>>,
+    inv_temperature: Acc,
+    num_rows: u32,
+    #[comptime] params: SoftmaxParams,
+) {
+    let row = CUBE_POS;
+    if row >= num_rows {
+        terminate!();
+    }
+
+    let axis = input.rank() - 1;
+    let length = input.shape(axis);
+    let input_offset = row_offset(input, row);
+    let output_offset = row_offset(output, row);
+    let bias_offset = match bias {
+        CubeOption::Some(bias) => row_offset(bias, row),
+        CubeOption::None => 0,
+    };
+    let mask_offset = match mask {
+        CubeOption::Some(mask) => row_offset(mask, row),
+        CubeOption::None => 0,
+    };
+
+    let mut cache = SharedMemory::<Acc>::new(comptime!(params.resident.unwrap_or(1)));
+
+    let mut state = SoftmaxState::<Acc>::empty();
+    for i in range_stepped(UNIT_POS, length, CUBE_DIM) {
+        let logit =
+            read_logit::<In, Acc>(input, bias, input_offset, bias_offset, i, inv_temperature);
+        if comptime!(params.resident.is_some()) {
+            cache[i] = logit;
+        }
+        if !is_masked(mask, mask_offset, i) {
+            state.update(logit);
+        }
+    }
+
+    let state = reduce_state::<Acc>(&state, params.use_planes, params.accumulator_size);
+    // The sum is 0 only when all the columns are masked.
+    let scale = select(
+        state.sum > Acc::new(0.0),
+        Acc::new(1.0) / state.sum,
+        Acc::new(0.0),
+    );
+
+    for i in range_stepped(UNIT_POS, length, CUBE_DIM) {
+        let logit = match comptime!(params.resident) {
+            Some(_) => cache[i],
+            None => {
+                read_logit::<In, Acc>(input, bias, input_offset, bias_offset, i, inv_temperature)
+            }
+        };
+        let probability = select(
+            is_masked(mask, mask_offset, i),
+            Acc::new(0.0),
+            Exp::exp(logit - state.max) * scale,
+        );
+        output[output_offset + i * output.stride(axis)] = In::cast_from(probability);
+    }
+}
+
+/// The offset of the first element of a row, numbered in row-major order over all the axes except the last.
+#[cube]
+pub(crate) fn row_offset<E: CubePrimitive>(tensor: &Tensor<E>, row: u32) -> u32 {
+    let rank = tensor.rank();
+    let mut offset = 0;
+    let mut remainder = row;
+    for i in 1..rank {
+        let dim = rank - 1 - i;
+        offset += (remainder % tensor.shape(dim)) * tensor.stride(dim);
+        remainder /= tensor.shape(dim);
+    }
+    offset
+}
+
+#[cube]
+fn read_logit<In: Float, Acc: Float>(
+    input: &Tensor<In>,
+    bias: &CubeOption<Tensor<In>>,
+    input_offset: u32,
+    bias_offset: u32,
+    column: u32,
+    inv_temperature: Acc,
+) -> Acc {
+    let axis = input.rank() - 1;
+    let logit = Acc::cast_from(input[input_offset + column * input.stride(axis)]) * inv_temperature;
+    match bias {
+        CubeOption::Some(bias) => {
+            logit + Acc::cast_from(bias[bias_offset + column * bias.stride(axis)])
+        }
+        CubeOption::None => logit,
+    }
+}
+
+#[cube]
+fn is_masked(mask: &CubeOption<Tensor<u32>>, mask_offset: u32, column: u32) -> bool {
+    match mask {
+        CubeOption::Some(mask) => mask[mask_offset + column * mask.stride(mask.rank() - 1)] != 0,
+        CubeOption::None => false,
+    }
+}
+
+/// Combine the states of all the units of the cube, returning the state of the whole row to every unit.
+#[cube]
+fn reduce_state<F: Float>(
+    state: &SoftmaxState<F>,
+    #[comptime] use_planes: bool,
+    #[comptime] size: u32,
+) -> SoftmaxState<F> {
+    // With planes, the first unit of each plane holds the state of its plane.
+    let state = match comptime!(use_planes) {
+        true => SoftmaxState::<F>::combine_plane(state),
+        false => SoftmaxState::<F> {
+            max: state.max,
+            sum: state.sum,
+        },
+    };
+    let index = match comptime!(use_planes) {
+        true => UNIT_POS_Y,
+        false => UNIT_POS,
+    };
+    let is_writer = match comptime!(use_planes) {
+        true => UNIT_POS_X == 0,
+        false => true.runtime(),
+    };
+
+    let mut maxes = SharedMemory::<F>::new(size);
+    let mut sums = SharedMemory::<F>::new(size);
+    if is_writer {
+        maxes[index] = state.max;
+        sums[index] = state.sum;
+    }
+    sync_cube();
+
+    // Fuse the upper half of the states into the lower half until a single state remains.
+    let mut stride = comptime!(size.next_power_of_two() / 2).runtime();
+    while stride > 0 {
+        if is_writer && index < stride && index + stride < size {
+            let lhs = SoftmaxState::<F> {
+                max: maxes[index],
+                sum: sums[index],
+            };
+            let rhs = SoftmaxState::<F> {
+                max: maxes[index + stride],
+                sum: sums[index + stride],
+            };
+            let fused = SoftmaxState::<F>::combine(&lhs, &rhs);
+            maxes[index] = fused.max;
+            sums[index] = fused.sum;
+        }
+        sync_cube();
+        stride /= 2;
+    }
+
+    SoftmaxState::<F> {
+        max: maxes[0],
+        sum: sums[0],
+    }
+}
@@ -0,0 +1,382 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape, server::Handle};
+
+use crate::instructions::Sum;
+use crate::scan::scan_cube;
+use crate::{ReduceError, scan};
+
+/// The number of units in a cube sorting a tile.
+const CUBE_SIZE: u32 = 256;
+/// The number of consecutive keys handled by each unit of a tile.
+const KEYS_PER_UNIT: u32 = 8;
+/// The number of bits of the digit sorted by each pass.
+const RADIX_BITS: u32 = 4;
+/// The number of passes over the 32 bits of the keys, which must be even
+/// for the sorted keys and values to end up in the original buffers.
+const NUM_PASSES: u32 = 32 / RADIX_BITS;
+
+/// A key sorted by [`radix_sort`], mapped to bits that are ordered like the keys
+/// when compared as unsigned integers.
+#[cube]
+pub trait RadixKey: Numeric + CubeElement {
+    /// The bits of the key, ordered like the keys.
+    fn to_radix(key: Self) -> u32;
+
+    /// The key of the given bits, the inverse of [`to_radix`](RadixKey::to_radix).
+    fn from_radix(bits: u32) -> Self;
+}
+
+#[cube]
+impl RadixKey for u32 {
+    fn to_radix(key: Self) -> u32 {
+        key
+    }
+
+    fn from_radix(bits: u32) -> Self {
+        bits
+    }
+}
+
+#[cube]
+impl RadixKey for f32 {
+    // All the bits of negative floats are flipped to reverse their order, and the sign bit
+    // of positive ones is set to put them after the negative ones.
+    // This is the order of `f32::total_cmp`, where `-0.0` comes before `0.0`.
+    fn to_radix(key: Self) -> u32 {
+        let bits = u32::reinterpret(key);
+        select(bits >= 0x80000000, bits ^ 0xFFFFFFFF, bits | 0x80000000)
+    }
+
+    fn from_radix(bits: u32) -> Self {
+        f32::reinterpret(select(
+            bits >= 0x80000000,
+            bits ^ 0x80000000,
+            bits ^ 0xFFFFFFFF,
+        ))
+    }
+}
+
+/// Sort the `keys` tensor in place, moving the elements of the optional `values` tensor of `u32`
+/// along with their keys.
+///
+/// The sort is stable, so equal keys keep their order, and `descending` puts the largest keys first.
+/// Floats are sorted with the total order of [`f32::total_cmp`].
+/// The tensors are sorted as contiguous buffers, and `values` must have the same shape as `keys`.
+///
+/// Each pass sorts the keys by a digit of 4 bits, from the least significant one:
+/// the digits of each tile of keys are counted, the counts are [scanned](scan) into the offsets
+/// of each tile in the output, and the keys are scattered to their offsets. The keys and values
+/// are moved between the given tensors and buffers of the same size allocated for the sort.
+///
+/// Return an error if the shape of `values` is invalid.
+///
+/// # Example
+///
+/// This examples show how to sort some keys while keeping track of their original position.
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let keys = /* a f32 tensor of shape [n] */;
+/// let indices = /* a u32 tensor with the values [0, 1, ..., n - 1] */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let result = radix_sort::<R, f32>(&client, keys, Some(indices), false);
+/// ```
+pub fn radix_sort<R: Runtime, K: RadixKey>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    keys: TensorHandleRef<R>,
+    values: Option<TensorHandleRef<R>>,
+    descending: bool,
+) -> Result<(), ReduceError> {
+    if let Some(values) = values
+        && values.shape != keys.shape
+    {
+        return Err(ReduceError::MismatchShape {
+            expected_shape: keys.shape.to_vec(),
+            output_shape: values.shape.to_vec(),
+        });
+    }
+
+    let length = keys.shape.iter().product::<usize>();
+    if length == 0 {
+        return Ok(());
+    }
+
+    let params = RadixParams {
+        cube_size: CUBE_SIZE,
+        keys_per_unit: KEYS_PER_UNIT,
+        num_digits: 1 << RADIX_BITS,
+        with_values: values.is_some(),
+    };
+    let num_tiles = length.div_ceil((CUBE_SIZE * KEYS_PER_UNIT) as usize);
+    let cube_dim = CubeDim::new_1d(CUBE_SIZE);
+    let tile_cube_count = LaunchShape::elementwise(num_tiles)
+        .units_per_cube(1)
+        .build::<R>()?
+        .cube_count;
+    let key_cube_count = LaunchShape::elementwise(length)
+        .units_per_cube(CUBE_SIZE)
+        .build::<R>()?
+        .cube_count;
+
+    // The keys are sorted as their radix bits, moved back and forth between two buffers.
+    let shape = [length];
+    let bits_handles = [
+        client.empty(length * size_of::<u32>()),
+        client.empty(length * size_of::<u32>()),
+    ];
+    let bits = [
+        linear_ref::<R>(&bits_handles[0], &shape),
+        linear_ref::<R>(&bits_handles[1], &shape),
+    ];
+
+    // The values are moved between the given tensor and a buffer of the same size,
+    // or between two placeholders when only the keys are sorted.
+    let values_shape = [values.map_or(1, |_| length)];
+    let values_handles = [
+        client.empty(size_of::<u32>()),
+        client.empty(values_shape[0] * size_of::<u32>()),
+    ];
+    let values = [
+        values.unwrap_or(linear_ref::<R>(&values_handles[0], &values_shape)),
+        linear_ref::<R>(&values_handles[1], &values_shape),
+    ];
+
+    let histogram_shape = [num_tiles * params.num_digits as usize];
+    let histogram_handle = client.empty(histogram_shape[0] * size_of::<u32>());
+    let histogram = linear_ref::<R>(&histogram_handle, &histogram_shape);
+
+    unsafe {
+        radix_keys_kernel::launch_unchecked::<K, R>(
+            client,
+            key_cube_count.clone(),
+            cube_dim,
+            keys.as_tensor_arg(1),
+            bits[0].as_tensor_arg(1),
+            descending,
+        );
+    }
+
+    for pass in 0..NUM_PASSES as usize {
+        let (source, target) = (pass % 2, (pass + 1) % 2);
+        let shift = pass as u32 * RADIX_BITS;
+
+        unsafe {
+            radix_histogram_kernel::launch_unchecked::<R>(
+                client,
+                tile_cube_count.clone(),
+                cube_dim,
+                bits[source].as_tensor_arg(1),
+                histogram.as_tensor_arg(1),
+                ScalarArg::new(shift),
+                params,
+            );
+        }
+
+        // The counts are laid out by digit then by tile, so their exclusive scan is the offset
+        // of the keys of each digit of each tile in the sorted output.
+        scan::<R, u32, Sum>(client, histogram, histogram, false, None)?;
+
+        unsafe {
+            radix_scatter_kernel::launch_unchecked::<R>(
+                client,
+                tile_cube_count.clone(),
+                cube_dim,
+                bits[source].as_tensor_arg(1),
+                values[source].as_tensor_arg(1),
+                bits[target].as_tensor_arg(1),
+                values[target].as_tensor_arg(1),
+                histogram.as_tensor_arg(1),
+                ScalarArg::new(shift),
+                params,
+            );
+        }
+    }
+
+    unsafe {
+        radix_restore_kernel::launch_unchecked::<K, R>(
+            client,
+            key_cube_count,
+            cube_dim,
+            bits[0].as_tensor_arg(1),
+            keys.as_tensor_arg(1),
+            descending,
+        );
+    }
+
+    Ok(())
+}
+
+fn linear_ref<'a, R: Runtime>(handle: &'a Handle, shape: &'a [usize]) -> TensorHandleRef<'a, R> {
+    unsafe { TensorHandleRef::from_raw_parts(handle, &[1], shape, size_of::<u32>()) }
+}
+
+/// The comptime parameters of the radix sort kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RadixParams {
+    /// The number of units in a cube, which must be the cube dimension.
+    pub cube_size: u32,
+    /// The number of consecutive keys handled by each unit.
+    pub keys_per_unit: u32,
+    /// The number of values of a digit, a power of two.
+    pub num_digits: u32,
+    /// Whether values are moved along with the keys.
+    pub with_values: bool,
+}
+
+/// Write the radix bits of the `keys`, inverted when sorting in `descending` order.
+#[cube(launch_unchecked)]
+pub fn radix_keys_kernel<K: RadixKey>(
+    keys: &Tensor<K>,
+    bits: &mut Tensor<u32>,
+    #[comptime] descending: bool,
+) {
+    if ABSOLUTE_POS >= keys.len() {
+        terminate!();
+    }
+
+    let mut radix = K::to_radix(keys[ABSOLUTE_POS]);
+    if comptime![descending] {
+        radix = radix ^ 0xFFFFFFFF;
+    }
+    bits[ABSOLUTE_POS] = radix;
+}
+
+/// Write the keys of the radix `bits`, the inverse of [`radix_keys_kernel`].
+#[cube(launch_unchecked)]
+pub fn radix_restore_kernel<K: RadixKey>(
+    bits: &Tensor<u32>,
+    keys: &mut Tensor<K>,
+    #[comptime] descending: bool,
+) {
+    if ABSOLUTE_POS >= keys.len() {
+        terminate!();
+    }
+
+    let mut radix = bits[ABSOLUTE_POS];
+    if comptime![descending] {
+        radix = radix ^ 0xFFFFFFFF;
+    }
+    keys[ABSOLUTE_POS] = K::from_radix(radix);
+}
+
+/// Count the digits at `shift` of the keys of each tile into `histogram`,
+/// where the count of a digit for a tile is at `digit * num_tiles + tile`.
+#[cube(launch_unchecked)]
+pub fn radix_histogram_kernel(
+    keys: &Tensor<u32>,
+    histogram: &mut Tensor<u32>,
+    shift: u32,
+    #[comptime] params: RadixParams,
+) {
+    let tile = CUBE_POS;
+    let num_tiles = histogram.len() / params.num_digits;
+    if tile >= num_tiles {
+        terminate!();
+    }
+
+    let mut counts = SharedMemory::<u32>::new(comptime!(params.num_digits * params.cube_size));
+    let mut totals = SharedMemory::<u32>::new(params.num_digits);
+    let first_key = (tile * params.cube_size + UNIT_POS) * params.keys_per_unit;
+    count_digits(keys, &mut counts, first_key, shift, params);
+    scan_digits(&mut counts, &mut totals, params);
+
+    if UNIT_POS < params.num_digits {
+        histogram[UNIT_POS * num_tiles + tile] = totals[UNIT_POS];
+    }
+}
+
+/// Move the keys of each tile, and their values, to the `offsets` of their digit at `shift`.
+///
+/// The keys of a tile with the same digit are written in the order they are read,
+/// which makes each pass, and so the whole sort, stable.
+#[cube(launch_unchecked)]
+pub fn radix_scatter_kernel(
+    keys: &Tensor<u32>,
+    values: &Tensor<u32>,
+    keys_output: &mut Tensor<u32>,
+    values_output: &mut Tensor<u32>,
+    offsets: &Tensor<u32>,
+    shift: u32,
+    #[comptime] params: RadixParams,
+) {
+    let tile = CUBE_POS;
+    let num_tiles = offsets.len() / params.num_digits;
+    if tile >= num_tiles {
+        terminate!();
+    }
+
+    let mut counts = SharedMemory::<u32>::new(comptime!(params.num_digits * params.cube_size));
+    let mut totals = SharedMemory::<u32>::new(params.num_digits);
+    let first_key = (tile * params.cube_size + UNIT_POS) * params.keys_per_unit;
+    count_digits(keys, &mut counts, first_key, shift, params);
+    scan_digits(&mut counts, &mut totals, params);
+
+    // The offsets of the tile replace its totals, which aren't needed.
+    if UNIT_POS < params.num_digits {
+        totals[UNIT_POS] = offsets[UNIT_POS * num_tiles + tile];
+    }
+    sync_cube();
+
+    for i in 0..params.keys_per_unit {
+        let index = first_key + i;
+        if index < keys.len() {
+            let key = keys[index];
+            let digit = (key >> shift) & comptime!(params.num_digits - 1);
+            let slot = digit * params.cube_size + UNIT_POS;
+            let position = totals[digit] + counts[slot];
+            counts[slot] += 1;
+
+            keys_output[position] = key;
+            if comptime![params.with_values] {
+                values_output[position] = values[index];
+            }
+        }
+    }
+}
+
+/// Count the digits at `shift` of the keys of the unit starting at `first_key`,
+/// where the count of a digit for a unit is at `digit * cube_size + UNIT_POS` in `counts`.
+#[cube]
+fn count_digits(
+    keys: &Tensor<u32>,
+    counts: &mut SharedMemory<u32>,
+    first_key: u32,
+    shift: u32,
+    #[comptime] params: RadixParams,
+) {
+    for digit in 0..params.num_digits {
+        counts[digit * params.cube_size + UNIT_POS] = 0;
+    }
+
+    for i in 0..params.keys_per_unit {
+        let index = first_key + i;
+        if index < keys.len() {
+            let digit = (keys[index] >> shift) & comptime!(params.num_digits - 1);
+            counts[digit * params.cube_size + UNIT_POS] += 1;
+        }
+    }
+    sync_cube();
+}
+
+/// Replace the counts of each digit by their exclusive prefix over the units of the cube,
+/// and write the number of keys of each digit in the tile into `totals`.
+#[cube]
+fn scan_digits(
+    counts: &mut SharedMemory<u32>,
+    totals: &mut SharedMemory<u32>,
+    #[comptime] params: RadixParams,
+) {
+    let mut shared = SharedMemory::<u32>::new(params.cube_size);
+
+    for digit in 0..params.num_digits {
+        let slot = digit * params.cube_size + UNIT_POS;
+        counts[slot] = scan_cube::<u32, Sum>(&mut shared, counts[slot], params.cube_size);
+        if UNIT_POS == 0 {
+            totals[digit] = shared[params.cube_size - 1];
+        }
+
+        // The shared memory is overwritten by the next digit.
+        sync_cube();
+    }
+}
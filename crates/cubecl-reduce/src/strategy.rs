@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::ReduceError;
 
+/// The longest contiguous rows reduced by planes without shared memory when no strategy is given.
+const SHORT_ROW_MAX_LENGTH: usize = 512;
+/// The fewest rows worth reducing with a plane each, enough to keep the device busy without the inter-plane reduction.
+const SHORT_ROW_MIN_COUNT: usize = 1024;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
 pub struct ReduceStrategy {
     /// If true and the compute client support plane instructions,
@@ -13,6 +18,9 @@ pub struct ReduceStrategy {
 
     /// If true, all units within a single cube cooperate to reduce a single item in the output.
     /// Else, each unit or plane (if planes is true) reduce a single item by itself.
+    ///
+    /// With planes and without sharing, the rows too short for a whole plane along a contiguous axis
+    /// are reduced by segments of a plane, up to 4 rows per plane, using only plane collectives.
     pub shared: bool,
 }
 
@@ -39,6 +47,32 @@ impl ReduceStrategy {
             shared,
         }
     }
+
+    /// The strategy used by [`reduce`](crate::reduce) when none is given for reducing the `axis` of `input`.
+    ///
+    /// Many short rows along a contiguous axis, like the per-token norms of activations, are reduced by planes
+    /// without shared memory. Everything else is reduced by whole cubes.
+    pub fn for_input<R: Runtime>(
+        client: &ComputeClient<R::Server, R::Channel>,
+        input: &TensorHandleRef<R>,
+        axis: usize,
+    ) -> Self {
+        let strategy = Self::new::<R>(client, true);
+        let row_count = input
+            .shape
+            .iter()
+            .enumerate()
+            .filter_map(|(i, shape)| (i != axis).then_some(shape))
+            .product::<usize>();
+        let short_rows = input.strides[axis] == 1
+            && input.shape[axis] <= SHORT_ROW_MAX_LENGTH
+            && row_count >= SHORT_ROW_MIN_COUNT;
+
+        Self {
+            shared: !(strategy.use_planes && short_rows),
+            ..strategy
+        }
+    }
 }
 
 fn support_plane<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> bool {
@@ -1,6 +1,7 @@
 #![allow(missing_docs)]
 
 use cubecl_core::prelude::*;
+use cubecl_std::tensor::{IndexOptions, IndexPolicy};
 use rand::{
     SeedableRng,
     distr::{Distribution, Uniform},
@@ -8,7 +9,12 @@ use rand::{
 };
 
 use crate::{
-    ReduceError, ReduceStrategy, instructions::*, precision::ReducePrecision, reduce, shared_sum,
+    CumsumOptions, HistogramElement, HistogramOptions, HistogramOutliers, NormKind, RadixKey,
+    ReduceError, ReduceOp, ReduceOpFamily, ReduceStrategy, ScanInstruction, ScanStrategy,
+    SegmentOffset, SegmentedReduceOp, SoftmaxMask, SoftmaxOptions, cumsum, histogram,
+    instructions::*, layer_norm, mean_var, precision::ReducePrecision, radix_sort, reduce,
+    reduce_axes, reduce_custom, rms_norm, scan, scatter_add_deterministic, segmented_reduce,
+    shared_sum, shared_sum_deterministic, softmax, topk,
 };
 
 // All random values generated for tests will be in the set
@@ -17,6 +23,111 @@ use crate::{
 // also to add multiple similar values to properly test ArgMax and ArgMin.
 const PRECISION: i32 = 4;
 
+// The epsilon added to the variance by the normalization tests.
+const NORM_EPSILON: f32 = 1e-5;
+
+/// The logarithm of the sum of the exponentials of the reduced elements, a custom [`ReduceOp`]
+/// keeping the largest element and the sum of the exponentials shifted by it.
+///
+/// The accumulation is done in `f32` for any precision.
+#[derive(Debug, CubeType, Clone)]
+pub struct LogSumExp;
+
+impl ReduceOpFamily for LogSumExp {
+    type Op<P: ReducePrecision> = Self;
+}
+
+#[derive(CubeType)]
+pub struct LogSumExpState {
+    pub max: Line<f32>,
+    pub sum: Line<f32>,
+}
+
+#[derive(CubeType)]
+pub struct LogSumExpAccumulator {
+    pub max: SharedMemory<Line<f32>>,
+    pub sum: SharedMemory<Line<f32>>,
+}
+
+#[cube]
+impl SharedAccumulator for LogSumExpAccumulator {
+    type Item = LogSumExpState;
+
+    fn allocate(
+        #[comptime] length: u32,
+        #[comptime] line_size: u32,
+        #[comptime] _coordinate: bool,
+    ) -> Self {
+        LogSumExpAccumulator {
+            max: SharedMemory::new_lined(length, line_size),
+            sum: SharedMemory::new_lined(length, line_size),
+        }
+    }
+
+    fn read(accumulator: &Self, index: u32) -> Self::Item {
+        LogSumExpState {
+            max: accumulator.max[index],
+            sum: accumulator.sum[index],
+        }
+    }
+
+    fn write(accumulator: &mut Self, index: u32, item: Self::Item) {
+        accumulator.max[index] = item.max;
+        accumulator.sum[index] = item.sum;
+    }
+}
+
+#[cube]
+impl<P: ReducePrecision> ReduceOp<P> for LogSumExp {
+    type Accumulator = LogSumExpState;
+    type SharedAccumulator = LogSumExpAccumulator;
+
+    fn identity(#[comptime] line_size: u32) -> Self::Accumulator {
+        // The lowest finite max never produces a `NaN` when shifted by another max.
+        LogSumExpState {
+            max: Line::empty(line_size).fill(f32::min_value()),
+            sum: Line::empty(line_size).fill(0.0),
+        }
+    }
+
+    fn neutral_input(#[comptime] line_size: u32) -> Line<P::EI> {
+        Line::empty(line_size).fill(P::EI::min_value())
+    }
+
+    fn map(item: Line<P::EI>) -> Self::Accumulator {
+        let max = Line::<f32>::cast_from(item);
+        LogSumExpState {
+            max,
+            sum: Line::empty(max.size()).fill(1.0),
+        }
+    }
+
+    fn combine(lhs: Self::Accumulator, rhs: Self::Accumulator) -> Self::Accumulator {
+        let max = Max::max(lhs.max, rhs.max);
+        LogSumExpState {
+            max,
+            sum: lhs.sum * Exp::exp(lhs.max - max) + rhs.sum * Exp::exp(rhs.max - max),
+        }
+    }
+
+    fn plane_combine(accumulator: Self::Accumulator) -> Self::Accumulator {
+        let max = plane_max(accumulator.max);
+        LogSumExpState {
+            max,
+            sum: plane_sum(accumulator.sum * Exp::exp(accumulator.max - max)),
+        }
+    }
+
+    fn assign(destination: &mut Self::Accumulator, source: &Self::Accumulator) {
+        destination.max = source.max;
+        destination.sum = source.sum;
+    }
+
+    fn finalize<Out: Numeric>(accumulator: Self::Accumulator) -> Line<Out> {
+        Line::cast_from(accumulator.max + Log::log(accumulator.sum))
+    }
+}
+
 #[macro_export]
 macro_rules! testgen_shared_sum {
     // Generate all the tests for a list of types.
@@ -67,603 +178,3317 @@ macro_rules! testgen_shared_sum {
                     };
                     test.test_shared_sum::<$float, TestRuntime>(&Default::default());
                 }
+
+                #[test]
+                pub fn vector_deterministic() {
+                    let test = cubecl_reduce::test::TestCase {
+                        shape: vec![1024],
+                        stride: vec![1],
+                        axis: None,
+                        strategy: None,
+                    };
+                    test.test_shared_sum_deterministic::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn rank_three_deterministic() {
+                    let test = cubecl_reduce::test::TestCase {
+                        shape: vec![12, 15, 101],
+                        stride: vec![1515, 1, 15],
+                        axis: None,
+                        strategy: None,
+                    };
+                    test.test_shared_sum_deterministic::<$float, TestRuntime>(&Default::default());
+                }
             }
         }
     }
 
 }
 
-// This macro generate all the tests.
 #[macro_export]
-macro_rules! testgen_reduce {
+macro_rules! testgen_mean_var {
     // Generate all the tests for a list of types.
     ([$($float:ident), *]) => {
-        mod test_reduce {
-            use super::*;
-            ::paste::paste! {
-                $(mod [<$float _ty>] {
-                    use super::*;
-
-                    $crate::testgen_reduce!($float);
-                })*
-            }
-        }
-    };
-
-    // Generate all the tests for f32
-    () => {
-        mod test_reduce {
+        mod test_mean_var {
             use super::*;
-            $crate::testgen_reduce!(f32);
+            $(
+                $crate::testgen_mean_var!($float);
+            )*
         }
     };
 
-    // Generate all the tests for a specific float type.
     ($float:ident) => {
-        use cubecl_reduce::test::TestCase;
-        use cubecl_core::prelude::CubeCount;
+        ::paste::paste! {
+            mod [<$float _ty>] {
+                use super::*;
 
-        $crate::impl_test_reduce!(
-            $float,
-            [
-                {
-                    id: "vector_small",
-                    shape: [22],
-                    stride: [1],
-                    axis: 0,
-                },
-                {
-                    id: "vector_large",
-                    shape: [1024],
-                    stride: [1],
-                    axis: 0,
-                },
-                {
-                    id: "parallel_matrix_small",
-                    shape: [4, 8],
-                    stride: [8, 1],
-                    axis: 1,
-                },
-                {
-                    id: "perpendicular_matrix_small",
-                    shape: [4, 8],
-                    stride: [8, 1],
-                    axis: 0,
-                },
-                {
-                    id: "parallel_matrix_large",
-                    shape: [8, 256],
-                    stride: [256, 1],
-                    axis: 1,
-                },
-                {
-                    id: "perpendicular_matrix_large",
-                    shape: [8, 256],
-                    stride: [256, 1],
-                    axis: 0,
-                },
-                {
-                    id: "parallel_rank_three_tensor",
-                    shape: [16, 16, 16],
-                    stride: [1, 256, 16],
-                    axis: 0,
-                },
-                {
-                    id: "perpendicular_rank_three_tensor",
-                    shape: [16, 16, 16],
-                    stride: [1, 256, 16],
-                    axis: 1,
-                },
-                {
-                    id: "perpendicular_rank_three_tensor_unexact_shape",
-                    shape: [11, 12, 13],
-                    stride: [156, 13, 1],
-                    axis: 1,
-                },
-                {
-                    id: "parallel_rank_three_tensor_unexact_shape",
-                    shape: [11, 12, 13],
-                    stride: [156, 13, 1],
-                    axis: 2,
-                },
-                {
-                    id: "parallel_rank_four_tensor",
-                    shape: [4, 4, 4, 4],
-                    stride: [1, 16, 64, 4],
-                    axis: 0,
-                },
-                {
-                    id: "perpendicular_rank_four_tensor",
-                    shape: [4, 4, 4, 4],
-                    stride: [1, 16, 64, 4],
-                    axis: 1,
-                },
-                {
-                    id: "decreasing_rank_four_tensor",
-                    shape: [4, 4, 4, 4],
-                    stride: [64, 16, 4, 1],
-                    axis: 3,
-                },
-                {
-                    id: "parallel_matrix_with_jumps",
-                    shape: [8, 8],
-                    stride: [64, 1],
-                    axis: 1,
-                },
-                {
-                    id: "perpendicular_matrix_with_jumps",
-                    shape: [8, 8],
-                    stride: [64, 1],
-                    axis: 0,
-                },
-                {
-                    id: "broadcast_slice_0",
-                    shape: [4, 32],
-                    stride: [0, 1],
-                    axis: 0,
+                #[test]
+                pub fn short_rows() {
+                    let test = cubecl_reduce::test::MeanVarTestCase {
+                        shape: vec![4, 37],
+                        stride: vec![37, 1],
+                        axis: 1,
+                        unbiased: true,
+                    };
+                    test.test_mean_var::<$float, TestRuntime>(&Default::default());
                 }
-            ]
-        );
-    };
-}
 
-// For a given tensor description and cube settings
-// run the tests for `Sum`, `Prod`, `Mean`, `ArgMax` and `ArgMin`
-// for all strategies.
-// For each test, a reference reduction is computed on the CPU to compare the outcome of the kernel.
-#[macro_export]
-macro_rules! impl_test_reduce {
-    (
-        $float:ident,
-        [
-            $(
-                {
-                    id: $id:literal,
-                    shape: $shape:expr,
-                    stride: $stride:expr,
-                    axis: $axis:expr,
-                }
-            ),*
-        ]
-    ) => {
-        ::paste::paste! {
-            $(
-                $crate::impl_test_reduce_with_strategy!{
-                    $float,
-                    {
-                        id: $id,
-                        shape: $shape,
-                        stride: $stride,
-                        axis: $axis,
-                    },
-                    [ use_planes: false, shared: false ],
-                    [ use_planes: true, shared: false ],
-                    [ use_planes: false, shared: true ],
-                    [ use_planes: true, shared: true ]
+                #[test]
+                pub fn columns() {
+                    let test = cubecl_reduce::test::MeanVarTestCase {
+                        shape: vec![300, 6],
+                        stride: vec![6, 1],
+                        axis: 0,
+                        unbiased: false,
+                    };
+                    test.test_mean_var::<$float, TestRuntime>(&Default::default());
                 }
-            )*
-        }
-    };
-}
 
-#[macro_export]
-macro_rules! impl_test_reduce_with_strategy {
-    (
-        $float:ident,
-        {
-            id: $id:literal,
-            shape: $shape:expr,
-            stride: $stride:expr,
-            axis: $axis:expr,
-        },
-        $([use_planes: $use_planes:expr, shared: $shared:expr]),*
-    ) => {
-        ::paste::paste! {
-            $(
                 #[test]
-                pub fn [< argmax_plane_ $use_planes _shared_ $shared _ $id >]() {
-                    let test = TestCase {
-                        shape: $shape.into(),
-                        stride: $stride.into(),
-                        axis: Some($axis),
-                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                pub fn long_row_split_over_cubes() {
+                    let test = cubecl_reduce::test::MeanVarTestCase {
+                        shape: vec![1, 50000],
+                        stride: vec![50000, 1],
+                        axis: 1,
+                        unbiased: true,
                     };
-                    test.test_argmax::<$float, TestRuntime>(&Default::default());
+                    test.test_mean_var::<$float, TestRuntime>(&Default::default());
                 }
 
-
                 #[test]
-                pub fn [< argmin_plane_ $use_planes _shared_ $shared _ $id >]() {
-                    let test = TestCase {
-                        shape: $shape.into(),
-                        stride: $stride.into(),
-                        axis: Some($axis),
-                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                pub fn rank_three_middle_axis() {
+                    let test = cubecl_reduce::test::MeanVarTestCase {
+                        shape: vec![3, 1000, 5],
+                        stride: vec![5000, 5, 1],
+                        axis: 1,
+                        unbiased: false,
                     };
-                    test.test_argmin::<$float, TestRuntime>(&Default::default());
+                    test.test_mean_var::<$float, TestRuntime>(&Default::default());
                 }
 
                 #[test]
-                pub fn [< mean_plane_ $use_planes _shared_ $shared _ $id >]() {
-                    let test = TestCase {
+                pub fn many_rows() {
+                    let test = cubecl_reduce::test::MeanVarTestCase {
+                        shape: vec![2048, 10],
+                        stride: vec![1, 2048],
+                        axis: 1,
+                        unbiased: true,
+                    };
+                    test.test_mean_var::<$float, TestRuntime>(&Default::default());
+                }
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! testgen_softmax {
+    // Generate all the tests for a list of types.
+    ([$($float:ident), *]) => {
+        mod test_softmax {
+            use super::*;
+            $(
+                $crate::testgen_softmax!($float);
+            )*
+        }
+    };
+
+    ($float:ident) => {
+        ::paste::paste! {
+            mod [<$float _ty>] {
+                use super::*;
+                use cubecl_reduce::test::{SoftmaxTestCase, SoftmaxTestMask};
+
+                #[test]
+                pub fn short_rows() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![6, 13],
+                        temperature: 1.0,
+                        mask: SoftmaxTestMask::None,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn attention_rows() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![2, 3, 1024],
+                        temperature: 1.0,
+                        mask: SoftmaxTestMask::None,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn long_rows_streaming() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![3, 20000],
+                        temperature: 4.0,
+                        mask: SoftmaxTestMask::None,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn low_temperature() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![4, 100],
+                        temperature: 0.5,
+                        mask: SoftmaxTestMask::None,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn bias_with_fully_masked_row() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![4, 37],
+                        temperature: 1.0,
+                        mask: SoftmaxTestMask::Bias,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn skipped_columns_with_fully_masked_row() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![4, 300],
+                        temperature: 1.0,
+                        mask: SoftmaxTestMask::Columns,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn long_rows_streaming_with_bias() {
+                    let test = SoftmaxTestCase {
+                        shape: vec![2, 20000],
+                        temperature: 4.0,
+                        mask: SoftmaxTestMask::Bias,
+                    };
+                    test.test_softmax::<$float, TestRuntime>(&Default::default());
+                }
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! testgen_norm {
+    // Generate all the tests for a list of types.
+    ([$($float:ident), *]) => {
+        mod test_norm {
+            use super::*;
+            $(
+                $crate::testgen_norm!($float);
+            )*
+        }
+    };
+
+    ($float:ident) => {
+        ::paste::paste! {
+            mod [<$float _ty>] {
+                use super::*;
+                use cubecl_reduce::NormKind;
+                use cubecl_reduce::test::NormTestCase;
+
+                #[test]
+                pub fn plane_rows() {
+                    let test = NormTestCase {
+                        shape: vec![64, 24],
+                        kind: NormKind::LayerNorm,
+                        affine: true,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn plane_rows_rms() {
+                    let test = NormTestCase {
+                        shape: vec![64, 24],
+                        kind: NormKind::RmsNorm,
+                        affine: true,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn odd_length_without_affine() {
+                    let test = NormTestCase {
+                        shape: vec![5, 37],
+                        kind: NormKind::LayerNorm,
+                        affine: false,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn resident_rows() {
+                    let test = NormTestCase {
+                        shape: vec![2, 3, 2048],
+                        kind: NormKind::LayerNorm,
+                        affine: true,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn resident_rows_rms_without_gamma() {
+                    let test = NormTestCase {
+                        shape: vec![6, 1000],
+                        kind: NormKind::RmsNorm,
+                        affine: false,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn long_rows_streaming() {
+                    let test = NormTestCase {
+                        shape: vec![3, 20000],
+                        kind: NormKind::LayerNorm,
+                        affine: true,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn long_rows_streaming_rms() {
+                    let test = NormTestCase {
+                        shape: vec![3, 20000],
+                        kind: NormKind::RmsNorm,
+                        affine: true,
+                    };
+                    test.test_norm::<$float, TestRuntime>(&Default::default());
+                }
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! testgen_topk {
+    // Generate all the tests for a list of types.
+    ([$($float:ident), *]) => {
+        mod test_topk {
+            use super::*;
+            $(
+                $crate::testgen_topk!($float);
+            )*
+        }
+    };
+
+    ($float:ident) => {
+        ::paste::paste! {
+            mod [<$float _ty>] {
+                use super::*;
+                use cubecl_reduce::test::TopKTestCase;
+
+                #[test]
+                pub fn short_rows() {
+                    let test = TopKTestCase {
+                        shape: vec![5, 37],
+                        axis: 1,
+                        k: 5,
+                    };
+                    test.test_topk::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn k_equal_to_row_width() {
+                    let test = TopKTestCase {
+                        shape: vec![3, 300],
+                        axis: 1,
+                        k: 300,
+                    };
+                    test.test_topk::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn k_one_matches_argmax() {
+                    let test = TopKTestCase {
+                        shape: vec![7, 5000],
+                        axis: 1,
+                        k: 1,
+                    };
+                    test.test_topk::<$float, TestRuntime>(&Default::default());
+                    test.test_k_one_matches_argmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn rows_merged_over_chunks() {
+                    let test = TopKTestCase {
+                        shape: vec![3, 100_003],
+                        axis: 1,
+                        k: 50,
+                    };
+                    test.test_topk::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn largest_k_over_wide_rows() {
+                    let test = TopKTestCase {
+                        shape: vec![2, 1 << 18],
+                        axis: 1,
+                        k: 1024,
+                    };
+                    test.test_topk::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn not_last_axis() {
+                    let test = TopKTestCase {
+                        shape: vec![4, 3000, 3],
+                        axis: 1,
+                        k: 10,
+                    };
+                    test.test_topk::<$float, TestRuntime>(&Default::default());
+                }
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! testgen_scan {
+    () => {
+        mod test_scan {
+            use super::*;
+
+            $crate::impl_test_scan!(lookback, DecoupledLookback);
+            $crate::impl_test_scan!(reduce_then_scan, ReduceThenScan);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_scan {
+    ($name:ident, $strategy:ident) => {
+        mod $name {
+            use super::*;
+
+            // The tiles hold 1024 lines, so the lengths divisible by 4 have tiles of 4096 elements
+            // on runtimes supporting lines of 4 elements.
+            $crate::impl_test_scan!(
+                $strategy,
+                [
+                    empty: 0,
+                    single: 1,
+                    tile: 1024,
+                    tile_plus_one: 1025,
+                    lined_tile: 4096,
+                    lined_tile_plus_one: 4097,
+                    many_millions: 1 << 22,
+                    many_millions_plus_three: (1 << 22) + 3
+                ]
+            );
+        }
+    };
+
+    ($strategy:ident, [$($case:ident: $length:expr),*]) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [<$case _sum_inclusive_f32>]() {
+                    let test = cubecl_reduce::test::ScanTestCase {
+                        length: $length,
+                        inclusive: true,
+                        in_place: false,
+                        strategy: cubecl_reduce::ScanStrategy::$strategy,
+                    };
+                    test.test_sum::<f32, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [<$case _sum_exclusive_u32>]() {
+                    let test = cubecl_reduce::test::ScanTestCase {
+                        length: $length,
+                        inclusive: false,
+                        in_place: false,
+                        strategy: cubecl_reduce::ScanStrategy::$strategy,
+                    };
+                    test.test_sum::<u32, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [<$case _max_exclusive_i32>]() {
+                    let test = cubecl_reduce::test::ScanTestCase {
+                        length: $length,
+                        inclusive: false,
+                        in_place: false,
+                        strategy: cubecl_reduce::ScanStrategy::$strategy,
+                    };
+                    test.test_max::<i32, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [<$case _sum_inclusive_in_place_i32>]() {
+                    let test = cubecl_reduce::test::ScanTestCase {
+                        length: $length,
+                        inclusive: true,
+                        in_place: true,
+                        strategy: cubecl_reduce::ScanStrategy::$strategy,
+                    };
+                    test.test_sum::<i32, TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! testgen_cumsum {
+    () => {
+        mod test_cumsum {
+            use super::*;
+
+            // The rows of up to 1024 elements are scanned by a single cube.
+            $crate::impl_test_cumsum!([
+                single_element: [3, 1], [1, 1], 1;
+                short_rows: [7, 100], [100, 1], 1;
+                tile_minus_one: [3, 1023], [1023, 1], 1;
+                tile: [3, 1024], [1024, 1], 1;
+                tile_plus_one: [3, 1025], [1025, 1], 1;
+                many_tiles: [2, 5000], [5000, 1], 1;
+                many_tiles_of_totals: [1, (1 << 20) + 7], [(1 << 20) + 7, 1], 1;
+                first_axis: [1500, 3], [3, 1], 0;
+                middle_axis: [4, 2000, 3], [6000, 3, 1], 1;
+                column_major: [5, 3000], [1, 5], 1
+            ]);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_cumsum {
+    ([$($case:ident: $shape:expr, $stride:expr, $axis:expr);*]) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [<$case _inclusive_f32>]() {
+                    let test = cubecl_reduce::test::CumsumTestCase {
                         shape: $shape.into(),
                         stride: $stride.into(),
-                        axis: Some($axis),
-                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                        axis: $axis,
+                        inclusive: true,
+                        reverse: false,
                     };
-                    test.test_mean::<$float, TestRuntime>(&Default::default());
+                    test.test_cumsum::<f32, TestRuntime>(&Default::default());
                 }
 
                 #[test]
-                pub fn [< prod_plane_ $use_planes _shared_ $shared _ $id >]() {
-                    let test = TestCase {
+                pub fn [<$case _exclusive_i32>]() {
+                    let test = cubecl_reduce::test::CumsumTestCase {
                         shape: $shape.into(),
                         stride: $stride.into(),
-                        axis: Some($axis),
-                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                        axis: $axis,
+                        inclusive: false,
+                        reverse: false,
                     };
-                    test.test_prod::<$float, TestRuntime>(&Default::default());
+                    test.test_cumsum::<i32, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [<$case _reverse_inclusive_u32>]() {
+                    let test = cubecl_reduce::test::CumsumTestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: $axis,
+                        inclusive: true,
+                        reverse: true,
+                    };
+                    test.test_cumsum::<u32, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [<$case _reverse_exclusive_f32>]() {
+                    let test = cubecl_reduce::test::CumsumTestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: $axis,
+                        inclusive: false,
+                        reverse: true,
+                    };
+                    test.test_cumsum::<f32, TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! testgen_radix_sort {
+    () => {
+        mod test_radix_sort {
+            use super::*;
+
+            $crate::impl_test_radix_sort!(u32, [
+                empty: 0, Random, false, false;
+                single: 1, Random, true, false;
+                random: 1_000_003, Random, false, false;
+                random_with_values: (1 << 20) + 7, Random, true, false;
+                random_descending_with_values: 300_007, Random, true, true;
+                few_distinct_with_values: 100_001, FewDistinct, true, false;
+                few_distinct_descending_with_values: 100_001, FewDistinct, true, true;
+                sorted: 65_537, Sorted, false, false;
+                sorted_descending_with_values: 65_537, Sorted, true, true;
+                all_equal_with_values: 4099, AllEqual, true, false
+            ]);
+
+            $crate::impl_test_radix_sort!(f32, [
+                random: 300_007, Random, false, false;
+                random_descending_with_values: 300_007, Random, true, true;
+                few_distinct_with_values: 100_001, FewDistinct, true, false;
+                sorted: 65_537, Sorted, false, false;
+                all_equal_with_values: 4099, AllEqual, true, true
+            ]);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_radix_sort {
+    ($key:ident, [$($case:ident: $length:expr, $keys:ident, $with_values:expr, $descending:expr);*]) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [<$key _ $case>]() {
+                    let test = cubecl_reduce::test::RadixSortTestCase {
+                        length: $length,
+                        keys: cubecl_reduce::test::RadixSortTestKeys::$keys,
+                        with_values: $with_values,
+                        descending: $descending,
+                    };
+                    test.[<test_ $key>]::<TestRuntime>(&Default::default());
                 }
+            )*
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! testgen_scatter_add_deterministic {
+    () => {
+        mod test_scatter_add_deterministic {
+            use super::*;
+
+            $crate::impl_test_scatter_add_deterministic!([
+                embedding_clamp: [100, 16], 0, [64, 32], Clamp;
+                middle_axis_check: [3, 50, 7], 1, [600], Check;
+                last_axis_wrap: [8, 33], 1, [5, 40], Wrap
+            ]);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_scatter_add_deterministic {
+    ([$($case:ident: [$($target:expr),*], $axis:expr, [$($indices:expr),*], $policy:ident);*]) => {
+        $(
+            #[test]
+            pub fn $case() {
+                let test = cubecl_reduce::test::ScatterAddTestCase {
+                    target_shape: vec![$($target),*],
+                    axis: $axis,
+                    indices_shape: vec![$($indices),*],
+                    policy: cubecl_std::tensor::IndexPolicy::$policy,
+                };
+                test.test_f32::<TestRuntime>(&Default::default());
+            }
+        )*
+    };
+}
+
+#[macro_export]
+macro_rules! testgen_histogram {
+    () => {
+        mod test_histogram {
+            use super::*;
+
+            $crate::impl_test_histogram!(u8, [
+                uniform: 1_000_003, Uniform, 256, Clamp;
+                skewed: 1_000_003, Skewed, 256, Clamp;
+                few_bins_skewed_overflow: 65_537, Skewed, 16, Overflow
+            ]);
+
+            $crate::impl_test_histogram!(u32, [
+                empty: 0, Uniform, 256, Clamp;
+                single: 1, Uniform, 256, Overflow;
+                uniform: (1 << 20) + 3, Uniform, 1000, Clamp;
+                skewed_overflow: (1 << 20) + 3, Skewed, 100, Overflow;
+                global_atomics: 300_007, Skewed, 1 << 16, Overflow
+            ]);
+
+            $crate::impl_test_histogram!(f32, [
+                uniform: (1 << 20) + 3, Uniform, 256, Clamp;
+                uniform_overflow: (1 << 20) + 3, Uniform, 256, Overflow;
+                skewed: 1_000_003, Skewed, 64, Clamp;
+                skewed_overflow: 1_000_003, Skewed, 64, Overflow
+            ]);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_histogram {
+    ($elem:ident, [$($case:ident: $length:expr, $data:ident, $num_bins:expr, $outliers:ident);*]) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [<$elem _ $case>]() {
+                    let test = cubecl_reduce::test::HistogramTestCase {
+                        length: $length,
+                        data: cubecl_reduce::test::HistogramTestData::$data,
+                        num_bins: $num_bins,
+                        outliers: cubecl_reduce::HistogramOutliers::$outliers,
+                    };
+                    test.[<test_ $elem>]::<TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! testgen_segmented_reduce {
+    () => {
+        mod test_segmented_reduce {
+            use super::*;
+
+            $crate::impl_test_segmented_reduce!(f32, [
+                uniform_sum: 3000, Uniform, Sum;
+                uniform_min: 3000, Uniform, Min;
+                uniform_max: 3000, Uniform, Max;
+                uniform_mean: 3000, Uniform, Mean;
+                one_huge_sum: 5000, OneHuge, Sum;
+                one_huge_min: 5000, OneHuge, Min;
+                one_huge_mean: 5000, OneHuge, Mean;
+                single_huge_max: 1, OneHuge, Max;
+                all_empty_sum: 4000, AllEmpty, Sum;
+                all_empty_min: 4000, AllEmpty, Min;
+                all_empty_max: 4000, AllEmpty, Max;
+                skewed_sum: 2000, Skewed, Sum;
+                skewed_max: 2000, Skewed, Max;
+                skewed_mean: 2000, Skewed, Mean
+            ]);
+
+            $crate::impl_test_segmented_reduce!(f16, [
+                uniform_sum: 3000, Uniform, Sum;
+                one_huge_sum: 5000, OneHuge, Sum;
+                skewed_mean: 2000, Skewed, Mean
+            ]);
+
+            $crate::impl_test_segmented_reduce!(u64_offsets, [
+                one_huge_sum: 5000, OneHuge, Sum;
+                skewed_min: 2000, Skewed, Min
+            ]);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_segmented_reduce {
+    ($kind:ident, [$($case:ident: $num_segments:expr, $data:ident, $op:ident);*]) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [<$kind _ $case>]() {
+                    let test = cubecl_reduce::test::SegmentedReduceTestCase {
+                        num_segments: $num_segments,
+                        data: cubecl_reduce::test::SegmentedReduceTestData::$data,
+                        op: cubecl_reduce::SegmentedReduceOp::$op,
+                    };
+                    test.[<test_ $kind>]::<TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+// This macro generate all the tests.
+#[macro_export]
+macro_rules! testgen_reduce {
+    // Generate all the tests for a list of types.
+    ([$($float:ident), *]) => {
+        mod test_reduce {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$float _ty>] {
+                    use super::*;
+
+                    $crate::testgen_reduce!($float);
+                })*
+            }
+        }
+    };
+
+    // Generate all the tests for f32
+    () => {
+        mod test_reduce {
+            use super::*;
+            $crate::testgen_reduce!(f32);
+        }
+    };
+
+    // Generate all the tests for a specific float type.
+    ($float:ident) => {
+        use cubecl_reduce::test::TestCase;
+        use cubecl_core::prelude::CubeCount;
+
+        $crate::impl_test_reduce!(
+            $float,
+            [
+                {
+                    id: "vector_small",
+                    shape: [22],
+                    stride: [1],
+                    axis: 0,
+                },
+                {
+                    id: "vector_large",
+                    shape: [1024],
+                    stride: [1],
+                    axis: 0,
+                },
+                {
+                    id: "parallel_matrix_small",
+                    shape: [4, 8],
+                    stride: [8, 1],
+                    axis: 1,
+                },
+                {
+                    id: "perpendicular_matrix_small",
+                    shape: [4, 8],
+                    stride: [8, 1],
+                    axis: 0,
+                },
+                {
+                    id: "parallel_matrix_large",
+                    shape: [8, 256],
+                    stride: [256, 1],
+                    axis: 1,
+                },
+                {
+                    id: "perpendicular_matrix_large",
+                    shape: [8, 256],
+                    stride: [256, 1],
+                    axis: 0,
+                },
+                {
+                    id: "parallel_rank_three_tensor",
+                    shape: [16, 16, 16],
+                    stride: [1, 256, 16],
+                    axis: 0,
+                },
+                {
+                    id: "perpendicular_rank_three_tensor",
+                    shape: [16, 16, 16],
+                    stride: [1, 256, 16],
+                    axis: 1,
+                },
+                {
+                    id: "perpendicular_rank_three_tensor_unexact_shape",
+                    shape: [11, 12, 13],
+                    stride: [156, 13, 1],
+                    axis: 1,
+                },
+                {
+                    id: "parallel_rank_three_tensor_unexact_shape",
+                    shape: [11, 12, 13],
+                    stride: [156, 13, 1],
+                    axis: 2,
+                },
+                {
+                    id: "parallel_rank_four_tensor",
+                    shape: [4, 4, 4, 4],
+                    stride: [1, 16, 64, 4],
+                    axis: 0,
+                },
+                {
+                    id: "perpendicular_rank_four_tensor",
+                    shape: [4, 4, 4, 4],
+                    stride: [1, 16, 64, 4],
+                    axis: 1,
+                },
+                {
+                    id: "permuted_rank_four_tensor_axis_0",
+                    shape: [3, 5, 4, 6],
+                    stride: [1, 72, 3, 12],
+                    axis: 0,
+                },
+                {
+                    id: "permuted_rank_four_tensor_axis_1",
+                    shape: [3, 5, 4, 6],
+                    stride: [1, 72, 3, 12],
+                    axis: 1,
+                },
+                {
+                    id: "permuted_rank_four_tensor_axis_2",
+                    shape: [3, 5, 4, 6],
+                    stride: [1, 72, 3, 12],
+                    axis: 2,
+                },
+                {
+                    id: "permuted_rank_four_tensor_axis_3",
+                    shape: [3, 5, 4, 6],
+                    stride: [1, 72, 3, 12],
+                    axis: 3,
+                },
+                {
+                    id: "unit_axis",
+                    shape: [4, 1, 16],
+                    stride: [16, 16, 1],
+                    axis: 1,
+                },
+                {
+                    id: "column_sums",
+                    shape: [64, 96],
+                    stride: [96, 1],
+                    axis: 0,
+                },
+                {
+                    id: "decreasing_rank_four_tensor",
+                    shape: [4, 4, 4, 4],
+                    stride: [64, 16, 4, 1],
+                    axis: 3,
+                },
+                {
+                    id: "parallel_matrix_with_jumps",
+                    shape: [8, 8],
+                    stride: [64, 1],
+                    axis: 1,
+                },
+                {
+                    id: "perpendicular_matrix_with_jumps",
+                    shape: [8, 8],
+                    stride: [64, 1],
+                    axis: 0,
+                },
+                {
+                    id: "broadcast_slice_0",
+                    shape: [4, 32],
+                    stride: [0, 1],
+                    axis: 0,
+                }
+            ]
+        );
+
+        // Short rows reduced by the segments of a plane, with a number of rows
+        // that doesn't fill the last plane.
+        $crate::impl_test_reduce_rows!(
+            $float,
+            [1, 3, 8, 17, 32, 33, 64, 100, 128, 255, 256, 512, 1000, 4096]
+        );
+
+        #[test]
+        pub fn sum_short_rows_default_strategy() {
+            let test = TestCase {
+                shape: vec![4097, 128],
+                stride: vec![128, 1],
+                axis: Some(1),
+                strategy: None,
+            };
+            test.test_sum::<$float, TestRuntime>(&Default::default());
+        }
+
+        $crate::impl_test_reduce_axes!(
+            $float,
+            [
+                {
+                    id: "to_scalar",
+                    shape: [8, 16, 32],
+                    stride: [512, 32, 1],
+                    axes: [0, 1, 2],
+                    keep_dims: false,
+                },
+                {
+                    id: "to_scalar_unordered_axes",
+                    shape: [6, 100],
+                    stride: [1, 6],
+                    axes: [1, 0],
+                    keep_dims: true,
+                },
+                {
+                    id: "leading_axes",
+                    shape: [4, 6, 5, 8],
+                    stride: [240, 40, 8, 1],
+                    axes: [0, 1, 2],
+                    keep_dims: true,
+                },
+                {
+                    id: "leading_axes_many_outputs",
+                    shape: [3, 4, 64, 32],
+                    stride: [8192, 2048, 32, 1],
+                    axes: [0, 1],
+                    keep_dims: false,
+                },
+                {
+                    id: "non_adjacent_axes",
+                    shape: [6, 5, 7, 4],
+                    stride: [140, 28, 4, 1],
+                    axes: [1, 3],
+                    keep_dims: false,
+                },
+                {
+                    id: "non_adjacent_axes_permuted",
+                    shape: [6, 5, 7, 4],
+                    stride: [1, 168, 6, 42],
+                    axes: [0, 2],
+                    keep_dims: true,
+                }
+            ]
+        );
+
+        #[test]
+        pub fn sum_empty_output() {
+            let test = TestCase {
+                shape: vec![0, 16, 8],
+                stride: vec![128, 8, 1],
+                axis: Some(1),
+                strategy: None,
+            };
+            test.test_empty_output::<$float, TestRuntime>(&Default::default());
+        }
+
+        #[test]
+        pub fn reduce_axes_duplicate_axis() {
+            let test = cubecl_reduce::test::ReduceAxesTestCase {
+                shape: vec![4, 8],
+                stride: vec![8, 1],
+                axes: vec![1, 1],
+                keep_dims: false,
+            };
+            test.test_duplicate_axis::<$float, TestRuntime>(&Default::default());
+        }
+    };
+}
+
+// For a given tensor description and set of axes
+// run the multi-axis tests for `Sum`, `Mean` and `ArgMax`.
+// For each test, a reference reduction is computed on the CPU to compare the outcome of the kernel.
+#[macro_export]
+macro_rules! impl_test_reduce_axes {
+    (
+        $float:ident,
+        [
+            $(
+                {
+                    id: $id:literal,
+                    shape: $shape:expr,
+                    stride: $stride:expr,
+                    axes: $axes:expr,
+                    keep_dims: $keep_dims:expr,
+                }
+            ),*
+        ]
+    ) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [< argmax_axes_ $id >]() {
+                    let test = cubecl_reduce::test::ReduceAxesTestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axes: $axes.into(),
+                        keep_dims: $keep_dims,
+                    };
+                    test.test_argmax::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< mean_axes_ $id >]() {
+                    let test = cubecl_reduce::test::ReduceAxesTestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axes: $axes.into(),
+                        keep_dims: $keep_dims,
+                    };
+                    test.test_mean::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< sum_axes_ $id >]() {
+                    let test = cubecl_reduce::test::ReduceAxesTestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axes: $axes.into(),
+                        keep_dims: $keep_dims,
+                    };
+                    test.test_sum::<$float, TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+// For each row length, run the tests for `Sum`, `Mean` and `ArgMax`
+// reducing 67 contiguous rows with planes and without shared memory.
+#[macro_export]
+macro_rules! impl_test_reduce_rows {
+    ($float:ident, [$($length:literal),*]) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [< sum_rows_ $length >]() {
+                    let test = TestCase {
+                        shape: vec![67, $length],
+                        stride: vec![$length, 1],
+                        axis: Some(1),
+                        strategy: Some($crate::ReduceStrategy { use_planes: true, shared: false }),
+                    };
+                    test.test_sum::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< mean_rows_ $length >]() {
+                    let test = TestCase {
+                        shape: vec![67, $length],
+                        stride: vec![$length, 1],
+                        axis: Some(1),
+                        strategy: Some($crate::ReduceStrategy { use_planes: true, shared: false }),
+                    };
+                    test.test_mean::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< argmax_rows_ $length >]() {
+                    let test = TestCase {
+                        shape: vec![67, $length],
+                        stride: vec![$length, 1],
+                        axis: Some(1),
+                        strategy: Some($crate::ReduceStrategy { use_planes: true, shared: false }),
+                    };
+                    test.test_argmax::<$float, TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+// For a given tensor description and cube settings
+// run the tests for `Sum`, `Prod`, `Mean`, `ArgMax` and `ArgMin`
+// for all strategies.
+// For each test, a reference reduction is computed on the CPU to compare the outcome of the kernel.
+#[macro_export]
+macro_rules! impl_test_reduce {
+    (
+        $float:ident,
+        [
+            $(
+                {
+                    id: $id:literal,
+                    shape: $shape:expr,
+                    stride: $stride:expr,
+                    axis: $axis:expr,
+                }
+            ),*
+        ]
+    ) => {
+        ::paste::paste! {
+            $(
+                $crate::impl_test_reduce_with_strategy!{
+                    $float,
+                    {
+                        id: $id,
+                        shape: $shape,
+                        stride: $stride,
+                        axis: $axis,
+                    },
+                    [ use_planes: false, shared: false ],
+                    [ use_planes: true, shared: false ],
+                    [ use_planes: false, shared: true ],
+                    [ use_planes: true, shared: true ]
+                }
+            )*
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_test_reduce_with_strategy {
+    (
+        $float:ident,
+        {
+            id: $id:literal,
+            shape: $shape:expr,
+            stride: $stride:expr,
+            axis: $axis:expr,
+        },
+        $([use_planes: $use_planes:expr, shared: $shared:expr]),*
+    ) => {
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [< argmax_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_argmax::<$float, TestRuntime>(&Default::default());
+                }
+
+
+                #[test]
+                pub fn [< argmin_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_argmin::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< argmax_ties_and_nans_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_argmax_ties_and_nans::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< argmin_ties_and_nans_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_argmin_ties_and_nans::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< mean_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_mean::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< prod_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_prod::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< custom_sum_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_custom_sum::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< logsumexp_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_logsumexp::<$float, TestRuntime>(&Default::default());
+                }
+
+                #[test]
+                pub fn [< sum_plane_ $use_planes _shared_ $shared _ $id >]() {
+                    let test = TestCase {
+                        shape: $shape.into(),
+                        stride: $stride.into(),
+                        axis: Some($axis),
+                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
+                    };
+                    test.test_sum::<$float, TestRuntime>(&Default::default());
+                }
+            )*
+        }
+    };
+}
+
+#[derive(Debug)]
+pub struct TestCase {
+    pub shape: Vec<usize>,
+    pub stride: Vec<usize>,
+    pub axis: Option<usize>,
+    pub strategy: Option<ReduceStrategy>,
+}
+
+impl TestCase {
+    pub fn test_argmax<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        self.run_argmax_test::<F, R>(device, input_values)
+    }
+
+    pub fn test_argmax_ties_and_nans<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.ties_and_nans_input_values();
+        self.run_argmax_test::<F, R>(device, input_values)
+    }
+
+    fn run_argmax_test<F, R>(&self, device: &R::Device, input_values: Vec<F::EI>)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => vec![0; input_values.len()],
+            _ => self.cpu_argmax(&input_values),
+        };
+        self.run_reduce_test::<F, u32, R, ArgMax>(device, input_values, expected_values)
+    }
+
+    fn cpu_argmax<F: Float>(&self, values: &[F]) -> Vec<u32> {
+        let mut expected = vec![(F::min_value(), 0_u32); self.num_output_values()];
+        for (input_index, &value) in values.iter().enumerate() {
+            if let Some(output_index) = self.to_output_index(input_index) {
+                let (best, _) = expected[output_index];
+                if value > best {
+                    let coordinate = self.to_input_coordinate(input_index).unwrap();
+                    expected[output_index] = (value, coordinate[self.axis.unwrap()] as u32);
+                }
+            }
+        }
+        expected.into_iter().map(|(_, i)| i).collect()
+    }
+
+    pub fn test_argmin<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        self.run_argmin_test::<F, R>(device, input_values)
+    }
+
+    pub fn test_argmin_ties_and_nans<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.ties_and_nans_input_values();
+        self.run_argmin_test::<F, R>(device, input_values)
+    }
+
+    fn run_argmin_test<F, R>(&self, device: &R::Device, input_values: Vec<F::EI>)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => vec![0; input_values.len()],
+            _ => self.cpu_argmin(&input_values),
+        };
+        self.run_reduce_test::<F, u32, R, ArgMin>(device, input_values, expected_values)
+    }
+
+    fn cpu_argmin<F: Float>(&self, values: &[F]) -> Vec<u32> {
+        let mut expected = vec![(F::max_value(), 0_u32); self.num_output_values()];
+        for (input_index, &value) in values.iter().enumerate() {
+            if let Some(output_index) = self.to_output_index(input_index) {
+                let (best, _) = expected[output_index];
+                if value < best {
+                    let coordinate = self.to_input_coordinate(input_index).unwrap();
+                    expected[output_index] = (value, coordinate[self.axis.unwrap()] as u32);
+                }
+            }
+        }
+        expected.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// A reduction into an empty output succeeds without launching anything.
+    pub fn test_empty_output<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let client = R::client(device);
+        let axis = self.axis.unwrap();
+        let mut output_shape = self.shape.clone();
+        output_shape[axis] = 1;
+        let output_stride = contiguous_strides(&output_shape);
+        // The handles of empty tensors are never read, they only need to exist.
+        let input_handle = client.empty(size_of::<F::EI>());
+        let output_handle = client.empty(size_of::<F::EI>());
+
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<F::EI>(),
+            )
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &output_handle,
+                &output_stride,
+                &output_shape,
+                size_of::<F::EI>(),
+            )
+        };
+
+        let (result, launches) = cubecl_core::compute::count_launches(|| {
+            reduce::<R, F, F::EI, Sum>(&client, input, output, axis, self.strategy, ())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(launches, 0);
+    }
+
+    pub fn test_mean<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => input_values.clone(),
+            _ => self.cpu_mean(&input_values),
+        };
+        self.run_reduce_test::<F, F::EI, R, Mean>(device, input_values, expected_values)
+    }
+
+    fn cpu_mean<F: Float>(&self, values: &[F]) -> Vec<F> {
+        self.cpu_sum(values)
+            .into_iter()
+            .map(|sum| sum / F::new(self.shape[self.axis.unwrap()] as f32))
+            .collect()
+    }
+
+    pub fn test_prod<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => input_values
+                .iter()
+                .map(|v| Self::powf(*v, self.shape[axis]))
+                .collect(),
+            _ => self.cpu_prod(&input_values),
+        };
+        self.run_reduce_test::<F, F::EI, R, Prod>(device, input_values, expected_values)
+    }
+
+    fn powf<F: Float>(base: F, power: usize) -> F {
+        let mut result = F::new(1.0);
+        for _ in 0..power {
+            result *= base;
+        }
+        result
+    }
+
+    fn cpu_prod<F: Float>(&self, values: &[F]) -> Vec<F> {
+        let mut expected = vec![F::new(1.0); self.num_output_values()];
+
+        for (input_index, value) in values.iter().enumerate() {
+            if let Some(output_index) = self.to_output_index(input_index) {
+                expected[output_index] *= *value;
+            }
+        }
+        expected
+    }
+
+    pub fn test_sum<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => input_values
+                .iter()
+                .map(|v| *v * F::EI::from_int(self.shape[axis] as i64))
+                .collect(),
+            _ => self.cpu_sum(&input_values),
+        };
+        self.run_reduce_test::<F, F::EI, R, Sum>(device, input_values, expected_values)
+    }
+
+    fn cpu_sum<F: Float>(&self, values: &[F]) -> Vec<F> {
+        let mut expected = vec![F::new(0.0); self.num_output_values()];
+
+        for (input_index, value) in values.iter().enumerate() {
+            if let Some(output_index) = self.to_output_index(input_index) {
+                expected[output_index] += *value;
+            }
+        }
+        expected
+    }
+
+    pub fn test_custom_sum<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => input_values
+                .iter()
+                .map(|v| *v * F::EI::from_int(self.shape[axis] as i64))
+                .collect(),
+            _ => self.cpu_sum(&input_values),
+        };
+        self.run_reduce_custom_test::<F, F::EI, R, Sum>(device, input_values, expected_values)
+    }
+
+    pub fn test_logsumexp<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.random_input_values();
+        let expected_values = match self.axis {
+            Some(axis) if self.stride[axis] == 0 => input_values
+                .iter()
+                .map(|v| *v + F::EI::new((self.shape[axis] as f32).ln()))
+                .collect(),
+            _ => self.cpu_logsumexp(&input_values),
+        };
+        self.run_reduce_custom_test::<F, F::EI, R, LogSumExp>(device, input_values, expected_values)
+    }
+
+    fn cpu_logsumexp<F: Float>(&self, values: &[F]) -> Vec<F> {
+        let mut slices = vec![Vec::new(); self.num_output_values()];
+        for (input_index, value) in values.iter().enumerate() {
+            if let Some(output_index) = self.to_output_index(input_index) {
+                slices[output_index].push(value.to_f64().unwrap());
+            }
+        }
+        slices
+            .into_iter()
+            .map(|slice| {
+                let max = slice.iter().cloned().fold(f64::MIN, f64::max);
+                let sum = slice.iter().map(|value| (value - max).exp()).sum::<f64>();
+                F::new((max + sum.ln()) as f32)
+            })
+            .collect()
+    }
+
+    pub fn test_shared_sum<F, R>(&self, device: &R::Device)
+    where
+        F: Float + CubeElement + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F> = self.random_input_values();
+        let mut expected = F::from_int(0);
+        for v in input_values.iter() {
+            expected += *v;
+        }
+        self.run_shared_sum_test::<F, R>(device, input_values, expected);
+    }
+
+    pub fn test_shared_sum_deterministic<F, R>(&self, device: &R::Device)
+    where
+        F: Float + CubeElement + std::fmt::Display,
+        R: Runtime,
+    {
+        let client = R::client(device);
+
+        // Unlike the usual random values, these values aren't summed exactly, so the sum depends
+        // on the order of the additions.
+        let length = self.shape.iter().product::<usize>();
+        let rng = StdRng::seed_from_u64(123456789);
+        let distribution = Uniform::new(0.0f32, 1.0).unwrap();
+        let input_values = distribution
+            .sample_iter(rng)
+            .take(length)
+            .map(F::new)
+            .collect::<Vec<_>>();
+        let expected = input_values
+            .iter()
+            .map(|value| value.to_f64().unwrap())
+            .sum::<f64>();
+
+        let input_handle = client.create(F::as_bytes(&input_values));
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<F>(),
+            )
+        };
+
+        let runs = (0..5)
+            .map(|_| {
+                let output_handle = client.create(F::as_bytes(&[F::from_int(0)]));
+                let output = unsafe {
+                    TensorHandleRef::<R>::from_raw_parts(&output_handle, &[1], &[1], size_of::<F>())
+                };
+                shared_sum_deterministic::<R, F>(&client, input, output, 7);
+                client.read_one(output_handle)
+            })
+            .collect::<Vec<_>>();
+
+        for run in runs.iter().skip(1) {
+            assert_eq!(run, &runs[0], "The sums aren't bit-identical");
+        }
+        assert_approx_equal(F::from_bytes(&runs[0]), &[F::new(expected as f32)]);
+    }
+
+    pub fn run_reduce_test<P, O, R, K>(
+        &self,
+        device: &R::Device,
+        input_values: Vec<P::EI>,
+        expected_values: Vec<O>,
+    ) where
+        P: ReducePrecision,
+        P::EI: CubeElement,
+        O: Numeric + CubeElement + std::fmt::Display,
+        R: Runtime,
+        K: ReduceFamily<Config = ()>,
+    {
+        self.run_reduce_test_with::<P, O, R>(
+            device,
+            input_values,
+            expected_values,
+            |client, input, output| {
+                reduce::<R, P, O, K>(client, input, output, self.axis.unwrap(), self.strategy, ())
+            },
+        )
+    }
+
+    pub fn run_reduce_custom_test<P, O, R, K>(
+        &self,
+        device: &R::Device,
+        input_values: Vec<P::EI>,
+        expected_values: Vec<O>,
+    ) where
+        P: ReducePrecision,
+        P::EI: CubeElement,
+        O: Numeric + CubeElement + std::fmt::Display,
+        R: Runtime,
+        K: ReduceOpFamily,
+    {
+        self.run_reduce_test_with::<P, O, R>(
+            device,
+            input_values,
+            expected_values,
+            |client, input, output| {
+                reduce_custom::<R, P, O, K>(
+                    client,
+                    input,
+                    output,
+                    self.axis.unwrap(),
+                    self.strategy,
+                )
+            },
+        )
+    }
+
+    fn run_reduce_test_with<P, O, R>(
+        &self,
+        device: &R::Device,
+        input_values: Vec<P::EI>,
+        expected_values: Vec<O>,
+        launch: impl FnOnce(
+            &ComputeClient<R::Server, R::Channel>,
+            TensorHandleRef<R>,
+            TensorHandleRef<R>,
+        ) -> Result<(), ReduceError>,
+    ) where
+        P: ReducePrecision,
+        P::EI: CubeElement,
+        O: Numeric + CubeElement + std::fmt::Display,
+        R: Runtime,
+    {
+        let client = R::client(device);
+
+        let input_handle = client.create(<P::EI as CubeElement>::as_bytes(&input_values));
+
+        // Zero initialize a tensor with the same shape as input
+        // except for the `self.axis` axis where the shape is 1.
+        let output_handle =
+            client.create(O::as_bytes(&vec![O::from_int(0); expected_values.len()]));
+        let mut output_shape = self.shape.clone();
+        output_shape[self.axis.unwrap()] = 1;
+        let output_stride = self.output_stride();
+
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<P>(),
+            )
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &output_handle,
+                &output_stride,
+                &output_shape,
+                size_of::<O>(),
+            )
+        };
+
+        let result = launch(&client, input, output);
+        if result.is_err_and(|e| {
+            e == ReduceError::PlanesUnavailable || e == ReduceError::ImprecisePlaneDim
+        }) {
+            return; // We don't test in that case.
+        }
+
+        let bytes = client.read_one(output_handle);
+        let output_values = O::from_bytes(&bytes);
+        assert_approx_equal(output_values, &expected_values);
+    }
+
+    pub fn run_shared_sum_test<F, R>(&self, device: &R::Device, input_values: Vec<F>, expected: F)
+    where
+        F: Float + CubeElement + std::fmt::Display,
+        R: Runtime,
+    {
+        let client = R::client(device);
+
+        let input_handle = client.create(F::as_bytes(&input_values));
+        let output_handle = client.create(F::as_bytes(&[F::from_int(0)]));
+
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<F>(),
+            )
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&output_handle, &[1], &[1], size_of::<F>())
+        };
+
+        let cube_count = 3;
+        let result = shared_sum::<R, F>(&client, input, output, cube_count);
+
+        if result.is_err() {
+            return; // don't execute the test in that case since atomic adds are not supported.
+        }
+        let bytes = client.read_one(output_handle);
+        let actual = F::from_bytes(&bytes);
+        assert_approx_equal(actual, &[expected]);
+    }
+
+    fn num_output_values(&self) -> usize {
+        self.shape.iter().product::<usize>() / self.shape[self.axis.unwrap()]
+    }
+
+    fn to_output_index(&self, input_index: usize) -> Option<usize> {
+        let mut coordinate = self.to_input_coordinate(input_index)?;
+        coordinate[self.axis.unwrap()] = 0;
+        Some(self.from_output_coordinate(coordinate))
+    }
+
+    fn to_input_coordinate(&self, index: usize) -> Option<Vec<usize>> {
+        let coordinate = self
+            .stride
+            .iter()
+            .zip(self.shape.iter())
+            .map(|(stride, shape)| {
+                if *stride > 0 {
+                    (index / stride) % shape
+                } else {
+                    index % shape
+                }
+            })
+            .collect::<Vec<usize>>();
+        self.validate_input_index(index, &coordinate)
+            .then_some(coordinate)
+    }
+
+    fn validate_input_index(&self, index: usize, coordinate: &[usize]) -> bool {
+        coordinate
+            .iter()
+            .zip(self.stride.iter())
+            .map(|(c, s)| c * s)
+            .sum::<usize>()
+            == index
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn from_output_coordinate(&self, coordinate: Vec<usize>) -> usize {
+        coordinate
+            .into_iter()
+            .zip(self.output_stride().iter())
+            .map(|(c, s)| c * s)
+            .sum()
+    }
+
+    fn output_stride(&self) -> Vec<usize> {
+        self.shape
+            .iter()
+            .enumerate()
+            .scan(1, |stride, (axis, shape)| {
+                if axis == self.axis.unwrap() {
+                    Some(1)
+                } else {
+                    let current = Some(*stride);
+                    *stride *= shape;
+                    current
+                }
+            })
+            .collect()
+    }
+
+    fn random_input_values<F: Float>(&self) -> Vec<F> {
+        let size = self.input_size();
+        let rng = StdRng::seed_from_u64(self.pseudo_random_seed());
+        let distribution = Uniform::new_inclusive(-2 * PRECISION, 2 * PRECISION).unwrap();
+        let factor = 1.0 / (PRECISION as f32);
+        distribution
+            .sample_iter(rng)
+            .take(size)
+            .map(|r| F::new(r as f32 * factor))
+            .collect()
+        // (0..size).map(|x| F::from_int(x as i64)).collect() TODO DELETE
+    }
+
+    // Values in {-1, 0, 1, NaN}, so most reductions have many tied extrema
+    // and NaNs that must never be selected.
+    fn ties_and_nans_input_values<F: Float>(&self) -> Vec<F> {
+        self.random_input_values::<F>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                if i % 5 == 3 {
+                    F::NAN
+                } else if value > F::new(0.5) {
+                    F::new(1.0)
+                } else if value < F::new(-0.5) {
+                    F::new(-1.0)
+                } else {
+                    F::new(0.0)
+                }
+            })
+            .collect()
+    }
+
+    fn input_size(&self) -> usize {
+        let (stride, shape) = self
+            .stride
+            .iter()
+            .zip(self.shape.iter())
+            .max_by_key(|(stride, _)| *stride)
+            .unwrap();
+        stride * shape
+    }
+
+    // We don't need a fancy crypto-secure seed as this is only for testing.
+    fn pseudo_random_seed(&self) -> u64 {
+        123456789
+    }
+}
+
+#[derive(Debug)]
+pub struct ReduceAxesTestCase {
+    pub shape: Vec<usize>,
+    pub stride: Vec<usize>,
+    pub axes: Vec<usize>,
+    pub keep_dims: bool,
+}
+
+impl ReduceAxesTestCase {
+    pub fn test_argmax<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.as_test_case().random_input_values();
+        let expected_values = self
+            .cpu_reduced_values(&input_values)
+            .into_iter()
+            .map(|values| {
+                let mut best = 0;
+                for (coordinate, value) in values.iter().enumerate() {
+                    if *value > values[best] {
+                        best = coordinate;
+                    }
+                }
+                best as u32
+            })
+            .collect();
+        self.run_reduce_axes_test::<F, u32, R, ArgMax>(device, input_values, expected_values)
+    }
+
+    pub fn test_mean<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.as_test_case().random_input_values();
+        let expected_values = self
+            .cpu_reduced_values(&input_values)
+            .into_iter()
+            .map(|values| F::EI::new((Self::sum(&values) / values.len() as f64) as f32))
+            .collect();
+        self.run_reduce_axes_test::<F, F::EI, R, Mean>(device, input_values, expected_values)
+    }
+
+    pub fn test_sum<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision + std::fmt::Display,
+        F::EI: CubeElement + Float + std::fmt::Display,
+        R: Runtime,
+    {
+        let input_values: Vec<F::EI> = self.as_test_case().random_input_values();
+        let expected_values = self
+            .cpu_reduced_values(&input_values)
+            .into_iter()
+            .map(|values| F::EI::new(Self::sum(&values) as f32))
+            .collect();
+        self.run_reduce_axes_test::<F, F::EI, R, Sum>(device, input_values, expected_values)
+    }
+
+    pub fn test_duplicate_axis<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision,
+        F::EI: CubeElement + Float,
+        R: Runtime,
+    {
+        let client = R::client(device);
+        let input_values: Vec<F::EI> = self.as_test_case().random_input_values();
+        let input_handle = client.create(F::EI::as_bytes(&input_values));
+        let output_handle = client.empty(self.num_output_values() * size_of::<F::EI>());
+        let output_shape = self.output_shape();
+        let output_stride = contiguous_strides(&output_shape);
+
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<F::EI>(),
+            )
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &output_handle,
+                &output_stride,
+                &output_shape,
+                size_of::<F::EI>(),
+            )
+        };
+
+        let result =
+            reduce_axes::<R, F, F::EI, Sum>(&client, input, output, &self.axes, self.keep_dims, ());
+        assert_eq!(
+            result,
+            Err(ReduceError::DuplicateAxis { axis: self.axes[1] })
+        );
+    }
+
+    pub fn run_reduce_axes_test<P, O, R, K>(
+        &self,
+        device: &R::Device,
+        input_values: Vec<P::EI>,
+        expected_values: Vec<O>,
+    ) where
+        P: ReducePrecision,
+        P::EI: CubeElement,
+        O: Numeric + CubeElement + std::fmt::Display,
+        R: Runtime,
+        K: ReduceFamily<Config = ()>,
+    {
+        let client = R::client(device);
+
+        let input_handle = client.create(<P::EI as CubeElement>::as_bytes(&input_values));
+        let output_handle =
+            client.create(O::as_bytes(&vec![O::from_int(0); expected_values.len()]));
+        let output_shape = self.output_shape();
+        let output_stride = contiguous_strides(&output_shape);
+
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<P::EI>(),
+            )
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &output_handle,
+                &output_stride,
+                &output_shape,
+                size_of::<O>(),
+            )
+        };
+
+        reduce_axes::<R, P, O, K>(&client, input, output, &self.axes, self.keep_dims, ()).unwrap();
+
+        let bytes = client.read_one(output_handle);
+        let output_values = O::from_bytes(&bytes);
+        assert_approx_equal(output_values, &expected_values);
+    }
+
+    // The input values reduced into each output value, in the order of their coordinate
+    // in the collapsed space of the reduced axes.
+    fn cpu_reduced_values<F: Float>(&self, values: &[F]) -> Vec<Vec<F>> {
+        let reduced_size = self
+            .axes
+            .iter()
+            .map(|axis| self.shape[*axis])
+            .product::<usize>();
+        let mut reduced = vec![vec![F::new(0.0); reduced_size]; self.num_output_values()];
+
+        for index in 0..self.shape.iter().product::<usize>() {
+            let coordinate = contiguous_strides(&self.shape)
+                .iter()
+                .zip(self.shape.iter())
+                .map(|(stride, shape)| (index / stride) % shape)
+                .collect::<Vec<_>>();
+
+            let mut output_index = 0;
+            let mut reduced_index = 0;
+            for (axis, (c, shape)) in coordinate.iter().zip(self.shape.iter()).enumerate() {
+                if self.axes.contains(&axis) {
+                    reduced_index = reduced_index * shape + c;
+                } else {
+                    output_index = output_index * shape + c;
+                }
+            }
+
+            let input_index = coordinate
+                .iter()
+                .zip(self.stride.iter())
+                .map(|(c, s)| c * s)
+                .sum::<usize>();
+            reduced[output_index][reduced_index] = values[input_index];
+        }
+        reduced
+    }
+
+    fn sum<F: Float>(values: &[F]) -> f64 {
+        values.iter().map(|value| value.to_f64().unwrap()).sum()
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        self.shape
+            .iter()
+            .enumerate()
+            .filter(|(axis, _)| self.keep_dims || !self.axes.contains(axis))
+            .map(|(axis, shape)| match self.axes.contains(&axis) {
+                true => 1,
+                false => *shape,
+            })
+            .collect()
+    }
+
+    fn num_output_values(&self) -> usize {
+        self.output_shape().iter().product()
+    }
+
+    fn as_test_case(&self) -> TestCase {
+        TestCase {
+            shape: self.shape.clone(),
+            stride: self.stride.clone(),
+            axis: None,
+            strategy: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MeanVarTestCase {
+    pub shape: Vec<usize>,
+    pub stride: Vec<usize>,
+    pub axis: usize,
+    pub unbiased: bool,
+}
+
+impl MeanVarTestCase {
+    pub fn test_mean_var<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision,
+        F::EI: CubeElement + Float,
+        F::EA: Float,
+        R: Runtime,
+    {
+        let client = R::client(device);
+        let input_values = self.adversarial_input_values::<F::EI>();
+        let (expected_mean, expected_var) = self.cpu_mean_var(&input_values);
+
+        let input_handle = client.create(F::EI::as_bytes(&input_values));
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<F::EI>(),
+            )
+        };
+
+        let (mean, var) = mean_var::<R, F>(&client, input, self.axis, self.unbiased).unwrap();
+
+        let bytes = client.read_one_tensor(mean.as_copy_descriptor());
+        assert_approx_equal(F::EI::from_bytes(&bytes), &expected_mean);
+        let bytes = client.read_one_tensor(var.as_copy_descriptor());
+        assert_approx_equal(F::EI::from_bytes(&bytes), &expected_var);
+    }
+
+    // A large constant offset plus a small noise, where computing the variance as E[x²] - E[x]²
+    // cancels most of the significant digits. The offset is the largest power of two
+    // for which steps of 1/64 around it are still representable, so the noise is stored exactly.
+    fn adversarial_input_values<F: Float>(&self) -> Vec<F> {
+        let step = F::new(1.0 / 64.0);
+        let offset = (0..=46)
+            .rev()
+            .map(|exponent| F::from_int(1i64 << exponent))
+            .find(|offset| (*offset + step) - *offset == step)
+            .unwrap();
+
+        self.as_test_case()
+            .random_input_values::<F>()
+            .into_iter()
+            .map(|noise| offset + noise)
+            .collect()
+    }
 
-                #[test]
-                pub fn [< sum_plane_ $use_planes _shared_ $shared _ $id >]() {
-                    let test = TestCase {
-                        shape: $shape.into(),
-                        stride: $stride.into(),
-                        axis: Some($axis),
-                        strategy: Some($crate::ReduceStrategy { use_planes: $use_planes, shared: $shared }),
-                    };
-                    test.test_sum::<$float, TestRuntime>(&Default::default());
-                }
-            )*
+    // The two-pass mean and variance computed in f64.
+    fn cpu_mean_var<F: Float>(&self, values: &[F]) -> (Vec<F>, Vec<F>) {
+        let length = self.shape[self.axis];
+        let num_rows = self.shape.iter().product::<usize>() / length;
+
+        let mut means = Vec::with_capacity(num_rows);
+        let mut variances = Vec::with_capacity(num_rows);
+        for row in 0..num_rows {
+            // The offset of the first element of the row, iterating over the other axes in row-major order.
+            let mut offset = 0;
+            let mut remainder = row;
+            for axis in (0..self.shape.len())
+                .rev()
+                .filter(|axis| *axis != self.axis)
+            {
+                offset += (remainder % self.shape[axis]) * self.stride[axis];
+                remainder /= self.shape[axis];
+            }
+
+            let row_values = (0..length)
+                .map(|i| {
+                    values[offset + i * self.stride[self.axis]]
+                        .to_f64()
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let mean = row_values.iter().sum::<f64>() / length as f64;
+            let m2 = row_values
+                .iter()
+                .map(|value| (value - mean) * (value - mean))
+                .sum::<f64>();
+            let divisor = match self.unbiased {
+                true => length - 1,
+                false => length,
+            };
+
+            means.push(F::new(mean as f32));
+            variances.push(F::new((m2 / divisor as f64) as f32));
         }
-    };
+        (means, variances)
+    }
+
+    fn as_test_case(&self) -> TestCase {
+        TestCase {
+            shape: self.shape.clone(),
+            stride: self.stride.clone(),
+            axis: None,
+            strategy: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoftmaxTestMask {
+    None,
+    Bias,
+    Columns,
 }
 
 #[derive(Debug)]
-pub struct TestCase {
+pub struct SoftmaxTestCase {
     pub shape: Vec<usize>,
-    pub stride: Vec<usize>,
-    pub axis: Option<usize>,
-    pub strategy: Option<ReduceStrategy>,
+    pub temperature: f32,
+    pub mask: SoftmaxTestMask,
 }
 
-impl TestCase {
-    pub fn test_argmax<F, R>(&self, device: &R::Device)
+impl SoftmaxTestCase {
+    pub fn test_softmax<F, R>(&self, device: &R::Device)
     where
-        F: ReducePrecision + std::fmt::Display,
+        F: ReducePrecision,
         F::EI: CubeElement + Float,
+        F::EA: Float,
         R: Runtime,
     {
-        let input_values: Vec<F::EI> = self.random_input_values();
-        let expected_values = match self.axis {
-            Some(axis) if self.stride[axis] == 0 => vec![0; input_values.len()],
-            _ => self.cpu_argmax(&input_values),
+        let client = R::client(device);
+        let strides = contiguous_strides(&self.shape);
+        let input_values = self.input_values::<F::EI>();
+        let masked = self.masked_columns();
+        let expected = self.cpu_softmax(&input_values, &masked);
+
+        let input_handle = client.create(F::EI::as_bytes(&input_values));
+        let output_handle = client.create(F::EI::as_bytes(&vec![
+            F::EI::from_int(0);
+            input_values.len()
+        ]));
+        let bias_values = masked
+            .iter()
+            .map(|masked| match masked {
+                true => F::EI::NEG_INFINITY,
+                false => F::EI::from_int(0),
+            })
+            .collect::<Vec<_>>();
+        let mask_handle = match self.mask {
+            SoftmaxTestMask::None => None,
+            SoftmaxTestMask::Bias => Some(client.create(F::EI::as_bytes(&bias_values))),
+            SoftmaxTestMask::Columns => {
+                let mask = masked
+                    .iter()
+                    .map(|masked| *masked as u32)
+                    .collect::<Vec<_>>();
+                Some(client.create(u32::as_bytes(&mask)))
+            }
         };
-        self.run_reduce_test::<F, u32, R, ArgMax>(device, input_values, expected_values)
+
+        let elem_size = size_of::<F::EI>();
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&input_handle, &strides, &self.shape, elem_size)
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&output_handle, &strides, &self.shape, elem_size)
+        };
+        let mask = mask_handle.as_ref().map(|handle| match self.mask {
+            SoftmaxTestMask::Columns => SoftmaxMask::Columns(unsafe {
+                TensorHandleRef::<R>::from_raw_parts(
+                    handle,
+                    &strides,
+                    &self.shape,
+                    size_of::<u32>(),
+                )
+            }),
+            _ => SoftmaxMask::Bias(unsafe {
+                TensorHandleRef::<R>::from_raw_parts(handle, &strides, &self.shape, elem_size)
+            }),
+        });
+
+        let options = SoftmaxOptions {
+            temperature: self.temperature,
+            mask,
+        };
+        softmax::<R, F>(&client, input, output, options).unwrap();
+
+        let bytes = client.read_one(output_handle);
+        assert_approx_equal(F::EI::from_bytes(&bytes), &expected);
     }
 
-    fn cpu_argmax<F: Float>(&self, values: &[F]) -> Vec<u32> {
-        let mut expected = vec![(F::min_value(), 0_u32); self.num_output_values()];
-        for (input_index, &value) in values.iter().enumerate() {
-            if let Some(output_index) = self.to_output_index(input_index) {
-                let (best, _) = expected[output_index];
-                if value > best {
-                    let coordinate = self.to_input_coordinate(input_index).unwrap();
-                    expected[output_index] = (value, coordinate[self.axis.unwrap()] as u32);
-                }
+    // Random logits where every other row has an offset large enough for exp to overflow
+    // when the max of the row isn't subtracted.
+    fn input_values<F: Float>(&self) -> Vec<F> {
+        let length = self.row_length();
+        let test = TestCase {
+            shape: self.shape.clone(),
+            stride: contiguous_strides(&self.shape),
+            axis: None,
+            strategy: None,
+        };
+        test.random_input_values::<F>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| value + F::from_int(100 * ((i / length) % 2) as i64))
+            .collect()
+    }
+
+    // The second row is fully masked, and a few columns of the other rows.
+    fn masked_columns(&self) -> Vec<bool> {
+        let length = self.row_length();
+        let size = self.shape.iter().product::<usize>();
+        (0..size)
+            .map(|i| match self.mask {
+                SoftmaxTestMask::None => false,
+                _ => i / length == 1 || (i / length + i % length) % 7 == 0,
+            })
+            .collect()
+    }
+
+    // The softmax of each row computed in f64, with 0 for the rows that are fully masked.
+    fn cpu_softmax<F: Float>(&self, values: &[F], masked: &[bool]) -> Vec<F> {
+        let length = self.row_length();
+        let mut expected = vec![F::from_int(0); values.len()];
+
+        for row in 0..values.len() / length {
+            let columns = row * length..(row + 1) * length;
+            let logits = columns
+                .clone()
+                .filter(|i| !masked[*i])
+                .map(|i| (i, values[i].to_f64().unwrap() / self.temperature as f64))
+                .collect::<Vec<_>>();
+            let max = logits
+                .iter()
+                .map(|(_, logit)| *logit)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let sum = logits
+                .iter()
+                .map(|(_, logit)| (logit - max).exp())
+                .sum::<f64>();
+
+            for (i, logit) in logits {
+                expected[i] = F::new(((logit - max).exp() / sum) as f32);
             }
         }
-        expected.into_iter().map(|(_, i)| i).collect()
+        expected
     }
 
-    pub fn test_argmin<F, R>(&self, device: &R::Device)
+    fn row_length(&self) -> usize {
+        *self.shape.last().unwrap()
+    }
+}
+
+#[derive(Debug)]
+pub struct NormTestCase {
+    pub shape: Vec<usize>,
+    pub kind: NormKind,
+    /// Scale the rows by `gamma`, and shift them by `beta` for [`NormKind::LayerNorm`].
+    pub affine: bool,
+}
+
+impl NormTestCase {
+    pub fn test_norm<F, R>(&self, device: &R::Device)
     where
-        F: ReducePrecision + std::fmt::Display,
+        F: ReducePrecision,
         F::EI: CubeElement + Float,
+        F::EA: Float,
         R: Runtime,
     {
-        let input_values: Vec<F::EI> = self.random_input_values();
-        let expected_values = match self.axis {
-            Some(axis) if self.stride[axis] == 0 => vec![0; input_values.len()],
-            _ => self.cpu_argmin(&input_values),
+        let client = R::client(device);
+        let strides = contiguous_strides(&self.shape);
+        let length = *self.shape.last().unwrap();
+        let input_values = self.input_values::<F::EI>();
+        // Values exactly representable in half precision.
+        let gamma_values = (0..length)
+            .map(|i| F::EI::new(0.5 + (i % 8) as f32 / 8.0))
+            .collect::<Vec<_>>();
+        let beta_values = (0..length)
+            .map(|i| F::EI::new((i % 5) as f32 / 4.0 - 0.5))
+            .collect::<Vec<_>>();
+        let expected = self.cpu_norm(&input_values, &gamma_values, &beta_values);
+
+        let input_handle = client.create(F::EI::as_bytes(&input_values));
+        let output_handle = client.create(F::EI::as_bytes(&vec![
+            F::EI::from_int(0);
+            input_values.len()
+        ]));
+        let gamma_handle = client.create(F::EI::as_bytes(&gamma_values));
+        let beta_handle = client.create(F::EI::as_bytes(&beta_values));
+
+        let elem_size = size_of::<F::EI>();
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&input_handle, &strides, &self.shape, elem_size)
         };
-        self.run_reduce_test::<F, u32, R, ArgMin>(device, input_values, expected_values)
-    }
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&output_handle, &strides, &self.shape, elem_size)
+        };
+        let param_shape = [length];
+        let gamma = self.affine.then(|| unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&gamma_handle, &[1], &param_shape, elem_size)
+        });
+        let beta = self.affine.then(|| unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&beta_handle, &[1], &param_shape, elem_size)
+        });
 
-    fn cpu_argmin<F: Float>(&self, values: &[F]) -> Vec<u32> {
-        let mut expected = vec![(F::max_value(), 0_u32); self.num_output_values()];
-        for (input_index, &value) in values.iter().enumerate() {
-            if let Some(output_index) = self.to_output_index(input_index) {
-                let (best, _) = expected[output_index];
-                if value < best {
-                    let coordinate = self.to_input_coordinate(input_index).unwrap();
-                    expected[output_index] = (value, coordinate[self.axis.unwrap()] as u32);
-                }
+        match self.kind {
+            NormKind::LayerNorm => {
+                layer_norm::<R, F>(&client, input, output, gamma, beta, NORM_EPSILON).unwrap()
+            }
+            NormKind::RmsNorm => {
+                rms_norm::<R, F>(&client, input, output, gamma, NORM_EPSILON).unwrap()
             }
         }
-        expected.into_iter().map(|(_, i)| i).collect()
-    }
 
-    pub fn test_mean<F, R>(&self, device: &R::Device)
-    where
-        F: ReducePrecision + std::fmt::Display,
-        F::EI: CubeElement + Float + std::fmt::Display,
-        R: Runtime,
-    {
-        let input_values: Vec<F::EI> = self.random_input_values();
-        let expected_values = match self.axis {
-            Some(axis) if self.stride[axis] == 0 => input_values.clone(),
-            _ => self.cpu_mean(&input_values),
+        let bytes = client.read_one(output_handle);
+        let actual = F::EI::from_bytes(&bytes);
+
+        // The rounding of the output dominates the error for half precision, the accumulation otherwise.
+        let tolerance = match size_of::<F::EI>() {
+            2 => 1e-2,
+            4 => 1e-4,
+            _ => 1e-8,
         };
-        self.run_reduce_test::<F, F::EI, R, Mean>(device, input_values, expected_values)
+        for (i, (actual, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+            let actual = actual.to_f64().unwrap();
+            let diff = (actual - expected).abs();
+            assert!(
+                diff <= tolerance * expected.abs().max(1.0),
+                "Values are not close: index={i} actual={actual}, expected={expected}, difference={diff}"
+            );
+        }
     }
 
-    fn cpu_mean<F: Float>(&self, values: &[F]) -> Vec<F> {
-        self.cpu_sum(values)
+    // Random values where every other row has an offset much larger than its deviation,
+    // so the statistics lose most of their precision when the mean isn't subtracted.
+    fn input_values<F: Float>(&self) -> Vec<F> {
+        let length = *self.shape.last().unwrap();
+        let test = TestCase {
+            shape: self.shape.clone(),
+            stride: contiguous_strides(&self.shape),
+            axis: None,
+            strategy: None,
+        };
+        test.random_input_values::<F>()
             .into_iter()
-            .map(|sum| sum / F::new(self.shape[self.axis.unwrap()] as f32))
+            .enumerate()
+            .map(|(i, value)| value + F::from_int(100 * ((i / length) % 2) as i64))
             .collect()
     }
 
-    pub fn test_prod<F, R>(&self, device: &R::Device)
+    // The normalized rows computed in f64.
+    fn cpu_norm<F: Float>(&self, values: &[F], gamma: &[F], beta: &[F]) -> Vec<f64> {
+        let length = *self.shape.last().unwrap();
+        let values = values
+            .iter()
+            .map(|value| value.to_f64().unwrap())
+            .collect::<Vec<_>>();
+
+        values
+            .chunks(length)
+            .flat_map(|row| {
+                let mean = row.iter().sum::<f64>() / length as f64;
+                let (shift, second_moment) = match self.kind {
+                    NormKind::LayerNorm => (
+                        mean,
+                        row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / length as f64,
+                    ),
+                    NormKind::RmsNorm => {
+                        (0.0, row.iter().map(|x| x * x).sum::<f64>() / length as f64)
+                    }
+                };
+                let scale = 1.0 / (second_moment + NORM_EPSILON as f64).sqrt();
+
+                row.iter()
+                    .enumerate()
+                    .map(move |(i, x)| {
+                        let normalized = (x - shift) * scale;
+                        match (self.affine, self.kind) {
+                            (false, _) => normalized,
+                            (true, NormKind::RmsNorm) => normalized * gamma[i].to_f64().unwrap(),
+                            (true, NormKind::LayerNorm) => {
+                                normalized * gamma[i].to_f64().unwrap() + beta[i].to_f64().unwrap()
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct ScanTestCase {
+    pub length: usize,
+    pub inclusive: bool,
+    pub in_place: bool,
+    pub strategy: ScanStrategy,
+}
+
+impl ScanTestCase {
+    pub fn test_sum<N, R>(&self, device: &R::Device)
     where
-        F: ReducePrecision + std::fmt::Display,
-        F::EI: CubeElement + Float + std::fmt::Display,
+        N: Numeric + CubeElement,
         R: Runtime,
     {
-        let input_values: Vec<F::EI> = self.random_input_values();
-        let expected_values = match self.axis {
-            Some(axis) if self.stride[axis] == 0 => input_values
-                .iter()
-                .map(|v| Self::powf(*v, self.shape[axis]))
-                .collect(),
-            _ => self.cpu_prod(&input_values),
-        };
-        self.run_reduce_test::<F, F::EI, R, Prod>(device, input_values, expected_values)
+        // Small non-negative integers keep the prefixes of a few million elements exact, even for f32.
+        let values = self.input_values(0, 3);
+        self.run_scan_test::<N, R, Sum>(device, &values, N::from_int(0), |lhs, rhs| lhs + rhs);
     }
 
-    fn powf<F: Float>(base: F, power: usize) -> F {
-        let mut result = F::new(1.0);
-        for _ in 0..power {
-            result *= base;
-        }
-        result
+    pub fn test_max<N, R>(&self, device: &R::Device)
+    where
+        N: Numeric + CubeElement,
+        R: Runtime,
+    {
+        let values = self.input_values(-1000, 1000);
+        self.run_scan_test::<N, R, crate::instructions::Max>(
+            device,
+            &values,
+            N::min_value(),
+            i64::max,
+        );
     }
 
-    fn cpu_prod<F: Float>(&self, values: &[F]) -> Vec<F> {
-        let mut expected = vec![F::new(1.0); self.num_output_values()];
+    pub fn run_scan_test<N, R, I>(
+        &self,
+        device: &R::Device,
+        values: &[i64],
+        identity: N,
+        combine: fn(i64, i64) -> i64,
+    ) where
+        N: Numeric + CubeElement,
+        R: Runtime,
+        I: ScanInstruction,
+    {
+        let client = R::client(device);
+        let expected = self.cpu_scan(values, identity, combine);
 
-        for (input_index, value) in values.iter().enumerate() {
-            if let Some(output_index) = self.to_output_index(input_index) {
-                expected[output_index] *= *value;
+        // Empty buffers aren't supported by all runtimes, so the handles hold at least one element.
+        let mut input_values = values.iter().map(|v| N::from_int(*v)).collect::<Vec<_>>();
+        input_values.resize(self.length.max(1), N::from_int(0));
+        let input_handle = client.create(N::as_bytes(&input_values));
+        let output_handle = match self.in_place {
+            true => input_handle.clone(),
+            false => client.create(N::as_bytes(&vec![N::from_int(0); input_values.len()])),
+        };
+
+        let shape = [self.length];
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&input_handle, &[1], &shape, size_of::<N>())
+        };
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&output_handle, &[1], &shape, size_of::<N>())
+        };
+
+        match scan::<R, N, I>(&client, input, output, self.inclusive, Some(self.strategy)) {
+            Err(ReduceError::LookbackUnavailable) => {
+                println!("Decoupled lookback not supported - skipped");
+                return;
             }
+            result => result.unwrap(),
         }
-        expected
+
+        let bytes = client.read_one(output_handle);
+        assert_approx_equal(&N::from_bytes(&bytes)[..self.length], &expected);
     }
 
-    pub fn test_sum<F, R>(&self, device: &R::Device)
+    fn cpu_scan<N: Numeric>(
+        &self,
+        values: &[i64],
+        identity: N,
+        combine: fn(i64, i64) -> i64,
+    ) -> Vec<N> {
+        let mut prefix = None;
+        values
+            .iter()
+            .map(|value| {
+                let previous = prefix;
+                let current = previous.map_or(*value, |previous| combine(previous, *value));
+                prefix = Some(current);
+                match (self.inclusive, previous) {
+                    (true, _) => N::from_int(current),
+                    (false, Some(previous)) => N::from_int(previous),
+                    (false, None) => identity,
+                }
+            })
+            .collect()
+    }
+
+    fn input_values(&self, low: i64, high: i64) -> Vec<i64> {
+        let rng = StdRng::seed_from_u64(123456789);
+        let distribution = Uniform::new_inclusive(low, high).unwrap();
+        distribution.sample_iter(rng).take(self.length).collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct CumsumTestCase {
+    pub shape: Vec<usize>,
+    pub stride: Vec<usize>,
+    pub axis: usize,
+    pub inclusive: bool,
+    pub reverse: bool,
+}
+
+impl CumsumTestCase {
+    pub fn test_cumsum<N, R>(&self, device: &R::Device)
     where
-        F: ReducePrecision + std::fmt::Display,
-        F::EI: CubeElement + Float + std::fmt::Display,
+        N: Numeric + CubeElement,
         R: Runtime,
     {
-        let input_values: Vec<F::EI> = self.random_input_values();
-        let expected_values = match self.axis {
-            Some(axis) if self.stride[axis] == 0 => input_values
-                .iter()
-                .map(|v| *v * F::EI::from_int(self.shape[axis] as i64))
-                .collect(),
-            _ => self.cpu_sum(&input_values),
+        let client = R::client(device);
+
+        // Small non-negative integers keep the sums of a million elements exact, even for f32.
+        let size = self.shape.iter().product::<usize>();
+        let rng = StdRng::seed_from_u64(123456789);
+        let distribution = Uniform::new_inclusive(0, 3).unwrap();
+        let values: Vec<i64> = distribution.sample_iter(rng).take(size).collect();
+        let expected = self.cpu_cumsum(&values);
+
+        let input_values = values.iter().map(|v| N::from_int(*v)).collect::<Vec<_>>();
+        let input_handle = client.create(N::as_bytes(&input_values));
+        let output_handle = client.create(N::as_bytes(&vec![N::from_int(0); size]));
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<N>(),
+            )
         };
-        self.run_reduce_test::<F, F::EI, R, Sum>(device, input_values, expected_values)
+        let output = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &output_handle,
+                &self.stride,
+                &self.shape,
+                size_of::<N>(),
+            )
+        };
+
+        let options = CumsumOptions {
+            inclusive: self.inclusive,
+            reverse: self.reverse,
+        };
+        cumsum::<R, N>(&client, input, output, self.axis, options).unwrap();
+
+        let bytes = client.read_one(output_handle);
+        assert_approx_equal(N::from_bytes(&bytes), &expected);
     }
 
-    fn cpu_sum<F: Float>(&self, values: &[F]) -> Vec<F> {
-        let mut expected = vec![F::new(0.0); self.num_output_values()];
+    // The running totals of the buffer `values` strided like the tensors, at the same buffer indices.
+    fn cpu_cumsum<N: Numeric>(&self, values: &[i64]) -> Vec<N> {
+        let length = self.shape[self.axis];
+        let num_rows = values.len() / length;
+        let mut expected = vec![N::from_int(0); values.len()];
 
-        for (input_index, value) in values.iter().enumerate() {
-            if let Some(output_index) = self.to_output_index(input_index) {
-                expected[output_index] += *value;
+        for row in 0..num_rows {
+            let mut row_offset = 0;
+            let mut remainder = row;
+            for dim in (0..self.shape.len()).rev().filter(|dim| *dim != self.axis) {
+                row_offset += (remainder % self.shape[dim]) * self.stride[dim];
+                remainder /= self.shape[dim];
+            }
+
+            let mut sum = 0;
+            for position in 0..length {
+                let index = match self.reverse {
+                    true => length - 1 - position,
+                    false => position,
+                };
+                let index = row_offset + index * self.stride[self.axis];
+                let previous = sum;
+                sum += values[index];
+                expected[index] = N::from_int(if self.inclusive { sum } else { previous });
             }
         }
         expected
     }
+}
 
-    pub fn test_shared_sum<F, R>(&self, device: &R::Device)
+#[derive(Debug)]
+pub struct TopKTestCase {
+    pub shape: Vec<usize>,
+    pub axis: usize,
+    pub k: usize,
+}
+
+impl TopKTestCase {
+    pub fn test_topk<F, R>(&self, device: &R::Device)
     where
-        F: Float + CubeElement + std::fmt::Display,
+        F: Float + CubeElement,
         R: Runtime,
     {
-        let input_values: Vec<F> = self.random_input_values();
-        let mut expected = F::from_int(0);
-        for v in input_values.iter() {
-            expected += *v;
+        let client = R::client(device);
+        let input_values = self.input_values::<F>();
+        let selected = self.cpu_topk(&input_values);
+
+        let input_handle = client.create(F::as_bytes(&input_values));
+        let strides = contiguous_strides(&self.shape);
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &input_handle,
+                &strides,
+                &self.shape,
+                size_of::<F>(),
+            )
+        };
+        let (values, indices) = topk::<R, F>(&client, input, self.k, self.axis).unwrap();
+        assert_eq!(values.shape, self.output_shape());
+
+        let actual_values = client.read_one_tensor(values.as_copy_descriptor());
+        let actual_values = F::from_bytes(&actual_values);
+        let actual_indices = client.read_one_tensor(indices.as_copy_descriptor());
+        let actual_indices = u32::from_bytes(&actual_indices);
+
+        let output_shape = self.output_shape();
+        for i in 0..actual_values.len() {
+            let (row, position) = self.row_and_position(&output_shape, i);
+            let (value, index) = selected[row][position];
+            assert_eq!(
+                (actual_values[i].to_f32().unwrap(), actual_indices[i]),
+                (value.to_f32().unwrap(), index),
+                "Values are not selected in order: row={row} position={position}"
+            );
         }
-        self.run_shared_sum_test::<F, R>(device, input_values, expected);
     }
 
-    pub fn run_reduce_test<P, O, R, K>(
-        &self,
-        device: &R::Device,
-        input_values: Vec<P::EI>,
-        expected_values: Vec<O>,
-    ) where
-        P: ReducePrecision,
-        P::EI: CubeElement,
-        O: Numeric + CubeElement + std::fmt::Display,
+    pub fn test_k_one_matches_argmax<F, R>(&self, device: &R::Device)
+    where
+        F: ReducePrecision<EI = F> + Float + CubeElement,
         R: Runtime,
-        K: ReduceFamily<Config = ()>,
     {
         let client = R::client(device);
+        let input_values = self.input_values::<F>();
 
-        let input_handle = client.create(<P::EI as CubeElement>::as_bytes(&input_values));
-
-        // Zero initialize a tensor with the same shape as input
-        // except for the `self.axis` axis where the shape is 1.
-        let output_handle =
-            client.create(O::as_bytes(&vec![O::from_int(0); expected_values.len()]));
-        let mut output_shape = self.shape.clone();
-        output_shape[self.axis.unwrap()] = 1;
-        let output_stride = self.output_stride();
-
+        let input_handle = client.create(F::as_bytes(&input_values));
+        let strides = contiguous_strides(&self.shape);
         let input = unsafe {
             TensorHandleRef::<R>::from_raw_parts(
                 &input_handle,
-                &self.stride,
+                &strides,
                 &self.shape,
-                size_of::<P>(),
+                size_of::<F>(),
             )
         };
+        let (_, indices) = topk::<R, F>(&client, input, 1, self.axis).unwrap();
+
+        let output_shape = self.output_shape();
+        let output_strides = contiguous_strides(&output_shape);
+        let output_handle = client.create(u32::as_bytes(&vec![0; indices.shape.iter().product()]));
         let output = unsafe {
             TensorHandleRef::<R>::from_raw_parts(
                 &output_handle,
-                &output_stride,
+                &output_strides,
                 &output_shape,
-                size_of::<O>(),
+                size_of::<u32>(),
             )
         };
+        reduce::<R, F, u32, ArgMax>(&client, input, output, self.axis, None, ()).unwrap();
 
-        let result = reduce::<R, P, O, K>(
-            &client,
-            input,
-            output,
-            self.axis.unwrap(),
-            self.strategy,
-            (),
+        let topk_indices = client.read_one_tensor(indices.as_copy_descriptor());
+        let argmax_indices = client.read_one(output_handle);
+        assert_eq!(
+            u32::from_bytes(&topk_indices),
+            u32::from_bytes(&argmax_indices)
         );
-        if result.is_err_and(|e| {
-            e == ReduceError::PlanesUnavailable || e == ReduceError::ImprecisePlaneDim
-        }) {
-            return; // We don't test in that case.
+    }
+
+    fn input_values<F: Float>(&self) -> Vec<F> {
+        let test = TestCase {
+            shape: self.shape.clone(),
+            stride: contiguous_strides(&self.shape),
+            axis: None,
+            strategy: None,
+        };
+        test.random_input_values::<F>()
+    }
+
+    // The `k` largest values of each row with their positions, where equal values are ordered
+    // by position, selected with a partial sort.
+    fn cpu_topk<F: Float>(&self, values: &[F]) -> Vec<Vec<(F, u32)>> {
+        let num_rows = values.len() / self.shape[self.axis];
+        let mut rows = vec![Vec::new(); num_rows];
+        for (i, value) in values.iter().enumerate() {
+            let (row, position) = self.row_and_position(&self.shape, i);
+            rows[row].push((*value, position as u32));
         }
 
-        let bytes = client.read_one(output_handle);
-        let output_values = O::from_bytes(&bytes);
-        assert_approx_equal(output_values, &expected_values);
+        let order = |lhs: &(F, u32), rhs: &(F, u32)| {
+            rhs.0.partial_cmp(&lhs.0).unwrap().then(lhs.1.cmp(&rhs.1))
+        };
+        for row in rows.iter_mut() {
+            if self.k < row.len() {
+                row.select_nth_unstable_by(self.k, order);
+                row.truncate(self.k);
+            }
+            row.sort_unstable_by(order);
+        }
+        rows
     }
 
-    pub fn run_shared_sum_test<F, R>(&self, device: &R::Device, input_values: Vec<F>, expected: F)
-    where
-        F: Float + CubeElement + std::fmt::Display,
+    // The row of the element at the index `i` of a contiguous tensor, numbered in row-major order
+    // of the other axes, and its position along the axis.
+    fn row_and_position(&self, shape: &[usize], i: usize) -> (usize, usize) {
+        let strides = contiguous_strides(shape);
+        let mut row = 0;
+        let mut position = 0;
+        for axis in 0..shape.len() {
+            let coordinate = (i / strides[axis]) % shape[axis];
+            if axis == self.axis {
+                position = coordinate;
+            } else {
+                row = row * shape[axis] + coordinate;
+            }
+        }
+        (row, position)
+    }
+
+    fn output_shape(&self) -> Vec<usize> {
+        let mut shape = self.shape.clone();
+        shape[self.axis] = self.k;
+        shape
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadixSortTestKeys {
+    /// Keys spread over the whole range of the type.
+    Random,
+    /// Keys with many ties, where stability matters.
+    FewDistinct,
+    /// Keys already sorted in ascending order.
+    Sorted,
+    /// A single key repeated.
+    AllEqual,
+}
+
+#[derive(Debug)]
+pub struct RadixSortTestCase {
+    pub length: usize,
+    pub keys: RadixSortTestKeys,
+    pub with_values: bool,
+    pub descending: bool,
+}
+
+impl RadixSortTestCase {
+    pub fn test_u32<R: Runtime>(&self, device: &R::Device) {
+        let keys = match self.keys {
+            RadixSortTestKeys::Random => self.random_values(u32::MIN, u32::MAX),
+            RadixSortTestKeys::FewDistinct => self.random_values(0, 15),
+            RadixSortTestKeys::Sorted => (0..self.length as u32).collect(),
+            RadixSortTestKeys::AllEqual => vec![7; self.length],
+        };
+        self.run_radix_sort_test::<R, u32>(device, keys, u32::cmp);
+    }
+
+    pub fn test_f32<R: Runtime>(&self, device: &R::Device) {
+        // Random keys include signed zeros, infinities and the extreme values.
+        let special = [
+            -0.0,
+            0.0,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::MIN,
+            f32::MAX,
+        ];
+        let keys = match self.keys {
+            RadixSortTestKeys::Random => self
+                .random_values(-1_000_000.0, 1_000_000.0)
+                .into_iter()
+                .enumerate()
+                .map(|(i, key)| match i % 97 {
+                    0 => special[(i / 97) % special.len()],
+                    _ => key,
+                })
+                .collect(),
+            RadixSortTestKeys::FewDistinct => self
+                .random_values(-4i32, 4)
+                .into_iter()
+                .map(|key| key as f32 * 0.5)
+                .collect(),
+            RadixSortTestKeys::Sorted => (0..self.length).map(|i| i as f32 - 1000.0).collect(),
+            RadixSortTestKeys::AllEqual => vec![-1.5; self.length],
+        };
+        self.run_radix_sort_test::<R, f32>(device, keys, f32::total_cmp);
+    }
+
+    pub fn run_radix_sort_test<R, K>(
+        &self,
+        device: &R::Device,
+        keys: Vec<K>,
+        compare: fn(&K, &K) -> std::cmp::Ordering,
+    ) where
         R: Runtime,
+        K: RadixKey + std::fmt::Debug,
     {
         let client = R::client(device);
 
-        let input_handle = client.create(F::as_bytes(&input_values));
-        let output_handle = client.create(F::as_bytes(&[F::from_int(0)]));
+        // The values are the original positions of the keys, so ties are sorted by position
+        // when the sort is stable.
+        let mut expected_values = (0..self.length as u32).collect::<Vec<_>>();
+        expected_values.sort_unstable_by(|lhs, rhs| {
+            let (lhs_key, rhs_key) = (&keys[*lhs as usize], &keys[*rhs as usize]);
+            let order = match self.descending {
+                true => compare(rhs_key, lhs_key),
+                false => compare(lhs_key, rhs_key),
+            };
+            order.then(lhs.cmp(rhs))
+        });
+        let expected_keys = expected_values
+            .iter()
+            .map(|i| keys[*i as usize])
+            .collect::<Vec<_>>();
 
-        let input = unsafe {
-            TensorHandleRef::<R>::from_raw_parts(
-                &input_handle,
-                &self.stride,
-                &self.shape,
-                size_of::<F>(),
-            )
+        // Empty buffers aren't supported by all runtimes, so the handles hold at least one element.
+        let mut key_values = keys.clone();
+        key_values.resize(self.length.max(1), K::from_int(0));
+        let mut values = (0..self.length as u32).collect::<Vec<_>>();
+        values.resize(self.length.max(1), 0);
+        let keys_handle = client.create(K::as_bytes(&key_values));
+        let values_handle = client.create(u32::as_bytes(&values));
+
+        let shape = [self.length];
+        let keys = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&keys_handle, &[1], &shape, size_of::<K>())
         };
-        let output = unsafe {
-            TensorHandleRef::<R>::from_raw_parts(&output_handle, &[1], &[1], size_of::<F>())
+        let values = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&values_handle, &[1], &shape, size_of::<u32>())
         };
+        let values = self.with_values.then_some(values);
+        radix_sort::<R, K>(&client, keys, values, self.descending).unwrap();
 
-        let cube_count = 3;
-        let result = shared_sum::<R, F>(&client, input, output, cube_count);
+        let actual_keys = client.read_one(keys_handle);
+        let actual_keys = &K::from_bytes(&actual_keys)[..self.length];
+        for (i, (actual, expected)) in actual_keys.iter().zip(expected_keys.iter()).enumerate() {
+            assert!(
+                compare(actual, expected).is_eq(),
+                "Keys are not sorted: index={i} actual={actual:?}, expected={expected:?}"
+            );
+        }
 
-        if result.is_err() {
-            return; // don't execute the test in that case since atomic adds are not supported.
+        if self.with_values {
+            let actual_values = client.read_one(values_handle);
+            let actual_values = &u32::from_bytes(&actual_values)[..self.length];
+            assert_eq!(actual_values, &expected_values[..], "The sort isn't stable");
         }
-        let bytes = client.read_one(output_handle);
-        let actual = F::from_bytes(&bytes);
-        assert_approx_equal(actual, &[expected]);
     }
 
-    fn num_output_values(&self) -> usize {
-        self.shape.iter().product::<usize>() / self.shape[self.axis.unwrap()]
+    fn random_values<T: rand::distr::uniform::SampleUniform>(&self, low: T, high: T) -> Vec<T> {
+        let rng = StdRng::seed_from_u64(123456789);
+        let distribution = Uniform::new_inclusive(low, high).unwrap();
+        distribution.sample_iter(rng).take(self.length).collect()
     }
+}
 
-    fn to_output_index(&self, input_index: usize) -> Option<usize> {
-        let mut coordinate = self.to_input_coordinate(input_index)?;
-        coordinate[self.axis.unwrap()] = 0;
-        Some(self.from_output_coordinate(coordinate))
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistogramTestData {
+    /// Values spread over all the bins and a few outside of them.
+    Uniform,
+    /// Most values in the same bin, the worst case for the contention on the counters.
+    Skewed,
+}
+
+#[derive(Debug)]
+pub struct HistogramTestCase {
+    pub length: usize,
+    pub data: HistogramTestData,
+    pub num_bins: usize,
+    pub outliers: HistogramOutliers,
+}
+
+impl HistogramTestCase {
+    /// The width of the bins of the float tests, so the values at the center of the bins are exact.
+    const BIN_WIDTH: f32 = 1.0 / 16.0;
+    /// The minimum of the range of the float tests.
+    const RANGE_MIN: f32 = -8.0;
+
+    pub fn test_u8<R: Runtime>(&self, device: &R::Device) {
+        let client = R::client(device);
+        if !client
+            .properties()
+            .supports_type(u8::as_type_native_unchecked())
+        {
+            println!("u8 not supported - skipped");
+            return;
+        }
+
+        let bins = self
+            .random_bins(false)
+            .into_iter()
+            .map(|bin| bin.map(|bin| bin.min(u8::MAX as i64)))
+            .collect::<Vec<_>>();
+        let values = bins.iter().map(|bin| bin.unwrap() as u8).collect();
+        self.run_histogram_test::<R, u8>(
+            device,
+            &bins,
+            values,
+            HistogramOptions::new(self.num_bins),
+        );
     }
 
-    fn to_input_coordinate(&self, index: usize) -> Option<Vec<usize>> {
-        let coordinate = self
-            .stride
+    pub fn test_u32<R: Runtime>(&self, device: &R::Device) {
+        let bins = self.random_bins(false);
+        let values = bins.iter().map(|bin| bin.unwrap() as u32).collect();
+        self.run_histogram_test::<R, u32>(
+            device,
+            &bins,
+            values,
+            HistogramOptions::new(self.num_bins),
+        );
+    }
+
+    pub fn test_f32<R: Runtime>(&self, device: &R::Device) {
+        let bins = self.random_bins(true);
+        let values = bins
             .iter()
-            .zip(self.shape.iter())
-            .map(|(stride, shape)| {
-                if *stride > 0 {
-                    (index / stride) % shape
-                } else {
-                    index % shape
+            .map(|bin| match bin {
+                None => f32::NAN,
+                Some(i64::MAX) => f32::INFINITY,
+                Some(i64::MIN) => f32::NEG_INFINITY,
+                Some(bin) => Self::RANGE_MIN + (*bin as f32 + 0.5) * Self::BIN_WIDTH,
+            })
+            .collect();
+        let range_max = Self::RANGE_MIN + self.num_bins as f32 * Self::BIN_WIDTH;
+        let options = HistogramOptions::new(self.num_bins).with_range(Self::RANGE_MIN, range_max);
+        self.run_histogram_test::<R, f32>(device, &bins, values, options);
+    }
+
+    /// Compare the histogram of the `values` with the histogram computed from their `bins`,
+    /// which are outside of the range of bins for outliers, and `None` for NaNs.
+    pub fn run_histogram_test<R: Runtime, E: HistogramElement>(
+        &self,
+        device: &R::Device,
+        bins: &[Option<i64>],
+        mut values: Vec<E>,
+        options: HistogramOptions,
+    ) {
+        let client = R::client(device);
+        let options = options.with_outliers(self.outliers);
+
+        let num_bins = self.num_bins as i64;
+        let mut expected = vec![0u32; options.num_counters()];
+        for bin in bins {
+            let bin = match (bin, self.outliers) {
+                (Some(bin), _) if (0..num_bins).contains(bin) => Some(*bin),
+                (Some(bin), HistogramOutliers::Clamp) => Some((*bin).clamp(0, num_bins - 1)),
+                (None, HistogramOutliers::Clamp) => None,
+                (_, HistogramOutliers::Overflow) => Some(num_bins),
+            };
+            if let Some(bin) = bin {
+                expected[bin as usize] += 1;
+            }
+        }
+
+        // Empty buffers aren't supported by all runtimes, so the handle holds at least one element.
+        values.resize(self.length.max(1), E::from_int(0));
+        let input_handle = client.create(E::as_bytes(&values));
+        let shape = [self.length];
+        let input = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&input_handle, &[1], &shape, size_of::<E>())
+        };
+        let output = histogram::<R, E>(&client, input, options).unwrap();
+
+        let actual = client.read_one(output.handle);
+        let actual = u32::from_bytes(&actual);
+        assert_eq!(actual, &expected[..]);
+    }
+
+    /// The bins of `length` random values, with an eighth of the number of bins on each side
+    /// of the range for the outliers. When `special` is true, some values are NaNs or infinities.
+    fn random_bins(&self, special: bool) -> Vec<Option<i64>> {
+        let num_bins = self.num_bins as i64;
+        let margin = (num_bins / 8).max(1);
+        let low = if special { -margin } else { 0 };
+        let mut rng = StdRng::seed_from_u64(123456789);
+        let distribution = Uniform::new_inclusive(low, num_bins - 1 + margin).unwrap();
+        let percent = Uniform::new(0, 100).unwrap();
+        let specials = [None, Some(i64::MAX), Some(i64::MIN)];
+
+        (0..self.length)
+            .map(|i| {
+                if special && i % 97 == 0 {
+                    return specials[(i / 97) % specials.len()];
+                }
+                match self.data {
+                    HistogramTestData::Skewed if percent.sample(&mut rng) < 95 => {
+                        Some(num_bins / 3)
+                    }
+                    _ => Some(distribution.sample(&mut rng)),
                 }
             })
-            .collect::<Vec<usize>>();
-        self.validate_input_index(index, &coordinate)
-            .then_some(coordinate)
+            .collect()
     }
+}
 
-    fn validate_input_index(&self, index: usize, coordinate: &[usize]) -> bool {
-        coordinate
-            .iter()
-            .zip(self.stride.iter())
-            .map(|(c, s)| c * s)
-            .sum::<usize>()
-            == index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentedReduceTestData {
+    /// Segments of random lengths between 0 and 200.
+    Uniform,
+    /// A single segment of a million values in the middle of empty segments.
+    OneHuge,
+    /// Only empty segments.
+    AllEmpty,
+    /// Mostly segments of a few values, with a few segments of thousands of values.
+    Skewed,
+}
+
+#[derive(Debug)]
+pub struct SegmentedReduceTestCase {
+    pub num_segments: usize,
+    pub data: SegmentedReduceTestData,
+    pub op: SegmentedReduceOp,
+}
+
+impl SegmentedReduceTestCase {
+    pub fn test_f32<R: Runtime>(&self, device: &R::Device) {
+        self.run_segmented_reduce_test::<R, f32, u32>(device);
     }
 
-    #[allow(clippy::wrong_self_convention)]
-    fn from_output_coordinate(&self, coordinate: Vec<usize>) -> usize {
-        coordinate
-            .into_iter()
-            .zip(self.output_stride().iter())
-            .map(|(c, s)| c * s)
-            .sum()
+    pub fn test_f16<R: Runtime>(&self, device: &R::Device) {
+        let client = R::client(device);
+        if !client
+            .properties()
+            .supports_type(half::f16::as_type_native_unchecked())
+        {
+            println!("f16 not supported - skipped");
+            return;
+        }
+        self.run_segmented_reduce_test::<R, half::f16, u32>(device);
     }
 
-    fn output_stride(&self) -> Vec<usize> {
-        self.shape
-            .iter()
-            .enumerate()
-            .scan(1, |stride, (axis, shape)| {
-                if axis == self.axis.unwrap() {
-                    Some(1)
-                } else {
-                    let current = Some(*stride);
-                    *stride *= shape;
-                    current
+    pub fn test_u64_offsets<R: Runtime>(&self, device: &R::Device) {
+        let client = R::client(device);
+        if !client
+            .properties()
+            .supports_type(u64::as_type_native_unchecked())
+        {
+            println!("u64 not supported - skipped");
+            return;
+        }
+        self.run_segmented_reduce_test::<R, f32, u64>(device);
+    }
+
+    /// Compare the reduction of each segment with a reduction on the CPU. The values are multiples
+    /// of a quarter in `[-2, 2]`, so the sums are exact even for a million values.
+    pub fn run_segmented_reduce_test<R, P, O>(&self, device: &R::Device)
+    where
+        R: Runtime,
+        P: ReducePrecision<EA = f32>,
+        P::EI: CubeElement + Float,
+        O: SegmentOffset,
+    {
+        let client = R::client(device);
+        let lengths = self.random_lengths();
+        let mut offsets = vec![0usize];
+        for length in &lengths {
+            offsets.push(offsets.last().unwrap() + length);
+        }
+        let num_values = *offsets.last().unwrap();
+
+        let mut rng = StdRng::seed_from_u64(123456789);
+        let distribution = Uniform::new_inclusive(-8, 8).unwrap();
+        let values = (0..num_values)
+            .map(|_| distribution.sample(&mut rng) as f32 / 4.0)
+            .collect::<Vec<_>>();
+
+        let expected = offsets
+            .windows(2)
+            .map(|segment| {
+                let values = &values[segment[0]..segment[1]];
+                match self.op {
+                    SegmentedReduceOp::Sum => values.iter().sum::<f32>(),
+                    SegmentedReduceOp::Min => values.iter().copied().fold(f32::MAX, f32::min),
+                    SegmentedReduceOp::Max => values.iter().copied().fold(f32::MIN, f32::max),
+                    SegmentedReduceOp::Mean if values.is_empty() => 0.0,
+                    SegmentedReduceOp::Mean => {
+                        values.iter().map(|v| *v as f64).sum::<f64>() as f32 / values.len() as f32
+                    }
                 }
             })
-            .collect()
+            .collect::<Vec<f32>>();
+
+        // Empty buffers aren't supported by all runtimes, so the handle holds at least one element.
+        let mut input_values = values.iter().map(|v| P::EI::new(*v)).collect::<Vec<_>>();
+        input_values.resize(num_values.max(1), P::EI::from_int(0));
+        let offsets = offsets
+            .iter()
+            .map(|offset| O::from_int(*offset as i64))
+            .collect::<Vec<_>>();
+        let values_handle = client.create(P::EI::as_bytes(&input_values));
+        let offsets_handle = client.create(O::as_bytes(&offsets));
+        let values_shape = [num_values];
+        let offsets_shape = [offsets.len()];
+        let (values, offsets) = unsafe {
+            (
+                TensorHandleRef::<R>::from_raw_parts(
+                    &values_handle,
+                    &[1],
+                    &values_shape,
+                    size_of::<P::EI>(),
+                ),
+                TensorHandleRef::<R>::from_raw_parts(
+                    &offsets_handle,
+                    &[1],
+                    &offsets_shape,
+                    size_of::<O>(),
+                ),
+            )
+        };
+        let output = segmented_reduce::<R, P, O>(&client, values, offsets, self.op).unwrap();
+
+        let actual = client.read_one(output.handle);
+        let actual = f32::from_bytes(&actual);
+        for (segment, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+            let tolerance = match self.op {
+                SegmentedReduceOp::Mean => 1e-5 * e.abs().max(1.0),
+                _ => 0.0,
+            };
+            assert!(
+                (a - e).abs() <= tolerance,
+                "Segment {segment} of length {}: actual={a}, expected={e}",
+                lengths[segment]
+            );
+        }
     }
 
-    fn random_input_values<F: Float>(&self) -> Vec<F> {
-        let size = self.input_size();
-        let rng = StdRng::seed_from_u64(self.pseudo_random_seed());
-        let distribution = Uniform::new_inclusive(-2 * PRECISION, 2 * PRECISION).unwrap();
-        let factor = 1.0 / (PRECISION as f32);
-        distribution
-            .sample_iter(rng)
-            .take(size)
-            .map(|r| F::new(r as f32 * factor))
+    fn random_lengths(&self) -> Vec<usize> {
+        let mut rng = StdRng::seed_from_u64(987654321);
+        let uniform = Uniform::new_inclusive(0, 200).unwrap();
+        let short = Uniform::new_inclusive(0, 4).unwrap();
+        let long = Uniform::new_inclusive(1000, 20_000).unwrap();
+        let percent = Uniform::new(0, 100).unwrap();
+
+        (0..self.num_segments)
+            .map(|segment| match self.data {
+                SegmentedReduceTestData::Uniform => uniform.sample(&mut rng),
+                SegmentedReduceTestData::OneHuge if segment == self.num_segments / 2 => {
+                    (1 << 20) + 3
+                }
+                SegmentedReduceTestData::OneHuge | SegmentedReduceTestData::AllEmpty => 0,
+                SegmentedReduceTestData::Skewed if percent.sample(&mut rng) < 2 => {
+                    long.sample(&mut rng)
+                }
+                SegmentedReduceTestData::Skewed => short.sample(&mut rng),
+            })
             .collect()
-        // (0..size).map(|x| F::from_int(x as i64)).collect() TODO DELETE
     }
+}
 
-    fn input_size(&self) -> usize {
-        let (stride, shape) = self
-            .stride
-            .iter()
-            .zip(self.shape.iter())
-            .max_by_key(|(stride, _)| *stride)
-            .unwrap();
-        stride * shape
+#[derive(Debug)]
+pub struct ScatterAddTestCase {
+    pub target_shape: Vec<usize>,
+    pub axis: usize,
+    pub indices_shape: Vec<usize>,
+    pub policy: IndexPolicy,
+}
+
+impl ScatterAddTestCase {
+    /// Scatter random updates five times to the same target, with many duplicate indices and
+    /// some out of range, and compare the results bit for bit.
+    pub fn test_f32<R: Runtime>(&self, device: &R::Device) {
+        let client = R::client(device);
+        let length = self.target_shape[self.axis];
+        let outer = self.target_shape[..self.axis].iter().product::<usize>();
+        let inner = self.target_shape[self.axis + 1..].iter().product::<usize>();
+        let num_indices = self.indices_shape.iter().product::<usize>();
+
+        let mut rng = StdRng::seed_from_u64(123456789);
+        let index_distribution = Uniform::new_inclusive(-3, length as i32 + 2).unwrap();
+        let indices = (0..num_indices)
+            .map(|_| index_distribution.sample(&mut rng))
+            .collect::<Vec<i32>>();
+        let value_distribution = Uniform::new(-1.0f32, 1.0).unwrap();
+        let mut random_values = |count: usize| {
+            (0..count)
+                .map(|_| value_distribution.sample(&mut rng))
+                .collect::<Vec<f32>>()
+        };
+        let target_values = random_values(outer * length * inner);
+        let update_values = random_values(outer * num_indices * inner);
+
+        let resolve = |index: i32| {
+            let length = length as i32;
+            match self.policy {
+                IndexPolicy::Clamp => Some(index.clamp(0, length - 1)),
+                IndexPolicy::Wrap => Some(index.rem_euclid(length)),
+                IndexPolicy::Check => (0..length).contains(&index).then_some(index),
+            }
+        };
+        let mut expected = target_values.iter().map(|v| *v as f64).collect::<Vec<_>>();
+        for o in 0..outer {
+            for (position, index) in indices.iter().enumerate() {
+                let Some(index) = resolve(*index) else {
+                    continue;
+                };
+                for i in 0..inner {
+                    expected[(o * length + index as usize) * inner + i] +=
+                        update_values[(o * num_indices + position) * inner + i] as f64;
+                }
+            }
+        }
+        let expected = expected.into_iter().map(|v| v as f32).collect::<Vec<_>>();
+        let out_of_range = indices.iter().any(|index| resolve(*index).is_none());
+
+        let mut updates_shape = self.target_shape[..self.axis].to_vec();
+        updates_shape.extend_from_slice(&self.indices_shape);
+        updates_shape.extend_from_slice(&self.target_shape[self.axis + 1..]);
+        let target_strides = contiguous_strides(&self.target_shape);
+        let indices_strides = contiguous_strides(&self.indices_shape);
+        let updates_strides = contiguous_strides(&updates_shape);
+
+        let indices_handle = client.create(i32::as_bytes(&indices));
+        let updates_handle = client.create(f32::as_bytes(&update_values));
+        let indices = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &indices_handle,
+                &indices_strides,
+                &self.indices_shape,
+                size_of::<i32>(),
+            )
+        };
+        let updates = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                &updates_handle,
+                &updates_strides,
+                &updates_shape,
+                size_of::<f32>(),
+            )
+        };
+
+        let runs = (0..5)
+            .map(|_| {
+                let target_handle = client.create(f32::as_bytes(&target_values));
+                let target = unsafe {
+                    TensorHandleRef::<R>::from_raw_parts(
+                        &target_handle,
+                        &target_strides,
+                        &self.target_shape,
+                        size_of::<f32>(),
+                    )
+                };
+                let options = IndexOptions::new(self.policy);
+                let flag = scatter_add_deterministic::<R, f32>(
+                    &client, &target, &indices, &updates, self.axis, options,
+                )
+                .unwrap();
+                assert_eq!(flag.out_of_range::<R>(&client), out_of_range);
+                client.read_one(target_handle)
+            })
+            .collect::<Vec<_>>();
+
+        for run in runs.iter().skip(1) {
+            assert_eq!(run, &runs[0], "The scatters aren't bit-identical");
+        }
+        assert_approx_equal(f32::from_bytes(&runs[0]), &expected);
     }
+}
 
-    // We don't need a fancy crypto-secure seed as this is only for testing.
-    fn pseudo_random_seed(&self) -> u64 {
-        123456789
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
     }
+    strides
 }
 
 pub fn assert_approx_equal<N: Numeric>(actual: &[N], expected: &[N]) {
@@ -0,0 +1,257 @@
+use cubecl_core::prelude::*;
+use cubecl_core::{self as cubecl, LaunchShape};
+use cubecl_std::tensor::TensorHandle;
+use cubecl_std::{CubeOption, CubeOptionArgs, CubeOptionExpand};
+
+use crate::ReduceError;
+
+/// The number of units in a cube selecting the largest values of a chunk.
+const CUBE_SIZE: u32 = 256;
+/// The largest number of values that can be selected in each row.
+pub const MAX_TOPK: usize = 1024;
+/// The smallest chunk of a row sorted by a cube.
+const MIN_CHUNK_SIZE: usize = 1024;
+
+/// Select the `k` largest values of each row along the given `axis` of the `input` tensor,
+/// with their coordinates along the `axis`.
+///
+/// The selected values are sorted in descending order. Like for [`ArgMax`](crate::instructions::ArgMax),
+/// equal values are ordered by their coordinate, the lowest first, and NaNs come after all the other values,
+/// so `k = 1` selects the same coordinate as [`ArgMax`](crate::instructions::ArgMax).
+///
+/// Each cube sorts a chunk of a row in shared memory with a bitonic network and keeps its `k` largest values.
+/// When a row spans multiple chunks, the values kept by all its chunks are merged by the same kernel
+/// until a single chunk remains, without ever sorting the whole row.
+///
+/// Return the values and the coordinates, both with the shape of `input` except for a value of `k` for the given `axis`.
+/// Return an error if the `axis` is larger than the `input` rank, or if `k` is 0 or larger than
+/// the length of the `axis` or [`MAX_TOPK`].
+///
+/// # Example
+///
+/// ```ignore
+/// let client = /* ... */;
+/// let logits = /* a tensor of shape [batch, vocabulary] */;
+///
+/// // Here `R` is a `cubecl::Runtime`.
+/// let (values, indices) = topk::<R, f32>(&client, logits, 50, 1)?;
+/// ```
+pub fn topk<R: Runtime, F: Float + CubeElement>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: TensorHandleRef<R>,
+    k: usize,
+    axis: usize,
+) -> Result<(TensorHandle<R, F>, TensorHandle<R, u32>), ReduceError> {
+    let rank = input.shape.len();
+    if axis >= rank {
+        return Err(ReduceError::InvalidAxis { axis, rank });
+    }
+    let length = input.shape[axis];
+    if k == 0 || k > length || k > MAX_TOPK {
+        return Err(ReduceError::InvalidTopK { k, length });
+    }
+
+    // A chunk keeps the largest values of a power of two at least twice as large as `k`,
+    // so each merge pass divides the number of values of a row by at least 2.
+    let candidates = k.next_power_of_two();
+    let chunk_size = (2 * candidates).max(MIN_CHUNK_SIZE);
+    let params = TopKParams {
+        chunk_size: chunk_size as u32,
+    };
+    let num_rows = input.shape.iter().product::<usize>() / length;
+    let cube_dim = CubeDim::new_1d(CUBE_SIZE);
+
+    let mut values: Option<TensorHandle<R, F>> = None;
+    let mut indices: Option<TensorHandle<R, u32>> = None;
+    let mut length = length;
+    loop {
+        let num_chunks = length.div_ceil(chunk_size);
+        let cube_count = LaunchShape::elementwise(num_rows * num_chunks)
+            .units_per_cube(1)
+            .build::<R>()?
+            .cube_count;
+
+        // The last pass writes the `k` values of each row, the others the candidates of each chunk.
+        let mut output_shape = input.shape.to_vec();
+        output_shape[axis] = match num_chunks {
+            1 => k,
+            _ => num_chunks * candidates,
+        };
+        let values_output = TensorHandle::<R, F>::empty(client, output_shape.clone());
+        let indices_output = TensorHandle::<R, u32>::empty(client, output_shape);
+
+        let (values_input, indices_input) = match (&values, &indices) {
+            (Some(values), Some(indices)) => (
+                values.as_ref().as_tensor_arg(1),
+                CubeOptionArgs::Some(indices.as_ref().as_tensor_arg(1)),
+            ),
+            _ => (input.as_tensor_arg(1), CubeOptionArgs::None),
+        };
+
+        unsafe {
+            topk_kernel::launch_unchecked::<F, R>(
+                client,
+                cube_count,
+                cube_dim,
+                values_input,
+                indices_input,
+                values_output.as_ref().as_tensor_arg(1),
+                indices_output.as_ref().as_tensor_arg(1),
+                ScalarArg::new(axis as u32),
+                params,
+            );
+        }
+
+        if num_chunks == 1 {
+            return Ok((values_output, indices_output));
+        }
+        length = values_output.shape[axis];
+        values = Some(values_output);
+        indices = Some(indices_output);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TopKParams {
+    /// The number of values of a row sorted by a cube, a power of two.
+    pub chunk_size: u32,
+}
+
+/// Sort each chunk of each row of `values` along `axis` and write its largest values into the outputs.
+///
+/// The coordinates of the values are read from `indices` when given, and are the positions along `axis` otherwise.
+/// Each chunk writes as many values as the length of the output `axis` divided by the number of chunks of a row.
+#[cube(launch_unchecked)]
+pub fn topk_kernel<F: Float>(
+    values: &Tensor<F>,
+    indices: &CubeOption<Tensor<u32>>,
+    values_output: &mut Tensor<F>,
+    indices_output: &mut Tensor<u32>,
+    axis: u32,
+    #[comptime] params: TopKParams,
+) {
+    let length = values.shape(axis);
+    let num_chunks = (length + params.chunk_size - 1) / params.chunk_size;
+    let num_rows = values_output.len() / values_output.shape(axis);
+    let row = CUBE_POS / num_chunks;
+    let chunk = CUBE_POS % num_chunks;
+    if row >= num_rows {
+        terminate!();
+    }
+
+    let mut shared_values = SharedMemory::<F>::new(params.chunk_size);
+    let mut shared_indices = SharedMemory::<u32>::new(params.chunk_size);
+
+    // The positions past the end of the row are filled with placeholders, which have the coordinate
+    // `u32::MAX` and come after all the values of the row.
+    let offset = row_offset(values, row, axis);
+    for i in range_stepped(UNIT_POS, params.chunk_size, CUBE_DIM) {
+        let position = chunk * params.chunk_size + i;
+        let mut value = F::min_value();
+        let mut index = u32::MAX;
+        if position < length {
+            value = values[offset + position * values.stride(axis)];
+            index = match indices {
+                CubeOption::Some(indices) => {
+                    indices[row_offset(indices, row, axis) + position * indices.stride(axis)]
+                }
+                CubeOption::None => position,
+            };
+        }
+        shared_values[i] = value;
+        shared_indices[i] = index;
+    }
+    sync_cube();
+
+    bitonic_sort::<F>(&mut shared_values, &mut shared_indices, params.chunk_size);
+
+    let count = values_output.shape(axis) / num_chunks;
+    let values_offset = row_offset(values_output, row, axis);
+    let indices_offset = row_offset(indices_output, row, axis);
+    for i in range_stepped(UNIT_POS, count, CUBE_DIM) {
+        let position = chunk * count + i;
+        values_output[values_offset + position * values_output.stride(axis)] = shared_values[i];
+        indices_output[indices_offset + position * indices_output.stride(axis)] = shared_indices[i];
+    }
+}
+
+/// The offset of the first element of the row, iterating over the other axes in row-major order.
+#[cube]
+fn row_offset<E: CubePrimitive>(tensor: &Tensor<E>, row: u32, axis: u32) -> u32 {
+    let mut offset = 0;
+    let mut remainder = row;
+    let rank = tensor.rank();
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        if dim != axis {
+            offset += (remainder % tensor.shape(dim)) * tensor.stride(dim);
+            remainder /= tensor.shape(dim);
+        }
+    }
+    offset
+}
+
+/// Sort the `size` pairs of values and indices of the shared memories, where `size` is a power of two,
+/// in the order of [`comes_before`].
+#[cube]
+fn bitonic_sort<F: Float>(
+    values: &mut SharedMemory<F>,
+    indices: &mut SharedMemory<u32>,
+    #[comptime] size: u32,
+) {
+    let mut block = 2;
+    while block <= size {
+        let mut stride = block / 2;
+        while stride > 0 {
+            for pair in range_stepped(UNIT_POS, comptime!(size / 2), CUBE_DIM) {
+                let i = 2 * stride * (pair / stride) + pair % stride;
+                let j = i + stride;
+                let value_i = values[i];
+                let index_i = indices[i];
+                let value_j = values[j];
+                let index_j = indices[j];
+
+                // The blocks are sorted in alternating orders, so that each pair of blocks
+                // forms a bitonic sequence for the next block size.
+                let swap = select(
+                    (i & block) == 0,
+                    comes_before::<F>(value_j, index_j, value_i, index_i),
+                    comes_before::<F>(value_i, index_i, value_j, index_j),
+                );
+                if swap {
+                    values[i] = value_j;
+                    indices[i] = index_j;
+                    values[j] = value_i;
+                    indices[j] = index_i;
+                }
+            }
+            sync_cube();
+            stride /= 2;
+        }
+        block *= 2;
+    }
+}
+
+/// Whether the pair `(value, index)` is selected before `(other_value, other_index)`.
+///
+/// Larger values come first, then equal values by lowest index. NaNs come after all the other values,
+/// and the placeholders past the end of the rows after everything.
+#[cube]
+fn comes_before<F: Float>(value: F, index: u32, other_value: F, other_index: u32) -> bool {
+    let rank = selection_rank::<F>(value, index);
+    let other_rank = selection_rank::<F>(other_value, other_index);
+
+    // NaNs are neither larger nor smaller than each other, so they are ordered by index.
+    let by_value = select(
+        value > other_value,
+        true,
+        select(value < other_value, false, index < other_index),
+    );
+    select(rank != other_rank, rank > other_rank, by_value)
+}
+
+/// 2 for the values, 1 for the NaNs and 0 for the placeholders.
+#[cube]
+fn selection_rank<F: Float>(value: F, index: u32) -> u32 {
+    select(index == u32::MAX, 0u32, select(value != value, 1u32, 2u32))
+}
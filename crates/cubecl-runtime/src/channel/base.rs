@@ -3,13 +3,15 @@ use cubecl_common::{ExecutionMode, bytes::Bytes, future::DynFut, profile::Profil
 use crate::{
     data_service::DataTransferId,
     logging::ServerLogger,
-    memory_management::MemoryAllocationMode,
+    memory_management::{MemoryAllocationMode, MemoryCleanupMode, MemoryScopeUsage},
     server::{
-        Allocation, AllocationDescriptor, Binding, Bindings, ComputeServer, CopyDescriptor,
-        CubeCount, IoError, ProfileError, ProfilingToken,
+        Allocation, AllocationDescriptor, BatchId, BatchedKernel, Binding, Bindings, ComputeServer,
+        CopyDescriptor, CubeCount, ExecutionStream, Handle, IoError, NativeEvent, PinnedBuffer,
+        ProfileError, ProfilingToken, StreamEvent,
     },
     storage::{BindingResource, ComputeStorage},
 };
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
@@ -23,11 +25,31 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     ) -> Result<Vec<Allocation>, IoError>;
 
     /// Given bindings, returns owned resources as bytes
+    ///
+    /// The copies are enqueued before returning, only their completion is awaited by the future.
     fn read(&self, descriptors: Vec<CopyDescriptor<'_>>) -> DynFut<Result<Vec<Bytes>, IoError>>;
 
     /// Write bytes to each binding
     fn write(&self, descriptors: Vec<(CopyDescriptor<'_>, &[u8])>) -> Result<(), IoError>;
 
+    /// Allocate a host buffer that can be transferred to and from the device without an
+    /// intermediate copy.
+    fn create_pinned(&self, size: usize) -> Result<PinnedBuffer, IoError>;
+
+    /// Copy the pinned buffer into the destination, giving it back once the copy is done.
+    fn write_from_pinned(
+        &self,
+        src: PinnedBuffer,
+        dst: CopyDescriptor<'_>,
+    ) -> DynFut<Result<PinnedBuffer, IoError>>;
+
+    /// Copy the source into the pinned buffer, giving it back once the copy is done.
+    fn read_to_pinned(
+        &self,
+        src: CopyDescriptor<'_>,
+        dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>>;
+
     /// Send data to another server.
     fn data_transfer_send(&self, id: DataTransferId, src: CopyDescriptor<'_>);
 
@@ -37,15 +59,27 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     /// Wait for the completion of every task in the server.
     fn sync(&self) -> DynFut<()>;
 
+    /// Wait for the completion of every task in the server, returning an error when it can't
+    /// complete.
+    fn try_sync(&self) -> DynFut<Result<(), IoError>>;
+
     /// Given a resource handle, return the storage resource.
     fn get_resource(
         &self,
         binding: Binding,
     ) -> BindingResource<<Server::Storage as ComputeStorage>::Resource>;
 
+    /// Import a resource allocated outside of the server.
+    fn import_resource(
+        &self,
+        resource: <Server::Storage as ComputeStorage>::Resource,
+    ) -> Result<Handle, IoError>;
+
     /// Executes the `kernel` over the given `bindings`.
     ///
-    /// Optionally returns some debug information about the compilation to be logged.
+    /// Returns an error when the memory needed to launch the kernel can't be allocated, or when
+    /// the backend rejects the kernel.
+    ///
     /// # Safety
     ///
     /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
@@ -56,7 +90,139 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
         bindings: Bindings,
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
-    );
+    ) -> Result<(), IoError>;
+
+    /// Compiles the `kernel` without executing it, returning once it is compiled.
+    ///
+    /// Returns an error when the kernel is rejected by the backend.
+    fn precompile(
+        &self,
+        kernel: Server::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError>;
+
+    /// Create a new execution stream.
+    fn create_stream(&self) -> ExecutionStream;
+
+    /// Executes the `kernel` over the given `bindings` on the given stream.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn execute_on(
+        &self,
+        stream: ExecutionStream,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError>;
+
+    /// Executes the `kernels` in order on the given stream.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn execute_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError>;
+
+    /// Records the `kernels` into a batch that can be replayed.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn record_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<BatchId, IoError>;
+
+    /// Executes a recorded batch on the given stream.
+    fn replay_batch(&self, stream: ExecutionStream, batch: BatchId) -> Result<(), IoError>;
+
+    /// Records the `kernels` in place of the kernels of a recorded batch.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn update_batch(
+        &self,
+        stream: ExecutionStream,
+        batch: BatchId,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError>;
+
+    /// Releases a recorded batch.
+    fn release_batch(&self, batch: BatchId);
+
+    /// Given bindings, returns owned resources as bytes, copied on the given stream.
+    fn read_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<CopyDescriptor<'_>>,
+    ) -> DynFut<Result<Vec<Bytes>, IoError>>;
+
+    /// Write bytes to each binding on the given stream.
+    fn write_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<(CopyDescriptor<'_>, &[u8])>,
+    ) -> Result<(), IoError>;
+
+    /// Record an event on the given stream.
+    fn record_event(&self, stream: ExecutionStream) -> StreamEvent;
+
+    /// Make the given stream wait for the event.
+    fn wait_event(&self, stream: ExecutionStream, event: StreamEvent);
+
+    /// Whether the event is reached, without blocking.
+    fn is_event_complete(&self, event: StreamEvent) -> bool;
+
+    /// Wait until the event is reached.
+    fn sync_event(&self, event: StreamEvent) -> DynFut<()>;
+
+    /// The time elapsed on the device between two events.
+    fn event_elapsed(
+        &self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError>;
+
+    /// Release the event.
+    fn release_event(&self, event: StreamEvent);
+
+    /// Make the given stream wait for the native event.
+    ///
+    /// # Safety
+    ///
+    /// The event must be a valid event of the device of the server.
+    unsafe fn wait_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError>;
+
+    /// Record the native event on the given stream.
+    ///
+    /// # Safety
+    ///
+    /// The event must be a valid event of the device of the server.
+    unsafe fn record_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError>;
+
+    /// Wait for the completion of every task in the server, on every stream.
+    fn sync_all(&self) -> DynFut<()>;
 
     /// Flush outstanding work of the server.
     fn flush(&self);
@@ -64,11 +230,20 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     /// Get the current memory usage of the server.
     fn memory_usage(&self) -> crate::memory_management::MemoryUsage;
 
+    /// Attribute the memory allocated from now on to the tag.
+    fn push_memory_scope(&self, tag: String);
+
+    /// Stop attributing the memory allocated to the innermost scope.
+    fn pop_memory_scope(&self);
+
+    /// Get the memory attributed to each scope tag.
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage>;
+
     /// Change the memory allocation mode.
     fn allocation_mode(&self, mode: MemoryAllocationMode);
 
     /// Ask the server to release memory that it can release.
-    fn memory_cleanup(&self);
+    fn memory_cleanup(&self, mode: MemoryCleanupMode);
 
     /// Start a profile on the server. This allows you to profile kernels.
     ///
@@ -81,4 +256,11 @@ pub trait ComputeChannel<Server: ComputeServer>: Clone + core::fmt::Debug + Send
     ///
     /// You can retrieve the Duration of the client profile asynchronously. This function will handle any required synchronization.
     fn end_profile(&self, token: ProfilingToken) -> Result<ProfileDuration, ProfileError>;
+
+    /// Start timing the work submitted from now on with device timestamps, without any
+    /// synchronization. Returns `None` when the server doesn't support it.
+    fn start_device_timing(&self) -> Option<ProfilingToken>;
+
+    /// End a device timing and return a lazily resolved [`ProfileDuration`].
+    fn end_device_timing(&self, token: ProfilingToken) -> Result<ProfileDuration, ProfileError>;
 }
@@ -1,13 +1,16 @@
 use super::ComputeChannel;
 use crate::data_service::DataTransferId;
+use crate::memory_management::{MemoryCleanupMode, MemoryScopeUsage};
 use crate::server::{
-    Binding, Bindings, ComputeServer, CopyDescriptor, CubeCount, ProfileError, ProfilingToken,
+    BatchId, BatchedKernel, Binding, Bindings, ComputeServer, CopyDescriptor, CubeCount,
+    ExecutionStream, Handle, NativeEvent, PinnedBuffer, ProfileError, ProfilingToken, StreamEvent,
 };
 use crate::storage::{BindingResource, ComputeStorage};
 use crate::{
     logging::ServerLogger,
     server::{Allocation, AllocationDescriptor, IoError},
 };
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use cubecl_common::ExecutionMode;
@@ -71,6 +74,29 @@ where
         server.write(descriptors)
     }
 
+    fn create_pinned(&self, size: usize) -> Result<PinnedBuffer, IoError> {
+        let mut server = self.server.borrow_mut();
+        server.create_pinned(size)
+    }
+
+    fn write_from_pinned(
+        &self,
+        src: PinnedBuffer,
+        dst: CopyDescriptor<'_>,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let mut server = self.server.borrow_mut();
+        server.write_from_pinned(src, dst)
+    }
+
+    fn read_to_pinned(
+        &self,
+        src: CopyDescriptor<'_>,
+        dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let mut server = self.server.borrow_mut();
+        server.read_to_pinned(src, dst)
+    }
+
     fn data_transfer_send(&self, id: DataTransferId, src: CopyDescriptor<'_>) {
         let mut server = self.server.borrow_mut();
         server.register_src(id, src);
@@ -86,6 +112,11 @@ where
         server.sync()
     }
 
+    fn try_sync(&self) -> DynFut<Result<(), IoError>> {
+        let mut server = self.server.borrow_mut();
+        server.try_sync()
+    }
+
     fn get_resource(
         &self,
         binding: Binding,
@@ -93,6 +124,13 @@ where
         self.server.borrow_mut().get_resource(binding)
     }
 
+    fn import_resource(
+        &self,
+        resource: <Server::Storage as ComputeStorage>::Resource,
+    ) -> Result<Handle, IoError> {
+        self.server.borrow_mut().import_resource(resource)
+    }
+
     unsafe fn execute(
         &self,
         kernel_description: Server::Kernel,
@@ -100,7 +138,7 @@ where
         bindings: Bindings,
         kind: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
         unsafe {
             self.server
                 .borrow_mut()
@@ -108,6 +146,156 @@ where
         }
     }
 
+    fn precompile(
+        &self,
+        kernel: Server::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        self.server.borrow_mut().precompile(kernel, mode, logger)
+    }
+
+    fn create_stream(&self) -> ExecutionStream {
+        let mut server = self.server.borrow_mut();
+        server.create_stream()
+    }
+
+    unsafe fn execute_on(
+        &self,
+        stream: ExecutionStream,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe {
+            self.server
+                .borrow_mut()
+                .execute_on(stream, kernel, count, bindings, mode, logger)
+        }
+    }
+
+    unsafe fn execute_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe {
+            self.server
+                .borrow_mut()
+                .execute_batch(stream, kernels, logger)
+        }
+    }
+
+    unsafe fn record_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<BatchId, IoError> {
+        unsafe {
+            self.server
+                .borrow_mut()
+                .record_batch(stream, kernels, logger)
+        }
+    }
+
+    fn replay_batch(&self, stream: ExecutionStream, batch: BatchId) -> Result<(), IoError> {
+        self.server.borrow_mut().replay_batch(stream, batch)
+    }
+
+    unsafe fn update_batch(
+        &self,
+        stream: ExecutionStream,
+        batch: BatchId,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe {
+            self.server
+                .borrow_mut()
+                .update_batch(stream, batch, kernels, logger)
+        }
+    }
+
+    fn release_batch(&self, batch: BatchId) {
+        self.server.borrow_mut().release_batch(batch)
+    }
+
+    fn read_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<CopyDescriptor<'_>>,
+    ) -> DynFut<Result<Vec<Bytes>, IoError>> {
+        let mut server = self.server.borrow_mut();
+        server.read_on(stream, descriptors)
+    }
+
+    fn write_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<(CopyDescriptor<'_>, &[u8])>,
+    ) -> Result<(), IoError> {
+        let mut server = self.server.borrow_mut();
+        server.write_on(stream, descriptors)
+    }
+
+    fn record_event(&self, stream: ExecutionStream) -> StreamEvent {
+        let mut server = self.server.borrow_mut();
+        server.record_event(stream)
+    }
+
+    fn wait_event(&self, stream: ExecutionStream, event: StreamEvent) {
+        let mut server = self.server.borrow_mut();
+        server.wait_event(stream, event)
+    }
+
+    fn is_event_complete(&self, event: StreamEvent) -> bool {
+        self.server.borrow_mut().is_event_complete(event)
+    }
+
+    fn sync_event(&self, event: StreamEvent) -> DynFut<()> {
+        let mut server = self.server.borrow_mut();
+        server.sync_event(event)
+    }
+
+    fn event_elapsed(
+        &self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.server.borrow_mut().event_elapsed(start, end)
+    }
+
+    fn release_event(&self, event: StreamEvent) {
+        self.server.borrow_mut().release_event(event)
+    }
+
+    unsafe fn wait_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let mut server = self.server.borrow_mut();
+        unsafe { server.wait_native_event(stream, event) }
+    }
+
+    unsafe fn record_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let mut server = self.server.borrow_mut();
+        unsafe { server.record_native_event(stream, event) }
+    }
+
+    fn sync_all(&self) -> DynFut<()> {
+        let mut server = self.server.borrow_mut();
+        server.sync_all()
+    }
+
     fn flush(&self) {
         self.server.borrow_mut().flush()
     }
@@ -116,8 +304,20 @@ where
         self.server.borrow_mut().memory_usage()
     }
 
-    fn memory_cleanup(&self) {
-        self.server.borrow_mut().memory_cleanup();
+    fn push_memory_scope(&self, tag: String) {
+        self.server.borrow_mut().push_memory_scope(tag)
+    }
+
+    fn pop_memory_scope(&self) {
+        self.server.borrow_mut().pop_memory_scope()
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.server.borrow_mut().memory_scope_usage()
+    }
+
+    fn memory_cleanup(&self, mode: MemoryCleanupMode) {
+        self.server.borrow_mut().memory_cleanup(mode);
     }
 
     fn start_profile(&self) -> ProfilingToken {
@@ -128,6 +328,14 @@ where
         self.server.borrow_mut().end_profile(token)
     }
 
+    fn start_device_timing(&self) -> Option<ProfilingToken> {
+        self.server.borrow_mut().start_device_timing()
+    }
+
+    fn end_device_timing(&self, token: ProfilingToken) -> Result<ProfileDuration, ProfileError> {
+        self.server.borrow_mut().end_device_timing(token)
+    }
+
     fn allocation_mode(&self, mode: crate::memory_management::MemoryAllocationMode) {
         self.server.borrow_mut().allocation_mode(mode)
     }
@@ -11,10 +11,11 @@ use super::ComputeChannel;
 use crate::{
     data_service::DataTransferId,
     logging::ServerLogger,
-    memory_management::{MemoryAllocationMode, MemoryUsage},
+    memory_management::{MemoryAllocationMode, MemoryCleanupMode, MemoryScopeUsage, MemoryUsage},
     server::{
-        Allocation, AllocationDescriptor, AllocationKind, Binding, Bindings, ComputeServer,
-        CopyDescriptor, CubeCount, IoError, ProfileError, ProfilingToken,
+        Allocation, AllocationDescriptor, AllocationKind, BatchId, BatchedKernel, Binding,
+        Bindings, ComputeServer, CopyDescriptor, CubeCount, ExecutionStream, Handle, IoError,
+        NativeEvent, PinnedBuffer, ProfileError, ProfilingToken, StreamEvent,
     },
     storage::{BindingResource, ComputeStorage},
 };
@@ -99,32 +100,100 @@ where
         Callback<Result<Vec<Allocation>, IoError>>,
     ),
     Read(
+        ExecutionStream,
         Vec<CopyDescriptorOwned>,
-        Callback<Result<Vec<Bytes>, IoError>>,
+        Callback<DynFut<Result<Vec<Bytes>, IoError>>>,
     ),
     Write(
+        ExecutionStream,
         Vec<(CopyDescriptorOwned, Vec<u8>)>,
         Callback<Result<(), IoError>>,
     ),
+    CreatePinned(usize, Callback<Result<PinnedBuffer, IoError>>),
+    WriteFromPinned(
+        PinnedBuffer,
+        CopyDescriptorOwned,
+        Callback<DynFut<Result<PinnedBuffer, IoError>>>,
+    ),
+    ReadToPinned(
+        CopyDescriptorOwned,
+        PinnedBuffer,
+        Callback<DynFut<Result<PinnedBuffer, IoError>>>,
+    ),
     GetResource(
         Binding,
         Callback<BindingResource<<Server::Storage as ComputeStorage>::Resource>>,
     ),
+    ImportResource(
+        <Server::Storage as ComputeStorage>::Resource,
+        Callback<Result<Handle, IoError>>,
+    ),
     ExecuteKernel(
+        ExecutionStream,
         (Server::Kernel, CubeCount, ExecutionMode),
         Bindings,
         Arc<ServerLogger>,
+        Callback<Result<(), IoError>>,
+    ),
+    Precompile(
+        (Server::Kernel, ExecutionMode),
+        Arc<ServerLogger>,
+        Callback<Result<(), IoError>>,
+    ),
+    ExecuteBatch(
+        ExecutionStream,
+        Vec<BatchedKernel<Server::Kernel>>,
+        Arc<ServerLogger>,
+        Callback<Result<(), IoError>>,
+    ),
+    RecordBatch(
+        ExecutionStream,
+        Vec<BatchedKernel<Server::Kernel>>,
+        Arc<ServerLogger>,
+        Callback<Result<BatchId, IoError>>,
+    ),
+    ReplayBatch(ExecutionStream, BatchId, Callback<Result<(), IoError>>),
+    UpdateBatch(
+        ExecutionStream,
+        BatchId,
+        Vec<BatchedKernel<Server::Kernel>>,
+        Arc<ServerLogger>,
+        Callback<Result<(), IoError>>,
     ),
+    ReleaseBatch(BatchId),
     Flush,
     Sync(Callback<()>),
+    TrySync(Callback<Result<(), IoError>>),
+    CreateStream(Callback<ExecutionStream>),
+    RecordEvent(ExecutionStream, Callback<StreamEvent>),
+    WaitEvent(ExecutionStream, StreamEvent),
+    IsEventComplete(StreamEvent, Callback<bool>),
+    SyncEvent(StreamEvent, Callback<()>),
+    EventElapsed(
+        StreamEvent,
+        StreamEvent,
+        Callback<Result<ProfileDuration, ProfileError>>,
+    ),
+    ReleaseEvent(StreamEvent),
+    WaitNativeEvent(ExecutionStream, NativeEvent, Callback<Result<(), IoError>>),
+    RecordNativeEvent(ExecutionStream, NativeEvent, Callback<Result<(), IoError>>),
+    SyncAll(Callback<()>),
     MemoryUsage(Callback<MemoryUsage>),
-    MemoryCleanup,
+    PushMemoryScope(String),
+    PopMemoryScope,
+    MemoryScopeUsage(Callback<Vec<MemoryScopeUsage>>),
+    MemoryCleanup(MemoryCleanupMode),
     AllocationMode(MemoryAllocationMode),
     StartProfile(Callback<ProfilingToken>),
     StopMeasure(
         Callback<Result<ProfileDuration, ProfileError>>,
         ProfilingToken,
     ),
+    StartDeviceTiming(Callback<Option<ProfilingToken>>),
+    StopDeviceTiming(
+        Callback<Result<ProfileDuration, ProfileError>>,
+        ProfilingToken,
+    ),
     DataTransferSend(DataTransferId, CopyDescriptorOwned),
     DataTransferRecv(DataTransferId, CopyDescriptorOwned),
 }
@@ -145,38 +214,137 @@ where
                         let data = server.create(descriptors);
                         callback.send(data).await.unwrap();
                     }
-                    Message::Read(descriptors, callback) => {
+                    Message::Read(stream, descriptors, callback) => {
                         let descriptors = descriptors.iter().map(|it| it.as_ref()).collect();
-                        let data = server.read(descriptors).await;
-                        callback.send(data).await.unwrap();
+                        // Only the copies are enqueued here, the caller waits for their
+                        // completion so the server can keep processing other messages.
+                        let fut = server.read_on(stream, descriptors);
+                        callback.send(fut).await.unwrap();
                     }
-                    Message::Write(descriptors, callback) => {
+                    Message::Write(stream, descriptors, callback) => {
                         let descriptors = descriptors
                             .iter()
                             .map(|(desc, data)| (desc.as_ref(), data.as_slice()))
                             .collect();
-                        let data = server.write(descriptors);
+                        let data = server.write_on(stream, descriptors);
+                        callback.send(data).await.unwrap();
+                    }
+                    Message::CreatePinned(size, callback) => {
+                        let data = server.create_pinned(size);
                         callback.send(data).await.unwrap();
                     }
+                    Message::WriteFromPinned(src, dst, callback) => {
+                        let fut = server.write_from_pinned(src, dst.as_ref());
+                        callback.send(fut).await.unwrap();
+                    }
+                    Message::ReadToPinned(src, dst, callback) => {
+                        let fut = server.read_to_pinned(src.as_ref(), dst);
+                        callback.send(fut).await.unwrap();
+                    }
                     Message::GetResource(binding, callback) => {
                         let data = server.get_resource(binding);
                         callback.send(data).await.unwrap();
                     }
-                    Message::ExecuteKernel(kernel, bindings, logger) => unsafe {
-                        server.execute(kernel.0, kernel.1, bindings, kernel.2, logger);
-                    },
+                    Message::ImportResource(resource, callback) => {
+                        let handle = server.import_resource(resource);
+                        callback.send(handle).await.unwrap();
+                    }
+                    Message::ExecuteKernel(stream, kernel, bindings, logger, callback) => {
+                        let result = unsafe {
+                            server
+                                .execute_on(stream, kernel.0, kernel.1, bindings, kernel.2, logger)
+                        };
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::Precompile(kernel, logger, callback) => {
+                        let result = server.precompile(kernel.0, kernel.1, logger);
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::ExecuteBatch(stream, kernels, logger, callback) => {
+                        let result = unsafe { server.execute_batch(stream, kernels, logger) };
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::RecordBatch(stream, kernels, logger, callback) => {
+                        let result = unsafe { server.record_batch(stream, kernels, logger) };
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::ReplayBatch(stream, batch, callback) => {
+                        callback
+                            .send(server.replay_batch(stream, batch))
+                            .await
+                            .unwrap();
+                    }
+                    Message::UpdateBatch(stream, batch, kernels, logger, callback) => {
+                        let result = unsafe { server.update_batch(stream, batch, kernels, logger) };
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::ReleaseBatch(batch) => {
+                        server.release_batch(batch);
+                    }
+                    Message::CreateStream(callback) => {
+                        callback.send(server.create_stream()).await.unwrap();
+                    }
+                    Message::RecordEvent(stream, callback) => {
+                        callback.send(server.record_event(stream)).await.unwrap();
+                    }
+                    Message::WaitEvent(stream, event) => {
+                        server.wait_event(stream, event);
+                    }
+                    Message::IsEventComplete(event, callback) => {
+                        callback
+                            .send(server.is_event_complete(event))
+                            .await
+                            .unwrap();
+                    }
+                    Message::SyncEvent(event, callback) => {
+                        server.sync_event(event).await;
+                        callback.send(()).await.unwrap();
+                    }
+                    Message::EventElapsed(start, end, callback) => {
+                        callback
+                            .send(server.event_elapsed(start, end))
+                            .await
+                            .unwrap();
+                    }
+                    Message::ReleaseEvent(event) => {
+                        server.release_event(event);
+                    }
+                    Message::WaitNativeEvent(stream, event, callback) => {
+                        let result = unsafe { server.wait_native_event(stream, event) };
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::RecordNativeEvent(stream, event, callback) => {
+                        let result = unsafe { server.record_native_event(stream, event) };
+                        callback.send(result).await.unwrap();
+                    }
+                    Message::SyncAll(callback) => {
+                        server.sync_all().await;
+                        callback.send(()).await.unwrap();
+                    }
                     Message::Sync(callback) => {
                         server.sync().await;
                         callback.send(()).await.unwrap();
                     }
+                    Message::TrySync(callback) => {
+                        callback.send(server.try_sync().await).await.unwrap();
+                    }
                     Message::Flush => {
                         server.flush();
                     }
                     Message::MemoryUsage(callback) => {
                         callback.send(server.memory_usage()).await.unwrap();
                     }
-                    Message::MemoryCleanup => {
-                        server.memory_cleanup();
+                    Message::PushMemoryScope(tag) => {
+                        server.push_memory_scope(tag);
+                    }
+                    Message::PopMemoryScope => {
+                        server.pop_memory_scope();
+                    }
+                    Message::MemoryScopeUsage(callback) => {
+                        callback.send(server.memory_scope_usage()).await.unwrap();
+                    }
+                    Message::MemoryCleanup(mode) => {
+                        server.memory_cleanup(mode);
                     }
                     Message::StartProfile(callback) => {
                         let token = server.start_profile();
@@ -185,6 +353,16 @@ where
                     Message::StopMeasure(callback, token) => {
                         callback.send(server.end_profile(token)).await.unwrap();
                     }
+                    Message::StartDeviceTiming(callback) => {
+                        let token = server.start_device_timing();
+                        callback.send(token).await.unwrap();
+                    }
+                    Message::StopDeviceTiming(callback, token) => {
+                        callback
+                            .send(server.end_device_timing(token))
+                            .await
+                            .unwrap();
+                    }
                     Message::AllocationMode(mode) => {
                         server.allocation_mode(mode);
                     }
@@ -233,20 +411,38 @@ where
     }
 
     fn read(&self, descriptors: Vec<CopyDescriptor<'_>>) -> DynFut<Result<Vec<Bytes>, IoError>> {
-        let sender = self.state.sender.clone();
+        self.read_on(ExecutionStream::default(), descriptors)
+    }
+
+    fn read_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<CopyDescriptor<'_>>,
+    ) -> DynFut<Result<Vec<Bytes>, IoError>> {
         let descriptors = descriptors.into_iter().map(|it| it.into()).collect();
+        let (callback, response) = async_channel::unbounded();
+
+        // Send the message right away, so the read is ordered with the other submissions.
+        self.state
+            .sender
+            .send_blocking(Message::Read(stream, descriptors, callback))
+            .unwrap();
 
         Box::pin(async move {
-            let (callback, response) = async_channel::unbounded();
-            sender
-                .send(Message::Read(descriptors, callback))
-                .await
-                .unwrap();
-            handle_response(response.recv().await)
+            let fut = handle_response(response.recv().await);
+            fut.await
         })
     }
 
     fn write(&self, descriptors: Vec<(CopyDescriptor<'_>, &[u8])>) -> Result<(), IoError> {
+        self.write_on(ExecutionStream::default(), descriptors)
+    }
+
+    fn write_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<(CopyDescriptor<'_>, &[u8])>,
+    ) -> Result<(), IoError> {
         let descriptors = descriptors
             .into_iter()
             .map(|(desc, data)| (desc.into(), data.to_vec()))
@@ -256,7 +452,50 @@ where
 
         self.state
             .sender
-            .send_blocking(Message::Write(descriptors, callback))
+            .send_blocking(Message::Write(stream, descriptors, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn create_pinned(&self, size: usize) -> Result<PinnedBuffer, IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::CreatePinned(size, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn write_from_pinned(
+        &self,
+        src: PinnedBuffer,
+        dst: CopyDescriptor<'_>,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let (callback, response) = async_channel::unbounded();
+
+        // The transfer is enqueued before returning, only its completion is awaited.
+        self.state
+            .sender
+            .send_blocking(Message::WriteFromPinned(src, dst.into(), callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn read_to_pinned(
+        &self,
+        src: CopyDescriptor<'_>,
+        dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let (callback, response) = async_channel::unbounded();
+
+        // The transfer is enqueued before returning, only its completion is awaited.
+        self.state
+            .sender
+            .send_blocking(Message::ReadToPinned(src.into(), dst, callback))
             .unwrap();
 
         handle_response(response.recv_blocking())
@@ -294,6 +533,20 @@ where
         handle_response(response.recv_blocking())
     }
 
+    fn import_resource(
+        &self,
+        resource: <Server::Storage as ComputeStorage>::Resource,
+    ) -> Result<Handle, IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::ImportResource(resource, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
     unsafe fn execute(
         &self,
         kernel: Server::Kernel,
@@ -301,15 +554,242 @@ where
         bindings: Bindings,
         kind: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
+        unsafe {
+            self.execute_on(
+                ExecutionStream::default(),
+                kernel,
+                count,
+                bindings,
+                kind,
+                logger,
+            )
+        }
+    }
+
+    unsafe fn execute_on(
+        &self,
+        stream: ExecutionStream,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+        kind: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
         self.state
             .sender
             .send_blocking(Message::ExecuteKernel(
+                stream,
                 (kernel, count, kind),
                 bindings,
                 logger,
+                callback,
+            ))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn precompile(
+        &self,
+        kernel: Server::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::Precompile((kernel, mode), logger, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    unsafe fn execute_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::ExecuteBatch(stream, kernels, logger, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    unsafe fn record_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<BatchId, IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::RecordBatch(stream, kernels, logger, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn replay_batch(&self, stream: ExecutionStream, batch: BatchId) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::ReplayBatch(stream, batch, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    unsafe fn update_batch(
+        &self,
+        stream: ExecutionStream,
+        batch: BatchId,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::UpdateBatch(
+                stream, batch, kernels, logger, callback,
             ))
             .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn release_batch(&self, batch: BatchId) {
+        self.state
+            .sender
+            .send_blocking(Message::ReleaseBatch(batch))
+            .unwrap();
+    }
+
+    fn create_stream(&self) -> ExecutionStream {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::CreateStream(callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn record_event(&self, stream: ExecutionStream) -> StreamEvent {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::RecordEvent(stream, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn wait_event(&self, stream: ExecutionStream, event: StreamEvent) {
+        self.state
+            .sender
+            .send_blocking(Message::WaitEvent(stream, event))
+            .unwrap();
+    }
+
+    fn is_event_complete(&self, event: StreamEvent) -> bool {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::IsEventComplete(event, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn sync_event(&self, event: StreamEvent) -> DynFut<()> {
+        let sender = self.state.sender.clone();
+
+        Box::pin(async move {
+            let (callback, response) = async_channel::unbounded();
+            sender
+                .send(Message::SyncEvent(event, callback))
+                .await
+                .unwrap();
+            handle_response(response.recv().await)
+        })
+    }
+
+    fn event_elapsed(
+        &self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::EventElapsed(start, end, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn release_event(&self, event: StreamEvent) {
+        self.state
+            .sender
+            .send_blocking(Message::ReleaseEvent(event))
+            .unwrap();
+    }
+
+    unsafe fn wait_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::WaitNativeEvent(stream, event, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    unsafe fn record_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::RecordNativeEvent(stream, event, callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn sync_all(&self) -> DynFut<()> {
+        let sender = self.state.sender.clone();
+
+        Box::pin(async move {
+            let (callback, response) = async_channel::unbounded();
+            sender.send(Message::SyncAll(callback)).await.unwrap();
+            handle_response(response.recv().await)
+        })
     }
 
     fn flush(&self) {
@@ -326,6 +806,16 @@ where
         })
     }
 
+    fn try_sync(&self) -> DynFut<Result<(), IoError>> {
+        let sender = self.state.sender.clone();
+
+        Box::pin(async move {
+            let (callback, response) = async_channel::unbounded();
+            sender.send(Message::TrySync(callback)).await.unwrap();
+            handle_response(response.recv().await)
+        })
+    }
+
     fn memory_usage(&self) -> crate::memory_management::MemoryUsage {
         let (callback, response) = async_channel::unbounded();
         self.state
@@ -335,10 +825,33 @@ where
         handle_response(response.recv_blocking())
     }
 
-    fn memory_cleanup(&self) {
+    fn push_memory_scope(&self, tag: String) {
+        self.state
+            .sender
+            .send_blocking(Message::PushMemoryScope(tag))
+            .unwrap()
+    }
+
+    fn pop_memory_scope(&self) {
+        self.state
+            .sender
+            .send_blocking(Message::PopMemoryScope)
+            .unwrap()
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::MemoryScopeUsage(callback))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
+    fn memory_cleanup(&self, mode: MemoryCleanupMode) {
         self.state
             .sender
-            .send_blocking(Message::MemoryCleanup)
+            .send_blocking(Message::MemoryCleanup(mode))
             .unwrap()
     }
 
@@ -362,6 +875,26 @@ where
         handle_response(response.recv_blocking())
     }
 
+    fn start_device_timing(&self) -> Option<ProfilingToken> {
+        let (callback, response) = async_channel::unbounded();
+
+        self.state
+            .sender
+            .send_blocking(Message::StartDeviceTiming(callback))
+            .unwrap();
+
+        handle_response(response.recv_blocking())
+    }
+
+    fn end_device_timing(&self, token: ProfilingToken) -> Result<ProfileDuration, ProfileError> {
+        let (callback, response) = async_channel::unbounded();
+        self.state
+            .sender
+            .send_blocking(Message::StopDeviceTiming(callback, token))
+            .unwrap();
+        handle_response(response.recv_blocking())
+    }
+
     fn allocation_mode(&self, mode: crate::memory_management::MemoryAllocationMode) {
         self.state
             .sender
@@ -1,14 +1,16 @@
 use super::ComputeChannel;
 use crate::data_service::DataTransferId;
-use crate::memory_management::MemoryAllocationMode;
+use crate::memory_management::{MemoryAllocationMode, MemoryCleanupMode, MemoryScopeUsage};
 use crate::server::{
-    Binding, Bindings, ComputeServer, CopyDescriptor, CubeCount, ProfileError, ProfilingToken,
+    BatchId, BatchedKernel, Binding, Bindings, ComputeServer, CopyDescriptor, CubeCount,
+    ExecutionStream, Handle, NativeEvent, PinnedBuffer, ProfileError, ProfilingToken, StreamEvent,
 };
 use crate::storage::{BindingResource, ComputeStorage};
 use crate::{
     logging::ServerLogger,
     server::{Allocation, AllocationDescriptor, IoError},
 };
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use cubecl_common::ExecutionMode;
@@ -65,6 +67,29 @@ where
         server.write(descriptors)
     }
 
+    fn create_pinned(&self, size: usize) -> Result<PinnedBuffer, IoError> {
+        let mut server = self.server.lock();
+        server.create_pinned(size)
+    }
+
+    fn write_from_pinned(
+        &self,
+        src: PinnedBuffer,
+        dst: CopyDescriptor<'_>,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let mut server = self.server.lock();
+        server.write_from_pinned(src, dst)
+    }
+
+    fn read_to_pinned(
+        &self,
+        src: CopyDescriptor<'_>,
+        dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let mut server = self.server.lock();
+        server.read_to_pinned(src, dst)
+    }
+
     fn data_transfer_send(&self, id: DataTransferId, src: CopyDescriptor<'_>) {
         let mut server = self.server.lock();
         server.register_src(id, src);
@@ -80,6 +105,11 @@ where
         server.sync()
     }
 
+    fn try_sync(&self) -> DynFut<Result<(), IoError>> {
+        let mut server = self.server.lock();
+        server.try_sync()
+    }
+
     fn get_resource(
         &self,
         binding: Binding,
@@ -87,6 +117,13 @@ where
         self.server.lock().get_resource(binding)
     }
 
+    fn import_resource(
+        &self,
+        resource: <Server::Storage as ComputeStorage>::Resource,
+    ) -> Result<Handle, IoError> {
+        self.server.lock().import_resource(resource)
+    }
+
     unsafe fn execute(
         &self,
         kernel: Server::Kernel,
@@ -94,7 +131,7 @@ where
         handles: Bindings,
         kind: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
         unsafe {
             self.server
                 .lock()
@@ -102,6 +139,148 @@ where
         }
     }
 
+    fn precompile(
+        &self,
+        kernel: Server::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        self.server.lock().precompile(kernel, mode, logger)
+    }
+
+    fn create_stream(&self) -> ExecutionStream {
+        let mut server = self.server.lock();
+        server.create_stream()
+    }
+
+    unsafe fn execute_on(
+        &self,
+        stream: ExecutionStream,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe {
+            self.server
+                .lock()
+                .execute_on(stream, kernel, count, bindings, mode, logger)
+        }
+    }
+
+    unsafe fn execute_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe { self.server.lock().execute_batch(stream, kernels, logger) }
+    }
+
+    unsafe fn record_batch(
+        &self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<BatchId, IoError> {
+        unsafe { self.server.lock().record_batch(stream, kernels, logger) }
+    }
+
+    fn replay_batch(&self, stream: ExecutionStream, batch: BatchId) -> Result<(), IoError> {
+        self.server.lock().replay_batch(stream, batch)
+    }
+
+    unsafe fn update_batch(
+        &self,
+        stream: ExecutionStream,
+        batch: BatchId,
+        kernels: Vec<BatchedKernel<Server::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe {
+            self.server
+                .lock()
+                .update_batch(stream, batch, kernels, logger)
+        }
+    }
+
+    fn release_batch(&self, batch: BatchId) {
+        self.server.lock().release_batch(batch)
+    }
+
+    fn read_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<CopyDescriptor<'_>>,
+    ) -> DynFut<Result<Vec<Bytes>, IoError>> {
+        let mut server = self.server.lock();
+        server.read_on(stream, descriptors)
+    }
+
+    fn write_on(
+        &self,
+        stream: ExecutionStream,
+        descriptors: Vec<(CopyDescriptor<'_>, &[u8])>,
+    ) -> Result<(), IoError> {
+        let mut server = self.server.lock();
+        server.write_on(stream, descriptors)
+    }
+
+    fn record_event(&self, stream: ExecutionStream) -> StreamEvent {
+        let mut server = self.server.lock();
+        server.record_event(stream)
+    }
+
+    fn wait_event(&self, stream: ExecutionStream, event: StreamEvent) {
+        let mut server = self.server.lock();
+        server.wait_event(stream, event)
+    }
+
+    fn is_event_complete(&self, event: StreamEvent) -> bool {
+        self.server.lock().is_event_complete(event)
+    }
+
+    fn sync_event(&self, event: StreamEvent) -> DynFut<()> {
+        let mut server = self.server.lock();
+        server.sync_event(event)
+    }
+
+    fn event_elapsed(
+        &self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.server.lock().event_elapsed(start, end)
+    }
+
+    fn release_event(&self, event: StreamEvent) {
+        self.server.lock().release_event(event)
+    }
+
+    unsafe fn wait_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let mut server = self.server.lock();
+        unsafe { server.wait_native_event(stream, event) }
+    }
+
+    unsafe fn record_native_event(
+        &self,
+        stream: ExecutionStream,
+        event: NativeEvent,
+    ) -> Result<(), IoError> {
+        let mut server = self.server.lock();
+        unsafe { server.record_native_event(stream, event) }
+    }
+
+    fn sync_all(&self) -> DynFut<()> {
+        let mut server = self.server.lock();
+        server.sync_all()
+    }
+
     fn flush(&self) {
         self.server.lock().flush();
     }
@@ -110,8 +289,20 @@ where
         self.server.lock().memory_usage()
     }
 
-    fn memory_cleanup(&self) {
-        self.server.lock().memory_cleanup();
+    fn push_memory_scope(&self, tag: String) {
+        self.server.lock().push_memory_scope(tag);
+    }
+
+    fn pop_memory_scope(&self) {
+        self.server.lock().pop_memory_scope();
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.server.lock().memory_scope_usage()
+    }
+
+    fn memory_cleanup(&self, mode: MemoryCleanupMode) {
+        self.server.lock().memory_cleanup(mode);
     }
 
     fn start_profile(&self) -> ProfilingToken {
@@ -122,6 +313,14 @@ where
         self.server.lock().end_profile(token)
     }
 
+    fn start_device_timing(&self) -> Option<ProfilingToken> {
+        self.server.lock().start_device_timing()
+    }
+
+    fn end_device_timing(&self, token: ProfilingToken) -> Result<ProfileDuration, ProfileError> {
+        self.server.lock().end_device_timing(token)
+    }
+
     fn allocation_mode(&self, mode: MemoryAllocationMode) {
         let mut server = self.server.lock();
         server.allocation_mode(mode)
@@ -1,22 +1,24 @@
 use crate::{
-    DeviceProperties,
+    DeviceFeatures, DeviceProperties,
     channel::ComputeChannel,
     config::{TypeNameFormatLevel, type_name_format},
     data_service::DataTransferId,
     kernel::KernelMetadata,
-    logging::{ProfileLevel, ServerLogger},
-    memory_management::{MemoryAllocationMode, MemoryUsage},
+    logging::{KernelTimingState, KernelTimings, ProfileLevel, ProfileScope, ServerLogger},
+    memory_management::{MemoryAllocationMode, MemoryCleanupMode, MemoryScopeUsage, MemoryUsage},
     server::{
-        Allocation, AllocationDescriptor, AllocationKind, Binding, Bindings, ComputeServer,
-        CopyDescriptor, CubeCount, Handle, IoError, ProfileError,
+        Allocation, AllocationDescriptor, AllocationKind, BatchId, BatchedKernel, Binding,
+        Bindings, ComputeServer, CopyDescriptor, CubeCount, ExecutionStream, Handle, IoError,
+        NativeEvent, PinnedBuffer, ProfileError, ServerId, StreamEvent,
     },
     storage::{BindingResource, ComputeStorage},
 };
 use alloc::format;
+use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
-use cubecl_common::{ExecutionMode, bytes::Bytes, profile::ProfileDuration};
+use cubecl_common::{ExecutionMode, bytes::Bytes, future::DynFut, profile::ProfileDuration};
 
 #[allow(unused)]
 use cubecl_common::profile::TimingMethod;
@@ -29,6 +31,9 @@ use cubecl_common::stream_id::StreamId;
 pub struct ComputeClient<Server: ComputeServer, Channel> {
     channel: Channel,
     state: Arc<ComputeClientState<Server>>,
+    stream: ExecutionStream,
+    precompile_only: bool,
+    recording: Option<Arc<spin::Mutex<Vec<BatchedKernel<Server::Kernel>>>>>,
 }
 
 #[derive(new)]
@@ -39,9 +44,11 @@ struct ComputeClientState<Server: ComputeServer> {
     #[cfg(feature = "profile-tracy")]
     gpu_client: tracy_client::GpuContext,
 
+    id: ServerId,
     properties: DeviceProperties,
     info: Server::Info,
     logger: Arc<ServerLogger>,
+    kernel_timing: Arc<KernelTimingState>,
 
     #[cfg(multi_threading)]
     current_profiling: spin::RwLock<Option<StreamId>>,
@@ -56,6 +63,9 @@ where
         Self {
             channel: self.channel.clone(),
             state: self.state.clone(),
+            stream: self.stream,
+            precompile_only: self.precompile_only,
+            recording: self.recording.clone(),
         }
     }
 }
@@ -70,6 +80,11 @@ where
         &self.state.info
     }
 
+    /// The id of the server, used to tag the handles it allocates.
+    pub fn id(&self) -> ServerId {
+        self.state.id
+    }
+
     /// Create a new client.
     pub fn new(channel: Channel, properties: DeviceProperties, info: Server::Info) -> Self {
         let logger = ServerLogger::default();
@@ -79,8 +94,10 @@ where
         let client = tracy_client::Client::start();
 
         let state = ComputeClientState {
+            id: ServerId::new(),
             properties,
             logger: Arc::new(logger),
+            kernel_timing: Default::default(),
             #[cfg(multi_threading)]
             current_profiling: spin::RwLock::new(None),
             // Create the GPU client if needed.
@@ -103,17 +120,113 @@ where
         Self {
             channel,
             state: Arc::new(state),
+            stream: ExecutionStream::default(),
+            precompile_only: false,
+            recording: None,
+        }
+    }
+
+    /// Creates a new [execution stream](ExecutionStream) on the server.
+    ///
+    /// Use [with_stream](Self::with_stream) to get a client submitting its work to it.
+    pub fn create_stream(&self) -> ExecutionStream {
+        self.channel.create_stream()
+    }
+
+    /// Returns a client submitting kernels, reads and writes to the given stream.
+    ///
+    /// Memory handles can be shared between clients of different streams, but the ordering
+    /// between streams isn't guaranteed: a handle written on a stream has to be protected with
+    /// [record_event](Self::record_event) and [wait_event](Self::wait_event) before being
+    /// accessed from another one. The server keeps memory used by in-flight work alive, so
+    /// dropping a handle is always safe.
+    pub fn with_stream(&self, stream: ExecutionStream) -> Self {
+        let mut client = self.clone();
+        client.stream = stream;
+        client
+    }
+
+    /// The stream this client submits its work to.
+    pub fn stream(&self) -> ExecutionStream {
+        self.stream
+    }
+
+    /// Records an event on the stream of this client, reached once all the work submitted
+    /// before is completed.
+    ///
+    /// The memory used by the work submitted before stays reserved for as long as that work is
+    /// in flight, whether the event is still alive or not.
+    pub fn record_event(&self) -> Event<Server, Channel> {
+        self.profile_guard();
+
+        Event {
+            client: self.clone(),
+            id: self.channel.record_event(self.stream),
         }
     }
 
-    async fn do_read(&self, descriptors: Vec<CopyDescriptor<'_>>) -> Result<Vec<Bytes>, IoError> {
+    /// Makes all the work submitted to the stream of this client after this call wait until
+    /// the event is reached, without blocking the host.
+    pub fn wait_event(&self, event: &Event<Server, Channel>) {
+        self.profile_guard();
+
+        self.channel.wait_event(self.stream, event.id)
+    }
+
+    /// Makes all the work submitted to the stream of this client after this call wait until
+    /// the native event of another library sharing the device is reached.
+    ///
+    /// # Safety
+    ///
+    /// The event must be a valid event of the device of the client, e.g. a `CUevent` on CUDA.
+    pub unsafe fn wait_native_event(&self, event: NativeEvent) -> Result<(), IoError> {
         self.profile_guard();
 
-        self.channel.read(descriptors).await
+        unsafe { self.channel.wait_native_event(self.stream, event) }
+    }
+
+    /// Records the native event of another library sharing the device on the stream of this
+    /// client, reached once all the work submitted before is completed.
+    ///
+    /// # Safety
+    ///
+    /// The event must be a valid event of the device of the client, e.g. a `CUevent` on CUDA.
+    pub unsafe fn record_native_event(&self, event: NativeEvent) -> Result<(), IoError> {
+        self.profile_guard();
+
+        unsafe { self.channel.record_native_event(self.stream, event) }
+    }
+
+    /// Wait for the completion of every task in the server, on every stream.
+    pub async fn sync_all(&self) {
+        self.profile_guard();
+
+        self.channel.sync_all().await;
+        self.state.logger.profile_summary();
+    }
+
+    fn do_read(&self, descriptors: Vec<CopyDescriptor<'_>>) -> DynFut<Result<Vec<Bytes>, IoError>> {
+        self.profile_guard();
+
+        for descriptor in descriptors.iter() {
+            self.check_owner(&descriptor.binding);
+        }
+
+        self.channel.read_on(self.stream, descriptors)
     }
 
     /// Given bindings, returns owned resources as bytes.
-    pub async fn read_async(&self, handles: Vec<Handle>) -> Vec<Bytes> {
+    ///
+    /// The copies are enqueued when calling this function, so they see the result of all the
+    /// work submitted before and none of the work submitted after, even if the future is awaited
+    /// later. Multiple pending reads can be awaited in any order.
+    ///
+    /// Dropping the future cancels the wait, the memory used for the copies is released once
+    /// they are completed.
+    pub fn read_async(
+        &self,
+        handles: Vec<Handle>,
+    ) -> impl Future<Output = Vec<Bytes>> + Send + use<Server, Channel> {
         let strides = [1];
         let shapes = handles
             .iter()
@@ -129,7 +242,41 @@ where
             .map(|(binding, shape)| CopyDescriptor::new(binding, shape, &strides, 1))
             .collect();
 
-        self.do_read(descriptors).await.unwrap()
+        let fut = self.do_read(descriptors);
+        async move { fut.await.unwrap() }
+    }
+
+    /// Given bindings, returns owned resources as bytes, or the error that prevented the copies
+    /// to complete, e.g. a [kernel timeout](IoError::KernelTimeout).
+    ///
+    /// See [ComputeClient::read_async].
+    pub fn try_read_async(
+        &self,
+        handles: Vec<Handle>,
+    ) -> impl Future<Output = Result<Vec<Bytes>, IoError>> + Send + use<Server, Channel> {
+        let strides = [1];
+        let shapes = handles
+            .iter()
+            .map(|it| [it.size() as usize])
+            .collect::<Vec<_>>();
+        let descriptors = handles
+            .into_iter()
+            .zip(shapes.iter())
+            .map(|(handle, shape)| CopyDescriptor::new(handle.binding(), shape, &strides, 1))
+            .collect();
+
+        self.do_read(descriptors)
+    }
+
+    /// Given a binding, returns owned resource as bytes.
+    ///
+    /// See [ComputeClient::read_async].
+    pub fn read_one_async(
+        &self,
+        handle: Handle,
+    ) -> impl Future<Output = Bytes> + Send + use<Server, Channel> {
+        let fut = self.read_async(vec![handle]);
+        async move { fut.await.remove(0) }
     }
 
     /// Given bindings, returns owned resources as bytes.
@@ -150,8 +297,25 @@ where
     }
 
     /// Given bindings, returns owned resources as bytes.
-    pub async fn read_tensor_async(&self, descriptors: Vec<CopyDescriptor<'_>>) -> Vec<Bytes> {
-        self.do_read(descriptors).await.unwrap()
+    ///
+    /// See [ComputeClient::read_async] for the ordering guarantees.
+    pub fn read_tensor_async(
+        &self,
+        descriptors: Vec<CopyDescriptor<'_>>,
+    ) -> impl Future<Output = Vec<Bytes>> + Send + use<Server, Channel> {
+        let fut = self.do_read(descriptors);
+        async move { fut.await.unwrap() }
+    }
+
+    /// Given bindings, returns owned resources as bytes, or the error that prevented the copies
+    /// to complete.
+    ///
+    /// See [ComputeClient::try_read_async].
+    pub fn try_read_tensor_async(
+        &self,
+        descriptors: Vec<CopyDescriptor<'_>>,
+    ) -> impl Future<Output = Result<Vec<Bytes>, IoError>> + Send + use<Server, Channel> {
+        self.do_read(descriptors)
     }
 
     /// Given bindings, returns owned resources as bytes.
@@ -172,8 +336,12 @@ where
 
     /// Given a binding, returns owned resource as bytes.
     /// See [ComputeClient::read_tensor]
-    pub async fn read_one_tensor_async(&self, descriptor: CopyDescriptor<'_>) -> Bytes {
-        self.read_tensor_async(vec![descriptor]).await.remove(0)
+    pub fn read_one_tensor_async(
+        &self,
+        descriptor: CopyDescriptor<'_>,
+    ) -> impl Future<Output = Bytes> + Send + use<Server, Channel> {
+        let fut = self.read_tensor_async(vec![descriptor]);
+        async move { fut.await.remove(0) }
     }
 
     /// Given a binding, returns owned resource as bytes.
@@ -185,6 +353,53 @@ where
         self.read_tensor(vec![descriptor]).remove(0)
     }
 
+    /// Allocates a host buffer of `size` bytes, page-locked when the runtime supports it, to be
+    /// used with [write_from_pinned](Self::write_from_pinned) and
+    /// [read_to_pinned](Self::read_to_pinned).
+    ///
+    /// # Remarks
+    ///
+    /// Panics if the allocation fails.
+    pub fn create_pinned(&self, size: usize) -> PinnedBuffer {
+        self.channel.create_pinned(size).unwrap()
+    }
+
+    /// Copies the start of the pinned buffer into the given handle.
+    ///
+    /// The copy is enqueued without blocking, and the returned future gives the buffer back once
+    /// the copy is completed.
+    pub fn write_from_pinned(
+        &self,
+        src: PinnedBuffer,
+        dst: Handle,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        self.profile_guard();
+
+        let shape = [dst.size() as usize];
+        let descriptor = CopyDescriptor::new(dst.binding(), &shape, &[1], 1);
+        self.check_owner(&descriptor.binding);
+
+        self.channel.write_from_pinned(src, descriptor)
+    }
+
+    /// Copies the content of the given handle into the start of the pinned buffer.
+    ///
+    /// The copy is enqueued without blocking, and the returned future gives the buffer back once
+    /// the copy is completed.
+    pub fn read_to_pinned(
+        &self,
+        src: Handle,
+        dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        self.profile_guard();
+
+        let shape = [src.size() as usize];
+        let descriptor = CopyDescriptor::new(src.binding(), &shape, &[1], 1);
+        self.check_owner(&descriptor.binding);
+
+        self.channel.read_to_pinned(descriptor, dst)
+    }
+
     /// Given a resource handle, returns the storage resource.
     pub fn get_resource(
         &self,
@@ -195,6 +410,23 @@ where
         self.channel.get_resource(binding)
     }
 
+    /// Imports a resource allocated outside of the server, e.g. by another library sharing the
+    /// device, returning a handle that can be used like the ones allocated by the client.
+    ///
+    /// The memory of the resource is never reused nor freed by the server. The work submitted to
+    /// the device by the other library isn't synchronized with the work of the client, which is
+    /// up to the caller.
+    pub fn import_resource(
+        &self,
+        resource: <Server::Storage as ComputeStorage>::Resource,
+    ) -> Result<Handle, IoError> {
+        self.profile_guard();
+
+        self.channel
+            .import_resource(resource)
+            .map(|handle| handle.with_owner(self.state.id))
+    }
+
     fn do_create(
         &self,
         descriptors: Vec<AllocationDescriptor<'_>>,
@@ -202,7 +434,7 @@ where
     ) -> Result<Vec<Allocation>, IoError> {
         self.profile_guard();
 
-        let allocations = self.channel.create(descriptors.clone())?;
+        let allocations = self.allocate(descriptors.clone())?;
         let descriptors = descriptors
             .into_iter()
             .zip(allocations.iter())
@@ -219,12 +451,25 @@ where
                 )
             })
             .collect();
-        self.channel.write(descriptors)?;
+        self.channel.write_on(self.stream, descriptors)?;
         Ok(allocations)
     }
 
     /// Given a resource, stores it and returns the resource handle.
+    ///
+    /// # Panics
+    ///
+    /// If the device is out of memory, see [try_create](Self::try_create) to recover from it.
     pub fn create(&self, data: &[u8]) -> Handle {
+        self.try_create(data).unwrap()
+    }
+
+    /// Given a resource, stores it and returns the resource handle, or an error if it can't be
+    /// allocated.
+    ///
+    /// When the device is [out of memory](IoError::is_out_of_memory), freeing handles and
+    /// retrying can succeed.
+    pub fn try_create(&self, data: &[u8]) -> Result<Handle, IoError> {
         let shape = [data.len()];
 
         self.do_create(
@@ -235,9 +480,7 @@ where
             )],
             vec![data],
         )
-        .unwrap()
-        .remove(0)
-        .handle
+        .map(|mut allocations| allocations.remove(0).handle)
     }
 
     /// Given a resource and shape, stores it and returns the tensor handle and strides.
@@ -254,6 +497,16 @@ where
     /// However, the stride must be taken into account when indexing and reading the tensor
     /// (also see [ComputeClient::read_tensor]).
     pub fn create_tensor(&self, data: &[u8], shape: &[usize], elem_size: usize) -> Allocation {
+        self.try_create_tensor(data, shape, elem_size).unwrap()
+    }
+
+    /// Fallible version of [create_tensor](Self::create_tensor).
+    pub fn try_create_tensor(
+        &self,
+        data: &[u8],
+        shape: &[usize],
+        elem_size: usize,
+    ) -> Result<Allocation, IoError> {
         self.do_create(
             vec![AllocationDescriptor::new(
                 AllocationKind::Optimized,
@@ -262,8 +515,7 @@ where
             )],
             vec![data],
         )
-        .unwrap()
-        .remove(0)
+        .map(|mut allocations| allocations.remove(0))
     }
 
     /// Reserves all `shapes` in a single storage buffer, copies the corresponding `data` into each
@@ -273,9 +525,17 @@ where
         &self,
         descriptors: Vec<(AllocationDescriptor<'_>, &[u8])>,
     ) -> Vec<Allocation> {
+        self.try_create_tensors(descriptors).unwrap()
+    }
+
+    /// Fallible version of [create_tensors](Self::create_tensors).
+    pub fn try_create_tensors(
+        &self,
+        descriptors: Vec<(AllocationDescriptor<'_>, &[u8])>,
+    ) -> Result<Vec<Allocation>, IoError> {
         let (descriptors, data) = descriptors.into_iter().unzip();
 
-        self.do_create(descriptors, data).unwrap()
+        self.do_create(descriptors, data)
     }
 
     fn do_empty(
@@ -284,27 +544,107 @@ where
     ) -> Result<Vec<Allocation>, IoError> {
         self.profile_guard();
 
-        self.channel.create(descriptors)
+        self.allocate(descriptors)
+    }
+
+    /// Allocates the descriptors, tagging the handles with the id of the server.
+    fn allocate(
+        &self,
+        descriptors: Vec<AllocationDescriptor<'_>>,
+    ) -> Result<Vec<Allocation>, IoError> {
+        let allocations = self.channel.create(descriptors)?;
+
+        Ok(allocations
+            .into_iter()
+            .map(|alloc| Allocation::new(alloc.handle.with_owner(self.state.id), alloc.strides))
+            .collect())
+    }
+
+    /// Makes sure the binding was allocated by this server, since the memory of another device
+    /// can't be accessed directly.
+    #[track_caller]
+    fn check_owner(&self, binding: &Binding) {
+        if let Some(owner) = binding.owner() {
+            assert_eq!(
+                owner, self.state.id,
+                "A handle allocated by {owner} is used with {}, it has to be copied with \
+                 `copy_to` first",
+                self.state.id
+            );
+        }
+    }
+
+    /// Makes sure the binding starts at an offset the device can bind, which matters for
+    /// [windows](Handle::offset) of a handle.
+    fn check_alignment(&self, binding: &Binding) -> Result<(), IoError> {
+        let offset = binding.offset_start.unwrap_or(0);
+        let alignment = self.state.properties.memory.alignment;
+
+        match offset.is_multiple_of(alignment) {
+            true => Ok(()),
+            false => Err(IoError::MisalignedOffset { offset, alignment }),
+        }
     }
 
     /// Reserves `size` bytes in the storage, and returns a handle over them.
+    ///
+    /// # Panics
+    ///
+    /// If the device is out of memory, see [try_empty](Self::try_empty) to recover from it.
     pub fn empty(&self, size: usize) -> Handle {
+        self.try_empty(size).unwrap()
+    }
+
+    /// Reserves `size` bytes in the storage, and returns a handle over them, or an error if they
+    /// can't be allocated.
+    ///
+    /// When the device is [out of memory](IoError::is_out_of_memory), freeing handles and
+    /// retrying can succeed.
+    pub fn try_empty(&self, size: usize) -> Result<Handle, IoError> {
         let shape = [size];
         let descriptor = AllocationDescriptor::new(AllocationKind::Contiguous, &shape, 1);
-        self.do_empty(vec![descriptor]).unwrap().remove(0).handle
+        self.do_empty(vec![descriptor])
+            .map(|mut allocations| allocations.remove(0).handle)
     }
 
     /// Reserves `shape` in the storage, and returns a tensor handle for it.
     /// See [ComputeClient::create_tensor]
     pub fn empty_tensor(&self, shape: &[usize], elem_size: usize) -> Allocation {
+        self.try_empty_tensor(shape, elem_size).unwrap()
+    }
+
+    /// Fallible version of [empty_tensor](Self::empty_tensor).
+    pub fn try_empty_tensor(
+        &self,
+        shape: &[usize],
+        elem_size: usize,
+    ) -> Result<Allocation, IoError> {
         let descriptor = AllocationDescriptor::new(AllocationKind::Optimized, shape, elem_size);
-        self.do_empty(vec![descriptor]).unwrap().remove(0)
+        self.do_empty(vec![descriptor])
+            .map(|mut allocations| allocations.remove(0))
     }
 
     /// Reserves all `shapes` in a single storage buffer, and returns the handles for them.
     /// See [ComputeClient::create_tensor]
     pub fn empty_tensors(&self, descriptors: Vec<AllocationDescriptor<'_>>) -> Vec<Allocation> {
-        self.do_empty(descriptors).unwrap()
+        self.try_empty_tensors(descriptors).unwrap()
+    }
+
+    /// Fallible version of [empty_tensors](Self::empty_tensors).
+    pub fn try_empty_tensors(
+        &self,
+        descriptors: Vec<AllocationDescriptor<'_>>,
+    ) -> Result<Vec<Allocation>, IoError> {
+        self.do_empty(descriptors)
+    }
+
+    /// Copies the handle to the device of another client, returning the handle owned by it.
+    ///
+    /// On runtimes supporting it the data is copied directly between the devices, otherwise it
+    /// is staged through host memory. The copy is ordered after the work submitted to the source
+    /// client and before the work submitted to the destination client.
+    pub fn copy_to(&self, dst_server: &Self, handle: Handle) -> Handle {
+        self.to_client(handle, dst_server).handle
     }
 
     /// Transfer data from one client to another
@@ -335,7 +675,56 @@ where
         count: CubeCount,
         bindings: Bindings,
         mode: ExecutionMode,
-    ) {
+    ) -> Result<(), IoError> {
+        if self.precompile_only {
+            self.precompile(kernel, mode);
+            return Ok(());
+        }
+
+        for binding in bindings.buffers.iter() {
+            self.check_owner(binding);
+            self.check_alignment(binding)?;
+        }
+        for map in bindings.tensor_maps.iter() {
+            self.check_owner(&map.binding);
+        }
+        if let CubeCount::Dynamic(binding) = &count {
+            self.check_owner(binding);
+        }
+
+        if let Some(recording) = &self.recording {
+            recording
+                .lock()
+                .push(BatchedKernel::new(kernel, count, bindings, mode));
+            return Ok(());
+        }
+
+        if self.state.kernel_timing.is_enabled() {
+            let name = type_name_format(kernel.name(), TypeNameFormatLevel::Balanced);
+            let mut result = Ok(());
+            let profile = self.profile_device(
+                || unsafe {
+                    result = self.channel.execute_on(
+                        self.stream,
+                        kernel,
+                        count,
+                        bindings,
+                        mode,
+                        self.state.logger.clone(),
+                    )
+                },
+                &name,
+            );
+
+            match profile {
+                Ok(profile) if result.is_ok() => self.state.kernel_timing.register(&name, profile),
+                Ok(_) => {}
+                Err(err) => log::warn!("Failed to time kernel {name}: {err:?}"),
+            }
+
+            return result;
+        }
+
         let level = self.state.logger.profile_level();
 
         match level {
@@ -345,22 +734,32 @@ where
                 let name = kernel.name();
 
                 unsafe {
-                    self.channel
-                        .execute(kernel, count, bindings, mode, self.state.logger.clone())
+                    self.channel.execute_on(
+                        self.stream,
+                        kernel,
+                        count,
+                        bindings,
+                        mode,
+                        self.state.logger.clone(),
+                    )?
                 };
 
                 if matches!(level, Some(ProfileLevel::ExecutionOnly)) {
                     let info = type_name_format(name, TypeNameFormatLevel::Balanced);
                     self.state.logger.register_execution(info);
                 }
+
+                Ok(())
             }
             Some(level) => {
                 let name = kernel.name();
                 let kernel_id = kernel.id();
+                let mut result = Ok(());
                 let profile = self
                     .profile(
                         || unsafe {
-                            self.channel.execute(
+                            result = self.channel.execute_on(
+                                self.stream,
                                 kernel,
                                 count.clone(),
                                 bindings,
@@ -371,6 +770,8 @@ where
                         name,
                     )
                     .unwrap();
+                result?;
+
                 let info = match level {
                     ProfileLevel::Full => {
                         format!("{name}: {kernel_id} CubeCount {count:?}")
@@ -378,17 +779,37 @@ where
                     _ => type_name_format(name, TypeNameFormatLevel::Balanced),
                 };
                 self.state.logger.register_profiled(info, profile);
+
+                Ok(())
             }
         }
     }
 
     /// Executes the `kernel` over the given `bindings`.
+    ///
+    /// # Panics
+    ///
+    /// If the memory needed to launch the kernel can't be allocated, or if the backend rejects
+    /// the kernel, see [try_execute](Self::try_execute) to recover from it.
     #[track_caller]
     pub fn execute(&self, kernel: Server::Kernel, count: CubeCount, bindings: Bindings) {
+        self.try_execute(kernel, count, bindings)
+            .expect("Failed to execute the kernel");
+    }
+
+    /// Executes the `kernel` over the given `bindings`, returning an error if the memory needed
+    /// to launch it can't be allocated or if the backend rejects it.
+    ///
+    /// The client can still launch other kernels after an error.
+    #[track_caller]
+    pub fn try_execute(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+    ) -> Result<(), IoError> {
         // SAFETY: Using checked execution mode.
-        unsafe {
-            self.execute_inner(kernel, count, bindings, ExecutionMode::Checked);
-        }
+        unsafe { self.execute_inner(kernel, count, bindings, ExecutionMode::Checked) }
     }
 
     /// Executes the `kernel` over the given `bindings` without performing any bound checks.
@@ -407,10 +828,195 @@ where
     ) {
         // SAFETY: Caller has to uphold kernel being safe.
         unsafe {
-            self.execute_inner(kernel, count, bindings, ExecutionMode::Unchecked);
+            self.try_execute_unchecked(kernel, count, bindings)
+                .expect("Failed to execute the kernel");
         }
     }
 
+    /// Executes the `kernel` over the given `bindings` without performing any bound checks,
+    /// returning an error if the memory needed to launch it can't be allocated or if the backend
+    /// rejects it.
+    ///
+    /// # Safety
+    ///
+    /// See [execute_unchecked](Self::execute_unchecked).
+    #[track_caller]
+    pub unsafe fn try_execute_unchecked(
+        &self,
+        kernel: Server::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+    ) -> Result<(), IoError> {
+        // SAFETY: Caller has to uphold kernel being safe.
+        unsafe { self.execute_inner(kernel, count, bindings, ExecutionMode::Unchecked) }
+    }
+
+    /// Compiles the `kernel` and caches it without launching it, so its first launch doesn't
+    /// have to compile it.
+    ///
+    /// Runtimes with a compilation cache on disk also fill it when the kernel isn't in it yet.
+    /// A kernel rejected by the backend is only logged, and its error is returned again when it
+    /// is launched, see [try_precompile](Self::try_precompile) to handle it.
+    pub fn precompile(&self, kernel: Server::Kernel, mode: ExecutionMode) {
+        if let Err(err) = self.try_precompile(kernel, mode) {
+            log::warn!("Failed to precompile a kernel: {err}");
+        }
+    }
+
+    /// Compiles the `kernel` and caches it without launching it, see
+    /// [precompile](Self::precompile).
+    ///
+    /// Returns a [compilation error](IoError::Compilation) when the backend rejects the kernel,
+    /// after which the client can still launch other kernels.
+    pub fn try_precompile(
+        &self,
+        kernel: Server::Kernel,
+        mode: ExecutionMode,
+    ) -> Result<(), IoError> {
+        self.channel
+            .precompile(kernel, mode, self.state.logger.clone())
+    }
+
+    /// Compiles all the `kernels` on background threads, see [precompile](Self::precompile).
+    ///
+    /// Without threads, the kernels are compiled before returning.
+    pub fn precompile_all(&self, kernels: Vec<(Server::Kernel, ExecutionMode)>) -> PrecompileHandle
+    where
+        Server: 'static,
+        Channel: 'static,
+    {
+        #[cfg(multi_threading)]
+        {
+            let num_threads = std::thread::available_parallelism()
+                .map(|num| num.get())
+                .unwrap_or(1)
+                .min(kernels.len());
+            let queue = Arc::new(spin::Mutex::new(kernels));
+
+            let threads = (0..num_threads)
+                .map(|_| {
+                    let client = self.clone();
+                    let queue = queue.clone();
+
+                    std::thread::spawn(move || {
+                        loop {
+                            let next = queue.lock().pop();
+                            match next {
+                                Some((kernel, mode)) => client.precompile(kernel, mode),
+                                None => break,
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            PrecompileHandle { threads }
+        }
+
+        #[cfg(not(multi_threading))]
+        {
+            for (kernel, mode) in kernels {
+                self.precompile(kernel, mode);
+            }
+
+            PrecompileHandle {}
+        }
+    }
+
+    /// Returns a client [precompiling](Self::precompile) the kernels it's asked to execute
+    /// instead of launching them.
+    ///
+    /// Useful to warm up the kernels used by a function without running it. Reads and
+    /// allocations are still performed, only kernel executions are skipped.
+    pub fn precompile_only(&self) -> Self {
+        let mut client = self.clone();
+        client.precompile_only = true;
+        client
+    }
+
+    /// Executes the kernels launched by `func` as a single batch, sent to the server at once and
+    /// submitted back-to-back, which amortizes the launch overhead of many small kernels.
+    ///
+    /// `func` receives a client recording the kernels it's asked to execute instead of launching
+    /// them. Allocations and copies are still performed right away, so the memory allocated
+    /// inside the batch is ready before the kernels are submitted, but reads don't see the
+    /// results of the recorded kernels. The kernels of a batch aren't timed individually.
+    ///
+    /// # Panics
+    ///
+    /// If a kernel of the batch fails to execute, see [try_batch](Self::try_batch) to recover
+    /// from it.
+    #[track_caller]
+    pub fn batch<O>(&self, func: impl FnOnce(&Self) -> O) -> O {
+        self.try_batch(func).expect("Failed to execute the batch")
+    }
+
+    /// Executes the kernels launched by `func` as a single batch, see [batch](Self::batch),
+    /// returning an error if a kernel fails to execute.
+    ///
+    /// The kernels recorded before the failing one stay enqueued.
+    pub fn try_batch<O>(&self, func: impl FnOnce(&Self) -> O) -> Result<O, IoError> {
+        let (output, kernels) = self.record(func);
+
+        if !kernels.is_empty() {
+            self.profile_guard();
+
+            unsafe {
+                self.channel
+                    .execute_batch(self.stream, kernels, self.state.logger.clone())?
+            };
+        }
+
+        Ok(output)
+    }
+
+    /// Records the kernels launched by `func` into a [batch](ReplayableBatch) that can be
+    /// replayed many times without submitting the kernels again, see [batch](Self::batch).
+    ///
+    /// The kernels aren't executed until the batch is replayed, each replay executing them over
+    /// the same bindings, which are kept alive as long as the batch is. The memory allocated
+    /// inside `func` is allocated while recording, so replays don't allocate, and the parameters
+    /// of the kernels can be changed between replays with [update](ReplayableBatch::update).
+    ///
+    /// Returns an error on runtimes that can't record batches.
+    pub fn batch_reusable(
+        &self,
+        func: impl FnOnce(&Self),
+    ) -> Result<ReplayableBatch<Server, Channel>, IoError> {
+        let ((), kernels) = self.record(func);
+
+        let id = unsafe {
+            self.channel
+                .record_batch(self.stream, kernels, self.state.logger.clone())?
+        };
+
+        Ok(ReplayableBatch {
+            client: self.clone(),
+            id,
+        })
+    }
+
+    fn record<O>(&self, func: impl FnOnce(&Self) -> O) -> (O, Vec<BatchedKernel<Server::Kernel>>) {
+        // Nested batches are recorded into the outer one.
+        if self.recording.is_some() {
+            return (func(self), Vec::new());
+        }
+
+        let recording = Arc::new(spin::Mutex::new(Vec::new()));
+        let mut client = self.clone();
+        client.recording = Some(recording.clone());
+
+        let output = func(&client);
+        let kernels = core::mem::take(&mut *recording.lock());
+
+        (output, kernels)
+    }
+
+    /// The number of kernels compiled by the server since it was created.
+    pub fn compilation_count(&self) -> u64 {
+        self.state.logger.compilation_count()
+    }
+
     /// Flush all outstanding commands.
     pub fn flush(&self) {
         self.profile_guard();
@@ -426,11 +1032,30 @@ where
         self.state.logger.profile_summary();
     }
 
+    /// Wait for the completion of every task in the server, returning an error instead of
+    /// panicking when the work can't complete.
+    ///
+    /// On runtimes configured with a kernel timeout, a kernel that doesn't complete in time
+    /// returns a [kernel timeout](IoError::KernelTimeout), and the later calls fail with a
+    /// [poisoned](IoError::Poisoned) error instead of waiting for it.
+    pub async fn try_sync(&self) -> Result<(), IoError> {
+        self.profile_guard();
+
+        let result = self.channel.try_sync().await;
+        self.state.logger.profile_summary();
+        result
+    }
+
     /// Get the features supported by the compute server.
     pub fn properties(&self) -> &DeviceProperties {
         &self.state.properties
     }
 
+    /// Summarize what the device of the compute server can do, see [DeviceFeatures].
+    pub fn device_features(&self) -> DeviceFeatures {
+        self.state.properties.device_features()
+    }
+
     /// # Warning
     ///
     /// For private use only.
@@ -445,6 +1070,29 @@ where
         self.channel.memory_usage()
     }
 
+    /// Attribute the memory allocated on the server to the tag until the returned scope is
+    /// dropped, to find which part of a program holds on to memory with
+    /// [memory_scope_usage](Self::memory_scope_usage).
+    ///
+    /// Scopes can be nested, the memory being attributed to the innermost one. An allocation
+    /// stays attributed to its scope until it is freed, even once the scope is dropped.
+    ///
+    /// The scopes are shared by all the clients of the server, so the memory allocated by other
+    /// threads while a scope is alive is attributed to it as well.
+    pub fn memory_scope(&self, tag: impl Into<String>) -> MemoryScope<Server, Channel> {
+        self.channel.push_memory_scope(tag.into());
+
+        MemoryScope {
+            client: self.clone(),
+        }
+    }
+
+    /// Get the memory attributed to each [memory scope](Self::memory_scope) tag, sorted by the
+    /// bytes still in use.
+    pub fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.channel.memory_scope_usage()
+    }
+
     /// Change the memory allocation mode.
     ///
     /// # Safety
@@ -461,7 +1109,7 @@ where
     /// # Notes
     ///
     /// Using that memory strategy is beneficial for weights loading and similar workflows.
-    /// However make sure to call [Self::memory_cleanup()] if you want to free the allocated
+    /// However make sure to call [Self::memory_cleanup] if you want to free the allocated
     /// memory.
     pub fn memory_static_allocation<Input, Output, Func: Fn(Input) -> Output>(
         &self,
@@ -489,18 +1137,77 @@ where
     ///
     /// Nb: Results will vary on what the memory allocator deems beneficial,
     /// so it's not guaranteed any memory is freed.
-    pub fn memory_cleanup(&self) {
+    ///
+    /// With [MemoryCleanupMode::Aggressive], live allocations might be moved to other pages,
+    /// so any [resource](Self::get_resource) fetched before can't be used anymore.
+    pub fn memory_cleanup(&self, mode: MemoryCleanupMode) {
         self.profile_guard();
 
-        self.channel.memory_cleanup()
+        self.channel.memory_cleanup(mode)
+    }
+
+    /// Enable or disable timing every kernel execution with device timestamps.
+    ///
+    /// The timings are accumulated per kernel and retrieved with
+    /// [kernel_timings](Self::kernel_timings). Timestamps are resolved lazily, so this doesn't
+    /// wait for the device after each launch when the runtime supports device timestamps.
+    /// While enabled, kernels aren't registered to the profiling logger.
+    pub fn enable_kernel_timing(&self, enabled: bool) {
+        self.state.kernel_timing.set_enabled(enabled);
+    }
+
+    /// Groups the kernels executed until the returned guard is dropped under the given name.
+    ///
+    /// Scopes can be nested and apply to every user of the client.
+    pub fn profile_scope(&self, name: &str) -> ProfileScope {
+        ProfileScope::new(self.state.kernel_timing.clone(), name)
+    }
+
+    /// Resolves the timings of the kernels executed with
+    /// [kernel timing](Self::enable_kernel_timing) enabled.
+    pub async fn kernel_timings(&self) -> KernelTimings {
+        // Device timings are resolved once submitted.
+        self.flush();
+        self.state.kernel_timing.timings().await
+    }
+
+    /// Discards the kernel timings collected so far.
+    pub fn reset_kernel_timings(&self) {
+        self.state.kernel_timing.reset();
     }
 
     /// Measure the execution time of some inner operations.
     #[track_caller]
     pub fn profile<O>(
+        &self,
+        func: impl FnOnce() -> O,
+        func_name: &str,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.profile_inner(func, func_name, false)
+    }
+
+    /// Measure the execution time of some inner operations with device timestamps, without
+    /// synchronizing the device.
+    ///
+    /// The duration is resolved once the work is [flushed](Self::flush). Falls back to
+    /// [profile](Self::profile) when the runtime doesn't support device timestamps, in which case
+    /// the [timing method](ProfileDuration::timing_method) of the duration is
+    /// [system](TimingMethod::System).
+    #[track_caller]
+    pub fn profile_device<O>(
+        &self,
+        func: impl FnOnce() -> O,
+        func_name: &str,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.profile_inner(func, func_name, true)
+    }
+
+    #[track_caller]
+    fn profile_inner<O>(
         &self,
         func: impl FnOnce() -> O,
         #[allow(unused)] func_name: &str,
+        device_timing: bool,
     ) -> Result<ProfileDuration, ProfileError> {
         // Get the outer caller. For execute() this points straight to the
         // cube kernel. For general profiling it points to whoever calls profile.
@@ -532,12 +1239,22 @@ where
             None
         };
 
-        let token = self.channel.start_profile();
+        let device_token = match device_timing {
+            true => self.channel.start_device_timing(),
+            false => None,
+        };
+        let token = match device_token {
+            Some(token) => token,
+            None => self.channel.start_profile(),
+        };
 
         let out = func();
 
         #[allow(unused_mut)]
-        let mut result = self.channel.end_profile(token);
+        let mut result = match device_token {
+            Some(token) => self.channel.end_device_timing(token),
+            None => self.channel.end_profile(token),
+        };
 
         core::mem::drop(out);
 
@@ -578,10 +1295,11 @@ where
         let shape = src_descriptor.shape;
         let elem_size = src_descriptor.elem_size;
 
+        self.check_owner(&src_descriptor.binding);
+
         // Allocate destination
         let alloc = dst_server
-            .channel
-            .create(vec![alloc_descriptor])
+            .allocate(vec![alloc_descriptor])
             .unwrap()
             .remove(0);
 
@@ -608,14 +1326,15 @@ where
         let shape = src_descriptor.shape;
         let elem_size = src_descriptor.elem_size;
 
+        self.check_owner(&src_descriptor.binding);
+
         // Allocate destination
         let alloc = dst_server
-            .channel
-            .create(vec![alloc_descriptor])
+            .allocate(vec![alloc_descriptor])
             .unwrap()
             .remove(0);
 
-        let read = self.channel.read(vec![src_descriptor]);
+        let read = self.channel.read_on(self.stream, vec![src_descriptor]);
         let data = cubecl_common::future::block_on(read).unwrap();
 
         let desc_descriptor = CopyDescriptor {
@@ -627,7 +1346,7 @@ where
 
         dst_server
             .channel
-            .write(vec![(desc_descriptor, &data[0])])
+            .write_on(dst_server.stream, vec![(desc_descriptor, &data[0])])
             .unwrap();
 
         alloc
@@ -739,3 +1458,181 @@ where
         }
     }
 }
+
+/// Kernels being [precompiled](ComputeClient::precompile_all) in the background.
+#[must_use = "The compilation isn't guaranteed to be completed before waiting on the handle"]
+pub struct PrecompileHandle {
+    #[cfg(multi_threading)]
+    threads: Vec<std::thread::JoinHandle<()>>,
+}
+
+impl PrecompileHandle {
+    /// If all the kernels are compiled.
+    pub fn is_finished(&self) -> bool {
+        #[cfg(multi_threading)]
+        {
+            self.threads.iter().all(|thread| thread.is_finished())
+        }
+
+        #[cfg(not(multi_threading))]
+        {
+            true
+        }
+    }
+
+    /// Wait until all the kernels are compiled.
+    ///
+    /// # Panics
+    ///
+    /// If the compilation of a kernel panicked.
+    pub fn wait(self) {
+        #[cfg(multi_threading)]
+        for thread in self.threads {
+            thread.join().expect("Failed to precompile a kernel");
+        }
+    }
+}
+
+/// A [memory scope](ComputeClient::memory_scope), popped when dropped.
+pub struct MemoryScope<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    client: ComputeClient<Server, Channel>,
+}
+
+impl<Server, Channel> Drop for MemoryScope<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    fn drop(&mut self) {
+        self.client.channel.pop_memory_scope();
+    }
+}
+
+/// An event [recorded](ComputeClient::record_event) on a stream, reached once all the work
+/// submitted to the stream before is completed.
+///
+/// Other streams can [wait](ComputeClient::wait_event) for it on the device, and the host can
+/// poll or wait for it without synchronizing the whole server. Two events
+/// [time](Self::elapsed) the work submitted between them.
+///
+/// The event is released when dropped.
+pub struct Event<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    client: ComputeClient<Server, Channel>,
+    id: StreamEvent,
+}
+
+impl<Server, Channel> Event<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    /// Whether the event is reached, without blocking.
+    pub fn is_complete(&self) -> bool {
+        self.client.channel.is_event_complete(self.id)
+    }
+
+    /// Wait until the event is reached.
+    pub async fn wait(&self) {
+        self.client.channel.sync_event(self.id).await
+    }
+
+    /// Block the current thread until the event is reached.
+    pub fn wait_blocking(&self) {
+        cubecl_common::future::block_on(self.wait())
+    }
+
+    /// The time elapsed on the device between this event and the `end` event recorded after it
+    /// on the same stream, resolved once the `end` event is reached.
+    pub fn elapsed(&self, end: &Self) -> Result<ProfileDuration, ProfileError> {
+        self.client.channel.event_elapsed(self.id, end.id)
+    }
+}
+
+impl<Server, Channel> Drop for Event<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    fn drop(&mut self) {
+        self.client.channel.release_event(self.id);
+    }
+}
+
+/// A batch of kernels [recorded](ComputeClient::batch_reusable) by the server, executed each
+/// time it is [replayed](Self::replay).
+///
+/// The batch is released when dropped.
+pub struct ReplayableBatch<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    client: ComputeClient<Server, Channel>,
+    id: BatchId,
+}
+
+impl<Server, Channel> ReplayableBatch<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    /// Executes the recorded kernels on the stream of the client that recorded them.
+    ///
+    /// # Panics
+    ///
+    /// If the batch fails to execute, see [try_replay](Self::try_replay).
+    #[track_caller]
+    pub fn replay(&self) {
+        self.try_replay().expect("Failed to replay the batch")
+    }
+
+    /// Executes the recorded kernels, returning an error if the batch fails to execute.
+    pub fn try_replay(&self) -> Result<(), IoError> {
+        self.client.profile_guard();
+        self.client
+            .channel
+            .replay_batch(self.client.stream, self.id)
+    }
+
+    /// Records the kernels launched by `func` in place of the kernels of the batch, for the next
+    /// replays.
+    ///
+    /// The kernels should be the recorded ones with other bindings, scalars or cube counts,
+    /// in which case only the parameters of the recorded kernels are updated. Other kernels are
+    /// recorded as a new batch under the same handle, which costs as much as
+    /// [recording](ComputeClient::batch_reusable) it. The replays already submitted still
+    /// execute the previous kernels.
+    pub fn update(
+        &self,
+        func: impl FnOnce(&ComputeClient<Server, Channel>),
+    ) -> Result<(), IoError> {
+        let ((), kernels) = self.client.record(func);
+
+        unsafe {
+            self.client.channel.update_batch(
+                self.client.stream,
+                self.id,
+                kernels,
+                self.client.state.logger.clone(),
+            )
+        }
+    }
+}
+
+impl<Server, Channel> Drop for ReplayableBatch<Server, Channel>
+where
+    Server: ComputeServer,
+    Channel: ComputeChannel<Server>,
+{
+    fn drop(&mut self) {
+        self.client.channel.release_batch(self.id);
+    }
+}
@@ -13,10 +13,21 @@ pub struct AutotuneConfig {
     #[serde(default)]
     pub level: AutotuneLevel,
 
+    /// Compare the candidates with device timestamps when the runtime supports them, instead of
+    /// the default timing method of the runtime.
+    #[serde(default)]
+    pub device_timing: bool,
+
     /// Cache location for storing autotune results.
     #[serde(default)]
     #[cfg(std_io)]
     pub cache: CacheConfig,
+
+    /// A JSON file of the candidates to force for some autotune keys, over the tuned ones, see
+    /// [AutotuneOverride](crate::tune::AutotuneOverride).
+    #[serde(default)]
+    #[cfg(std_io)]
+    pub overrides: Option<std::path::PathBuf>,
 }
 
 /// Log levels for autotune logging in CubeCL.
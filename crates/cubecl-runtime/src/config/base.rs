@@ -174,6 +174,10 @@ impl GlobalConfig {
             }
         }
 
+        if let Ok(val) = std::env::var("CUBECL_AUTOTUNE_OVERRIDES") {
+            self.autotune.overrides = Some(val.into());
+        }
+
         self
     }
 
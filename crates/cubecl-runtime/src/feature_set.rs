@@ -1,9 +1,10 @@
 use crate::{
-    Features, TypeUsage,
+    Features, MmaConfig, Plane, Tma, TypeUsage,
     memory_management::{HardwareProperties, MemoryDeviceProperties},
 };
-use cubecl_common::profile::TimingMethod;
-use cubecl_ir::{SemanticType, StorageType, Type};
+use alloc::collections::BTreeSet;
+use cubecl_common::{CubeDim, profile::TimingMethod};
+use cubecl_ir::{ElemType, FloatKind, IntKind, SemanticType, StorageType, Type, UIntKind};
 use enumset::EnumSet;
 
 /// Properties of what the device can do, like what `Feature` are
@@ -59,4 +60,86 @@ impl DeviceProperties {
     pub fn register_semantic_type(&mut self, ty: SemanticType) {
         self.features.semantic_types.insert(ty);
     }
+
+    /// Summarize what the device can do, see [DeviceFeatures].
+    pub fn device_features(&self) -> DeviceFeatures {
+        let arithmetic = |kind: FloatKind| {
+            self.type_usage(ElemType::Float(kind).into())
+                .contains(TypeUsage::Arithmetic)
+        };
+        let atomic_64 = [ElemType::Int(IntKind::I64), ElemType::UInt(UIntKind::U64)]
+            .into_iter()
+            .any(|ty| !self.type_usage(StorageType::Atomic(ty)).is_empty());
+
+        DeviceFeatures {
+            accelerated_tiles: self.features.cmma.clone(),
+            async_copy: self.supports_type(SemanticType::Pipeline),
+            barrier: self.supports_type(SemanticType::Barrier),
+            tma: self.features.tma.contains(Tma::Base),
+            plane_ops: self.features.plane.contains(Plane::Ops),
+            plane_size_min: self.hardware.plane_size_min,
+            plane_size_max: self.hardware.plane_size_max,
+            max_shared_memory_size: self.hardware.max_shared_memory_size,
+            max_cube_dim: self.hardware.max_cube_dim,
+            max_units_per_cube: self.hardware.max_units_per_cube,
+            num_streaming_multiprocessors: self.hardware.num_streaming_multiprocessors,
+            f16: arithmetic(FloatKind::F16),
+            bf16: arithmetic(FloatKind::BF16),
+            f64: arithmetic(FloatKind::F64),
+            atomic_64,
+        }
+    }
+}
+
+/// What a device can do, in a single place independent of the runtime.
+///
+/// It is a summary of the [device properties](DeviceProperties) filled by each runtime, e.g. from
+/// the CUDA device attributes, the HIP device properties or the wgpu adapter features and limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceFeatures {
+    /// The tile shapes and element types of the accelerated matmuls, i.e. tensor cores or
+    /// cooperative matrices.
+    pub accelerated_tiles: BTreeSet<MmaConfig>,
+    /// Asynchronous copies from global to shared memory.
+    pub async_copy: bool,
+    /// Barriers for asynchronous copies.
+    pub barrier: bool,
+    /// Tensor Memory Accelerator copies.
+    pub tma: bool,
+    /// Plane-wide operations.
+    pub plane_ops: bool,
+    /// The minimum size of a plane.
+    pub plane_size_min: u32,
+    /// The maximum size of a plane.
+    pub plane_size_max: u32,
+    /// Maximum amount of shared memory of a cube, in bytes.
+    pub max_shared_memory_size: usize,
+    /// Maximum `CubeDim` in x, y, and z dimensions.
+    pub max_cube_dim: CubeDim,
+    /// Maximum number of total units in a cube.
+    pub max_units_per_cube: u32,
+    /// Number of streaming multiprocessors, if available.
+    pub num_streaming_multiprocessors: Option<u32>,
+    /// Arithmetic on `f16`.
+    pub f16: bool,
+    /// Arithmetic on `bf16`.
+    pub bf16: bool,
+    /// Arithmetic on `f64`.
+    pub f64: bool,
+    /// Atomic operations on 64-bit integers.
+    pub atomic_64: bool,
+}
+
+impl DeviceFeatures {
+    /// Whether the accelerated matmuls support tiles of the given shape and element types.
+    pub fn supports_accelerated_tile(&self, config: &MmaConfig) -> bool {
+        self.accelerated_tiles.contains(config)
+    }
+
+    /// Whether any accelerated matmul has inputs of the given element types.
+    pub fn accelerates(&self, a_type: StorageType, b_type: StorageType) -> bool {
+        self.accelerated_tiles
+            .iter()
+            .any(|config| config.a_type == a_type && config.b_type == b_type)
+    }
 }
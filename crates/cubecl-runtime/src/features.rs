@@ -12,6 +12,9 @@ pub struct Features {
     pub plane: EnumSet<Plane>,
     /// Clustered launches and intra-cluster operations like cluster shared memory
     pub cube_cluster: bool,
+    /// Cubes that started executing keep making progress while other cubes wait on them,
+    /// so cubes can communicate through global memory, e.g. in single-pass scans.
+    pub forward_progress: bool,
     /// Enables to change the line size of containers during kernel execution.
     pub dynamic_line_size: bool,
 
@@ -1,7 +1,7 @@
 use alloc::format;
 use alloc::string::String;
 use alloc::string::ToString;
-use alloc::sync::Arc;
+use alloc::sync::{Arc, Weak};
 use core::{
     any::{Any, TypeId},
     fmt::Display,
@@ -101,6 +101,11 @@ where
     pub(crate) fn is_free(&self) -> bool {
         Arc::strong_count(&self.all) <= 1
     }
+
+    /// A reference counting the handles and bindings of the resource without keeping it used.
+    pub(crate) fn weak(&self) -> Weak<()> {
+        Arc::downgrade(&self.all)
+    }
 }
 
 #[macro_export(local_inner_macros)]
@@ -2,5 +2,7 @@ mod profiling;
 pub use profiling::*;
 
 mod server;
+mod timings;
 
 pub use server::*;
+pub use timings::*;
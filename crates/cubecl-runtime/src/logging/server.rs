@@ -1,4 +1,5 @@
 use core::fmt::Display;
+use core::sync::atomic::{AtomicU64, Ordering};
 
 use crate::config::{Logger, compilation::CompilationLogLevel, profiling::ProfilingLogLevel};
 use alloc::format;
@@ -23,6 +24,7 @@ pub struct ServerLogger {
     profile_level: Option<ProfileLevel>,
     log_compile_info: bool,
     log_channel: Option<Sender<LogMessage>>,
+    compilations: AtomicU64,
 }
 
 impl Default for ServerLogger {
@@ -42,6 +44,7 @@ impl Default for ServerLogger {
                 profile_level: None,
                 log_compile_info: false,
                 log_channel: None,
+                compilations: AtomicU64::new(0),
             };
         }
         let profile_level = match logger.config.profiling.logger.level {
@@ -73,6 +76,7 @@ impl Default for ServerLogger {
             profile_level,
             log_compile_info,
             log_channel: Some(send),
+            compilations: AtomicU64::new(0),
         }
     }
 }
@@ -89,10 +93,15 @@ impl ServerLogger {
     }
 
     /// Log the argument to a file when the compilation logger is activated.
+    ///
+    /// Servers call it once for every kernel they compile, which is also how compilations are
+    /// [counted](Self::compilation_count).
     pub fn log_compilation<I>(&self, arg: &I)
     where
         I: Display,
     {
+        self.compilations.fetch_add(1, Ordering::Relaxed);
+
         if let Some(channel) = &self.log_channel
             && self.log_compile_info
         {
@@ -101,6 +110,11 @@ impl ServerLogger {
         }
     }
 
+    /// The number of kernels compiled so far.
+    pub fn compilation_count(&self) -> u64 {
+        self.compilations.load(Ordering::Relaxed)
+    }
+
     /// Register a profiled task without timing.
     pub fn register_execution(&self, name: impl Display) {
         if let Some(channel) = &self.log_channel
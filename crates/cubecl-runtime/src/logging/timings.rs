@@ -0,0 +1,265 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use cubecl_common::profile::{Duration, ProfileDuration, TimingMethod};
+
+/// Device time statistics of a kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KernelTiming {
+    /// How the executions were timed.
+    ///
+    /// Devices without timestamps are timed with the [system](TimingMethod::System) clock
+    /// around the submission, which includes the submission and synchronization overhead.
+    pub timing_method: TimingMethod,
+    /// The number of executions.
+    pub count: usize,
+    /// The total time spent executing the kernel.
+    pub total: Duration,
+    /// The shortest execution.
+    pub min: Duration,
+    /// The longest execution.
+    pub max: Duration,
+}
+
+impl KernelTiming {
+    fn new(duration: Duration, timing_method: TimingMethod) -> Self {
+        Self {
+            timing_method,
+            count: 1,
+            total: duration,
+            min: duration,
+            max: duration,
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        if other.timing_method == TimingMethod::System {
+            self.timing_method = TimingMethod::System;
+        }
+        self.count += other.count;
+        self.total += other.total;
+        self.min = Duration::min(self.min, other.min);
+        self.max = Duration::max(self.max, other.max);
+    }
+
+    /// The mean execution time.
+    pub fn mean(&self) -> Duration {
+        self.total / self.count as u32
+    }
+}
+
+/// The time spent in each kernel executed while
+/// [kernel timing](crate::client::ComputeClient::enable_kernel_timing) is enabled.
+///
+/// Kernels executed inside a [profile scope](crate::client::ComputeClient::profile_scope) are
+/// named after the path of the scope, e.g. `forward/attention/matmul`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KernelTimings {
+    /// The statistics of each kernel, by name.
+    pub kernels: BTreeMap<String, KernelTiming>,
+}
+
+impl KernelTimings {
+    /// The statistics of the given kernel.
+    pub fn get(&self, name: &str) -> Option<&KernelTiming> {
+        self.kernels.get(name)
+    }
+
+    /// The statistics of all the kernels executed inside a scope, nested scopes included.
+    pub fn scope(&self, path: &str) -> Option<KernelTiming> {
+        let prefix = format!("{path}/");
+
+        self.kernels
+            .iter()
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .map(|(_, timing)| *timing)
+            .reduce(|mut acc, timing| {
+                acc.merge(&timing);
+                acc
+            })
+    }
+
+    /// The total time spent executing kernels.
+    pub fn total(&self) -> Duration {
+        self.kernels.values().map(|timing| timing.total).sum()
+    }
+
+    /// If no kernel was timed.
+    pub fn is_empty(&self) -> bool {
+        self.kernels.is_empty()
+    }
+
+    fn register(&mut self, name: String, duration: Duration, timing_method: TimingMethod) {
+        let timing = KernelTiming::new(duration, timing_method);
+
+        match self.kernels.get_mut(&name) {
+            Some(item) => item.merge(&timing),
+            None => {
+                self.kernels.insert(name, timing);
+            }
+        }
+    }
+}
+
+impl Display for KernelTimings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let headers = ["Name", "Count", "Total", "Min", "Max", "Mean", "Ratio"];
+        let total = self.total();
+
+        let mut items: Vec<(&String, &KernelTiming)> = self.kernels.iter().collect();
+        items.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+
+        let rows: Vec<[String; 7]> = items
+            .into_iter()
+            .map(|(name, timing)| {
+                let ratio = match total.is_zero() {
+                    true => 0,
+                    false => 100 * timing.total.as_nanos() / total.as_nanos(),
+                };
+
+                [
+                    name.clone(),
+                    timing.count.to_string(),
+                    format!("{:?}", timing.total),
+                    format!("{:?}", timing.min),
+                    format!("{:?}", timing.max),
+                    format!("{:?}", timing.mean()),
+                    format!("{ratio} %"),
+                ]
+            })
+            .collect();
+
+        let mut widths = headers.map(|header| header.len());
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = usize::max(*width, cell.len());
+            }
+        }
+
+        let write_row = |cells: &[&str], f: &mut core::fmt::Formatter<'_>| {
+            write!(f, "|")?;
+            for (cell, width) in cells.iter().zip(widths.iter()) {
+                write!(f, " {cell:<width$} |")?;
+            }
+            writeln!(f)
+        };
+        let line_length = widths.iter().sum::<usize>() + 3 * widths.len() - 1;
+
+        writeln!(f, "|{}|", "⎺".repeat(line_length))?;
+        write_row(&headers, f)?;
+        writeln!(f, "|{}|", "⎼".repeat(line_length))?;
+        for row in rows.iter() {
+            write_row(&row.each_ref().map(|cell| cell.as_str()), f)?;
+        }
+        writeln!(f, "|{}|", "⎯".repeat(line_length))?;
+        writeln!(f, "Total: {total:?}")
+    }
+}
+
+/// Kernel timings of a client, resolved lazily.
+#[derive(Default)]
+pub(crate) struct KernelTimingState {
+    enabled: AtomicBool,
+    inner: spin::Mutex<KernelTimingInner>,
+}
+
+#[derive(Default)]
+struct KernelTimingInner {
+    scopes: Vec<String>,
+    pending: Vec<(String, ProfileDuration)>,
+    timings: KernelTimings,
+}
+
+impl core::fmt::Debug for KernelTimingState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KernelTimingState")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+impl KernelTimingState {
+    /// If kernel executions should be timed.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enable or disable the timing of kernel executions.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Enter a new scope.
+    pub fn push_scope(&self, name: &str) {
+        self.inner.lock().scopes.push(name.to_string());
+    }
+
+    /// Exit the current scope.
+    pub fn pop_scope(&self) {
+        self.inner.lock().scopes.pop();
+    }
+
+    /// Register the duration of a kernel, named after the current scope.
+    pub fn register(&self, name: &str, duration: ProfileDuration) {
+        let mut inner = self.inner.lock();
+        let name = match inner.scopes.is_empty() {
+            true => name.to_string(),
+            false => format!("{}/{name}", inner.scopes.join("/")),
+        };
+
+        inner.pending.push((name, duration));
+    }
+
+    /// Resolve the pending durations and return the timings collected so far.
+    pub async fn timings(&self) -> KernelTimings {
+        let pending = core::mem::take(&mut self.inner.lock().pending);
+        let mut resolved = Vec::with_capacity(pending.len());
+
+        for (name, duration) in pending {
+            let timing_method = duration.timing_method();
+            resolved.push((name, duration.resolve().await.duration(), timing_method));
+        }
+
+        let mut inner = self.inner.lock();
+        for (name, duration, timing_method) in resolved {
+            inner.timings.register(name, duration, timing_method);
+        }
+
+        inner.timings.clone()
+    }
+
+    /// Discard the timings collected so far.
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock();
+        inner.pending.clear();
+        inner.timings = KernelTimings::default();
+    }
+}
+
+/// Groups the kernels executed while the scope is alive under its name.
+///
+/// Created with [profile_scope](crate::client::ComputeClient::profile_scope).
+#[must_use = "The scope ends when dropped"]
+pub struct ProfileScope {
+    state: alloc::sync::Arc<KernelTimingState>,
+}
+
+impl ProfileScope {
+    pub(crate) fn new(state: alloc::sync::Arc<KernelTimingState>, name: &str) -> Self {
+        state.push_scope(name);
+        Self { state }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        self.state.pop_scope();
+    }
+}
@@ -80,6 +80,38 @@ impl core::fmt::Display for MemoryUsage {
     }
 }
 
+/// Error returned when memory can't be reserved.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+pub enum MemoryError {
+    /// Not enough memory is available to satisfy the allocation, even after releasing the unused
+    /// memory.
+    ///
+    /// This is recoverable: freeing handles and retrying the allocation can succeed.
+    #[error(
+        "out of memory: requested {requested} bytes with {in_use} bytes in use and {reserved} bytes reserved"
+    )]
+    OutOfMemory {
+        /// The number of bytes requested.
+        requested: u64,
+        /// The number of bytes in use when the allocation failed.
+        in_use: u64,
+        /// The number of bytes reserved when the allocation failed.
+        reserved: u64,
+    },
+}
+
+impl MemoryError {
+    /// Out of memory error for an allocation of `requested` bytes given the current usage.
+    pub fn out_of_memory(requested: u64, usage: &MemoryUsage) -> Self {
+        Self::OutOfMemory {
+            requested,
+            in_use: usage.bytes_in_use,
+            reserved: usage.bytes_reserved,
+        }
+    }
+}
+
 /// The managed tensor buffer handle that points to some memory segment.
 /// It should not contain actual data.
 pub trait MemoryHandle<Binding>: Clone + Send + Sync + core::fmt::Debug {
@@ -1,6 +1,8 @@
 use super::{
-    MemoryConfiguration, MemoryDeviceProperties, MemoryPoolOptions, MemoryUsage, PoolType,
+    MemoryCleanupMode, MemoryConfiguration, MemoryDeviceProperties, MemoryError, MemoryLimits,
+    MemoryPoolOptions, MemoryScopeUsage, MemoryUsage, PoolType,
     memory_pool::{ExclusiveMemoryPool, MemoryPool, SlicedPool, StaticPool},
+    scopes::MemoryScopes,
 };
 use crate::{
     server::IoError,
@@ -9,7 +11,7 @@ use crate::{
 
 #[cfg(not(exclusive_memory_only))]
 use alloc::vec;
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 pub use super::memory_pool::{SliceBinding, handle::*};
 
@@ -36,6 +38,13 @@ impl MemoryPool for DynamicPool {
         }
     }
 
+    fn alloc_page_size(&self, size: u64) -> u64 {
+        match self {
+            DynamicPool::Sliced(m) => m.alloc_page_size(size),
+            DynamicPool::Exclusive(m) => m.alloc_page_size(size),
+        }
+    }
+
     fn alloc<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
@@ -72,6 +81,13 @@ impl MemoryPool for DynamicPool {
             DynamicPool::Exclusive(m) => m.cleanup(storage, alloc_nr, explicit),
         }
     }
+
+    fn compact<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        match self {
+            DynamicPool::Sliced(m) => m.compact(storage),
+            DynamicPool::Exclusive(m) => m.compact(storage),
+        }
+    }
 }
 
 #[derive(Default, Clone, Copy)]
@@ -92,6 +108,10 @@ pub struct MemoryManagement<Storage> {
     storage: Storage,
     alloc_reserve_count: u64,
     mode: MemoryAllocationMode,
+    max_reserved: Option<u64>,
+    /// Memory allocated outside of the memory management, see [register](Self::register).
+    external: Vec<(SliceHandle, StorageHandle)>,
+    scopes: MemoryScopes,
 }
 
 fn generate_bucket_sizes(
@@ -129,6 +149,7 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         properties: &MemoryDeviceProperties,
         config: MemoryConfiguration,
     ) -> Self {
+        let mut limits = MemoryLimits::default();
         let pool_options = match config {
             #[cfg(not(exclusive_memory_only))]
             MemoryConfiguration::SubSlices => {
@@ -219,7 +240,26 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                     })
                     .collect()
             }
-            MemoryConfiguration::Custom { pool_options } => pool_options,
+            MemoryConfiguration::Custom {
+                pool_options,
+                limits: custom_limits,
+            } => {
+                limits = custom_limits;
+                pool_options
+            }
+        };
+
+        let alignment = match limits.alignment {
+            Some(alignment) => {
+                assert_eq!(
+                    alignment % properties.alignment,
+                    0,
+                    "the memory alignment {alignment} needs to be a multiple of the device alignment {}",
+                    properties.alignment
+                );
+                alignment
+            }
+            None => properties.alignment,
         };
 
         for pool in pool_options.iter() {
@@ -232,15 +272,11 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
                 PoolType::SlicedPages {
                     page_size,
                     max_slice_size,
-                } => DynamicPool::Sliced(SlicedPool::new(
-                    page_size,
-                    max_slice_size,
-                    properties.alignment,
-                )),
+                } => DynamicPool::Sliced(SlicedPool::new(page_size, max_slice_size, alignment)),
                 PoolType::ExclusivePages { max_alloc_size } => {
                     DynamicPool::Exclusive(ExclusiveMemoryPool::new(
                         max_alloc_size,
-                        properties.alignment,
+                        alignment,
                         options.dealloc_period.unwrap_or(u64::MAX),
                     ))
                 }
@@ -253,6 +289,9 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
             storage,
             alloc_reserve_count: 0,
             mode: MemoryAllocationMode::Auto,
+            max_reserved: limits.max_reserved,
+            external: Vec::new(),
+            scopes: MemoryScopes::default(),
         }
     }
 
@@ -269,6 +308,36 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         for pool in self.pools.iter_mut() {
             pool.cleanup(&mut self.storage, self.alloc_reserve_count, explicit);
         }
+
+        self.release_external();
+    }
+
+    /// Deallocate the [registered](Self::register) memory that isn't used anymore.
+    fn release_external(&mut self) {
+        let count = self.external.len();
+        let storage = &mut self.storage;
+        self.external.retain(|(slice, handle)| {
+            let free = slice.is_free();
+            if free {
+                storage.dealloc(handle.id);
+            }
+            !free
+        });
+
+        if self.external.len() < count {
+            self.storage.flush();
+        }
+    }
+
+    /// Release memory following the given [mode](MemoryCleanupMode).
+    pub fn memory_cleanup(&mut self, mode: MemoryCleanupMode) {
+        if let MemoryCleanupMode::Aggressive = mode {
+            for pool in self.pools.iter_mut() {
+                pool.compact(&mut self.storage);
+            }
+        }
+
+        self.cleanup(true);
     }
 
     /// Returns the storage from the specified binding
@@ -277,7 +346,26 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
             return Some(val.clone());
         }
 
-        self.pools.iter().find_map(|p| p.get(&binding)).cloned()
+        if let Some(val) = self.pools.iter().find_map(|p| p.get(&binding)) {
+            return Some(val.clone());
+        }
+
+        self.external
+            .iter()
+            .find(|(slice, _)| slice.id() == binding.id())
+            .map(|(_, handle)| handle.clone())
+    }
+
+    /// Registers memory allocated outside of the memory management, e.g. imported from another
+    /// library, returning a slice over it.
+    ///
+    /// The memory isn't counted in the [memory usage](Self::memory_usage) and is never used for
+    /// other allocations. It is deallocated from the storage once the slice and its bindings are
+    /// dropped, during the next reservation or cleanup.
+    pub fn register(&mut self, handle: StorageHandle) -> SliceHandle {
+        let slice = SliceHandle::new();
+        self.external.push((slice.clone(), handle));
+        slice
     }
 
     /// Returns the resource from the storage at the specified handle
@@ -304,6 +392,34 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
 
     /// Finds a spot in memory for a resource with the given size in bytes, and returns a handle to it
     pub fn reserve(&mut self, size: u64) -> Result<SliceHandle, IoError> {
+        let handle = self.reserve_slice(size)?;
+        self.scopes.register(&handle, size);
+
+        Ok(handle)
+    }
+
+    /// Attribute the memory reserved from now on to the tag, until the scope is
+    /// [popped](Self::pop_scope). Scopes can be nested, the memory being attributed to the
+    /// innermost one.
+    pub fn push_scope(&mut self, tag: String) {
+        self.scopes.push(tag);
+    }
+
+    /// Stop attributing the memory reserved to the innermost scope.
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// The memory attributed to each scope tag, sorted by the bytes still in use.
+    pub fn scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.scopes.usage()
+    }
+
+    fn reserve_slice(&mut self, size: u64) -> Result<SliceHandle, IoError> {
+        if !self.external.is_empty() {
+            self.release_external();
+        }
+
         if let MemoryAllocationMode::Static = self.mode {
             return self.static_pool.alloc(&mut self.storage, size);
         }
@@ -313,17 +429,45 @@ impl<Storage: ComputeStorage> MemoryManagement<Storage> {
         self.alloc_reserve_count += 1;
 
         // Find first pool that fits this allocation
-        let pool = self
+        let pool_index = self
             .pools
-            .iter_mut()
-            .find(|p| p.max_alloc_size() >= size)
+            .iter()
+            .position(|p| p.max_alloc_size() >= size)
             .ok_or(IoError::BufferTooBig(size as usize))?;
 
-        if let Some(slice) = pool.try_reserve(size) {
+        if let Some(slice) = self.pools[pool_index].try_reserve(size) {
             return Ok(slice);
         }
 
-        pool.alloc(&mut self.storage, size)
+        if let Some(max_reserved) = self.max_reserved {
+            let page_size = self.pools[pool_index].alloc_page_size(size);
+
+            if self.memory_usage().bytes_reserved + page_size > max_reserved {
+                // Release what can be released before giving up on the allocation.
+                self.cleanup(true);
+
+                if self.memory_usage().bytes_reserved + page_size > max_reserved {
+                    return Err(MemoryError::out_of_memory(size, &self.memory_usage()).into());
+                }
+            }
+        }
+
+        match self.pools[pool_index].alloc(&mut self.storage, size) {
+            // The storage is out of memory, release what can be released and try again.
+            Err(IoError::BufferTooBig(_)) => {
+                self.cleanup(true);
+
+                self.pools[pool_index]
+                    .alloc(&mut self.storage, size)
+                    .map_err(|err| match err {
+                        IoError::BufferTooBig(_) => {
+                            MemoryError::out_of_memory(size, &self.memory_usage()).into()
+                        }
+                        err => err,
+                    })
+            }
+            result => result,
+        }
     }
 
     /// Fetch the storage used by the memory manager.
@@ -376,7 +520,10 @@ impl<Storage> core::fmt::Debug for MemoryManagement<Storage> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{memory_management::MemoryManagement, storage::BytesStorage};
+    use crate::{
+        memory_management::{MemoryHandle, MemoryManagement, PoolStrategy, SizeClass},
+        storage::BytesStorage,
+    };
 
     const DUMMY_MEM_PROPS: MemoryDeviceProperties = MemoryDeviceProperties {
         max_page_size: 128 * 1024 * 1024,
@@ -409,14 +556,12 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::ExclusivePages {
-                        max_alloc_size: max_page_size,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::ExclusivePages {
+                    max_alloc_size: max_page_size,
+                },
+                dealloc_period: None,
+            }]),
         );
         let handle = memory_management.reserve(100);
         let usage = memory_management.memory_usage();
@@ -438,15 +583,13 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::SlicedPages {
-                        page_size,
-                        max_slice_size: page_size,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::SlicedPages {
+                    page_size,
+                    max_slice_size: page_size,
+                },
+                dealloc_period: None,
+            }]),
         );
 
         let alloc_size = 512;
@@ -467,15 +610,13 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::SlicedPages {
-                        page_size,
-                        max_slice_size: page_size,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::SlicedPages {
+                    page_size,
+                    max_slice_size: page_size,
+                },
+                dealloc_period: None,
+            }]),
         );
 
         let alloc_size = 512;
@@ -496,15 +637,13 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::SlicedPages {
-                        page_size,
-                        max_slice_size: page_size,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::SlicedPages {
+                    page_size,
+                    max_slice_size: page_size,
+                },
+                dealloc_period: None,
+            }]),
         );
 
         let alloc_size = 768;
@@ -527,15 +666,13 @@ mod tests {
                 alignment: 50,
                 data_transfer_async: false,
             },
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::SlicedPages {
-                        page_size,
-                        max_slice_size: page_size,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::SlicedPages {
+                    page_size,
+                    max_slice_size: page_size,
+                },
+                dealloc_period: None,
+            }]),
         );
         let alloc_size = 40;
         let _handle = memory_management.reserve(alloc_size);
@@ -566,9 +703,7 @@ mod tests {
                 alignment: 10,
                 data_transfer_async: false,
             },
-            MemoryConfiguration::Custom {
-                pool_options: pools,
-            },
+            MemoryConfiguration::custom(pools),
         );
         // Allocate one thing on each page.
         let alloc_sizes = [50, 150, 250, 350];
@@ -667,14 +802,12 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::ExclusivePages {
-                        max_alloc_size: 1024,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::ExclusivePages {
+                    max_alloc_size: 1024,
+                },
+                dealloc_period: None,
+            }]),
         );
 
         let alloc_size = 512;
@@ -693,14 +826,12 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::ExclusivePages {
-                        max_alloc_size: 1024,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::ExclusivePages {
+                    max_alloc_size: 1024,
+                },
+                dealloc_period: None,
+            }]),
         );
 
         let alloc_size = 512;
@@ -719,14 +850,12 @@ mod tests {
         let mut memory_management = MemoryManagement::from_configuration(
             BytesStorage::default(),
             &DUMMY_MEM_PROPS,
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::ExclusivePages {
-                        max_alloc_size: 1024,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::ExclusivePages {
+                    max_alloc_size: 1024,
+                },
+                dealloc_period: None,
+            }]),
         );
 
         let alloc_size = 768;
@@ -747,14 +876,12 @@ mod tests {
                 alignment: 50,
                 data_transfer_async: false,
             },
-            MemoryConfiguration::Custom {
-                pool_options: vec![MemoryPoolOptions {
-                    pool_type: PoolType::ExclusivePages {
-                        max_alloc_size: 50 * 20,
-                    },
-                    dealloc_period: None,
-                }],
-            },
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::ExclusivePages {
+                    max_alloc_size: 50 * 20,
+                },
+                dealloc_period: None,
+            }]),
         );
         let alloc_size = 40;
         let _handle = memory_management.reserve(alloc_size);
@@ -783,9 +910,7 @@ mod tests {
                 alignment: 10,
                 data_transfer_async: false,
             },
-            MemoryConfiguration::Custom {
-                pool_options: pools,
-            },
+            MemoryConfiguration::custom(pools),
         );
         // Allocate one thing on each page.
         let alloc_sizes = [50, 150, 250, 350];
@@ -822,4 +947,364 @@ mod tests {
         assert_eq!(usage_before.bytes_in_use, usage_after.bytes_in_use);
         assert_eq!(usage_before.bytes_reserved, usage_after.bytes_reserved);
     }
+
+    const KB: u64 = 1024;
+
+    /// A synthetic trace of transient allocations: each step frees the oldest handle once
+    /// `live` handles are alive.
+    fn run_trace(
+        memory_management: &mut MemoryManagement<BytesStorage>,
+        sizes: &[u64],
+        live: usize,
+    ) -> u64 {
+        let mut handles = alloc::collections::VecDeque::new();
+        let mut max_reserved = 0;
+
+        for &size in sizes {
+            if handles.len() == live {
+                handles.pop_front();
+            }
+            handles.push_back(memory_management.reserve(size).unwrap());
+            max_reserved = max_reserved.max(memory_management.memory_usage().bytes_reserved);
+        }
+
+        max_reserved
+    }
+
+    fn trace_sizes() -> Vec<u64> {
+        (0..64).map(|i| (90 + (i * 7) % 20) * KB).collect()
+    }
+
+    #[test]
+    fn size_classes_exclusive_large_allocations_bounded() {
+        let limits = MemoryLimits {
+            max_reserved: Some(512 * KB),
+            alignment: Some(KB),
+        };
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::size_classes(
+                vec![
+                    SizeClass::new(KB, PoolStrategy::Sliced { page_size: 8 * KB }, None),
+                    SizeClass::new(128 * KB, PoolStrategy::Exclusive, None),
+                ],
+                limits,
+            ),
+        );
+
+        let max_reserved = run_trace(&mut memory_management, &trace_sizes(), 3);
+        let usage = memory_management.memory_usage();
+
+        assert!(max_reserved <= 512 * KB);
+        assert_eq!(usage.number_allocs, 3);
+        assert_eq!(usage.bytes_reserved % KB, 0);
+    }
+
+    #[test]
+    fn size_classes_sliced_large_pages_bounded() {
+        let limits = MemoryLimits {
+            max_reserved: Some(1024 * KB),
+            alignment: None,
+        };
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::size_classes(
+                vec![SizeClass::new(
+                    128 * KB,
+                    PoolStrategy::Sliced {
+                        page_size: 256 * KB,
+                    },
+                    None,
+                )],
+                limits,
+            ),
+        );
+
+        let max_reserved = run_trace(&mut memory_management, &trace_sizes(), 4);
+
+        assert!(max_reserved <= 1024 * KB);
+        assert_eq!(max_reserved % (256 * KB), 0);
+    }
+
+    #[test]
+    fn max_reserved_rejects_allocation_over_limit() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::size_classes(
+                vec![SizeClass::new(1024, PoolStrategy::Exclusive, None)],
+                MemoryLimits {
+                    max_reserved: Some(2048),
+                    alignment: None,
+                },
+            ),
+        );
+
+        let _first = memory_management.reserve(1024).unwrap();
+        let _second = memory_management.reserve(1024).unwrap();
+
+        assert!(memory_management.reserve(1024).is_err());
+    }
+
+    #[test]
+    fn max_reserved_reports_out_of_memory() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::size_classes(
+                vec![SizeClass::new(1024, PoolStrategy::Exclusive, None)],
+                MemoryLimits {
+                    max_reserved: Some(1024),
+                    alignment: None,
+                },
+            ),
+        );
+
+        let first = memory_management.reserve(1024).unwrap();
+
+        match memory_management.reserve(1024) {
+            Err(IoError::Memory(MemoryError::OutOfMemory {
+                requested,
+                in_use,
+                reserved,
+            })) => {
+                assert_eq!(requested, 1024);
+                assert_eq!(in_use, 1024);
+                assert_eq!(reserved, 1024);
+            }
+            other => panic!("Expected an out of memory error, got {other:?}"),
+        }
+
+        core::mem::drop(first);
+        assert!(memory_management.reserve(1024).is_ok());
+    }
+
+    #[test]
+    fn max_reserved_releases_unused_pages_first() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::size_classes(
+                vec![
+                    SizeClass::new(512, PoolStrategy::Exclusive, None),
+                    SizeClass::new(1024, PoolStrategy::Exclusive, None),
+                ],
+                MemoryLimits {
+                    max_reserved: Some(1024),
+                    alignment: None,
+                },
+            ),
+        );
+
+        let small = memory_management.reserve(512).unwrap();
+        drop(small);
+        let _big = memory_management.reserve(1024).unwrap();
+
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 1024);
+    }
+
+    #[test]
+    fn custom_alignment_rounds_allocations() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::size_classes(
+                vec![SizeClass::new(
+                    4096,
+                    PoolStrategy::Sliced { page_size: 4096 },
+                    None,
+                )],
+                MemoryLimits {
+                    max_reserved: None,
+                    alignment: Some(256),
+                },
+            ),
+        );
+
+        let _handle = memory_management.reserve(100).unwrap();
+        let usage = memory_management.memory_usage();
+
+        assert_eq!(usage.bytes_padding, 156);
+    }
+
+    fn two_pages_of_four_slices() -> (MemoryManagement<BytesStorage>, Vec<SliceHandle>) {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::SlicedPages {
+                    page_size: 1024,
+                    max_slice_size: 1024,
+                },
+                dealloc_period: None,
+            }]),
+        );
+        let handles = (0..8)
+            .map(|_| memory_management.reserve(256).unwrap())
+            .collect();
+
+        (memory_management, handles)
+    }
+
+    #[test]
+    fn cleanup_release_unused_frees_empty_pages() {
+        let (mut memory_management, mut handles) = two_pages_of_four_slices();
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 2048);
+
+        handles.truncate(4);
+        memory_management.memory_cleanup(MemoryCleanupMode::ReleaseUnused);
+
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.bytes_reserved, 1024);
+        assert_eq!(usage.number_allocs, 4);
+    }
+
+    #[test]
+    fn cleanup_release_unused_keeps_partially_used_pages() {
+        let (mut memory_management, handles) = two_pages_of_four_slices();
+        let _kept = [handles[0].clone(), handles[4].clone()];
+        drop(handles);
+
+        memory_management.memory_cleanup(MemoryCleanupMode::ReleaseUnused);
+
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 2048);
+    }
+
+    #[test]
+    fn cleanup_aggressive_moves_live_slices() {
+        let (mut memory_management, handles) = two_pages_of_four_slices();
+        let kept = [handles[0].clone(), handles[1].clone(), handles[4].clone()];
+        drop(handles);
+
+        let moved = MemoryHandle::binding(kept[2].clone());
+        memory_management
+            .get_resource(moved.clone(), None, None)
+            .unwrap()
+            .write()
+            .copy_from_slice(&[7; 256]);
+
+        memory_management.memory_cleanup(MemoryCleanupMode::Aggressive);
+
+        let usage = memory_management.memory_usage();
+        assert_eq!(usage.bytes_reserved, 1024);
+        assert_eq!(usage.number_allocs, 3);
+
+        let data = memory_management
+            .get_resource(moved, None, None)
+            .unwrap()
+            .read()
+            .to_vec();
+        assert_eq!(data, [7; 256]);
+    }
+
+    #[test]
+    fn registered_memory_is_released_once_dropped() {
+        let mut memory_management = MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::ExclusivePages,
+        );
+        let storage = memory_management.storage().alloc(256).unwrap();
+        let id = storage.id;
+        let slice = memory_management.register(storage);
+        let binding = MemoryHandle::binding(slice.clone());
+
+        memory_management.memory_cleanup(MemoryCleanupMode::Aggressive);
+
+        assert!(memory_management.get(binding.clone()).is_some());
+        assert_eq!(memory_management.memory_usage().bytes_reserved, 0);
+        // The registered memory isn't used for other allocations.
+        let other = memory_management.reserve(256).unwrap();
+        let other = memory_management.get(MemoryHandle::binding(other)).unwrap();
+        assert_ne!(other.id, id);
+
+        drop(slice);
+        memory_management.cleanup(false);
+        assert!(memory_management.get(binding.clone()).is_some());
+
+        drop(binding);
+        memory_management.cleanup(false);
+        assert!(memory_management.external.is_empty());
+    }
+
+    fn sliced_pages(page_size: u64) -> MemoryManagement<BytesStorage> {
+        MemoryManagement::from_configuration(
+            BytesStorage::default(),
+            &DUMMY_MEM_PROPS,
+            MemoryConfiguration::custom(vec![MemoryPoolOptions {
+                pool_type: PoolType::SlicedPages {
+                    page_size,
+                    max_slice_size: page_size,
+                },
+                dealloc_period: None,
+            }]),
+        )
+    }
+
+    fn scope_usage(memory_management: &MemoryManagement<BytesStorage>, tag: &str) -> (u64, u64) {
+        memory_management
+            .scope_usage()
+            .into_iter()
+            .find(|usage| usage.tag == tag)
+            .map(|usage| (usage.bytes_in_use, usage.peak_bytes_in_use))
+            .unwrap()
+    }
+
+    #[test]
+    fn nested_scopes_attribute_to_the_innermost() {
+        let mut memory_management = sliced_pages(4096);
+
+        let _untagged = memory_management.reserve(64).unwrap();
+        memory_management.push_scope("model".into());
+        let weights = memory_management.reserve(512).unwrap();
+        memory_management.push_scope("attention".into());
+        let scores = memory_management.reserve(256).unwrap();
+        let _keys = memory_management.reserve(128).unwrap();
+        memory_management.pop_scope();
+        let _bias = memory_management.reserve(32).unwrap();
+        memory_management.pop_scope();
+
+        let usage = memory_management.scope_usage();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].tag, "model");
+        assert_eq!(usage[0].number_allocs, 2);
+        assert_eq!(usage[0].bytes_in_use, 544);
+        assert_eq!(usage[1].tag, "attention");
+        assert_eq!(usage[1].bytes_in_use, 384);
+
+        // The scopes were popped, but the memory is still given back to them.
+        drop(scores);
+        drop(weights);
+        assert_eq!(scope_usage(&memory_management, "model"), (32, 544));
+        assert_eq!(scope_usage(&memory_management, "attention"), (128, 384));
+        assert_eq!(memory_management.scope_usage()[0].tag, "attention");
+    }
+
+    #[test]
+    fn freed_slice_reused_by_another_scope() {
+        let mut memory_management = sliced_pages(512);
+
+        memory_management.push_scope("first".into());
+        let first = memory_management.reserve(512).unwrap();
+        let first_id = *first.id();
+        memory_management.pop_scope();
+        drop(first);
+
+        memory_management.push_scope("second".into());
+        let second = memory_management.reserve(512).unwrap();
+        memory_management.pop_scope();
+
+        // The same slice is reserved again, for the second scope only.
+        assert_eq!(*second.id(), first_id);
+        assert_eq!(scope_usage(&memory_management, "first"), (0, 512));
+        assert_eq!(scope_usage(&memory_management, "second"), (512, 512));
+
+        // The peak is updated again when reopening a scope.
+        memory_management.push_scope("first".into());
+        let _a = memory_management.reserve(100).unwrap();
+        memory_management.pop_scope();
+        assert_eq!(scope_usage(&memory_management, "first"), (100, 512));
+    }
 }
@@ -42,6 +42,10 @@ pub trait MemoryPool {
 
     fn try_reserve(&mut self, size: u64) -> Option<SliceHandle>;
 
+    /// The number of bytes that [alloc](MemoryPool::alloc) would reserve on the storage for an
+    /// allocation of the given size.
+    fn alloc_page_size(&self, size: u64) -> u64;
+
     fn alloc<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
@@ -56,4 +60,8 @@ pub trait MemoryPool {
         alloc_nr: u64,
         explicit: bool,
     );
+
+    /// Move live allocations together so that more pages can be released by
+    /// [cleanup](MemoryPool::cleanup).
+    fn compact<Storage: ComputeStorage>(&mut self, _storage: &mut Storage) {}
 }
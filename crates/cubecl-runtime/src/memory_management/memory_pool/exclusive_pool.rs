@@ -58,14 +58,18 @@ impl ExclusiveMemoryPool {
             .min_by_key(|page| page.free_count)
     }
 
+    fn page_size(&self, size: u64) -> u64 {
+        (self.cur_avg_size as u64)
+            .max(size)
+            .next_multiple_of(self.alignment)
+    }
+
     fn alloc_page<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
         size: u64,
     ) -> Result<&mut MemoryPage, IoError> {
-        let alloc_size = (self.cur_avg_size as u64)
-            .max(size)
-            .next_multiple_of(self.alignment);
+        let alloc_size = self.page_size(size);
 
         let storage = storage.alloc(alloc_size)?;
 
@@ -121,6 +125,10 @@ impl MemoryPool for ExclusiveMemoryPool {
         })
     }
 
+    fn alloc_page_size(&self, size: u64) -> u64 {
+        self.page_size(size)
+    }
+
     fn alloc<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
@@ -31,6 +31,29 @@ impl RingBuffer {
             .insert(storage_id, self.queue.len() - 1);
     }
 
+    pub fn remove_page(&mut self, storage_id: StorageId) {
+        let position = match self.chunk_positions.remove(&storage_id) {
+            Some(position) => position,
+            None => return,
+        };
+
+        self.queue.remove(position);
+        for (index, id) in self.queue.iter().enumerate().skip(position) {
+            self.chunk_positions.insert(*id, index);
+        }
+
+        if self.cursor_chunk > position {
+            self.cursor_chunk -= 1;
+        } else if self.cursor_chunk == position {
+            self.cursor_slice = 0;
+        }
+
+        if self.cursor_chunk >= self.queue.len() {
+            self.cursor_chunk = 0;
+            self.cursor_slice = 0;
+        }
+    }
+
     pub fn find_free_slice(
         &mut self,
         size: u64,
@@ -49,7 +72,7 @@ impl RingBuffer {
         self.find_free_slice_in_all_chunks(size, pages, slices, max_second)
     }
 
-    fn find_free_slice_in_chunk(
+    pub(crate) fn find_free_slice_in_chunk(
         &mut self,
         size: u64,
         page: &mut MemoryPage,
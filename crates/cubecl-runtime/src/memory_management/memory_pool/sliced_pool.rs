@@ -116,6 +116,10 @@ impl MemoryPool for SlicedPool {
         Some(slice.handle.clone())
     }
 
+    fn alloc_page_size(&self, _size: u64) -> u64 {
+        self.page_size
+    }
+
     fn alloc<Storage: ComputeStorage>(
         &mut self,
         storage: &mut Storage,
@@ -171,11 +175,51 @@ impl MemoryPool for SlicedPool {
 
     fn cleanup<Storage: ComputeStorage>(
         &mut self,
-        _storage: &mut Storage,
+        storage: &mut Storage,
         _alloc_nr: u64,
-        _explicit: bool,
+        explicit: bool,
     ) {
-        // This pool doesn't do any shrinking currently.
+        // Finding free pages requires going through all slices, so it's only done when asked.
+        if !explicit {
+            return;
+        }
+
+        let free_pages: Vec<_> = self
+            .pages
+            .iter()
+            .filter(|(_, page)| page.slices.values().all(|id| self.slices[id].is_free()))
+            .map(|(id, _)| *id)
+            .collect();
+
+        if free_pages.is_empty() {
+            return;
+        }
+
+        for page in free_pages {
+            self.release_page(storage, page);
+        }
+
+        storage.flush();
+    }
+
+    fn compact<Storage: ComputeStorage>(&mut self, storage: &mut Storage) {
+        if !storage.supports_copy() {
+            return;
+        }
+
+        // Evacuate the emptiest pages first, only into pages that are fuller.
+        let mut pages: Vec<_> = self
+            .pages
+            .iter()
+            .map(|(id, page)| (*id, self.page_bytes_in_use(page)))
+            .filter(|(_, in_use)| *in_use > 0)
+            .collect();
+        pages.sort_by_key(|(_, in_use)| *in_use);
+
+        let pages: Vec<_> = pages.into_iter().map(|(id, _)| id).collect();
+        for (index, page) in pages.iter().enumerate() {
+            self.evacuate_page(storage, *page, &pages[index + 1..]);
+        }
     }
 }
 
@@ -216,6 +260,98 @@ impl SlicedPool {
         Slice::new(storage, handle, padding)
     }
 
+    fn page_bytes_in_use(&self, page: &MemoryPage) -> u64 {
+        page.slices
+            .values()
+            .map(|id| &self.slices[id])
+            .filter(|slice| !slice.is_free())
+            .map(|slice| slice.effective_size())
+            .sum()
+    }
+
+    /// Moves all live slices of a page into the free space of the `targets` pages.
+    fn evacuate_page<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        page: StorageId,
+        targets: &[StorageId],
+    ) {
+        let live_slices: Vec<_> = self.pages[&page]
+            .slices
+            .values()
+            .filter(|id| !self.slices[*id].is_free())
+            .copied()
+            .collect();
+
+        for slice_id in live_slices {
+            let size = self.slices[&slice_id].storage.size();
+            let effective_size = size + calculate_padding(size, self.alignment);
+
+            let target = targets.iter().find_map(|target| {
+                let target_page = self.pages.get_mut(target)?;
+                self.ring
+                    .find_free_slice_in_chunk(effective_size, target_page, &mut self.slices, 0)
+                    .map(|(_, id)| id)
+            });
+
+            match target {
+                Some(target) => self.move_slice(storage, slice_id, target, size),
+                // Nothing else fits either, the page will stay.
+                None => return,
+            }
+        }
+    }
+
+    /// Copies the data of a live slice into a free slice, and swaps their locations so the
+    /// handle of the live slice points to the new memory.
+    fn move_slice<Storage: ComputeStorage>(
+        &mut self,
+        storage: &mut Storage,
+        live_id: SliceId,
+        target_id: SliceId,
+        size: u64,
+    ) {
+        let mut live = self.slices.remove(&live_id).unwrap();
+        let mut target = self.slices.remove(&target_id).unwrap();
+
+        let old_target_size = target.effective_size();
+        target.storage.utilization = StorageUtilization {
+            offset: target.storage.offset(),
+            size,
+        };
+        target.padding = old_target_size - size;
+
+        storage.copy(&live.storage, &target.storage);
+
+        core::mem::swap(&mut live.storage, &mut target.storage);
+        core::mem::swap(&mut live.padding, &mut target.padding);
+
+        self.pages
+            .get_mut(&live.storage.id)
+            .unwrap()
+            .insert_slice(live.storage.offset(), live_id);
+        self.pages
+            .get_mut(&target.storage.id)
+            .unwrap()
+            .insert_slice(target.storage.offset(), target_id);
+
+        self.slices.insert(live_id, live);
+        self.slices.insert(target_id, target);
+    }
+
+    fn release_page<Storage: ComputeStorage>(&mut self, storage: &mut Storage, id: StorageId) {
+        if let Some(page) = self.pages.remove(&id) {
+            for slice in page.slices.values() {
+                self.slices.remove(slice);
+            }
+        }
+
+        self.ring.remove_page(id);
+        self.storage_index.remove(&id);
+        self.recently_added_pages.retain(|page| *page != id);
+        storage.dealloc(id);
+    }
+
     /// Creates a page of given size by allocating on the storage.
     fn create_page<Storage: ComputeStorage>(
         &mut self,
@@ -31,6 +31,10 @@ impl MemoryPool for StaticPool {
         None
     }
 
+    fn alloc_page_size(&self, size: u64) -> u64 {
+        size
+    }
+
     fn alloc<Storage: crate::storage::ComputeStorage>(
         &mut self,
         storage: &mut Storage,
@@ -4,6 +4,10 @@ mod base;
 
 pub use base::*;
 
+mod scopes;
+
+pub use scopes::MemoryScopeUsage;
+
 /// Dynamic memory management strategy.
 mod memory_manage;
 use cubecl_common::CubeDim;
@@ -12,7 +16,7 @@ pub use memory_manage::*;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
-use crate::server::CubeCount;
+use crate::server::{CubeCount, IoError, KernelResource};
 
 /// The type of memory pool to use.
 #[derive(Debug, Clone)]
@@ -58,7 +62,98 @@ pub enum MemoryConfiguration {
         /// Options for each pool to construct. When allocating, the first
         /// possible pool will be picked for an allocation.
         pool_options: Vec<MemoryPoolOptions>,
+        /// Options shared by all pools.
+        limits: MemoryLimits,
+    },
+}
+
+impl MemoryConfiguration {
+    /// Create a custom configuration from the given pools, using the default [limits](MemoryLimits).
+    pub fn custom(pool_options: Vec<MemoryPoolOptions>) -> Self {
+        MemoryConfiguration::Custom {
+            pool_options,
+            limits: MemoryLimits::default(),
+        }
+    }
+
+    /// Create a custom configuration from a list of size classes.
+    ///
+    /// Every size class covers allocations up to its `max_alloc_size`, and the classes must be
+    /// sorted from the smallest to the biggest. Allocations bigger than the last class fail.
+    pub fn size_classes(classes: Vec<SizeClass>, limits: MemoryLimits) -> Self {
+        let pool_options = classes
+            .into_iter()
+            .map(|class| MemoryPoolOptions {
+                pool_type: match class.strategy {
+                    PoolStrategy::Sliced { page_size } => PoolType::SlicedPages {
+                        page_size,
+                        max_slice_size: class.max_alloc_size,
+                    },
+                    PoolStrategy::Exclusive => PoolType::ExclusivePages {
+                        max_alloc_size: class.max_alloc_size,
+                    },
+                },
+                dealloc_period: class.dealloc_period,
+            })
+            .collect();
+
+        MemoryConfiguration::Custom {
+            pool_options,
+            limits,
+        }
+    }
+}
+
+/// How much work the memory manager does to release memory back to the device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemoryCleanupMode {
+    /// Release the pages that don't contain any live allocation.
+    #[default]
+    ReleaseUnused,
+    /// Also move live allocations out of sparsely used pages to release them, when the storage
+    /// supports device to device copies.
+    ///
+    /// Resources previously fetched from a handle can't be used after this, since their memory
+    /// might have moved.
+    Aggressive,
+}
+
+/// Limits and rounding rules applied to every allocation of a memory manager.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLimits {
+    /// The maximum number of bytes that can be reserved on the device in total.
+    ///
+    /// When an allocation would go over that limit, unused pages are released first, and the
+    /// allocation fails only if it still doesn't fit. `None` means no limit.
+    pub max_reserved: Option<u64>,
+    /// Round every allocation to a multiple of this alignment in bytes.
+    ///
+    /// It has to be a multiple of the device alignment, and page sizes have to be a multiple of it.
+    /// `None` uses the device alignment.
+    pub alignment: Option<u64>,
+}
+
+/// The allocation strategy used for a [size class](SizeClass).
+#[derive(Debug, Clone, Copy)]
+pub enum PoolStrategy {
+    /// Allocations are slices of pages of the given size.
+    Sliced {
+        /// The page size to allocate.
+        page_size: u64,
     },
+    /// Every allocation gets its own page.
+    Exclusive,
+}
+
+/// A range of allocation sizes served by the same pool.
+#[derive(new, Debug, Clone)]
+pub struct SizeClass {
+    /// The biggest allocation in bytes handled by this class.
+    pub max_alloc_size: u64,
+    /// How allocations of this class are laid out.
+    pub strategy: PoolStrategy,
+    /// See [MemoryPoolOptions::dealloc_period].
+    pub dealloc_period: Option<u64>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -126,3 +221,53 @@ pub struct HardwareProperties {
     /// For a backend that also supports 32x8x16, the value would be 8.
     pub min_tensor_cores_dim: Option<u32>,
 }
+
+impl HardwareProperties {
+    /// Check that a kernel with the given cube dimensions and bytes of shared memory can be
+    /// launched on the device, returning the first limit exceeded otherwise.
+    pub fn check_kernel_limits(
+        &self,
+        cube_dim: &CubeDim,
+        shared_memory_size: usize,
+    ) -> Result<(), IoError> {
+        let limits = [
+            (
+                KernelResource::SharedMemory,
+                shared_memory_size as u64,
+                self.max_shared_memory_size as u64,
+            ),
+            (
+                KernelResource::UnitsPerCube,
+                cube_dim.num_elems() as u64,
+                self.max_units_per_cube as u64,
+            ),
+            (
+                KernelResource::CubeDimX,
+                cube_dim.x as u64,
+                self.max_cube_dim.x as u64,
+            ),
+            (
+                KernelResource::CubeDimY,
+                cube_dim.y as u64,
+                self.max_cube_dim.y as u64,
+            ),
+            (
+                KernelResource::CubeDimZ,
+                cube_dim.z as u64,
+                self.max_cube_dim.z as u64,
+            ),
+        ];
+
+        for (resource, requested, limit) in limits {
+            if requested > limit {
+                return Err(IoError::LimitExceeded {
+                    resource,
+                    requested,
+                    limit,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use super::memory_pool::{SliceHandle, SliceId};
+use alloc::string::String;
+use alloc::sync::Weak;
+use alloc::vec::Vec;
+use hashbrown::{HashMap, HashSet};
+
+/// The memory attributed to a [memory scope](crate::client::ComputeClient::memory_scope).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryScopeUsage {
+    /// The tag of the scope.
+    pub tag: String,
+    /// The number of allocations of the scope that are still alive.
+    pub number_allocs: u64,
+    /// The number of bytes of the allocations of the scope that are still alive.
+    pub bytes_in_use: u64,
+    /// The highest number of bytes the allocations of the scope were using at once.
+    pub peak_bytes_in_use: u64,
+}
+
+/// Attributes the allocations made while a scope is active to its tag.
+///
+/// Freed allocations are found lazily, either when their slice is reserved again or when the
+/// allocations of their tag are counted, so nothing is done when no scope was ever pushed.
+#[derive(Default, Debug)]
+pub(crate) struct MemoryScopes {
+    tags: Vec<TagUsage>,
+    tag_ids: HashMap<String, usize>,
+    /// The active scopes, the last one being the one allocations are attributed to.
+    stack: Vec<usize>,
+    allocations: HashMap<SliceId, Allocation>,
+}
+
+#[derive(Debug)]
+struct TagUsage {
+    tag: String,
+    slices: HashSet<SliceId>,
+    peak_bytes: u64,
+}
+
+/// The record of an allocation, which travels with it until it is freed, whatever the active
+/// scope is by then.
+#[derive(Debug)]
+struct Allocation {
+    tag: usize,
+    size: u64,
+    handle: Weak<()>,
+}
+
+impl Allocation {
+    fn is_free(&self) -> bool {
+        // The memory pool keeps one reference to the slice while it exists.
+        self.handle.strong_count() <= 1
+    }
+}
+
+impl MemoryScopes {
+    /// Attribute the allocations made from now on to the tag, until the scope is
+    /// [popped](Self::pop).
+    pub fn push(&mut self, tag: String) {
+        let id = match self.tag_ids.get(&tag) {
+            Some(id) => *id,
+            None => {
+                let id = self.tags.len();
+                self.tag_ids.insert(tag.clone(), id);
+                self.tags.push(TagUsage {
+                    tag,
+                    slices: HashSet::new(),
+                    peak_bytes: 0,
+                });
+                id
+            }
+        };
+
+        self.stack.push(id);
+    }
+
+    /// Stop attributing allocations to the last pushed tag.
+    pub fn pop(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Record an allocation of `size` bytes just reserved.
+    pub fn register(&mut self, handle: &SliceHandle, size: u64) {
+        if self.allocations.is_empty() && self.stack.is_empty() {
+            return;
+        }
+
+        let id = *handle.id();
+        // The pool only reserves free slices again, so the previous allocation was freed.
+        if let Some(previous) = self.allocations.remove(&id) {
+            self.tags[previous.tag].slices.remove(&id);
+        }
+
+        let Some(tag) = self.stack.last().copied() else {
+            return;
+        };
+
+        self.allocations.insert(
+            id,
+            Allocation {
+                tag,
+                size,
+                handle: handle.weak(),
+            },
+        );
+        self.tags[tag].slices.insert(id);
+
+        self.release_freed(tag);
+        let (_, bytes) = self.usage_of(tag);
+        let usage = &mut self.tags[tag];
+        usage.peak_bytes = usage.peak_bytes.max(bytes);
+    }
+
+    /// The memory attributed to each tag, sorted by the bytes still in use.
+    pub fn usage(&self) -> Vec<MemoryScopeUsage> {
+        let mut usage: Vec<_> = self
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(id, tag)| {
+                let (number_allocs, bytes_in_use) = self.usage_of(id);
+
+                MemoryScopeUsage {
+                    tag: tag.tag.clone(),
+                    number_allocs,
+                    bytes_in_use,
+                    peak_bytes_in_use: tag.peak_bytes.max(bytes_in_use),
+                }
+            })
+            .collect();
+        usage.sort_by(|a, b| b.bytes_in_use.cmp(&a.bytes_in_use));
+
+        usage
+    }
+
+    /// The number of live allocations of the tag and their bytes.
+    fn usage_of(&self, tag: usize) -> (u64, u64) {
+        self.tags[tag]
+            .slices
+            .iter()
+            .map(|id| &self.allocations[id])
+            .filter(|allocation| !allocation.is_free())
+            .fold((0, 0), |(count, bytes), allocation| {
+                (count + 1, bytes + allocation.size)
+            })
+    }
+
+    /// Forget the allocations of the tag that were freed.
+    fn release_freed(&mut self, tag: usize) {
+        let allocations = &mut self.allocations;
+        self.tags[tag].slices.retain(|id| {
+            let freed = allocations[id].is_free();
+            if freed {
+                allocations.remove(id);
+            }
+            !freed
+        });
+    }
+}
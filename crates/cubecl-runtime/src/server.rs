@@ -3,18 +3,21 @@ use crate::{
     kernel::KernelMetadata,
     logging::ServerLogger,
     memory_management::{
-        MemoryAllocationMode, MemoryHandle, MemoryUsage,
+        MemoryAllocationMode, MemoryCleanupMode, MemoryError, MemoryHandle, MemoryScopeUsage,
+        MemoryUsage,
         memory_pool::{SliceBinding, SliceHandle},
     },
     storage::{BindingResource, ComputeStorage},
     tma::{OobFill, TensorMapFormat, TensorMapInterleave, TensorMapPrefetch, TensorMapSwizzle},
 };
+use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 use core::fmt::Debug;
+use core::time::Duration;
 use cubecl_common::{ExecutionMode, bytes::Bytes, future::DynFut, profile::ProfileDuration};
 use cubecl_ir::StorageType;
 use thiserror::Error;
@@ -71,6 +74,9 @@ where
     }
 
     /// Given bindings, returns the owned resources as bytes.
+    ///
+    /// The copies have to be enqueued before returning, so that they are ordered with the other
+    /// tasks of the server. The returned future only waits for their completion.
     fn read<'a>(
         &mut self,
         descriptors: Vec<CopyDescriptor<'a>>,
@@ -79,20 +85,98 @@ where
     /// Writes the specified bytes into the buffers given
     fn write(&mut self, descriptors: Vec<(CopyDescriptor<'_>, &[u8])>) -> Result<(), IoError>;
 
+    /// Allocates a host buffer of `size` bytes that can be transferred to and from the device
+    /// without an intermediate copy.
+    ///
+    /// Runtimes without page-locked host memory return a regular host buffer, and transfers go
+    /// through the regular [read](Self::read) and [write](Self::write) paths.
+    fn create_pinned(&mut self, size: usize) -> Result<PinnedBuffer, IoError> {
+        Ok(PinnedBuffer::new(Bytes::from_bytes_vec(vec![0; size])))
+    }
+
+    /// Enqueues a copy of the pinned buffer into the given destination, without waiting for it
+    /// to complete.
+    ///
+    /// The buffer is given back by the returned future once the transfer is done.
+    fn write_from_pinned(
+        &mut self,
+        src: PinnedBuffer,
+        dst: CopyDescriptor<'_>,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let result = src
+            .check_size(&dst)
+            .and_then(|num_bytes| self.write(vec![(dst, &src[..num_bytes])]));
+
+        Box::pin(async move { result.map(|_| src) })
+    }
+
+    /// Enqueues a copy of the given source into the pinned buffer, without waiting for it to
+    /// complete.
+    ///
+    /// The buffer is given back by the returned future once the transfer is done.
+    fn read_to_pinned(
+        &mut self,
+        src: CopyDescriptor<'_>,
+        mut dst: PinnedBuffer,
+    ) -> DynFut<Result<PinnedBuffer, IoError>> {
+        let num_bytes = match dst.check_size(&src) {
+            Ok(num_bytes) => num_bytes,
+            Err(err) => return Box::pin(async move { Err(err) }),
+        };
+        let read = self.read(vec![src]);
+
+        Box::pin(async move {
+            let data = read.await?.remove(0);
+            dst[..num_bytes].copy_from_slice(&data[..num_bytes]);
+            Ok(dst)
+        })
+    }
+
     /// Wait for the completion of every task in the server.
     fn sync(&mut self) -> DynFut<()>;
 
+    /// Wait for the completion of every task in the server, returning an error when it can't
+    /// complete, e.g. when a kernel [timed out](IoError::KernelTimeout).
+    ///
+    /// Servers that can't detect such failures wait as [sync](Self::sync) does.
+    fn try_sync(&mut self) -> DynFut<Result<(), IoError>> {
+        let sync = self.sync();
+        Box::pin(async move {
+            sync.await;
+            Ok(())
+        })
+    }
+
     /// Given a resource handle, returns the storage resource.
     fn get_resource(
         &mut self,
         binding: Binding,
     ) -> BindingResource<<Self::Storage as ComputeStorage>::Resource>;
 
+    /// Imports a resource allocated outside of the server, returning a handle over it.
+    ///
+    /// The memory of the resource is never reused nor freed by the server, which only releases
+    /// its reference once the handle and its bindings are dropped.
+    ///
+    /// Servers that can't import resources return an error.
+    fn import_resource(
+        &mut self,
+        _resource: <Self::Storage as ComputeStorage>::Resource,
+    ) -> Result<Handle, IoError> {
+        Err(IoError::Unknown(String::from(
+            "Importing resources isn't supported by this runtime",
+        )))
+    }
+
     /// Executes the `kernel` over the given memory `handles`.
     ///
     /// Kernels have mutable access to every resource they are given
     /// and are responsible of determining which should be read or written.
     ///
+    /// Returns an error when the memory needed to launch the kernel, e.g. for its metadata and
+    /// scalars, can't be allocated, or when the backend rejects the kernel. The server must stay
+    /// usable after a [compilation error](IoError::Compilation).
+    ///
     /// # Safety
     ///
     /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
@@ -103,7 +187,226 @@ where
         bindings: Bindings,
         kind: ExecutionMode,
         logger: Arc<ServerLogger>,
-    );
+    ) -> Result<(), IoError>;
+
+    /// Creates a new [execution stream](ExecutionStream).
+    ///
+    /// Servers that can only execute work in order return the default stream, in which case the
+    /// work of all streams is serialized and events are always reached.
+    fn create_stream(&mut self) -> ExecutionStream {
+        ExecutionStream::default()
+    }
+
+    /// Executes the `kernel` on the given stream, see [execute](Self::execute).
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn execute_on(
+        &mut self,
+        _stream: ExecutionStream,
+        kernel: Self::Kernel,
+        count: CubeCount,
+        bindings: Bindings,
+        kind: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        unsafe { self.execute(kernel, count, bindings, kind, logger) }
+    }
+
+    /// Compiles the `kernel` and caches it without executing it, so its first execution doesn't
+    /// have to compile it.
+    ///
+    /// Returns an error when the kernel is rejected by the backend, in which case nothing is
+    /// cached and the server can keep executing other kernels. Servers that don't cache compiled
+    /// kernels ignore it.
+    fn precompile(
+        &mut self,
+        _kernel: Self::Kernel,
+        _mode: ExecutionMode,
+        _logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        Ok(())
+    }
+
+    /// Executes the `kernels` in order on the given stream, without synchronizing between them.
+    ///
+    /// Stops at the first kernel that fails to execute, the previous ones staying enqueued.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn execute_batch(
+        &mut self,
+        stream: ExecutionStream,
+        kernels: Vec<BatchedKernel<Self::Kernel>>,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        for kernel in kernels {
+            unsafe {
+                self.execute_on(
+                    stream,
+                    kernel.kernel,
+                    kernel.count,
+                    kernel.bindings,
+                    kernel.mode,
+                    logger.clone(),
+                )?
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Records the `kernels` into a batch that is executed each time it is
+    /// [replayed](Self::replay_batch), without submitting the kernels again.
+    ///
+    /// Recording doesn't execute the kernels. The bindings of the kernels are kept alive until
+    /// the batch is [released](Self::release_batch).
+    ///
+    /// Servers that can't record batches return an error.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn record_batch(
+        &mut self,
+        _stream: ExecutionStream,
+        _kernels: Vec<BatchedKernel<Self::Kernel>>,
+        _logger: Arc<ServerLogger>,
+    ) -> Result<BatchId, IoError> {
+        Err(IoError::Unknown(String::from(
+            "Reusable batches aren't supported by this runtime",
+        )))
+    }
+
+    /// Executes a [recorded batch](Self::record_batch) on the given stream.
+    fn replay_batch(&mut self, _stream: ExecutionStream, _batch: BatchId) -> Result<(), IoError> {
+        Err(IoError::InvalidHandle)
+    }
+
+    /// Records the `kernels` in place of the kernels of a [recorded batch](Self::record_batch),
+    /// which updates the parameters of the recorded kernels when only their bindings, scalars or
+    /// cube counts changed.
+    ///
+    /// The replays in flight complete with the previous kernels, whose bindings are released
+    /// once they are done.
+    ///
+    /// # Safety
+    ///
+    /// When executing with mode [ExecutionMode::Unchecked], out-of-bound reads and writes can happen.
+    unsafe fn update_batch(
+        &mut self,
+        _stream: ExecutionStream,
+        _batch: BatchId,
+        _kernels: Vec<BatchedKernel<Self::Kernel>>,
+        _logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        Err(IoError::InvalidHandle)
+    }
+
+    /// Releases a [recorded batch](Self::record_batch), once its replays in flight are done.
+    fn release_batch(&mut self, _batch: BatchId) {}
+
+    /// Enqueues the copies of the given bindings on the given stream, see [read](Self::read).
+    fn read_on<'a>(
+        &mut self,
+        _stream: ExecutionStream,
+        descriptors: Vec<CopyDescriptor<'a>>,
+    ) -> DynFut<Result<Vec<Bytes>, IoError>> {
+        self.read(descriptors)
+    }
+
+    /// Writes the specified bytes into the buffers given on the given stream, see
+    /// [write](Self::write).
+    fn write_on(
+        &mut self,
+        _stream: ExecutionStream,
+        descriptors: Vec<(CopyDescriptor<'_>, &[u8])>,
+    ) -> Result<(), IoError> {
+        self.write(descriptors)
+    }
+
+    /// Records an event on the stream, reached once all the work submitted to the stream before
+    /// is completed.
+    fn record_event(&mut self, _stream: ExecutionStream) -> StreamEvent {
+        StreamEvent { id: 0 }
+    }
+
+    /// Makes the work submitted to the stream after this call wait until the event is reached,
+    /// without blocking the host.
+    fn wait_event(&mut self, _stream: ExecutionStream, _event: StreamEvent) {}
+
+    /// Whether the event is reached, without blocking.
+    ///
+    /// The default is for servers completing the work before returning from the call submitting
+    /// it.
+    fn is_event_complete(&mut self, _event: StreamEvent) -> bool {
+        true
+    }
+
+    /// Wait until the event is reached.
+    ///
+    /// The default waits for the completion of every task in the server.
+    fn sync_event(&mut self, _event: StreamEvent) -> DynFut<()> {
+        self.sync()
+    }
+
+    /// The time elapsed on the device between two events, resolved once the `end` event is
+    /// reached.
+    fn event_elapsed(
+        &mut self,
+        _start: StreamEvent,
+        _end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        Err(ProfileError::Unknown(String::from(
+            "Timing events isn't supported by this runtime",
+        )))
+    }
+
+    /// Releases an event, which can't be waited for, queried or timed after.
+    fn release_event(&mut self, _event: StreamEvent) {}
+
+    /// Makes the work submitted to the stream after this call wait until the native event of
+    /// another library is reached.
+    ///
+    /// Servers without native events return an error.
+    ///
+    /// # Safety
+    ///
+    /// The event must be a valid event of the device of the server.
+    unsafe fn wait_native_event(
+        &mut self,
+        _stream: ExecutionStream,
+        _event: NativeEvent,
+    ) -> Result<(), IoError> {
+        Err(IoError::Unknown(String::from(
+            "Native events aren't supported by this runtime",
+        )))
+    }
+
+    /// Records the native event of another library on the stream, reached once all the work
+    /// submitted to the stream before is completed.
+    ///
+    /// Servers without native events return an error.
+    ///
+    /// # Safety
+    ///
+    /// The event must be a valid event of the device of the server.
+    unsafe fn record_native_event(
+        &mut self,
+        _stream: ExecutionStream,
+        _event: NativeEvent,
+    ) -> Result<(), IoError> {
+        Err(IoError::Unknown(String::from(
+            "Native events aren't supported by this runtime",
+        )))
+    }
+
+    /// Wait for the completion of every task in the server, on every stream.
+    fn sync_all(&mut self) -> DynFut<()> {
+        self.sync()
+    }
 
     /// Flush all outstanding tasks in the server.
     fn flush(&mut self);
@@ -111,8 +414,21 @@ where
     /// The current memory usage of the server.
     fn memory_usage(&self) -> MemoryUsage;
 
+    /// Attribute the memory allocated from now on to the tag, until the scope is
+    /// [popped](Self::pop_memory_scope).
+    fn push_memory_scope(&mut self, tag: String);
+
+    /// Stop attributing the memory allocated to the innermost scope.
+    fn pop_memory_scope(&mut self);
+
+    /// The memory attributed to each scope tag, sorted by the bytes still in use.
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage>;
+
     /// Ask the server to release memory that it can release.
-    fn memory_cleanup(&mut self);
+    ///
+    /// Outstanding tasks should be flushed first, so that no memory is released while it is
+    /// still in use.
+    fn memory_cleanup(&mut self, mode: MemoryCleanupMode);
 
     /// Enable collecting timestamps.
     fn start_profile(&mut self) -> ProfilingToken;
@@ -120,6 +436,24 @@ where
     /// Disable collecting timestamps.
     fn end_profile(&mut self, token: ProfilingToken) -> Result<ProfileDuration, ProfileError>;
 
+    /// Start timing the work submitted from now on with device timestamps, without waiting for
+    /// the work already submitted to complete.
+    ///
+    /// Returns `None` when the server can't record device timestamps, in which case the regular
+    /// [profiling](Self::start_profile) has to be used.
+    fn start_device_timing(&mut self) -> Option<ProfilingToken> {
+        None
+    }
+
+    /// Stop a timing started with [start_device_timing](Self::start_device_timing), the duration
+    /// being resolved lazily once the work is [flushed](Self::flush) and completed.
+    fn end_device_timing(
+        &mut self,
+        _token: ProfilingToken,
+    ) -> Result<ProfileDuration, ProfileError> {
+        Err(ProfileError::NotRegistered)
+    }
+
     /// Update the memory mode of allocation in the server.
     fn allocation_mode(&mut self, mode: MemoryAllocationMode);
 }
@@ -154,6 +488,62 @@ pub trait DataTransferService {
     }
 }
 
+/// An execution stream of a server.
+///
+/// Work submitted to the same stream executes in order, while work submitted to different
+/// streams can overlap. Ordering between streams has to be enforced with [events](StreamEvent).
+///
+/// Memory handles can be used from any stream, the server making sure memory isn't reused while
+/// work in flight on any stream still accesses it.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExecutionStream {
+    /// The stream index, `0` being the default stream of the server.
+    pub id: u32,
+}
+
+impl ExecutionStream {
+    /// Whether this is the default stream of the server.
+    pub fn is_default(&self) -> bool {
+        self.id == 0
+    }
+}
+
+/// A point recorded on an [execution stream](ExecutionStream) that other streams can wait for,
+/// that can be queried and timed until it's [released](ComputeServer::release_event).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct StreamEvent {
+    /// The event identifier.
+    pub id: u64,
+}
+
+/// An event created by another library sharing the device, e.g. a `CUevent` on CUDA, see
+/// [wait_native_event](ComputeServer::wait_native_event).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct NativeEvent {
+    /// The native handle of the event.
+    pub handle: u64,
+}
+
+/// A kernel launch recorded in a batch, see [execute_batch](ComputeServer::execute_batch).
+#[derive(new, Debug)]
+pub struct BatchedKernel<K> {
+    /// The kernel to execute.
+    pub kernel: K,
+    /// The number of cubes to launch.
+    pub count: CubeCount,
+    /// The bindings of the kernel.
+    pub bindings: Bindings,
+    /// The execution mode of the kernel.
+    pub mode: ExecutionMode,
+}
+
+/// Identifies a batch [recorded](ComputeServer::record_batch) by a server.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BatchId {
+    /// The batch identifier.
+    pub id: u64,
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 /// Profiling identification so that the server can support recursive and overlapping profilings.
 pub struct ProfilingToken {
@@ -161,6 +551,32 @@ pub struct ProfilingToken {
     pub id: u64,
 }
 
+/// Identifies the [compute server](ComputeServer) owning a [handle](Handle).
+///
+/// Each device has its own server, so handles used on the wrong device can be detected before
+/// they reach the server.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ServerId {
+    id: u64,
+}
+
+impl ServerId {
+    /// Create a new unique server id.
+    pub(crate) fn new() -> Self {
+        static COUNTER: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+
+        Self {
+            id: COUNTER.fetch_add(1, core::sync::atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+impl core::fmt::Display for ServerId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "server {}", self.id)
+    }
+}
+
 /// Server handle containing the [memory handle](crate::server::Handle).
 #[derive(new, Debug, PartialEq, Eq)]
 pub struct Handle {
@@ -172,6 +588,9 @@ pub struct Handle {
     pub offset_end: Option<u64>,
     /// Length of the underlying buffer ignoring offsets
     size: u64,
+    /// The server that allocated the handle, set by the [client](crate::client::ComputeClient).
+    #[new(default)]
+    owner: Option<ServerId>,
 }
 
 /// Type of allocation, either contiguous or optimized (row-aligned when possible)
@@ -207,6 +626,60 @@ impl<'a> AllocationDescriptor<'a> {
     }
 }
 
+/// A host buffer used for transfers between the host and the device.
+///
+/// When supported by the runtime the memory is page-locked, so the device can access it directly.
+/// A transfer takes ownership of the buffer and only gives it back once it is completed, making it
+/// impossible to modify or free the buffer while the device still accesses it.
+#[derive(new, Debug)]
+pub struct PinnedBuffer {
+    bytes: Bytes,
+}
+
+impl PinnedBuffer {
+    /// The number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Consumes the buffer, returning the underlying bytes.
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+
+    /// Returns the number of bytes copied by the descriptor, making sure they fit in the buffer.
+    pub fn check_size(&self, descriptor: &CopyDescriptor<'_>) -> Result<usize, IoError> {
+        let num_bytes = descriptor.shape.iter().product::<usize>() * descriptor.elem_size;
+
+        match num_bytes <= self.len() {
+            true => Ok(num_bytes),
+            false => Err(IoError::BufferTooSmall {
+                size: self.len(),
+                required: num_bytes,
+            }),
+        }
+    }
+}
+
+impl core::ops::Deref for PinnedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl core::ops::DerefMut for PinnedBuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bytes
+    }
+}
+
 /// An allocation with associated strides. Strides depend on tensor layout.
 #[derive(new, Debug)]
 pub struct Allocation {
@@ -223,17 +696,156 @@ pub enum IoError {
     /// Buffer size exceeds the max available
     #[error("can't allocate buffer of size")]
     BufferTooBig(usize),
+    /// Host buffer is too small for the copy
+    #[error("host buffer of size {size} is too small for a copy of {required} bytes")]
+    BufferTooSmall {
+        /// The size of the host buffer.
+        size: usize,
+        /// The number of bytes to copy.
+        required: usize,
+    },
     /// Strides aren't supported for this copy operation on this runtime
     #[error("the provided strides are not supported for this operation")]
     UnsupportedStrides,
     /// Handle wasn't found in the memory pool
     #[error("couldn't find resource for that handle")]
     InvalidHandle,
+    /// The offset of a binding isn't aligned as required by the device
+    #[error(
+        "binding offset of {offset} bytes isn't a multiple of the alignment of {alignment} bytes"
+    )]
+    MisalignedOffset {
+        /// The offset of the binding in bytes.
+        offset: u64,
+        /// The alignment required by the device in bytes.
+        alignment: u64,
+    },
+    /// Not enough memory is available for the allocation
+    #[error(transparent)]
+    Memory(#[from] MemoryError),
+    /// The kernel needs more of a resource than the device provides
+    #[error("the kernel requires {requested} {resource}, but the device limit is {limit}")]
+    LimitExceeded {
+        /// The resource exceeding the limit.
+        resource: KernelResource,
+        /// The amount of the resource required by the kernel.
+        requested: u64,
+        /// The amount of the resource provided by the device.
+        limit: u64,
+    },
+    /// The kernel uses a type the device doesn't support
+    #[error("the kernel uses {0}, which the device doesn't support")]
+    UnsupportedType(StorageType),
+    /// The backend rejected the generated kernel
+    #[error(transparent)]
+    Compilation(#[from] CompilationError),
+    /// The work submitted to the server didn't complete within the timeout of the server, which
+    /// is then [poisoned](IoError::Poisoned)
+    #[error("the kernel {kernel_name} didn't complete after {elapsed:?}")]
+    KernelTimeout {
+        /// The name of the first kernel found still running.
+        kernel_name: String,
+        /// The time waited for the work to complete.
+        elapsed: Duration,
+    },
+    /// A kernel previously timed out, so the server can't be used anymore
+    #[error("the server is unusable since the kernel {kernel_name} timed out")]
+    Poisoned {
+        /// The name of the kernel that timed out.
+        kernel_name: String,
+    },
     /// Unknown error happened during execution
     #[error("Unknown error happened during execution")]
     Unknown(String),
 }
 
+/// A resource of the device with a limit on how much a kernel can use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KernelResource {
+    /// Bytes of shared memory per cube.
+    SharedMemory,
+    /// Units per cube.
+    UnitsPerCube,
+    /// Units along the x axis of a cube.
+    CubeDimX,
+    /// Units along the y axis of a cube.
+    CubeDimY,
+    /// Units along the z axis of a cube.
+    CubeDimZ,
+}
+
+impl core::fmt::Display for KernelResource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KernelResource::SharedMemory => f.write_str("bytes of shared memory"),
+            KernelResource::UnitsPerCube => f.write_str("units per cube"),
+            KernelResource::CubeDimX => f.write_str("units along the x axis of a cube"),
+            KernelResource::CubeDimY => f.write_str("units along the y axis of a cube"),
+            KernelResource::CubeDimZ => f.write_str("units along the z axis of a cube"),
+        }
+    }
+}
+
+/// The step of turning a kernel into a program of the device that failed.
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompilationStage {
+    /// Generating the source of the kernel in the language of the backend.
+    Codegen,
+    /// Compiling the source with the compiler of the backend, e.g. NVRTC or naga.
+    Compile,
+    /// Loading the compiled program on the device, e.g. as a module or a pipeline.
+    Load,
+}
+
+impl core::fmt::Display for CompilationStage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CompilationStage::Codegen => f.write_str("code generation"),
+            CompilationStage::Compile => f.write_str("compilation"),
+            CompilationStage::Load => f.write_str("loading"),
+        }
+    }
+}
+
+/// A kernel rejected by the backend, e.g. because it uses a feature the device doesn't support.
+///
+/// The server stays usable after the error, only the rejected kernel can't be executed.
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("the {stage} of kernel {kernel_name} failed:\n{backend_log}")]
+pub struct CompilationError {
+    /// The log of the backend explaining why the kernel was rejected.
+    pub backend_log: String,
+    /// The name of the kernel.
+    pub kernel_name: String,
+    /// The step that failed.
+    pub stage: CompilationStage,
+}
+
+impl CompilationError {
+    /// Creates an error for the kernel named `kernel_name` that failed at the `stage`.
+    pub fn new(
+        kernel_name: impl Into<String>,
+        stage: CompilationStage,
+        backend_log: impl Into<String>,
+    ) -> Self {
+        Self {
+            backend_log: backend_log.into(),
+            kernel_name: kernel_name.into(),
+            stage,
+        }
+    }
+}
+
+impl IoError {
+    /// If the error is caused by the device running out of memory, in which case freeing memory
+    /// and retrying can succeed.
+    pub fn is_out_of_memory(&self) -> bool {
+        matches!(self, Self::Memory(MemoryError::OutOfMemory { .. }))
+    }
+}
+
 impl Handle {
     /// Add to the current offset in bytes.
     pub fn offset_start(mut self, offset: u64) -> Self {
@@ -256,10 +868,44 @@ impl Handle {
         self
     }
 
+    /// A window of `len_bytes` bytes starting `start_bytes` bytes into the handle, which can be
+    /// bound to a kernel like any other handle.
+    ///
+    /// The window shares the memory of the handle, so it is accounted to it and keeps it alive.
+    /// When bound to a kernel, the start of the window must be a multiple of the
+    /// [alignment](crate::memory_management::MemoryDeviceProperties::alignment) of the device.
+    ///
+    /// # Panics
+    ///
+    /// If the window is out of the bounds of the handle.
+    pub fn offset(&self, start_bytes: u64, len_bytes: u64) -> Self {
+        let size = self.size();
+        assert!(
+            start_bytes + len_bytes <= size,
+            "The window [{start_bytes}..{}] is out of the bounds of the handle of {size} bytes",
+            start_bytes + len_bytes
+        );
+
+        self.clone()
+            .offset_start(start_bytes)
+            .offset_end(size - start_bytes - len_bytes)
+    }
+
     /// Get the size of the handle, in bytes, accounting for offsets
     pub fn size(&self) -> u64 {
         self.size - self.offset_start.unwrap_or(0) - self.offset_end.unwrap_or(0)
     }
+
+    /// The server that allocated the handle, if known.
+    pub fn owner(&self) -> Option<ServerId> {
+        self.owner
+    }
+
+    /// Tag the handle with the server that allocated it.
+    pub(crate) fn with_owner(mut self, owner: ServerId) -> Self {
+        self.owner = Some(owner);
+        self
+    }
 }
 
 /// Bindings to execute a kernel.
@@ -359,6 +1005,9 @@ pub struct Binding {
     pub offset_end: Option<u64>,
     /// Size in bytes
     size: u64,
+    /// The server that allocated the memory.
+    #[new(default)]
+    owner: Option<ServerId>,
 }
 
 impl Binding {
@@ -366,6 +1015,11 @@ impl Binding {
     pub fn size(&self) -> u64 {
         self.size - self.offset_start.unwrap_or(0) - self.offset_end.unwrap_or(0)
     }
+
+    /// The server that allocated the memory, if known.
+    pub fn owner(&self) -> Option<ServerId> {
+        self.owner
+    }
 }
 
 /// A binding with shape and stride info for non-contiguous reading
@@ -431,6 +1085,7 @@ impl Handle {
             offset_start: self.offset_start,
             offset_end: self.offset_end,
             size: self.size,
+            owner: self.owner,
         }
     }
 
@@ -457,6 +1112,7 @@ impl Clone for Handle {
             offset_start: self.offset_start,
             offset_end: self.offset_end,
             size: self.size,
+            owner: self.owner,
         }
     }
 }
@@ -468,6 +1124,7 @@ impl Clone for Binding {
             offset_start: self.offset_start,
             offset_end: self.offset_end,
             size: self.size,
+            owner: self.owner,
         }
     }
 }
@@ -480,6 +1137,11 @@ pub enum CubeCount {
     /// Dispatch a known count of x, y, z cubes.
     Static(u32, u32, u32),
     /// Dispatch an amount based on the values in this buffer. The buffer should contain a u32 array [x, y, z].
+    ///
+    /// The count can be written by a previous kernel without syncing with the host. Runtimes
+    /// without indirect dispatch (CUDA, HIP) emulate it by reading the buffer back before the
+    /// launch. Since the count isn't known when launching, kernels are responsible for checking
+    /// their bounds.
     Dynamic(Binding),
 }
 
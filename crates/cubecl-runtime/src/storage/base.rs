@@ -90,6 +90,20 @@ pub trait ComputeStorage: Send {
 
     /// Flush deallocations when required.
     fn flush(&mut self) {}
+
+    /// Whether the storage can copy memory between its allocations with
+    /// [copy](ComputeStorage::copy).
+    fn supports_copy(&self) -> bool {
+        false
+    }
+
+    /// Copies the memory pointed by `src` into `dst`, ordered after all previously submitted work.
+    ///
+    /// Only called when [supports_copy](ComputeStorage::supports_copy) returns `true`.
+    #[allow(unused_variables)]
+    fn copy(&mut self, src: &StorageHandle, dst: &StorageHandle) {
+        unimplemented!("Memory copies not supported on the current storage.")
+    }
 }
 
 /// Access to the underlying resource for a given binding.
@@ -103,6 +103,23 @@ impl ComputeStorage for BytesStorage {
             }
         }
     }
+
+    fn supports_copy(&self) -> bool {
+        true
+    }
+
+    fn copy(&mut self, src: &StorageHandle, dst: &StorageHandle) {
+        let src = self.get(src);
+        let dst = self.get(dst);
+        let (src_ptr, src_len) = src.get_exact_location_and_length();
+        let (dst_ptr, dst_len) = dst.get_exact_location_and_length();
+        assert!(src_len <= dst_len, "Copy destination is too small");
+
+        // `copy` handles overlapping ranges of the same allocation.
+        unsafe {
+            core::ptr::copy(src_ptr, dst_ptr, src_len);
+        }
+    }
 }
 
 #[cfg(test)]
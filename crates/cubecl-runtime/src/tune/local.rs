@@ -1,9 +1,15 @@
-use super::{AutotuneKey, AutotuneOutput, TunableSet, Tuner};
+#[cfg(std_io)]
+use super::tuner::autotune_checksum;
+use super::{
+    AutotuneDisqualification, AutotuneError, AutotuneKey, AutotuneOutput, AutotuneOverride,
+    CandidateInfo, TunableSet, Tuner,
+};
 use crate::{
     channel::ComputeChannel, client::ComputeClient, server::ComputeServer, tune::TuneCacheResult,
 };
 use alloc::boxed::Box;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use core::{
     any::{Any, TypeId},
     fmt::Display,
@@ -113,7 +119,142 @@ where
         super::check_autotune_outputs(checks_outputs);
     }
 
+    /// The candidates of the [tunable set](TunableSet) that were disqualified when tuning the
+    /// provided inputs, because they failed to compile or launch on the device.
+    pub fn disqualified<In, Out>(
+        &self,
+        id: &ID,
+        operations: &TunableSet<AK, In, Out>,
+        inputs: &In,
+    ) -> Vec<AutotuneDisqualification>
+    where
+        In: Clone + Send + 'static,
+        Out: AutotuneOutput,
+    {
+        let key = operations.generate_key(inputs);
+        let state = self.state.lock();
+
+        state
+            .as_ref()
+            .and_then(|map| map.get(id))
+            .map(|tuner| tuner.disqualified(&key).to_vec())
+            .unwrap_or_default()
+    }
+
+    /// The candidate of the [tunable set](TunableSet) executed for the autotune key, along with
+    /// the benchmark results that led to its selection.
+    ///
+    /// Returns `None` when the key isn't tuned yet, or when every candidate is disqualified.
+    pub fn resolve<S, C, In, Out>(
+        &self,
+        id: &ID,
+        #[cfg_attr(not(std_io), allow(unused_variables))] client: &ComputeClient<S, C>,
+        operations: &TunableSet<AK, In, Out>,
+        key: &AK,
+    ) -> Option<CandidateInfo>
+    where
+        S: ComputeServer + 'static,
+        C: ComputeChannel<S> + 'static,
+        In: Clone + Send + 'static,
+        Out: AutotuneOutput,
+    {
+        let mut state = self.state.lock();
+        let tuner = self.tuner(state.get_or_insert_with(Default::default), id);
+        tuner.handle_results();
+
+        let (index, forced) = match tuner.forced(key) {
+            Some(index) => (index, true),
+            None => match tuner.fastest(key) {
+                TuneCacheResult::Hit { fastest_index } => (fastest_index, false),
+                #[cfg(std_io)]
+                TuneCacheResult::Unchecked => {
+                    let checksum = autotune_checksum(operations, client);
+                    tuner.validate_checksum(key, &checksum);
+
+                    match tuner.fastest(key) {
+                        TuneCacheResult::Hit { fastest_index } => (fastest_index, false),
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            },
+        };
+
+        if index >= operations.len() {
+            return None;
+        }
+
+        Some(CandidateInfo {
+            index,
+            name: operations.fastest(index).name().to_string(),
+            forced,
+            measured_times: tuner.measured(key).to_vec(),
+        })
+    }
+
+    /// Force the candidate at the given index of the [tunable set](TunableSet) to be executed for
+    /// the autotune key, over the tuned one.
+    ///
+    /// Returns an [error](AutotuneError::InvalidOverride) if the set has no candidate at the index.
+    pub fn force<In, Out>(
+        &self,
+        id: &ID,
+        operations: &TunableSet<AK, In, Out>,
+        key: AK,
+        index: usize,
+    ) -> Result<(), AutotuneError>
+    where
+        In: Clone + Send + 'static,
+        Out: AutotuneOutput,
+    {
+        let num_candidates = operations.len();
+        if index >= num_candidates {
+            return Err(AutotuneError::InvalidOverride {
+                index,
+                num_candidates,
+            });
+        }
+
+        let mut state = self.state.lock();
+        let tuner = self.tuner(state.get_or_insert_with(Default::default), id);
+        tuner.force(key, index);
+
+        Ok(())
+    }
+
+    /// The autotune keys in the cache, including the ones loaded from the persistent cache.
+    pub fn keys(&self, id: &ID) -> Vec<AK> {
+        let mut state = self.state.lock();
+        let tuner = self.tuner(state.get_or_insert_with(Default::default), id);
+        tuner.handle_results();
+
+        tuner.keys().cloned().collect()
+    }
+
+    /// The forced candidates, in the format of the
+    /// [overrides file](crate::config::autotune::AutotuneConfig::overrides).
+    pub fn overrides(&self, id: &ID) -> Vec<AutotuneOverride<AK>> {
+        let mut state = self.state.lock();
+        let tuner = self.tuner(state.get_or_insert_with(Default::default), id);
+
+        tuner.overrides()
+    }
+
+    /// The tuner of the id, created with the persistent cache on first use.
+    fn tuner<'a>(&self, map: &'a mut HashMap<ID, Tuner<AK>>, id: &ID) -> &'a mut Tuner<AK> {
+        if !map.contains_key(id) {
+            let name = self.name.replace("::", "-");
+            map.insert(id.clone(), Tuner::new(&name, &id.to_string()));
+        }
+
+        map.get_mut(id).unwrap()
+    }
+
     /// Execute the best operation in the provided [tunable set](TunableSet)
+    ///
+    /// # Panics
+    ///
+    /// If every operation fails, see [try_execute](Self::try_execute) to recover from it.
     pub fn execute<S, C, In, Out>(
         &self,
         id: &ID,
@@ -121,6 +262,30 @@ where
         operations: Arc<TunableSet<AK, In, Out>>,
         inputs: In,
     ) -> Out
+    where
+        S: ComputeServer + 'static,
+        C: ComputeChannel<S> + 'static,
+        In: Clone + Send + 'static,
+        Out: AutotuneOutput,
+    {
+        match self.try_execute(id, client, operations, inputs) {
+            Ok(out) => out,
+            Err(err) => panic!("Should run when selected by autotune: {err}"),
+        }
+    }
+
+    /// Execute the best operation in the provided [tunable set](TunableSet), skipping the
+    /// operations that fail to compile or launch on the device.
+    ///
+    /// Returns an [error](AutotuneError::AllCandidatesFailed) listing each failure if every
+    /// operation fails.
+    pub fn try_execute<S, C, In, Out>(
+        &self,
+        id: &ID,
+        client: &ComputeClient<S, C>,
+        operations: Arc<TunableSet<AK, In, Out>>,
+        inputs: In,
+    ) -> Result<Out, AutotuneError>
     where
         S: ComputeServer + 'static,
         C: ComputeChannel<S> + 'static,
@@ -132,15 +297,22 @@ where
         // If this is cached and ready, use the operation.
         let autotune_job = {
             let mut state = self.state.lock();
-            let map = state.get_or_insert_with(Default::default);
+            let tuner = self.tuner(state.get_or_insert_with(Default::default), id);
+
+            // A forced candidate wins over the tuned one.
+            if let Some(index) = tuner.forced(&key) {
+                core::mem::drop(state);
+
+                let num_candidates = operations.len();
+                if index >= num_candidates {
+                    return Err(AutotuneError::InvalidOverride {
+                        index,
+                        num_candidates,
+                    });
+                }
 
-            let tuner = match map.get_mut(id) {
-                Some(val) => val,
-                None => map.entry(id.clone()).or_insert_with(move || {
-                    let name = self.name.replace("::", "-");
-                    Tuner::new(&name, &id.to_string())
-                }),
-            };
+                return operations.fastest(index).execute(inputs);
+            }
 
             match tuner.fastest(&key) {
                 TuneCacheResult::Hit { fastest_index } => {
@@ -150,35 +322,39 @@ where
                     self.checks(&operations, &inputs);
 
                     let op = operations.fastest(fastest_index);
-                    let result = op
-                        .execute(inputs)
-                        .expect("Should run when selected by autotune.");
-                    return result;
+                    return op.execute(inputs);
                 }
                 TuneCacheResult::Pending => {
                     core::mem::drop(state);
 
                     let op = operations.fastest(0);
-                    let result = op
-                        .execute(inputs)
-                        .expect("Should run when selected by autotune.");
-                    return result;
+                    return op.execute(inputs);
+                }
+                TuneCacheResult::Failed => {
+                    return Err(AutotuneError::AllCandidatesFailed(
+                        tuner.disqualified(&key).to_vec(),
+                    ));
                 }
                 #[cfg(std_io)]
                 TuneCacheResult::Unchecked => {
                     // If the cache checksum hasn't been checked, do so now, and retry.
-                    let checksum = operations.compute_checksum();
+                    let checksum = autotune_checksum(&operations, client);
                     tuner.validate_checksum(&key, &checksum);
 
                     // Check if with validation we can use its result
-                    if let TuneCacheResult::Hit { fastest_index } = tuner.fastest(&key) {
-                        core::mem::drop(state);
-
-                        let op = operations.fastest(fastest_index);
-                        let result = op
-                            .execute(inputs)
-                            .expect("Should run when selected by autotune.");
-                        return result;
+                    match tuner.fastest(&key) {
+                        TuneCacheResult::Hit { fastest_index } => {
+                            core::mem::drop(state);
+
+                            let op = operations.fastest(fastest_index);
+                            return op.execute(inputs);
+                        }
+                        TuneCacheResult::Failed => {
+                            return Err(AutotuneError::AllCandidatesFailed(
+                                tuner.disqualified(&key).to_vec(),
+                            ));
+                        }
+                        _ => {}
                     }
                 }
 
@@ -220,15 +396,17 @@ where
                     // This should only happen on wasm since we can't block waiting on the results there.
                     0
                 }
+                TuneCacheResult::Failed => {
+                    return Err(AutotuneError::AllCandidatesFailed(
+                        tuner.disqualified(&key).to_vec(),
+                    ));
+                }
                 TuneCacheResult::Unchecked => {
                     panic!("Should have checked the cache.")
                 }
             }
         };
 
-        operations
-            .fastest(index_to_run)
-            .execute(inputs)
-            .expect("Should run when selected by autotune.")
+        operations.fastest(index_to_run).execute(inputs)
     }
 }
@@ -4,6 +4,7 @@ use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::fmt::{Debug, Display};
 use core::hash::Hash;
+use cubecl_common::benchmark::BenchmarkConfig;
 
 use super::{
     AutotuneError,
@@ -31,6 +32,7 @@ pub struct TunableSet<K: AutotuneKey, Inputs: Send + 'static, Output: 'static> {
     input_gen: Arc<dyn InputGenerator<K, Inputs>>,
     #[allow(clippy::type_complexity)]
     checksum_override: Option<Arc<dyn Fn(&Self) -> String + Send + Sync>>,
+    benchmark: BenchmarkConfig,
 }
 
 unsafe impl<K: AutotuneKey, In: Send, Out> Send for TunableSet<K, In, Out> {}
@@ -57,6 +59,10 @@ impl<K: AutotuneKey, Inputs: Clone + Send + 'static, Output: 'static>
             input_gen: Arc::new(input_gen.into_input_gen()),
             key_gen: Arc::new(key_gen.into_key_gen()),
             checksum_override: None,
+            benchmark: BenchmarkConfig {
+                warmup_iters: 1,
+                ..BenchmarkConfig::fixed(10)
+            },
         }
     }
 
@@ -75,6 +81,20 @@ impl<K: AutotuneKey, Inputs: Clone + Send + 'static, Output: 'static>
         self
     }
 
+    /// Override how candidates are benchmarked, e.g. taking more samples to get more stable
+    /// results at the cost of a longer tuning.
+    ///
+    /// By default, 10 samples are taken after a single warmup execution.
+    pub fn with_benchmark_config(mut self, config: BenchmarkConfig) -> Self {
+        self.benchmark = config;
+        self
+    }
+
+    /// How candidates are benchmarked.
+    pub fn benchmark_config(&self) -> &BenchmarkConfig {
+        &self.benchmark
+    }
+
     /// All candidate operations for autotuning this operation type
     /// Operations can run on toy tensors of relevant size
     pub fn autotunables(&self) -> Vec<Arc<dyn TuneFn<Inputs = Inputs, Output = Output>>> {
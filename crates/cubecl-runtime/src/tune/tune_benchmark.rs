@@ -1,13 +1,14 @@
 use alloc::format;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
-use cubecl_common::profile::{ProfileDuration, TimingMethod};
+use cubecl_common::benchmark::BenchmarkConfig;
+use cubecl_common::profile::{Instant, ProfileDuration, TimingMethod};
 
 use crate::channel::ComputeChannel;
 use crate::client::ComputeClient;
 use crate::server::ComputeServer;
 
-use super::{AutotuneError, TuneFn};
+use super::{AutotuneError, TuneFn, util::autotune_device_timing};
 
 /// A benchmark that runs on server handles
 #[derive(new)]
@@ -15,6 +16,7 @@ pub struct TuneBenchmark<S: ComputeServer, C, In: Clone + Send + 'static, Out: S
     operation: Arc<dyn TuneFn<Inputs = In, Output = Out>>,
     inputs: In,
     client: ComputeClient<S, C>,
+    config: BenchmarkConfig,
 }
 
 /// The trait to be implemented by an autotune output.
@@ -39,7 +41,11 @@ impl<
     Out: AutotuneOutput,
 > TuneBenchmark<S, C, In, Out>
 {
-    /// Benchmark how long this operation takes for a number of samples.
+    /// Benchmark how long this operation takes for the number of samples of the
+    /// [config](BenchmarkConfig).
+    ///
+    /// Since durations are resolved lazily, the [target duration](BenchmarkConfig::target_duration)
+    /// is compared with the time spent on the host while sampling.
     ///
     /// Returns at least one duration, otherwise an error is returned.
     pub fn profile(self) -> Result<Vec<ProfileDuration>, AutotuneError> {
@@ -49,36 +55,42 @@ impl<
 
         // For now we wrap the warmup operation inside a profiling task, since we have basic error
         // handling for system timing methods.
-        match self.client.properties().timing_method {
-            TimingMethod::System => self.warmup_full_error_handling(),
-            TimingMethod::Device => self.warmup_minimal_error_handling(),
-        }?;
+        for _ in 0..usize::max(self.config.warmup_iters, 1) {
+            match self.client.properties().timing_method {
+                TimingMethod::System => self.warmup_full_error_handling(),
+                TimingMethod::Device => self.warmup_minimal_error_handling(),
+            }?;
+        }
 
         let operation = self.operation;
-        let num_samples = 10;
-        let durations: Vec<_> = (0..num_samples)
-            .filter_map(|_| {
-                let result: Result<ProfileDuration, crate::server::ProfileError> =
-                    self.client.profile(
-                        || {
-                            // It is important to return the output since otherwise deadcode elimination
-                            // might optimize away code that needs to be profiled.
-                            operation
-                                .execute(self.inputs.clone())
-                                .expect("Should not fail when previously tried during the warmup.")
-                        },
-                        operation.name(),
-                    );
-
-                match result {
-                    Ok(val) => Some(val),
-                    Err(err) => {
-                        log::warn!("Error while autotuning {err:?}");
-                        None
-                    }
-                }
-            })
-            .collect();
+        let device_timing = autotune_device_timing();
+        let mut durations = Vec::with_capacity(self.config.min_samples);
+        let mut num_attempts = 0;
+        let start = Instant::now();
+
+        while self.config.needs_sample(num_attempts, start.elapsed()) {
+            num_attempts += 1;
+
+            let func = || {
+                // It is important to return the output since otherwise deadcode elimination
+                // might optimize away code that needs to be profiled.
+                operation
+                    .execute(self.inputs.clone())
+                    .expect("Should not fail when previously tried during the warmup.")
+            };
+            let result: Result<ProfileDuration, crate::server::ProfileError> = match device_timing {
+                true => self.client.profile_device(func, operation.name()),
+                false => self.client.profile(func, operation.name()),
+            };
+
+            match result {
+                Ok(val) => durations.push(val),
+                Err(err) => log::warn!("Error while autotuning {err:?}"),
+            }
+        }
+
+        // Device timings are resolved once submitted.
+        self.client.flush();
 
         if durations.is_empty() {
             Err(AutotuneError::InvalidSamples)
@@ -92,7 +104,7 @@ impl<
 
         let result = self.client.profile(
             || {
-                if let Err(err) = self.operation.execute(self.inputs.clone()) {
+                if let Err(err) = self.execute_caught() {
                     error = Some(err);
                 }
             },
@@ -110,7 +122,38 @@ impl<
         Ok(())
     }
     fn warmup_minimal_error_handling(&self) -> Result<(), AutotuneError> {
-        self.operation.execute(self.inputs.clone())?;
+        self.execute_caught()?;
         Ok(())
     }
+
+    /// Execute the operation, turning a panic into an error, since kernels launched with the
+    /// infallible API panic when the backend rejects them. The candidate is then disqualified
+    /// instead of aborting the whole autotune. Candidates returning the errors of their launches
+    /// are disqualified with the [compilation error](AutotuneError::Compilation) itself.
+    fn execute_caught(&self) -> Result<Out, AutotuneError> {
+        #[cfg(feature = "std")]
+        {
+            let execute = || self.operation.execute(self.inputs.clone());
+
+            std::panic::catch_unwind(core::panic::AssertUnwindSafe(execute)).unwrap_or_else(
+                |payload| {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "Unknown panic".to_string());
+
+                    Err(AutotuneError::Unknown(format!(
+                        "Panicked while executing {}: {message}",
+                        self.operation.name()
+                    )))
+                },
+            )
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            self.operation.execute(self.inputs.clone())
+        }
+    }
 }
@@ -1,16 +1,15 @@
 #[cfg(std_io)]
 use super::AutotuneError;
 #[cfg(std_io)]
-use super::AutotuneOutcome;
-#[cfg(std_io)]
 use cubecl_common::cache::Cache;
 #[cfg(std_io)]
 use cubecl_common::cache::CacheError;
 #[cfg(std_io)]
 use serde::{Deserialize, Serialize};
 
-use super::AutotuneKey;
+use super::{AutotuneDisqualification, AutotuneKey, AutotuneOutcome};
 use alloc::string::String;
+use alloc::vec::Vec;
 use hashbrown::HashMap;
 
 /// In-memory cache entry
@@ -18,7 +17,11 @@ use hashbrown::HashMap;
 pub(crate) enum CacheEntry {
     Done {
         checksum: ChecksumState,
-        fastest_index: usize,
+        /// `None` when every candidate is disqualified.
+        fastest_index: Option<usize>,
+        /// The candidates that were benchmarked, fastest first.
+        measured: Vec<AutotuneOutcome>,
+        disqualified: Vec<AutotuneDisqualification>,
     },
     Pending,
 }
@@ -43,8 +46,10 @@ pub(crate) struct PersistentCacheKey<K> {
 #[cfg(std_io)]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub(crate) struct PersistentCacheValue {
-    fastest_index: usize,
+    fastest_index: Option<usize>,
     results: Vec<Result<AutotuneOutcome, AutotuneError>>,
+    #[serde(default)]
+    disqualified: Vec<AutotuneDisqualification>,
 }
 
 /// Use to find and reuse the best kernel for some input
@@ -67,6 +72,8 @@ pub enum TuneCacheResult {
     Unchecked,
     /// We don't know yet what is fastest, but are waiting for a result to come in.
     Pending,
+    /// Every operation is disqualified, see [disqualified](crate::tune::Tuner::disqualified).
+    Failed,
     /// No operation is found yet.
     Miss,
 }
@@ -110,21 +117,25 @@ impl<K: AutotuneKey> TuneCache<K> {
             CacheEntry::Done {
                 checksum,
                 fastest_index,
+                ..
             } => {
+                let done = match fastest_index {
+                    Some(fastest_index) => TuneCacheResult::Hit {
+                        fastest_index: *fastest_index,
+                    },
+                    None => TuneCacheResult::Failed,
+                };
+
                 if cfg!(std_io) {
                     match checksum {
                         ChecksumState::ToBeVerified(..) => TuneCacheResult::Unchecked, // Don't know yet.
                         ChecksumState::NoMatch => TuneCacheResult::Miss, // Can't use this.
-                        ChecksumState::Match => TuneCacheResult::Hit {
-                            fastest_index: *fastest_index,
-                        },
+                        ChecksumState::Match => done,
                     }
                 } else {
                     // Clippy;
                     let _ = checksum;
-                    TuneCacheResult::Hit {
-                        fastest_index: *fastest_index,
-                    }
+                    done
                 }
             }
             CacheEntry::Pending => TuneCacheResult::Pending,
@@ -152,17 +163,45 @@ impl<K: AutotuneKey> TuneCache<K> {
         }
     }
 
+    pub fn disqualified(&self, key: &K) -> &[AutotuneDisqualification] {
+        match self.in_memory_cache.get(key) {
+            Some(CacheEntry::Done { disqualified, .. }) => disqualified,
+            _ => &[],
+        }
+    }
+
+    /// The benchmark results of the candidates when tuning the key, fastest first.
+    pub fn measured(&self, key: &K) -> &[AutotuneOutcome] {
+        match self.in_memory_cache.get(key) {
+            Some(CacheEntry::Done { measured, .. }) => measured,
+            _ => &[],
+        }
+    }
+
+    /// The keys that are tuned, or pending.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.in_memory_cache.keys()
+    }
+
     #[allow(unused)]
     pub(crate) fn mark_pending(&mut self, key: K) {
         self.in_memory_cache.insert(key, CacheEntry::Pending);
     }
 
-    pub(crate) fn cache_insert(&mut self, key: K, fastest_index: usize) {
+    pub(crate) fn cache_insert(
+        &mut self,
+        key: K,
+        fastest_index: Option<usize>,
+        measured: Vec<AutotuneOutcome>,
+        disqualified: Vec<AutotuneDisqualification>,
+    ) {
         self.in_memory_cache.insert(
             key,
             CacheEntry::Done {
                 checksum: ChecksumState::Match,
                 fastest_index,
+                measured,
+                disqualified,
             },
         );
     }
@@ -174,14 +213,16 @@ impl<K: AutotuneKey> TuneCache<K> {
         &mut self,
         key: K,
         checksum: String,
-        fastest_index: usize,
+        fastest_index: Option<usize>,
         results: Vec<Result<AutotuneOutcome, AutotuneError>>,
+        disqualified: Vec<AutotuneDisqualification>,
     ) {
         if let Err(err) = self.persistent_cache.insert(
             PersistentCacheKey { key, checksum },
             PersistentCacheValue {
                 fastest_index,
                 results,
+                disqualified,
             },
         ) {
             match err {
@@ -213,6 +254,12 @@ impl<K: AutotuneKey> TuneCache<K> {
                 CacheEntry::Done {
                     checksum: ChecksumState::ToBeVerified(key.checksum.clone()),
                     fastest_index: value.fastest_index,
+                    measured: value
+                        .results
+                        .iter()
+                        .filter_map(|result| result.as_ref().ok().cloned())
+                        .collect(),
+                    disqualified: value.disqualified.clone(),
                 },
             );
         });
@@ -4,17 +4,20 @@ use alloc::sync::Arc;
 use alloc::vec::Vec;
 use async_channel::{Receiver, Sender};
 use cubecl_common::profile::ProfileDuration;
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
 
 use core::time::Duration;
 
 use alloc::string::{String, ToString};
-use cubecl_common::benchmark::{BenchmarkComputations, BenchmarkDurations};
+use cubecl_common::benchmark::{
+    BenchmarkComputations, BenchmarkConfig, BenchmarkDurations, OutlierPolicy,
+};
 
 use crate::channel::ComputeChannel;
 use crate::client::ComputeClient;
 use crate::config::{Logger, autotune::AutotuneLogLevel};
-use crate::server::ComputeServer;
+use crate::memory_management::MemoryError;
+use crate::server::{CompilationError, ComputeServer, IoError};
 use crate::tune::{TuneBenchmark, TuneCache};
 
 use super::{AutotuneKey, AutotuneOutput, TunableSet, TuneCacheResult, TuneFn, TunePlan};
@@ -26,15 +29,54 @@ pub struct Tuner<K: AutotuneKey> {
     logger: Logger,
     channel: (Sender<AutotuneMessage<K>>, Receiver<AutotuneMessage<K>>),
     pub(crate) autotuning: HashSet<K>,
+    /// The candidates forced for some keys, over the tuned ones.
+    overrides: HashMap<K, usize>,
+    /// The name of the tuner, see [AutotuneOverride::tuner].
+    name: String,
 }
 
 /// The measured outcome for a given autotune invocation.
 #[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize, PartialEq, Eq))]
 #[derive(new, Debug, Clone)]
 pub struct AutotuneOutcome {
-    name: String,
-    index: usize,
-    computation: BenchmarkComputations,
+    /// The name of the candidate.
+    pub name: String,
+    /// The index of the candidate in its [tunable set](TunableSet).
+    pub index: usize,
+    /// The statistics of the durations measured when benchmarking the candidate.
+    pub computation: BenchmarkComputations,
+}
+
+/// The candidate executed for an autotune key, see [resolve](crate::tune::LocalTuner::resolve).
+#[derive(Debug, Clone)]
+pub struct CandidateInfo {
+    /// The index of the candidate in its [tunable set](TunableSet).
+    pub index: usize,
+    /// The name of the candidate.
+    pub name: String,
+    /// Whether the candidate is [forced](crate::tune::LocalTuner::force) instead of tuned.
+    pub forced: bool,
+    /// The outcomes of the candidates benchmarked when tuning the key, fastest first.
+    ///
+    /// Empty when nothing was benchmarked, e.g. when the set has a single candidate.
+    pub measured_times: Vec<AutotuneOutcome>,
+}
+
+/// A candidate forced for an autotune key, as written in the
+/// [overrides file](crate::config::autotune::AutotuneConfig::overrides).
+///
+/// The file is a JSON list of overrides, each applied to the tuner with the same name when it is
+/// created.
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutotuneOverride<K> {
+    /// The name of the tuner, the same as the directory of its persistent cache, i.e.
+    /// `{device}/{tuner}`.
+    pub tuner: String,
+    /// The autotune key.
+    pub key: K,
+    /// The index of the candidate in the [tunable set](TunableSet) of the tuner.
+    pub index: usize,
 }
 
 impl core::fmt::Display for AutotuneOutcome {
@@ -47,11 +89,36 @@ impl core::fmt::Display for AutotuneOutcome {
     }
 }
 
+/// A candidate disqualified while autotuning, because it failed to compile or launch on the
+/// device.
+#[cfg_attr(std_io, derive(serde::Serialize, serde::Deserialize))]
+#[derive(new, Debug, Clone, PartialEq, Eq)]
+pub struct AutotuneDisqualification {
+    /// The name of the candidate.
+    pub name: String,
+    /// The index of the candidate in its [tunable set](TunableSet).
+    pub index: usize,
+    /// Why the candidate was disqualified.
+    pub error: AutotuneError,
+}
+
+impl core::fmt::Display for AutotuneDisqualification {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Autotune[{}] name {} => {}",
+            self.index, self.name, self.error
+        )
+    }
+}
+
 enum AutotuneMessage<K> {
     Done {
         key: K,
-        fastest_index: usize,
+        /// `None` when every candidate is disqualified.
+        fastest_index: Option<usize>,
         results: Vec<Result<AutotuneOutcome, AutotuneError>>,
+        disqualified: Vec<AutotuneDisqualification>,
         #[cfg(std_io)]
         checksum: String,
     },
@@ -69,6 +136,45 @@ pub enum AutotuneError {
     InvalidSamples,
     /// The autotune is skipped manually.
     Skip,
+    /// The device ran out of memory, e.g. when allocating a workspace.
+    Memory(MemoryError),
+    /// The backend rejected a kernel of the candidate, which is disqualified for the device.
+    Compilation(CompilationError),
+    /// Every candidate failed to compile or launch on the device.
+    AllCandidatesFailed(Vec<AutotuneDisqualification>),
+    /// The candidate forced for a key isn't in the tunable set.
+    InvalidOverride {
+        /// The index of the forced candidate.
+        index: usize,
+        /// The number of candidates in the tunable set.
+        num_candidates: usize,
+    },
+}
+
+impl core::fmt::Display for AutotuneError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AutotuneError::Unknown(err) => write!(f, "{err}"),
+            AutotuneError::InvalidSamples => write!(f, "All samples are invalid"),
+            AutotuneError::Skip => write!(f, "Skipped"),
+            AutotuneError::Memory(err) => write!(f, "{err}"),
+            AutotuneError::Compilation(err) => write!(f, "{err}"),
+            AutotuneError::AllCandidatesFailed(disqualified) => {
+                write!(f, "Every autotune candidate failed:")?;
+                for candidate in disqualified {
+                    write!(f, "\n - {candidate}")?;
+                }
+                Ok(())
+            }
+            AutotuneError::InvalidOverride {
+                index,
+                num_candidates,
+            } => write!(
+                f,
+                "The forced candidate {index} is out of range, the set has {num_candidates} candidates"
+            ),
+        }
+    }
 }
 
 impl From<String> for AutotuneError {
@@ -77,17 +183,33 @@ impl From<String> for AutotuneError {
     }
 }
 
+impl From<IoError> for AutotuneError {
+    fn from(value: IoError) -> Self {
+        match value {
+            IoError::Memory(err) => Self::Memory(err),
+            IoError::Compilation(err) => Self::Compilation(err),
+            err => Self::Unknown(format!("{err:?}")),
+        }
+    }
+}
+
 #[allow(clippy::new_without_default)]
 impl<K: AutotuneKey> Tuner<K> {
     /// Returns a tuner with cache initialized from persistent cache
     pub fn new(name: &str, device_id: &str) -> Self {
         let channel = async_channel::unbounded();
+        let tuner_name = format!("{device_id}/{name}");
 
         Self {
             tune_cache: TuneCache::new(name, device_id),
             logger: Logger::new(),
             channel,
             autotuning: HashSet::new(),
+            #[cfg(std_io)]
+            overrides: load_overrides(&tuner_name),
+            #[cfg(not(std_io))]
+            overrides: HashMap::new(),
+            name: tuner_name,
         }
     }
 
@@ -96,6 +218,45 @@ impl<K: AutotuneKey> Tuner<K> {
         self.tune_cache.fastest(key)
     }
 
+    /// The candidates disqualified when tuning the autotune key.
+    pub fn disqualified(&self, key: &K) -> &[AutotuneDisqualification] {
+        self.tune_cache.disqualified(key)
+    }
+
+    /// The outcomes of the candidates benchmarked when tuning the autotune key, fastest first.
+    pub fn measured(&self, key: &K) -> &[AutotuneOutcome] {
+        self.tune_cache.measured(key)
+    }
+
+    /// The autotune keys in the cache, including the ones loaded from the persistent cache.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.tune_cache.keys()
+    }
+
+    /// Force the candidate at the given index to be executed for the autotune key, over the
+    /// tuned one.
+    pub fn force(&mut self, key: K, index: usize) {
+        self.overrides.insert(key, index);
+    }
+
+    /// The candidate forced for the autotune key, if any.
+    pub fn forced(&self, key: &K) -> Option<usize> {
+        self.overrides.get(key).copied()
+    }
+
+    /// The forced candidates, in the format of the
+    /// [overrides file](crate::config::autotune::AutotuneConfig::overrides).
+    pub fn overrides(&self) -> Vec<AutotuneOverride<K>> {
+        self.overrides
+            .iter()
+            .map(|(key, index)| AutotuneOverride {
+                tuner: self.name.clone(),
+                key: key.clone(),
+                index: *index,
+            })
+            .collect()
+    }
+
     /// Fetch the fastest autotune operation index for an autotune key and validate the checksum.
     #[cfg(std_io)]
     pub fn validate_checksum(&mut self, key: &K, checksum: &str) {
@@ -116,10 +277,20 @@ impl<K: AutotuneKey> Tuner<K> {
                 key,
                 fastest_index,
                 results,
+                disqualified,
                 #[cfg(std_io)]
                 checksum,
             } => {
+                let fastest = results.first().and_then(|result| result.as_ref().ok());
+
                 match self.logger.log_level_autotune() {
+                    AutotuneLogLevel::Disabled => {}
+                    _ if fastest.is_none() => {
+                        self.logger.log_autotune(&format!(
+                            "{}",
+                            AutotuneError::AllCandidatesFailed(disqualified.clone())
+                        ));
+                    }
                     AutotuneLogLevel::Minimal => {
                         let top_times = results
                             .iter()
@@ -135,26 +306,16 @@ impl<K: AutotuneKey> Tuner<K> {
                             .take(3)
                             .collect::<Vec<_>>();
 
-                        let result = results
-                            .first()
-                            .expect("At least one kernel needed.")
-                            .as_ref()
-                            .expect("At least one kernel has to succeed.");
-
                         self.logger.log_autotune(&format!(
                             "Fastest result {}-{key}. \n Top 3 times: {top_times:?}",
-                            result.name,
+                            fastest.unwrap().name,
                         ));
                     }
                     AutotuneLogLevel::Full => {
-                        let result = results
-                            .first()
-                            .expect("At least one kernel needed.")
-                            .as_ref()
-                            .expect("At least one kernel has to succeed.");
-
-                        self.logger
-                            .log_autotune(&format!("Fastest result {}-{key}.", result.name,));
+                        self.logger.log_autotune(&format!(
+                            "Fastest result {}-{key}.",
+                            fastest.unwrap().name
+                        ));
 
                         for result in results.iter() {
                             match result {
@@ -165,15 +326,28 @@ impl<K: AutotuneKey> Tuner<K> {
                             }
                         }
                     }
-                    AutotuneLogLevel::Disabled => {}
                 };
 
-                self.tune_cache.cache_insert(key.clone(), fastest_index);
+                let measured = results
+                    .iter()
+                    .filter_map(|result| result.as_ref().ok().cloned())
+                    .collect();
+                self.tune_cache.cache_insert(
+                    key.clone(),
+                    fastest_index,
+                    measured,
+                    disqualified.clone(),
+                );
 
                 #[cfg(std_io)]
                 {
-                    self.tune_cache
-                        .persistent_cache_insert(key, checksum, fastest_index, results);
+                    self.tune_cache.persistent_cache_insert(
+                        key,
+                        checksum,
+                        fastest_index,
+                        results,
+                        disqualified,
+                    );
                 }
             }
         }
@@ -216,10 +390,11 @@ impl<K: AutotuneKey> Tuner<K> {
         if autotunables.len() == 1 {
             let message = AutotuneMessage::Done {
                 key,
-                fastest_index: 0,
+                fastest_index: Some(0),
                 results,
+                disqualified: Vec::new(),
                 #[cfg(std_io)]
-                checksum: tunables.compute_checksum(),
+                checksum: autotune_checksum(tunables, client),
             };
 
             return Box::new(move || {
@@ -232,10 +407,11 @@ impl<K: AutotuneKey> Tuner<K> {
         let client = client.clone();
         let key_cloned = key.clone();
         let plan = tunables.plan(&key);
+        let config = tunables.benchmark_config().clone();
         let inputs_generator = tunables.inputs_generator(&key.clone(), inputs);
 
         #[cfg(std_io)]
-        let checksum = tunables.compute_checksum();
+        let checksum = autotune_checksum(tunables, &client);
 
         let fut_result = async move {
             let test_inputs = inputs_generator();
@@ -247,6 +423,7 @@ impl<K: AutotuneKey> Tuner<K> {
                 autotunables,
                 test_inputs,
                 results,
+                &config,
                 #[cfg(std_io)]
                 checksum,
             )
@@ -293,9 +470,33 @@ impl<K: AutotuneKey> Tuner<K> {
         autotunables: Vec<Arc<dyn TuneFn<Inputs = In, Output = Out> + 'static>>,
         test_inputs: In,
         mut results: Vec<Result<AutotuneOutcome, AutotuneError>>,
+        config: &BenchmarkConfig,
         #[cfg(std_io)] checksum: String,
     ) -> AutotuneMessage<K> {
-        Self::execute_tune_plan(client, &mut plan, autotunables, &test_inputs, &mut results).await;
+        let names: Vec<String> = autotunables
+            .iter()
+            .map(|op| op.name().to_string())
+            .collect();
+
+        Self::execute_tune_plan(
+            client,
+            &mut plan,
+            autotunables,
+            &test_inputs,
+            &mut results,
+            config,
+        )
+        .await;
+
+        let disqualified = results
+            .iter()
+            .zip(names)
+            .enumerate()
+            .filter_map(|(index, (result, name))| match result {
+                Err(AutotuneError::Skip) | Ok(_) => None,
+                Err(err) => Some(AutotuneDisqualification::new(name, index, err.clone())),
+            })
+            .collect();
 
         // Finds the fastest operation (by the median time).
         results.sort_by(|a, b| {
@@ -311,17 +512,17 @@ impl<K: AutotuneKey> Tuner<K> {
             a.cmp(&b)
         });
 
-        // Log & send results.
-        let result = results
+        // Every candidate might be disqualified, in which case no index is the fastest.
+        let fastest_index = results
             .first()
-            .expect("At least one kernel needed.")
-            .as_ref()
-            .expect("At least one kernel has to succeed.");
+            .and_then(|result| result.as_ref().ok())
+            .map(|result| result.index);
 
         AutotuneMessage::Done {
             key,
-            fastest_index: result.index,
+            fastest_index,
             results,
+            disqualified,
             #[cfg(std_io)]
             checksum,
         }
@@ -338,27 +539,44 @@ impl<K: AutotuneKey> Tuner<K> {
         autotunables: Vec<Arc<dyn TuneFn<Inputs = In, Output = Out> + 'static>>,
         test_inputs: &In,
         results: &mut [Result<AutotuneOutcome, AutotuneError>],
+        config: &BenchmarkConfig,
     ) {
+        let mut num_tried = 0;
+
         loop {
             let mut num_autotuned = 0;
 
             let tunable_indices = plan.next();
 
             if tunable_indices.is_empty() {
-                panic!("No autotune was flagged as valid for the problem.")
+                if num_tried == 0 {
+                    panic!("No autotune was flagged as valid for the problem.")
+                }
+
+                // Every candidate of the plan is disqualified.
+                break;
             }
 
+            num_tried += tunable_indices.len();
+
             for index in tunable_indices {
                 let op = &autotunables[index];
                 let name = op.name().to_string();
-                let tuner = TuneBenchmark::new(op.clone(), test_inputs.clone(), client.clone());
+                let tuner = TuneBenchmark::new(
+                    op.clone(),
+                    test_inputs.clone(),
+                    client.clone(),
+                    config.clone(),
+                );
                 let profiles = tuner.profile().map(|bench| (name, index, bench));
 
                 match profiles {
                     Ok(result) => {
                         // Wait for the results to come in, and determine the outcome.
                         let (name, index, profiles) = result;
-                        let result = Self::process_autotune(name, index, profiles).await;
+                        let result =
+                            Self::process_autotune(name, index, profiles, config.outlier_policy)
+                                .await;
                         match result {
                             Ok(val) => {
                                 results[index] = Ok(val);
@@ -385,6 +603,7 @@ impl<K: AutotuneKey> Tuner<K> {
         name: String,
         index: usize,
         profiles: Vec<ProfileDuration>,
+        outlier_policy: OutlierPolicy,
     ) -> Result<AutotuneOutcome, AutotuneError> {
         let mut durations = Vec::new();
         if !profiles.is_empty() {
@@ -397,7 +616,7 @@ impl<K: AutotuneKey> Tuner<K> {
             Ok(AutotuneOutcome::new(
                 name,
                 index,
-                BenchmarkComputations::new(&bench_durations),
+                BenchmarkComputations::with_outlier_policy(&bench_durations, outlier_policy),
             ))
         } else {
             Err(AutotuneError::Unknown(format!(
@@ -407,6 +626,78 @@ impl<K: AutotuneKey> Tuner<K> {
     }
 }
 
+/// Load the overrides of the tuner from the
+/// [overrides file](crate::config::autotune::AutotuneConfig::overrides), if any.
+#[cfg(std_io)]
+fn load_overrides<K: AutotuneKey>(tuner: &str) -> HashMap<K, usize> {
+    let Some(path) = crate::config::GlobalConfig::get()
+        .autotune
+        .overrides
+        .clone()
+    else {
+        return HashMap::new();
+    };
+
+    let overrides = std::fs::read_to_string(&path)
+        .map_err(|err| format!("{err}"))
+        .and_then(|content| {
+            serde_json::from_str::<Vec<AutotuneOverride<serde_json::Value>>>(&content)
+                .map_err(|err| format!("{err}"))
+        });
+    let overrides = match overrides {
+        Ok(overrides) => overrides,
+        Err(err) => {
+            log::warn!("Can't load the autotune overrides from {path:?}: {err}");
+            return HashMap::new();
+        }
+    };
+
+    overrides
+        .into_iter()
+        .filter(|entry| entry.tuner == tuner)
+        .filter_map(
+            |entry| match serde_json::from_value::<K>(entry.key.clone()) {
+                Ok(key) => Some((key, entry.index)),
+                Err(err) => {
+                    log::warn!(
+                        "Invalid autotune override key {} for {tuner}: {err}",
+                        entry.key
+                    );
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+/// The checksum of the tunables, combined with a fingerprint of the device, so that cached
+/// results, disqualifications included, are discarded when the device changes.
+#[cfg(std_io)]
+pub(crate) fn autotune_checksum<
+    K: AutotuneKey,
+    In: Clone + Send + 'static,
+    Out: AutotuneOutput,
+    S: ComputeServer,
+    C: ComputeChannel<S>,
+>(
+    tunables: &TunableSet<K, In, Out>,
+    client: &ComputeClient<S, C>,
+) -> String {
+    let properties = client.properties();
+    let fingerprint = format!(
+        "{:?}{:?}{:?}",
+        client.info(),
+        properties.features,
+        properties.hardware
+    );
+
+    format!(
+        "{}-{:x}",
+        tunables.compute_checksum(),
+        md5::compute(fingerprint)
+    )
+}
+
 #[cfg(feature = "autotune-checks")]
 pub(crate) fn check_autotune_outputs<O: AutotuneOutput>(
     mut checks_outputs: Vec<Result<O, AutotuneError>>,
@@ -1,4 +1,4 @@
-use core::sync::atomic::{AtomicI32, Ordering};
+use core::sync::atomic::{AtomicI8, AtomicI32, Ordering};
 
 use crate::config::GlobalConfig;
 
@@ -10,6 +10,9 @@ use crate::config::GlobalConfig;
 /// '3' => Autotune everything without anchor.
 static AUTOTUNE_LEVEL: AtomicI32 = AtomicI32::new(-1);
 
+/// Whether candidates are compared with device timestamps, `-1` when not loaded yet.
+static AUTOTUNE_DEVICE_TIMING: AtomicI8 = AtomicI8::new(-1);
+
 /// Anchor a number to a power of the provided base.
 ///
 /// Useful when creating autotune keys.
@@ -57,3 +60,15 @@ fn load_autotune_level() -> u32 {
         autotune_level as u32
     }
 }
+
+/// Whether the autotuner should compare candidates with device timestamps.
+pub(crate) fn autotune_device_timing() -> bool {
+    match AUTOTUNE_DEVICE_TIMING.load(Ordering::Relaxed) {
+        -1 => {
+            let enabled = GlobalConfig::get().autotune.device_timing;
+            AUTOTUNE_DEVICE_TIMING.store(enabled as i8, Ordering::Relaxed);
+            enabled
+        }
+        value => value == 1,
+    }
+}
@@ -8,7 +8,10 @@ use cubecl_runtime::memory_management::{
 use cubecl_runtime::server::CubeCount;
 use cubecl_runtime::storage::BytesStorage;
 use cubecl_runtime::{ComputeRuntime, DeviceProperties};
-use cubecl_runtime::{channel::MutexComputeChannel, memory_management::HardwareProperties};
+use cubecl_runtime::{
+    channel::{ComputeChannel, MpscComputeChannel, MutexComputeChannel},
+    memory_management::HardwareProperties,
+};
 
 /// The dummy device.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -20,6 +23,26 @@ pub type DummyClient = ComputeClient<DummyServer, DummyChannel>;
 static RUNTIME: ComputeRuntime<DummyDevice, DummyServer, DummyChannel> = ComputeRuntime::new();
 
 pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServer>> {
+    init_client_with(MutexComputeChannel::new, MemoryConfiguration::default())
+}
+
+/// Create a client with the given memory configuration.
+pub fn init_client_with_memory(
+    memory_config: MemoryConfiguration,
+) -> ComputeClient<DummyServer, MutexComputeChannel<DummyServer>> {
+    init_client_with(MutexComputeChannel::new, memory_config)
+}
+
+/// Create a client using a [mpsc channel](MpscComputeChannel), with the server running on its
+/// own thread.
+pub fn init_mpsc_client() -> ComputeClient<DummyServer, MpscComputeChannel<DummyServer>> {
+    init_client_with(MpscComputeChannel::new, MemoryConfiguration::default())
+}
+
+fn init_client_with<Channel: ComputeChannel<DummyServer>>(
+    channel: impl FnOnce(DummyServer) -> Channel,
+    memory_config: MemoryConfiguration,
+) -> ComputeClient<DummyServer, Channel> {
     let storage = BytesStorage::default();
     let mem_properties = MemoryDeviceProperties {
         max_page_size: 1024 * 1024 * 512,
@@ -38,13 +61,10 @@ pub fn init_client() -> ComputeClient<DummyServer, MutexComputeChannel<DummyServ
         num_tensor_cores: None,
         min_tensor_cores_dim: None,
     };
-    let memory_management = MemoryManagement::from_configuration(
-        storage,
-        &mem_properties,
-        MemoryConfiguration::default(),
-    );
+    let memory_management =
+        MemoryManagement::from_configuration(storage, &mem_properties, memory_config);
     let server = DummyServer::new(memory_management);
-    let channel = MutexComputeChannel::new(server);
+    let channel = channel(server);
     ComputeClient::new(
         channel,
         DeviceProperties::new(
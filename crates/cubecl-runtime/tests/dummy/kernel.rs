@@ -6,6 +6,12 @@ pub trait DummyKernel: Sync + Send + 'static + core::fmt::Debug {
 
     fn id(&self) -> KernelId;
 
+    /// Check that the kernel can be compiled for the device, returning the log of the backend
+    /// otherwise.
+    fn compile(&self) -> Result<(), String> {
+        Ok(())
+    }
+
     fn name(&self) -> &'static str {
         core::any::type_name::<Self>()
     }
@@ -34,3 +40,21 @@ impl DummyKernel for DummyElementwiseAddition {
         KernelId::new::<Self>()
     }
 }
+
+/// A kernel using a feature the device lacks, so the backend rejects it.
+#[derive(Debug)]
+pub struct DummyUnsupportedKernel;
+
+impl DummyKernel for DummyUnsupportedKernel {
+    fn compute(&self, _inputs: &mut [&BytesResource]) {
+        unreachable!("The kernel can't be compiled")
+    }
+
+    fn id(&self) -> KernelId {
+        KernelId::new::<Self>()
+    }
+
+    fn compile(&self) -> Result<(), String> {
+        Err("error: the type f64 isn't supported by the device".into())
+    }
+}
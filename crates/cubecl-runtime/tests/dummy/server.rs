@@ -4,7 +4,8 @@ use cubecl_common::future::DynFut;
 use cubecl_common::profile::ProfileDuration;
 use cubecl_runtime::logging::ServerLogger;
 use cubecl_runtime::server::{
-    Bindings, CopyDescriptor, DataTransferService, ProfileError, ProfilingToken,
+    Bindings, CompilationError, CompilationStage, CopyDescriptor, DataTransferService,
+    ProfileError, ProfilingToken,
 };
 use cubecl_runtime::timestamp_profiler::TimestampProfiler;
 use cubecl_runtime::{id::KernelId, server::IoError};
@@ -15,7 +16,7 @@ use cubecl_runtime::{
 use std::sync::Arc;
 
 use super::DummyKernel;
-use cubecl_runtime::memory_management::MemoryUsage;
+use cubecl_runtime::memory_management::{MemoryCleanupMode, MemoryScopeUsage, MemoryUsage};
 use cubecl_runtime::server::CubeCount;
 use cubecl_runtime::storage::{BindingResource, BytesResource, ComputeStorage};
 use cubecl_runtime::{
@@ -57,6 +58,12 @@ impl KernelTask {
     pub fn compute(&self, resources: &mut [&BytesResource]) {
         self.kernel.compute(resources);
     }
+
+    pub fn compile(&self) -> Result<(), IoError> {
+        self.kernel.compile().map_err(|log| {
+            CompilationError::new(self.name(), CompilationStage::Compile, log).into()
+        })
+    }
 }
 
 impl DataTransferService for DummyServer {}
@@ -91,23 +98,17 @@ impl ComputeServer for DummyServer {
     }
 
     fn read(&mut self, descriptors: Vec<CopyDescriptor>) -> DynFut<Result<Vec<Bytes>, IoError>> {
+        // Copy right away, like a device executing the copies in submission order.
         let bytes: Vec<_> = descriptors
             .into_iter()
             .map(|b| {
                 let bytes_handle = self.memory_management.get(b.binding.memory).unwrap();
-                self.memory_management.storage().get(&bytes_handle)
+                let bytes = self.memory_management.storage().get(&bytes_handle);
+                Bytes::from_bytes_vec(bytes.read().to_vec())
             })
             .collect();
 
-        Box::pin(async move {
-            Ok(bytes
-                .into_iter()
-                .map(|b| {
-                    let bytes = b.read();
-                    Bytes::from_bytes_vec(bytes.to_vec())
-                })
-                .collect())
-        })
+        Box::pin(async move { Ok(bytes) })
     }
 
     fn write(&mut self, descriptors: Vec<(CopyDescriptor<'_>, &[u8])>) -> Result<(), IoError> {
@@ -124,8 +125,15 @@ impl ComputeServer for DummyServer {
     }
 
     fn get_resource(&mut self, binding: Binding) -> BindingResource<BytesResource> {
-        let handle = self.memory_management.get(binding.clone().memory).unwrap();
-        BindingResource::new(binding, self.memory_management.storage().get(&handle))
+        let resource = self
+            .memory_management
+            .get_resource(
+                binding.memory.clone(),
+                binding.offset_start,
+                binding.offset_end,
+            )
+            .unwrap();
+        BindingResource::new(binding, resource)
     }
 
     unsafe fn execute(
@@ -135,27 +143,38 @@ impl ComputeServer for DummyServer {
         bindings: Bindings,
         _mode: ExecutionMode,
         _logger: Arc<ServerLogger>,
-    ) {
+    ) -> Result<(), IoError> {
+        kernel.compile()?;
+
         let mut resources: Vec<_> = bindings
             .buffers
             .into_iter()
             .map(|b| self.get_resource(b))
             .collect();
-        let metadata = self
-            .create_with_data(bytemuck::cast_slice(&bindings.metadata.data))
-            .unwrap();
+        let metadata = self.create_with_data(bytemuck::cast_slice(&bindings.metadata.data))?;
         resources.push(self.get_resource(metadata.binding()));
 
         let scalars = bindings
             .scalars
             .into_values()
-            .map(|s| self.create_with_data(s.data()).unwrap())
-            .collect::<Vec<_>>();
+            .map(|s| self.create_with_data(s.data()))
+            .collect::<Result<Vec<_>, IoError>>()?;
         resources.extend(scalars.into_iter().map(|h| self.get_resource(h.binding())));
 
         let mut resources: Vec<_> = resources.iter().map(|x| x.resource()).collect();
 
         kernel.compute(&mut resources);
+
+        Ok(())
+    }
+
+    fn precompile(
+        &mut self,
+        kernel: Self::Kernel,
+        _mode: ExecutionMode,
+        _logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        kernel.compile()
     }
 
     fn flush(&mut self) {
@@ -166,8 +185,20 @@ impl ComputeServer for DummyServer {
         self.memory_management.memory_usage()
     }
 
-    fn memory_cleanup(&mut self) {
-        self.memory_management.cleanup(true);
+    fn push_memory_scope(&mut self, tag: String) {
+        self.memory_management.push_scope(tag);
+    }
+
+    fn pop_memory_scope(&mut self) {
+        self.memory_management.pop_scope();
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.memory_management.scope_usage()
+    }
+
+    fn memory_cleanup(&mut self, mode: MemoryCleanupMode) {
+        self.memory_management.memory_cleanup(mode);
     }
 
     fn start_profile(&mut self) -> ProfilingToken {
@@ -20,12 +20,29 @@ impl TuneFn for OneKernelAutotuneOperation {
     type Output = ();
 
     fn execute(&self, inputs: Vec<Binding>) -> Result<(), AutotuneError> {
-        self.client.execute(
+        self.client.try_execute(
             self.kernel.clone(),
             CubeCount::Static(1, 1, 1),
             Bindings::new().with_buffers(inputs),
-        );
+        )?;
 
         Ok(())
     }
 }
+
+#[derive(Clone)]
+/// An operation that always fails, like a kernel needing a feature the device lacks.
+pub struct FailingAutotuneOperation;
+
+impl TuneFn for FailingAutotuneOperation {
+    type Inputs = Vec<Binding>;
+    type Output = ();
+
+    fn execute(&self, _inputs: Vec<Binding>) -> Result<(), AutotuneError> {
+        Err(AutotuneError::Unknown("Unsupported on this device".into()))
+    }
+
+    fn name(&self) -> &str {
+        "failing"
+    }
+}
@@ -5,7 +5,8 @@ use cubecl_runtime::{
 
 use crate::dummy::{
     DummyClient, DummyElementwiseAddition, DummyElementwiseMultiplication,
-    DummyElementwiseMultiplicationSlowWrong, KernelTask, OneKernelAutotuneOperation,
+    DummyElementwiseMultiplicationSlowWrong, DummyUnsupportedKernel, FailingAutotuneOperation,
+    KernelTask, OneKernelAutotuneOperation,
 };
 
 use super::DummyElementwiseAdditionSlowWrong;
@@ -50,6 +51,44 @@ pub fn multiplication_set(client: DummyClient, shapes: Vec<Vec<usize>>) -> TestS
     )))
 }
 
+pub fn addition_set_with_failing(client: DummyClient, shapes: Vec<Vec<usize>>) -> TestSet {
+    TestSet::new(
+        move |_input: &Vec<Binding>| format!("{}-{}", "add-failing", log_shape_input_key(&shapes)),
+        clone_bindings,
+    )
+    .with(Tunable::new(FailingAutotuneOperation))
+    .with(Tunable::new(OneKernelAutotuneOperation::new(
+        KernelTask::new(DummyElementwiseAddition),
+        client.clone(),
+    )))
+}
+
+pub fn addition_set_with_unsupported(client: DummyClient, shapes: Vec<Vec<usize>>) -> TestSet {
+    TestSet::new(
+        move |_input: &Vec<Binding>| {
+            format!("{}-{}", "add-unsupported", log_shape_input_key(&shapes))
+        },
+        clone_bindings,
+    )
+    .with(Tunable::new(OneKernelAutotuneOperation::new(
+        KernelTask::new(DummyUnsupportedKernel),
+        client.clone(),
+    )))
+    .with(Tunable::new(OneKernelAutotuneOperation::new(
+        KernelTask::new(DummyElementwiseAddition),
+        client.clone(),
+    )))
+}
+
+pub fn failing_set(shapes: Vec<Vec<usize>>) -> TestSet {
+    TestSet::new(
+        move |_input: &Vec<Binding>| format!("{}-{}", "failing", log_shape_input_key(&shapes)),
+        clone_bindings,
+    )
+    .with(Tunable::new(FailingAutotuneOperation))
+    .with(Tunable::new(FailingAutotuneOperation))
+}
+
 pub fn log_shape_input_key(shapes: &[Vec<usize>]) -> String {
     let mut hash = String::new();
     let lhs = &shapes[0];
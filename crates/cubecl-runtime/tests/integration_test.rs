@@ -1,10 +1,26 @@
 mod dummy;
 
-use crate::dummy::{DummyDevice, DummyElementwiseAddition, test_client};
+use crate::dummy::{
+    DummyDevice, DummyElementwiseAddition, init_client, init_client_with_memory, init_mpsc_client,
+    test_client,
+};
 
+use cubecl_runtime::channel::ComputeChannel;
+use cubecl_runtime::client::ComputeClient;
+use cubecl_runtime::memory_management::{
+    MemoryCleanupMode, MemoryConfiguration, MemoryError, MemoryLimits, PoolStrategy, SizeClass,
+};
+
+use cubecl_common::ExecutionMode;
+use cubecl_common::future::block_on;
 use cubecl_runtime::server::Bindings;
+use cubecl_runtime::server::CompilationStage;
 use cubecl_runtime::server::CubeCount;
-use cubecl_runtime::{local_tuner, tune::LocalTuner};
+use cubecl_runtime::server::IoError;
+use cubecl_runtime::{
+    local_tuner,
+    tune::{AutotuneError, LocalTuner},
+};
 use dummy::*;
 
 #[test]
@@ -28,6 +44,135 @@ fn empty_allocates_memory() {
     assert_eq!(empty_resource.len(), 4);
 }
 
+#[test]
+fn memory_cleanup_releases_unused_memory() {
+    // Use a dedicated client, so that other tests don't allocate concurrently.
+    let client = init_client();
+    let handles: Vec<_> = (0..8).map(|_| client.empty(1024 * 1024)).collect();
+    let reserved_before = client.memory_usage().bytes_reserved;
+
+    drop(handles);
+    client.memory_cleanup(MemoryCleanupMode::ReleaseUnused);
+
+    let usage = client.memory_usage();
+    assert!(usage.bytes_reserved < reserved_before);
+    assert_eq!(usage.bytes_in_use, 0);
+}
+
+#[test]
+fn out_of_memory_is_recoverable() {
+    let client = init_client_with_memory(MemoryConfiguration::size_classes(
+        vec![SizeClass::new(1024, PoolStrategy::Exclusive, None)],
+        MemoryLimits {
+            max_reserved: Some(4 * 1024),
+            alignment: None,
+        },
+    ));
+    let mut handles: Vec<_> = (0..4).map(|_| client.try_empty(1024).unwrap()).collect();
+
+    match client.try_empty(1024) {
+        Err(IoError::Memory(MemoryError::OutOfMemory {
+            requested,
+            in_use,
+            reserved,
+        })) => {
+            assert_eq!(requested, 1024);
+            assert_eq!(in_use, 4 * 1024);
+            assert_eq!(reserved, 4 * 1024);
+        }
+        other => panic!("Expected an out of memory error, got {other:?}"),
+    }
+    assert!(
+        client
+            .try_create(&[0; 1024])
+            .unwrap_err()
+            .is_out_of_memory()
+    );
+
+    // Free some memory and retry.
+    handles.truncate(2);
+    let handle = client.try_create(&[1; 1024]).unwrap();
+
+    assert_eq!(client.read_one(handle).to_vec(), vec![1; 1024]);
+}
+
+#[test]
+fn overlapping_async_reads_resolve_in_submission_order() {
+    let client = test_client(&DummyDevice);
+    overlapping_async_reads(&client);
+}
+
+#[test]
+fn overlapping_async_reads_resolve_in_submission_order_mpsc() {
+    let client = init_mpsc_client();
+    overlapping_async_reads(&client);
+}
+
+fn overlapping_async_reads<Channel: ComputeChannel<DummyServer>>(
+    client: &ComputeClient<DummyServer, Channel>,
+) {
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.create(&[9, 9, 9]);
+
+    let read_lhs = client.read_one_async(lhs.clone());
+    let read_out_before = client.read_one_async(out.clone());
+    client.execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        Bindings::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.clone().binding()]),
+    );
+    let read_out_after = client.read_one_async(out);
+
+    // Await in the reverse order, every read still sees the state at its submission.
+    assert_eq!(block_on(read_out_after).to_vec(), vec![4, 5, 6]);
+    assert_eq!(block_on(read_out_before).to_vec(), vec![9, 9, 9]);
+    assert_eq!(block_on(read_lhs).to_vec(), vec![0, 1, 2]);
+}
+
+#[test]
+fn dropped_async_read_does_not_block_the_client() {
+    let client = init_mpsc_client();
+    let handle = client.create(&[1, 2, 3]);
+
+    drop(client.read_one_async(handle.clone()));
+
+    assert_eq!(client.read_one(handle).to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn pinned_buffer_round_trip() {
+    let client = test_client(&DummyDevice);
+    let data: Vec<u8> = (0..64).collect();
+    let handle = client.empty(data.len());
+
+    let mut src = client.create_pinned(data.len());
+    src.copy_from_slice(&data);
+    let src = block_on(client.write_from_pinned(src, handle.clone())).unwrap();
+    assert_eq!(client.read_one(handle.clone()).to_vec(), data);
+
+    let dst = client.create_pinned(data.len());
+    let dst = block_on(client.read_to_pinned(handle, dst)).unwrap();
+    assert_eq!(&dst[..], &src[..]);
+}
+
+#[test]
+fn pinned_buffer_too_small_is_rejected() {
+    let client = test_client(&DummyDevice);
+    let handle = client.empty(64);
+    let dst = client.create_pinned(32);
+
+    let result = block_on(client.read_to_pinned(handle, dst));
+
+    assert!(matches!(
+        result,
+        Err(IoError::BufferTooSmall {
+            size: 32,
+            required: 64
+        })
+    ));
+}
+
 #[test]
 fn execute_elementwise_addition() {
     let client = test_client(&DummyDevice);
@@ -46,6 +191,285 @@ fn execute_elementwise_addition() {
     assert_eq!(obtained_resource, Vec::from([4, 5, 6]))
 }
 
+#[test]
+fn precompile_only_client_does_not_execute() {
+    let client = test_client(&DummyDevice);
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.create(&[0, 0, 0]);
+
+    client.precompile_only().execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        Bindings::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.clone().binding()]),
+    );
+
+    let obtained_resource = client.read_one(out).to_vec();
+
+    assert_eq!(obtained_resource, Vec::from([0, 0, 0]))
+}
+
+#[test]
+fn precompile_all_completes() {
+    let client = test_client(&DummyDevice);
+    let kernels = (0..16)
+        .map(|_| {
+            (
+                KernelTask::new(DummyElementwiseAddition),
+                ExecutionMode::Checked,
+            )
+        })
+        .collect();
+
+    let handle = client.precompile_all(kernels);
+    handle.wait();
+}
+
+#[test]
+fn rejected_kernel_returns_a_compilation_error_and_server_keeps_working() {
+    let client = init_mpsc_client();
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let bindings =
+        || Bindings::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.clone().binding()]);
+
+    let precompiled = client.try_precompile(
+        KernelTask::new(DummyUnsupportedKernel),
+        ExecutionMode::Checked,
+    );
+    let executed = client.try_execute(
+        KernelTask::new(DummyUnsupportedKernel),
+        CubeCount::Static(1, 1, 1),
+        bindings(),
+    );
+    for result in [precompiled, executed] {
+        match result {
+            Err(IoError::Compilation(err)) => {
+                assert_eq!(err.stage, CompilationStage::Compile);
+                assert!(err.kernel_name.contains("DummyUnsupportedKernel"));
+                assert!(err.backend_log.contains("f64"));
+            }
+            other => panic!("Expected a compilation error, got {other:?}"),
+        }
+    }
+
+    // The server isn't poisoned by the error.
+    client.execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        bindings(),
+    );
+    let obtained_resource = client.read_one(out).to_vec();
+    assert_eq!(obtained_resource, Vec::from([4, 5, 6]));
+}
+
+#[test]
+fn batch_executes_kernels_in_order() {
+    let client = init_mpsc_client();
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let intermediate = client.create(&[0, 0, 0]);
+
+    let out = client.batch(|batch| {
+        // Allocated inside the batch, before the kernels are submitted.
+        let out = batch.create(&[0, 0, 0]);
+
+        batch.execute(
+            KernelTask::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            Bindings::new().with_buffers(vec![
+                lhs.binding(),
+                rhs.clone().binding(),
+                intermediate.clone().binding(),
+            ]),
+        );
+        batch.execute(
+            KernelTask::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            Bindings::new().with_buffers(vec![
+                intermediate.binding(),
+                rhs.binding(),
+                out.clone().binding(),
+            ]),
+        );
+
+        out
+    });
+
+    let obtained_resource = client.read_one(out).to_vec();
+
+    assert_eq!(obtained_resource, Vec::from([8, 9, 10]))
+}
+
+#[test]
+fn reusable_batch_is_an_error_when_unsupported() {
+    let client = test_client(&DummyDevice);
+    let out = client.create(&[0, 0, 0]);
+
+    let result = client.batch_reusable(|batch| {
+        batch.execute(
+            KernelTask::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            Bindings::new().with_buffers(vec![
+                batch.create(&[0, 1, 2]).binding(),
+                batch.create(&[4, 4, 4]).binding(),
+                out.clone().binding(),
+            ]),
+        );
+    });
+
+    assert!(result.is_err());
+    assert_eq!(client.read_one(out).to_vec(), Vec::from([0, 0, 0]));
+}
+
+#[test]
+fn execute_on_windows_of_a_single_buffer() {
+    // Use a dedicated client, so that the memory usage isn't affected by other tests.
+    let client = init_client();
+    let alignment = client.properties().memory.alignment as usize;
+    let mut data = vec![0u8; 3 * alignment];
+    data[..3].copy_from_slice(&[0, 1, 2]);
+    data[alignment..alignment + 3].copy_from_slice(&[4, 4, 4]);
+    data[2 * alignment..2 * alignment + 3].copy_from_slice(&[9, 9, 9]);
+
+    let buffer = client.create(&data);
+    let usage_before = client.memory_usage();
+
+    let window = |index: usize| buffer.offset((index * alignment) as u64, 3);
+    let (lhs, rhs, out) = (window(0), window(1), window(2));
+
+    // Windows share the memory of the buffer they are created from.
+    assert_eq!(client.memory_usage(), usage_before);
+
+    client.execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        Bindings::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.binding()]),
+    );
+
+    let mut expected = data.clone();
+    expected[2 * alignment..2 * alignment + 3].copy_from_slice(&[4, 5, 6]);
+
+    assert_eq!(client.read_one(buffer).to_vec(), expected);
+}
+
+#[test]
+fn misaligned_window_is_rejected() {
+    let client = init_client();
+    let buffer = client.empty(256);
+    let aligned = buffer.offset(0, 3);
+    let misaligned = buffer.offset(1, 3);
+
+    let result = client.try_execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        Bindings::new().with_buffers(vec![
+            aligned.clone().binding(),
+            aligned.binding(),
+            misaligned.binding(),
+        ]),
+    );
+
+    assert!(matches!(
+        result,
+        Err(IoError::MisalignedOffset { offset: 1, .. })
+    ));
+}
+
+#[test]
+fn kernel_timing_records_executed_kernels() {
+    let client = init_client();
+    client.enable_kernel_timing(true);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let execute = |out: &cubecl_runtime::server::Handle| {
+        client.execute(
+            KernelTask::new(DummyElementwiseAddition),
+            CubeCount::Static(1, 1, 1),
+            Bindings::new().with_buffers(vec![
+                lhs.clone().binding(),
+                rhs.clone().binding(),
+                out.clone().binding(),
+            ]),
+        );
+    };
+
+    execute(&out);
+    {
+        let _scope = client.profile_scope("outer");
+        execute(&out);
+        let _scope = client.profile_scope("inner");
+        execute(&out);
+    }
+
+    let timings = block_on(client.kernel_timings());
+    let (name, timing) = timings
+        .kernels
+        .iter()
+        .find(|(name, _)| !name.contains('/'))
+        .unwrap();
+
+    assert!(name.contains("DummyElementwiseAddition"));
+    assert_eq!(timing.count, 1);
+    assert!(timing.total > core::time::Duration::ZERO);
+    assert!(timing.min <= timing.max);
+    assert_eq!(timings.kernels.len(), 3);
+    assert_eq!(timings.scope("outer").unwrap().count, 2);
+    assert_eq!(timings.scope("outer/inner").unwrap().count, 1);
+    assert!(format!("{timings}").contains("DummyElementwiseAddition"));
+}
+
+#[test]
+fn disabled_kernel_timing_records_nothing() {
+    let client = init_client();
+    client.enable_kernel_timing(true);
+    client.enable_kernel_timing(false);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+
+    client.execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        Bindings::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.clone().binding()]),
+    );
+
+    assert_eq!(client.read_one(out).to_vec(), Vec::from([4, 5, 6]));
+    assert!(block_on(client.kernel_timings()).is_empty());
+}
+
+#[test]
+fn copy_to_moves_handle_to_another_server() {
+    let client_0 = init_client();
+    let client_1 = init_client();
+    let handle = client_0.create(&[0, 1, 2]);
+
+    let copied = client_0.copy_to(&client_1, handle);
+
+    assert_eq!(copied.owner(), Some(client_1.id()));
+    assert_eq!(client_1.read_one(copied).to_vec(), Vec::from([0, 1, 2]));
+}
+
+#[test]
+#[should_panic]
+fn handle_from_another_server_is_rejected() {
+    let client_0 = init_client();
+    let client_1 = init_client();
+    let lhs = client_0.create(&[0, 1, 2]);
+    let rhs = client_1.create(&[4, 4, 4]);
+    let out = client_1.empty(3);
+
+    client_1.execute(
+        KernelTask::new(DummyElementwiseAddition),
+        CubeCount::Static(1, 1, 1),
+        Bindings::new().with_buffers(vec![lhs.binding(), rhs.binding(), out.binding()]),
+    );
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn autotune_basic_addition_execution() {
@@ -96,3 +520,184 @@ fn autotune_basic_multiplication_execution() {
     // If slow kernel was selected it would output [0, 1, 2]
     assert_eq!(obtained_resource, Vec::from([0, 4, 8]));
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn autotune_skips_failing_candidates() {
+    static TUNER: LocalTuner<String, String> = local_tuner!("autotune_skips_failing_candidates");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+
+    let test_set = TUNER.init(|| {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::addition_set_with_failing(client, shapes)
+    });
+    let id = "test".to_string();
+    TUNER
+        .try_execute(&id, &client, test_set.clone(), handles.clone())
+        .unwrap();
+
+    let obtained_resource = client.read_one(out).to_vec();
+    assert_eq!(obtained_resource, Vec::from([4, 5, 6]));
+
+    let disqualified = TUNER.disqualified(&id, &test_set, &handles);
+    assert_eq!(disqualified.len(), 1);
+    assert_eq!(disqualified[0].index, 0);
+    assert_eq!(disqualified[0].name, "failing");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn autotune_disqualifies_candidates_failing_to_compile() {
+    static TUNER: LocalTuner<String, String> =
+        local_tuner!("autotune_disqualifies_candidates_failing_to_compile");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.clone().binding()];
+
+    let test_set = TUNER.init(|| {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::addition_set_with_unsupported(client, shapes)
+    });
+    let id = "test".to_string();
+    TUNER
+        .try_execute(&id, &client, test_set.clone(), handles.clone())
+        .unwrap();
+
+    let obtained_resource = client.read_one(out).to_vec();
+    assert_eq!(obtained_resource, Vec::from([4, 5, 6]));
+
+    let disqualified = TUNER.disqualified(&id, &test_set, &handles);
+    assert_eq!(disqualified.len(), 1);
+    assert_eq!(disqualified[0].index, 0);
+    match &disqualified[0].error {
+        AutotuneError::Compilation(err) => assert_eq!(err.stage, CompilationStage::Compile),
+        err => panic!("Expected a compilation error, got {err:?}"),
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn autotune_reports_every_failure_when_all_candidates_fail() {
+    static TUNER: LocalTuner<String, String> =
+        local_tuner!("autotune_reports_every_failure_when_all_candidates_fail");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.binding()];
+
+    let test_set = TUNER.init(|| {
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::failing_set(shapes)
+    });
+    let id = "test".to_string();
+
+    // The failure is cached, the second execution reports it without tuning again.
+    for _ in 0..2 {
+        let err = TUNER
+            .try_execute(&id, &client, test_set.clone(), handles.clone())
+            .unwrap_err();
+
+        match err {
+            AutotuneError::AllCandidatesFailed(disqualified) => {
+                let indices: Vec<_> = disqualified.iter().map(|it| it.index).collect();
+                assert_eq!(indices, [0, 1]);
+            }
+            err => panic!("Expected every candidate to fail, got {err:?}"),
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn autotune_resolves_a_freshly_tuned_key() {
+    static TUNER: LocalTuner<String, String> =
+        local_tuner!("autotune_resolves_a_freshly_tuned_key");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out = client.empty(3);
+    let handles = vec![lhs.binding(), rhs.binding(), out.binding()];
+
+    let test_set = TUNER.init(|| {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::addition_set(client, shapes)
+    });
+    let id = "test".to_string();
+    let key = test_set.generate_key(&handles);
+    TUNER.execute(&id, &client, test_set.clone(), handles);
+
+    assert!(TUNER.keys(&id).contains(&key));
+
+    let info = TUNER.resolve(&id, &client, &test_set, &key).unwrap();
+    assert_eq!(info.index, 0);
+    assert_eq!(info.name, test_set.fastest(0).name());
+    assert!(!info.forced);
+    assert_eq!(info.measured_times.first().map(|it| it.index), Some(0));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn autotune_forced_candidate_wins_over_the_tuned_one() {
+    static TUNER: LocalTuner<String, String> =
+        local_tuner!("autotune_forced_candidate_wins_over_the_tuned_one");
+
+    let client = test_client(&DummyDevice);
+
+    let lhs = client.create(&[0, 1, 2]);
+    let rhs = client.create(&[4, 4, 4]);
+    let out_tuned = client.empty(3);
+    let out_forced = client.empty(3);
+
+    let test_set = TUNER.init(|| {
+        let client = test_client(&DummyDevice);
+        let shapes = vec![vec![1, 3], vec![1, 3], vec![1, 3]];
+        dummy::addition_set(client, shapes)
+    });
+    let id = "test".to_string();
+    let handles = vec![lhs.binding(), rhs.binding(), out_tuned.clone().binding()];
+    let key = test_set.generate_key(&handles);
+    TUNER.execute(&id, &client, test_set.clone(), handles);
+    assert_eq!(client.read_one(out_tuned).to_vec(), Vec::from([4, 5, 6]));
+
+    // Only the candidates of the set can be forced.
+    let err = TUNER.force(&id, &test_set, key.clone(), 2).unwrap_err();
+    assert_eq!(
+        err,
+        AutotuneError::InvalidOverride {
+            index: 2,
+            num_candidates: 2
+        }
+    );
+
+    // The slow kernel outputs [0, 1, 2], even though it isn't the fastest.
+    TUNER.force(&id, &test_set, key.clone(), 1).unwrap();
+    let handles = vec![lhs.binding(), rhs.binding(), out_forced.clone().binding()];
+    TUNER.execute(&id, &client, test_set.clone(), handles);
+    assert_eq!(client.read_one(out_forced).to_vec(), Vec::from([0, 1, 2]));
+
+    let info = TUNER.resolve(&id, &client, &test_set, &key).unwrap();
+    assert_eq!(info.index, 1);
+    assert!(info.forced);
+
+    let overrides = TUNER.overrides(&id);
+    assert_eq!(overrides.len(), 1);
+    assert_eq!((&overrides[0].key, overrides[0].index), (&key, 1));
+}
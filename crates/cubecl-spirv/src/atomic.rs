@@ -1,4 +1,4 @@
-use cubecl_core::ir::{AtomicOp, Variable};
+use cubecl_core::ir::{AtomicOp, ElemType, IntKind, UIntKind, Variable};
 use rspirv::spirv::{Capability, MemorySemantics, Scope};
 
 use crate::{SpirvCompiler, SpirvTarget, item::Elem};
@@ -6,6 +6,16 @@ use crate::{SpirvCompiler, SpirvTarget, item::Elem};
 impl<T: SpirvTarget> SpirvCompiler<T> {
     pub fn compile_atomic(&mut self, atomic: AtomicOp, out: Option<Variable>) {
         let out = out.unwrap();
+
+        let elem = out.elem_type();
+        if matches!(
+            elem,
+            ElemType::Int(IntKind::I64) | ElemType::UInt(UIntKind::U64)
+        ) {
+            self.capabilities.insert(Capability::Int64Atomics);
+            self.int64_atomic = Some(elem);
+        }
+
         match atomic {
             AtomicOp::Load(op) => {
                 let input = self.compile_variable(op.input);
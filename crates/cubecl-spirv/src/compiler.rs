@@ -24,7 +24,7 @@ use rspirv::{
 };
 
 use crate::{
-    SpirvKernel,
+    SpecConstant, SpirvKernel,
     debug::DebugInfo,
     item::Item,
     lookups::LookupTables,
@@ -55,7 +55,11 @@ pub struct SpirvCompiler<Target: SpirvTarget = GLCompute> {
     pub ext_meta_pos: Vec<u32>,
     pub metadata: Metadata,
     pub debug_info: Option<DebugInfo>,
-    compilation_options: WgpuCompilationOptions,
+    /// The 64-bit integer type of the atomics used by the kernel, if any.
+    pub int64_atomic: Option<core::ElemType>,
+    /// The specialization constants of the kernel, indexed by their `SpecId`.
+    pub spec_constants: Vec<SpecConstant>,
+    pub(crate) compilation_options: WgpuCompilationOptions,
 }
 
 unsafe impl<T: SpirvTarget> Send for SpirvCompiler<T> {}
@@ -83,6 +87,8 @@ impl<T: SpirvTarget> Clone for SpirvCompiler<T> {
             metadata: self.metadata.clone(),
             debug_info: self.debug_info.clone(),
             ext_meta_pos: self.ext_meta_pos.clone(),
+            int64_atomic: self.int64_atomic,
+            spec_constants: self.spec_constants.clone(),
             compilation_options: self.compilation_options.clone(),
         }
     }
@@ -116,6 +122,8 @@ impl<T: SpirvTarget> Default for SpirvCompiler<T> {
             metadata: Default::default(),
             debug_info: Default::default(),
             ext_meta_pos: Default::default(),
+            int64_atomic: Default::default(),
+            spec_constants: Default::default(),
             compilation_options: Default::default(),
         }
     }
@@ -177,12 +185,22 @@ impl<T: SpirvTarget> Compiler for SpirvCompiler<T> {
         self.ext_meta_pos = ext_meta_pos;
 
         let (module, optimizer) = self.compile_kernel(value);
+        let shared_memory_size = self
+            .state
+            .shared_memories
+            .values()
+            .map(|memory| memory.item.size() as usize * memory.len as usize)
+            .sum();
+
         SpirvKernel {
             module,
             optimizer,
             bindings,
             scalars,
             has_metadata: self.metadata.static_len() > 0,
+            shared_memory_size,
+            int64_atomic: self.int64_atomic,
+            spec_constants: take(&mut self.spec_constants),
         }
     }
 
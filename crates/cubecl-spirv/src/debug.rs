@@ -225,6 +225,9 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
                 core::NonSemantic::Comment { .. } => {
                     // Comments not supported for SPIR-V
                 }
+                core::NonSemantic::CacheHint { .. } => {
+                    // Cache hints not supported for SPIR-V
+                }
                 core::NonSemantic::EnterDebugScope => {
                     let new_top = self.stack_top().clone();
                     self.stack().push(new_top);
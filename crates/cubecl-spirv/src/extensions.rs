@@ -15,8 +15,11 @@ pub trait TargetExtensions<T: SpirvTarget> {
     fn tanh(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn pow(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word);
     fn exp(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn exp2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn log(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn log2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn sqrt(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
+    fn inverse_sqrt(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word);
     fn f_min(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word);
     fn u_min(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word);
     fn s_min(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word);
@@ -77,14 +80,26 @@ pub mod glcompute {
             b.gl_exp_id(ty, Some(out), input).unwrap();
         }
 
+        fn exp2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            b.gl_exp2_id(ty, Some(out), input).unwrap();
+        }
+
         fn log(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
             b.gl_log_id(ty, Some(out), input).unwrap();
         }
 
+        fn log2(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            b.gl_log2_id(ty, Some(out), input).unwrap();
+        }
+
         fn sqrt(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
             b.gl_sqrt_id(ty, Some(out), input).unwrap();
         }
 
+        fn inverse_sqrt(b: &mut SpirvCompiler<T>, ty: Word, input: Word, out: Word) {
+            b.gl_inverse_sqrt_id(ty, Some(out), input).unwrap();
+        }
+
         fn f_min(b: &mut SpirvCompiler<T>, ty: Word, lhs: Word, rhs: Word, out: Word) {
             b.gl_f_min_id(ty, Some(out), lhs, rhs).unwrap();
         }
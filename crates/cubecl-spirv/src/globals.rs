@@ -180,7 +180,7 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
     fn constant_var(&mut self, value: u32) -> Variable {
         let var =
             ir::Variable::constant(ir::ConstantScalarValue::UInt(value as u64, UIntKind::U32));
-        self.compile_variable(var)
+        self.compile_const_expr(var)
     }
 
     fn extract(&mut self, builtin: BuiltIn, idx: u32) -> Word {
@@ -7,7 +7,9 @@ use cubecl_opt::Optimizer;
 use item::Elem;
 use rspirv::{
     binary::{Assemble, Disassemble},
-    dr::Module,
+    dr::{Module, Operand},
+    grammar::CoreInstructionTable,
+    spirv::Op,
 };
 
 mod arithmetic;
@@ -39,6 +41,22 @@ pub struct SpirvKernel {
     pub bindings: Vec<Binding>,
     pub scalars: Vec<(Elem, usize)>,
     pub has_metadata: bool,
+    /// Total size in bytes of the shared memories used by the kernel.
+    pub shared_memory_size: usize,
+    /// The 64-bit integer type of the atomics used by the kernel, needing the `Int64Atomics`
+    /// capability.
+    pub int64_atomic: Option<cubecl_core::ir::ElemType>,
+    /// The specialization constants of the kernel, indexed by their `SpecId`.
+    pub spec_constants: Vec<SpecConstant>,
+}
+
+/// A comptime `u32` or `bool` value lowered to a specialization constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpecConstant {
+    /// The `SpecId` of the constant.
+    pub id: u32,
+    /// The value of the constant, `bool` values being `0` or `1`.
+    pub value: u32,
 }
 
 impl Display for SpirvKernel {
@@ -51,4 +69,20 @@ impl SpirvKernel {
     pub fn assemble(&self) -> Vec<u32> {
         self.module.assemble()
     }
+
+    /// Assemble the module with the defaults of its specialization constants cleared, so the
+    /// kernels only differing by the values of these constants share the same template.
+    pub fn assemble_template(&self) -> Vec<u32> {
+        let mut module = self.module.clone();
+        for inst in module.types_global_values.iter_mut() {
+            match inst.class.opcode {
+                Op::SpecConstant => inst.operands = vec![Operand::LiteralBit32(0)],
+                Op::SpecConstantTrue => {
+                    inst.class = CoreInstructionTable::get(Op::SpecConstantFalse)
+                }
+                _ => {}
+            }
+        }
+        module.assemble()
+    }
 }
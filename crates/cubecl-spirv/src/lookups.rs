@@ -313,7 +313,7 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
         let values = arr
             .values
             .into_iter()
-            .map(|it| self.compile_variable(it))
+            .map(|it| self.compile_const_expr(it))
             .collect::<Vec<_>>()
             .into_iter()
             .map(|it| self.read_as(&it, &item))
@@ -3,13 +3,13 @@ use cubecl_core::{
         Arithmetic, Bitwise, ElemType, ExpandElement, Instruction, IntKind, Operation, Scope,
         UIntKind, Variable,
     },
-    prelude::{IntExpand, assign, expand_erf},
+    prelude::{IntExpand, assign, expand_erf, expand_erfc},
 };
 use cubecl_opt::{IrTransformer, TransformAction};
 
 use crate::bitwise::{small_int_reverse, u64_count_bits, u64_ffs, u64_leading_zeros, u64_reverse};
 
-/// Expand erf
+/// Expand erf and erfc
 #[derive(Debug)]
 pub(crate) struct ErfTransform;
 
@@ -21,6 +21,11 @@ impl IrTransformer for ErfTransform {
                 expand_erf(&mut scope, op.input, inst.out.unwrap());
                 TransformAction::Replace(into_instructions(scope))
             }
+            Operation::Arithmetic(Arithmetic::Erfc(op)) => {
+                let mut scope = scope.child();
+                expand_erfc(&mut scope, op.input, inst.out.unwrap());
+                TransformAction::Replace(into_instructions(scope))
+            }
             _ => TransformAction::Ignore,
         }
     }
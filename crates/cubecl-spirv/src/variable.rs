@@ -3,14 +3,14 @@
 use std::mem::transmute;
 
 use crate::{
-    SpirvCompiler, SpirvTarget,
+    SpecConstant, SpirvCompiler, SpirvTarget,
     item::{Elem, Item},
     lookups::Array,
 };
-use cubecl_core::ir::{self, ConstantScalarValue, FloatKind, Id};
+use cubecl_core::ir::{self, ConstantScalarValue, FloatKind, Id, UIntKind};
 use rspirv::{
-    dr::Builder,
-    spirv::{FPEncoding, StorageClass, Word},
+    dr::{Builder, Operand},
+    spirv::{Decoration, FPEncoding, StorageClass, Word},
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -345,18 +345,7 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
     pub fn compile_variable(&mut self, variable: ir::Variable) -> Variable {
         let item = variable.ty;
         match variable.kind {
-            ir::VariableKind::ConstantScalar(value) => {
-                let item = self.compile_type(ir::Type::new(value.storage_type()));
-                let const_val = value.into();
-
-                if let Some(existing) = self.state.constants.get(&(const_val, item.clone())) {
-                    Variable::ConstantScalar(*existing, const_val, item.elem())
-                } else {
-                    let id = item.elem().constant(self, const_val);
-                    self.state.constants.insert((const_val, item.clone()), id);
-                    Variable::ConstantScalar(id, const_val, item.elem())
-                }
-            }
+            ir::VariableKind::ConstantScalar(value) => self.compile_constant(value),
             ir::VariableKind::GlobalInputArray(pos) => {
                 let id = self.state.buffers[pos as usize];
                 Variable::GlobalInputArray(id, self.compile_type(item), pos)
@@ -450,6 +439,63 @@ impl<T: SpirvTarget> SpirvCompiler<T> {
         }
     }
 
+    /// Compile a constant, lowering it to a specialization constant when enabled.
+    ///
+    /// Each use gets its own `SpecId`, so kernels with the same structure share the same
+    /// template whichever values happen to be equal.
+    fn compile_constant(&mut self, value: ConstantScalarValue) -> Variable {
+        let spec_value = match value {
+            ConstantScalarValue::UInt(val, UIntKind::U32) => Some(val as u32),
+            ConstantScalarValue::Bool(val) => Some(val as u32),
+            _ => None,
+        };
+
+        match spec_value.filter(|_| self.compilation_options.override_constants) {
+            Some(spec_value) => {
+                let elem = self
+                    .compile_type(ir::Type::new(value.storage_type()))
+                    .elem();
+                let ty = elem.id(self);
+                let id = match value {
+                    ConstantScalarValue::Bool(true) => self.spec_constant_true(ty),
+                    ConstantScalarValue::Bool(false) => self.spec_constant_false(ty),
+                    _ => self.spec_constant_bit32(ty, spec_value),
+                };
+                let spec_id = self.spec_constants.len() as u32;
+                self.decorate(id, Decoration::SpecId, [Operand::LiteralBit32(spec_id)]);
+                self.spec_constants.push(SpecConstant {
+                    id: spec_id,
+                    value: spec_value,
+                });
+                Variable::Raw(id, Item::Scalar(elem))
+            }
+            None => self.compile_literal(value),
+        }
+    }
+
+    /// Compile a constant as a literal of the module.
+    fn compile_literal(&mut self, value: ConstantScalarValue) -> Variable {
+        let item = self.compile_type(ir::Type::new(value.storage_type()));
+        let const_val = value.into();
+
+        if let Some(existing) = self.state.constants.get(&(const_val, item.clone())) {
+            Variable::ConstantScalar(*existing, const_val, item.elem())
+        } else {
+            let id = item.elem().constant(self, const_val);
+            self.state.constants.insert((const_val, item.clone()), id);
+            Variable::ConstantScalar(id, const_val, item.elem())
+        }
+    }
+
+    /// Compile a variable used where SPIR-V requires a constant instruction, keeping constants
+    /// as literals.
+    pub fn compile_const_expr(&mut self, variable: ir::Variable) -> Variable {
+        match variable.kind {
+            ir::VariableKind::ConstantScalar(value) => self.compile_literal(value),
+            _ => self.compile_variable(variable),
+        }
+    }
+
     pub fn read(&mut self, variable: &Variable) -> Word {
         match variable {
             Variable::Slice { ptr, .. } => self.read(ptr),
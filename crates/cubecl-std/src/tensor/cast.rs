@@ -0,0 +1,181 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, ir::StorageType};
+
+use super::{TensorHandle, into_contiguous, is_contiguous};
+
+/// Cast `input` into a new contiguous tensor of `O`.
+///
+/// See [`cast_ref`].
+pub fn cast<R: Runtime, I: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+) -> TensorHandle<R, O> {
+    let num_elems = input.shape.iter().product::<usize>();
+    let output = TensorHandle::<R, O>::new_contiguous(
+        input.shape.to_vec(),
+        client.empty(num_elems * size_of::<O>()),
+    );
+    cast_ref::<R, I, O>(client, input, &output.as_ref());
+    output
+}
+
+/// Cast each element of `input` to `O` into the contiguous `output` of the same shape.
+///
+/// The input and the output are read and written in lines of their own largest line size, so a
+/// unit casting `f32` to `f16` reads two lines of 4 elements and writes a single line of 8. The
+/// elements left over after the last full chunk of lines are cast one at a time.
+///
+/// Floats are cast to integers by rounding toward zero and saturating: values beyond the range of
+/// the integer become its minimum or maximum, and NaN becomes zero, like `as` in Rust. Integers
+/// are cast to narrower integers by wrapping. A non-contiguous input is made contiguous first.
+///
+/// # Panics
+///
+/// When the shapes differ or the output isn't contiguous.
+pub fn cast_ref<R: Runtime, I: Numeric, O: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+) {
+    assert_eq!(
+        input.shape, output.shape,
+        "the input and the output should have the same shape"
+    );
+    assert!(
+        is_contiguous(output.shape, output.strides),
+        "the output should be contiguous"
+    );
+
+    let num_elems = input.shape.iter().product::<usize>();
+    if num_elems == 0 {
+        return;
+    }
+
+    let contiguous = (!is_contiguous(input.shape, input.strides))
+        .then(|| into_contiguous::<R, I>(client, input));
+    let contiguous_ref = contiguous.as_ref().map(|tensor| tensor.as_ref());
+    let input = contiguous_ref.as_ref().unwrap_or(input);
+
+    let elem_in = I::as_type_native_unchecked();
+    let elem_out = O::as_type_native_unchecked();
+    let line_size_in = cast_line_size::<R>(input, elem_in);
+    let line_size_out = cast_line_size::<R>(output, elem_out);
+    let saturate = elem_in.is_float() && elem_out.is_int();
+
+    // Each unit casts as many elements as the largest line holds, a multiple of both line sizes.
+    let chunk_size = Ord::max(line_size_in, line_size_out) as usize;
+    let num_chunks = num_elems / chunk_size;
+    let tail_start = num_chunks * chunk_size;
+    let cube_dim = CubeDim::default();
+
+    if num_chunks > 0 {
+        unsafe {
+            cast_kernel::launch_unchecked::<I, O, R>(
+                client,
+                calculate_cube_count_elemwise(num_chunks, cube_dim),
+                cube_dim,
+                input.as_tensor_arg(line_size_in),
+                output.as_tensor_arg(line_size_out),
+                ScalarArg::new(0),
+                ScalarArg::new(num_chunks as u32),
+                saturate,
+            );
+        }
+    }
+
+    if tail_start < num_elems {
+        let num_tail = num_elems - tail_start;
+        unsafe {
+            cast_kernel::launch_unchecked::<I, O, R>(
+                client,
+                calculate_cube_count_elemwise(num_tail, cube_dim),
+                cube_dim,
+                input.as_tensor_arg(1),
+                output.as_tensor_arg(1),
+                ScalarArg::new(tail_start as u32),
+                ScalarArg::new(num_tail as u32),
+                saturate,
+            );
+        }
+    }
+}
+
+/// The largest line size of `elem` supported by the runtime whose lines start at the offset of the
+/// handle.
+fn cast_line_size<R: Runtime>(tensor: &TensorHandleRef<R>, elem: StorageType) -> u8 {
+    let offset = tensor.handle.offset_start.unwrap_or(0) as usize;
+    R::line_size_type(&elem)
+        .filter(|line_size| offset.is_multiple_of(*line_size as usize * elem.size()))
+        .max()
+        .unwrap_or(1)
+}
+
+/// Each unit casts a chunk of the largest line size of the contiguous `input` and `output`,
+/// starting at the element `offset`.
+///
+/// A line of the input is split into several lines of the output when it is larger, and several
+/// lines of the input are fused into a line of the output when it is smaller.
+#[cube(launch_unchecked)]
+fn cast_kernel<I: Numeric, O: Numeric>(
+    input: &Tensor<Line<I>>,
+    output: &mut Tensor<Line<O>>,
+    offset: u32,
+    num_chunks: u32,
+    #[comptime] saturate: bool,
+) {
+    if ABSOLUTE_POS >= num_chunks {
+        terminate!();
+    }
+
+    let line_size_in = input.line_size();
+    let line_size_out = output.line_size();
+    let chunk_size = comptime!(u32::max(line_size_in, line_size_out));
+    let start = offset + ABSOLUTE_POS * chunk_size;
+
+    if comptime!(line_size_in == line_size_out && !saturate) {
+        output[start / line_size_out] = Line::cast_from(input[start / line_size_in]);
+    } else if comptime!(line_size_in >= line_size_out) {
+        let line = input[start / line_size_in];
+        let num_writes = comptime!(line_size_in / line_size_out);
+
+        #[unroll]
+        for i in 0..num_writes {
+            let mut value = Line::<O>::empty(line_size_out);
+            #[unroll]
+            for j in 0..line_size_out {
+                value[j] = cast_element::<I, O>(line[i * line_size_out + j], saturate);
+            }
+            output[start / line_size_out + i] = value;
+        }
+    } else {
+        let mut value = Line::<O>::empty(line_size_out);
+        let num_reads = comptime!(line_size_out / line_size_in);
+
+        #[unroll]
+        for i in 0..num_reads {
+            let line = input[start / line_size_in + i];
+            #[unroll]
+            for j in 0..line_size_in {
+                value[i * line_size_in + j] = cast_element::<I, O>(line[j], saturate);
+            }
+        }
+        output[start / line_size_out] = value;
+    }
+}
+
+/// Cast a `value` to `O`, saturating a float to the range of an integer `O` when `saturate`.
+#[cube]
+fn cast_element<I: Numeric, O: Numeric>(value: I, #[comptime] saturate: bool) -> O {
+    if comptime!(saturate) {
+        // The bounds are rounded to the float, so a value beyond them can't be cast directly.
+        let min = I::cast_from(O::min_value());
+        let max = I::cast_from(O::max_value());
+        let cast = O::cast_from(Clamp::clamp(value, min, max));
+        let cast = select(value >= max, O::max_value(), cast);
+        let cast = select(value <= min, O::min_value(), cast);
+        // NaN is the only value that isn't equal to itself.
+        select(value == value, cast, O::from_int(0))
+    } else {
+        O::cast_from(value)
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     },
 };
 
-use super::TensorHandle;
+use super::{TensorHandle, transpose_ref};
 use cubecl::prelude::*;
 use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size_parallel};
 
@@ -149,11 +149,125 @@ fn into_contiguous_kernel_pack<N: CubePrimitive>(
     }
 }
 
+/// Each unit copies the line at its position, the input being in order.
+#[cube(launch_unchecked)]
+fn copy_kernel<N: CubePrimitive>(
+    input: &Tensor<Line<N>>,
+    output: &mut Tensor<Line<N>>,
+    num_lines: u32,
+) {
+    if ABSOLUTE_POS >= num_lines {
+        terminate!();
+    }
+
+    output[ABSOLUTE_POS] = input[ABSOLUTE_POS];
+}
+
+/// Each unit copies the line at its position of the contiguous `output` from a row of the `input`,
+/// whose last dimension is contiguous. Only the row is mapped to the strides of the `input`.
+#[cube(launch_unchecked)]
+fn into_contiguous_rows_kernel<N: CubePrimitive>(
+    input: &Tensor<Line<N>>,
+    output: &mut Tensor<Line<N>>,
+    num_lines: u32,
+    #[comptime] rank: u32,
+) {
+    if ABSOLUTE_POS >= num_lines {
+        terminate!();
+    }
+
+    let line_size = input.line_size();
+    let row_lines = input.shape(rank - 1) / line_size;
+    let mut row = ABSOLUTE_POS / row_lines;
+    let mut offset = (ABSOLUTE_POS % row_lines) * line_size;
+
+    #[unroll]
+    for i in 0..rank - 1 {
+        let dim = rank - 2 - i;
+        offset += (row % input.shape(dim)) * input.stride(dim);
+        row /= input.shape(dim);
+    }
+
+    output[ABSOLUTE_POS] = input[offset / line_size];
+}
+
+/// How [`into_contiguous_ref`] copies a tensor, from the pattern of its strides.
+///
+/// The pattern is found after dropping the dimensions of size one and merging the dimensions that
+/// are contiguous with each other, so a slice of a contiguous tensor along its first dimension is
+/// still [`ContiguousStrategy::Copy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContiguousStrategy {
+    /// The elements are already in order, and are copied with the widest lines.
+    Copy,
+    /// Only the last dimension is contiguous, and the rows are copied with lines.
+    Rows,
+    /// The last two dimensions are swapped, and the matrices are copied with the tiled [`transpose`](super::transpose).
+    Transpose,
+    /// Any other pattern, like a broadcast last dimension, gathered with the strides of every dimension.
+    Strided,
+}
+
+/// Find how [`into_contiguous_ref`] copies a tensor with the given shape and strides.
+pub fn contiguous_strategy(shape: &[usize], strides: &[usize]) -> ContiguousStrategy {
+    let (shape, strides) = merge_dims(shape, strides);
+    strategy_of_merged(&shape, &strides)
+}
+
+fn strategy_of_merged(shape: &[usize], strides: &[usize]) -> ContiguousStrategy {
+    let rank = shape.len();
+    if strides[rank - 1] == 1 {
+        match rank {
+            1 => ContiguousStrategy::Copy,
+            _ => ContiguousStrategy::Rows,
+        }
+    } else if rank >= 2 && strides[rank - 2] == 1 {
+        ContiguousStrategy::Transpose
+    } else {
+        ContiguousStrategy::Strided
+    }
+}
+
+/// Drop the dimensions of size one and merge each dimension with the next one when they are
+/// contiguous with each other. The merged shape keeps at least one dimension.
+fn merge_dims(shape: &[usize], strides: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut merged_shape: Vec<usize> = Vec::with_capacity(shape.len());
+    let mut merged_strides: Vec<usize> = Vec::with_capacity(shape.len());
+
+    for (&dim, &stride) in shape.iter().zip(strides) {
+        if dim == 1 {
+            continue;
+        }
+        match (merged_shape.last_mut(), merged_strides.last_mut()) {
+            (Some(last_dim), Some(last_stride)) if *last_stride == dim * stride => {
+                *last_dim *= dim;
+                *last_stride = stride;
+            }
+            _ => {
+                merged_shape.push(dim);
+                merged_strides.push(stride);
+            }
+        }
+    }
+
+    if merged_shape.is_empty() {
+        return (vec![1], vec![1]);
+    }
+    (merged_shape, merged_strides)
+}
+
 /// Make a jit tensor contiguous.
+///
+/// A tensor that is already contiguous is returned as is, sharing its handle. Use [`into_contiguous_ref`]
+/// with a new output to always copy it.
 pub fn into_contiguous<R: Runtime, E: CubePrimitive>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: &TensorHandleRef<'_, R>,
 ) -> TensorHandle<R, E> {
+    if is_contiguous(input.shape, input.strides) {
+        return TensorHandle::from_ref(input);
+    }
+
     let num_elems: usize = input.shape.iter().product();
 
     let handle = client.empty(num_elems * size_of::<E>());
@@ -181,13 +295,127 @@ pub fn into_contiguous_pitched<R: Runtime, E: CubePrimitive>(
     output
 }
 
-/// Make a jit tensor contiguous.
+/// Copy a jit tensor into the `output`, which is contiguous or pitched.
+///
+/// The copy depends on the [`ContiguousStrategy`] of the `input` when the `output` is contiguous.
 pub fn into_contiguous_ref<R: Runtime, E: CubePrimitive>(
     client: &ComputeClient<R::Server, R::Channel>,
     input: &TensorHandleRef<'_, R>,
     output: &TensorHandleRef<'_, R>,
 ) {
     let num_elems: usize = input.shape.iter().product();
+    if num_elems == 0 {
+        return;
+    }
+    if !is_contiguous(output.shape, output.strides) {
+        into_contiguous_strided::<R, E>(client, input, output);
+        return;
+    }
+
+    let (shape, strides) = merge_dims(input.shape, input.strides);
+    let output_strides = compact_strides(&shape);
+    let (input, output) = unsafe {
+        (
+            TensorHandleRef::<R>::from_raw_parts(input.handle, &strides, &shape, input.elem_size),
+            TensorHandleRef::<R>::from_raw_parts(
+                output.handle,
+                &output_strides,
+                &shape,
+                output.elem_size,
+            ),
+        )
+    };
+    let rank = shape.len();
+    let mut strategy = strategy_of_merged(&shape, &strides);
+    // The transpose launches a cube per tile of every matrix along a single dimension.
+    let num_matrices = shape[..rank.saturating_sub(2)].iter().product::<usize>();
+    if strategy == ContiguousStrategy::Transpose && num_matrices > R::max_cube_count().2 as usize {
+        strategy = ContiguousStrategy::Strided;
+    }
+
+    match strategy {
+        ContiguousStrategy::Copy | ContiguousStrategy::Rows => {
+            let line_size = tensor_line_size_parallel(
+                R::supported_line_sizes().iter().cloned(),
+                &shape,
+                &strides,
+                rank - 1,
+            );
+            let num_lines = num_elems / line_size as usize;
+            let cube_dim = CubeDim::default();
+            let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+            let input_arg = input.as_tensor_arg(line_size);
+            let output_arg = output.as_tensor_arg(line_size);
+            let num_lines = ScalarArg::new(num_lines as u32);
+
+            unsafe {
+                match rank {
+                    1 => copy_kernel::launch_unchecked::<E, R>(
+                        client, cube_count, cube_dim, input_arg, output_arg, num_lines,
+                    ),
+                    _ => into_contiguous_rows_kernel::launch_unchecked::<E, R>(
+                        client,
+                        cube_count,
+                        cube_dim,
+                        input_arg,
+                        output_arg,
+                        num_lines,
+                        rank as u32,
+                    ),
+                }
+            }
+        }
+        ContiguousStrategy::Transpose => {
+            // The swapped view has contiguous rows, which the transpose turns into the output.
+            let mut rows_shape = shape.clone();
+            let mut rows_strides = strides.clone();
+            rows_shape.swap(rank - 2, rank - 1);
+            rows_strides.swap(rank - 2, rank - 1);
+            let rows_view = unsafe {
+                TensorHandleRef::<R>::from_raw_parts(
+                    input.handle,
+                    &rows_strides,
+                    &rows_shape,
+                    input.elem_size,
+                )
+            };
+            transpose_ref::<R, E>(client, &rows_view, &output);
+        }
+        ContiguousStrategy::Strided => {
+            // The linear layouts don't map the strides of a single dimension.
+            let (shape, strides) = match rank {
+                1 => (vec![1, shape[0]], vec![shape[0] * strides[0], strides[0]]),
+                _ => (shape, strides),
+            };
+            let output_strides = compact_strides(&shape);
+            let (input, output) = unsafe {
+                (
+                    TensorHandleRef::<R>::from_raw_parts(
+                        input.handle,
+                        &strides,
+                        &shape,
+                        input.elem_size,
+                    ),
+                    TensorHandleRef::<R>::from_raw_parts(
+                        output.handle,
+                        &output_strides,
+                        &shape,
+                        output.elem_size,
+                    ),
+                )
+            };
+            into_contiguous_strided::<R, E>(client, &input, &output);
+        }
+    }
+}
+
+/// Gather the elements of the `input` with its strides into the contiguous or pitched `output`.
+fn into_contiguous_strided<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    output: &TensorHandleRef<'_, R>,
+) {
+    let num_elems: usize = input.shape.iter().product();
 
     // Vectorization is only enabled when the last dimension is contiguous.
     let rank = input.strides.len();
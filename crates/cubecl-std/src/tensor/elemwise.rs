@@ -0,0 +1,637 @@
+use cubecl::prelude::*;
+use cubecl_core::{
+    self as cubecl, LaunchShape, logical_cube_pos, next_logical_cube_pos, tensor_line_size_parallel,
+};
+
+use super::is_contiguous;
+
+/// A value computed by an [`ElemwiseGraph`], referring to a node of the [`ElemwiseBuilder`] that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElemwiseValue(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOp {
+    Neg,
+    Abs,
+    Exp,
+    Log,
+    Tanh,
+    Sqrt,
+    Recip,
+}
+
+/// The binary operations of an [`ElemwiseGraph`]. The comparisons produce masks of 1 and 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Powf,
+    Min,
+    Max,
+    Equal,
+    NotEqual,
+    Lower,
+    LowerEqual,
+    Greater,
+    GreaterEqual,
+}
+
+/// The element a value is rounded through by [`ElemwiseBuilder::cast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CastElem {
+    F16,
+    BF16,
+    F32,
+    /// Truncate toward zero to a signed integer.
+    I32,
+    /// Truncate toward zero to an unsigned integer.
+    U32,
+}
+
+/// A node of an [`ElemwiseGraph`], whose operands are the positions of previous nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElemwiseNode {
+    /// The element of the input tensor at the given position.
+    Input(u32),
+    /// The scalar at the given position.
+    Scalar(u32),
+    Unary {
+        op: UnaryOp,
+        input: u32,
+    },
+    Binary {
+        op: BinaryOp,
+        lhs: u32,
+        rhs: u32,
+    },
+    Clamp {
+        input: u32,
+        min: u32,
+        max: u32,
+    },
+    /// Select `on_true` where the `mask` isn't 0, and `on_false` elsewhere.
+    Select {
+        mask: u32,
+        on_true: u32,
+        on_false: u32,
+    },
+    Cast {
+        input: u32,
+        elem: CastElem,
+    },
+}
+
+/// The structure of an [`ElemwiseGraph`], without the values of its scalars.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ElemwiseProgram {
+    /// The nodes in topological order.
+    pub nodes: Vec<ElemwiseNode>,
+    /// The nodes written into the outputs.
+    pub outputs: Vec<u32>,
+    pub num_inputs: u32,
+}
+
+/// A chain of elementwise operations over input tensors and scalars, evaluated by a single kernel
+/// with [`fused_elemwise`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElemwiseGraph {
+    pub program: ElemwiseProgram,
+    pub scalars: Vec<f32>,
+}
+
+/// Record the operations of an [`ElemwiseGraph`].
+///
+/// Identical operations on the same values are recorded once, so the subexpressions shared by multiple outputs
+/// are computed once.
+///
+/// # Example
+///
+/// ```ignore
+/// // clamp(x * scale + bias, 0, 6), with `x` and `bias` the two inputs of the graph.
+/// let mut builder = ElemwiseBuilder::new();
+/// let x = builder.input();
+/// let bias = builder.input();
+/// let scale = builder.scalar(0.5);
+/// let scaled = builder.mul(x, scale);
+/// let shifted = builder.add(scaled, bias);
+/// let (min, max) = (builder.scalar(0.0), builder.scalar(6.0));
+/// let clamped = builder.clamp(shifted, min, max);
+/// builder.output(clamped);
+/// let graph = builder.build();
+/// ```
+#[derive(Debug, Default)]
+pub struct ElemwiseBuilder {
+    nodes: Vec<ElemwiseNode>,
+    outputs: Vec<u32>,
+    scalars: Vec<f32>,
+    num_inputs: u32,
+}
+
+impl ElemwiseBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The element of the next input tensor, in the order of the inputs of [`fused_elemwise`].
+    pub fn input(&mut self) -> ElemwiseValue {
+        self.num_inputs += 1;
+        self.push(ElemwiseNode::Input(self.num_inputs - 1))
+    }
+
+    /// A scalar constant broadcast to all the elements.
+    ///
+    /// The scalars are arguments of the kernel, so graphs only differing by their scalars share a kernel.
+    pub fn scalar(&mut self, value: f32) -> ElemwiseValue {
+        self.scalars.push(value);
+        self.push(ElemwiseNode::Scalar(self.scalars.len() as u32 - 1))
+    }
+
+    pub fn unary(&mut self, op: UnaryOp, input: ElemwiseValue) -> ElemwiseValue {
+        self.push(ElemwiseNode::Unary { op, input: input.0 })
+    }
+
+    pub fn binary(
+        &mut self,
+        op: BinaryOp,
+        lhs: ElemwiseValue,
+        rhs: ElemwiseValue,
+    ) -> ElemwiseValue {
+        self.push(ElemwiseNode::Binary {
+            op,
+            lhs: lhs.0,
+            rhs: rhs.0,
+        })
+    }
+
+    pub fn add(&mut self, lhs: ElemwiseValue, rhs: ElemwiseValue) -> ElemwiseValue {
+        self.binary(BinaryOp::Add, lhs, rhs)
+    }
+
+    pub fn sub(&mut self, lhs: ElemwiseValue, rhs: ElemwiseValue) -> ElemwiseValue {
+        self.binary(BinaryOp::Sub, lhs, rhs)
+    }
+
+    pub fn mul(&mut self, lhs: ElemwiseValue, rhs: ElemwiseValue) -> ElemwiseValue {
+        self.binary(BinaryOp::Mul, lhs, rhs)
+    }
+
+    pub fn div(&mut self, lhs: ElemwiseValue, rhs: ElemwiseValue) -> ElemwiseValue {
+        self.binary(BinaryOp::Div, lhs, rhs)
+    }
+
+    pub fn min(&mut self, lhs: ElemwiseValue, rhs: ElemwiseValue) -> ElemwiseValue {
+        self.binary(BinaryOp::Min, lhs, rhs)
+    }
+
+    pub fn max(&mut self, lhs: ElemwiseValue, rhs: ElemwiseValue) -> ElemwiseValue {
+        self.binary(BinaryOp::Max, lhs, rhs)
+    }
+
+    pub fn exp(&mut self, input: ElemwiseValue) -> ElemwiseValue {
+        self.unary(UnaryOp::Exp, input)
+    }
+
+    pub fn log(&mut self, input: ElemwiseValue) -> ElemwiseValue {
+        self.unary(UnaryOp::Log, input)
+    }
+
+    pub fn tanh(&mut self, input: ElemwiseValue) -> ElemwiseValue {
+        self.unary(UnaryOp::Tanh, input)
+    }
+
+    /// Compare the values, producing 1 where the comparison holds and 0 elsewhere.
+    pub fn compare(
+        &mut self,
+        op: BinaryOp,
+        lhs: ElemwiseValue,
+        rhs: ElemwiseValue,
+    ) -> ElemwiseValue {
+        assert!(
+            matches!(
+                op,
+                BinaryOp::Equal
+                    | BinaryOp::NotEqual
+                    | BinaryOp::Lower
+                    | BinaryOp::LowerEqual
+                    | BinaryOp::Greater
+                    | BinaryOp::GreaterEqual
+            ),
+            "op should be a comparison"
+        );
+        self.binary(op, lhs, rhs)
+    }
+
+    pub fn clamp(
+        &mut self,
+        input: ElemwiseValue,
+        min: ElemwiseValue,
+        max: ElemwiseValue,
+    ) -> ElemwiseValue {
+        self.push(ElemwiseNode::Clamp {
+            input: input.0,
+            min: min.0,
+            max: max.0,
+        })
+    }
+
+    /// Select `on_true` where the `mask` isn't 0, and `on_false` elsewhere.
+    pub fn select(
+        &mut self,
+        mask: ElemwiseValue,
+        on_true: ElemwiseValue,
+        on_false: ElemwiseValue,
+    ) -> ElemwiseValue {
+        self.push(ElemwiseNode::Select {
+            mask: mask.0,
+            on_true: on_true.0,
+            on_false: on_false.0,
+        })
+    }
+
+    /// Round the value through the given element, like casting it to `elem` and back.
+    pub fn cast(&mut self, input: ElemwiseValue, elem: CastElem) -> ElemwiseValue {
+        self.push(ElemwiseNode::Cast {
+            input: input.0,
+            elem,
+        })
+    }
+
+    /// Write the value into the next output tensor, in the order of the outputs of [`fused_elemwise`].
+    pub fn output(&mut self, value: ElemwiseValue) {
+        self.outputs.push(value.0);
+    }
+
+    pub fn build(self) -> ElemwiseGraph {
+        assert!(!self.outputs.is_empty(), "graph should have an output");
+        ElemwiseGraph {
+            program: ElemwiseProgram {
+                nodes: self.nodes,
+                outputs: self.outputs,
+                num_inputs: self.num_inputs,
+            },
+            scalars: self.scalars,
+        }
+    }
+
+    fn push(&mut self, node: ElemwiseNode) -> ElemwiseValue {
+        // The inputs and the scalars are always different values.
+        let reused = match node {
+            ElemwiseNode::Input(_) | ElemwiseNode::Scalar(_) => None,
+            _ => self.nodes.iter().position(|other| *other == node),
+        };
+        let position = reused.unwrap_or_else(|| {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        });
+        ElemwiseValue(position as u32)
+    }
+}
+
+/// Evaluate the `graph` for each element of the `outputs` in a single kernel.
+///
+/// The inputs are read as `I`, the graph is computed in `C` and the outputs are written as `O`.
+/// The outputs must be contiguous and have the same shape. The inputs are broadcast to that shape like in NumPy:
+/// their dimensions are aligned from the last one, and the missing dimensions or the dimensions of size 1 are
+/// repeated. The elements are vectorized along the last dimension of the outputs when all the inputs
+/// that aren't broadcast along it allow it.
+///
+/// The graph is a comptime argument of the kernel and its scalars are runtime arguments, so launching
+/// graphs with the same structure on the same element types and layouts reuses the compiled kernel.
+///
+/// The lines past the capacity of the grid of the device are computed in turn by the cubes launched.
+pub fn fused_elemwise<R: Runtime, I: Numeric, O: Numeric, C: Float>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    graph: &ElemwiseGraph,
+    inputs: &[TensorHandleRef<'_, R>],
+    outputs: &[TensorHandleRef<'_, R>],
+) {
+    let program = &graph.program;
+    assert_eq!(
+        inputs.len(),
+        program.num_inputs as usize,
+        "inputs should match the inputs of the graph"
+    );
+    assert_eq!(
+        outputs.len(),
+        program.outputs.len(),
+        "outputs should match the outputs of the graph"
+    );
+    let shape = outputs[0].shape;
+    let rank = shape.len();
+    for output in outputs {
+        assert_eq!(output.shape, shape, "outputs should have the same shape");
+        assert!(
+            is_contiguous(output.shape, output.strides),
+            "outputs should be contiguous"
+        );
+    }
+
+    let num_elems = shape.iter().product::<usize>();
+    if num_elems == 0 {
+        return;
+    }
+
+    // Each input is viewed with the shape of the outputs, with a stride of 0 for the broadcast dimensions.
+    let strides = inputs
+        .iter()
+        .map(|input| broadcast_strides(input.shape, input.strides, shape))
+        .collect::<Vec<_>>();
+    let views = inputs
+        .iter()
+        .zip(strides.iter())
+        .map(|(input, strides)| unsafe {
+            TensorHandleRef::<R>::from_raw_parts(input.handle, strides, shape, input.elem_size)
+        })
+        .collect::<Vec<_>>();
+
+    let output_strides = outputs[0].strides;
+    let layouts = views
+        .iter()
+        .map(|view| match view.strides {
+            strides if strides == output_strides => InputLayout::Contiguous,
+            strides if rank > 0 && strides[rank - 1] == 0 => InputLayout::BroadcastLast,
+            _ => InputLayout::Strided,
+        })
+        .collect::<Vec<_>>();
+
+    let input_line_sizes = R::line_size_type(&I::as_type_native_unchecked()).collect::<Vec<_>>();
+    let output_line_sizes = R::line_size_type(&O::as_type_native_unchecked()).collect::<Vec<_>>();
+    let line_size = match rank {
+        0 => 1,
+        _ => views
+            .iter()
+            .zip(layouts.iter())
+            .filter(|(_, layout)| **layout != InputLayout::BroadcastLast)
+            .map(|(view, _)| {
+                tensor_line_size_parallel(
+                    input_line_sizes.iter().cloned(),
+                    view.shape,
+                    view.strides,
+                    rank - 1,
+                )
+            })
+            .chain(outputs.iter().map(|output| {
+                tensor_line_size_parallel(
+                    output_line_sizes.iter().cloned(),
+                    output.shape,
+                    output.strides,
+                    rank - 1,
+                )
+            }))
+            .min()
+            .unwrap_or(1),
+    };
+
+    let mut input_args = SequenceArg::new();
+    for (view, layout) in views.iter().zip(layouts.iter()) {
+        let input_line_size = match layout {
+            InputLayout::BroadcastLast => 1,
+            _ => line_size,
+        };
+        input_args.push(view.as_tensor_arg(input_line_size));
+    }
+    let mut output_args = SequenceArg::new();
+    for output in outputs {
+        output_args.push(output.as_tensor_arg(line_size));
+    }
+    let mut scalar_args = SequenceArg::new();
+    for scalar in graph.scalars.iter() {
+        scalar_args.push(ScalarArg::new(C::new(*scalar)));
+    }
+
+    let num_lines = num_elems / line_size as usize;
+    let config = LaunchShape::elementwise(num_lines)
+        .round_robin()
+        .build::<R>()
+        .expect("the lines of the outputs should be indexable with 32-bit positions");
+
+    unsafe {
+        fused_elemwise_kernel::launch_unchecked::<I, O, C, R>(
+            client,
+            config.cube_count.clone(),
+            config.cube_dim,
+            input_args,
+            output_args,
+            scalar_args,
+            config.num_logical_cubes_arg(),
+            ElemwiseParams {
+                program: program.clone(),
+                layouts,
+                rank: rank as u32,
+                line_size: line_size as u32,
+            },
+        );
+    }
+}
+
+/// The strides of a tensor viewed with the given `shape`, 0 for the dimensions it's broadcast along.
+fn broadcast_strides(
+    input_shape: &[usize],
+    input_strides: &[usize],
+    shape: &[usize],
+) -> Vec<usize> {
+    assert!(
+        input_shape.len() <= shape.len(),
+        "inputs should not have more dimensions than the outputs"
+    );
+    let missing = shape.len() - input_shape.len();
+    (0..shape.len())
+        .map(|dim| match dim.checked_sub(missing) {
+            Some(input_dim) if input_shape[input_dim] == shape[dim] => input_strides[input_dim],
+            Some(input_dim) => {
+                assert!(
+                    input_shape[input_dim] == 1,
+                    "inputs should be broadcastable to the shape of the outputs"
+                );
+                0
+            }
+            None => 0,
+        })
+        .collect()
+}
+
+/// How an input is read for each line of the outputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputLayout {
+    /// The input has the layout of the outputs.
+    Contiguous,
+    /// The lines of the input are at broadcast or permuted positions.
+    Strided,
+    /// The input is broadcast along the last dimension, so each line repeats a single element.
+    BroadcastLast,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ElemwiseParams {
+    pub program: ElemwiseProgram,
+    pub layouts: Vec<InputLayout>,
+    pub rank: u32,
+    pub line_size: u32,
+}
+
+/// Evaluate the program for each line of the outputs of the logical cubes of the cube, out of the
+/// `num_cubes` of the launch.
+#[cube(launch_unchecked)]
+fn fused_elemwise_kernel<I: Numeric, O: Numeric, C: Float>(
+    inputs: &Sequence<Tensor<Line<I>>>,
+    outputs: &mut Sequence<Tensor<Line<O>>>,
+    scalars: &Sequence<C>,
+    num_cubes: u32,
+    #[comptime] params: ElemwiseParams,
+) {
+    let mut cube = logical_cube_pos(num_cubes);
+    while cube < num_cubes {
+        let pos = cube * CUBE_DIM + UNIT_POS;
+        if pos < outputs.index(0).len() {
+            eval_program::<I, O, C>(inputs, outputs, scalars, pos, comptime!(params.clone()));
+        }
+        cube = next_logical_cube_pos(cube, num_cubes);
+    }
+}
+
+/// Evaluate the nodes of the program in order for the line at `pos` of the outputs.
+#[cube]
+fn eval_program<I: Numeric, O: Numeric, C: Float>(
+    inputs: &Sequence<Tensor<Line<I>>>,
+    outputs: &mut Sequence<Tensor<Line<O>>>,
+    scalars: &Sequence<C>,
+    pos: u32,
+    #[comptime] params: ElemwiseParams,
+) {
+    let line_size = params.line_size;
+
+    let mut values = Sequence::<Line<C>>::new();
+    let mut node = comptime![0usize];
+
+    #[unroll]
+    for _ in 0..comptime!(params.program.nodes.len() as u32) {
+        let value = match comptime!(params.program.nodes[node]) {
+            ElemwiseNode::Input(input) => {
+                let layout = comptime!(params.layouts[input as usize]);
+                read_input::<I, C>(inputs.index(input), pos, layout, params.rank, line_size)
+            }
+            ElemwiseNode::Scalar(scalar) => Line::empty(line_size).fill(*scalars.index(scalar)),
+            ElemwiseNode::Unary { op, input } => {
+                apply_unary::<C>(*values.index(input), op, line_size)
+            }
+            ElemwiseNode::Binary { op, lhs, rhs } => {
+                apply_binary::<C>(*values.index(lhs), *values.index(rhs), op, line_size)
+            }
+            ElemwiseNode::Clamp { input, min, max } => Max::max(
+                Min::min(*values.index(input), *values.index(max)),
+                *values.index(min),
+            ),
+            ElemwiseNode::Select {
+                mask,
+                on_true,
+                on_false,
+            } => select_many(
+                values
+                    .index(mask)
+                    .not_equal(Line::empty(line_size).fill(C::new(0.0))),
+                *values.index(on_true),
+                *values.index(on_false),
+            ),
+            ElemwiseNode::Cast { input, elem } => apply_cast::<C>(*values.index(input), elem),
+        };
+        values.push(value);
+
+        comptime![node += 1;]
+    }
+
+    let mut output = comptime![0usize];
+
+    #[unroll]
+    for _ in 0..comptime!(params.program.outputs.len() as u32) {
+        let value = *values.index(comptime!(params.program.outputs[output]));
+        let tensor = outputs.index_mut(comptime!(output as u32));
+        tensor[pos] = Line::cast_from(value);
+
+        comptime![output += 1;]
+    }
+}
+
+#[cube]
+fn read_input<I: Numeric, C: Float>(
+    input: &Tensor<Line<I>>,
+    pos: u32,
+    #[comptime] layout: InputLayout,
+    #[comptime] rank: u32,
+    #[comptime] line_size: u32,
+) -> Line<C> {
+    if comptime!(layout == InputLayout::Contiguous) {
+        Line::cast_from(input[pos])
+    } else {
+        // The view of the input has the shape of the outputs, so its coordinates are the coordinates of the outputs.
+        let mut remainder = pos * line_size;
+        let mut offset = 0;
+        #[unroll]
+        for i in 0..rank {
+            let dim = rank - 1 - i;
+            offset += (remainder % input.shape(dim)) * input.stride(dim);
+            remainder /= input.shape(dim);
+        }
+
+        if comptime!(layout == InputLayout::BroadcastLast) {
+            Line::empty(line_size).fill(C::cast_from(input[offset][0]))
+        } else {
+            Line::cast_from(input[offset / line_size])
+        }
+    }
+}
+
+#[cube]
+fn apply_unary<C: Float>(
+    x: Line<C>,
+    #[comptime] op: UnaryOp,
+    #[comptime] line_size: u32,
+) -> Line<C> {
+    match comptime!(op) {
+        UnaryOp::Neg => Line::empty(line_size).fill(C::new(0.0)) - x,
+        UnaryOp::Abs => Abs::abs(x),
+        UnaryOp::Exp => Exp::exp(x),
+        UnaryOp::Log => Log::log(x),
+        UnaryOp::Tanh => Tanh::tanh(x),
+        UnaryOp::Sqrt => Sqrt::sqrt(x),
+        UnaryOp::Recip => Recip::recip(x),
+    }
+}
+
+#[cube]
+fn apply_binary<C: Float>(
+    lhs: Line<C>,
+    rhs: Line<C>,
+    #[comptime] op: BinaryOp,
+    #[comptime] line_size: u32,
+) -> Line<C> {
+    let one = Line::empty(line_size).fill(C::new(1.0));
+    let zero = Line::empty(line_size).fill(C::new(0.0));
+    match comptime!(op) {
+        BinaryOp::Add => lhs + rhs,
+        BinaryOp::Sub => lhs - rhs,
+        BinaryOp::Mul => lhs * rhs,
+        BinaryOp::Div => lhs / rhs,
+        BinaryOp::Powf => Powf::powf(lhs, rhs),
+        BinaryOp::Min => Min::min(lhs, rhs),
+        BinaryOp::Max => Max::max(lhs, rhs),
+        BinaryOp::Equal => select_many(lhs.equal(rhs), one, zero),
+        BinaryOp::NotEqual => select_many(lhs.not_equal(rhs), one, zero),
+        BinaryOp::Lower => select_many(lhs.less_than(rhs), one, zero),
+        BinaryOp::LowerEqual => select_many(lhs.less_equal(rhs), one, zero),
+        BinaryOp::Greater => select_many(lhs.greater_than(rhs), one, zero),
+        BinaryOp::GreaterEqual => select_many(lhs.greater_equal(rhs), one, zero),
+    }
+}
+
+#[cube]
+fn apply_cast<C: Float>(x: Line<C>, #[comptime] elem: CastElem) -> Line<C> {
+    match comptime!(elem) {
+        CastElem::F16 => Line::cast_from(Line::<half::f16>::cast_from(x)),
+        CastElem::BF16 => Line::cast_from(Line::<half::bf16>::cast_from(x)),
+        CastElem::F32 => Line::cast_from(Line::<f32>::cast_from(x)),
+        CastElem::I32 => Line::cast_from(Line::<i32>::cast_from(x)),
+        CastElem::U32 => Line::cast_from(Line::<u32>::cast_from(x)),
+    }
+}
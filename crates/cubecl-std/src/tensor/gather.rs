@@ -0,0 +1,316 @@
+use cubecl::prelude::*;
+use cubecl::{calculate_cube_count_elemwise, server::Handle};
+use cubecl_core as cubecl;
+
+use super::{TensorHandle, into_contiguous, is_contiguous};
+
+/// How [`gather`] and [`scatter_add`] handle the indices outside of the indexed axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IndexPolicy {
+    /// Clamp the indices to the first or the last position of the axis.
+    #[default]
+    Clamp,
+    /// Wrap the indices around the length of the axis.
+    Wrap,
+    /// Skip the out-of-range indices and raise the [`IndexFlag`] returned by the launch.
+    /// Gathered values at skipped indices are zero.
+    Check,
+}
+
+/// The handling of the indices of [`gather`] and [`scatter_add`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IndexOptions {
+    pub policy: IndexPolicy,
+    /// Count the negative indices from the end of the axis, like Python, before applying the policy.
+    pub negative_indices: bool,
+}
+
+impl IndexOptions {
+    /// Resolve the indices with the given `policy`, without negative indices.
+    pub fn new(policy: IndexPolicy) -> Self {
+        Self {
+            policy,
+            negative_indices: false,
+        }
+    }
+
+    /// Count the negative indices from the end of the axis.
+    pub fn with_negative_indices(mut self) -> Self {
+        self.negative_indices = true;
+        self
+    }
+}
+
+/// A flag raised on the device when an index is out of range with [`IndexPolicy::Check`].
+///
+/// Each launch returns its own flag, which is read with [`IndexFlag::out_of_range`] after the launch.
+pub struct IndexFlag {
+    pub handle: Handle,
+}
+
+impl IndexFlag {
+    /// A flag that isn't raised.
+    pub fn new<R: Runtime>(client: &ComputeClient<R::Server, R::Channel>) -> Self {
+        Self {
+            handle: client.create(u32::as_bytes(&[0])),
+        }
+    }
+
+    /// Whether any index was out of range, waiting for the launch to complete.
+    pub fn out_of_range<R: Runtime>(&self, client: &ComputeClient<R::Server, R::Channel>) -> bool {
+        let flag = client.read_one(self.handle.clone());
+        u32::from_bytes(&flag)[0] != 0
+    }
+
+    /// The flag as a `u32` tensor of a single element, raised by writing `1`.
+    pub fn as_arg<'a, R: Runtime>(&'a self) -> TensorArg<'a, R> {
+        unsafe { TensorArg::from_raw_parts::<u32>(&self.handle, &[1], &[1], 1) }
+    }
+}
+
+/// Select the slices of `input` at the `indices` along the given `axis`.
+///
+/// The output has the shape of `input` with the `axis` replaced by the shape of `indices`,
+/// so gathering the axis 0 of a `[vocabulary, dim]` table with `[batch, sequence]` indices looks up
+/// `[batch, sequence, dim]` embeddings. The lines are vectorized along the contiguous axes after `axis`.
+///
+/// The indices are `i32` and resolved with the given `options`.
+pub fn gather<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    indices: &TensorHandleRef<R>,
+    axis: usize,
+    options: IndexOptions,
+) -> (TensorHandle<R, E>, IndexFlag) {
+    assert!(
+        axis < input.shape.len(),
+        "axis should be smaller than the rank"
+    );
+    let (outer, length, inner) = split_shape(input.shape, axis);
+    let num_indices = indices.shape.iter().product::<usize>();
+    check_length(length, num_indices, options);
+
+    let mut shape = input.shape[..axis].to_vec();
+    shape.extend_from_slice(indices.shape);
+    shape.extend_from_slice(&input.shape[axis + 1..]);
+    let output = TensorHandle::<R, E>::empty(client, shape);
+    let flag = IndexFlag::new::<R>(client);
+
+    let num_elems = outer * num_indices * inner;
+    if num_elems == 0 {
+        return (output, flag);
+    }
+
+    let input = into_contiguous::<R, E>(client, input);
+    let indices = into_contiguous::<R, i32>(client, indices);
+    let line_size = R::line_size_type(&E::as_type_native_unchecked())
+        .filter(|line_size| inner.is_multiple_of(*line_size as usize))
+        .max()
+        .unwrap_or(1);
+
+    let num_lines = num_elems / line_size as usize;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        gather_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_arg(line_size),
+            indices.as_arg(1),
+            output.as_arg(line_size),
+            flag.as_arg(),
+            ScalarArg::new(length as u32),
+            ScalarArg::new((inner / line_size as usize) as u32),
+            options,
+        );
+    }
+
+    (output, flag)
+}
+
+/// Add the slices of `updates` to the slices of `target` at the `indices` along the given `axis`.
+///
+/// The `updates` have the shape of `target` with the `axis` replaced by the shape of `indices`.
+/// Duplicate indices add all their updates with atomics, in any order, so the client must support
+/// the atomic addition of `E`. The order changes the rounding of floats between runs,
+/// `cubecl_reduce::scatter_add_deterministic` adds them in the order of the indices instead.
+///
+/// The indices are `i32` and resolved with the given `options`. The `target` must be contiguous.
+pub fn scatter_add<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    target: &TensorHandleRef<R>,
+    indices: &TensorHandleRef<R>,
+    updates: &TensorHandleRef<R>,
+    axis: usize,
+    options: IndexOptions,
+) -> IndexFlag {
+    assert!(
+        client
+            .properties()
+            .type_usage(Atomic::<E>::as_type_native_unchecked())
+            .contains(TypeUsage::AtomicAdd),
+        "atomic add should be supported for {}",
+        E::as_type_native_unchecked()
+    );
+    let (outer, length, inner) = check_scatter_add(target, indices, updates, axis, options);
+    let num_indices = indices.shape.iter().product::<usize>();
+
+    let flag = IndexFlag::new::<R>(client);
+    let num_elems = outer * num_indices * inner;
+    if num_elems == 0 {
+        return flag;
+    }
+
+    let updates = into_contiguous::<R, E>(client, updates);
+    let indices = into_contiguous::<R, i32>(client, indices);
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_elems, cube_dim);
+
+    unsafe {
+        scatter_add_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            target.as_tensor_arg(1),
+            indices.as_arg(1),
+            updates.as_arg(1),
+            flag.as_arg(),
+            ScalarArg::new(length as u32),
+            ScalarArg::new(inner as u32),
+            options,
+        );
+    }
+
+    flag
+}
+
+/// Check the shapes of a [`scatter_add`] of `updates` to `target` at the `indices` along the
+/// `axis`, returning the number of elements of `target` before, along and after the `axis`.
+pub fn check_scatter_add<R: Runtime>(
+    target: &TensorHandleRef<R>,
+    indices: &TensorHandleRef<R>,
+    updates: &TensorHandleRef<R>,
+    axis: usize,
+    options: IndexOptions,
+) -> (usize, usize, usize) {
+    assert!(
+        axis < target.shape.len(),
+        "axis should be smaller than the rank"
+    );
+    assert!(
+        is_contiguous(target.shape, target.strides),
+        "target should be contiguous"
+    );
+    let (outer, length, inner) = split_shape(target.shape, axis);
+    let num_indices = indices.shape.iter().product::<usize>();
+    check_length(length, num_indices, options);
+
+    let mut expected_shape = target.shape[..axis].to_vec();
+    expected_shape.extend_from_slice(indices.shape);
+    expected_shape.extend_from_slice(&target.shape[axis + 1..]);
+    assert_eq!(
+        updates.shape,
+        &expected_shape[..],
+        "updates should have the shape of target with the axis replaced by the shape of indices"
+    );
+
+    (outer, length, inner)
+}
+
+/// The number of elements before, along and after the `axis`.
+fn split_shape(shape: &[usize], axis: usize) -> (usize, usize, usize) {
+    let outer = shape[..axis].iter().product::<usize>();
+    let inner = shape[axis + 1..].iter().product::<usize>();
+    (outer, shape[axis], inner)
+}
+
+fn check_length(length: usize, num_indices: usize, options: IndexOptions) {
+    assert!(
+        length <= i32::MAX as usize,
+        "the indexed axis should be addressable by i32 indices"
+    );
+    assert!(
+        length > 0 || num_indices == 0 || options.policy == IndexPolicy::Check,
+        "an empty axis can only be indexed with the check policy"
+    );
+}
+
+/// Each unit copies the line at its position of the contiguous `output`, seen as
+/// `[outer, num_indices, inner_lines]`, from the contiguous `input`, seen as `[outer, length, inner_lines]`.
+#[cube(launch_unchecked)]
+fn gather_kernel<E: Numeric>(
+    input: &Tensor<Line<E>>,
+    indices: &Tensor<i32>,
+    output: &mut Tensor<Line<E>>,
+    flag: &mut Tensor<u32>,
+    length: u32,
+    inner_lines: u32,
+    #[comptime] options: IndexOptions,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let inner = ABSOLUTE_POS % inner_lines;
+    let position = (ABSOLUTE_POS / inner_lines) % indices.len();
+    let outer = ABSOLUTE_POS / inner_lines / indices.len();
+
+    let index = resolve_index(indices[position], length, options);
+    if index == u32::MAX {
+        flag[0] = 1;
+        output[ABSOLUTE_POS] = Line::empty(output.line_size()).fill(E::from_int(0));
+    } else {
+        output[ABSOLUTE_POS] = input[(outer * length + index) * inner_lines + inner];
+    }
+}
+
+/// Each unit adds the element at its position of the contiguous `updates`, seen as
+/// `[outer, num_indices, inner]`, to the contiguous `target`, seen as `[outer, length, inner]`.
+#[cube(launch_unchecked)]
+fn scatter_add_kernel<E: Numeric>(
+    target: &mut Tensor<Atomic<E>>,
+    indices: &Tensor<i32>,
+    updates: &Tensor<E>,
+    flag: &mut Tensor<u32>,
+    length: u32,
+    inner: u32,
+    #[comptime] options: IndexOptions,
+) {
+    if ABSOLUTE_POS >= updates.len() {
+        terminate!();
+    }
+
+    let position = (ABSOLUTE_POS / inner) % indices.len();
+    let outer = ABSOLUTE_POS / inner / indices.len();
+
+    let index = resolve_index(indices[position], length, options);
+    if index == u32::MAX {
+        flag[0] = 1;
+    } else {
+        let offset = (outer * length + index) * inner + ABSOLUTE_POS % inner;
+        Atomic::add(&target[offset], updates[ABSOLUTE_POS]);
+    }
+}
+
+/// The position along an axis of `length` for the `index`, or `u32::MAX` when it is skipped.
+#[cube]
+pub fn resolve_index(index: i32, length: u32, #[comptime] options: IndexOptions) -> u32 {
+    let length = i32::cast_from(length);
+    let mut index = index;
+    if comptime!(options.negative_indices) {
+        index = select(index < 0, index + length, index);
+    }
+
+    if comptime!(options.policy == IndexPolicy::Clamp) {
+        u32::cast_from(Max::max(Min::min(index, length - 1), 0))
+    } else if comptime!(options.policy == IndexPolicy::Wrap) {
+        // The remainder of a negative index is negative.
+        let remainder = index % length;
+        u32::cast_from(select(remainder < 0, remainder + length, remainder))
+    } else {
+        let in_range = index >= 0 && index < length;
+        select(in_range, u32::cast_from(index), u32::MAX)
+    }
+}
@@ -1,7 +1,7 @@
 use core::marker::PhantomData;
 use cubecl_core::tensor_line_size_parallel;
 use cubecl_core::{Runtime, server};
-use cubecl_core::{calculate_cube_count_elemwise, server::Allocation};
+use cubecl_core::{calculate_cube_count_elemwise, server::Allocation, server::IoError};
 use cubecl_core::{prelude::*, server::CopyDescriptor};
 use cubecl_runtime::server::Handle;
 
@@ -67,10 +67,18 @@ where
     }
 
     pub fn empty(client: &ComputeClient<R::Server, R::Channel>, shape: Vec<usize>) -> Self {
+        Self::try_empty(client, shape).unwrap()
+    }
+
+    /// Create a new tensor, returning an error if it can't be allocated.
+    pub fn try_empty(
+        client: &ComputeClient<R::Server, R::Channel>,
+        shape: Vec<usize>,
+    ) -> Result<Self, IoError> {
         let elem_size = E::size().expect("To be a native type");
-        let Allocation { handle, strides } = client.empty_tensor(&shape, elem_size);
+        let Allocation { handle, strides } = client.try_empty_tensor(&shape, elem_size)?;
 
-        Self::new(handle, shape, strides)
+        Ok(Self::new(handle, shape, strides))
     }
 
     /// Create a new tensor.
@@ -157,6 +165,9 @@ where
         let num_elements: usize = shape.iter().product();
         let rank = shape.len();
         let output = Self::empty(client, shape);
+        if num_elements == 0 {
+            return output;
+        }
 
         let line_size = tensor_line_size_parallel(
             R::supported_line_sizes().iter().cloned(),
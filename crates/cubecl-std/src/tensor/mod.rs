@@ -1,12 +1,24 @@
+mod cast;
 mod contiguous;
+mod elemwise;
+mod gather;
 mod handle;
 pub mod identity;
 mod matrix_batch_layout;
+mod pad;
+mod slice;
+mod transpose;
 
+pub use cast::*;
 pub use contiguous::*;
+pub use elemwise::*;
+pub use gather::*;
 pub use handle::*;
 pub use identity::*;
 pub use matrix_batch_layout::*;
+pub use pad::*;
+pub use slice::*;
+pub use transpose::*;
 pub use view::*;
 
 pub mod layout;
@@ -0,0 +1,271 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size_parallel};
+
+use super::TensorHandle;
+
+/// The values of the positions added by [`pad`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PadValue {
+    #[default]
+    Zero,
+    /// A constant, cast to the element of the tensor.
+    Const(f32),
+    /// Mirror the tensor around its first and last positions, without repeating them, like the
+    /// `reflect` mode of NumPy. Along a dimension of size one, the position is repeated.
+    Reflect,
+    /// Repeat the first and last positions of the tensor.
+    Edge,
+}
+
+/// How the padded positions are mapped to the input, known at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PadMode {
+    Constant,
+    Reflect,
+    Edge,
+}
+
+/// Pad each dimension of `input` with `pads[dim] = (before, after)` positions filled with `value`,
+/// into a new contiguous tensor.
+///
+/// The input is copied in lines into the interior of the output, and only the positions added by
+/// the pads are computed one element at a time. Corners are mapped separately along each
+/// dimension, so a [reflected](PadValue::Reflect) corner is mirrored along both of its
+/// dimensions. Any strides are supported.
+///
+/// # Panics
+///
+/// When `input` is a scalar, when the number of pads isn't its rank, or when an empty dimension
+/// is padded by [reflecting](PadValue::Reflect) or [repeating](PadValue::Edge) the input.
+pub fn pad<R: Runtime, E: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    pads: &[(usize, usize)],
+    value: PadValue,
+) -> TensorHandle<R, E> {
+    let rank = input.shape.len();
+    assert!(rank > 0, "the tensor should have at least one dimension");
+    assert_eq!(pads.len(), rank, "there should be a pad for each dimension");
+    let (mode, constant) = match value {
+        PadValue::Zero => (PadMode::Constant, 0.0),
+        PadValue::Const(constant) => (PadMode::Constant, constant),
+        PadValue::Reflect => (PadMode::Reflect, 0.0),
+        PadValue::Edge => (PadMode::Edge, 0.0),
+    };
+    if mode != PadMode::Constant {
+        assert!(
+            input
+                .shape
+                .iter()
+                .zip(pads)
+                .all(|(size, (before, after))| *size > 0 || before + after == 0),
+            "an empty dimension can only be padded with a constant"
+        );
+    }
+
+    let shape = input
+        .shape
+        .iter()
+        .zip(pads)
+        .map(|(size, (before, after))| before + size + after)
+        .collect::<Vec<_>>();
+    let num_elems = shape.iter().product::<usize>();
+    let output = TensorHandle::<R, E>::new_contiguous(
+        shape.clone(),
+        client.empty(num_elems * size_of::<E>()),
+    );
+    if num_elems == 0 {
+        return output;
+    }
+
+    let pads_flat = pads
+        .iter()
+        .flat_map(|(before, after)| [*before as u32, *after as u32])
+        .collect::<Vec<_>>();
+    let pads_handle = client.create(u32::as_bytes(&pads_flat));
+    let pads_arg =
+        || unsafe { TensorArg::from_raw_parts::<u32>(&pads_handle, &[1], &[2 * rank], 1) };
+
+    let input_elems = input.shape.iter().product::<usize>();
+    if input_elems > 0 {
+        // The lines of the interior must start at a line of the output.
+        let (before_last, _) = pads[rank - 1];
+        let supported = R::line_size_type(&E::as_type_native_unchecked()).filter(|line_size| {
+            before_last.is_multiple_of(*line_size as usize)
+                && shape[rank - 1].is_multiple_of(*line_size as usize)
+        });
+        let line_size = tensor_line_size_parallel(supported, input.shape, input.strides, rank - 1);
+        let num_lines = input_elems / line_size as usize;
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+        unsafe {
+            pad_interior_kernel::launch_unchecked::<E, R>(
+                client,
+                cube_count,
+                cube_dim,
+                input.as_tensor_arg(line_size),
+                output.as_arg(line_size),
+                pads_arg(),
+                ScalarArg::new(num_lines as u32),
+                rank as u32,
+            );
+        }
+    }
+
+    // The border is split in a slab per dimension, the positions padded along that dimension
+    // within the interior of the previous dimensions.
+    let mut slab_ends = Vec::with_capacity(rank);
+    let mut num_border = 0;
+    for dim in 0..rank {
+        let (before, after) = pads[dim];
+        num_border += input.shape[..dim].iter().product::<usize>()
+            * (before + after)
+            * shape[dim + 1..].iter().product::<usize>();
+        slab_ends.push(num_border as u32);
+    }
+    if num_border == 0 {
+        return output;
+    }
+
+    let slab_ends_handle = client.create(u32::as_bytes(&slab_ends));
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_border, cube_dim);
+
+    unsafe {
+        pad_border_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            output.as_arg(1),
+            pads_arg(),
+            TensorArg::from_raw_parts::<u32>(&slab_ends_handle, &[1], &[rank], 1),
+            ScalarArg::new(num_border as u32),
+            ScalarArg::new(constant),
+            rank as u32,
+            mode,
+        );
+    }
+
+    output
+}
+
+/// Each unit copies the line at its position of the `input`, in order, to the interior of the
+/// contiguous `output`.
+#[cube(launch_unchecked)]
+fn pad_interior_kernel<E: CubePrimitive>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Line<E>>,
+    pads: &Tensor<u32>,
+    num_lines: u32,
+    #[comptime] rank: u32,
+) {
+    if ABSOLUTE_POS >= num_lines {
+        terminate!();
+    }
+
+    let line_size = input.line_size();
+    let mut remainder = ABSOLUTE_POS * line_size;
+    let mut input_offset = 0;
+    let mut output_offset = 0;
+
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        let coordinate = remainder % input.shape(dim);
+        remainder /= input.shape(dim);
+
+        input_offset += coordinate * input.stride(dim);
+        output_offset += (coordinate + pads[2 * dim]) * output.stride(dim);
+    }
+
+    output[output_offset / line_size] = input[input_offset / line_size];
+}
+
+/// Each unit computes an element of the border of the contiguous `output`.
+///
+/// The slab of dimension `d` holds the positions padded along `d`, with the dimensions before `d`
+/// in the interior and the ones after `d` anywhere in the output. The slabs don't overlap and
+/// together cover the border.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn pad_border_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    output: &mut Tensor<E>,
+    pads: &Tensor<u32>,
+    slab_ends: &Tensor<u32>,
+    num_border: u32,
+    constant: f32,
+    #[comptime] rank: u32,
+    #[comptime] mode: PadMode,
+) {
+    if ABSOLUTE_POS >= num_border {
+        terminate!();
+    }
+
+    let mut slab = 0;
+    let mut slab_start = 0;
+    #[unroll]
+    for dim in 0..rank - 1 {
+        if ABSOLUTE_POS >= slab_ends[dim] {
+            slab = dim + 1;
+            slab_start = slab_ends[dim];
+        }
+    }
+
+    let mut remainder = ABSOLUTE_POS - slab_start;
+    let mut input_offset = 0;
+    let mut output_offset = 0;
+
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        let before = pads[2 * dim];
+        let length = input.shape(dim);
+
+        let mut coordinate = 0;
+        if dim > slab {
+            coordinate = remainder % output.shape(dim);
+            remainder /= output.shape(dim);
+        } else if dim == slab {
+            let num_padded = before + pads[2 * dim + 1];
+            let position = remainder % num_padded;
+            remainder /= num_padded;
+            coordinate = select(position < before, position, position + length);
+        } else {
+            coordinate = remainder % length + before;
+            remainder /= length;
+        }
+        output_offset += coordinate * output.stride(dim);
+
+        if comptime!(mode != PadMode::Constant) {
+            let source = source_position(
+                i32::cast_from(coordinate) - i32::cast_from(before),
+                i32::cast_from(length),
+                mode,
+            );
+            input_offset += source * input.stride(dim);
+        }
+    }
+
+    if comptime!(mode == PadMode::Constant) {
+        output[output_offset] = E::cast_from(constant);
+    } else {
+        output[output_offset] = input[input_offset];
+    }
+}
+
+/// The position of the input along a dimension of `length` for the `position` relative to its
+/// start, which may be before or after it.
+#[cube]
+fn source_position(position: i32, length: i32, #[comptime] mode: PadMode) -> u32 {
+    if comptime!(mode == PadMode::Edge) {
+        u32::cast_from(Max::max(Min::min(position, length - 1), 0))
+    } else {
+        // The reflection has a period of twice the length without the first and last positions.
+        let period = Max::max(2 * length - 2, 1);
+        let position = select(position < 0, -position, position) % period;
+        u32::cast_from(select(position >= length, period - position, position))
+    }
+}
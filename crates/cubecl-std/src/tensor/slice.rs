@@ -0,0 +1,117 @@
+use cubecl::prelude::*;
+use cubecl_core::{self as cubecl, calculate_cube_count_elemwise, tensor_line_size_parallel};
+
+use super::{TensorHandle, into_contiguous_ref};
+
+/// Copy the positions `starts[dim]..ends[dim]` of each dimension of `input` into a new contiguous
+/// tensor, cropping it.
+///
+/// A slice starting at the first position of every dimension is a view of the input with a
+/// smaller shape, copied with [`into_contiguous_ref`] and its fast paths. Other slices are copied
+/// with the strides of the input, in lines along the last dimension when the input is contiguous
+/// along it.
+///
+/// # Panics
+///
+/// When the number of starts or ends isn't the rank of `input`, or when a range isn't within its
+/// dimension.
+pub fn slice<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<R>,
+    starts: &[usize],
+    ends: &[usize],
+) -> TensorHandle<R, E> {
+    let rank = input.shape.len();
+    assert!(rank > 0, "the tensor should have at least one dimension");
+    assert!(
+        starts.len() == rank && ends.len() == rank,
+        "there should be a start and an end for each dimension"
+    );
+    for dim in 0..rank {
+        assert!(
+            starts[dim] <= ends[dim] && ends[dim] <= input.shape[dim],
+            "the range {}..{} should be within the dimension {dim} of size {}",
+            starts[dim],
+            ends[dim],
+            input.shape[dim]
+        );
+    }
+
+    let shape = starts
+        .iter()
+        .zip(ends)
+        .map(|(start, end)| end - start)
+        .collect::<Vec<_>>();
+    let num_elems = shape.iter().product::<usize>();
+    let output = TensorHandle::<R, E>::new_contiguous(
+        shape.clone(),
+        client.empty(num_elems * size_of::<E>()),
+    );
+    if num_elems == 0 {
+        return output;
+    }
+
+    let view = unsafe {
+        TensorHandleRef::<R>::from_raw_parts(input.handle, input.strides, &shape, input.elem_size)
+    };
+    if starts.iter().all(|start| *start == 0) {
+        into_contiguous_ref::<R, E>(client, &view, &output.as_ref());
+        return output;
+    }
+
+    // The lines must start at a line of the input.
+    let offset = starts
+        .iter()
+        .zip(input.strides)
+        .map(|(start, stride)| start * stride)
+        .sum::<usize>();
+    let supported = R::line_size_type(&E::as_type_native_unchecked())
+        .filter(|line_size| offset.is_multiple_of(*line_size as usize));
+    let line_size = tensor_line_size_parallel(supported, &shape, input.strides, rank - 1);
+    let num_lines = num_elems / line_size as usize;
+    let cube_dim = CubeDim::default();
+    let cube_count = calculate_cube_count_elemwise(num_lines, cube_dim);
+
+    unsafe {
+        slice_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            view.as_tensor_arg(line_size),
+            output.as_arg(line_size),
+            ScalarArg::new(offset as u32),
+            ScalarArg::new(num_lines as u32),
+            rank as u32,
+        );
+    }
+
+    output
+}
+
+/// Each unit copies the line at its position of the contiguous `output` from the `input`, seen
+/// with the shape of the slice and starting at `offset`.
+#[cube(launch_unchecked)]
+fn slice_kernel<E: CubePrimitive>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Line<E>>,
+    offset: u32,
+    num_lines: u32,
+    #[comptime] rank: u32,
+) {
+    if ABSOLUTE_POS >= num_lines {
+        terminate!();
+    }
+
+    let line_size = input.line_size();
+    let mut remainder = ABSOLUTE_POS * line_size;
+    let mut input_offset = offset;
+
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - 1 - i;
+        input_offset += (remainder % input.shape(dim)) * input.stride(dim);
+        remainder /= input.shape(dim);
+    }
+
+    output[ABSOLUTE_POS] = input[input_offset / line_size];
+}
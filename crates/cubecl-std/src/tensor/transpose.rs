@@ -0,0 +1,173 @@
+use cubecl::prelude::*;
+use cubecl::tensor_line_size_parallel;
+use cubecl_core as cubecl;
+
+use super::{TensorHandle, into_contiguous, is_contiguous};
+
+/// The number of rows and columns of the tile transposed by a cube.
+const TILE_SIZE: u32 = 32;
+/// The number of tile rows covered by the units of a cube at once.
+const TILE_ROWS: u32 = 8;
+
+/// Transpose the last two dimensions of `input` into a new contiguous tensor.
+///
+/// The leading dimensions are batch dimensions, and keep their order.
+pub fn transpose<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+) -> TensorHandle<R, E> {
+    let rank = input.shape.len();
+    assert!(rank >= 2, "input should have at least two dimensions");
+
+    let mut shape = input.shape.to_vec();
+    shape.swap(rank - 2, rank - 1);
+    let output = TensorHandle::empty(client, shape);
+
+    transpose_ref::<R, E>(client, input, &output.as_ref());
+
+    output
+}
+
+/// Transpose the last two dimensions of `input` into the contiguous `output`.
+///
+/// Each cube copies a 32×32 tile of a matrix through shared memory, padded by one column so the columns
+/// of the tile are read from different banks. The loads are vectorized along the rows of `input` and the
+/// stores along the rows of `output` when their lengths allow it.
+pub fn transpose_ref<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+    output: &TensorHandleRef<'_, R>,
+) {
+    let rank = input.shape.len();
+    assert!(rank >= 2, "input should have at least two dimensions");
+    let mut expected_shape = input.shape.to_vec();
+    expected_shape.swap(rank - 2, rank - 1);
+    assert_eq!(
+        output.shape,
+        &expected_shape[..],
+        "output should have the shape of input with the last two dimensions swapped"
+    );
+    assert!(
+        is_contiguous(output.shape, output.strides),
+        "output should be contiguous"
+    );
+
+    let rows = input.shape[rank - 2];
+    let cols = input.shape[rank - 1];
+    let num_batches = input.shape[..rank - 2].iter().product::<usize>();
+    if rows * cols * num_batches == 0 {
+        return;
+    }
+
+    let input_line_size = tensor_line_size_parallel(
+        R::supported_line_sizes().iter().cloned(),
+        input.shape,
+        input.strides,
+        rank - 1,
+    );
+    let output_line_size = tensor_line_size_parallel(
+        R::supported_line_sizes().iter().cloned(),
+        output.shape,
+        output.strides,
+        rank - 1,
+    );
+
+    let cube_count = CubeCount::Static(
+        cols.div_ceil(TILE_SIZE as usize) as u32,
+        rows.div_ceil(TILE_SIZE as usize) as u32,
+        num_batches as u32,
+    );
+    let cube_dim = CubeDim::new_2d(TILE_SIZE, TILE_ROWS);
+    if let CubeCount::Static(x, y, z) = cube_count {
+        let (max_x, max_y, max_z) = R::max_cube_count();
+        assert!(
+            x <= max_x && y <= max_y && z <= max_z,
+            "input has too many tiles to transpose in a single launch"
+        );
+    }
+
+    unsafe {
+        transpose_kernel::launch_unchecked::<E, R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(input_line_size),
+            output.as_tensor_arg(output_line_size),
+            input_line_size as u32,
+            output_line_size as u32,
+        );
+    }
+}
+
+/// Make a matrix batch contiguous, with the tiled [`transpose`] when its last two dimensions are swapped,
+/// like a column-major matrix.
+///
+/// This is [`into_contiguous`], which finds the swapped dimensions itself.
+pub fn into_contiguous_matrix<R: Runtime, E: CubePrimitive>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    input: &TensorHandleRef<'_, R>,
+) -> TensorHandle<R, E> {
+    into_contiguous::<R, E>(client, input)
+}
+
+#[cube(launch_unchecked)]
+fn transpose_kernel<E: CubePrimitive>(
+    input: &Tensor<Line<E>>,
+    output: &mut Tensor<Line<E>>,
+    #[comptime] input_line_size: u32,
+    #[comptime] output_line_size: u32,
+) {
+    let rank = input.rank();
+    let rows = input.shape(rank - 2);
+    let cols = input.shape(rank - 1);
+
+    // The cubes along z transpose the matrices of the batch, in row-major order.
+    let mut input_offset = 0;
+    let mut remainder = CUBE_POS_Z;
+    for i in 0..rank - 2 {
+        let dim = rank - 3 - i;
+        input_offset += (remainder % input.shape(dim)) * input.stride(dim);
+        remainder /= input.shape(dim);
+    }
+    let output_offset = CUBE_POS_Z * rows * cols;
+
+    let row_start = CUBE_POS_Y * TILE_SIZE;
+    let col_start = CUBE_POS_X * TILE_SIZE;
+    let tile_stride = comptime!(TILE_SIZE + 1);
+    let mut tile = SharedMemory::<E>::new(comptime!(TILE_SIZE * (TILE_SIZE + 1)));
+
+    // Consecutive units load consecutive lines of a row of the input.
+    let input_lines = comptime!(TILE_SIZE / input_line_size);
+    for i in range_stepped(UNIT_POS, comptime!(TILE_SIZE * input_lines), CUBE_DIM) {
+        let r = i / input_lines;
+        let c = (i % input_lines) * input_line_size;
+        let row = row_start + r;
+        let col = col_start + c;
+        if row < rows && col < cols {
+            let index = input_offset + row * input.stride(rank - 2) + col * input.stride(rank - 1);
+            let line = input[index / input_line_size];
+            #[unroll]
+            for k in 0..input_line_size {
+                tile[r * tile_stride + c + k] = line[k];
+            }
+        }
+    }
+    sync_cube();
+
+    // Consecutive units store consecutive lines of a row of the output, reading a column of the tile.
+    let output_lines = comptime!(TILE_SIZE / output_line_size);
+    for i in range_stepped(UNIT_POS, comptime!(TILE_SIZE * output_lines), CUBE_DIM) {
+        let c = i / output_lines;
+        let r = (i % output_lines) * output_line_size;
+        let output_row = col_start + c;
+        let output_col = row_start + r;
+        if output_row < cols && output_col < rows {
+            let mut line = Line::empty(output_line_size);
+            #[unroll]
+            for k in 0..output_line_size {
+                line[k] = tile[(r + k) * tile_stride + c];
+            }
+            output[(output_offset + output_row * rows + output_col) / output_line_size] = line;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime, TensorHandleRef},
+};
+use half::{bf16, f16};
+
+use crate::tensor::{self, is_contiguous};
+
+use super::contiguous::{into_contiguous_cpu, permuted};
+
+/// An element whose casts can be computed on the CPU.
+pub trait CastElement: Numeric + CubeElement + core::fmt::Debug {
+    fn into_f64(self) -> f64;
+    /// Round to the nearest float, or toward zero and saturating to the range of an integer,
+    /// like `as`.
+    fn saturating_from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_cast_element {
+    ($($ty:ty),*) => {
+        $(#[allow(clippy::unnecessary_cast)]
+        impl CastElement for $ty {
+            fn into_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn saturating_from_f64(value: f64) -> Self {
+                value as $ty
+            }
+        })*
+    };
+}
+
+impl_cast_element!(u8, i8, u32, i32, f32, f64);
+
+impl CastElement for f16 {
+    fn into_f64(self) -> f64 {
+        f16::to_f64(self)
+    }
+
+    fn saturating_from_f64(value: f64) -> Self {
+        f16::from_f64(value)
+    }
+}
+
+impl CastElement for bf16 {
+    fn into_f64(self) -> f64 {
+        bf16::to_f64(self)
+    }
+
+    fn saturating_from_f64(value: f64) -> Self {
+        bf16::from_f64(value)
+    }
+}
+
+/// Values with a fraction, beyond the range of each integer, infinite or NaN.
+const FLOAT_VALUES: [f64; 24] = [
+    0.0,
+    1.0,
+    -1.0,
+    0.75,
+    -0.75,
+    2.5,
+    -2.5,
+    100.5,
+    127.0,
+    128.0,
+    -128.0,
+    -129.0,
+    255.0,
+    256.0,
+    1000.25,
+    -1000.25,
+    70000.0,
+    3.0e9,
+    -3.0e9,
+    5.0e10,
+    -5.0e10,
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+    f64::NAN,
+];
+
+/// Values cycling through [`FLOAT_VALUES`] for floats, and through the integers representable by
+/// each element for integers.
+fn cast_input<I: CastElement>(num_elems: usize) -> Vec<I> {
+    let is_float = I::as_type_native_unchecked().is_float();
+    (0..num_elems)
+        .map(|i| match is_float {
+            true => I::saturating_from_f64(FLOAT_VALUES[i % FLOAT_VALUES.len()]),
+            false => I::saturating_from_f64((i % 128) as f64),
+        })
+        .collect()
+}
+
+fn cast_cpu<I: CastElement, O: CastElement>(data: &[I]) -> Vec<O> {
+    data.iter()
+        .map(|value| O::saturating_from_f64(value.into_f64()))
+        .collect()
+}
+
+fn is_supported<R: Runtime, E: Numeric>(device: &R::Device) -> bool {
+    let supported = R::client(device)
+        .properties()
+        .supports_type(E::as_type_native_unchecked());
+    if !supported {
+        println!("{} not supported - skipped", E::as_type_native_unchecked());
+    }
+    supported
+}
+
+/// Cast the view with the given `shape` and `strides`, and compare it with the tensor cast on
+/// the CPU.
+fn test_cast_view<R: Runtime, I: CastElement, O: CastElement>(
+    device: &R::Device,
+    shape: &[usize],
+    strides: &[usize],
+) {
+    let client = R::client(device);
+    let num_elems = shape.iter().product::<usize>();
+    let input = cast_input::<I>(num_elems);
+    let expected = cast_cpu::<I, O>(&into_contiguous_cpu(&input, shape, strides));
+
+    let handle = client.create(I::as_bytes(&input));
+    let output = tensor::cast::<R, I, O>(&client, unsafe {
+        &TensorHandleRef::from_raw_parts(&handle, strides, shape, size_of::<I>())
+    });
+    assert_eq!(output.shape, shape);
+    assert!(is_contiguous(&output.shape, &output.strides));
+
+    let actual = client.read_one(output.handle);
+    let actual = O::from_bytes(&actual);
+    for (i, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        let (actual, expected) = (actual.into_f64(), expected.into_f64());
+        assert!(
+            actual == expected || (actual.is_nan() && expected.is_nan()),
+            "Values differ at {i}: actual={actual}, expected={expected}"
+        );
+    }
+}
+
+/// Cast contiguous tensors of lengths with and without elements left over after the lines.
+pub fn test_cast<R: Runtime, I: CastElement, O: CastElement>(device: &R::Device) {
+    if !is_supported::<R, I>(device) || !is_supported::<R, O>(device) {
+        return;
+    }
+    for num_elems in [1, 5, 16, 67, 1024, 4099] {
+        test_cast_view::<R, I, O>(device, &[num_elems], &[1]);
+    }
+}
+
+/// Cast a transposed tensor, made contiguous before the cast.
+pub fn test_cast_permuted<R: Runtime, I: CastElement, O: CastElement>(device: &R::Device) {
+    if !is_supported::<R, I>(device) || !is_supported::<R, O>(device) {
+        return;
+    }
+    let (shape, strides) = permuted(&[6, 5, 32], &[2, 0, 1]);
+    test_cast_view::<R, I, O>(device, &shape, &strides);
+}
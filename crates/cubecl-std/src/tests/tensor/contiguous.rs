@@ -0,0 +1,136 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime, TensorHandleRef},
+};
+
+use crate::tensor::{self, ContiguousStrategy, contiguous_strategy, is_contiguous};
+
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+/// The shape and strides of the contiguous tensor of `shape` with its dimensions permuted, so the
+/// dimension `i` of the view is the dimension `permutation[i]` of the tensor.
+pub fn permuted(shape: &[usize], permutation: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let strides = contiguous_strides(shape);
+    permutation
+        .iter()
+        .map(|&dim| (shape[dim], strides[dim]))
+        .unzip()
+}
+
+/// The elements of the view in row-major order.
+pub(crate) fn into_contiguous_cpu<E: Copy>(
+    data: &[E],
+    shape: &[usize],
+    strides: &[usize],
+) -> Vec<E> {
+    let num_elems = shape.iter().product::<usize>();
+    (0..num_elems)
+        .map(|index| {
+            let mut remainder = index;
+            let mut offset = 0;
+            for (dim, stride) in shape.iter().zip(strides).rev() {
+                offset += (remainder % dim) * stride;
+                remainder /= dim;
+            }
+            data[offset]
+        })
+        .collect()
+}
+
+/// Values that wrap around the range of the smallest element types.
+fn test_values<E: Numeric>(num_elems: usize) -> Vec<E> {
+    (0..num_elems)
+        .map(|i| E::from_int((i % 251) as i64))
+        .collect()
+}
+
+/// Make the view with the given `shape` and `strides` contiguous, and compare it with the elements
+/// rearranged on the CPU.
+pub fn test_into_contiguous<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+    strides: &[usize],
+) {
+    let client = R::client(device);
+    let buffer_len = match shape.contains(&0) {
+        true => 0,
+        false => {
+            1 + shape
+                .iter()
+                .zip(strides)
+                .map(|(dim, stride)| (dim - 1) * stride)
+                .sum::<usize>()
+        }
+    };
+    let input = test_values::<E>(buffer_len);
+    let expected = into_contiguous_cpu(&input, shape, strides);
+
+    let handle = client.create(E::as_bytes(&input));
+    let output = tensor::into_contiguous::<R, E>(&client, unsafe {
+        &TensorHandleRef::from_raw_parts(&handle, strides, shape, size_of::<E>())
+    });
+
+    assert_eq!(output.shape, shape);
+    assert!(is_contiguous(&output.shape, &output.strides));
+    if expected.is_empty() {
+        return;
+    }
+    let actual = client.read_one(output.handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "contiguous values differ"
+    );
+}
+
+/// A contiguous tensor is returned without a copy.
+pub fn test_into_contiguous_shares_contiguous<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let shape = [4, 33, 64];
+    let strides = contiguous_strides(&shape);
+    let handle = client.create(f32::as_bytes(&test_values::<f32>(4 * 33 * 64)));
+
+    let output = tensor::into_contiguous::<R, f32>(&client, unsafe {
+        &TensorHandleRef::from_raw_parts(&handle, &strides, &shape, size_of::<f32>())
+    });
+
+    assert!(!output.can_mut(), "the handle should be shared");
+    assert_eq!(output.strides, strides);
+}
+
+pub fn test_contiguous_strategies() {
+    let strategy = |shape: &[usize], strides: &[usize]| contiguous_strategy(shape, strides);
+
+    assert_eq!(strategy(&[], &[]), ContiguousStrategy::Copy);
+    assert_eq!(
+        strategy(&[4, 33, 64], &[2112, 64, 1]),
+        ContiguousStrategy::Copy
+    );
+    // Dimensions of size one have any stride.
+    assert_eq!(
+        strategy(&[1, 5, 1, 7], &[999, 7, 999, 1]),
+        ContiguousStrategy::Copy
+    );
+    // Rows of 30 elements in rows of 32.
+    assert_eq!(strategy(&[8, 30], &[32, 1]), ContiguousStrategy::Rows);
+    let (shape, strides) = permuted(&[3, 2, 4, 8], &[1, 0, 2, 3]);
+    assert_eq!(strategy(&shape, &strides), ContiguousStrategy::Rows);
+    assert_eq!(strategy(&[4, 5, 6], &[0, 6, 1]), ContiguousStrategy::Rows);
+
+    let (shape, strides) = permuted(&[2, 36, 100], &[0, 2, 1]);
+    assert_eq!(strategy(&shape, &strides), ContiguousStrategy::Transpose);
+    // The dimensions contiguous with each other are merged into the rows of the transpose.
+    let (shape, strides) = permuted(&[6, 2, 3, 5], &[3, 0, 1, 2]);
+    assert_eq!(strategy(&shape, &strides), ContiguousStrategy::Transpose);
+
+    let (shape, strides) = permuted(&[3, 2, 3, 2, 4, 2], &[5, 3, 1, 4, 2, 0]);
+    assert_eq!(strategy(&shape, &strides), ContiguousStrategy::Strided);
+    assert_eq!(strategy(&[100], &[3]), ContiguousStrategy::Strided);
+    assert_eq!(strategy(&[3, 8], &[0, 0]), ContiguousStrategy::Strided);
+}
@@ -0,0 +1,306 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{ComputeClient, CubePrimitive, Numeric, Runtime, TensorHandleRef},
+    server::Handle,
+};
+
+use crate::tensor::{self, BinaryOp, CastElem, ElemwiseBuilder, ElemwiseGraph, UnaryOp};
+
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+/// Values in `[-2, 2]` that are exact in half precision.
+fn test_values(shape: &[usize], seed: usize) -> Vec<f32> {
+    (0..shape.iter().product::<usize>())
+        .map(|i| ((i * 7 + seed * 13) % 17) as f32 / 4.0 - 2.0)
+        .collect()
+}
+
+fn assert_close(actual: &[f32], expected: &[f32], tolerance: f32) {
+    assert_eq!(actual.len(), expected.len());
+    for (i, (actual, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+        let diff = (actual - expected).abs();
+        assert!(
+            diff <= tolerance * expected.abs().max(1.0),
+            "Values are not close: index={i} actual={actual}, expected={expected}, difference={diff}"
+        );
+    }
+}
+
+/// Launch the `graph` in `f32` over contiguous inputs of the given shapes and read its outputs of `output_shape`.
+fn run_graph<R: Runtime>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    graph: &ElemwiseGraph,
+    inputs: &[(&[f32], &[usize])],
+    output_shape: &[usize],
+) -> Vec<Vec<f32>> {
+    let handles = inputs
+        .iter()
+        .map(|(values, _)| client.create(f32::as_bytes(values)))
+        .collect::<Vec<_>>();
+    let strides = inputs
+        .iter()
+        .map(|(_, shape)| contiguous_strides(shape))
+        .collect::<Vec<_>>();
+    let input_refs = handles
+        .iter()
+        .zip(inputs.iter().zip(strides.iter()))
+        .map(|(handle, ((_, shape), strides))| unsafe {
+            TensorHandleRef::<R>::from_raw_parts(handle, strides, shape, size_of::<f32>())
+        })
+        .collect::<Vec<_>>();
+
+    run_graph_refs::<R, f32>(client, graph, &input_refs, output_shape)
+}
+
+fn run_graph_refs<R: Runtime, I: Numeric>(
+    client: &ComputeClient<R::Server, R::Channel>,
+    graph: &ElemwiseGraph,
+    inputs: &[TensorHandleRef<'_, R>],
+    output_shape: &[usize],
+) -> Vec<Vec<f32>> {
+    let num_elems = output_shape.iter().product::<usize>();
+    let output_strides = contiguous_strides(output_shape);
+    let output_handles = graph
+        .program
+        .outputs
+        .iter()
+        .map(|_| client.empty(num_elems * size_of::<f32>()))
+        .collect::<Vec<Handle>>();
+    let output_refs = output_handles
+        .iter()
+        .map(|handle| unsafe {
+            TensorHandleRef::<R>::from_raw_parts(
+                handle,
+                &output_strides,
+                output_shape,
+                size_of::<f32>(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    tensor::fused_elemwise::<R, I, f32, f32>(client, graph, inputs, &output_refs);
+
+    output_handles
+        .into_iter()
+        .map(|handle| f32::from_bytes(&client.read_one(handle)).to_vec())
+        .collect()
+}
+
+/// `clamp(round_f16(a * 2.5 + b), -1, 1)`, the chain of launches the fusion replaces.
+pub fn test_cast_mul_add_clamp<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    if !client
+        .properties()
+        .supports_type(half::f16::as_type_native_unchecked())
+    {
+        println!("f16 not supported - skipped");
+        return;
+    }
+    let shape = [4, 33, 64];
+    let a = test_values(&shape, 0);
+    let b = test_values(&shape, 1);
+
+    let mut builder = ElemwiseBuilder::new();
+    let a_value = builder.input();
+    let b_value = builder.input();
+    let scale = builder.scalar(2.5);
+    let scaled = builder.mul(a_value, scale);
+    let shifted = builder.add(scaled, b_value);
+    let rounded = builder.cast(shifted, CastElem::F16);
+    let (min, max) = (builder.scalar(-1.0), builder.scalar(1.0));
+    let clamped = builder.clamp(rounded, min, max);
+    builder.output(clamped);
+    let graph = builder.build();
+
+    let expected = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| half::f16::from_f32(a * 2.5 + b).to_f32().clamp(-1.0, 1.0))
+        .collect::<Vec<_>>();
+    let actual = run_graph::<R>(&client, &graph, &[(&a, &shape), (&b, &shape)], &shape);
+    assert_close(&actual[0], &expected, 1e-6);
+}
+
+/// `exp(x * scale + bias)` with a `[rows, 1]` scale and a `[cols]` bias broadcast to the `[rows, cols]` input.
+pub fn test_broadcast<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (rows, cols) = (8, 96);
+    let x = test_values(&[rows, cols], 0);
+    let scale = test_values(&[rows, 1], 1);
+    let bias = test_values(&[cols], 2);
+
+    let mut builder = ElemwiseBuilder::new();
+    let x_value = builder.input();
+    let scale_value = builder.input();
+    let bias_value = builder.input();
+    let scaled = builder.mul(x_value, scale_value);
+    let shifted = builder.add(scaled, bias_value);
+    let exp = builder.exp(shifted);
+    builder.output(exp);
+    let graph = builder.build();
+
+    let expected = (0..rows * cols)
+        .map(|i| (x[i] * scale[i / cols] + bias[i % cols]).exp())
+        .collect::<Vec<_>>();
+    let actual = run_graph::<R>(
+        &client,
+        &graph,
+        &[(&x, &[rows, cols]), (&scale, &[rows, 1]), (&bias, &[cols])],
+        &[rows, cols],
+    );
+    assert_close(&actual[0], &expected, 1e-5);
+}
+
+/// A `tanh` shared by three outputs, one of them a mask.
+pub fn test_shared_subexpressions<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let shape = [1000];
+    let x = test_values(&shape, 0);
+
+    let mut builder = ElemwiseBuilder::new();
+    let x_value = builder.input();
+    let half = builder.scalar(0.5);
+    let scaled = builder.mul(x_value, half);
+    let tanh = builder.tanh(scaled);
+    let squared = builder.mul(tanh, tanh);
+    let zero = builder.scalar(0.0);
+    let positive = builder.compare(BinaryOp::Greater, tanh, zero);
+    builder.output(squared);
+    builder.output(positive);
+    builder.output(tanh);
+
+    // Recording the same operation again reuses its node.
+    let tanh_again = builder.tanh(scaled);
+    assert_eq!(tanh, tanh_again);
+    let graph = builder.build();
+
+    let tanh = x.iter().map(|x| (x * 0.5).tanh()).collect::<Vec<_>>();
+    let squared = tanh.iter().map(|t| t * t).collect::<Vec<_>>();
+    let positive = tanh
+        .iter()
+        .map(|t| (*t > 0.0) as u32 as f32)
+        .collect::<Vec<_>>();
+    let actual = run_graph::<R>(&client, &graph, &[(&x, &shape)], &shape);
+    assert_close(&actual[0], &squared, 1e-5);
+    assert_eq!(actual[1], positive);
+    assert_close(&actual[2], &tanh, 1e-5);
+}
+
+/// `select(x > y, log(|x| + 1), -y)`.
+pub fn test_select_mask<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let shape = [3, 7, 5];
+    let x = test_values(&shape, 0);
+    let y = test_values(&shape, 3);
+
+    let mut builder = ElemwiseBuilder::new();
+    let x_value = builder.input();
+    let y_value = builder.input();
+    let mask = builder.compare(BinaryOp::Greater, x_value, y_value);
+    let abs = builder.unary(UnaryOp::Abs, x_value);
+    let one = builder.scalar(1.0);
+    let shifted = builder.add(abs, one);
+    let log = builder.log(shifted);
+    let neg = builder.unary(UnaryOp::Neg, y_value);
+    let selected = builder.select(mask, log, neg);
+    builder.output(selected);
+    let graph = builder.build();
+
+    let expected = x
+        .iter()
+        .zip(y.iter())
+        .map(|(x, y)| match x > y {
+            true => (x.abs() + 1.0).ln(),
+            false => -y,
+        })
+        .collect::<Vec<_>>();
+    let actual = run_graph::<R>(&client, &graph, &[(&x, &shape), (&y, &shape)], &shape);
+    assert_close(&actual[0], &expected, 1e-5);
+}
+
+/// `max(x, y) - min(x, y)` with `x` read through a transposed view.
+pub fn test_strided_input<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let (rows, cols) = (24, 40);
+    // The buffer of `x` holds its transpose, so `x[r][c]` is at `c * rows + r`.
+    let x_transposed = test_values(&[cols, rows], 0);
+    let y = test_values(&[rows, cols], 1);
+
+    let mut builder = ElemwiseBuilder::new();
+    let x_value = builder.input();
+    let y_value = builder.input();
+    let max = builder.max(x_value, y_value);
+    let min = builder.min(x_value, y_value);
+    let diff = builder.sub(max, min);
+    builder.output(diff);
+    let graph = builder.build();
+
+    let expected = (0..rows * cols)
+        .map(|i| (x_transposed[(i % cols) * rows + i / cols] - y[i]).abs())
+        .collect::<Vec<_>>();
+
+    let x_handle = client.create(f32::as_bytes(&x_transposed));
+    let y_handle = client.create(f32::as_bytes(&y));
+    let shape = [rows, cols];
+    let y_strides = contiguous_strides(&shape);
+    let inputs = unsafe {
+        [
+            TensorHandleRef::<R>::from_raw_parts(&x_handle, &[1, rows], &shape, size_of::<f32>()),
+            TensorHandleRef::<R>::from_raw_parts(&y_handle, &y_strides, &shape, size_of::<f32>()),
+        ]
+    };
+    let actual = run_graph_refs::<R, f32>(&client, &graph, &inputs, &shape);
+    assert_close(&actual[0], &expected, 1e-6);
+}
+
+/// `(x / 3)` truncated to an integer, with `u32` inputs computed in `f32`.
+pub fn test_integer_input<R: Runtime>(device: &R::Device) {
+    let client = R::client(device);
+    let shape = [517];
+    let x = (0..517u32).map(|i| i * 5 % 97).collect::<Vec<_>>();
+
+    let mut builder = ElemwiseBuilder::new();
+    let x_value = builder.input();
+    let three = builder.scalar(3.0);
+    let divided = builder.div(x_value, three);
+    let truncated = builder.cast(divided, CastElem::I32);
+    builder.output(truncated);
+    let graph = builder.build();
+
+    let expected = x.iter().map(|x| (x / 3) as f32).collect::<Vec<_>>();
+    let x_handle = client.create(u32::as_bytes(&x));
+    let inputs = unsafe {
+        [TensorHandleRef::<R>::from_raw_parts(
+            &x_handle,
+            &[1],
+            &shape,
+            size_of::<u32>(),
+        )]
+    };
+    let actual = run_graph_refs::<R, u32>(&client, &graph, &inputs, &shape);
+    assert_eq!(actual[0], expected);
+}
+
+/// Graphs recorded the same way have the same program, which keys their compiled kernel,
+/// whatever the values of their scalars.
+pub fn test_program_ignores_scalars() {
+    let record = |scale: f32| {
+        let mut builder = ElemwiseBuilder::new();
+        let x = builder.input();
+        let scale = builder.scalar(scale);
+        let scaled = builder.mul(x, scale);
+        builder.output(scaled);
+        builder.build()
+    };
+
+    let graph_a = record(2.0);
+    let graph_b = record(-0.5);
+    assert_eq!(graph_a.program, graph_b.program);
+    assert_ne!(graph_a.scalars, graph_b.scalars);
+}
@@ -0,0 +1,198 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{Atomic, CubePrimitive, Numeric, Runtime, TensorHandleRef, TypeUsage},
+};
+
+use crate::tensor::{self, IndexOptions, IndexPolicy};
+
+/// Indices spread around the axis, with duplicates, negative and out-of-range values.
+fn test_indices(length: usize, num_indices: usize) -> Vec<i32> {
+    let span = 2 * length as i32 + 5;
+    (0..num_indices as i32)
+        .map(|i| (i * 7) % span - length as i32 - 2)
+        .collect()
+}
+
+/// The position gathered or scattered for an index, if any.
+fn resolve_index_cpu(index: i32, length: usize, options: IndexOptions) -> Option<usize> {
+    let length = length as i32;
+    let index = match options.negative_indices && index < 0 {
+        true => index + length,
+        false => index,
+    };
+    match options.policy {
+        IndexPolicy::Clamp => Some(index.clamp(0, length - 1) as usize),
+        IndexPolicy::Wrap => Some(index.rem_euclid(length) as usize),
+        IndexPolicy::Check => (0..length).contains(&index).then_some(index as usize),
+    }
+}
+
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+pub fn test_gather<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+    axis: usize,
+    indices_shape: &[usize],
+    options: IndexOptions,
+) {
+    let client = R::client(device);
+    let outer = shape[..axis].iter().product::<usize>();
+    let length = shape[axis];
+    let inner = shape[axis + 1..].iter().product::<usize>();
+    let num_indices = indices_shape.iter().product::<usize>();
+
+    let input = (0..shape.iter().product::<usize>())
+        .map(|i| E::from_int(i as i64 % 1000))
+        .collect::<Vec<_>>();
+    let indices = test_indices(length, num_indices);
+
+    let mut expected = Vec::with_capacity(outer * num_indices * inner);
+    let mut out_of_range = false;
+    for o in 0..outer {
+        for index in indices.iter() {
+            match resolve_index_cpu(*index, length, options) {
+                Some(index) => expected.extend_from_slice(
+                    &input[(o * length + index) * inner..(o * length + index + 1) * inner],
+                ),
+                None => {
+                    out_of_range = true;
+                    expected.extend(std::iter::repeat_n(E::from_int(0), inner));
+                }
+            }
+        }
+    }
+
+    let input_handle = client.create(E::as_bytes(&input));
+    let indices_handle = client.create(i32::as_bytes(&indices));
+    let input_strides = contiguous_strides(shape);
+    let indices_strides = contiguous_strides(indices_shape);
+    let (output, flag) = unsafe {
+        tensor::gather::<R, E>(
+            &client,
+            &TensorHandleRef::from_raw_parts(&input_handle, &input_strides, shape, size_of::<E>()),
+            &TensorHandleRef::from_raw_parts(
+                &indices_handle,
+                &indices_strides,
+                indices_shape,
+                size_of::<i32>(),
+            ),
+            axis,
+            options,
+        )
+    };
+
+    let mut expected_shape = shape[..axis].to_vec();
+    expected_shape.extend_from_slice(indices_shape);
+    expected_shape.extend_from_slice(&shape[axis + 1..]);
+    assert_eq!(output.shape, expected_shape);
+
+    let actual = client.read_one(output.handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "gathered values differ"
+    );
+    assert_eq!(flag.out_of_range::<R>(&client), out_of_range);
+}
+
+pub fn test_scatter_add<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+    axis: usize,
+    num_indices: usize,
+    options: IndexOptions,
+) {
+    let client = R::client(device);
+    if !client
+        .properties()
+        .type_usage(Atomic::<E>::as_type_native_unchecked())
+        .contains(TypeUsage::AtomicAdd)
+    {
+        println!(
+            "Atomic add of {} not supported - skipped",
+            E::as_type_native_unchecked()
+        );
+        return;
+    }
+
+    let outer = shape[..axis].iter().product::<usize>();
+    let length = shape[axis];
+    let inner = shape[axis + 1..].iter().product::<usize>();
+    let mut updates_shape = shape.to_vec();
+    updates_shape[axis] = num_indices;
+
+    let target = (0..shape.iter().product::<usize>())
+        .map(|i| E::from_int(i as i64 % 7))
+        .collect::<Vec<_>>();
+    let updates = (0..outer * num_indices * inner)
+        .map(|i| E::from_int(i as i64 % 13 - 6))
+        .collect::<Vec<_>>();
+    let indices = test_indices(length, num_indices);
+
+    // Small integers are added exactly in any order, even as floats.
+    let mut expected = target
+        .iter()
+        .map(|v| v.to_i64().unwrap())
+        .collect::<Vec<_>>();
+    let mut out_of_range = false;
+    for o in 0..outer {
+        for (j, index) in indices.iter().enumerate() {
+            match resolve_index_cpu(*index, length, options) {
+                Some(index) => {
+                    for i in 0..inner {
+                        expected[(o * length + index) * inner + i] +=
+                            updates[(o * num_indices + j) * inner + i].to_i64().unwrap();
+                    }
+                }
+                None => out_of_range = true,
+            }
+        }
+    }
+    let expected = expected.into_iter().map(E::from_int).collect::<Vec<_>>();
+
+    let target_handle = client.create(E::as_bytes(&target));
+    let indices_handle = client.create(i32::as_bytes(&indices));
+    let updates_handle = client.create(E::as_bytes(&updates));
+    let target_strides = contiguous_strides(shape);
+    let updates_strides = contiguous_strides(&updates_shape);
+    let flag = unsafe {
+        tensor::scatter_add::<R, E>(
+            &client,
+            &TensorHandleRef::from_raw_parts(
+                &target_handle,
+                &target_strides,
+                shape,
+                size_of::<E>(),
+            ),
+            &TensorHandleRef::from_raw_parts(
+                &indices_handle,
+                &[1],
+                &[num_indices],
+                size_of::<i32>(),
+            ),
+            &TensorHandleRef::from_raw_parts(
+                &updates_handle,
+                &updates_strides,
+                &updates_shape,
+                size_of::<E>(),
+            ),
+            axis,
+            options,
+        )
+    };
+
+    let actual = client.read_one(target_handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "scattered sums differ"
+    );
+    assert_eq!(flag.out_of_range::<R>(&client), out_of_range);
+}
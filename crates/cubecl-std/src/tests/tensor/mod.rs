@@ -1,4 +1,11 @@
+pub mod cast;
+pub mod contiguous;
+pub mod elemwise;
+pub mod gather;
 pub mod identity;
+pub mod pad;
+pub mod slice;
+pub mod transpose;
 
 mod test_macros;
 mod test_utils;
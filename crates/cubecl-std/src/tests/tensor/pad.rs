@@ -0,0 +1,113 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime, TensorHandleRef},
+};
+
+use crate::tensor::{self, PadValue, is_contiguous};
+
+/// The position of the input along a dimension of `length` for the `position` relative to its
+/// start, if it isn't a constant.
+fn source_position_cpu(position: i64, length: i64, value: PadValue) -> Option<i64> {
+    if (0..length).contains(&position) {
+        return Some(position);
+    }
+    match value {
+        PadValue::Zero | PadValue::Const(_) => None,
+        PadValue::Edge => Some(position.clamp(0, length - 1)),
+        PadValue::Reflect => {
+            let period = i64::max(2 * length - 2, 1);
+            let position = position.abs() % period;
+            Some(match position >= length {
+                true => period - position,
+                false => position,
+            })
+        }
+    }
+}
+
+/// The padded tensor in row-major order.
+fn pad_cpu<E: Numeric>(
+    data: &[E],
+    shape: &[usize],
+    strides: &[usize],
+    pads: &[(usize, usize)],
+    value: PadValue,
+) -> Vec<E> {
+    let padded = shape
+        .iter()
+        .zip(pads)
+        .map(|(size, (before, after))| before + size + after)
+        .collect::<Vec<_>>();
+    let constant = match value {
+        PadValue::Const(constant) => E::from_int(constant as i64),
+        _ => E::from_int(0),
+    };
+
+    (0..padded.iter().product::<usize>())
+        .map(|index| {
+            let mut remainder = index;
+            let mut offset = Some(0);
+            for dim in (0..shape.len()).rev() {
+                let coordinate = (remainder % padded[dim]) as i64;
+                remainder /= padded[dim];
+                let source =
+                    source_position_cpu(coordinate - pads[dim].0 as i64, shape[dim] as i64, value);
+                offset = offset
+                    .zip(source)
+                    .map(|(offset, source)| offset + source as usize * strides[dim]);
+            }
+            offset.map_or(constant, |offset| data[offset])
+        })
+        .collect()
+}
+
+/// Pad the view with the given `shape` and `strides`, and compare it with the tensor padded on
+/// the CPU.
+pub fn test_pad<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+    strides: &[usize],
+    pads: &[(usize, usize)],
+    value: PadValue,
+) {
+    let client = R::client(device);
+    let buffer_len = match shape.contains(&0) {
+        true => 0,
+        false => {
+            1 + shape
+                .iter()
+                .zip(strides)
+                .map(|(dim, stride)| (dim - 1) * stride)
+                .sum::<usize>()
+        }
+    };
+    let input = (0..buffer_len)
+        .map(|i| E::from_int((i % 251) as i64))
+        .collect::<Vec<_>>();
+    let expected = pad_cpu(&input, shape, strides, pads, value);
+
+    let handle = client.create(E::as_bytes(&input));
+    let output = tensor::pad::<R, E>(
+        &client,
+        unsafe { &TensorHandleRef::from_raw_parts(&handle, strides, shape, size_of::<E>()) },
+        pads,
+        value,
+    );
+
+    let expected_shape = shape
+        .iter()
+        .zip(pads)
+        .map(|(size, (before, after))| before + size + after)
+        .collect::<Vec<_>>();
+    assert_eq!(output.shape, expected_shape);
+    assert!(is_contiguous(&output.shape, &output.strides));
+    if expected.is_empty() {
+        return;
+    }
+    let actual = client.read_one(output.handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "padded values differ"
+    );
+}
@@ -0,0 +1,78 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime, TensorHandleRef},
+};
+
+use crate::tensor::{self, is_contiguous};
+
+/// The elements of the slice in row-major order.
+fn slice_cpu<E: Copy>(data: &[E], strides: &[usize], starts: &[usize], ends: &[usize]) -> Vec<E> {
+    let shape = starts
+        .iter()
+        .zip(ends)
+        .map(|(start, end)| end - start)
+        .collect::<Vec<_>>();
+
+    (0..shape.iter().product::<usize>())
+        .map(|index| {
+            let mut remainder = index;
+            let mut offset = 0;
+            for dim in (0..shape.len()).rev() {
+                offset += (starts[dim] + remainder % shape[dim]) * strides[dim];
+                remainder /= shape[dim];
+            }
+            data[offset]
+        })
+        .collect()
+}
+
+/// Slice the view with the given `shape` and `strides`, and compare it with the slice copied on
+/// the CPU.
+pub fn test_slice<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+    strides: &[usize],
+    starts: &[usize],
+    ends: &[usize],
+) {
+    let client = R::client(device);
+    let buffer_len = match shape.contains(&0) {
+        true => 0,
+        false => {
+            1 + shape
+                .iter()
+                .zip(strides)
+                .map(|(dim, stride)| (dim - 1) * stride)
+                .sum::<usize>()
+        }
+    };
+    let input = (0..buffer_len)
+        .map(|i| E::from_int((i % 251) as i64))
+        .collect::<Vec<_>>();
+    let expected = slice_cpu(&input, strides, starts, ends);
+
+    let handle = client.create(E::as_bytes(&input));
+    let output = tensor::slice::<R, E>(
+        &client,
+        unsafe { &TensorHandleRef::from_raw_parts(&handle, strides, shape, size_of::<E>()) },
+        starts,
+        ends,
+    );
+
+    let expected_shape = starts
+        .iter()
+        .zip(ends)
+        .map(|(start, end)| end - start)
+        .collect::<Vec<_>>();
+    assert_eq!(output.shape, expected_shape);
+    assert!(is_contiguous(&output.shape, &output.strides));
+    if expected.is_empty() {
+        return;
+    }
+    let actual = client.read_one(output.handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "sliced values differ"
+    );
+}
@@ -0,0 +1,40 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_cast {
+    () => {
+        $crate::testgen_tensor_cast!([u32, i32, f32], [u32, i32, f32]);
+    };
+    (@outputs $input:ident, [$($output:ident),*]) => {
+        use super::*;
+        use $crate::tests::tensor::cast::{test_cast, test_cast_permuted};
+
+        pub type InputT = $input;
+
+        ::paste::paste! {
+            $(
+                #[test]
+                pub fn [<test_to_ $output>]() {
+                    test_cast::<TestRuntime, InputT, $output>(&Default::default());
+                }
+
+                #[test]
+                pub fn [<test_permuted_to_ $output>]() {
+                    test_cast_permuted::<TestRuntime, InputT, $output>(&Default::default());
+                }
+            )*
+        }
+    };
+    ([$($input:ident),*], $outputs:tt) => {
+        mod cast {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$input _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_cast!(@outputs $input, $outputs);
+                })*
+            }
+        }
+    };
+}
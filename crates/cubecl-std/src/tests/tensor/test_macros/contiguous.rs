@@ -0,0 +1,106 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_contiguous {
+    () => {
+        mod contiguous {
+            $crate::testgen_tensor_contiguous!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use $crate::tests::tensor::contiguous::{
+                permuted, test_contiguous_strategies, test_into_contiguous,
+                test_into_contiguous_shares_contiguous,
+            };
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_sliced_rows() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[8, 30], &[32, 1]);
+            }
+
+            #[test]
+            pub fn test_permuted_batches() {
+                let (shape, strides) = permuted(&[3, 2, 4, 8], &[1, 0, 2, 3]);
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &shape, &strides);
+            }
+
+            #[test]
+            pub fn test_odd_rows() {
+                let (shape, strides) = permuted(&[5, 7, 3], &[1, 0, 2]);
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &shape, &strides);
+            }
+
+            #[test]
+            pub fn test_transposed() {
+                let (shape, strides) = permuted(&[2, 36, 100], &[0, 2, 1]);
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &shape, &strides);
+            }
+
+            #[test]
+            pub fn test_transposed_merged() {
+                let (shape, strides) = permuted(&[6, 2, 3, 5], &[3, 0, 1, 2]);
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &shape, &strides);
+            }
+
+            #[test]
+            pub fn test_permuted_rank_6() {
+                let (shape, strides) = permuted(&[3, 2, 3, 2, 4, 2], &[5, 3, 1, 4, 2, 0]);
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &shape, &strides);
+            }
+
+            #[test]
+            pub fn test_strided_vector() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[100], &[3]);
+            }
+
+            #[test]
+            pub fn test_broadcast_batches() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[4, 5, 6], &[0, 6, 1]);
+            }
+
+            #[test]
+            pub fn test_broadcast_last_dim() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[7, 9], &[1, 0]);
+            }
+
+            #[test]
+            pub fn test_broadcast_scalar() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[3, 8], &[0, 0]);
+            }
+
+            #[test]
+            pub fn test_size_one_dims() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[1, 5, 1, 7], &[999, 1, 999, 5]);
+            }
+
+            #[test]
+            pub fn test_zero_sized() {
+                test_into_contiguous::<TestRuntime, NumericT>(&Default::default(), &[4, 0, 8], &[1, 32, 4]);
+            }
+
+            #[test]
+            pub fn test_shares_contiguous() {
+                test_into_contiguous_shares_contiguous::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn test_strategies() {
+                test_contiguous_strategies();
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod contiguous {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_contiguous!($numeric);
+                })*
+            }
+        }
+    };
+}
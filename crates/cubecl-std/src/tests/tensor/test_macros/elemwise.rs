@@ -0,0 +1,46 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_elemwise {
+    () => {
+        mod elemwise {
+            use super::*;
+            use $crate::tests::tensor::elemwise::*;
+
+            #[test]
+            pub fn cast_mul_add_clamp() {
+                test_cast_mul_add_clamp::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn broadcast() {
+                test_broadcast::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn shared_subexpressions() {
+                test_shared_subexpressions::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn select_mask() {
+                test_select_mask::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn strided_input() {
+                test_strided_input::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn integer_input() {
+                test_integer_input::<TestRuntime>(&Default::default());
+            }
+
+            #[test]
+            pub fn program_ignores_scalars() {
+                test_program_ignores_scalars();
+            }
+        }
+    };
+}
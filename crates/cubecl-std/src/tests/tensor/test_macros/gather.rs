@@ -0,0 +1,95 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_gather {
+    () => {
+        mod gather {
+            use super::*;
+            use $crate::tensor::{IndexOptions, IndexPolicy};
+            use $crate::tests::tensor::gather::{test_gather, test_scatter_add};
+
+            $crate::testgen_tensor_gather!(clamp, IndexOptions::new(IndexPolicy::Clamp));
+            $crate::testgen_tensor_gather!(wrap, IndexOptions::new(IndexPolicy::Wrap));
+            $crate::testgen_tensor_gather!(check, IndexOptions::new(IndexPolicy::Check));
+            $crate::testgen_tensor_gather!(
+                check_negative,
+                IndexOptions::new(IndexPolicy::Check).with_negative_indices()
+            );
+            $crate::testgen_tensor_gather!(
+                clamp_negative,
+                IndexOptions::new(IndexPolicy::Clamp).with_negative_indices()
+            );
+        }
+    };
+    ($policy:ident, $options:expr) => {
+        mod $policy {
+            use super::*;
+
+            #[test]
+            pub fn gather_embeddings() {
+                test_gather::<TestRuntime, f32>(
+                    &Default::default(),
+                    &[50, 64],
+                    0,
+                    &[4, 9],
+                    $options,
+                );
+            }
+
+            #[test]
+            pub fn gather_middle_axis() {
+                test_gather::<TestRuntime, u32>(
+                    &Default::default(),
+                    &[3, 10, 6],
+                    1,
+                    &[17],
+                    $options,
+                );
+            }
+
+            #[test]
+            pub fn gather_last_axis() {
+                test_gather::<TestRuntime, f32>(
+                    &Default::default(),
+                    &[5, 11],
+                    1,
+                    &[2, 13],
+                    $options,
+                );
+            }
+
+            #[test]
+            pub fn scatter_add_duplicates_i32() {
+                test_scatter_add::<TestRuntime, i32>(
+                    &Default::default(),
+                    &[12, 8],
+                    0,
+                    100,
+                    $options,
+                );
+            }
+
+            #[test]
+            pub fn scatter_add_middle_axis_i32() {
+                test_scatter_add::<TestRuntime, i32>(
+                    &Default::default(),
+                    &[3, 9, 5],
+                    1,
+                    40,
+                    $options,
+                );
+            }
+
+            #[test]
+            pub fn scatter_add_duplicates_f32() {
+                test_scatter_add::<TestRuntime, f32>(
+                    &Default::default(),
+                    &[12, 8],
+                    0,
+                    100,
+                    $options,
+                );
+            }
+        }
+    };
+}
@@ -1 +1,8 @@
+mod cast;
+mod contiguous;
+mod elemwise;
+mod gather;
 mod identity;
+mod pad;
+mod slice;
+mod transpose;
@@ -0,0 +1,93 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_pad {
+    () => {
+        mod pad {
+            $crate::testgen_tensor_pad!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use $crate::{
+                tensor::PadValue,
+                tests::tensor::{contiguous::permuted, pad::test_pad},
+            };
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_rank_1_zero() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[10], &[1], &[(3, 5)], PadValue::Zero);
+            }
+
+            #[test]
+            pub fn test_rank_2_const() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[6, 8], &[8, 1], &[(1, 2), (4, 0)], PadValue::Const(7.0));
+            }
+
+            #[test]
+            pub fn test_rank_3_reflect() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[4, 5, 6], &[30, 6, 1], &[(2, 1), (0, 3), (3, 2)], PadValue::Reflect);
+            }
+
+            #[test]
+            pub fn test_rank_4_edge() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[2, 3, 4, 8], &[96, 32, 8, 1], &[(1, 0), (2, 1), (0, 2), (4, 4)], PadValue::Edge);
+            }
+
+            #[test]
+            pub fn test_reflect_corners_beyond_length() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[5, 7], &[7, 1], &[(3, 6), (9, 2)], PadValue::Reflect);
+            }
+
+            #[test]
+            pub fn test_reflect_size_one() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[1, 6], &[6, 1], &[(2, 2), (1, 1)], PadValue::Reflect);
+            }
+
+            #[test]
+            pub fn test_edge_transposed() {
+                let (shape, strides) = permuted(&[4, 6, 5], &[0, 2, 1]);
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &shape, &strides, &[(1, 1), (2, 3), (1, 0)], PadValue::Edge);
+            }
+
+            #[test]
+            pub fn test_const_permuted_rank_4() {
+                let (shape, strides) = permuted(&[3, 2, 4, 5], &[2, 0, 3, 1]);
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &shape, &strides, &[(0, 1), (2, 0), (1, 1), (0, 3)], PadValue::Const(3.0));
+            }
+
+            #[test]
+            pub fn test_vectorized_interior() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[3, 16], &[16, 1], &[(1, 1), (4, 12)], PadValue::Reflect);
+            }
+
+            #[test]
+            pub fn test_no_pads() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[4, 8], &[8, 1], &[(0, 0), (0, 0)], PadValue::Reflect);
+            }
+
+            #[test]
+            pub fn test_empty_input() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[3, 0], &[1, 1], &[(0, 0), (2, 1)], PadValue::Const(5.0));
+            }
+
+            #[test]
+            pub fn test_zero_sized() {
+                test_pad::<TestRuntime, NumericT>(&Default::default(), &[4, 0, 3], &[3, 3, 1], &[(1, 1), (0, 0), (2, 0)], PadValue::Zero);
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod pad {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_pad!($numeric);
+                })*
+            }
+        }
+    };
+}
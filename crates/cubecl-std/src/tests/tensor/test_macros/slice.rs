@@ -0,0 +1,65 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_slice {
+    () => {
+        mod slice {
+            $crate::testgen_tensor_slice!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use $crate::tests::tensor::{contiguous::permuted, slice::test_slice};
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_rank_1() {
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &[20], &[1], &[3], &[17]);
+            }
+
+            #[test]
+            pub fn test_rank_2_from_origin() {
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &[8, 30], &[32, 1], &[0, 0], &[5, 17]);
+            }
+
+            #[test]
+            pub fn test_rank_3_lines() {
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &[4, 6, 16], &[96, 16, 1], &[1, 2, 4], &[3, 6, 12]);
+            }
+
+            #[test]
+            pub fn test_rank_4() {
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &[3, 4, 5, 6], &[120, 30, 6, 1], &[1, 0, 2, 1], &[3, 3, 5, 6]);
+            }
+
+            #[test]
+            pub fn test_transposed() {
+                let (shape, strides) = permuted(&[6, 10], &[1, 0]);
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &shape, &strides, &[2, 1], &[9, 5]);
+            }
+
+            #[test]
+            pub fn test_permuted_from_origin() {
+                let (shape, strides) = permuted(&[3, 4, 5], &[2, 0, 1]);
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &shape, &strides, &[0, 0, 0], &[4, 2, 3]);
+            }
+
+            #[test]
+            pub fn test_zero_sized() {
+                test_slice::<TestRuntime, NumericT>(&Default::default(), &[5, 6], &[6, 1], &[2, 3], &[2, 6]);
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod slice {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_slice!($numeric);
+                })*
+            }
+        }
+    };
+}
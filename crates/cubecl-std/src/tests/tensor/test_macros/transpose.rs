@@ -0,0 +1,73 @@
+#![allow(missing_docs)]
+
+#[macro_export]
+macro_rules! testgen_tensor_transpose {
+    () => {
+        mod transpose {
+            $crate::testgen_tensor_transpose!(f32);
+        }
+    };
+    ($numeric:ident) => {
+            use super::*;
+            use $crate::tests::tensor::transpose::{test_into_contiguous_matrix, test_transpose};
+
+            pub type NumericT = $numeric;
+
+            #[test]
+            pub fn test_single_tile() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[32, 32]);
+            }
+
+            #[test]
+            pub fn test_non_square() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[64, 96]);
+            }
+
+            #[test]
+            pub fn test_single_row() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[1, 1000]);
+            }
+
+            #[test]
+            pub fn test_single_column() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[1000, 1]);
+            }
+
+            #[test]
+            pub fn test_partial_tiles() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[4097, 33]);
+            }
+
+            #[test]
+            pub fn test_partial_tiles_wide() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[33, 4097]);
+            }
+
+            #[test]
+            pub fn test_batched() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[3, 65, 130]);
+            }
+
+            #[test]
+            pub fn test_batched_rank_4() {
+                test_transpose::<TestRuntime, NumericT>(&Default::default(), &[2, 3, 17, 48]);
+            }
+
+            #[test]
+            pub fn test_into_contiguous_column_major() {
+                test_into_contiguous_matrix::<TestRuntime, NumericT>(&Default::default(), &[2, 100, 36]);
+            }
+    };
+    ([$($numeric:ident),*]) => {
+        mod transpose {
+            use super::*;
+            ::paste::paste! {
+                $(mod [<$numeric _ty>] {
+                    use super::*;
+
+                    $crate::testgen_tensor_transpose!($numeric);
+                })*
+            }
+        }
+    };
+}
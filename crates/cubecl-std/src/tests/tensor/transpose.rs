@@ -0,0 +1,91 @@
+use cubecl_core::{
+    CubeElement,
+    prelude::{Numeric, Runtime, TensorHandleRef},
+};
+
+use crate::tensor::{self, is_contiguous};
+
+fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for axis in (0..shape.len().saturating_sub(1)).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+    strides
+}
+
+fn transpose_cpu<E: Copy>(data: &[E], shape: &[usize]) -> Vec<E> {
+    let rank = shape.len();
+    let (rows, cols) = (shape[rank - 2], shape[rank - 1]);
+    let mut output = Vec::with_capacity(data.len());
+    for matrix in data.chunks(rows * cols) {
+        for col in 0..cols {
+            for row in 0..rows {
+                output.push(matrix[row * cols + col]);
+            }
+        }
+    }
+    output
+}
+
+/// Values that wrap around the range of the smallest element types.
+fn test_values<E: Numeric>(num_elems: usize) -> Vec<E> {
+    (0..num_elems)
+        .map(|i| E::from_int((i % 251) as i64))
+        .collect()
+}
+
+pub fn test_transpose<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+) {
+    let client = R::client(device);
+    let input = test_values::<E>(shape.iter().product());
+    let expected = transpose_cpu(&input, shape);
+
+    let handle = client.create(E::as_bytes(&input));
+    let strides = contiguous_strides(shape);
+    let output = tensor::transpose::<R, E>(&client, unsafe {
+        &TensorHandleRef::from_raw_parts(&handle, &strides, shape, size_of::<E>())
+    });
+
+    let rank = shape.len();
+    let mut expected_shape = shape.to_vec();
+    expected_shape.swap(rank - 2, rank - 1);
+    assert_eq!(output.shape, expected_shape);
+
+    let actual = client.read_one(output.handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "transposed values differ"
+    );
+}
+
+/// Make the transposed view of a row-major matrix batch contiguous, like a column-major matmul input.
+pub fn test_into_contiguous_matrix<R: Runtime, E: Numeric + CubeElement + core::fmt::Debug>(
+    device: &R::Device,
+    shape: &[usize],
+) {
+    let client = R::client(device);
+    let input = test_values::<E>(shape.iter().product());
+    let expected = transpose_cpu(&input, shape);
+
+    let handle = client.create(E::as_bytes(&input));
+    let rank = shape.len();
+    let mut view_shape = shape.to_vec();
+    let mut view_strides = contiguous_strides(shape);
+    view_shape.swap(rank - 2, rank - 1);
+    view_strides.swap(rank - 2, rank - 1);
+    let output = tensor::into_contiguous_matrix::<R, E>(&client, unsafe {
+        &TensorHandleRef::from_raw_parts(&handle, &view_strides, &view_shape, size_of::<E>())
+    });
+
+    assert_eq!(output.shape, view_shape);
+    assert!(is_contiguous(&output.shape, &output.strides));
+    let actual = client.read_one(output.handle);
+    assert_eq!(
+        E::from_bytes(&actual),
+        &expected[..],
+        "contiguous values differ"
+    );
+}
@@ -0,0 +1,240 @@
+/// How the adapter of a device is selected when its setup is created, overriding the selection
+/// based on the [device](crate::WgpuDevice) kind.
+///
+/// # Example
+///
+/// Forcing Vulkan on the discrete GPU, before any client of the device is created:
+///
+/// ```ignore
+/// use cubecl_wgpu::{
+///     AdapterSelection, AdapterSelector, AutoGraphicsApi, RuntimeOptions, WgpuDevice, init_setup,
+/// };
+///
+/// let options = RuntimeOptions {
+///     adapter: Some(AdapterSelection {
+///         backends: vec![wgpu::Backend::Vulkan],
+///         selector: AdapterSelector::Power(wgpu::PowerPreference::HighPerformance),
+///         fallback: false,
+///     }),
+///     ..Default::default()
+/// };
+/// let device = WgpuDevice::DefaultDevice;
+/// init_setup::<AutoGraphicsApi>(&device, options);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdapterSelection {
+    /// The backends to pick the adapter from, in priority order: the adapters of a backend are
+    /// only considered when no adapter of the previous ones matches the selector.
+    ///
+    /// When empty, the backend of the [graphics API](crate::GraphicsApi) is used.
+    pub backends: Vec<wgpu::Backend>,
+    /// Which adapter of a backend to pick.
+    pub selector: AdapterSelector,
+    /// Whether to fall back to the best adapter of any of the backends when none matches the
+    /// selector, instead of panicking.
+    pub fallback: bool,
+}
+
+impl Default for AdapterSelection {
+    fn default() -> Self {
+        Self {
+            backends: Vec::new(),
+            selector: AdapterSelector::Power(wgpu::PowerPreference::HighPerformance),
+            fallback: true,
+        }
+    }
+}
+
+/// Which adapter of a backend to pick.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdapterSelector {
+    /// The adapter at the given [index](AdapterDescription::index) of the backend.
+    Index(usize),
+    /// The first adapter whose name contains the given string, ignoring the case.
+    Name(String),
+    /// The adapter best matching the power preference, a discrete GPU being preferred for
+    /// [high performance](wgpu::PowerPreference::HighPerformance) and an integrated GPU for
+    /// [low power](wgpu::PowerPreference::LowPower).
+    Power(wgpu::PowerPreference),
+}
+
+/// An adapter found by [enumerate_adapters].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdapterDescription {
+    /// The index of the adapter among the adapters of its backend.
+    pub index: usize,
+    /// The name of the adapter.
+    pub name: String,
+    /// The backend of the adapter.
+    pub backend: wgpu::Backend,
+    /// The kind of the adapter.
+    pub device_type: wgpu::DeviceType,
+    /// The maximum size of a buffer, in bytes.
+    pub max_buffer_size: u64,
+    /// The maximum size of a storage buffer binding, in bytes.
+    pub max_storage_buffer_binding_size: u32,
+    /// The maximum size of the shared memory of a cube, in bytes.
+    pub max_shared_memory_size: u32,
+    /// The maximum number of units of a cube.
+    pub max_units_per_cube: u32,
+}
+
+impl AdapterDescription {
+    fn new(index: usize, adapter: &wgpu::Adapter) -> Self {
+        let info = adapter.get_info();
+        let limits = adapter.limits();
+
+        Self {
+            index,
+            name: info.name,
+            backend: info.backend,
+            device_type: info.device_type,
+            max_buffer_size: limits.max_buffer_size,
+            max_storage_buffer_binding_size: limits.max_storage_buffer_binding_size,
+            max_shared_memory_size: limits.max_compute_workgroup_storage_size,
+            max_units_per_cube: limits.max_compute_invocations_per_workgroup,
+        }
+    }
+}
+
+/// The adapters of the given backends, in the order of the backends, so applications can
+/// present a choice to be [selected](AdapterSelection).
+#[cfg(not(target_family = "wasm"))]
+pub fn enumerate_adapters(backends: &[wgpu::Backend]) -> Vec<AdapterDescription> {
+    backends
+        .iter()
+        .flat_map(|backend| enumerate_backend(*backend, wgpu::InstanceFlags::default()).1)
+        .map(|(_, description)| description)
+        .collect()
+}
+
+/// The instance of the backend with its adapters.
+#[cfg(not(target_family = "wasm"))]
+fn enumerate_backend(
+    backend: wgpu::Backend,
+    flags: wgpu::InstanceFlags,
+) -> (wgpu::Instance, Vec<(wgpu::Adapter, AdapterDescription)>) {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends: backend.into(),
+        flags,
+        ..Default::default()
+    });
+    let adapters = instance
+        .enumerate_adapters(backend.into())
+        .into_iter()
+        .enumerate()
+        .map(|(index, adapter)| {
+            let description = AdapterDescription::new(index, &adapter);
+            (adapter, description)
+        })
+        .collect();
+
+    (instance, adapters)
+}
+
+/// Select the adapter on the instance created with the flags for each backend.
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn select_adapter(
+    selection: &AdapterSelection,
+    default_backend: wgpu::Backend,
+    flags: wgpu::InstanceFlags,
+) -> (wgpu::Instance, wgpu::Adapter) {
+    let backends = match selection.backends.is_empty() {
+        true => vec![default_backend],
+        false => selection.backends.clone(),
+    };
+
+    let mut instances = Vec::new();
+    let mut adapters = Vec::new();
+    for backend in backends {
+        let (instance, backend_adapters) = enumerate_backend(backend, flags);
+        for (adapter, description) in backend_adapters {
+            adapters.push((instances.len(), adapter, description));
+        }
+        instances.push(instance);
+    }
+
+    let descriptions: Vec<_> = adapters.iter().map(|(_, _, desc)| desc.clone()).collect();
+    let index = select(&descriptions, selection)
+        .unwrap_or_else(|| panic!("No adapter matches {selection:?}, adapters {descriptions:?}"));
+    let (instance, adapter, _) = adapters.swap_remove(index);
+
+    (instances.swap_remove(instance), adapter)
+}
+
+/// The index of the adapter to use among the `adapters` of the backends, listed in the order
+/// of the backends.
+pub(crate) fn select(
+    adapters: &[AdapterDescription],
+    selection: &AdapterSelection,
+) -> Option<usize> {
+    let mut backends = Vec::new();
+    for adapter in adapters {
+        if !backends.contains(&adapter.backend) {
+            backends.push(adapter.backend);
+        }
+    }
+
+    let selected = backends.into_iter().find_map(|backend| {
+        let candidates = adapters
+            .iter()
+            .enumerate()
+            .filter(|(_, adapter)| adapter.backend == backend);
+
+        match &selection.selector {
+            AdapterSelector::Index(index) => candidates
+                .find(|(_, adapter)| adapter.index == *index)
+                .map(|(position, _)| position),
+            AdapterSelector::Name(name) => {
+                let name = name.to_lowercase();
+                candidates
+                    .find(|(_, adapter)| adapter.name.to_lowercase().contains(&name))
+                    .map(|(position, _)| position)
+            }
+            AdapterSelector::Power(power) => best(candidates, *power),
+        }
+    });
+
+    match selected {
+        Some(position) => Some(position),
+        None if selection.fallback => best(
+            adapters.iter().enumerate(),
+            wgpu::PowerPreference::HighPerformance,
+        ),
+        None => None,
+    }
+}
+
+/// The first adapter of the kind best matching the power preference, or the first adapter
+/// without any preference.
+fn best<'a>(
+    mut adapters: impl Iterator<Item = (usize, &'a AdapterDescription)>,
+    power: wgpu::PowerPreference,
+) -> Option<usize> {
+    let order = match power {
+        wgpu::PowerPreference::None => return adapters.next().map(|(position, _)| position),
+        wgpu::PowerPreference::HighPerformance => [
+            wgpu::DeviceType::DiscreteGpu,
+            wgpu::DeviceType::IntegratedGpu,
+            wgpu::DeviceType::VirtualGpu,
+            wgpu::DeviceType::Other,
+            wgpu::DeviceType::Cpu,
+        ],
+        wgpu::PowerPreference::LowPower => [
+            wgpu::DeviceType::IntegratedGpu,
+            wgpu::DeviceType::DiscreteGpu,
+            wgpu::DeviceType::VirtualGpu,
+            wgpu::DeviceType::Other,
+            wgpu::DeviceType::Cpu,
+        ],
+    };
+    let rank = |device_type| order.iter().position(|kind| *kind == device_type);
+
+    adapters
+        // The first adapter wins among the adapters of the same rank.
+        .min_by_key(|(position, adapter)| (rank(adapter.device_type), *position))
+        .map(|(position, _)| position)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,151 @@
+use super::{AdapterDescription, AdapterSelection, AdapterSelector, select};
+use wgpu::{Backend, DeviceType, PowerPreference};
+
+fn adapter(
+    index: usize,
+    name: &str,
+    backend: Backend,
+    device_type: DeviceType,
+) -> AdapterDescription {
+    AdapterDescription {
+        index,
+        name: name.into(),
+        backend,
+        device_type,
+        max_buffer_size: 1 << 30,
+        max_storage_buffer_binding_size: 1 << 27,
+        max_shared_memory_size: 1 << 15,
+        max_units_per_cube: 1024,
+    }
+}
+
+/// The adapters of a laptop with an integrated and a discrete GPU, listed in the order of the
+/// backends.
+fn laptop() -> Vec<AdapterDescription> {
+    vec![
+        adapter(
+            0,
+            "Intel(R) UHD Graphics",
+            Backend::Dx12,
+            DeviceType::IntegratedGpu,
+        ),
+        adapter(
+            1,
+            "NVIDIA GeForce RTX 4070",
+            Backend::Dx12,
+            DeviceType::DiscreteGpu,
+        ),
+        adapter(
+            2,
+            "Microsoft Basic Render Driver",
+            Backend::Dx12,
+            DeviceType::Cpu,
+        ),
+        adapter(
+            0,
+            "Intel(R) UHD Graphics",
+            Backend::Vulkan,
+            DeviceType::IntegratedGpu,
+        ),
+        adapter(
+            1,
+            "NVIDIA GeForce RTX 4070",
+            Backend::Vulkan,
+            DeviceType::DiscreteGpu,
+        ),
+    ]
+}
+
+fn selection(selector: AdapterSelector, fallback: bool) -> AdapterSelection {
+    AdapterSelection {
+        backends: Vec::new(),
+        selector,
+        fallback,
+    }
+}
+
+#[test]
+fn power_preference_picks_the_kind_on_the_first_backend() {
+    let adapters = laptop();
+
+    let high = select(
+        &adapters,
+        &selection(
+            AdapterSelector::Power(PowerPreference::HighPerformance),
+            false,
+        ),
+    );
+    let low = select(
+        &adapters,
+        &selection(AdapterSelector::Power(PowerPreference::LowPower), false),
+    );
+    let none = select(
+        &adapters,
+        &selection(AdapterSelector::Power(PowerPreference::None), false),
+    );
+
+    assert_eq!(high, Some(1));
+    assert_eq!(low, Some(0));
+    assert_eq!(none, Some(0));
+}
+
+#[test]
+fn backends_are_tried_in_priority_order() {
+    let vulkan_only: Vec<_> = laptop()
+        .into_iter()
+        .filter(|adapter| adapter.backend == Backend::Vulkan)
+        .collect();
+    let mut vulkan_first = vulkan_only.clone();
+    vulkan_first.extend(
+        laptop()
+            .into_iter()
+            .filter(|adapter| adapter.backend == Backend::Dx12),
+    );
+    let nvidia = selection(AdapterSelector::Name("nvidia".into()), false);
+    let basic = selection(AdapterSelector::Name("basic".into()), false);
+
+    // Only the adapters of the requested backends are enumerated.
+    assert_eq!(select(&vulkan_only, &nvidia), Some(1));
+    assert_eq!(select(&vulkan_only, &basic), None);
+
+    // The adapters of DX12 are only considered when no adapter of Vulkan matches.
+    assert_eq!(select(&vulkan_first, &nvidia), Some(1));
+    let selected = select(&vulkan_first, &basic).map(|index| &vulkan_first[index]);
+    assert_eq!(selected.map(|adapter| adapter.backend), Some(Backend::Dx12));
+}
+
+#[test]
+fn index_and_name_select_within_a_backend() {
+    let adapters = laptop();
+
+    assert_eq!(
+        select(&adapters, &selection(AdapterSelector::Index(2), false)),
+        Some(2)
+    );
+    assert_eq!(
+        select(
+            &adapters,
+            &selection(AdapterSelector::Name("uhd".into()), false)
+        ),
+        Some(0)
+    );
+}
+
+#[test]
+fn no_match_falls_back_or_errors() {
+    let adapters = laptop();
+
+    let strict = select(&adapters, &selection(AdapterSelector::Index(5), false));
+    let fallback = select(
+        &adapters,
+        &selection(AdapterSelector::Name("radeon".into()), true),
+    );
+
+    assert_eq!(strict, None);
+    // The best adapter of any backend, the first one winning among the same kind.
+    assert_eq!(fallback, Some(1));
+    assert_eq!(
+        select(&[], &selection(AdapterSelector::Index(0), true)),
+        None
+    );
+}
@@ -1,10 +1,20 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{
+    borrow::Cow,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+};
 
-use cubecl_core::{ExecutionMode, WgpuCompilationOptions, prelude::CompiledKernel};
-use cubecl_runtime::DeviceProperties;
+use cubecl_core::{
+    ExecutionMode, WgpuCompilationOptions, compute::Visibility, prelude::CompiledKernel,
+};
+use cubecl_runtime::{
+    DeviceProperties,
+    server::{CompilationError, CompilationStage, IoError},
+};
 use wgpu::{
     Adapter, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
-    ComputePipeline, Device, PipelineLayoutDescriptor, Queue, ShaderModuleDescriptor, ShaderStages,
+    ComputePipeline, Device, PipelineLayoutDescriptor, PushConstantRange, Queue, ShaderModule,
+    ShaderModuleDescriptor, ShaderStages,
 };
 
 use crate::{AutoCompiler, AutoRepresentation, WgpuServer};
@@ -19,85 +29,184 @@ use super::metal;
 #[cfg(all(feature = "msl", target_os = "macos"))]
 use cubecl_cpp::metal as cpp_metal;
 
+/// A compute pipeline, along with how the buffers and scalars of its kernel are bound.
+#[derive(Debug)]
+pub(crate) struct WgpuPipeline {
+    pub pipeline: ComputePipeline,
+    /// Whether each buffer of the kernel is bound as read-only.
+    pub read_only: Vec<bool>,
+    /// Whether the scalars are set as push constants instead of being bound as buffers.
+    pub push_constants: bool,
+}
+
+impl WgpuPipeline {
+    /// Whether the pipeline binds some buffers as read-only and others as read-write, in which
+    /// case they can't share the same memory.
+    pub fn has_mixed_access(&self) -> bool {
+        self.read_only.iter().any(|read_only| *read_only)
+            && self.read_only.iter().any(|read_only| !*read_only)
+    }
+}
+
 impl WgpuServer {
-    pub fn create_pipeline(
+    /// Create the pipeline of a compiled kernel.
+    ///
+    /// When `aliased` is set, every buffer is bound as read-write, so buffers sharing the same
+    /// memory can be bound together regardless of how the kernel accesses them.
+    ///
+    /// Returns a [compilation error](IoError::Compilation) when the device rejects the module or
+    /// the pipeline, in which case nothing is cached.
+    pub(crate) fn create_pipeline(
         &mut self,
-        kernel: CompiledKernel<AutoCompiler>,
+        mut kernel: CompiledKernel<AutoCompiler>,
         mode: ExecutionMode,
-    ) -> Arc<ComputePipeline> {
+        aliased: bool,
+    ) -> Result<Arc<WgpuPipeline>, IoError> {
+        if let Some(AutoRepresentation::Wgsl(repr)) = kernel.repr.as_mut().filter(|_| aliased) {
+            // The access of WGSL bindings has to match the layout.
+            for binding in repr.buffers.iter_mut() {
+                binding.visibility = Visibility::ReadWrite;
+            }
+            kernel.source = repr.to_string();
+        }
+
+        let bindings_info = match &kernel.repr {
+            Some(AutoRepresentation::Wgsl(repr)) => Some(wgsl::bindings(repr)),
+            #[cfg(all(feature = "msl", target_os = "macos"))]
+            Some(AutoRepresentation::Msl(repr)) => Some(cpp_metal::bindings(repr)),
+            #[cfg(feature = "spirv")]
+            Some(AutoRepresentation::SpirV(repr)) => Some(vulkan::bindings(repr)),
+            _ => None,
+        };
+        let bindings_info = bindings_info.map(|(mut bindings, meta)| {
+            if aliased {
+                bindings.fill(Visibility::ReadWrite);
+            }
+            (bindings, meta)
+        });
+        let push_constant_size = match &kernel.repr {
+            Some(AutoRepresentation::Wgsl(repr)) => wgsl::push_constant_size(repr),
+            _ => 0,
+        };
+        let constants = match &kernel.repr {
+            Some(AutoRepresentation::Wgsl(repr)) => wgsl::override_constants(repr),
+            _ => vec![],
+        };
+
+        // Kernels with the same source, layout and constants share the same pipeline, and the
+        // WGSL ones only differing by their override constants share the same module. SPIR-V
+        // modules are keyed by their template and the values of their specialization constants.
+        let shader_keys = match &kernel.repr {
+            #[cfg(all(feature = "msl", target_os = "macos"))]
+            Some(AutoRepresentation::Msl(_)) => None,
+            repr => {
+                let mut hasher = DefaultHasher::new();
+                match repr {
+                    #[cfg(feature = "spirv")]
+                    Some(AutoRepresentation::SpirV(repr)) => {
+                        repr.assemble_template().hash(&mut hasher);
+                        repr.spec_constants.hash(&mut hasher);
+                    }
+                    Some(_) | None => kernel.source.hash(&mut hasher),
+                }
+                (mode == ExecutionMode::Checked).hash(&mut hasher);
+                let module_key = hasher.finish();
+
+                bindings_info.hash(&mut hasher);
+                push_constant_size.hash(&mut hasher);
+                kernel.entrypoint_name.hash(&mut hasher);
+                for (id, value) in constants.iter() {
+                    id.hash(&mut hasher);
+                    value.to_bits().hash(&mut hasher);
+                }
+                Some((module_key, hasher.finish()))
+            }
+        };
+
+        if let Some(pipeline) = shader_keys.and_then(|(_, key)| self.shader_pipelines.get(&key)) {
+            return Ok(pipeline.clone());
+        }
+        let name = kernel.entrypoint_name.as_str();
+
         let module = match &kernel.repr {
             #[cfg(feature = "spirv")]
             Some(AutoRepresentation::SpirV(repr)) => {
-                let spirv = repr.assemble();
-                unsafe {
-                    self.device.create_shader_module_passthrough(
-                        wgpu::ShaderModuleDescriptorPassthrough::SpirV(
-                            wgpu::ShaderModuleDescriptorSpirV {
-                                label: Some(&kernel.entrypoint_name),
-                                source: Cow::Borrowed(&spirv),
-                            },
-                        ),
-                    )
+                let (module_key, _) = shader_keys.expect("SPIR-V kernels should be hashed");
+
+                if let Some(module) = self.shader_modules.get(&module_key) {
+                    module.clone()
+                } else {
+                    // wgpu doesn't forward specialization info for passthrough modules, so the
+                    // specialization constants keep their values as defaults.
+                    let spirv = repr.assemble();
+                    let module = self.validated(name, CompilationStage::Compile, || unsafe {
+                        self.device.create_shader_module_passthrough(
+                            wgpu::ShaderModuleDescriptorPassthrough::SpirV(
+                                wgpu::ShaderModuleDescriptorSpirV {
+                                    label: Some(name),
+                                    source: Cow::Borrowed(&spirv),
+                                },
+                            ),
+                        )
+                    })?;
+                    self.shader_modules.insert(module_key, module.clone());
+                    module
                 }
             }
             #[cfg(all(feature = "msl", target_os = "macos"))]
             Some(AutoRepresentation::Msl(repr)) => {
                 let source = &kernel.source;
-                unsafe {
+                self.validated(name, CompilationStage::Compile, || unsafe {
                     self.device.create_shader_module_passthrough(
                         wgpu::ShaderModuleDescriptorPassthrough::Msl(
                             wgpu::ShaderModuleDescriptorMsl {
                                 entry_point: kernel.entrypoint_name.clone(),
-                                label: Some(&kernel.entrypoint_name),
+                                label: Some(name),
                                 source: Cow::Borrowed(source),
                                 num_workgroups: (repr.cube_dim.x, repr.cube_dim.y, repr.cube_dim.z),
                             },
                         ),
                     )
-                }
+                })?
             }
             _ => {
-                let source = &kernel.source;
+                let (module_key, _) = shader_keys.expect("WGSL kernels should be hashed");
 
-                let checks = wgpu::ShaderRuntimeChecks {
-                    // Cube does not need wgpu bounds checks - OOB behaviour is instead
-                    // checked by cube (if enabled).
-                    // This is because the WebGPU specification only makes loose guarantees that Cube can't rely on.
-                    bounds_checks: false,
-                    // Loop bounds are only checked in checked mode.
-                    force_loop_bounding: mode == ExecutionMode::Checked,
-                };
-
-                // SAFETY: Cube guarantees OOB safety when launching in checked mode. Launching in unchecked mode
-                // is only available through the use of unsafe code.
-                unsafe {
-                    self.device.create_shader_module_trusted(
-                        ShaderModuleDescriptor {
-                            label: None,
-                            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
-                        },
-                        checks,
-                    )
+                if let Some(module) = self.shader_modules.get(&module_key) {
+                    module.clone()
+                } else {
+                    let module = self.validated(name, CompilationStage::Compile, || {
+                        self.create_wgsl_module(&kernel.source, mode)
+                    })?;
+                    self.shader_modules.insert(module_key, module.clone());
+                    module
                 }
             }
         };
-        let bindings_info = match &kernel.repr {
-            Some(AutoRepresentation::Wgsl(repr)) => Some(wgsl::bindings(repr)),
-            #[cfg(all(feature = "msl", target_os = "macos"))]
-            Some(AutoRepresentation::Msl(repr)) => Some(cpp_metal::bindings(repr)),
-            #[cfg(feature = "spirv")]
-            Some(AutoRepresentation::SpirV(repr)) => Some(vulkan::bindings(repr)),
-            _ => None,
+
+        let constants = constants
+            .iter()
+            .map(|(id, value)| (id.as_str(), *value))
+            .collect::<Vec<_>>();
+        let push_constant_ranges = match push_constant_size {
+            0 => vec![],
+            size => vec![PushConstantRange {
+                stages: ShaderStages::COMPUTE,
+                range: 0..size,
+            }],
         };
 
-        let layout = bindings_info.map(|bindings| {
-            let (mut bindings, meta) = bindings;
-            // When slices are shared, it needs to be read-write if ANY of the slices is read-write,
-            // and since we can't be sure, we'll assume everything is read-write.
-            if !cfg!(exclusive_memory_only) {
-                bindings.fill(cubecl_core::compute::Visibility::ReadWrite);
-            }
+        let read_only = bindings_info
+            .as_ref()
+            .map(|(bindings, _)| {
+                bindings
+                    .iter()
+                    .map(|visibility| *visibility == Visibility::Read)
+                    .collect()
+            })
+            .unwrap_or_default();
 
+        let layout = bindings_info.map(|(bindings, meta)| {
             let bindings = bindings
                 .into_iter()
                 .chain(meta)
@@ -107,7 +216,7 @@ impl WgpuServer {
                     visibility: ShaderStages::COMPUTE,
                     ty: BindingType::Buffer {
                         ty: BufferBindingType::Storage {
-                            read_only: matches!(visibility, cubecl_core::compute::Visibility::Read),
+                            read_only: visibility == Visibility::Read,
                         },
                         has_dynamic_offset: false,
                         min_binding_size: None,
@@ -125,24 +234,83 @@ impl WgpuServer {
                 .create_pipeline_layout(&PipelineLayoutDescriptor {
                     label: None,
                     bind_group_layouts: &[&layout],
-                    push_constant_ranges: &[],
+                    push_constant_ranges: &push_constant_ranges,
                 })
         });
 
-        Arc::new(
+        let pipeline = self.validated(name, CompilationStage::Load, || {
             self.device
                 .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                    label: Some(&kernel.entrypoint_name),
+                    label: Some(name),
                     layout: layout.as_ref(),
                     module: &module,
-                    entry_point: Some(&kernel.entrypoint_name),
+                    entry_point: Some(name),
                     compilation_options: wgpu::PipelineCompilationOptions {
+                        constants: &constants,
                         zero_initialize_workgroup_memory: false,
                         ..Default::default()
                     },
                     cache: None,
-                }),
-        )
+                })
+        })?;
+        let pipeline = Arc::new(WgpuPipeline {
+            pipeline,
+            read_only,
+            push_constants: push_constant_size > 0,
+        });
+
+        if let Some((_, key)) = shader_keys {
+            self.shader_pipelines.insert(key, pipeline.clone());
+        }
+
+        Ok(pipeline)
+    }
+
+    /// Create an object of the device, returning the validation error it raises as an error of
+    /// the kernel `name` at the `stage` instead of the uncaptured error handler panicking.
+    #[cfg_attr(target_family = "wasm", allow(unused_variables))]
+    fn validated<T>(
+        &self,
+        name: &str,
+        stage: CompilationStage,
+        create: impl FnOnce() -> T,
+    ) -> Result<T, IoError> {
+        // Errors are only reported through error scopes, which can't be waited for on wasm.
+        #[cfg(not(target_family = "wasm"))]
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let created = create();
+
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(err) = cubecl_common::future::block_on(self.device.pop_error_scope()) {
+            return Err(CompilationError::new(name, stage, err.to_string()).into());
+        }
+
+        Ok(created)
+    }
+
+    /// Create the module of a WGSL shader.
+    fn create_wgsl_module(&self, source: &str, mode: ExecutionMode) -> ShaderModule {
+        let checks = wgpu::ShaderRuntimeChecks {
+            // Cube does not need wgpu bounds checks - OOB behaviour is instead
+            // checked by cube (if enabled).
+            // This is because the WebGPU specification only makes loose guarantees that Cube can't rely on.
+            bounds_checks: false,
+            // Loop bounds are only checked in checked mode.
+            force_loop_bounding: mode == ExecutionMode::Checked,
+        };
+
+        // SAFETY: Cube guarantees OOB safety when launching in checked mode. Launching in unchecked mode
+        // is only available through the use of unsafe code.
+        unsafe {
+            self.device.create_shader_module_trusted(
+                ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
+                },
+                checks,
+            )
+        }
     }
 }
 
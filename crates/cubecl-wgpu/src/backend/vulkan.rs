@@ -24,6 +24,9 @@ use crate::{AutoCompiler, WgpuServer};
 
 mod features;
 
+#[cfg(test)]
+mod tests;
+
 pub type VkSpirvCompiler = SpirvCompiler<GLCompute>;
 
 pub fn bindings(repr: &SpirvKernel) -> (Vec<Visibility>, Vec<Visibility>) {
@@ -156,8 +159,9 @@ fn register_features(
 
     log::debug!("Supported Vulkan features: {extended_feat:#?}");
 
-    register_types(props, &extended_feat);
+    register_types(props, &extended_feat, features);
     comp_options.supports_u64 = true;
+    comp_options.supports_int64_atomics = features.contains(Features::SHADER_INT64_ATOMIC_ALL_OPS);
     props.features.plane.insert(Plane::Sync);
 
     if let Some(float_controls2) = &extended_feat.float_controls2
@@ -171,7 +175,11 @@ fn register_features(
     }
 }
 
-fn register_types(props: &mut DeviceProperties, ext_feat: &ExtendedFeatures<'_>) {
+fn register_types(
+    props: &mut DeviceProperties,
+    ext_feat: &ExtendedFeatures<'_>,
+    features: Features,
+) {
     use cubecl_core::ir::{ElemType, FloatKind, IntKind, StorageType};
 
     let mut register = |elem: StorageType, usage: EnumSet<TypeUsage>| {
@@ -190,12 +198,7 @@ fn register_types(props: &mut DeviceProperties, ext_feat: &ExtendedFeatures<'_>)
         ElemType::Bool,
     ];
 
-    let default_atomic_types = [
-        ElemType::Int(IntKind::I32),
-        ElemType::Int(IntKind::I64),
-        ElemType::UInt(UIntKind::U32),
-        ElemType::UInt(UIntKind::U64),
-    ];
+    let default_atomic_types = [ElemType::Int(IntKind::I32), ElemType::UInt(UIntKind::U32)];
 
     for ty in default_types {
         register(ty.into(), TypeUsage::all_scalar());
@@ -205,6 +208,13 @@ fn register_types(props: &mut DeviceProperties, ext_feat: &ExtendedFeatures<'_>)
         register(StorageType::Atomic(ty), TypeUsage::all_atomic())
     }
 
+    // Requires `shaderBufferInt64Atomics`, enabled by wgpu along with the feature.
+    if features.contains(Features::SHADER_INT64_ATOMIC_ALL_OPS) {
+        for ty in [ElemType::Int(IntKind::I64), ElemType::UInt(UIntKind::U64)] {
+            register(StorageType::Atomic(ty), TypeUsage::all_atomic())
+        }
+    }
+
     if ext_feat.float16_int8.shader_float16 == TRUE {
         register(
             ElemType::Float(FloatKind::F16).into(),
@@ -377,7 +387,7 @@ fn convert_type(vk_ty: ComponentTypeKHR) -> Option<ElemType> {
 pub(crate) fn compile(
     dyn_comp: &mut AutoCompiler,
     server: &mut WgpuServer,
-    kernel: <WgpuServer as ComputeServer>::Kernel,
+    kernel: &<WgpuServer as ComputeServer>::Kernel,
     mode: ExecutionMode,
 ) -> CompiledKernel<AutoCompiler> {
     log::debug!("Compiling {}", kernel.name());
@@ -0,0 +1,113 @@
+use cubecl_core::{WgpuCompilationOptions, prelude::*, runtime_tests::to_client::kernel_matmul};
+use cubecl_spirv::SpirvKernel;
+
+use super::VkSpirvCompiler;
+use crate::WgpuRuntime;
+
+#[cube(launch, debug_symbols)]
+fn kernel_scale(input: &Array<f32>, output: &mut Array<f32>, factor: f32) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * factor;
+    }
+}
+
+fn disassemble(settings: KernelSettings) -> String {
+    let array = ArrayCompilationArg {
+        inplace: None,
+        line_size: 1,
+    };
+    let kernel = kernel_scale::KernelScale::<WgpuRuntime>::new(settings, array.clone(), array);
+
+    compile(kernel)
+}
+
+fn compile(kernel: impl CubeKernel) -> String {
+    let compiled = KernelTask::<VkSpirvCompiler, _>::new(kernel).compile(
+        &mut VkSpirvCompiler::default(),
+        &Default::default(),
+        ExecutionMode::Checked,
+    );
+    compiled.repr.unwrap().to_string()
+}
+
+fn has_name(source: &str, name: &str) -> bool {
+    let name = format!("\"{name}\"");
+    source
+        .lines()
+        .any(|line| line.trim_start().starts_with("OpName") && line.ends_with(&name))
+}
+
+#[test]
+fn debug_symbols_name_the_bindings() {
+    // The macro activates the debug symbols of this kernel.
+    let source = disassemble(KernelSettings::default().cube_dim(CubeDim::new_1d(16)));
+
+    assert!(has_name(&source, "input"));
+    assert!(has_name(&source, "output"));
+    // The kernel source is embedded for the line information.
+    assert!(source.contains("backend/vulkan/tests.rs\""));
+}
+
+#[test]
+fn no_debug_symbols_by_default() {
+    let array = ArrayCompilationArg {
+        inplace: None,
+        line_size: 1,
+    };
+    let kernel = kernel_matmul::KernelMatmul::<WgpuRuntime>::new(
+        KernelSettings::default().cube_dim(CubeDim::new_2d(4, 4)),
+        array.clone(),
+        array.clone(),
+        array,
+        3,
+    );
+    let source = compile(kernel);
+
+    assert!(!source.contains("OpName"));
+    assert!(!source.contains("OpString"));
+}
+
+#[test]
+fn comptime_values_are_lowered_to_spec_constants() {
+    let options = WgpuCompilationOptions {
+        override_constants: true,
+        ..Default::default()
+    };
+    let matmul = |k: u32| {
+        let array = ArrayCompilationArg {
+            inplace: None,
+            line_size: 1,
+        };
+        let kernel = kernel_matmul::KernelMatmul::<WgpuRuntime>::new(
+            KernelSettings::default().cube_dim(CubeDim::new_2d(4, 4)),
+            array.clone(),
+            array.clone(),
+            array,
+            k,
+        );
+        KernelTask::<VkSpirvCompiler, _>::new(kernel)
+            .compile(
+                &mut VkSpirvCompiler::default(),
+                &options,
+                ExecutionMode::Checked,
+            )
+            .repr
+            .unwrap()
+    };
+    let kernel = matmul(3);
+    let other = matmul(5);
+
+    assert!(kernel.to_string().contains("OpSpecConstant"));
+    assert_eq!(kernel.assemble_template(), other.assemble_template());
+    assert_ne!(kernel.assemble(), other.assemble());
+
+    let values = |kernel: &SpirvKernel| {
+        kernel
+            .spec_constants
+            .iter()
+            .map(|it| it.value)
+            .collect::<Vec<_>>()
+    };
+    assert!(values(&kernel).contains(&3));
+    assert!(values(&other).contains(&5));
+}
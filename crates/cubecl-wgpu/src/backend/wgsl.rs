@@ -2,14 +2,15 @@ use cubecl_core::{Compiler, compute::Visibility};
 #[cfg(not(all(target_os = "macos", feature = "msl")))]
 use cubecl_core::{
     WgpuCompilationOptions,
-    ir::{ElemType, UIntKind},
+    ir::{ElemType, FloatKind, UIntKind},
 };
 #[cfg(not(all(target_os = "macos", feature = "msl")))]
-use cubecl_runtime::DeviceProperties;
+use cubecl_runtime::{DeviceProperties, TypeUsage};
 #[cfg(not(all(target_os = "macos", feature = "msl")))]
 use wgpu::Features;
 
 use crate::WgslCompiler;
+use crate::compiler::wgsl::push_constant_layout;
 
 pub fn bindings(
     repr: &<WgslCompiler as Compiler>::Representation,
@@ -23,10 +24,35 @@ pub fn bindings(
     if repr.has_metadata {
         meta.push(Visibility::Read);
     }
-    meta.extend(repr.scalars.iter().map(|_| Visibility::Read));
+    if !repr.push_constants {
+        meta.extend(repr.scalars.iter().map(|_| Visibility::Read));
+    }
     (bindings, meta)
 }
 
+/// The size of the push constants of the shader, zero when it doesn't use any.
+pub fn push_constant_size(repr: &<WgslCompiler as Compiler>::Representation) -> u32 {
+    if !repr.push_constants {
+        return 0;
+    }
+
+    let (_, size) = push_constant_layout(
+        repr.scalars
+            .iter()
+            .map(|(elem, count)| (elem.size(), *count)),
+    );
+    size as u32
+}
+
+/// The values of the override constants of the shader, keyed by their `@id`.
+pub fn override_constants(repr: &<WgslCompiler as Compiler>::Representation) -> Vec<(String, f64)> {
+    repr.overrides
+        .iter()
+        .enumerate()
+        .map(|(id, constant)| (id.to_string(), constant.value))
+        .collect()
+}
+
 #[cfg(not(all(target_os = "macos", feature = "msl")))]
 pub async fn request_device(adapter: &wgpu::Adapter) -> (wgpu::Device, wgpu::Queue) {
     let limits = adapter.limits();
@@ -64,11 +90,19 @@ pub fn register_wgsl_features(
     if props.supports_type(ElemType::UInt(UIntKind::U64)) {
         comp_options.supports_u64 = true;
     }
+    if adapter.features().contains(Features::PUSH_CONSTANTS) {
+        comp_options.max_push_constant_size = adapter.limits().max_push_constant_size;
+    }
+    if props.supports_type(ElemType::Float(FloatKind::F16)) {
+        comp_options.supports_f16 = true;
+    } else if comp_options.emulate_f16 {
+        props.register_type_usage(ElemType::Float(FloatKind::F16), TypeUsage::all_scalar());
+    }
 }
 
 #[cfg(not(all(target_os = "macos", feature = "msl")))]
 pub fn register_types(props: &mut DeviceProperties, adapter: &wgpu::Adapter) {
-    use cubecl_core::ir::{ElemType, FloatKind, IntKind, StorageType};
+    use cubecl_core::ir::{ElemType, FloatKind, IntKind, SemanticType, StorageType};
     use cubecl_runtime::{EnumSet, TypeUsage};
 
     let supported_types = [
@@ -89,6 +123,9 @@ pub fn register_types(props: &mut DeviceProperties, adapter: &wgpu::Adapter) {
         register(ty.into(), TypeUsage::all_scalar())
     }
 
+    // Barriers are emulated with synchronous copies and workgroup barriers.
+    props.register_semantic_type(SemanticType::Barrier);
+
     for ty in supported_atomic_types {
         register(
             StorageType::Atomic(ty),
@@ -1,10 +1,12 @@
 use std::fmt::Display;
 
 use cubecl_common::ExecutionMode;
+#[cfg(feature = "spirv")]
+use cubecl_core::ir::StorageType;
 use cubecl_core::{
     Compiler, WgpuCompilationOptions,
     prelude::{CompiledKernel, KernelDefinition},
-    server::ComputeServer,
+    server::{ComputeServer, IoError},
 };
 #[cfg(feature = "msl")]
 use cubecl_cpp::shared::MslComputeKernel;
@@ -54,6 +56,33 @@ impl AutoRepresentation {
     }
 }
 
+impl AutoRepresentation {
+    /// Total size in bytes of the shared memories used by the kernel.
+    pub fn shared_memory_size(&self) -> usize {
+        match self {
+            AutoRepresentation::Wgsl(compute_shader) => compute_shader.shared_memory_size(),
+            #[cfg(feature = "spirv")]
+            AutoRepresentation::SpirV(spirv_kernel) => spirv_kernel.shared_memory_size,
+            #[cfg(feature = "msl")]
+            AutoRepresentation::Msl(compute_shader) => compute_shader.shared_memory_size(),
+        }
+    }
+
+    /// Check that the device supports the types used by the kernel.
+    #[cfg_attr(not(feature = "spirv"), allow(unused_variables))]
+    pub fn check_support(&self, options: &WgpuCompilationOptions) -> Result<(), IoError> {
+        #[cfg(feature = "spirv")]
+        if let AutoRepresentation::SpirV(spirv_kernel) = self
+            && let Some(elem) = spirv_kernel.int64_atomic
+            && !options.supports_int64_atomics
+        {
+            return Err(IoError::UnsupportedType(StorageType::Atomic(elem)));
+        }
+
+        Ok(())
+    }
+}
+
 impl Display for AutoRepresentation {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -120,7 +149,7 @@ impl AutoCompiler {
     pub fn compile(
         &mut self,
         server: &mut WgpuServer,
-        kernel: <WgpuServer as ComputeServer>::Kernel,
+        kernel: &<WgpuServer as ComputeServer>::Kernel,
         mode: ExecutionMode,
     ) -> CompiledKernel<Self> {
         match self {
@@ -8,6 +8,8 @@ pub enum Variable {
     GlobalOutputArray(Id, Item),
     GlobalScalar(Id, Elem),
     ConstantScalar(ConstantScalarValue, Elem),
+    /// A constant set when creating the pipeline.
+    Override(u32, Elem),
     LocalMut {
         id: Id,
         item: Item,
@@ -56,6 +58,8 @@ pub enum Variable {
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Elem {
     F16,
+    /// Pairs of f16 packed in a u32 word, used for f16 buffers when f16 is emulated.
+    PackedF16,
     F32,
     F64,
     AtomicF32,
@@ -87,6 +91,7 @@ impl Variable {
         match self {
             Variable::GlobalScalar(_, _) => true,
             Variable::ConstantScalar(_, _) => true,
+            Variable::Override(_, _) => true,
             Variable::LocalScalar { .. } => true,
             Variable::Id => true,
             Variable::LocalInvocationIndex => true,
@@ -151,6 +156,7 @@ impl Variable {
             Self::LocalConst { item, .. } => *item,
             Self::Named { item, .. } => *item,
             Self::ConstantScalar(_, e) => Item::Scalar(*e),
+            Self::Override(_, e) => Item::Scalar(*e),
             Self::GlobalScalar(_, e) => Item::Scalar(*e),
             Self::Id => Item::Scalar(Elem::U32),
             Self::LocalInvocationIndex => Item::Scalar(Elem::U32),
@@ -225,12 +231,44 @@ impl Item {
             text
         }
     }
+
+    /// The item computed from a line of [packed f16](Elem::PackedF16).
+    pub fn unpacked(&self) -> Item {
+        match self {
+            Item::Vec4(Elem::PackedF16) => Item::Vec4(Elem::F32),
+            Item::Vec2(Elem::PackedF16) => Item::Vec2(Elem::F32),
+            item => *item,
+        }
+    }
+
+    /// Unpack a line of [packed f16](Elem::PackedF16) to f32.
+    pub fn fmt_unpack_f16(&self, text: String) -> String {
+        match self {
+            Item::Vec4(Elem::PackedF16) => {
+                format!("vec4(unpack2x16float({text}.x), unpack2x16float({text}.y))")
+            }
+            Item::Vec2(Elem::PackedF16) => format!("unpack2x16float({text})"),
+            _ => text,
+        }
+    }
+
+    /// Pack a value to a line of [packed f16](Elem::PackedF16), converting it to f32 first.
+    pub fn fmt_pack_f16(&self, text: String) -> String {
+        match self {
+            Item::Vec4(Elem::PackedF16) => format!(
+                "vec2(pack2x16float(vec4<f32>({text}).xy), pack2x16float(vec4<f32>({text}).zw))"
+            ),
+            Item::Vec2(Elem::PackedF16) => format!("pack2x16float(vec2<f32>({text}))"),
+            _ => text,
+        }
+    }
 }
 
 impl Elem {
     pub fn size(&self) -> usize {
         match self {
             Self::F16 => core::mem::size_of::<half::f16>(),
+            Self::PackedF16 => core::mem::size_of::<half::f16>(),
             Self::F32 => core::mem::size_of::<f32>(),
             Self::F64 => core::mem::size_of::<f64>(),
             Self::AtomicF32 => core::mem::size_of::<f32>(),
@@ -253,6 +291,7 @@ impl Display for Elem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::F16 => f.write_str("f16"),
+            Self::PackedF16 => f.write_str("u32"),
             Self::F32 => f.write_str("f32"),
             Self::F64 => f.write_str("f64"),
             Self::AtomicF32 => f.write_str("atomic<f32>"),
@@ -270,6 +309,8 @@ impl Display for Elem {
 impl Display for Item {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Item::Vec4(Elem::PackedF16) => f.write_str("vec2<u32>"),
+            Item::Vec2(Elem::PackedF16) => f.write_str("u32"),
             Item::Vec4(elem) => write!(f, "vec4<{elem}>"),
             Item::Vec3(elem) => write!(f, "vec3<{elem}>"),
             Item::Vec2(elem) => write!(f, "vec2<{elem}>"),
@@ -302,7 +343,7 @@ impl Display for Variable {
             }
             // We do the conversion in Rust and then render the number to avoid overflow or other
             // precision related problems.
-            Variable::ConstantScalar(number, _elem) => match number {
+            Variable::ConstantScalar(number, elem) => match number {
                 ConstantScalarValue::Int(val, _) => write!(f, "{}", *val),
                 ConstantScalarValue::Float(val, kind) => match kind {
                     FloatKind::BF16
@@ -315,6 +356,8 @@ impl Display for Variable {
                     | FloatKind::UE8M0 => {
                         todo!("Unsupported")
                     }
+                    // Emulated f16 is computed in f32.
+                    FloatKind::F16 if *elem != Elem::F16 => f.write_str(&format_number(*val, "f")),
                     FloatKind::F16 => f.write_str(&format_number(*val, "h")),
                     FloatKind::F32 | FloatKind::Flex32 => f.write_str(&format_number(*val, "f")),
                     FloatKind::F64 => f.write_str(&format_number(*val, "lf")),
@@ -324,6 +367,7 @@ impl Display for Variable {
                 ConstantScalarValue::UInt(_, _) => unimplemented!("Unsupported"),
                 ConstantScalarValue::Bool(val) => write!(f, "{val}"),
             },
+            Variable::Override(id, _) => write!(f, "override_{id}"),
             Variable::SharedMemory(number, _, _) => {
                 write!(f, "shared_memory_{number}")
             }
@@ -1,6 +1,6 @@
 use super::Subgroup;
 use super::{ConstantArray, shader::ComputeShader};
-use super::{Item, LocalArray, SharedMemory};
+use super::{Item, LocalArray, OverrideConstant, SharedMemory};
 use crate::compiler::wgsl;
 
 use cubecl_common::ExecutionMode;
@@ -9,7 +9,7 @@ use cubecl_core::prelude::*;
 use cubecl_core::{
     Metadata, WgpuCompilationOptions, compute,
     ir::{self as cube, Scope},
-    prelude::expand_erf,
+    prelude::{expand_erf, expand_erfc},
 };
 use cubecl_core::{
     ir::{ConstantScalarValue, Processor, UIntKind},
@@ -37,11 +37,16 @@ pub struct WgslCompiler {
     num_workgroup_no_axis: bool,
     shared_memories: Vec<SharedMemory>,
     const_arrays: Vec<ConstantArray>,
+    overrides: Vec<OverrideConstant>,
     local_arrays: Vec<LocalArray>,
-    #[allow(dead_code)]
     compilation_options: WgpuCompilationOptions,
     strategy: ExecutionMode,
     subgroup_instructions_used: bool,
+    /// How many branches on a condition that may vary between units enclose the current scope.
+    divergent_depth: u32,
+    divergent_subgroup_warned: bool,
+    kernel_name: String,
+    push_constants: bool,
     f16_used: bool,
 }
 
@@ -81,6 +86,10 @@ impl WgslCompiler {
         mode: ExecutionMode,
     ) -> wgsl::ComputeShader {
         self.strategy = mode;
+        self.kernel_name = value.options.kernel_name.clone();
+        self.divergent_depth = 0;
+        self.divergent_subgroup_warned = false;
+        self.push_constants = self.use_push_constants(&value.scalars);
 
         let num_meta = value.buffers.len();
 
@@ -119,10 +128,18 @@ impl WgslCompiler {
             scalars: value
                 .scalars
                 .into_iter()
-                .map(|binding| (self.compile_storage_type(binding.ty), binding.count))
+                .map(|binding| {
+                    if self.is_emulated_f16(binding.ty) {
+                        (wgsl::Elem::PackedF16, binding.count.div_ceil(2))
+                    } else {
+                        (self.compile_storage_type(binding.ty), binding.count)
+                    }
+                })
                 .collect(),
+            push_constants: self.push_constants,
             shared_memories: self.shared_memories.clone(),
             constant_arrays: self.const_arrays.clone(),
+            overrides: core::mem::take(&mut self.overrides),
             local_arrays: self.local_arrays.clone(),
             has_metadata: self.metadata.static_len() > 0,
             workgroup_size: value.cube_dim,
@@ -187,6 +204,47 @@ impl WgslCompiler {
         }
     }
 
+    /// Global buffers keep their layout when f16 is emulated, so their f16 are packed in pairs.
+    fn compile_global_type(&mut self, item: cube::Type) -> wgsl::Item {
+        let compiled = self.compile_type(item);
+
+        if !self.is_emulated_f16(item.storage_type()) {
+            return compiled;
+        }
+
+        match compiled {
+            wgsl::Item::Vec2(_) => wgsl::Item::Vec2(wgsl::Elem::PackedF16),
+            wgsl::Item::Vec4(_) => wgsl::Item::Vec4(wgsl::Elem::PackedF16),
+            _ => panic!("Emulated f16 buffers must be accessed with a line size of 2 or 4"),
+        }
+    }
+
+    /// Scalars are set as push constants when they fit, saving a buffer per launch.
+    fn use_push_constants(&self, scalars: &[compute::ScalarBinding]) -> bool {
+        let max_size = self.compilation_options.max_push_constant_size as usize;
+
+        if scalars.is_empty() || max_size == 0 {
+            return false;
+        }
+        if scalars.iter().any(|scalar| self.is_emulated_f16(scalar.ty)) {
+            return false;
+        }
+
+        let (_, size) = wgsl::push_constant_layout(
+            scalars
+                .iter()
+                .map(|scalar| (scalar.ty.size(), scalar.count)),
+        );
+        size <= max_size
+    }
+
+    fn is_emulated_f16(&self, ty: cube::StorageType) -> bool {
+        let options = &self.compilation_options;
+        options.emulate_f16
+            && !options.supports_f16
+            && ty == cube::StorageType::Scalar(cube::ElemType::Float(cube::FloatKind::F16))
+    }
+
     fn compile_elem(&mut self, value: cube::ElemType) -> wgsl::Elem {
         match value {
             cube::ElemType::Float(f) => match f {
@@ -196,10 +254,16 @@ impl WgslCompiler {
                 | cube::FloatKind::E4M3
                 | cube::FloatKind::E5M2
                 | cube::FloatKind::UE8M0 => panic!("Minifloat is not a valid WgpuElement"),
-                cube::FloatKind::F16 => {
+                cube::FloatKind::F16 if self.compilation_options.supports_f16 => {
                     self.f16_used = true;
                     wgsl::Elem::F16
                 }
+                cube::FloatKind::F16 if self.compilation_options.emulate_f16 => wgsl::Elem::F32,
+                cube::FloatKind::F16 => {
+                    panic!(
+                        "f16 isn't supported by the adapter, it can be emulated with F16Policy::Emulate"
+                    )
+                }
                 cube::FloatKind::BF16 => panic!("bf16 is not a valid WgpuElement"),
                 cube::FloatKind::TF32 => panic!("tf32 is not a valid WgpuElement"),
                 cube::FloatKind::Flex32 => wgsl::Elem::F32,
@@ -229,7 +293,22 @@ impl WgslCompiler {
         let item = value.ty;
         match value.kind {
             cube::VariableKind::GlobalInputArray(id) => {
-                wgsl::Variable::GlobalInputArray(id, self.compile_type(item))
+                wgsl::Variable::GlobalInputArray(id, self.compile_global_type(item))
+            }
+            cube::VariableKind::GlobalScalar(id) if self.is_emulated_f16(item.storage_type()) => {
+                wgsl::Variable::Named {
+                    name: format!("unpack2x16float(scalars_f16[{}])[{}]", id / 2, id % 2),
+                    item: wgsl::Item::Scalar(wgsl::Elem::F32),
+                    is_array: false,
+                }
+            }
+            cube::VariableKind::GlobalScalar(id) if self.push_constants => {
+                let elem = self.compile_storage_type(item.storage_type());
+                wgsl::Variable::Named {
+                    name: format!("push_constants.scalars_{elem}_{id}"),
+                    item: wgsl::Item::Scalar(elem),
+                    is_array: false,
+                }
             }
             cube::VariableKind::GlobalScalar(id) => {
                 wgsl::Variable::GlobalScalar(id, self.compile_storage_type(item.storage_type()))
@@ -245,11 +324,9 @@ impl WgslCompiler {
                 item: self.compile_type(item),
             },
             cube::VariableKind::GlobalOutputArray(id) => {
-                wgsl::Variable::GlobalOutputArray(id, self.compile_type(item))
-            }
-            cube::VariableKind::ConstantScalar(value) => {
-                wgsl::Variable::ConstantScalar(value, self.compile_elem(value.elem_type()))
+                wgsl::Variable::GlobalOutputArray(id, self.compile_global_type(item))
             }
+            cube::VariableKind::ConstantScalar(value) => self.compile_constant(value),
             cube::VariableKind::SharedMemory {
                 id,
                 length,
@@ -387,7 +464,40 @@ impl WgslCompiler {
 
     fn constant_var(&mut self, value: u32) -> wgsl::Variable {
         let var = cube::Variable::constant(ConstantScalarValue::UInt(value as u64, UIntKind::U32));
-        self.compile_variable(var)
+        self.compile_const_expr(var)
+    }
+
+    /// Compile a constant, lowering it to a pipeline-overridable constant when enabled.
+    ///
+    /// Each use gets its own override, so kernels with the same structure share the same module
+    /// whichever values happen to be equal.
+    fn compile_constant(&mut self, value: ConstantScalarValue) -> wgsl::Variable {
+        let elem = self.compile_elem(value.elem_type());
+        let override_value = match value {
+            ConstantScalarValue::UInt(val, UIntKind::U32) => Some(val as f64),
+            ConstantScalarValue::Bool(val) => Some(val as u32 as f64),
+            _ => None,
+        };
+
+        match override_value.filter(|_| self.compilation_options.override_constants) {
+            Some(value) => {
+                let id = self.overrides.len() as u32;
+                self.overrides.push(OverrideConstant { elem, value });
+                wgsl::Variable::Override(id, elem)
+            }
+            None => wgsl::Variable::ConstantScalar(value, elem),
+        }
+    }
+
+    /// Compile a variable used where WGSL requires a const-expression, keeping constants as
+    /// literals.
+    fn compile_const_expr(&mut self, var: cube::Variable) -> wgsl::Variable {
+        match var.kind {
+            cube::VariableKind::ConstantScalar(value) => {
+                wgsl::Variable::ConstantScalar(value, self.compile_elem(value.elem_type()))
+            }
+            _ => self.compile_variable(var),
+        }
     }
 
     fn compile_scope(&mut self, scope: &mut cube::Scope) -> Vec<wgsl::Instruction> {
@@ -402,7 +512,7 @@ impl WgslCompiler {
                 size: values.len() as u32,
                 values: values
                     .into_iter()
-                    .map(|val| self.compile_variable(val))
+                    .map(|val| self.compile_const_expr(val))
                     .collect(),
             })
             .collect::<Vec<_>>();
@@ -461,9 +571,7 @@ impl WgslCompiler {
                 self.compile_comment(instructions, content)
             }
             cube::Operation::NonSemantic(_) => {}
-            cube::Operation::Barrier(_) => {
-                panic!("Barrier isn't supported on wgpu.")
-            }
+            cube::Operation::Barrier(barrier) => self.compile_barrier(instructions, barrier, out),
             cube::Operation::Tma(_) => panic!("TMA isn't supported on wgpu."),
         }
     }
@@ -476,6 +584,17 @@ impl WgslCompiler {
     ) {
         self.subgroup_instructions_used = true;
 
+        // WGSL requires subgroup operations to be in uniform control flow, but naga doesn't
+        // enforce it. Units that don't take part in the operation make its result undefined.
+        if self.divergent_depth > 0 && !self.divergent_subgroup_warned {
+            self.divergent_subgroup_warned = true;
+            log::warn!(
+                "Kernel {} uses plane operations inside a branch, all units of the plane must \
+                 take the same path for their results to be defined",
+                self.kernel_name
+            );
+        }
+
         let out = out.unwrap();
         let op = match subgroup {
             cube::Plane::Elect => Subgroup::Elect {
@@ -539,21 +658,24 @@ impl WgslCompiler {
         match branch {
             cube::Branch::If(mut op) => instructions.push(wgsl::Instruction::If {
                 cond: self.compile_variable(op.cond),
-                instructions: self.compile_scope(&mut op.scope),
+                instructions: self.compile_divergent_scope(&mut op.scope),
             }),
             cube::Branch::IfElse(mut op) => instructions.push(wgsl::Instruction::IfElse {
                 cond: self.compile_variable(op.cond),
-                instructions_if: self.compile_scope(&mut op.scope_if),
-                instructions_else: self.compile_scope(&mut op.scope_else),
+                instructions_if: self.compile_divergent_scope(&mut op.scope_if),
+                instructions_else: self.compile_divergent_scope(&mut op.scope_else),
             }),
             cube::Branch::Switch(mut op) => instructions.push(wgsl::Instruction::Switch {
                 value: self.compile_variable(op.value),
-                instructions_default: self.compile_scope(&mut op.scope_default),
+                instructions_default: self.compile_divergent_scope(&mut op.scope_default),
                 cases: op
                     .cases
                     .into_iter()
                     .map(|(val, mut scope)| {
-                        (self.compile_variable(val), self.compile_scope(&mut scope))
+                        (
+                            self.compile_const_expr(val),
+                            self.compile_divergent_scope(&mut scope),
+                        )
                     })
                     .collect(),
             }),
@@ -575,6 +697,74 @@ impl WgslCompiler {
         };
     }
 
+    /// Barriers are emulated with synchronous copies, so waiting on a barrier only has to make
+    /// the copies of other units visible.
+    fn compile_barrier(
+        &mut self,
+        instructions: &mut Vec<wgsl::Instruction>,
+        barrier: cube::BarrierOps,
+        out: Option<cube::Variable>,
+    ) {
+        let level = |barrier: &cube::Variable| match barrier.kind {
+            cube::VariableKind::Barrier { level, .. } => level,
+            _ => unreachable!(),
+        };
+
+        match barrier {
+            cube::BarrierOps::Init { .. } | cube::BarrierOps::Arrive { .. } => {}
+            cube::BarrierOps::MemCopyAsync {
+                barrier,
+                source,
+                source_length,
+                offset_source,
+                offset_out,
+            } => {
+                // Cooperative copies are called with the same arguments by all units, so the
+                // copy is distributed over the cube.
+                let stride = match level(&barrier) {
+                    cube::BarrierLevel::CubeCoop(_) => {
+                        self.local_invocation_index = true;
+                        self.workgroup_size_no_axis = true;
+                        Some((
+                            wgsl::Variable::LocalInvocationIndex,
+                            wgsl::Variable::WorkgroupSize,
+                        ))
+                    }
+                    cube::BarrierLevel::Unit | cube::BarrierLevel::CubeManual(_) => None,
+                };
+
+                instructions.push(wgsl::Instruction::CopyLoop {
+                    input: self.compile_variable(source),
+                    in_index: self.compile_variable(offset_source),
+                    out: self.compile_variable(out.unwrap()),
+                    out_index: self.compile_variable(offset_out),
+                    len: self.compile_variable(source_length),
+                    stride,
+                });
+            }
+            cube::BarrierOps::Wait { barrier } | cube::BarrierOps::ArriveAndWait { barrier } => {
+                match level(&barrier) {
+                    // A unit only waits on its own copies, which are already done.
+                    cube::BarrierLevel::Unit => {}
+                    cube::BarrierLevel::CubeCoop(_) | cube::BarrierLevel::CubeManual(_) => {
+                        instructions.push(wgsl::Instruction::WorkgroupBarrier)
+                    }
+                }
+            }
+            cube::BarrierOps::TmaLoad { .. }
+            | cube::BarrierOps::TmaLoadIm2col { .. }
+            | cube::BarrierOps::ArriveTx { .. }
+            | cube::BarrierOps::ExpectTx { .. } => panic!("TMA isn't supported on wgpu."),
+        }
+    }
+
+    fn compile_divergent_scope(&mut self, scope: &mut cube::Scope) -> Vec<wgsl::Instruction> {
+        self.divergent_depth += 1;
+        let instructions = self.compile_scope(scope);
+        self.divergent_depth -= 1;
+        instructions
+    }
+
     fn compile_synchronization(
         &mut self,
         instructions: &mut Vec<wgsl::Instruction>,
@@ -732,6 +922,10 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::Exp2(op) => instructions.push(wgsl::Instruction::Exp2 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
             cube::Arithmetic::Log(op) => instructions.push(wgsl::Instruction::Log {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
@@ -740,6 +934,10 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::Log2(op) => instructions.push(wgsl::Instruction::Log2 {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
             cube::Arithmetic::Cos(op) => instructions.push(wgsl::Instruction::Cos {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
@@ -764,6 +962,10 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            cube::Arithmetic::Rsqrt(op) => instructions.push(wgsl::Instruction::Rsqrt {
+                input: self.compile_variable(op.input),
+                out: self.compile_variable(out),
+            }),
             cube::Arithmetic::Round(op) => instructions.push(wgsl::Instruction::Round {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
@@ -776,11 +978,17 @@ impl WgslCompiler {
                 input: self.compile_variable(op.input),
                 out: self.compile_variable(out),
             }),
+            // No erf or erfc in WGSL
             cube::Arithmetic::Erf(op) => {
                 let mut scope = scope.child();
                 expand_erf(&mut scope, op.input, out);
                 instructions.extend(self.compile_scope(&mut scope));
             }
+            cube::Arithmetic::Erfc(op) => {
+                let mut scope = scope.child();
+                expand_erfc(&mut scope, op.input, out);
+                instructions.extend(self.compile_scope(&mut scope));
+            }
             cube::Arithmetic::MulHi(op) => {
                 let mut scope = scope.child();
                 match self.compilation_options.supports_u64 {
@@ -1073,7 +1281,7 @@ impl WgslCompiler {
             id: value.id,
             visibility: value.visibility,
             location: Self::compile_location(value.location),
-            item: self.compile_type(value.ty),
+            item: self.compile_global_type(value.ty),
             size: value.size,
         }
     }
@@ -219,10 +219,12 @@ fn format_safe_tanh_primitive(
     write!(
         f,
         "
-/// Metal has a weird numerical behaviour with tanh for inputs over 43.0
+/// Metal has a weird numerical behaviour with tanh for inputs over 43.0 in magnitude
 fn {function_name}(x: {elem}) -> {elem} {{
     if x > 43.0 {{
         return 1.0;
+    }} else if x < -43.0 {{
+        return -1.0;
     }} else {{
         return tanh(x);
     }}
@@ -101,6 +101,10 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Exp2 {
+        input: Variable,
+        out: Variable,
+    },
     Log {
         input: Variable,
         out: Variable,
@@ -109,6 +113,10 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Log2 {
+        input: Variable,
+        out: Variable,
+    },
     Cos {
         input: Variable,
         out: Variable,
@@ -130,6 +138,10 @@ pub enum Instruction {
         input: Variable,
         out: Variable,
     },
+    Rsqrt {
+        input: Variable,
+        out: Variable,
+    },
     Recip {
         input: Variable,
         out: Variable,
@@ -369,6 +381,16 @@ pub enum Instruction {
         out: Variable,
         out_index: Variable,
     },
+    /// Copy `len` items, each unit copying one item every `stride.1` items starting at `stride.0`
+    /// when a stride is given, or all of them otherwise.
+    CopyLoop {
+        input: Variable,
+        in_index: Variable,
+        out: Variable,
+        out_index: Variable,
+        len: Variable,
+        stride: Option<(Variable, Variable)>,
+    },
     CopyBulk {
         input: Variable,
         in_index: Variable,
@@ -496,10 +518,31 @@ impl Display for Instruction {
                 out,
                 out_index,
             } => {
-                let rhs = format!("{input}[{in_index}]");
+                let rhs = fmt_copy_value(input, out, format!("{input}[{in_index}]"));
                 let lhs = format!("{out}[{out_index}]");
                 writeln!(f, "{lhs} = {rhs};")
             }
+            Instruction::CopyLoop {
+                input,
+                in_index,
+                out,
+                out_index,
+                len,
+                stride,
+            } => {
+                let (start, step) = match stride {
+                    Some((start, step)) => (format!("{start}"), format!("{step}")),
+                    None => ("0u".to_string(), "1u".to_string()),
+                };
+                let rhs = fmt_copy_value(input, out, format!("{input}[{in_index} + copy_i]"));
+
+                writeln!(
+                    f,
+                    "for (var copy_i: u32 = {start}; copy_i < {len}; copy_i += {step}) {{"
+                )?;
+                writeln!(f, "{out}[{out_index} + copy_i] = {rhs};")?;
+                f.write_str("}\n")
+            }
             Instruction::CopyBulk {
                 input,
                 in_index,
@@ -508,7 +551,7 @@ impl Display for Instruction {
                 len,
             } => {
                 for i in 0..*len {
-                    let rhs = format!("{input}[{in_index} + {i}]");
+                    let rhs = fmt_copy_value(input, out, format!("{input}[{in_index} + {i}]"));
                     let lhs = format!("{out}[{out_index} + {i}]");
                     writeln!(f, "{lhs} = {rhs};")?;
                 }
@@ -557,10 +600,18 @@ impl Display for Instruction {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = exp({input});")
             }
+            Instruction::Exp2 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = exp2({input});")
+            }
             Instruction::Log { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = log({input});")
             }
+            Instruction::Log2 { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = log2({input});")
+            }
             Instruction::Clamp {
                 input,
                 min_value,
@@ -577,6 +628,10 @@ impl Display for Instruction {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = sqrt({input});")
             }
+            Instruction::Rsqrt { input, out } => {
+                let out = out.fmt_left();
+                writeln!(f, "{out} = inverseSqrt({input});")
+            }
             Instruction::Log1p { input, out } => {
                 let out = out.fmt_left();
                 writeln!(f, "{out} = log({input} + 1.0);")
@@ -1069,6 +1124,18 @@ impl Display for IndexOffset {
     }
 }
 
+/// Convert a copied value when only one side of the copy is [packed f16](Elem::PackedF16).
+fn fmt_copy_value(input: &Variable, out: &Variable, value: String) -> String {
+    match (
+        input.elem() == Elem::PackedF16,
+        out.elem() == Elem::PackedF16,
+    ) {
+        (true, false) => input.item().fmt_unpack_f16(value),
+        (false, true) => out.item().fmt_pack_f16(value),
+        _ => value,
+    }
+}
+
 fn index(
     f: &mut std::fmt::Formatter<'_>,
     lhs: &Variable,
@@ -1080,7 +1147,7 @@ fn index(
     let is_scalar = match lhs {
         Variable::LocalMut { item, .. } => item.vectorization_factor() == 1,
         Variable::LocalConst { item, .. } => item.vectorization_factor() == 1,
-        Variable::ConstantScalar(..) => true,
+        Variable::ConstantScalar(..) | Variable::Override(..) => true,
         _ => false,
     };
 
@@ -1096,14 +1163,17 @@ fn index(
         (value, Some(format!("{rhs}")))
     };
 
+    let lhs_item = lhs.item().unpacked();
+    value = lhs.item().fmt_unpack_f16(value);
+
     if out.item().elem().is_atomic() {
         // Atomic values don't support casting or bound checking - we just assign the reference.
         value = format!("&{value}");
         writeln!(f, "let {out} = {value};")
     } else {
         // Check for casting
-        if lhs.elem() != out.elem() {
-            value = lhs.item().fmt_cast_to(out.item(), value)
+        if *lhs_item.elem() != out.elem() {
+            value = lhs_item.fmt_cast_to(out.item(), value)
         };
 
         // Check for bounds.
@@ -1130,6 +1200,12 @@ fn index_assign(
     out: &Variable,
     offset: Option<Variable>,
 ) -> core::fmt::Result {
+    if out.elem() == Elem::PackedF16 {
+        let lhs = IndexOffset::new(lhs, &offset, 0);
+        let rhs = out.item().fmt_pack_f16(format!("{rhs}"));
+        return writeln!(f, "{out}[{lhs}] = {rhs};");
+    }
+
     match lhs.item() {
         Item::Vec4(elem) => {
             let item = Item::Scalar(elem);
@@ -13,3 +13,6 @@ pub(crate) use extension::*;
 pub(crate) use instructions::*;
 pub(crate) use shader::*;
 pub(crate) use subgroup::*;
+
+#[cfg(test)]
+mod tests;
@@ -38,6 +38,17 @@ impl SharedMemory {
     }
 }
 
+impl SharedMemory {
+    fn size_bytes(&self) -> usize {
+        // Arrays of 3-element vectors are laid out like arrays of 4-element vectors.
+        let factor = match self.item {
+            Item::Vec3(_) => 4,
+            item => item.vectorization_factor(),
+        };
+        self.size as usize * factor * self.item.elem().size()
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct ConstantArray {
     pub index: Id,
@@ -46,6 +57,14 @@ pub struct ConstantArray {
     pub values: Vec<Variable>,
 }
 
+/// A constant of the shader set when creating its pipeline, declared as `override_{index}`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OverrideConstant {
+    pub elem: Elem,
+    /// The value of the constant, as expected by the pipeline.
+    pub value: f64,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LocalArray {
     pub index: Id,
@@ -63,8 +82,12 @@ impl LocalArray {
 pub struct ComputeShader {
     pub buffers: Vec<Binding>,
     pub scalars: Vec<(Elem, usize)>,
+    /// Whether the scalars are set as push constants instead of being bound as buffers.
+    pub push_constants: bool,
     pub shared_memories: Vec<SharedMemory>,
     pub constant_arrays: Vec<ConstantArray>,
+    /// The constants set when creating the pipeline, indexed by their `@id`.
+    pub overrides: Vec<OverrideConstant>,
     pub local_arrays: Vec<LocalArray>,
     pub has_metadata: bool,
     pub workgroup_size: CubeDim,
@@ -106,14 +129,16 @@ impl Display for ComputeShader {
             offset += 1;
         }
 
-        for (i, (elem, len)) in self.scalars.iter().enumerate() {
-            Self::format_scalar_binding(
-                f,
-                &format!("scalars_{elem}"),
-                *elem,
-                Some(*len),
-                offset + i,
-            )?;
+        if self.push_constants {
+            Self::format_push_constants(f, &self.scalars)?;
+        } else {
+            for (i, (elem, len)) in self.scalars.iter().enumerate() {
+                let name = match elem {
+                    Elem::PackedF16 => "scalars_f16".to_string(),
+                    elem => format!("scalars_{elem}"),
+                };
+                Self::format_scalar_binding(f, &name, *elem, Some(*len), offset + i)?;
+            }
         }
 
         for array in self.shared_memories.iter() {
@@ -137,6 +162,10 @@ impl Display for ComputeShader {
             f.write_str(");\n\n")?;
         }
 
+        for (id, constant) in self.overrides.iter().enumerate() {
+            writeln!(f, "@id({id}) override override_{id}: {};", constant.elem)?;
+        }
+
         write!(
             f,
             "const WORKGROUP_SIZE_X = {}u;
@@ -220,6 +249,14 @@ fn {}(
 }
 
 impl ComputeShader {
+    /// Total size in bytes of the shared memories used by the shader.
+    pub fn shared_memory_size(&self) -> usize {
+        self.shared_memories
+            .iter()
+            .map(SharedMemory::size_bytes)
+            .sum()
+    }
+
     fn format_bindings(
         f: &mut core::fmt::Formatter<'_>,
         prefix: &str,
@@ -250,9 +287,8 @@ impl ComputeShader {
         };
 
         let visibility = match binding.visibility {
-            #[cfg(exclusive_memory_only)]
             Visibility::Read => "read",
-            _ => "read_write",
+            Visibility::ReadWrite => "read_write",
         };
 
         write!(
@@ -267,6 +303,19 @@ var<{}, {}> {}: {};
         Ok(())
     }
 
+    fn format_push_constants(
+        f: &mut core::fmt::Formatter<'_>,
+        scalars: &[(Elem, usize)],
+    ) -> core::fmt::Result {
+        f.write_str("struct PushConstants {\n")?;
+        for (elem, len) in scalars {
+            for i in 0..*len {
+                writeln!(f, "    scalars_{elem}_{i}: {elem},")?;
+            }
+        }
+        f.write_str("}\n\nvar<push_constant> push_constants: PushConstants;\n\n")
+    }
+
     fn format_scalar_binding(
         f: &mut core::fmt::Formatter<'_>,
         name: &str,
@@ -304,3 +353,25 @@ impl Display for Location {
         }
     }
 }
+
+/// The offset of each group of scalars in the push constants, given the size of their elements
+/// and their count, along with the size of the push constants.
+///
+/// This follows the layout of the WGSL struct, where each scalar is aligned to its size. The size
+/// is padded to a multiple of 4 bytes, as required by wgpu.
+pub(crate) fn push_constant_layout(
+    groups: impl IntoIterator<Item = (usize, usize)>,
+) -> (Vec<usize>, usize) {
+    let mut offsets = Vec::new();
+    let mut size = 0;
+    let mut align = 4;
+
+    for (elem_size, count) in groups {
+        size = size.next_multiple_of(elem_size);
+        offsets.push(size);
+        size += elem_size * count;
+        align = align.max(elem_size);
+    }
+
+    (offsets, size.next_multiple_of(align))
+}
@@ -121,7 +121,13 @@ impl Display for Subgroup {
             }
             Subgroup::Broadcast { lhs, rhs, out } => {
                 let out = out.fmt_left();
-                writeln!(f, "{out} = subgroupBroadcast({lhs}, {rhs});")
+                match rhs {
+                    // The lane of `subgroupBroadcast` must be a const-expression.
+                    Variable::ConstantScalar(..) => {
+                        writeln!(f, "{out} = subgroupBroadcast({lhs}, {rhs});")
+                    }
+                    _ => writeln!(f, "{out} = subgroupShuffle({lhs}, {rhs});"),
+                }
             }
             Subgroup::Ballot { input, out } => {
                 let out = out.fmt_left();
@@ -0,0 +1,52 @@
+use cubecl_core::{WgpuCompilationOptions, prelude::*, runtime_tests::to_client::kernel_matmul};
+
+use crate::{WgpuRuntime, WgslCompiler};
+
+fn matmul_shader(k: u32, options: &WgpuCompilationOptions) -> CompiledKernel<WgslCompiler> {
+    let array = ArrayCompilationArg {
+        inplace: None,
+        line_size: 1,
+    };
+    let kernel = kernel_matmul::KernelMatmul::<WgpuRuntime>::new(
+        KernelSettings::default().cube_dim(CubeDim::new_2d(4, 4)),
+        array.clone(),
+        array.clone(),
+        array,
+        k,
+    );
+
+    KernelTask::<WgslCompiler, _>::new(kernel).compile(
+        &mut WgslCompiler::default(),
+        options,
+        ExecutionMode::Checked,
+    )
+}
+
+#[test]
+fn inputs_are_bound_as_read_only() {
+    let source = matmul_shader(3, &Default::default()).source;
+
+    assert!(source.contains("var<storage, read> buffer_0_global: array<f32>;"));
+    assert!(source.contains("var<storage, read> buffer_1_global: array<f32>;"));
+    assert!(source.contains("var<storage, read_write> buffer_2_global: array<f32>;"));
+}
+
+#[test]
+fn comptime_values_are_lowered_to_overrides() {
+    let options = WgpuCompilationOptions {
+        override_constants: true,
+        ..Default::default()
+    };
+    let shader = matmul_shader(3, &options);
+    let other = matmul_shader(5, &options);
+
+    assert!(shader.source.contains("@id(0) override override_0: u32;"));
+    assert_eq!(shader.source, other.source);
+
+    let values = |shader: &CompiledKernel<WgslCompiler>| {
+        let repr = shader.repr.as_ref().unwrap();
+        repr.overrides.iter().map(|it| it.value).collect::<Vec<_>>()
+    };
+    assert!(values(&shader).contains(&3.0));
+    assert!(values(&other).contains(&5.0));
+}
@@ -0,0 +1,81 @@
+use cubecl_runtime::memory_management::SliceHandle;
+use hashbrown::HashMap;
+
+/// Maximum number of bind groups kept by the cache.
+const MAX_BIND_GROUPS: usize = 1024;
+
+/// What a bind group is created from.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) struct BindGroupKey {
+    pub pipeline: wgpu::ComputePipeline,
+    /// The buffer, offset and size of each binding.
+    pub buffers: Vec<(wgpu::Buffer, u64, u64)>,
+    /// The data of the uniforms, bound after the buffers.
+    pub uniforms: Vec<Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct CachedBindGroup {
+    bind_group: wgpu::BindGroup,
+    /// Keeps the memory of the uniforms from being reused while the bind group is cached.
+    _uniforms: Vec<SliceHandle>,
+    last_used: u64,
+}
+
+/// The bind groups of previous launches, reused when a pipeline is launched again on the same
+/// resources, evicting the least recently used ones once full.
+///
+/// The cache is cleared when the memory pool deallocates buffers, so that it doesn't keep them
+/// alive.
+#[derive(Debug, Default)]
+pub(crate) struct BindGroupCache {
+    bind_groups: HashMap<BindGroupKey, CachedBindGroup>,
+    generation: u64,
+    clock: u64,
+}
+
+impl BindGroupCache {
+    /// Clear the cache if the [generation](crate::WgpuStorage::generation) of the memory pool
+    /// changed since the last call.
+    pub fn invalidate(&mut self, generation: u64) {
+        if generation != self.generation {
+            self.bind_groups.clear();
+            self.generation = generation;
+        }
+    }
+
+    pub fn get(&mut self, key: &BindGroupKey) -> Option<wgpu::BindGroup> {
+        self.clock += 1;
+        let cached = self.bind_groups.get_mut(key)?;
+        cached.last_used = self.clock;
+
+        Some(cached.bind_group.clone())
+    }
+
+    pub fn insert(
+        &mut self,
+        key: BindGroupKey,
+        bind_group: wgpu::BindGroup,
+        uniforms: Vec<SliceHandle>,
+    ) {
+        if self.bind_groups.len() >= MAX_BIND_GROUPS {
+            let oldest = self
+                .bind_groups
+                .values()
+                .map(|cached| cached.last_used)
+                .min()
+                .unwrap_or_default();
+            self.bind_groups
+                .retain(|_, cached| cached.last_used != oldest);
+        }
+
+        self.bind_groups.insert(
+            key,
+            CachedBindGroup {
+                bind_group,
+                _uniforms: uniforms,
+                last_used: self.clock,
+            },
+        );
+    }
+}
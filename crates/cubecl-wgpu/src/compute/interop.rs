@@ -0,0 +1,65 @@
+use cubecl_core::server::{Handle, IoError};
+use cubecl_runtime::{
+    channel::MutexComputeChannel, client::ComputeClient, storage::BindingResource,
+};
+
+use super::{WgpuResource, WgpuServer};
+
+/// Zero-copy interop between a [client](ComputeClient) of the wgpu runtime and wgpu code using
+/// the same [device and queue](crate::init_device).
+///
+/// # Synchronization
+///
+/// The client records its work in its own command encoder, only submitted to the queue when the
+/// client is flushed, during a read, a sync, or once enough work is recorded. Submissions to a
+/// queue execute in order, so:
+///
+/// - Work submitted to the queue before a kernel is launched is visible to the kernel, since the
+///   kernel is submitted later.
+/// - Work of the client can still be pending in its encoder when you submit your own commands.
+///   [Flush](Self::flush_submission) the client before submitting commands that use a buffer
+///   written by the client, so they execute after its kernels.
+///
+/// Waiting on the host for the work of the client, e.g. before mapping a buffer, can be done with
+/// the [submission index](wgpu::SubmissionIndex) returned when flushing.
+pub trait WgpuInterop {
+    /// Import `size` bytes of a buffer created outside of the client as a handle, without copying
+    /// them.
+    ///
+    /// The buffer must be created on the device of the client with the
+    /// [STORAGE](wgpu::BufferUsages::STORAGE) usage. It is never reused for other allocations
+    /// nor destroyed by the client, which only keeps a reference to it until the handle and the
+    /// kernels using it are dropped.
+    fn import_buffer(&self, buffer: wgpu::Buffer, size: u64) -> Result<Handle, IoError>;
+
+    /// The buffer behind a handle, with the offset and size of the handle in the buffer.
+    ///
+    /// The memory of the handle isn't reused while the returned binding is alive.
+    fn buffer(&self, handle: &Handle) -> BindingResource<WgpuResource>;
+
+    /// Submit the work of the client to the queue, returning the index of a submission that
+    /// completes after it, to be waited for with
+    /// [`PollType::WaitForSubmissionIndex`](wgpu::PollType::WaitForSubmissionIndex).
+    ///
+    /// The queue must be the one the client was created with.
+    fn flush_submission(&self, queue: &wgpu::Queue) -> wgpu::SubmissionIndex;
+}
+
+impl WgpuInterop for ComputeClient<WgpuServer, MutexComputeChannel<WgpuServer>> {
+    fn import_buffer(&self, buffer: wgpu::Buffer, size: u64) -> Result<Handle, IoError> {
+        self.import_resource(WgpuResource::new(buffer, 0, size))
+    }
+
+    fn buffer(&self, handle: &Handle) -> BindingResource<WgpuResource> {
+        self.get_resource(handle.clone().binding())
+    }
+
+    fn flush_submission(&self, queue: &wgpu::Queue) -> wgpu::SubmissionIndex {
+        self.flush();
+        // Submissions complete in order, so an empty one completes after the work of the client.
+        queue.submit([])
+    }
+}
+
+#[cfg(test)]
+mod tests;
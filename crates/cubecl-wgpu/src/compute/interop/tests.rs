@@ -0,0 +1,129 @@
+use cubecl_core::{future, prelude::*};
+use cubecl_runtime::channel::MutexComputeChannel;
+
+use super::WgpuInterop;
+use crate::{
+    AutoGraphicsApi, GraphicsApi, WgpuDevice, WgpuRuntime, WgpuServer, WgpuSetup, init_device,
+};
+
+const LEN: usize = 64;
+
+const FILL_SHADER: &str = "
+@group(0) @binding(0) var<storage, read_write> data: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    data[id.x] = f32(id.x);
+}
+";
+
+#[cube(launch)]
+fn kernel_double(input: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * 2.0;
+    }
+}
+
+fn setup() -> (
+    WgpuSetup,
+    ComputeClient<WgpuServer, MutexComputeChannel<WgpuServer>>,
+) {
+    let setup = future::block_on(crate::runtime::create_setup_for_device(
+        &WgpuDevice::DefaultDevice,
+        AutoGraphicsApi::backend(),
+        None,
+    ));
+    let device = init_device(setup.clone(), Default::default());
+
+    (setup, WgpuRuntime::client(&device))
+}
+
+/// Fill a buffer with its indices from a compute pass recorded outside of the client.
+fn fill_indices(setup: &WgpuSetup, buffer: &wgpu::Buffer) {
+    let module = setup
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(FILL_SHADER.into()),
+        });
+    let pipeline = setup
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+    let bind_group = setup.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: buffer.as_entire_binding(),
+        }],
+    });
+
+    let mut encoder = setup
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(1, 1, 1);
+    }
+    setup.queue.submit([encoder.finish()]);
+}
+
+#[test]
+fn imported_buffer_round_trip() {
+    let (setup, client) = setup();
+    let size = (LEN * size_of::<f32>()) as u64;
+    let buffer = setup.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size,
+        usage: wgpu::BufferUsages::STORAGE,
+        mapped_at_creation: false,
+    });
+
+    fill_indices(&setup, &buffer);
+
+    let input = client.import_buffer(buffer.clone(), size).unwrap();
+    assert_eq!(client.buffer(&input).resource().buffer, buffer);
+
+    let output = client.empty(size as usize);
+    unsafe {
+        kernel_double::launch::<WgpuRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(LEN as u32),
+            ArrayArg::from_raw_parts::<f32>(&input, LEN, 1),
+            ArrayArg::from_raw_parts::<f32>(&output, LEN, 1),
+        );
+    }
+
+    let index = client.flush_submission(&setup.queue);
+    setup
+        .device
+        .poll(wgpu::PollType::WaitForSubmissionIndex(index))
+        .unwrap();
+
+    let actual = client.read_one(output);
+    let expected = (0..LEN).map(|i| i as f32 * 2.0).collect::<Vec<_>>();
+    assert_eq!(f32::from_bytes(&actual), expected);
+}
+
+#[test]
+fn import_requires_storage_usage() {
+    let (setup, client) = setup();
+    let buffer = setup.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 256,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    assert!(client.import_buffer(buffer, 256).is_err());
+}
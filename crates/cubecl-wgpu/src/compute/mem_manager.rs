@@ -5,7 +5,8 @@ use cubecl_core::{
 };
 use cubecl_runtime::{
     memory_management::{
-        MemoryDeviceProperties, MemoryHandle, MemoryManagement, SliceBinding, SliceHandle,
+        MemoryCleanupMode, MemoryDeviceProperties, MemoryHandle, MemoryManagement, SliceBinding,
+        SliceHandle,
     },
     storage::ComputeStorage,
 };
@@ -82,6 +83,31 @@ impl WgpuMemManager {
         ))
     }
 
+    /// Import a buffer created outside of the memory pool, see
+    /// [import_resource](cubecl_runtime::server::ComputeServer::import_resource).
+    pub(crate) fn import(&mut self, resource: WgpuResource) -> Result<Handle, IoError> {
+        let usage = resource.buffer.usage();
+        if !usage.contains(BufferUsages::STORAGE) {
+            return Err(IoError::Unknown(format!(
+                "Can't import a buffer with the usages {usage:?}, it must be created with STORAGE"
+            )));
+        }
+        if resource.offset + resource.size > resource.buffer.size() {
+            return Err(IoError::Unknown(format!(
+                "Can't import {} bytes at offset {} of a buffer of {} bytes",
+                resource.size,
+                resource.offset,
+                resource.buffer.size()
+            )));
+        }
+
+        let storage = self.memory_pool.storage().register(resource.buffer);
+        let size = storage.size();
+        let slice = self.memory_pool.register(storage);
+
+        Ok(Handle::new(slice, None, None, size).offset(resource.offset, resource.size))
+    }
+
     pub(crate) fn reserve_staging(
         &mut self,
         size: u64,
@@ -112,28 +138,51 @@ impl WgpuMemManager {
         self.memory_pool.storage().get(&handle)
     }
 
-    pub(crate) fn reserve_uniform(&mut self, size: u64) -> WgpuResource {
-        let slice = self
-            .memory_uniforms
-            .reserve(size)
-            .expect("Must have enough memory for a uniform");
+    /// Reserve a uniform, kept alive until the uniforms are released or the returned slice is
+    /// dropped, whichever comes last.
+    pub(crate) fn reserve_uniform(
+        &mut self,
+        size: u64,
+    ) -> Result<(WgpuResource, SliceHandle), IoError> {
+        let slice = self.memory_uniforms.reserve(size)?;
         // Keep track of this uniform until it is released.
         self.uniforms.push(slice.clone());
         let handle = self
             .memory_uniforms
             .get(slice.binding())
             .expect("Failed to find storage!");
-        self.memory_uniforms.storage().get(&handle)
+        Ok((self.memory_uniforms.storage().get(&handle), slice))
+    }
+
+    /// The [generation](WgpuStorage::generation) of the main memory pool.
+    pub(crate) fn generation(&mut self) -> u64 {
+        self.memory_pool.storage().generation()
     }
 
     pub(crate) fn memory_usage(&self) -> cubecl_runtime::memory_management::MemoryUsage {
         self.memory_pool.memory_usage()
     }
 
+    pub(crate) fn push_scope(&mut self, tag: String) {
+        self.memory_pool.push_scope(tag);
+    }
+
+    pub(crate) fn pop_scope(&mut self) {
+        self.memory_pool.pop_scope();
+    }
+
+    pub(crate) fn scope_usage(&self) -> Vec<cubecl_runtime::memory_management::MemoryScopeUsage> {
+        self.memory_pool.scope_usage()
+    }
+
     pub(crate) fn memory_cleanup(&mut self, explicit: bool) {
         self.memory_pool.cleanup(explicit);
     }
 
+    pub(crate) fn memory_cleanup_mode(&mut self, mode: MemoryCleanupMode) {
+        self.memory_pool.memory_cleanup(mode);
+    }
+
     pub(crate) fn mode(&mut self, mode: cubecl_runtime::memory_management::MemoryAllocationMode) {
         self.memory_pool.mode(mode);
     }
@@ -1,4 +1,6 @@
+pub(super) mod bind_groups;
 pub(crate) mod controller;
+mod interop;
 mod storage;
 
 pub(super) mod mem_manager;
@@ -8,5 +10,6 @@ pub(super) mod timings;
 
 mod server;
 
+pub use interop::*;
 pub use server::*;
 pub use storage::*;
@@ -1,35 +1,48 @@
 use super::storage::{WgpuResource, WgpuStorage};
 use super::stream::WgpuStream;
-use crate::AutoCompiler;
+use crate::{AutoCompiler, WgpuPipeline};
 use alloc::sync::Arc;
 use cubecl_common::bytes::Bytes;
 use cubecl_common::profile::{ProfileDuration, TimingMethod};
 use cubecl_core::future::DynFut;
-use cubecl_core::server::{DataTransferService, ProfileError, ProfilingToken};
+use cubecl_core::server::{
+    DataTransferService, ExecutionStream, ProfileError, ProfilingToken, StreamEvent,
+};
 use cubecl_core::{
     MemoryConfiguration, WgpuCompilationOptions,
     prelude::*,
-    server::{Binding, Bindings, CopyDescriptor},
+    server::{Binding, Bindings, CopyDescriptor, Handle},
 };
 use cubecl_core::{
     compute::{CubeTask, DebugInformation},
     server::{Allocation, AllocationDescriptor, IoError},
 };
 use cubecl_runtime::logging::ServerLogger;
-use cubecl_runtime::memory_management::offset_handles;
+use cubecl_runtime::memory_management::{
+    HardwareProperties, MemoryCleanupMode, MemoryScopeUsage, offset_handles,
+};
 use cubecl_runtime::{
     memory_management::MemoryDeviceProperties, server::ComputeServer, storage::BindingResource,
 };
 use hashbrown::HashMap;
-use wgpu::ComputePipeline;
 
 /// Wgpu compute server.
 #[derive(Debug)]
 pub struct WgpuServer {
     pub(crate) device: wgpu::Device,
-    pipelines: HashMap<KernelId, Arc<ComputePipeline>>,
+    pipelines: HashMap<KernelId, Arc<WgpuPipeline>>,
+    /// Pipelines binding every buffer as read-write, for launches with buffers sharing memory.
+    aliased_pipelines: HashMap<KernelId, Arc<WgpuPipeline>>,
+    /// WGSL modules by the hash of their source, shared by the kernels that only differ by
+    /// their override constants, and SPIR-V modules by the hash of their template and
+    /// specialization constants.
+    pub(crate) shader_modules: HashMap<u64, wgpu::ShaderModule>,
+    /// Pipelines by the hash of their module, layout and constants, shared by the kernels
+    /// compiling to the same shader.
+    pub(crate) shader_pipelines: HashMap<u64, Arc<WgpuPipeline>>,
     stream: WgpuStream,
     pub compilation_options: WgpuCompilationOptions,
+    hardware_properties: HardwareProperties,
     pub(crate) backend: wgpu::Backend,
 }
 
@@ -40,6 +53,7 @@ impl WgpuServer {
         memory_properties: MemoryDeviceProperties,
         memory_config: MemoryConfiguration,
         compilation_options: WgpuCompilationOptions,
+        hardware_properties: HardwareProperties,
         device: wgpu::Device,
         queue: wgpu::Queue,
         tasks_max: usize,
@@ -57,8 +71,12 @@ impl WgpuServer {
 
         Self {
             compilation_options,
+            hardware_properties,
             device,
             pipelines: HashMap::new(),
+            aliased_pipelines: HashMap::new(),
+            shader_modules: HashMap::new(),
+            shader_pipelines: HashMap::new(),
             stream,
             backend,
         }
@@ -66,15 +84,21 @@ impl WgpuServer {
 
     fn pipeline(
         &mut self,
-        kernel: <Self as ComputeServer>::Kernel,
+        kernel: &<Self as ComputeServer>::Kernel,
         mode: ExecutionMode,
+        aliased: bool,
         logger: Arc<ServerLogger>,
-    ) -> Arc<ComputePipeline> {
+    ) -> Result<Arc<WgpuPipeline>, IoError> {
         let mut kernel_id = kernel.id();
         kernel_id.mode(mode);
 
-        if let Some(pipeline) = self.pipelines.get(&kernel_id) {
-            return pipeline.clone();
+        let pipelines = if aliased {
+            &self.aliased_pipelines
+        } else {
+            &self.pipelines
+        };
+        if let Some(pipeline) = pipelines.get(&kernel_id) {
+            return Ok(pipeline.clone());
         }
 
         let mut compiler = compiler(self.backend);
@@ -87,6 +111,19 @@ impl WgpuServer {
             ));
         }
         logger.log_compilation(&compile);
+
+        // Checked before creating the pipeline, since exceeding the limits would only surface as
+        // a validation error of the driver.
+        let shared_memory_size = compile
+            .repr
+            .as_ref()
+            .map(|repr| repr.shared_memory_size())
+            .unwrap_or_default();
+        self.hardware_properties
+            .check_kernel_limits(&compile.cube_dim, shared_memory_size)?;
+        if let Some(repr) = compile.repr.as_ref() {
+            repr.check_support(&self.compilation_options)?;
+        }
         // /!\ Do not delete the following commented code.
         // This is useful while working on the metal compiler.
         // Also the errors are printed nicely which is not the case when this is the runtime
@@ -109,10 +146,15 @@ impl WgpuServer {
         //         .expect("should launch the command");
         //     // std::process::exit(status.code().unwrap());
         // }
-        let pipeline = self.create_pipeline(compile, mode);
-        self.pipelines.insert(kernel_id.clone(), pipeline.clone());
+        let pipeline = self.create_pipeline(compile, mode, aliased)?;
+        let pipelines = if aliased {
+            &mut self.aliased_pipelines
+        } else {
+            &mut self.pipelines
+        };
+        pipelines.insert(kernel_id.clone(), pipeline.clone());
 
-        pipeline
+        Ok(pipeline)
     }
 }
 
@@ -178,6 +220,10 @@ impl ComputeServer for WgpuServer {
         BindingResource::new(binding, resource)
     }
 
+    fn import_resource(&mut self, resource: WgpuResource) -> Result<Handle, IoError> {
+        self.stream.mem_manage.import(resource)
+    }
+
     unsafe fn execute(
         &mut self,
         kernel: Self::Kernel,
@@ -185,9 +231,24 @@ impl ComputeServer for WgpuServer {
         bindings: Bindings,
         mode: ExecutionMode,
         logger: Arc<ServerLogger>,
-    ) {
-        let pipeline = self.pipeline(kernel, mode, logger);
-        self.stream.register(pipeline, bindings, &count);
+    ) -> Result<(), IoError> {
+        let mut pipeline = self.pipeline(&kernel, mode, false, logger.clone())?;
+        // wgpu doesn't allow binding the same buffer as both read-only and read-write.
+        if pipeline.has_mixed_access() && self.stream.has_aliased_access(&pipeline, &bindings) {
+            pipeline = self.pipeline(&kernel, mode, true, logger)?;
+        }
+        self.stream.register(pipeline, bindings, &count)
+    }
+
+    fn precompile(
+        &mut self,
+        kernel: Self::Kernel,
+        mode: ExecutionMode,
+        logger: Arc<ServerLogger>,
+    ) -> Result<(), IoError> {
+        // Not cached on error, so the error is returned again when launching the kernel.
+        self.pipeline(&kernel, mode, false, logger)?;
+        Ok(())
     }
 
     fn flush(&mut self) {
@@ -200,6 +261,31 @@ impl ComputeServer for WgpuServer {
         self.stream.sync()
     }
 
+    // All the work is submitted to a single queue, so streams never have to wait for events.
+    fn record_event(&mut self, _stream: ExecutionStream) -> StreamEvent {
+        self.stream.record_event()
+    }
+
+    fn is_event_complete(&mut self, event: StreamEvent) -> bool {
+        self.stream.is_event_complete(event)
+    }
+
+    fn sync_event(&mut self, event: StreamEvent) -> DynFut<()> {
+        self.stream.sync_event(event)
+    }
+
+    fn event_elapsed(
+        &mut self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.stream.event_elapsed(start, end)
+    }
+
+    fn release_event(&mut self, event: StreamEvent) {
+        self.stream.release_event(event);
+    }
+
     fn start_profile(&mut self) -> ProfilingToken {
         self.stream.start_profile()
     }
@@ -208,12 +294,37 @@ impl ComputeServer for WgpuServer {
         self.stream.end_profile(token)
     }
 
+    fn start_device_timing(&mut self) -> Option<ProfilingToken> {
+        self.stream.start_device_timing()
+    }
+
+    fn end_device_timing(
+        &mut self,
+        token: ProfilingToken,
+    ) -> Result<ProfileDuration, ProfileError> {
+        self.stream.end_device_timing(token)
+    }
+
     fn memory_usage(&self) -> cubecl_runtime::memory_management::MemoryUsage {
         self.stream.mem_manage.memory_usage()
     }
 
-    fn memory_cleanup(&mut self) {
-        self.stream.mem_manage.memory_cleanup(true);
+    fn push_memory_scope(&mut self, tag: String) {
+        self.stream.mem_manage.push_scope(tag);
+    }
+
+    fn pop_memory_scope(&mut self) {
+        self.stream.mem_manage.pop_scope();
+    }
+
+    fn memory_scope_usage(&self) -> Vec<MemoryScopeUsage> {
+        self.stream.mem_manage.scope_usage()
+    }
+
+    fn memory_cleanup(&mut self, mode: MemoryCleanupMode) {
+        // Submit pending work before releasing anything it might still reference.
+        self.stream.flush();
+        self.stream.mem_manage.memory_cleanup_mode(mode);
     }
 
     fn allocation_mode(&mut self, mode: cubecl_runtime::memory_management::MemoryAllocationMode) {
@@ -10,6 +10,7 @@ pub struct WgpuStorage {
     device: wgpu::Device,
     buffer_usages: BufferUsages,
     mem_alignment: usize,
+    generation: u64,
 }
 
 impl core::fmt::Debug for WgpuStorage {
@@ -56,8 +57,25 @@ impl WgpuStorage {
             device,
             buffer_usages: usages,
             mem_alignment,
+            generation: 0,
         }
     }
+
+    /// Incremented each time a buffer is deallocated, so what refers to the buffers of the
+    /// storage can be invalidated.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Keep a reference to a buffer created outside of the storage, which is released when the
+    /// returned handle is deallocated, without destroying the buffer.
+    pub fn register(&mut self, buffer: wgpu::Buffer) -> StorageHandle {
+        let id = StorageId::new();
+        let size = buffer.size();
+
+        self.memory.insert(id, buffer);
+        StorageHandle::new(id, StorageUtilization { offset: 0, size })
+    }
 }
 
 impl ComputeStorage for WgpuStorage {
@@ -75,6 +93,13 @@ impl ComputeStorage for WgpuStorage {
     fn alloc(&mut self, size: u64) -> Result<StorageHandle, IoError> {
         let id = StorageId::new();
 
+        // Errors are only reported through error scopes, which can't be waited for on wasm.
+        #[cfg(not(target_family = "wasm"))]
+        {
+            self.device.push_error_scope(wgpu::ErrorFilter::Internal);
+            self.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        }
+
         let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
             size,
@@ -82,6 +107,24 @@ impl ComputeStorage for WgpuStorage {
             mapped_at_creation: false,
         });
 
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let out_of_memory = cubecl_common::future::block_on(self.device.pop_error_scope());
+            let internal = cubecl_common::future::block_on(self.device.pop_error_scope());
+
+            // Running out of memory is reported the same way as the other storages, so the
+            // memory management can release memory and retry. Other errors mean the device is
+            // lost and can't be recovered from.
+            if out_of_memory.is_some() {
+                return Err(IoError::BufferTooBig(size as usize));
+            }
+            if let Some(err) = internal {
+                return Err(IoError::Unknown(format!(
+                    "Can't allocate a buffer of {size} bytes, the device may be lost: {err}"
+                )));
+            }
+        }
+
         self.memory.insert(id, buffer);
         Ok(StorageHandle::new(
             id,
@@ -90,6 +133,8 @@ impl ComputeStorage for WgpuStorage {
     }
 
     fn dealloc(&mut self, id: StorageId) {
-        self.memory.remove(&id);
+        if self.memory.remove(&id).is_some() {
+            self.generation += 1;
+        }
     }
 }
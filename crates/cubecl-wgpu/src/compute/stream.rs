@@ -1,19 +1,68 @@
-use super::{mem_manager::WgpuMemManager, poll::WgpuPoll, timings::QueryProfiler};
-use crate::{WgpuResource, controller::WgpuAllocController};
+use super::{
+    bind_groups::{BindGroupCache, BindGroupKey},
+    mem_manager::WgpuMemManager,
+    poll::WgpuPoll,
+    timings::{KernelQueries, QueryProfiler},
+};
+use crate::{
+    WgpuPipeline, WgpuResource, compiler::wgsl::push_constant_layout,
+    controller::WgpuAllocController,
+};
 use cubecl_common::{
     bytes::Bytes,
-    profile::{ProfileDuration, TimingMethod},
+    profile::{Instant, ProfileDuration, ProfileTicks, TimingMethod},
 };
 use cubecl_core::{
     CubeCount, MemoryConfiguration,
     future::{self, DynFut},
-    server::{Binding, Bindings, CopyDescriptor, Handle, IoError, ProfileError, ProfilingToken},
+    server::{
+        Binding, Bindings, CopyDescriptor, Handle, IoError, ProfileError, ProfilingToken,
+        StreamEvent,
+    },
 };
 use cubecl_runtime::{
-    memory_management::MemoryDeviceProperties, timestamp_profiler::TimestampProfiler,
+    memory_management::{MemoryDeviceProperties, SliceBinding, SliceHandle},
+    timestamp_profiler::TimestampProfiler,
 };
-use std::{future::Future, num::NonZero, pin::Pin, sync::Arc};
-use wgpu::ComputePipeline;
+use std::{
+    collections::HashMap,
+    future::Future,
+    num::NonZero,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
+
+/// Staging buffers of a read that isn't completed yet.
+///
+/// When the read is dropped before completion, the buffers are unmapped, cancelling any pending
+/// mapping, so they can be reused by the staging pool.
+struct PendingStaging {
+    buffers: Vec<(WgpuResource, SliceBinding, usize)>,
+}
+
+impl Drop for PendingStaging {
+    fn drop(&mut self) {
+        for (staging, _binding, _size) in self.buffers.iter() {
+            staging.buffer.unmap();
+        }
+    }
+}
+
+/// An [event](StreamEvent) recorded on the queue.
+#[derive(Debug)]
+struct WgpuEvent {
+    #[cfg_attr(target_family = "wasm", allow(dead_code))]
+    index: wgpu::SubmissionIndex,
+    /// The host time at which the queue reported the work submitted before the event as done.
+    completed: Arc<Mutex<Option<Instant>>>,
+}
+
+impl WgpuEvent {
+    fn completed(&self) -> Option<Instant> {
+        *self.completed.lock().unwrap()
+    }
+}
 
 #[derive(Debug)]
 enum Timings {
@@ -28,12 +77,17 @@ pub struct WgpuStream {
     sync_buffer: Option<Handle>,
     compute_pass: Option<wgpu::ComputePass<'static>>,
     timings: Timings,
+    /// Timestamp queries timing kernels, when the device supports them.
+    kernel_queries: Option<KernelQueries>,
     tasks_count: usize,
     tasks_max: usize,
     queue: wgpu::Queue,
     encoder: wgpu::CommandEncoder,
     poll: WgpuPoll,
     submission_load: SubmissionLoad,
+    bind_groups: BindGroupCache,
+    events: HashMap<u64, WgpuEvent>,
+    event_count: u64,
 }
 
 impl WgpuStream {
@@ -45,6 +99,8 @@ impl WgpuStream {
         timing_method: TimingMethod,
         tasks_max: usize,
     ) -> Self {
+        let kernel_queries =
+            (timing_method == TimingMethod::Device).then(|| KernelQueries::new(&queue));
         let timings = if timing_method == TimingMethod::Device {
             Timings::Device(QueryProfiler::new(&queue, &device))
         } else {
@@ -78,6 +134,7 @@ impl WgpuStream {
             mem_manage,
             compute_pass: None,
             timings,
+            kernel_queries,
             encoder: {
                 device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
                     label: Some("CubeCL Tasks Encoder"),
@@ -90,43 +147,58 @@ impl WgpuStream {
             poll,
             sync_buffer,
             submission_load: SubmissionLoad::default(),
+            bind_groups: BindGroupCache::default(),
+            events: HashMap::new(),
+            event_count: 0,
         }
     }
 
+    /// Whether a buffer bound as read-only by the pipeline shares its memory with a buffer bound
+    /// as read-write.
+    pub fn has_aliased_access(&mut self, pipeline: &WgpuPipeline, bindings: &Bindings) -> bool {
+        let buffers = bindings
+            .buffers
+            .iter()
+            .map(|binding| self.mem_manage.get_resource(binding.clone()).buffer)
+            .collect::<Vec<_>>();
+        let (read, read_write): (Vec<_>, Vec<_>) = buffers
+            .iter()
+            .zip(pipeline.read_only.iter())
+            .partition(|(_, read_only)| **read_only);
+
+        read.iter()
+            .any(|(buffer, _)| read_write.iter().any(|(other, _)| buffer == other))
+    }
+
     pub fn register(
         &mut self,
-        pipeline: Arc<ComputePipeline>,
+        pipeline: Arc<WgpuPipeline>,
         bindings: Bindings,
         dispatch: &CubeCount,
-    ) {
+    ) -> Result<(), IoError> {
         // Store all the resources we'll be using. This could be eliminated if
         // there was a way to tie the lifetime of the resource to the memory handle.
-        let mut resources = bindings
+        let resources = bindings
             .buffers
             .iter()
             .map(|b| self.mem_manage.get_resource(b.clone()))
             .collect::<Vec<_>>();
 
+        let mut uniforms = Vec::new();
         if !bindings.metadata.data.is_empty() {
-            let info = self.create_uniform(bytemuck::cast_slice(&bindings.metadata.data));
-            resources.push(info);
+            uniforms.push(bytemuck::cast_slice(&bindings.metadata.data).to_vec());
         }
 
-        resources.extend(
-            bindings
-                .scalars
-                .values()
-                .map(|s| self.create_uniform(s.data())),
-        );
+        let push_constants = if pipeline.push_constants {
+            Some(pack_push_constants(&bindings))
+        } else {
+            for scalar in bindings.scalars.values() {
+                uniforms.push(scalar.data().to_vec());
+            }
+            None
+        };
 
-        let entries = resources
-            .iter()
-            .enumerate()
-            .map(|(i, r)| wgpu::BindGroupEntry {
-                binding: i as u32,
-                resource: r.as_wgpu_bind_resource(),
-            })
-            .collect::<Vec<_>>();
+        let bind_group = self.bind_group(&pipeline, resources, uniforms)?;
 
         // Start a new compute pass if needed. The forget_lifetime allows
         // to store this with a 'static lifetime, but the compute pass must
@@ -153,15 +225,11 @@ impl WgpuStream {
 
         self.tasks_count += 1;
 
-        let group_layout = pipeline.get_bind_group_layout(0);
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: None,
-            layout: &group_layout,
-            entries: &entries,
-        });
-
-        pass.set_pipeline(&pipeline);
+        pass.set_pipeline(&pipeline.pipeline);
         pass.set_bind_group(0, &bind_group, &[]);
+        if let Some(data) = push_constants {
+            pass.set_push_constants(0, &data);
+        }
 
         match dispatch.clone() {
             CubeCount::Static(x, y, z) => {
@@ -173,6 +241,56 @@ impl WgpuStream {
             }
         }
         self.flush_if_needed();
+
+        Ok(())
+    }
+
+    /// Get the bind group of a launch, reusing the one of a previous launch of the pipeline on
+    /// the same buffers with the same uniforms, which then don't have to be written again.
+    fn bind_group(
+        &mut self,
+        pipeline: &WgpuPipeline,
+        mut resources: Vec<WgpuResource>,
+        uniforms: Vec<Vec<u8>>,
+    ) -> Result<wgpu::BindGroup, IoError> {
+        self.bind_groups.invalidate(self.mem_manage.generation());
+
+        let key = BindGroupKey {
+            pipeline: pipeline.pipeline.clone(),
+            buffers: resources
+                .iter()
+                .map(|resource| (resource.buffer.clone(), resource.offset, resource.size))
+                .collect(),
+            uniforms,
+        };
+        if let Some(bind_group) = self.bind_groups.get(&key) {
+            return Ok(bind_group);
+        }
+
+        let mut slices = Vec::with_capacity(key.uniforms.len());
+        for data in key.uniforms.iter() {
+            let (resource, slice) = self.create_uniform(data)?;
+            resources.push(resource);
+            slices.push(slice);
+        }
+
+        let entries = resources
+            .iter()
+            .enumerate()
+            .map(|(i, r)| wgpu::BindGroupEntry {
+                binding: i as u32,
+                resource: r.as_wgpu_bind_resource(),
+            })
+            .collect::<Vec<_>>();
+        let group_layout = pipeline.pipeline.get_bind_group_layout(0);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &group_layout,
+            entries: &entries,
+        });
+        self.bind_groups.insert(key, bind_group.clone(), slices);
+
+        Ok(bind_group)
     }
 
     /// Read multiple buffers lazily to [Bytes], potentially using pinned memory.
@@ -232,6 +350,9 @@ impl WgpuStream {
         }
 
         let poll = self.poll.start_polling();
+        let mut pending = PendingStaging {
+            buffers: staging_info,
+        };
 
         Box::pin(async move {
             for callback in callbacks {
@@ -246,7 +367,7 @@ impl WgpuStream {
             core::mem::drop(poll);
 
             let result = {
-                staging_info
+                core::mem::take(&mut pending.buffers)
                     .into_iter()
                     .map(|(staging, binding, size)| {
                         let (controller, alloc) =
@@ -327,6 +448,40 @@ impl WgpuStream {
         }
     }
 
+    /// Start timing the work registered from now on with timestamp queries, see
+    /// [KernelQueries].
+    ///
+    /// Returns `None` when the device doesn't support timestamp queries.
+    pub fn start_device_timing(&mut self) -> Option<ProfilingToken> {
+        let queries = self.kernel_queries.as_mut()?;
+
+        // Passes are timed as a whole, so the timed work starts in a new pass.
+        self.compute_pass = None;
+        self.tasks_count += 1;
+
+        Some(queries.start(&self.device, &mut self.encoder))
+    }
+
+    /// Stop a timing started with [start_device_timing](Self::start_device_timing).
+    ///
+    /// The duration is resolved once the stream is flushed.
+    pub fn end_device_timing(
+        &mut self,
+        token: ProfilingToken,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let queries = self
+            .kernel_queries
+            .as_mut()
+            .ok_or(ProfileError::NotRegistered)?;
+
+        self.compute_pass = None;
+        self.tasks_count += 1;
+        let duration = queries.stop(token, &self.device, &mut self.encoder);
+        self.flush_if_needed();
+
+        duration
+    }
+
     pub fn sync(&mut self) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>> {
         self.flush();
 
@@ -365,14 +520,120 @@ impl WgpuStream {
         }
     }
 
+    /// Record an event reached once the work submitted so far is done.
+    ///
+    /// The queue keeps the buffers used by the submitted work alive until it is done, so their
+    /// memory isn't reused before the event is reached.
+    pub fn record_event(&mut self) -> StreamEvent {
+        self.flush();
+
+        let completed = Arc::new(Mutex::new(None));
+        let callback = completed.clone();
+        // Polls the device until the work is done, so the completion time is accurate.
+        let poll = self.poll.start_polling();
+        self.queue.on_submitted_work_done(move || {
+            *callback.lock().unwrap() = Some(Instant::now());
+            core::mem::drop(poll);
+        });
+        // An empty submission, to know when the work submitted before is done.
+        let index = self.queue.submit([]);
+
+        let id = self.event_count;
+        self.event_count += 1;
+        self.events.insert(id, WgpuEvent { index, completed });
+
+        StreamEvent { id }
+    }
+
+    /// Whether the event is reached, without blocking.
+    pub fn is_event_complete(&mut self, event: StreamEvent) -> bool {
+        self.event(event).completed().is_some()
+    }
+
+    /// Wait until the event is reached.
+    pub fn sync_event(&mut self, event: StreamEvent) -> DynFut<()> {
+        let event = self.event(event);
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let index = event.index.clone();
+            if let Err(e) = self
+                .device
+                .poll(wgpu::PollType::WaitForSubmissionIndex(index))
+            {
+                log::warn!(
+                    "wgpu: requested wait timed out before the submission was completed during sync. ({e})"
+                )
+            }
+            Box::pin(async move {})
+        }
+
+        #[cfg(target_family = "wasm")]
+        {
+            let completed = event.completed.clone();
+            Box::pin(core::future::poll_fn(move |cx| {
+                match *completed.lock().unwrap() {
+                    Some(_) => Poll::Ready(()),
+                    None => {
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                }
+            }))
+        }
+    }
+
+    /// The time elapsed between the completion of two events, measured on the host since the
+    /// queue doesn't time its submissions.
+    pub fn event_elapsed(
+        &mut self,
+        start: StreamEvent,
+        end: StreamEvent,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let start = self
+            .events
+            .get(&start.id)
+            .ok_or(ProfileError::NotRegistered)?;
+        let end = self
+            .events
+            .get(&end.id)
+            .ok_or(ProfileError::NotRegistered)?;
+        let (start, end) = (start.completed.clone(), end.completed.clone());
+
+        let ticks = core::future::poll_fn(move |cx| {
+            let start = *start.lock().unwrap();
+            let end = *end.lock().unwrap();
+            match (start, end) {
+                (Some(start), Some(end)) => Poll::Ready(ProfileTicks::from_start_end(start, end)),
+                _ => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            }
+        });
+
+        Ok(ProfileDuration::new(Box::pin(ticks), TimingMethod::System))
+    }
+
+    /// Release the event.
+    pub fn release_event(&mut self, event: StreamEvent) {
+        self.events.remove(&event.id);
+    }
+
+    fn event(&self, event: StreamEvent) -> &WgpuEvent {
+        self.events
+            .get(&event.id)
+            .unwrap_or_else(|| panic!("Unknown or released stream event {}", event.id))
+    }
+
     pub fn empty(&mut self, size: u64) -> Result<Handle, IoError> {
         self.mem_manage.reserve(size)
     }
 
-    fn create_uniform(&mut self, data: &[u8]) -> WgpuResource {
-        let resource = self.mem_manage.reserve_uniform(data.len() as u64);
+    fn create_uniform(&mut self, data: &[u8]) -> Result<(WgpuResource, SliceHandle), IoError> {
+        let (resource, slice) = self.mem_manage.reserve_uniform(data.len() as u64)?;
         self.write_to_buffer(&resource, data);
-        resource
+        Ok((resource, slice))
     }
 
     pub fn write(&mut self, binding: Binding, data: &[u8]) {
@@ -429,6 +690,10 @@ impl WgpuStream {
         // End the current compute pass.
         self.compute_pass = None;
 
+        if let Some(queries) = self.kernel_queries.as_mut() {
+            queries.resolve(&self.device, &mut self.encoder);
+        }
+
         // Submit the pending actions to the queue. This will _first_ submit the
         // pending uniforms copy operations, then the main tasks.
         let tasks_encoder = {
@@ -443,6 +708,10 @@ impl WgpuStream {
         // This will _first_ fire off all pending write_buffer work.
         let index = self.queue.submit([tasks_encoder.finish()]);
 
+        if let Some(queries) = self.kernel_queries.as_mut() {
+            queries.map_resolved(&self.poll);
+        }
+
         self.submission_load
             .regulate(&self.device, self.tasks_count, index);
 
@@ -530,3 +799,21 @@ mod __submission_load_wasm {
 use __submission_load::*;
 #[cfg(target_family = "wasm")]
 use __submission_load_wasm::*;
+
+/// Pack the scalars in the layout of the push constants of the kernel.
+fn pack_push_constants(bindings: &Bindings) -> Vec<u8> {
+    let scalars = bindings.scalars.values();
+    let (offsets, size) = push_constant_layout(
+        scalars
+            .clone()
+            .map(|scalar| (scalar.ty.size(), scalar.length)),
+    );
+    let mut data = vec![0; size];
+
+    for (scalar, offset) in scalars.zip(offsets) {
+        let len = scalar.ty.size() * scalar.length;
+        data[offset..offset + len].copy_from_slice(&scalar.data()[..len]);
+    }
+
+    data
+}
@@ -1,9 +1,10 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use super::poll::WgpuPoll;
 use cubecl_common::profile::{Duration, Instant, ProfileDuration, ProfileTicks};
 use cubecl_core::server::{ProfileError, ProfilingToken};
 use hashbrown::HashMap;
-use wgpu::{QUERY_SIZE, QuerySet, QuerySetDescriptor, QueryType};
+use wgpu::{BufferAsyncError, QUERY_SIZE, QuerySet, QuerySetDescriptor, QueryType};
 
 type QuerySetId = u64;
 
@@ -311,3 +312,228 @@ impl QueryProfiler {
         }
     }
 }
+
+/// The number of timestamps of each query set used by [KernelQueries].
+const QUERY_SET_CAPACITY: u32 = 256;
+
+/// Times kernels with timestamps written by the device between compute passes.
+///
+/// The timestamps of many timings are allocated in the same query set, which is resolved when
+/// it is full or when the commands are submitted. Timing a kernel doesn't add a submission or a
+/// synchronization, the durations being available once the commands are flushed.
+#[derive(Debug)]
+pub struct KernelQueries {
+    starts: HashMap<ProfilingToken, QuerySlot>,
+    counter: u64,
+    current: Option<QueryBatch>,
+    /// Batches resolved in the current encoder, mapped once it is submitted.
+    resolved: Vec<QueryBatch>,
+    pool: Vec<QuerySet>,
+    resolve_buffer: Option<wgpu::Buffer>,
+    queue_period: f64,
+    epoch_instant: Instant,
+}
+
+/// The timestamps written in a query set.
+#[derive(Debug)]
+struct QueryBatch {
+    query_set: QuerySet,
+    len: u32,
+    ticks: Arc<ResolvedTicks>,
+    mapped: async_channel::Sender<Result<(), BufferAsyncError>>,
+}
+
+/// The timestamps of a [batch](QueryBatch), read once the buffer they are copied to is mapped.
+#[derive(Debug)]
+struct ResolvedTicks {
+    map_buffer: wgpu::Buffer,
+    mapped: async_channel::Receiver<Result<(), BufferAsyncError>>,
+    ticks: Mutex<Option<Vec<u64>>>,
+}
+
+#[derive(Debug)]
+struct QuerySlot {
+    ticks: Arc<ResolvedTicks>,
+    index: u32,
+}
+
+impl ResolvedTicks {
+    async fn get(&self, index: u32) -> u64 {
+        // The first timing to be resolved receives the mapping result, the others are notified
+        // when the channel is closed.
+        if let Ok(Err(err)) = self.mapped.recv().await {
+            panic!("Failed to map the timestamps: {err}");
+        }
+
+        let mut ticks = self.ticks.lock().unwrap();
+        let ticks = ticks.get_or_insert_with(|| {
+            let view = self.map_buffer.slice(..).get_mapped_range();
+            let ticks = bytemuck::cast_slice::<u8, u64>(&view).to_vec();
+            drop(view);
+            self.map_buffer.unmap();
+            ticks
+        });
+
+        ticks[index as usize]
+    }
+}
+
+impl KernelQueries {
+    pub fn new(queue: &wgpu::Queue) -> Self {
+        Self {
+            starts: HashMap::new(),
+            counter: 0,
+            current: None,
+            resolved: Vec::new(),
+            pool: Vec::new(),
+            resolve_buffer: None,
+            queue_period: queue.get_timestamp_period() as f64,
+            epoch_instant: Instant::now(),
+        }
+    }
+
+    /// Record the start of a timing, the current compute pass must be ended.
+    pub fn start(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> ProfilingToken {
+        let token = ProfilingToken { id: self.counter };
+        self.counter += 1;
+
+        let slot = self.write_timestamp(device, encoder);
+        self.starts.insert(token, slot);
+
+        token
+    }
+
+    /// Record the end of a timing, the current compute pass must be ended.
+    pub fn stop(
+        &mut self,
+        token: ProfilingToken,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Result<ProfileDuration, ProfileError> {
+        let start = self
+            .starts
+            .remove(&token)
+            .ok_or(ProfileError::NotRegistered)?;
+        let end = self.write_timestamp(device, encoder);
+
+        let period = self.queue_period;
+        let epoch_instant = self.epoch_instant;
+
+        Ok(ProfileDuration::new_device_time(async move {
+            let start = start.ticks.get(start.index).await;
+            let end = end.ticks.get(end.index).await;
+            // Convert the ticks to nanoseconds.
+            let instant =
+                |ticks: u64| epoch_instant + Duration::from_nanos((ticks as f64 * period) as u64);
+
+            ProfileTicks::from_start_end(instant(start), instant(end))
+        }))
+    }
+
+    /// Resolve the timestamps written so far, before the encoder is submitted.
+    pub fn resolve(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        let Some(batch) = self.current.take() else {
+            return;
+        };
+
+        let resolve_buffer = self
+            .resolve_buffer
+            .get_or_insert_with(|| create_resolve_buffer(device, QUERY_SET_CAPACITY));
+        let size = batch.len as u64 * QUERY_SIZE as u64;
+
+        encoder.resolve_query_set(&batch.query_set, 0..batch.len, resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(resolve_buffer, 0, &batch.ticks.map_buffer, 0, size);
+
+        self.resolved.push(batch);
+    }
+
+    /// Map the resolved timestamps, once the encoder resolving them is submitted.
+    pub fn map_resolved(&mut self, poll: &WgpuPoll) {
+        for batch in self.resolved.drain(..) {
+            let QueryBatch {
+                query_set,
+                ticks,
+                mapped,
+                ..
+            } = batch;
+            let poll_signal = poll.start_polling();
+
+            ticks
+                .map_buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |result| {
+                    // This might fail if all the timings were dropped, which is fine.
+                    let _ = mapped.try_send(result);
+                    core::mem::drop(poll_signal);
+                });
+
+            // Timestamps written from now on are submitted after the resolution.
+            self.pool.push(query_set);
+        }
+    }
+
+    /// Write a timestamp at the end of an empty compute pass, which is reached once the
+    /// previous passes are completed.
+    fn write_timestamp(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> QuerySlot {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|batch| batch.len == QUERY_SET_CAPACITY)
+        {
+            self.resolve(device, encoder);
+        }
+
+        if self.current.is_none() {
+            let batch = self.new_batch(device);
+            self.current = Some(batch);
+        }
+
+        let batch = self.current.as_mut().unwrap();
+        let index = batch.len;
+        batch.len += 1;
+
+        encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("CubeCL timestamp"),
+            timestamp_writes: Some(wgpu::ComputePassTimestampWrites {
+                query_set: &batch.query_set,
+                beginning_of_pass_write_index: None,
+                end_of_pass_write_index: Some(index),
+            }),
+        });
+
+        QuerySlot {
+            ticks: batch.ticks.clone(),
+            index,
+        }
+    }
+
+    fn new_batch(&mut self, device: &wgpu::Device) -> QueryBatch {
+        let query_set = self.pool.pop().unwrap_or_else(|| {
+            device.create_query_set(&QuerySetDescriptor {
+                label: Some("CubeCL kernel queries"),
+                ty: QueryType::Timestamp,
+                count: QUERY_SET_CAPACITY,
+            })
+        });
+        let (sender, receiver) = async_channel::bounded(1);
+
+        QueryBatch {
+            query_set,
+            len: 0,
+            ticks: Arc::new(ResolvedTicks {
+                map_buffer: create_map_buffer(device, QUERY_SET_CAPACITY),
+                mapped: receiver,
+                ticks: Mutex::new(None),
+            }),
+            mapped: sender,
+        }
+    }
+}
@@ -3,6 +3,7 @@ extern crate derive_new;
 
 extern crate alloc;
 
+mod adapter;
 mod backend;
 mod compiler;
 mod compute;
@@ -11,6 +12,7 @@ mod element;
 mod graphics;
 mod runtime;
 
+pub use adapter::*;
 pub use compiler::base::*;
 pub use compiler::wgsl::WgslCompiler;
 pub use compute::*;
@@ -31,15 +33,42 @@ mod tests {
     pub type TestRuntime = crate::WgpuRuntime;
 
     cubecl_core::testgen_all!();
+    cubecl_core::testgen_launch_limits!();
+    cubecl_core::testgen_launch_compilation_error!();
     cubecl_std::testgen!();
     cubecl_std::testgen_tensor_identity!([flex32, f32, u32]);
-    cubecl_matmul::testgen_matmul_simple!([flex32, f32]);
+    cubecl_std::testgen_tensor_contiguous!();
+    cubecl_std::testgen_tensor_elemwise!();
+    cubecl_std::testgen_tensor_gather!();
+    cubecl_std::testgen_tensor_cast!();
+    cubecl_std::testgen_tensor_pad!();
+    cubecl_std::testgen_tensor_slice!();
+    cubecl_std::testgen_tensor_transpose!([f32, u32]);
+    cubecl_matmul::testgen_matmul!([flex32, f32]);
+    cubecl_matmul::testgen_trsm!();
+    cubecl_matmul::testgen_syrk!();
+    cubecl_matmul::testgen_matmul_heuristic!();
+    cubecl_matmul::testgen_matmul_batched_gemv!();
+    cubecl_matmul::testgen_matmul_batched_tiny!();
+    cubecl_matmul::testgen_matmul_degenerate!();
+    cubecl_matmul::testgen_matmul_blocked!();
+    cubecl_matmul::testgen_matmul_stage_dump!();
     cubecl_matmul::testgen_matmul_plane_vecmat!();
     cubecl_matmul::testgen_matmul_unit!();
     cubecl_reduce::testgen_reduce!();
     cubecl_random::testgen_random!();
     cubecl_attention::testgen_attention!();
     cubecl_reduce::testgen_shared_sum!([f32]);
+    cubecl_reduce::testgen_mean_var!([f32]);
+    cubecl_reduce::testgen_softmax!([f32]);
+    cubecl_reduce::testgen_norm!([f32]);
+    cubecl_reduce::testgen_topk!([f32]);
+    cubecl_reduce::testgen_scan!();
+    cubecl_reduce::testgen_cumsum!();
+    cubecl_reduce::testgen_radix_sort!();
+    cubecl_reduce::testgen_histogram!();
+    cubecl_reduce::testgen_segmented_reduce!();
+    cubecl_reduce::testgen_scatter_add_deterministic!();
     cubecl_quant::testgen_quant!();
 }
 
@@ -51,16 +80,37 @@ mod tests_spirv {
     use half::f16;
 
     cubecl_core::testgen_all!(f32: [f16, flex32, f32], i32: [i8, i16, i32, i64], u32: [u8, u16, u32, u64]);
+    cubecl_core::testgen_launch_limits!();
     cubecl_std::testgen!();
     cubecl_std::testgen_tensor_identity!([f16, flex32, f32, u32]);
+    cubecl_std::testgen_tensor_contiguous!();
+    cubecl_std::testgen_tensor_elemwise!();
+    cubecl_std::testgen_tensor_gather!();
+    cubecl_std::testgen_tensor_cast!();
+    cubecl_std::testgen_tensor_pad!();
+    cubecl_std::testgen_tensor_slice!();
+    cubecl_std::testgen_tensor_transpose!([f16, f32, u32]);
     cubecl_convolution::testgen_conv2d_accelerated!([f16: f16]);
-    cubecl_matmul::testgen_matmul_simple!([f32]);
+    cubecl_matmul::testgen_matmul!([f32]);
+    cubecl_matmul::testgen_trsm!();
+    cubecl_matmul::testgen_syrk!();
     cubecl_matmul::testgen_matmul_plane_accelerated!();
     cubecl_matmul::testgen_matmul_plane_vecmat!();
     cubecl_matmul::testgen_matmul_unit!();
+    cubecl_matmul::testgen_matmul_scaled_fp8!();
     cubecl_reduce::testgen_reduce!();
     cubecl_random::testgen_random!();
     cubecl_reduce::testgen_shared_sum!([f32]);
+    cubecl_reduce::testgen_mean_var!([f32]);
+    cubecl_reduce::testgen_softmax!([f32]);
+    cubecl_reduce::testgen_norm!([f32]);
+    cubecl_reduce::testgen_topk!([f32]);
+    cubecl_reduce::testgen_scan!();
+    cubecl_reduce::testgen_cumsum!();
+    cubecl_reduce::testgen_radix_sort!();
+    cubecl_reduce::testgen_histogram!();
+    cubecl_reduce::testgen_segmented_reduce!();
+    cubecl_reduce::testgen_scatter_add_deterministic!();
     cubecl_quant::testgen_quant!();
 }
 
@@ -71,10 +121,20 @@ mod tests_msl {
     use half::f16;
 
     cubecl_core::testgen_all!(f32: [f16, f32], i32: [i16, i32], u32: [u16, u32]);
+    cubecl_core::testgen_launch_limits!();
     cubecl_std::testgen!();
     cubecl_std::testgen_tensor_identity!([f16, flex32, f32, u32]);
+    cubecl_std::testgen_tensor_contiguous!();
+    cubecl_std::testgen_tensor_elemwise!();
+    cubecl_std::testgen_tensor_gather!();
+    cubecl_std::testgen_tensor_cast!();
+    cubecl_std::testgen_tensor_pad!();
+    cubecl_std::testgen_tensor_slice!();
+    cubecl_std::testgen_tensor_transpose!([f16, f32, u32]);
     cubecl_convolution::testgen_conv2d_accelerated!([f16: f16]);
-    cubecl_matmul::testgen_matmul_simple!([f16, f32]);
+    cubecl_matmul::testgen_matmul!([f16, f32]);
+    cubecl_matmul::testgen_trsm!();
+    cubecl_matmul::testgen_syrk!();
     cubecl_matmul::testgen_matmul_plane_accelerated!();
     cubecl_matmul::testgen_matmul_plane_vecmat!();
     cubecl_matmul::testgen_matmul_unit!();
@@ -82,4 +142,14 @@ mod tests_msl {
     cubecl_reduce::testgen_reduce!();
     cubecl_random::testgen_random!();
     cubecl_reduce::testgen_shared_sum!([f32]);
+    cubecl_reduce::testgen_mean_var!([f32]);
+    cubecl_reduce::testgen_softmax!([f32]);
+    cubecl_reduce::testgen_norm!([f32]);
+    cubecl_reduce::testgen_topk!([f32]);
+    cubecl_reduce::testgen_scan!();
+    cubecl_reduce::testgen_cumsum!();
+    cubecl_reduce::testgen_radix_sort!();
+    cubecl_reduce::testgen_histogram!();
+    cubecl_reduce::testgen_segmented_reduce!();
+    cubecl_reduce::testgen_scatter_add_deterministic!();
 }
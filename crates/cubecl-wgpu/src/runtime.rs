@@ -1,10 +1,10 @@
 use crate::{
-    AutoCompiler, AutoGraphicsApi, GraphicsApi, WgpuDevice, backend, compute::WgpuServer,
-    contiguous_strides,
+    AdapterSelection, AutoCompiler, AutoGraphicsApi, GraphicsApi, WgpuDevice, adapter, backend,
+    compute::WgpuServer, contiguous_strides,
 };
 use cubecl_common::{future, profile::TimingMethod};
 
-use cubecl_core::{CubeCount, CubeDim, Runtime, ir::TargetProperties};
+use cubecl_core::{CubeCount, CubeDim, Runtime, WgpuCompilationOptions, ir::TargetProperties};
 pub use cubecl_runtime::memory_management::MemoryConfiguration;
 use cubecl_runtime::memory_management::MemoryDeviceProperties;
 use cubecl_runtime::{
@@ -37,9 +37,19 @@ impl Runtime for WgpuRuntime {
 
     fn client(device: &Self::Device) -> ComputeClient<Self::Server, Self::Channel> {
         RUNTIME.client(device, move || {
-            let setup =
-                future::block_on(create_setup_for_device(device, AutoGraphicsApi::backend()));
-            create_client_on_setup(setup, RuntimeOptions::default())
+            // The adapter and device can't be requested synchronously on wasm.
+            #[cfg(target_family = "wasm")]
+            panic!("The device {device:?} must be initialized with init_setup_async on wasm");
+
+            #[cfg(not(target_family = "wasm"))]
+            {
+                let setup = future::block_on(create_setup_for_device(
+                    device,
+                    AutoGraphicsApi::backend(),
+                    None,
+                ));
+                create_client_on_setup(setup, RuntimeOptions::default())
+            }
         })
     }
 
@@ -107,6 +117,35 @@ pub struct RuntimeOptions {
     pub tasks_max: usize,
     /// Configures the memory management.
     pub memory_config: MemoryConfiguration,
+    /// How f16 kernels are handled on adapters without `SHADER_F16`.
+    pub f16_policy: F16Policy,
+    /// Lower the `u32` and `bool` constants of kernels to override constants set when creating
+    /// their pipeline in WGSL, and to specialization constants in SPIR-V, so kernels only
+    /// differing by these values, like the configurations of an autotune sweep, share the same
+    /// shader template.
+    ///
+    /// Values shaping the kernel, like the sizes of arrays, the cube dimensions or the values
+    /// folded by the optimizer, remain part of the shader.
+    pub override_constants: bool,
+    /// Select the adapter by backend and name, index or power preference instead of by the
+    /// [device](WgpuDevice) kind.
+    ///
+    /// Only used when the setup is created by [init_setup] or [init_setup_async], before any
+    /// client of the device exists.
+    pub adapter: Option<AdapterSelection>,
+}
+
+/// How f16 kernels are handled on adapters that don't support f16 in shaders.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum F16Policy {
+    /// f16 isn't registered as a supported type, and compiling a kernel using it panics.
+    #[default]
+    Error,
+    /// f16 is computed in f32, while buffers keep their f16 layout, packed in pairs.
+    ///
+    /// f16 buffers must then be accessed with a line size of 2 or 4, since a single f16 can't be
+    /// written without overwriting its neighbour.
+    Emulate,
 }
 
 impl Default for RuntimeOptions {
@@ -126,6 +165,9 @@ impl Default for RuntimeOptions {
         Self {
             tasks_max,
             memory_config: MemoryConfiguration::default(),
+            f16_policy: F16Policy::default(),
+            override_constants: false,
+            adapter: None,
         }
     }
 }
@@ -178,7 +220,7 @@ pub fn init_setup<G: GraphicsApi>(device: &WgpuDevice, options: RuntimeOptions)
     cfg_if::cfg_if! {
         if #[cfg(target_family = "wasm")] {
             let _ = (device, options);
-            panic!("Creating a wgpu setup synchronously is unsupported on wasm. Use init_async instead");
+            panic!("Creating a wgpu setup synchronously is unsupported on wasm. Use init_setup_async instead");
         } else {
             future::block_on(init_setup_async::<G>(device, options))
         }
@@ -192,7 +234,7 @@ pub async fn init_setup_async<G: GraphicsApi>(
     device: &WgpuDevice,
     options: RuntimeOptions,
 ) -> WgpuSetup {
-    let setup = create_setup_for_device(device, G::backend()).await;
+    let setup = create_setup_for_device(device, G::backend(), options.adapter.as_ref()).await;
     let return_setup = setup.clone();
     let client = create_client_on_setup(setup, options);
     RUNTIME.register(device, client);
@@ -253,7 +295,11 @@ pub(crate) fn create_client_on_setup(
         min_tensor_cores_dim: None,
     };
 
-    let mut compilation_options = Default::default();
+    let mut compilation_options = WgpuCompilationOptions {
+        emulate_f16: options.f16_policy == F16Policy::Emulate,
+        override_constants: options.override_constants,
+        ..Default::default()
+    };
 
     let features = setup.adapter.features();
 
@@ -277,6 +323,8 @@ pub(crate) fn create_client_on_setup(
         {
             use cubecl_runtime::Plane;
 
+            // Plane operations are lowered to the WGSL subgroup builtins. Without them, the
+            // algorithms checking for `Plane::Ops` fall back to shared memory.
             device_props.features.plane.insert(Plane::Ops);
         }
     }
@@ -287,6 +335,7 @@ pub(crate) fn create_client_on_setup(
         mem_props,
         options.memory_config,
         compilation_options,
+        device_props.hardware.clone(),
         setup.device.clone(),
         setup.queue,
         options.tasks_max,
@@ -310,12 +359,15 @@ pub(crate) fn create_client_on_setup(
 }
 
 /// Select the wgpu device and queue based on the provided [device](WgpuDevice) and
-/// [backend](wgpu::Backend).
+/// [backend](wgpu::Backend), or on the adapter selection when provided.
 pub(crate) async fn create_setup_for_device(
     device: &WgpuDevice,
     backend: wgpu::Backend,
+    selection: Option<&AdapterSelection>,
 ) -> WgpuSetup {
-    let (instance, adapter) = request_adapter(device, backend).await;
+    let (instance, adapter) = request_adapter(device, backend, selection).await;
+    // The selection can pick an adapter of another backend.
+    let backend = adapter.get_info().backend;
     let (device, queue) = backend::request_device(&adapter).await;
 
     log::info!(
@@ -336,6 +388,7 @@ pub(crate) async fn create_setup_for_device(
 async fn request_adapter(
     device: &WgpuDevice,
     backend: wgpu::Backend,
+    selection: Option<&AdapterSelection>,
 ) -> (wgpu::Instance, wgpu::Adapter) {
     let debug = ServerLogger::default();
     let instance_flags = match (debug.profile_level(), debug.compilation_activated()) {
@@ -344,6 +397,20 @@ async fn request_adapter(
         (_, false) => InstanceFlags::default(),
     };
     log::debug!("{instance_flags:?}");
+
+    if let Some(selection) = selection {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let (instance, adapter) = adapter::select_adapter(selection, backend, instance_flags);
+            log::info!("Using adapter {:?}", adapter.get_info());
+            return (instance, adapter);
+        }
+
+        // Adapters can't be enumerated on wasm.
+        #[cfg(target_family = "wasm")]
+        log::warn!("Ignoring the adapter selection {selection:?} on wasm");
+    }
+
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
         backends: backend.into(),
         flags: instance_flags,
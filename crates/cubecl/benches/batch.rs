@@ -0,0 +1,143 @@
+use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::client::ReplayableBatch;
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cube(launch)]
+fn add_one(output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] += 1.0;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Submission {
+    /// Each kernel is sent to the server on its own.
+    Unbatched,
+    /// All the kernels are sent to the server at once.
+    Batched,
+    /// The kernels are recorded once, then replayed.
+    Replayed,
+    /// The kernels are recorded once, then updated to another output before each replay.
+    Updated,
+}
+
+struct BatchBench<R: Runtime> {
+    num_kernels: usize,
+    submission: Submission,
+    outputs: [Handle; 2],
+    replayable: Mutex<Option<ReplayableBatch<R::Server, R::Channel>>>,
+    updates: AtomicUsize,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> BatchBench<R> {
+    fn launch_all(&self, client: &ComputeClient<R::Server, R::Channel>, output: &Handle) {
+        for _ in 0..self.num_kernels {
+            add_one::launch::<R>(
+                client,
+                CubeCount::Static(1, 1, 1),
+                CubeDim::new_1d(32),
+                unsafe { ArrayArg::from_raw_parts::<f32>(output, 32, 1) },
+            );
+        }
+    }
+}
+
+impl<R: Runtime> Benchmark for BatchBench<R> {
+    type Input = ();
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        if let Submission::Replayed | Submission::Updated = self.submission {
+            let mut replayable = self.replayable.lock().unwrap();
+            if replayable.is_none() {
+                *replayable = self
+                    .client
+                    .batch_reusable(|client| self.launch_all(client, &self.outputs[0]))
+                    .ok();
+            }
+        }
+    }
+
+    fn execute(&self, _input: Self::Input) -> Result<Self::Output, String> {
+        match self.submission {
+            Submission::Unbatched => self.launch_all(&self.client, &self.outputs[0]),
+            Submission::Batched => self
+                .client
+                .try_batch(|client| self.launch_all(client, &self.outputs[0]))
+                .map_err(|err| format!("{err:?}"))?,
+            Submission::Replayed => match self.replayable.lock().unwrap().as_ref() {
+                Some(batch) => batch.try_replay().map_err(|err| format!("{err:?}"))?,
+                None => return Err("Reusable batches aren't supported".to_string()),
+            },
+            Submission::Updated => match self.replayable.lock().unwrap().as_ref() {
+                Some(batch) => {
+                    let output = &self.outputs[self.updates.fetch_add(1, Ordering::Relaxed) % 2];
+                    batch
+                        .update(|client| self.launch_all(client, output))
+                        .and_then(|_| batch.try_replay())
+                        .map_err(|err| format!("{err:?}"))?
+                }
+                None => return Err("Reusable batches aren't supported".to_string()),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-batch-{:?}-{}",
+            R::name(&self.client),
+            self.submission,
+            self.num_kernels
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+    let outputs = [
+        client.create(f32::as_bytes(&[0.0; 32])),
+        client.create(f32::as_bytes(&[0.0; 32])),
+    ];
+
+    for submission in [
+        Submission::Unbatched,
+        Submission::Batched,
+        Submission::Replayed,
+        Submission::Updated,
+    ] {
+        let bench = BatchBench::<R> {
+            num_kernels: 1000,
+            submission,
+            outputs: outputs.clone(),
+            replayable: Mutex::new(None),
+            updates: AtomicUsize::new(0),
+            client: client.clone(),
+        };
+
+        println!("{}", bench.name());
+        match bench.run(TimingMethod::System) {
+            Ok(val) => println!("{val}"),
+            Err(err) => println!("{err:?}"),
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
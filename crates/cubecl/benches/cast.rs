@@ -0,0 +1,126 @@
+use std::marker::PhantomData;
+
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::{TensorHandle, cast_ref, into_contiguous_ref};
+
+/// Cast a contiguous tensor of `I` to `O`, or copy it when `copy` to compare with a kernel of the
+/// same size that only moves the data.
+struct CastBench<R: Runtime, I: Numeric, O: Numeric> {
+    num_elems: usize,
+    copy: bool,
+    client: ComputeClient<R::Server, R::Channel>,
+    _elems: PhantomData<(I, O)>,
+}
+
+impl<R: Runtime, I: Numeric, O: Numeric> Benchmark for CastBench<R, I, O> {
+    type Input = (TensorHandle<R, I>, TensorHandle<R, O>);
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let input = TensorHandle::<R, I>::new_contiguous(
+            vec![self.num_elems],
+            self.client.empty(self.num_elems * size_of::<I>()),
+        );
+        random_uniform::<R, I>(
+            &self.client,
+            I::from_int(0),
+            I::from_int(100),
+            input.as_ref(),
+        );
+        let output = TensorHandle::<R, O>::new_contiguous(
+            vec![self.num_elems],
+            self.client.empty(self.num_elems * size_of::<O>()),
+        );
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Input) -> Result<Self::Output, String> {
+        match self.copy {
+            true => into_contiguous_ref::<R, I>(&self.client, &input.as_ref(), &output.as_ref()),
+            false => cast_ref::<R, I, O>(&self.client, &input.as_ref(), &output.as_ref()),
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        let op = match self.copy {
+            true => "copy",
+            false => "cast",
+        };
+        format!(
+            "{}-{op}-{}-{}-{}",
+            R::name(&self.client),
+            I::as_type_native_unchecked(),
+            O::as_type_native_unchecked(),
+            self.num_elems
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.num_elems]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "cast-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run_one<R: Runtime, I: Numeric, O: Numeric>(device: &R::Device, num_elems: usize, copy: bool) {
+    assert!(
+        !copy || size_of::<I>() == size_of::<O>(),
+        "a copy should be between elements of the same size"
+    );
+    let bench = CastBench::<R, I, O> {
+        num_elems,
+        copy,
+        client: R::client(device),
+        _elems: PhantomData,
+    };
+    // Each element is read once and written once.
+    let size = num_elems * (size_of::<I>() + size_of::<O>());
+
+    println!("{}", bench.name());
+    match bench.run(TimingMethod::Device) {
+        Ok(val) => {
+            let computed = BenchmarkComputations::new(&val);
+            let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+            println!("Bandwidth: {bandwidth:.2} GB/s");
+            println!("Times: {val}");
+        }
+        Err(err) => println!("{err:?}"),
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    // With and without elements left over after the lines.
+    for num_elems in [1 << 26, (1 << 26) + 3] {
+        run_one::<R, f32, f32>(&device, num_elems, true);
+        run_one::<R, f32, half::f16>(&device, num_elems, false);
+        run_one::<R, half::f16, f32>(&device, num_elems, false);
+        run_one::<R, f32, half::bf16>(&device, num_elems, false);
+        run_one::<R, f32, i32>(&device, num_elems, false);
+        run_one::<R, f32, u8>(&device, num_elems, false);
+        run_one::<R, u8, f32>(&device, num_elems, false);
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
@@ -0,0 +1,136 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::{TensorHandle, contiguous_strategy, into_contiguous_ref};
+
+/// A view of a contiguous `[a, b, c]` tensor.
+#[derive(Debug, Clone, Copy)]
+enum ViewKind {
+    /// The tensor itself.
+    Contiguous,
+    /// The first two dimensions swapped, with contiguous rows.
+    SwapBatches,
+    /// The last two dimensions swapped.
+    Transposed,
+    /// All dimensions reversed.
+    Reversed,
+}
+
+impl ViewKind {
+    fn permutation(&self) -> [usize; 3] {
+        match self {
+            ViewKind::Contiguous => [0, 1, 2],
+            ViewKind::SwapBatches => [1, 0, 2],
+            ViewKind::Transposed => [0, 2, 1],
+            ViewKind::Reversed => [2, 1, 0],
+        }
+    }
+}
+
+struct ContiguousBench<R: Runtime> {
+    shape: [usize; 3],
+    kind: ViewKind,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> ContiguousBench<R> {
+    fn view(&self) -> (Vec<usize>, Vec<usize>) {
+        let strides = [self.shape[1] * self.shape[2], self.shape[2], 1];
+        self.kind
+            .permutation()
+            .iter()
+            .map(|&dim| (self.shape[dim], strides[dim]))
+            .unzip()
+    }
+}
+
+impl<R: Runtime> Benchmark for ContiguousBench<R> {
+    type Input = (TensorHandle<R, f32>, TensorHandle<R, f32>);
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let input = TensorHandle::<R, f32>::empty(&self.client, self.shape.to_vec());
+        random_uniform::<R, f32>(&self.client, 0.0, 1.0, input.as_ref());
+        let (shape, _) = self.view();
+        let output = TensorHandle::<R, f32>::empty(&self.client, shape);
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Input) -> Result<Self::Output, String> {
+        let (shape, strides) = self.view();
+        let view = unsafe {
+            TensorHandleRef::<R>::from_raw_parts(&input.handle, &strides, &shape, size_of::<f32>())
+        };
+        into_contiguous_ref::<R, f32>(&self.client, &view, &output.as_ref());
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        let (shape, strides) = self.view();
+        format!(
+            "{}-contiguous-{:?}-{:?}-{:?}",
+            R::name(&self.client),
+            self.kind,
+            contiguous_strategy(&shape, &strides),
+            self.shape
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.to_vec()]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "contiguous-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    for shape in [[32, 512, 512], [64, 128, 1000]] {
+        for kind in [
+            ViewKind::Contiguous,
+            ViewKind::SwapBatches,
+            ViewKind::Transposed,
+            ViewKind::Reversed,
+        ] {
+            let bench = ContiguousBench::<R> {
+                shape,
+                kind,
+                client: client.clone(),
+            };
+            // Each element is read once and written once.
+            let size = 2 * shape.iter().product::<usize>() * size_of::<f32>();
+
+            println!("{}", bench.name());
+            match bench.run(TimingMethod::Device) {
+                Ok(val) => {
+                    let computed = BenchmarkComputations::new(&val);
+                    let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                    println!("Bandwidth: {bandwidth:.2} GB/s");
+                    println!("Times: {val}");
+                }
+                Err(err) => println!("{err:?}"),
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
@@ -0,0 +1,209 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::{IndexOptions, IndexPolicy, TensorHandle};
+
+/// The sum of a vector with atomics between the cubes, or with the deterministic workspace.
+struct SharedSumBench<R: Runtime> {
+    length: usize,
+    deterministic: bool,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for SharedSumBench<R> {
+    type Input = (TensorHandle<R, f32>, TensorHandle<R, f32>);
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let input = TensorHandle::<R, f32>::empty(&self.client, vec![self.length]);
+        random_uniform::<R, f32>(&self.client, 0.0, 1.0, input.as_ref());
+        let output = TensorHandle::<R, f32>::zeros(&self.client, vec![1]);
+
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Input) -> Result<Self::Output, String> {
+        let cube_count = 64;
+        if self.deterministic {
+            cubecl_reduce::shared_sum_deterministic::<R, f32>(
+                &self.client,
+                input.as_ref(),
+                output.as_ref(),
+                cube_count,
+            );
+            Ok(())
+        } else {
+            cubecl_reduce::shared_sum::<R, f32>(
+                &self.client,
+                input.as_ref(),
+                output.as_ref(),
+                cube_count,
+            )
+            .map_err(|err| format!("{err}"))
+        }
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-shared-sum-{}-deterministic-{}",
+            R::name(&self.client),
+            self.length,
+            self.deterministic
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.length]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "shared-sum-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+/// The scatter of the gradients of an embedding lookup to its `[rows, dim]` table, with atomics
+/// or sorted by index.
+struct ScatterAddBench<R: Runtime> {
+    rows: usize,
+    dim: usize,
+    num_indices: usize,
+    /// The number of distinct rows indexed, few rows meaning many duplicates per row.
+    distinct: usize,
+    deterministic: bool,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for ScatterAddBench<R> {
+    type Input = (
+        TensorHandle<R, f32>,
+        TensorHandle<R, i32>,
+        TensorHandle<R, f32>,
+    );
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let target = TensorHandle::<R, f32>::zeros(&self.client, vec![self.rows, self.dim]);
+        let indices = (0..self.num_indices)
+            .map(|i| ((i * 7919) % self.distinct) as i32)
+            .collect::<Vec<_>>();
+        let indices = TensorHandle::<R, i32>::new_contiguous(
+            vec![self.num_indices],
+            self.client.create(i32::as_bytes(&indices)),
+        );
+        let updates = TensorHandle::<R, f32>::empty(&self.client, vec![self.num_indices, self.dim]);
+        random_uniform::<R, f32>(&self.client, -1.0, 1.0, updates.as_ref());
+
+        (target, indices, updates)
+    }
+
+    fn execute(&self, (target, indices, updates): Self::Input) -> Result<Self::Output, String> {
+        let options = IndexOptions::new(IndexPolicy::Clamp);
+        if self.deterministic {
+            cubecl_reduce::scatter_add_deterministic::<R, f32>(
+                &self.client,
+                &target.as_ref(),
+                &indices.as_ref(),
+                &updates.as_ref(),
+                0,
+                options,
+            )
+            .map(|_| ())
+            .map_err(|err| format!("{err}"))
+        } else {
+            cubecl_std::tensor::scatter_add::<R, f32>(
+                &self.client,
+                &target.as_ref(),
+                &indices.as_ref(),
+                &updates.as_ref(),
+                0,
+                options,
+            );
+            Ok(())
+        }
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-scatter-add-{}x{}-indices-{}-distinct-{}-deterministic-{}",
+            R::name(&self.client),
+            self.rows,
+            self.dim,
+            self.num_indices,
+            self.distinct,
+            self.deterministic
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.rows, self.dim], vec![self.num_indices]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "scatter-add-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+fn print<B: Benchmark>(bench: &B) {
+    println!("{}", bench.name());
+    match bench.run(TimingMethod::Device) {
+        Ok(val) => {
+            let computed = BenchmarkComputations::new(&val);
+            println!("Median: {:?}", computed.median);
+            println!("Times: {val}");
+        }
+        Err(err) => println!("{err:?}"),
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    // The deterministic sum costs a second launch and a workspace of one element per cube.
+    for deterministic in [false, true] {
+        print(&SharedSumBench::<R> {
+            length: 1 << 26,
+            deterministic,
+            client: client.clone(),
+        });
+    }
+
+    // The deterministic scatter costs a sort of the indices, and the duplicates of each index
+    // are summed by a single unit, so few distinct indices are the worst case.
+    for distinct in [32_000, 64] {
+        for deterministic in [false, true] {
+            print(&ScatterAddBench::<R> {
+                rows: 32_000,
+                dim: 512,
+                num_indices: 16_384,
+                distinct,
+                deterministic,
+                client: client.clone(),
+            });
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
@@ -0,0 +1,181 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::{CastElem, ElemwiseBuilder, ElemwiseGraph, TensorHandle, fused_elemwise};
+
+#[derive(Debug, Clone, Copy)]
+enum ElemwiseKind {
+    /// The whole chain in a single kernel.
+    Fused,
+    /// One kernel per operation, writing each intermediate result to global memory.
+    Unfused,
+}
+
+struct ElemwiseBench<R: Runtime> {
+    shape: Vec<usize>,
+    kind: ElemwiseKind,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+/// `clamp(cast(a * scale + b), -1, 1)` as a single graph, or as its graphs of one operation each.
+fn graphs(kind: ElemwiseKind) -> Vec<ElemwiseGraph> {
+    match kind {
+        ElemwiseKind::Fused => {
+            let mut builder = ElemwiseBuilder::new();
+            let a = builder.input();
+            let b = builder.input();
+            let scale = builder.scalar(2.5);
+            let scaled = builder.mul(a, scale);
+            let shifted = builder.add(scaled, b);
+            let rounded = builder.cast(shifted, CastElem::F16);
+            let (min, max) = (builder.scalar(-1.0), builder.scalar(1.0));
+            let clamped = builder.clamp(rounded, min, max);
+            builder.output(clamped);
+            vec![builder.build()]
+        }
+        ElemwiseKind::Unfused => {
+            let mut mul = ElemwiseBuilder::new();
+            let a = mul.input();
+            let scale = mul.scalar(2.5);
+            let scaled = mul.mul(a, scale);
+            mul.output(scaled);
+
+            let mut add = ElemwiseBuilder::new();
+            let (scaled, b) = (add.input(), add.input());
+            let shifted = add.add(scaled, b);
+            add.output(shifted);
+
+            let mut cast = ElemwiseBuilder::new();
+            let shifted = cast.input();
+            let rounded = cast.cast(shifted, CastElem::F16);
+            cast.output(rounded);
+
+            let mut clamp = ElemwiseBuilder::new();
+            let rounded = clamp.input();
+            let (min, max) = (clamp.scalar(-1.0), clamp.scalar(1.0));
+            let clamped = clamp.clamp(rounded, min, max);
+            clamp.output(clamped);
+
+            vec![mul.build(), add.build(), cast.build(), clamp.build()]
+        }
+    }
+}
+
+impl<R: Runtime> Benchmark for ElemwiseBench<R> {
+    type Input = (TensorHandle<R, f32>, TensorHandle<R, f32>);
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let a = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+        let b = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+        random_uniform::<R, f32>(&self.client, -1.0, 1.0, a.as_ref());
+        random_uniform::<R, f32>(&self.client, -1.0, 1.0, b.as_ref());
+        (a, b)
+    }
+
+    fn execute(&self, (a, b): Self::Input) -> Result<Self::Output, String> {
+        let output = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+        let graphs = graphs(self.kind);
+        match self.kind {
+            ElemwiseKind::Fused => {
+                fused_elemwise::<R, f32, f32, f32>(
+                    &self.client,
+                    &graphs[0],
+                    &[a.as_ref(), b.as_ref()],
+                    &[output.as_ref()],
+                );
+            }
+            ElemwiseKind::Unfused => {
+                let intermediate = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+                let (mul, add, cast, clamp) = (&graphs[0], &graphs[1], &graphs[2], &graphs[3]);
+                fused_elemwise::<R, f32, f32, f32>(
+                    &self.client,
+                    mul,
+                    &[a.as_ref()],
+                    &[intermediate.as_ref()],
+                );
+                fused_elemwise::<R, f32, f32, f32>(
+                    &self.client,
+                    add,
+                    &[intermediate.as_ref(), b.as_ref()],
+                    &[output.as_ref()],
+                );
+                fused_elemwise::<R, f32, f32, f32>(
+                    &self.client,
+                    cast,
+                    &[output.as_ref()],
+                    &[intermediate.as_ref()],
+                );
+                fused_elemwise::<R, f32, f32, f32>(
+                    &self.client,
+                    clamp,
+                    &[intermediate.as_ref()],
+                    &[output.as_ref()],
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-elemwise-{:?}-{:?}",
+            R::name(&self.client),
+            self.kind,
+            self.shape
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.clone()]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "elemwise-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    for shape in [vec![32, 1024, 1024], vec![4096, 1000]] {
+        for kind in [ElemwiseKind::Fused, ElemwiseKind::Unfused] {
+            let bench = ElemwiseBench::<R> {
+                shape: shape.clone(),
+                kind,
+                client: client.clone(),
+            };
+            // The fused kernel reads both inputs once and writes the output once.
+            let size = 3 * shape.iter().product::<usize>() * size_of::<f32>();
+
+            println!("{}", bench.name());
+            match bench.run(TimingMethod::Device) {
+                Ok(val) => {
+                    let computed = BenchmarkComputations::new(&val);
+                    let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                    println!("Effective bandwidth: {bandwidth:.2} GB/s");
+                    println!("Times: {val}");
+                }
+                Err(err) => println!("{err:?}"),
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
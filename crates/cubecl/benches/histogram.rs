@@ -0,0 +1,96 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_reduce::HistogramOptions;
+use cubecl_std::tensor::TensorHandle;
+
+struct HistogramBench<R: Runtime> {
+    length: usize,
+    num_bins: usize,
+    /// The fraction of the range covered by the values, small values putting them all in the same bins.
+    spread: f32,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for HistogramBench<R> {
+    type Input = TensorHandle<R, f32>;
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let input = TensorHandle::<R, f32>::empty(&self.client, vec![self.length]);
+        random_uniform::<R, f32>(&self.client, 0.0, self.spread, input.as_ref());
+        input
+    }
+
+    fn execute(&self, input: Self::Input) -> Result<Self::Output, String> {
+        let options = HistogramOptions::new(self.num_bins).with_range(0.0, 1.0);
+        cubecl_reduce::histogram::<R, f32>(&self.client, input.as_ref(), options)
+            .map(|_| ())
+            .map_err(|err| format!("{err}"))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-histogram-{}-bins-{}-spread-{}",
+            R::name(&self.client),
+            self.length,
+            self.num_bins,
+            self.spread
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![vec![self.length]]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "histogram-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    // Uniform values are the reference, values covering a tiny part of the range all fall
+    // in the same bin and measure the contention on its counters.
+    for num_bins in [16, 256, 4096] {
+        for spread in [1.0, 0.0001] {
+            let bench = HistogramBench::<R> {
+                length: 1 << 26,
+                num_bins,
+                spread,
+                client: client.clone(),
+            };
+            let size = bench.length * size_of::<f32>();
+
+            println!("{}", bench.name());
+            match bench.run(TimingMethod::Device) {
+                Ok(val) => {
+                    let computed = BenchmarkComputations::new(&val);
+                    let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                    println!("Bandwidth: {bandwidth:.2} GB/s");
+                    println!("Times: {val}");
+                }
+                Err(err) => println!("{err:?}"),
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
@@ -0,0 +1,76 @@
+use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+
+#[cube(launch)]
+fn add(lhs: &Array<f32>, rhs: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < output.len() {
+        output[ABSOLUTE_POS] = lhs[ABSOLUTE_POS] + rhs[ABSOLUTE_POS];
+    }
+}
+
+/// Launches the same tiny kernel on the same buffers many times, so the time is dominated by
+/// the cost of binding its resources.
+struct LaunchBench<R: Runtime> {
+    num_kernels: usize,
+    lhs: Handle,
+    rhs: Handle,
+    output: Handle,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for LaunchBench<R> {
+    type Input = ();
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {}
+
+    fn execute(&self, _input: Self::Input) -> Result<Self::Output, String> {
+        for _ in 0..self.num_kernels {
+            add::launch::<R>(
+                &self.client,
+                CubeCount::Static(1, 1, 1),
+                CubeDim::new_1d(32),
+                unsafe { ArrayArg::from_raw_parts::<f32>(&self.lhs, 32, 1) },
+                unsafe { ArrayArg::from_raw_parts::<f32>(&self.rhs, 32, 1) },
+                unsafe { ArrayArg::from_raw_parts::<f32>(&self.output, 32, 1) },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!("{}-launch-{}", R::name(&self.client), self.num_kernels).to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+    let bench = LaunchBench::<R> {
+        num_kernels: 1000,
+        lhs: client.create(f32::as_bytes(&[1.0; 32])),
+        rhs: client.create(f32::as_bytes(&[2.0; 32])),
+        output: client.empty(32 * size_of::<f32>()),
+        client,
+    };
+
+    println!("{}", bench.name());
+    match bench.run(TimingMethod::System) {
+        Ok(val) => println!("{val}"),
+        Err(err) => println!("{err:?}"),
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
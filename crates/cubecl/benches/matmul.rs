@@ -3,8 +3,8 @@ use cubecl::prelude::*;
 use cubecl_matmul::components::batch::HypercubeSelection;
 use cubecl_matmul::components::stage::PartitionBuffering;
 use cubecl_matmul::components::{
-    LhsG, LoadingPrecomputeStrategy, MatmulElems, MatmulPrecision, MatmulSelection, RhsG,
-    StageSize, TilingScheme,
+    CacheHints, LhsG, LoadingPrecomputeStrategy, MatmulElems, MatmulPrecision, MatmulSelection,
+    RhsG, StageSize, TilingScheme,
 };
 use cubecl_matmul::kernels::layered::double_buffering::DoubleBufferingArgs;
 use cubecl_matmul::kernels::layered::double_unit::DoubleUnitSelectionArgs;
@@ -295,6 +295,7 @@ fn run_algos_vecmat<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
             tile_size: TileSizeSelection::MinTileSize,
+            ..Default::default()
         })),
     );
 
@@ -303,6 +304,7 @@ fn run_algos_vecmat<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
             tile_size: TileSizeSelection::MaxTileSize,
+            ..Default::default()
         })),
     );
 }
@@ -316,6 +318,7 @@ fn run_algos_unit<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
             tile_size: TileSizeSelection::MinTileSize,
+            ..Default::default()
         })),
     );
 
@@ -324,6 +327,7 @@ fn run_algos_unit<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
             tile_size: TileSizeSelection::MaxTileSize,
+            ..Default::default()
         })),
     );
 
@@ -332,6 +336,7 @@ fn run_algos_unit<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::DoubleUnit(Selection::Inferred(DoubleUnitSelectionArgs {
             tile_size: TileSizeSelection::MinTileSize,
+            ..Default::default()
         })),
     );
     println!("Double Unit Max");
@@ -339,6 +344,7 @@ fn run_algos_unit<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::DoubleUnit(Selection::Inferred(DoubleUnitSelectionArgs {
             tile_size: TileSizeSelection::MaxTileSize,
+            ..Default::default()
         })),
     );
 }
@@ -370,7 +376,10 @@ fn run_algos_wmma<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::DoubleBuffering(
             SyncPartialLoadingStrategy::Tilewise,
-            Selection::Inferred(DoubleBufferingArgs { specialized: false }),
+            Selection::Inferred(DoubleBufferingArgs {
+                specialized: false,
+                ..Default::default()
+            }),
         ),
     );
 
@@ -379,7 +388,10 @@ fn run_algos_wmma<R: Runtime, MP: MatmulPrecision>() {
         Default::default(),
         matmul::Strategy::DoubleBuffering(
             SyncPartialLoadingStrategy::Tilewise,
-            Selection::Inferred(DoubleBufferingArgs { specialized: true }),
+            Selection::Inferred(DoubleBufferingArgs {
+                specialized: true,
+                ..Default::default()
+            }),
         ),
     );
 
@@ -394,12 +406,67 @@ fn run_algos_wmma<R: Runtime, MP: MatmulPrecision>() {
     );
 }
 
+#[allow(unused)]
+// Compares the loads without cache hints to the lhs persisting and the rhs streaming, on a large
+// `k` where the tiles of the lhs are reused across `n`.
+fn run_cache_hints<R: Runtime, MP: MatmulPrecision>() {
+    for (name, cache_hints) in [
+        ("default", CacheHints::default()),
+        ("persisting lhs", CacheHints::persisting_lhs()),
+    ] {
+        println!("Double Buffering with cache hints: {name}");
+        let _ = run_one::<R, MP>(
+            Default::default(),
+            matmul::Strategy::DoubleBuffering(
+                SyncPartialLoadingStrategy::Hybrid,
+                Selection::Inferred(DoubleBufferingArgs {
+                    specialized: false,
+                    cache_hints,
+                }),
+            ),
+            (1, 4096, 4096, 16384),
+            (false, false),
+        );
+    }
+}
+
+#[allow(unused)]
+// Compares the unit matmuls with and without swizzled stages, where neighbouring units read the
+// same line of their tiles in lockstep, so the same banks without the swizzle.
+fn run_stage_swizzle<R: Runtime, MP: MatmulPrecision>() {
+    for stage_swizzle in [false, true] {
+        println!("Simple Unit Max with stage swizzle: {stage_swizzle}");
+        let _ = run_one::<R, MP>(
+            Default::default(),
+            matmul::Strategy::SimpleUnit(Selection::Inferred(SimpleUnitSelectionArgs {
+                tile_size: TileSizeSelection::MaxTileSize,
+                stage_swizzle,
+            })),
+            (1, 2048, 2048, 2048),
+            (false, false),
+        );
+
+        println!("Double Unit Max with stage swizzle: {stage_swizzle}");
+        let _ = run_one::<R, MP>(
+            Default::default(),
+            matmul::Strategy::DoubleUnit(Selection::Inferred(DoubleUnitSelectionArgs {
+                tile_size: TileSizeSelection::MaxTileSize,
+                stage_swizzle,
+            })),
+            (1, 2048, 2048, 2048),
+            (false, false),
+        );
+    }
+}
+
 #[allow(unused)]
 fn run_benches<R: Runtime, MP: MatmulPrecision>() {
     // run_grid_search::<R, MP>();
     run_algos_unit::<R, MP>();
     run_algos_wmma::<R, MP>();
     // run_algos_vecmat::<R, MP>();
+    // run_cache_hints::<R, MP>();
+    // run_stage_swizzle::<R, MP>();
 }
 
 fn main() {
@@ -0,0 +1,138 @@
+use core::marker::PhantomData;
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_matmul::components::{AccG, LhsG, MatmulPrecision, RhsG};
+use cubecl_matmul::{self as matmul, MatmulInputHandle, SyncLoadingStrategy};
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::TensorHandle;
+
+/// A batch of matrix-vector products, compared across strategies.
+struct GemvBench<R: Runtime, MP> {
+    b: usize,
+    m: usize,
+    k: usize,
+    /// Broadcast a single vector to each matrix of the batch.
+    broadcast: bool,
+    strategy: matmul::Strategy,
+    client: ComputeClient<R::Server, R::Channel>,
+    _mp: PhantomData<MP>,
+}
+
+impl<R: Runtime, MP: MatmulPrecision> GemvBench<R, MP> {
+    fn vector_batches(&self) -> usize {
+        match self.broadcast {
+            true => 1,
+            false => self.b,
+        }
+    }
+}
+
+impl<R: Runtime, MP: MatmulPrecision> Benchmark for GemvBench<R, MP> {
+    type Input = (
+        MatmulInputHandle<R, LhsG<MP>>,
+        MatmulInputHandle<R, RhsG<MP>>,
+    );
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let lhs = TensorHandle::<R, LhsG<MP>>::empty(&self.client, vec![self.b, self.m, self.k]);
+        random_uniform::<R, LhsG<MP>>(
+            &self.client,
+            LhsG::<MP>::from_int(0),
+            LhsG::<MP>::from_int(1),
+            lhs.as_ref(),
+        );
+        let rhs = TensorHandle::<R, RhsG<MP>>::empty(
+            &self.client,
+            vec![self.vector_batches(), self.k, 1],
+        );
+        random_uniform::<R, RhsG<MP>>(
+            &self.client,
+            RhsG::<MP>::from_int(0),
+            RhsG::<MP>::from_int(1),
+            rhs.as_ref(),
+        );
+
+        (
+            MatmulInputHandle::Normal(lhs),
+            MatmulInputHandle::Normal(rhs),
+        )
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Result<Self::Output, String> {
+        let out = TensorHandle::empty(&self.client, vec![self.b, self.m, 1]);
+
+        matmul::launch::<R, MP>(&self.strategy, &self.client, lhs, rhs, out)
+            .map_err(|err| format!("{err:?}"))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-matmul-gemv-{}x{}x{}-{}-{:?}",
+            R::name(&self.client),
+            self.b,
+            self.m,
+            self.k,
+            match self.broadcast {
+                true => "broadcast",
+                false => "batched",
+            },
+            self.strategy
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime, MP: MatmulPrecision>(device: R::Device) {
+    let client = R::client(&device);
+
+    // Vectors held in registers, and vectors loaded in shared memory.
+    for (b, m, k) in [(64, 4096, 128), (16, 4096, 4096), (1, 16384, 16384)] {
+        for broadcast in [true, false] {
+            for strategy in [
+                matmul::Strategy::BatchedGemv,
+                matmul::Strategy::Simple(SyncLoadingStrategy::Cyclic, Default::default()),
+                matmul::Strategy::SimpleUnit(Default::default()),
+            ] {
+                let bench = GemvBench::<R, MP> {
+                    b,
+                    m,
+                    k,
+                    broadcast,
+                    strategy,
+                    client: client.clone(),
+                    _mp: PhantomData,
+                };
+                // The product is bound by reading the matrices, the vectors and the output once.
+                let size = (b * m * k + bench.vector_batches() * k) * size_of::<LhsG<MP>>()
+                    + b * m * size_of::<AccG<MP>>();
+
+                println!("{}", bench.name());
+                match bench.run(TimingMethod::Device) {
+                    Ok(val) => {
+                        let computed = BenchmarkComputations::new(&val);
+                        let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                        println!("Bandwidth: {bandwidth:.2} GB/s");
+                        println!("Times: {val}");
+                    }
+                    Err(err) => println!("{err:?}"),
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default());
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, half::f16>(Default::default());
+    #[cfg(all(feature = "hip", target_os = "linux"))]
+    run::<cubecl::hip::HipRuntime, half::f16>(Default::default());
+}
@@ -0,0 +1,115 @@
+use core::marker::PhantomData;
+use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_matmul::components::{LhsG, MatmulPrecision, RhsG};
+use cubecl_matmul::{self as matmul, MatmulInputHandle, SyncLoadingStrategy};
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::TensorHandle;
+
+/// A batch of tiny matmuls, compared across strategies.
+struct TinyMatmulBench<R: Runtime, MP> {
+    b: usize,
+    m: usize,
+    n: usize,
+    k: usize,
+    strategy: matmul::Strategy,
+    client: ComputeClient<R::Server, R::Channel>,
+    _mp: PhantomData<MP>,
+}
+
+impl<R: Runtime, MP: MatmulPrecision> Benchmark for TinyMatmulBench<R, MP> {
+    type Input = (
+        MatmulInputHandle<R, LhsG<MP>>,
+        MatmulInputHandle<R, RhsG<MP>>,
+    );
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let lhs = TensorHandle::<R, LhsG<MP>>::empty(&self.client, vec![self.b, self.m, self.k]);
+        random_uniform::<R, LhsG<MP>>(
+            &self.client,
+            LhsG::<MP>::from_int(0),
+            LhsG::<MP>::from_int(1),
+            lhs.as_ref(),
+        );
+        let rhs = TensorHandle::<R, RhsG<MP>>::empty(&self.client, vec![self.b, self.k, self.n]);
+        random_uniform::<R, RhsG<MP>>(
+            &self.client,
+            RhsG::<MP>::from_int(0),
+            RhsG::<MP>::from_int(1),
+            rhs.as_ref(),
+        );
+
+        (
+            MatmulInputHandle::Normal(lhs),
+            MatmulInputHandle::Normal(rhs),
+        )
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Input) -> Result<Self::Output, String> {
+        let out = TensorHandle::empty(&self.client, vec![self.b, self.m, self.n]);
+
+        matmul::launch::<R, MP>(&self.strategy, &self.client, lhs, rhs, out)
+            .map_err(|err| format!("{err:?}"))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-matmul-tiny-{}x{}x{}x{}-{:?}",
+            R::name(&self.client),
+            self.b,
+            self.m,
+            self.n,
+            self.k,
+            self.strategy
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime, MP: MatmulPrecision>(device: R::Device) {
+    let client = R::client(&device);
+
+    for (b, m, n, k) in [
+        (10_000, 16, 16, 16),
+        (10_000, 8, 8, 8),
+        (10_000, 32, 32, 32),
+    ] {
+        for strategy in [
+            matmul::Strategy::BatchedTiny,
+            matmul::Strategy::Simple(SyncLoadingStrategy::Cyclic, Default::default()),
+            matmul::Strategy::SimpleUnit(Default::default()),
+        ] {
+            let bench = TinyMatmulBench::<R, MP> {
+                b,
+                m,
+                n,
+                k,
+                strategy,
+                client: client.clone(),
+                _mp: PhantomData,
+            };
+
+            println!("{}", bench.name());
+            match bench.run(TimingMethod::System) {
+                Ok(val) => println!("{val}"),
+                Err(err) => println!("{err:?}"),
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default());
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, half::f16>(Default::default());
+    #[cfg(all(feature = "hip", target_os = "linux"))]
+    run::<cubecl::hip::HipRuntime, half::f16>(Default::default());
+}
@@ -0,0 +1,134 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::{TensorHandle, into_contiguous_ref};
+
+#[derive(Debug, Clone, Copy)]
+enum NormBenchKind {
+    LayerNorm,
+    RmsNorm,
+    /// A copy of the input, the bandwidth reference of the normalizations.
+    Copy,
+}
+
+struct NormBench<R: Runtime> {
+    shape: Vec<usize>,
+    kind: NormBenchKind,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for NormBench<R> {
+    type Input = (
+        TensorHandle<R, f32>,
+        TensorHandle<R, f32>,
+        TensorHandle<R, f32>,
+        TensorHandle<R, f32>,
+    );
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let length = *self.shape.last().unwrap();
+        let input = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+        let gamma = TensorHandle::<R, f32>::empty(&self.client, vec![length]);
+        let beta = TensorHandle::<R, f32>::empty(&self.client, vec![length]);
+        random_uniform::<R, f32>(&self.client, -1.0, 1.0, input.as_ref());
+        random_uniform::<R, f32>(&self.client, 0.5, 1.5, gamma.as_ref());
+        random_uniform::<R, f32>(&self.client, -0.5, 0.5, beta.as_ref());
+
+        let output = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+        (input, output, gamma, beta)
+    }
+
+    fn execute(&self, (input, output, gamma, beta): Self::Input) -> Result<Self::Output, String> {
+        match self.kind {
+            NormBenchKind::LayerNorm => cubecl_reduce::layer_norm::<R, f32>(
+                &self.client,
+                input.as_ref(),
+                output.as_ref(),
+                Some(gamma.as_ref()),
+                Some(beta.as_ref()),
+                1e-5,
+            )
+            .map_err(|err| format!("{err}")),
+            NormBenchKind::RmsNorm => cubecl_reduce::rms_norm::<R, f32>(
+                &self.client,
+                input.as_ref(),
+                output.as_ref(),
+                Some(gamma.as_ref()),
+                1e-5,
+            )
+            .map_err(|err| format!("{err}")),
+            NormBenchKind::Copy => {
+                into_contiguous_ref::<R, f32>(&self.client, &input.as_ref(), &output.as_ref());
+                Ok(())
+            }
+        }
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-norm-{:?}-{:?}",
+            R::name(&self.client),
+            self.kind,
+            self.shape
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.clone()]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "norm-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    // Short rows normalized by planes, rows kept in shared memory, and rows streamed twice from global memory.
+    for shape in [vec![65536, 128], vec![8192, 4096], vec![256, 65536]] {
+        for kind in [
+            NormBenchKind::LayerNorm,
+            NormBenchKind::RmsNorm,
+            NormBenchKind::Copy,
+        ] {
+            let bench = NormBench::<R> {
+                shape: shape.clone(),
+                kind,
+                client: client.clone(),
+            };
+            // The rows are read once and written once, when they fit in shared memory.
+            let size = 2 * shape.iter().product::<usize>() * size_of::<f32>();
+
+            println!("{}", bench.name());
+            match bench.run(TimingMethod::Device) {
+                Ok(val) => {
+                    let computed = BenchmarkComputations::new(&val);
+                    let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                    println!("Bandwidth: {bandwidth:.2} GB/s");
+                    println!("Times: {val}");
+                }
+                Err(err) => println!("{err:?}"),
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
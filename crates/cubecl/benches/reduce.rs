@@ -0,0 +1,141 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_reduce::ReduceStrategy;
+use cubecl_reduce::instructions::Sum;
+use cubecl_std::tensor::TensorHandle;
+use std::marker::PhantomData;
+
+struct ReduceBench<R: Runtime, E> {
+    shape: Vec<usize>,
+    axis: usize,
+    strategy: Option<ReduceStrategy>,
+    client: ComputeClient<R::Server, R::Channel>,
+    _e: PhantomData<E>,
+}
+
+impl<R: Runtime, E: Float + cubecl_reduce::ReducePrecision> Benchmark for ReduceBench<R, E> {
+    type Input = (TensorHandle<R, E>, TensorHandle<R, E>);
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let input = TensorHandle::<R, E>::empty(&self.client, self.shape.clone());
+        random_uniform::<R, E>(&self.client, E::from_int(0), E::from_int(1), input.as_ref());
+
+        let mut output_shape = self.shape.clone();
+        output_shape[self.axis] = 1;
+        let output = TensorHandle::<R, E>::empty(&self.client, output_shape);
+
+        (input, output)
+    }
+
+    fn execute(&self, (input, output): Self::Input) -> Result<Self::Output, String> {
+        cubecl_reduce::reduce::<R, E, E, Sum>(
+            &self.client,
+            input.as_ref(),
+            output.as_ref(),
+            self.axis,
+            self.strategy,
+            (),
+        )
+        .map_err(|err| format!("{err}"))
+    }
+
+    fn name(&self) -> String {
+        let strategy = match self.strategy {
+            None => "default".to_string(),
+            Some(ReduceStrategy { use_planes, shared }) => {
+                format!("planes-{use_planes}-shared-{shared}")
+            }
+        };
+        format!(
+            "{}-reduce-sum-{}-{:?}-axis-{}-{}",
+            R::name(&self.client),
+            E::as_type_native_unchecked(),
+            self.shape,
+            self.axis,
+            strategy
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.clone()]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "reduce-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime, E: Float + cubecl_reduce::ReducePrecision>(device: R::Device) {
+    let client = R::client(&device);
+
+    // Column sums of a row-major matrix reduce its non-contiguous axis,
+    // row sums are the contiguous reference.
+    for axis in [0, 1] {
+        run_bench(ReduceBench::<R, E> {
+            shape: vec![8192, 8192],
+            axis,
+            strategy: None,
+            client: client.clone(),
+            _e: PhantomData,
+        });
+    }
+
+    // Many short rows, like per-token norms, reduced by whole cubes, by planes and with the default strategy.
+    for strategy in [
+        Some(ReduceStrategy {
+            use_planes: true,
+            shared: true,
+        }),
+        Some(ReduceStrategy {
+            use_planes: true,
+            shared: false,
+        }),
+        None,
+    ] {
+        run_bench(ReduceBench::<R, E> {
+            shape: vec![1 << 20, 128],
+            axis: 1,
+            strategy,
+            client: client.clone(),
+            _e: PhantomData,
+        });
+    }
+}
+
+#[allow(dead_code)]
+fn run_bench<R: Runtime, E: Float + cubecl_reduce::ReducePrecision>(bench: ReduceBench<R, E>) {
+    let size = bench.shape.iter().product::<usize>() * size_of::<E>();
+
+    println!("{}", bench.name());
+    match bench.run(TimingMethod::Device) {
+        Ok(val) => {
+            let computed = BenchmarkComputations::new(&val);
+            let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+            println!("Bandwidth: {bandwidth:.2} GB/s");
+            println!("Times: {val}");
+        }
+        Err(err) => println!("{err:?}"),
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, f32>(Default::default());
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime, half::f16>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime, f32>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime, f32>(Default::default());
+}
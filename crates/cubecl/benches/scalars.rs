@@ -0,0 +1,72 @@
+use cubecl::benchmark::{Benchmark, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+
+#[cube(launch)]
+fn scale(output: &mut Array<f32>, alpha: f32, beta: f32, len: u32) {
+    if ABSOLUTE_POS < len {
+        output[ABSOLUTE_POS] = output[ABSOLUTE_POS] * alpha + beta;
+    }
+}
+
+/// Launches many tiny kernels, so the time is dominated by the cost of setting their scalars.
+struct ScalarsBench<R: Runtime> {
+    num_kernels: usize,
+    output: Handle,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for ScalarsBench<R> {
+    type Input = ();
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {}
+
+    fn execute(&self, _input: Self::Input) -> Result<Self::Output, String> {
+        for i in 0..self.num_kernels {
+            scale::launch::<R>(
+                &self.client,
+                CubeCount::Static(1, 1, 1),
+                CubeDim::new_1d(32),
+                unsafe { ArrayArg::from_raw_parts::<f32>(&self.output, 32, 1) },
+                ScalarArg::new(1.0),
+                ScalarArg::new(i as f32),
+                ScalarArg::new(32),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!("{}-scalars-{}", R::name(&self.client), self.num_kernels).to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+    let bench = ScalarsBench::<R> {
+        num_kernels: 1000,
+        output: client.create(f32::as_bytes(&[0.0; 32])),
+        client,
+    };
+
+    println!("{}", bench.name());
+    match bench.run(TimingMethod::System) {
+        Ok(val) => println!("{val}"),
+        Err(err) => println!("{err:?}"),
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
@@ -0,0 +1,117 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl::server::{Handle, PinnedBuffer};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    HostToDevice,
+    DeviceToHost,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HostMemory {
+    /// The regular path, with data copied from and to host vectors.
+    Pageable,
+    /// Transfers going through a pinned buffer allocated once.
+    Pinned,
+}
+
+struct TransferBench<R: Runtime> {
+    size: usize,
+    direction: Direction,
+    memory: HostMemory,
+    data: Vec<u8>,
+    pinned: Mutex<Option<PinnedBuffer>>,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for TransferBench<R> {
+    type Input = Handle;
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        self.client.create(&self.data)
+    }
+
+    fn execute(&self, handle: Self::Input) -> Result<Self::Output, String> {
+        match (self.memory, self.direction) {
+            (HostMemory::Pageable, Direction::HostToDevice) => {
+                self.client.create(&self.data);
+            }
+            (HostMemory::Pageable, Direction::DeviceToHost) => {
+                self.client.read_one(handle);
+            }
+            (HostMemory::Pinned, direction) => {
+                let mut pinned = self.pinned.lock().unwrap();
+                let buffer = pinned.take().unwrap();
+                let transfer = match direction {
+                    Direction::HostToDevice => self.client.write_from_pinned(buffer, handle),
+                    Direction::DeviceToHost => self.client.read_to_pinned(handle, buffer),
+                };
+                let buffer = future::block_on(transfer).map_err(|err| format!("{err:?}"))?;
+                *pinned = Some(buffer);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-transfer-{:?}-{:?}-{}",
+            R::name(&self.client),
+            self.memory,
+            self.direction,
+            self.size
+        )
+        .to_lowercase()
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    for size in [1 << 20, 16 << 20, 256 << 20] {
+        for direction in [Direction::HostToDevice, Direction::DeviceToHost] {
+            for memory in [HostMemory::Pageable, HostMemory::Pinned] {
+                let pinned = match memory {
+                    HostMemory::Pageable => None,
+                    HostMemory::Pinned => Some(client.create_pinned(size)),
+                };
+                let bench = TransferBench::<R> {
+                    size,
+                    direction,
+                    memory,
+                    data: vec![1; size],
+                    pinned: Mutex::new(pinned),
+                    client: client.clone(),
+                };
+
+                println!("{}", bench.name());
+                match bench.run(TimingMethod::System) {
+                    Ok(val) => {
+                        let computed = BenchmarkComputations::new(&val);
+                        let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                        println!("Bandwidth: {bandwidth:.2} GB/s");
+                        println!("Times: {val}");
+                    }
+                    Err(err) => println!("{err:?}"),
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
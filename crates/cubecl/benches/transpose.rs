@@ -0,0 +1,104 @@
+use cubecl::benchmark::{Benchmark, BenchmarkComputations, TimingMethod};
+use cubecl::future;
+use cubecl::prelude::*;
+use cubecl_random::random_uniform;
+use cubecl_std::tensor::{TensorHandle, into_contiguous_ref, transpose};
+
+#[derive(Debug, Clone, Copy)]
+enum TransposeKind {
+    /// The tiled transpose of the last two dimensions.
+    Tiled,
+    /// A copy of a contiguous tensor, the bandwidth reference of the transpose.
+    Copy,
+}
+
+struct TransposeBench<R: Runtime> {
+    shape: Vec<usize>,
+    kind: TransposeKind,
+    client: ComputeClient<R::Server, R::Channel>,
+}
+
+impl<R: Runtime> Benchmark for TransposeBench<R> {
+    type Input = TensorHandle<R, f32>;
+    type Output = ();
+
+    fn prepare(&self) -> Self::Input {
+        let input = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+        random_uniform::<R, f32>(&self.client, 0.0, 1.0, input.as_ref());
+        input
+    }
+
+    fn execute(&self, input: Self::Input) -> Result<Self::Output, String> {
+        match self.kind {
+            TransposeKind::Tiled => {
+                transpose::<R, f32>(&self.client, &input.as_ref());
+            }
+            TransposeKind::Copy => {
+                let output = TensorHandle::<R, f32>::empty(&self.client, self.shape.clone());
+                into_contiguous_ref::<R, f32>(&self.client, &input.as_ref(), &output.as_ref());
+            }
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}-transpose-{:?}-{:?}",
+            R::name(&self.client),
+            self.kind,
+            self.shape
+        )
+        .to_lowercase()
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.clone()]
+    }
+
+    fn sync(&self) {
+        future::block_on(self.client.sync())
+    }
+
+    fn profile(&self, args: Self::Input) -> Result<cubecl::benchmark::ProfileDuration, String> {
+        self.client
+            .profile(|| self.execute(args), "transpose-bench")
+            .map_err(|err| format!("{err:?}"))
+    }
+}
+
+#[allow(dead_code)]
+fn run<R: Runtime>(device: R::Device) {
+    let client = R::client(&device);
+
+    for shape in [vec![8192, 8192], vec![4096, 1000], vec![32, 512, 512]] {
+        for kind in [TransposeKind::Tiled, TransposeKind::Copy] {
+            let bench = TransposeBench::<R> {
+                shape: shape.clone(),
+                kind,
+                client: client.clone(),
+            };
+            // Each element is read once and written once.
+            let size = 2 * shape.iter().product::<usize>() * size_of::<f32>();
+
+            println!("{}", bench.name());
+            match bench.run(TimingMethod::Device) {
+                Ok(val) => {
+                    let computed = BenchmarkComputations::new(&val);
+                    let bandwidth = size as f64 / (computed.median.as_secs_f64() * 1e9);
+                    println!("Bandwidth: {bandwidth:.2} GB/s");
+                    println!("Times: {val}");
+                }
+                Err(err) => println!("{err:?}"),
+            }
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "cuda")]
+    run::<cubecl::cuda::CudaRuntime>(Default::default());
+    #[cfg(feature = "hip")]
+    run::<cubecl::hip::HipRuntime>(Default::default());
+    #[cfg(feature = "wgpu")]
+    run::<cubecl::wgpu::WgpuRuntime>(Default::default());
+}
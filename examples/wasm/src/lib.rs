@@ -0,0 +1,87 @@
+//! Kernels running in the browser with WebGPU.
+//!
+//! Run the tests in a headless browser with
+//! `wasm-pack test --headless --chrome examples/wasm`.
+//!
+//! Nothing can block on wasm, so the device is initialized with
+//! [`init_setup_async`](cubecl::wgpu::init_setup_async) and the results are read with
+//! [`read_one_async`](ComputeClient::read_one_async).
+
+use cubecl::{
+    prelude::*,
+    wgpu::{AutoGraphicsApi, WgpuDevice, WgpuRuntime, init_setup_async},
+};
+
+#[cube(launch)]
+fn kernel_square(input: &Array<f32>, output: &mut Array<f32>) {
+    if ABSOLUTE_POS < input.len() {
+        output[ABSOLUTE_POS] = input[ABSOLUTE_POS] * input[ABSOLUTE_POS];
+    }
+}
+
+#[cube(launch)]
+fn kernel_matmul(lhs: &Array<f32>, rhs: &Array<f32>, out: &mut Array<f32>, #[comptime] k: u32) {
+    let n = CUBE_DIM_X;
+    let mut acc = 0.0;
+
+    for i in 0..k {
+        acc += lhs[UNIT_POS_Y * k + i] * rhs[i * n + UNIT_POS_X];
+    }
+
+    out[UNIT_POS_Y * n + UNIT_POS_X] = acc;
+}
+
+/// Initialize the default device, which must be done before creating its client.
+pub async fn init() -> WgpuDevice {
+    let device = WgpuDevice::default();
+    init_setup_async::<AutoGraphicsApi>(&device, Default::default()).await;
+    device
+}
+
+/// Square each element of `input`.
+pub async fn square(device: &WgpuDevice, input: &[f32]) -> Vec<f32> {
+    let client = WgpuRuntime::client(device);
+    let input_handle = client.create(f32::as_bytes(input));
+    let output_handle = client.empty(size_of_val(input));
+
+    unsafe {
+        kernel_square::launch::<WgpuRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_1d(input.len() as u32),
+            ArrayArg::from_raw_parts::<f32>(&input_handle, input.len(), 1),
+            ArrayArg::from_raw_parts::<f32>(&output_handle, input.len(), 1),
+        )
+    };
+
+    let bytes = client.read_one_async(output_handle).await;
+    f32::from_bytes(&bytes).to_vec()
+}
+
+/// Multiply the row-major `m x k` matrix `lhs` with the `k x n` matrix `rhs`.
+pub async fn matmul(
+    device: &WgpuDevice,
+    lhs: &[f32],
+    rhs: &[f32],
+    (m, k, n): (usize, usize, usize),
+) -> Vec<f32> {
+    let client = WgpuRuntime::client(device);
+    let lhs_handle = client.create(f32::as_bytes(lhs));
+    let rhs_handle = client.create(f32::as_bytes(rhs));
+    let out_handle = client.empty(m * n * size_of::<f32>());
+
+    unsafe {
+        kernel_matmul::launch::<WgpuRuntime>(
+            &client,
+            CubeCount::Static(1, 1, 1),
+            CubeDim::new_2d(n as u32, m as u32),
+            ArrayArg::from_raw_parts::<f32>(&lhs_handle, m * k, 1),
+            ArrayArg::from_raw_parts::<f32>(&rhs_handle, k * n, 1),
+            ArrayArg::from_raw_parts::<f32>(&out_handle, m * n, 1),
+            k as u32,
+        )
+    };
+
+    let bytes = client.read_one_async(out_handle).await;
+    f32::from_bytes(&bytes).to_vec()
+}
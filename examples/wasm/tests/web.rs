@@ -0,0 +1,33 @@
+#![cfg(target_family = "wasm")]
+
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn square_in_browser() {
+    let device = wasm::init().await;
+    let output = wasm::square(&device, &[-2.0, 0.0, 1.0, 3.0]).await;
+
+    assert_eq!(output, [4.0, 0.0, 1.0, 9.0]);
+}
+
+#[wasm_bindgen_test]
+async fn matmul_in_browser() {
+    let device = wasm::init().await;
+    let (m, k, n) = (4, 3, 4);
+    let lhs: Vec<f32> = (0..m * k).map(|i| i as f32).collect();
+    let rhs: Vec<f32> = (0..k * n).map(|i| (i % 5) as f32).collect();
+
+    let output = wasm::matmul(&device, &lhs, &rhs, (m, k, n)).await;
+
+    let mut expected = vec![0.0; m * n];
+    for row in 0..m {
+        for col in 0..n {
+            for i in 0..k {
+                expected[row * n + col] += lhs[row * k + i] * rhs[i * n + col];
+            }
+        }
+    }
+    assert_eq!(output, expected);
+}